@@ -86,29 +86,39 @@ cat > "${OUTPUT_DIR}/ecdsa_secp256k1_test_vectors.json" << 'EOF'
   "description": "ECDSA Secp256k1 DSIGN test vectors from Cardano Haskell reference",
   "algorithm": "EcdsaSecp256k1DSIGN",
   "source": "cardano-crypto-tests/src/Test/Crypto/Vector/Vectors.hs",
+  "message_hash_algorithm": "sha3_256",
+  "message_hash_note": "ECDSA signs a 32-byte MessageHash. As in the Haskell tests, each 'message' is hashed as ASCII text with hashAndPack (Proxy @SHA3_256); 'message_hash' gives the resulting pre-hash explicitly.",
   "sign_and_verify_vectors": [
     {
       "test_name": "sign_and_verify_1",
       "secret_key": "0000000000000000000000000000000000000000000000000000000000000003",
       "message": "0000000000000000000000000000000000000000000000000000000000000000",
+      "message_hash": "c6fdd7a7f70862b36a26ccd14752268061e98103299b28fe7763bd9629926f4b",
+      "signature": "5ef63d477c5d1572550016ccf72a2310c7368beeb843c85b1b5697290872222a09e7519702cb2c9a65bbce92d273080a0193b77588bc2eac6dbcbfc15c6dfefd",
       "description": "Minimal secret key value"
     },
     {
       "test_name": "sign_and_verify_2",
       "secret_key": "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
       "message": "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+      "message_hash": "f2088aeef07c808c36c7d8f4d92eb59ecef9a0e5d37e4e6ab0a9eb6d1df8cec7",
+      "signature": "c0730606584a92b4a979fdbfbb89a6b304827ab5084e55f61f6c1fbf36cf359b49a8e128aee4bba7fa5b8b0491ba2425aa97a2af668cb4c54fb68dfae8a675c8",
       "description": "Standard test vector"
     },
     {
       "test_name": "sign_and_verify_3",
       "secret_key": "C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9",
       "message": "7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C",
+      "message_hash": "d3a889532943d8cba1c92133cd2fe094ff4e6b9779ac614a40b39ff568037b69",
+      "signature": "32f9d09cea5204988fbc1e75631c2dfe91e2e9f9416ef20afd8a355f6e6b86530d6fb2ea3c4a81af1f92d94390274dc8602a702268c379ee830f7cee7a812aa1",
       "description": "Another standard vector"
     },
     {
       "test_name": "sign_and_verify_4",
       "secret_key": "0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710",
       "message": "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+      "message_hash": "868fe8a565353b51559283e70271344953b9d43e83f9e6b4ce8b7cfa0a1d01b5",
+      "signature": "3dccc57be49991e95b112954217e8b4fe884d4d26843dfec794feb370981407b79151d1e5af85aba21721876896957adb2b35bcbb84986dcf82daa520a87a9f9",
       "description": "Maximum message value"
     }
   ],
@@ -117,6 +127,7 @@ cat > "${OUTPUT_DIR}/ecdsa_secp256k1_test_vectors.json" << 'EOF'
       "test_name": "verify_with_known_signature",
       "verification_key": "02599de3e582e2a3779208a210dfeae8f330b9af00a47a7fb22e9bb8ef596f301b",
       "message": "0000000000000000000000000000000000000000000000000000000000000000",
+      "message_hash": "c6fdd7a7f70862b36a26ccd14752268061e98103299b28fe7763bd9629926f4b",
       "signature": "354b868c757ef0b796003f7c23dd754d2d1726629145be2c7b7794a25fec80a06254f0915935f33b91bceb16d46ff2814f659e9b6791a4a21ff8764b78d7e114",
       "should_verify": true,
       "description": "Known good signature"
@@ -125,6 +136,7 @@ cat > "${OUTPUT_DIR}/ecdsa_secp256k1_test_vectors.json" << 'EOF'
       "test_name": "negative_signature_normalized",
       "verification_key": "02599de3e582e2a3779208a210dfeae8f330b9af00a47a7fb22e9bb8ef596f301b",
       "message": "0000000000000000000000000000000000000000000000000000000000000000",
+      "message_hash": "c6fdd7a7f70862b36a26ccd14752268061e98103299b28fe7763bd9629926f4b",
       "signature": "354b868c757ef0b796003f7c23dd754d2d1726629145be2c7b7794a25fec80a09dab0f6ea6ca0cc46e4314e92b900d7d6b493e4b47b6fb999fd9e841575e602d",
       "should_verify": false,
       "description": "Negative signature component should be normalized to low-s form"
@@ -169,6 +181,7 @@ cat > "${OUTPUT_DIR}/ecdsa_secp256k1_test_vectors.json" << 'EOF'
     {
       "test_name": "mismatch_message_wrong_verify",
       "message": "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+      "message_hash": "f2088aeef07c808c36c7d8f4d92eb59ecef9a0e5d37e4e6ab0a9eb6d1df8cec7",
       "verification_key": "0325d1dff95105f5253c4022f628a996ad3a0d95fbf21d468a1b33f8c160d8f517",
       "signature": "3dccc57be49991e95b112954217e8b4fe884d4d26843dfec794feb370981407b79151d1e5af85aba21721876896957adb2b35bcbb84986dcf82daa520a87a9f9",
       "should_verify": false,
@@ -177,10 +190,89 @@ cat > "${OUTPUT_DIR}/ecdsa_secp256k1_test_vectors.json" << 'EOF'
     {
       "test_name": "mismatch_signature_wrong_message",
       "message": "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+      "message_hash": "868fe8a565353b51559283e70271344953b9d43e83f9e6b4ce8b7cfa0a1d01b5",
       "verification_key": "0325d1dff95105f5253c4022f628a996ad3a0d95fbf21d468a1b33f8c160d8f517",
       "signature": "5ef63d477c5d1572550016ccf72a2310c7368beeb843c85b1b5697290872222a09e7519702cb2c9a65bbce92d273080a0193b77588bc2eac6dbcbfc15c6dfefd",
       "should_verify": false,
       "description": "Wrong signature for given message"
+    },
+    {
+      "test_name": "message_not_hashed_short",
+      "verification_key": "02599de3e582e2a3779208a210dfeae8f330b9af00a47a7fb22e9bb8ef596f301b",
+      "message_raw": "00000000000000000000000000000000000000000000000000000000000000",
+      "signature": "354b868c757ef0b796003f7c23dd754d2d1726629145be2c7b7794a25fec80a06254f0915935f33b91bceb16d46ff2814f659e9b6791a4a21ff8764b78d7e114",
+      "should_verify": false,
+      "description": "31-byte message is not a MessageHash and must be rejected, not hashed"
+    },
+    {
+      "test_name": "message_not_hashed_long",
+      "verification_key": "02599de3e582e2a3779208a210dfeae8f330b9af00a47a7fb22e9bb8ef596f301b",
+      "message_raw": "000000000000000000000000000000000000000000000000000000000000000000",
+      "signature": "354b868c757ef0b796003f7c23dd754d2d1726629145be2c7b7794a25fec80a06254f0915935f33b91bceb16d46ff2814f659e9b6791a4a21ff8764b78d7e114",
+      "should_verify": false,
+      "description": "33-byte message is not a MessageHash and must be rejected, not hashed"
+    }
+  ],
+  "message_hash_vectors": [
+    {
+      "test_name": "hash_and_pack_sha256_1",
+      "hash_algorithm": "sha256",
+      "secret_key": "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+      "verification_key": "02dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+      "preimage": "",
+      "message_hash": "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+      "signature": "97f8bc0634d2154da933aea55f8d4c374c35a71a668ece6ee3b41fa866df34cb071f5de974734f53a9afd615006166d9f9a69ef42c1db63269aec6def452f650",
+      "description": "hashAndPack with sha256 over the empty string"
+    },
+    {
+      "test_name": "hash_and_pack_sha256_2",
+      "hash_algorithm": "sha256",
+      "secret_key": "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+      "verification_key": "02dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+      "preimage": "63726f73732d636861696e20627269646765207472616e73666572",
+      "message_hash": "c870518ffd5b20453aedb3a5a6896bd763f1db7604a539e7f21755b9d129419f",
+      "signature": "a01fbf268516ccd3a979d6b0bfd7144eeef017807ee0711739bf1b534b4517a128c6661873be2a19eb6712bf87dce1dd5999c3e434265ece25682da59b39ec52",
+      "description": "hashAndPack with sha256 over an ASCII message"
+    },
+    {
+      "test_name": "hash_and_pack_sha3_256_1",
+      "hash_algorithm": "sha3_256",
+      "secret_key": "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+      "verification_key": "02dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+      "preimage": "",
+      "message_hash": "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+      "signature": "8d27e746980dbeca306085c9ac048276aebc3eafebc635fbaa6c3d5560fffa5a4fffe5ee458496d438fdd96e44251c96207ff858d361f7f1934affd5c5c446a3",
+      "description": "hashAndPack with sha3_256 over the empty string"
+    },
+    {
+      "test_name": "hash_and_pack_sha3_256_2",
+      "hash_algorithm": "sha3_256",
+      "secret_key": "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+      "verification_key": "02dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+      "preimage": "63726f73732d636861696e20627269646765207472616e73666572",
+      "message_hash": "4416c5bef6a75277c3fa68b18cb9cdc16b14fa7ceae6c32b78443bdde337e95c",
+      "signature": "de5284bca5f2e8ee17a35a401b429a6ee02ac46eee6663cf8a6ebb96f0081eb531adb75f42e97ba72f49b717d108f89ad07a6454604ff64edae943bd2e55464f",
+      "description": "hashAndPack with sha3_256 over an ASCII message"
+    },
+    {
+      "test_name": "hash_and_pack_blake2b_256_1",
+      "hash_algorithm": "blake2b_256",
+      "secret_key": "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+      "verification_key": "02dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+      "preimage": "",
+      "message_hash": "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8",
+      "signature": "306d594c172a23ab77bae4510ae92b06b5364e7a6d04d38dbd7642640ed3fc713f1ca35084b798cf3236409e4c720d1993b5d3fa51bdf650aa89da1b994bd62a",
+      "description": "hashAndPack with blake2b_256 over the empty string"
+    },
+    {
+      "test_name": "hash_and_pack_blake2b_256_2",
+      "hash_algorithm": "blake2b_256",
+      "secret_key": "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+      "verification_key": "02dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+      "preimage": "63726f73732d636861696e20627269646765207472616e73666572",
+      "message_hash": "728dcb9c7f56a03bb3daa07cb1d492181a4609f0e39bbaab2c77359e8c62b0f5",
+      "signature": "78574aed4a06e6cfc9f0152ac0b40a0962847cc558fe3a88db48c4acc446bae42cf7db19dae34f34958826ad3e940fc4bfc26e088a951c4ab01540743eff3ba0",
+      "description": "hashAndPack with blake2b_256 over an ASCII message"
     }
   ]
 }
@@ -24,7 +24,8 @@ pub trait Generic: Sized {
     /// Reconstruct a value from its representation.
     fn from_repr(repr: Self::Repr) -> Self;
 
-    /// Borrow the value as its underlying representation.
+    /// Borrow the value as its underlying representation, for traits that
+    /// only inspect the fields.
     fn as_repr(&self) -> Self::ReprRef<'_>;
 }
 
@@ -60,6 +60,8 @@ where
     }
 }
 
+/// Combines by value through [`Generic::into_repr`] and
+/// [`Generic::from_repr`], so fields are moved rather than cloned.
 impl<T> Semigroup for InstantiatedAt<T>
 where
     T: Generic,
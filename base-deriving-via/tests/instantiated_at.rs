@@ -50,3 +50,43 @@ fn monoid_identity_behaves_as_expected() {
     assert_eq!(left, value.clone().into_inner());
     assert_eq!(right, value.into_inner());
 }
+
+/// Deliberately not `Clone`: combining must move the fields.
+#[derive(Debug)]
+struct Columns {
+    rows: Vec<u64>,
+    names: String,
+}
+
+impl_generic_for_struct!(
+    struct Columns {
+        rows: Vec<u64>,
+        names: String,
+    }
+);
+
+#[test]
+fn combination_moves_fields_without_cloning() {
+    const LEN: usize = 1_000_000;
+
+    let mut rows = Vec::with_capacity(2 * LEN);
+    rows.extend(0..LEN as u64);
+    let (rows_ptr, rows_capacity) = (rows.as_ptr(), rows.capacity());
+    let left = InstantiatedAt::new(Columns {
+        rows,
+        names: String::from("left"),
+    });
+    let right = InstantiatedAt::new(Columns {
+        rows: (LEN as u64..2 * LEN as u64).collect(),
+        names: String::from("right"),
+    });
+
+    let combined = left.combine(right).into_inner();
+
+    // The left buffer is reused in place: no copy of it was made.
+    assert_eq!(combined.rows.as_ptr(), rows_ptr);
+    assert_eq!(combined.rows.capacity(), rows_capacity);
+    assert_eq!(combined.rows.len(), 2 * LEN);
+    assert!(combined.rows.iter().copied().eq(0..2 * LEN as u64));
+    assert_eq!(combined.names, "leftright");
+}
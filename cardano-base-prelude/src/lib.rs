@@ -0,0 +1,149 @@
+//! The recommended API surface of the cardano-base-rust workspace in one
+//! dependency.
+//!
+//! Each workspace crate mirrors one Haskell package, so a consumer usually
+//! pulls in five or six of them and has to work out which features to enable
+//! where. This crate re-exports a coherent subset at its root and forwards
+//! three features:
+//!
+//! | Feature | Forwards to |
+//! | --- | --- |
+//! | `serde` | `cardano-crypto-class/serde` (containers, CBOR helpers and slotting types always have serde) |
+//! | `test-util` | `test-util` on `cardano-binary`, `cardano-crypto-class` and `cardano-slotting` |
+//! | `metrics` | `kes-metrics` and `mlocked-metrics` on `cardano-crypto-class` |
+//!
+//! `cardano-slotting`'s `nothunks` feature is always on, so the slot and
+//! epoch types implement [`NoThunks`]. Anything not re-exported is still
+//! reachable through the crate aliases ([`binary`], [`crypto`],
+//! [`slotting`], [`containers`], [`deriving`]).
+//!
+//! # Name choices
+//!
+//! - [`Semigroup`] and [`Monoid`] are `base-deriving-via`'s (by-value
+//!   `combine`, with `stimes` and `concat`). The finger tree's by-reference
+//!   traits, which a [`Measured`] measure implements, are
+//!   [`FingerTreeSemigroup`] and [`FingerTreeMonoid`].
+//! - `heapwords` and `measures` are left out. Their size vocabularies
+//!   (`HeapWords`, `Measure`) answer different questions, and few consumers
+//!   need either; depend on them directly.
+//!
+//! # Encoding a value to CBOR
+//!
+//! ```
+//! use cardano_base_prelude::{EpochNo, SlotNo, StrictMaybe, StrictSeq, decode_full, serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Checkpoint {
+//!     slot: SlotNo,
+//!     epoch: EpochNo,
+//!     note: StrictMaybe<String>,
+//!     hashes: StrictSeq<u64>,
+//! }
+//!
+//! let checkpoint = Checkpoint {
+//!     slot: SlotNo(4_492_800),
+//!     epoch: EpochNo(208),
+//!     note: StrictMaybe::SNothing,
+//!     hashes: StrictSeq::from_vec(vec![1, 2, 3]),
+//! };
+//! let bytes = serialize(&checkpoint)?;
+//! assert_eq!(decode_full::<Checkpoint>(&bytes)?, checkpoint);
+//! # Ok::<(), cardano_base_prelude::BinaryError>(())
+//! ```
+//!
+//! # Signing with DSIGN and KES
+//!
+//! ```
+//! use cardano_base_prelude::{DsignAlgorithm, Ed25519, KesAlgorithm, Sum6Kes};
+//!
+//! let message = b"block header body";
+//!
+//! let cold_key = Ed25519::gen_key_from_seed_bytes(&[7; 32]);
+//! let cold_vk = Ed25519::derive_verification_key(&cold_key);
+//! let signature = Ed25519::sign_bytes(&(), message, &cold_key);
+//! Ed25519::verify_bytes(&(), &cold_vk, message, &signature)?;
+//!
+//! let hot_key = Sum6Kes::gen_key_kes_from_seed_bytes(&[9; 32])?;
+//! let hot_vk = Sum6Kes::derive_verification_key(&hot_key)?;
+//! let kes_signature = Sum6Kes::sign_kes(&(), 0, message, &hot_key)?;
+//! Sum6Kes::verify_kes(&(), &hot_vk, 0, message, &kes_signature)?;
+//! Sum6Kes::forget_signing_key_kes(hot_key);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! # Placing a slot in time
+//!
+//! ```
+//! use cardano_base_prelude::{
+//!     EpochInfoSource, EpochNo, EpochSize, NoThunks, SlotNo, SystemStart, fixed_epoch_info,
+//!     slot_length_from_sec, wallclock_for_slot,
+//! };
+//!
+//! let slot_length = slot_length_from_sec(1);
+//! let info = fixed_epoch_info(EpochSize(432_000), slot_length);
+//! let slot = SlotNo(4_492_800);
+//! let epoch = info.epoch_of_slot(slot)?;
+//! assert_eq!(epoch, EpochNo(10));
+//! assert_eq!(info.first_slot_of_epoch(epoch)?, SlotNo(4_320_000));
+//!
+//! let start = SystemStart::from_unix_millis(1_506_203_091_000)?;
+//! let wallclock = wallclock_for_slot(slot, slot_length, start);
+//! assert_eq!(wallclock.unix_timestamp(), 1_506_203_091 + 4_492_800);
+//! assert!(epoch.no_thunks(&["epoch"]).is_ok());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+pub use base_deriving_via as deriving;
+pub use cardano_binary as binary;
+pub use cardano_crypto_class as crypto;
+pub use cardano_slotting as slotting;
+pub use cardano_strict_containers as containers;
+
+// Strict containers. The finger tree's `Semigroup` / `Monoid` are renamed so
+// the root names belong to `base-deriving-via`.
+pub use base_deriving_via::{Monoid, Semigroup};
+pub use cardano_strict_containers::{
+    Measured, Monoid as FingerTreeMonoid, SearchResult, Semigroup as FingerTreeSemigroup,
+    StrictFingerTree, StrictMaybe, StrictSeq, ViewL, ViewR,
+};
+
+// CBOR.
+pub use cardano_binary::{
+    BinaryError, BinaryErrorKind, FromCbor, ToCbor, bytes_or_hex, decode_full,
+    decode_full_borrowed, serialize,
+};
+
+// Crypto algorithm traits and the algorithms the node uses.
+pub use cardano_crypto_class::vrf::PraosVRF;
+pub use cardano_crypto_class::{
+    Blake2b224, Blake2b256, CertifiedVRF, CompactSum6Kes, DsignAlgorithm, DsignError, Ed25519,
+    Ed25519Signature, Ed25519SigningKey, Ed25519VerificationKey, KesAlgorithm, KesError, KesMError,
+    OutputVRF, Period, Seed, Sum6Kes, VRFAlgorithm, VRFError, mk_seed_from_bytes,
+};
+
+// Slotting.
+pub use cardano_slotting::{
+    BlockNo, EpochInfo, EpochInfoSource, EpochInterval, EpochNo, EpochSize, RelativeTime,
+    SecurityParam, SlotLength, SlotNo, SystemStart, WithOrigin, fixed_epoch_info,
+    slot_for_wallclock, slot_length_from_millisec, slot_length_from_sec, wallclock_for_slot,
+};
+
+// Evaluation traits.
+pub use deepseq::{NFData, deepseq, force};
+pub use nothunks::{NoThunks, NoThunksResult, ThunkInfo, no_thunks, unsafe_no_thunks};
+
+/// KES and mlocked memory counters.
+#[cfg(feature = "metrics")]
+pub mod metrics {
+    pub use cardano_crypto_class::kes::metrics::{KesMetrics, snapshot as kes_snapshot};
+    pub use cardano_crypto_class::mlocked_metrics::{MLockedMetrics, snapshot as mlocked_snapshot};
+}
+
+/// Helpers for downstream test suites.
+#[cfg(feature = "test-util")]
+pub mod test_util {
+    pub use cardano_binary::golden::{assert_golden_cbor, diagnostic, structural_diff};
+    pub use cardano_crypto_class::vrf::laws::{vrf_algorithm_laws, vrf_eval_laws, vrf_key_laws};
+    pub use cardano_slotting::test_util::*;
+}
@@ -0,0 +1,94 @@
+//! Smoke tests for the forwarded features. Each test touches the API its
+//! feature forwards, so building this file under every combination of
+//! `serde`, `test-util` and `metrics` (as CI does) checks the forwarding.
+
+use cardano_base_prelude::{
+    DsignAlgorithm, Ed25519, EpochNo, FingerTreeMonoid, FingerTreeSemigroup, Measured, Monoid,
+    NoThunks, Semigroup, SlotNo, StrictFingerTree, StrictMaybe, decode_full, serialize,
+};
+
+#[test]
+fn active_features_are_reported() {
+    let active: Vec<&str> = [
+        (cfg!(feature = "serde"), "serde"),
+        (cfg!(feature = "test-util"), "test-util"),
+        (cfg!(feature = "metrics"), "metrics"),
+    ]
+    .into_iter()
+    .filter_map(|(on, name)| on.then_some(name))
+    .collect();
+    println!("cardano-base-prelude features: {active:?}");
+}
+
+#[test]
+fn base_surface_is_always_available() {
+    let bytes = serialize(&(SlotNo(42), StrictMaybe::SJust(EpochNo(1)))).expect("encode");
+    let back: (SlotNo, StrictMaybe<EpochNo>) = decode_full(&bytes).expect("decode");
+    assert_eq!(back, (SlotNo(42), StrictMaybe::SJust(EpochNo(1))));
+    assert!(SlotNo(42).no_thunks(&["slot"]).is_ok());
+    assert_eq!(Ed25519::SEED_SIZE, 32);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Count(usize);
+
+impl FingerTreeSemigroup for Count {
+    fn combine(&self, other: &Self) -> Self {
+        Count(self.0 + other.0)
+    }
+}
+
+impl FingerTreeMonoid for Count {
+    fn empty() -> Self {
+        Count(0)
+    }
+}
+
+impl Measured<Count> for u8 {
+    fn measure(&self) -> Count {
+        Count(1)
+    }
+}
+
+#[test]
+fn semigroup_names_do_not_collide() {
+    let tree = StrictFingerTree::<Count, u8>::from_list([1, 2, 3]);
+    assert_eq!(tree.measure(), Count(3));
+    assert_eq!(Semigroup::combine(vec![1], vec![2]), vec![1, 2]);
+    assert_eq!(<String as Monoid>::empty(), "");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_forwards_to_crypto_keys() {
+    use cardano_base_prelude::Ed25519VerificationKey;
+
+    let key = Ed25519::derive_verification_key(&Ed25519::gen_key_from_seed_bytes(&[3; 32]));
+    let json = serde_json::to_string(&key).expect("json");
+    let back: Ed25519VerificationKey = serde_json::from_str(&json).expect("parse");
+    assert_eq!(back, key);
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_util_forwards_helpers() {
+    use cardano_base_prelude::PraosVRF;
+    use cardano_base_prelude::test_util::{diagnostic, vrf_key_laws};
+
+    vrf_key_laws::<PraosVRF>();
+    assert_eq!(diagnostic(&[0x01]).expect("diagnostic"), "1");
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn metrics_forward_to_crypto_counters() {
+    use cardano_base_prelude::metrics::{kes_snapshot, mlocked_snapshot};
+    use cardano_base_prelude::{KesAlgorithm, Sum6Kes};
+
+    let before = kes_snapshot();
+    let key = Sum6Kes::gen_key_kes_from_seed_bytes(&[5; 32]).expect("KES key");
+    Sum6Kes::sign_kes(&(), 0, b"metrics", &key).expect("sign");
+    Sum6Kes::forget_signing_key_kes(key);
+    assert!(kes_snapshot().signatures > before.signatures);
+    assert!(mlocked_snapshot().allocations > 0);
+}
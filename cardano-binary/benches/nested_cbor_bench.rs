@@ -0,0 +1,100 @@
+//! Compare buffered and streaming tag 24 encoding of a large payload.
+//!
+//! Besides timing, each strategy reports the peak heap usage observed while
+//! wrapping an 8 MiB payload and writing it into a preallocated output, using a
+//! counting global allocator. The buffered path holds the inner encoding and
+//! the tagged copy at the same time; the streaming path only ever holds the
+//! output.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use cardano_binary::{encode_nested_cbor, encode_nested_cbor_into, nested_cbor_len};
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use serde_bytes::ByteBuf;
+
+struct PeakAlloc;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+// SAFETY: defers to the system allocator and only adds bookkeeping.
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: forwarded unchanged to the system allocator.
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let now = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(now, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: forwarded unchanged to the system allocator.
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAlloc = PeakAlloc;
+
+const PAYLOAD_LEN: usize = 8 * 1024 * 1024;
+
+/// Peak heap growth above the current baseline while `f` runs.
+fn peak_during(f: impl FnOnce()) -> usize {
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    f();
+    PEAK.load(Ordering::Relaxed) - baseline
+}
+
+fn nested_large_payload(c: &mut Criterion) {
+    let payload = ByteBuf::from(vec![0x5A; PAYLOAD_LEN]);
+    let encoded_len = nested_cbor_len(&payload).expect("payload length must be computable");
+    let mut output = Vec::with_capacity(encoded_len);
+
+    let buffered_peak = peak_during(|| {
+        output.clear();
+        let tagged = encode_nested_cbor(&payload).expect("buffered encoding must succeed");
+        output.extend_from_slice(&tagged);
+    });
+    let streaming_peak = peak_during(|| {
+        output.clear();
+        encode_nested_cbor_into(&payload, &mut output).expect("streaming encoding must succeed");
+    });
+    println!(
+        "nested_cbor_8mib peak heap: buffered {:.1} MiB, streaming {:.1} MiB",
+        buffered_peak as f64 / (1024.0 * 1024.0),
+        streaming_peak as f64 / (1024.0 * 1024.0)
+    );
+
+    let mut group = c.benchmark_group("nested_cbor_8mib");
+    group.sample_size(20);
+    group.throughput(Throughput::Bytes(encoded_len as u64));
+
+    group.bench_function("buffered", |b| {
+        b.iter(|| {
+            output.clear();
+            let tagged =
+                encode_nested_cbor(black_box(&payload)).expect("buffered encoding must succeed");
+            output.extend_from_slice(&tagged);
+            black_box(&output);
+        });
+    });
+
+    group.bench_function("streaming", |b| {
+        b.iter(|| {
+            output.clear();
+            encode_nested_cbor_into(black_box(&payload), &mut output)
+                .expect("streaming encoding must succeed");
+            black_box(&output);
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, nested_large_payload);
+criterion_main!(benches);
@@ -0,0 +1,241 @@
+//! Decoded values that remember where their encoding sits in the input.
+//!
+//! Mirrors Haskell's `Annotated a ByteSpan` / `decodeAnnotated`: hashing a
+//! sub-structure, such as a transaction body inside a block, must use the
+//! bytes that were received, since re-serialising the decoded value only
+//! reproduces them when the sender's encoding was canonical.
+//!
+//! [`decode_annotated`] keeps the whole input in an `Arc<[u8]>` alongside the
+//! value. Fields declared as [`Spanned<T>`] record the [`ByteSpan`] of their
+//! own encoding while decoding, and [`Annotated::bytes_of`] /
+//! [`Annotated::annotate`] turn that span back into bytes without copying
+//! the input.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::sync::Arc;
+
+use serde::de::{self, DeserializeOwned, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::borrowed::decode_full_borrowed;
+use crate::error::BinaryError;
+
+/// Newtype name by which the span-tracking deserializer recognises a
+/// [`Spanned`] field.
+pub(crate) const SPANNED_NAME: &str = "$cardano_binary::Spanned";
+
+/// The byte range of an encoded value within its input, Haskell's
+/// `ByteSpan` as an offset and a length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ByteSpan {
+    pub offset: usize,
+    pub len: usize,
+}
+
+impl ByteSpan {
+    /// Offset one past the last byte of the span.
+    #[must_use]
+    pub const fn end(&self) -> usize {
+        self.offset + self.len
+    }
+
+    #[must_use]
+    pub const fn range(&self) -> Range<usize> {
+        self.offset..self.end()
+    }
+}
+
+/// A field that records the [`ByteSpan`] of its encoding when decoded with
+/// [`decode_annotated`] or [`decode_full_borrowed`].
+///
+/// It encodes exactly as `T`. Other decoders, such as
+/// [`decode_full`](crate::decode_full), do not track offsets and reject it.
+/// Equality compares the values only, so a re-encoded value equals the
+/// decoded one wherever its span ends up.
+#[derive(Debug, Clone)]
+pub struct Spanned<T> {
+    value: T,
+    span: ByteSpan,
+}
+
+impl<T> Spanned<T> {
+    #[must_use]
+    pub fn new(value: T, span: ByteSpan) -> Self {
+        Spanned { value, span }
+    }
+
+    #[must_use]
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    #[must_use]
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Where the value's encoding sits in the input it was decoded from.
+    #[must_use]
+    pub fn span(&self) -> ByteSpan {
+        self.span
+    }
+}
+
+impl<T: PartialEq> PartialEq for Spanned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for Spanned<T> {}
+
+impl<T: Serialize> Serialize for Spanned<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Spanned<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_newtype_struct(SPANNED_NAME, SpannedVisitor(PhantomData))
+    }
+}
+
+struct SpannedVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for SpannedVisitor<T> {
+    type Value = Spanned<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a value decoded with its byte span")
+    }
+
+    /// The span-tracking deserializer hands over the start offset, the value
+    /// and the end offset.
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let missing = || de::Error::custom("span-tracking deserializer sent too few fields");
+        let start: usize = seq.next_element()?.ok_or_else(missing)?;
+        let value: T = seq.next_element()?.ok_or_else(missing)?;
+        let end: usize = seq.next_element()?.ok_or_else(missing)?;
+        Ok(Spanned::new(
+            value,
+            ByteSpan {
+                offset: start,
+                len: end - start,
+            },
+        ))
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        _deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        Err(de::Error::custom(
+            "`Spanned` fields need `decode_annotated` or `decode_full_borrowed`, which track byte offsets",
+        ))
+    }
+}
+
+/// A decoded value together with the shared input it was decoded from and
+/// the span of its encoding.
+#[derive(Debug, Clone)]
+pub struct Annotated<T> {
+    value: T,
+    input: Arc<[u8]>,
+    span: ByteSpan,
+}
+
+impl<T> Annotated<T> {
+    #[must_use]
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    #[must_use]
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    #[must_use]
+    pub fn span(&self) -> ByteSpan {
+        self.span
+    }
+
+    /// The whole input the value was decoded from.
+    #[must_use]
+    pub fn input(&self) -> &Arc<[u8]> {
+        &self.input
+    }
+
+    /// The bytes the value was decoded from, exactly as received.
+    #[must_use]
+    pub fn original_bytes(&self) -> &[u8] {
+        &self.input[self.span.range()]
+    }
+
+    /// The bytes under `span`, or `None` if it lies outside the input.
+    #[must_use]
+    pub fn bytes_of(&self, span: ByteSpan) -> Option<&[u8]> {
+        self.input.get(self.span_range(span)?)
+    }
+
+    /// `field`, decoded somewhere inside this value, annotated with the same
+    /// shared input; `None` if its span lies outside the input.
+    #[must_use]
+    pub fn annotate<U: Clone>(&self, field: &Spanned<U>) -> Option<Annotated<U>> {
+        self.span_range(field.span)?;
+        Some(Annotated {
+            value: field.value.clone(),
+            input: Arc::clone(&self.input),
+            span: field.span,
+        })
+    }
+
+    /// Digest of [`original_bytes`](Self::original_bytes) with `H`, e.g.
+    /// `blake2::Blake2b<U32>` for Cardano's Blake2b-256 hashes.
+    #[must_use]
+    pub fn hash_original<H: digest::Digest>(&self) -> digest::Output<H> {
+        H::digest(self.original_bytes())
+    }
+
+    fn span_range(&self, span: ByteSpan) -> Option<Range<usize>> {
+        let end = span.offset.checked_add(span.len)?;
+        (end <= self.input.len()).then_some(span.offset..end)
+    }
+}
+
+/// Decode a value from a shared buffer, consuming the entire payload, and
+/// keep the buffer so the value's original bytes stay available.
+///
+/// The top-level span covers the whole input; [`Spanned`] fields inside `T`
+/// record their own. Decoding follows [`decode_full_borrowed`].
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use cardano_binary::{Spanned, decode_annotated, serialize};
+///
+/// let bytes: Arc<[u8]> = serialize(&(1_000u32, "body")).unwrap().into();
+/// let block = decode_annotated::<(u32, Spanned<String>)>(Arc::clone(&bytes)).unwrap();
+/// let body = block.annotate(&block.value().1).unwrap();
+/// assert_eq!(block.original_bytes(), &bytes[..]);
+/// assert_eq!(body.original_bytes(), &bytes[4..]);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`BinaryError::Deserialization`] if the input is not valid CBOR or
+/// does not match `T`, and [`BinaryError::Leftover`] if bytes follow the
+/// first data item.
+pub fn decode_annotated<T: DeserializeOwned>(
+    input: impl Into<Arc<[u8]>>,
+) -> Result<Annotated<T>, BinaryError> {
+    let input = input.into();
+    let value = decode_full_borrowed(&input)?;
+    let span = ByteSpan {
+        offset: 0,
+        len: input.len(),
+    };
+    Ok(Annotated { value, input, span })
+}
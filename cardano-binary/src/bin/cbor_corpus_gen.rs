@@ -0,0 +1,182 @@
+//! Emit random, pending corpus entries for the Haskell side to encode.
+//!
+//! Usage: `cbor_corpus_gen <count> [seed]`. The JSON corpus goes to stdout
+//! with every `hex` left out; a `cborg` script fills it in and the result is
+//! appended to `tests/data/cbor_corpus.json`. Generated items are canonical by
+//! construction: map keys are unique and sorted by their encoding, and each
+//! float is described in the shortest width that holds it losslessly.
+
+use std::{env, process};
+
+use cardano_binary::corpus::{Corpus, CorpusEntry, CorpusFloat, CorpusValue};
+use cardano_binary::{Value, encode_value};
+
+const MAX_DEPTH: u32 = 3;
+const MAX_LEN: u64 = 6;
+
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("error: {error}");
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let args: Vec<String> = env::args().collect();
+    if !(2..=3).contains(&args.len()) {
+        return Err(format!(
+            "usage: {} <count> [seed]",
+            args.first().map_or("cbor_corpus_gen", String::as_str)
+        ));
+    }
+    let count: usize = args[1]
+        .parse()
+        .map_err(|err| format!("invalid count {:?}: {err}", args[1]))?;
+    let seed: u64 = match args.get(2) {
+        Some(text) => text
+            .parse()
+            .map_err(|err| format!("invalid seed {text:?}: {err}"))?,
+        None => 0,
+    };
+
+    let mut rng = SplitMix64(seed);
+    let mut entries = Vec::with_capacity(count);
+    for index in 0..count {
+        entries.push(CorpusEntry {
+            name: format!("generated_{seed}_{index}"),
+            value: random_value(&mut rng, 0)?,
+            hex: None,
+            canonical: true,
+            note: None,
+        });
+    }
+
+    let corpus = Corpus {
+        description: format!("cbor_corpus_gen {count} {seed}; hex pending from cborg"),
+        entries,
+    };
+    let json = corpus.to_json_pretty().map_err(|err| err.to_string())?;
+    println!("{json}");
+    Ok(())
+}
+
+fn random_value(rng: &mut SplitMix64, depth: u32) -> Result<CorpusValue, String> {
+    let kinds = if depth < MAX_DEPTH { 10 } else { 6 };
+    Ok(match rng.below(kinds) {
+        0 => CorpusValue::Int(random_int(rng).to_string()),
+        1 => CorpusValue::Bytes(
+            (0..rng.below(40))
+                .map(|_| format!("{:02x}", rng.next() as u8))
+                .collect(),
+        ),
+        2 => CorpusValue::Text(
+            (0..rng.below(30))
+                .map(|_| ['a', 'Z', '0', ' ', 'é', '₳', '水', '𐅑'][rng.below(8) as usize])
+                .collect(),
+        ),
+        3 => CorpusValue::Bool(rng.below(2) == 0),
+        4 => CorpusValue::Null,
+        5 => random_float(rng)?,
+        6 | 7 => CorpusValue::Array(
+            (0..rng.below(MAX_LEN))
+                .map(|_| random_value(rng, depth + 1))
+                .collect::<Result<_, _>>()?,
+        ),
+        8 => random_map(rng, depth)?,
+        _ => random_tag(rng, depth)?,
+    })
+}
+
+/// Integers cluster around the CBOR width boundaries, where encoders differ.
+fn random_int(rng: &mut SplitMix64) -> i128 {
+    const BOUNDARIES: [i128; 6] = [0, 24, 1 << 8, 1 << 16, 1 << 32, 1 << 64];
+    let boundary = BOUNDARIES[rng.below(BOUNDARIES.len() as u64) as usize];
+    let magnitude = (boundary + i128::from(rng.below(5)) - 2).clamp(0, (1 << 64) - 1);
+    if rng.below(2) == 0 {
+        magnitude
+    } else {
+        -1 - magnitude
+    }
+}
+
+fn random_float(rng: &mut SplitMix64) -> Result<CorpusValue, String> {
+    let value = match rng.below(3) {
+        0 => f64::from(f32::from_bits(rng.next() as u32)),
+        1 => f64::from_bits(rng.next()),
+        _ => f64::from(rng.below(1 << 12) as u16) / f64::from(1u16 << rng.below(8)),
+    };
+    // NaN payloads are not preserved by every encoder; use the quiet NaN.
+    let float = CorpusFloat(if value.is_nan() { f64::NAN } else { value });
+    let width = encode_value(&Value::Float(float.0))
+        .map_err(|err| err.to_string())?
+        .len();
+    Ok(match width {
+        3 => CorpusValue::Float16(float),
+        5 => CorpusValue::Float32(float),
+        _ => CorpusValue::Float64(float),
+    })
+}
+
+fn random_map(rng: &mut SplitMix64, depth: u32) -> Result<CorpusValue, String> {
+    let mut entries = Vec::new();
+    for _ in 0..rng.below(MAX_LEN) {
+        let key = match rng.below(2) {
+            0 => CorpusValue::Int(random_int(rng).to_string()),
+            _ => CorpusValue::Text(format!("k{}", rng.below(100))),
+        };
+        let encoded = encode_description(&key)?;
+        entries.push((encoded, key, random_value(rng, depth + 1)?));
+    }
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries.dedup_by(|a, b| a.0 == b.0);
+    Ok(CorpusValue::Map(
+        entries
+            .into_iter()
+            .map(|(_, key, value)| (key, value))
+            .collect(),
+    ))
+}
+
+fn random_tag(rng: &mut SplitMix64, depth: u32) -> Result<CorpusValue, String> {
+    Ok(match rng.below(4) {
+        // CBOR-in-CBOR: the payload is the canonical encoding of a random item.
+        0 => {
+            let inner = random_value(rng, depth + 1)?;
+            let bytes = encode_description(&inner)?;
+            CorpusValue::Tag(
+                24,
+                Box::new(CorpusValue::Bytes(
+                    bytes.iter().map(|b| format!("{b:02x}")).collect(),
+                )),
+            )
+        },
+        1 => CorpusValue::Tag(258, Box::new(random_value(rng, depth + 1)?)),
+        2 => CorpusValue::Tag(121 + rng.below(7), Box::new(random_value(rng, depth + 1)?)),
+        _ => CorpusValue::Tag(
+            rng.next() >> rng.below(64),
+            Box::new(random_value(rng, depth + 1)?),
+        ),
+    })
+}
+
+fn encode_description(value: &CorpusValue) -> Result<Vec<u8>, String> {
+    let value = value.to_value().map_err(|err| err.to_string())?;
+    encode_value(&value).map_err(|err| err.to_string())
+}
+
+/// SplitMix64, so a seed reproduces the same corpus on every platform.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+}
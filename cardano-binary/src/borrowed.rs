@@ -0,0 +1,506 @@
+#![cfg_attr(test, allow(clippy::unwrap_used))]
+
+use crate::annotated::SPANNED_NAME;
+use crate::error::BinaryError;
+use ciborium::de::Error;
+use ciborium_ll::{Decoder, Header};
+use serde::de::{self, DeserializeSeed, Visitor};
+use serde::{Deserialize, forward_to_deserialize_any};
+use std::io;
+
+/// Nesting depth at which decoding gives up, matching ciborium's default.
+const RECURSION_LIMIT: usize = 256;
+
+const BREAK: u8 = 0xff;
+const NULL: u8 = 0xf6;
+const UNDEFINED: u8 = 0xf7;
+
+type DeResult<T> = Result<T, Error<io::Error>>;
+
+/// Deserialize a value that may borrow from `bytes`, consuming the entire
+/// payload.
+///
+/// Unlike [`decode_full`](crate::decode_full), definite-length byte and text
+/// strings are handed to the visitor as slices of `bytes`
+/// (`visit_borrowed_bytes` / `visit_borrowed_str`), so types such as
+/// `&'a [u8]`, `&'a str` or key views that wrap them decode without copying.
+/// Owned types decode as they would with [`decode_full`](crate::decode_full).
+/// Indefinite-length strings cannot be borrowed and are passed as owned
+/// buffers.
+///
+/// ```rust
+/// use cardano_binary::{decode_full_borrowed, serialize};
+///
+/// let bytes = serialize(&(7u8, serde_bytes::Bytes::new(b"key"))).unwrap();
+/// let (n, key): (u8, &[u8]) = decode_full_borrowed(&bytes).unwrap();
+/// assert_eq!((n, key), (7, &b"key"[..]));
+/// assert!(std::ptr::eq(key.as_ptr(), bytes[bytes.len() - 3..].as_ptr()));
+/// ```
+///
+/// # Errors
+///
+/// Returns [`BinaryError::Deserialization`] if the input is not valid CBOR or
+/// does not match `T`, and [`BinaryError::Leftover`] if bytes follow the
+/// first data item.
+pub fn decode_full_borrowed<'a, T: Deserialize<'a>>(bytes: &'a [u8]) -> Result<T, BinaryError> {
+    let result = decode_full_borrowed_untraced(bytes);
+    #[cfg(feature = "trace")]
+    crate::trace::emit::<T, _>(crate::trace::TraceDirection::Decode, bytes, &result);
+    result
+}
+
+fn decode_full_borrowed_untraced<'a, T: Deserialize<'a>>(
+    bytes: &'a [u8],
+) -> Result<T, BinaryError> {
+    let mut deserializer = BorrowedDeserializer {
+        input: bytes,
+        pos: 0,
+        depth: RECURSION_LIMIT,
+    };
+    let value = T::deserialize(&mut deserializer)
+        .map_err(|err| BinaryError::deserialization(std::any::type_name::<T>(), err))?;
+    if deserializer.pos < bytes.len() {
+        return Err(BinaryError::leftover(
+            std::any::type_name::<T>(),
+            bytes,
+            deserializer.pos,
+        ));
+    }
+    Ok(value)
+}
+
+struct BorrowedDeserializer<'de> {
+    input: &'de [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl<'de> BorrowedDeserializer<'de> {
+    fn header(&mut self) -> DeResult<Header> {
+        let mut decoder = Decoder::from(&self.input[self.pos..]);
+        let header = decoder.pull().map_err(|err| match err {
+            ciborium_ll::Error::Io(err) => Error::Io(err),
+            ciborium_ll::Error::Syntax(offset) => Error::Syntax(self.pos + offset),
+        })?;
+        self.pos += decoder.offset();
+        Ok(header)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn take(&mut self, len: usize) -> DeResult<&'de [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.input.len())
+            .ok_or_else(|| Error::Io(io::ErrorKind::UnexpectedEof.into()))?;
+        let slice = &self.input[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn take_str(&mut self, len: usize) -> DeResult<&'de str> {
+        let offset = self.pos;
+        std::str::from_utf8(self.take(len)?).map_err(|_| Error::Syntax(offset))
+    }
+
+    /// Concatenate the segments of an indefinite-length string.
+    fn segments(&mut self, text: bool) -> DeResult<Vec<u8>> {
+        let mut buf = Vec::new();
+        loop {
+            let offset = self.pos;
+            match self.header()? {
+                Header::Break => return Ok(buf),
+                Header::Bytes(Some(len)) if !text => buf.extend_from_slice(self.take(len)?),
+                Header::Text(Some(len)) if text => buf.extend_from_slice(self.take(len)?),
+                _ => return Err(Error::Syntax(offset)),
+            }
+        }
+    }
+
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> DeResult<T>) -> DeResult<T> {
+        if self.depth == 0 {
+            return Err(Error::RecursionLimitExceeded);
+        }
+        self.depth -= 1;
+        let result = f(self);
+        self.depth += 1;
+        result
+    }
+
+    /// After a sequence or map visitor returns, check that it consumed every
+    /// element.
+    fn finish_container(&mut self, remaining: Option<usize>) -> DeResult<()> {
+        match remaining {
+            Some(0) => Ok(()),
+            Some(_) => Err(de::Error::custom("trailing elements in CBOR container")),
+            None if self.peek() == Some(BREAK) => {
+                self.pos += 1;
+                Ok(())
+            },
+            None => Err(de::Error::custom("trailing elements in CBOR container")),
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut BorrowedDeserializer<'de> {
+    type Error = Error<io::Error>;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> DeResult<V::Value> {
+        let offset = self.pos;
+        match self.header()? {
+            Header::Positive(n) => visitor.visit_u64(n),
+            Header::Negative(n) => match i64::try_from(n) {
+                Ok(n) => visitor.visit_i64(-1 - n),
+                Err(_) => visitor.visit_i128(-1 - i128::from(n)),
+            },
+            Header::Float(f) => visitor.visit_f64(f),
+            Header::Simple(20) => visitor.visit_bool(false),
+            Header::Simple(21) => visitor.visit_bool(true),
+            Header::Simple(22 | 23) => visitor.visit_none(),
+            Header::Tag(_) => {
+                self.nested(|de| de::Deserializer::deserialize_any(&mut *de, visitor))
+            },
+            Header::Bytes(Some(len)) => visitor.visit_borrowed_bytes(self.take(len)?),
+            Header::Bytes(None) => visitor.visit_byte_buf(self.segments(false)?),
+            Header::Text(Some(len)) => visitor.visit_borrowed_str(self.take_str(len)?),
+            Header::Text(None) => {
+                let text =
+                    String::from_utf8(self.segments(true)?).map_err(|_| Error::Syntax(offset))?;
+                visitor.visit_string(text)
+            },
+            Header::Array(len) => self.nested(|de| {
+                let mut access = Access { de, remaining: len };
+                let value = visitor.visit_seq(&mut access)?;
+                let remaining = access.remaining;
+                de.finish_container(remaining)?;
+                Ok(value)
+            }),
+            Header::Map(len) => self.nested(|de| {
+                let mut access = Access { de, remaining: len };
+                let value = visitor.visit_map(&mut access)?;
+                let remaining = access.remaining;
+                de.finish_container(remaining)?;
+                Ok(value)
+            }),
+            Header::Simple(_) | Header::Break => Err(Error::Syntax(offset)),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> DeResult<V::Value> {
+        match self.peek() {
+            Some(NULL | UNDEFINED) => {
+                self.pos += 1;
+                visitor.visit_none()
+            },
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    /// A [`Spanned`](crate::Spanned) field is handed its start offset, its
+    /// value and its end offset as a sequence.
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> DeResult<V::Value> {
+        if name == SPANNED_NAME {
+            let start = self.pos;
+            return visitor.visit_seq(SpanAccess {
+                de: self,
+                start,
+                field: 0,
+            });
+        }
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Unit variants are text strings, other variants single-entry maps
+    /// from the variant name to its content, as ciborium writes them.
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> DeResult<V::Value> {
+        let offset = self.pos;
+        match self.header()? {
+            Header::Text(Some(len)) => {
+                let variant =
+                    de::value::BorrowedStrDeserializer::<Self::Error>::new(self.take_str(len)?);
+                visitor.visit_enum(variant)
+            },
+            Header::Map(Some(1)) => self.nested(|de| visitor.visit_enum(Enum { de })),
+            _ => Err(Error::Syntax(offset)),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// Elements of an array or entries of a map; `remaining` is `None` for an
+/// indefinite-length container.
+struct Access<'a, 'de> {
+    de: &'a mut BorrowedDeserializer<'de>,
+    remaining: Option<usize>,
+}
+
+impl Access<'_, '_> {
+    fn has_next(&mut self) -> bool {
+        match &mut self.remaining {
+            Some(0) => false,
+            Some(n) => {
+                *n -= 1;
+                true
+            },
+            None => self.de.peek() != Some(BREAK),
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for Access<'_, 'de> {
+    type Error = Error<io::Error>;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> DeResult<Option<T::Value>> {
+        if !self.has_next() {
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.remaining
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Access<'_, 'de> {
+    type Error = Error<io::Error>;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> DeResult<Option<K::Value>> {
+        if !self.has_next() {
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> DeResult<V::Value> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.remaining
+    }
+}
+
+/// The start offset, value and end offset of a [`Spanned`](crate::Spanned)
+/// field, in that order.
+struct SpanAccess<'a, 'de> {
+    de: &'a mut BorrowedDeserializer<'de>,
+    start: usize,
+    field: u8,
+}
+
+impl<'de> de::SeqAccess<'de> for SpanAccess<'_, 'de> {
+    type Error = Error<io::Error>;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> DeResult<Option<T::Value>> {
+        let field = self.field;
+        self.field = self.field.saturating_add(1);
+        match field {
+            0 => seed
+                .deserialize(de::value::UsizeDeserializer::new(self.start))
+                .map(Some),
+            1 => seed.deserialize(&mut *self.de).map(Some),
+            2 => seed
+                .deserialize(de::value::UsizeDeserializer::new(self.de.pos))
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(3usize.saturating_sub(usize::from(self.field)))
+    }
+}
+
+struct Enum<'a, 'de> {
+    de: &'a mut BorrowedDeserializer<'de>,
+}
+
+impl<'de> de::EnumAccess<'de> for Enum<'_, 'de> {
+    type Error = Error<io::Error>;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> DeResult<(V::Value, Self)> {
+        let variant = seed.deserialize(&mut *self.de)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Enum<'_, 'de> {
+    type Error = Error<io::Error>;
+
+    fn unit_variant(self) -> DeResult<()> {
+        de::IgnoredAny::deserialize(&mut *self.de).map(|_| ())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> DeResult<T::Value> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> DeResult<V::Value> {
+        de::Deserializer::deserialize_any(&mut *self.de, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> DeResult<V::Value> {
+        de::Deserializer::deserialize_any(&mut *self.de, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode_full, serialize};
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Kind {
+        Plain,
+        Sized(u32),
+        Pair(u8, i64),
+        Named { label: String },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Owned {
+        id: u64,
+        delta: i64,
+        ratio: f64,
+        flag: bool,
+        note: Option<String>,
+        missing: Option<u8>,
+        payload: serde_bytes::ByteBuf,
+        items: Vec<(u16, String)>,
+        index: BTreeMap<String, i128>,
+        kinds: Vec<Kind>,
+    }
+
+    fn sample() -> Owned {
+        Owned {
+            id: u64::MAX,
+            delta: i64::MIN,
+            ratio: 1.5,
+            flag: true,
+            note: Some("cardano".into()),
+            missing: None,
+            payload: serde_bytes::ByteBuf::from(vec![1, 2, 3]),
+            items: vec![(1, "a".into()), (65_535, "b".into())],
+            index: [("neg".to_string(), -(1i128 << 64)), ("pos".to_string(), 3)]
+                .into_iter()
+                .collect(),
+            kinds: vec![
+                Kind::Plain,
+                Kind::Sized(9),
+                Kind::Pair(1, -2),
+                Kind::Named { label: "x".into() },
+            ],
+        }
+    }
+
+    #[test]
+    fn owned_types_decode_like_decode_full() {
+        let bytes = serialize(&sample()).unwrap();
+        let borrowed: Owned = decode_full_borrowed(&bytes).unwrap();
+        assert_eq!(borrowed, decode_full::<Owned>(&bytes).unwrap());
+        assert_eq!(borrowed, sample());
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct View<'a> {
+        #[serde(borrow)]
+        key: &'a [u8],
+        name: &'a str,
+    }
+
+    #[test]
+    fn strings_borrow_from_the_input() {
+        // {"key": h'0a0b', "name": "vk"}
+        let bytes = [
+            0xa2, 0x63, b'k', b'e', b'y', 0x42, 0x0a, 0x0b, 0x64, b'n', b'a', b'm', b'e', 0x62,
+            b'v', b'k',
+        ];
+        let view: View<'_> = decode_full_borrowed(&bytes).unwrap();
+        assert_eq!(view.key, [0x0a, 0x0b]);
+        assert_eq!(view.name, "vk");
+        assert!(std::ptr::eq(view.key.as_ptr(), bytes[6..].as_ptr()));
+        assert!(std::ptr::eq(view.name.as_ptr(), bytes[14..].as_ptr()));
+    }
+
+    #[test]
+    fn indefinite_strings_cannot_be_borrowed() {
+        // (_ h'01', h'02') as a byte buffer, then as a borrowed slice.
+        let bytes = [0x5f, 0x41, 0x01, 0x41, 0x02, 0xff];
+        let owned: serde_bytes::ByteBuf = decode_full_borrowed(&bytes).unwrap();
+        assert_eq!(owned.as_slice(), [1, 2]);
+        assert!(decode_full_borrowed::<&[u8]>(&bytes).is_err());
+
+        // Indefinite array [_ 1, 2].
+        let array = [0x9f, 0x01, 0x02, 0xff];
+        assert_eq!(decode_full_borrowed::<Vec<u8>>(&array).unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        // Truncated byte string.
+        assert!(matches!(
+            decode_full_borrowed::<&[u8]>(&[0x43, 0x01]).unwrap_err(),
+            BinaryError::Deserialization {
+                source: Error::Io(_),
+                ..
+            }
+        ));
+        // Trailing bytes.
+        assert!(matches!(
+            decode_full_borrowed::<u8>(&[0x01, 0x02]).unwrap_err(),
+            BinaryError::Leftover {
+                consumed: 1,
+                remaining: 1,
+                ..
+            }
+        ));
+        // A 2-tuple decoder given a 3-element array.
+        assert!(decode_full_borrowed::<(u8, u8)>(&[0x83, 0x01, 0x02, 0x03]).is_err());
+        // Invalid UTF-8.
+        assert!(decode_full_borrowed::<&str>(&[0x61, 0xff]).is_err());
+        // Deep nesting stops at the recursion limit instead of overflowing.
+        let deep = vec![0x81; RECURSION_LIMIT + 1];
+        assert!(matches!(
+            decode_full_borrowed::<de::IgnoredAny>(&deep).unwrap_err(),
+            BinaryError::Deserialization {
+                source: Error::RecursionLimitExceeded,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,151 @@
+#![cfg_attr(test, allow(clippy::unwrap_used))]
+
+use crate::error::{BinaryError, ResultExt};
+use ciborium::value::Value;
+use serde::{Serialize, Serializer};
+use std::io::Cursor;
+
+/// Wrapper that forces a canonical CBOR encoding of the inner value.
+///
+/// Types whose `Serialize` implementation walks a `HashMap` (or any other
+/// container with nondeterministic iteration order) produce map entries in an
+/// arbitrary order. Serialising through `CanonicalCbor` buffers the inner value,
+/// sorts every map — recursively — by the bytewise order of its encoded keys
+/// (RFC 8949 §4.2.1), and only then hands the result to the outer serializer.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use cardano_binary::{CanonicalCbor, serialize};
+///
+/// let map: HashMap<&str, u64> = [("zebra", 1), ("apple", 2)].into_iter().collect();
+/// let bytes = serialize(&CanonicalCbor(&map)).unwrap();
+/// assert_eq!(bytes[1..7], [0x65, b'a', b'p', b'p', b'l', b'e']);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CanonicalCbor<T>(pub T);
+
+impl<T: Serialize> Serialize for CanonicalCbor<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = Value::serialized(&self.0).map_err(serde::ser::Error::custom)?;
+        let canonical = canonical_value(value).map_err(serde::ser::Error::custom)?;
+        canonical.serialize(serializer)
+    }
+}
+
+/// Re-encode an arbitrary CBOR document into canonical form.
+///
+/// Map keys are sorted recursively by their encoded bytes, integers and
+/// lengths use their smallest encoding, and indefinite-length strings, arrays,
+/// and maps are converted to their definite-length equivalents.
+///
+/// # Errors
+///
+/// Returns [`BinaryError::Deserialization`] if `bytes` is not valid CBOR,
+/// [`BinaryError::Leftover`] if trailing bytes follow the first data item, and
+/// [`BinaryError::Serialization`] if the canonical form cannot be re-encoded.
+/// Decoding and encoding failures are wrapped in a [`BinaryError::Context`]
+/// naming the stage; use [`BinaryError::root_cause`] to match on them.
+pub fn canonicalize(bytes: &[u8]) -> Result<Vec<u8>, BinaryError> {
+    let mut cursor = Cursor::new(bytes);
+    let value: Value = ciborium::from_reader(&mut cursor)
+        .map_err(BinaryError::from)
+        .context("canonical CBOR input")?;
+
+    let position = cursor.position() as usize;
+    if position < bytes.len() {
+        return Err(BinaryError::leftover("canonical CBOR", bytes, position));
+    }
+
+    let mut buf = Vec::with_capacity(bytes.len());
+    ciborium::into_writer(&canonical_value(value)?, &mut buf)
+        .map_err(BinaryError::from)
+        .context("canonical CBOR re-encoding")?;
+    Ok(buf)
+}
+
+/// Check whether `bytes` is already in canonical form.
+///
+/// This is the validation counterpart of [`canonicalize`]: map keys must be in
+/// bytewise order of their encodings and every length and integer must use
+/// its shortest definite form.
+///
+/// # Errors
+///
+/// Returns the same errors as [`canonicalize`] when `bytes` cannot be decoded
+/// as a single CBOR data item.
+pub fn is_canonical(bytes: &[u8]) -> Result<bool, BinaryError> {
+    Ok(canonicalize(bytes)? == bytes)
+}
+
+/// Recursively sort every map in `value` by the encoded bytes of its keys.
+fn canonical_value(value: Value) -> Result<Value, BinaryError> {
+    Ok(match value {
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(canonical_value)
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Map(entries) => {
+            let mut keyed = entries
+                .into_iter()
+                .map(|(key, val)| {
+                    let key = canonical_value(key)?;
+                    let mut encoded = Vec::new();
+                    ciborium::into_writer(&key, &mut encoded)?;
+                    Ok((encoded, key, canonical_value(val)?))
+                })
+                .collect::<Result<Vec<_>, BinaryError>>()?;
+            keyed.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Map(keyed.into_iter().map(|(_, key, val)| (key, val)).collect())
+        },
+        Value::Tag(tag, inner) => Value::Tag(tag, Box::new(canonical_value(*inner)?)),
+        other => other,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serialize::serialize;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[test]
+    fn wrapper_matches_btreemap_encoding() {
+        let hashed: HashMap<String, u64> = (0..32).map(|i| (format!("key-{i:02}"), i)).collect();
+        let sorted: BTreeMap<String, u64> = hashed.clone().into_iter().collect();
+
+        let canonical = serialize(&CanonicalCbor(&hashed)).unwrap();
+        assert_eq!(canonical, serialize(&sorted).unwrap());
+        assert!(is_canonical(&canonical).unwrap());
+    }
+
+    #[test]
+    fn sorts_nested_maps_inside_arrays() {
+        let inner: HashMap<u64, &str> = [(500, "big"), (1, "small")].into_iter().collect();
+        let bytes = serialize(&CanonicalCbor(vec![inner])).unwrap();
+        // [ {1: "small", 500: "big"} ]
+        assert_eq!(&bytes[..3], &[0x81, 0xa2, 0x01]);
+    }
+
+    #[test]
+    fn canonicalize_rejects_trailing_bytes() {
+        let err = canonicalize(&[0x01, 0x02]).unwrap_err();
+        assert!(matches!(
+            err,
+            BinaryError::Leftover {
+                consumed: 1,
+                remaining: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn is_canonical_detects_unsorted_keys() {
+        // {"b": 1, "a": 2}
+        let unsorted = [0xa2, 0x61, 0x62, 0x01, 0x61, 0x61, 0x02];
+        assert!(!is_canonical(&unsorted).unwrap());
+        assert!(is_canonical(&canonicalize(&unsorted).unwrap()).unwrap());
+    }
+}
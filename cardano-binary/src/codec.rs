@@ -0,0 +1,268 @@
+//! `ToCbor` / `FromCbor`, the counterparts of Haskell's `ToCBOR` and
+//! `FromCBOR` classes.
+//!
+//! [`serialize`](crate::serialize) and [`decode_full`](crate::decode_full)
+//! are bounded on these traits. Every `serde` type implements them through
+//! the blanket impls below, so existing types keep their exact encoding.
+//!
+//! Rust's coherence rules do not allow a second, hand-written impl next to a
+//! blanket one, so a type that needs a CBOR form different from its other
+//! `serde` forms (hex in JSON, a byte string in CBOR) makes the choice in its
+//! `serde` impls: every encoder and decoder in this crate reports
+//! [`is_human_readable`](serde::Serializer::is_human_readable) as `false`,
+//! while JSON reports `true`. [`bytes_or_hex`](crate::bytes_or_hex)
+//! implements the common byte-string case.
+
+use std::io::Cursor;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::error::BinaryError;
+
+/// A value with a CBOR encoding, Haskell's `ToCBOR`.
+pub trait ToCbor {
+    /// Append the CBOR encoding of `self` to `buffer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BinaryError::Serialization`] if the value cannot be encoded.
+    fn encode_cbor(&self, buffer: &mut Vec<u8>) -> Result<(), BinaryError>;
+
+    /// The CBOR encoding of `self` as a fresh vector.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BinaryError::Serialization`] if the value cannot be encoded.
+    fn to_cbor(&self) -> Result<Vec<u8>, BinaryError> {
+        let mut buffer = Vec::new();
+        self.encode_cbor(&mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+/// A value decodable from exactly one CBOR data item, Haskell's `FromCBOR`.
+pub trait FromCbor: Sized {
+    /// Decode `bytes`, which must hold one complete data item and nothing
+    /// else.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BinaryError::Deserialization`] for malformed or mistyped
+    /// input and [`BinaryError::Leftover`] if bytes follow the data item.
+    fn decode_cbor(bytes: &[u8]) -> Result<Self, BinaryError>;
+}
+
+impl<T: Serialize + ?Sized> ToCbor for T {
+    fn encode_cbor(&self, buffer: &mut Vec<u8>) -> Result<(), BinaryError> {
+        ciborium::into_writer(self, buffer)?;
+        Ok(())
+    }
+}
+
+impl<T: DeserializeOwned> FromCbor for T {
+    fn decode_cbor(bytes: &[u8]) -> Result<Self, BinaryError> {
+        let mut cursor = Cursor::new(bytes);
+        let value: T = ciborium::from_reader(&mut cursor)
+            .map_err(|err| BinaryError::deserialization(std::any::type_name::<T>(), err))?;
+
+        let position = cursor.position() as usize;
+        if position < bytes.len() {
+            return Err(BinaryError::leftover(
+                std::any::type_name::<T>(),
+                bytes,
+                position,
+            ));
+        }
+
+        Ok(value)
+    }
+}
+
+/// `serde` helpers for byte strings that are hex text in human-readable
+/// formats and a CBOR byte string otherwise.
+///
+/// Use with `#[serde(with = "cardano_binary::bytes_or_hex")]` on a
+/// `Vec<u8>` field, or call the functions from a hand-written impl that
+/// validates the decoded bytes.
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct KeyHash(#[serde(with = "cardano_binary::bytes_or_hex")] Vec<u8>);
+///
+/// let hash = KeyHash(vec![0xca, 0xfe]);
+/// assert_eq!(cardano_binary::serialize(&hash).unwrap(), [0x42, 0xca, 0xfe]);
+/// ```
+pub mod bytes_or_hex {
+    use std::fmt;
+
+    use serde::de::{self, SeqAccess, Visitor};
+    use serde::{Deserializer, Serializer};
+
+    /// Write `bytes` as lowercase hex text or as a byte string.
+    ///
+    /// # Errors
+    ///
+    /// Returns the serializer's error if writing fails.
+    pub fn serialize<B, S>(bytes: &B, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        B: AsRef<[u8]> + ?Sized,
+        S: Serializer,
+    {
+        let bytes = bytes.as_ref();
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&encode_hex(bytes))
+        } else {
+            serializer.serialize_bytes(bytes)
+        }
+    }
+
+    /// Read bytes written by [`serialize`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error if the input is neither a byte
+    /// string nor valid hex text.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_with(deserializer, BytesVisitor)
+    }
+
+    /// Read bytes written by [`serialize`] and pass them to `visitor`'s
+    /// `visit_bytes` / `visit_byte_buf`, so an existing byte-string visitor
+    /// that validates its input also accepts hex text.
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error for invalid hex text and whatever
+    /// `visitor` reports.
+    pub fn deserialize_with<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Visitor<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let bytes = deserializer.deserialize_str(HexVisitor)?;
+            visitor.visit_byte_buf(bytes)
+        } else {
+            deserializer.deserialize_bytes(visitor)
+        }
+    }
+
+    fn encode_hex(bytes: &[u8]) -> String {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let mut text = String::with_capacity(bytes.len() * 2);
+        for byte in bytes {
+            text.push(char::from(DIGITS[usize::from(byte >> 4)]));
+            text.push(char::from(DIGITS[usize::from(byte & 0x0f)]));
+        }
+        text
+    }
+
+    fn decode_hex(text: &str) -> Option<Vec<u8>> {
+        fn nibble(digit: u8) -> Option<u8> {
+            match digit {
+                b'0'..=b'9' => Some(digit - b'0'),
+                b'a'..=b'f' => Some(digit - b'a' + 10),
+                b'A'..=b'F' => Some(digit - b'A' + 10),
+                _ => None,
+            }
+        }
+
+        let digits = text.as_bytes();
+        if digits.len() % 2 != 0 {
+            return None;
+        }
+        digits
+            .chunks_exact(2)
+            .map(|pair| Some((nibble(pair[0])? << 4) | nibble(pair[1])?))
+            .collect()
+    }
+
+    struct HexVisitor;
+
+    impl Visitor<'_> for HexVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a hex string")
+        }
+
+        fn visit_str<E: de::Error>(self, text: &str) -> Result<Vec<u8>, E> {
+            decode_hex(text).ok_or_else(|| E::invalid_value(de::Unexpected::Str(text), &self))
+        }
+    }
+
+    struct BytesVisitor;
+
+    impl<'de> Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a byte string")
+        }
+
+        fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Vec<u8>, E> {
+            Ok(bytes.to_vec())
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, bytes: Vec<u8>) -> Result<Vec<u8>, E> {
+            Ok(bytes)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+            Ok(bytes)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn hex_round_trips() {
+            let bytes = [0x00, 0x7f, 0x80, 0xff];
+            assert_eq!(encode_hex(&bytes), "007f80ff");
+            assert_eq!(decode_hex("007F80ff").unwrap(), bytes);
+            assert_eq!(decode_hex("abc"), None);
+            assert_eq!(decode_hex("zz"), None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blanket_impls_match_ciborium() {
+        let value = (7u32, "seven", vec![1u8, 2]);
+        let mut expected = Vec::new();
+        ciborium::into_writer(&value, &mut expected).unwrap();
+        assert_eq!(value.to_cbor().unwrap(), expected);
+
+        let decoded = <(u32, String, Vec<u8>)>::decode_cbor(&expected).unwrap();
+        assert_eq!(decoded, (7, "seven".to_owned(), vec![1, 2]));
+    }
+
+    #[test]
+    fn decode_cbor_rejects_trailing_bytes() {
+        let err = u8::decode_cbor(&[0x01, 0x02]).unwrap_err();
+        assert!(matches!(
+            err,
+            BinaryError::Leftover {
+                consumed: 1,
+                remaining: 1,
+                ..
+            }
+        ));
+    }
+}
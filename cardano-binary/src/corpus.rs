@@ -0,0 +1,430 @@
+//! JSON-described CBOR corpus for differential testing against Haskell's
+//! `cborg` (`test-util` feature).
+//!
+//! A corpus entry describes a CBOR data item structurally — integers, byte and
+//! text strings, arrays, maps, tags, booleans, `null`, and floats with an
+//! explicit width — together with the hex `cborg` produces for it. Checking an
+//! entry decodes the hex to a [`Value`], compares it with the description,
+//! re-encodes it, and asserts byte equality; [`is_canonical`] must agree with
+//! the entry's `canonical` flag, so an encoding one side considers canonical
+//! and the other does not is flagged instead of passing silently.
+//!
+//! The JSON shape of a description mirrors [`Value`]:
+//!
+//! ```json
+//! {"int": "-18446744073709551616"}     integers as decimal strings
+//! {"bytes": "00ff"}                    lowercase hex
+//! {"text": "IETF"}
+//! {"array": [{"int": "1"}, "null"]}
+//! {"map": [[{"text": "a"}, {"bool": true}]]}
+//! {"tag": [24, {"bytes": "83010203"}]}
+//! {"float16": 1.5}                     also float32 / float64; "NaN",
+//!                                      "Infinity" and "-Infinity" as strings
+//! ```
+//!
+//! Entries without `hex` are pending: the `cbor_corpus_gen` binary emits
+//! random descriptions that way for the Haskell side to encode, and
+//! [`Corpus::check`] counts them without checking them.
+//!
+//! ```rust
+//! use cardano_binary::corpus::Corpus;
+//!
+//! let corpus = Corpus::from_json(
+//!     r#"{"entries": [{"name": "uint_24", "value": {"int": "24"}, "hex": "1818"}]}"#,
+//! )
+//! .unwrap();
+//! let report = corpus.check();
+//! assert_eq!(report.checked, 1);
+//! assert!(report.mismatches.is_empty());
+//! ```
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{BinaryError, Value, decode_value, encode_value, is_canonical};
+
+/// A set of corpus entries, as stored in a JSON file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Corpus {
+    /// Free-form provenance, e.g. the `cborg` version the hex came from.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// The entries, checked in order.
+    pub entries: Vec<CorpusEntry>,
+}
+
+/// One described CBOR data item and its `cborg` encoding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorpusEntry {
+    /// Unique name used in reports.
+    pub name: String,
+    /// Structural description of the data item.
+    pub value: CorpusValue,
+    /// Lowercase hex of the `cborg` encoding; `None` while pending.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hex: Option<String>,
+    /// Whether `hex` is canonical (shortest widths, definite lengths, sorted
+    /// map keys). Non-canonical entries must decode to the described value
+    /// and be reported as non-canonical by [`is_canonical`].
+    #[serde(default = "canonical_default", skip_serializing_if = "is_true")]
+    pub canonical: bool,
+    /// Why the entry exists, for non-obvious cases.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+fn canonical_default() -> bool {
+    true
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_true(flag: &bool) -> bool {
+    *flag
+}
+
+/// Structural description of a CBOR data item; see the module docs for the
+/// JSON shape.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CorpusValue {
+    /// Major types 0 and 1, as a decimal string covering `-2^64 ..= 2^64 - 1`.
+    Int(String),
+    /// Major type 2, as lowercase hex.
+    Bytes(String),
+    /// Major type 3.
+    Text(String),
+    /// Major type 4.
+    Array(Vec<CorpusValue>),
+    /// Major type 5, entries in encoding order.
+    Map(Vec<(CorpusValue, CorpusValue)>),
+    /// Major type 6.
+    Tag(u64, Box<CorpusValue>),
+    /// Simple values 20 and 21.
+    Bool(bool),
+    /// Simple value 22.
+    Null,
+    /// Half-precision float (`encodeHalf`).
+    Float16(CorpusFloat),
+    /// Single-precision float (`encodeFloat`).
+    Float32(CorpusFloat),
+    /// Double-precision float (`encodeDouble`).
+    Float64(CorpusFloat),
+}
+
+/// A float in a corpus description. JSON has no NaN or infinities, so those
+/// are written as the strings `"NaN"`, `"Infinity"` and `"-Infinity"`.
+#[derive(Debug, Clone, Copy)]
+pub struct CorpusFloat(pub f64);
+
+impl PartialEq for CorpusFloat {
+    fn eq(&self, other: &Self) -> bool {
+        same_float(self.0, other.0)
+    }
+}
+
+impl Serialize for CorpusFloat {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = self.0;
+        if value.is_nan() {
+            serializer.serialize_str("NaN")
+        } else if value.is_infinite() {
+            serializer.serialize_str(if value > 0.0 { "Infinity" } else { "-Infinity" })
+        } else {
+            serializer.serialize_f64(value)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CorpusFloat {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Number(f64),
+            Special(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Number(value) => Ok(CorpusFloat(value)),
+            Repr::Special(text) => match text.as_str() {
+                "NaN" => Ok(CorpusFloat(f64::NAN)),
+                "Infinity" => Ok(CorpusFloat(f64::INFINITY)),
+                "-Infinity" => Ok(CorpusFloat(f64::NEG_INFINITY)),
+                other => Err(serde::de::Error::custom(format!(
+                    "expected a number, \"NaN\", \"Infinity\" or \"-Infinity\", got {other:?}"
+                ))),
+            },
+        }
+    }
+}
+
+impl CorpusValue {
+    /// Build the [`Value`] this description stands for. Float widths only
+    /// matter for the encoding, so they are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CorpusMismatch::InvalidEntry`] if an integer is not a
+    /// decimal string or a byte string is not hex.
+    pub fn to_value(&self) -> Result<Value, CorpusMismatch> {
+        Ok(match self {
+            CorpusValue::Int(text) => {
+                Value::Int(text.parse().map_err(|_| CorpusMismatch::InvalidEntry {
+                    reason: format!("integer {text:?} is not a decimal string"),
+                })?)
+            },
+            CorpusValue::Bytes(text) => Value::Bytes(parse_hex(text)?),
+            CorpusValue::Text(text) => Value::Text(text.clone()),
+            CorpusValue::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .map(CorpusValue::to_value)
+                    .collect::<Result<_, _>>()?,
+            ),
+            CorpusValue::Map(entries) => Value::Map(
+                entries
+                    .iter()
+                    .map(|(key, value)| Ok((key.to_value()?, value.to_value()?)))
+                    .collect::<Result<_, CorpusMismatch>>()?,
+            ),
+            CorpusValue::Tag(tag, inner) => Value::Tag(*tag, Box::new(inner.to_value()?)),
+            CorpusValue::Bool(flag) => Value::Bool(*flag),
+            CorpusValue::Null => Value::Null,
+            CorpusValue::Float16(float)
+            | CorpusValue::Float32(float)
+            | CorpusValue::Float64(float) => Value::Float(float.0),
+        })
+    }
+}
+
+/// Why a corpus entry failed its check.
+#[derive(Debug, thiserror::Error)]
+pub enum CorpusMismatch {
+    /// The entry itself is malformed.
+    #[error("invalid corpus entry: {reason}")]
+    InvalidEntry { reason: String },
+    /// The hex did not decode as a single CBOR data item.
+    #[error("decoding failed: {0}")]
+    Decode(BinaryError),
+    /// The hex decoded to a different value than the description.
+    #[error("decoded {decoded:?}, described {described:?}")]
+    Value { decoded: Value, described: Value },
+    /// Re-encoding the decoded value produced different bytes.
+    #[error("re-encoded as {}, expected {}", hex(actual), hex(expected))]
+    Reencode { expected: Vec<u8>, actual: Vec<u8> },
+    /// [`is_canonical`] disagrees with the entry's `canonical` flag.
+    #[error("entry is marked canonical = {expected}, is_canonical returned {actual}")]
+    Canonicity { expected: bool, actual: bool },
+}
+
+/// Outcome of [`Corpus::check`].
+#[derive(Debug, Default)]
+pub struct CorpusReport {
+    /// Entries with hex that were checked.
+    pub checked: usize,
+    /// Entries without hex, still waiting for a `cborg` encoding.
+    pub pending: usize,
+    /// Entry names and why they failed.
+    pub mismatches: Vec<(String, CorpusMismatch)>,
+}
+
+impl fmt::Display for CorpusReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} checked, {} pending, {} mismatched",
+            self.checked,
+            self.pending,
+            self.mismatches.len()
+        )?;
+        for (name, mismatch) in &self.mismatches {
+            write!(f, "\n  {name}: {mismatch}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Corpus {
+    /// Parse a corpus from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `serde_json` error if `json` does not follow the corpus
+    /// schema.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Render the corpus as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `serde_json` error if serialisation fails.
+    pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Check every entry that has hex and collect the mismatches.
+    #[must_use]
+    pub fn check(&self) -> CorpusReport {
+        let mut report = CorpusReport::default();
+        for entry in &self.entries {
+            if entry.hex.is_none() {
+                report.pending += 1;
+                continue;
+            }
+            report.checked += 1;
+            if let Err(mismatch) = entry.check() {
+                report.mismatches.push((entry.name.clone(), mismatch));
+            }
+        }
+        report
+    }
+}
+
+impl CorpusEntry {
+    /// Check this entry: decode its hex, compare the result with the
+    /// description, re-encode it (canonical entries must reproduce the hex
+    /// byte for byte) and compare [`is_canonical`] with the `canonical` flag.
+    /// Pending entries pass.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`CorpusMismatch`] found.
+    pub fn check(&self) -> Result<(), CorpusMismatch> {
+        let Some(hex) = &self.hex else {
+            return Ok(());
+        };
+        let bytes = parse_hex(hex)?;
+        let described = self.value.to_value()?;
+
+        let decoded = decode_value(&bytes).map_err(CorpusMismatch::Decode)?;
+        if !same_value(&decoded, &described) {
+            return Err(CorpusMismatch::Value { decoded, described });
+        }
+
+        let reencoded = encode_value(&decoded).map_err(CorpusMismatch::Decode)?;
+        if self.canonical && reencoded != bytes {
+            return Err(CorpusMismatch::Reencode {
+                expected: bytes,
+                actual: reencoded,
+            });
+        }
+
+        let canonical = is_canonical(&bytes).map_err(CorpusMismatch::Decode)?;
+        if canonical != self.canonical {
+            return Err(CorpusMismatch::Canonicity {
+                expected: self.canonical,
+                actual: canonical,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Structural equality in which NaN equals NaN and `0.0` differs from `-0.0`.
+fn same_value(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Float(a), Value::Float(b)) => same_float(*a, *b),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(x, y)| same_value(x, y))
+        },
+        (Value::Map(a), Value::Map(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b)
+                    .all(|((ka, va), (kb, vb))| same_value(ka, kb) && same_value(va, vb))
+        },
+        (Value::Tag(ta, a), Value::Tag(tb, b)) => ta == tb && same_value(a, b),
+        _ => left == right,
+    }
+}
+
+fn same_float(a: f64, b: f64) -> bool {
+    (a.is_nan() && b.is_nan()) || a.to_bits() == b.to_bits()
+}
+
+fn parse_hex(text: &str) -> Result<Vec<u8>, CorpusMismatch> {
+    hex::decode(text).map_err(|err| CorpusMismatch::InvalidEntry {
+        reason: format!("{text:?} is not hex: {err}"),
+    })
+}
+
+fn hex(bytes: &[u8]) -> String {
+    hex::encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(value: CorpusValue, hex: &str, canonical: bool) -> CorpusEntry {
+        CorpusEntry {
+            name: "entry".into(),
+            value,
+            hex: Some(hex.into()),
+            canonical,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn json_shape_roundtrips() {
+        let json = r#"{"entries":[{"name":"n","value":{"array":[{"int":"-1"},"null",{"tag":[24,{"bytes":"01"}]},{"map":[[{"text":"a"},{"float16":"NaN"}]]}]},"hex":"00","canonical":false}]}"#;
+        let corpus = Corpus::from_json(json).unwrap();
+        assert_eq!(serde_json::to_string(&corpus).unwrap(), json);
+    }
+
+    #[test]
+    fn flags_each_kind_of_mismatch() {
+        let int = |text: &str| CorpusValue::Int(text.into());
+        assert!(entry(int("24"), "1818", true).check().is_ok());
+        assert!(matches!(
+            entry(int("25"), "1818", true).check(),
+            Err(CorpusMismatch::Value { .. })
+        ));
+        assert!(matches!(
+            entry(int("23"), "1817", true).check(),
+            Err(CorpusMismatch::Reencode { .. })
+        ));
+        assert!(matches!(
+            entry(int("24"), "1818", false).check(),
+            Err(CorpusMismatch::Canonicity {
+                expected: false,
+                actual: true
+            })
+        ));
+        assert!(matches!(
+            entry(int("1"), "zz", true).check(),
+            Err(CorpusMismatch::InvalidEntry { .. })
+        ));
+        assert!(matches!(
+            entry(int("1"), "1f", true).check(),
+            Err(CorpusMismatch::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn nan_matches_nan_but_zero_signs_differ() {
+        let float = |value| CorpusValue::Float16(CorpusFloat(value));
+        assert!(entry(float(f64::NAN), "f97e00", true).check().is_ok());
+        assert!(matches!(
+            entry(float(0.0), "f98000", true).check(),
+            Err(CorpusMismatch::Value { .. })
+        ));
+    }
+
+    #[test]
+    fn pending_entries_are_counted_not_checked() {
+        let corpus = Corpus {
+            description: String::new(),
+            entries: vec![CorpusEntry {
+                hex: None,
+                ..entry(CorpusValue::Null, "", true)
+            }],
+        };
+        let report = corpus.check();
+        assert_eq!((report.checked, report.pending), (0, 1));
+    }
+}
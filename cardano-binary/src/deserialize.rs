@@ -1,8 +1,10 @@
 #![cfg_attr(test, allow(clippy::unwrap_used))]
 
-use crate::error::BinaryError;
+use std::io::{self, Read};
+
+use crate::codec::FromCbor;
+use crate::error::{BinaryError, LEFTOVER_PREVIEW_LEN, ResultExt};
 use serde::de::DeserializeOwned;
-use std::io::Cursor;
 
 /// Deserialize a value, panicking if decoding fails.
 ///
@@ -47,37 +49,103 @@ pub fn unsafe_deserialize_owned<T: DeserializeOwned>(bytes: Vec<u8>) -> T {
 
 /// Deserialize a value from a byte slice, consuming the entire payload.
 ///
+/// Decoding goes through the value's [`FromCbor`] impl, which for `serde`
+/// types is the blanket one.
+///
 /// # Errors
 ///
-/// Returns [`BinaryError::Deserialization`] if:
-/// - The input is not valid CBOR
-/// - The CBOR structure doesn't match the expected type
-/// - There are leftover bytes after deserialization
-pub fn decode_full<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, BinaryError> {
-    let mut cursor = Cursor::new(bytes);
-    let value: T = ciborium::from_reader(&mut cursor)?;
-
-    let position = cursor.position() as usize;
-    if position < bytes.len() {
-        let leftover = bytes[position..].to_vec();
-        return Err(BinaryError::leftover(std::any::type_name::<T>(), leftover));
-    }
-
-    Ok(value)
+/// Returns [`BinaryError::Deserialization`], labelled with the name of `T`,
+/// if the input is not valid CBOR or doesn't match the expected type, and
+/// [`BinaryError::Leftover`] if bytes follow the data item.
+pub fn decode_full<T: FromCbor>(bytes: &[u8]) -> Result<T, BinaryError> {
+    let result = T::decode_cbor(bytes);
+    #[cfg(feature = "trace")]
+    crate::trace::emit::<T, _>(crate::trace::TraceDirection::Decode, bytes, &result);
+    result
 }
 
 /// Strict variant of [`decode_full`] operating on owned bytes.
 ///
 /// # Errors
 ///
-/// Returns [`BinaryError::Deserialization`] if:
-/// - The input is not valid CBOR
-/// - The CBOR structure doesn't match the expected type
-/// - There are leftover bytes after deserialization
-pub fn decode_full_owned<T: DeserializeOwned>(bytes: Vec<u8>) -> Result<T, BinaryError> {
+/// Returns what [`decode_full`] does.
+pub fn decode_full_owned<T: FromCbor>(bytes: Vec<u8>) -> Result<T, BinaryError> {
     decode_full(&bytes)
 }
 
+/// Decode one data item from `reader`, reading no further than its last byte.
+///
+/// The input is pulled through `ciborium` as it decodes, so a large payload
+/// such as a ledger snapshot never has to be held in memory as raw bytes.
+/// Pass `&mut reader` to decode several consecutive items from one stream.
+/// The decoder issues many small reads, so wrap a file or socket in a
+/// [`std::io::BufReader`].
+///
+/// # Errors
+///
+/// Returns [`BinaryError::AtOffset`] with the number of bytes read before
+/// decoding stopped, wrapping [`BinaryError::Deserialization`] for
+/// malformed, mistyped or truncated input and for read failures.
+pub fn decode_from_reader<T: DeserializeOwned, R: Read>(reader: R) -> Result<T, BinaryError> {
+    let mut counting = CountingReader {
+        inner: reader,
+        consumed: 0,
+    };
+    ciborium::from_reader(&mut counting).map_err(|err| {
+        BinaryError::deserialization(std::any::type_name::<T>(), err).at_offset(counting.consumed)
+    })
+}
+
+/// Reader counterpart of [`decode_full`]: decode one data item from `reader`
+/// and require the stream to end right after it.
+///
+/// Trailing bytes, if any, are read to the end of the stream to count them,
+/// keeping only the first [`LEFTOVER_PREVIEW_LEN`].
+///
+/// # Errors
+///
+/// Returns what [`decode_from_reader`] does, and [`BinaryError::Leftover`]
+/// if the stream continues after the data item.
+pub fn decode_full_from_reader<T: DeserializeOwned, R: Read>(reader: R) -> Result<T, BinaryError> {
+    let mut counting = CountingReader {
+        inner: reader,
+        consumed: 0,
+    };
+    let value = decode_from_reader(&mut counting)?;
+    let end = counting.consumed;
+
+    let mut preview = Vec::with_capacity(LEFTOVER_PREVIEW_LEN);
+    (&mut counting)
+        .take(LEFTOVER_PREVIEW_LEN as u64)
+        .read_to_end(&mut preview)
+        .and_then(|_| io::copy(&mut counting, &mut io::sink()))
+        .map_err(|err| BinaryError::from(err).at_offset(counting.consumed))?;
+    if !preview.is_empty() {
+        return Err(BinaryError::Leftover {
+            label: std::any::type_name::<T>().into(),
+            consumed: end as usize,
+            remaining: (counting.consumed - end) as usize,
+            preview,
+        });
+    }
+
+    Ok(value)
+}
+
+/// Counts the bytes read through it, for [`BinaryError::AtOffset`].
+struct CountingReader<R> {
+    inner: R,
+    consumed: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.consumed += read as u64;
+        Ok(read)
+    }
+}
+
 /// Decode a nested CBOR payload wrapped in semantic tag 24 and deserialize it as type `T`.
 ///
 /// # Errors
@@ -89,7 +157,7 @@ pub fn decode_full_owned<T: DeserializeOwned>(bytes: Vec<u8>) -> Result<T, Binar
 /// - The inner bytes cannot be deserialized as type `T`
 pub fn decode_nested_cbor<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, BinaryError> {
     let raw = decode_nested_cbor_bytes(bytes)?;
-    decode_full(&raw)
+    decode_full(&raw).context("nested CBOR payload")
 }
 
 /// Decode a nested CBOR payload wrapped in semantic tag 24 and return the raw bytes.
@@ -104,6 +172,14 @@ pub fn decode_nested_cbor_bytes(bytes: &[u8]) -> Result<Vec<u8>, BinaryError> {
     use ciborium::value::Value;
 
     let value: Value = ciborium::from_reader(bytes)?;
+    nested_cbor_value_bytes(value)
+}
+
+/// The payload of an already decoded tag 24 value.
+pub(crate) fn nested_cbor_value_bytes(
+    value: ciborium::value::Value,
+) -> Result<Vec<u8>, BinaryError> {
+    use ciborium::value::Value;
 
     match value {
         Value::Tag(24, boxed_value) => match *boxed_value {
@@ -155,12 +231,18 @@ mod tests {
         ciborium::into_writer(&sample, &mut bytes).unwrap();
         bytes.extend_from_slice(&[0xff]); // extra data
         let err = decode_full::<Sample>(&bytes).unwrap_err();
-        let leftover_len = match err {
-            BinaryError::Leftover { leftover_len, .. } => Ok(leftover_len),
+        let (consumed, remaining, preview) = match err {
+            BinaryError::Leftover {
+                consumed,
+                remaining,
+                preview,
+                ..
+            } => Ok((consumed, remaining, preview)),
             _ => Err(()),
         }
         .expect("expected leftover error");
-        assert_eq!(leftover_len, 1);
+        assert_eq!((consumed, remaining), (bytes.len() - 1, 1));
+        assert_eq!(preview, [0xff]);
     }
 
     #[test]
@@ -196,4 +278,119 @@ mod tests {
         let err = decode_nested_cbor_bytes(&encoded).unwrap_err();
         assert!(matches!(err, BinaryError::NestedPayload));
     }
+
+    #[test]
+    fn nested_inner_failures_are_labelled() {
+        let payload = ByteBuf::from(vec![0x01, 0x02]);
+        let tagged = crate::serialize::encode_nested_cbor(&payload).unwrap();
+        let err = decode_nested_cbor::<u8>(&tagged).unwrap_err();
+        assert!(err.to_string().starts_with("nested CBOR payload: "));
+        assert!(matches!(
+            err.root_cause(),
+            BinaryError::Deserialization { .. }
+        ));
+    }
+
+    /// Hands out at most `chunk` bytes per read, like a socket.
+    struct Chunked {
+        inner: io::Cursor<Vec<u8>>,
+        chunk: usize,
+    }
+
+    impl Read for Chunked {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(self.chunk);
+            self.inner.read(&mut buf[..len])
+        }
+    }
+
+    fn chunked(bytes: Vec<u8>) -> Chunked {
+        Chunked {
+            inner: io::Cursor::new(bytes),
+            chunk: 7,
+        }
+    }
+
+    #[test]
+    fn reader_decodes_large_payloads_in_chunks() {
+        // Every CBOR integer width, from 1 to 9 bytes.
+        let values: Vec<u64> = (0..100_000u64)
+            .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> (i % 64))
+            .collect();
+        let bytes = crate::serialize(&values).unwrap();
+
+        let decoded: Vec<u64> = decode_full_from_reader(chunked(bytes.clone())).unwrap();
+        assert_eq!(decoded, values);
+        let decoded: Vec<u64> = decode_from_reader(io::Cursor::new(&bytes)).unwrap();
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn reader_stops_after_each_item() {
+        let mut bytes = crate::serialize(&7u32).unwrap();
+        bytes.extend(crate::serialize(&"next").unwrap());
+        let mut reader = io::Cursor::new(bytes);
+
+        assert_eq!(decode_from_reader::<u32, _>(&mut reader).unwrap(), 7);
+        assert_eq!(reader.position(), 1);
+        assert_eq!(
+            decode_from_reader::<String, _>(&mut reader).unwrap(),
+            "next"
+        );
+    }
+
+    #[test]
+    fn full_reader_detects_trailing_garbage() {
+        let values: Vec<u64> = (0..1_000).collect();
+        let mut bytes = crate::serialize(&values).unwrap();
+        let end = bytes.len() as u64;
+        bytes.extend_from_slice(&[0xff, 0x00, 0x01]);
+
+        let err = decode_full_from_reader::<Vec<u64>, _>(chunked(bytes)).unwrap_err();
+        assert!(err.is_trailing_bytes());
+        assert_eq!(err.offset(), Some(end));
+        assert!(matches!(
+            &err,
+            BinaryError::Leftover {
+                remaining: 3,
+                preview,
+                ..
+            } if preview[..] == [0xff, 0x00, 0x01]
+        ));
+
+        // Only the preview of a long remainder is kept.
+        let mut bytes = crate::serialize(&7u8).unwrap();
+        bytes.extend(vec![0xee; 100_000]);
+        let err = decode_full_from_reader::<u8, _>(chunked(bytes)).unwrap_err();
+        assert!(matches!(
+            &err,
+            BinaryError::Leftover {
+                consumed: 1,
+                remaining: 100_000,
+                preview,
+                ..
+            } if preview.len() == LEFTOVER_PREVIEW_LEN
+        ));
+    }
+
+    #[test]
+    fn reader_failures_carry_the_offset() {
+        let values: Vec<u64> = (0..1_000).map(|i| i * 1_000).collect();
+        let bytes = crate::serialize(&values).unwrap();
+
+        let cut = bytes.len() - 2;
+        let err =
+            decode_full_from_reader::<Vec<u64>, _>(chunked(bytes[..cut].to_vec())).unwrap_err();
+        assert!(err.is_eof());
+        assert_eq!(err.offset(), Some(cut as u64));
+
+        // 999_000 takes a 0x1a header and four bytes; make it a text string
+        // of four bytes instead. Decoding stops right after that header.
+        let mut corrupt = bytes;
+        let last = corrupt.len() - 5;
+        corrupt[last] = 0x64;
+        let err = decode_full_from_reader::<Vec<u64>, _>(chunked(corrupt)).unwrap_err();
+        assert_eq!(err.kind(), crate::BinaryErrorKind::Semantic);
+        assert_eq!(err.offset(), Some(last as u64 + 1));
+    }
 }
@@ -1,22 +1,45 @@
 use std::borrow::Cow;
 use std::io;
+use std::ops::RangeInclusive;
 use thiserror::Error;
 
+/// Number of trailing bytes kept in [`BinaryError::Leftover`]'s `preview`.
+pub const LEFTOVER_PREVIEW_LEN: usize = 16;
+
 /// High-level errors produced when encoding or decoding CBOR data within
 /// the Cardano binary helpers.
+///
+/// Callers add context with [`BinaryError::context`] or
+/// [`ResultExt::context`]; the wrapped error stays reachable through
+/// [`std::error::Error::source`] and [`BinaryError::root_cause`], so retry
+/// and matching logic never has to inspect the formatted message.
 #[derive(Debug, Error)]
 pub enum BinaryError {
     #[error("CBOR serialization failed: {0}")]
     Serialization(#[from] ciborium::ser::Error<io::Error>),
 
-    #[error("CBOR deserialization failed: {0}")]
-    Deserialization(#[from] ciborium::de::Error<io::Error>),
+    /// The input is not valid CBOR for the target. `label` names the
+    /// decoded type, like the label of Haskell's
+    /// `DecoderErrorDeserialiseFailure`; it is `None` for errors converted
+    /// with `?`, which do not know it.
+    #[error("CBOR deserialization{} failed: {source}", of_label(.label.as_deref()))]
+    Deserialization {
+        label: Option<Cow<'static, str>>,
+        source: ciborium::de::Error<io::Error>,
+    },
 
-    #[error("decoding `{label}` left {leftover_len} trailing bytes")]
+    /// A data item decoded from the first `consumed` bytes, and `remaining`
+    /// more followed it. `preview` holds the first of those, at most
+    /// [`LEFTOVER_PREVIEW_LEN`], however long the remainder is.
+    #[error(
+        "decoding `{label}` left {remaining} trailing bytes after byte {consumed}: {}",
+        hex_preview(.preview, .remaining)
+    )]
     Leftover {
         label: Cow<'static, str>,
-        leftover: Vec<u8>,
-        leftover_len: usize,
+        consumed: usize,
+        remaining: usize,
+        preview: Vec<u8>,
     },
 
     #[error("nested CBOR expects tag {expected}, found {found:?}")]
@@ -27,15 +50,447 @@ pub enum BinaryError {
 
     #[error("I/O error: {0}")]
     Io(#[from] io::Error),
+
+    /// A fixed-arity CBOR array had the wrong number of elements.
+    #[error("`{type_name}` expects a CBOR array of {expected} elements, found {actual}")]
+    WrongArity {
+        type_name: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+
+    /// A canonical decoder met an indefinite-length array.
+    #[error("`{type_name}` must be encoded as a definite-length CBOR array")]
+    IndefiniteLength { type_name: &'static str },
+
+    /// A versioned envelope carried a version with no known decoder.
+    #[error(
+        "unsupported format version {found}, supported versions are {}..={}",
+        supported.start(),
+        supported.end()
+    )]
+    UnsupportedVersion {
+        found: u16,
+        supported: RangeInclusive<u16>,
+    },
+
+    /// A [`Value`](crate::Value) has no CBOR encoding, or a decoded item has
+    /// no [`Value`](crate::Value) representation.
+    #[error("CBOR value cannot be represented: {reason}")]
+    UnrepresentableValue { reason: &'static str },
+
+    /// A [`ByteKeyMap`](crate::ByteKeyMap) or [`IntKeyMap`](crate::IntKeyMap)
+    /// repeated a key. `key` is the CBOR encoding of the repeated key.
+    #[error("`{type_name}` map entry {index} repeats an earlier key")]
+    DuplicateMapKey {
+        type_name: &'static str,
+        index: usize,
+        key: Vec<u8>,
+    },
+
+    /// A canonical map decoder met a key that does not sort after the
+    /// previous one. `key` is the CBOR encoding of the offending key.
+    #[error("`{type_name}` map entry {index} is out of canonical key order")]
+    UnsortedMapKey {
+        type_name: &'static str,
+        index: usize,
+        key: Vec<u8>,
+    },
+
+    /// A canonical decoder met an unsigned integer written in more bytes
+    /// than its shortest encoding, header included.
+    #[error(
+        "unsigned integer {value} is encoded in {width} bytes, its canonical form takes {minimal}"
+    )]
+    NonMinimalInteger {
+        value: u64,
+        width: usize,
+        minimal: usize,
+    },
+
+    /// `source` annotated with what was being processed. The message lists
+    /// every label from the outermost inwards, followed by the root cause.
+    #[error("{label}: {source}")]
+    Context {
+        label: Cow<'static, str>,
+        #[source]
+        source: Box<BinaryError>,
+    },
+
+    /// `source` was raised by a reader-based decoder after `offset` bytes
+    /// had been read from the stream.
+    #[error("at byte {offset}: {source}")]
+    AtOffset {
+        offset: u64,
+        #[source]
+        source: Box<BinaryError>,
+    },
+}
+
+impl From<ciborium::de::Error<io::Error>> for BinaryError {
+    fn from(source: ciborium::de::Error<io::Error>) -> Self {
+        BinaryError::Deserialization {
+            label: None,
+            source,
+        }
+    }
+}
+
+fn of_label(label: Option<&str>) -> String {
+    label
+        .map(|label| format!(" of `{label}`"))
+        .unwrap_or_default()
+}
+
+fn hex_preview(preview: &[u8], remaining: &usize) -> String {
+    let mut hex: String = preview.iter().map(|byte| format!("{byte:02x}")).collect();
+    if *remaining > preview.len() {
+        hex.push_str("...");
+    }
+    hex
 }
 
 impl BinaryError {
-    pub(crate) fn leftover(label: impl Into<Cow<'static, str>>, leftover: Vec<u8>) -> Self {
-        let leftover_len = leftover.len();
+    /// A [`BinaryError::Deserialization`] naming the type being decoded.
+    pub(crate) fn deserialization(
+        label: impl Into<Cow<'static, str>>,
+        source: ciborium::de::Error<io::Error>,
+    ) -> Self {
+        BinaryError::Deserialization {
+            label: Some(label.into()),
+            source,
+        }
+    }
+
+    /// A [`BinaryError::Leftover`] for `input`, of which a data item took
+    /// the first `consumed` bytes.
+    pub(crate) fn leftover(
+        label: impl Into<Cow<'static, str>>,
+        input: &[u8],
+        consumed: usize,
+    ) -> Self {
+        let rest = &input[consumed..];
         BinaryError::Leftover {
             label: label.into(),
-            leftover,
-            leftover_len,
+            consumed,
+            remaining: rest.len(),
+            preview: rest[..rest.len().min(LEFTOVER_PREVIEW_LEN)].to_vec(),
         }
     }
+
+    /// Wrap `self` in a [`BinaryError::Context`] carrying `label`.
+    #[must_use]
+    pub fn context(self, label: impl Into<Cow<'static, str>>) -> Self {
+        BinaryError::Context {
+            label: label.into(),
+            source: Box::new(self),
+        }
+    }
+
+    pub(crate) fn at_offset(self, offset: u64) -> Self {
+        BinaryError::AtOffset {
+            offset,
+            source: Box::new(self),
+        }
+    }
+
+    /// The innermost error once every [`BinaryError::Context`] and
+    /// [`BinaryError::AtOffset`] layer is stripped.
+    #[must_use]
+    pub fn root_cause(&self) -> &BinaryError {
+        let mut err = self;
+        while let BinaryError::Context { source, .. } | BinaryError::AtOffset { source, .. } = err {
+            err = source;
+        }
+        err
+    }
+
+    /// Where in the input decoding failed, if known: the outermost
+    /// [`BinaryError::AtOffset`], the end of the data item for
+    /// [`BinaryError::Leftover`], or else the offset `ciborium` reports for a
+    /// syntax or semantic error.
+    #[must_use]
+    pub fn offset(&self) -> Option<u64> {
+        let mut err = self;
+        loop {
+            match err {
+                BinaryError::AtOffset { offset, .. } => return Some(*offset),
+                BinaryError::Context { source, .. } => err = source,
+                BinaryError::Leftover { consumed, .. } => return Some(*consumed as u64),
+                BinaryError::Deserialization {
+                    source:
+                        ciborium::de::Error::Syntax(offset)
+                        | ciborium::de::Error::Semantic(Some(offset), _),
+                    ..
+                } => return Some(*offset as u64),
+                _ => return None,
+            }
+        }
+    }
+
+    /// Whether decoding stopped because the input ended early.
+    #[must_use]
+    pub fn is_eof(&self) -> bool {
+        match self.root_cause() {
+            BinaryError::Deserialization {
+                source: ciborium::de::Error::Io(err),
+                ..
+            }
+            | BinaryError::Io(err) => err.kind() == io::ErrorKind::UnexpectedEof,
+            _ => false,
+        }
+    }
+
+    /// Backend-independent classification of the root cause.
+    #[must_use]
+    pub fn kind(&self) -> BinaryErrorKind {
+        if self.is_eof() {
+            return BinaryErrorKind::Eof;
+        }
+        match self.root_cause() {
+            BinaryError::Serialization(_) => BinaryErrorKind::Encode,
+            BinaryError::Deserialization { source, .. } => match source {
+                ciborium::de::Error::Io(_) => BinaryErrorKind::Io,
+                ciborium::de::Error::Syntax(_) => BinaryErrorKind::Syntax,
+                ciborium::de::Error::Semantic(..) => BinaryErrorKind::Semantic,
+                ciborium::de::Error::RecursionLimitExceeded => BinaryErrorKind::RecursionLimit,
+            },
+            BinaryError::Leftover { .. } => BinaryErrorKind::TrailingBytes,
+            BinaryError::NestedTag { .. } | BinaryError::NestedPayload => BinaryErrorKind::Nested,
+            BinaryError::Io(_) => BinaryErrorKind::Io,
+            BinaryError::WrongArity { .. } | BinaryError::IndefiniteLength { .. } => {
+                BinaryErrorKind::Arity
+            },
+            BinaryError::UnsupportedVersion { .. } => BinaryErrorKind::UnsupportedVersion,
+            BinaryError::UnrepresentableValue { .. } => BinaryErrorKind::UnrepresentableValue,
+            BinaryError::DuplicateMapKey { .. } | BinaryError::UnsortedMapKey { .. } => {
+                BinaryErrorKind::MapKey
+            },
+            BinaryError::NonMinimalInteger { .. } => BinaryErrorKind::NonCanonical,
+            BinaryError::Context { .. } | BinaryError::AtOffset { .. } => {
+                unreachable!("root_cause strips context")
+            },
+        }
+    }
+
+    /// Whether a value decoded successfully but was followed by unconsumed
+    /// bytes ([`BinaryError::Leftover`]).
+    #[must_use]
+    pub fn is_trailing_bytes(&self) -> bool {
+        matches!(self.root_cause(), BinaryError::Leftover { .. })
+    }
+}
+
+/// What went wrong, independent of the CBOR backend.
+///
+/// [`BinaryError::Serialization`] and [`BinaryError::Deserialization`] wrap
+/// the backend's own error types; match on [`BinaryError::kind`] instead so
+/// that code keeps compiling if the backend changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum BinaryErrorKind {
+    /// Encoding a value failed.
+    Encode,
+    /// The input ended before the data item did.
+    Eof,
+    /// The input is not well-formed CBOR.
+    Syntax,
+    /// Well-formed CBOR that does not match the requested type.
+    Semantic,
+    /// Nesting exceeded the decoder's recursion limit.
+    RecursionLimit,
+    /// A value decoded but bytes were left over ([`BinaryError::Leftover`]).
+    TrailingBytes,
+    /// Nested CBOR had the wrong tag or payload.
+    Nested,
+    /// A fixed-arity array had the wrong length or was indefinite.
+    Arity,
+    /// A versioned envelope carried an unknown version.
+    UnsupportedVersion,
+    /// See [`BinaryError::UnrepresentableValue`].
+    UnrepresentableValue,
+    /// A map repeated a key or broke the required key order.
+    MapKey,
+    /// An integer was written in more bytes than canonical CBOR allows.
+    NonCanonical,
+    /// Reading or writing failed for a reason other than end of input.
+    Io,
+}
+
+/// Attach a context label to the error of a `Result<T, BinaryError>`.
+///
+/// ```rust
+/// use cardano_binary::{ResultExt, decode_full};
+///
+/// let err = decode_full::<u64>(&[0x18])
+///     .context("block header")
+///     .context("block 42")
+///     .unwrap_err();
+/// assert!(err.to_string().starts_with("block 42: block header: "));
+/// assert!(err.is_eof());
+/// ```
+pub trait ResultExt<T> {
+    /// Wrap the error, if any, in a [`BinaryError::Context`] carrying `label`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the original error wrapped with `label`.
+    fn context(self, label: impl Into<Cow<'static, str>>) -> Result<T, BinaryError>;
+}
+
+impl<T> ResultExt<T> for Result<T, BinaryError> {
+    fn context(self, label: impl Into<Cow<'static, str>>) -> Result<T, BinaryError> {
+        self.map_err(|err| err.context(label))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+
+    use super::*;
+    use crate::decode_full;
+
+    fn wrapped_leftover() -> BinaryError {
+        Err::<(), _>(BinaryError::leftover("u8", &[0x07, 0x00], 1))
+            .context("inner")
+            .context("outer")
+            .unwrap_err()
+    }
+
+    #[test]
+    fn display_lists_labels_outermost_first() {
+        assert_eq!(
+            wrapped_leftover().to_string(),
+            "outer: inner: decoding `u8` left 1 trailing bytes after byte 1: 00"
+        );
+    }
+
+    #[test]
+    fn source_walks_every_layer() {
+        let err = wrapped_leftover();
+        let inner = err.source().expect("outer source");
+        assert_eq!(
+            inner.to_string(),
+            "inner: decoding `u8` left 1 trailing bytes after byte 1: 00"
+        );
+        let root = inner.source().expect("inner source");
+        assert_eq!(
+            root.to_string(),
+            "decoding `u8` left 1 trailing bytes after byte 1: 00"
+        );
+        assert!(root.source().is_none());
+        assert!(matches!(err.root_cause(), BinaryError::Leftover { .. }));
+    }
+
+    #[test]
+    fn classification_sees_through_context() {
+        let trailing = wrapped_leftover();
+        assert!(trailing.is_trailing_bytes());
+        assert!(!trailing.is_eof());
+
+        // 0x18 announces a one-byte unsigned integer that never arrives.
+        let eof = decode_full::<u64>(&[0x18])
+            .context("field")
+            .context("record")
+            .unwrap_err();
+        assert!(eof.is_eof());
+        assert!(!eof.is_trailing_bytes());
+
+        let io = BinaryError::from(io::Error::from(io::ErrorKind::UnexpectedEof)).context("read");
+        assert!(io.is_eof());
+        assert!(!BinaryError::NestedPayload.context("x").is_eof());
+    }
+
+    #[test]
+    fn kind_classifies_the_root_cause() {
+        assert_eq!(wrapped_leftover().kind(), BinaryErrorKind::TrailingBytes);
+        assert_eq!(
+            decode_full::<u64>(&[0x18]).context("x").unwrap_err().kind(),
+            BinaryErrorKind::Eof
+        );
+        // 0x1c is a reserved additional-information value.
+        assert_eq!(
+            decode_full::<u64>(&[0x1c]).unwrap_err().kind(),
+            BinaryErrorKind::Syntax
+        );
+        assert_eq!(
+            decode_full::<u64>(&[0x60]).unwrap_err().kind(),
+            BinaryErrorKind::Semantic
+        );
+        assert_eq!(
+            BinaryError::NestedPayload.context("x").kind(),
+            BinaryErrorKind::Nested
+        );
+        assert_eq!(
+            BinaryError::from(io::Error::from(io::ErrorKind::BrokenPipe)).kind(),
+            BinaryErrorKind::Io
+        );
+    }
+
+    #[test]
+    fn offset_prefers_the_outermost_position() {
+        let err = wrapped_leftover().at_offset(12).context("snapshot");
+        assert_eq!(err.offset(), Some(12));
+        assert_eq!(
+            err.to_string(),
+            "snapshot: at byte 12: outer: inner: decoding `u8` left 1 trailing bytes after byte 1: 00"
+        );
+        assert_eq!(err.kind(), BinaryErrorKind::TrailingBytes);
+        assert_eq!(wrapped_leftover().offset(), Some(1));
+
+        // [1, <reserved>]: ciborium reports where the bad header starts.
+        let syntax = decode_full::<Vec<u64>>(&[0x82, 0x01, 0x1c]).unwrap_err();
+        assert_eq!(syntax.kind(), BinaryErrorKind::Syntax);
+        assert_eq!(syntax.offset(), Some(2));
+        assert_eq!(BinaryError::NestedPayload.offset(), None);
+    }
+
+    #[test]
+    fn leftover_preview_is_capped() {
+        let mut input = vec![0x01];
+        input.extend((0..1_000_000u32).map(|i| i as u8));
+        let err = decode_full::<u8>(&input).unwrap_err();
+        let (consumed, remaining, preview) = match &err {
+            BinaryError::Leftover {
+                consumed,
+                remaining,
+                preview,
+                ..
+            } => Ok((*consumed, *remaining, preview)),
+            _ => Err(()),
+        }
+        .expect("expected leftover error");
+        assert_eq!((consumed, remaining), (1, 1_000_000));
+        assert_eq!(preview.len(), LEFTOVER_PREVIEW_LEN);
+        assert_eq!(preview[..], input[1..=LEFTOVER_PREVIEW_LEN]);
+        assert!(err.to_string().ends_with(
+            "left 1000000 trailing bytes after byte 1: 000102030405060708090a0b0c0d0e0f..."
+        ));
+
+        // A short remainder is shown whole, without an ellipsis.
+        let err = decode_full::<u8>(&[0x01, 0xab, 0xcd]).unwrap_err();
+        assert!(
+            err.to_string()
+                .ends_with("left 2 trailing bytes after byte 1: abcd")
+        );
+    }
+
+    #[test]
+    fn deserialization_names_the_target_type() {
+        let err = decode_full::<Vec<u64>>(&[0x61, b'a']).unwrap_err();
+        assert!(matches!(
+            &err,
+            BinaryError::Deserialization { label: Some(label), .. }
+                if label == "alloc::vec::Vec<u64>"
+        ));
+        assert!(
+            err.to_string()
+                .starts_with("CBOR deserialization of `alloc::vec::Vec<u64>` failed: ")
+        );
+
+        // Errors converted with `?` have no label to report.
+        let err = BinaryError::from(ciborium::de::Error::<io::Error>::RecursionLimitExceeded);
+        assert!(err.to_string().starts_with("CBOR deserialization failed: "));
+    }
 }
@@ -0,0 +1,515 @@
+#![cfg_attr(test, allow(clippy::unwrap_used))]
+
+use crate::error::{BinaryError, ResultExt};
+use ciborium_ll::{Decoder, Encoder, Header};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::any::type_name;
+use std::io::Cursor;
+
+/// CBOR "break" stop code terminating an indefinite-length item.
+const BREAK: u8 = 0xff;
+
+/// Decode a CBOR array of exactly `N` elements into `[T; N]`.
+///
+/// Definite- and indefinite-length arrays are accepted alike; use
+/// [`decode_fixed_array_canonical`] to require the definite form.
+///
+/// ```rust
+/// use cardano_binary::{BinaryError, decode_fixed_array, encode_fixed_array};
+///
+/// let bytes = encode_fixed_array(&[1u64, 2, 3]).unwrap();
+/// assert_eq!(decode_fixed_array::<u64, 3>(&bytes).unwrap(), [1, 2, 3]);
+///
+/// let err = decode_fixed_array::<u64, 4>(&bytes).unwrap_err();
+/// assert!(matches!(err, BinaryError::WrongArity { expected: 4, actual: 3, .. }));
+/// ```
+///
+/// # Errors
+///
+/// Returns [`BinaryError::WrongArity`] if the array has a different number of
+/// elements, [`BinaryError::Deserialization`] if the input is not an array or
+/// an element fails to decode (wrapped in a [`BinaryError::Context`] naming
+/// the element), and [`BinaryError::Leftover`] if bytes follow the array.
+pub fn decode_fixed_array<T: DeserializeOwned, const N: usize>(
+    bytes: &[u8],
+) -> Result<[T; N], BinaryError> {
+    decode_array_with(bytes, false)
+}
+
+/// [`decode_fixed_array`] that additionally rejects indefinite-length arrays.
+///
+/// Only the array header is checked; elements are decoded as usual.
+///
+/// # Errors
+///
+/// Returns [`BinaryError::IndefiniteLength`] for an indefinite-length array,
+/// otherwise the same errors as [`decode_fixed_array`].
+pub fn decode_fixed_array_canonical<T: DeserializeOwned, const N: usize>(
+    bytes: &[u8],
+) -> Result<[T; N], BinaryError> {
+    decode_array_with(bytes, true)
+}
+
+/// Decode a CBOR 2-array into `(A, B)`.
+///
+/// # Errors
+///
+/// Returns the same errors as [`decode_fixed_array`].
+pub fn decode_pair<A, B>(bytes: &[u8]) -> Result<(A, B), BinaryError>
+where
+    A: DeserializeOwned,
+    B: DeserializeOwned,
+{
+    decode_pair_with(bytes, false)
+}
+
+/// [`decode_pair`] that additionally rejects indefinite-length arrays.
+///
+/// # Errors
+///
+/// Returns the same errors as [`decode_fixed_array_canonical`].
+pub fn decode_pair_canonical<A, B>(bytes: &[u8]) -> Result<(A, B), BinaryError>
+where
+    A: DeserializeOwned,
+    B: DeserializeOwned,
+{
+    decode_pair_with(bytes, true)
+}
+
+/// Decode a CBOR 3-array into `(A, B, C)`.
+///
+/// # Errors
+///
+/// Returns the same errors as [`decode_fixed_array`].
+pub fn decode_triple<A, B, C>(bytes: &[u8]) -> Result<(A, B, C), BinaryError>
+where
+    A: DeserializeOwned,
+    B: DeserializeOwned,
+    C: DeserializeOwned,
+{
+    decode_triple_with(bytes, false)
+}
+
+/// [`decode_triple`] that additionally rejects indefinite-length arrays.
+///
+/// # Errors
+///
+/// Returns the same errors as [`decode_fixed_array_canonical`].
+pub fn decode_triple_canonical<A, B, C>(bytes: &[u8]) -> Result<(A, B, C), BinaryError>
+where
+    A: DeserializeOwned,
+    B: DeserializeOwned,
+    C: DeserializeOwned,
+{
+    decode_triple_with(bytes, true)
+}
+
+/// Encode `items` as a definite-length CBOR array of `N` elements.
+///
+/// # Errors
+///
+/// Returns [`BinaryError::Serialization`] if an element cannot be serialised.
+pub fn encode_fixed_array<T: Serialize, const N: usize>(
+    items: &[T; N],
+) -> Result<Vec<u8>, BinaryError> {
+    let mut buf = array_header(N)?;
+    for item in items {
+        ciborium::into_writer(item, &mut buf)?;
+    }
+    Ok(buf)
+}
+
+/// Encode `(a, b)` as a definite-length CBOR 2-array.
+///
+/// # Errors
+///
+/// Returns [`BinaryError::Serialization`] if an element cannot be serialised.
+pub fn encode_pair<A: Serialize, B: Serialize>(a: &A, b: &B) -> Result<Vec<u8>, BinaryError> {
+    let mut buf = array_header(2)?;
+    ciborium::into_writer(a, &mut buf)?;
+    ciborium::into_writer(b, &mut buf)?;
+    Ok(buf)
+}
+
+/// Encode `(a, b, c)` as a definite-length CBOR 3-array.
+///
+/// # Errors
+///
+/// Returns [`BinaryError::Serialization`] if an element cannot be serialised.
+pub fn encode_triple<A, B, C>(a: &A, b: &B, c: &C) -> Result<Vec<u8>, BinaryError>
+where
+    A: Serialize,
+    B: Serialize,
+    C: Serialize,
+{
+    let mut buf = array_header(3)?;
+    ciborium::into_writer(a, &mut buf)?;
+    ciborium::into_writer(b, &mut buf)?;
+    ciborium::into_writer(c, &mut buf)?;
+    Ok(buf)
+}
+
+pub(crate) fn array_header(len: usize) -> Result<Vec<u8>, BinaryError> {
+    let mut buf = Vec::new();
+    Encoder::from(&mut buf).push(Header::Array(Some(len)))?;
+    Ok(buf)
+}
+
+fn decode_array_with<T: DeserializeOwned, const N: usize>(
+    bytes: &[u8],
+    canonical: bool,
+) -> Result<[T; N], BinaryError> {
+    let mut reader = ArrayReader::open(bytes, type_name::<[T; N]>(), N, canonical)?;
+    let mut items = Vec::with_capacity(N);
+    for _ in 0..N {
+        items.push(reader.element()?);
+    }
+    reader.finish()?;
+    items
+        .try_into()
+        .map_err(|items: Vec<T>| BinaryError::WrongArity {
+            type_name: type_name::<[T; N]>(),
+            expected: N,
+            actual: items.len(),
+        })
+}
+
+fn decode_pair_with<A, B>(bytes: &[u8], canonical: bool) -> Result<(A, B), BinaryError>
+where
+    A: DeserializeOwned,
+    B: DeserializeOwned,
+{
+    let mut reader = ArrayReader::open(bytes, type_name::<(A, B)>(), 2, canonical)?;
+    let pair = (reader.element()?, reader.element()?);
+    reader.finish()?;
+    Ok(pair)
+}
+
+fn decode_triple_with<A, B, C>(bytes: &[u8], canonical: bool) -> Result<(A, B, C), BinaryError>
+where
+    A: DeserializeOwned,
+    B: DeserializeOwned,
+    C: DeserializeOwned,
+{
+    let mut reader = ArrayReader::open(bytes, type_name::<(A, B, C)>(), 3, canonical)?;
+    let triple = (reader.element()?, reader.element()?, reader.element()?);
+    reader.finish()?;
+    Ok(triple)
+}
+
+/// Element-by-element reader over a CBOR array of known arity.
+///
+/// A definite-length header is checked against the arity up front. For an
+/// indefinite-length array the elements are counted as they are read, and
+/// any surplus is skipped so the error can report the actual length.
+struct ArrayReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    type_name: &'static str,
+    expected: usize,
+    read: usize,
+    indefinite: bool,
+}
+
+impl<'a> ArrayReader<'a> {
+    fn open(
+        bytes: &'a [u8],
+        type_name: &'static str,
+        expected: usize,
+        canonical: bool,
+    ) -> Result<Self, BinaryError> {
+        let mut decoder = Decoder::from(Cursor::new(bytes));
+        let header = decoder.pull().map_err(ciborium::de::Error::from)?;
+        let indefinite = match header {
+            Header::Array(Some(len)) if len == expected => false,
+            Header::Array(Some(len)) => {
+                return Err(BinaryError::WrongArity {
+                    type_name,
+                    expected,
+                    actual: len,
+                });
+            },
+            Header::Array(None) if canonical => {
+                return Err(BinaryError::IndefiniteLength { type_name });
+            },
+            Header::Array(None) => true,
+            other => {
+                return Err(ciborium::de::Error::Semantic(
+                    Some(0),
+                    format!("`{type_name}` expects a CBOR array, found {other:?}"),
+                )
+                .into());
+            },
+        };
+        Ok(Self {
+            bytes,
+            position: decoder.offset(),
+            type_name,
+            expected,
+            read: 0,
+            indefinite,
+        })
+    }
+
+    fn at_break(&self) -> bool {
+        self.indefinite && self.bytes.get(self.position) == Some(&BREAK)
+    }
+
+    fn decode<T: DeserializeOwned>(&mut self) -> Result<T, ciborium::de::Error<std::io::Error>> {
+        let mut cursor = Cursor::new(&self.bytes[self.position..]);
+        let value = ciborium::from_reader(&mut cursor)?;
+        self.position += cursor.position() as usize;
+        Ok(value)
+    }
+
+    fn element<T: DeserializeOwned>(&mut self) -> Result<T, BinaryError> {
+        if self.at_break() {
+            return Err(BinaryError::WrongArity {
+                type_name: self.type_name,
+                expected: self.expected,
+                actual: self.read,
+            });
+        }
+        let index = self.read;
+        let value = self
+            .decode()
+            .map_err(BinaryError::from)
+            .context(format!("element {index} of `{}`", self.type_name))?;
+        self.read += 1;
+        Ok(value)
+    }
+
+    fn finish(mut self) -> Result<(), BinaryError> {
+        if self.indefinite {
+            while !self.at_break() {
+                self.decode::<ciborium::value::Value>()?;
+                self.read += 1;
+            }
+            self.position += 1;
+            if self.read != self.expected {
+                return Err(BinaryError::WrongArity {
+                    type_name: self.type_name,
+                    expected: self.expected,
+                    actual: self.read,
+                });
+            }
+        }
+        if self.position < self.bytes.len() {
+            return Err(BinaryError::leftover(
+                self.type_name,
+                self.bytes,
+                self.position,
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serialize::serialize;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    struct Version {
+        major: u64,
+        minor: u64,
+    }
+
+    fn versions() -> [Version; 2] {
+        [
+            Version { major: 9, minor: 0 },
+            Version {
+                major: 10,
+                minor: 1,
+            },
+        ]
+    }
+
+    /// `[_ 1, 2, 3]` with an indefinite-length header.
+    const INDEFINITE_U64: [u8; 5] = [0x9f, 0x01, 0x02, 0x03, 0xff];
+
+    #[test]
+    fn decodes_u64_arrays_and_tuples() {
+        let bytes = encode_fixed_array(&[1u64, 2, 3]).unwrap();
+        assert_eq!(bytes, serialize(&[1u64, 2, 3]).unwrap());
+        assert_eq!(decode_fixed_array::<u64, 3>(&bytes).unwrap(), [1, 2, 3]);
+        assert_eq!(decode_triple::<u64, u64, u64>(&bytes).unwrap(), (1, 2, 3));
+
+        let pair = encode_pair(&7u64, &"seven").unwrap();
+        assert_eq!(pair, serialize(&(7u64, "seven")).unwrap());
+        assert_eq!(
+            decode_pair::<u64, String>(&pair).unwrap(),
+            (7, "seven".to_owned())
+        );
+    }
+
+    #[test]
+    fn decodes_nested_structs() {
+        let bytes = encode_fixed_array(&versions()).unwrap();
+        assert_eq!(bytes[0], 0x82);
+        assert_eq!(
+            decode_fixed_array::<Version, 2>(&bytes).unwrap(),
+            versions()
+        );
+
+        let [a, b] = versions();
+        let bytes = encode_triple(&a, &b, &[1u64, 2]).unwrap();
+        assert_eq!(
+            decode_triple::<Version, Version, [u64; 2]>(&bytes).unwrap(),
+            (a, b, [1, 2])
+        );
+    }
+
+    #[test]
+    fn too_few_elements_report_the_arity() {
+        let bytes = encode_fixed_array(&[1u64, 2]).unwrap();
+        let err = decode_fixed_array::<u64, 3>(&bytes).unwrap_err();
+        assert!(matches!(
+            err,
+            BinaryError::WrongArity {
+                type_name: "[u64; 3]",
+                expected: 3,
+                actual: 2,
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "`[u64; 3]` expects a CBOR array of 3 elements, found 2"
+        );
+
+        let bytes = encode_fixed_array(&versions()).unwrap();
+        let err = decode_triple::<Version, Version, Version>(&bytes).unwrap_err();
+        assert!(matches!(
+            err,
+            BinaryError::WrongArity {
+                expected: 3,
+                actual: 2,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn too_many_elements_report_the_arity() {
+        let bytes = encode_fixed_array(&[1u64, 2, 3]).unwrap();
+        let err = decode_pair::<u64, u64>(&bytes).unwrap_err();
+        assert!(matches!(
+            err,
+            BinaryError::WrongArity {
+                expected: 2,
+                actual: 3,
+                ..
+            }
+        ));
+
+        let mut three = versions().to_vec();
+        three.push(Version { major: 1, minor: 0 });
+        let bytes = serialize(&three).unwrap();
+        let err = decode_fixed_array::<Version, 2>(&bytes).unwrap_err();
+        assert!(matches!(
+            err,
+            BinaryError::WrongArity {
+                expected: 2,
+                actual: 3,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn indefinite_arrays_decode_like_definite_ones() {
+        assert_eq!(
+            decode_fixed_array::<u64, 3>(&INDEFINITE_U64).unwrap(),
+            [1, 2, 3]
+        );
+        assert_eq!(
+            decode_triple::<u64, u64, u64>(&INDEFINITE_U64).unwrap(),
+            (1, 2, 3)
+        );
+
+        let short = decode_fixed_array::<u64, 4>(&INDEFINITE_U64).unwrap_err();
+        assert!(matches!(
+            short,
+            BinaryError::WrongArity {
+                expected: 4,
+                actual: 3,
+                ..
+            }
+        ));
+        let long = decode_pair::<u64, u64>(&INDEFINITE_U64).unwrap_err();
+        assert!(matches!(
+            long,
+            BinaryError::WrongArity {
+                expected: 2,
+                actual: 3,
+                ..
+            }
+        ));
+
+        let mut nested = vec![0x9f];
+        for version in versions() {
+            nested.extend(serialize(&version).unwrap());
+        }
+        nested.push(BREAK);
+        assert_eq!(
+            decode_fixed_array::<Version, 2>(&nested).unwrap(),
+            versions()
+        );
+    }
+
+    #[test]
+    fn canonical_mode_rejects_indefinite_arrays() {
+        let err = decode_fixed_array_canonical::<u64, 3>(&INDEFINITE_U64).unwrap_err();
+        assert!(matches!(
+            err,
+            BinaryError::IndefiniteLength {
+                type_name: "[u64; 3]"
+            }
+        ));
+        assert!(matches!(
+            decode_triple_canonical::<u64, u64, u64>(&INDEFINITE_U64),
+            Err(BinaryError::IndefiniteLength { .. })
+        ));
+
+        let definite = encode_pair(&1u64, &2u64).unwrap();
+        assert_eq!(
+            decode_pair_canonical::<u64, u64>(&definite).unwrap(),
+            (1, 2)
+        );
+    }
+
+    #[test]
+    fn element_errors_name_the_element() {
+        let bytes = encode_pair(&1u64, &"two").unwrap();
+        let err = decode_pair::<u64, u64>(&bytes).unwrap_err();
+        assert!(err.to_string().starts_with("element 1 of `(u64, u64)`: "));
+        assert!(matches!(
+            err.root_cause(),
+            BinaryError::Deserialization { .. }
+        ));
+    }
+
+    #[test]
+    fn rejects_non_arrays_and_trailing_bytes() {
+        let err = decode_pair::<u64, u64>(&serialize(&5u64).unwrap()).unwrap_err();
+        assert!(matches!(err, BinaryError::Deserialization { .. }));
+
+        let mut bytes = encode_pair(&1u64, &2u64).unwrap();
+        bytes.push(0x00);
+        assert!(
+            decode_pair::<u64, u64>(&bytes)
+                .unwrap_err()
+                .is_trailing_bytes()
+        );
+
+        let unterminated = &INDEFINITE_U64[..4];
+        assert!(
+            decode_fixed_array::<u64, 3>(unterminated)
+                .unwrap_err()
+                .is_eof()
+        );
+    }
+}
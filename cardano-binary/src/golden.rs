@@ -0,0 +1,366 @@
+//! Golden-file assertions for CBOR encodings (`test-util` feature).
+//!
+//! [`assert_golden_cbor`] serialises a value and compares it with bytes stored
+//! on disk next to a `.diag` file holding the diagnostic-notation rendering
+//! (RFC 8949 §8). Reviewers diff the `.diag` files, and a failing assertion
+//! reports the path of the first structural difference instead of two hex
+//! dumps.
+//!
+//! Golden files are written when they do not exist yet or when the
+//! `UPDATE_GOLDEN` environment variable is `1` or `true`; any other value,
+//! including `0` and the empty string, leaves them alone. Under CI (detected through
+//! the usual `CI`, `GITHUB_ACTIONS`, … variables) updates are refused, so a
+//! format change cannot be accepted silently; `UPDATE_GOLDEN=force`
+//! overrides the check.
+//!
+//! ```rust,no_run
+//! use cardano_binary::golden::assert_golden_cbor;
+//!
+//! assert_golden_cbor(&(1u8, "one"), "tests/golden/pair.cbor");
+//! ```
+
+#![allow(clippy::panic)]
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use ciborium::value::Value;
+use serde::Serialize;
+
+use crate::{BinaryError, decode_full, serialize};
+
+/// Environment variable requesting golden files to be rewritten.
+pub const UPDATE_GOLDEN_ENV: &str = "UPDATE_GOLDEN";
+
+const CI_ENV_VARS: &[&str] = &[
+    "CI",
+    "GITHUB_ACTIONS",
+    "GITLAB_CI",
+    "BUILDKITE",
+    "CIRCLECI",
+    "TRAVIS",
+    "JENKINS_URL",
+    "TF_BUILD",
+];
+
+/// Render CBOR bytes in diagnostic notation, e.g. `[1, h'00ff', {"a": true}]`.
+///
+/// The rendering reflects the decoded data model, so two encodings that
+/// differ only in integer width or definite/indefinite lengths render the
+/// same.
+///
+/// # Errors
+///
+/// Returns an error if `bytes` is not a single well-formed CBOR item.
+pub fn diagnostic(bytes: &[u8]) -> Result<String, BinaryError> {
+    let value: Value = decode_full(bytes)?;
+    let mut out = String::new();
+    render(&value, &mut out);
+    Ok(out)
+}
+
+/// Describe the first structural difference between two CBOR encodings.
+///
+/// Returns `None` when the bytes are identical. Otherwise the message names
+/// the path to the first differing item (`$[2]["name"]`, …) with both
+/// sides in diagnostic notation, falling back to the first differing byte
+/// offset when the structures match or either side fails to decode.
+#[must_use]
+pub fn structural_diff(expected: &[u8], actual: &[u8]) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+    let decoded = decode_full::<Value>(expected).and_then(|e| Ok((e, decode_full(actual)?)));
+    if let Ok((expected_value, actual_value)) = decoded {
+        let mut path = String::from("$");
+        if let Some((expected_item, actual_item)) =
+            first_difference(&expected_value, &actual_value, &mut path)
+        {
+            return Some(format!(
+                "at {path}: expected {}, got {}",
+                to_diag(expected_item),
+                to_diag(actual_item)
+            ));
+        }
+    }
+    let offset = expected
+        .iter()
+        .zip(actual)
+        .position(|(e, a)| e != a)
+        .unwrap_or_else(|| expected.len().min(actual.len()));
+    Some(format!(
+        "encodings differ at byte offset {offset} (expected {} bytes, got {})",
+        expected.len(),
+        actual.len()
+    ))
+}
+
+/// Compare the CBOR encoding of `value` with the golden file at `path`.
+///
+/// `path` is resolved against the working directory, which `cargo test` sets
+/// to the package root. The diagnostic rendering lives beside it with a
+/// `.diag` extension.
+///
+/// # Panics
+///
+/// Panics if `value` cannot be serialised, the golden file cannot be read or
+/// written, the encoding differs from the golden file, or an update is
+/// requested under CI without `UPDATE_GOLDEN=force`.
+pub fn assert_golden_cbor<T: Serialize>(value: &T, path: &str) {
+    let bytes =
+        serialize(value).unwrap_or_else(|err| panic!("{path}: serialization failed: {err}"));
+    let cbor_path = Path::new(path);
+    let diag_path = cbor_path.with_extension("diag");
+
+    let update = update_request(std::env::var(UPDATE_GOLDEN_ENV).ok().as_deref());
+    let forced = update == Update::Forced;
+    let existing = fs::read(cbor_path).ok();
+
+    if existing.is_none() || update != Update::No {
+        if existing.as_deref() == Some(bytes.as_slice()) {
+            return;
+        }
+        if running_in_ci() && !forced {
+            panic!(
+                "{path}: refusing to {} golden file under CI; run locally or set \
+                 {UPDATE_GOLDEN_ENV}=force",
+                if existing.is_some() {
+                    "update"
+                } else {
+                    "create"
+                }
+            );
+        }
+        write_golden(cbor_path, &diag_path, &bytes);
+        return;
+    }
+
+    let expected = existing.unwrap_or_default();
+    if let Some(difference) = structural_diff(&expected, &bytes) {
+        panic!(
+            "{path}: CBOR encoding changed {difference}\nrun with {UPDATE_GOLDEN_ENV}=1 to accept \
+             the new encoding and review {}",
+            diag_path.display()
+        );
+    }
+}
+
+/// What the value of `UPDATE_GOLDEN` asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Update {
+    No,
+    Yes,
+    Forced,
+}
+
+fn update_request(value: Option<&str>) -> Update {
+    match value {
+        Some("1" | "true") => Update::Yes,
+        Some("force") => Update::Forced,
+        _ => Update::No,
+    }
+}
+
+fn running_in_ci() -> bool {
+    CI_ENV_VARS
+        .iter()
+        .any(|name| std::env::var(name).is_ok_and(|value| !value.is_empty() && value != "false"))
+}
+
+fn write_golden(cbor_path: &Path, diag_path: &Path, bytes: &[u8]) {
+    if let Some(parent) = cbor_path.parent() {
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|err| panic!("{}: cannot create directory: {err}", parent.display()));
+    }
+    let mut diag = diagnostic(bytes).unwrap_or_else(|err| format!("<undecodable: {err}>"));
+    diag.push('\n');
+    fs::write(cbor_path, bytes)
+        .unwrap_or_else(|err| panic!("{}: cannot write: {err}", cbor_path.display()));
+    fs::write(diag_path, diag)
+        .unwrap_or_else(|err| panic!("{}: cannot write: {err}", diag_path.display()));
+}
+
+fn first_difference<'a>(
+    expected: &'a Value,
+    actual: &'a Value,
+    path: &mut String,
+) -> Option<(&'a Value, &'a Value)> {
+    match (expected, actual) {
+        (Value::Array(e), Value::Array(a)) if e.len() == a.len() => {
+            e.iter().zip(a).enumerate().find_map(|(index, (e, a))| {
+                let len = path.len();
+                let _ = write!(path, "[{index}]");
+                let found = first_difference(e, a, path);
+                if found.is_none() {
+                    path.truncate(len);
+                }
+                found
+            })
+        },
+        (Value::Map(e), Value::Map(a)) if e.len() == a.len() => e
+            .iter()
+            .zip(a)
+            .enumerate()
+            .find_map(|(index, ((ek, ev), (ak, av)))| {
+                if ek != ak {
+                    let _ = write!(path, "{{key {index}}}");
+                    return Some((ek, ak));
+                }
+                let len = path.len();
+                let _ = write!(path, "[{}]", to_diag(ek));
+                let found = first_difference(ev, av, path);
+                if found.is_none() {
+                    path.truncate(len);
+                }
+                found
+            }),
+        (Value::Tag(et, e), Value::Tag(at, a)) if et == at => {
+            let len = path.len();
+            let _ = write!(path, "<{et}>");
+            let found = first_difference(e, a, path);
+            if found.is_none() {
+                path.truncate(len);
+            }
+            found
+        },
+        _ if expected == actual => None,
+        _ => Some((expected, actual)),
+    }
+}
+
+fn to_diag(value: &Value) -> String {
+    let mut out = String::new();
+    render(value, &mut out);
+    out
+}
+
+fn render(value: &Value, out: &mut String) {
+    match value {
+        Value::Integer(int) => {
+            let _ = write!(out, "{}", i128::from(*int));
+        },
+        Value::Bytes(bytes) => {
+            out.push_str("h'");
+            for byte in bytes {
+                let _ = write!(out, "{byte:02x}");
+            }
+            out.push('\'');
+        },
+        Value::Float(float) if float.is_nan() => out.push_str("NaN"),
+        Value::Float(float) if float.is_infinite() => {
+            out.push_str(if *float > 0.0 {
+                "Infinity"
+            } else {
+                "-Infinity"
+            });
+        },
+        Value::Float(float) => {
+            let _ = write!(out, "{float:?}");
+        },
+        Value::Text(text) => {
+            let _ = write!(out, "{text:?}");
+        },
+        Value::Bool(flag) => {
+            let _ = write!(out, "{flag}");
+        },
+        Value::Null => out.push_str("null"),
+        Value::Tag(tag, inner) => {
+            let _ = write!(out, "{tag}(");
+            render(inner, out);
+            out.push(')');
+        },
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                render(item, out);
+            }
+            out.push(']');
+        },
+        Value::Map(entries) => {
+            out.push('{');
+            for (index, (key, item)) in entries.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                render(key, out);
+                out.push_str(": ");
+                render(item, out);
+            }
+            out.push('}');
+        },
+        _ => out.push_str("undefined"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagnostic_renders_nested_items() {
+        let bytes = serialize(&(
+            1u8,
+            serde_bytes::Bytes::new(&[0, 255]),
+            "a",
+            -2i8,
+            None::<u8>,
+        ))
+        .unwrap();
+        assert_eq!(
+            diagnostic(&bytes).unwrap(),
+            r#"[1, h'00ff', "a", -2, null]"#
+        );
+    }
+
+    #[test]
+    fn only_explicit_values_request_an_update() {
+        assert_eq!(update_request(Some("1")), Update::Yes);
+        assert_eq!(update_request(Some("true")), Update::Yes);
+        assert_eq!(update_request(Some("force")), Update::Forced);
+        for value in [None, Some(""), Some("0"), Some("false"), Some("yes")] {
+            assert_eq!(update_request(value), Update::No, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn structural_diff_reports_path() {
+        let expected = serialize(&(1u8, vec![10u8, 20, 30])).unwrap();
+        let actual = serialize(&(1u8, vec![10u8, 21, 30])).unwrap();
+        assert_eq!(
+            structural_diff(&expected, &actual).unwrap(),
+            "at $[1][1]: expected 20, got 21"
+        );
+        assert!(structural_diff(&expected, &expected).is_none());
+    }
+
+    #[test]
+    fn structural_diff_falls_back_to_offsets() {
+        // 1 encoded minimally and with a one-byte argument.
+        let diff = structural_diff(&[0x01], &[0x18, 0x01]).unwrap();
+        assert_eq!(
+            diff,
+            "encodings differ at byte offset 0 (expected 1 bytes, got 2)"
+        );
+    }
+
+    #[test]
+    fn mismatch_reports_structural_path() {
+        let path =
+            std::env::temp_dir().join(format!("cardano-binary-golden-{}.cbor", std::process::id()));
+        fs::write(&path, serialize(&(1u8, 2u8)).unwrap()).unwrap();
+        let path_str = path.to_str().unwrap().to_owned();
+
+        let result = std::panic::catch_unwind(|| assert_golden_cbor(&(1u8, 3u8), &path_str));
+        let _ = fs::remove_file(&path);
+
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(
+            message.contains("CBOR encoding changed at $[1]: expected 2, got 3"),
+            "{message}"
+        );
+    }
+}
@@ -0,0 +1,451 @@
+#![cfg_attr(test, allow(clippy::unwrap_used))]
+
+use crate::error::{BinaryError, ResultExt};
+use ciborium_ll::{Decoder, Header};
+use serde::de::{self, DeserializeOwned, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::any::type_name;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Cursor;
+use std::marker::PhantomData;
+
+/// CBOR "break" stop code terminating an indefinite-length item.
+const BREAK: u8 = 0xff;
+
+/// How [`ByteKeyMap::decode_with_order`] and [`IntKeyMap::decode_with_order`]
+/// treat map keys that are not in canonical order.
+///
+/// Duplicate keys are rejected under either policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MapKeyOrder {
+    /// Accept keys in any order, as decoding a `BTreeMap` always has.
+    #[default]
+    Lenient,
+    /// Require keys in strictly ascending bytewise order of their encodings
+    /// (RFC 8949 §4.2.1), reporting [`BinaryError::UnsortedMapKey`]
+    /// otherwise.
+    Canonical,
+}
+
+/// A map encoded as a CBOR map whose keys are byte strings, Haskell's
+/// `Map ByteString v` and every map keyed by a hash.
+///
+/// Plain `serde` encodes a `BTreeMap<Vec<u8>, V>` with array keys and a
+/// `BTreeMap<[u8; 28], V>` with tuple keys; this wrapper writes each key as a
+/// definite-length byte string instead. Entries are emitted in canonical
+/// order (shorter keys first, then bytewise), which for fixed-width keys
+/// such as key hashes is exactly the ascending order Haskell's `Map`
+/// encodes in.
+///
+/// Decoding through `serde` (for example with [`decode_full`](crate::decode_full)
+/// or as a field of a larger type) accepts keys in any order and fails on a
+/// repeated key. [`ByteKeyMap::decode_with_order`] does the same for a
+/// complete input but reports the typed [`BinaryError::DuplicateMapKey`] and
+/// can require canonical order.
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use cardano_binary::{ByteKeyMap, serialize};
+///
+/// let map = ByteKeyMap(BTreeMap::from([(vec![0xca, 0xfe], 1u64)]));
+/// assert_eq!(serialize(&map).unwrap(), [0xa1, 0x42, 0xca, 0xfe, 0x01]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteKeyMap<K, V>(pub BTreeMap<K, V>);
+
+/// A map encoded as a CBOR map whose keys are integers, Haskell's
+/// `Map Word64 v` and maps keyed by integer newtypes.
+///
+/// Keys convert through `i128`, so any primitive integer works, and a
+/// newtype only needs `From<Key> for i128` and `TryFrom<i128> for Key`.
+/// Entries are emitted in canonical order: non-negative keys ascending, then
+/// negative keys descending. Decoding behaves as for [`ByteKeyMap`].
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use cardano_binary::{IntKeyMap, serialize};
+///
+/// let map = IntKeyMap(BTreeMap::from([(-1i64, true), (500, false)]));
+/// assert_eq!(serialize(&map).unwrap(), [0xa2, 0x19, 0x01, 0xf4, 0xf4, 0x20, 0xf5]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IntKeyMap<K, V>(pub BTreeMap<K, V>);
+
+impl<K, V> Default for ByteKeyMap<K, V> {
+    fn default() -> Self {
+        ByteKeyMap(BTreeMap::new())
+    }
+}
+
+impl<K, V> Default for IntKeyMap<K, V> {
+    fn default() -> Self {
+        IntKeyMap(BTreeMap::new())
+    }
+}
+
+impl<K, V> From<BTreeMap<K, V>> for ByteKeyMap<K, V> {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        ByteKeyMap(map)
+    }
+}
+
+impl<K, V> From<BTreeMap<K, V>> for IntKeyMap<K, V> {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        IntKeyMap(map)
+    }
+}
+
+impl<K, V> ByteKeyMap<K, V>
+where
+    K: TryFrom<Vec<u8>> + Ord,
+    V: DeserializeOwned,
+{
+    /// Decode `bytes`, which must hold exactly one CBOR map, under `order`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BinaryError::DuplicateMapKey`] for a repeated key,
+    /// [`BinaryError::UnsortedMapKey`] for a key out of order under
+    /// [`MapKeyOrder::Canonical`], [`BinaryError::Deserialization`] if the
+    /// input is not a map or an entry fails to decode (wrapped in a
+    /// [`BinaryError::Context`] naming the entry), and
+    /// [`BinaryError::Leftover`] if bytes follow the map.
+    pub fn decode_with_order(bytes: &[u8], order: MapKeyOrder) -> Result<Self, BinaryError> {
+        decode_map::<ByteKey<K>, V>(bytes, order, type_name::<Self>()).map(ByteKeyMap)
+    }
+}
+
+impl<K, V> IntKeyMap<K, V>
+where
+    K: TryFrom<i128> + Ord,
+    V: DeserializeOwned,
+{
+    /// Decode `bytes`, which must hold exactly one CBOR map, under `order`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`ByteKeyMap::decode_with_order`].
+    pub fn decode_with_order(bytes: &[u8], order: MapKeyOrder) -> Result<Self, BinaryError> {
+        decode_map::<IntKey<K>, V>(bytes, order, type_name::<Self>()).map(IntKeyMap)
+    }
+}
+
+impl<K: AsRef<[u8]>, V: Serialize> Serialize for ByteKeyMap<K, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_canonical(
+            self.0
+                .iter()
+                .map(|(key, value)| (ByteKeyRef(key.as_ref()), value)),
+            serializer,
+        )
+    }
+}
+
+impl<K: Copy + Into<i128>, V: Serialize> Serialize for IntKeyMap<K, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_canonical(
+            self.0.iter().map(|(key, value)| ((*key).into(), value)),
+            serializer,
+        )
+    }
+}
+
+impl<'de, K, V> Deserialize<'de> for ByteKeyMap<K, V>
+where
+    K: TryFrom<Vec<u8>> + Ord,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_map(MapVisitor::<ByteKey<K>, V>(PhantomData))
+            .map(ByteKeyMap)
+    }
+}
+
+impl<'de, K, V> Deserialize<'de> for IntKeyMap<K, V>
+where
+    K: TryFrom<i128> + Ord,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_map(MapVisitor::<IntKey<K>, V>(PhantomData))
+            .map(IntKeyMap)
+    }
+}
+
+/// Write `entries` as a definite-length map sorted by encoded key.
+fn serialize_canonical<'a, K, V, S>(
+    entries: impl Iterator<Item = (K, &'a V)>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    K: Serialize,
+    V: Serialize + 'a,
+    S: Serializer,
+{
+    let mut keyed = entries
+        .map(|(key, value)| {
+            let mut encoded = Vec::new();
+            ciborium::into_writer(&key, &mut encoded).map_err(serde::ser::Error::custom)?;
+            Ok((encoded, key, value))
+        })
+        .collect::<Result<Vec<_>, S::Error>>()?;
+    keyed.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut map = serializer.serialize_map(Some(keyed.len()))?;
+    for (_, key, value) in &keyed {
+        map.serialize_entry(key, value)?;
+    }
+    map.end()
+}
+
+struct ByteKeyRef<'a>(&'a [u8]);
+
+impl Serialize for ByteKeyRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// A key as it appears on the wire, converted to the map's key type.
+trait WireKey: Sized {
+    type Key: Ord;
+
+    fn into_key(self) -> Self::Key;
+}
+
+struct ByteKey<K>(K);
+
+impl<K: Ord> WireKey for ByteKey<K> {
+    type Key = K;
+
+    fn into_key(self) -> K {
+        self.0
+    }
+}
+
+impl<'de, K: TryFrom<Vec<u8>>> Deserialize<'de> for ByteKey<K> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BytesVisitor<K>(PhantomData<K>);
+
+        impl<K: TryFrom<Vec<u8>>> Visitor<'_> for BytesVisitor<K> {
+            type Value = ByteKey<K>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a byte-string map key")
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                self.visit_byte_buf(v.to_vec())
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                let len = v.len();
+                K::try_from(v).map(ByteKey).map_err(|_| {
+                    E::custom(format!(
+                        "a {len}-byte map key does not fit `{}`",
+                        type_name::<K>()
+                    ))
+                })
+            }
+        }
+
+        deserializer.deserialize_bytes(BytesVisitor(PhantomData))
+    }
+}
+
+struct IntKey<K>(K);
+
+impl<K: Ord> WireKey for IntKey<K> {
+    type Key = K;
+
+    fn into_key(self) -> K {
+        self.0
+    }
+}
+
+impl<'de, K: TryFrom<i128>> Deserialize<'de> for IntKey<K> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = i128::deserialize(deserializer)?;
+        K::try_from(value).map(IntKey).map_err(|_| {
+            de::Error::custom(format!(
+                "map key {value} does not fit `{}`",
+                type_name::<K>()
+            ))
+        })
+    }
+}
+
+struct MapVisitor<W, V>(PhantomData<(W, V)>);
+
+impl<'de, W, V> Visitor<'de> for MapVisitor<W, V>
+where
+    W: WireKey + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    type Value = BTreeMap<W::Key, V>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a CBOR map without duplicate keys")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut map = BTreeMap::new();
+        let mut index = 0usize;
+        while let Some((key, value)) = access.next_entry::<W, V>()? {
+            if map.insert(key.into_key(), value).is_some() {
+                return Err(de::Error::custom(format!(
+                    "map entry {index} repeats an earlier key"
+                )));
+            }
+            index += 1;
+        }
+        Ok(map)
+    }
+}
+
+/// Entry-by-entry decoder keeping the raw bytes of each key, so duplicates
+/// and ordering are reported with the offending key's encoding.
+fn decode_map<W, V>(
+    bytes: &[u8],
+    order: MapKeyOrder,
+    type_name: &'static str,
+) -> Result<BTreeMap<W::Key, V>, BinaryError>
+where
+    W: WireKey + DeserializeOwned,
+    V: DeserializeOwned,
+{
+    let mut decoder = Decoder::from(Cursor::new(bytes));
+    let header = decoder.pull().map_err(ciborium::de::Error::from)?;
+    let len = match header {
+        Header::Map(len) => len,
+        other => {
+            return Err(ciborium::de::Error::Semantic(
+                Some(0),
+                format!("`{type_name}` expects a CBOR map, found {other:?}"),
+            )
+            .into());
+        },
+    };
+    let mut position = decoder.offset();
+
+    let mut map = BTreeMap::new();
+    let mut previous_key: Option<&[u8]> = None;
+    let mut index = 0usize;
+    loop {
+        match len {
+            Some(len) if index == len => break,
+            None if bytes.get(position) == Some(&BREAK) => {
+                position += 1;
+                break;
+            },
+            _ => {},
+        }
+
+        let key_start = position;
+        let key: W = decode_at(bytes, &mut position)
+            .context(format!("key of map entry {index} of `{type_name}`"))?;
+        let raw_key = &bytes[key_start..position];
+        let value: V = decode_at(bytes, &mut position)
+            .context(format!("value of map entry {index} of `{type_name}`"))?;
+
+        if map.insert(key.into_key(), value).is_some() {
+            return Err(BinaryError::DuplicateMapKey {
+                type_name,
+                index,
+                key: raw_key.to_vec(),
+            });
+        }
+        if order == MapKeyOrder::Canonical && previous_key.is_some_and(|prev| prev >= raw_key) {
+            return Err(BinaryError::UnsortedMapKey {
+                type_name,
+                index,
+                key: raw_key.to_vec(),
+            });
+        }
+        previous_key = Some(raw_key);
+        index += 1;
+    }
+
+    if position < bytes.len() {
+        return Err(BinaryError::leftover(type_name, bytes, position));
+    }
+    Ok(map)
+}
+
+fn decode_at<T: DeserializeOwned>(bytes: &[u8], position: &mut usize) -> Result<T, BinaryError> {
+    let mut cursor = Cursor::new(&bytes[*position..]);
+    let value = ciborium::from_reader(&mut cursor)?;
+    *position += cursor.position() as usize;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode_full, serialize};
+
+    fn byte_map() -> ByteKeyMap<Vec<u8>, u64> {
+        ByteKeyMap(BTreeMap::from([(vec![2, 2], 1), (vec![1], 2), (vec![], 3)]))
+    }
+
+    #[test]
+    fn byte_keys_are_byte_strings_in_canonical_order() {
+        let bytes = serialize(&byte_map()).unwrap();
+        assert_eq!(
+            bytes,
+            [0xa3, 0x40, 0x03, 0x41, 0x01, 0x02, 0x42, 0x02, 0x02, 0x01]
+        );
+        assert_eq!(
+            decode_full::<ByteKeyMap<Vec<u8>, u64>>(&bytes).unwrap(),
+            byte_map()
+        );
+    }
+
+    #[test]
+    fn int_keys_sort_negative_after_non_negative() {
+        let map = IntKeyMap(BTreeMap::from([(-2i32, 0u8), (-1, 1), (0, 2), (24, 3)]));
+        let bytes = serialize(&map).unwrap();
+        assert_eq!(
+            bytes,
+            [0xa4, 0x00, 0x02, 0x18, 0x18, 0x03, 0x20, 0x01, 0x21, 0x00]
+        );
+        assert_eq!(
+            IntKeyMap::decode_with_order(&bytes, MapKeyOrder::Canonical).unwrap(),
+            map
+        );
+    }
+
+    #[test]
+    fn serde_path_rejects_duplicates() {
+        // {h'01': 1, h'01': 2}
+        let bytes = [0xa2, 0x41, 0x01, 0x01, 0x41, 0x01, 0x02];
+        let err = decode_full::<ByteKeyMap<Vec<u8>, u64>>(&bytes).unwrap_err();
+        assert!(err.to_string().contains("repeats an earlier key"), "{err}");
+    }
+
+    #[test]
+    fn indefinite_maps_decode() {
+        // {_ 1: 10, 0: 20}
+        let bytes = [0xbf, 0x01, 0x0a, 0x00, 0x14, 0xff];
+        let map = IntKeyMap::<u8, u8>::decode_with_order(&bytes, MapKeyOrder::Lenient).unwrap();
+        assert_eq!(map.0, BTreeMap::from([(0, 20), (1, 10)]));
+        assert!(matches!(
+            IntKeyMap::<u8, u8>::decode_with_order(&bytes, MapKeyOrder::Canonical),
+            Err(BinaryError::UnsortedMapKey { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn key_conversion_failures_are_reported() {
+        // {h'0102': 0} into a one-byte key type
+        let bytes = [0xa1, 0x42, 0x01, 0x02, 0x00];
+        let err =
+            ByteKeyMap::<[u8; 1], u8>::decode_with_order(&bytes, MapKeyOrder::Lenient).unwrap_err();
+        assert!(err.to_string().contains("key of map entry 0"), "{err}");
+        assert!(decode_full::<IntKeyMap<u8, u8>>(&[0xa1, 0x19, 0x01, 0x00, 0x00]).is_err());
+    }
+}
@@ -7,21 +7,71 @@
 #![cfg_attr(test, allow(clippy::unwrap_used))]
 #![cfg_attr(test, allow(clippy::approx_constant))]
 
+mod annotated;
+mod borrowed;
+mod canonical;
+mod codec;
+#[cfg(feature = "test-util")]
+pub mod corpus;
 mod deserialize;
 mod error;
+mod fixed;
+#[cfg(feature = "test-util")]
+pub mod golden;
+mod keyed_map;
+mod plutus_data;
 mod serialize;
+#[cfg(feature = "trace")]
+mod trace;
+mod untraced;
+mod value;
+mod versioned;
+mod word64;
 
 #[allow(deprecated)]
 pub use crate::deserialize::{
-    decode_full, decode_full_owned, decode_nested_cbor, decode_nested_cbor_bytes,
-    unsafe_deserialize, unsafe_deserialize_owned,
+    decode_from_reader, decode_full, decode_full_from_reader, decode_full_owned,
+    decode_nested_cbor, decode_nested_cbor_bytes, unsafe_deserialize, unsafe_deserialize_owned,
 };
 
-pub use crate::error::BinaryError;
+pub use crate::annotated::{Annotated, ByteSpan, Spanned, decode_annotated};
+
+pub use crate::borrowed::decode_full_borrowed;
+
+pub use crate::canonical::{CanonicalCbor, canonicalize, is_canonical};
+
+pub use crate::codec::{FromCbor, ToCbor, bytes_or_hex};
+
+pub use crate::error::{BinaryError, BinaryErrorKind, LEFTOVER_PREVIEW_LEN, ResultExt};
+
+pub use crate::fixed::{
+    decode_fixed_array, decode_fixed_array_canonical, decode_pair, decode_pair_canonical,
+    decode_triple, decode_triple_canonical, encode_fixed_array, encode_pair, encode_triple,
+};
+
+pub use crate::keyed_map::{ByteKeyMap, IntKeyMap, MapKeyOrder};
+
+pub use crate::plutus_data::{PLUTUS_BYTES_CHUNK, PlutusData};
 
 pub use crate::serialize::{
-    encode_nested_cbor, encode_nested_cbor_bytes, serialize, serialize_into_vec,
-    serialize_into_writer, serialize_strict, serialize_with_capacity,
+    encode_nested_cbor, encode_nested_cbor_bytes, encode_nested_cbor_into, nested_cbor_len,
+    serialize, serialize_into_vec, serialize_into_writer, serialize_strict,
+    serialize_with_capacity,
+};
+
+pub use crate::untraced::untraced;
+
+pub use crate::value::{Value, decode_value, encode_value};
+
+pub use crate::versioned::{VersionRegistry, decode_versioned, encode_versioned};
+
+pub use crate::word64::{decode_word64, decode_word64_canonical, encode_word64_canonical};
+
+#[cfg(feature = "trace")]
+pub use crate::trace::{
+    CaptureRecord, CaptureWriter, DEFAULT_TRACE_PAYLOAD_LIMIT, ReplayOutcome, ReplayRegistry,
+    TraceDirection, TraceEvent, TraceOutcome, clear_trace_sink, read_capture, replay,
+    set_trace_payload_limit, set_trace_sink,
 };
 
 #[cfg(test)]
@@ -0,0 +1,453 @@
+#![cfg_attr(test, allow(clippy::unwrap_used))]
+
+use crate::codec::{FromCbor, ToCbor};
+use crate::error::BinaryError;
+use ciborium::de::Error;
+use ciborium_ll::{Decoder, Encoder, Header};
+use num_bigint::{BigInt, BigUint, Sign};
+use std::io;
+
+/// Nesting depth at which decoding gives up, matching ciborium's default.
+const RECURSION_LIMIT: usize = 256;
+
+const BREAK: u8 = 0xff;
+
+/// Longest byte string, or chunk of an indefinite-length one, in `Data`.
+pub const PLUTUS_BYTES_CHUNK: usize = 64;
+
+/// CBOR tag of constructor 0; constructors 1 to 6 follow it.
+const COMPACT_TAG: u64 = 121;
+/// CBOR tag of constructor 7; constructors 8 to 127 follow it.
+const EXTENDED_TAG: u64 = 1280;
+/// CBOR tag of `[constructor, fields]` for any other constructor.
+const GENERAL_TAG: u64 = 102;
+const POSITIVE_BIGNUM_TAG: u64 = 2;
+const NEGATIVE_BIGNUM_TAG: u64 = 3;
+
+type DeResult<T> = Result<T, Error<io::Error>>;
+
+/// A Plutus `Data` value, the type of datums and redeemers.
+///
+/// [`serialize`](crate::serialize) and [`decode_full`](crate::decode_full)
+/// use the encoding the ledger and `plutus-core` use on chain:
+///
+/// - `Constr` under CBOR tags 121..=127 for constructors 0 to 6, tags
+///   1280..=1400 for constructors 7 to 127, and otherwise tag 102 over the
+///   array `[constructor, fields]`.
+/// - Non-empty field lists and `List`s as indefinite-length arrays, empty
+///   ones as `[]`. `Map`s are definite-length, entries in the order given.
+/// - `Int`s in the CBOR integer range `-2^64..2^64` as plain integers, larger
+///   ones as tag 2 or 3 bignums.
+/// - `Bytes` of up to 64 bytes as a byte string, longer ones (and bignum
+///   magnitudes) as an indefinite-length string of 64-byte chunks.
+///
+/// Decoding also accepts definite- and indefinite-length arrays and maps
+/// wherever either may appear, and bignums for small integers, so a value
+/// decoded from non-canonical bytes can re-encode differently. Like the
+/// ledger, it rejects byte strings and chunks longer than 64 bytes.
+///
+/// There are no `serde` impls: `serde` cannot express chunked byte strings,
+/// so [`ToCbor`] and [`FromCbor`] are implemented directly. Encode a datum
+/// on its own and embed the bytes where a larger structure needs it.
+///
+/// ```rust
+/// use cardano_binary::{PlutusData, decode_full, serialize};
+///
+/// let unit = PlutusData::Constr { tag: 0, fields: vec![] };
+/// assert_eq!(serialize(&unit).unwrap(), [0xd8, 0x79, 0x80]);
+/// assert_eq!(decode_full::<PlutusData>(&[0xd8, 0x79, 0x80]).unwrap(), unit);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PlutusData {
+    /// Constructor `tag` applied to `fields`. `tag` is the constructor
+    /// index, not the CBOR tag it is encoded under.
+    Constr { tag: u64, fields: Vec<PlutusData> },
+    /// Key-value pairs in order; keys may repeat.
+    Map(Vec<(PlutusData, PlutusData)>),
+    /// A list of values.
+    List(Vec<PlutusData>),
+    /// An integer of any size.
+    Int(BigInt),
+    /// A byte string of any length.
+    Bytes(Vec<u8>),
+}
+
+/// The CBOR tag constructor `tag` is encoded under, if it has a compact one.
+fn compact_tag(tag: u64) -> Option<u64> {
+    match tag {
+        0..=6 => Some(COMPACT_TAG + tag),
+        7..=127 => Some(EXTENDED_TAG + tag - 7),
+        _ => None,
+    }
+}
+
+impl ToCbor for PlutusData {
+    fn encode_cbor(&self, buffer: &mut Vec<u8>) -> Result<(), BinaryError> {
+        encode(self, &mut Encoder::from(buffer))?;
+        Ok(())
+    }
+}
+
+impl FromCbor for PlutusData {
+    fn decode_cbor(bytes: &[u8]) -> Result<Self, BinaryError> {
+        let mut reader = Reader {
+            input: bytes,
+            pos: 0,
+            depth: RECURSION_LIMIT,
+        };
+        let data = reader.data()?;
+        if reader.pos < bytes.len() {
+            return Err(BinaryError::leftover(
+                std::any::type_name::<Self>(),
+                bytes,
+                reader.pos,
+            ));
+        }
+        Ok(data)
+    }
+}
+
+fn encode(data: &PlutusData, encoder: &mut Encoder<&mut Vec<u8>>) -> io::Result<()> {
+    match data {
+        PlutusData::Constr { tag, fields } => {
+            if let Some(cbor_tag) = compact_tag(*tag) {
+                encoder.push(Header::Tag(cbor_tag))?;
+            } else {
+                encoder.push(Header::Tag(GENERAL_TAG))?;
+                encoder.push(Header::Array(Some(2)))?;
+                encoder.push(Header::Positive(*tag))?;
+            }
+            encode_list(fields, encoder)
+        },
+        PlutusData::Map(entries) => {
+            encoder.push(Header::Map(Some(entries.len())))?;
+            for (key, value) in entries {
+                encode(key, encoder)?;
+                encode(value, encoder)?;
+            }
+            Ok(())
+        },
+        PlutusData::List(items) => encode_list(items, encoder),
+        PlutusData::Int(int) => encode_int(int, encoder),
+        PlutusData::Bytes(bytes) => encoder.bytes(bytes, PLUTUS_BYTES_CHUNK),
+    }
+}
+
+fn encode_list(items: &[PlutusData], encoder: &mut Encoder<&mut Vec<u8>>) -> io::Result<()> {
+    if items.is_empty() {
+        return encoder.push(Header::Array(Some(0)));
+    }
+    encoder.push(Header::Array(None))?;
+    for item in items {
+        encode(item, encoder)?;
+    }
+    encoder.push(Header::Break)
+}
+
+fn encode_int(int: &BigInt, encoder: &mut Encoder<&mut Vec<u8>>) -> io::Result<()> {
+    let (sign, magnitude) = if int.sign() == Sign::Minus {
+        // CBOR stores -1 - n for negative n.
+        (Sign::Minus, (-int - 1u8).magnitude().clone())
+    } else {
+        (Sign::Plus, int.magnitude().clone())
+    };
+    match (u64::try_from(&magnitude), sign) {
+        (Ok(n), Sign::Minus) => encoder.push(Header::Negative(n)),
+        (Ok(n), _) => encoder.push(Header::Positive(n)),
+        (Err(_), sign) => {
+            let tag = if sign == Sign::Minus {
+                NEGATIVE_BIGNUM_TAG
+            } else {
+                POSITIVE_BIGNUM_TAG
+            };
+            encoder.push(Header::Tag(tag))?;
+            encoder.bytes(&magnitude.to_bytes_be(), PLUTUS_BYTES_CHUNK)
+        },
+    }
+}
+
+struct Reader<'a> {
+    input: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl Reader<'_> {
+    fn header(&mut self) -> DeResult<Header> {
+        let mut decoder = Decoder::from(&self.input[self.pos..]);
+        let header = decoder.pull().map_err(|err| match err {
+            ciborium_ll::Error::Io(err) => Error::Io(err),
+            ciborium_ll::Error::Syntax(offset) => Error::Syntax(self.pos + offset),
+        })?;
+        self.pos += decoder.offset();
+        Ok(header)
+    }
+
+    fn take(&mut self, len: usize) -> DeResult<&[u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.input.len())
+            .ok_or_else(|| Error::Io(io::ErrorKind::UnexpectedEof.into()))?;
+        let slice = &self.input[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    /// Whether an indefinite-length container ends here, consuming the
+    /// break if so.
+    fn at_break(&mut self) -> bool {
+        let found = self.input.get(self.pos) == Some(&BREAK);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> DeResult<T>) -> DeResult<T> {
+        if self.depth == 0 {
+            return Err(Error::RecursionLimitExceeded);
+        }
+        self.depth -= 1;
+        let result = f(self);
+        self.depth += 1;
+        result
+    }
+
+    fn data(&mut self) -> DeResult<PlutusData> {
+        let offset = self.pos;
+        match self.header()? {
+            Header::Tag(tag @ 121..=127) => self.constr(tag - COMPACT_TAG),
+            Header::Tag(tag @ 1280..=1400) => self.constr(tag - EXTENDED_TAG + 7),
+            Header::Tag(GENERAL_TAG) => self.general_constr(),
+            Header::Tag(POSITIVE_BIGNUM_TAG) => {
+                let magnitude = BigUint::from_bytes_be(&self.bignum_bytes()?);
+                Ok(PlutusData::Int(BigInt::from(magnitude)))
+            },
+            Header::Tag(NEGATIVE_BIGNUM_TAG) => {
+                let magnitude = BigUint::from_bytes_be(&self.bignum_bytes()?);
+                Ok(PlutusData::Int(-BigInt::from(magnitude) - 1u8))
+            },
+            Header::Positive(n) => Ok(PlutusData::Int(BigInt::from(n))),
+            Header::Negative(n) => Ok(PlutusData::Int(-BigInt::from(n) - 1u8)),
+            Header::Bytes(len) => self.bytes(len, offset).map(PlutusData::Bytes),
+            Header::Array(len) => self.items(len).map(PlutusData::List),
+            Header::Map(len) => self.entries(len).map(PlutusData::Map),
+            other => Err(Error::Semantic(
+                Some(offset),
+                format!("Plutus data cannot start with {other:?}"),
+            )),
+        }
+    }
+
+    fn constr(&mut self, tag: u64) -> DeResult<PlutusData> {
+        let fields = self.fields()?;
+        Ok(PlutusData::Constr { tag, fields })
+    }
+
+    /// Tag 102 over `[constructor, fields]`.
+    fn general_constr(&mut self) -> DeResult<PlutusData> {
+        let offset = self.pos;
+        let indefinite = match self.header()? {
+            Header::Array(Some(2)) => false,
+            Header::Array(None) => true,
+            other => {
+                return Err(Error::Semantic(
+                    Some(offset),
+                    format!("constructor tag 102 expects a 2-element array, found {other:?}"),
+                ));
+            },
+        };
+        let offset = self.pos;
+        let tag = match self.header()? {
+            Header::Positive(tag) => tag,
+            other => {
+                return Err(Error::Semantic(
+                    Some(offset),
+                    format!("constructor index must be an unsigned integer, found {other:?}"),
+                ));
+            },
+        };
+        let fields = self.fields()?;
+        if indefinite && !self.at_break() {
+            return Err(Error::Semantic(
+                Some(self.pos),
+                "constructor tag 102 expects a 2-element array".into(),
+            ));
+        }
+        Ok(PlutusData::Constr { tag, fields })
+    }
+
+    fn fields(&mut self) -> DeResult<Vec<PlutusData>> {
+        let offset = self.pos;
+        match self.header()? {
+            Header::Array(len) => self.items(len),
+            other => Err(Error::Semantic(
+                Some(offset),
+                format!("constructor fields must be an array, found {other:?}"),
+            )),
+        }
+    }
+
+    fn items(&mut self, len: Option<usize>) -> DeResult<Vec<PlutusData>> {
+        self.nested(|reader| {
+            let mut items = Vec::with_capacity(len.unwrap_or(0).min(reader.remaining()));
+            match len {
+                Some(len) => {
+                    for _ in 0..len {
+                        items.push(reader.data()?);
+                    }
+                },
+                None => {
+                    while !reader.at_break() {
+                        items.push(reader.data()?);
+                    }
+                },
+            }
+            Ok(items)
+        })
+    }
+
+    fn entries(&mut self, len: Option<usize>) -> DeResult<Vec<(PlutusData, PlutusData)>> {
+        self.nested(|reader| {
+            let mut entries = Vec::with_capacity(len.unwrap_or(0).min(reader.remaining()));
+            match len {
+                Some(len) => {
+                    for _ in 0..len {
+                        entries.push((reader.data()?, reader.data()?));
+                    }
+                },
+                None => {
+                    while !reader.at_break() {
+                        entries.push((reader.data()?, reader.data()?));
+                    }
+                },
+            }
+            Ok(entries)
+        })
+    }
+
+    /// A `Bytes` payload, rejecting any string or chunk over 64 bytes.
+    fn bytes(&mut self, len: Option<usize>, offset: usize) -> DeResult<Vec<u8>> {
+        let too_long = |len: usize| {
+            Error::Semantic(
+                Some(offset),
+                format!("Plutus byte string of {len} bytes exceeds {PLUTUS_BYTES_CHUNK}"),
+            )
+        };
+        match len {
+            Some(len) if len > PLUTUS_BYTES_CHUNK => Err(too_long(len)),
+            Some(len) => Ok(self.take(len)?.to_vec()),
+            None => {
+                let mut buf = Vec::new();
+                loop {
+                    let chunk_offset = self.pos;
+                    match self.header()? {
+                        Header::Break => return Ok(buf),
+                        Header::Bytes(Some(len)) if len > PLUTUS_BYTES_CHUNK => {
+                            return Err(too_long(len));
+                        },
+                        Header::Bytes(Some(len)) => buf.extend_from_slice(self.take(len)?),
+                        _ => return Err(Error::Syntax(chunk_offset)),
+                    }
+                }
+            },
+        }
+    }
+
+    /// The magnitude of a bignum, a byte string of any length.
+    fn bignum_bytes(&mut self) -> DeResult<Vec<u8>> {
+        let offset = self.pos;
+        match self.header()? {
+            Header::Bytes(Some(len)) => Ok(self.take(len)?.to_vec()),
+            Header::Bytes(None) => {
+                let mut buf = Vec::new();
+                loop {
+                    let chunk_offset = self.pos;
+                    match self.header()? {
+                        Header::Break => return Ok(buf),
+                        Header::Bytes(Some(len)) => buf.extend_from_slice(self.take(len)?),
+                        _ => return Err(Error::Syntax(chunk_offset)),
+                    }
+                }
+            },
+            other => Err(Error::Semantic(
+                Some(offset),
+                format!("bignum expects a byte string, found {other:?}"),
+            )),
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        self.input.len() - self.pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode_full, serialize};
+
+    fn constr(tag: u64) -> PlutusData {
+        PlutusData::Constr {
+            tag,
+            fields: vec![],
+        }
+    }
+
+    #[test]
+    fn constructor_tags_follow_the_three_ranges() {
+        for (tag, encoding) in [
+            (0, &[0xd8, 0x79, 0x80][..]),
+            (6, &[0xd8, 0x7f, 0x80]),
+            (7, &[0xd9, 0x05, 0x00, 0x80]),
+            (127, &[0xd9, 0x05, 0x78, 0x80]),
+            (128, &[0xd8, 0x66, 0x82, 0x18, 0x80, 0x80]),
+        ] {
+            assert_eq!(serialize(&constr(tag)).unwrap(), encoding, "{tag}");
+            assert_eq!(decode_full::<PlutusData>(encoding).unwrap(), constr(tag));
+        }
+    }
+
+    #[test]
+    fn integers_switch_to_bignums_outside_the_cbor_range() {
+        let two_64 = BigInt::from(u64::MAX) + 1u8;
+        for (int, encoding) in [
+            (BigInt::from(u64::MAX), hex("1bffffffffffffffff")),
+            (-two_64.clone(), hex("3bffffffffffffffff")),
+            (two_64.clone(), hex("c249010000000000000000")),
+            (-two_64 - 1u8, hex("c349010000000000000000")),
+        ] {
+            let data = PlutusData::Int(int);
+            assert_eq!(serialize(&data).unwrap(), encoding);
+            assert_eq!(decode_full::<PlutusData>(&encoding).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn oversized_byte_strings_are_rejected() {
+        let mut definite = vec![0x58, 65];
+        definite.extend([0; 65]);
+        let err = decode_full::<PlutusData>(&definite).unwrap_err();
+        assert!(err.to_string().contains("65 bytes exceeds 64"), "{err}");
+
+        let mut chunked = vec![0x5f, 0x58, 65];
+        chunked.extend([0; 65]);
+        chunked.push(BREAK);
+        assert!(decode_full::<PlutusData>(&chunked).is_err());
+    }
+
+    #[test]
+    fn deep_nesting_hits_the_recursion_limit() {
+        let bytes = vec![0x81; RECURSION_LIMIT + 1];
+        let err = decode_full::<PlutusData>(&bytes).unwrap_err();
+        assert_eq!(err.kind(), crate::BinaryErrorKind::RecursionLimit);
+    }
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+}
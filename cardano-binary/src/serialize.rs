@@ -1,31 +1,46 @@
 #![cfg_attr(test, allow(clippy::unwrap_used))]
 
+use crate::codec::ToCbor;
 use crate::error::BinaryError;
 use serde::Serialize;
 use std::io::Write;
 
 /// Serialise a value into a vector of bytes using canonical CBOR semantics.
 ///
+/// The bytes come from the value's [`ToCbor`] impl, which for `serde` types
+/// is the blanket one.
+///
 /// # Errors
 ///
 /// Returns [`BinaryError::Serialization`] if the value cannot be serialized to CBOR.
-pub fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>, BinaryError> {
+pub fn serialize<T: ToCbor + ?Sized>(value: &T) -> Result<Vec<u8>, BinaryError> {
     let mut buf = Vec::new();
-    ciborium::into_writer(value, &mut buf)?;
+    let result = value.encode_cbor(&mut buf);
+    #[cfg(feature = "trace")]
+    crate::trace::emit::<T, _>(crate::trace::TraceDirection::Encode, &buf, &result);
+    result?;
     Ok(buf)
 }
 
-/// Serialise a value and return an owned byte vector (strict variant).
+/// Haskell's `serialize'`: the encoding as one contiguous buffer.
+///
+/// Rust has no lazy byte string, so this is the same as [`serialize`]. The
+/// counterpart of Haskell's lazy `serialize` is [`serialize_into_writer`],
+/// which streams the encoding instead of materialising it.
 ///
 /// # Errors
 ///
 /// Returns [`BinaryError::Serialization`] if the value cannot be serialized to CBOR.
-pub fn serialize_strict<T: Serialize>(value: &T) -> Result<Vec<u8>, BinaryError> {
+pub fn serialize_strict<T: ToCbor + ?Sized>(value: &T) -> Result<Vec<u8>, BinaryError> {
     serialize(value)
 }
 
 /// Serialise a value using an existing IO writer.
 ///
+/// With the `trace` feature and a sink installed, the value is encoded into
+/// a buffer first so the payload can be reported, and write failures surface
+/// as [`BinaryError::Io`].
+///
 /// # Errors
 ///
 /// Returns [`BinaryError::Serialization`] if:
@@ -36,6 +51,13 @@ where
     T: Serialize,
     W: Write,
 {
+    #[cfg(feature = "trace")]
+    if crate::trace::is_enabled() {
+        let mut writer = writer;
+        let bytes = serialize(value)?;
+        writer.write_all(&bytes)?;
+        return Ok(());
+    }
     ciborium::into_writer(value, writer)?;
     Ok(())
 }
@@ -50,8 +72,10 @@ pub fn serialize_into_vec<T: Serialize>(
     buffer: &mut Vec<u8>,
 ) -> Result<(), BinaryError> {
     buffer.clear();
-    ciborium::into_writer(value, buffer)?;
-    Ok(())
+    let result = ciborium::into_writer(value, &mut *buffer).map_err(BinaryError::from);
+    #[cfg(feature = "trace")]
+    crate::trace::emit::<T, _>(crate::trace::TraceDirection::Encode, buffer, &result);
+    result
 }
 
 /// Serialise into a vector after reserving the provided capacity hint.
@@ -92,6 +116,109 @@ pub fn encode_nested_cbor_bytes(bytes: &[u8]) -> Result<Vec<u8>, BinaryError> {
     Ok(buf)
 }
 
+/// Length of the tag 24 encoding produced for `value`, without allocating the
+/// encoding.
+///
+/// This is the number of bytes [`encode_nested_cbor`] returns and
+/// [`encode_nested_cbor_into`] writes, so outer encoders can size their
+/// buffers or length prefixes up front.
+///
+/// # Errors
+///
+/// Returns [`BinaryError::Serialization`] if the value cannot be serialized to CBOR.
+pub fn nested_cbor_len<T: Serialize>(value: &T) -> Result<usize, BinaryError> {
+    let payload_len = encoded_len(value)?;
+    Ok(nested_header(payload_len).len() + payload_len)
+}
+
+/// Stream the nested (tag 24) CBOR encoding of `value` into `writer`.
+///
+/// Produces the same bytes as [`encode_nested_cbor`] without materialising
+/// the inner encoding: a first pass only counts the payload length, then the
+/// tag and byte-string header are written and the value is serialized
+/// straight into `writer`. `value` must serialize identically on both passes.
+///
+/// With the `trace` feature and a sink installed, this falls back to the
+/// buffered encoding so the inner payload can be reported.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The value cannot be serialized to CBOR
+/// - Writing to `writer` fails
+/// - The second pass produces a different number of bytes than the first
+pub fn encode_nested_cbor_into<T, W>(value: &T, writer: &mut W) -> Result<(), BinaryError>
+where
+    T: Serialize,
+    W: Write,
+{
+    #[cfg(feature = "trace")]
+    if crate::trace::is_enabled() {
+        writer.write_all(&encode_nested_cbor(value)?)?;
+        return Ok(());
+    }
+    let payload_len = encoded_len(value)?;
+    writer.write_all(&nested_header(payload_len))?;
+
+    let mut counted = CountingWriter {
+        inner: &mut *writer,
+        written: 0,
+    };
+    ciborium::into_writer(value, &mut counted)?;
+    if counted.written != payload_len {
+        return Err(BinaryError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "nested CBOR payload changed length between passes: {payload_len} then {}",
+                counted.written
+            ),
+        )));
+    }
+    Ok(())
+}
+
+/// Number of bytes `value` serializes to.
+fn encoded_len<T: Serialize>(value: &T) -> Result<usize, BinaryError> {
+    let mut counter = CountingWriter {
+        inner: std::io::sink(),
+        written: 0,
+    };
+    ciborium::into_writer(value, &mut counter)?;
+    Ok(counter.written)
+}
+
+/// Tag 24 followed by the definite-length byte-string header for a payload of
+/// `payload_len` bytes, using the shortest argument encoding.
+fn nested_header(payload_len: usize) -> Vec<u8> {
+    use ciborium_ll::{Encoder, Header};
+
+    let mut header = Vec::with_capacity(11);
+    let mut encoder = Encoder::from(&mut header);
+    encoder
+        .push(Header::Tag(24))
+        .and_then(|()| encoder.push(Header::Bytes(Some(payload_len))))
+        .expect("writing to a Vec cannot fail");
+    header
+}
+
+/// Writer adaptor counting the bytes passed through it.
+struct CountingWriter<W> {
+    inner: W,
+    written: usize,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.written += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,4 +292,50 @@ mod tests {
         assert_eq!(decoded, sample);
         assert!(encoded.capacity() >= 128);
     }
+
+    #[test]
+    fn streaming_nested_matches_buffered_encoding() {
+        // Payload lengths straddling every byte-string header width; the
+        // inner encoding adds its own header, so the outer length crosses the
+        // boundaries too.
+        for len in [
+            0, 1, 21, 22, 23, 24, 253, 254, 255, 256, 65_532, 65_533, 65_536, 70_000,
+        ] {
+            let payload = ByteBuf::from(vec![0xA5; len]);
+            let expected = encode_nested_cbor(&payload).unwrap();
+
+            let mut streamed = Vec::new();
+            encode_nested_cbor_into(&payload, &mut streamed).unwrap();
+            assert_eq!(streamed, expected, "payload length {len}");
+            assert_eq!(nested_cbor_len(&payload).unwrap(), expected.len());
+        }
+
+        let sample = Sample {
+            label: "nested".into(),
+            value: 1_000_000,
+        };
+        let mut streamed = Vec::new();
+        encode_nested_cbor_into(&sample, &mut streamed).unwrap();
+        assert_eq!(streamed, encode_nested_cbor(&sample).unwrap());
+        assert_eq!(nested_cbor_len(&sample).unwrap(), streamed.len());
+    }
+
+    #[test]
+    fn streaming_nested_surfaces_writer_errors() {
+        struct Full;
+
+        impl Write for Full {
+            fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::WriteZero.into())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let payload = ByteBuf::from(vec![0x01; 64]);
+        let err = encode_nested_cbor_into(&payload, &mut Full).unwrap_err();
+        assert!(matches!(err, BinaryError::Io(_)), "{err:?}");
+    }
 }
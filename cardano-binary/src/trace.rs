@@ -0,0 +1,339 @@
+//! Instrumentation hooks for capturing and replaying CBOR traffic.
+//!
+//! When a sink is installed with [`set_trace_sink`], [`serialize`],
+//! [`decode_full`] and the helpers built on them report every payload they
+//! encode or decode as a [`TraceEvent`]. Without a sink the only cost is a
+//! relaxed atomic load per call.
+//!
+//! Payloads that must not leave the process, such as signing keys, are
+//! encoded and decoded inside [`untraced`](crate::untraced), which hides
+//! them from the sink on that thread.
+//!
+//! Captured payloads can be stored with [`CaptureWriter`], read back with
+//! [`read_capture`], and re-decoded by newer code through [`replay`] and a
+//! [`ReplayRegistry`] mapping type names to decoders.
+//!
+//! [`serialize`]: crate::serialize
+//! [`decode_full`]: crate::decode_full
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use serde::de::DeserializeOwned;
+
+use crate::deserialize::decode_full;
+use crate::error::BinaryError;
+
+/// Default upper bound on payloads copied into [`TraceEvent::payload`].
+pub const DEFAULT_TRACE_PAYLOAD_LIMIT: usize = 4096;
+
+type Sink = Arc<dyn Fn(TraceEvent) + Send + Sync>;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static PAYLOAD_LIMIT: AtomicUsize = AtomicUsize::new(DEFAULT_TRACE_PAYLOAD_LIMIT);
+static SINK: RwLock<Option<Sink>> = RwLock::new(None);
+
+thread_local! {
+    /// Depth of nested [`untraced`](crate::untraced) calls on this thread.
+    static SUPPRESSED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Whether a payload was being encoded or decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TraceDirection {
+    Encode,
+    Decode,
+}
+
+/// Result of the traced operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceOutcome {
+    Success,
+    /// The operation failed; carries the rendered [`BinaryError`].
+    Failure(String),
+}
+
+/// A single encode or decode observed by the trace hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEvent {
+    pub direction: TraceDirection,
+    /// `std::any::type_name` of the value being encoded or decoded.
+    pub type_name: &'static str,
+    /// Length of the CBOR payload in bytes (zero for failed encodes).
+    pub len: usize,
+    /// FNV-1a 64-bit hash of the payload, stable across processes.
+    pub payload_hash: u64,
+    /// The payload itself, when no longer than the configured limit.
+    pub payload: Option<Vec<u8>>,
+    pub outcome: TraceOutcome,
+}
+
+/// Install the process-wide trace sink, replacing any previous one.
+///
+/// The sink runs synchronously on the encoding or decoding thread, so it
+/// should hand events off quickly.
+pub fn set_trace_sink(sink: Box<dyn Fn(TraceEvent) + Send + Sync>) {
+    let mut slot = SINK.write().unwrap_or_else(PoisonError::into_inner);
+    *slot = Some(Arc::from(sink));
+    INSTALLED.store(true, Ordering::Release);
+}
+
+/// Remove the trace sink, restoring the untraced fast path.
+pub fn clear_trace_sink() {
+    let mut slot = SINK.write().unwrap_or_else(PoisonError::into_inner);
+    INSTALLED.store(false, Ordering::Release);
+    *slot = None;
+}
+
+/// Set the largest payload copied into [`TraceEvent::payload`].
+///
+/// Longer payloads are still reported by length and hash.
+pub fn set_trace_payload_limit(limit: usize) {
+    PAYLOAD_LIMIT.store(limit, Ordering::Relaxed);
+}
+
+#[inline]
+pub(crate) fn is_enabled() -> bool {
+    INSTALLED.load(Ordering::Relaxed) && SUPPRESSED.with(|depth| depth.get() == 0)
+}
+
+/// Hides events from the sink on the current thread while alive.
+pub(crate) struct Suppressed(());
+
+impl Suppressed {
+    pub(crate) fn enter() -> Self {
+        SUPPRESSED.with(|depth| depth.set(depth.get() + 1));
+        Suppressed(())
+    }
+}
+
+impl Drop for Suppressed {
+    fn drop(&mut self) {
+        SUPPRESSED.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+pub(crate) fn emit<T: ?Sized, V>(
+    direction: TraceDirection,
+    payload: &[u8],
+    result: &Result<V, BinaryError>,
+) {
+    if !is_enabled() {
+        return;
+    }
+    let sink = SINK.read().unwrap_or_else(PoisonError::into_inner).clone();
+    let Some(sink) = sink else {
+        return;
+    };
+    let limit = PAYLOAD_LIMIT.load(Ordering::Relaxed);
+    sink(TraceEvent {
+        direction,
+        type_name: std::any::type_name::<T>(),
+        len: payload.len(),
+        payload_hash: fnv1a64(payload),
+        payload: (payload.len() <= limit).then(|| payload.to_vec()),
+        outcome: match result {
+            Ok(_) => TraceOutcome::Success,
+            Err(err) => TraceOutcome::Failure(err.to_string()),
+        },
+    });
+}
+
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// One payload stored in a capture file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureRecord {
+    pub direction: TraceDirection,
+    pub type_name: String,
+    pub payload: Vec<u8>,
+}
+
+/// Writes capture records in a simple length-prefixed format.
+///
+/// Each record is a direction byte (`0` encode, `1` decode), followed by the
+/// type name and the payload, each prefixed by its length as a big-endian
+/// `u32`.
+pub struct CaptureWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> CaptureWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Append one record.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a field exceeds `u32::MAX` bytes or writing fails.
+    pub fn write_record(&mut self, record: &CaptureRecord) -> io::Result<()> {
+        let direction = match record.direction {
+            TraceDirection::Encode => 0u8,
+            TraceDirection::Decode => 1u8,
+        };
+        self.writer.write_all(&[direction])?;
+        write_chunk(&mut self.writer, record.type_name.as_bytes())?;
+        write_chunk(&mut self.writer, &record.payload)
+    }
+
+    /// Append a traced event, returning `false` without writing anything if
+    /// the event carries no payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_event(&mut self, event: &TraceEvent) -> io::Result<bool> {
+        let Some(payload) = &event.payload else {
+            return Ok(false);
+        };
+        self.write_record(&CaptureRecord {
+            direction: event.direction,
+            type_name: event.type_name.to_owned(),
+            payload: payload.clone(),
+        })?;
+        Ok(true)
+    }
+
+    /// Flush and return the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if flushing fails.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn write_chunk<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(bytes.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "capture field too long"))?;
+    writer.write_all(&len.to_be_bytes())?;
+    writer.write_all(bytes)
+}
+
+/// Read every record from a capture produced by [`CaptureWriter`].
+///
+/// # Errors
+///
+/// Returns [`BinaryError::Io`] if the capture is truncated, has an unknown
+/// direction byte, or a type name that is not UTF-8.
+pub fn read_capture<R: Read>(mut reader: R) -> Result<Vec<CaptureRecord>, BinaryError> {
+    let mut records = Vec::new();
+    loop {
+        let mut direction = [0u8; 1];
+        match reader.read_exact(&mut direction) {
+            Ok(()) => {},
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(records),
+            Err(err) => return Err(err.into()),
+        }
+        let direction = match direction[0] {
+            0 => TraceDirection::Encode,
+            1 => TraceDirection::Decode,
+            other => {
+                return Err(invalid_capture(format!(
+                    "unknown capture direction {other}"
+                )));
+            },
+        };
+        let type_name = String::from_utf8(read_chunk(&mut reader)?)
+            .map_err(|_| invalid_capture("capture type name is not UTF-8".to_owned()))?;
+        let payload = read_chunk(&mut reader)?;
+        records.push(CaptureRecord {
+            direction,
+            type_name,
+            payload,
+        });
+    }
+}
+
+/// Read one length-prefixed chunk. The buffer grows with the bytes actually
+/// read, so a corrupt length cannot force a large allocation up front.
+fn read_chunk<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let len = u64::from(u32::from_be_bytes(len));
+    let mut bytes = Vec::new();
+    reader.by_ref().take(len).read_to_end(&mut bytes)?;
+    if (bytes.len() as u64) < len {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!(
+                "capture chunk declares {len} bytes but only {} remain",
+                bytes.len()
+            ),
+        ));
+    }
+    Ok(bytes)
+}
+
+fn invalid_capture(message: String) -> BinaryError {
+    BinaryError::Io(io::Error::new(io::ErrorKind::InvalidData, message))
+}
+
+type Decoder = Box<dyn Fn(&[u8]) -> Result<(), BinaryError> + Send + Sync>;
+
+/// Maps captured type names to the decoders used by [`replay`].
+#[derive(Default)]
+pub struct ReplayRegistry {
+    decoders: HashMap<String, Decoder>,
+}
+
+impl ReplayRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode records captured for `T` with [`decode_full::<T>`](decode_full).
+    #[must_use]
+    pub fn register<T: DeserializeOwned + 'static>(self) -> Self {
+        self.register_as::<T>(std::any::type_name::<T>())
+    }
+
+    /// Decode records captured under `type_name` as `T`, for types that have
+    /// been renamed or moved since the capture was taken.
+    #[must_use]
+    pub fn register_as<T: DeserializeOwned + 'static>(mut self, type_name: &str) -> Self {
+        self.decoders.insert(
+            type_name.to_owned(),
+            Box::new(|bytes| decode_full::<T>(bytes).map(drop)),
+        );
+        self
+    }
+}
+
+/// Outcome of replaying one capture record.
+#[derive(Debug)]
+pub enum ReplayOutcome {
+    Decoded,
+    Failed(BinaryError),
+    /// No decoder is registered for the record's type name.
+    Unregistered,
+}
+
+/// Re-run decoding for every record, in order.
+#[must_use]
+pub fn replay(records: &[CaptureRecord], registry: &ReplayRegistry) -> Vec<ReplayOutcome> {
+    records
+        .iter()
+        .map(|record| match registry.decoders.get(&record.type_name) {
+            Some(decode) => match decode(&record.payload) {
+                Ok(()) => ReplayOutcome::Decoded,
+                Err(err) => ReplayOutcome::Failed(err),
+            },
+            None => ReplayOutcome::Unregistered,
+        })
+        .collect()
+}
@@ -0,0 +1,21 @@
+/// Run `f` without reporting its encodes and decodes to the trace sink.
+///
+/// Wrap every [`serialize`](crate::serialize) or
+/// [`decode_full`](crate::decode_full) of secret material, such as signing
+/// key bytes, in this: a sink would otherwise receive a plain copy of the
+/// payload, which [`CaptureWriter`](crate::CaptureWriter) may write to disk.
+/// Only the current thread is affected, and calls nest. Without the `trace`
+/// feature this just calls `f`.
+///
+/// ```rust
+/// use cardano_binary::{Value, encode_value, untraced};
+///
+/// let secret = [7u8; 32];
+/// let cbor = untraced(|| encode_value(&Value::Bytes(secret.to_vec()))).unwrap();
+/// assert_eq!(cbor.len(), 34);
+/// ```
+pub fn untraced<R>(f: impl FnOnce() -> R) -> R {
+    #[cfg(feature = "trace")]
+    let _suppressed = crate::trace::Suppressed::enter();
+    f()
+}
@@ -0,0 +1,280 @@
+#![cfg_attr(test, allow(clippy::unwrap_used))]
+
+use crate::deserialize::decode_full;
+use crate::error::BinaryError;
+use crate::serialize::serialize;
+use ciborium::value::{Integer, Value as BackendValue};
+
+/// A dynamically typed CBOR data item.
+///
+/// Use it to build or inspect CBOR whose shape is not known statically
+/// without depending on the CBOR engine behind this crate: go through
+/// [`encode_value`] and [`decode_value`]. The `TryFrom` conversions to and
+/// from `ciborium::value::Value` only exist to ease migrating code that
+/// still holds backend values, and will change if the backend does.
+///
+/// Integers cover the full CBOR range, `-2^64 ..= 2^64 - 1`. Bignums are not
+/// special-cased: they appear as `Tag(2 | 3, Bytes(..))`. Floats of every
+/// width decode to `f64` and encode in the shortest lossless width.
+///
+/// ```rust
+/// use cardano_binary::{Value, decode_value, encode_value, serialize};
+///
+/// let value = Value::Array(vec![Value::Int(1), Value::Text("a".into())]);
+/// let bytes = encode_value(&value).unwrap();
+/// assert_eq!(bytes, serialize(&(1u8, "a")).unwrap());
+/// assert_eq!(decode_value(&bytes).unwrap(), value);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// Major types 0 and 1.
+    Int(i128),
+    /// Major type 2; indefinite-length strings decode concatenated.
+    Bytes(Vec<u8>),
+    /// Major type 3; indefinite-length strings decode concatenated.
+    Text(String),
+    /// Major type 4.
+    Array(Vec<Value>),
+    /// Major type 5, entries in encoding order (duplicates are kept).
+    Map(Vec<(Value, Value)>),
+    /// Major type 6: a tag number and the tagged item.
+    Tag(u64, Box<Value>),
+    /// Simple values 20 and 21.
+    Bool(bool),
+    /// Simple value 22; `undefined` (23) also decodes to `Null`.
+    Null,
+    /// Major type 7 floats.
+    Float(f64),
+}
+
+impl TryFrom<BackendValue> for Value {
+    type Error = BinaryError;
+
+    fn try_from(value: BackendValue) -> Result<Self, BinaryError> {
+        Ok(match value {
+            BackendValue::Integer(int) => Value::Int(i128::from(int)),
+            BackendValue::Bytes(bytes) => Value::Bytes(bytes),
+            BackendValue::Text(text) => Value::Text(text),
+            BackendValue::Array(items) => Value::Array(
+                items
+                    .into_iter()
+                    .map(Value::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            BackendValue::Map(entries) => Value::Map(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| Ok((Value::try_from(key)?, Value::try_from(value)?)))
+                    .collect::<Result<_, BinaryError>>()?,
+            ),
+            BackendValue::Tag(tag, item) => Value::Tag(tag, Box::new(Value::try_from(*item)?)),
+            BackendValue::Bool(flag) => Value::Bool(flag),
+            BackendValue::Null => Value::Null,
+            BackendValue::Float(float) => Value::Float(float),
+            _ => {
+                return Err(BinaryError::UnrepresentableValue {
+                    reason: "unsupported CBOR data item",
+                });
+            },
+        })
+    }
+}
+
+impl TryFrom<&Value> for BackendValue {
+    type Error = BinaryError;
+
+    fn try_from(value: &Value) -> Result<Self, BinaryError> {
+        Ok(match value {
+            Value::Int(int) => BackendValue::Integer(Integer::try_from(*int).map_err(|_| {
+                BinaryError::UnrepresentableValue {
+                    reason: "integer outside the CBOR range -2^64..2^64",
+                }
+            })?),
+            Value::Bytes(bytes) => BackendValue::Bytes(bytes.clone()),
+            Value::Text(text) => BackendValue::Text(text.clone()),
+            Value::Array(items) => BackendValue::Array(
+                items
+                    .iter()
+                    .map(BackendValue::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            Value::Map(entries) => BackendValue::Map(
+                entries
+                    .iter()
+                    .map(|(key, value)| {
+                        Ok((BackendValue::try_from(key)?, BackendValue::try_from(value)?))
+                    })
+                    .collect::<Result<_, BinaryError>>()?,
+            ),
+            Value::Tag(tag, item) => {
+                BackendValue::Tag(*tag, Box::new(BackendValue::try_from(item.as_ref())?))
+            },
+            Value::Bool(flag) => BackendValue::Bool(*flag),
+            Value::Null => BackendValue::Null,
+            Value::Float(float) => BackendValue::Float(*float),
+        })
+    }
+}
+
+/// Decode one complete CBOR data item into a [`Value`].
+///
+/// # Errors
+///
+/// Returns [`BinaryError::Deserialization`] if the input is not valid CBOR
+/// and [`BinaryError::Leftover`] if bytes follow the data item.
+pub fn decode_value(bytes: &[u8]) -> Result<Value, BinaryError> {
+    Value::try_from(decode_full::<BackendValue>(bytes)?)
+}
+
+/// Encode a [`Value`] as CBOR, with the same encoding [`serialize`] uses for
+/// the equivalent statically typed value.
+///
+/// # Errors
+///
+/// Returns [`BinaryError::UnrepresentableValue`] if an [`Value::Int`] lies
+/// outside the CBOR integer range, and [`BinaryError::Serialization`] if
+/// encoding fails.
+pub fn encode_value(value: &Value) -> Result<Vec<u8>, BinaryError> {
+    serialize(&BackendValue::try_from(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BinaryErrorKind;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    fn roundtrip(value: &Value) {
+        let bytes = encode_value(value).unwrap();
+        assert_eq!(&decode_value(&bytes).unwrap(), value, "{}", hex(&bytes));
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn every_variant_roundtrips() {
+        for value in [
+            Value::Int(0),
+            Value::Int(23),
+            Value::Int(-1),
+            Value::Int(i128::from(u64::MAX)),
+            Value::Int(-1 - i128::from(u64::MAX)),
+            Value::Bytes(vec![]),
+            Value::Bytes(vec![0, 1, 0xff]),
+            Value::Text(String::new()),
+            Value::Text("ada ₳".into()),
+            Value::Array(vec![]),
+            Value::Array(vec![Value::Int(1), Value::Null, Value::Bool(false)]),
+            Value::Map(vec![]),
+            Value::Map(vec![
+                (Value::Int(1), Value::Text("one".into())),
+                (Value::Text("k".into()), Value::Bytes(vec![2])),
+            ]),
+            Value::Tag(24, Box::new(Value::Bytes(vec![0x01]))),
+            Value::Bool(true),
+            Value::Bool(false),
+            Value::Null,
+            Value::Float(1.5),
+            Value::Float(-0.1),
+            Value::Float(f64::INFINITY),
+        ] {
+            roundtrip(&value);
+        }
+    }
+
+    #[test]
+    fn nested_tags_roundtrip() {
+        let value = Value::Tag(
+            258,
+            Box::new(Value::Array(vec![
+                Value::Tag(
+                    121,
+                    Box::new(Value::Tag(24, Box::new(Value::Bytes(vec![0x80])))),
+                ),
+                Value::Map(vec![(
+                    Value::Tag(1, Box::new(Value::Int(1_700_000_000))),
+                    Value::Tag(2, Box::new(Value::Bytes(vec![1, 0, 0, 0, 0, 0, 0, 0, 0]))),
+                )]),
+            ])),
+        );
+        roundtrip(&value);
+    }
+
+    #[test]
+    fn encode_value_matches_serialize() {
+        #[derive(Serialize)]
+        struct Record {
+            id: u64,
+            name: &'static str,
+        }
+
+        assert_eq!(
+            encode_value(&Value::Array(vec![
+                Value::Int(7),
+                Value::Int(-300),
+                Value::Bytes(vec![1, 2]),
+                Value::Array(vec![Value::Bool(true), Value::Null]),
+            ]))
+            .unwrap(),
+            serialize(&(7u8, -300i32, serde_bytes::Bytes::new(&[1, 2]), (true, ()),)).unwrap()
+        );
+        assert_eq!(
+            encode_value(&Value::Map(vec![
+                (Value::Text("id".into()), Value::Int(9)),
+                (Value::Text("name".into()), Value::Text("pool".into())),
+            ]))
+            .unwrap(),
+            serialize(&Record {
+                id: 9,
+                name: "pool"
+            })
+            .unwrap()
+        );
+        let map: BTreeMap<u8, f64> = [(1, 0.5), (2, 1e300)].into_iter().collect();
+        assert_eq!(
+            encode_value(&Value::Map(vec![
+                (Value::Int(1), Value::Float(0.5)),
+                (Value::Int(2), Value::Float(1e300)),
+            ]))
+            .unwrap(),
+            serialize(&map).unwrap()
+        );
+        assert_eq!(
+            encode_value(&Value::Int(i128::from(u64::MAX))).unwrap(),
+            serialize(&u64::MAX).unwrap()
+        );
+    }
+
+    #[test]
+    fn decodes_indefinite_and_undefined_items() {
+        // [_ (_ h'01', h'02'), undefined]
+        let bytes = [0x9f, 0x5f, 0x41, 0x01, 0x41, 0x02, 0xff, 0xf7, 0xff];
+        assert_eq!(
+            decode_value(&bytes).unwrap(),
+            Value::Array(vec![Value::Bytes(vec![1, 2]), Value::Null])
+        );
+    }
+
+    #[test]
+    fn rejects_unrepresentable_and_malformed_input() {
+        let err = encode_value(&Value::Array(vec![Value::Int(1 << 64)])).unwrap_err();
+        assert_eq!(err.kind(), BinaryErrorKind::UnrepresentableValue);
+        assert_eq!(
+            encode_value(&Value::Int(-2 - i128::from(u64::MAX)))
+                .unwrap_err()
+                .kind(),
+            BinaryErrorKind::UnrepresentableValue
+        );
+        assert_eq!(
+            decode_value(&[0x01, 0x02]).unwrap_err().kind(),
+            BinaryErrorKind::TrailingBytes
+        );
+        assert_eq!(
+            decode_value(&[0x42, 0x01]).unwrap_err().kind(),
+            BinaryErrorKind::Eof
+        );
+    }
+}
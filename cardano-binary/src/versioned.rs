@@ -0,0 +1,302 @@
+#![cfg_attr(test, allow(clippy::unwrap_used))]
+
+use crate::deserialize::nested_cbor_value_bytes;
+use crate::error::{BinaryError, ResultExt};
+use crate::fixed::decode_pair;
+use crate::serialize::{encode_nested_cbor, serialize};
+use ciborium::value::Value;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// Encode `value` in a version-tagged envelope: the CBOR array
+/// `[version, #6.24(bytes .cbor payload)]`.
+///
+/// Carrying the payload as nested CBOR lets a reader skip or hand off a
+/// payload whose version it does not understand without decoding it.
+///
+/// ```rust
+/// use cardano_binary::encode_versioned;
+///
+/// let bytes = encode_versioned(2, &(1u8, 2u8)).unwrap();
+/// assert_eq!(bytes, [0x82, 0x02, 0xd8, 0x18, 0x43, 0x82, 0x01, 0x02]);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`BinaryError::Serialization`] if the value cannot be serialized to CBOR.
+pub fn encode_versioned<T: Serialize>(version: u16, value: &T) -> Result<Vec<u8>, BinaryError> {
+    let mut buf = crate::fixed::array_header(2)?;
+    buf.extend_from_slice(&serialize(&version)?);
+    buf.extend_from_slice(&encode_nested_cbor(value)?);
+    Ok(buf)
+}
+
+/// Decode a version-tagged envelope written by [`encode_versioned`].
+///
+/// `dispatch` receives the version and the raw payload bytes and decodes
+/// them, typically by matching on the version and returning
+/// [`BinaryError::UnsupportedVersion`] for versions it does not know. For the
+/// common case of migrating every known version to one type, see
+/// [`VersionRegistry`].
+///
+/// ```rust
+/// use cardano_binary::{BinaryError, decode_full, decode_versioned, encode_versioned};
+///
+/// let bytes = encode_versioned(1, &7u32).unwrap();
+/// let value = decode_versioned(&bytes, |version, payload| match version {
+///     1 => decode_full::<u32>(payload).map(u64::from),
+///     found => Err(BinaryError::UnsupportedVersion { found, supported: 1..=1 }),
+/// });
+/// assert_eq!(value.unwrap(), 7);
+/// ```
+///
+/// # Errors
+///
+/// Returns the errors of [`decode_pair`] if the input is not a 2-array whose
+/// first element fits in a `u16`, [`BinaryError::NestedTag`] or
+/// [`BinaryError::NestedPayload`] if the second element is not tag 24 bytes,
+/// and whatever `dispatch` returns.
+pub fn decode_versioned<T, F>(bytes: &[u8], dispatch: F) -> Result<T, BinaryError>
+where
+    F: FnOnce(u16, &[u8]) -> Result<T, BinaryError>,
+{
+    let (version, payload) = decode_pair::<u16, Value>(bytes).context("versioned envelope")?;
+    let payload = nested_cbor_value_bytes(payload).context("versioned envelope")?;
+    dispatch(version, &payload)
+}
+
+type VersionDecoder<T> = Box<dyn Fn(&[u8]) -> Result<T, BinaryError> + Send + Sync>;
+
+/// Per-version decoders producing one target type, for reading every format
+/// version that was ever written into the current representation.
+///
+/// ```rust
+/// use cardano_binary::{VersionRegistry, decode_full, encode_versioned};
+///
+/// let registry = VersionRegistry::new()
+///     .register(1, |payload| decode_full::<u32>(payload).map(u64::from))
+///     .register(2, decode_full::<u64>);
+///
+/// assert_eq!(registry.decode(&encode_versioned(1, &5u32).unwrap()).unwrap(), 5);
+/// assert_eq!(registry.decode(&encode_versioned(2, &6u64).unwrap()).unwrap(), 6);
+/// ```
+pub struct VersionRegistry<T> {
+    decoders: BTreeMap<u16, VersionDecoder<T>>,
+}
+
+impl<T> VersionRegistry<T> {
+    /// An empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            decoders: BTreeMap::new(),
+        }
+    }
+
+    /// Add the decoder for `version`, replacing any earlier one.
+    #[must_use]
+    pub fn register<F>(mut self, version: u16, decoder: F) -> Self
+    where
+        F: Fn(&[u8]) -> Result<T, BinaryError> + Send + Sync + 'static,
+    {
+        self.decoders.insert(version, Box::new(decoder));
+        self
+    }
+
+    /// The registered versions in ascending order.
+    pub fn versions(&self) -> impl Iterator<Item = u16> + '_ {
+        self.decoders.keys().copied()
+    }
+
+    /// The lowest through the highest registered version. Empty (`1..=0`)
+    /// when nothing is registered.
+    #[must_use]
+    pub fn supported(&self) -> RangeInclusive<u16> {
+        match (
+            self.decoders.keys().next(),
+            self.decoders.keys().next_back(),
+        ) {
+            (Some(&min), Some(&max)) => min..=max,
+            _ => RangeInclusive::new(1, 0),
+        }
+    }
+
+    /// Decode an envelope with the decoder registered for its version.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BinaryError::UnsupportedVersion`] if no decoder is
+    /// registered for the version, the decoder's error wrapped in a
+    /// [`BinaryError::Context`] naming the version if the payload fails to
+    /// decode, and otherwise the same errors as [`decode_versioned`].
+    pub fn decode(&self, bytes: &[u8]) -> Result<T, BinaryError> {
+        decode_versioned(bytes, |version, payload| {
+            let decoder =
+                self.decoders
+                    .get(&version)
+                    .ok_or_else(|| BinaryError::UnsupportedVersion {
+                        found: version,
+                        supported: self.supported(),
+                    })?;
+            decoder(payload).context(format!("version {version} payload"))
+        })
+    }
+}
+
+impl<T> Default for VersionRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for VersionRegistry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VersionRegistry")
+            .field("versions", &self.decoders.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode_full;
+    use serde::Deserialize;
+
+    /// The current representation.
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Params {
+        slot_length_ms: u64,
+        label: String,
+    }
+
+    /// Version 1 stored whole seconds and had no label.
+    #[derive(Serialize, Deserialize)]
+    struct ParamsV1 {
+        slot_length_secs: u32,
+    }
+
+    impl From<ParamsV1> for Params {
+        fn from(old: ParamsV1) -> Self {
+            Params {
+                slot_length_ms: u64::from(old.slot_length_secs) * 1_000,
+                label: String::new(),
+            }
+        }
+    }
+
+    fn registry() -> VersionRegistry<Params> {
+        VersionRegistry::new()
+            .register(1, |payload| {
+                decode_full::<ParamsV1>(payload).map(Params::from)
+            })
+            .register(2, decode_full::<Params>)
+    }
+
+    #[test]
+    fn migrates_every_version_to_the_current_type() {
+        let registry = registry();
+        let v1 = encode_versioned(
+            1,
+            &ParamsV1 {
+                slot_length_secs: 20,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            registry.decode(&v1).unwrap(),
+            Params {
+                slot_length_ms: 20_000,
+                label: String::new(),
+            }
+        );
+
+        let current = Params {
+            slot_length_ms: 1_000,
+            label: "preview".into(),
+        };
+        let v2 = encode_versioned(2, &current).unwrap();
+        assert_eq!(registry.decode(&v2).unwrap(), current);
+    }
+
+    #[test]
+    fn unknown_version_reports_supported_range() {
+        let bytes = encode_versioned(7, &0u8).unwrap();
+        let err = registry().decode(&bytes).unwrap_err();
+        assert!(matches!(
+            &err,
+            BinaryError::UnsupportedVersion { found: 7, supported } if *supported == (1..=2)
+        ));
+        assert_eq!(
+            err.to_string(),
+            "unsupported format version 7, supported versions are 1..=2"
+        );
+
+        let empty = VersionRegistry::<Params>::new();
+        assert!(empty.supported().is_empty());
+        assert!(matches!(
+            empty.decode(&bytes).unwrap_err(),
+            BinaryError::UnsupportedVersion { found: 7, .. }
+        ));
+    }
+
+    #[test]
+    fn wire_bytes_round_trip() {
+        // [1000, 24(h'82 01 63 61 62 63')]: version 1000 takes a two-byte
+        // argument, the payload is the CBOR of (1, "abc").
+        let expected = [
+            0x82, 0x19, 0x03, 0xe8, 0xd8, 0x18, 0x46, 0x82, 0x01, 0x63, b'a', b'b', b'c',
+        ];
+        let bytes = encode_versioned(1000, &(1u8, "abc")).unwrap();
+        assert_eq!(bytes, expected);
+
+        let decoded = decode_versioned(&bytes, |version, payload| {
+            assert_eq!(version, 1000);
+            assert_eq!(payload, &expected[7..]);
+            decode_full::<(u8, String)>(payload)
+        })
+        .unwrap();
+        assert_eq!(decoded, (1, "abc".to_owned()));
+        assert_eq!(encode_versioned(1000, &decoded).unwrap(), expected);
+    }
+
+    #[test]
+    fn payload_errors_name_the_version() {
+        let bytes = encode_versioned(2, &"not params").unwrap();
+        let err = registry().decode(&bytes).unwrap_err();
+        assert!(err.to_string().starts_with("version 2 payload: "));
+        assert!(matches!(
+            err.root_cause(),
+            BinaryError::Deserialization { .. }
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_envelopes() {
+        // Payload not wrapped in tag 24.
+        let untagged = crate::encode_pair(&1u16, &serde_bytes::Bytes::new(&[0x01])).unwrap();
+        let err = registry().decode(&untagged).unwrap_err();
+        assert!(matches!(
+            err.root_cause(),
+            BinaryError::NestedTag { found: None, .. }
+        ));
+
+        // Version does not fit in a u16.
+        let wide = crate::encode_pair(&70_000u32, &Value::Tag(24, Box::new(Value::Bytes(vec![0]))))
+            .unwrap();
+        assert!(registry().decode(&wide).is_err());
+
+        // Three elements instead of two.
+        let triple = crate::encode_triple(&1u16, &0u8, &0u8).unwrap();
+        assert!(matches!(
+            registry().decode(&triple).unwrap_err().root_cause(),
+            BinaryError::WrongArity {
+                expected: 2,
+                actual: 3,
+                ..
+            }
+        ));
+    }
+}
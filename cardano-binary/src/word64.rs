@@ -0,0 +1,184 @@
+#![cfg_attr(test, allow(clippy::unwrap_used))]
+
+use crate::error::BinaryError;
+use ciborium_ll::{Decoder, Header};
+use std::io::Cursor;
+
+/// Encode `value` as a CBOR unsigned integer in its shortest form.
+///
+/// This is Haskell's `encodeWord64`, the encoding of KES periods and
+/// operational certificate counters: one byte up to 23, then 2, 3, 5 or 9
+/// bytes. It is what serde's `u64` produces through [`serialize`](crate::serialize),
+/// and never a fixed-width byte string.
+///
+/// ```rust
+/// use cardano_binary::encode_word64_canonical;
+///
+/// assert_eq!(encode_word64_canonical(23), [0x17]);
+/// assert_eq!(encode_word64_canonical(24), [0x18, 0x18]);
+/// assert_eq!(encode_word64_canonical(65_536), [0x1a, 0x00, 0x01, 0x00, 0x00]);
+/// ```
+#[must_use]
+pub fn encode_word64_canonical(value: u64) -> Vec<u8> {
+    let width = minimal_width(value);
+    let additional = match width {
+        1 => return vec![value as u8],
+        2 => 24,
+        3 => 25,
+        5 => 26,
+        _ => 27,
+    };
+    let mut bytes = Vec::with_capacity(width);
+    bytes.push(additional);
+    bytes.extend_from_slice(&value.to_be_bytes()[9 - width..]);
+    bytes
+}
+
+/// Decode a CBOR unsigned integer, in any width, into a `u64`.
+///
+/// Accepts everything Haskell's `decodeWord64` accepts, including integers
+/// written wider than necessary; use [`decode_word64_canonical`] to require
+/// the shortest form.
+///
+/// # Errors
+///
+/// Returns [`BinaryError::Deserialization`] if the input is not a CBOR
+/// unsigned integer (a negative integer, a bignum or a byte string are all
+/// rejected) and [`BinaryError::Leftover`] if bytes follow it.
+pub fn decode_word64(bytes: &[u8]) -> Result<u64, BinaryError> {
+    decode_word64_with(bytes, false)
+}
+
+/// [`decode_word64`] that additionally rejects integers not written in their
+/// shortest form, as [`encode_word64_canonical`] writes them.
+///
+/// ```rust
+/// use cardano_binary::{BinaryError, decode_word64, decode_word64_canonical};
+///
+/// // 1 written in two bytes
+/// assert_eq!(decode_word64(&[0x18, 0x01]).unwrap(), 1);
+/// let err = decode_word64_canonical(&[0x18, 0x01]).unwrap_err();
+/// assert!(matches!(err, BinaryError::NonMinimalInteger { value: 1, width: 2, minimal: 1 }));
+/// ```
+///
+/// # Errors
+///
+/// Returns [`BinaryError::NonMinimalInteger`] for a longer encoding than
+/// necessary, otherwise the same errors as [`decode_word64`].
+pub fn decode_word64_canonical(bytes: &[u8]) -> Result<u64, BinaryError> {
+    decode_word64_with(bytes, true)
+}
+
+fn decode_word64_with(bytes: &[u8], canonical: bool) -> Result<u64, BinaryError> {
+    let mut decoder = Decoder::from(Cursor::new(bytes));
+    let header = decoder.pull().map_err(ciborium::de::Error::from)?;
+    let Header::Positive(value) = header else {
+        return Err(ciborium::de::Error::Semantic(
+            Some(0),
+            format!("`Word64` expects a CBOR unsigned integer, found {header:?}"),
+        )
+        .into());
+    };
+    let width = decoder.offset();
+    let minimal = minimal_width(value);
+    if canonical && width != minimal {
+        return Err(BinaryError::NonMinimalInteger {
+            value,
+            width,
+            minimal,
+        });
+    }
+    if width < bytes.len() {
+        return Err(BinaryError::leftover("Word64", bytes, width));
+    }
+    Ok(value)
+}
+
+/// Bytes in the shortest CBOR encoding of `value`, header included.
+const fn minimal_width(value: u64) -> usize {
+    match value {
+        0..=23 => 1,
+        24..=0xff => 2,
+        0x100..=0xffff => 3,
+        0x1_0000..=0xffff_ffff => 5,
+        _ => 9,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode_full, serialize};
+
+    /// Boundary values and their canonical encodings.
+    const GOLDEN: [(u64, &str); 11] = [
+        (0, "00"),
+        (23, "17"),
+        (24, "1818"),
+        (255, "18ff"),
+        (256, "190100"),
+        (65_535, "19ffff"),
+        (65_536, "1a00010000"),
+        (u32::MAX as u64, "1affffffff"),
+        (1 << 32, "1b0000000100000000"),
+        (u64::MAX - 1, "1bfffffffffffffffe"),
+        (u64::MAX, "1bffffffffffffffff"),
+    ];
+
+    #[test]
+    fn golden_boundary_encodings() {
+        for (value, encoding) in GOLDEN {
+            let bytes = hex::decode(encoding).unwrap();
+            assert_eq!(encode_word64_canonical(value), bytes, "{value}");
+            assert_eq!(serialize(&value).unwrap(), bytes, "{value}");
+            assert_eq!(decode_word64_canonical(&bytes).unwrap(), value);
+            assert_eq!(decode_word64(&bytes).unwrap(), value);
+            assert_eq!(decode_full::<u64>(&bytes).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn wider_encodings_are_only_accepted_outside_canonical_mode() {
+        for (encoding, value, width) in [
+            ("1817", 23, 2),
+            ("1900ff", 255, 3),
+            ("1a0000ffff", 65_535, 5),
+            ("1b00000000ffffffff", u64::from(u32::MAX), 9),
+            ("1b0000000000000000", 0, 9),
+        ] {
+            let bytes = hex::decode(encoding).unwrap();
+            assert_eq!(decode_word64(&bytes).unwrap(), value);
+            let err = decode_word64_canonical(&bytes).unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    BinaryError::NonMinimalInteger { value: v, width: w, minimal }
+                        if v == value && w == width && minimal == minimal_width(value)
+                ),
+                "{encoding}: {err}"
+            );
+            assert_eq!(err.kind(), crate::BinaryErrorKind::NonCanonical);
+        }
+    }
+
+    #[test]
+    fn other_items_are_rejected() {
+        for encoding in [
+            // Period 1 as an 8-byte big-endian byte string
+            "480000000000000001",
+            // -1
+            "20",
+            // 2^64 as a bignum
+            "c249010000000000000000",
+            // Truncated
+            "1a0001",
+            "",
+        ] {
+            let bytes = hex::decode(encoding).unwrap();
+            assert!(decode_word64(&bytes).is_err(), "{encoding}");
+            assert!(decode_word64_canonical(&bytes).is_err(), "{encoding}");
+        }
+        let err = decode_word64(&[0x01, 0x02]).unwrap_err();
+        assert!(err.is_trailing_bytes());
+    }
+}
@@ -0,0 +1,184 @@
+//! Spans recorded by `decode_annotated` point at the exact bytes each
+//! `Spanned` field was decoded from.
+
+use std::sync::Arc;
+
+use blake2::Blake2b;
+use blake2::digest::consts::U32;
+use cardano_binary::{
+    Annotated, BinaryErrorKind, ByteSpan, Spanned, decode_annotated, decode_full,
+    decode_full_borrowed, serialize,
+};
+use serde::{Deserialize, Serialize};
+
+type Blake2b256 = Blake2b<U32>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Body {
+    fee: u64,
+    inputs: Vec<u32>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Block {
+    slot: u64,
+    body: Spanned<Body>,
+    note: Option<String>,
+}
+
+fn body() -> Body {
+    Body {
+        fee: 170_000,
+        inputs: vec![1, 24, 70_000],
+    }
+}
+
+fn block() -> Block {
+    Block {
+        slot: 12_345_678,
+        body: Spanned::new(body(), ByteSpan { offset: 0, len: 0 }),
+        note: Some("genesis".into()),
+    }
+}
+
+#[test]
+fn nested_spans_re_decode_to_the_field() {
+    let bytes: Arc<[u8]> = serialize(&block()).expect("serialise").into();
+    let annotated: Annotated<Block> = decode_annotated(Arc::clone(&bytes)).expect("decode");
+
+    assert_eq!(annotated.value(), &block());
+    assert_eq!(annotated.original_bytes(), &bytes[..]);
+    assert!(Arc::ptr_eq(annotated.input(), &bytes));
+
+    let span = annotated.value().body.span();
+    let body_bytes = annotated.bytes_of(span).expect("span inside input");
+    assert_eq!(
+        decode_full::<Body>(body_bytes).expect("re-decode span"),
+        body()
+    );
+    assert_eq!(body_bytes, serialize(&body()).expect("serialise"));
+
+    let inner = annotated
+        .annotate(&annotated.value().body)
+        .expect("span inside input");
+    assert_eq!(inner.value(), &body());
+    assert_eq!(inner.span(), span);
+    assert_eq!(inner.original_bytes(), body_bytes);
+    assert_eq!(
+        inner.hash_original::<Blake2b256>(),
+        <Blake2b256 as blake2::Digest>::digest(body_bytes)
+    );
+}
+
+#[test]
+fn only_canonical_encodings_hash_like_a_re_serialisation() {
+    // {"slot": 1, "body": {"fee": 5, "inputs": []}, "note": null}, with the
+    // fee written canonically (0x05) or in a needless one-byte argument
+    // (0x18 0x05).
+    let encode = |fee: &[u8]| -> Vec<u8> {
+        let mut bytes = vec![0xa3, 0x64];
+        bytes.extend_from_slice(b"slot");
+        bytes.push(0x01);
+        bytes.push(0x64);
+        bytes.extend_from_slice(b"body");
+        bytes.extend_from_slice(&[0xa2, 0x63]);
+        bytes.extend_from_slice(b"fee");
+        bytes.extend_from_slice(fee);
+        bytes.push(0x66);
+        bytes.extend_from_slice(b"inputs");
+        bytes.push(0x80);
+        bytes.push(0x64);
+        bytes.extend_from_slice(b"note");
+        bytes.push(0xf6);
+        bytes
+    };
+    let value = Body {
+        fee: 5,
+        inputs: vec![],
+    };
+    let re_serialised =
+        <Blake2b256 as blake2::Digest>::digest(serialize(&value).expect("serialise"));
+
+    for (fee, canonical) in [(&[0x05][..], true), (&[0x18, 0x05][..], false)] {
+        let block = decode_annotated::<Block>(encode(fee)).expect("decode");
+        let body = block
+            .annotate(&block.value().body)
+            .expect("span inside input");
+        assert_eq!(body.value(), &value);
+        assert_eq!(
+            body.hash_original::<Blake2b256>() == re_serialised,
+            canonical,
+            "fee encoded as {fee:02x?}"
+        );
+        // The span still hashes the bytes that were actually received.
+        assert_eq!(
+            body.hash_original::<Blake2b256>(),
+            <Blake2b256 as blake2::Digest>::digest(&encode(fee)[12..12 + body.span().len])
+        );
+    }
+}
+
+#[test]
+fn offsets_follow_variable_width_integers() {
+    // [n, "x"] where n takes 1, 2, 3, 5 or 9 bytes.
+    for (n, width) in [
+        (0u64, 1),
+        (23, 1),
+        (24, 2),
+        (255, 2),
+        (256, 3),
+        (65_535, 3),
+        (65_536, 5),
+        (u64::from(u32::MAX), 5),
+        (u64::from(u32::MAX) + 1, 9),
+        (u64::MAX, 9),
+    ] {
+        let bytes = serialize(&(n, "x")).expect("serialise");
+        let decoded = decode_annotated::<(u64, Spanned<String>)>(bytes).expect("decode");
+        let (value, text) = decoded.value();
+        assert_eq!(*value, n);
+        assert_eq!(
+            text.span(),
+            ByteSpan {
+                offset: 1 + width,
+                len: 2
+            },
+            "{n}"
+        );
+        assert_eq!(decoded.bytes_of(text.span()), Some(&[0x61, b'x'][..]));
+    }
+}
+
+#[test]
+fn spans_need_an_offset_tracking_decoder() {
+    let bytes = serialize(&block()).expect("serialise");
+    let err = decode_full::<Block>(&bytes).expect_err("decode_full cannot track spans");
+    assert_eq!(err.kind(), BinaryErrorKind::Semantic);
+
+    let borrowed: Block = decode_full_borrowed(&bytes).expect("decode");
+    let span = borrowed.body.span();
+    assert_eq!(&bytes[span.range()], serialize(&body()).expect("serialise"));
+}
+
+#[test]
+fn spans_outside_the_input_are_refused() {
+    let annotated =
+        decode_annotated::<Block>(serialize(&block()).expect("serialise")).expect("decode");
+    let len = annotated.input().len();
+    let outside = Spanned::new(
+        body(),
+        ByteSpan {
+            offset: len - 1,
+            len: 2,
+        },
+    );
+    assert!(annotated.annotate(&outside).is_none());
+    assert!(
+        annotated
+            .bytes_of(ByteSpan {
+                offset: usize::MAX,
+                len: 2
+            })
+            .is_none()
+    );
+}
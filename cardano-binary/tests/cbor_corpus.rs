@@ -0,0 +1,91 @@
+//! Differential checks against the shared `cborg` corpus in
+//! `tests/data/cbor_corpus.json`.
+//!
+//! Every entry's hex is decoded to a `Value`, compared with its description
+//! and re-encoded; canonical entries must reproduce the hex byte for byte and
+//! `is_canonical` must agree with each entry's `canonical` flag. New entries
+//! come from `cargo run -p cardano-binary --features test-util --bin
+//! cbor_corpus_gen -- <count> <seed>`, encoded on the Haskell side.
+
+use std::collections::HashSet;
+
+use cardano_binary::corpus::{Corpus, CorpusValue};
+
+const CORPUS: &str = include_str!("data/cbor_corpus.json");
+
+fn corpus() -> Corpus {
+    Corpus::from_json(CORPUS).expect("tests/data/cbor_corpus.json follows the corpus schema")
+}
+
+#[test]
+fn every_corpus_entry_matches_cborg() {
+    let report = corpus().check();
+    println!("cbor corpus: {report}");
+    assert!(report.mismatches.is_empty(), "{report}");
+    assert_eq!(
+        report.pending, 0,
+        "pending entries belong in a generator run, not the corpus"
+    );
+}
+
+#[test]
+fn corpus_names_are_unique() {
+    let corpus = corpus();
+    let mut seen = HashSet::new();
+    for entry in &corpus.entries {
+        assert!(
+            seen.insert(entry.name.as_str()),
+            "duplicate entry {}",
+            entry.name
+        );
+    }
+}
+
+#[test]
+fn corpus_covers_the_seed_categories() {
+    fn contains(value: &CorpusValue, pred: &dyn Fn(&CorpusValue) -> bool) -> bool {
+        pred(value)
+            || match value {
+                CorpusValue::Array(items) => items.iter().any(|item| contains(item, pred)),
+                CorpusValue::Map(entries) => entries
+                    .iter()
+                    .any(|(key, value)| contains(key, pred) || contains(value, pred)),
+                CorpusValue::Tag(_, inner) => contains(inner, pred),
+                _ => false,
+            }
+    }
+
+    let corpus = corpus();
+    let count = |pred: &dyn Fn(&CorpusValue) -> bool| {
+        corpus
+            .entries
+            .iter()
+            .filter(|entry| contains(&entry.value, pred))
+            .count()
+    };
+
+    assert!(corpus.entries.len() >= 200);
+    for boundary in [
+        "23",
+        "24",
+        "255",
+        "256",
+        "4294967295",
+        "4294967296",
+        "4294967297",
+    ] {
+        assert!(
+            count(&|v| matches!(v, CorpusValue::Int(n) if n == boundary)) > 0,
+            "missing integer boundary {boundary}"
+        );
+    }
+    assert!(count(&|v| matches!(v, CorpusValue::Int(n) if n.starts_with('-'))) >= 20);
+    assert!(count(&|v| matches!(v, CorpusValue::Float16(_))) >= 10);
+    assert!(count(&|v| matches!(v, CorpusValue::Float32(_))) >= 10);
+    assert!(count(&|v| matches!(v, CorpusValue::Float64(_))) >= 10);
+    assert!(count(&|v| matches!(v, CorpusValue::Tag(24, _))) >= 10);
+    assert!(
+        corpus.entries.iter().any(|entry| !entry.canonical),
+        "the corpus should pin non-canonical cborg encodings too"
+    );
+}
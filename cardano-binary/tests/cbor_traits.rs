@@ -0,0 +1,130 @@
+//! `serialize` and `decode_full` now go through `ToCbor` / `FromCbor`. Types
+//! relying on the blanket `serde` impls must produce the bytes pinned under
+//! `tests/golden/` before the change, and a type may give CBOR and JSON
+//! different shapes.
+
+use cardano_binary::{
+    FromCbor, ToCbor, bytes_or_hex, decode_full, decode_full_borrowed, serialize, serialize_strict,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct TestStruct {
+    name: String,
+    value: u32,
+    flag: bool,
+}
+
+fn golden(name: &str) -> Vec<u8> {
+    std::fs::read(format!("tests/golden/{name}.cbor")).expect("golden file exists")
+}
+
+fn same_bytes_as_before<T>(value: &T, name: &str)
+where
+    T: ToCbor + FromCbor + PartialEq + Debug,
+{
+    let expected = golden(name);
+    assert_eq!(serialize(value).expect("serialize"), expected, "{name}");
+    assert_eq!(serialize_strict(value).expect("strict"), expected, "{name}");
+    assert_eq!(value.to_cbor().expect("to_cbor"), expected, "{name}");
+    assert_eq!(
+        &decode_full::<T>(&expected).expect("decode"),
+        value,
+        "{name}"
+    );
+    assert_eq!(
+        &T::decode_cbor(&expected).expect("decode_cbor"),
+        value,
+        "{name}"
+    );
+}
+
+#[test]
+fn blanket_path_keeps_golden_bytes() {
+    same_bytes_as_before(&42u64, "u64_42");
+    same_bytes_as_before(&-42i32, "negative_int");
+    same_bytes_as_before(&"hello".to_string(), "string");
+    same_bytes_as_before(&vec![1u8, 2, 3], "array_1_2_3");
+    same_bytes_as_before(&Some(42u32), "option_some");
+    same_bytes_as_before(&None::<u32>, "option_none");
+    same_bytes_as_before(&(1u8, 2u8, 3u8), "tuple");
+    same_bytes_as_before(
+        &TestStruct {
+            name: "test".to_string(),
+            value: 100,
+            flag: true,
+        },
+        "struct",
+    );
+}
+
+#[test]
+fn unsized_values_serialize() {
+    let text: &str = "hello";
+    assert_eq!(serialize(text).expect("serialize str"), golden("string"));
+    let slice: &[u8] = &[1, 2, 3];
+    assert_eq!(
+        serialize(slice).expect("serialize slice"),
+        golden("array_1_2_3")
+    );
+}
+
+/// Hex in JSON, a byte string in CBOR.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct VerificationKey(#[serde(with = "bytes_or_hex")] Vec<u8>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Registration {
+    pool: String,
+    vrf: VerificationKey,
+}
+
+#[test]
+fn cbor_and_json_forms_diverge() {
+    let key = VerificationKey(vec![0xde, 0xad, 0xbe, 0xef]);
+
+    // A byte string, where a plain `Vec<u8>` would be an array of integers.
+    let cbor = [0x44, 0xde, 0xad, 0xbe, 0xef];
+    assert_eq!(serialize(&key).expect("cbor"), cbor);
+    assert_ne!(serialize(&key.0).expect("cbor"), cbor);
+    assert_eq!(decode_full::<VerificationKey>(&cbor).expect("decode"), key);
+    assert_eq!(
+        decode_full_borrowed::<VerificationKey>(&cbor).expect("borrowed decode"),
+        key
+    );
+
+    let json = serde_json::to_string(&key).expect("json");
+    assert_eq!(json, "\"deadbeef\"");
+    assert_eq!(
+        serde_json::from_str::<VerificationKey>(&json).expect("parse"),
+        key
+    );
+
+    let registration = Registration {
+        pool: "pool1".to_string(),
+        vrf: key,
+    };
+    assert_eq!(
+        serde_json::to_string(&registration).expect("json"),
+        r#"{"pool":"pool1","vrf":"deadbeef"}"#
+    );
+    let cbor = serialize(&registration).expect("cbor");
+    assert!(
+        cbor.windows(5)
+            .any(|window| window == [0x44, 0xde, 0xad, 0xbe, 0xef])
+    );
+    assert_eq!(
+        decode_full::<Registration>(&cbor).expect("decode"),
+        registration
+    );
+}
+
+#[test]
+fn bytes_or_hex_rejects_mismatched_forms() {
+    assert!(serde_json::from_str::<VerificationKey>("\"abc\"").is_err());
+    assert!(serde_json::from_str::<VerificationKey>("\"xyz0\"").is_err());
+    // Hex text is not accepted in CBOR.
+    let text = serialize("deadbeef").expect("text");
+    assert!(decode_full::<VerificationKey>(&text).is_err());
+}
@@ -1,10 +1,17 @@
 //! Golden tests for CBOR serialization format stability
 //!
 //! These tests ensure that the CBOR encoding format remains stable across versions.
-//! If these tests fail after a code change, it indicates a breaking change in serialization format.
-
-use cardano_binary::{decode_full, serialize};
+//! Expected encodings live under `tests/golden/` as `.cbor` files with a `.diag`
+//! rendering next to each. If these tests fail after a code change, it indicates a
+//! breaking change in serialization format; rerun with `UPDATE_GOLDEN=1` only if the
+//! change is intended, and review the `.diag` diff.
+
+use cardano_binary::golden::assert_golden_cbor;
+use cardano_binary::{CanonicalCbor, canonicalize, decode_full};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Debug;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 struct TestStruct {
@@ -13,151 +20,83 @@ struct TestStruct {
     flag: bool,
 }
 
-/// Test that a simple u64 serializes to the expected CBOR bytes
-#[test]
-fn golden_u64_42() {
-    let value: u64 = 42;
-    let bytes = serialize(&value).expect("serialization failed");
+/// Pin the encoding of `value` to `tests/golden/<name>.cbor` and check it decodes back.
+fn golden_roundtrip<T>(value: &T, name: &str)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let path = format!("tests/golden/{name}.cbor");
+    assert_golden_cbor(value, &path);
 
-    // CBOR encoding of unsigned integer 42 is: 0x18, 0x2a
-    assert_eq!(bytes, vec![0x18, 0x2a], "CBOR format changed for u64");
+    let bytes = std::fs::read(&path).expect("golden file exists");
+    let decoded: T = decode_full(&bytes).expect("deserialization failed");
+    assert_eq!(
+        &decoded, value,
+        "{name}: golden bytes decode to a different value"
+    );
+}
 
-    // Verify roundtrip
-    let decoded: u64 = decode_full(&bytes).expect("deserialization failed");
-    assert_eq!(decoded, 42);
+/// Unsigned integer 42 needs a one-byte argument (0x18 0x2a)
+#[test]
+fn golden_u64_42() {
+    golden_roundtrip(&42u64, "u64_42");
 }
 
-/// Test that a small u64 serializes to the expected CBOR bytes
+/// Small unsigned integers are encoded inline
 #[test]
 fn golden_u64_small() {
-    let value: u64 = 10;
-    let bytes = serialize(&value).expect("serialization failed");
-
-    // CBOR encoding of unsigned integer 10 is: 0x0a
-    assert_eq!(bytes, vec![0x0a], "CBOR format changed for small u64");
-
-    let decoded: u64 = decode_full(&bytes).expect("deserialization failed");
-    assert_eq!(decoded, 10);
+    golden_roundtrip(&10u64, "u64_small");
 }
 
-/// Test that a simple string serializes to the expected CBOR bytes
+/// Strings encode as definite-length text
 #[test]
 fn golden_string() {
-    let value = "hello";
-    let bytes = serialize(&value).expect("serialization failed");
-
-    // CBOR encoding of "hello" is:
-    // 0x65 (text string of length 5)
-    // followed by "hello" in UTF-8
-    assert_eq!(bytes[0], 0x65, "CBOR format changed for string length");
-    assert_eq!(
-        &bytes[1..],
-        b"hello",
-        "CBOR format changed for string content"
-    );
-
-    let decoded: String = decode_full(&bytes).expect("deserialization failed");
-    assert_eq!(decoded, "hello");
+    golden_roundtrip(&"hello".to_string(), "string");
 }
 
-/// Test that an empty array serializes to the expected CBOR bytes
+/// Empty arrays encode as 0x80
 #[test]
 fn golden_empty_array() {
-    let value: Vec<u32> = vec![];
-    let bytes = serialize(&value).expect("serialization failed");
-
-    // CBOR encoding of empty array is: 0x80
-    assert_eq!(bytes, vec![0x80], "CBOR format changed for empty array");
-
-    let decoded: Vec<u32> = decode_full(&bytes).expect("deserialization failed");
-    assert_eq!(decoded, vec![]);
+    golden_roundtrip(&Vec::<u32>::new(), "empty_array");
 }
 
-/// Test that a simple array serializes to the expected CBOR bytes
+/// Vectors encode as definite-length arrays
 #[test]
 fn golden_array_1_2_3() {
-    let value: Vec<u8> = vec![1, 2, 3];
-    let bytes = serialize(&value).expect("serialization failed");
-
-    // CBOR encoding of [1, 2, 3] is:
-    // 0x83 (array of length 3)
-    // 0x01, 0x02, 0x03 (the three integers)
-    assert_eq!(
-        bytes,
-        vec![0x83, 0x01, 0x02, 0x03],
-        "CBOR format changed for simple array"
-    );
-
-    let decoded: Vec<u8> = decode_full(&bytes).expect("deserialization failed");
-    assert_eq!(decoded, vec![1, 2, 3]);
+    golden_roundtrip(&vec![1u8, 2, 3], "array_1_2_3");
 }
 
-/// Test that None serializes to the expected CBOR bytes
+/// None encodes as null
 #[test]
 fn golden_option_none() {
-    let value: Option<u32> = None;
-    let bytes = serialize(&value).expect("serialization failed");
-
-    // CBOR encoding of null is: 0xf6
-    assert_eq!(bytes, vec![0xf6], "CBOR format changed for None");
-
-    let decoded: Option<u32> = decode_full(&bytes).expect("deserialization failed");
-    assert_eq!(decoded, None);
+    golden_roundtrip(&None::<u32>, "option_none");
 }
 
-/// Test that Some(value) serializes to the expected CBOR bytes
+/// Some(value) encodes as the bare value
 #[test]
 fn golden_option_some() {
-    let value: Option<u32> = Some(42);
-    let bytes = serialize(&value).expect("serialization failed");
-
-    // CBOR encoding of Some(42) is just the encoding of 42: 0x18, 0x2a
-    assert_eq!(bytes, vec![0x18, 0x2a], "CBOR format changed for Some");
-
-    let decoded: Option<u32> = decode_full(&bytes).expect("deserialization failed");
-    assert_eq!(decoded, Some(42));
+    golden_roundtrip(&Some(42u32), "option_some");
 }
 
-/// Test that true serializes to the expected CBOR bytes
+/// `true` encodes as simple value 0xf5
 #[test]
 fn golden_bool_true() {
-    let value = true;
-    let bytes = serialize(&value).expect("serialization failed");
-
-    // CBOR encoding of true is: 0xf5
-    assert_eq!(bytes, vec![0xf5], "CBOR format changed for true");
-
-    let decoded: bool = decode_full(&bytes).expect("deserialization failed");
-    assert!(decoded);
+    golden_roundtrip(&true, "bool_true");
 }
 
-/// Test that false serializes to the expected CBOR bytes
+/// `false` encodes as simple value 0xf4
 #[test]
 fn golden_bool_false() {
-    let value = false;
-    let bytes = serialize(&value).expect("serialization failed");
-
-    // CBOR encoding of false is: 0xf4
-    assert_eq!(bytes, vec![0xf4], "CBOR format changed for false");
-
-    let decoded: bool = decode_full(&bytes).expect("deserialization failed");
-    assert!(!decoded);
+    golden_roundtrip(&false, "bool_false");
 }
 
-/// Test that byte arrays serialize with the correct CBOR type
+/// `Vec<u8>` without `serde_bytes` encodes as an array of integers, not a byte string
 #[test]
 fn golden_bytes() {
-    let value = vec![0xde, 0xad, 0xbe, 0xef];
-    let bytes = serialize(&value).expect("serialization failed");
-
-    // Should start with 0x84 (array of 4 elements) since Vec<u8> serializes as array
-    assert_eq!(bytes[0], 0x84, "CBOR format changed for byte array");
-
-    let decoded: Vec<u8> = decode_full(&bytes).expect("deserialization failed");
-    assert_eq!(decoded, vec![0xde, 0xad, 0xbe, 0xef]);
+    golden_roundtrip(&vec![0xdeu8, 0xad, 0xbe, 0xef], "bytes");
 }
 
-/// Test that a struct serializes to a consistent CBOR map
+/// Structs encode as maps keyed by field name
 #[test]
 fn golden_struct() {
     let value = TestStruct {
@@ -165,49 +104,48 @@ fn golden_struct() {
         value: 100,
         flag: true,
     };
-    let bytes = serialize(&value).expect("serialization failed");
-
-    // Struct should serialize as CBOR map (starts with 0xa3 for map of 3 entries)
-    assert_eq!(
-        bytes[0], 0xa3,
-        "CBOR format changed for struct (should be map)"
-    );
-
-    // Verify roundtrip
-    let decoded: TestStruct = decode_full(&bytes).expect("deserialization failed");
-    assert_eq!(decoded, value);
+    golden_roundtrip(&value, "struct");
 }
 
-/// Test that negative integers serialize correctly
+/// Negative integers use major type 1 (-42 is 0x38 0x29)
 #[test]
 fn golden_negative_int() {
-    let value: i32 = -42;
-    let bytes = serialize(&value).expect("serialization failed");
-
-    // CBOR encoding of -42 is: 0x38, 0x29 (negative integer 41, which represents -42)
-    assert_eq!(
-        bytes,
-        vec![0x38, 0x29],
-        "CBOR format changed for negative integer"
-    );
-
-    let decoded: i32 = decode_full(&bytes).expect("deserialization failed");
-    assert_eq!(decoded, -42);
+    golden_roundtrip(&-42i32, "negative_int");
 }
 
-/// Test that tuples serialize as CBOR arrays
+/// Tuples encode as arrays
 #[test]
 fn golden_tuple() {
-    let value = (1u8, 2u8, 3u8);
-    let bytes = serialize(&value).expect("serialization failed");
+    golden_roundtrip(&(1u8, 2u8, 3u8), "tuple");
+}
 
-    // Tuple should serialize as array: 0x83 (array of 3), 0x01, 0x02, 0x03
-    assert_eq!(
-        bytes,
-        vec![0x83, 0x01, 0x02, 0x03],
-        "CBOR format changed for tuple"
-    );
+/// Test that `CanonicalCbor` hides `HashMap` insertion order
+#[test]
+fn golden_canonical_hashmap_insertion_order() {
+    let keys = ["delta", "alpha", "charlie", "bravo", "echo"];
+
+    let mut forward: HashMap<String, u64> = HashMap::new();
+    for (i, key) in keys.iter().enumerate() {
+        forward.insert((*key).to_string(), i as u64);
+    }
+    let mut backward: HashMap<String, u64> = HashMap::with_capacity(64);
+    for (i, key) in keys.iter().enumerate().rev() {
+        backward.insert((*key).to_string(), i as u64);
+    }
+
+    // Keys sort bytewise by their encodings, so the shorter "echo" header
+    // (0x64) precedes the five-letter keys (0x65) and "charlie" (0x67).
+    assert_golden_cbor(&CanonicalCbor(&forward), "tests/golden/canonical_map.cbor");
+    assert_golden_cbor(&CanonicalCbor(&backward), "tests/golden/canonical_map.cbor");
+}
+
+/// Test that `canonicalize` rewrites a non-canonical document to the golden form
+#[test]
+fn golden_canonicalize_fixture() {
+    // Indefinite map {"b": 1 (two-byte uint), "a": [_ 1, 2]}
+    let non_canonical = hex::decode("bf6162180161619f0102ffff").expect("valid fixture hex");
+    let canonical = canonicalize(&non_canonical).expect("canonicalize failed");
 
-    let decoded: (u8, u8, u8) = decode_full(&bytes).expect("deserialization failed");
-    assert_eq!(decoded, (1, 2, 3));
+    // {"a": [1, 2], "b": 1}
+    assert_eq!(hex::encode(canonical), "a26161820102616201");
 }
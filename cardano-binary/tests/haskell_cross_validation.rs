@@ -3,7 +3,12 @@
 //! This test suite validates byte-exact compatibility with the Haskell implementation
 //! by comparing against known golden test vectors extracted from the Haskell test suite.
 
-use cardano_binary::{decode_full, encode_nested_cbor_bytes, serialize};
+use std::collections::BTreeMap;
+
+use cardano_binary::{
+    BinaryError, BinaryErrorKind, ByteKeyMap, MapKeyOrder, decode_full, encode_nested_cbor_bytes,
+    serialize,
+};
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -422,6 +427,76 @@ fn haskell_known_test_vector_3() {
     assert_roundtrip_with_encoding(&tuple, &decode_hex_vector("83f5f4182a"));
 }
 
+// ============================================================================
+// Maps Keyed by Hashes
+// ============================================================================
+
+const KEY_HASH_1: &str = "276fd18711931e2c0e21430192dbeac0e458093cd9d1fcd7210f64b3";
+const KEY_HASH_2: &str = "c5e21ab1c9f6022d81c3b25e3436cb7f1df77f9652ae3e1310c28e62";
+
+/// `serialize' (Map.fromList [(kh1, 1 :: Word64), (kh2, 1000)])` with two
+/// 28-byte `KeyHash` keys: a definite map of byte-string keys in ascending
+/// order.
+const KEY_HASH_MAP: &str = "a2581c276fd18711931e2c0e21430192dbeac0e458093cd9d1fcd7210f64b301\
+                            581cc5e21ab1c9f6022d81c3b25e3436cb7f1df77f9652ae3e1310c28e621903e8";
+
+fn key_hash(hex: &str) -> [u8; 28] {
+    decode_hex_vector(hex).try_into().expect("28-byte key hash")
+}
+
+#[test]
+fn haskell_compat_map_keyed_by_key_hashes() {
+    let map: ByteKeyMap<[u8; 28], u64> = ByteKeyMap(BTreeMap::from([
+        (key_hash(KEY_HASH_2), 1000),
+        (key_hash(KEY_HASH_1), 1),
+    ]));
+    let golden = decode_hex_vector(KEY_HASH_MAP);
+    assert_roundtrip_with_encoding(&map, &golden);
+    assert_eq!(
+        ByteKeyMap::decode_with_order(&golden, MapKeyOrder::Canonical).expect("canonical"),
+        map
+    );
+
+    // The same entries with `Vec<u8>` keys encode identically.
+    let vec_keys: ByteKeyMap<Vec<u8>, u64> =
+        ByteKeyMap(map.0.iter().map(|(k, v)| (k.to_vec(), *v)).collect());
+    assert_cbor_matches_haskell(&vec_keys, &golden);
+}
+
+#[test]
+fn map_key_order_policy_and_duplicates() {
+    let (k1, k2) = (decode_hex_vector(KEY_HASH_1), decode_hex_vector(KEY_HASH_2));
+    let entry = |key: &[u8], value: u8| [&[0x58, 0x1c][..], key, &[value]].concat();
+
+    let unsorted = [vec![0xa2], entry(&k2, 2), entry(&k1, 1)].concat();
+    let lenient = ByteKeyMap::<[u8; 28], u8>::decode_with_order(&unsorted, MapKeyOrder::default())
+        .expect("lenient accepts unsorted keys");
+    assert_eq!(lenient.0.len(), 2);
+    assert_eq!(
+        decode_full::<ByteKeyMap<[u8; 28], u8>>(&unsorted).expect("serde is lenient"),
+        lenient
+    );
+    let err = ByteKeyMap::<[u8; 28], u8>::decode_with_order(&unsorted, MapKeyOrder::Canonical)
+        .expect_err("canonical rejects unsorted keys");
+    assert!(
+        matches!(err, BinaryError::UnsortedMapKey { index: 1, .. }),
+        "{err}"
+    );
+    assert_eq!(err.kind(), BinaryErrorKind::MapKey);
+
+    let duplicated = [vec![0xa2], entry(&k1, 1), entry(&k1, 2)].concat();
+    for order in [MapKeyOrder::Lenient, MapKeyOrder::Canonical] {
+        let err = ByteKeyMap::<[u8; 28], u8>::decode_with_order(&duplicated, order)
+            .expect_err("duplicate keys are rejected");
+        let expected_key = [&[0x58, 0x1c][..], &k1].concat();
+        assert!(
+            matches!(&err, BinaryError::DuplicateMapKey { index: 1, key, .. } if *key == expected_key),
+            "{err:?}"
+        );
+    }
+    assert!(decode_full::<ByteKeyMap<[u8; 28], u8>>(&duplicated).is_err());
+}
+
 // ============================================================================
 // Summary Test
 // ============================================================================
@@ -0,0 +1,176 @@
+//! Plutus `Data` must decode datum encodings as they appear on chain and
+//! re-encode them byte for byte, and any value must survive a round trip
+//! with a stable encoding.
+
+use cardano_binary::{PlutusData, decode_full, serialize};
+use num_bigint::BigInt;
+use proptest::prelude::*;
+
+fn hex(text: &str) -> Vec<u8> {
+    hex::decode(text).expect("fixture hex")
+}
+
+fn constr(tag: u64, fields: Vec<PlutusData>) -> PlutusData {
+    PlutusData::Constr { tag, fields }
+}
+
+fn bytes(text: &str) -> PlutusData {
+    PlutusData::Bytes(hex(text))
+}
+
+fn int(value: i64) -> PlutusData {
+    PlutusData::Int(BigInt::from(value))
+}
+
+/// Datum shapes seen on chain, with the value each must decode to.
+fn fixtures() -> Vec<(&'static str, String, PlutusData)> {
+    let key_hash = "a2c20c77887ace1cd986193e4e75babd8993cfd56995cd5cfce609c2";
+    let image = "ipfs://QmRhTTbUrPYEw3mJGGhQqQST9k86v1DPBiTTWJGKDJsVFw/0123456789abcdef0123456789";
+    vec![
+        ("unit datum", "d87980".into(), constr(0, vec![])),
+        (
+            // Address { PubKeyCredential pkh, Nothing }
+            "address without staking credential",
+            format!("d8799fd8799f581c{key_hash}ffd87a80ff"),
+            constr(0, vec![constr(0, vec![bytes(key_hash)]), constr(1, vec![])]),
+        ),
+        (
+            // CIP-68 reference datum: metadata map, version, extra
+            "CIP-68 metadata with a chunked image URL",
+            format!(
+                "d8799fa2446e616d654a4d7920546f6b656e203145696d6167655f5840{}50{}ff01d87980ff",
+                hex::encode(&image.as_bytes()[..64]),
+                hex::encode(&image.as_bytes()[64..]),
+            ),
+            constr(
+                0,
+                vec![
+                    PlutusData::Map(vec![
+                        (
+                            PlutusData::Bytes(b"name".to_vec()),
+                            PlutusData::Bytes(b"My Token 1".to_vec()),
+                        ),
+                        (
+                            PlutusData::Bytes(b"image".to_vec()),
+                            PlutusData::Bytes(image.as_bytes().to_vec()),
+                        ),
+                    ]),
+                    int(1),
+                    constr(0, vec![]),
+                ],
+            ),
+        ),
+        (
+            // Order datum: owner, amounts, deadline, an extended constructor
+            "order with lists, negative and big integers",
+            "d8799f9f0120ff9f1b0000018959a78d10c249010000000000000000ff\
+             d90501809f80a0ffff"
+                .into(),
+            constr(
+                0,
+                vec![
+                    PlutusData::List(vec![int(1), int(-1)]),
+                    PlutusData::List(vec![
+                        int(1_689_426_300_176),
+                        PlutusData::Int(BigInt::from(u64::MAX) + 1u8),
+                    ]),
+                    constr(8, vec![]),
+                    PlutusData::List(vec![PlutusData::List(vec![]), PlutusData::Map(vec![])]),
+                ],
+            ),
+        ),
+        (
+            "general constructor 1000",
+            "d866821903e89f40ff".into(),
+            constr(1000, vec![PlutusData::Bytes(vec![])]),
+        ),
+    ]
+}
+
+#[test]
+fn on_chain_fixtures_roundtrip_byte_for_byte() {
+    for (name, encoding, expected) in fixtures() {
+        let encoding = hex(&encoding);
+        let decoded: PlutusData = decode_full(&encoding).expect(name);
+        assert_eq!(decoded, expected, "{name}");
+        assert_eq!(serialize(&decoded).expect(name), encoding, "{name}");
+    }
+}
+
+#[test]
+fn non_canonical_inputs_decode_and_reencode_canonically() {
+    for (input, canonical) in [
+        // Definite-length fields and list
+        ("d879820102", "d8799f0102ff"),
+        ("820102", "9f0102ff"),
+        // Indefinite-length map and empty list
+        ("bf0102ff", "a10102"),
+        ("9fff", "80"),
+        // Small integer as a bignum
+        ("c24105", "05"),
+        // Chunked short byte string
+        ("5f4101420203ff", "43010203"),
+        // Tag 102 for a constructor that has a compact tag
+        ("d866820080", "d87980"),
+    ] {
+        let decoded: PlutusData = decode_full(&hex(input)).expect(input);
+        assert_eq!(hex::encode(serialize(&decoded).expect(input)), canonical);
+    }
+}
+
+#[test]
+fn malformed_inputs_are_rejected() {
+    for input in [
+        // Text is not Data
+        "6161",
+        // Tag 102 over a 3-element array
+        "d86683008000",
+        // Unknown tag
+        "d87801",
+        // Trailing bytes
+        "d8798000",
+        // Truncated chunked string
+        "5f41",
+    ] {
+        assert!(decode_full::<PlutusData>(&hex(input)).is_err(), "{input}");
+    }
+}
+
+fn big_int() -> impl Strategy<Value = BigInt> {
+    prop_oneof![
+        any::<i64>().prop_map(BigInt::from),
+        any::<u64>().prop_map(BigInt::from),
+        (any::<bool>(), proptest::collection::vec(any::<u8>(), 0..80)).prop_map(
+            |(negative, magnitude)| {
+                let int = BigInt::from_bytes_be(num_bigint::Sign::Plus, &magnitude);
+                if negative { -int } else { int }
+            }
+        ),
+    ]
+}
+
+fn plutus_data() -> impl Strategy<Value = PlutusData> {
+    let leaf = prop_oneof![
+        big_int().prop_map(PlutusData::Int),
+        proptest::collection::vec(any::<u8>(), 0..200).prop_map(PlutusData::Bytes),
+    ];
+    leaf.prop_recursive(4, 48, 6, |inner| {
+        let tag = prop_oneof![0u64..10, 120u64..135, any::<u64>()];
+        prop_oneof![
+            (tag, proptest::collection::vec(inner.clone(), 0..6))
+                .prop_map(|(tag, fields)| PlutusData::Constr { tag, fields }),
+            proptest::collection::vec(inner.clone(), 0..6).prop_map(PlutusData::List),
+            proptest::collection::vec((inner.clone(), inner), 0..4).prop_map(PlutusData::Map),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn proptest_reencoding_is_stable(data in plutus_data()) {
+        let encoding = serialize(&data).expect("encode");
+        let decoded: PlutusData = decode_full(&encoding).expect("decode");
+        prop_assert_eq!(&decoded, &data);
+        prop_assert_eq!(serialize(&decoded).expect("re-encode"), encoding);
+    }
+}
@@ -142,3 +142,50 @@ fn roundtrip_enum_variant3() {
     let decoded: SimpleEnum = decode_full(&bytes).expect("deserialization failed");
     assert_eq!(original, decoded);
 }
+
+fn arb_simple_enum() -> impl Strategy<Value = SimpleEnum> {
+    prop_oneof![
+        Just(SimpleEnum::Variant1),
+        any::<u64>().prop_map(SimpleEnum::Variant2),
+        (".{0,16}", any::<i32>()).prop_map(|(name, value)| SimpleEnum::Variant3 { name, value }),
+    ]
+}
+
+/// Arbitrary bytes, or a valid `SimpleEnum` encoding with one byte replaced
+/// and possibly truncated, so decoding gets past the first header.
+fn arb_enum_input() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        prop::collection::vec(any::<u8>(), 0..64),
+        (
+            arb_simple_enum(),
+            any::<prop::sample::Index>(),
+            any::<u8>(),
+            any::<bool>()
+        )
+            .prop_map(|(value, index, byte, truncate)| {
+                let mut bytes = serialize(&value).expect("serialization failed");
+                let at = index.index(bytes.len());
+                bytes[at] = byte;
+                if truncate {
+                    bytes.truncate(at);
+                }
+                bytes
+            }),
+    ]
+}
+
+proptest! {
+    /// Decoding arbitrary input never panics, and anything that decodes
+    /// re-encodes to a stable encoding of the same value. The input bytes
+    /// themselves need not come back: CBOR allows non-minimal integer and
+    /// length encodings that `serialize` never emits.
+    #[test]
+    fn decode_arbitrary_enum_input(bytes in arb_enum_input()) {
+        if let Ok(value) = decode_full::<SimpleEnum>(&bytes) {
+            let encoded = serialize(&value).expect("serialization failed");
+            let decoded: SimpleEnum = decode_full(&encoded).expect("re-decoding failed");
+            prop_assert_eq!(&decoded, &value);
+            prop_assert_eq!(serialize(&decoded).expect("serialization failed"), encoded);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+#![cfg(feature = "trace")]
+
+use std::sync::{Arc, Mutex};
+
+use cardano_binary::{
+    CaptureRecord, CaptureWriter, DEFAULT_TRACE_PAYLOAD_LIMIT, ReplayOutcome, ReplayRegistry,
+    TraceDirection, TraceEvent, TraceOutcome, clear_trace_sink, decode_full, read_capture, replay,
+    serialize, set_trace_payload_limit, set_trace_sink, untraced,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+struct Sample {
+    label: String,
+    value: u32,
+}
+
+/// The sink is process-wide, so tests that install one must not overlap.
+static SINK_LOCK: Mutex<()> = Mutex::new(());
+
+fn collect_events(run: impl FnOnce()) -> Vec<TraceEvent> {
+    let _guard = SINK_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink_events = Arc::clone(&events);
+    set_trace_sink(Box::new(move |event| {
+        sink_events.lock().expect("event lock").push(event);
+    }));
+    run();
+    clear_trace_sink();
+    events.lock().expect("event lock").clone()
+}
+
+#[test]
+fn round_trip_emits_encode_then_decode() {
+    let sample = Sample {
+        label: "trace".into(),
+        value: 7,
+    };
+    let mut encoded = Vec::new();
+    let events = collect_events(|| {
+        encoded = serialize(&sample).expect("serialize");
+        let decoded: Sample = decode_full(&encoded).expect("decode");
+        assert_eq!(decoded, sample);
+        assert!(decode_full::<u64>(&encoded).is_err());
+    });
+
+    let type_name = std::any::type_name::<Sample>();
+    let summary: Vec<_> = events
+        .iter()
+        .map(|event| {
+            (
+                event.direction,
+                event.type_name,
+                event.len,
+                event.outcome == TraceOutcome::Success,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (TraceDirection::Encode, type_name, encoded.len(), true),
+            (TraceDirection::Decode, type_name, encoded.len(), true),
+            (TraceDirection::Decode, "u64", encoded.len(), false),
+        ]
+    );
+    assert!(
+        events
+            .iter()
+            .all(|event| event.payload.as_deref() == Some(&encoded[..]))
+    );
+    assert!(
+        events
+            .windows(2)
+            .all(|pair| pair[0].payload_hash == pair[1].payload_hash)
+    );
+}
+
+#[test]
+fn payloads_above_the_limit_are_reported_without_bytes() {
+    let long = "x".repeat(64);
+    let events = collect_events(|| {
+        set_trace_payload_limit(16);
+        serialize(&long).expect("serialize");
+        set_trace_payload_limit(DEFAULT_TRACE_PAYLOAD_LIMIT);
+    });
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].len, 66);
+    assert_eq!(events[0].payload, None);
+}
+
+#[test]
+fn untraced_payloads_never_reach_the_sink() {
+    let secret = vec![0x5a_u8; 32];
+    let events = collect_events(|| {
+        let bytes = untraced(|| {
+            let bytes = untraced(|| serialize(&secret)).expect("serialize");
+            decode_full::<Vec<u8>>(&bytes).expect("decode");
+            bytes
+        });
+        decode_full::<Vec<u8>>(&bytes).expect("decode");
+    });
+    // Only the decode outside `untraced` is reported.
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].direction, TraceDirection::Decode);
+}
+
+#[test]
+fn captured_events_replay_through_registry() {
+    let sample = Sample {
+        label: "capture".into(),
+        value: 1,
+    };
+    let events = collect_events(|| {
+        let bytes = serialize(&sample).expect("serialize");
+        decode_full::<Sample>(&bytes).expect("decode");
+    });
+
+    let mut writer = CaptureWriter::new(Vec::new());
+    for event in &events {
+        assert!(writer.write_event(event).expect("write event"));
+    }
+    let capture = writer.finish().expect("finish");
+
+    let records = read_capture(capture.as_slice()).expect("read capture");
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].direction, TraceDirection::Encode);
+    assert_eq!(records[1].type_name, std::any::type_name::<Sample>());
+
+    let registry = ReplayRegistry::new().register::<Sample>();
+    let outcomes = replay(&records, &registry);
+    assert!(
+        outcomes
+            .iter()
+            .all(|outcome| matches!(outcome, ReplayOutcome::Decoded))
+    );
+}
+
+#[test]
+fn replays_capture_fixture() {
+    let fixture = include_bytes!("data/trace_capture.bin");
+    let records = read_capture(&fixture[..]).expect("fixture parses");
+    assert_eq!(
+        records[0],
+        CaptureRecord {
+            direction: TraceDirection::Decode,
+            type_name: "u64".into(),
+            payload: vec![0x18, 0x2a],
+        }
+    );
+    assert_eq!(records[3].direction, TraceDirection::Encode);
+
+    let registry = ReplayRegistry::new()
+        .register::<u64>()
+        .register::<String>()
+        .register_as::<Vec<u64>>("legacy::Header");
+    let outcomes = replay(&records, &registry);
+    assert_eq!(outcomes.len(), 5);
+    assert!(matches!(outcomes[0], ReplayOutcome::Decoded));
+    assert!(matches!(outcomes[1], ReplayOutcome::Decoded));
+    assert!(matches!(outcomes[2], ReplayOutcome::Failed(_)));
+    assert!(matches!(outcomes[3], ReplayOutcome::Decoded));
+    assert!(matches!(outcomes[4], ReplayOutcome::Unregistered));
+}
+
+#[test]
+fn truncated_capture_is_rejected() {
+    let fixture = include_bytes!("data/trace_capture.bin");
+    let truncated = &fixture[..fixture.len() - 1];
+    assert!(read_capture(truncated).is_err());
+}
+
+#[test]
+fn oversized_chunk_length_is_rejected_without_reading_past_the_end() {
+    // An encode record whose type name claims u32::MAX bytes but carries three.
+    let mut capture = vec![0u8];
+    capture.extend_from_slice(&u32::MAX.to_be_bytes());
+    capture.extend_from_slice(b"abc");
+    let err = read_capture(capture.as_slice()).expect_err("truncated chunk");
+    assert!(err.to_string().contains("4294967295"), "{err}");
+}
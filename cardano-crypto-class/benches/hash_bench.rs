@@ -1,5 +1,5 @@
+use cardano_crypto_class::hash::HashAlgorithm;
 use cardano_crypto_class::hash::{self, Blake2b224, Blake2b256, Blake2b512};
-use cardano_crypto_class::kes::hash::KesHashAlgorithm;
 use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
 use std::time::Duration;
 
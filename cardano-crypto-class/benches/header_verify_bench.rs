@@ -0,0 +1,162 @@
+//! Decode and verify 10,000 header-shaped records: an issuer Ed25519 key and
+//! signature, a `Sum6Kes` verification key and signature, and a body.
+//!
+//! `owned` decodes with `decode_full` into owned keys, `borrowed` decodes with
+//! `decode_full_borrowed` into `Ed25519VerificationKeyRef` /
+//! `KesVerificationKeyRef` and verifies through `verify_bytes_ref` /
+//! `verify_kes_ref`. Before timing, the heap allocations of one pass over all
+//! headers are counted and printed for each variant.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use cardano_binary::{decode_full, decode_full_borrowed, serialize};
+use cardano_crypto_class::dsign::DsignAlgorithm;
+use cardano_crypto_class::dsign::ed25519::{
+    Ed25519, Ed25519Signature, Ed25519VerificationKey, Ed25519VerificationKeyRef,
+};
+use cardano_crypto_class::kes::{KesAlgorithm, KesVerificationKeyRef, Period, Sum6Kes};
+use cardano_crypto_class::seed::mk_seed_from_bytes;
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use serde::{Deserialize, Serialize};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: forwarded unchanged to the system allocator.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: forwarded unchanged to the system allocator.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const HEADERS: u64 = 10_000;
+
+#[derive(Serialize, Deserialize)]
+struct OwnedHeader {
+    #[serde(with = "serde_bytes")]
+    body: Vec<u8>,
+    issuer_vk: Ed25519VerificationKey,
+    issuer_sig: Ed25519Signature,
+    #[serde(with = "serde_bytes")]
+    kes_vk: Vec<u8>,
+    period: Period,
+    #[serde(with = "serde_bytes")]
+    kes_sig: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct BorrowedHeader<'a> {
+    #[serde(borrow)]
+    body: &'a [u8],
+    #[serde(borrow)]
+    issuer_vk: Ed25519VerificationKeyRef<'a>,
+    issuer_sig: Ed25519Signature,
+    #[serde(borrow)]
+    kes_vk: KesVerificationKeyRef<'a, Sum6Kes>,
+    period: Period,
+    #[serde(borrow)]
+    kes_sig: &'a [u8],
+}
+
+fn encoded_headers() -> Vec<Vec<u8>> {
+    let issuer_sk = Ed25519::gen_key(&mk_seed_from_bytes(vec![1u8; 32]));
+    let issuer_vk = Ed25519::derive_verification_key(&issuer_sk);
+    let kes_sk = Sum6Kes::gen_key_kes_from_seed_bytes(&[2u8; 32]).expect("KES key");
+    let kes_vk = Sum6Kes::derive_verification_key(&kes_sk).expect("KES verification key");
+
+    let headers = (0..HEADERS)
+        .map(|slot| {
+            let body = [slot.to_be_bytes(); 8].concat();
+            let kes_sig = Sum6Kes::sign_kes(&(), 0, &body, &kes_sk).expect("KES signature");
+            serialize(&OwnedHeader {
+                issuer_sig: Ed25519::sign_bytes(&(), &body, &issuer_sk),
+                body,
+                issuer_vk: issuer_vk.clone(),
+                kes_vk: kes_vk.clone(),
+                period: 0,
+                kes_sig: Sum6Kes::raw_serialize_signature_kes(&kes_sig),
+            })
+            .expect("encode header")
+        })
+        .collect();
+    Sum6Kes::forget_signing_key_kes(kes_sk);
+    headers
+}
+
+fn verify_owned(bytes: &[u8]) {
+    let header: OwnedHeader = decode_full(bytes).expect("decode header");
+    Ed25519::verify_bytes(&(), &header.issuer_vk, &header.body, &header.issuer_sig)
+        .expect("issuer signature");
+    let kes_vk =
+        Sum6Kes::raw_deserialize_verification_key_kes(&header.kes_vk).expect("KES key size");
+    let kes_sig = Sum6Kes::raw_deserialize_signature_kes(&header.kes_sig).expect("KES signature");
+    Sum6Kes::verify_kes(&(), &kes_vk, header.period, &header.body, &kes_sig)
+        .expect("KES signature");
+}
+
+fn verify_borrowed(bytes: &[u8]) {
+    let header: BorrowedHeader<'_> = decode_full_borrowed(bytes).expect("decode header");
+    Ed25519::verify_bytes_ref(&(), header.issuer_vk, header.body, &header.issuer_sig)
+        .expect("issuer signature");
+    let kes_sig = Sum6Kes::raw_deserialize_signature_kes(header.kes_sig).expect("KES signature");
+    Sum6Kes::verify_kes_ref(&(), header.kes_vk, header.period, header.body, &kes_sig)
+        .expect("KES signature");
+}
+
+fn allocations_per_pass(headers: &[Vec<u8>], verify: fn(&[u8])) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for header in headers {
+        verify(header);
+    }
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn bench_header_verify(c: &mut Criterion) {
+    let headers = encoded_headers();
+
+    for (name, verify) in [
+        ("owned", verify_owned as fn(&[u8])),
+        ("borrowed", verify_borrowed),
+    ] {
+        let allocations = allocations_per_pass(&headers, verify);
+        println!(
+            "header_verify/{name}: {allocations} allocations for {HEADERS} headers ({:.1} per header)",
+            allocations as f64 / HEADERS as f64
+        );
+    }
+
+    let mut group = c.benchmark_group("header_verify_10k");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+    group.throughput(Throughput::Elements(HEADERS));
+    group.bench_function("owned", |b| {
+        b.iter(|| {
+            for header in &headers {
+                verify_owned(black_box(header));
+            }
+        })
+    });
+    group.bench_function("borrowed", |b| {
+        b.iter(|| {
+            for header in &headers {
+                verify_borrowed(black_box(header));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_header_verify);
+criterion_main!(benches);
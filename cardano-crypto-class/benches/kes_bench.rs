@@ -1,5 +1,8 @@
+use cardano_crypto_class::dsign::DsignAlgorithm;
 use cardano_crypto_class::dsign::ed25519::Ed25519;
-use cardano_crypto_class::kes::{CompactSum4Kes, KesAlgorithm, SingleKes, Sum4Kes};
+use cardano_crypto_class::kes::{
+    CompactSum4Kes, CompactSum7Kes, KesAlgorithm, SingleKes, Sum4Kes, Sum7Kes,
+};
 use criterion::{BenchmarkId, Criterion, SamplingMode, criterion_group, criterion_main};
 use std::sync::Once;
 
@@ -127,7 +130,51 @@ where
     group.finish();
 }
 
+// The KES leaf key lives in mlocked memory (`Ed25519MLockedSigningKey`). Compare
+// signing through `SingleKes` against a plain heap-held Ed25519 key to keep
+// the cost of the mlocked storage visible.
+fn bench_leaf_storage(c: &mut Criterion) {
+    let seed = vec![0x42; Ed25519::SEED_SIZE];
+    let message = b"bench-leaf-storage".to_vec();
+    let heap_key = Ed25519::gen_key_from_seed_bytes(&seed);
+    let mlocked_key =
+        SingleKes::<Ed25519>::gen_key_kes_from_seed_bytes(&seed).expect("signing key");
+
+    let mut group = c.benchmark_group("KES/leaf_storage");
+    group.bench_function("heap_ed25519_sign", |b| {
+        b.iter(|| Ed25519::sign_bytes(&(), &message, &heap_key))
+    });
+    group.bench_function("mlocked_single_kes_sign", |b| {
+        b.iter(|| SingleKes::<Ed25519>::sign_kes(&(), 0, &message, &mlocked_key).expect("sign"))
+    });
+    group.finish();
+
+    SingleKes::<Ed25519>::forget_signing_key_kes(mlocked_key);
+}
+
+// Sum and CompactSum signing keys cache the root verification key, so
+// deriving it is a copy regardless of tree depth.
+fn bench_derive_verification_key(c: &mut Criterion) {
+    let seed = vec![0x42; Sum7Kes::SEED_SIZE];
+    let sum_key = Sum7Kes::gen_key_kes_from_seed_bytes(&seed).expect("signing key");
+    let compact_key = CompactSum7Kes::gen_key_kes_from_seed_bytes(&seed).expect("signing key");
+
+    let mut group = c.benchmark_group("KES/derive_verification_key");
+    group.bench_function("Sum7", |b| {
+        b.iter(|| Sum7Kes::derive_verification_key(&sum_key).expect("verification key"))
+    });
+    group.bench_function("CompactSum7", |b| {
+        b.iter(|| CompactSum7Kes::derive_verification_key(&compact_key).expect("verification key"))
+    });
+    group.finish();
+
+    Sum7Kes::forget_signing_key_kes(sum_key);
+    CompactSum7Kes::forget_signing_key_kes(compact_key);
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
+    bench_leaf_storage(c);
+    bench_derive_verification_key(c);
     bench_kes_alg::<SingleKes<Ed25519>>(c, "Single");
     bench_kes_alg::<Sum4Kes>(c, "Sum4");
     bench_kes_alg::<CompactSum4Kes>(c, "CompactSum4");
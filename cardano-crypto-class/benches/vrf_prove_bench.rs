@@ -0,0 +1,78 @@
+//! Leadership-schedule style proving: one Praos key, 10,000 slot messages.
+//!
+//! Compares `prove` (which expands the secret on every call) with a
+//! `ProvingContext` that expands it once. The expansion is a single SHA-512
+//! of 32 bytes, so both sides are dominated by hash-to-curve and the scalar
+//! multiplications; expect the two to be within noise of each other.
+
+use cardano_crypto_class::vrf::{
+    PraosBatchCompatProvingContext, PraosBatchCompatVRF, PraosProvingContext, PraosVRF,
+    VRFAlgorithm,
+};
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use std::time::Duration;
+
+const PROOFS: u64 = 10_000;
+
+fn slot_messages() -> Vec<[u8; 8]> {
+    (0..PROOFS).map(u64::to_be_bytes).collect()
+}
+
+fn bench_praos(c: &mut Criterion) {
+    let messages = slot_messages();
+    let sk = PraosVRF::gen_key_from_seed_bytes(&[0x42; 32]);
+    let context = PraosProvingContext::new(&sk).expect("proving context");
+
+    let mut group = c.benchmark_group("VRF/praos_10k_proofs");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(30));
+    group.throughput(Throughput::Elements(PROOFS));
+    group.bench_function("prove", |b| {
+        b.iter(|| {
+            for message in &messages {
+                black_box(sk.prove(message).expect("prove"));
+            }
+        })
+    });
+    group.bench_function("proving_context", |b| {
+        b.iter(|| {
+            black_box(
+                context
+                    .prove_many(messages.iter().map(|m| m.as_slice()))
+                    .expect("prove_many"),
+            )
+        })
+    });
+    group.finish();
+}
+
+fn bench_praos_batch_compat(c: &mut Criterion) {
+    let messages = slot_messages();
+    let sk = PraosBatchCompatVRF::gen_key_from_seed_bytes(&[0x42; 32]);
+    let context = PraosBatchCompatProvingContext::new(&sk).expect("proving context");
+
+    let mut group = c.benchmark_group("VRF/praos_batch_compat_10k_proofs");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(30));
+    group.throughput(Throughput::Elements(PROOFS));
+    group.bench_function("prove", |b| {
+        b.iter(|| {
+            for message in &messages {
+                black_box(sk.prove(message).expect("prove"));
+            }
+        })
+    });
+    group.bench_function("proving_context", |b| {
+        b.iter(|| {
+            black_box(
+                context
+                    .prove_many(messages.iter().map(|m| m.as_slice()))
+                    .expect("prove_many"),
+            )
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_praos, bench_praos_batch_compat);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+//! Relay-style verification: 1,000 proofs from one pool's Praos key.
+//!
+//! Compares `PraosVerificationKey::verify`, which decompresses the key on
+//! every call, with a `PraosVerificationKeyPrepared` that decompressed it once
+//! beforehand. Preparation itself is timed separately. Decompression is a
+//! small part of a verification next to hash-to-curve and the scalar
+//! multiplications, so expect the two to differ by about 1%.
+
+use cardano_crypto_class::vrf::{PraosProof, PraosVRF, VRFAlgorithm};
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use std::time::Duration;
+
+const PROOFS: u64 = 1_000;
+
+fn bench_praos_verify(c: &mut Criterion) {
+    let sk = PraosVRF::gen_key_from_seed_bytes(&[0x42; 32]);
+    let vk = PraosVRF::derive_verification_key(&sk);
+    let prepared = vk.prepare().expect("prepare");
+    let proofs: Vec<([u8; 8], PraosProof)> = (0..PROOFS)
+        .map(|slot| {
+            let message = slot.to_be_bytes();
+            let proof = sk.prove(&message).expect("prove");
+            (message, proof)
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("VRF/praos_1k_verifications");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(30));
+    group.throughput(Throughput::Elements(PROOFS));
+    group.bench_function("verify", |b| {
+        b.iter(|| {
+            for (message, proof) in &proofs {
+                black_box(vk.verify(message, proof).expect("verify"));
+            }
+        })
+    });
+    group.bench_function("prepared", |b| {
+        b.iter(|| {
+            for (message, proof) in &proofs {
+                black_box(prepared.verify(message, proof).expect("verify"));
+            }
+        })
+    });
+    group.finish();
+
+    c.bench_function("VRF/praos_prepare", |b| {
+        b.iter(|| black_box(vk.prepare().expect("prepare")))
+    });
+}
+
+criterion_group!(benches, bench_praos_verify);
+criterion_main!(benches);
@@ -0,0 +1,43 @@
+//! Write a pool's keys as `cardano-cli` key files and read them back.
+//!
+//! Run with `cargo run --example envelope_store --features workflows`.
+
+use cardano_crypto_class::dsign::ed25519::{Ed25519, Ed25519SigningKey};
+use cardano_crypto_class::role::{
+    ColdRole, PaymentRole, VrfRole, derive_dsign_verification_key, derive_vrf_verification_key,
+    gen_dsign_key, gen_vrf_key,
+};
+use cardano_crypto_class::vrf::praos::{PraosSigningKey, PraosVRF};
+use cardano_crypto_class::workflows::EnvelopeStore;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let store = EnvelopeStore::new(dir.path());
+
+    let cold = gen_dsign_key::<ColdRole, Ed25519>(&[0x01; 32])?;
+    let vrf = gen_vrf_key::<PraosVRF>(&[0x02; 32])?;
+    for path in [
+        store.save("cold", &cold)?,
+        store.save(
+            "cold",
+            &derive_dsign_verification_key::<ColdRole, Ed25519>(&cold),
+        )?,
+        store.save("vrf", &vrf)?,
+        store.save("vrf", &derive_vrf_verification_key::<PraosVRF>(&vrf))?,
+    ] {
+        println!("wrote {}", path.display());
+    }
+    println!("{}", std::fs::read_to_string(dir.path().join("cold.vkey"))?);
+
+    let loaded = store.load::<ColdRole, Ed25519SigningKey>("cold")?;
+    assert_eq!(loaded, cold);
+    let loaded = store.load::<VrfRole, PraosSigningKey>("vrf")?;
+    assert_eq!(loaded.inner().as_bytes(), vrf.inner().as_bytes());
+
+    // A cold key file is not accepted as a payment key.
+    match store.load::<PaymentRole, Ed25519SigningKey>("cold") {
+        Err(err) => println!("refused: {err}"),
+        Ok(_) => return Err("a cold key loaded as a payment key".into()),
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! Sign headers across several KES periods, save the session, and resume it.
+//!
+//! Run with `cargo run --example kes_session --features workflows`.
+
+use cardano_crypto_class::kes::{KesAlgorithm, Sum6Kes};
+use cardano_crypto_class::role::gen_kes_key;
+use cardano_crypto_class::workflows::{
+    EnvelopeStore, KesSessionConfig, KesSessionManager, WorkflowError,
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let config = KesSessionConfig {
+        slots_per_kes_period: 129_600,
+        ocert_start_period: 800,
+        max_kes_evolutions: 62,
+    };
+    let signing_key = gen_kes_key::<Sum6Kes>(&[0x42; 32])?;
+    let mut session = KesSessionManager::<Sum6Kes>::new(config, signing_key);
+    let verification_key = session.verification_key()?;
+
+    let first_slot = config.ocert_start_period * config.slots_per_kes_period;
+    for offset in [0, 1_000, 130_000, 400_000] {
+        let slot = first_slot + offset;
+        let signature = session.sign(&(), slot, b"header body")?;
+        let evolution = session.evolution();
+        Sum6Kes::verify_kes(
+            &(),
+            &verification_key,
+            evolution,
+            b"header body",
+            &signature,
+        )?;
+        println!("slot {slot}: signed at evolution {evolution}");
+    }
+
+    // The key has moved on, so an earlier slot is refused.
+    match session.sign(&(), first_slot, b"late header") {
+        Err(err @ WorkflowError::StalePeriod { .. }) => println!("refused: {err}"),
+        other => return Err(format!("expected a stale period, got {other:?}").into()),
+    }
+
+    let dir = tempfile::tempdir()?;
+    let store = EnvelopeStore::new(dir.path());
+    let path = store.save_kes_session("pool", &session.persist()?)?;
+    println!("saved session to {}", path.display());
+    drop(session);
+
+    let mut resumed =
+        KesSessionManager::<Sum6Kes>::restore(config, &store.load_kes_session("pool")?)?;
+    let slot = first_slot + 500_000;
+    let signature = resumed.sign(&(), slot, b"header body")?;
+    Sum6Kes::verify_kes(
+        &(),
+        &verification_key,
+        resumed.evolution(),
+        b"header body",
+        &signature,
+    )?;
+    println!(
+        "resumed at evolution {} and signed slot {slot}",
+        resumed.evolution()
+    );
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! Compute the slots a pool leads in part of an epoch and check them as a
+//! peer would.
+//!
+//! Run with `cargo run --release --example leadership_schedule --features workflows`.
+
+use cardano_crypto_class::role::{derive_vrf_verification_key, gen_vrf_key};
+use cardano_crypto_class::vrf::praos::PraosVRF;
+use cardano_crypto_class::workflows::{EpochLeadershipParams, LeaderThreshold, LeadershipSchedule};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let signing_key = gen_vrf_key::<PraosVRF>(&[0x07; 32])?;
+    // Mainnet's active slot coefficient, for a pool with 2% of the stake,
+    // over the first 4 320 slots of the epoch.
+    let params = EpochLeadershipParams {
+        epoch_nonce: Some([0xab; 32]),
+        threshold: LeaderThreshold::from_stake(0.05, 0.02).ok_or("invalid stake")?,
+    };
+
+    let slots = 0..4_320;
+    let schedule = LeadershipSchedule::compute(&signing_key, &params, slots.clone())?;
+    println!(
+        "leader in {} of {} slots:",
+        schedule.leader_slots().len(),
+        slots.end - slots.start
+    );
+    for leader in schedule.leader_slots() {
+        println!(
+            "  slot {:>5}  leader value {}",
+            leader.slot,
+            hex::encode(&leader.leader_value[..8])
+        );
+    }
+
+    let verification_key = derive_vrf_verification_key::<PraosVRF>(&signing_key);
+    schedule.verify(&verification_key.inner().prepare()?, &params)?;
+    println!("every proof verifies against the pool's VRF key");
+    Ok(())
+}
@@ -0,0 +1,579 @@
+//! BLS12-381 curve operations and pairings (feature `bls12-381`).
+//!
+//! Mirrors Haskell's `Cardano.Crypto.EllipticCurve.BLS12_381`, the bindings
+//! behind Plutus' BLS12-381 builtins, on top of the same `blst` library:
+//!
+//! | Haskell | Rust |
+//! |---------|------|
+//! | `Point1`, `Point2`, `PT` | [`Point1`], [`Point2`], [`PairingTarget`] |
+//! | `blsAddOrDouble`, `blsNeg`, `blsCneg`, `blsMult`, `blsMSM` | `add`, `neg`, `cneg`, `mult`, `msm` |
+//! | `blsCompress` / `blsUncompress` | `compress` / `uncompress` |
+//! | `blsSerialize` / `blsDeserialize` | `serialize` / `deserialize` |
+//! | `blsHash` | `hash` |
+//! | `blsGenerator`, `blsZero`, `blsIsInf`, `blsInGroup` | `generator`, `zero`, `is_inf`, `in_group` |
+//! | `millerLoop`, `ptMult`, `ptFinalVerify` | [`miller_loop`], [`PairingTarget::mult`], [`final_verify`] |
+//! | `Scalar`, `scalarFromInteger`, `scalarFromBS` | [`Scalar`], [`Scalar::from_integer`], [`Scalar::from_be_bytes`] |
+//!
+//! Decoding checks that the point is on the curve and in the prime-order
+//! subgroup, as `blsUncompress` does, so every [`Point1`] and [`Point2`] is
+//! a group element. `hash` follows RFC 9380 and reduces domain separation
+//! tags longer than 255 bytes to `SHA-256("H2C-OVERSIZE-DST-" ‖ DST)`.
+//!
+//! ```rust
+//! use cardano_crypto_class::bls12_381::{Point1, Point2, Scalar, final_verify, miller_loop};
+//!
+//! let a = Scalar::from_u64(6);
+//! let p = Point1::generator().mult(&a);
+//! let q = Point2::generator();
+//! // e([a]P, Q) = e(P, [a]Q)
+//! assert!(final_verify(
+//!     &miller_loop(&p, &q),
+//!     &miller_loop(&Point1::generator(), &q.mult(&a)),
+//! ));
+//! assert_eq!(Point1::uncompress(&p.compress()), Ok(p));
+//! ```
+
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use blst::{
+    BLST_ERROR, blst_bendian_from_scalar, blst_fp12, blst_fp12_finalverify, blst_fp12_is_equal,
+    blst_fp12_mul, blst_fp12_one, blst_fr, blst_fr_add, blst_fr_cneg, blst_fr_eucl_inverse,
+    blst_fr_from_scalar, blst_fr_from_uint64, blst_fr_mul, blst_fr_sub, blst_hash_to_g1,
+    blst_hash_to_g2, blst_miller_loop, blst_p1, blst_p1_add_or_double, blst_p1_affine,
+    blst_p1_affine_in_g1, blst_p1_cneg, blst_p1_compress, blst_p1_deserialize, blst_p1_from_affine,
+    blst_p1_generator, blst_p1_is_equal, blst_p1_is_inf, blst_p1_mult, blst_p1_serialize,
+    blst_p1_to_affine, blst_p1_uncompress, blst_p2, blst_p2_add_or_double, blst_p2_affine,
+    blst_p2_affine_in_g2, blst_p2_cneg, blst_p2_compress, blst_p2_deserialize, blst_p2_from_affine,
+    blst_p2_generator, blst_p2_is_equal, blst_p2_is_inf, blst_p2_mult, blst_p2_serialize,
+    blst_p2_to_affine, blst_p2_uncompress, blst_scalar, blst_scalar_fr_check,
+    blst_scalar_from_be_bytes, blst_scalar_from_bendian, blst_scalar_from_fr,
+};
+use num_bigint::{BigInt, BigUint, Sign};
+use thiserror::Error;
+
+/// Error decoding a BLS12-381 point or scalar, Haskell's `BLSTError`.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum BlsError {
+    /// The input is not a point or scalar encoding of the expected width.
+    #[error("{context}: wrong length, expected {expected} bytes but got {actual}")]
+    WrongLength {
+        context: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// The flag bits or coordinates are malformed (`BLST_BAD_ENCODING`).
+    #[error("malformed point encoding")]
+    BadEncoding,
+    /// The coordinates do not satisfy the curve equation
+    /// (`BLST_POINT_NOT_ON_CURVE`).
+    #[error("point is not on the curve")]
+    PointNotOnCurve,
+    /// The point is on the curve but outside the prime-order subgroup
+    /// (`BLST_POINT_NOT_IN_GROUP`).
+    #[error("point is not in the prime-order subgroup")]
+    PointNotInGroup,
+    /// The scalar is not below the group order (`BLST_BAD_SCALAR`).
+    #[error("scalar is not below the group order")]
+    BadScalar,
+}
+
+impl BlsError {
+    /// Stable identifier of this variant (see the crate-level *Error codes*
+    /// section).
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            BlsError::WrongLength { .. } => "bls.wrong_length",
+            BlsError::BadEncoding => "bls.bad_encoding",
+            BlsError::PointNotOnCurve => "bls.point_not_on_curve",
+            BlsError::PointNotInGroup => "bls.point_not_in_group",
+            BlsError::BadScalar => "bls.bad_scalar",
+        }
+    }
+
+    /// Always `false`: a malformed encoding stays malformed.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        false
+    }
+
+    fn from_blst(err: BLST_ERROR) -> Self {
+        match err {
+            BLST_ERROR::BLST_POINT_NOT_ON_CURVE => BlsError::PointNotOnCurve,
+            BLST_ERROR::BLST_POINT_NOT_IN_GROUP => BlsError::PointNotInGroup,
+            BLST_ERROR::BLST_BAD_SCALAR => BlsError::BadScalar,
+            _ => BlsError::BadEncoding,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for BlsError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        if let BlsError::WrongLength {
+            context,
+            expected,
+            actual,
+        } = self
+        {
+            map.serialize_entry("context", context)?;
+            map.serialize_entry("expected", expected)?;
+            map.serialize_entry("actual", actual)?;
+        }
+        map.serialize_entry("message", &self.to_string())?;
+        map.end()
+    }
+}
+
+fn check_length(context: &'static str, expected: usize, bytes: &[u8]) -> Result<(), BlsError> {
+    if bytes.len() == expected {
+        Ok(())
+    } else {
+        Err(BlsError::WrongLength {
+            context,
+            expected,
+            actual: bytes.len(),
+        })
+    }
+}
+
+/// An element of the scalar field `Fr`, the integers modulo the group order
+/// `r`.
+#[derive(Clone, Copy)]
+pub struct Scalar(blst_fr);
+
+impl Scalar {
+    /// Width of [`to_be_bytes`](Self::to_be_bytes).
+    pub const SIZE: usize = 32;
+
+    /// The additive identity.
+    #[must_use]
+    pub fn zero() -> Self {
+        Scalar::from_u64(0)
+    }
+
+    /// The multiplicative identity.
+    #[must_use]
+    pub fn one() -> Self {
+        Scalar::from_u64(1)
+    }
+
+    /// `value`, which is always below the group order.
+    #[must_use]
+    pub fn from_u64(value: u64) -> Self {
+        let mut fr = blst_fr::default();
+        let limbs = [value, 0, 0, 0];
+        // SAFETY: `limbs` holds the four limbs `blst_fr_from_uint64` reads.
+        unsafe { blst_fr_from_uint64(&mut fr, limbs.as_ptr()) };
+        Scalar(fr)
+    }
+
+    /// The canonical 32-byte big-endian encoding, Haskell's `scalarFromBS`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlsError::WrongLength`] for any other width and
+    /// [`BlsError::BadScalar`] unless the value is below the group order.
+    pub fn from_be_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        check_length("scalar", Self::SIZE, bytes)?;
+        let mut scalar = blst_scalar::default();
+        // SAFETY: `bytes` is exactly the 32 bytes the function reads.
+        unsafe { blst_scalar_from_bendian(&mut scalar, bytes.as_ptr()) };
+        // SAFETY: `scalar` is initialised.
+        if !unsafe { blst_scalar_fr_check(&scalar) } {
+            return Err(BlsError::BadScalar);
+        }
+        Ok(Scalar::from_blst_scalar(&scalar))
+    }
+
+    /// Big-endian bytes of any length, reduced modulo the group order.
+    #[must_use]
+    pub fn from_be_bytes_reduced(bytes: &[u8]) -> Self {
+        let mut scalar = blst_scalar::default();
+        // SAFETY: the function reads `bytes.len()` bytes from `bytes`.
+        unsafe { blst_scalar_from_be_bytes(&mut scalar, bytes.as_ptr(), bytes.len()) };
+        Scalar::from_blst_scalar(&scalar)
+    }
+
+    /// `value` modulo the group order, negative values included, as
+    /// `blsMult` and `scalarFromInteger` treat an `Integer`.
+    #[must_use]
+    pub fn from_integer(value: &BigInt) -> Self {
+        let magnitude = Scalar::from_be_bytes_reduced(&value.magnitude().to_bytes_be());
+        match value.sign() {
+            Sign::Minus => -magnitude,
+            Sign::NoSign | Sign::Plus => magnitude,
+        }
+    }
+
+    /// The canonical 32-byte big-endian encoding.
+    #[must_use]
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let scalar = self.to_blst_scalar();
+        let mut out = [0u8; 32];
+        // SAFETY: `out` has room for the 32 bytes written.
+        unsafe { blst_bendian_from_scalar(out.as_mut_ptr(), &scalar) };
+        out
+    }
+
+    /// The value as a natural below the group order.
+    #[must_use]
+    pub fn to_natural(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.to_be_bytes())
+    }
+
+    /// The multiplicative inverse, or `None` for zero.
+    #[must_use]
+    pub fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let mut out = blst_fr::default();
+        // SAFETY: both pointers refer to initialised field elements.
+        unsafe { blst_fr_eucl_inverse(&mut out, &self.0) };
+        Some(Scalar(out))
+    }
+
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.to_be_bytes() == [0u8; 32]
+    }
+
+    fn from_blst_scalar(scalar: &blst_scalar) -> Self {
+        let mut fr = blst_fr::default();
+        // SAFETY: both pointers refer to initialised values.
+        unsafe { blst_fr_from_scalar(&mut fr, scalar) };
+        Scalar(fr)
+    }
+
+    fn to_blst_scalar(self) -> blst_scalar {
+        let mut scalar = blst_scalar::default();
+        // SAFETY: both pointers refer to initialised values.
+        unsafe { blst_scalar_from_fr(&mut scalar, &self.0) };
+        scalar
+    }
+}
+
+impl PartialEq for Scalar {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_be_bytes() == other.to_be_bytes()
+    }
+}
+
+impl Eq for Scalar {}
+
+impl fmt::Debug for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Scalar({})", hex::encode(self.to_be_bytes()))
+    }
+}
+
+macro_rules! fr_op {
+    ($trait:ident, $method:ident, $blst:ident) => {
+        impl $trait for Scalar {
+            type Output = Scalar;
+
+            fn $method(self, rhs: Scalar) -> Scalar {
+                let mut out = blst_fr::default();
+                // SAFETY: all pointers refer to initialised field elements.
+                unsafe { $blst(&mut out, &self.0, &rhs.0) };
+                Scalar(out)
+            }
+        }
+    };
+}
+
+fr_op!(Add, add, blst_fr_add);
+fr_op!(Sub, sub, blst_fr_sub);
+fr_op!(Mul, mul, blst_fr_mul);
+
+impl Neg for Scalar {
+    type Output = Scalar;
+
+    fn neg(self) -> Scalar {
+        let mut out = blst_fr::default();
+        // SAFETY: both pointers refer to initialised field elements.
+        unsafe { blst_fr_cneg(&mut out, &self.0, true) };
+        Scalar(out)
+    }
+}
+
+macro_rules! point {
+    (
+        $(#[$doc:meta])*
+        $name:ident, $group:literal, $point:ident, $affine:ident,
+        compressed: $compressed:literal, serialized: $serialized:literal,
+        $add:ident, $cneg:ident, $mult:ident, $is_equal:ident, $is_inf:ident,
+        $generator:ident, $compress:ident, $serialize:ident, $uncompress:ident,
+        $deserialize:ident, $from_affine:ident, $to_affine:ident, $in_group:ident,
+        $hash:ident
+    ) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy)]
+        pub struct $name($point);
+
+        impl $name {
+            /// Width of [`compress`](Self::compress).
+            pub const COMPRESSED_SIZE: usize = $compressed;
+            /// Width of [`serialize`](Self::serialize).
+            pub const SERIALIZED_SIZE: usize = $serialized;
+
+            /// The standard generator of the group.
+            #[must_use]
+            pub fn generator() -> Self {
+                // SAFETY: blst returns a pointer to a static point.
+                $name(unsafe { *$generator() })
+            }
+
+            /// The point at infinity, the group's identity.
+            #[must_use]
+            pub fn zero() -> Self {
+                $name($point::default())
+            }
+
+            #[must_use]
+            pub fn is_inf(&self) -> bool {
+                // SAFETY: `self.0` is an initialised point.
+                unsafe { $is_inf(&self.0) }
+            }
+
+            /// Always `true` for points built by this module, which only
+            /// decodes subgroup elements; kept for parity with
+            /// `blsInGroup`.
+            #[must_use]
+            pub fn in_group(&self) -> bool {
+                let affine = self.affine();
+                // SAFETY: `affine` is an initialised point.
+                unsafe { $in_group(&affine) }
+            }
+
+            /// `self + other`, doubling when they are equal.
+            #[must_use]
+            pub fn add(&self, other: &Self) -> Self {
+                let mut out = $point::default();
+                // SAFETY: all pointers refer to initialised points.
+                unsafe { $add(&mut out, &self.0, &other.0) };
+                $name(out)
+            }
+
+            #[must_use]
+            pub fn neg(&self) -> Self {
+                self.cneg(true)
+            }
+
+            /// `-self` if `negate`, otherwise `self`.
+            #[must_use]
+            pub fn cneg(&self, negate: bool) -> Self {
+                let mut out = self.0;
+                // SAFETY: `out` is an initialised point.
+                unsafe { $cneg(&mut out, negate) };
+                $name(out)
+            }
+
+            /// `[scalar] self`.
+            #[must_use]
+            pub fn mult(&self, scalar: &Scalar) -> Self {
+                let scalar = scalar.to_blst_scalar();
+                let mut out = $point::default();
+                // SAFETY: `scalar.b` holds the 256 bits read.
+                unsafe { $mult(&mut out, &self.0, scalar.b.as_ptr(), 256) };
+                $name(out)
+            }
+
+            /// `Σ [scalar] point`, Haskell's `blsMSM`.
+            #[must_use]
+            pub fn msm(terms: &[(Scalar, Self)]) -> Self {
+                terms
+                    .iter()
+                    .fold(Self::zero(), |sum, (scalar, point)| sum.add(&point.mult(scalar)))
+            }
+
+            /// Hash `message` to the group with RFC 9380's
+            /// `expand_message_xmd` over SHA-256, prefixing it with `aug`.
+            ///
+            /// A `dst` over 255 bytes is first reduced with SHA-256, as the
+            /// RFC specifies.
+            #[must_use]
+            pub fn hash(message: &[u8], dst: &[u8], aug: &[u8]) -> Self {
+                let mut out = $point::default();
+                // SAFETY: each pointer is valid for the length passed with it.
+                unsafe {
+                    $hash(
+                        &mut out,
+                        message.as_ptr(),
+                        message.len(),
+                        dst.as_ptr(),
+                        dst.len(),
+                        aug.as_ptr(),
+                        aug.len(),
+                    );
+                }
+                $name(out)
+            }
+
+            /// The compressed encoding (ZCash format).
+            #[must_use]
+            pub fn compress(&self) -> [u8; $compressed] {
+                let mut out = [0u8; $compressed];
+                // SAFETY: `out` has room for the compressed encoding.
+                unsafe { $compress(out.as_mut_ptr(), &self.0) };
+                out
+            }
+
+            /// The uncompressed encoding (ZCash format).
+            #[must_use]
+            pub fn serialize(&self) -> [u8; $serialized] {
+                let mut out = [0u8; $serialized];
+                // SAFETY: `out` has room for the uncompressed encoding.
+                unsafe { $serialize(out.as_mut_ptr(), &self.0) };
+                out
+            }
+
+            /// Decode a compressed point.
+            ///
+            /// # Errors
+            ///
+            /// Returns [`BlsError::WrongLength`], [`BlsError::BadEncoding`],
+            /// [`BlsError::PointNotOnCurve`] or
+            /// [`BlsError::PointNotInGroup`].
+            pub fn uncompress(bytes: &[u8]) -> Result<Self, BlsError> {
+                check_length(concat!($group, " compressed point"), $compressed, bytes)?;
+                let mut affine = $affine::default();
+                // SAFETY: `bytes` holds the compressed width read.
+                let result = unsafe { $uncompress(&mut affine, bytes.as_ptr()) };
+                Self::from_decoded(result, &affine)
+            }
+
+            /// Decode an uncompressed point.
+            ///
+            /// # Errors
+            ///
+            /// As [`uncompress`](Self::uncompress).
+            pub fn deserialize(bytes: &[u8]) -> Result<Self, BlsError> {
+                check_length(concat!($group, " uncompressed point"), $serialized, bytes)?;
+                let mut affine = $affine::default();
+                // SAFETY: `bytes` holds the uncompressed width read.
+                let result = unsafe { $deserialize(&mut affine, bytes.as_ptr()) };
+                Self::from_decoded(result, &affine)
+            }
+
+            fn from_decoded(result: BLST_ERROR, affine: &$affine) -> Result<Self, BlsError> {
+                if result != BLST_ERROR::BLST_SUCCESS {
+                    return Err(BlsError::from_blst(result));
+                }
+                // SAFETY: `affine` was initialised by the decoder.
+                if !unsafe { $in_group(affine) } {
+                    return Err(BlsError::PointNotInGroup);
+                }
+                let mut point = $point::default();
+                // SAFETY: both pointers refer to initialised points.
+                unsafe { $from_affine(&mut point, affine) };
+                Ok($name(point))
+            }
+
+            fn affine(&self) -> $affine {
+                let mut affine = $affine::default();
+                // SAFETY: both pointers refer to initialised points.
+                unsafe { $to_affine(&mut affine, &self.0) };
+                affine
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                // SAFETY: both pointers refer to initialised points.
+                unsafe { $is_equal(&self.0, &other.0) }
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, concat!(stringify!($name), "({})"), hex::encode(self.compress()))
+            }
+        }
+    };
+}
+
+point!(
+    /// A point of the prime-order subgroup G1 of the curve over `Fp`.
+    Point1, "G1", blst_p1, blst_p1_affine, compressed: 48, serialized: 96,
+    blst_p1_add_or_double, blst_p1_cneg, blst_p1_mult, blst_p1_is_equal, blst_p1_is_inf,
+    blst_p1_generator, blst_p1_compress, blst_p1_serialize, blst_p1_uncompress,
+    blst_p1_deserialize, blst_p1_from_affine, blst_p1_to_affine, blst_p1_affine_in_g1,
+    blst_hash_to_g1
+);
+
+point!(
+    /// A point of the prime-order subgroup G2 of the twist over `Fp2`.
+    Point2, "G2", blst_p2, blst_p2_affine, compressed: 96, serialized: 192,
+    blst_p2_add_or_double, blst_p2_cneg, blst_p2_mult, blst_p2_is_equal, blst_p2_is_inf,
+    blst_p2_generator, blst_p2_compress, blst_p2_serialize, blst_p2_uncompress,
+    blst_p2_deserialize, blst_p2_from_affine, blst_p2_to_affine, blst_p2_affine_in_g2,
+    blst_hash_to_g2
+);
+
+/// A Miller loop output in `Fp12`, Haskell's `PT`, compared only after the
+/// final exponentiation by [`final_verify`].
+#[derive(Clone, Copy)]
+pub struct PairingTarget(blst_fp12);
+
+impl PairingTarget {
+    /// The multiplicative identity.
+    #[must_use]
+    pub fn one() -> Self {
+        // SAFETY: blst returns a pointer to a static field element.
+        PairingTarget(unsafe { *blst_fp12_one() })
+    }
+
+    /// The product in `Fp12`, Haskell's `ptMult`.
+    #[must_use]
+    pub fn mult(&self, other: &Self) -> Self {
+        let mut out = blst_fp12::default();
+        // SAFETY: all pointers refer to initialised field elements.
+        unsafe { blst_fp12_mul(&mut out, &self.0, &other.0) };
+        PairingTarget(out)
+    }
+}
+
+/// Equality of the raw `Fp12` values; pairing results must be compared
+/// with [`final_verify`] instead.
+impl PartialEq for PairingTarget {
+    fn eq(&self, other: &Self) -> bool {
+        // SAFETY: both pointers refer to initialised field elements.
+        unsafe { blst_fp12_is_equal(&self.0, &other.0) }
+    }
+}
+
+impl Eq for PairingTarget {}
+
+impl fmt::Debug for PairingTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PairingTarget(..)")
+    }
+}
+
+/// The Miller loop of the pairing `e(p, q)`, before final exponentiation.
+#[must_use]
+pub fn miller_loop(p: &Point1, q: &Point2) -> PairingTarget {
+    let (p, q) = (p.affine(), q.affine());
+    let mut out = blst_fp12::default();
+    // SAFETY: all pointers refer to initialised values.
+    unsafe { blst_miller_loop(&mut out, &q, &p) };
+    PairingTarget(out)
+}
+
+/// Whether two Miller loop outputs are equal after final exponentiation,
+/// i.e. whether the pairings they stand for are equal; Haskell's
+/// `ptFinalVerify`.
+#[must_use]
+pub fn final_verify(a: &PairingTarget, b: &PairingTarget) -> bool {
+    // SAFETY: both pointers refer to initialised field elements.
+    unsafe { blst_fp12_finalverify(&a.0, &b.0) }
+}
@@ -0,0 +1,354 @@
+//! Startup probe of how fast this machine runs the node's hot-path crypto.
+//!
+//! [`measure_crypto_throughput`] runs short verification loops for Ed25519,
+//! Praos VRF and `Sum6Kes`, and Blake2b-256 over 1 KiB blocks, and reports
+//! operations per second for each, so a node can log whether the hardware
+//! keeps up without every consumer writing its own timing loops. The inputs
+//! are fixed published vectors embedded here (RFC 8032 test 2, the
+//! draft-03 Praos vector 10, and the level 6 `Sum6Kes` vector from
+//! `cardano-test-vectors`), so each operation does the same work on every
+//! machine and run, and no keys are generated or mlocked.
+//!
+//! The figures are indicative: the budget is short by design, and a busy
+//! machine or frequency scaling moves them. Compare them against a margin,
+//! not an exact threshold.
+
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::dsign::DsignAlgorithm;
+use crate::dsign::ed25519::{Ed25519, Ed25519Signature, Ed25519VerificationKey};
+use crate::hash::HashAlgorithm;
+use crate::kes::{Blake2b256, KesAlgorithm, Sum6Kes};
+use crate::vrf::VRFAlgorithm;
+use crate::vrf::praos::{PraosProof, PraosVRF, PraosVerificationKey};
+
+/// RFC 8032 section 7.1, test 2.
+const ED25519_VERIFICATION_KEY: &str =
+    "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c";
+const ED25519_MESSAGE: &str = "72";
+const ED25519_SIGNATURE: &str = "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+                                 085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00";
+
+/// `vrf_ver03_standard_10`: draft-03 Praos, empty message.
+const PRAOS_VERIFICATION_KEY: &str =
+    "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+const PRAOS_MESSAGE: &str = "";
+const PRAOS_PROOF: &str = "b6b4699f87d56126c9117a7da55bd0085246f4c56dbc95d20172612e9d38e8d7\
+                           ca65e573a126ed88d4e30a46f80a666854d675cf3ba81de0de043c3774f06156\
+                           0f55edc256a787afe701677c0f602900";
+
+/// `level6_kes_vector_1` of `sum_kes_test_vectors.json`, period 0.
+const SUM6_VERIFICATION_KEY: &str =
+    "3de0de3e9050092b65d3b0eca5fa49ec31c6e6e5f5ac0e97f9fde1d8b775f6d2";
+const SUM6_PERIOD: u64 = 0;
+const SUM6_MESSAGE: &str = "0000000000000000";
+const SUM6_SIGNATURE: &str = "d0acc28934fc7fd05e2b5e4b7c0bdaf809b34f4772a66e6920e95bbf5b2c663b\
+                              496ab274dc8bf786f192505265e5633e35c6a3fe9247f525a81e98094de00607\
+                              0b35ba6c50e54abcdcfed25789574ec5b18e954d1ab55cfe46c6872a833b6b2d\
+                              5a665143b5c2cea81e197a7667f19868614367dc5341f2c47852a386f386fc29\
+                              de92ac42c6ba925ed798fd3797dc11786962f30fcaac5148a069d64b349f6a28\
+                              43bc9072993fb1e0200c26ffd7574f15031b140366c4d5c21450a50ad3ff0e99\
+                              63d5f30d8dc458ce95b13cd8366c892809ae023bca5257a131321b52685e03d6\
+                              a233665cf7f96c13567d6b7060075335bdd0387712d9069ecaa453efafc656b1\
+                              5057dc1f65a7450ffa9b2eb87f34d65e625caf0a544f7eeb43931b6e62129335\
+                              9e2f335946f650e38cabddbbfacbec5402ee2371f014b30cd75916faf6d362b2\
+                              57f4847079e551c4646058e77375803e75ad3e837ae33f8980c65a5b8619302d\
+                              10c2e96352d94adfdd72c87028c9a01976aaab2cb201f95645be50c174078bed\
+                              c0f048013d98b043154b506accfe7e1f59a075ec8934ec59b87c44abda8dffc4\
+                              10fd3f7ca16ee3c9407fd49e022f40797c76373dfc5e0afa3d38d80fbc82e504";
+
+/// Size of each block hashed by the Blake2b-256 workload.
+pub const HASH_BLOCK_SIZE: usize = 1024;
+
+/// Settings for [`measure_crypto_throughput`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProbeConfig {
+    /// Total time spent in measurement loops, split evenly across the four
+    /// workloads. Decoding the embedded inputs and starting threads come on
+    /// top, and each workload runs at least one operation per thread.
+    pub budget: Duration,
+    /// Threads running each workload at once; the reported rate is their sum.
+    pub threads: NonZeroUsize,
+}
+
+impl ProbeConfig {
+    /// Budget of [`ProbeConfig::default`].
+    pub const DEFAULT_BUDGET: Duration = Duration::from_millis(200);
+}
+
+impl Default for ProbeConfig {
+    fn default() -> Self {
+        ProbeConfig {
+            budget: Self::DEFAULT_BUDGET,
+            threads: NonZeroUsize::MIN,
+        }
+    }
+}
+
+/// Operations completed by one workload and the wall-clock time they took.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Throughput {
+    /// Operations completed, summed over all threads.
+    pub operations: u64,
+    /// Wall-clock time of the measurement loop, from the start of the first
+    /// thread to the end of the last.
+    pub elapsed: Duration,
+}
+
+impl Throughput {
+    /// Operations per second, zero if nothing was measured.
+    #[must_use]
+    pub fn ops_per_sec(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds == 0.0 {
+            0.0
+        } else {
+            self.operations as f64 / seconds
+        }
+    }
+}
+
+/// Result of [`measure_crypto_throughput`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CryptoThroughputReport {
+    /// Ed25519 signature verifications.
+    pub ed25519_verify: Throughput,
+    /// Praos VRF proof verifications.
+    pub praos_vrf_verify: Throughput,
+    /// `Sum6Kes` signature verifications.
+    pub sum6_kes_verify: Throughput,
+    /// Blake2b-256 digests of [`HASH_BLOCK_SIZE`]-byte blocks.
+    pub blake2b256_1kib: Throughput,
+    /// Threads each workload ran on.
+    pub threads: NonZeroUsize,
+    /// Wall-clock time of the whole probe, including setup.
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for CryptoThroughputReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Ed25519 verify {:.0}/s, Praos VRF verify {:.0}/s, Sum6 KES verify {:.0}/s, \
+             Blake2b-256 1 KiB {:.0}/s ({} thread(s), {} ms)",
+            self.ed25519_verify.ops_per_sec(),
+            self.praos_vrf_verify.ops_per_sec(),
+            self.sum6_kes_verify.ops_per_sec(),
+            self.blake2b256_1kib.ops_per_sec(),
+            self.threads,
+            self.elapsed.as_millis(),
+        )
+    }
+}
+
+/// Decoded inputs, shared read-only by every measurement thread.
+struct Inputs {
+    ed25519_vk: Ed25519VerificationKey,
+    ed25519_message: Vec<u8>,
+    ed25519_signature: Ed25519Signature,
+    praos_vk: PraosVerificationKey,
+    praos_message: Vec<u8>,
+    praos_proof: PraosProof,
+    sum6_vk: <Sum6Kes as KesAlgorithm>::VerificationKey,
+    sum6_message: Vec<u8>,
+    sum6_signature: <Sum6Kes as KesAlgorithm>::Signature,
+    hash_block: Vec<u8>,
+}
+
+fn decode(hex: &str) -> Vec<u8> {
+    hex::decode(hex).expect("embedded probe input is valid hex")
+}
+
+impl Inputs {
+    fn embedded() -> Self {
+        Inputs {
+            ed25519_vk: Ed25519::raw_deserialize_verification_key(&decode(
+                ED25519_VERIFICATION_KEY,
+            ))
+            .expect("embedded Ed25519 verification key"),
+            ed25519_message: decode(ED25519_MESSAGE),
+            ed25519_signature: Ed25519::raw_deserialize_signature(&decode(ED25519_SIGNATURE))
+                .expect("embedded Ed25519 signature"),
+            praos_vk: PraosVerificationKey::from_bytes(&decode(PRAOS_VERIFICATION_KEY))
+                .expect("embedded Praos verification key"),
+            praos_message: decode(PRAOS_MESSAGE),
+            praos_proof: PraosProof::from_bytes(&decode(PRAOS_PROOF))
+                .expect("embedded Praos proof"),
+            sum6_vk: Sum6Kes::raw_deserialize_verification_key_kes(&decode(SUM6_VERIFICATION_KEY))
+                .expect("embedded Sum6 verification key"),
+            sum6_message: decode(SUM6_MESSAGE),
+            sum6_signature: Sum6Kes::raw_deserialize_signature_kes(&decode(SUM6_SIGNATURE))
+                .expect("embedded Sum6 signature"),
+            hash_block: (0..HASH_BLOCK_SIZE).map(|i| i as u8).collect(),
+        }
+    }
+
+    fn ed25519_verify(&self) -> bool {
+        Ed25519::verify_bytes(
+            &(),
+            &self.ed25519_vk,
+            &self.ed25519_message,
+            &self.ed25519_signature,
+        )
+        .is_ok()
+    }
+
+    fn praos_vrf_verify(&self) -> bool {
+        PraosVRF::verify_bytes(&(), &self.praos_vk, &self.praos_message, &self.praos_proof)
+            .is_some()
+    }
+
+    fn sum6_kes_verify(&self) -> bool {
+        Sum6Kes::verify_kes(
+            &(),
+            &self.sum6_vk,
+            SUM6_PERIOD,
+            &self.sum6_message,
+            &self.sum6_signature,
+        )
+        .is_ok()
+    }
+
+    fn blake2b256(&self) -> bool {
+        Blake2b256::hash(&self.hash_block).len() == Blake2b256::OUTPUT_SIZE
+    }
+}
+
+/// Run `operation` until `slice` has passed, at least once.
+fn run_until(slice: Duration, operation: &dyn Fn() -> bool) -> u64 {
+    run_until_on(&Instant::now, slice, operation)
+}
+
+/// [`run_until`] reading the time from `clock`, which tests replace with a
+/// deterministic one.
+fn run_until_on(clock: &dyn Fn() -> Instant, slice: Duration, operation: &dyn Fn() -> bool) -> u64 {
+    let start = clock();
+    let mut operations = 0u64;
+    loop {
+        std::hint::black_box(operation());
+        operations += 1;
+        if clock().duration_since(start) >= slice {
+            return operations;
+        }
+    }
+}
+
+fn measure(
+    slice: Duration,
+    threads: NonZeroUsize,
+    operation: &(dyn Fn() -> bool + Sync),
+) -> Throughput {
+    let start = Instant::now();
+    let operations = if threads.get() == 1 {
+        run_until(slice, operation)
+    } else {
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads.get())
+                .map(|_| scope.spawn(|| run_until(slice, operation)))
+                .collect();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().expect("probe thread panicked"))
+                .sum()
+        })
+    };
+    Throughput {
+        operations,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Measure verification and hashing throughput on this machine.
+///
+/// Each of the four workloads runs for a quarter of `config.budget` on
+/// `config.threads` threads, so the call returns after roughly the budget
+/// plus a few milliseconds of setup. It is meant for startup logging, not
+/// benchmarking; see the [module documentation](self).
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use cardano_crypto_class::capability::{ProbeConfig, measure_crypto_throughput};
+///
+/// let report = measure_crypto_throughput(ProbeConfig {
+///     budget: Duration::from_millis(20),
+///     ..ProbeConfig::default()
+/// });
+/// assert!(report.ed25519_verify.operations > 0);
+/// println!("crypto throughput: {report}");
+/// ```
+#[must_use]
+pub fn measure_crypto_throughput(config: ProbeConfig) -> CryptoThroughputReport {
+    let start = Instant::now();
+    let inputs = Inputs::embedded();
+    let slice = config.budget / 4;
+    let threads = config.threads;
+    CryptoThroughputReport {
+        ed25519_verify: measure(slice, threads, &|| inputs.ed25519_verify()),
+        praos_vrf_verify: measure(slice, threads, &|| inputs.praos_vrf_verify()),
+        sum6_kes_verify: measure(slice, threads, &|| inputs.sum6_kes_verify()),
+        blake2b256_1kib: measure(slice, threads, &|| inputs.blake2b256()),
+        threads,
+        elapsed: start.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_inputs_verify() {
+        let inputs = Inputs::embedded();
+        assert!(inputs.ed25519_verify());
+        assert!(inputs.praos_vrf_verify());
+        assert!(inputs.sum6_kes_verify());
+        assert!(inputs.blake2b256());
+    }
+
+    /// A clock that moves forward one millisecond every time it is read.
+    fn ticking_clock() -> impl Fn() -> Instant {
+        let base = Instant::now();
+        let ticks = std::cell::Cell::new(0u64);
+        move || {
+            let tick = ticks.get();
+            ticks.set(tick + 1);
+            base + Duration::from_millis(tick)
+        }
+    }
+
+    #[test]
+    fn operations_scale_with_the_budget() {
+        let small = run_until_on(&ticking_clock(), Duration::from_millis(10), &|| true);
+        let large = run_until_on(&ticking_clock(), Duration::from_millis(100), &|| true);
+        assert_eq!(small, 10);
+        assert_eq!(large, 100);
+    }
+
+    #[test]
+    fn stops_at_the_first_check_past_the_deadline() {
+        let calls = std::cell::Cell::new(0u64);
+        let operation = || {
+            calls.set(calls.get() + 1);
+            true
+        };
+        let operations = run_until_on(&ticking_clock(), Duration::from_millis(3), &operation);
+        assert_eq!(operations, 3);
+        assert_eq!(calls.get(), 3);
+
+        // An empty slice still runs one operation.
+        assert_eq!(run_until_on(&ticking_clock(), Duration::ZERO, &|| true), 1);
+    }
+
+    #[test]
+    fn ops_per_sec_handles_zero_elapsed() {
+        let throughput = Throughput {
+            operations: 5,
+            elapsed: Duration::ZERO,
+        };
+        assert_eq!(throughput.ops_per_sec(), 0.0);
+    }
+}
@@ -9,17 +9,176 @@ use std::cell::Cell;
 
 use thiserror::Error;
 
-/// Error raised when a direct serialisation or deserialisation operation
-/// writes or reads more bytes than expected.
-#[derive(Debug, Error, Clone, PartialEq, Eq)]
+use crate::mlocked_bytes::MLockedError;
+
+/// Sizes reported when a direct serialisation or deserialisation operation
+/// writes or reads more, or fewer, bytes than expected.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
 #[error("size check failed: expected {expected_size}, actual {actual_size}")]
 pub struct SizeCheckError {
     pub expected_size: usize,
     pub actual_size: usize,
 }
 
+impl SizeCheckError {
+    /// Whether more bytes were produced or requested than the buffer holds.
+    #[must_use]
+    pub const fn is_overflow(&self) -> bool {
+        self.actual_size > self.expected_size
+    }
+
+    /// Stable identifier: `direct.overflow` or `direct.underflow`, the code
+    /// of the [`DirectSerialiseError`] this converts into.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        if self.is_overflow() {
+            "direct.overflow"
+        } else {
+            "direct.underflow"
+        }
+    }
+
+    /// Always `false`: the sizes are fixed by the value and the buffer.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SizeCheckError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(4))?;
+        map.serialize_entry("code", self.code())?;
+        map.serialize_entry("expected_size", &self.expected_size)?;
+        map.serialize_entry("actual_size", &self.actual_size)?;
+        map.serialize_entry("message", &self.to_string())?;
+        map.end()
+    }
+}
+
+/// Error raised by direct serialisation and deserialisation.
+///
+/// Size mismatches keep their [`SizeCheckError`] as the error source; the
+/// `From<SizeCheckError>` conversion picks [`Overflow`](Self::Overflow) or
+/// [`Underflow`](Self::Underflow) from the sizes, so callbacks can return a
+/// `SizeCheckError` with `?`. `KesMError`, `DsignMError` and
+/// `cardano_binary::BinaryError` convert from both types.
+#[derive(Debug, Error)]
+pub enum DirectSerialiseError {
+    /// More bytes were written or read than the buffer holds.
+    #[error("direct serialisation overran the buffer")]
+    Overflow(#[source] SizeCheckError),
+    /// Fewer bytes were written or read than the buffer holds.
+    #[error("direct serialisation did not fill the buffer")]
+    Underflow(#[source] SizeCheckError),
+    /// The bytes were read but do not form a valid value, e.g. a
+    /// verification key that is not a curve point.
+    #[error("direct deserialisation produced an invalid {what}")]
+    InvalidValue { what: &'static str },
+    /// Mlocked memory for a deserialised secret could not be allocated.
+    #[error(transparent)]
+    Mlocked(#[from] MLockedError),
+}
+
+impl DirectSerialiseError {
+    /// The size mismatch behind an overflow or underflow.
+    #[must_use]
+    pub const fn size_check(&self) -> Option<&SizeCheckError> {
+        match self {
+            DirectSerialiseError::Overflow(sizes) | DirectSerialiseError::Underflow(sizes) => {
+                Some(sizes)
+            },
+            DirectSerialiseError::InvalidValue { .. } | DirectSerialiseError::Mlocked(_) => None,
+        }
+    }
+
+    /// Stable identifier of this variant; a wrapped mlocked error reports
+    /// its own code.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            DirectSerialiseError::Overflow(_) => "direct.overflow",
+            DirectSerialiseError::Underflow(_) => "direct.underflow",
+            DirectSerialiseError::InvalidValue { .. } => "direct.invalid_value",
+            DirectSerialiseError::Mlocked(err) => err.code(),
+        }
+    }
+
+    /// Whether retrying could succeed; only mlocked allocation failures are
+    /// transient.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        match self {
+            DirectSerialiseError::Mlocked(err) => err.is_transient(),
+            _ => false,
+        }
+    }
+}
+
+impl From<SizeCheckError> for DirectSerialiseError {
+    fn from(sizes: SizeCheckError) -> Self {
+        if sizes.is_overflow() {
+            DirectSerialiseError::Overflow(sizes)
+        } else {
+            DirectSerialiseError::Underflow(sizes)
+        }
+    }
+}
+
+/// Size mismatches serialise with their sizes; a wrapped mlocked error
+/// serialises as itself.
+#[cfg(feature = "serde")]
+impl serde::Serialize for DirectSerialiseError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        match self {
+            DirectSerialiseError::Mlocked(err) => err.serialize(serializer),
+            _ => {
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_entry("code", self.code())?;
+                match self {
+                    DirectSerialiseError::Overflow(sizes)
+                    | DirectSerialiseError::Underflow(sizes) => {
+                        map.serialize_entry("expected_size", &sizes.expected_size)?;
+                        map.serialize_entry("actual_size", &sizes.actual_size)?;
+                    },
+                    DirectSerialiseError::InvalidValue { what } => {
+                        map.serialize_entry("what", what)?;
+                    },
+                    DirectSerialiseError::Mlocked(_) => {},
+                }
+                map.serialize_entry("message", &self.to_string())?;
+                map.end()
+            },
+        }
+    }
+}
+
+/// `BinaryError` has no direct-serialisation variant, so the error travels
+/// as the source of an I/O error: `UnexpectedEof` for an overflow (the
+/// buffer ran out), `InvalidData` otherwise.
+impl From<DirectSerialiseError> for cardano_binary::BinaryError {
+    fn from(err: DirectSerialiseError) -> Self {
+        let kind = match err {
+            DirectSerialiseError::Overflow(_) => std::io::ErrorKind::UnexpectedEof,
+            _ => std::io::ErrorKind::InvalidData,
+        };
+        cardano_binary::BinaryError::Io(std::io::Error::new(kind, err))
+    }
+}
+
+impl From<SizeCheckError> for cardano_binary::BinaryError {
+    fn from(sizes: SizeCheckError) -> Self {
+        DirectSerialiseError::from(sizes).into()
+    }
+}
+
 /// Convenience alias for results produced by direct serialisation helpers.
-pub type DirectResult<T> = Result<T, SizeCheckError>;
+pub type DirectResult<T> = Result<T, DirectSerialiseError>;
 
 /// Trait for types that can expose their internal representation as raw
 /// memory blocks for serialisation.
@@ -48,7 +207,8 @@ pub trait DirectDeserialise: Sized {
 ///
 /// # Errors
 ///
-/// Returns an error if more than `dst_len` bytes are written.
+/// Returns [`DirectSerialiseError::Overflow`] if more than `dst_len` bytes
+/// are written.
 pub fn direct_serialise_to<T: DirectSerialise>(
     mut write: impl FnMut(usize, &[u8]) -> DirectResult<()>,
     dst_len: usize,
@@ -64,7 +224,8 @@ pub fn direct_serialise_to<T: DirectSerialise>(
             return Err(SizeCheckError {
                 expected_size: dst_len - current,
                 actual_size: next - current,
-            });
+            }
+            .into());
         }
         write(current, chunk)?;
         pos.set(next);
@@ -91,7 +252,8 @@ pub fn direct_serialise_to_checked<T: DirectSerialise>(
         Err(SizeCheckError {
             expected_size: dst_len,
             actual_size: written,
-        })
+        }
+        .into())
     } else {
         Ok(())
     }
@@ -159,7 +321,8 @@ pub fn direct_deserialise_from<T: DirectDeserialise>(
             return Err(SizeCheckError {
                 expected_size: src_len - current,
                 actual_size: next - current,
-            });
+            }
+            .into());
         }
         read(current, chunk)?;
         pos.set(next);
@@ -185,7 +348,8 @@ pub fn direct_deserialise_from_checked<T: DirectDeserialise>(
         Err(SizeCheckError {
             expected_size: src_len,
             actual_size: read_len,
-        })
+        }
+        .into())
     } else {
         Ok(value)
     }
@@ -271,25 +435,40 @@ mod tests {
         let pair = Pair(*b"AAAA", *b"BBBB");
         let mut buf = [0u8; 4];
         let err = direct_serialise_buf(&mut buf, &pair).unwrap_err();
-        assert_eq!(
+        assert!(matches!(
             err,
-            SizeCheckError {
+            DirectSerialiseError::Overflow(SizeCheckError {
                 expected_size: 0,
                 actual_size: 4
-            }
-        );
+            })
+        ));
     }
 
     #[test]
     fn deserialise_size_mismatch_errors() {
         let buf = [0u8; 4];
         let err = direct_deserialise_buf_checked::<Pair>(&buf).unwrap_err();
-        assert_eq!(
+        assert!(matches!(
             err,
-            SizeCheckError {
+            DirectSerialiseError::Overflow(SizeCheckError {
                 expected_size: 0,
                 actual_size: 4
-            }
+            })
+        ));
+    }
+
+    #[test]
+    fn short_writes_are_underflows() {
+        let pair = Pair(*b"AAAA", *b"BBBB");
+        let mut buf = [0u8; 12];
+        let err = direct_serialise_buf_checked(&mut buf, &pair).unwrap_err();
+        assert_eq!(err.code(), "direct.underflow");
+        assert_eq!(
+            err.size_check(),
+            Some(&SizeCheckError {
+                expected_size: 12,
+                actual_size: 8
+            })
         );
     }
 }
@@ -10,8 +10,38 @@
 //!
 //! This implementation is provided for cross-chain bridge compatibility only.
 //! For Cardano consensus, use Ed25519 signatures instead.
+//!
+//! # Message hashes
+//!
+//! ECDSA over Secp256k1 signs a 32-byte digest, never an arbitrary message.
+//! As in the Haskell `EcdsaSecp256k1DSIGN` instance (and the Plutus builtin
+//! `verifyEcdsaSecp256k1Signature`), callers hash the message themselves and
+//! pass the result as a [`MessageHash`], typically via [`hash_and_pack`].
+//! The byte-oriented [`DsignAlgorithm`] methods accept only 32-byte inputs and
+//! treat them as the hash; nothing is hashed implicitly.
+//!
+//! ```rust
+//! use cardano_crypto_class::dsign::ecdsa_secp256k1::{
+//!     EcdsaContext, EcdsaSecp256k1DSIGN, hash_and_pack,
+//! };
+//! use cardano_crypto_class::dsign::DsignAlgorithm;
+//! use cardano_crypto_class::hash::Sha3_256;
+//! use cardano_crypto_class::seed::Seed;
+//!
+//! let signing_key = EcdsaSecp256k1DSIGN::gen_key(&Seed::from_bytes(&[7u8; 32]));
+//! let verification_key = EcdsaSecp256k1DSIGN::derive_verification_key(&signing_key);
+//!
+//! let hash = hash_and_pack::<Sha3_256>(b"bridge transfer #42");
+//! let context = EcdsaContext::default();
+//! let signature = EcdsaSecp256k1DSIGN::sign_message_hash(&context, &hash, &signing_key);
+//! assert!(
+//!     EcdsaSecp256k1DSIGN::verify_message_hash(&context, &verification_key, &hash, &signature)
+//!         .is_ok()
+//! );
+//! ```
 
 use crate::dsign::{DsignAlgorithm, DsignError};
+use crate::hash::HashAlgorithm;
 use rand_core::{CryptoRng, RngCore};
 use secp256k1::{Message, PublicKey, Secp256k1, SecretKey, ecdsa::Signature as Secp256k1Signature};
 use std::fmt;
@@ -51,13 +81,90 @@ impl fmt::Debug for Signature {
     }
 }
 
-/// Context type for ECDSA Secp256k1 (no context needed).
-#[derive(Clone, Copy, Debug)]
-pub struct Context;
+/// Size in bytes of the digest ECDSA Secp256k1 signs.
+pub const MESSAGE_HASH_SIZE: usize = 32;
+
+/// A 32-byte message digest, the only input ECDSA Secp256k1 signs.
+///
+/// Mirrors Haskell's `MessageHash`. Build one from an existing digest with
+/// [`MessageHash::from_bytes`], or hash a message with [`hash_and_pack`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MessageHash([u8; MESSAGE_HASH_SIZE]);
+
+impl MessageHash {
+    /// Wrap an existing 32-byte digest (Haskell's `toMessageHash`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DsignError::WrongLength`] if `bytes` is not exactly
+    /// [`MESSAGE_HASH_SIZE`] bytes long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DsignError> {
+        <[u8; MESSAGE_HASH_SIZE]>::try_from(bytes)
+            .map(Self)
+            .map_err(|_| {
+                DsignError::wrong_length(
+                    "EcdsaSecp256k1 message hash",
+                    MESSAGE_HASH_SIZE,
+                    bytes.len(),
+                )
+            })
+    }
 
-impl Default for Context {
+    /// The digest bytes (Haskell's `fromMessageHash`).
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; MESSAGE_HASH_SIZE] {
+        &self.0
+    }
+}
+
+impl From<[u8; MESSAGE_HASH_SIZE]> for MessageHash {
+    fn from(bytes: [u8; MESSAGE_HASH_SIZE]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl fmt::Debug for MessageHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MessageHash({})", hex::encode(self.0))
+    }
+}
+
+/// Hash `message` with `H` and pack the digest as a [`MessageHash`].
+///
+/// Mirrors Haskell's `hashAndPack`. `H` must produce a 32-byte digest (for
+/// example [`Sha256`](crate::hash::Sha256), [`Sha3_256`](crate::hash::Sha3_256),
+/// [`Keccak256`](crate::hash::Keccak256) or
+/// [`Blake2b256`](crate::hash::Blake2b256)); other sizes are rejected at
+/// compile time.
+#[must_use]
+pub fn hash_and_pack<H: HashAlgorithm>(message: &[u8]) -> MessageHash {
+    const {
+        assert!(
+            H::OUTPUT_SIZE == MESSAGE_HASH_SIZE,
+            "hash_and_pack needs a 32-byte hash algorithm"
+        );
+    }
+    let mut digest = [0u8; MESSAGE_HASH_SIZE];
+    digest.copy_from_slice(&H::hash(message));
+    MessageHash(digest)
+}
+
+/// Verification policy for ECDSA Secp256k1.
+///
+/// Signing always produces low-s signatures (RFC 6979 nonces). By default
+/// verification rejects high-s signatures as libsecp256k1 and the Haskell
+/// reference do; clearing `enforce_low_s` normalises `s` first, which some
+/// bridges need to accept signatures produced by other ECDSA libraries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EcdsaContext {
+    pub enforce_low_s: bool,
+}
+
+impl Default for EcdsaContext {
     fn default() -> Self {
-        Context
+        EcdsaContext {
+            enforce_low_s: true,
+        }
     }
 }
 
@@ -65,7 +172,7 @@ impl DsignAlgorithm for EcdsaSecp256k1DSIGN {
     type SigningKey = SigningKey;
     type VerificationKey = VerificationKey;
     type Signature = Signature;
-    type Context = Context;
+    type Context = EcdsaContext;
 
     const ALGORITHM_NAME: &'static str = "EcdsaSecp256k1DSIGN";
     const SEED_SIZE: usize = 32;
@@ -79,60 +186,57 @@ impl DsignAlgorithm for EcdsaSecp256k1DSIGN {
         VerificationKey(public_key)
     }
 
+    /// Sign a 32-byte message hash given as raw bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `message` is not exactly [`MESSAGE_HASH_SIZE`] bytes; hash
+    /// longer messages with [`hash_and_pack`] and use
+    /// [`EcdsaSecp256k1DSIGN::sign_message_hash`]. Generic code signs through
+    /// [`DsignAlgorithm::try_sign_bytes`], which reports the length instead.
     fn sign_bytes(
-        _context: &Self::Context,
+        context: &Self::Context,
         message: &[u8],
         signing_key: &Self::SigningKey,
     ) -> Self::Signature {
-        let secp = Secp256k1::new();
-
-        // For ECDSA, we need a 32-byte message hash
-        // If message is not 32 bytes, hash it first
-        let message_hash = if message.len() == 32 {
-            let mut arr = [0u8; 32];
-            arr.copy_from_slice(message);
-            arr
-        } else {
-            use sha2::{Digest, Sha256};
-            let digest = Sha256::digest(message);
-            let mut arr = [0u8; 32];
-            arr.copy_from_slice(&digest);
-            arr
-        };
-
-        let message_obj = Message::from_digest(message_hash);
+        let hash = MessageHash::from_bytes(message)
+            .expect("ECDSA Secp256k1 signs 32-byte message hashes only");
+        Self::sign_message_hash(context, &hash, signing_key)
+    }
 
-        let signature = secp.sign_ecdsa(message_obj, &signing_key.0);
-        Signature(signature)
+    /// Sign a 32-byte message hash given as raw bytes.
+    ///
+    /// Returns [`DsignError::WrongLength`] rather than hashing when `message`
+    /// is not exactly [`MESSAGE_HASH_SIZE`] bytes.
+    fn try_sign_bytes(
+        context: &Self::Context,
+        message: &[u8],
+        signing_key: &Self::SigningKey,
+    ) -> Result<Self::Signature, DsignError> {
+        let hash = MessageHash::from_bytes(message)?;
+        Ok(Self::sign_message_hash(context, &hash, signing_key))
     }
 
+    /// Verify a signature over a 32-byte message hash given as raw bytes.
+    ///
+    /// Returns [`DsignError::WrongLength`] rather than hashing when `message`
+    /// is not exactly [`MESSAGE_HASH_SIZE`] bytes.
     fn verify_bytes(
-        _context: &Self::Context,
+        context: &Self::Context,
         verification_key: &Self::VerificationKey,
         message: &[u8],
         signature: &Self::Signature,
     ) -> Result<(), DsignError> {
-        let secp = Secp256k1::new();
-
-        // Hash the message if it's not already 32 bytes
-        let message_hash = if message.len() == 32 {
-            let mut arr = [0u8; 32];
-            arr.copy_from_slice(message);
-            arr
-        } else {
-            use sha2::{Digest, Sha256};
-            let digest = Sha256::digest(message);
-            let mut arr = [0u8; 32];
-            arr.copy_from_slice(&digest);
-            arr
-        };
-
-        let message_obj = Message::from_digest(message_hash);
-
-        secp.verify_ecdsa(message_obj, &signature.0, &verification_key.0)
-            .map_err(|_| DsignError::VerificationFailed)
+        let hash = MessageHash::from_bytes(message)?;
+        Self::verify_message_hash(context, verification_key, &hash, signature)
     }
 
+    /// Interpret the 32 seed bytes as a big-endian secret scalar.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scalar is zero or not below the curve order `n`: the
+    /// bytes are neither reduced modulo `n` nor replaced by a retry.
     fn gen_key_from_seed_bytes(seed: &[u8]) -> Self::SigningKey {
         assert_eq!(
             seed.len(),
@@ -184,6 +288,47 @@ impl DsignAlgorithm for EcdsaSecp256k1DSIGN {
     }
 }
 
+impl EcdsaSecp256k1DSIGN {
+    /// Sign a message hash (RFC 6979 deterministic nonce, low-s form).
+    #[must_use]
+    pub fn sign_message_hash(
+        _context: &EcdsaContext,
+        hash: &MessageHash,
+        signing_key: &SigningKey,
+    ) -> Signature {
+        let secp = Secp256k1::new();
+        Signature(secp.sign_ecdsa(Message::from_digest(hash.0), &signing_key.0))
+    }
+
+    /// Verify a signature over a message hash.
+    ///
+    /// High-s signatures are rejected, matching libsecp256k1 and the Haskell
+    /// reference, unless `context.enforce_low_s` is `false`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DsignError::VerificationFailed`] if the signature does not
+    /// verify.
+    pub fn verify_message_hash(
+        context: &EcdsaContext,
+        verification_key: &VerificationKey,
+        hash: &MessageHash,
+        signature: &Signature,
+    ) -> Result<(), DsignError> {
+        let secp = Secp256k1::new();
+        let mut signature = signature.0;
+        if !context.enforce_low_s {
+            signature.normalize_s();
+        }
+        secp.verify_ecdsa(
+            Message::from_digest(hash.0),
+            &signature,
+            &verification_key.0,
+        )
+        .map_err(|_| DsignError::VerificationFailed)
+    }
+}
+
 /// Generate a keypair using a cryptographic RNG.
 ///
 /// # Panics
@@ -204,6 +349,7 @@ pub fn generate_keypair<R: RngCore + CryptoRng>(rng: &mut R) -> (SigningKey, Ver
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hash::{Sha3_256, Sha256};
     use crate::seed::Seed;
 
     #[test]
@@ -211,13 +357,27 @@ mod tests {
         let mut rng = rand::rng();
         let (signing_key, verification_key) = generate_keypair(&mut rng);
 
-        let context = Context;
-        let message = b"Hello, cross-chain world!";
-        let signature = EcdsaSecp256k1DSIGN::sign_bytes(&context, message, &signing_key);
+        let context = EcdsaContext::default();
+        let hash = hash_and_pack::<Sha256>(b"Hello, cross-chain world!");
+        let signature = EcdsaSecp256k1DSIGN::sign_message_hash(&context, &hash, &signing_key);
 
         assert!(
-            EcdsaSecp256k1DSIGN::verify_bytes(&context, &verification_key, message, &signature)
-                .is_ok()
+            EcdsaSecp256k1DSIGN::verify_message_hash(
+                &context,
+                &verification_key,
+                &hash,
+                &signature
+            )
+            .is_ok()
+        );
+        assert!(
+            EcdsaSecp256k1DSIGN::verify_bytes(
+                &context,
+                &verification_key,
+                hash.as_bytes(),
+                &signature
+            )
+            .is_ok()
         );
     }
 
@@ -246,10 +406,10 @@ mod tests {
     fn test_ecdsa_secp256k1_signature_format() {
         let mut rng = rand::rng();
         let (signing_key, _) = generate_keypair(&mut rng);
-        let context = Context;
-        let message = b"Test message";
+        let context = EcdsaContext::default();
+        let message = hash_and_pack::<Sha256>(b"Test message");
 
-        let signature = EcdsaSecp256k1DSIGN::sign_bytes(&context, message, &signing_key);
+        let signature = EcdsaSecp256k1DSIGN::sign_bytes(&context, message.as_bytes(), &signing_key);
         let sig_bytes = EcdsaSecp256k1DSIGN::raw_serialize_signature(&signature);
 
         assert_eq!(sig_bytes.len(), EcdsaSecp256k1DSIGN::SIGNATURE_SIZE);
@@ -276,20 +436,167 @@ mod tests {
     fn test_ecdsa_secp256k1_wrong_signature() {
         let mut rng = rand::rng();
         let (signing_key, verification_key) = generate_keypair(&mut rng);
-        let context = Context;
+        let context = EcdsaContext::default();
 
-        let message = b"Original message";
-        let signature = EcdsaSecp256k1DSIGN::sign_bytes(&context, message, &signing_key);
+        let message = hash_and_pack::<Sha256>(b"Original message");
+        let signature = EcdsaSecp256k1DSIGN::sign_message_hash(&context, &message, &signing_key);
 
-        let wrong_message = b"Modified message";
-        assert!(
-            EcdsaSecp256k1DSIGN::verify_bytes(
+        let wrong_message = hash_and_pack::<Sha256>(b"Modified message");
+        assert_eq!(
+            EcdsaSecp256k1DSIGN::verify_message_hash(
                 &context,
                 &verification_key,
-                wrong_message,
+                &wrong_message,
+                &signature
+            ),
+            Err(DsignError::VerificationFailed)
+        );
+    }
+
+    #[test]
+    fn test_message_hash_from_bytes_checks_length() {
+        assert!(MessageHash::from_bytes(&[0u8; 32]).is_ok());
+        for len in [0, 31, 33, 64] {
+            assert_eq!(
+                MessageHash::from_bytes(&vec![0u8; len]),
+                Err(DsignError::wrong_length(
+                    "EcdsaSecp256k1 message hash",
+                    MESSAGE_HASH_SIZE,
+                    len
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn test_hash_and_pack_uses_requested_algorithm() {
+        let message = b"abc";
+        assert_eq!(
+            hash_and_pack::<Sha256>(message).as_bytes().as_slice(),
+            Sha256::hash(message).as_slice()
+        );
+        assert_eq!(
+            hash_and_pack::<Sha3_256>(message).as_bytes().as_slice(),
+            Sha3_256::hash(message).as_slice()
+        );
+        assert_ne!(
+            hash_and_pack::<Sha256>(message),
+            hash_and_pack::<Sha3_256>(message)
+        );
+    }
+
+    #[test]
+    fn test_ecdsa_secp256k1_verify_bytes_rejects_unhashed_message() {
+        let (signing_key, verification_key) = generate_keypair(&mut rand::rng());
+        let message = b"not a 32-byte hash";
+        let hash = hash_and_pack::<Sha256>(message);
+        let signature =
+            EcdsaSecp256k1DSIGN::sign_message_hash(&EcdsaContext::default(), &hash, &signing_key);
+
+        // Earlier versions hashed short messages with SHA-256 internally; the
+        // raw message must now be rejected instead of silently accepted.
+        assert_eq!(
+            EcdsaSecp256k1DSIGN::verify_bytes(
+                &EcdsaContext::default(),
+                &verification_key,
+                message,
                 &signature
+            ),
+            Err(DsignError::wrong_length(
+                "EcdsaSecp256k1 message hash",
+                MESSAGE_HASH_SIZE,
+                message.len()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_ecdsa_secp256k1_sign_bytes_matches_message_hash() {
+        let (signing_key, _) = generate_keypair(&mut rand::rng());
+        let hash = hash_and_pack::<Sha3_256>(b"same digest");
+        assert_eq!(
+            EcdsaSecp256k1DSIGN::sign_bytes(
+                &EcdsaContext::default(),
+                hash.as_bytes(),
+                &signing_key
+            ),
+            EcdsaSecp256k1DSIGN::sign_message_hash(&EcdsaContext::default(), &hash, &signing_key)
+        );
+    }
+
+    #[test]
+    fn test_ecdsa_secp256k1_high_s_policy() {
+        use num_bigint::BigUint;
+
+        let (signing_key, verification_key) = generate_keypair(&mut rand::rng());
+        let hash = hash_and_pack::<Sha256>(b"malleable");
+        let low =
+            EcdsaSecp256k1DSIGN::sign_message_hash(&EcdsaContext::default(), &hash, &signing_key);
+
+        // Flip s to n - s, the other valid but high-s form.
+        let order = BigUint::from_bytes_be(&secp256k1::constants::CURVE_ORDER);
+        let mut bytes = EcdsaSecp256k1DSIGN::raw_serialize_signature(&low);
+        let high_s = (order - BigUint::from_bytes_be(&bytes[32..])).to_bytes_be();
+        bytes[32..].fill(0);
+        bytes[64 - high_s.len()..].copy_from_slice(&high_s);
+        let high = EcdsaSecp256k1DSIGN::raw_deserialize_signature(&bytes).unwrap();
+
+        let strict = EcdsaContext::default();
+        let lenient = EcdsaContext {
+            enforce_low_s: false,
+        };
+        assert!(
+            EcdsaSecp256k1DSIGN::verify_message_hash(&strict, &verification_key, &hash, &high)
+                .is_err()
+        );
+        assert!(
+            EcdsaSecp256k1DSIGN::verify_message_hash(&lenient, &verification_key, &hash, &high)
+                .is_ok()
+        );
+        assert!(
+            EcdsaSecp256k1DSIGN::verify_message_hash(&lenient, &verification_key, &hash, &low)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "32-byte message hashes")]
+    fn test_ecdsa_secp256k1_sign_bytes_rejects_unhashed_message() {
+        let (signing_key, _) = generate_keypair(&mut rand::rng());
+        let _ =
+            EcdsaSecp256k1DSIGN::sign_bytes(&EcdsaContext::default(), b"too short", &signing_key);
+    }
+
+    #[test]
+    fn test_ecdsa_secp256k1_signed_dsign_rejects_unhashed_message() {
+        use crate::dsign::signed_dsign;
+
+        let (signing_key, _) = generate_keypair(&mut rand::rng());
+        let message = b"a signable representation longer than 32 bytes";
+        assert_eq!(
+            signed_dsign::<EcdsaSecp256k1DSIGN, _>(
+                &EcdsaContext::default(),
+                &message[..],
+                &signing_key
             )
-            .is_err()
+            .err(),
+            Some(DsignError::wrong_length(
+                "EcdsaSecp256k1 message hash",
+                MESSAGE_HASH_SIZE,
+                message.len()
+            ))
+        );
+
+        let hash = hash_and_pack::<Sha256>(message);
+        let signed = signed_dsign::<EcdsaSecp256k1DSIGN, _>(
+            &EcdsaContext::default(),
+            &hash.as_bytes()[..],
+            &signing_key,
+        )
+        .unwrap();
+        assert_eq!(
+            signed.signature(),
+            &EcdsaSecp256k1DSIGN::sign_message_hash(&EcdsaContext::default(), &hash, &signing_key)
         );
     }
 }
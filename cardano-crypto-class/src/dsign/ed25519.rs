@@ -2,11 +2,15 @@ use std::fmt;
 
 use core::convert::TryFrom;
 
+use ed25519_dalek::hazmat::{ExpandedSecretKey, raw_sign_byupdate};
 use ed25519_dalek::{Signature as DalekSignature, SigningKey, VerifyingKey};
 use ed25519_dalek::{Signer, Verifier};
+use sha2::{Digest, Sha512};
 
-use crate::direct_serialise::{DirectDeserialise, DirectResult, DirectSerialise, SizeCheckError};
-use crate::dsign::{DsignAlgorithm, DsignError};
+use crate::direct_serialise::{
+    DirectDeserialise, DirectResult, DirectSerialise, DirectSerialiseError,
+};
+use crate::dsign::{DsignAlgorithm, DsignError, DsignVectoredVerify};
 use crate::pinned_sized_bytes::PinnedSizedBytes;
 
 pub(crate) const SEED_BYTES: usize = 32;
@@ -44,15 +48,14 @@ impl Ed25519VerificationKey {
     }
 }
 
-// CBOR Serialization for Ed25519VerificationKey
+// Serde for Ed25519VerificationKey: a CBOR byte string, hex text in JSON
 #[cfg(feature = "serde")]
 impl serde::Serialize for Ed25519VerificationKey {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        // Serialize as CBOR bytes wrapping the raw verification key
-        serializer.serialize_bytes(self.as_bytes())
+        cardano_binary::bytes_or_hex::serialize(self.as_bytes(), serializer)
     }
 }
 
@@ -87,7 +90,92 @@ impl<'de> serde::Deserialize<'de> for Ed25519VerificationKey {
             }
         }
 
-        deserializer.deserialize_bytes(BytesVisitor)
+        cardano_binary::bytes_or_hex::deserialize_with(deserializer, BytesVisitor)
+    }
+}
+
+/// Borrowed view of an Ed25519 verification key, for verifying straight out
+/// of a decode buffer without copying the key into an owned
+/// [`Ed25519VerificationKey`].
+///
+/// Construction validates the key exactly as the owned key's deserialisers
+/// do, and [`Ed25519::verify_bytes_ref`] gives the same result as
+/// [`DsignAlgorithm::verify_bytes`] on the owned key.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Ed25519VerificationKeyRef<'a>(&'a [u8; VERIFICATION_KEY_BYTES]);
+
+impl fmt::Debug for Ed25519VerificationKeyRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ed25519VerificationKeyRef({})", hex::encode(self.0))
+    }
+}
+
+impl<'a> Ed25519VerificationKeyRef<'a> {
+    /// Borrow `bytes` as a verification key. Returns `None` unless `bytes`
+    /// is 32 bytes encoding a valid curve point.
+    #[must_use]
+    pub fn from_bytes(bytes: &'a [u8]) -> Option<Self> {
+        let array: &'a [u8; VERIFICATION_KEY_BYTES] = bytes.try_into().ok()?;
+        VerifyingKey::from_bytes(array).ok()?;
+        Some(Self(array))
+    }
+
+    /// The borrowed key bytes.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8; VERIFICATION_KEY_BYTES] {
+        self.0
+    }
+
+    /// Copy the key into an owned [`Ed25519VerificationKey`].
+    #[must_use]
+    pub fn to_verification_key(&self) -> Ed25519VerificationKey {
+        Ed25519VerificationKey(PinnedSizedBytes::from_array(*self.0))
+    }
+}
+
+impl<'a> From<&'a Ed25519VerificationKey> for Ed25519VerificationKeyRef<'a> {
+    fn from(key: &'a Ed25519VerificationKey) -> Self {
+        Self(key.as_bytes())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Ed25519VerificationKeyRef<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        cardano_binary::bytes_or_hex::serialize(self.0, serializer)
+    }
+}
+
+/// Borrows from the input, so it needs a deserializer that hands out
+/// borrowed bytes, such as [`cardano_binary::decode_full_borrowed`].
+#[cfg(feature = "serde")]
+impl<'de: 'a, 'a> serde::Deserialize<'de> for Ed25519VerificationKeyRef<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct BorrowedBytesVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for BorrowedBytesVisitor {
+            type Value = Ed25519VerificationKeyRef<'de>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "borrowed Ed25519 verification key bytes")
+            }
+
+            fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ed25519VerificationKeyRef::from_bytes(v)
+                    .ok_or_else(|| E::custom("invalid Ed25519 verification key"))
+            }
+        }
+
+        deserializer.deserialize_bytes(BorrowedBytesVisitor)
     }
 }
 
@@ -107,10 +195,11 @@ impl DirectDeserialise for Ed25519VerificationKey {
         let (bytes, result) =
             PinnedSizedBytes::<VERIFICATION_KEY_BYTES>::create_result_with_slice(|buf| pull(buf));
         result?;
-        Ed25519VerificationKey::from_bytes(bytes.as_bytes()).ok_or(SizeCheckError {
-            expected_size: VERIFICATION_KEY_BYTES,
-            actual_size: VERIFICATION_KEY_BYTES,
-        })
+        Ed25519VerificationKey::from_bytes(bytes.as_bytes()).ok_or(
+            DirectSerialiseError::InvalidValue {
+                what: "Ed25519 verification key",
+            },
+        )
     }
 }
 
@@ -178,14 +267,14 @@ impl Ed25519Signature {
     }
 }
 
-// CBOR Serialization for Ed25519Signature
+// Serde for Ed25519Signature: a CBOR byte string, hex text in JSON
 #[cfg(feature = "serde")]
 impl serde::Serialize for Ed25519Signature {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_bytes(self.as_bytes())
+        cardano_binary::bytes_or_hex::serialize(self.as_bytes(), serializer)
     }
 }
 
@@ -230,7 +319,7 @@ impl<'de> serde::Deserialize<'de> for Ed25519Signature {
             }
         }
 
-        deserializer.deserialize_bytes(BytesVisitor)
+        cardano_binary::bytes_or_hex::deserialize_with(deserializer, BytesVisitor)
     }
 }
 
@@ -254,9 +343,8 @@ impl DirectDeserialise for Ed25519Signature {
         array.copy_from_slice(bytes.as_bytes());
         DalekSignature::try_from(array.as_ref())
             .map(|sig| Ed25519Signature::from_dalek(&sig))
-            .map_err(|_| SizeCheckError {
-                expected_size: SIGNATURE_BYTES,
-                actual_size: SIGNATURE_BYTES,
+            .map_err(|_| DirectSerialiseError::InvalidValue {
+                what: "Ed25519 signature",
             })
     }
 }
@@ -264,6 +352,37 @@ impl DirectDeserialise for Ed25519Signature {
 /// Marker type implementing [`DsignAlgorithm`] for Ed25519.
 pub struct Ed25519;
 
+impl Ed25519 {
+    /// [`DsignAlgorithm::verify_bytes`] against a borrowed verification key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DsignError::VerificationFailed`] if the signature does not
+    /// verify, exactly as the owned variant does.
+    pub fn verify_bytes_ref(
+        _context: &(),
+        verification_key: Ed25519VerificationKeyRef<'_>,
+        message: &[u8],
+        signature: &Ed25519Signature,
+    ) -> Result<(), DsignError> {
+        let (verifying_key, signature) = to_dalek(verification_key, signature)?;
+        verifying_key
+            .verify(message, &signature)
+            .map_err(|_| DsignError::VerificationFailed)
+    }
+}
+
+fn to_dalek(
+    verification_key: Ed25519VerificationKeyRef<'_>,
+    signature: &Ed25519Signature,
+) -> Result<(VerifyingKey, DalekSignature), DsignError> {
+    let verifying_key = VerifyingKey::from_bytes(verification_key.as_bytes())
+        .map_err(|err| DsignError::Message(err.to_string()))?;
+    let signature = DalekSignature::try_from(signature.as_bytes().as_ref())
+        .map_err(|err| DsignError::Message(err.to_string()))?;
+    Ok((verifying_key, signature))
+}
+
 impl DsignAlgorithm for Ed25519 {
     type SigningKey = Ed25519SigningKey;
     type VerificationKey = Ed25519VerificationKey;
@@ -293,18 +412,12 @@ impl DsignAlgorithm for Ed25519 {
     }
 
     fn verify_bytes(
-        _context: &Self::Context,
+        context: &Self::Context,
         verification_key: &Self::VerificationKey,
         message: &[u8],
         signature: &Self::Signature,
     ) -> Result<(), DsignError> {
-        let verifying_key = VerifyingKey::from_bytes(verification_key.as_bytes())
-            .map_err(|err| DsignError::Message(err.to_string()))?;
-        let signature = DalekSignature::try_from(signature.as_bytes().as_ref())
-            .map_err(|err| DsignError::Message(err.to_string()))?;
-        verifying_key
-            .verify(message, &signature)
-            .map_err(|_| DsignError::VerificationFailed)
+        Self::verify_bytes_ref(context, verification_key.into(), message, signature)
     }
 
     fn gen_key_from_seed_bytes(seed: &[u8]) -> Self::SigningKey {
@@ -347,6 +460,87 @@ impl DsignAlgorithm for Ed25519 {
     }
 }
 
+impl DsignVectoredVerify for Ed25519 {
+    /// Hashes the parts in place, once for the nonce and once for the
+    /// challenge, exactly as [`DsignAlgorithm::sign_bytes`] hashes the whole
+    /// message.
+    fn sign_bytes_vectored(
+        _context: &Self::Context,
+        parts: &[&[u8]],
+        signing_key: &Self::SigningKey,
+    ) -> Self::Signature {
+        let signing_key = signing_key.signing_key();
+        let expanded = ExpandedSecretKey::from(signing_key.as_bytes());
+        let signature = raw_sign_byupdate::<Sha512, _>(
+            &expanded,
+            |hasher| {
+                parts.iter().for_each(|part| hasher.update(part));
+                Ok(())
+            },
+            &signing_key.verifying_key(),
+        )
+        .expect("hashing message parts cannot fail");
+        Ed25519Signature::from_dalek(&signature)
+    }
+
+    fn verify_bytes_vectored(
+        _context: &Self::Context,
+        verification_key: &Self::VerificationKey,
+        parts: &[&[u8]],
+        signature: &Self::Signature,
+    ) -> Result<(), DsignError> {
+        let (verifying_key, signature) = to_dalek(verification_key.into(), signature)?;
+        let mut verifier = verifying_key
+            .verify_stream(&signature)
+            .map_err(|_| DsignError::VerificationFailed)?;
+        parts.iter().for_each(|part| verifier.update(part));
+        verifier
+            .finalize_and_verify()
+            .map_err(|_| DsignError::VerificationFailed)
+    }
+}
+
+/// RFC 8032 section 7.1, test 2: seed, message and published signature.
+const CANARY_SEED: [u8; SEED_BYTES] = [
+    0x4c, 0xcd, 0x08, 0x9b, 0x28, 0xff, 0x96, 0xda, 0x9d, 0xb6, 0xc3, 0x46, 0xec, 0x11, 0x4e, 0x0f,
+    0x5b, 0x8a, 0x31, 0x9f, 0x35, 0xab, 0xa6, 0x24, 0xda, 0x8c, 0xf6, 0xed, 0x4f, 0xb8, 0xa6, 0xfb,
+];
+const CANARY_MESSAGE: [u8; 1] = [0x72];
+const CANARY_SIGNATURE: [u8; SIGNATURE_BYTES] = [
+    0x92, 0xa0, 0x09, 0xa9, 0xf0, 0xd4, 0xca, 0xb8, 0x72, 0x0e, 0x82, 0x0b, 0x5f, 0x64, 0x25, 0x40,
+    0xa2, 0xb2, 0x7b, 0x54, 0x16, 0x50, 0x3f, 0x8f, 0xb3, 0x76, 0x22, 0x23, 0xeb, 0xdb, 0x69, 0xda,
+    0x08, 0x5a, 0xc1, 0xe4, 0x3e, 0x15, 0x99, 0x6e, 0x45, 0x8f, 0x36, 0x13, 0xd0, 0xf1, 0x1d, 0x8c,
+    0x38, 0x7b, 0x2e, 0xae, 0xb4, 0x30, 0x2a, 0xee, 0xb0, 0x0d, 0x29, 0x16, 0x12, 0xbb, 0x0c, 0x00,
+];
+
+/// Check that Ed25519 signing is the deterministic RFC 8032 algorithm that
+/// Haskell's pure `signDSIGN` implements.
+///
+/// Signs an RFC 8032 vector twice and compares both signatures with the
+/// published one. Mlocked signing keys go through the same `ed25519-dalek`
+/// signer, so one check covers both. Cargo unifies `ed25519-dalek` features across the
+/// dependency graph, so another crate could change the backend's behaviour
+/// without this crate noticing at build time; call this once at startup to
+/// fail loudly instead of producing signatures that differ from Haskell's.
+/// It costs two signatures.
+///
+/// # Errors
+///
+/// Returns [`DsignError::NonDeterministicSigning`] if either signature
+/// differs from the published one.
+pub fn check_deterministic_signing() -> Result<(), DsignError> {
+    let signing_key = Ed25519SigningKey::from_seed_bytes(&CANARY_SEED);
+    for _ in 0..2 {
+        let signature = Ed25519::sign_bytes(&(), &CANARY_MESSAGE, &signing_key);
+        if signature.as_bytes() != &CANARY_SIGNATURE {
+            return Err(DsignError::NonDeterministicSigning {
+                algorithm: Ed25519::ALGORITHM_NAME,
+            });
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -368,7 +562,7 @@ mod tests {
         let signing = <Ed25519 as DsignAlgorithm>::gen_key(&seed);
         let verifying = <Ed25519 as DsignAlgorithm>::derive_verification_key(&signing);
         let message = b"cardano";
-        let signed = signed_dsign::<Ed25519, _>(&(), message, &signing);
+        let signed = signed_dsign::<Ed25519, _>(&(), message, &signing).unwrap();
         assert!(verify_signed_dsign::<Ed25519, _>(&(), &verifying, message, &signed).is_ok());
     }
 
@@ -393,7 +587,7 @@ mod tests {
         let seed = mk_seed_from_bytes(vec![9u8; SEED_BYTES]);
         let signing = <Ed25519 as DsignAlgorithm>::gen_key(&seed);
         let verifying = <Ed25519 as DsignAlgorithm>::derive_verification_key(&signing);
-        let signed = signed_dsign::<Ed25519, _>(&(), b"hello", &signing);
+        let signed = signed_dsign::<Ed25519, _>(&(), b"hello", &signing).unwrap();
         let result = verify_signed_dsign::<Ed25519, _>(&(), &verifying, b"world", &signed);
         assert!(matches!(result, Err(DsignError::VerificationFailed)));
     }
@@ -1,7 +1,7 @@
 use ed25519_dalek::Signer;
 use ed25519_dalek::SigningKey;
 
-use crate::direct_serialise::{DirectDeserialise, DirectResult, DirectSerialise, SizeCheckError};
+use crate::direct_serialise::{DirectDeserialise, DirectResult, DirectSerialise};
 use crate::dsign::ed25519::{
     Ed25519, Ed25519Signature, Ed25519SigningKey, Ed25519VerificationKey, SECRET_COMPOUND_BYTES,
     SEED_BYTES, VERIFICATION_KEY_BYTES,
@@ -42,6 +42,38 @@ impl Ed25519MLockedSigningKey {
     }
 }
 
+#[cfg(all(target_os = "linux", feature = "memfd-handoff"))]
+impl Ed25519MLockedSigningKey {
+    /// Export the 32-byte seed into a sealed `memfd` for handing to another
+    /// process via `sendmsg(SCM_RIGHTS)`.
+    ///
+    /// The memfd pages are not mlocked, so the receiver should restore the
+    /// key with [`Ed25519MLockedSigningKey::from_sealed_memfd`] immediately.
+    /// See [`MLockedBytes::seal_to_memfd`](crate::mlocked_bytes::MLockedBytes::seal_to_memfd).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MLockedError::Memfd`] if creating or sealing the memfd fails.
+    pub fn seal_to_memfd(&self) -> Result<std::os::fd::OwnedFd, MLockedError> {
+        crate::mlocked_bytes::memfd::seal_bytes(&self.0.as_slice()[..SEED_BYTES])
+    }
+
+    /// Rebuild a signing key from a sealed `memfd` produced by
+    /// [`Ed25519MLockedSigningKey::seal_to_memfd`], closing the descriptor.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the memfd is not sealed, does not hold exactly
+    /// 32 bytes, cannot be read, or if mlocked allocation fails.
+    pub fn from_sealed_memfd(fd: std::os::fd::OwnedFd) -> Result<Self, MLockedError> {
+        let mut seed = MLockedSeed::<SEED_BYTES>::new_zeroed()?;
+        crate::mlocked_bytes::memfd::read_sealed(fd, seed.as_mut_bytes())?;
+        let signing_key = Self::from_seed(&seed)?;
+        seed.finalize();
+        Ok(signing_key)
+    }
+}
+
 impl DirectSerialise for Ed25519MLockedSigningKey {
     fn direct_serialise(
         &self,
@@ -58,19 +90,12 @@ impl DirectDeserialise for Ed25519MLockedSigningKey {
     fn direct_deserialise(
         pull: &mut dyn FnMut(&mut [u8]) -> DirectResult<()>,
     ) -> DirectResult<Self> {
-        let mut seed = MLockedSeed::<SEED_BYTES>::new_zeroed().map_err(|_| SizeCheckError {
-            expected_size: SEED_BYTES,
-            actual_size: 0,
-        })?;
+        let mut seed = MLockedSeed::<SEED_BYTES>::new_zeroed()?;
         {
             let slice = seed.as_mut_bytes();
             pull(slice)?;
         }
-        let signing_key =
-            Ed25519MLockedSigningKey::from_seed(&seed).map_err(|_| SizeCheckError {
-                expected_size: SEED_BYTES,
-                actual_size: SEED_BYTES,
-            })?;
+        let signing_key = Ed25519MLockedSigningKey::from_seed(&seed)?;
         seed.finalize();
         Ok(signing_key)
     }
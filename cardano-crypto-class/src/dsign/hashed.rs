@@ -0,0 +1,162 @@
+//! Hash-then-sign over any DSIGN algorithm.
+//!
+//! [`HashedDsign<A, H>`] signs the `H` digest of a message with `A` instead
+//! of the message itself. Because the digest is computed incrementally, it
+//! is the streaming DSIGN algorithm: a [`SignableStream`] is hashed chunk by
+//! chunk and never held in memory, so multi-gigabyte payloads such as ledger
+//! snapshots can be signed directly.
+//!
+//! The signature is `A`'s signature over `H(message)`, so it verifies with
+//! plain `A` against the digest. `HashedDsign<EcdsaSecp256k1DSIGN, H>`
+//! therefore matches signing the output of
+//! [`hash_and_pack::<H>`](crate::dsign::ecdsa_secp256k1::hash_and_pack).
+//!
+//! ```rust
+//! use cardano_crypto_class::dsign::hashed::HashedDsign;
+//! use cardano_crypto_class::dsign::{DsignAlgorithm, signed_dsign_streaming};
+//! use cardano_crypto_class::hash::Blake2b256;
+//! use cardano_crypto_class::{Ed25519, SignableStream};
+//!
+//! struct Chunks(Vec<Vec<u8>>);
+//!
+//! impl SignableStream for Chunks {
+//!     fn write_signable(&self, out: &mut dyn FnMut(&[u8])) {
+//!         self.0.iter().for_each(|chunk| out(chunk));
+//!     }
+//! }
+//!
+//! type Signer = HashedDsign<Ed25519, Blake2b256>;
+//! let signing_key = Signer::gen_key_from_seed_bytes(&[3u8; 32]);
+//! let message = Chunks(vec![b"ledger ".to_vec(), b"snapshot".to_vec()]);
+//!
+//! let streamed = signed_dsign_streaming::<Signer, _>(&(), &message, &signing_key).unwrap();
+//! let buffered = Signer::sign_bytes(&(), b"ledger snapshot", &signing_key);
+//! assert_eq!(streamed.signature(), &buffered);
+//! ```
+
+use std::marker::PhantomData;
+
+use super::{DsignAlgorithm, DsignError};
+use crate::hash::IncrementalHashAlgorithm;
+use crate::util::SignableStream;
+
+/// `A` signing the `H` digest of the message.
+///
+/// Keys and signatures are `A`'s, and [`DsignAlgorithm::ALGORITHM_NAME`] is
+/// `A`'s name, as for the Sum KES constructions; the digest is not recorded
+/// in either.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HashedDsign<A, H>(PhantomData<(A, H)>);
+
+fn digest<H, M>(message: &M) -> Vec<u8>
+where
+    H: IncrementalHashAlgorithm,
+    M: SignableStream + ?Sized,
+{
+    let mut context = H::new_context();
+    message.write_signable(&mut |chunk| H::update(&mut context, chunk));
+    H::finalize(context)
+}
+
+impl<A, H> DsignAlgorithm for HashedDsign<A, H>
+where
+    A: DsignAlgorithm,
+    H: IncrementalHashAlgorithm,
+{
+    type SigningKey = A::SigningKey;
+    type VerificationKey = A::VerificationKey;
+    type Signature = A::Signature;
+    type Context = A::Context;
+
+    const ALGORITHM_NAME: &'static str = A::ALGORITHM_NAME;
+    const SEED_SIZE: usize = A::SEED_SIZE;
+    const VERIFICATION_KEY_SIZE: usize = A::VERIFICATION_KEY_SIZE;
+    const SIGNING_KEY_SIZE: usize = A::SIGNING_KEY_SIZE;
+    const SIGNATURE_SIZE: usize = A::SIGNATURE_SIZE;
+    const STREAMING: bool = true;
+
+    fn derive_verification_key(signing_key: &Self::SigningKey) -> Self::VerificationKey {
+        A::derive_verification_key(signing_key)
+    }
+
+    fn sign_bytes(
+        context: &Self::Context,
+        message: &[u8],
+        signing_key: &Self::SigningKey,
+    ) -> Self::Signature {
+        A::sign_bytes(context, &H::hash(message), signing_key)
+    }
+
+    fn try_sign_bytes(
+        context: &Self::Context,
+        message: &[u8],
+        signing_key: &Self::SigningKey,
+    ) -> Result<Self::Signature, DsignError> {
+        A::try_sign_bytes(context, &H::hash(message), signing_key)
+    }
+
+    fn verify_bytes(
+        context: &Self::Context,
+        verification_key: &Self::VerificationKey,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), DsignError> {
+        A::verify_bytes(context, verification_key, &H::hash(message), signature)
+    }
+
+    fn gen_key_from_seed_bytes(seed: &[u8]) -> Self::SigningKey {
+        A::gen_key_from_seed_bytes(seed)
+    }
+
+    fn raw_serialize_verification_key(key: &Self::VerificationKey) -> Vec<u8> {
+        A::raw_serialize_verification_key(key)
+    }
+
+    fn raw_deserialize_verification_key(bytes: &[u8]) -> Option<Self::VerificationKey> {
+        A::raw_deserialize_verification_key(bytes)
+    }
+
+    fn raw_serialize_signing_key(signing_key: &Self::SigningKey) -> Vec<u8> {
+        A::raw_serialize_signing_key(signing_key)
+    }
+
+    fn raw_deserialize_signing_key(bytes: &[u8]) -> Option<Self::SigningKey> {
+        A::raw_deserialize_signing_key(bytes)
+    }
+
+    fn raw_serialize_signature(signature: &Self::Signature) -> Vec<u8> {
+        A::raw_serialize_signature(signature)
+    }
+
+    fn raw_deserialize_signature(bytes: &[u8]) -> Option<Self::Signature> {
+        A::raw_deserialize_signature(bytes)
+    }
+
+    fn sign_stream<M>(
+        context: &Self::Context,
+        message: &M,
+        signing_key: &Self::SigningKey,
+    ) -> Result<Self::Signature, DsignError>
+    where
+        M: SignableStream + ?Sized,
+    {
+        A::try_sign_bytes(context, &digest::<H, M>(message), signing_key)
+    }
+
+    fn verify_stream<M>(
+        context: &Self::Context,
+        verification_key: &Self::VerificationKey,
+        message: &M,
+        signature: &Self::Signature,
+    ) -> Result<(), DsignError>
+    where
+        M: SignableStream + ?Sized,
+    {
+        A::verify_bytes(
+            context,
+            verification_key,
+            &digest::<H, M>(message),
+            signature,
+        )
+    }
+}
@@ -3,13 +3,15 @@ use std::marker::PhantomData;
 
 use thiserror::Error;
 
+use crate::direct_serialise::{DirectSerialiseError, SizeCheckError};
 use crate::mlocked_bytes::MLockedError;
-use crate::seed::{Seed, get_bytes_from_seed_t};
-use crate::util::SignableRepresentation;
+use crate::seed::{KEY_DERIVATION_LABEL_PREFIX, Seed, expand_seed_labelled};
+use crate::util::{SignableRepresentation, SignableStream};
 
 pub mod ecdsa_secp256k1;
 pub mod ed25519;
 pub mod ed25519_mlocked;
+pub mod hashed;
 pub mod schnorr_secp256k1;
 
 /// Error raised by DSIGN operations.
@@ -25,6 +27,14 @@ pub enum DsignError {
     },
     #[error("{0}")]
     Message(String),
+    /// Signing a pinned vector did not give its published signature, so the
+    /// backend is not the deterministic RFC 8032 algorithm Haskell uses.
+    #[error("{algorithm} signing is not deterministic RFC 8032")]
+    NonDeterministicSigning { algorithm: &'static str },
+    /// The algorithm needs the whole message at once (see
+    /// [`DsignAlgorithm::STREAMING`]).
+    #[error("{algorithm} cannot sign or verify a streamed message")]
+    UnsupportedStreaming { algorithm: &'static str },
 }
 
 impl DsignError {
@@ -37,6 +47,54 @@ impl DsignError {
             actual,
         }
     }
+
+    /// Stable identifier of this variant (see the crate-level *Error codes*
+    /// section).
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            DsignError::VerificationFailed => "dsign.verification_failed",
+            DsignError::WrongLength { .. } => "dsign.wrong_length",
+            DsignError::Message(_) => "dsign.message",
+            DsignError::NonDeterministicSigning { .. } => "dsign.non_deterministic_signing",
+            DsignError::UnsupportedStreaming { .. } => "dsign.unsupported_streaming",
+        }
+    }
+
+    /// Always `false`: signing and verification are pure functions of their
+    /// inputs.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for DsignError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        match self {
+            DsignError::WrongLength {
+                context,
+                expected,
+                actual,
+            } => {
+                map.serialize_entry("context", context)?;
+                map.serialize_entry("expected", expected)?;
+                map.serialize_entry("actual", actual)?;
+            },
+            DsignError::NonDeterministicSigning { algorithm }
+            | DsignError::UnsupportedStreaming { algorithm } => {
+                map.serialize_entry("algorithm", algorithm)?;
+            },
+            DsignError::VerificationFailed | DsignError::Message(_) => {},
+        }
+        map.serialize_entry("message", &self.to_string())?;
+        map.end()
+    }
 }
 
 /// Error raised by DSIGNM operations.
@@ -46,6 +104,48 @@ pub enum DsignMError {
     Dsign(#[from] DsignError),
     #[error(transparent)]
     Mlocked(#[from] MLockedError),
+    #[error(transparent)]
+    DirectSerialise(#[from] DirectSerialiseError),
+}
+
+impl From<SizeCheckError> for DsignMError {
+    fn from(sizes: SizeCheckError) -> Self {
+        DsignMError::DirectSerialise(sizes.into())
+    }
+}
+
+impl DsignMError {
+    /// Stable identifier of the wrapped error.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            DsignMError::Dsign(err) => err.code(),
+            DsignMError::Mlocked(err) => err.code(),
+            DsignMError::DirectSerialise(err) => err.code(),
+        }
+    }
+
+    /// Whether retrying could succeed, as reported by the wrapped error.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        match self {
+            DsignMError::Dsign(err) => err.is_transient(),
+            DsignMError::Mlocked(err) => err.is_transient(),
+            DsignMError::DirectSerialise(err) => err.is_transient(),
+        }
+    }
+}
+
+/// Serialises as the wrapped error.
+#[cfg(feature = "serde")]
+impl serde::Serialize for DsignMError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            DsignMError::Dsign(err) => err.serialize(serializer),
+            DsignMError::Mlocked(err) => err.serialize(serializer),
+            DsignMError::DirectSerialise(err) => err.serialize(serializer),
+        }
+    }
 }
 
 /// Trait capturing the common DSIGN interface across algorithms.
@@ -80,6 +180,25 @@ pub trait DsignAlgorithm {
         signing_key: &Self::SigningKey,
     ) -> Self::Signature;
 
+    /// Sign a message provided as raw bytes, reporting messages the
+    /// algorithm cannot sign instead of panicking.
+    ///
+    /// The generic helpers ([`signed_dsign`], [`crate::role::sign_dsign`])
+    /// sign through this method. The default forwards to
+    /// [`DsignAlgorithm::sign_bytes`]; algorithms that only accept some
+    /// messages, such as ECDSA Secp256k1 with its 32-byte hashes, override it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `message` is not a valid input for the algorithm.
+    fn try_sign_bytes(
+        context: &Self::Context,
+        message: &[u8],
+        signing_key: &Self::SigningKey,
+    ) -> Result<Self::Signature, DsignError> {
+        Ok(Self::sign_bytes(context, message, signing_key))
+    }
+
     /// Verify a signature over raw bytes.
     ///
     /// # Errors
@@ -104,8 +223,11 @@ pub trait DsignAlgorithm {
     /// [`DsignAlgorithm::SEED_SIZE`] bytes.
     #[must_use]
     fn gen_key(seed: &Seed) -> Self::SigningKey {
-        let (material, _) = get_bytes_from_seed_t(Self::SEED_SIZE, seed.clone());
-        Self::gen_key_from_seed_bytes(&material)
+        let material = seed
+            .as_slice()
+            .get(..Self::SEED_SIZE)
+            .expect("seed bytes exhausted");
+        Self::gen_key_from_seed_bytes(material)
     }
 
     /// Construct a signing key from raw seed bytes. The slice length is
@@ -129,21 +251,122 @@ pub trait DsignAlgorithm {
 
     /// Deserialise a signature from raw bytes.
     fn raw_deserialize_signature(bytes: &[u8]) -> Option<Self::Signature>;
+
+    /// Whether [`DsignAlgorithm::sign_stream`] and
+    /// [`DsignAlgorithm::verify_stream`] consume a message chunk by chunk.
+    /// Algorithms that need the whole message, such as plain Ed25519, leave
+    /// this `false` and reject streams.
+    const STREAMING: bool = false;
+
+    /// Sign a message emitted in chunks, without buffering it.
+    ///
+    /// # Errors
+    ///
+    /// The default returns [`DsignError::UnsupportedStreaming`].
+    fn sign_stream<M>(
+        context: &Self::Context,
+        message: &M,
+        signing_key: &Self::SigningKey,
+    ) -> Result<Self::Signature, DsignError>
+    where
+        M: SignableStream + ?Sized,
+    {
+        let _ = (context, message, signing_key);
+        Err(DsignError::UnsupportedStreaming {
+            algorithm: Self::ALGORITHM_NAME,
+        })
+    }
+
+    /// Verify a signature over a message emitted in chunks, without
+    /// buffering it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature is invalid; the default returns
+    /// [`DsignError::UnsupportedStreaming`].
+    fn verify_stream<M>(
+        context: &Self::Context,
+        verification_key: &Self::VerificationKey,
+        message: &M,
+        signature: &Self::Signature,
+    ) -> Result<(), DsignError>
+    where
+        M: SignableStream + ?Sized,
+    {
+        let _ = (context, verification_key, message, signature);
+        Err(DsignError::UnsupportedStreaming {
+            algorithm: Self::ALGORITHM_NAME,
+        })
+    }
+}
+
+/// Algorithms that sign and verify a message held as several non-contiguous
+/// slices without concatenating them first.
+///
+/// The message is `parts` joined in order: the result must be the same as
+/// [`DsignAlgorithm::sign_bytes`] or [`DsignAlgorithm::verify_bytes`] over
+/// `parts.concat()`, wherever the message is split. This suits a header
+/// whose signed bytes are assembled from fields, where
+/// [`DsignAlgorithm::STREAMING`] does not apply because the algorithm still
+/// needs every byte before it can finish.
+pub trait DsignVectoredVerify: DsignAlgorithm {
+    /// Sign the concatenation of `parts`.
+    #[must_use]
+    fn sign_bytes_vectored(
+        context: &Self::Context,
+        parts: &[&[u8]],
+        signing_key: &Self::SigningKey,
+    ) -> Self::Signature;
+
+    /// Verify a signature over the concatenation of `parts`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error [`DsignAlgorithm::verify_bytes`] returns for the
+    /// concatenated message.
+    fn verify_bytes_vectored(
+        context: &Self::Context,
+        verification_key: &Self::VerificationKey,
+        parts: &[&[u8]],
+        signature: &Self::Signature,
+    ) -> Result<(), DsignError>;
 }
 
 /// Convenience wrapper producing a [`SignedDsign`] value.
+///
+/// # Errors
+///
+/// Returns the error [`DsignAlgorithm::try_sign_bytes`] reports when the
+/// signable representation is not a valid input for `A`.
 pub fn signed_dsign<A, M>(
     context: &A::Context,
     message: &M,
     signing_key: &A::SigningKey,
-) -> SignedDsign<A, M>
+) -> Result<SignedDsign<A, M>, DsignError>
 where
     A: DsignAlgorithm,
     M: SignableRepresentation + ?Sized,
 {
     let representation = message.signable_representation();
-    let signature = A::sign_bytes(context, representation.as_ref(), signing_key);
-    SignedDsign::new(signature)
+    A::try_sign_bytes(context, representation.as_ref(), signing_key).map(SignedDsign::new)
+}
+
+/// Generate a signing key for `A` from a seed that may also seed other
+/// algorithms.
+///
+/// Unlike [`DsignAlgorithm::gen_key`], which feeds the first
+/// [`DsignAlgorithm::SEED_SIZE`] seed bytes straight to `A`, this first
+/// expands the whole seed with [`expand_seed_labelled`] under the label
+/// `cardano-crypto-class/keygen/v1/dsign/<ALGORITHM_NAME>`. The same master
+/// seed therefore gives unrelated Ed25519 and Schnorr keys, or an unrelated
+/// DSIGN and VRF key. Keys generated this way differ from Haskell's
+/// `genKeyDSIGN` for the same seed; see the crate-level *Key generation from
+/// a shared seed* section.
+#[must_use]
+pub fn gen_key_domain_separated<A: DsignAlgorithm>(seed: &Seed) -> A::SigningKey {
+    let label = format!("{KEY_DERIVATION_LABEL_PREFIX}dsign/{}", A::ALGORITHM_NAME);
+    let material = expand_seed_labelled(seed, label.as_bytes(), A::SEED_SIZE);
+    A::gen_key_from_seed_bytes(material.as_slice())
 }
 
 /// Verify a [`SignedDsign`] value.
@@ -170,6 +393,56 @@ where
     )
 }
 
+/// Sign a message emitted in chunks, producing a [`SignedDsign`] value.
+///
+/// Only algorithms with [`DsignAlgorithm::STREAMING`] set accept a stream.
+/// For the others, buffer the message with
+/// [`SignableStream::collect_signable`] and use [`signed_dsign`].
+///
+/// # Errors
+///
+/// Returns [`DsignError::UnsupportedStreaming`] if `A` cannot sign a stream.
+pub fn signed_dsign_streaming<A, M>(
+    context: &A::Context,
+    message: &M,
+    signing_key: &A::SigningKey,
+) -> Result<SignedDsign<A, M>, DsignError>
+where
+    A: DsignAlgorithm,
+    M: SignableStream + ?Sized,
+{
+    if !A::STREAMING {
+        return Err(DsignError::UnsupportedStreaming {
+            algorithm: A::ALGORITHM_NAME,
+        });
+    }
+    A::sign_stream(context, message, signing_key).map(SignedDsign::new)
+}
+
+/// Verify a [`SignedDsign`] value over a message emitted in chunks.
+///
+/// # Errors
+///
+/// Returns [`DsignError::UnsupportedStreaming`] if `A` cannot verify a
+/// stream, or an error if the signature is invalid.
+pub fn verify_signed_dsign_streaming<A, M>(
+    context: &A::Context,
+    verification_key: &A::VerificationKey,
+    message: &M,
+    signed: &SignedDsign<A, M>,
+) -> Result<(), DsignError>
+where
+    A: DsignAlgorithm,
+    M: SignableStream + ?Sized,
+{
+    if !A::STREAMING {
+        return Err(DsignError::UnsupportedStreaming {
+            algorithm: A::ALGORITHM_NAME,
+        });
+    }
+    A::verify_stream(context, verification_key, message, signed.signature())
+}
+
 /// Helper mirroring `failSizeCheck` from the Haskell implementation.
 #[must_use]
 pub fn fail_size_check(function: &'static str, expected: usize, actual: usize) -> DsignError {
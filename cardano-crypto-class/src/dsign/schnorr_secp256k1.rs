@@ -10,6 +10,22 @@
 //!
 //! This implementation is provided for cross-chain bridge compatibility only.
 //! For Cardano consensus, use Ed25519 signatures instead.
+//!
+//! # Auxiliary randomness
+//!
+//! The BIP340 auxiliary randomness is carried by [`SchnorrContext`], so the
+//! generic [`crate::dsign::signed_dsign`] path can sign deterministically:
+//!
+//! ```rust
+//! use cardano_crypto_class::dsign::DsignAlgorithm;
+//! use cardano_crypto_class::dsign::schnorr_secp256k1::{SchnorrContext, SchnorrSecp256k1DSIGN};
+//!
+//! let signing_key = SchnorrSecp256k1DSIGN::gen_key_from_seed_bytes(&[3u8; 32]);
+//! let context = SchnorrContext::with_aux_rand([0u8; 32]);
+//! let first = SchnorrSecp256k1DSIGN::sign_bytes(&context, &[0u8; 32], &signing_key);
+//! let second = SchnorrSecp256k1DSIGN::sign_bytes(&context, &[0u8; 32], &signing_key);
+//! assert_eq!(first, second);
+//! ```
 
 use crate::dsign::{DsignAlgorithm, DsignError};
 use rand_core::{CryptoRng, RngCore};
@@ -53,13 +69,24 @@ impl fmt::Debug for Signature {
     }
 }
 
-/// Context type for Schnorr Secp256k1 (no context needed).
-#[derive(Clone, Copy, Debug)]
-pub struct Context;
+/// Signing context for Schnorr Secp256k1.
+///
+/// BIP340 mixes 32 bytes of auxiliary randomness into the nonce derivation.
+/// With `aux_rand: None` (the default) every signature draws fresh
+/// randomness from the thread RNG; a fixed value makes signing deterministic,
+/// as the BIP340 test vectors require. Verification ignores the context.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SchnorrContext {
+    pub aux_rand: Option<[u8; 32]>,
+}
 
-impl Default for Context {
-    fn default() -> Self {
-        Context
+impl SchnorrContext {
+    /// Context signing with the given auxiliary randomness.
+    #[must_use]
+    pub const fn with_aux_rand(aux_rand: [u8; 32]) -> Self {
+        Self {
+            aux_rand: Some(aux_rand),
+        }
     }
 }
 
@@ -67,7 +94,7 @@ impl DsignAlgorithm for SchnorrSecp256k1DSIGN {
     type SigningKey = SigningKey;
     type VerificationKey = VerificationKey;
     type Signature = Signature;
-    type Context = Context;
+    type Context = SchnorrContext;
 
     const ALGORITHM_NAME: &'static str = "SchnorrSecp256k1DSIGN";
     const SEED_SIZE: usize = 32;
@@ -81,7 +108,7 @@ impl DsignAlgorithm for SchnorrSecp256k1DSIGN {
     }
 
     fn sign_bytes(
-        _context: &Self::Context,
+        context: &Self::Context,
         message: &[u8],
         signing_key: &Self::SigningKey,
     ) -> Self::Signature {
@@ -102,7 +129,12 @@ impl DsignAlgorithm for SchnorrSecp256k1DSIGN {
         };
 
         // Schnorr sign takes raw bytes, not a Message object
-        let signature = secp.sign_schnorr(&message_hash, &signing_key.0);
+        let signature = match &context.aux_rand {
+            Some(aux_rand) => {
+                secp.sign_schnorr_with_aux_rand(&message_hash, &signing_key.0, aux_rand)
+            },
+            None => secp.sign_schnorr(&message_hash, &signing_key.0),
+        };
         Signature(signature)
     }
 
@@ -132,6 +164,12 @@ impl DsignAlgorithm for SchnorrSecp256k1DSIGN {
             .map_err(|_| DsignError::VerificationFailed)
     }
 
+    /// Interpret the 32 seed bytes as a big-endian secret scalar.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scalar is zero or not below the curve order `n`: the
+    /// bytes are neither reduced modulo `n` nor replaced by a retry.
     fn gen_key_from_seed_bytes(seed: &[u8]) -> Self::SigningKey {
         assert_eq!(
             seed.len(),
@@ -222,7 +260,7 @@ mod tests {
         let mut rng = rand::rng();
         let (signing_key, verification_key) = generate_keypair(&mut rng);
 
-        let context = Context;
+        let context = SchnorrContext::default();
         let message = b"Hello, Bitcoin Taproot!";
         let signature = SchnorrSecp256k1DSIGN::sign_bytes(&context, message, &signing_key);
 
@@ -258,7 +296,7 @@ mod tests {
     fn test_schnorr_secp256k1_signature_format() {
         let mut rng = rand::rng();
         let (signing_key, _) = generate_keypair(&mut rng);
-        let context = Context;
+        let context = SchnorrContext::default();
         let message = b"Test message";
 
         let signature = SchnorrSecp256k1DSIGN::sign_bytes(&context, message, &signing_key);
@@ -288,7 +326,7 @@ mod tests {
     fn test_schnorr_secp256k1_wrong_signature() {
         let mut rng = rand::rng();
         let (signing_key, verification_key) = generate_keypair(&mut rng);
-        let context = Context;
+        let context = SchnorrContext::default();
 
         let message = b"Original message";
         let signature = SchnorrSecp256k1DSIGN::sign_bytes(&context, message, &signing_key);
@@ -318,12 +356,15 @@ mod tests {
         let ecdsa_sk = ecdsa_secp256k1::EcdsaSecp256k1DSIGN::gen_key(&seed);
 
         let message = b"Same message, different algorithms";
-        let schnorr_ctx = Context;
-        let ecdsa_ctx = ecdsa_secp256k1::Context;
+        let schnorr_ctx = SchnorrContext::default();
+        let ecdsa_ctx = ecdsa_secp256k1::EcdsaContext::default();
 
         let schnorr_sig = SchnorrSecp256k1DSIGN::sign_bytes(&schnorr_ctx, message, &schnorr_sk);
-        let ecdsa_sig =
-            ecdsa_secp256k1::EcdsaSecp256k1DSIGN::sign_bytes(&ecdsa_ctx, message, &ecdsa_sk);
+        let ecdsa_sig = ecdsa_secp256k1::EcdsaSecp256k1DSIGN::sign_message_hash(
+            &ecdsa_ctx,
+            &ecdsa_secp256k1::hash_and_pack::<crate::hash::Sha256>(message),
+            &ecdsa_sk,
+        );
 
         // Signatures should be different (different algorithms)
         assert_ne!(
@@ -1,5 +1,36 @@
+//! Size-tagged pointers for handing fixed-size buffers to C-style code.
+//!
+//! These mirror Haskell's `SizedPtr` and are normally obtained through the
+//! bracketed accessors on [`PinnedSizedBytes`](crate::PinnedSizedBytes) and
+//! [`MLockedSizedBytes`](crate::MLockedSizedBytes) (`with_sized_ptr`,
+//! `with_sized_ptr_mut`, `create_sized`), which play the role of
+//! `useAsSizedPtr` and `psbCreateSized`. The callback receives a pointer whose
+//! lifetime is tied to the call, so it cannot be returned or stored.
+//!
+//! ```rust
+//! use cardano_crypto_class::PinnedSizedBytes;
+//!
+//! /// Stand-in for a C routine `void reverse32(const uint8_t *in, uint8_t *out)`.
+//! unsafe extern "C" fn reverse32(input: *const u8, output: *mut u8) {
+//!     for i in 0..32 {
+//!         unsafe { *output.add(i) = *input.add(31 - i) };
+//!     }
+//! }
+//!
+//! let input = PinnedSizedBytes::<32>::from_array(core::array::from_fn(|i| i as u8));
+//! let output = input.with_sized_ptr(|src| {
+//!     PinnedSizedBytes::<32>::create_sized(|dst| {
+//!         // SAFETY: both pointers reference exactly 32 live bytes for the
+//!         // duration of the callbacks.
+//!         unsafe { reverse32(src.as_ptr(), dst.as_mut_ptr()) }
+//!     })
+//! });
+//! assert_eq!(output.as_bytes()[0], 31);
+//! assert_eq!(output.as_bytes()[31], 0);
+//! ```
+
 use std::marker::PhantomData;
-use std::ptr::NonNull;
+use std::ptr::{self, NonNull};
 
 /// Pointer tagged with a compile-time size guaranteeing the backing region
 /// contains exactly `N` bytes. The lifetime ensures the pointer cannot outlive
@@ -18,6 +49,12 @@ impl<'a, const N: usize> SizedPtr<'a, N> {
         }
     }
 
+    /// Borrow a fixed-size array as a sized pointer.
+    #[must_use]
+    pub fn from_array(array: &'a [u8; N]) -> Self {
+        Self::new(NonNull::from(array).cast::<u8>())
+    }
+
     /// Returns the raw pointer.
     #[must_use]
     pub fn as_ptr(self) -> *const u8 {
@@ -58,6 +95,12 @@ impl<'a, const N: usize> SizedMutPtr<'a, N> {
         }
     }
 
+    /// Borrow a fixed-size array as a mutable sized pointer.
+    #[must_use]
+    pub fn from_array(array: &'a mut [u8; N]) -> Self {
+        Self::new(NonNull::from(array).cast::<u8>())
+    }
+
     #[must_use]
     pub fn as_ptr(self) -> *const u8 {
         self.ptr.as_ptr()
@@ -79,3 +122,12 @@ impl<'a, const N: usize> SizedMutPtr<'a, N> {
         N == 0
     }
 }
+
+/// Copy `N` bytes from `src` to `dst`, mirroring Haskell's `copyMem` on sized
+/// pointers. Both pointers carry their size in the type, so the copy needs no
+/// caller-side bounds checks.
+pub fn copy_sized<const N: usize>(dst: SizedMutPtr<'_, N>, src: SizedPtr<'_, N>) {
+    // SAFETY: both pointers were derived from live borrows of exactly `N`
+    // bytes that outlive this call. `ptr::copy` tolerates overlap.
+    unsafe { ptr::copy(src.as_ptr(), dst.as_mut_ptr(), N) }
+}
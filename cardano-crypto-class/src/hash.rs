@@ -14,24 +14,243 @@
 //! - **Keccak-256**: Original Keccak, used in Ethereum 1.0
 //! - **RIPEMD-160**: Used in Bitcoin addresses
 //! - **Blake2b-224/256/512**: Used across Cardano for verification-key and tree hashing
+//!
+//! # Generic hashing
+//!
+//! [`HashAlgorithm`] mirrors the `HashAlgorithm` class from
+//! `Cardano.Crypto.Hash.Class`, letting typed hash wrappers and serialiser
+//! hashing be written once and instantiated per algorithm. It is implemented
+//! for the Blake2b family and for the marker types [`Sha256`], [`Sha512`],
+//! [`Sha3_256`], [`Keccak256`], and [`Ripemd160`], all of which also
+//! implement [`IncrementalHashAlgorithm`]. The deprecated KES trait
+//! `KesHashAlgorithm` has the same required items, and every implementor of
+//! it is a `HashAlgorithm`. The Blake2b types also implement
+//! [`KeyedHashAlgorithm`] for keyed and personalized hashing.
 
 use blake2::Blake2b;
 use blake2::digest::consts::U28;
 use digest::Digest;
-use ripemd::Ripemd160;
-use sha2::{Sha256, Sha512};
-use sha3::{Keccak256, Sha3_256, Sha3_512};
+use ripemd::Ripemd160 as Ripemd160Digest;
+use sha2::{Sha256 as Sha256Digest, Sha512 as Sha512Digest};
+use sha3::{Keccak256 as Keccak256Digest, Sha3_256 as Sha3_256Digest, Sha3_512 as Sha3_512Digest};
 use subtle::ConstantTimeEq;
 
 // Re-export KES Blake2b implementations for unified hashing API surface.
 pub use crate::kes::hash::{Blake2b224, Blake2b256, Blake2b512};
 
+/// Trait implemented by every hash algorithm usable with generic hashing code.
+///
+/// Mirrors Haskell's `HashAlgorithm` class: each algorithm advertises a fixed
+/// digest size and a stable name and hashes a complete buffer via
+/// [`HashAlgorithm::hash`]. Algorithms that can also absorb input piece by
+/// piece implement [`IncrementalHashAlgorithm`].
+pub trait HashAlgorithm: Clone + Send + Sync + 'static {
+    /// The size of the hash output in bytes.
+    const OUTPUT_SIZE: usize;
+
+    /// The name of the hash algorithm (matches Haskell's `hashAlgorithmName`).
+    const ALGORITHM_NAME: &'static str;
+
+    /// Hash arbitrary data and return a fixed-size output.
+    fn hash(data: &[u8]) -> Vec<u8>;
+
+    /// Hash two pieces of data concatenated together.
+    /// Default implementation concatenates then hashes, but can be overridden for efficiency.
+    #[must_use]
+    fn hash_concat(data1: &[u8], data2: &[u8]) -> Vec<u8> {
+        let mut combined = Vec::with_capacity(data1.len() + data2.len());
+        combined.extend_from_slice(data1);
+        combined.extend_from_slice(data2);
+        Self::hash(&combined)
+    }
+
+    /// Expand a seed into two seeds using the hash algorithm.
+    /// This is used for seed expansion in Sum compositions.
+    /// Uses prefixes 1 and 2 to match Haskell cardano-base implementation:
+    /// - r0 = hash(1 || seed)
+    /// - r1 = hash(2 || seed)
+    #[must_use]
+    fn expand_seed(seed: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        // Using 1 and 2 to match Haskell: BS.cons 1 and BS.cons 2
+        (
+            Self::hash_concat(&[1u8], seed),
+            Self::hash_concat(&[2u8], seed),
+        )
+    }
+}
+
+/// Hash algorithms that absorb their input incrementally.
+///
+/// Streaming consumers such as [`HashedDsign`](crate::dsign::hashed::HashedDsign)
+/// feed a message chunk by chunk through a [`IncrementalHashAlgorithm::Context`]
+/// instead of buffering it. Finalizing a context that absorbed `data` gives
+/// [`HashAlgorithm::hash`] of `data`.
+pub trait IncrementalHashAlgorithm: HashAlgorithm {
+    /// Incremental hashing state.
+    type Context: Clone + Send;
+
+    /// Create a fresh incremental hashing context.
+    fn new_context() -> Self::Context;
+
+    /// Absorb `data` into an incremental hashing context.
+    fn update(context: &mut Self::Context, data: &[u8]);
+
+    /// Consume the context and return the [`HashAlgorithm::OUTPUT_SIZE`]-byte digest.
+    fn finalize(context: Self::Context) -> Vec<u8>;
+}
+
+/// Hash algorithms with BLAKE2-style keyed and personalized modes.
+///
+/// Both modes set fields of the BLAKE2 parameter block, so they change every
+/// output bit without changing [`HashAlgorithm::OUTPUT_SIZE`]. That makes them
+/// suitable for domain separation: two uses of Blake2b-256 with different
+/// personalization strings can never collide with each other or with plain
+/// [`HashAlgorithm::hash`]. An empty key and no personalization is exactly the
+/// unkeyed hash.
+///
+/// Sum and CompactSum KES keep hashing their tree nodes with the unkeyed
+/// form, since that is what Haskell's `cardano-base` does and verification
+/// keys must stay byte-identical across the two implementations.
+pub trait KeyedHashAlgorithm: HashAlgorithm {
+    /// Longest key accepted by [`KeyedHashAlgorithm::hash_keyed`], in bytes.
+    const MAX_KEY_SIZE: usize;
+
+    /// Personalization string, sized by the algorithm's parameter block
+    /// (16 bytes for Blake2b, 8 for Blake2s).
+    type Personal: AsRef<[u8]>;
+
+    /// Keyed hash (BLAKE2 MAC) of `data` under `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HashKeyTooLong`] if `key` is longer than
+    /// [`KeyedHashAlgorithm::MAX_KEY_SIZE`].
+    fn hash_keyed(key: &[u8], data: &[u8]) -> Result<Vec<u8>, HashKeyTooLong>;
+
+    /// Unkeyed hash of `data` with the personalization string `personal`.
+    #[must_use]
+    fn hash_personalized(personal: &Self::Personal, data: &[u8]) -> Vec<u8>;
+}
+
+/// A key passed to [`KeyedHashAlgorithm::hash_keyed`] exceeds the
+/// algorithm's maximum key size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("hash key too long: {actual} bytes, maximum {max}")]
+pub struct HashKeyTooLong {
+    /// Maximum key size of the algorithm.
+    pub max: usize,
+    /// Length of the rejected key.
+    pub actual: usize,
+}
+
+impl HashKeyTooLong {
+    /// Stable identifier of this error (see the crate-level *Error codes*
+    /// section).
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        "hash.key_too_long"
+    }
+
+    /// Always `false`: the key length is fixed by the caller.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for HashKeyTooLong {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(4))?;
+        map.serialize_entry("code", self.code())?;
+        map.serialize_entry("max", &self.max)?;
+        map.serialize_entry("actual", &self.actual)?;
+        map.serialize_entry("message", &self.to_string())?;
+        map.end()
+    }
+}
+
+/// Implement [`HashAlgorithm`] (or, with a trailing trait path, another trait
+/// of the same shape) and [`IncrementalHashAlgorithm`] for a marker type
+/// backed by a RustCrypto digest.
+macro_rules! impl_digest_hash_algorithm {
+    ($ty:ty, $digest:ty, $size:expr, $name:expr) => {
+        impl_digest_hash_algorithm!($ty, $digest, $size, $name, $crate::hash::HashAlgorithm);
+    };
+    ($ty:ty, $digest:ty, $size:expr, $name:expr, $trait:path) => {
+        #[allow(deprecated)]
+        impl $trait for $ty {
+            const OUTPUT_SIZE: usize = $size;
+            const ALGORITHM_NAME: &'static str = $name;
+
+            fn hash(data: &[u8]) -> Vec<u8> {
+                <$digest as ::digest::Digest>::digest(data).to_vec()
+            }
+
+            // Feed both slices through one digest so the inputs never need
+            // to be copied into a combined buffer.
+            fn hash_concat(data1: &[u8], data2: &[u8]) -> Vec<u8> {
+                let mut context = <$digest as ::digest::Digest>::new();
+                ::digest::Digest::update(&mut context, data1);
+                ::digest::Digest::update(&mut context, data2);
+                ::digest::Digest::finalize(context).to_vec()
+            }
+        }
+
+        impl $crate::hash::IncrementalHashAlgorithm for $ty {
+            type Context = $digest;
+
+            fn new_context() -> Self::Context {
+                <$digest as ::digest::Digest>::new()
+            }
+
+            fn update(context: &mut Self::Context, data: &[u8]) {
+                ::digest::Digest::update(context, data);
+            }
+
+            fn finalize(context: Self::Context) -> Vec<u8> {
+                ::digest::Digest::finalize(context).to_vec()
+            }
+        }
+    };
+}
+
+pub(crate) use impl_digest_hash_algorithm;
+
+/// SHA-256 hash algorithm (32-byte output), `Cardano.Crypto.Hash.SHA256`.
+#[derive(Clone, Debug)]
+pub struct Sha256;
+
+/// SHA-512 hash algorithm (64-byte output).
+#[derive(Clone, Debug)]
+pub struct Sha512;
+
+/// SHA3-256 hash algorithm (32-byte output), `Cardano.Crypto.Hash.SHA3_256`.
+#[derive(Clone, Debug)]
+pub struct Sha3_256;
+
+/// Keccak-256 hash algorithm (32-byte output), `Cardano.Crypto.Hash.Keccak256`.
+#[derive(Clone, Debug)]
+pub struct Keccak256;
+
+/// RIPEMD-160 hash algorithm (20-byte output), `Cardano.Crypto.Hash.RIPEMD160`.
+#[derive(Clone, Debug)]
+pub struct Ripemd160;
+
+impl_digest_hash_algorithm!(Sha256, Sha256Digest, 32, "sha256");
+impl_digest_hash_algorithm!(Sha512, Sha512Digest, 64, "sha512");
+impl_digest_hash_algorithm!(Sha3_256, Sha3_256Digest, 32, "sha3_256");
+impl_digest_hash_algorithm!(Keccak256, Keccak256Digest, 32, "keccak256");
+impl_digest_hash_algorithm!(Ripemd160, Ripemd160Digest, 20, "ripemd160");
+
 /// SHA-256 hash (32 bytes output).
 ///
 /// Used extensively in Bitcoin for transaction hashing, block mining, and address generation.
 #[must_use]
 pub fn sha256(data: &[u8]) -> [u8; 32] {
-    let mut hasher = Sha256::new();
+    let mut hasher = Sha256Digest::new();
     hasher.update(data);
     hasher.finalize().into()
 }
@@ -50,7 +269,7 @@ pub fn sha256d(data: &[u8]) -> [u8; 32] {
 /// General purpose cryptographic hash with longer output.
 #[must_use]
 pub fn sha512(data: &[u8]) -> [u8; 64] {
-    let mut hasher = Sha512::new();
+    let mut hasher = Sha512Digest::new();
     hasher.update(data);
     hasher.finalize().into()
 }
@@ -61,7 +280,7 @@ pub fn sha512(data: &[u8]) -> [u8; 64] {
 /// Used in Ethereum 2.0 and various modern protocols.
 #[must_use]
 pub fn sha3_256(data: &[u8]) -> [u8; 32] {
-    let mut hasher = Sha3_256::new();
+    let mut hasher = Sha3_256Digest::new();
     hasher.update(data);
     hasher.finalize().into()
 }
@@ -71,7 +290,7 @@ pub fn sha3_256(data: &[u8]) -> [u8; 32] {
 /// Keccak-based standardized hash function with longer output.
 #[must_use]
 pub fn sha3_512(data: &[u8]) -> [u8; 64] {
-    let mut hasher = Sha3_512::new();
+    let mut hasher = Sha3_512Digest::new();
     hasher.update(data);
     hasher.finalize().into()
 }
@@ -82,7 +301,7 @@ pub fn sha3_512(data: &[u8]) -> [u8; 64] {
 /// Used in Ethereum 1.0 for transaction hashing and address generation.
 #[must_use]
 pub fn keccak256(data: &[u8]) -> [u8; 32] {
-    let mut hasher = Keccak256::new();
+    let mut hasher = Keccak256Digest::new();
     hasher.update(data);
     hasher.finalize().into()
 }
@@ -92,7 +311,7 @@ pub fn keccak256(data: &[u8]) -> [u8; 32] {
 /// Used in Bitcoin address generation: `RIPEMD160(SHA256(pubkey))`.
 #[must_use]
 pub fn ripemd160(data: &[u8]) -> [u8; 20] {
-    let mut hasher = Ripemd160::new();
+    let mut hasher = Ripemd160Digest::new();
     hasher.update(data);
     hasher.finalize().into()
 }
@@ -134,7 +353,6 @@ pub fn constant_time_eq(lhs: &[u8], rhs: &[u8]) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::kes::hash::KesHashAlgorithm; // bring trait providing ::hash into scope
 
     #[test]
     fn test_blake2b224_empty() {
@@ -406,6 +624,153 @@ mod tests {
         }
     }
 
+    fn digest_hex<H: HashAlgorithm>(data: &[u8]) -> String {
+        hex::encode(<H as HashAlgorithm>::hash(data))
+    }
+
+    #[test]
+    fn test_hash_algorithm_nist_abc_vectors() {
+        // FIPS 180-2 / FIPS 202 "abc" vectors, Keccak team reference and the
+        // RIPEMD-160 reference page.
+        assert_eq!(
+            digest_hex::<Sha256>(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            digest_hex::<Sha512>(b"abc"),
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+        assert_eq!(
+            digest_hex::<Sha3_256>(b"abc"),
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        );
+        assert_eq!(
+            digest_hex::<Keccak256>(b"abc"),
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+        assert_eq!(
+            digest_hex::<Ripemd160>(b"abc"),
+            "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"
+        );
+    }
+
+    #[test]
+    fn test_hash_algorithm_nist_multiblock_vectors() {
+        let msg = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+        assert_eq!(
+            digest_hex::<Sha256>(msg),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+        assert_eq!(
+            digest_hex::<Sha3_256>(msg),
+            "41c0dba2a9d6240849100376a8235e2c82e1b9998a999e21db32dd97496d3376"
+        );
+        assert_eq!(
+            digest_hex::<Ripemd160>(msg),
+            "12a053384a9c0c88e405a06c27dcf49ada62eb2b"
+        );
+    }
+
+    #[test]
+    fn test_hash_algorithm_matches_free_functions() {
+        let data = b"cardano cross-chain";
+        assert_eq!(Sha256::hash(data), sha256(data).to_vec());
+        assert_eq!(Sha512::hash(data), sha512(data).to_vec());
+        assert_eq!(Sha3_256::hash(data), sha3_256(data).to_vec());
+        assert_eq!(Keccak256::hash(data), keccak256(data).to_vec());
+        assert_eq!(Ripemd160::hash(data), ripemd160(data).to_vec());
+    }
+
+    #[test]
+    fn test_hash_algorithm_output_sizes_and_names() {
+        fn check<H: HashAlgorithm>(name: &str) {
+            assert_eq!(H::ALGORITHM_NAME, name);
+            assert_eq!(H::hash(b"size").len(), H::OUTPUT_SIZE);
+        }
+        check::<Sha256>("sha256");
+        check::<Sha512>("sha512");
+        check::<Sha3_256>("sha3_256");
+        check::<Keccak256>("keccak256");
+        check::<Ripemd160>("ripemd160");
+        check::<Blake2b224>("blake2b_224");
+        check::<Blake2b256>("blake2b_256");
+        check::<Blake2b512>("blake2b_512");
+    }
+
+    #[test]
+    fn test_hash_algorithm_incremental_context() {
+        fn check<H: IncrementalHashAlgorithm>() {
+            let mut context = H::new_context();
+            H::update(&mut context, b"hello ");
+            let forked = context.clone();
+            H::update(&mut context, b"world");
+            assert_eq!(H::finalize(context), H::hash(b"hello world"));
+            assert_eq!(H::finalize(forked), H::hash(b"hello "));
+        }
+        check::<Sha256>();
+        check::<Sha512>();
+        check::<Sha3_256>();
+        check::<Keccak256>();
+        check::<Ripemd160>();
+        check::<Blake2b224>();
+        check::<Blake2b256>();
+    }
+
+    /// Blake2b-256 written against the legacy KES trait: only the two
+    /// constants and `hash`, as downstream implementors had to provide.
+    #[derive(Clone)]
+    struct LegacyBlake2b256;
+
+    #[allow(deprecated)]
+    impl crate::kes::hash::KesHashAlgorithm for LegacyBlake2b256 {
+        const OUTPUT_SIZE: usize = 32;
+        const ALGORITHM_NAME: &'static str = "legacy_blake2b_256";
+
+        fn hash(data: &[u8]) -> Vec<u8> {
+            Blake2b::<blake2::digest::consts::U32>::digest(data).to_vec()
+        }
+    }
+
+    #[test]
+    fn test_legacy_kes_hash_implementor_is_a_hash_algorithm() {
+        use crate::dsign::ed25519::Ed25519;
+        use crate::kes::{KesAlgorithm, SingleKes, Sum1Kes, sum::SumKes};
+
+        let inputs = [b"".as_ref(), b"kes vk".as_ref(), &[0xAB; 300]];
+        for input in inputs {
+            assert_eq!(
+                <LegacyBlake2b256 as HashAlgorithm>::hash(input),
+                Blake2b256::hash(input)
+            );
+            assert_eq!(
+                <LegacyBlake2b256 as HashAlgorithm>::hash_concat(input, b"tail"),
+                Blake2b256::hash_concat(input, b"tail")
+            );
+            assert_eq!(
+                <LegacyBlake2b256 as HashAlgorithm>::expand_seed(input),
+                Blake2b256::expand_seed(input)
+            );
+        }
+        assert_eq!(<LegacyBlake2b256 as HashAlgorithm>::OUTPUT_SIZE, 32);
+        assert_eq!(
+            <LegacyBlake2b256 as HashAlgorithm>::ALGORITHM_NAME,
+            "legacy_blake2b_256"
+        );
+
+        // The legacy implementor still parameterises the sum composition and
+        // gives the same keys as the built-in Blake2b-256.
+        type LegacySum1Kes = SumKes<SingleKes<Ed25519>, LegacyBlake2b256>;
+        let legacy_sk = LegacySum1Kes::gen_key_kes_from_seed_bytes(&[9u8; 32]).unwrap();
+        let sk = Sum1Kes::gen_key_kes_from_seed_bytes(&[9u8; 32]).unwrap();
+        assert_eq!(
+            LegacySum1Kes::derive_verification_key(&legacy_sk).unwrap(),
+            Sum1Kes::derive_verification_key(&sk).unwrap()
+        );
+        LegacySum1Kes::forget_signing_key_kes(legacy_sk);
+        Sum1Kes::forget_signing_key_kes(sk);
+    }
+
     #[test]
     fn test_blake2b224_not_truncation() {
         let cases = [
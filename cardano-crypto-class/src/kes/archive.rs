@@ -0,0 +1,162 @@
+//! Verification of historical KES signatures across operational certificates.
+//!
+//! Verifying an old block header only needs the KES verification key from
+//! its operational certificate and the period relative to the certificate's
+//! start, so signatures remain checkable long after the signing keys have
+//! expired. [`HistoricalVerifier`] holds the verification keys of a
+//! sequence of operational certificates and routes each slot to the
+//! certificate that covers it:
+//!
+//! 1. the absolute KES period is `slot / slots_per_kes_period`;
+//! 2. a certificate starting at period `c0` covers `[c0, c0 + total_periods)`;
+//! 3. the signature is checked at the relative period `kes_period - c0`.
+//!
+//! When several certificates cover a period, the one with the latest start
+//! period wins, mirroring how a newly issued certificate supersedes the
+//! previous one. Periods covered by no certificate are rejected with
+//! [`HistoricalVerifyError::NoKeyForPeriod`].
+//!
+//! The Sum and CompactSum families have no per-key precomputation to cache
+//! (the root key is a hash compared against the one rebuilt from the
+//! signature), so the verifier keeps the decoded keys and each call costs
+//! exactly one `verify_kes`.
+//!
+//! ```rust
+//! use cardano_crypto_class::kes::archive::HistoricalVerifier;
+//! use cardano_crypto_class::kes::{KesAlgorithm, Sum2Kes};
+//!
+//! let sk = Sum2Kes::gen_key_kes_from_seed_bytes(&[1u8; 32]).unwrap();
+//! let vk = Sum2Kes::derive_verification_key(&sk).unwrap();
+//! let sig = Sum2Kes::sign_kes(&(), 0, b"header", &sk).unwrap();
+//!
+//! // The certificate starts at KES period 10; with 100 slots per period,
+//! // slot 1_050 falls into relative period 0.
+//! let verifier = HistoricalVerifier::<Sum2Kes>::new([(10, vk)]);
+//! verifier.verify_at_slot(1_050, 100, b"header", &sig).unwrap();
+//! assert!(verifier.verify_at_slot(950, 100, b"header", &sig).is_err());
+//! ```
+
+use thiserror::Error;
+
+use crate::kes::{KesAlgorithm, KesError, Period};
+
+/// Error raised when a historical signature cannot be verified.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum HistoricalVerifyError {
+    #[error("slots per KES period must be non-zero")]
+    ZeroSlotsPerKesPeriod,
+    #[error("no operational certificate covers slot {slot} (KES period {kes_period})")]
+    NoKeyForPeriod { slot: u64, kes_period: Period },
+    #[error(
+        "signature at KES period {kes_period} (certificate starting at {ocert_start_period}) \
+         failed verification: {source}"
+    )]
+    Verification {
+        ocert_start_period: Period,
+        kes_period: Period,
+        #[source]
+        source: KesError,
+    },
+}
+
+/// Absolute KES period containing `slot`, or `None` when
+/// `slots_per_kes_period` is zero.
+#[must_use]
+pub fn kes_period_of_slot(slot: u64, slots_per_kes_period: u64) -> Option<Period> {
+    slot.checked_div(slots_per_kes_period)
+}
+
+/// Verification keys of consecutive operational certificates, indexed by the
+/// KES period at which each certificate starts.
+pub struct HistoricalVerifier<K: KesAlgorithm> {
+    context: K::Context,
+    /// Sorted by start period; equal starts keep their insertion order, so the
+    /// later entry wins.
+    ranges: Vec<(Period, K::VerificationKey)>,
+}
+
+impl<K> HistoricalVerifier<K>
+where
+    K: KesAlgorithm,
+    K::Context: Default,
+{
+    /// Build a verifier from `(ocert_start_period, verification_key)` pairs
+    /// using the default KES context.
+    pub fn new(ranges: impl IntoIterator<Item = (Period, K::VerificationKey)>) -> Self {
+        Self::with_context(K::Context::default(), ranges)
+    }
+}
+
+impl<K: KesAlgorithm> HistoricalVerifier<K> {
+    /// Build a verifier from `(ocert_start_period, verification_key)` pairs
+    /// with an explicit KES context.
+    pub fn with_context(
+        context: K::Context,
+        ranges: impl IntoIterator<Item = (Period, K::VerificationKey)>,
+    ) -> Self {
+        let mut ranges: Vec<_> = ranges.into_iter().collect();
+        ranges.sort_by_key(|(start, _)| *start);
+        Self { context, ranges }
+    }
+
+    /// Number of certificates known to the verifier.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns `true` if the verifier holds no certificates.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Certificate covering the absolute `kes_period`, as its start period and
+    /// verification key. The latest-starting certificate wins on overlap.
+    #[must_use]
+    pub fn key_for_period(&self, kes_period: Period) -> Option<(Period, &K::VerificationKey)> {
+        let candidates = self
+            .ranges
+            .partition_point(|(start, _)| *start <= kes_period);
+        self.ranges[..candidates]
+            .iter()
+            .rev()
+            .find(|(start, _)| kes_period - start < K::total_periods())
+            .map(|(start, key)| (*start, key))
+    }
+
+    /// Verify `signature` over `message` for the block produced in `slot`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HistoricalVerifyError::ZeroSlotsPerKesPeriod`] if
+    /// `slots_per_kes_period` is zero,
+    /// [`HistoricalVerifyError::NoKeyForPeriod`] if no certificate covers the
+    /// slot's KES period, and [`HistoricalVerifyError::Verification`] if the
+    /// signature does not verify under the selected key.
+    pub fn verify_at_slot(
+        &self,
+        slot: u64,
+        slots_per_kes_period: u64,
+        message: &[u8],
+        signature: &K::Signature,
+    ) -> Result<(), HistoricalVerifyError> {
+        let kes_period = kes_period_of_slot(slot, slots_per_kes_period)
+            .ok_or(HistoricalVerifyError::ZeroSlotsPerKesPeriod)?;
+        let (ocert_start_period, key) = self
+            .key_for_period(kes_period)
+            .ok_or(HistoricalVerifyError::NoKeyForPeriod { slot, kes_period })?;
+        K::verify_kes(
+            &self.context,
+            key,
+            kes_period - ocert_start_period,
+            message,
+            signature,
+        )
+        .map_err(|source| HistoricalVerifyError::Verification {
+            ocert_start_period,
+            kes_period,
+            source,
+        })
+    }
+}
@@ -1,7 +1,9 @@
 use std::marker::PhantomData;
 
 use crate::dsign::{DsignMAlgorithm, UnsoundDsignMAlgorithm};
-use crate::kes::{KesAlgorithm, KesError, KesMError, Period};
+use crate::kes::{
+    KesAlgorithm, KesError, KesMError, KesVerifyFailure, KesVerifyStage, Period, observer,
+};
 
 /// CompactSingleKES wraps a DSIGNM algorithm with an embedded verification key.
 ///
@@ -11,11 +13,28 @@ use crate::kes::{KesAlgorithm, KesError, KesMError, Period};
 pub struct CompactSingleKes<D: DsignMAlgorithm>(PhantomData<D>);
 
 /// Signature type that embeds the verification key.
+///
+/// The raw form is the DSIGN signature followed by the verification key.
+/// Decoding validates both halves with `D`'s own deserialisers, so an
+/// embedded key that is not a valid `D` key (for Ed25519, bytes that do not
+/// decode to a curve point) is rejected before verification.
 pub struct CompactSingleSig<D: DsignMAlgorithm> {
     pub(crate) signature: D::Signature,
     pub(crate) verification_key: D::VerificationKey,
 }
 
+impl<D: DsignMAlgorithm> CompactSingleSig<D> {
+    /// The verification key carried in the signature.
+    pub fn embedded_verification_key(&self) -> &D::VerificationKey {
+        &self.verification_key
+    }
+
+    /// The DSIGN signature, without the embedded key.
+    pub fn dsign_signature(&self) -> &D::Signature {
+        &self.signature
+    }
+}
+
 impl<D> Clone for CompactSingleSig<D>
 where
     D: DsignMAlgorithm,
@@ -164,19 +183,21 @@ where
         message: &[u8],
         signing_key: &Self::SigningKey,
     ) -> Result<Self::Signature, KesMError> {
-        if period != 0 {
-            return Err(KesMError::Kes(KesError::PeriodOutOfRange {
-                period,
-                max_period: 1,
-            }));
-        }
-        let signature = D::sign_bytes_m(context, message, signing_key)
-            .map_err(|e| KesMError::Dsign(format!("{:?}", e)))?;
-        let verification_key = D::derive_verification_key_m(signing_key)
-            .map_err(|e| KesMError::Dsign(format!("{:?}", e)))?;
-        Ok(CompactSingleSig {
-            signature,
-            verification_key,
+        observer::observe_sign(Self::ALGORITHM_NAME, period, || {
+            if period != 0 {
+                return Err(KesMError::Kes(KesError::PeriodOutOfRange {
+                    period,
+                    max_period: 1,
+                }));
+            }
+            let signature = D::sign_bytes_m(context, message, signing_key)
+                .map_err(|e| KesMError::Dsign(format!("{:?}", e)))?;
+            let verification_key = D::derive_verification_key_m(signing_key)
+                .map_err(|e| KesMError::Dsign(format!("{:?}", e)))?;
+            Ok(CompactSingleSig {
+                signature,
+                verification_key,
+            })
         })
     }
 
@@ -203,19 +224,47 @@ where
         .map_err(|_| KesError::VerificationFailed)
     }
 
+    fn explain_verify_failure_kes(
+        _context: &Self::Context,
+        verification_key: &Self::VerificationKey,
+        period: Period,
+        _message: &[u8],
+        signature: &Self::Signature,
+    ) -> KesVerifyFailure {
+        // Verification uses the embedded key, so a failing leaf under a key
+        // other than the expected one points at the embedded key.
+        let stage = if D::raw_serialize_verification_key(verification_key)
+            == D::raw_serialize_verification_key(&signature.verification_key)
+        {
+            KesVerifyStage::LeafSignature
+        } else {
+            KesVerifyStage::EmbeddedKeyMismatch
+        };
+        KesVerifyFailure::at_root(stage, period)
+    }
+
     fn update_kes(
         _context: &Self::Context,
         signing_key: Self::SigningKey,
         period: Period,
     ) -> Result<Option<Self::SigningKey>, KesMError> {
-        let last_period = Self::total_periods().saturating_sub(1);
+        observer::observe_update(Self::ALGORITHM_NAME, period, || {
+            if period != 0 {
+                D::forget_signing_key_m(signing_key);
+                return Err(KesMError::Kes(KesError::PeriodOutOfRange {
+                    period,
+                    max_period: 1,
+                }));
+            }
+            let last_period = Self::total_periods().saturating_sub(1);
 
-        if period >= last_period {
-            D::forget_signing_key_m(signing_key);
-            Ok(None)
-        } else {
-            Ok(Some(signing_key))
-        }
+            if period >= last_period {
+                D::forget_signing_key_m(signing_key);
+                Ok(None)
+            } else {
+                Ok(Some(signing_key))
+            }
+        })
     }
 
     fn gen_key_kes_from_seed_bytes(seed: &[u8]) -> Result<Self::SigningKey, KesMError> {
@@ -261,6 +310,25 @@ where
     }
 }
 
+#[cfg(feature = "tooling")]
+impl<D> crate::kes::UnsoundKesAlgorithm for CompactSingleKes<D>
+where
+    D: DsignMAlgorithm + UnsoundDsignMAlgorithm,
+    D::VerificationKey: Clone,
+{
+    fn raw_serialize_signing_key_kes(
+        signing_key: &Self::SigningKey,
+    ) -> Result<zeroize::Zeroizing<Vec<u8>>, KesMError> {
+        D::raw_serialize_signing_key_m(signing_key)
+            .map(zeroize::Zeroizing::new)
+            .map_err(|e| KesMError::Dsign(format!("{:?}", e)))
+    }
+
+    fn raw_deserialize_signing_key_kes(bytes: &[u8]) -> Result<Self::SigningKey, KesMError> {
+        D::raw_deserialize_signing_key_m(bytes).map_err(|e| KesMError::Dsign(format!("{:?}", e)))
+    }
+}
+
 /// Helper trait to extract the verification key from a CompactSingle signature.
 pub trait OptimizedKesSignature {
     type VerificationKey;
@@ -1,9 +1,15 @@
 use std::marker::PhantomData;
 
-use crate::direct_serialise::{DirectDeserialise, DirectResult, DirectSerialise};
+use crate::direct_serialise::{
+    DirectDeserialise, DirectResult, DirectSerialise, DirectSerialiseError,
+};
+use crate::hash::HashAlgorithm;
 use crate::kes::compact_single::OptimizedKesSignature;
-use crate::kes::hash::KesHashAlgorithm;
-use crate::kes::{KesAlgorithm, KesError, KesMError, Period};
+use crate::kes::sum::{ChildPeriod, root_verification_key, route_period, sum_total_periods};
+use crate::kes::{
+    KesAlgorithm, KesError, KesMError, KesVerificationKeyRef, KesVerifyFailure, KesVerifyStage,
+    Period, observer,
+};
 use crate::mlocked_bytes::MLockedBytes;
 use crate::seed::Seed;
 
@@ -32,28 +38,55 @@ pub struct CompactSumKes<D, H>(PhantomData<(D, H)>)
 where
     D: KesAlgorithm,
     D::Signature: OptimizedKesSignature,
-    H: KesHashAlgorithm;
+    H: HashAlgorithm;
 
 /// Signing key for CompactSumKES.
 pub struct CompactSumSigningKey<D, H>
 where
     D: KesAlgorithm,
     D::Signature: OptimizedKesSignature,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     pub(crate) sk: D::SigningKey,
     pub(crate) r1_seed: Option<MLockedBytes>,
     pub(crate) vk0: D::VerificationKey,
     pub(crate) vk1: D::VerificationKey,
+    /// Cached root verification key `H(vk0 || vk1)`
+    pub(crate) vk: Vec<u8>,
     _phantom: PhantomData<H>,
 }
 
+impl<D, H> CompactSumSigningKey<D, H>
+where
+    D: KesAlgorithm,
+    D::Signature: OptimizedKesSignature,
+    H: HashAlgorithm,
+{
+    /// Assemble a key from its parts, computing the cached root verification key.
+    pub(crate) fn from_parts(
+        sk: D::SigningKey,
+        r1_seed: Option<MLockedBytes>,
+        vk0: D::VerificationKey,
+        vk1: D::VerificationKey,
+    ) -> Self {
+        let vk = root_verification_key::<D, H>(&vk0, &vk1);
+        Self {
+            sk,
+            r1_seed,
+            vk0,
+            vk1,
+            vk,
+            _phantom: PhantomData,
+        }
+    }
+}
+
 /// Signature for CompactSumKES - only stores the "other" verification key.
 pub struct CompactSumSignature<D, H>
 where
     D: KesAlgorithm,
     D::Signature: OptimizedKesSignature,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     /// Signature from the active subtree (contains embedded vk)
     pub(crate) sigma: D::Signature,
@@ -62,14 +95,58 @@ where
     _phantom: PhantomData<H>,
 }
 
+impl<D, H> CompactSumSignature<D, H>
+where
+    D: KesAlgorithm,
+    D::Signature: OptimizedKesSignature,
+    H: HashAlgorithm,
+{
+    pub(crate) fn from_parts(sigma: D::Signature, vk_other: D::VerificationKey) -> Self {
+        Self {
+            sigma,
+            vk_other,
+            _phantom: PhantomData,
+        }
+    }
+}
+
 /// Helper trait used to recover the verification key associated with a compact subtree
 /// for any supported KES algorithm. CompactSum verification stitches these keys back
 /// together when recomputing higher-level verification hashes.
 pub trait CompactKesComponents: KesAlgorithm {
+    /// The key `signature` was made under at `period`, rebuilt from the keys
+    /// embedded in it. `period` is not range checked.
     fn active_verification_key_from_signature(
         signature: &Self::Signature,
         period: Period,
     ) -> Self::VerificationKey;
+
+    /// Rebuild the root verification key from the keys embedded in a compact
+    /// signature made at `period`.
+    ///
+    /// This is what `verify_kes` compares against the expected key before it
+    /// checks the leaf signature. A light client can use it to match a
+    /// signature against an operational certificate's hot key before the
+    /// message is available. The key is **unauthenticated** until
+    /// `verify_kes` succeeds against a message: anyone can embed keys that
+    /// hash to a chosen root without holding the signing key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KesError::PeriodOutOfRange`] if `period` is not below
+    /// [`KesAlgorithm::total_periods`].
+    fn reconstruct_verification_key(
+        signature: &Self::Signature,
+        period: Period,
+    ) -> Result<Self::VerificationKey, KesError> {
+        let max_period = Self::total_periods();
+        if period >= max_period {
+            return Err(KesError::PeriodOutOfRange { period, max_period });
+        }
+        Ok(Self::active_verification_key_from_signature(
+            signature, period,
+        ))
+    }
 }
 
 impl<D, H> Clone for CompactSumSignature<D, H>
@@ -77,7 +154,7 @@ where
     D: KesAlgorithm,
     D::Signature: OptimizedKesSignature + Clone,
     D::VerificationKey: Clone,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     fn clone(&self) -> Self {
         Self {
@@ -93,7 +170,7 @@ where
     D: KesAlgorithm,
     D::VerificationKey: Clone,
     D::Signature: OptimizedKesSignature,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     type VerificationKey = <D::Signature as OptimizedKesSignature>::VerificationKey;
 
@@ -102,12 +179,63 @@ where
     }
 }
 
+impl<D, H> CompactSumKes<D, H>
+where
+    D: KesAlgorithm + CompactKesComponents,
+    D::VerificationKey: Clone,
+    D::Signature: OptimizedKesSignature + Clone,
+    H: HashAlgorithm,
+{
+    /// Rebuild this level's root from `signature`, returning it with the
+    /// active child's key. Shared by verification, failure explanation and
+    /// [`CompactKesComponents::reconstruct_verification_key`].
+    fn rebuild_root(
+        signature: &CompactSumSignature<D, H>,
+        child: ChildPeriod,
+    ) -> (Vec<u8>, D::VerificationKey) {
+        let vk_active = <D as CompactKesComponents>::active_verification_key_from_signature(
+            &signature.sigma,
+            child.period(),
+        );
+        let root = match child {
+            ChildPeriod::Left(_) => root_verification_key::<D, H>(&vk_active, &signature.vk_other),
+            ChildPeriod::Right(_) => root_verification_key::<D, H>(&signature.vk_other, &vk_active),
+        };
+        (root, vk_active)
+    }
+
+    /// Shared by `verify_kes` and `verify_kes_ref`: the root key is only
+    /// compared against, so it can be borrowed.
+    fn verify_against_hash(
+        context: &D::Context,
+        verification_key: &[u8],
+        period: Period,
+        message: &[u8],
+        signature: &CompactSumSignature<D, H>,
+    ) -> Result<(), KesError> {
+        let child = route_period::<D>(period)?;
+        let (computed_vk, vk_active) = Self::rebuild_root(signature, child);
+        if computed_vk.as_slice() != verification_key {
+            return Err(KesError::VerificationFailed);
+        }
+
+        // Verify the signature against the active verification key
+        D::verify_kes(
+            context,
+            &vk_active,
+            child.period(),
+            message,
+            &signature.sigma,
+        )
+    }
+}
+
 impl<D, H> KesAlgorithm for CompactSumKes<D, H>
 where
     D: KesAlgorithm + CompactKesComponents,
     D::VerificationKey: Clone,
     D::Signature: OptimizedKesSignature + Clone,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     type VerificationKey = Vec<u8>; // Hash of (vk0, vk1)
     type SigningKey = CompactSumSigningKey<D, H>;
@@ -123,16 +251,14 @@ where
     const SIGNATURE_SIZE: usize = D::SIGNATURE_SIZE + D::VERIFICATION_KEY_SIZE;
 
     fn total_periods() -> Period {
-        2 * D::total_periods()
+        sum_total_periods::<D>()
     }
 
     fn derive_verification_key(
         signing_key: &Self::SigningKey,
     ) -> Result<Self::VerificationKey, KesMError> {
-        // vk = H(vk0 || vk1)
-        let vk0_bytes = D::raw_serialize_verification_key_kes(&signing_key.vk0);
-        let vk1_bytes = D::raw_serialize_verification_key_kes(&signing_key.vk1);
-        Ok(H::hash_concat(&vk0_bytes, &vk1_bytes))
+        // H(vk0 || vk1), computed once when the key was generated
+        Ok(signing_key.vk.clone())
     }
 
     fn sign_kes(
@@ -141,22 +267,25 @@ where
         message: &[u8],
         signing_key: &Self::SigningKey,
     ) -> Result<Self::Signature, KesMError> {
-        let t_half = D::total_periods();
-
-        let (sigma, vk_other) = if period < t_half {
-            // Use left subtree, store right vk
-            let sig = D::sign_kes(context, period, message, &signing_key.sk)?;
-            (sig, signing_key.vk1.clone())
-        } else {
-            // Use right subtree, store left vk
-            let sig = D::sign_kes(context, period - t_half, message, &signing_key.sk)?;
-            (sig, signing_key.vk0.clone())
-        };
-
-        Ok(CompactSumSignature {
-            sigma,
-            vk_other,
-            _phantom: PhantomData,
+        observer::observe_sign(Self::ALGORITHM_NAME, period, || {
+            let (sigma, vk_other) = match route_period::<D>(period)? {
+                // Use left subtree, store right vk
+                ChildPeriod::Left(child_period) => {
+                    let sig = D::sign_kes(context, child_period, message, &signing_key.sk)?;
+                    (sig, signing_key.vk1.clone())
+                },
+                // Use right subtree, store left vk
+                ChildPeriod::Right(child_period) => {
+                    let sig = D::sign_kes(context, child_period, message, &signing_key.sk)?;
+                    (sig, signing_key.vk0.clone())
+                },
+            };
+
+            Ok(CompactSumSignature {
+                sigma,
+                vk_other,
+                _phantom: PhantomData,
+            })
         })
     }
 
@@ -167,41 +296,63 @@ where
         message: &[u8],
         signature: &Self::Signature,
     ) -> Result<(), KesError> {
-        let t_half = D::total_periods();
-        let active_is_left = period < t_half;
-        let child_period = if active_is_left {
-            period
-        } else {
-            period - t_half
-        };
+        Self::verify_against_hash(context, verification_key, period, message, signature)
+    }
 
-        // Derive the active subtree verification key (owned value so we can reuse it)
-        let vk_active = <D as CompactKesComponents>::active_verification_key_from_signature(
-            &signature.sigma,
-            child_period,
-        );
-        let vk_other = signature.vk_other.clone();
+    fn verify_kes_ref(
+        context: &Self::Context,
+        verification_key: KesVerificationKeyRef<'_, Self>,
+        period: Period,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), KesError> {
+        Self::verify_against_hash(
+            context,
+            verification_key.as_bytes(),
+            period,
+            message,
+            signature,
+        )
+    }
 
-        // Reconstruct both vk0 and vk1
-        let (vk0, vk1) = if active_is_left {
-            // Active is left, other is right
-            (vk_active.clone(), vk_other.clone())
-        } else {
-            // Active is right, other is left
-            (vk_other.clone(), vk_active.clone())
+    fn explain_verify_failure_kes(
+        context: &Self::Context,
+        verification_key: &Self::VerificationKey,
+        period: Period,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> KesVerifyFailure {
+        let Ok(child) = route_period::<D>(period) else {
+            return KesVerifyFailure::at_root(
+                KesVerifyStage::PeriodRouting {
+                    signed_period: None,
+                },
+                period,
+            );
         };
-
-        // Verify that H(vk0 || vk1) matches the provided verification key
-        let vk0_bytes = D::raw_serialize_verification_key_kes(&vk0);
-        let vk1_bytes = D::raw_serialize_verification_key_kes(&vk1);
-        let computed_vk = H::hash_concat(&vk0_bytes, &vk1_bytes);
-
-        if &computed_vk != verification_key {
-            return Err(KesError::VerificationFailed);
+        let child_period = child.period();
+        let (computed_vk, vk_active) = Self::rebuild_root(signature, child);
+
+        if computed_vk != *verification_key {
+            // The root is rebuilt from keys embedded in the signature. If the
+            // subtree does not even verify under its own embedded key, that
+            // key is the corrupt part rather than the root or `vk_other`.
+            let stage =
+                if D::verify_kes(context, &vk_active, child_period, message, &signature.sigma)
+                    .is_err()
+                {
+                    KesVerifyStage::EmbeddedKeyMismatch
+                } else {
+                    KesVerifyStage::RootHashMismatch {
+                        expected_hex: hex::encode(verification_key),
+                        actual_hex: hex::encode(&computed_vk),
+                    }
+                };
+            return KesVerifyFailure::at_root(stage, period);
         }
 
-        // Verify the signature against the active verification key
-        D::verify_kes(context, &vk_active, child_period, message, &signature.sigma)
+        D::explain_verify_failure_kes(context, &vk_active, child_period, message, &signature.sigma)
+            .below_parent(period)
     }
 
     fn update_kes(
@@ -209,60 +360,73 @@ where
         mut signing_key: Self::SigningKey,
         period: Period,
     ) -> Result<Option<Self::SigningKey>, KesMError> {
-        let t_half = D::total_periods();
-
-        if period + 1 >= 2 * t_half {
-            D::forget_signing_key_kes(signing_key.sk);
-            return Ok(None);
-        }
-
-        if period + 1 == t_half {
-            // Transition from left to right subtree
-            let r1_seed = signing_key
-                .r1_seed
-                .take()
-                .ok_or(KesMError::Kes(KesError::KeyExpired))?;
-
-            let seed = Seed::from_bytes(r1_seed.as_slice());
-            let sk1 = D::gen_key_kes(&seed)?;
-
-            D::forget_signing_key_kes(signing_key.sk);
+        observer::observe_update(Self::ALGORITHM_NAME, period, || {
+            let child = match route_period::<D>(period) {
+                Ok(child) => child,
+                Err(err) => {
+                    D::forget_signing_key_kes(signing_key.sk);
+                    return Err(err.into());
+                },
+            };
+
+            // period < total_periods, so period + 1 cannot overflow
+            if period + 1 == Self::total_periods() {
+                // Key has expired
+                D::forget_signing_key_kes(signing_key.sk);
+                return Ok(None);
+            }
 
-            Ok(Some(CompactSumSigningKey {
-                sk: sk1,
-                r1_seed: None,
+            let t_half = D::total_periods();
+            let (sk, r1_seed) = match child {
+                ChildPeriod::Left(child_period) if child_period + 1 == t_half => {
+                    // Transition from left to right subtree
+                    // Generate sk_1 from r1_seed
+                    let r1_seed = signing_key
+                        .r1_seed
+                        .take()
+                        .ok_or(KesMError::Kes(KesError::KeyExpired))?;
+
+                    let seed = Seed::from_bytes(r1_seed.as_slice());
+                    let sk1 = D::gen_key_kes(&seed)?;
+
+                    // Forget the old signing key
+                    D::forget_signing_key_kes(signing_key.sk);
+
+                    // The seed has been consumed
+                    (sk1, None)
+                },
+                // Still in left subtree, update sk_0
+                ChildPeriod::Left(child_period) => {
+                    match D::update_kes(context, signing_key.sk, child_period)? {
+                        Some(sk) => (sk, signing_key.r1_seed),
+                        None => return Ok(None),
+                    }
+                },
+                // In right subtree, update sk_1
+                ChildPeriod::Right(child_period) => {
+                    match D::update_kes(context, signing_key.sk, child_period)? {
+                        Some(sk) => (sk, None),
+                        None => return Ok(None),
+                    }
+                },
+            };
+
+            // The root verification key never changes; carry the cache forward.
+            let updated = CompactSumSigningKey {
+                sk,
+                r1_seed,
                 vk0: signing_key.vk0,
                 vk1: signing_key.vk1,
+                vk: signing_key.vk,
                 _phantom: PhantomData,
-            }))
-        } else if period + 1 < t_half {
-            // Still in left subtree
-            let updated_sk = D::update_kes(context, signing_key.sk, period)?;
-            match updated_sk {
-                Some(sk) => Ok(Some(CompactSumSigningKey {
-                    sk,
-                    r1_seed: signing_key.r1_seed,
-                    vk0: signing_key.vk0,
-                    vk1: signing_key.vk1,
-                    _phantom: PhantomData,
-                })),
-                None => Ok(None),
-            }
-        } else {
-            // In right subtree
-            let adjusted_period = period - t_half;
-            let updated_sk = D::update_kes(context, signing_key.sk, adjusted_period)?;
-            match updated_sk {
-                Some(sk) => Ok(Some(CompactSumSigningKey {
-                    sk,
-                    r1_seed: None,
-                    vk0: signing_key.vk0,
-                    vk1: signing_key.vk1,
-                    _phantom: PhantomData,
-                })),
-                None => Ok(None),
-            }
-        }
+            };
+            debug_assert_eq!(
+                updated.vk,
+                root_verification_key::<D, H>(&updated.vk0, &updated.vk1),
+                "cached root verification key diverged"
+            );
+            Ok(Some(updated))
+        })
     }
 
     fn gen_key_kes_from_seed_bytes(seed: &[u8]) -> Result<Self::SigningKey, KesMError> {
@@ -282,13 +446,12 @@ where
         let mut r1_mlocked = MLockedBytes::new(r1_bytes.len())?;
         r1_mlocked.as_mut_slice().copy_from_slice(&r1_bytes);
 
-        Ok(CompactSumSigningKey {
-            sk: sk0,
-            r1_seed: Some(r1_mlocked),
+        Ok(CompactSumSigningKey::from_parts(
+            sk0,
+            Some(r1_mlocked),
             vk0,
             vk1,
-            _phantom: PhantomData,
-        })
+        ))
     }
 
     fn raw_serialize_verification_key_kes(key: &Self::VerificationKey) -> Vec<u8> {
@@ -309,6 +472,8 @@ where
         result
     }
 
+    // Both halves go through the child's deserialisers, so the leaf's
+    // embedded key and the level-1 off-path key are checked as DSIGN keys.
     fn raw_deserialize_signature_kes(bytes: &[u8]) -> Option<Self::Signature> {
         if bytes.len() != Self::SIGNATURE_SIZE {
             return None;
@@ -332,8 +497,64 @@ where
     }
 }
 
+// Raw layout mirrors `DirectSerialise`: child signing key, right-subtree seed
+// (zeroes once consumed), then both child verification keys.
+#[cfg(feature = "tooling")]
+impl<D, H> crate::kes::UnsoundKesAlgorithm for CompactSumKes<D, H>
+where
+    D: crate::kes::UnsoundKesAlgorithm + CompactKesComponents,
+    D::VerificationKey: Clone,
+    D::Signature: OptimizedKesSignature + Clone,
+    H: HashAlgorithm,
+{
+    fn raw_serialize_signing_key_kes(
+        signing_key: &Self::SigningKey,
+    ) -> Result<zeroize::Zeroizing<Vec<u8>>, KesMError> {
+        // Sized up front so the buffer never reallocates, which would leave
+        // an unzeroed copy of the key bytes behind.
+        let mut bytes = zeroize::Zeroizing::new(Vec::with_capacity(Self::SIGNING_KEY_SIZE));
+        bytes.extend_from_slice(&D::raw_serialize_signing_key_kes(&signing_key.sk)?);
+        match &signing_key.r1_seed {
+            Some(r1_seed) => bytes.extend_from_slice(r1_seed.as_slice()),
+            None => bytes.extend(std::iter::repeat_n(0, D::SEED_SIZE)),
+        }
+        bytes.extend_from_slice(&D::raw_serialize_verification_key_kes(&signing_key.vk0));
+        bytes.extend_from_slice(&D::raw_serialize_verification_key_kes(&signing_key.vk1));
+        Ok(bytes)
+    }
+
+    fn raw_deserialize_signing_key_kes(bytes: &[u8]) -> Result<Self::SigningKey, KesMError> {
+        if bytes.len() != Self::SIGNING_KEY_SIZE {
+            return Err(KesError::wrong_length(
+                "raw_deserialize_signing_key_kes",
+                Self::SIGNING_KEY_SIZE,
+                bytes.len(),
+            )
+            .into());
+        }
+        let (sk_bytes, rest) = bytes.split_at(D::SIGNING_KEY_SIZE);
+        let (r1_bytes, rest) = rest.split_at(D::SEED_SIZE);
+        let (vk0_bytes, vk1_bytes) = rest.split_at(D::VERIFICATION_KEY_SIZE);
+
+        let invalid_vk = || KesError::Message("invalid child verification key".to_owned());
+        let vk0 = D::raw_deserialize_verification_key_kes(vk0_bytes).ok_or_else(invalid_vk)?;
+        let vk1 = D::raw_deserialize_verification_key_kes(vk1_bytes).ok_or_else(invalid_vk)?;
+        let sk = D::raw_deserialize_signing_key_kes(sk_bytes)?;
+        let mut r1_seed = MLockedBytes::new(D::SEED_SIZE)?;
+        r1_seed.as_mut_slice().copy_from_slice(r1_bytes);
+
+        Ok(CompactSumSigningKey::from_parts(
+            sk,
+            Some(r1_seed),
+            vk0,
+            vk1,
+        ))
+    }
+}
+
 // Type aliases for nested CompactSum compositions
 use crate::dsign::ed25519::Ed25519;
+use crate::dsign::{DsignMAlgorithm, UnsoundDsignMAlgorithm};
 use crate::kes::compact_single::CompactSingleKes;
 use crate::kes::hash::Blake2b256;
 
@@ -361,7 +582,11 @@ pub type CompactSum6Kes = CompactSumKes<CompactSum5Kes, Blake2b256>;
 /// 2^7 = 128 periods (compact, standard Cardano KES)
 pub type CompactSum7Kes = CompactSumKes<CompactSum6Kes, Blake2b256>;
 
-impl CompactKesComponents for CompactSum0Kes {
+impl<D> CompactKesComponents for CompactSingleKes<D>
+where
+    D: DsignMAlgorithm + UnsoundDsignMAlgorithm,
+    D::VerificationKey: Clone,
+{
     fn active_verification_key_from_signature(
         signature: &Self::Signature,
         _period: Period,
@@ -375,35 +600,19 @@ where
     D: KesAlgorithm + CompactKesComponents,
     D::VerificationKey: Clone,
     D::Signature: OptimizedKesSignature + Clone,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     fn active_verification_key_from_signature(
         signature: &Self::Signature,
         period: Period,
     ) -> Self::VerificationKey {
         let t_half = D::total_periods();
-        let active_is_left = period < t_half;
-        let child_period = if active_is_left {
-            period
-        } else {
-            period - t_half
-        };
-
-        let vk_active = <D as CompactKesComponents>::active_verification_key_from_signature(
-            &signature.sigma,
-            child_period,
-        );
-        let vk_other = signature.vk_other.clone();
-
-        let (vk_left, vk_right) = if active_is_left {
-            (vk_active.clone(), vk_other)
+        let child = if period < t_half {
+            ChildPeriod::Left(period)
         } else {
-            (vk_other.clone(), vk_active.clone())
+            ChildPeriod::Right(period - t_half)
         };
-
-        let left_bytes = D::raw_serialize_verification_key_kes(&vk_left);
-        let right_bytes = D::raw_serialize_verification_key_kes(&vk_right);
-        H::hash_concat(&left_bytes, &right_bytes)
+        Self::rebuild_root(signature, child).0
     }
 }
 
@@ -414,13 +623,16 @@ where
 // 2. MLocked seed for right subtree (r1_seed)
 // 3. Verification key for left subtree (vk0)
 // 4. Verification key for right subtree (vk1)
+//
+// This is the `UnsoundKesAlgorithm` raw layout, `SIGNING_KEY_SIZE` bytes in
+// total. Secret segments go to and from mlocked memory without intermediate
+// copies; only the public verification keys pass through a `Vec`.
 impl<D, H> DirectSerialise for CompactSumSigningKey<D, H>
 where
     D: KesAlgorithm,
     D::SigningKey: DirectSerialise,
-    D::VerificationKey: DirectSerialise,
     D::Signature: OptimizedKesSignature,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     fn direct_serialise(
         &self,
@@ -434,14 +646,16 @@ where
             let slice = r1_seed.as_slice();
             push(slice)?;
         } else {
-            // If r1_seed is None, serialize zero bytes
+            // Consumed once the key has moved to the right subtree; the raw
+            // layout stores zeroes in its place.
             let zero_bytes = vec![0u8; D::SEED_SIZE];
             push(&zero_bytes)?;
         }
 
-        // Serialize verification keys
-        self.vk0.direct_serialise(push)?;
-        self.vk1.direct_serialise(push)?;
+        // Serialize verification keys through the raw encoding so that the
+        // layout composes for every tree depth.
+        push(&D::raw_serialize_verification_key_kes(&self.vk0))?;
+        push(&D::raw_serialize_verification_key_kes(&self.vk1))?;
 
         Ok(())
     }
@@ -451,9 +665,8 @@ impl<D, H> DirectDeserialise for CompactSumSigningKey<D, H>
 where
     D: KesAlgorithm,
     D::SigningKey: DirectDeserialise,
-    D::VerificationKey: DirectDeserialise,
     D::Signature: OptimizedKesSignature,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     fn direct_deserialise(
         pull: &mut dyn FnMut(&mut [u8]) -> DirectResult<()>,
@@ -462,27 +675,30 @@ where
         let sk = D::SigningKey::direct_deserialise(pull)?;
 
         // Deserialize r1_seed into MLocked memory
-        let mut r1_mlocked = MLockedBytes::new(D::SEED_SIZE).map_err(|_| {
-            crate::direct_serialise::SizeCheckError {
-                expected_size: D::SEED_SIZE,
-                actual_size: 0,
-            }
-        })?;
+        let mut r1_mlocked = MLockedBytes::new(D::SEED_SIZE)?;
         {
             let slice = r1_mlocked.as_mut_slice();
             pull(slice)?;
         }
 
         // Deserialize verification keys
-        let vk0 = D::VerificationKey::direct_deserialise(pull)?;
-        let vk1 = D::VerificationKey::direct_deserialise(pull)?;
+        let mut pull_vk = || {
+            let mut bytes = vec![0u8; D::VERIFICATION_KEY_SIZE];
+            pull(&mut bytes)?;
+            D::raw_deserialize_verification_key_kes(&bytes).ok_or(
+                DirectSerialiseError::InvalidValue {
+                    what: "KES verification key",
+                },
+            )
+        };
+        let vk0 = pull_vk()?;
+        let vk1 = pull_vk()?;
 
-        Ok(CompactSumSigningKey {
+        Ok(CompactSumSigningKey::from_parts(
             sk,
-            r1_seed: Some(r1_mlocked),
+            Some(r1_mlocked),
             vk0,
             vk1,
-            _phantom: PhantomData,
-        })
+        ))
     }
 }
@@ -0,0 +1,248 @@
+//! Conversions between CompactSum and Sum KES signatures.
+//!
+//! A `CompactSumKes` signature stores only the off-path verification key at
+//! each level and relies on the verifier to rebuild the on-path key from the
+//! embedded leaf key. A `SumKes` signature stores both child keys at every
+//! level. Given the period, each representation carries enough information to
+//! rebuild the other, so signatures can be re-encoded for consumers that only
+//! understand one of the two families.
+//!
+//! The compact and non-compact families are related level by level through the
+//! sealed [`CompactKesCounterpart`] trait, so converting between mismatched
+//! depths (e.g. `CompactSum3Kes` and `Sum2Kes`) does not compile.
+//!
+//! ```rust
+//! use cardano_crypto_class::kes::{
+//!     CompactSum2Kes, KesAlgorithm, Sum2Kes, compact_to_sum_signature, sum_to_compact_signature,
+//! };
+//!
+//! let seed = vec![7u8; CompactSum2Kes::SEED_SIZE];
+//! let sk = CompactSum2Kes::gen_key_kes_from_seed_bytes(&seed).unwrap();
+//! let vk = CompactSum2Kes::derive_verification_key(&sk).unwrap();
+//! let compact = CompactSum2Kes::sign_kes(&(), 0, b"block", &sk).unwrap();
+//!
+//! let sum = compact_to_sum_signature::<CompactSum2Kes>(&compact, &vk, 0).unwrap();
+//! Sum2Kes::verify_kes(&(), &vk, 0, b"block", &sum).unwrap();
+//!
+//! let back = sum_to_compact_signature::<CompactSum2Kes>(&sum, 0);
+//! assert_eq!(
+//!     CompactSum2Kes::raw_serialize_signature_kes(&back),
+//!     CompactSum2Kes::raw_serialize_signature_kes(&compact),
+//! );
+//! ```
+
+use thiserror::Error;
+
+use crate::dsign::{DsignMAlgorithm, UnsoundDsignMAlgorithm};
+use crate::hash::HashAlgorithm;
+use crate::kes::compact_single::{CompactSingleKes, CompactSingleSig, OptimizedKesSignature};
+use crate::kes::compact_sum::{CompactKesComponents, CompactSumKes, CompactSumSignature};
+use crate::kes::single::SingleKes;
+use crate::kes::sum::{SumKes, SumSignature};
+use crate::kes::{KesAlgorithm, Period};
+
+/// Error raised when a compact signature cannot be converted.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ConversionError {
+    #[error("period {period} out of range [0, {max_period})")]
+    PeriodOutOfRange { period: Period, max_period: Period },
+    #[error("signature does not reconstruct the supplied verification key")]
+    VerificationKeyMismatch,
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Relates a compact KES level to the non-compact level of the same depth.
+///
+/// Implemented for `CompactSingleKes<D>` (paired with `SingleKes<D>`) and for
+/// `CompactSumKes<D, H>` (paired with `SumKes<D::Sum, H>`). Both members of a
+/// pair share verification keys, contexts, and period counts.
+pub trait CompactKesCounterpart: CompactKesComponents + sealed::Sealed {
+    /// The non-compact algorithm with the same depth.
+    type Sum: KesAlgorithm<VerificationKey = Self::VerificationKey, Context = Self::Context>;
+
+    /// Expand a compact signature, rebuilding the on-path verification keys
+    /// from the embedded leaf key.
+    fn to_sum_signature(
+        signature: &Self::Signature,
+        period: Period,
+    ) -> <Self::Sum as KesAlgorithm>::Signature;
+
+    /// Compress a non-compact signature. `verification_key` is the key of
+    /// this subtree as recorded by the parent level.
+    fn to_compact_signature(
+        signature: &<Self::Sum as KesAlgorithm>::Signature,
+        verification_key: &Self::VerificationKey,
+        period: Period,
+    ) -> Self::Signature;
+}
+
+impl<D> sealed::Sealed for CompactSingleKes<D>
+where
+    D: DsignMAlgorithm + UnsoundDsignMAlgorithm,
+    D::VerificationKey: Clone,
+{
+}
+
+impl<D> CompactKesCounterpart for CompactSingleKes<D>
+where
+    D: DsignMAlgorithm + UnsoundDsignMAlgorithm,
+    D::VerificationKey: Clone,
+    D::Signature: Clone,
+{
+    type Sum = SingleKes<D>;
+
+    fn to_sum_signature(signature: &CompactSingleSig<D>, _period: Period) -> D::Signature {
+        signature.signature.clone()
+    }
+
+    fn to_compact_signature(
+        signature: &D::Signature,
+        verification_key: &D::VerificationKey,
+        _period: Period,
+    ) -> CompactSingleSig<D> {
+        CompactSingleSig {
+            signature: signature.clone(),
+            verification_key: verification_key.clone(),
+        }
+    }
+}
+
+impl<D, H> sealed::Sealed for CompactSumKes<D, H>
+where
+    D: CompactKesCounterpart,
+    D::VerificationKey: Clone,
+    D::Signature: OptimizedKesSignature + Clone,
+    H: HashAlgorithm,
+{
+}
+
+impl<D, H> CompactKesCounterpart for CompactSumKes<D, H>
+where
+    D: CompactKesCounterpart,
+    D::VerificationKey: Clone,
+    D::Signature: OptimizedKesSignature + Clone,
+    H: HashAlgorithm,
+{
+    type Sum = SumKes<D::Sum, H>;
+
+    fn to_sum_signature(
+        signature: &CompactSumSignature<D, H>,
+        period: Period,
+    ) -> SumSignature<D::Sum, H> {
+        let (active_is_left, child_period) = route::<D>(period);
+        let vk_active = D::active_verification_key_from_signature(&signature.sigma, child_period);
+        let sigma = D::to_sum_signature(&signature.sigma, child_period);
+        let vk_other = signature.vk_other.clone();
+
+        if active_is_left {
+            SumSignature::from_parts(sigma, vk_active, vk_other)
+        } else {
+            SumSignature::from_parts(sigma, vk_other, vk_active)
+        }
+    }
+
+    fn to_compact_signature(
+        signature: &SumSignature<D::Sum, H>,
+        _verification_key: &Vec<u8>,
+        period: Period,
+    ) -> CompactSumSignature<D, H> {
+        Self::from_sum_signature(signature, period)
+    }
+}
+
+/// Compact levels built from at least one sum layer.
+///
+/// Unlike a bare `SingleKes` signature, the non-compact signature of these
+/// levels records both child verification keys, so it can be compressed
+/// without any extra input.
+pub trait CompactSumCounterpart: CompactKesCounterpart {
+    /// Compress a non-compact signature of the same depth.
+    fn from_sum_signature(
+        signature: &<Self::Sum as KesAlgorithm>::Signature,
+        period: Period,
+    ) -> Self::Signature;
+}
+
+impl<D, H> CompactSumCounterpart for CompactSumKes<D, H>
+where
+    D: CompactKesCounterpart,
+    D::VerificationKey: Clone,
+    D::Signature: OptimizedKesSignature + Clone,
+    H: HashAlgorithm,
+{
+    fn from_sum_signature(
+        signature: &SumSignature<D::Sum, H>,
+        period: Period,
+    ) -> CompactSumSignature<D, H> {
+        let (active_is_left, child_period) = route::<D>(period);
+        let (vk_active, vk_other) = if active_is_left {
+            (&signature.vk0, &signature.vk1)
+        } else {
+            (&signature.vk1, &signature.vk0)
+        };
+        let sigma = D::to_compact_signature(&signature.sigma, vk_active, child_period);
+        CompactSumSignature::from_parts(sigma, vk_other.clone())
+    }
+}
+
+/// Whether `period` falls in the left subtree, and the period within it.
+fn route<D: KesAlgorithm>(period: Period) -> (bool, Period) {
+    let t_half = D::total_periods();
+    if period < t_half {
+        (true, period)
+    } else {
+        (false, period - t_half)
+    }
+}
+
+/// Convert a compact signature into the non-compact representation of the
+/// same depth.
+///
+/// The on-path verification keys are rebuilt from the signature exactly as
+/// `verify_kes` does and the resulting root is checked against `vk_root`, so a
+/// successful conversion of a valid signature always yields a signature that
+/// verifies under `C::Sum`.
+///
+/// # Errors
+///
+/// Returns [`ConversionError::PeriodOutOfRange`] if `period` is not below
+/// `C::total_periods()`, and [`ConversionError::VerificationKeyMismatch`] if
+/// the keys embedded in `signature` do not hash to `vk_root`.
+pub fn compact_to_sum_signature<C: CompactKesCounterpart>(
+    signature: &C::Signature,
+    vk_root: &C::VerificationKey,
+    period: Period,
+) -> Result<<C::Sum as KesAlgorithm>::Signature, ConversionError> {
+    let max_period = C::total_periods();
+    if period >= max_period {
+        return Err(ConversionError::PeriodOutOfRange { period, max_period });
+    }
+
+    let reconstructed = C::active_verification_key_from_signature(signature, period);
+    if C::raw_serialize_verification_key_kes(&reconstructed)
+        != C::raw_serialize_verification_key_kes(vk_root)
+    {
+        return Err(ConversionError::VerificationKeyMismatch);
+    }
+
+    Ok(C::to_sum_signature(signature, period))
+}
+
+/// Convert a non-compact signature into the compact representation of the
+/// same depth.
+///
+/// Sum signatures carry both child keys at every level, so no verification key
+/// is needed. Only [`CompactSumCounterpart`] levels are supported: a bare
+/// `SingleKes` signature does not contain the key a `CompactSingleKes`
+/// signature must embed. The period is not range checked; converting a
+/// signature under an out-of-range period yields a signature that fails
+/// verification just as the input does.
+pub fn sum_to_compact_signature<C: CompactSumCounterpart>(
+    signature: &<C::Sum as KesAlgorithm>::Signature,
+    period: Period,
+) -> C::Signature {
+    C::from_sum_signature(signature, period)
+}
@@ -0,0 +1,124 @@
+//! Dry-run KES evolution for operator tooling.
+//!
+//! Evolution is one-way, so answering "will this key still sign at period
+//! X?" by evolving the production key would destroy it. These helpers work on
+//! a deep copy taken through the [`UnsoundKesAlgorithm`] raw serialisation
+//! path, evolve the copy, and forget it afterwards; the original key is never
+//! touched.
+//!
+//! The module is only compiled with the `tooling` feature. Node builds should
+//! leave it disabled: a signing key copy, however short-lived, weakens the
+//! forward security guarantee until it is forgotten.
+//!
+//! ```rust
+//! use cardano_crypto_class::kes::dry_run::{can_sign_at_period, clone_for_dry_run};
+//! use cardano_crypto_class::kes::{KesAlgorithm, Sum2Kes};
+//!
+//! let seed = vec![3u8; Sum2Kes::SEED_SIZE];
+//! let sk = Sum2Kes::gen_key_kes_from_seed_bytes(&seed).unwrap();
+//!
+//! let copy = clone_for_dry_run::<Sum2Kes>(&sk).unwrap();
+//! assert!(can_sign_at_period::<Sum2Kes>(&(), copy, 0, 3).unwrap());
+//!
+//! let copy = clone_for_dry_run::<Sum2Kes>(&sk).unwrap();
+//! assert!(!can_sign_at_period::<Sum2Kes>(&(), copy, 0, 4).unwrap());
+//! ```
+
+use crate::kes::{KesAlgorithm, KesMError, Period, UnsoundKesAlgorithm, observer};
+use crate::mlocked_bytes::MLockedBytes;
+
+/// Deep copy a signing key for a dry run.
+///
+/// The raw serialisation is moved into mlocked memory and the intermediate
+/// buffer, which the `raw_serialize_signing_key_kes` implementations build
+/// without reallocating, is zeroed before the copy is rebuilt. No plaintext
+/// copy of the key outlives this call. The returned key is as sensitive as the original
+/// and must be forgotten (for example by [`can_sign_at_period`]) once the dry
+/// run is over. Intended for tests and operator tooling only.
+///
+/// # Errors
+///
+/// Returns an error if the key cannot be serialised, mlocked memory cannot be
+/// allocated, or the copy cannot be rebuilt.
+pub fn clone_for_dry_run<K: UnsoundKesAlgorithm>(
+    signing_key: &K::SigningKey,
+) -> Result<K::SigningKey, KesMError> {
+    let raw = K::raw_serialize_signing_key_kes(signing_key)?;
+    let mut locked = MLockedBytes::new(raw.len())?;
+    locked.as_mut_slice().copy_from_slice(&raw);
+    drop(raw);
+    K::raw_deserialize_signing_key_kes(locked.as_slice())
+}
+
+/// Evolve `signing_key` from `current` to `target` and report whether it can
+/// still sign at `target`.
+///
+/// The key is consumed and forgotten before returning, so pass a copy from
+/// [`clone_for_dry_run`]. Targets before `current` are unreachable and yield
+/// `false`, as do targets at or beyond `K::total_periods()`.
+///
+/// # Errors
+///
+/// Returns an error if an evolution step fails for a reason other than
+/// expiry.
+pub fn can_sign_at_period<K: KesAlgorithm>(
+    context: &K::Context,
+    signing_key: K::SigningKey,
+    current: Period,
+    target: Period,
+) -> Result<bool, KesMError> {
+    match evolve_to::<K>(context, signing_key, current, target)? {
+        Some(evolved) => {
+            K::forget_signing_key_kes(evolved);
+            Ok(true)
+        },
+        None => Ok(false),
+    }
+}
+
+/// Sign `message` at `target` with a dry-run copy of `signing_key`, which
+/// is currently at period `current`.
+///
+/// Returns `None` if the key cannot reach `target`. The copy is forgotten
+/// before returning and the original key is left untouched.
+///
+/// # Errors
+///
+/// Returns an error if copying, evolving, or signing fails.
+pub fn dry_run_sign<K: UnsoundKesAlgorithm>(
+    context: &K::Context,
+    signing_key: &K::SigningKey,
+    current: Period,
+    target: Period,
+    message: &[u8],
+) -> Result<Option<K::Signature>, KesMError> {
+    let copy = clone_for_dry_run::<K>(signing_key)?;
+    let Some(evolved) = evolve_to::<K>(context, copy, current, target)? else {
+        return Ok(None);
+    };
+    let signature = observer::unobserved(|| K::sign_kes(context, target, message, &evolved));
+    K::forget_signing_key_kes(evolved);
+    signature.map(Some)
+}
+
+fn evolve_to<K: KesAlgorithm>(
+    context: &K::Context,
+    signing_key: K::SigningKey,
+    current: Period,
+    target: Period,
+) -> Result<Option<K::SigningKey>, KesMError> {
+    if target < current || target >= K::total_periods() {
+        K::forget_signing_key_kes(signing_key);
+        return Ok(None);
+    }
+    observer::unobserved(|| {
+        let mut key = signing_key;
+        for period in current..target {
+            match K::update_kes(context, key, period)? {
+                Some(next) => key = next,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(key))
+    })
+}
@@ -0,0 +1,346 @@
+//! KES period arithmetic for operational certificates.
+//!
+//! An operational certificate names the KES period `c0` its hot key starts
+//! in, and the protocol parameter `maxKESEvolutions` bounds how many periods
+//! it stays valid. A node forging at slot `s` is in period
+//! `kp = s / slotsPerKESPeriod` and the ledger rejects the header with
+//! `KESBeforeStart` if `kp < c0` and `KESAfterEnd` if
+//! `kp >= c0 + maxKESEvolutions`. [`validate_ocert_period`] applies the same
+//! checks up front, so a misconfigured certificate is caught before the node
+//! tries to forge.
+//!
+//! Slots are plain `u64`s here; `cardano-slotting` (feature `kes`) wraps this
+//! for `SlotNo`.
+//!
+//! ```rust
+//! use cardano_crypto_class::kes::evolution::validate_ocert_period;
+//!
+//! // Mainnet: 129600 slots per KES period, 62 evolutions.
+//! let info = validate_ocert_period(129_600 * 400 + 5, 129_600, 390, 62).unwrap();
+//! assert_eq!(info.current_period, 400);
+//! assert_eq!(info.evolution, 10);
+//! assert_eq!(info.periods_remaining, 52);
+//! ```
+
+use thiserror::Error;
+
+use super::Period;
+#[cfg(any(feature = "workflows", feature = "signer-service"))]
+use super::{KesAlgorithm, KesError, KesMError};
+
+/// Where the current slot falls within a valid operational certificate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KesPeriodInfo {
+    /// Absolute KES period of the current slot.
+    pub current_period: Period,
+    /// Evolution the hot key must be at to sign now, `current_period - start`;
+    /// the period to pass to `sign_kes`.
+    pub evolution: Period,
+    /// Periods, including the current one, in which the certificate is
+    /// still valid.
+    pub periods_remaining: u64,
+    /// First slot at which the certificate has expired.
+    pub expires_at_slot: u64,
+}
+
+/// Why an operational certificate cannot be used at the current slot.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum OcertPeriodError {
+    /// The certificate starts in a later period (`KESBeforeStart`).
+    #[error(
+        "operational certificate starts at KES period {start_period}, current period is {current_period}"
+    )]
+    NotYetValid {
+        current_period: Period,
+        start_period: Period,
+    },
+    /// The certificate's last period has passed (`KESAfterEnd`).
+    #[error(
+        "operational certificate expired at slot {expired_at_slot} (KES period {current_period})"
+    )]
+    Expired {
+        expired_at_slot: u64,
+        current_period: Period,
+    },
+    /// The parameters cannot describe a certificate.
+    #[error("invalid KES period parameters: {reason}")]
+    InvalidParameters { reason: &'static str },
+}
+
+impl OcertPeriodError {
+    /// Stable identifier of this variant (see the crate-level *Error codes*
+    /// section).
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            OcertPeriodError::NotYetValid { .. } => "kes.ocert_not_yet_valid",
+            OcertPeriodError::Expired { .. } => "kes.ocert_expired",
+            OcertPeriodError::InvalidParameters { .. } => "kes.ocert_invalid_parameters",
+        }
+    }
+
+    /// Always `false`: retrying at the same slot gives the same answer.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for OcertPeriodError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        match self {
+            OcertPeriodError::NotYetValid {
+                current_period,
+                start_period,
+            } => {
+                map.serialize_entry("current_period", current_period)?;
+                map.serialize_entry("start_period", start_period)?;
+            },
+            OcertPeriodError::Expired {
+                expired_at_slot,
+                current_period,
+            } => {
+                map.serialize_entry("expired_at_slot", expired_at_slot)?;
+                map.serialize_entry("current_period", current_period)?;
+            },
+            OcertPeriodError::InvalidParameters { reason } => {
+                map.serialize_entry("reason", reason)?;
+            },
+        }
+        map.serialize_entry("message", &self.to_string())?;
+        map.end()
+    }
+}
+
+/// The absolute KES period containing `slot`, or `None` if
+/// `slots_per_kes_period` is zero.
+#[must_use]
+pub const fn kes_period_of_slot(slot: u64, slots_per_kes_period: u64) -> Option<Period> {
+    slot.checked_div(slots_per_kes_period)
+}
+
+/// Check that an operational certificate starting at `ocert_start_period`
+/// and valid for `total_periods` periods can sign at `current_slot`.
+///
+/// `total_periods` is the protocol's `maxKESEvolutions` (62 on mainnet),
+/// which may be smaller than the KES scheme's own `total_periods()`.
+///
+/// # Errors
+///
+/// Returns [`OcertPeriodError::NotYetValid`] before the start period,
+/// [`OcertPeriodError::Expired`] from period `start + total_periods` on, and
+/// [`OcertPeriodError::InvalidParameters`] if `slots_per_kes_period` or
+/// `total_periods` is zero or the end of validity overflows a `u64` slot.
+pub fn validate_ocert_period(
+    current_slot: u64,
+    slots_per_kes_period: u64,
+    ocert_start_period: Period,
+    total_periods: u64,
+) -> Result<KesPeriodInfo, OcertPeriodError> {
+    let current_period = kes_period_of_slot(current_slot, slots_per_kes_period).ok_or(
+        OcertPeriodError::InvalidParameters {
+            reason: "slots per KES period must be positive",
+        },
+    )?;
+    if total_periods == 0 {
+        return Err(OcertPeriodError::InvalidParameters {
+            reason: "total periods must be positive",
+        });
+    }
+    let end_period = ocert_start_period.checked_add(total_periods);
+    let expires_at_slot = end_period
+        .and_then(|end| end.checked_mul(slots_per_kes_period))
+        .ok_or(OcertPeriodError::InvalidParameters {
+            reason: "certificate end slot overflows u64",
+        })?;
+
+    if current_period < ocert_start_period {
+        return Err(OcertPeriodError::NotYetValid {
+            current_period,
+            start_period: ocert_start_period,
+        });
+    }
+    if current_slot >= expires_at_slot {
+        return Err(OcertPeriodError::Expired {
+            expired_at_slot: expires_at_slot,
+            current_period,
+        });
+    }
+    let evolution = current_period - ocert_start_period;
+    Ok(KesPeriodInfo {
+        current_period,
+        evolution,
+        periods_remaining: total_periods - evolution,
+        expires_at_slot,
+    })
+}
+
+/// Why [`evolve_key_to`] stopped.
+#[cfg(any(feature = "workflows", feature = "signer-service"))]
+pub(crate) enum EvolveError {
+    /// `requested` is before the key's `current` period.
+    Stale {
+        requested: Period,
+        current: Period,
+    },
+    Kes(KesMError),
+}
+
+/// Evolve `signing_key`, at `period`, forward to `target` one period at a
+/// time, for the owners of a long-lived hot key (`KesSessionManager` and the
+/// KES signing service).
+///
+/// A `target` before `period` is [`EvolveError::Stale`] and leaves the key
+/// alone. Once the key expires, or `update_kes` fails and consumes it,
+/// `signing_key` is `None` and this and every later call report
+/// [`KesError::KeyExpired`].
+#[cfg(any(feature = "workflows", feature = "signer-service"))]
+pub(crate) fn evolve_key_to<K: KesAlgorithm>(
+    context: &K::Context,
+    signing_key: &mut Option<K::SigningKey>,
+    period: &mut Period,
+    target: Period,
+) -> Result<(), EvolveError> {
+    let expired = || EvolveError::Kes(KesMError::Kes(KesError::KeyExpired));
+    if target < *period {
+        return Err(EvolveError::Stale {
+            requested: target,
+            current: *period,
+        });
+    }
+    while *period < target {
+        let key = signing_key.take().ok_or_else(expired)?;
+        match K::update_kes(context, key, *period).map_err(EvolveError::Kes)? {
+            Some(next) => {
+                *signing_key = Some(next);
+                *period += 1;
+            },
+            None => return Err(expired()),
+        }
+    }
+    if signing_key.is_none() {
+        return Err(expired());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAINNET_SLOTS_PER_KES_PERIOD: u64 = 129_600;
+    const MAINNET_MAX_KES_EVOLUTIONS: u64 = 62;
+
+    #[test]
+    fn first_slot_of_start_period_is_valid() {
+        let info = validate_ocert_period(1_000, 100, 10, 5).unwrap();
+        assert_eq!(
+            info,
+            KesPeriodInfo {
+                current_period: 10,
+                evolution: 0,
+                periods_remaining: 5,
+                expires_at_slot: 1_500,
+            }
+        );
+    }
+
+    #[test]
+    fn last_slot_before_start_is_not_yet_valid() {
+        assert_eq!(
+            validate_ocert_period(999, 100, 10, 5),
+            Err(OcertPeriodError::NotYetValid {
+                current_period: 9,
+                start_period: 10,
+            })
+        );
+    }
+
+    #[test]
+    fn last_valid_slot_and_first_expired_slot() {
+        let info = validate_ocert_period(1_499, 100, 10, 5).unwrap();
+        assert_eq!(info.current_period, 14);
+        assert_eq!(info.evolution, 4);
+        assert_eq!(info.periods_remaining, 1);
+
+        assert_eq!(
+            validate_ocert_period(1_500, 100, 10, 5),
+            Err(OcertPeriodError::Expired {
+                expired_at_slot: 1_500,
+                current_period: 15,
+            })
+        );
+    }
+
+    #[test]
+    fn mainnet_parameters() {
+        let start = 400;
+        let first = start * MAINNET_SLOTS_PER_KES_PERIOD;
+        let end = (start + MAINNET_MAX_KES_EVOLUTIONS) * MAINNET_SLOTS_PER_KES_PERIOD;
+
+        let info = validate_ocert_period(
+            first,
+            MAINNET_SLOTS_PER_KES_PERIOD,
+            start,
+            MAINNET_MAX_KES_EVOLUTIONS,
+        )
+        .unwrap();
+        assert_eq!((info.evolution, info.periods_remaining), (0, 62));
+
+        let info = validate_ocert_period(
+            end - 1,
+            MAINNET_SLOTS_PER_KES_PERIOD,
+            start,
+            MAINNET_MAX_KES_EVOLUTIONS,
+        )
+        .unwrap();
+        assert_eq!(info.current_period, 461);
+        assert_eq!((info.evolution, info.periods_remaining), (61, 1));
+        assert_eq!(info.expires_at_slot, 59_875_200);
+
+        assert_eq!(
+            validate_ocert_period(
+                end,
+                MAINNET_SLOTS_PER_KES_PERIOD,
+                start,
+                MAINNET_MAX_KES_EVOLUTIONS
+            ),
+            Err(OcertPeriodError::Expired {
+                expired_at_slot: 59_875_200,
+                current_period: 462,
+            })
+        );
+        assert!(matches!(
+            validate_ocert_period(
+                first - 1,
+                MAINNET_SLOTS_PER_KES_PERIOD,
+                start,
+                MAINNET_MAX_KES_EVOLUTIONS
+            ),
+            Err(OcertPeriodError::NotYetValid {
+                current_period: 399,
+                start_period: 400,
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_degenerate_parameters() {
+        for (slots_per_period, start, total) in [(0, 0, 62), (129_600, 0, 0), (2, u64::MAX, 1)] {
+            let err = validate_ocert_period(0, slots_per_period, start, total).unwrap_err();
+            assert_eq!(err.code(), "kes.ocert_invalid_parameters", "{err}");
+        }
+    }
+
+    #[test]
+    fn period_of_slot() {
+        assert_eq!(kes_period_of_slot(129_599, 129_600), Some(0));
+        assert_eq!(kes_period_of_slot(129_600, 129_600), Some(1));
+        assert_eq!(kes_period_of_slot(1, 0), None);
+    }
+}
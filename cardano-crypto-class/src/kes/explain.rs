@@ -0,0 +1,182 @@
+//! Structured reasons for KES verification failures.
+//!
+//! [`KesAlgorithm::verify_kes`] reports every rejected signature as
+//! [`KesError::VerificationFailed`](super::KesError::VerificationFailed),
+//! which is all a validator needs but says nothing about *why* a header was
+//! rejected. [`KesAlgorithm::verify_kes_explain`] gives the same verdict and,
+//! on failure, the step of the tree walk that rejected the signature. The
+//! diagnosis only runs after `verify_kes` has failed, so accepting a valid
+//! signature costs the same as calling `verify_kes`.
+//!
+//! ```rust
+//! use cardano_crypto_class::kes::{KesAlgorithm, KesVerifyStage, Sum2Kes};
+//!
+//! let sk = Sum2Kes::gen_key_kes_from_seed_bytes(&[7u8; 32]).unwrap();
+//! let vk = Sum2Kes::derive_verification_key(&sk).unwrap();
+//! let sig = Sum2Kes::sign_kes(&(), 0, b"header body", &sk).unwrap();
+//!
+//! Sum2Kes::verify_kes_explain(&(), &vk, 0, b"header body", &sig).unwrap();
+//! let failure = Sum2Kes::verify_kes_explain(&(), &vk, 2, b"header body", &sig).unwrap_err();
+//! assert_eq!(failure.stage, KesVerifyStage::PeriodRouting { signed_period: Some(0) });
+//! ```
+
+use std::fmt;
+
+use thiserror::Error;
+
+use super::{KesAlgorithm, KesError, Period};
+
+/// The verification step that rejected a KES signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KesVerifyStage {
+    /// The DSIGN signature at the leaf does not verify against the leaf key.
+    LeafSignature,
+    /// The verification keys carried by the signature hash to `actual_hex`
+    /// instead of the key expected at that node (the root key at depth 0).
+    RootHashMismatch {
+        expected_hex: String,
+        actual_hex: String,
+    },
+    /// The leaf key embedded in a compact signature does not belong to the
+    /// expected key, and the leaf signature does not verify under it either.
+    EmbeddedKeyMismatch,
+    /// The signature verifies for `signed_period` rather than the requested
+    /// period, or the requested period is outside the key's range and no
+    /// period verifies (`signed_period` is `None`).
+    PeriodRouting { signed_period: Option<Period> },
+}
+
+impl KesVerifyStage {
+    /// Stable identifier of this stage (see the crate-level *Error codes*
+    /// section).
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            KesVerifyStage::LeafSignature => "kes.verify.leaf_signature",
+            KesVerifyStage::RootHashMismatch { .. } => "kes.verify.root_hash_mismatch",
+            KesVerifyStage::EmbeddedKeyMismatch => "kes.verify.embedded_key_mismatch",
+            KesVerifyStage::PeriodRouting { .. } => "kes.verify.period_routing",
+        }
+    }
+}
+
+impl fmt::Display for KesVerifyStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KesVerifyStage::LeafSignature => write!(f, "leaf signature does not verify"),
+            KesVerifyStage::RootHashMismatch {
+                expected_hex,
+                actual_hex,
+            } => write!(
+                f,
+                "verification key hash mismatch, expected {expected_hex} but computed {actual_hex}"
+            ),
+            KesVerifyStage::EmbeddedKeyMismatch => {
+                write!(f, "embedded verification key does not match")
+            },
+            KesVerifyStage::PeriodRouting {
+                signed_period: Some(signed),
+            } => write!(f, "signature is for period {signed}"),
+            KesVerifyStage::PeriodRouting {
+                signed_period: None,
+            } => write!(f, "period is out of range"),
+        }
+    }
+}
+
+/// Why [`KesAlgorithm::verify_kes_explain`] rejected a signature.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("KES verification failed for period {period} at depth {depth}: {stage}")]
+pub struct KesVerifyFailure {
+    /// The step that rejected the signature.
+    pub stage: KesVerifyStage,
+    /// The period the signature was verified for.
+    pub period: Period,
+    /// Tree level of the rejecting node: 0 is the root, and the leaf of a
+    /// `SumNKes` signature is at depth `N`.
+    pub depth: u32,
+}
+
+impl KesVerifyFailure {
+    /// A failure detected at the node being verified (depth 0).
+    #[must_use]
+    pub const fn at_root(stage: KesVerifyStage, period: Period) -> Self {
+        Self {
+            stage,
+            period,
+            depth: 0,
+        }
+    }
+
+    /// Re-anchor a failure reported by a subtree one level further down,
+    /// for the parent's `period`.
+    #[must_use]
+    pub(crate) fn below_parent(self, period: Period) -> Self {
+        Self {
+            stage: self.stage,
+            period,
+            depth: self.depth + 1,
+        }
+    }
+
+    /// Stable identifier of the failing stage.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        self.stage.code()
+    }
+
+    /// Always `false`, as for [`KesError`].
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        false
+    }
+}
+
+impl From<KesVerifyFailure> for KesError {
+    fn from(_: KesVerifyFailure) -> Self {
+        KesError::VerificationFailed
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for KesVerifyFailure {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        map.serialize_entry("period", &self.period)?;
+        map.serialize_entry("depth", &self.depth)?;
+        match &self.stage {
+            KesVerifyStage::RootHashMismatch {
+                expected_hex,
+                actual_hex,
+            } => {
+                map.serialize_entry("expected_hex", expected_hex)?;
+                map.serialize_entry("actual_hex", actual_hex)?;
+            },
+            KesVerifyStage::PeriodRouting { signed_period } => {
+                map.serialize_entry("signed_period", signed_period)?;
+            },
+            KesVerifyStage::LeafSignature | KesVerifyStage::EmbeddedKeyMismatch => {},
+        }
+        map.serialize_entry("message", &self.to_string())?;
+        map.end()
+    }
+}
+
+/// The period, other than `period`, for which `signature` verifies, if any.
+///
+/// Tries every period of `A`; only used once verification has already failed.
+pub(crate) fn find_signed_period<A: KesAlgorithm>(
+    context: &A::Context,
+    verification_key: &A::VerificationKey,
+    period: Period,
+    message: &[u8],
+    signature: &A::Signature,
+) -> Option<Period> {
+    (0..A::total_periods()).find(|&candidate| {
+        candidate != period
+            && A::verify_kes(context, verification_key, candidate, message, signature).is_ok()
+    })
+}
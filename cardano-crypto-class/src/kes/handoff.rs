@@ -0,0 +1,64 @@
+//! Handing KES signing keys to another process through sealed memfds.
+//!
+//! A block producer that splits key management from forging can pass the
+//! current signing key over a Unix socket with `sendmsg(SCM_RIGHTS)` instead
+//! of writing it to disk. [`seal_signing_key_to_memfd`] serialises the key
+//! through [`DirectSerialise`] into mlocked scratch memory and copies it into
+//! a memfd sealed against writes and resizing; [`signing_key_from_sealed_memfd`]
+//! checks the seals and size, copies the bytes back into mlocked memory and
+//! closes the descriptor.
+//!
+//! # Security notes
+//!
+//! - The memfd pages are ordinary page-cache memory: they are not mlocked and
+//!   can be swapped while the descriptor is in flight. Restore the key as soon
+//!   as the descriptor arrives and drop the sender's descriptor once sent.
+//! - The memfd is created with `MFD_CLOEXEC`, so it does not leak into child
+//!   processes, but anyone the descriptor is passed to can read the key.
+//! - The sender keeps its own key; forget it with
+//!   [`KesAlgorithm::forget_signing_key_kes`] if ownership is being handed over,
+//!   otherwise both processes hold a key able to sign the current period.
+//!
+//! The module is only compiled on Linux with the `memfd-handoff` feature.
+
+use std::os::fd::OwnedFd;
+
+use crate::direct_serialise::{
+    DirectDeserialise, DirectSerialise, direct_deserialise_buf_checked,
+    direct_serialise_buf_checked,
+};
+use crate::kes::{KesAlgorithm, KesMError};
+use crate::mlocked_bytes::MLockedBytes;
+
+/// Seal `signing_key` into a memfd ready to be sent with `SCM_RIGHTS`.
+///
+/// # Errors
+///
+/// Returns an error if mlocked scratch memory cannot be allocated, the key
+/// does not serialise to exactly `K::SIGNING_KEY_SIZE` bytes, or the memfd
+/// cannot be created or sealed.
+pub fn seal_signing_key_to_memfd<K>(signing_key: &K::SigningKey) -> Result<OwnedFd, KesMError>
+where
+    K: KesAlgorithm,
+    K::SigningKey: DirectSerialise,
+{
+    let mut scratch = MLockedBytes::new_zeroed(K::SIGNING_KEY_SIZE)?;
+    direct_serialise_buf_checked(scratch.as_mut_slice(), signing_key)?;
+    Ok(scratch.seal_to_memfd()?)
+}
+
+/// Restore a signing key from a memfd produced by
+/// [`seal_signing_key_to_memfd`]. The descriptor is closed on return.
+///
+/// # Errors
+///
+/// Returns an error if the memfd is not sealed, does not hold exactly
+/// `K::SIGNING_KEY_SIZE` bytes, cannot be read, or the key cannot be rebuilt.
+pub fn signing_key_from_sealed_memfd<K>(fd: OwnedFd) -> Result<K::SigningKey, KesMError>
+where
+    K: KesAlgorithm,
+    K::SigningKey: DirectDeserialise,
+{
+    let scratch = MLockedBytes::from_sealed_memfd(fd, K::SIGNING_KEY_SIZE)?;
+    Ok(direct_deserialise_buf_checked(scratch.as_slice())?)
+}
@@ -1,8 +1,19 @@
+use blake2::digest::consts::{U28, U32, U64};
+use blake2::digest::core_api::{CoreWrapper, VariableOutputCore};
+use blake2::digest::{Output, Update};
+use blake2::{Blake2b, Blake2bVarCore};
+
+use crate::hash::{HashAlgorithm, HashKeyTooLong, KeyedHashAlgorithm, impl_digest_hash_algorithm};
+
 /// Trait for hash algorithms used in KES schemes.
 ///
-/// This trait provides a simple interface for hash algorithms used in
-/// Key Evolving Signature (KES) constructions, particularly for the
-/// binary sum composition where verification keys are hashed.
+/// This is the legacy interface that predates [`HashAlgorithm`]: an
+/// implementor only supplies the two constants and [`KesHashAlgorithm::hash`].
+/// Every implementor is also a `HashAlgorithm` through a blanket impl, so it
+/// still works as the hash parameter of the sum compositions. The Blake2b
+/// types implement this trait directly, keeping code that names it
+/// compiling unchanged.
+#[deprecated(note = "implement and bound on `cardano_crypto_class::hash::HashAlgorithm` instead")]
 pub trait KesHashAlgorithm: Clone + Send + Sync + 'static {
     /// The size of the hash output in bytes.
     const OUTPUT_SIZE: usize;
@@ -23,24 +34,32 @@ pub trait KesHashAlgorithm: Clone + Send + Sync + 'static {
         Self::hash(&combined)
     }
 
-    /// Expand a seed into two seeds using the hash algorithm.
-    /// This is used for seed expansion in Sum compositions.
-    /// Uses prefixes 1 and 2 to match Haskell cardano-base implementation:
-    /// - r0 = hash(1 || seed)
-    /// - r1 = hash(2 || seed)
+    /// Expand a seed into two seeds using the hash algorithm, as
+    /// [`HashAlgorithm::expand_seed`] does.
     #[must_use]
     fn expand_seed(seed: &[u8]) -> (Vec<u8>, Vec<u8>) {
-        // Hash with different prefixes to get two independent seeds
-        // Using 1 and 2 to match Haskell: BS.cons 1 and BS.cons 2
-        let mut seed0_input = vec![1u8];
-        seed0_input.extend_from_slice(seed);
-        let seed0 = Self::hash(&seed0_input);
+        (
+            Self::hash_concat(&[1u8], seed),
+            Self::hash_concat(&[2u8], seed),
+        )
+    }
+}
+
+#[allow(deprecated)]
+impl<T: KesHashAlgorithm> HashAlgorithm for T {
+    const OUTPUT_SIZE: usize = <T as KesHashAlgorithm>::OUTPUT_SIZE;
+    const ALGORITHM_NAME: &'static str = <T as KesHashAlgorithm>::ALGORITHM_NAME;
+
+    fn hash(data: &[u8]) -> Vec<u8> {
+        <T as KesHashAlgorithm>::hash(data)
+    }
 
-        let mut seed1_input = vec![2u8];
-        seed1_input.extend_from_slice(seed);
-        let seed1 = Self::hash(&seed1_input);
+    fn hash_concat(data1: &[u8], data2: &[u8]) -> Vec<u8> {
+        <T as KesHashAlgorithm>::hash_concat(data1, data2)
+    }
 
-        (seed0, seed1)
+    fn expand_seed(seed: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        <T as KesHashAlgorithm>::expand_seed(seed)
     }
 }
 
@@ -50,60 +69,96 @@ pub trait KesHashAlgorithm: Clone + Send + Sync + 'static {
 #[derive(Clone, Debug)]
 pub struct Blake2b224;
 
-impl KesHashAlgorithm for Blake2b224 {
-    const OUTPUT_SIZE: usize = 28;
-    const ALGORITHM_NAME: &'static str = "blake2b_224";
-
-    fn hash(data: &[u8]) -> Vec<u8> {
-        use blake2::digest::consts::U28;
-        use blake2::{Blake2b, Digest};
-
-        let mut hasher = Blake2b::<U28>::new();
-        hasher.update(data);
-        hasher.finalize().to_vec()
-    }
-}
+impl_digest_hash_algorithm!(
+    Blake2b224,
+    Blake2b<U28>,
+    28,
+    "blake2b_224",
+    KesHashAlgorithm
+);
 
 /// Blake2b-256 hash algorithm (32-byte output).
 /// This is the hash algorithm used in Haskell's cardano-base for Sum types.
 #[derive(Clone, Debug)]
 pub struct Blake2b256;
 
-impl KesHashAlgorithm for Blake2b256 {
-    const OUTPUT_SIZE: usize = 32;
-    const ALGORITHM_NAME: &'static str = "blake2b_256";
-
-    fn hash(data: &[u8]) -> Vec<u8> {
-        use blake2::digest::consts::U32;
-        use blake2::{Blake2b, Digest};
-
-        let mut hasher = Blake2b::<U32>::new();
-        hasher.update(data);
-        hasher.finalize().to_vec()
-    }
-}
+impl_digest_hash_algorithm!(
+    Blake2b256,
+    Blake2b<U32>,
+    32,
+    "blake2b_256",
+    KesHashAlgorithm
+);
 
 /// Blake2b-512 hash algorithm (64-byte output).
 /// This is kept for compatibility with existing code that may use it.
 #[derive(Clone, Debug)]
 pub struct Blake2b512;
 
-impl KesHashAlgorithm for Blake2b512 {
-    const OUTPUT_SIZE: usize = 64;
-    const ALGORITHM_NAME: &'static str = "blake2b_512";
+impl_digest_hash_algorithm!(
+    Blake2b512,
+    Blake2b<U64>,
+    64,
+    "blake2b_512",
+    KesHashAlgorithm
+);
 
-    fn hash(data: &[u8]) -> Vec<u8> {
-        use blake2::{Blake2b512 as Blake2b512Hasher, Digest};
+/// Blake2b block size; a key is zero-padded to one full block.
+const BLAKE2B_BLOCK_SIZE: usize = 128;
 
-        let mut hasher = Blake2b512Hasher::new();
-        hasher.update(data);
-        hasher.finalize().to_vec()
+/// Blake2b with `key` and `personal` written into the parameter block.
+///
+/// `blake2`'s MAC wrapper always absorbs a key block, even for an empty key,
+/// so the core is driven directly: the key block is only fed when a key is
+/// present, keeping the empty-key case identical to the plain hash.
+fn blake2b_with_params(output_size: usize, key: &[u8], personal: &[u8], data: &[u8]) -> Vec<u8> {
+    let core = Blake2bVarCore::new_with_params(&[], personal, key.len(), output_size);
+    let mut hasher = CoreWrapper::from_core(core);
+    if !key.is_empty() {
+        let mut block = [0u8; BLAKE2B_BLOCK_SIZE];
+        block[..key.len()].copy_from_slice(key);
+        hasher.update(&block);
     }
+    hasher.update(data);
+
+    let (mut core, mut buffer) = hasher.decompose();
+    let mut out = Output::<Blake2bVarCore>::default();
+    core.finalize_variable_core(&mut buffer, &mut out);
+    out[..output_size].to_vec()
 }
 
+macro_rules! impl_blake2b_keyed {
+    ($ty:ty, $size:expr) => {
+        impl KeyedHashAlgorithm for $ty {
+            const MAX_KEY_SIZE: usize = 64;
+
+            type Personal = [u8; 16];
+
+            fn hash_keyed(key: &[u8], data: &[u8]) -> Result<Vec<u8>, HashKeyTooLong> {
+                if key.len() > Self::MAX_KEY_SIZE {
+                    return Err(HashKeyTooLong {
+                        max: Self::MAX_KEY_SIZE,
+                        actual: key.len(),
+                    });
+                }
+                Ok(blake2b_with_params($size, key, &[], data))
+            }
+
+            fn hash_personalized(personal: &Self::Personal, data: &[u8]) -> Vec<u8> {
+                blake2b_with_params($size, &[], personal, data)
+            }
+        }
+    };
+}
+
+impl_blake2b_keyed!(Blake2b224, 28);
+impl_blake2b_keyed!(Blake2b256, 32);
+impl_blake2b_keyed!(Blake2b512, 64);
+
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use super::{Blake2b224, Blake2b256, Blake2b512};
+    use crate::hash::HashAlgorithm;
 
     #[test]
     fn test_blake2b224_output_size() {
@@ -0,0 +1,127 @@
+//! KES verification keys with a cached hash.
+//!
+//! Header validation compares the hash of the operational certificate's KES
+//! verification key with an expected digest on every block. The key is the
+//! same for the certificate's whole lifetime, so [`HashedVerKeyKes`] hashes it
+//! once, on first use, and hands out the stored digest afterwards — the
+//! counterpart of building a Haskell `Hash h (VerKeyKES v)` once per
+//! certificate instead of calling `hashVerKeyKES` per header.
+//!
+//! ```rust
+//! use cardano_crypto_class::kes::{Blake2b224, HashedVerKeyKes, KesAlgorithm, Sum6Kes};
+//!
+//! let sk = Sum6Kes::gen_key_kes_from_seed_bytes(&[3u8; 32]).unwrap();
+//! let vk = Sum6Kes::derive_verification_key(&sk).unwrap();
+//! let expected = Sum6Kes::hash_verification_key_kes::<Blake2b224>(&vk);
+//!
+//! let hot_key = HashedVerKeyKes::<Blake2b224, Sum6Kes>::new(vk);
+//! assert_eq!(hot_key.hash(), expected.as_slice());
+//! assert!(hot_key.hash_matches(&expected));
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+
+use super::KesAlgorithm;
+use crate::hash::HashAlgorithm;
+
+/// A `K` verification key together with its `H` hash, computed on first
+/// access and cached.
+///
+/// The digest lives in a [`OnceLock`], so the key can be shared between
+/// threads: concurrent first calls to [`hash`](Self::hash) compute the digest
+/// once and every caller sees the same bytes. Cloning keeps an already
+/// computed digest.
+pub struct HashedVerKeyKes<H, K: KesAlgorithm> {
+    verification_key: K::VerificationKey,
+    hash: OnceLock<Vec<u8>>,
+    _hash_algorithm: PhantomData<fn() -> H>,
+}
+
+impl<H: HashAlgorithm, K: KesAlgorithm> HashedVerKeyKes<H, K> {
+    /// Wrap a verification key. Nothing is hashed until [`hash`](Self::hash)
+    /// is first called.
+    #[must_use]
+    pub const fn new(verification_key: K::VerificationKey) -> Self {
+        Self {
+            verification_key,
+            hash: OnceLock::new(),
+            _hash_algorithm: PhantomData,
+        }
+    }
+
+    /// The wrapped verification key.
+    #[must_use]
+    pub const fn verification_key(&self) -> &K::VerificationKey {
+        &self.verification_key
+    }
+
+    /// The `H` hash of the serialised key, equal to
+    /// [`KesAlgorithm::hash_verification_key_kes`]. Only the first call
+    /// serialises and hashes the key.
+    #[must_use]
+    pub fn hash(&self) -> &[u8] {
+        self.hash
+            .get_or_init(|| K::hash_verification_key_kes::<H>(&self.verification_key))
+    }
+
+    /// Whether the cached hash equals `expected`, e.g. the key hash an
+    /// operational certificate is checked against.
+    #[must_use]
+    pub fn hash_matches(&self, expected: &[u8]) -> bool {
+        self.hash() == expected
+    }
+
+    /// Unwrap the verification key, dropping the cached hash.
+    #[must_use]
+    pub fn into_verification_key(self) -> K::VerificationKey {
+        self.verification_key
+    }
+}
+
+impl<H, K> Clone for HashedVerKeyKes<H, K>
+where
+    K: KesAlgorithm,
+    K::VerificationKey: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            verification_key: self.verification_key.clone(),
+            hash: self.hash.clone(),
+            _hash_algorithm: PhantomData,
+        }
+    }
+}
+
+/// Keys are compared by value; whether either digest is cached yet does not
+/// matter.
+impl<H, K> PartialEq for HashedVerKeyKes<H, K>
+where
+    K: KesAlgorithm,
+    K::VerificationKey: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.verification_key == other.verification_key
+    }
+}
+
+impl<H, K> Eq for HashedVerKeyKes<H, K>
+where
+    K: KesAlgorithm,
+    K::VerificationKey: Eq,
+{
+}
+
+impl<H, K> fmt::Debug for HashedVerKeyKes<H, K>
+where
+    K: KesAlgorithm,
+    K::VerificationKey: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HashedVerKeyKes")
+            .field("verification_key", &self.verification_key)
+            .field("hash", &self.hash.get())
+            .finish()
+    }
+}
@@ -0,0 +1,127 @@
+//! Borrowed KES verification keys.
+//!
+//! Block headers carry the KES verification key of the operational
+//! certificate. Decoding it into an owned [`KesAlgorithm::VerificationKey`]
+//! copies it out of the header buffer, which for `SumKes` and `CompactSumKes`
+//! means a fresh heap allocation per header. [`KesVerificationKeyRef`] borrows
+//! the bytes instead, and [`KesAlgorithm::verify_kes_ref`] verifies against
+//! them directly.
+//!
+//! ```rust
+//! use cardano_crypto_class::kes::{KesAlgorithm, KesVerificationKeyRef, Sum2Kes};
+//!
+//! let sk = Sum2Kes::gen_key_kes_from_seed_bytes(&[7u8; 32]).unwrap();
+//! let vk = Sum2Kes::derive_verification_key(&sk).unwrap();
+//! let sig = Sum2Kes::sign_kes(&(), 0, b"header body", &sk).unwrap();
+//!
+//! // `header` stands in for a decoded block header buffer.
+//! let header = Sum2Kes::raw_serialize_verification_key_kes(&vk);
+//! let vk_ref = KesVerificationKeyRef::<Sum2Kes>::from_bytes(&header).unwrap();
+//! Sum2Kes::verify_kes_ref(&(), vk_ref, 0, b"header body", &sig).unwrap();
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use super::KesAlgorithm;
+
+/// Borrowed view of the serialised verification key of KES algorithm `K`.
+///
+/// For the hash-based `SumKes` and `CompactSumKes` keys this is a 32-byte
+/// slice of the input; verifying with [`KesAlgorithm::verify_kes_ref`]
+/// compares against it without constructing the owned key.
+pub struct KesVerificationKeyRef<'a, K: ?Sized> {
+    bytes: &'a [u8],
+    _algorithm: PhantomData<fn() -> K>,
+}
+
+impl<'a, K: KesAlgorithm> KesVerificationKeyRef<'a, K> {
+    /// Borrow `bytes` as a `K` verification key. Returns `None` unless
+    /// `bytes` is exactly [`KesAlgorithm::VERIFICATION_KEY_SIZE`] long.
+    #[must_use]
+    pub fn from_bytes(bytes: &'a [u8]) -> Option<Self> {
+        (bytes.len() == K::VERIFICATION_KEY_SIZE).then_some(Self {
+            bytes,
+            _algorithm: PhantomData,
+        })
+    }
+
+    /// The borrowed key bytes.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Decode the owned verification key, as
+    /// [`KesAlgorithm::raw_deserialize_verification_key_kes`] would.
+    #[must_use]
+    pub fn to_verification_key(&self) -> Option<K::VerificationKey> {
+        K::raw_deserialize_verification_key_kes(self.bytes)
+    }
+}
+
+impl<K: ?Sized> Clone for KesVerificationKeyRef<'_, K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: ?Sized> Copy for KesVerificationKeyRef<'_, K> {}
+
+impl<K: ?Sized> PartialEq for KesVerificationKeyRef<'_, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl<K: ?Sized> Eq for KesVerificationKeyRef<'_, K> {}
+
+impl<K: ?Sized> fmt::Debug for KesVerificationKeyRef<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KesVerificationKeyRef({})", hex::encode(self.bytes))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K: ?Sized> serde::Serialize for KesVerificationKeyRef<'_, K> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(self.bytes)
+    }
+}
+
+/// Borrows from the input, so it needs a deserializer that hands out
+/// borrowed bytes, such as [`cardano_binary::decode_full_borrowed`].
+#[cfg(feature = "serde")]
+impl<'de: 'a, 'a, K: KesAlgorithm> serde::Deserialize<'de> for KesVerificationKeyRef<'a, K> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct BorrowedBytesVisitor<K>(PhantomData<fn() -> K>);
+
+        impl<'de, K: KesAlgorithm> serde::de::Visitor<'de> for BorrowedBytesVisitor<K> {
+            type Value = KesVerificationKeyRef<'de, K>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    formatter,
+                    "a borrowed {}-byte KES verification key",
+                    K::VERIFICATION_KEY_SIZE
+                )
+            }
+
+            fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                KesVerificationKeyRef::from_bytes(v)
+                    .ok_or_else(|| E::invalid_length(v.len(), &self))
+            }
+        }
+
+        deserializer.deserialize_bytes(BorrowedBytesVisitor(PhantomData))
+    }
+}
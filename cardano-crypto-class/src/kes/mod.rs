@@ -12,6 +12,14 @@
 //! | `Cardano.Crypto.KES.Sum` | `kes::sum::{Sum0Kes..Sum7Kes}` |
 //! | `Cardano.Crypto.KES.CompactSum` | `kes::compact_sum::{CompactSum0Kes..CompactSum7Kes}` |
 //! | `hashVerKeyKES` (Haskell method) | `KesAlgorithm::hash_verification_key_kes` |
+//! | — | `kes::archive` (verify historical signatures across operational certificates) |
+//! | — | `kes::conversion` (Sum ⇄ CompactSum signature re-encoding) |
+//! | — | `kes::evolution` (operational certificate period checks, `validate_ocert_period`) |
+//! | — | `kes::explain` (structured failure reasons, `KesAlgorithm::verify_kes_explain`) |
+//! | — | `kes::dry_run` (evolve a copy of a signing key; `tooling` feature) |
+//! | — | `kes::handoff` (sealed memfd key handoff; Linux `memfd-handoff` feature) |
+//! | — | `kes::key_ref` (borrowed verification keys, `KesAlgorithm::verify_kes_ref`) |
+//! | — | `kes::observer` (key evolution and expiry events, `set_kes_observer`) |
 //!
 //! # Forward security model
 //!
@@ -27,9 +35,11 @@
 //!
 //! The `update_kes` method returns `Ok(Some(new_key))` for a successful
 //! transition and `Ok(None)` when the key has reached `total_periods()` and has
-//! expired. Attempting to sign outside `[0, total_periods())` yields
-//! `KesError::PeriodOutOfRange`, while using an evolved key beyond its final
-//! period yields `KesError::KeyExpired`.
+//! expired. Signing, verifying or updating at a period outside
+//! `[0, total_periods())` (see `period_range`) yields
+//! `KesError::PeriodOutOfRange`, checked at the root before any period
+//! arithmetic, while using an evolved key beyond its final period yields
+//! `KesError::KeyExpired`.
 //!
 //! # Period evolution guide
 //!
@@ -54,9 +64,21 @@
 //! # Unsound operations
 //!
 //! The `UnsoundKesAlgorithm` trait exposes raw signing key (de)serialization
-//! strictly for testing / vector generation. Production code should never
+//! strictly for testing / vector generation. Its implementations are only
+//! compiled with the `tooling` feature. Production code should never
 //! persist signing keys in raw form outside controlled secure memory contexts.
 //!
+//! Signing keys of every level of `SingleKes`, `CompactSingleKes`, `SumKes`,
+//! and `CompactSumKes` also implement
+//! [`DirectSerialise`](crate::direct_serialise::DirectSerialise) and
+//! [`DirectDeserialise`](crate::direct_serialise::DirectDeserialise), as in
+//! Haskell, where node snapshots and key handoff use them. These are not
+//! gated by `tooling`: they write exactly the raw layout above
+//! (`SIGNING_KEY_SIZE` bytes), but each secret segment is handed to the
+//! caller's callback straight from mlocked memory and read back straight into
+//! a fresh mlocked buffer, so no unlocked copy of the key is ever made by this
+//! crate. Where the bytes go from the callback is the caller's responsibility.
+//!
 //! # Metrics
 //!
 //! When compiled with the crate feature `kes-metrics`, lightweight relaxed
@@ -64,6 +86,9 @@
 //! keys, signatures, signature bytes, and update operations to aid benchmarking
 //! and regression analysis. They are zero-cost when the feature is disabled.
 //!
+//! For operational monitoring, [`set_kes_observer`] installs a callback told
+//! about each key evolution and expiry (see [`observer`]).
+//!
 //! # Example
 //!
 //! ```rust
@@ -80,23 +105,41 @@
 //! ```
 use std::fmt;
 use std::marker::PhantomData;
+use std::ops::Range;
 
 use thiserror::Error;
+use zeroize::Zeroizing;
 
+use crate::direct_serialise::{DirectSerialiseError, SizeCheckError};
 use crate::mlocked_bytes::MLockedError;
-use crate::seed::{Seed, get_bytes_from_seed_t};
+use crate::seed::{KEY_DERIVATION_LABEL_PREFIX, Seed, expand_seed_labelled};
 use crate::util::SignableRepresentation;
 
+pub mod archive;
 pub mod compact_single;
 pub mod compact_sum;
+pub mod conversion;
+#[cfg(feature = "tooling")]
+pub mod dry_run;
+pub mod evolution;
+pub mod explain;
+#[cfg(all(target_os = "linux", feature = "memfd-handoff"))]
+pub mod handoff;
 pub mod hash;
+pub mod hashed_vk;
+pub mod key_ref;
 pub mod metrics;
+pub mod observer;
+#[cfg(feature = "serde")]
+pub mod serde_period;
 pub mod single;
 pub mod sum;
 pub mod verify_hash;
 
 // Re-export hash algorithms for convenience
-pub use hash::{Blake2b224, Blake2b256, Blake2b512, KesHashAlgorithm};
+#[allow(deprecated)]
+pub use hash::KesHashAlgorithm;
+pub use hash::{Blake2b224, Blake2b256, Blake2b512};
 
 // Re-export SingleKes types
 pub use single::SingleKes;
@@ -109,8 +152,29 @@ pub use sum::{Sum0Kes, Sum1Kes, Sum2Kes, Sum3Kes, Sum4Kes, Sum5Kes, Sum6Kes, Sum
 
 // Re-export CompactSum type aliases (using Blake2b256)
 pub use compact_sum::{
-    CompactSum0Kes, CompactSum1Kes, CompactSum2Kes, CompactSum3Kes, CompactSum4Kes, CompactSum5Kes,
-    CompactSum6Kes, CompactSum7Kes,
+    CompactKesComponents, CompactSum0Kes, CompactSum1Kes, CompactSum2Kes, CompactSum3Kes,
+    CompactSum4Kes, CompactSum5Kes, CompactSum6Kes, CompactSum7Kes,
+};
+
+// Re-export the operational certificate period checks
+pub use evolution::{KesPeriodInfo, OcertPeriodError, validate_ocert_period};
+
+// Re-export the key evolution observer hooks
+pub use observer::{KesEventObserver, clear_kes_observer, set_kes_observer};
+
+// Re-export the structured verification failure reasons
+pub use explain::{KesVerifyFailure, KesVerifyStage};
+
+// Re-export the borrowed verification key view
+pub use key_ref::KesVerificationKeyRef;
+
+// Re-export the verification key with a cached hash
+pub use hashed_vk::HashedVerKeyKes;
+
+// Re-export signature conversions between the Sum and CompactSum families
+pub use conversion::{
+    CompactKesCounterpart, CompactSumCounterpart, ConversionError, compact_to_sum_signature,
+    sum_to_compact_signature,
 };
 
 /// The KES period. Periods are enumerated from zero.
@@ -144,6 +208,54 @@ impl KesError {
             actual,
         }
     }
+
+    /// Stable identifier of this variant (see the crate-level *Error codes*
+    /// section).
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            KesError::VerificationFailed => "kes.verification_failed",
+            KesError::WrongLength { .. } => "kes.wrong_length",
+            KesError::Message(_) => "kes.message",
+            KesError::KeyExpired => "kes.key_expired",
+            KesError::PeriodOutOfRange { .. } => "kes.period_out_of_range",
+        }
+    }
+
+    /// Always `false`: KES failures are determined by the key, period and
+    /// input bytes, so retrying cannot change the outcome.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for KesError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        match self {
+            KesError::WrongLength {
+                context,
+                expected,
+                actual,
+            } => {
+                map.serialize_entry("context", context)?;
+                map.serialize_entry("expected", expected)?;
+                map.serialize_entry("actual", actual)?;
+            },
+            KesError::PeriodOutOfRange { period, max_period } => {
+                map.serialize_entry("period", period)?;
+                map.serialize_entry("max_period", max_period)?;
+            },
+            KesError::VerificationFailed | KesError::Message(_) | KesError::KeyExpired => {},
+        }
+        map.serialize_entry("message", &self.to_string())?;
+        map.end()
+    }
 }
 
 /// Error raised by mlocked KES operations.
@@ -155,6 +267,61 @@ pub enum KesMError {
     Mlocked(#[from] MLockedError),
     #[error("DSIGN error: {0}")]
     Dsign(String),
+    #[error(transparent)]
+    DirectSerialise(#[from] DirectSerialiseError),
+}
+
+impl From<SizeCheckError> for KesMError {
+    fn from(sizes: SizeCheckError) -> Self {
+        KesMError::DirectSerialise(sizes.into())
+    }
+}
+
+impl KesMError {
+    /// Stable identifier of this error. Wrapped KES, mlocked and direct
+    /// serialisation errors report the code of the inner error.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            KesMError::Kes(err) => err.code(),
+            KesMError::Mlocked(err) => err.code(),
+            KesMError::Dsign(_) => "kes.dsign",
+            KesMError::DirectSerialise(err) => err.code(),
+        }
+    }
+
+    /// Whether retrying could succeed; only mlocked allocation failures are
+    /// transient.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        match self {
+            KesMError::Kes(err) => err.is_transient(),
+            KesMError::Mlocked(err) => err.is_transient(),
+            KesMError::Dsign(_) => false,
+            KesMError::DirectSerialise(err) => err.is_transient(),
+        }
+    }
+}
+
+/// Wrapped errors serialise as the inner error, so the `code` always names
+/// the underlying failure.
+#[cfg(feature = "serde")]
+impl serde::Serialize for KesMError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        match self {
+            KesMError::Kes(err) => err.serialize(serializer),
+            KesMError::Mlocked(err) => err.serialize(serializer),
+            KesMError::DirectSerialise(err) => err.serialize(serializer),
+            KesMError::Dsign(_) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("code", self.code())?;
+                map.serialize_entry("message", &self.to_string())?;
+                map.end()
+            },
+        }
+    }
 }
 
 /// Trait capturing the common KES (Key Evolving Signature) interface.
@@ -186,6 +353,21 @@ pub trait KesAlgorithm {
     /// Total number of periods this KES scheme supports.
     fn total_periods() -> Period;
 
+    /// The periods a key of this scheme can sign for, `0..total_periods()`.
+    ///
+    /// `sign_kes`, `verify_kes` and `update_kes` reject any period outside
+    /// it with [`KesError::PeriodOutOfRange`].
+    #[must_use]
+    fn period_range() -> Range<Period> {
+        0..Self::total_periods()
+    }
+
+    /// Every period of [`period_range`](Self::period_range), in order.
+    #[must_use]
+    fn periods() -> impl DoubleEndedIterator<Item = Period> {
+        Self::period_range()
+    }
+
     /// Derive the verification key from a signing key.
     ///
     /// # Errors
@@ -220,6 +402,100 @@ pub trait KesAlgorithm {
         signature: &Self::Signature,
     ) -> Result<(), KesError>;
 
+    /// Verify a KES signature against a borrowed verification key.
+    ///
+    /// Gives the same result as [`verify_kes`](Self::verify_kes) on the
+    /// owned key. The default decodes the owned key first; `SumKes` and
+    /// `CompactSumKes`, whose keys are hashes, compare against the borrowed
+    /// bytes without copying them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key does not decode or verification fails.
+    fn verify_kes_ref(
+        context: &Self::Context,
+        verification_key: KesVerificationKeyRef<'_, Self>,
+        period: Period,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), KesError>
+    where
+        Self: Sized,
+    {
+        let verification_key = verification_key
+            .to_verification_key()
+            .ok_or(KesError::VerificationFailed)?;
+        Self::verify_kes(context, &verification_key, period, message, signature)
+    }
+
+    /// Verify a KES signature like [`verify_kes`](Self::verify_kes), and say
+    /// which step rejected it.
+    ///
+    /// Returns `Ok(())` exactly when `verify_kes` does, and only diagnoses
+    /// after `verify_kes` has failed. A signature that verifies for another
+    /// period, or a period outside `[0, total_periods())`, is reported as
+    /// [`KesVerifyStage::PeriodRouting`]; otherwise
+    /// [`explain_verify_failure_kes`](Self::explain_verify_failure_kes)
+    /// walks the tree to the failing node.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`KesVerifyFailure`] naming the failing stage if
+    /// verification fails.
+    fn verify_kes_explain(
+        context: &Self::Context,
+        verification_key: &Self::VerificationKey,
+        period: Period,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), KesVerifyFailure>
+    where
+        Self: Sized,
+    {
+        if Self::verify_kes(context, verification_key, period, message, signature).is_ok() {
+            return Ok(());
+        }
+        let signed_period = explain::find_signed_period::<Self>(
+            context,
+            verification_key,
+            period,
+            message,
+            signature,
+        );
+        if signed_period.is_some() || period >= Self::total_periods() {
+            return Err(KesVerifyFailure::at_root(
+                KesVerifyStage::PeriodRouting { signed_period },
+                period,
+            ));
+        }
+        Err(Self::explain_verify_failure_kes(
+            context,
+            verification_key,
+            period,
+            message,
+            signature,
+        ))
+    }
+
+    /// Locate the step that rejects a signature which failed
+    /// [`verify_kes`](Self::verify_kes) for an in-range `period`.
+    ///
+    /// Called by [`verify_kes_explain`](Self::verify_kes_explain). The
+    /// default blames the leaf signature, which is the only check `SingleKes`
+    /// makes; the composite schemes check their own node and recurse.
+    fn explain_verify_failure_kes(
+        _context: &Self::Context,
+        _verification_key: &Self::VerificationKey,
+        period: Period,
+        _message: &[u8],
+        _signature: &Self::Signature,
+    ) -> KesVerifyFailure
+    where
+        Self: Sized,
+    {
+        KesVerifyFailure::at_root(KesVerifyStage::LeafSignature, period)
+    }
+
     /// Update (evolve) the signing key to the next period.
     ///
     /// Returns None if the key has expired (reached max period).
@@ -244,8 +520,11 @@ pub trait KesAlgorithm {
     /// Panics if the supplied [`Seed`] does not provide enough entropy to
     /// produce [`KesAlgorithm::SEED_SIZE`] bytes.
     fn gen_key_kes(seed: &Seed) -> Result<Self::SigningKey, KesMError> {
-        let (material, _) = get_bytes_from_seed_t(Self::SEED_SIZE, seed.clone());
-        Self::gen_key_kes_from_seed_bytes(&material)
+        let material = seed
+            .as_slice()
+            .get(..Self::SEED_SIZE)
+            .expect("seed bytes exhausted");
+        Self::gen_key_kes_from_seed_bytes(material)
     }
 
     /// Generate a signing key from raw seed bytes.
@@ -276,7 +555,7 @@ pub trait KesAlgorithm {
     /// Provides API parity with Haskell's `hashVerKeyKES` method.
     ///
     /// # Type Parameters
-    /// * `H` - The hash algorithm to use (must implement `KesHashAlgorithm`)
+    /// * `H` - The hash algorithm to use (must implement `HashAlgorithm`)
     ///
     /// # Example
     /// ```rust
@@ -294,7 +573,7 @@ pub trait KesAlgorithm {
     /// let digest = Sum1Kes::hash_verification_key_kes::<Blake2b256>(&verification_key);
     /// assert_eq!(digest.len(), 32);
     /// ```
-    fn hash_verification_key_kes<H: hash::KesHashAlgorithm>(
+    fn hash_verification_key_kes<H: crate::hash::HashAlgorithm>(
         verification_key: &Self::VerificationKey,
     ) -> Vec<u8> {
         let serialized = Self::raw_serialize_verification_key_kes(verification_key);
@@ -306,10 +585,15 @@ pub trait KesAlgorithm {
 pub trait UnsoundKesAlgorithm: KesAlgorithm {
     /// Serialize a signing key (UNSOUND - use only for testing).
     ///
+    /// The bytes are zeroed when the returned buffer is dropped, and
+    /// implementations must not leave other copies behind.
+    ///
     /// # Errors
     ///
     /// Returns an error if the signing key cannot be serialized.
-    fn raw_serialize_signing_key_kes(signing_key: &Self::SigningKey) -> Result<Vec<u8>, KesMError>;
+    fn raw_serialize_signing_key_kes(
+        signing_key: &Self::SigningKey,
+    ) -> Result<Zeroizing<Vec<u8>>, KesMError>;
 
     /// Deserialize a signing key (UNSOUND - use only for testing).
     ///
@@ -373,6 +657,11 @@ where
 
 /// Convenience function to create a signed KES value.
 ///
+/// KES has no streaming entry point: a
+/// [`SignableStream`](crate::util::SignableStream) message must be buffered
+/// with [`collect_signable`](crate::util::SignableStream::collect_signable)
+/// first.
+///
 /// # Errors
 ///
 /// Propagates failures from the underlying signing routine.
@@ -416,6 +705,33 @@ where
     )
 }
 
+/// Generate a KES signing key for `A` from a seed that may also seed other
+/// algorithms.
+///
+/// The KES counterpart of
+/// [`gen_key_domain_separated`](crate::dsign::gen_key_domain_separated): the
+/// seed is expanded under the label
+/// `cardano-crypto-class/keygen/v1/kes/<ALGORITHM_NAME>/<total_periods>`
+/// before [`KesAlgorithm::gen_key_kes_from_seed_bytes`]. The period count is
+/// part of the label because the Sum constructions report the name of their
+/// base DSIGN; `SumNKes` and `CompactSumNKes`, whose keys coincide by design,
+/// share a label.
+///
+/// # Errors
+///
+/// Returns an error if the expanded bytes do not produce a valid signing key.
+pub fn gen_key_kes_domain_separated<A: KesAlgorithm>(
+    seed: &Seed,
+) -> Result<A::SigningKey, KesMError> {
+    let label = format!(
+        "{KEY_DERIVATION_LABEL_PREFIX}kes/{}/{}",
+        A::ALGORITHM_NAME,
+        A::total_periods()
+    );
+    let material = expand_seed_labelled(seed, label.as_bytes(), A::SEED_SIZE);
+    A::gen_key_kes_from_seed_bytes(material.as_slice())
+}
+
 /// Helper functions
 #[must_use]
 pub const fn seed_size_kes<A: KesAlgorithm>() -> usize {
@@ -0,0 +1,206 @@
+//! Process-wide hooks reporting KES key evolution to operational monitoring.
+//!
+//! Install a [`KesEventObserver`] with [`set_kes_observer`] and every
+//! `update_kes` of `SingleKes`, `CompactSingleKes`, `SumKes` and
+//! `CompactSumKes` reports the evolution it performed, or that the key
+//! expired. With the `kes-sign-events` feature, `sign_kes` reports each
+//! signature too; it is off by default because a block producer signs far
+//! more often than it evolves.
+//!
+//! A Sum key evolves by evolving its subtree keys, but only the outermost
+//! call is reported: evolving a `Sum6Kes` key produces exactly one event.
+//! `algorithm` is the scheme's [`KesAlgorithm::ALGORITHM_NAME`]. Evolutions
+//! of the throwaway copies made by `kes::dry_run` are not reported.
+//!
+//! Without an observer the only cost is a relaxed atomic load per call. The
+//! observer runs synchronously on the evolving thread, after the operation
+//! has finished and with no lock held, so it should hand events off quickly.
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! use cardano_crypto_class::kes::observer::{
+//!     KesEventObserver, clear_kes_observer, set_kes_observer,
+//! };
+//! use cardano_crypto_class::kes::Period;
+//!
+//! struct Log;
+//!
+//! impl KesEventObserver for Log {
+//!     fn on_update(&self, algorithm: &'static str, from: Period, to: Period, took: Duration) {
+//!         println!("{algorithm}: evolved {from} -> {to} in {took:?}");
+//!     }
+//! }
+//!
+//! set_kes_observer(Arc::new(Log));
+//! clear_kes_observer();
+//! ```
+//!
+//! [`KesAlgorithm::ALGORITHM_NAME`]: super::KesAlgorithm::ALGORITHM_NAME
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+
+use super::{KesMError, Period};
+
+/// Receives KES key events. Every method defaults to doing nothing.
+pub trait KesEventObserver: Send + Sync {
+    /// A signing key evolved from period `from` to period `to`, taking
+    /// `duration`.
+    fn on_update(&self, algorithm: &'static str, from: Period, to: Period, duration: Duration) {
+        let _ = (algorithm, from, to, duration);
+    }
+
+    /// Evolving past `final_period`, the key's last period, destroyed the
+    /// key.
+    fn on_expiry(&self, algorithm: &'static str, final_period: Period) {
+        let _ = (algorithm, final_period);
+    }
+
+    /// A signature was produced for `period` (feature `kes-sign-events`).
+    #[cfg(feature = "kes-sign-events")]
+    fn on_sign(&self, algorithm: &'static str, period: Period) {
+        let _ = (algorithm, period);
+    }
+}
+
+type Observer = Arc<dyn KesEventObserver>;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static OBSERVER: RwLock<Option<Observer>> = RwLock::new(None);
+
+thread_local! {
+    /// Depth of observed KES calls on this thread, so that subtree calls
+    /// made by a Sum key are not reported.
+    static DEPTH: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Install the process-wide KES observer, replacing any previous one.
+pub fn set_kes_observer(observer: Arc<dyn KesEventObserver>) {
+    let mut slot = OBSERVER.write().unwrap_or_else(PoisonError::into_inner);
+    *slot = Some(observer);
+    INSTALLED.store(true, Ordering::Release);
+}
+
+/// Remove the KES observer, restoring the unobserved fast path.
+pub fn clear_kes_observer() {
+    let mut slot = OBSERVER.write().unwrap_or_else(PoisonError::into_inner);
+    INSTALLED.store(false, Ordering::Release);
+    *slot = None;
+}
+
+fn current() -> Option<Observer> {
+    OBSERVER
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Marks an observed call in progress on this thread.
+struct Nested {
+    outermost: bool,
+}
+
+impl Nested {
+    fn enter() -> Self {
+        let depth = DEPTH.get();
+        DEPTH.set(depth + 1);
+        Nested {
+            outermost: depth == 0,
+        }
+    }
+}
+
+impl Drop for Nested {
+    fn drop(&mut self) {
+        DEPTH.set(DEPTH.get() - 1);
+    }
+}
+
+/// Run `f` without reporting the KES calls it makes, for evolutions of
+/// throwaway key copies.
+#[cfg(feature = "tooling")]
+pub(crate) fn unobserved<T>(f: impl FnOnce() -> T) -> T {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        return f();
+    }
+    let _nested = Nested::enter();
+    f()
+}
+
+/// Run the body of `update_kes` at `period`, reporting the outcome.
+#[inline]
+pub(crate) fn observe_update<K>(
+    algorithm: &'static str,
+    period: Period,
+    update: impl FnOnce() -> Result<Option<K>, KesMError>,
+) -> Result<Option<K>, KesMError> {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        return update();
+    }
+    let nested = Nested::enter();
+    let start = Instant::now();
+    let result = update();
+    let duration = start.elapsed();
+    if nested.outermost {
+        drop(nested);
+        if let (Ok(updated), Some(observer)) = (&result, current()) {
+            match updated {
+                Some(_) => observer.on_update(algorithm, period, period + 1, duration),
+                None => observer.on_expiry(algorithm, period),
+            }
+        }
+    }
+    result
+}
+
+/// Run the body of `sign_kes` at `period`, reporting a produced signature
+/// with the `kes-sign-events` feature.
+#[inline]
+pub(crate) fn observe_sign<S>(
+    algorithm: &'static str,
+    period: Period,
+    sign: impl FnOnce() -> Result<S, KesMError>,
+) -> Result<S, KesMError> {
+    #[cfg(feature = "kes-sign-events")]
+    {
+        if !INSTALLED.load(Ordering::Relaxed) {
+            return sign();
+        }
+        let nested = Nested::enter();
+        let result = sign();
+        if nested.outermost {
+            drop(nested);
+            if let (Ok(_), Some(observer)) = (&result, current()) {
+                observer.on_sign(algorithm, period);
+            }
+        }
+        result
+    }
+    #[cfg(not(feature = "kes-sign-events"))]
+    {
+        let _ = (algorithm, period);
+        sign()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_outermost_call_is_marked() {
+        let outer = Nested::enter();
+        assert!(outer.outermost);
+        {
+            let inner = Nested::enter();
+            assert!(!inner.outermost);
+        }
+        drop(outer);
+        assert_eq!(DEPTH.get(), 0);
+        assert!(Nested::enter().outermost);
+    }
+}
@@ -0,0 +1,70 @@
+//! Serde form of KES periods and operational certificate counters.
+//!
+//! Haskell encodes both as a `Word64`: a CBOR unsigned integer in its
+//! shortest form ([`cardano_binary::encode_word64_canonical`]), and a plain
+//! number in JSON. Use this module on every `Period` or counter field so that
+//! the encoding is fixed in one place:
+//!
+//! ```rust
+//! use cardano_crypto_class::kes::{Period, serde_period};
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Certificate {
+//!     #[serde(with = "serde_period")]
+//!     counter: u64,
+//!     #[serde(with = "serde_period")]
+//!     kes_period: Period,
+//! }
+//!
+//! let certificate = Certificate { counter: 3, kes_period: 400 };
+//! let bytes = cardano_binary::serialize(&certificate).unwrap();
+//! // 400 is written in three bytes, as Haskell writes it
+//! assert!(bytes.ends_with(&[0x19, 0x01, 0x90]));
+//! ```
+//!
+//! Deserialising accepts only an unsigned integer. A period written as a
+//! byte string, such as an 8-byte big-endian `Word64`, a negative number or
+//! a float is rejected. Serde does not report how an integer was written on
+//! the wire, so a non-minimal width, or a CBOR bignum small enough to fit, is
+//! accepted; decode with [`cardano_binary::decode_word64_canonical`] where
+//! those must be rejected too.
+
+use std::fmt;
+
+use serde::de::{self, Visitor};
+use serde::{Deserializer, Serializer};
+
+use super::Period;
+
+/// Serialise `period` as an unsigned integer.
+///
+/// # Errors
+///
+/// Returns the serializer's error.
+pub fn serialize<S: Serializer>(period: &Period, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(*period)
+}
+
+/// Deserialise an unsigned integer as a period.
+///
+/// # Errors
+///
+/// Returns an error if the input is anything other than an unsigned integer
+/// that fits in 64 bits.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Period, D::Error> {
+    deserializer.deserialize_u64(Word64Visitor)
+}
+
+struct Word64Visitor;
+
+impl Visitor<'_> for Word64Visitor {
+    type Value = Period;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an unsigned integer (Word64)")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Period, E> {
+        Ok(value)
+    }
+}
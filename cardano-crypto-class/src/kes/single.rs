@@ -3,12 +3,16 @@ use std::marker::PhantomData;
 use crate::dsign::{DsignMAlgorithm, UnsoundDsignMAlgorithm};
 #[cfg(feature = "kes-metrics")]
 use crate::kes::metrics;
-use crate::kes::{KesAlgorithm, KesError, KesMError, Period}; // instrumentation (no-op when feature disabled)
+use crate::kes::{KesAlgorithm, KesError, KesMError, Period, observer}; // instrumentation (no-op when feature disabled)
 
 /// SingleKES wraps a DSIGNM algorithm to provide a 1-period KES.
 ///
 /// This is the base case for KES composition. It simply delegates to the
 /// underlying DSIGN algorithm and only supports period 0.
+///
+/// The signing key is the DSIGN algorithm's mlocked key
+/// (`Ed25519MLockedSigningKey` for Ed25519), so the leaf seed never lives on
+/// the ordinary heap and is zeroised by [`KesAlgorithm::forget_signing_key_kes`].
 pub struct SingleKes<D: DsignMAlgorithm>(PhantomData<D>);
 
 impl<D> KesAlgorithm for SingleKes<D>
@@ -42,17 +46,19 @@ where
         message: &[u8],
         signing_key: &Self::SigningKey,
     ) -> Result<Self::Signature, KesMError> {
-        if period != 0 {
-            return Err(KesMError::Kes(KesError::PeriodOutOfRange {
-                period,
-                max_period: 1,
-            }));
-        }
-        let sig = D::sign_bytes_m(context, message, signing_key)
-            .map_err(|e| KesMError::Dsign(format!("{:?}", e)))?;
-        #[cfg(feature = "kes-metrics")]
-        metrics::record_signature(Self::SIGNATURE_SIZE);
-        Ok(sig)
+        observer::observe_sign(Self::ALGORITHM_NAME, period, || {
+            if period != 0 {
+                return Err(KesMError::Kes(KesError::PeriodOutOfRange {
+                    period,
+                    max_period: 1,
+                }));
+            }
+            let sig = D::sign_bytes_m(context, message, signing_key)
+                .map_err(|e| KesMError::Dsign(format!("{:?}", e)))?;
+            #[cfg(feature = "kes-metrics")]
+            metrics::record_signature(Self::SIGNATURE_SIZE);
+            Ok(sig)
+        })
     }
 
     fn verify_kes(
@@ -77,17 +83,26 @@ where
         signing_key: Self::SigningKey,
         period: Period,
     ) -> Result<Option<Self::SigningKey>, KesMError> {
-        let last_period = Self::total_periods().saturating_sub(1);
-
-        if period >= last_period {
-            // Once we have signed for the final available period, the key expires.
-            D::forget_signing_key_m(signing_key);
-            Ok(None)
-        } else {
-            #[cfg(feature = "kes-metrics")]
-            metrics::record_update();
-            Ok(Some(signing_key))
-        }
+        observer::observe_update(Self::ALGORITHM_NAME, period, || {
+            if period != 0 {
+                D::forget_signing_key_m(signing_key);
+                return Err(KesMError::Kes(KesError::PeriodOutOfRange {
+                    period,
+                    max_period: 1,
+                }));
+            }
+            let last_period = Self::total_periods().saturating_sub(1);
+
+            if period >= last_period {
+                // Once we have signed for the final available period, the key expires.
+                D::forget_signing_key_m(signing_key);
+                Ok(None)
+            } else {
+                #[cfg(feature = "kes-metrics")]
+                metrics::record_update();
+                Ok(Some(signing_key))
+            }
+        })
     }
 
     fn gen_key_kes_from_seed_bytes(seed: &[u8]) -> Result<Self::SigningKey, KesMError> {
@@ -123,6 +138,22 @@ where
     }
 }
 
-// Note: We don't implement UnsoundKesAlgorithm for SingleKes to maintain
-// the same security properties as the Haskell implementation - signing keys
-// should not be serialized.
+// Raw signing key serialisation is only compiled for tooling builds, so node
+// binaries cannot persist signing keys in raw form.
+#[cfg(feature = "tooling")]
+impl<D> crate::kes::UnsoundKesAlgorithm for SingleKes<D>
+where
+    D: DsignMAlgorithm + UnsoundDsignMAlgorithm,
+{
+    fn raw_serialize_signing_key_kes(
+        signing_key: &Self::SigningKey,
+    ) -> Result<zeroize::Zeroizing<Vec<u8>>, KesMError> {
+        D::raw_serialize_signing_key_m(signing_key)
+            .map(zeroize::Zeroizing::new)
+            .map_err(|e| KesMError::Dsign(format!("{:?}", e)))
+    }
+
+    fn raw_deserialize_signing_key_kes(bytes: &[u8]) -> Result<Self::SigningKey, KesMError> {
+        D::raw_deserialize_signing_key_m(bytes).map_err(|e| KesMError::Dsign(format!("{:?}", e)))
+    }
+}
@@ -1,8 +1,13 @@
 use std::marker::PhantomData;
 
-use crate::direct_serialise::{DirectDeserialise, DirectResult, DirectSerialise};
-use crate::kes::hash::KesHashAlgorithm;
-use crate::kes::{KesAlgorithm, KesError, KesMError, Period};
+use crate::direct_serialise::{
+    DirectDeserialise, DirectResult, DirectSerialise, DirectSerialiseError,
+};
+use crate::hash::HashAlgorithm;
+use crate::kes::{
+    KesAlgorithm, KesError, KesMError, KesVerificationKeyRef, KesVerifyFailure, KesVerifyStage,
+    Period, observer,
+};
 use crate::mlocked_bytes::MLockedBytes;
 use crate::seed::Seed;
 
@@ -15,18 +20,24 @@ use crate::seed::Seed;
 /// - sk_0: signing key for the first half of periods
 /// - r_1: seed for generating sk_1 (second half)
 /// - vk_0, vk_1: verification keys for both halves
+/// - vk: the root verification key, cached because it never changes
 ///
 /// The verification key is: H(vk_0 || vk_1) where H is the hash algorithm parameter.
+/// The hash is always unkeyed, as in Haskell's `cardano-base`; keyed or
+/// personalized modes ([`crate::hash::KeyedHashAlgorithm`]) would change every
+/// verification key.
+/// It is computed once at key generation (or deserialisation) and carried
+/// through `update_kes`, so `derive_verification_key` is a copy.
 pub struct SumKes<D, H>(PhantomData<(D, H)>)
 where
     D: KesAlgorithm,
-    H: KesHashAlgorithm;
+    H: HashAlgorithm;
 
 /// Signing key for SumKES contains both constituent keys.
 pub struct SumSigningKey<D, H>
 where
     D: KesAlgorithm,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     /// Current signing key (either for left or right subtree)
     pub(crate) sk: D::SigningKey,
@@ -36,15 +47,101 @@ where
     pub(crate) vk0: D::VerificationKey,
     /// Verification key for right subtree
     pub(crate) vk1: D::VerificationKey,
+    /// Cached root verification key `H(vk0 || vk1)`, fixed for the key's lifetime
+    pub(crate) vk: Vec<u8>,
     _phantom: PhantomData<H>,
 }
 
+impl<D, H> SumSigningKey<D, H>
+where
+    D: KesAlgorithm,
+    H: HashAlgorithm,
+{
+    /// Assemble a key from its parts, computing the cached root verification key.
+    pub(crate) fn from_parts(
+        sk: D::SigningKey,
+        r1_seed: Option<MLockedBytes>,
+        vk0: D::VerificationKey,
+        vk1: D::VerificationKey,
+    ) -> Self {
+        let vk = root_verification_key::<D, H>(&vk0, &vk1);
+        Self {
+            sk,
+            r1_seed,
+            vk0,
+            vk1,
+            vk,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Root verification key `H(vk0 || vk1)` of a sum composition.
+pub(crate) fn root_verification_key<D, H>(
+    vk0: &D::VerificationKey,
+    vk1: &D::VerificationKey,
+) -> Vec<u8>
+where
+    D: KesAlgorithm,
+    H: HashAlgorithm,
+{
+    H::hash_concat(
+        &D::raw_serialize_verification_key_kes(vk0),
+        &D::raw_serialize_verification_key_kes(vk1),
+    )
+}
+
+/// The child of a sum node a period falls in, with the period inside that
+/// child.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChildPeriod {
+    Left(Period),
+    Right(Period),
+}
+
+impl ChildPeriod {
+    /// The period inside the child.
+    pub(crate) fn period(self) -> Period {
+        match self {
+            ChildPeriod::Left(period) | ChildPeriod::Right(period) => period,
+        }
+    }
+}
+
+/// Number of periods of a sum node whose children are `D`.
+pub(crate) fn sum_total_periods<D: KesAlgorithm>() -> Period {
+    D::total_periods()
+        .checked_mul(2)
+        .expect("sum KES nested more than 63 levels deep")
+}
+
+/// Route `period` of a sum node over `D` to the child that handles it.
+///
+/// The range check comes before any arithmetic, so a period anywhere in
+/// `[total_periods, Period::MAX]` is rejected against this node's bounds
+/// rather than shifted into the right child.
+pub(crate) fn route_period<D: KesAlgorithm>(period: Period) -> Result<ChildPeriod, KesError> {
+    let max_period = sum_total_periods::<D>();
+    let out_of_range = KesError::PeriodOutOfRange { period, max_period };
+    if period >= max_period {
+        return Err(out_of_range);
+    }
+    let half = D::total_periods();
+    if period < half {
+        Ok(ChildPeriod::Left(period))
+    } else {
+        period
+            .checked_sub(half)
+            .map(ChildPeriod::Right)
+            .ok_or(out_of_range)
+    }
+}
+
 /// Signature for SumKES includes constituent signature and both verification keys.
-#[derive(Clone)]
 pub struct SumSignature<D, H>
 where
     D: KesAlgorithm,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     pub(crate) sigma: D::Signature,
     pub(crate) vk0: D::VerificationKey,
@@ -52,13 +149,45 @@ where
     _phantom: PhantomData<H>,
 }
 
-// Implement PartialEq and Eq manually since we need them for testing
+impl<D, H> SumSignature<D, H>
+where
+    D: KesAlgorithm,
+    H: HashAlgorithm,
+{
+    pub(crate) fn from_parts(
+        sigma: D::Signature,
+        vk0: D::VerificationKey,
+        vk1: D::VerificationKey,
+    ) -> Self {
+        Self {
+            sigma,
+            vk0,
+            vk1,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+// Clone, PartialEq, Eq, and Debug are implemented by hand so that they are
+// bounded on the component types rather than on the `D`/`H` markers.
+impl<D, H> Clone for SumSignature<D, H>
+where
+    D: KesAlgorithm,
+    D::Signature: Clone,
+    D::VerificationKey: Clone,
+    H: HashAlgorithm,
+{
+    fn clone(&self) -> Self {
+        Self::from_parts(self.sigma.clone(), self.vk0.clone(), self.vk1.clone())
+    }
+}
+
 impl<D, H> PartialEq for SumSignature<D, H>
 where
     D: KesAlgorithm,
     D::Signature: PartialEq,
     D::VerificationKey: PartialEq,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     fn eq(&self, other: &Self) -> bool {
         self.sigma == other.sigma && self.vk0 == other.vk0 && self.vk1 == other.vk1
@@ -70,17 +199,16 @@ where
     D: KesAlgorithm,
     D::Signature: Eq,
     D::VerificationKey: Eq,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
 }
 
-// Implement Debug for better test output
 impl<D, H> std::fmt::Debug for SumSignature<D, H>
 where
     D: KesAlgorithm,
     D::Signature: std::fmt::Debug,
     D::VerificationKey: std::fmt::Debug,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SumSignature")
@@ -91,11 +219,58 @@ where
     }
 }
 
+impl<D, H> SumKes<D, H>
+where
+    D: KesAlgorithm,
+    D::VerificationKey: Clone,
+    H: HashAlgorithm,
+{
+    /// Shared by `verify_kes` and `verify_kes_ref`: the root key is only
+    /// compared against, so it can be borrowed.
+    fn verify_against_hash(
+        context: &D::Context,
+        verification_key: &[u8],
+        period: Period,
+        message: &[u8],
+        signature: &SumSignature<D, H>,
+    ) -> Result<(), KesError> {
+        let child = route_period::<D>(period)?;
+
+        // Verify that H(vk0 || vk1) matches the provided verification key
+        let vk0_bytes = D::raw_serialize_verification_key_kes(&signature.vk0);
+        let vk1_bytes = D::raw_serialize_verification_key_kes(&signature.vk1);
+        let computed_vk = H::hash_concat(&vk0_bytes, &vk1_bytes);
+
+        if computed_vk.as_slice() != verification_key {
+            return Err(KesError::VerificationFailed);
+        }
+
+        match child {
+            // Verify against left subtree
+            ChildPeriod::Left(child_period) => D::verify_kes(
+                context,
+                &signature.vk0,
+                child_period,
+                message,
+                &signature.sigma,
+            ),
+            // Verify against right subtree
+            ChildPeriod::Right(child_period) => D::verify_kes(
+                context,
+                &signature.vk1,
+                child_period,
+                message,
+                &signature.sigma,
+            ),
+        }
+    }
+}
+
 impl<D, H> KesAlgorithm for SumKes<D, H>
 where
     D: KesAlgorithm,
     D::VerificationKey: Clone,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     type VerificationKey = Vec<u8>; // Hash of (vk0, vk1)
     type SigningKey = SumSigningKey<D, H>;
@@ -110,16 +285,14 @@ where
     const SIGNATURE_SIZE: usize = D::SIGNATURE_SIZE + 2 * D::VERIFICATION_KEY_SIZE;
 
     fn total_periods() -> Period {
-        2 * D::total_periods()
+        sum_total_periods::<D>()
     }
 
     fn derive_verification_key(
         signing_key: &Self::SigningKey,
     ) -> Result<Self::VerificationKey, KesMError> {
-        // vk = H(vk0 || vk1)
-        let vk0_bytes = D::raw_serialize_verification_key_kes(&signing_key.vk0);
-        let vk1_bytes = D::raw_serialize_verification_key_kes(&signing_key.vk1);
-        Ok(H::hash_concat(&vk0_bytes, &vk1_bytes))
+        // H(vk0 || vk1), computed once when the key was generated
+        Ok(signing_key.vk.clone())
     }
 
     fn sign_kes(
@@ -128,21 +301,17 @@ where
         message: &[u8],
         signing_key: &Self::SigningKey,
     ) -> Result<Self::Signature, KesMError> {
-        let t_half = D::total_periods();
-
-        let sigma = if period < t_half {
-            // Use left subtree (sk_0)
-            D::sign_kes(context, period, message, &signing_key.sk)?
-        } else {
-            // Use right subtree (sk_1)
-            D::sign_kes(context, period - t_half, message, &signing_key.sk)?
-        };
-
-        Ok(SumSignature {
-            sigma,
-            vk0: signing_key.vk0.clone(),
-            vk1: signing_key.vk1.clone(),
-            _phantom: PhantomData,
+        observer::observe_sign(Self::ALGORITHM_NAME, period, || {
+            // sk is the key of whichever subtree the period falls in
+            let child_period = route_period::<D>(period)?.period();
+            let sigma = D::sign_kes(context, child_period, message, &signing_key.sk)?;
+
+            Ok(SumSignature {
+                sigma,
+                vk0: signing_key.vk0.clone(),
+                vk1: signing_key.vk1.clone(),
+                _phantom: PhantomData,
+            })
         })
     }
 
@@ -153,30 +322,57 @@ where
         message: &[u8],
         signature: &Self::Signature,
     ) -> Result<(), KesError> {
-        // Verify that H(vk0 || vk1) matches the provided verification key
-        let vk0_bytes = D::raw_serialize_verification_key_kes(&signature.vk0);
-        let vk1_bytes = D::raw_serialize_verification_key_kes(&signature.vk1);
-        let computed_vk = H::hash_concat(&vk0_bytes, &vk1_bytes);
-
-        if &computed_vk != verification_key {
-            return Err(KesError::VerificationFailed);
-        }
+        Self::verify_against_hash(context, verification_key, period, message, signature)
+    }
 
-        let t_half = D::total_periods();
+    fn verify_kes_ref(
+        context: &Self::Context,
+        verification_key: KesVerificationKeyRef<'_, Self>,
+        period: Period,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), KesError> {
+        Self::verify_against_hash(
+            context,
+            verification_key.as_bytes(),
+            period,
+            message,
+            signature,
+        )
+    }
 
-        if period < t_half {
-            // Verify against left subtree
-            D::verify_kes(context, &signature.vk0, period, message, &signature.sigma)
-        } else {
-            // Verify against right subtree
-            D::verify_kes(
-                context,
-                &signature.vk1,
-                period - t_half,
-                message,
-                &signature.sigma,
-            )
+    fn explain_verify_failure_kes(
+        context: &Self::Context,
+        verification_key: &Self::VerificationKey,
+        period: Period,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> KesVerifyFailure {
+        let computed_vk = root_verification_key::<D, H>(&signature.vk0, &signature.vk1);
+        if computed_vk != *verification_key {
+            return KesVerifyFailure::at_root(
+                KesVerifyStage::RootHashMismatch {
+                    expected_hex: hex::encode(verification_key),
+                    actual_hex: hex::encode(&computed_vk),
+                },
+                period,
+            );
         }
+
+        let (vk_active, child_period) = match route_period::<D>(period) {
+            Ok(ChildPeriod::Left(child_period)) => (&signature.vk0, child_period),
+            Ok(ChildPeriod::Right(child_period)) => (&signature.vk1, child_period),
+            Err(_) => {
+                return KesVerifyFailure::at_root(
+                    KesVerifyStage::PeriodRouting {
+                        signed_period: None,
+                    },
+                    period,
+                );
+            },
+        };
+        D::explain_verify_failure_kes(context, vk_active, child_period, message, &signature.sigma)
+            .below_parent(period)
     }
 
     fn update_kes(
@@ -184,63 +380,73 @@ where
         mut signing_key: Self::SigningKey,
         period: Period,
     ) -> Result<Option<Self::SigningKey>, KesMError> {
-        let t_half = D::total_periods();
-
-        if period + 1 >= 2 * t_half {
-            // Key has expired
-            D::forget_signing_key_kes(signing_key.sk);
-            return Ok(None);
-        }
-
-        if period + 1 == t_half {
-            // Transition from left to right subtree
-            // Generate sk_1 from r1_seed
-            let r1_seed = signing_key
-                .r1_seed
-                .take()
-                .ok_or(KesMError::Kes(KesError::KeyExpired))?;
-
-            let seed = Seed::from_bytes(r1_seed.as_slice());
-            let sk1 = D::gen_key_kes(&seed)?;
-
-            // Forget the old signing key
-            D::forget_signing_key_kes(signing_key.sk);
+        observer::observe_update(Self::ALGORITHM_NAME, period, || {
+            let child = match route_period::<D>(period) {
+                Ok(child) => child,
+                Err(err) => {
+                    D::forget_signing_key_kes(signing_key.sk);
+                    return Err(err.into());
+                },
+            };
+
+            // period < total_periods, so period + 1 cannot overflow
+            if period + 1 == Self::total_periods() {
+                // Key has expired
+                D::forget_signing_key_kes(signing_key.sk);
+                return Ok(None);
+            }
 
-            Ok(Some(SumSigningKey {
-                sk: sk1,
-                r1_seed: None, // Already used
+            let t_half = D::total_periods();
+            let (sk, r1_seed) = match child {
+                ChildPeriod::Left(child_period) if child_period + 1 == t_half => {
+                    // Transition from left to right subtree
+                    // Generate sk_1 from r1_seed
+                    let r1_seed = signing_key
+                        .r1_seed
+                        .take()
+                        .ok_or(KesMError::Kes(KesError::KeyExpired))?;
+
+                    let seed = Seed::from_bytes(r1_seed.as_slice());
+                    let sk1 = D::gen_key_kes(&seed)?;
+
+                    // Forget the old signing key
+                    D::forget_signing_key_kes(signing_key.sk);
+
+                    // The seed has been consumed
+                    (sk1, None)
+                },
+                // Still in left subtree, update sk_0
+                ChildPeriod::Left(child_period) => {
+                    match D::update_kes(context, signing_key.sk, child_period)? {
+                        Some(sk) => (sk, signing_key.r1_seed),
+                        None => return Ok(None),
+                    }
+                },
+                // In right subtree, update sk_1
+                ChildPeriod::Right(child_period) => {
+                    match D::update_kes(context, signing_key.sk, child_period)? {
+                        Some(sk) => (sk, None),
+                        None => return Ok(None),
+                    }
+                },
+            };
+
+            // The root verification key never changes; carry the cache forward.
+            let updated = SumSigningKey {
+                sk,
+                r1_seed,
                 vk0: signing_key.vk0,
                 vk1: signing_key.vk1,
+                vk: signing_key.vk,
                 _phantom: PhantomData,
-            }))
-        } else if period + 1 < t_half {
-            // Still in left subtree, update sk_0
-            let updated_sk = D::update_kes(context, signing_key.sk, period)?;
-            match updated_sk {
-                Some(sk) => Ok(Some(SumSigningKey {
-                    sk,
-                    r1_seed: signing_key.r1_seed,
-                    vk0: signing_key.vk0,
-                    vk1: signing_key.vk1,
-                    _phantom: PhantomData,
-                })),
-                None => Ok(None),
-            }
-        } else {
-            // In right subtree, update sk_1
-            let adjusted_period = period - t_half;
-            let updated_sk = D::update_kes(context, signing_key.sk, adjusted_period)?;
-            match updated_sk {
-                Some(sk) => Ok(Some(SumSigningKey {
-                    sk,
-                    r1_seed: None,
-                    vk0: signing_key.vk0,
-                    vk1: signing_key.vk1,
-                    _phantom: PhantomData,
-                })),
-                None => Ok(None),
-            }
-        }
+            };
+            debug_assert_eq!(
+                updated.vk,
+                root_verification_key::<D, H>(&updated.vk0, &updated.vk1),
+                "cached root verification key diverged"
+            );
+            Ok(Some(updated))
+        })
     }
 
     fn gen_key_kes_from_seed_bytes(seed: &[u8]) -> Result<Self::SigningKey, KesMError> {
@@ -262,13 +468,7 @@ where
         let mut r1_mlocked = MLockedBytes::new(r1_bytes.len())?;
         r1_mlocked.as_mut_slice().copy_from_slice(r1_bytes);
 
-        Ok(SumSigningKey {
-            sk: sk0,
-            r1_seed: Some(r1_mlocked),
-            vk0,
-            vk1,
-            _phantom: PhantomData,
-        })
+        Ok(SumSigningKey::from_parts(sk0, Some(r1_mlocked), vk0, vk1))
     }
 
     fn raw_serialize_verification_key_kes(key: &Self::VerificationKey) -> Vec<u8> {
@@ -317,6 +517,55 @@ where
     }
 }
 
+// Raw layout mirrors `DirectSerialise`: child signing key, right-subtree seed
+// (zeroes once consumed), then both child verification keys.
+#[cfg(feature = "tooling")]
+impl<D, H> crate::kes::UnsoundKesAlgorithm for SumKes<D, H>
+where
+    D: crate::kes::UnsoundKesAlgorithm,
+    D::VerificationKey: Clone,
+    H: HashAlgorithm,
+{
+    fn raw_serialize_signing_key_kes(
+        signing_key: &Self::SigningKey,
+    ) -> Result<zeroize::Zeroizing<Vec<u8>>, KesMError> {
+        // Sized up front so the buffer never reallocates, which would leave
+        // an unzeroed copy of the key bytes behind.
+        let mut bytes = zeroize::Zeroizing::new(Vec::with_capacity(Self::SIGNING_KEY_SIZE));
+        bytes.extend_from_slice(&D::raw_serialize_signing_key_kes(&signing_key.sk)?);
+        match &signing_key.r1_seed {
+            Some(r1_seed) => bytes.extend_from_slice(r1_seed.as_slice()),
+            None => bytes.extend(std::iter::repeat_n(0, D::SEED_SIZE)),
+        }
+        bytes.extend_from_slice(&D::raw_serialize_verification_key_kes(&signing_key.vk0));
+        bytes.extend_from_slice(&D::raw_serialize_verification_key_kes(&signing_key.vk1));
+        Ok(bytes)
+    }
+
+    fn raw_deserialize_signing_key_kes(bytes: &[u8]) -> Result<Self::SigningKey, KesMError> {
+        if bytes.len() != Self::SIGNING_KEY_SIZE {
+            return Err(KesError::wrong_length(
+                "raw_deserialize_signing_key_kes",
+                Self::SIGNING_KEY_SIZE,
+                bytes.len(),
+            )
+            .into());
+        }
+        let (sk_bytes, rest) = bytes.split_at(D::SIGNING_KEY_SIZE);
+        let (r1_bytes, rest) = rest.split_at(D::SEED_SIZE);
+        let (vk0_bytes, vk1_bytes) = rest.split_at(D::VERIFICATION_KEY_SIZE);
+
+        let invalid_vk = || KesError::Message("invalid child verification key".to_owned());
+        let vk0 = D::raw_deserialize_verification_key_kes(vk0_bytes).ok_or_else(invalid_vk)?;
+        let vk1 = D::raw_deserialize_verification_key_kes(vk1_bytes).ok_or_else(invalid_vk)?;
+        let sk = D::raw_deserialize_signing_key_kes(sk_bytes)?;
+        let mut r1_seed = MLockedBytes::new(D::SEED_SIZE)?;
+        r1_seed.as_mut_slice().copy_from_slice(r1_bytes);
+
+        Ok(SumSigningKey::from_parts(sk, Some(r1_seed), vk0, vk1))
+    }
+}
+
 // Serde implementations for SumKES types
 #[cfg(feature = "serde")]
 impl<D, H> serde::Serialize for SumSignature<D, H>
@@ -324,7 +573,7 @@ where
     D: KesAlgorithm,
     D::Signature: serde::Serialize,
     D::VerificationKey: serde::Serialize,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -345,7 +594,7 @@ where
     D: KesAlgorithm,
     D::Signature: serde::Deserialize<'de>,
     D::VerificationKey: serde::Deserialize<'de>,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     fn deserialize<DE>(deserializer: DE) -> Result<Self, DE::Error>
     where
@@ -360,7 +609,7 @@ where
             D: KesAlgorithm,
             D::Signature: serde::Deserialize<'de>,
             D::VerificationKey: serde::Deserialize<'de>,
-            H: KesHashAlgorithm,
+            H: HashAlgorithm,
         {
             type Value = SumSignature<D, H>;
 
@@ -431,12 +680,15 @@ pub type Sum7Kes = SumKes<Sum6Kes, Blake2b256>;
 // 2. MLocked seed for right subtree (r1_seed)
 // 3. Verification key for left subtree (vk0)
 // 4. Verification key for right subtree (vk1)
+//
+// This is the `UnsoundKesAlgorithm` raw layout, `SIGNING_KEY_SIZE` bytes in
+// total. Secret segments go to and from mlocked memory without intermediate
+// copies; only the public verification keys pass through a `Vec`.
 impl<D, H> DirectSerialise for SumSigningKey<D, H>
 where
     D: KesAlgorithm,
     D::SigningKey: DirectSerialise,
-    D::VerificationKey: DirectSerialise,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     fn direct_serialise(
         &self,
@@ -450,18 +702,16 @@ where
             let slice = r1_seed.as_slice();
             push(slice)?;
         } else {
-            // If r1_seed is None, we still need to serialize empty bytes
-            // This should not happen in a valid key, but we handle it for safety
-            // Actually, based on Haskell, if r1_seed is None it means we're in right subtree
-            // and the seed has been consumed. We should serialize zeroes or error.
-            // For now, let's serialize the expected number of zero bytes.
+            // Consumed once the key has moved to the right subtree; the raw
+            // layout stores zeroes in its place.
             let zero_bytes = vec![0u8; D::SEED_SIZE];
             push(&zero_bytes)?;
         }
 
-        // Serialize verification keys
-        self.vk0.direct_serialise(push)?;
-        self.vk1.direct_serialise(push)?;
+        // Serialize verification keys through the raw encoding so that the
+        // layout composes for every tree depth.
+        push(&D::raw_serialize_verification_key_kes(&self.vk0))?;
+        push(&D::raw_serialize_verification_key_kes(&self.vk1))?;
 
         Ok(())
     }
@@ -471,8 +721,7 @@ impl<D, H> DirectDeserialise for SumSigningKey<D, H>
 where
     D: KesAlgorithm,
     D::SigningKey: DirectDeserialise,
-    D::VerificationKey: DirectDeserialise,
-    H: KesHashAlgorithm,
+    H: HashAlgorithm,
 {
     fn direct_deserialise(
         pull: &mut dyn FnMut(&mut [u8]) -> DirectResult<()>,
@@ -481,27 +730,25 @@ where
         let sk = D::SigningKey::direct_deserialise(pull)?;
 
         // Deserialize r1_seed into MLocked memory
-        let mut r1_mlocked = MLockedBytes::new(D::SEED_SIZE).map_err(|_| {
-            crate::direct_serialise::SizeCheckError {
-                expected_size: D::SEED_SIZE,
-                actual_size: 0,
-            }
-        })?;
+        let mut r1_mlocked = MLockedBytes::new(D::SEED_SIZE)?;
         {
             let slice = r1_mlocked.as_mut_slice();
             pull(slice)?;
         }
 
         // Deserialize verification keys
-        let vk0 = D::VerificationKey::direct_deserialise(pull)?;
-        let vk1 = D::VerificationKey::direct_deserialise(pull)?;
+        let mut pull_vk = || {
+            let mut bytes = vec![0u8; D::VERIFICATION_KEY_SIZE];
+            pull(&mut bytes)?;
+            D::raw_deserialize_verification_key_kes(&bytes).ok_or(
+                DirectSerialiseError::InvalidValue {
+                    what: "KES verification key",
+                },
+            )
+        };
+        let vk0 = pull_vk()?;
+        let vk1 = pull_vk()?;
 
-        Ok(SumSigningKey {
-            sk,
-            r1_seed: Some(r1_mlocked),
-            vk0,
-            vk1,
-            _phantom: PhantomData,
-        })
+        Ok(SumSigningKey::from_parts(sk, Some(r1_mlocked), vk0, vk1))
     }
 }
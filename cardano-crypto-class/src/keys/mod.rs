@@ -0,0 +1,11 @@
+//! Algorithm-independent key handling.
+//!
+//! [`tagged`] prefixes raw verification keys with the name of their
+//! algorithm, so keys of different algorithms can share one store.
+
+pub mod tagged;
+
+pub use tagged::{
+    TaggedKey, TaggedKeyAlgorithm, TaggedKeyError, deserialize_tagged, deserialize_tagged_any,
+    known_tags, serialize_tagged,
+};
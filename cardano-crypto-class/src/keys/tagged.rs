@@ -0,0 +1,486 @@
+//! Self-describing ("raw with header") verification key encoding.
+//!
+//! Raw verification keys carry no type information: an Ed25519 key, a
+//! Schnorr key and a Sum KES root hash are all 32 bytes. This module prefixes
+//! the raw bytes with the name of their algorithm so a store holding keys of
+//! many algorithms can tell them apart:
+//!
+//! ```text
+//! [name length: u8][name: ASCII][raw verification key]
+//! ```
+//!
+//! The raw key is exactly what the algorithm's `raw_serialize_verification_key`
+//! (or `raw_serialize_verification_key_kes`) returns, and its length must be
+//! the algorithm's `VERIFICATION_KEY_SIZE`; nothing may follow it.
+//!
+//! DSIGN and VRF keys are named by their `ALGORITHM_NAME`. KES algorithms all
+//! report the name of their base DSIGN, so KES keys use the names Haskell's
+//! `algorithmNameKES` gives instead: `ed25519_kes` for a single-period key and
+//! `ed25519_kes_2^N` for a sum over `2^N` periods. A compact sum key has the
+//! same verification key as the plain sum of the same depth, so both encode
+//! under the same name and decode to the same [`TaggedKey`] variant.
+//!
+//! ```rust
+//! use cardano_crypto_class::keys::tagged::{
+//!     TaggedKey, deserialize_tagged, deserialize_tagged_any, serialize_tagged,
+//! };
+//! use cardano_crypto_class::{DsignAlgorithm, Ed25519};
+//!
+//! let vk = Ed25519::derive_verification_key(&Ed25519::gen_key_from_seed_bytes(&[7u8; 32]));
+//! let bytes = serialize_tagged::<Ed25519>(&vk);
+//! assert_eq!(&bytes[..8], b"\x07ed25519");
+//!
+//! assert_eq!(deserialize_tagged::<Ed25519>(&bytes).unwrap(), vk);
+//! let any = deserialize_tagged_any(&bytes).unwrap();
+//! assert_eq!(any, TaggedKey::Ed25519(vk.clone()));
+//! assert_eq!(any.expected::<Ed25519>().unwrap(), vk);
+//! ```
+
+use thiserror::Error;
+
+use crate::dsign::DsignAlgorithm;
+use crate::dsign::ecdsa_secp256k1::{self, EcdsaSecp256k1DSIGN};
+use crate::dsign::ed25519::{Ed25519, Ed25519VerificationKey};
+use crate::dsign::schnorr_secp256k1::{self, SchnorrSecp256k1DSIGN};
+use crate::kes::{
+    CompactSum0Kes, CompactSum1Kes, CompactSum2Kes, CompactSum3Kes, CompactSum4Kes, CompactSum5Kes,
+    CompactSum6Kes, CompactSum7Kes, KesAlgorithm, Sum0Kes, Sum1Kes, Sum2Kes, Sum3Kes, Sum4Kes,
+    Sum5Kes, Sum6Kes, Sum7Kes,
+};
+use crate::vrf::{
+    MockVRF, MockVerificationKey, NeverVRF, NeverVerificationKey, PraosBatchCompatVRF,
+    PraosBatchCompatVerificationKey, PraosVRF, PraosVerificationKey, SimpleVRF,
+    SimpleVerificationKey, VRFAlgorithm,
+};
+
+/// Errors raised when decoding a tagged verification key.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum TaggedKeyError {
+    #[error("tagged key header is truncated")]
+    TruncatedHeader,
+    #[error("unknown key algorithm {0:?}")]
+    UnknownAlgorithm(String),
+    #[error("expected a {expected} key but found a {found:?} key")]
+    WrongAlgorithm {
+        expected: &'static str,
+        found: String,
+    },
+    #[error("{algorithm} key: wrong length, expected {expected} bytes but got {actual}")]
+    LengthMismatch {
+        algorithm: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("{algorithm} key: {extra} trailing bytes")]
+    TrailingBytes {
+        algorithm: &'static str,
+        extra: usize,
+    },
+    #[error("{algorithm} key: bytes are not a valid verification key")]
+    InvalidKey { algorithm: &'static str },
+}
+
+impl TaggedKeyError {
+    /// Stable identifier of this variant (see the crate-level *Error codes*
+    /// section).
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            TaggedKeyError::TruncatedHeader => "tagged_key.truncated_header",
+            TaggedKeyError::UnknownAlgorithm(_) => "tagged_key.unknown_algorithm",
+            TaggedKeyError::WrongAlgorithm { .. } => "tagged_key.wrong_algorithm",
+            TaggedKeyError::LengthMismatch { .. } => "tagged_key.length_mismatch",
+            TaggedKeyError::TrailingBytes { .. } => "tagged_key.trailing_bytes",
+            TaggedKeyError::InvalidKey { .. } => "tagged_key.invalid_key",
+        }
+    }
+
+    /// Always `false`: every variant rejects malformed input.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TaggedKeyError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        match self {
+            TaggedKeyError::TruncatedHeader => {},
+            TaggedKeyError::UnknownAlgorithm(name) => map.serialize_entry("algorithm", name)?,
+            TaggedKeyError::WrongAlgorithm { expected, found } => {
+                map.serialize_entry("expected", expected)?;
+                map.serialize_entry("found", found)?;
+            },
+            TaggedKeyError::LengthMismatch {
+                algorithm,
+                expected,
+                actual,
+            } => {
+                map.serialize_entry("algorithm", algorithm)?;
+                map.serialize_entry("expected", expected)?;
+                map.serialize_entry("actual", actual)?;
+            },
+            TaggedKeyError::TrailingBytes { algorithm, extra } => {
+                map.serialize_entry("algorithm", algorithm)?;
+                map.serialize_entry("extra", extra)?;
+            },
+            TaggedKeyError::InvalidKey { algorithm } => {
+                map.serialize_entry("algorithm", algorithm)?;
+            },
+        }
+        map.serialize_entry("message", &self.to_string())?;
+        map.end()
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// An algorithm whose verification keys can be stored tagged.
+///
+/// Implemented for every DSIGN, KES and VRF algorithm the crate ships; the
+/// trait is sealed because [`deserialize_tagged_any`] must know every tag.
+pub trait TaggedKeyAlgorithm: sealed::Sealed {
+    /// Name written in front of the raw key.
+    const TAG: &'static str;
+    /// Exact length of the raw key following the name.
+    const VERIFICATION_KEY_SIZE: usize;
+    /// The algorithm's verification key type.
+    type VerificationKey;
+
+    /// The algorithm's raw verification key encoding.
+    fn raw_serialize(key: &Self::VerificationKey) -> Vec<u8>;
+
+    /// Inverse of [`TaggedKeyAlgorithm::raw_serialize`].
+    fn raw_deserialize(bytes: &[u8]) -> Option<Self::VerificationKey>;
+
+    /// Wrap a key in the matching [`TaggedKey`] variant.
+    fn into_tagged(key: Self::VerificationKey) -> TaggedKey;
+
+    /// Unwrap a [`TaggedKey`], handing it back if it holds another algorithm's
+    /// key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `key` unchanged if it is not a key of this algorithm.
+    fn from_tagged(key: TaggedKey) -> Result<Self::VerificationKey, TaggedKey>;
+}
+
+/// Registry entry used by [`deserialize_tagged_any`].
+struct Entry {
+    tag: &'static str,
+    size: usize,
+    decode: fn(&[u8]) -> Option<TaggedKey>,
+}
+
+macro_rules! tagged_keys {
+    ($(
+        $(#[$doc:meta])*
+        $variant:ident($vk:ty) = $alg:ty, $tag:expr, $trait:ident::{$ser:ident, $de:ident}
+        $(, also $alias:ty)?;
+    )*) => {
+        /// A verification key of any algorithm known to this module.
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #[non_exhaustive]
+        pub enum TaggedKey {
+            $($(#[$doc])* $variant($vk),)*
+        }
+
+        impl TaggedKey {
+            /// Name of the key's algorithm, as written in its header.
+            #[must_use]
+            pub fn tag(&self) -> &'static str {
+                match self {
+                    $(TaggedKey::$variant(_) => <$alg as TaggedKeyAlgorithm>::TAG,)*
+                }
+            }
+
+            /// Encode the key with its header, as [`serialize_tagged`] does.
+            #[must_use]
+            pub fn to_bytes(&self) -> Vec<u8> {
+                match self {
+                    $(TaggedKey::$variant(key) => serialize_tagged::<$alg>(key),)*
+                }
+            }
+        }
+
+        const REGISTRY: &[Entry] = &[$(
+            Entry {
+                tag: <$alg as TaggedKeyAlgorithm>::TAG,
+                size: <$alg as TaggedKeyAlgorithm>::VERIFICATION_KEY_SIZE,
+                decode: |bytes| {
+                    <$alg as TaggedKeyAlgorithm>::raw_deserialize(bytes).map(TaggedKey::$variant)
+                },
+            },
+        )*];
+
+        $(
+            tagged_keys!(@impl $variant, $alg, $tag, $trait::{$ser, $de});
+            $(tagged_keys!(@impl $variant, $alias, $tag, $trait::{$ser, $de});)?
+        )*
+    };
+    (@impl $variant:ident, $alg:ty, $tag:expr, $trait:ident::{$ser:ident, $de:ident}) => {
+        impl sealed::Sealed for $alg {}
+
+        impl TaggedKeyAlgorithm for $alg {
+            const TAG: &'static str = $tag;
+            const VERIFICATION_KEY_SIZE: usize = <$alg as $trait>::VERIFICATION_KEY_SIZE;
+            type VerificationKey = <$alg as $trait>::VerificationKey;
+
+            fn raw_serialize(key: &Self::VerificationKey) -> Vec<u8> {
+                <$alg as $trait>::$ser(key)
+            }
+
+            fn raw_deserialize(bytes: &[u8]) -> Option<Self::VerificationKey> {
+                <$alg as $trait>::$de(bytes)
+            }
+
+            fn into_tagged(key: Self::VerificationKey) -> TaggedKey {
+                TaggedKey::$variant(key)
+            }
+
+            fn from_tagged(key: TaggedKey) -> Result<Self::VerificationKey, TaggedKey> {
+                match key {
+                    TaggedKey::$variant(key) => Ok(key),
+                    other => Err(other),
+                }
+            }
+        }
+    };
+}
+
+tagged_keys! {
+    /// [`Ed25519`] DSIGN key.
+    Ed25519(Ed25519VerificationKey) = Ed25519, <Ed25519 as DsignAlgorithm>::ALGORITHM_NAME,
+        DsignAlgorithm::{raw_serialize_verification_key, raw_deserialize_verification_key};
+    /// [`EcdsaSecp256k1DSIGN`] key (compressed point).
+    EcdsaSecp256k1(ecdsa_secp256k1::VerificationKey) = EcdsaSecp256k1DSIGN,
+        <EcdsaSecp256k1DSIGN as DsignAlgorithm>::ALGORITHM_NAME,
+        DsignAlgorithm::{raw_serialize_verification_key, raw_deserialize_verification_key};
+    /// [`SchnorrSecp256k1DSIGN`] key (x-only point).
+    SchnorrSecp256k1(schnorr_secp256k1::VerificationKey) = SchnorrSecp256k1DSIGN,
+        <SchnorrSecp256k1DSIGN as DsignAlgorithm>::ALGORITHM_NAME,
+        DsignAlgorithm::{raw_serialize_verification_key, raw_deserialize_verification_key};
+    /// Single-period KES key ([`Sum0Kes`] or [`CompactSum0Kes`]).
+    Sum0Kes(Ed25519VerificationKey) = Sum0Kes, "ed25519_kes",
+        KesAlgorithm::{raw_serialize_verification_key_kes, raw_deserialize_verification_key_kes},
+        also CompactSum0Kes;
+    /// [`Sum1Kes`] or [`CompactSum1Kes`] root hash.
+    Sum1Kes(Vec<u8>) = Sum1Kes, "ed25519_kes_2^1",
+        KesAlgorithm::{raw_serialize_verification_key_kes, raw_deserialize_verification_key_kes},
+        also CompactSum1Kes;
+    /// [`Sum2Kes`] or [`CompactSum2Kes`] root hash.
+    Sum2Kes(Vec<u8>) = Sum2Kes, "ed25519_kes_2^2",
+        KesAlgorithm::{raw_serialize_verification_key_kes, raw_deserialize_verification_key_kes},
+        also CompactSum2Kes;
+    /// [`Sum3Kes`] or [`CompactSum3Kes`] root hash.
+    Sum3Kes(Vec<u8>) = Sum3Kes, "ed25519_kes_2^3",
+        KesAlgorithm::{raw_serialize_verification_key_kes, raw_deserialize_verification_key_kes},
+        also CompactSum3Kes;
+    /// [`Sum4Kes`] or [`CompactSum4Kes`] root hash.
+    Sum4Kes(Vec<u8>) = Sum4Kes, "ed25519_kes_2^4",
+        KesAlgorithm::{raw_serialize_verification_key_kes, raw_deserialize_verification_key_kes},
+        also CompactSum4Kes;
+    /// [`Sum5Kes`] or [`CompactSum5Kes`] root hash.
+    Sum5Kes(Vec<u8>) = Sum5Kes, "ed25519_kes_2^5",
+        KesAlgorithm::{raw_serialize_verification_key_kes, raw_deserialize_verification_key_kes},
+        also CompactSum5Kes;
+    /// [`Sum6Kes`] or [`CompactSum6Kes`] root hash, the mainnet KES key.
+    Sum6Kes(Vec<u8>) = Sum6Kes, "ed25519_kes_2^6",
+        KesAlgorithm::{raw_serialize_verification_key_kes, raw_deserialize_verification_key_kes},
+        also CompactSum6Kes;
+    /// [`Sum7Kes`] or [`CompactSum7Kes`] root hash.
+    Sum7Kes(Vec<u8>) = Sum7Kes, "ed25519_kes_2^7",
+        KesAlgorithm::{raw_serialize_verification_key_kes, raw_deserialize_verification_key_kes},
+        also CompactSum7Kes;
+    /// [`PraosVRF`] key.
+    PraosVrf(PraosVerificationKey) = PraosVRF, <PraosVRF as VRFAlgorithm>::ALGORITHM_NAME,
+        VRFAlgorithm::{raw_serialize_verification_key, raw_deserialize_verification_key};
+    /// [`PraosBatchCompatVRF`] key.
+    PraosBatchCompatVrf(PraosBatchCompatVerificationKey) = PraosBatchCompatVRF,
+        <PraosBatchCompatVRF as VRFAlgorithm>::ALGORITHM_NAME,
+        VRFAlgorithm::{raw_serialize_verification_key, raw_deserialize_verification_key};
+    /// [`SimpleVRF`] key (test algorithm).
+    SimpleVrf(SimpleVerificationKey) = SimpleVRF, <SimpleVRF as VRFAlgorithm>::ALGORITHM_NAME,
+        VRFAlgorithm::{raw_serialize_verification_key, raw_deserialize_verification_key};
+    /// [`MockVRF`] key (test algorithm).
+    MockVrf(MockVerificationKey) = MockVRF, <MockVRF as VRFAlgorithm>::ALGORITHM_NAME,
+        VRFAlgorithm::{raw_serialize_verification_key, raw_deserialize_verification_key};
+    /// [`NeverVRF`] key, which has no bytes.
+    NeverVrf(NeverVerificationKey) = NeverVRF, <NeverVRF as VRFAlgorithm>::ALGORITHM_NAME,
+        VRFAlgorithm::{raw_serialize_verification_key, raw_deserialize_verification_key};
+}
+
+impl TaggedKey {
+    /// Wrap a verification key of algorithm `A`.
+    #[must_use]
+    pub fn new<A: TaggedKeyAlgorithm>(key: A::VerificationKey) -> Self {
+        A::into_tagged(key)
+    }
+
+    /// Unwrap the key, checking that it belongs to algorithm `A`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaggedKeyError::WrongAlgorithm`] if the key belongs to a
+    /// different algorithm.
+    pub fn expected<A: TaggedKeyAlgorithm>(self) -> Result<A::VerificationKey, TaggedKeyError> {
+        A::from_tagged(self).map_err(|other| TaggedKeyError::WrongAlgorithm {
+            expected: A::TAG,
+            found: other.tag().to_owned(),
+        })
+    }
+}
+
+/// Tags of every algorithm [`deserialize_tagged_any`] understands.
+pub fn known_tags() -> impl Iterator<Item = &'static str> {
+    REGISTRY.iter().map(|entry| entry.tag)
+}
+
+/// Encode a verification key of algorithm `A` with its algorithm header.
+///
+/// # Panics
+///
+/// Panics if `A::TAG` is longer than 255 bytes, which no registered
+/// algorithm's is.
+#[must_use]
+pub fn serialize_tagged<A: TaggedKeyAlgorithm>(key: &A::VerificationKey) -> Vec<u8> {
+    let tag = A::TAG.as_bytes();
+    let raw = A::raw_serialize(key);
+    let mut out = Vec::with_capacity(1 + tag.len() + raw.len());
+    out.push(u8::try_from(tag.len()).expect("algorithm names fit in one length byte"));
+    out.extend_from_slice(tag);
+    out.extend_from_slice(&raw);
+    out
+}
+
+/// Decode a tagged key of algorithm `A`.
+///
+/// # Errors
+///
+/// Returns [`TaggedKeyError::WrongAlgorithm`] if the header names another
+/// algorithm, and [`TaggedKeyError::TruncatedHeader`],
+/// [`TaggedKeyError::LengthMismatch`], [`TaggedKeyError::TrailingBytes`] or
+/// [`TaggedKeyError::InvalidKey`] if the bytes are malformed.
+pub fn deserialize_tagged<A: TaggedKeyAlgorithm>(
+    bytes: &[u8],
+) -> Result<A::VerificationKey, TaggedKeyError> {
+    let (name, raw) = split_header(bytes)?;
+    if name != A::TAG.as_bytes() {
+        return Err(TaggedKeyError::WrongAlgorithm {
+            expected: A::TAG,
+            found: String::from_utf8_lossy(name).into_owned(),
+        });
+    }
+    check_length(A::TAG, A::VERIFICATION_KEY_SIZE, raw)?;
+    A::raw_deserialize(raw).ok_or(TaggedKeyError::InvalidKey { algorithm: A::TAG })
+}
+
+/// Decode a tagged key of any algorithm listed by [`known_tags`].
+///
+/// # Errors
+///
+/// Returns [`TaggedKeyError::UnknownAlgorithm`] if the header names no known
+/// algorithm, and [`TaggedKeyError::TruncatedHeader`],
+/// [`TaggedKeyError::LengthMismatch`], [`TaggedKeyError::TrailingBytes`] or
+/// [`TaggedKeyError::InvalidKey`] if the bytes are malformed.
+pub fn deserialize_tagged_any(bytes: &[u8]) -> Result<TaggedKey, TaggedKeyError> {
+    let (name, raw) = split_header(bytes)?;
+    let entry = REGISTRY
+        .iter()
+        .find(|entry| entry.tag.as_bytes() == name)
+        .ok_or_else(|| {
+            TaggedKeyError::UnknownAlgorithm(String::from_utf8_lossy(name).into_owned())
+        })?;
+    check_length(entry.tag, entry.size, raw)?;
+    (entry.decode)(raw).ok_or(TaggedKeyError::InvalidKey {
+        algorithm: entry.tag,
+    })
+}
+
+fn split_header(bytes: &[u8]) -> Result<(&[u8], &[u8]), TaggedKeyError> {
+    let (&len, rest) = bytes.split_first().ok_or(TaggedKeyError::TruncatedHeader)?;
+    let len = usize::from(len);
+    if rest.len() < len {
+        return Err(TaggedKeyError::TruncatedHeader);
+    }
+    Ok(rest.split_at(len))
+}
+
+fn check_length(
+    algorithm: &'static str,
+    expected: usize,
+    raw: &[u8],
+) -> Result<(), TaggedKeyError> {
+    if raw.len() < expected {
+        return Err(TaggedKeyError::LengthMismatch {
+            algorithm,
+            expected,
+            actual: raw.len(),
+        });
+    }
+    if raw.len() > expected {
+        return Err(TaggedKeyError::TrailingBytes {
+            algorithm,
+            extra: raw.len() - expected,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_unique_and_fit_the_length_byte() {
+        let tags: Vec<_> = known_tags().collect();
+        for (i, tag) in tags.iter().enumerate() {
+            assert!(tag.len() <= usize::from(u8::MAX));
+            assert!(!tags[i + 1..].contains(tag), "duplicate tag {tag}");
+        }
+    }
+
+    #[test]
+    fn kes_tags_extend_the_base_name_with_the_period_count() {
+        fn check<K: KesAlgorithm + TaggedKeyAlgorithm>() {
+            let tag = <K as TaggedKeyAlgorithm>::TAG;
+            assert!(tag.starts_with(<K as KesAlgorithm>::ALGORITHM_NAME));
+            let depth = K::total_periods().trailing_zeros();
+            if depth == 0 {
+                assert_eq!(tag, "ed25519_kes");
+            } else {
+                assert_eq!(tag, format!("ed25519_kes_2^{depth}"));
+            }
+        }
+        check::<Sum0Kes>();
+        check::<Sum1Kes>();
+        check::<Sum6Kes>();
+        check::<Sum7Kes>();
+        check::<CompactSum0Kes>();
+        check::<CompactSum3Kes>();
+        check::<CompactSum7Kes>();
+    }
+
+    #[test]
+    fn header_errors() {
+        assert_eq!(
+            deserialize_tagged_any(&[]),
+            Err(TaggedKeyError::TruncatedHeader)
+        );
+        assert_eq!(
+            deserialize_tagged_any(b"\x07ed255"),
+            Err(TaggedKeyError::TruncatedHeader)
+        );
+        assert_eq!(
+            deserialize_tagged_any(b"\x03rsa\x00"),
+            Err(TaggedKeyError::UnknownAlgorithm("rsa".to_owned()))
+        );
+    }
+}
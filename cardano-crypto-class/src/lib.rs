@@ -3,70 +3,135 @@
 //! This crate provides pure-Rust replacements for modules from
 //! `cardano-crypto-class`. The initial focus is the `Seed` abstraction used to
 //! seed deterministic key generation and pseudo-random generators.
+//!
+//! # Error codes
+//!
+//! Every error type exposes `code()`, a dotted identifier such as
+//! `kes.period_out_of_range` or `mlocked.lock_failed`, and `is_transient()`,
+//! which reports whether retrying the operation could succeed. Codes are
+//! unique across the crate and are a stability guarantee: an existing
+//! variant keeps its code, and removed variants never have their code
+//! reused. With the `serde` feature the errors serialise as a flat map of
+//! `code`, the variant's fields and the human-readable `message`.
+//!
+//! # Key generation from a shared seed
+//!
+//! `DsignAlgorithm::gen_key`, `KesAlgorithm::gen_key_kes` and
+//! `VRFAlgorithm::gen_key` hand the first `SEED_SIZE` seed bytes straight to
+//! the algorithm, exactly as Haskell's `genKeyDSIGN`, `genKeyKES` and
+//! `genKeyVRF` do. Use them whenever keys must match ones generated by the
+//! Haskell node or existing tooling from the same seed, and when every key
+//! has its own seed.
+//!
+//! If one master seed has to produce keys for several algorithms, for
+//! example an operator's cold key and VRF key, use
+//! [`gen_key_domain_separated`], [`kes::gen_key_kes_domain_separated`] or
+//! [`gen_key_vrf_domain_separated`] instead. They expand the seed under a
+//! label naming the algorithm family and algorithm (see
+//! [`expand_seed_labelled`]), so the keys are unrelated to each other and to
+//! the direct keys from the same seed. The derivation is fixed: the same seed
+//! always gives the same key, but not the key Haskell would generate.
+//!
+//! # WebAssembly
+//!
+//! mlocked memory needs `mlock` and the C allocator, so it sits behind the
+//! default `secure-memory` feature. Build for `wasm32-unknown-unknown` with
+//! `--no-default-features`, adding `wasm-entropy` to draw seeds from the
+//! JavaScript `crypto.getRandomValues`. Hashing, verification, raw
+//! (de)serialisation and CBOR work as usual; anything that allocates mlocked
+//! memory, such as KES or mlocked DSIGN signing keys, fails with
+//! [`MLockedError::Unsupported`].
 
 #![allow(clippy::unreadable_literal)]
 #![cfg_attr(test, allow(clippy::unwrap_used))]
 #![cfg_attr(test, allow(clippy::panic))]
 
+#[cfg(all(target_family = "wasm", feature = "secure-memory"))]
+compile_error!(
+    "the `secure-memory` feature needs mlock and is unavailable on wasm; build \
+     cardano-crypto-class with `default-features = false` (and `wasm-entropy` \
+     for system entropy)"
+);
+
+#[cfg(feature = "bls12-381")]
+pub mod bls12_381;
+pub mod capability;
 pub mod direct_serialise;
 pub mod dsign;
 pub mod ffi;
 pub mod hash;
 pub mod kes;
+pub mod keys;
 pub mod mlocked_bytes;
 #[cfg(feature = "mlocked-metrics")]
 pub mod mlocked_metrics;
 pub mod mlocked_seed;
 pub mod packed_bytes;
 pub mod pinned_sized_bytes;
+pub mod role;
 pub mod seed;
+pub mod signable;
+#[cfg(feature = "signer-service")]
+pub mod signer_service;
 pub mod util;
 pub mod vrf;
+#[cfg(feature = "workflows")]
+pub mod workflows;
 
 pub use seed::{
-    Seed, SeedBytesExhausted, SeedRng, expand_seed, get_bytes_from_seed,
-    get_bytes_from_seed_either, get_bytes_from_seed_t, get_seed_bytes, get_seed_size,
-    mk_seed_from_bytes, read_seed_from_system_entropy, run_with_seed, split_seed,
+    Seed, SeedBytesExhausted, SeedRng, expand_seed, expand_seed_labelled, expand_seed_with,
+    get_bytes_from_seed, get_bytes_from_seed_either, get_bytes_from_seed_t, get_seed_bytes,
+    get_seed_size, mk_seed_from_bytes, read_seed_from_system_entropy, read_seed_with,
+    run_with_seed, split_seed,
 };
 
 pub use packed_bytes::{
-    PackedBytes, PackedBytesError, pack_bytes, pack_bytes_maybe, pack_pinned_bytes, unpack_bytes,
-    unpack_pinned_bytes, xor_packed_bytes,
+    PackedBytes, PackedBytes8, PackedBytes28, PackedBytes32, PackedBytesError, pack_bytes,
+    pack_bytes_maybe, pack_pinned_bytes, unpack_bytes, unpack_pinned_bytes, xor_packed_bytes,
 };
 
+pub use signable::{HeaderBodyBytes, NonceProofMessage, OcertSignable};
+
 pub use util::{
-    DecodeHexError, Empty, SignableRepresentation, bytes_to_natural, decode_hex_byte_string,
-    decode_hex_string, get_random_word64, natural_to_bytes, read_binary_natural,
-    read_binary_word64, slice, splits_at, write_binary_natural, write_binary_word64,
+    DecodeHexError, Empty, SignableRepresentation, SignableStream, bytes_to_natural,
+    decode_hex_byte_string, decode_hex_string, get_random_word64, natural_to_bytes,
+    read_binary_natural, read_binary_word64, slice, splits_at, write_binary_natural,
+    write_binary_word64,
 };
 
 pub use direct_serialise::{
-    DirectDeserialise, DirectResult, DirectSerialise, SizeCheckError, direct_deserialise_buf,
-    direct_deserialise_buf_checked, direct_deserialise_from, direct_deserialise_from_checked,
-    direct_serialise_buf, direct_serialise_buf_checked, direct_serialise_to,
-    direct_serialise_to_checked,
+    DirectDeserialise, DirectResult, DirectSerialise, DirectSerialiseError, SizeCheckError,
+    direct_deserialise_buf, direct_deserialise_buf_checked, direct_deserialise_from,
+    direct_deserialise_from_checked, direct_serialise_buf, direct_serialise_buf_checked,
+    direct_serialise_to, direct_serialise_to_checked,
 };
 
-pub use ffi::{SizedMutPtr, SizedPtr};
+pub use ffi::{SizedMutPtr, SizedPtr, copy_sized};
 
 pub use pinned_sized_bytes::{PinnedSizedBytes, PinnedSizedBytesError};
 
 pub use mlocked_bytes::{
-    MLockedAllocator, MLockedBytes, MLockedError, MLockedSizedBytes, copy_mem, mlocked_alloc_bytes,
-    mlocked_alloc_bytes_aligned, mlocked_alloc_bytes_zeroed, mlocked_allocator, zero_mem,
+    MLockedAllocator, MLockedBytes, MLockedError, MLockedSizedBytes, copy_mem,
+    current_memlock_limit, mlocked_alloc_bytes, mlocked_alloc_bytes_aligned,
+    mlocked_alloc_bytes_zeroed, mlocked_allocator, preflight_check, zero_mem,
 };
 
 pub use mlocked_seed::MLockedSeed;
 
 pub use dsign::{
-    DsignAlgorithm, DsignError, DsignMAlgorithm, DsignMError, SignedDsign, UnsoundDsignMAlgorithm,
-    fail_size_check, seed_size, signed_dsign, signed_dsign_m, size_signature, size_signing_key,
-    size_verification_key, verify_signed_dsign,
+    DsignAlgorithm, DsignError, DsignMAlgorithm, DsignMError, DsignVectoredVerify, SignedDsign,
+    UnsoundDsignMAlgorithm, fail_size_check, gen_key_domain_separated, seed_size, signed_dsign,
+    signed_dsign_m, signed_dsign_streaming, size_signature, size_signing_key,
+    size_verification_key, verify_signed_dsign, verify_signed_dsign_streaming,
 };
 
-pub use dsign::ed25519::{Ed25519, Ed25519Signature, Ed25519SigningKey, Ed25519VerificationKey};
+pub use dsign::ed25519::{
+    Ed25519, Ed25519Signature, Ed25519SigningKey, Ed25519VerificationKey, Ed25519VerificationKeyRef,
+};
 pub use dsign::ed25519_mlocked::Ed25519MLockedSigningKey;
 
+#[allow(deprecated)]
+pub use kes::KesHashAlgorithm;
 pub use kes::{
     // Hash algorithms
     Blake2b224,
@@ -85,10 +150,11 @@ pub use kes::{
     CompactSum6Kes,
     CompactSum7Kes,
     // Core KES traits and types
+    HashedVerKeyKes,
     KesAlgorithm,
     KesError,
-    KesHashAlgorithm,
     KesMError,
+    KesVerificationKeyRef,
     OptimizedKesSignature,
     Period,
     // Single KES
@@ -104,4 +170,7 @@ pub use kes::{
     Sum7Kes,
 };
 
-pub use vrf::{CertifiedVRF, OutputVRF, VRFAlgorithm, VRFError, eval_certified, verify_certified};
+pub use vrf::{
+    CertifiedVRF, OutputSize, OutputVRF, VRFAlgorithm, VRFError, eval_certified,
+    gen_key_vrf_domain_separated, verify_certified,
+};
@@ -1,4 +1,6 @@
 use std::io;
+#[cfg(all(target_os = "linux", feature = "memfd-handoff"))]
+use std::os::fd::OwnedFd;
 use std::ptr::{self, NonNull};
 use std::slice;
 
@@ -19,26 +21,208 @@ pub enum MLockedError {
     AllocationFailed,
     #[error("mlock failed: {code}")]
     LockFailed { code: i32 },
+    /// `mlock` was refused because the process's `RLIMIT_MEMLOCK` is too
+    /// low. Memory the process has already locked counts against the limit.
+    #[error(
+        "cannot lock {requested} bytes: RLIMIT_MEMLOCK is {} (hard limit {}); \
+         raise it with `ulimit -l` or `LimitMEMLOCK=` in the service unit",
+        fmt_limit(*soft_limit),
+        fmt_limit(*hard_limit)
+    )]
+    LockLimitExceeded {
+        requested: usize,
+        soft_limit: u64,
+        hard_limit: u64,
+    },
     #[error("alignment must be non-zero")]
     InvalidAlignment,
     #[error("requested size is too large")]
     AllocationTooLarge,
     #[error("random generator failure: {source}")]
     RandomFailed { source: OsError },
+    /// The crate was built without the `secure-memory` feature, so no mlocked
+    /// memory can be allocated (as on `wasm32-unknown-unknown`).
+    #[error("mlocked memory is unavailable: built without the `secure-memory` feature")]
+    Unsupported,
+    /// Reading, writing or sealing a memfd failed. The `Memfd*` variants are
+    /// only returned with `memfd-handoff` on Linux, but exist in every build.
+    #[error("memfd handoff failed: {source}")]
+    Memfd { source: io::Error },
+    #[error("sealed memfd holds {actual} bytes, expected {expected}")]
+    MemfdLength { expected: usize, actual: usize },
+    #[error("memfd is not sealed against writes and resizing")]
+    MemfdNotSealed,
+}
+
+impl MLockedError {
+    /// Stable identifier of this variant (see the crate-level *Error codes*
+    /// section).
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            MLockedError::AllocationFailed => "mlocked.allocation_failed",
+            MLockedError::LockFailed { .. } => "mlocked.lock_failed",
+            MLockedError::LockLimitExceeded { .. } => "mlocked.lock_limit_exceeded",
+            MLockedError::InvalidAlignment => "mlocked.invalid_alignment",
+            MLockedError::AllocationTooLarge => "mlocked.allocation_too_large",
+            MLockedError::RandomFailed { .. } => "mlocked.random_failed",
+            MLockedError::Unsupported => "mlocked.unsupported",
+            MLockedError::Memfd { .. } => "mlocked.memfd",
+            MLockedError::MemfdLength { .. } => "mlocked.memfd_length",
+            MLockedError::MemfdNotSealed => "mlocked.memfd_not_sealed",
+        }
+    }
+
+    /// Whether the failure depends on system state rather than on the
+    /// request: allocation and `mlock` failures (including `RLIMIT_MEMLOCK`
+    /// pressure, which eases as other locked memory is freed), entropy
+    /// failures and memfd I/O may succeed on retry.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            MLockedError::AllocationFailed
+                | MLockedError::LockFailed { .. }
+                | MLockedError::LockLimitExceeded { .. }
+                | MLockedError::RandomFailed { .. }
+                | MLockedError::Memfd { .. }
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MLockedError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        match self {
+            MLockedError::LockFailed { code } => map.serialize_entry("errno", code)?,
+            MLockedError::LockLimitExceeded {
+                requested,
+                soft_limit,
+                hard_limit,
+            } => {
+                map.serialize_entry("requested", requested)?;
+                map.serialize_entry("soft_limit", soft_limit)?;
+                map.serialize_entry("hard_limit", hard_limit)?;
+            },
+            MLockedError::MemfdLength { expected, actual } => {
+                map.serialize_entry("expected", expected)?;
+                map.serialize_entry("actual", actual)?;
+            },
+            _ => {},
+        }
+        map.serialize_entry("message", &self.to_string())?;
+        map.end()
+    }
+}
+
+/// Render an rlimit value, with `RLIM_INFINITY` as `unlimited`.
+fn fmt_limit(limit: u64) -> String {
+    if limit == u64::MAX {
+        "unlimited".to_owned()
+    } else {
+        format!("{limit} bytes")
+    }
+}
+
+/// The process's `RLIMIT_MEMLOCK` as `(soft, hard)` in bytes, with
+/// `u64::MAX` standing for unlimited, or `None` if it cannot be read or the
+/// platform has no such limit.
+///
+/// A process with `CAP_IPC_LOCK` may lock more than this.
+#[must_use]
+pub fn current_memlock_limit() -> Option<(u64, u64)> {
+    #[cfg(unix)]
+    {
+        let mut limit = libc::rlimit {
+            rlim_cur: 0,
+            rlim_max: 0,
+        };
+        // SAFETY: `limit` is a valid, writable `rlimit`.
+        if unsafe { libc::getrlimit(libc::RLIMIT_MEMLOCK, &mut limit) } != 0 {
+            return None;
+        }
+        let to_bytes = |value: libc::rlim_t| {
+            if value == libc::RLIM_INFINITY {
+                u64::MAX
+            } else {
+                u64::try_from(value).unwrap_or(u64::MAX)
+            }
+        };
+        Some((to_bytes(limit.rlim_cur), to_bytes(limit.rlim_max)))
+    }
+    #[cfg(not(unix))]
+    {
+        None
+    }
+}
+
+/// Classify a failed `mlock` of `requested` bytes. `ENOMEM` and `EPERM` are
+/// what the kernel returns when `RLIMIT_MEMLOCK` is exhausted or zero.
+#[cfg(feature = "secure-memory")]
+fn lock_error(err: &io::Error, requested: usize) -> MLockedError {
+    let code = err.raw_os_error().unwrap_or_default();
+    if code == libc::ENOMEM || code == libc::EPERM {
+        if let Some((soft_limit, hard_limit)) = current_memlock_limit() {
+            return MLockedError::LockLimitExceeded {
+                requested,
+                soft_limit,
+                hard_limit,
+            };
+        }
+    }
+    MLockedError::LockFailed { code }
+}
+
+/// Lock and release `required_bytes` of memory, so a process can fail at
+/// startup with [`MLockedError::LockLimitExceeded`] instead of when its
+/// first key is generated.
+///
+/// Memory the process already holds locked is not released, so call this
+/// with the total it will need, before allocating keys.
+///
+/// # Errors
+///
+/// Returns the error allocating a buffer of `required_bytes` with
+/// [`MLockedBytes::new_zeroed`] would.
+pub fn preflight_check(required_bytes: usize) -> Result<(), MLockedError> {
+    MLockedRegion::allocate(required_bytes, true).map(drop)
 }
 
 #[derive(Debug)]
 struct MLockedRegion {
     ptr: NonNull<u8>,
     len: usize,
+    #[cfg_attr(not(feature = "secure-memory"), allow(dead_code))]
     locked: bool,
 }
 
+// SAFETY: the region uniquely owns its allocation, so moving it to another
+// thread moves ownership with it, and `&MLockedRegion` only permits reads.
+unsafe impl Send for MLockedRegion {}
+// SAFETY: see above; there is no interior mutability behind `&self`.
+unsafe impl Sync for MLockedRegion {}
+
 impl MLockedRegion {
     fn allocate(len: usize, zeroed: bool) -> Result<Self, MLockedError> {
         Self::allocate_aligned(len, zeroed, None)
     }
 
+    /// Without `secure-memory` there is no allocator: every request fails
+    /// with [`MLockedError::Unsupported`], so no region is ever created.
+    #[cfg(not(feature = "secure-memory"))]
+    fn allocate_aligned(
+        _len: usize,
+        _zeroed: bool,
+        _align: Option<usize>,
+    ) -> Result<Self, MLockedError> {
+        Err(MLockedError::Unsupported)
+    }
+
+    #[cfg(feature = "secure-memory")]
     fn allocate_aligned(
         len: usize,
         zeroed: bool,
@@ -98,9 +282,7 @@ impl MLockedRegion {
             {
                 record_mlocked_failed_lock();
             }
-            return Err(MLockedError::LockFailed {
-                code: err.raw_os_error().unwrap_or_default(),
-            });
+            return Err(lock_error(&err, alloc_len));
         }
 
         #[cfg(feature = "mlocked-metrics")]
@@ -164,22 +346,26 @@ impl Drop for MLockedRegion {
             }
         }
 
-        if self.locked {
-            // SAFETY: self.ptr was locked with mlock() in allocate_aligned.
-            // Unlocking before freeing to avoid resource leaks.
-            unsafe {
-                libc::munlock(self.ptr.as_ptr().cast(), self.len);
+        #[cfg(feature = "secure-memory")]
+        {
+            if self.locked {
+                // SAFETY: self.ptr was locked with mlock() in allocate_aligned.
+                // Unlocking before freeing to avoid resource leaks.
+                unsafe {
+                    libc::munlock(self.ptr.as_ptr().cast(), self.len);
+                }
             }
-        }
 
-        // SAFETY: self.ptr was allocated by malloc/calloc in allocate_aligned.
-        // This is the final cleanup, and ptr won't be used after this.
-        unsafe {
-            libc::free(self.ptr.as_ptr().cast());
+            // SAFETY: self.ptr was allocated by malloc/calloc in allocate_aligned.
+            // This is the final cleanup, and ptr won't be used after this.
+            unsafe {
+                libc::free(self.ptr.as_ptr().cast());
+            }
         }
     }
 }
 
+#[cfg(feature = "secure-memory")]
 fn round_up_to(value: usize, align: usize) -> Result<usize, MLockedError> {
     if align == 0 {
         return Err(MLockedError::InvalidAlignment);
@@ -310,6 +496,120 @@ impl MLockedBytes {
     }
 }
 
+#[cfg(all(target_os = "linux", feature = "memfd-handoff"))]
+impl MLockedBytes {
+    /// Copy the buffer into a sealed `memfd` suitable for handing to another
+    /// process with `sendmsg(SCM_RIGHTS)`.
+    ///
+    /// The file is sealed with `F_SEAL_WRITE`, `F_SEAL_SHRINK`, `F_SEAL_GROW`
+    /// and `F_SEAL_SEAL`, so neither side can alter it after this returns.
+    /// The memfd pages live in the page cache and are **not** mlocked; the
+    /// receiver should call [`MLockedBytes::from_sealed_memfd`] as soon as the
+    /// descriptor arrives, and the sender should drop its copy once sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MLockedError::Memfd`] if creating, writing or sealing the
+    /// memfd fails.
+    pub fn seal_to_memfd(&self) -> Result<OwnedFd, MLockedError> {
+        memfd::seal_bytes(self.as_slice())
+    }
+
+    /// Copy the contents of a sealed `memfd` into fresh mlocked memory.
+    ///
+    /// The descriptor is consumed and closed before this returns, whether or
+    /// not the copy succeeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The memfd is missing the write / shrink / grow seals
+    /// - The memfd size differs from `expected_len`
+    /// - Reading the memfd fails
+    /// - Memory allocation or `mlock()` fails
+    pub fn from_sealed_memfd(fd: OwnedFd, expected_len: usize) -> Result<Self, MLockedError> {
+        let mut bytes = Self::new_zeroed(expected_len)?;
+        memfd::read_sealed(fd, bytes.as_mut_slice())?;
+        Ok(bytes)
+    }
+}
+
+/// Sealed `memfd` plumbing shared by the key handoff helpers.
+#[cfg(all(target_os = "linux", feature = "memfd-handoff"))]
+pub(crate) mod memfd {
+    use std::fs::File;
+    use std::io::{self, Write};
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::os::unix::fs::FileExt;
+
+    use super::MLockedError;
+
+    const REQUIRED_SEALS: libc::c_int =
+        libc::F_SEAL_WRITE | libc::F_SEAL_SHRINK | libc::F_SEAL_GROW;
+
+    fn io_error(source: io::Error) -> MLockedError {
+        MLockedError::Memfd { source }
+    }
+
+    /// Create a memfd holding `bytes` and seal it.
+    pub(crate) fn seal_bytes(bytes: &[u8]) -> Result<OwnedFd, MLockedError> {
+        // SAFETY: the name is a NUL-terminated C string literal and the flags
+        // are valid for memfd_create(2).
+        let raw = unsafe {
+            libc::memfd_create(
+                c"cardano-key-handoff".as_ptr(),
+                libc::MFD_CLOEXEC | libc::MFD_ALLOW_SEALING,
+            )
+        };
+        if raw < 0 {
+            return Err(io_error(io::Error::last_os_error()));
+        }
+        // SAFETY: memfd_create returned a fresh descriptor owned by nobody else.
+        let mut file = File::from(unsafe { OwnedFd::from_raw_fd(raw) });
+        file.write_all(bytes).map_err(io_error)?;
+
+        // SAFETY: the descriptor is valid for the lifetime of `file`.
+        let rc = unsafe {
+            libc::fcntl(
+                file.as_raw_fd(),
+                libc::F_ADD_SEALS,
+                REQUIRED_SEALS | libc::F_SEAL_SEAL,
+            )
+        };
+        if rc < 0 {
+            return Err(io_error(io::Error::last_os_error()));
+        }
+        Ok(OwnedFd::from(file))
+    }
+
+    /// Check the seals and size of `fd`, then copy its contents into `dst`.
+    /// The descriptor is closed on return.
+    pub(crate) fn read_sealed(fd: OwnedFd, dst: &mut [u8]) -> Result<(), MLockedError> {
+        let file = File::from(fd);
+
+        // SAFETY: the descriptor is valid for the lifetime of `file`.
+        let seals = unsafe { libc::fcntl(file.as_raw_fd(), libc::F_GET_SEALS) };
+        if seals < 0 {
+            return Err(io_error(io::Error::last_os_error()));
+        }
+        if seals & REQUIRED_SEALS != REQUIRED_SEALS {
+            return Err(MLockedError::MemfdNotSealed);
+        }
+
+        let actual = file.metadata().map_err(io_error)?.len();
+        if actual != dst.len() as u64 {
+            return Err(MLockedError::MemfdLength {
+                expected: dst.len(),
+                actual: usize::try_from(actual).unwrap_or(usize::MAX),
+            });
+        }
+
+        // Positional reads: the file offset is shared with every duplicate of
+        // the descriptor, including the sender's.
+        file.read_exact_at(dst, 0).map_err(io_error)
+    }
+}
+
 /// Secure heap allocation backed by `mlock(2)` to prevent swapping.
 pub struct MLockedSizedBytes<const N: usize> {
     region: MLockedRegion,
@@ -417,12 +717,49 @@ impl<const N: usize> MLockedSizedBytes<N> {
         f(SizedMutPtr::new(self.region.as_non_null()))
     }
 
+    /// Allocate a zeroed buffer, initialise it through `f`, and return it
+    /// alongside the callback's result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Memory allocation fails
+    /// - `mlock()` system call fails
+    pub fn create_sized_result<R>(
+        f: impl FnOnce(SizedMutPtr<'_, N>) -> R,
+    ) -> Result<(Self, R), MLockedError> {
+        let mut bytes = Self::new_zeroed()?;
+        let result = bytes.with_sized_ptr_mut(f);
+        Ok((bytes, result))
+    }
+
+    /// Mlocked counterpart of `psbCreateSized`: allocate a zeroed buffer and
+    /// initialise it through `f`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Memory allocation fails
+    /// - `mlock()` system call fails
+    pub fn create_sized(f: impl FnOnce(SizedMutPtr<'_, N>)) -> Result<Self, MLockedError> {
+        Self::create_sized_result(f).map(|(bytes, ())| bytes)
+    }
+
     /// Explicitly zero and unlock the memory before dropping it.
     pub fn finalize(self) {
         drop(self);
     }
 }
 
+impl<const N: usize> From<MLockedSizedBytes<N>> for MLockedBytes {
+    /// Forget the static size, keeping the same mlocked allocation.
+    fn from(bytes: MLockedSizedBytes<N>) -> Self {
+        Self {
+            region: bytes.region,
+        }
+    }
+}
+
 /// Allocator mirroring the Haskell `MLockedAllocator` abstraction.
 #[derive(Clone, Copy, Default)]
 pub struct MLockedAllocator;
@@ -546,6 +883,28 @@ pub unsafe fn copy_mem(dst: *mut u8, src: *const u8, len: usize) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn create_sized_and_nested_pointers() {
+        let source = MLockedSizedBytes::<8>::create_sized(|dst| {
+            crate::ffi::copy_sized(dst, SizedPtr::from_array(b"mlocked!"));
+        })
+        .unwrap();
+        let (copy, len) = source
+            .with_sized_ptr(|src| {
+                source.with_sized_ptr(|again| assert_eq!(src.as_ptr(), again.as_ptr()));
+                MLockedSizedBytes::<8>::create_sized_result(|dst| {
+                    crate::ffi::copy_sized(dst, src);
+                    dst.len()
+                })
+            })
+            .unwrap();
+        assert_eq!(copy.as_slice(), b"mlocked!");
+        assert_eq!(len, 8);
+
+        let unsized_bytes = MLockedBytes::from(copy);
+        assert_eq!(unsized_bytes.as_slice(), b"mlocked!");
+    }
+
     #[test]
     fn allocate_zeroed() {
         let buffer = MLockedSizedBytes::<16>::new_zeroed().unwrap();
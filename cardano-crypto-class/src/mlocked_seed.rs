@@ -1,16 +1,65 @@
 use rand::rngs::OsRng;
-use rand_core::TryRngCore;
+use rand_core::{CryptoRng, RngCore, TryRngCore};
+use subtle::ConstantTimeEq;
 
 use crate::direct_serialise::{DirectDeserialise, DirectResult, DirectSerialise};
 use crate::ffi::{SizedMutPtr, SizedPtr};
-use crate::mlocked_bytes::{MLockedError, MLockedSizedBytes};
+use crate::mlocked_bytes::{MLockedBytes, MLockedError, MLockedSizedBytes};
+use crate::seed::Seed;
 
 /// Seed stored in mlocked memory to avoid swapping secrets to disk.
+///
+/// There is deliberately no `PartialEq`: compare seeds with
+/// [`MLockedSeed::ct_eq`], which does not leak the position of the first
+/// differing byte through timing.
 pub struct MLockedSeed<const N: usize> {
     bytes: MLockedSizedBytes<N>,
 }
 
 impl<const N: usize> MLockedSeed<N> {
+    /// Seed length in bytes.
+    pub const LEN: usize = N;
+
+    /// Seed length in bytes; always [`Self::LEN`].
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Whether the seed holds no bytes (only for `N == 0`).
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Constant-time comparison of the seed contents.
+    #[must_use]
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        bool::from(self.as_bytes().ct_eq(other.as_bytes()))
+    }
+
+    /// Run `f` with a [`Seed`] holding these bytes, for the non-mlocked key
+    /// generation paths (e.g. [`DsignAlgorithm::gen_key`]).
+    ///
+    /// The seed is backed by a fresh mlocked copy, so the material does not
+    /// pass through the regular heap: key generation borrows those bytes in
+    /// place, and [`Seed::take`] leaves its remainder in the same copy. The
+    /// copy is zeroed and freed when the last clone of the seed is dropped,
+    /// normally when `f` returns. Bytes `f` copies out (e.g. via
+    /// [`Seed::to_vec`]) are zeroed on drop but not mlocked, and the signing
+    /// key itself lives wherever its algorithm puts it.
+    ///
+    /// [`DsignAlgorithm::gen_key`]: crate::dsign::DsignAlgorithm::gen_key
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mlocked copy cannot be allocated or locked.
+    pub fn use_as_seed<R>(&self, f: impl FnOnce(&Seed) -> R) -> Result<R, MLockedError> {
+        let mut copy = MLockedBytes::new(N)?;
+        copy.as_mut_slice().copy_from_slice(self.as_bytes());
+        Ok(f(&Seed::from_mlocked(copy)))
+    }
+
     /// Allocate a new seed without initialising the contents.
     ///
     /// # Errors
@@ -61,6 +110,14 @@ impl<const N: usize> MLockedSeed<N> {
             .map_err(|err| MLockedError::RandomFailed { source: err })
     }
 
+    /// Fill the seed with bytes drawn from a caller-provided RNG.
+    ///
+    /// Use this in tests that need reproducible seeds; production code should
+    /// prefer [`MLockedSeed::fill_random`], which reads OS entropy.
+    pub fn fill_random_with<R: RngCore + CryptoRng + ?Sized>(&mut self, rng: &mut R) {
+        rng.fill_bytes(self.bytes.as_mut_slice());
+    }
+
     /// Construct a fresh random seed.
     ///
     /// # Errors
@@ -75,6 +132,21 @@ impl<const N: usize> MLockedSeed<N> {
         Ok(seed)
     }
 
+    /// Construct a seed filled from a caller-provided RNG.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Memory allocation fails
+    /// - `mlock()` system call fails
+    pub fn new_random_with<R: RngCore + CryptoRng + ?Sized>(
+        rng: &mut R,
+    ) -> Result<Self, MLockedError> {
+        let mut seed = Self::new_zeroed()?;
+        seed.fill_random_with(rng);
+        Ok(seed)
+    }
+
     /// Execute `f` with a raw pointer to the underlying bytes.
     pub fn with_c_ptr<R>(&self, f: impl FnOnce(*const u8, usize) -> R) -> R {
         self.bytes.with_c_ptr(|ptr| f(ptr, N))
@@ -120,7 +192,7 @@ impl<const N: usize> DirectSerialise for MLockedSeed<N> {
 
 impl<const N: usize> DirectDeserialise for MLockedSeed<N> {
     fn direct_deserialise(f: &mut dyn FnMut(&mut [u8]) -> DirectResult<()>) -> DirectResult<Self> {
-        let mut seed = Self::new_zeroed().expect("failed to allocate mlocked seed");
+        let mut seed = Self::new_zeroed()?;
         f(seed.as_mut_bytes())?;
         Ok(seed)
     }
@@ -137,6 +209,16 @@ mod tests {
         assert!(seed.as_bytes().iter().any(|&b| b != 0));
     }
 
+    #[test]
+    fn injected_rng_is_reproducible() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let first = MLockedSeed::<32>::new_random_with(&mut StdRng::seed_from_u64(42)).unwrap();
+        let second = MLockedSeed::<32>::new_random_with(&mut StdRng::seed_from_u64(42)).unwrap();
+        assert_eq!(first.as_bytes(), second.as_bytes());
+    }
+
     #[test]
     fn direct_serialise_roundtrip() {
         let mut seed = MLockedSeed::<16>::new_zeroed().unwrap();
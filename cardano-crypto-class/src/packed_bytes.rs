@@ -1,15 +1,54 @@
+//! Fixed-size byte strings stored inline, `Cardano.Crypto.PackedBytes`.
+//!
+//! Haskell's `PackedBytes` has dedicated `PackedBytes8`, `PackedBytes28`, and
+//! `PackedBytes32` constructors that unpack hashes into machine words so a
+//! key hash costs no separate heap object. Here the length is a const
+//! parameter, so every `PackedBytes<N>` is a plain `[u8; N]`: `#[repr(transparent)]`,
+//! `Copy`, alignment 1, exactly `N` bytes, and never heap-allocated, whatever
+//! `N` is. There is no boxed fallback to switch to. The [`PackedBytes8`],
+//! [`PackedBytes28`], and [`PackedBytes32`] aliases name the sizes the
+//! Haskell constructors specialise.
+//!
+//! `Eq`, `Ord`, and `Hash` agree with those of `[u8]` for the same content,
+//! which is what makes the [`Borrow<[u8]>`](core::borrow::Borrow) impl sound:
+//! a `HashMap<PackedBytes<28>, _>` or `BTreeMap` can be queried with a plain
+//! byte slice.
+//!
+//! `Display` and `FromStr` use lowercase hex, the latter accepting an
+//! optional `0x` prefix like [`decode_hex_string`]. With the `serde` feature
+//! the bytes are a CBOR byte string and hex text in human-readable formats
+//! such as JSON, never a sequence of integers; decoding checks the length
+//! against `N` and fills the inline array directly.
+
+use core::borrow::Borrow;
 use core::cmp::Ordering;
 use core::fmt;
+use core::str::FromStr;
+
+use crate::util::{DecodeHexError, decode_hex_string};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Fixed-size packed byte array with efficient equality and XOR support.
-#[derive(Clone, Eq, PartialEq, Hash)]
+///
+/// The bytes are stored inline; see the [module documentation](self) for the
+/// layout guarantees.
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+#[repr(transparent)]
 pub struct PackedBytes<const N: usize> {
     data: [u8; N],
 }
 
+/// Eight packed bytes, Haskell's `PackedBytes8`.
+pub type PackedBytes8 = PackedBytes<8>;
+
+/// 28 packed bytes (a Blake2b-224 key hash), Haskell's `PackedBytes28`.
+pub type PackedBytes28 = PackedBytes<28>;
+
+/// 32 packed bytes (a Blake2b-256 hash), Haskell's `PackedBytes32`.
+pub type PackedBytes32 = PackedBytes<32>;
+
 impl<const N: usize> PackedBytes<N> {
     /// Construct packed bytes from an array.
     #[must_use]
@@ -17,6 +56,25 @@ impl<const N: usize> PackedBytes<N> {
         Self { data }
     }
 
+    /// Number of bytes stored inline, without a heap allocation. Always `N`:
+    /// every `PackedBytes` is inline.
+    #[must_use]
+    pub const fn inline_capacity() -> usize {
+        N
+    }
+
+    /// Number of bytes, `N`.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Whether the value holds no bytes, that is `N == 0`.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
     /// View the packed bytes as a slice.
     #[must_use]
     pub fn as_slice(&self) -> &[u8] {
@@ -51,6 +109,26 @@ impl<const N: usize> fmt::Debug for PackedBytes<N> {
     }
 }
 
+impl<const N: usize> fmt::Display for PackedBytes<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.data {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> FromStr for PackedBytes<N> {
+    type Err = DecodeHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = decode_hex_string(s, N)?;
+        let mut data = [0u8; N];
+        data.copy_from_slice(&bytes);
+        Ok(Self::new(data))
+    }
+}
+
 impl<const N: usize> Ord for PackedBytes<N> {
     fn cmp(&self, other: &Self) -> Ordering {
         self.data.cmp(&other.data)
@@ -69,6 +147,12 @@ impl<const N: usize> AsRef<[u8]> for PackedBytes<N> {
     }
 }
 
+impl<const N: usize> Borrow<[u8]> for PackedBytes<N> {
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
 impl<const N: usize> From<[u8; N]> for PackedBytes<N> {
     fn from(data: [u8; N]) -> Self {
         Self::new(data)
@@ -81,13 +165,14 @@ impl<const N: usize> From<PackedBytes<N>> for [u8; N] {
     }
 }
 
+// A CBOR byte string, hex text in JSON.
 #[cfg(feature = "serde")]
 impl<const N: usize> Serialize for PackedBytes<N> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_bytes(&self.data)
+        cardano_binary::bytes_or_hex::serialize(&self.data, serializer)
     }
 }
 
@@ -146,7 +231,7 @@ impl<'de, const N: usize> Deserialize<'de> for PackedBytes<N> {
             }
         }
 
-        deserializer.deserialize_bytes(Visitor::<N>)
+        cardano_binary::bytes_or_hex::deserialize_with(deserializer, Visitor::<N>)
     }
 }
 
@@ -271,12 +356,54 @@ mod tests {
         assert!(a < b);
     }
 
+    #[test]
+    fn layout_is_the_bare_array() {
+        assert_eq!(core::mem::size_of::<PackedBytes8>(), 8);
+        assert_eq!(core::mem::size_of::<PackedBytes28>(), 28);
+        assert_eq!(core::mem::size_of::<PackedBytes32>(), 32);
+        assert_eq!(core::mem::align_of::<PackedBytes32>(), 1);
+        assert_eq!(core::mem::size_of::<Option<PackedBytes28>>(), 29);
+        assert_eq!(PackedBytes28::inline_capacity(), 28);
+    }
+
+    #[test]
+    fn slice_lookups_find_packed_keys() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let key = pack_bytes::<4>(b"key!", 0);
+        let hashed: HashSet<PackedBytes<4>> = [key].into_iter().collect();
+        let ordered: BTreeSet<PackedBytes<4>> = [key].into_iter().collect();
+        assert!(hashed.contains(&b"key!"[..]));
+        assert!(ordered.contains(&b"key!"[..]));
+        assert!(!hashed.contains(&b"key?"[..]));
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn serde_roundtrip() {
         let packed = pack_bytes::<4>(b"test", 0);
         let json = serde_json::to_string(&packed).unwrap();
+        assert_eq!(json, "\"74657374\"");
         let back: PackedBytes<4> = serde_json::from_str(&json).unwrap();
         assert_eq!(packed, back);
     }
+
+    #[test]
+    fn display_is_lowercase_hex() {
+        let packed = PackedBytes::new([0xde, 0xad, 0xBE, 0xef]);
+        assert_eq!(packed.to_string(), "deadbeef");
+        assert_eq!(packed.len(), 4);
+    }
+
+    #[test]
+    fn from_str_checks_length() {
+        assert_eq!(
+            "0x0102".parse::<PackedBytes<3>>(),
+            Err(DecodeHexError::LengthMismatch {
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert!("zz".parse::<PackedBytes<1>>().is_err());
+    }
 }
@@ -112,12 +112,25 @@ impl<const N: usize> PinnedSizedBytes<N> {
         f(self.data.as_ptr(), N)
     }
 
-    /// Run `f` with a sized pointer wrapper mirroring the Haskell API.
+    /// Run `f` with a sized pointer wrapper mirroring the Haskell API
+    /// (`psbUseAsSizedPtr`). The pointer is only valid inside `f`:
+    ///
+    /// ```compile_fail
+    /// use cardano_crypto_class::PinnedSizedBytes;
+    ///
+    /// let bytes = PinnedSizedBytes::<4>::from_array(*b"DATA");
+    /// let escaped = bytes.with_sized_ptr(|ptr| ptr);
+    /// ```
     pub fn with_sized_ptr<R>(&self, f: impl FnOnce(SizedPtr<'_, N>) -> R) -> R {
         let ptr = NonNull::from(&*self.data).cast::<u8>();
         f(SizedPtr::new(ptr))
     }
 
+    /// Run `f` with a mutable sized pointer to the buffer.
+    pub fn with_sized_ptr_mut<R>(&mut self, f: impl FnOnce(SizedMutPtr<'_, N>) -> R) -> R {
+        f(SizedMutPtr::from_array(&mut self.data))
+    }
+
     /// Allocate a new pinned buffer, execute `f` with a mutable pointer, and
     /// return the initialised bytes alongside the function's result.
     pub fn create_result<R>(mut f: impl FnMut(*mut u8) -> R) -> (Self, R) {
@@ -302,6 +315,26 @@ fn constant_time_compare<const N: usize>(lhs: &[u8; N], rhs: &[u8; N]) -> std::c
 mod tests {
     use super::*;
 
+    #[test]
+    fn nested_sized_ptrs_copy_between_buffers() {
+        let source = PinnedSizedBytes::<4>::from_array(*b"DATA");
+        let mut target = PinnedSizedBytes::<4>::zeroed();
+        source.with_sized_ptr(|outer| {
+            source.with_sized_ptr(|inner| assert_eq!(outer.as_ptr(), inner.as_ptr()));
+            target.with_sized_ptr_mut(|dst| crate::ffi::copy_sized(dst, outer));
+        });
+        assert_eq!(target.as_bytes(), b"DATA");
+    }
+
+    #[test]
+    fn create_sized_copies_from_array() {
+        let array = *b"SEED";
+        let bytes = PinnedSizedBytes::<4>::create_sized(|dst| {
+            crate::ffi::copy_sized(dst, SizedPtr::from_array(&array));
+        });
+        assert_eq!(bytes.as_bytes(), b"SEED");
+    }
+
     #[test]
     fn from_slice_exact() {
         let value = PinnedSizedBytes::<4>::from_slice(b"ABCD").unwrap();
@@ -0,0 +1,623 @@
+//! Role tagging for key material.
+//!
+//! Payment, stake and cold keys are all Ed25519 keys, so nothing in their
+//! types stops a stake key from being passed where a payment key was meant.
+//! [`RoleTagged`] attaches a zero-sized role marker to a key at creation or
+//! import time. Functions that care about the role accept only
+//! `RoleTagged<TheirRole, _>`, and the helpers in this module ([`sign_dsign`],
+//! [`sign_kes`], [`eval_vrf`], ...) only accept the roles that make sense for
+//! the underlying primitive.
+//!
+//! Keys are tagged by the generators in this module, by
+//! [`RoleTagged::from_text_envelope`], which checks the envelope type string
+//! for the role, or by [`AssumeRole::assume_role`], the single escape hatch for
+//! code migrating from untagged keys.
+//!
+//! KES envelope types name the scheme, `KesVerificationKey_ed25519_kes_2^6`
+//! for [`Sum6Kes`](crate::kes::Sum6Kes), so KES verification keys go through
+//! [`RoleTagged::to_kes_text_envelope`] and
+//! [`RoleTagged::from_kes_text_envelope`], which take the algorithm.
+//!
+//! ```rust
+//! use cardano_crypto_class::Ed25519;
+//! use cardano_crypto_class::role::{
+//!     PaymentRole, RoleTagged, derive_dsign_verification_key, gen_dsign_key, sign_dsign,
+//!     verify_dsign,
+//! };
+//!
+//! let sk = gen_dsign_key::<PaymentRole, Ed25519>(&[7u8; 32]).unwrap();
+//! let vk = derive_dsign_verification_key::<PaymentRole, Ed25519>(&sk);
+//! let signature = sign_dsign::<PaymentRole, Ed25519>(&(), b"tx body", &sk).unwrap();
+//! verify_dsign::<PaymentRole, Ed25519>(&(), &vk, b"tx body", &signature).unwrap();
+//!
+//! let envelope = vk.to_text_envelope();
+//! assert_eq!(envelope.type_, "PaymentVerificationKeyShelley_ed25519");
+//! let imported = RoleTagged::<PaymentRole, _>::from_text_envelope(&envelope).unwrap();
+//! assert_eq!(imported, vk);
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use cardano_binary::{Value, decode_value, encode_value, untraced};
+use thiserror::Error;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::dsign::ed25519::{Ed25519SigningKey, Ed25519VerificationKey};
+use crate::dsign::{DsignAlgorithm, DsignError};
+use crate::kes::{KesAlgorithm, KesError, KesMError, Period};
+use crate::vrf::praos::{PraosSigningKey, PraosVerificationKey};
+use crate::vrf::{OutputVRF, VRFAlgorithm};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A key role.
+pub trait RoleName: sealed::Sealed {
+    /// Human readable role name used in envelope descriptions.
+    const NAME: &'static str;
+}
+
+/// Roles whose text envelope type strings are fixed, whatever the key's
+/// algorithm. [`KesRole`] is not one: its type names the KES depth (see
+/// [`kes_envelope_type`]).
+pub trait EnvelopeRole: RoleName {
+    /// Text envelope `type` of signing keys with this role.
+    const SIGNING_KEY_TYPE: &'static str;
+    /// Text envelope `type` of verification keys with this role.
+    const VERIFICATION_KEY_TYPE: &'static str;
+}
+
+/// Roles whose keys are plain DSIGN keys.
+pub trait DsignRole: RoleName {}
+
+macro_rules! define_role {
+    ($(#[$doc:meta])* $role:ident, $name:literal) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $role;
+
+        impl sealed::Sealed for $role {}
+
+        impl RoleName for $role {
+            const NAME: &'static str = $name;
+        }
+    };
+    ($(#[$doc:meta])* $role:ident, $name:literal, $signing:literal, $verification:literal) => {
+        define_role!($(#[$doc])* $role, $name);
+
+        impl EnvelopeRole for $role {
+            const SIGNING_KEY_TYPE: &'static str = $signing;
+            const VERIFICATION_KEY_TYPE: &'static str = $verification;
+        }
+    };
+}
+
+define_role!(
+    /// Keys controlling payment addresses.
+    PaymentRole,
+    "Payment",
+    "PaymentSigningKeyShelley_ed25519",
+    "PaymentVerificationKeyShelley_ed25519"
+);
+define_role!(
+    /// Keys controlling stake credentials.
+    StakeRole,
+    "Stake",
+    "StakeSigningKeyShelley_ed25519",
+    "StakeVerificationKeyShelley_ed25519"
+);
+define_role!(
+    /// Stake pool operator (cold) keys.
+    ColdRole,
+    "Stake Pool Operator",
+    "StakePoolSigningKey_ed25519",
+    "StakePoolVerificationKey_ed25519"
+);
+define_role!(
+    /// Key-evolving hot keys used to sign block headers.
+    KesRole,
+    "KES"
+);
+define_role!(
+    /// VRF keys used for leader election.
+    VrfRole,
+    "VRF",
+    "VrfSigningKey_PraosVRF",
+    "VrfVerificationKey_PraosVRF"
+);
+
+impl DsignRole for PaymentRole {}
+impl DsignRole for StakeRole {}
+impl DsignRole for ColdRole {}
+
+/// A key of type `K` tagged with the role `R`.
+///
+/// The wrapper deliberately does not implement `Deref`; use
+/// [`RoleTagged::inner`] to reach the key. With the `serde` feature the
+/// wrapper serialises exactly like `K`, so it can replace a bare key field
+/// whose role is already fixed by the surrounding structure.
+pub struct RoleTagged<R, K> {
+    inner: K,
+    role: PhantomData<fn() -> R>,
+}
+
+impl<R, K> RoleTagged<R, K> {
+    fn tag(inner: K) -> Self {
+        Self {
+            inner,
+            role: PhantomData,
+        }
+    }
+
+    /// Borrow the underlying key.
+    #[must_use]
+    pub fn inner(&self) -> &K {
+        &self.inner
+    }
+
+    /// Drop the role and return the underlying key.
+    #[must_use]
+    pub fn into_inner(self) -> K {
+        self.inner
+    }
+}
+
+impl<R, K: Clone> Clone for RoleTagged<R, K> {
+    fn clone(&self) -> Self {
+        Self::tag(self.inner.clone())
+    }
+}
+
+impl<R, K: PartialEq> PartialEq for RoleTagged<R, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<R, K: Eq> Eq for RoleTagged<R, K> {}
+
+impl<R: RoleName, K: fmt::Debug> fmt::Debug for RoleTagged<R, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RoleTagged")
+            .field(&R::NAME)
+            .field(&self.inner)
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<R, K: serde::Serialize> serde::Serialize for RoleTagged<R, K> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.inner.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, R, K: serde::Deserialize<'de>> serde::Deserialize<'de> for RoleTagged<R, K> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        K::deserialize(deserializer).map(Self::tag)
+    }
+}
+
+/// Migration adapter for untagged keys.
+pub trait AssumeRole: Sized {
+    /// Tag `self` with role `R` without any check.
+    ///
+    /// This is the single escape hatch from the role discipline: every other
+    /// way of obtaining a [`RoleTagged`] key either creates the key for the
+    /// role or checks the role recorded alongside it. Prefer the generators
+    /// and [`RoleTagged::from_text_envelope`] in new code.
+    #[must_use]
+    fn assume_role<R: RoleName>(self) -> RoleTagged<R, Self> {
+        RoleTagged::tag(self)
+    }
+}
+
+impl<K> AssumeRole for K {}
+
+/// Whether a key is the signing or the verification half of a pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyKind {
+    Signing,
+    Verification,
+}
+
+/// Keys that can be stored in a text envelope as raw bytes.
+pub trait TextEnvelopeKey: Sized {
+    /// Which half of a key pair this is.
+    const KIND: KeyKind;
+
+    /// Raw key bytes stored in the envelope.
+    fn to_raw_bytes(&self) -> Vec<u8>;
+
+    /// Parse raw key bytes read from an envelope.
+    fn from_raw_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+impl TextEnvelopeKey for Ed25519SigningKey {
+    const KIND: KeyKind = KeyKind::Signing;
+
+    fn to_raw_bytes(&self) -> Vec<u8> {
+        crate::dsign::ed25519::Ed25519::raw_serialize_signing_key(self)
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Option<Self> {
+        crate::dsign::ed25519::Ed25519::raw_deserialize_signing_key(bytes)
+    }
+}
+
+impl TextEnvelopeKey for Ed25519VerificationKey {
+    const KIND: KeyKind = KeyKind::Verification;
+
+    fn to_raw_bytes(&self) -> Vec<u8> {
+        crate::dsign::ed25519::Ed25519::raw_serialize_verification_key(self)
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Option<Self> {
+        crate::dsign::ed25519::Ed25519::raw_deserialize_verification_key(bytes)
+    }
+}
+
+impl TextEnvelopeKey for PraosSigningKey {
+    const KIND: KeyKind = KeyKind::Signing;
+
+    fn to_raw_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::from_bytes(bytes).ok()
+    }
+}
+
+impl TextEnvelopeKey for PraosVerificationKey {
+    const KIND: KeyKind = KeyKind::Verification;
+
+    fn to_raw_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::from_bytes(bytes).ok()
+    }
+}
+
+/// The `cardano-cli` text envelope: a type string, a free-form description
+/// and the CBOR encoding of the raw key bytes.
+///
+/// The payload of a signing key envelope is the secret key itself: it is
+/// wiped when the envelope is dropped and `Debug` does not print it.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextEnvelope {
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_: String,
+    pub description: String,
+    #[cfg_attr(feature = "serde", serde(rename = "cborHex"))]
+    pub cbor_hex: Zeroizing<String>,
+}
+
+impl fmt::Debug for TextEnvelope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("TextEnvelope");
+        debug
+            .field("type_", &self.type_)
+            .field("description", &self.description);
+        if self.type_.contains("SigningKey") {
+            debug.field("cbor_hex", &"<redacted>");
+        } else {
+            debug.field("cbor_hex", &*self.cbor_hex);
+        }
+        debug.finish()
+    }
+}
+
+/// Error raised when importing a role-tagged key from a text envelope.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum TextEnvelopeError {
+    #[error("envelope type {found:?} does not match the expected {expected:?}")]
+    WrongType { expected: String, found: String },
+    #[error("envelope payload is not valid hex")]
+    InvalidHex,
+    #[error("envelope payload is not a CBOR byte string")]
+    InvalidCbor,
+    #[error("envelope payload is not a valid key")]
+    InvalidKey,
+}
+
+impl<R: EnvelopeRole, K: TextEnvelopeKey> RoleTagged<R, K> {
+    fn envelope_type() -> &'static str {
+        match K::KIND {
+            KeyKind::Signing => R::SIGNING_KEY_TYPE,
+            KeyKind::Verification => R::VERIFICATION_KEY_TYPE,
+        }
+    }
+
+    /// Encode the key in a text envelope whose type string names the role.
+    ///
+    /// # Panics
+    ///
+    /// Panics if CBOR encoding into an in-memory buffer fails, which cannot
+    /// happen for a byte string.
+    #[must_use]
+    pub fn to_text_envelope(&self) -> TextEnvelope {
+        let raw = Zeroizing::new(self.inner.to_raw_bytes());
+        TextEnvelope::from_raw_key::<R>(Self::envelope_type(), K::KIND, &raw)
+    }
+
+    /// Import a key from a text envelope, checking that its type string is
+    /// the one used for role `R`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TextEnvelopeError::WrongType`] if the envelope belongs to a
+    /// different role or key kind, and another variant if the payload does
+    /// not decode to a key.
+    pub fn from_text_envelope(envelope: &TextEnvelope) -> Result<Self, TextEnvelopeError> {
+        let bytes = envelope.raw_key(Self::envelope_type())?;
+        K::from_raw_bytes(&bytes)
+            .map(Self::tag)
+            .ok_or(TextEnvelopeError::InvalidKey)
+    }
+}
+
+/// `RoleTagged<KesRole, _>` verification keys; the envelope type names the
+/// KES algorithm, which the key type alone does not identify.
+impl<V> RoleTagged<KesRole, V> {
+    /// Encode a `K` verification key in a text envelope of type
+    /// [`kes_envelope_type::<K>(KeyKind::Verification)`](kes_envelope_type).
+    ///
+    /// # Panics
+    ///
+    /// Panics if CBOR encoding into an in-memory buffer fails, which cannot
+    /// happen for a byte string.
+    #[must_use]
+    pub fn to_kes_text_envelope<K>(&self) -> TextEnvelope
+    where
+        K: KesAlgorithm<VerificationKey = V>,
+    {
+        TextEnvelope::from_raw_key::<KesRole>(
+            &kes_envelope_type::<K>(KeyKind::Verification),
+            KeyKind::Verification,
+            &K::raw_serialize_verification_key_kes(&self.inner),
+        )
+    }
+
+    /// Import a `K` verification key, checking that the envelope type names
+    /// `K`'s depth.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TextEnvelopeError::WrongType`] if the envelope holds another
+    /// key kind or a KES key of another depth, and another variant if the
+    /// payload does not decode to a key.
+    pub fn from_kes_text_envelope<K>(envelope: &TextEnvelope) -> Result<Self, TextEnvelopeError>
+    where
+        K: KesAlgorithm<VerificationKey = V>,
+    {
+        let bytes = envelope.raw_key(&kes_envelope_type::<K>(KeyKind::Verification))?;
+        K::raw_deserialize_verification_key_kes(&bytes)
+            .map(Self::tag)
+            .ok_or(TextEnvelopeError::InvalidKey)
+    }
+}
+
+/// The `cardano-cli` text envelope type of `K`'s keys: `KesSigningKey_` or
+/// `KesVerificationKey_` followed by Haskell's `algorithmNameKES`, which is
+/// `ed25519_kes` for a single-period key and `ed25519_kes_2^N` for a sum
+/// over `2^N` periods, as in [`keys::tagged`](crate::keys::tagged).
+#[must_use]
+pub fn kes_envelope_type<K: KesAlgorithm>(kind: KeyKind) -> String {
+    let prefix = match kind {
+        KeyKind::Signing => "KesSigningKey",
+        KeyKind::Verification => "KesVerificationKey",
+    };
+    match K::total_periods().trailing_zeros() {
+        0 => format!("{prefix}_{}_kes", K::ALGORITHM_NAME),
+        depth => format!("{prefix}_{}_kes_2^{depth}", K::ALGORITHM_NAME),
+    }
+}
+
+impl TextEnvelope {
+    /// Wrap raw key bytes of role `R` in an envelope of type `type_`. The
+    /// copies made on the way are wiped, and the encoding is hidden from any
+    /// `cardano-binary` trace sink.
+    pub(crate) fn from_raw_key<R: RoleName>(type_: &str, kind: KeyKind, raw: &[u8]) -> Self {
+        let mut value = Value::Bytes(raw.to_vec());
+        let cbor = Zeroizing::new(
+            untraced(|| encode_value(&value))
+                .expect("encoding a byte string into a Vec cannot fail"),
+        );
+        if let Value::Bytes(bytes) = &mut value {
+            bytes.zeroize();
+        }
+        let kind = match kind {
+            KeyKind::Signing => "Signing Key",
+            KeyKind::Verification => "Verification Key",
+        };
+        TextEnvelope {
+            type_: type_.to_owned(),
+            description: format!("{} {kind}", R::NAME),
+            cbor_hex: Zeroizing::new(hex::encode(&*cbor)),
+        }
+    }
+
+    /// The raw key bytes of an envelope whose type must be `expected`,
+    /// wiped when dropped like every copy made on the way. The decoding is
+    /// hidden from any `cardano-binary` trace sink.
+    pub(crate) fn raw_key(&self, expected: &str) -> Result<Zeroizing<Vec<u8>>, TextEnvelopeError> {
+        if self.type_ != expected {
+            return Err(TextEnvelopeError::WrongType {
+                expected: expected.to_owned(),
+                found: self.type_.clone(),
+            });
+        }
+        let cbor = Zeroizing::new(
+            hex::decode(&*self.cbor_hex).map_err(|_| TextEnvelopeError::InvalidHex)?,
+        );
+        match untraced(|| decode_value(&cbor)) {
+            Ok(Value::Bytes(bytes)) => Ok(Zeroizing::new(bytes)),
+            _ => Err(TextEnvelopeError::InvalidCbor),
+        }
+    }
+}
+
+/// Generate a DSIGN signing key for role `R` from seed bytes.
+///
+/// # Errors
+///
+/// Returns [`DsignError::WrongLength`] if `seed` is not `D::SEED_SIZE` bytes.
+pub fn gen_dsign_key<R: DsignRole, D: DsignAlgorithm>(
+    seed: &[u8],
+) -> Result<RoleTagged<R, D::SigningKey>, DsignError> {
+    if seed.len() != D::SEED_SIZE {
+        return Err(DsignError::wrong_length(
+            "gen_dsign_key",
+            D::SEED_SIZE,
+            seed.len(),
+        ));
+    }
+    Ok(RoleTagged::tag(D::gen_key_from_seed_bytes(seed)))
+}
+
+/// Derive the verification key of a DSIGN signing key, keeping its role.
+#[must_use]
+pub fn derive_dsign_verification_key<R: DsignRole, D: DsignAlgorithm>(
+    signing_key: &RoleTagged<R, D::SigningKey>,
+) -> RoleTagged<R, D::VerificationKey> {
+    RoleTagged::tag(D::derive_verification_key(&signing_key.inner))
+}
+
+/// Sign `message` with a DSIGN key of role `R`.
+///
+/// # Errors
+///
+/// Returns the error [`DsignAlgorithm::try_sign_bytes`] reports when
+/// `message` is not a valid input for `D`.
+pub fn sign_dsign<R: DsignRole, D: DsignAlgorithm>(
+    context: &D::Context,
+    message: &[u8],
+    signing_key: &RoleTagged<R, D::SigningKey>,
+) -> Result<D::Signature, DsignError> {
+    D::try_sign_bytes(context, message, &signing_key.inner)
+}
+
+/// Verify a DSIGN signature against a verification key of role `R`.
+///
+/// # Errors
+///
+/// Returns an error if the signature is invalid.
+pub fn verify_dsign<R: DsignRole, D: DsignAlgorithm>(
+    context: &D::Context,
+    verification_key: &RoleTagged<R, D::VerificationKey>,
+    message: &[u8],
+    signature: &D::Signature,
+) -> Result<(), DsignError> {
+    D::verify_bytes(context, &verification_key.inner, message, signature)
+}
+
+/// Generate a KES signing key from seed bytes.
+///
+/// # Errors
+///
+/// Returns an error if the seed has the wrong length or key allocation fails.
+pub fn gen_kes_key<K: KesAlgorithm>(
+    seed: &[u8],
+) -> Result<RoleTagged<KesRole, K::SigningKey>, KesMError> {
+    K::gen_key_kes_from_seed_bytes(seed).map(RoleTagged::tag)
+}
+
+/// Derive the verification key of a KES signing key.
+///
+/// # Errors
+///
+/// Returns an error if the verification key cannot be derived.
+pub fn derive_kes_verification_key<K: KesAlgorithm>(
+    signing_key: &RoleTagged<KesRole, K::SigningKey>,
+) -> Result<RoleTagged<KesRole, K::VerificationKey>, KesMError> {
+    K::derive_verification_key(&signing_key.inner).map(RoleTagged::tag)
+}
+
+/// Sign `message` at `period` with a KES key.
+///
+/// # Errors
+///
+/// Returns an error if signing fails.
+pub fn sign_kes<K: KesAlgorithm>(
+    context: &K::Context,
+    period: Period,
+    message: &[u8],
+    signing_key: &RoleTagged<KesRole, K::SigningKey>,
+) -> Result<K::Signature, KesMError> {
+    K::sign_kes(context, period, message, &signing_key.inner)
+}
+
+/// Verify a KES signature at `period`.
+///
+/// # Errors
+///
+/// Returns an error if the signature is invalid.
+pub fn verify_kes<K: KesAlgorithm>(
+    context: &K::Context,
+    verification_key: &RoleTagged<KesRole, K::VerificationKey>,
+    period: Period,
+    message: &[u8],
+    signature: &K::Signature,
+) -> Result<(), KesError> {
+    K::verify_kes(context, &verification_key.inner, period, message, signature)
+}
+
+/// Generate a VRF signing key from seed bytes.
+///
+/// # Errors
+///
+/// Returns [`DsignError::WrongLength`] if `seed` is not `V::SEED_SIZE` bytes.
+pub fn gen_vrf_key<V: VRFAlgorithm>(
+    seed: &[u8],
+) -> Result<RoleTagged<VrfRole, V::SigningKey>, DsignError> {
+    if seed.len() != V::SEED_SIZE {
+        return Err(DsignError::wrong_length(
+            "gen_vrf_key",
+            V::SEED_SIZE,
+            seed.len(),
+        ));
+    }
+    Ok(RoleTagged::tag(V::gen_key_from_seed_bytes(seed)))
+}
+
+/// Derive the verification key of a VRF signing key.
+#[must_use]
+pub fn derive_vrf_verification_key<V: VRFAlgorithm>(
+    signing_key: &RoleTagged<VrfRole, V::SigningKey>,
+) -> RoleTagged<VrfRole, V::VerificationKey> {
+    RoleTagged::tag(V::derive_verification_key(&signing_key.inner))
+}
+
+/// Evaluate the VRF on `message` with a VRF key.
+#[must_use]
+pub fn eval_vrf<V: VRFAlgorithm>(
+    context: &V::Context,
+    message: &[u8],
+    signing_key: &RoleTagged<VrfRole, V::SigningKey>,
+) -> (OutputVRF<V>, V::Proof) {
+    V::evaluate_bytes(context, message, &signing_key.inner)
+}
+
+/// Verify a VRF proof, returning the output on success.
+#[must_use]
+pub fn verify_vrf<V: VRFAlgorithm>(
+    context: &V::Context,
+    verification_key: &RoleTagged<VrfRole, V::VerificationKey>,
+    message: &[u8],
+    proof: &V::Proof,
+) -> Option<OutputVRF<V>> {
+    V::verify_bytes(context, &verification_key.inner, message, proof)
+}
@@ -1,64 +1,138 @@
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 use digest::Digest;
+use hkdf::Hkdf;
 use rand::rngs::OsRng;
 use rand_core::{CryptoRng, RngCore, TryRngCore};
 use thiserror::Error;
+use zeroize::Zeroizing;
+
+use crate::hash::HashAlgorithm;
+use crate::mlocked_bytes::MLockedBytes;
 
 /// Deterministic seed material for cryptographic operations.
-#[derive(Clone, PartialEq, Eq, Hash)]
+///
+/// Seeds usually live on the regular heap, where their bytes are zeroed on
+/// drop. [`MLockedSeed::use_as_seed`] produces seeds backed by mlocked memory
+/// instead; both kinds compare and hash by content.
+///
+/// A seed is a window onto shared storage: splitting one with [`Seed::take`]
+/// or [`split_seed`] does not copy the remainder, so it stays wherever the
+/// original lived.
+///
+/// [`MLockedSeed::use_as_seed`]: crate::mlocked_seed::MLockedSeed::use_as_seed
+#[derive(Clone)]
 pub struct Seed {
-    bytes: Arc<[u8]>,
+    bytes: SeedBytes,
+    start: usize,
+    end: usize,
+}
+
+#[derive(Clone)]
+enum SeedBytes {
+    Heap(Arc<Zeroizing<Vec<u8>>>),
+    Locked(Arc<MLockedBytes>),
 }
 
 impl fmt::Debug for Seed {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("Seed")
-            .field(&format_args!("{} bytes", self.bytes.len()))
+            .field(&format_args!("{} bytes", self.len()))
             .finish()
     }
 }
 
+impl PartialEq for Seed {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for Seed {}
+
+impl Hash for Seed {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
 impl Seed {
     /// Construct a [`Seed`] from raw bytes.
     pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> Self {
+        let bytes = Zeroizing::new(bytes.into());
+        let end = bytes.len();
         Seed {
-            bytes: bytes.into().into(),
+            bytes: SeedBytes::Heap(Arc::new(bytes)),
+            start: 0,
+            end,
         }
     }
 
-    /// Return an owned byte vector of the seed contents.
+    /// Seed whose contents stay in `bytes`; no copy reaches the regular heap
+    /// unless a caller asks for one (e.g. [`Seed::to_vec`] or [`Seed::take`]).
+    pub(crate) fn from_mlocked(bytes: MLockedBytes) -> Self {
+        let end = bytes.len();
+        Seed {
+            bytes: SeedBytes::Locked(Arc::new(bytes)),
+            start: 0,
+            end,
+        }
+    }
+
+    /// Return an owned copy of the seed contents, zeroed when dropped.
     #[must_use]
-    pub fn to_vec(&self) -> Vec<u8> {
-        self.bytes.as_ref().to_vec()
+    pub fn to_vec(&self) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(self.as_slice().to_vec())
     }
 
     /// View the seed contents as a byte slice.
     #[must_use]
     pub fn as_slice(&self) -> &[u8] {
-        &self.bytes
+        let bytes = match &self.bytes {
+            SeedBytes::Heap(bytes) => bytes.as_slice(),
+            SeedBytes::Locked(bytes) => bytes.as_slice(),
+        };
+        &bytes[self.start..self.end]
+    }
+
+    /// The first `n` bytes as a seed sharing this one's storage, and the rest.
+    fn split(&self, n: usize) -> Option<(Seed, Seed)> {
+        let mid = self.start.checked_add(n).filter(|&mid| mid <= self.end)?;
+        let head = Seed {
+            bytes: self.bytes.clone(),
+            start: self.start,
+            end: mid,
+        };
+        let tail = Seed {
+            bytes: self.bytes.clone(),
+            start: mid,
+            end: self.end,
+        };
+        Some((head, tail))
     }
 
     /// Number of bytes contained in the seed.
     #[must_use]
     pub fn len(&self) -> usize {
-        self.bytes.len()
+        self.as_slice().len()
     }
 
     /// Whether the seed is empty.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.bytes.is_empty()
+        self.as_slice().is_empty()
     }
 
     /// Take `n` bytes from the start of the seed, returning the bytes and a
-    /// new seed representing the remainder.
+    /// new seed representing the remainder. The bytes are zeroed on drop; the
+    /// remainder shares this seed's storage.
     ///
     /// # Errors
     ///
     /// Returns an error if fewer than `n` bytes are available in the seed.
-    pub fn take(&self, n: usize) -> Result<(Vec<u8>, Seed), SeedBytesExhausted> {
+    pub fn take(&self, n: usize) -> Result<(Zeroizing<Vec<u8>>, Seed), SeedBytesExhausted> {
         get_bytes_from_seed_either(n, self.clone())
     }
 }
@@ -77,7 +151,7 @@ impl From<&[u8]> for Seed {
 
 impl AsRef<[u8]> for Seed {
     fn as_ref(&self) -> &[u8] {
-        &self.bytes
+        self.as_slice()
     }
 }
 
@@ -86,9 +160,9 @@ pub fn mk_seed_from_bytes(bytes: impl Into<Vec<u8>>) -> Seed {
     Seed::from_bytes(bytes)
 }
 
-/// Obtain the raw bytes of a [`Seed`].
+/// Obtain the raw bytes of a [`Seed`], zeroed when dropped.
 #[must_use]
-pub fn get_seed_bytes(seed: &Seed) -> Vec<u8> {
+pub fn get_seed_bytes(seed: &Seed) -> Zeroizing<Vec<u8>> {
     seed.to_vec()
 }
 
@@ -100,29 +174,28 @@ pub fn get_seed_size(seed: &Seed) -> usize {
 
 /// Take `n` bytes from the seed, returning `None` if insufficient bytes remain.
 #[must_use]
-pub fn get_bytes_from_seed(n: usize, seed: Seed) -> Option<(Vec<u8>, Seed)> {
+pub fn get_bytes_from_seed(n: usize, seed: Seed) -> Option<(Zeroizing<Vec<u8>>, Seed)> {
     get_bytes_from_seed_either(n, seed).ok()
 }
 
 /// Take `n` bytes from the seed, returning an error describing how many bytes
 /// were supplied versus demanded if there is insufficient material left.
 ///
+/// The returned bytes are zeroed on drop. The remainder is not copied: it
+/// shares the input seed's storage, so an mlocked seed stays mlocked.
+///
 /// # Errors
 ///
 /// Returns an error when fewer than `n` bytes remain.
 pub fn get_bytes_from_seed_either(
     n: usize,
     seed: Seed,
-) -> Result<(Vec<u8>, Seed), SeedBytesExhausted> {
-    if seed.bytes.len() < n {
-        return Err(SeedBytesExhausted {
-            supplied: seed.bytes.len(),
-            demanded: n,
-        });
-    }
-
-    let (head, tail) = seed.bytes.split_at(n);
-    Ok((head.to_vec(), Seed::from_bytes(tail.to_vec())))
+) -> Result<(Zeroizing<Vec<u8>>, Seed), SeedBytesExhausted> {
+    let (head, tail) = seed.split(n).ok_or(SeedBytesExhausted {
+        supplied: seed.len(),
+        demanded: n,
+    })?;
+    Ok((head.to_vec(), tail))
 }
 
 /// Take `n` bytes from the seed, panicking with [`SeedBytesExhausted`] on
@@ -132,15 +205,15 @@ pub fn get_bytes_from_seed_either(
 ///
 /// Panics when the seed contains fewer than `n` bytes.
 #[must_use]
-pub fn get_bytes_from_seed_t(n: usize, seed: Seed) -> (Vec<u8>, Seed) {
+pub fn get_bytes_from_seed_t(n: usize, seed: Seed) -> (Zeroizing<Vec<u8>>, Seed) {
     get_bytes_from_seed(n, seed).expect("seed bytes exhausted")
 }
 
 /// Split a seed into two smaller seeds. The first contains `n` bytes and the
-/// second the remaining bytes.
+/// second the remaining bytes; both share the input seed's storage.
 #[must_use]
 pub fn split_seed(n: usize, seed: Seed) -> Option<(Seed, Seed)> {
-    get_bytes_from_seed(n, seed).map(|(bytes, remainder)| (Seed::from_bytes(bytes), remainder))
+    seed.split(n)
 }
 
 /// Expand a seed into two seeds using the specified digest algorithm. The
@@ -164,6 +237,43 @@ where
     (Seed::from_bytes(first), Seed::from_bytes(second))
 }
 
+/// Expand a seed into two seeds using one of this crate's [`HashAlgorithm`]s,
+/// as Haskell's `expandSeed`: `(H(1 || seed), H(2 || seed))`.
+///
+/// This is the expansion Sum KES applies at every level of its tree, with
+/// [`Blake2b256`](crate::hash::Blake2b256) as the hash for all of the
+/// standard `SumNKes` and `CompactSumNKes` types. [`expand_seed`] computes the
+/// same thing for any RustCrypto [`Digest`].
+#[must_use]
+pub fn expand_seed_with<H: HashAlgorithm>(seed: &Seed) -> (Seed, Seed) {
+    let (first, second) = H::expand_seed(seed.as_ref());
+    (Seed::from_bytes(first), Seed::from_bytes(second))
+}
+
+/// Prefix of every label passed to [`expand_seed_labelled`] by the
+/// domain-separated key generators.
+pub(crate) const KEY_DERIVATION_LABEL_PREFIX: &str = "cardano-crypto-class/keygen/v1/";
+
+/// Expand a seed into `len` bytes bound to `label`, using HKDF-SHA256
+/// (RFC 5869) with the seed as input keying material, no salt, and `label`
+/// as the `info` parameter.
+///
+/// Different labels give independent outputs from the same seed, which is
+/// what the `*_domain_separated` key generators rely on. The whole seed is
+/// used, whatever its length.
+///
+/// # Panics
+///
+/// Panics if `len` exceeds HKDF-SHA256's limit of 8160 bytes.
+#[must_use]
+pub fn expand_seed_labelled(seed: &Seed, label: &[u8], len: usize) -> Seed {
+    let mut okm = vec![0u8; len];
+    Hkdf::<sha2::Sha256>::new(None, seed.as_slice())
+        .expand(label, &mut okm)
+        .expect("HKDF-SHA256 output length is at most 8160 bytes");
+    Seed::from_bytes(okm)
+}
+
 /// Obtain a [`Seed`] by reading `n` bytes of entropy from the operating
 /// system.
 ///
@@ -179,6 +289,17 @@ pub fn read_seed_from_system_entropy(n: usize) -> Seed {
     Seed::from_bytes(buffer)
 }
 
+/// Obtain a [`Seed`] by reading `n` bytes from a caller-provided RNG.
+///
+/// This is the injectable counterpart of [`read_seed_from_system_entropy`]
+/// for tests that need reproducible seeds.
+#[must_use]
+pub fn read_seed_with<R: RngCore + CryptoRng + ?Sized>(rng: &mut R, n: usize) -> Seed {
+    let mut buffer = vec![0u8; n];
+    rng.fill_bytes(&mut buffer);
+    Seed::from_bytes(buffer)
+}
+
 /// Error raised when the seed does not contain enough bytes for a request.
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 #[error("seed bytes exhausted: supplied {supplied}, demanded {demanded}")]
@@ -187,10 +308,41 @@ pub struct SeedBytesExhausted {
     pub demanded: usize,
 }
 
+impl SeedBytesExhausted {
+    /// Stable identifier of this error (see the crate-level *Error codes*
+    /// section).
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        "seed.bytes_exhausted"
+    }
+
+    /// Always `false`: the seed is too short and will stay so.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SeedBytesExhausted {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(4))?;
+        map.serialize_entry("code", self.code())?;
+        map.serialize_entry("supplied", &self.supplied)?;
+        map.serialize_entry("demanded", &self.demanded)?;
+        map.serialize_entry("message", &self.to_string())?;
+        map.end()
+    }
+}
+
 /// Deterministic RNG backed by a [`Seed`].
+///
+/// The RNG reads the seed in place rather than copying it.
 #[derive(Clone, Debug)]
 pub struct SeedRng {
-    data: Vec<u8>,
+    seed: Seed,
     position: usize,
 }
 
@@ -198,16 +350,13 @@ impl SeedRng {
     /// Create a new RNG from the supplied seed data.
     #[must_use]
     pub fn new(seed: Seed) -> Self {
-        Self {
-            data: seed.to_vec(),
-            position: 0,
-        }
+        Self { seed, position: 0 }
     }
 
     /// Remaining bytes in the RNG.
     #[must_use]
     pub fn remaining(&self) -> usize {
-        self.data.len().saturating_sub(self.position)
+        self.seed.len().saturating_sub(self.position)
     }
 
     fn consume(&mut self, amount: usize) -> Result<&[u8], SeedBytesExhausted> {
@@ -220,7 +369,7 @@ impl SeedRng {
 
         let start = self.position;
         self.position += amount;
-        Ok(&self.data[start..self.position])
+        Ok(&self.seed.as_slice()[start..self.position])
     }
 
     /// Fill the provided buffer with bytes from the RNG.
@@ -234,14 +383,15 @@ impl SeedRng {
         Ok(())
     }
 
-    /// Produce an owned vector of the requested number of bytes.
+    /// Produce an owned vector of the requested number of bytes, zeroed when
+    /// dropped.
     ///
     /// # Errors
     ///
     /// Returns an error if insufficient bytes remain in the seed.
-    pub fn random_bytes(&mut self, len: usize) -> Result<Vec<u8>, SeedBytesExhausted> {
+    pub fn random_bytes(&mut self, len: usize) -> Result<Zeroizing<Vec<u8>>, SeedBytesExhausted> {
         let bytes = self.consume(len)?;
-        Ok(bytes.to_vec())
+        Ok(Zeroizing::new(bytes.to_vec()))
     }
 }
 
@@ -290,8 +440,8 @@ mod tests {
     fn take_bytes_success() {
         let seed = mk_seed_from_bytes(vec![1, 2, 3, 4, 5]);
         let (taken, rest) = get_bytes_from_seed(3, seed).expect("enough bytes");
-        assert_eq!(taken, vec![1, 2, 3]);
-        assert_eq!(rest.to_vec(), vec![4, 5]);
+        assert_eq!(*taken, vec![1, 2, 3]);
+        assert_eq!(rest.as_slice(), [4, 5]);
     }
 
     #[test]
@@ -302,12 +452,20 @@ mod tests {
         assert_eq!(err.demanded, 3);
     }
 
+    #[test]
+    fn take_leaves_the_remainder_in_place() {
+        let seed = mk_seed_from_bytes(vec![1, 2, 3, 4, 5]);
+        let (_, rest) = seed.take(2).expect("enough bytes");
+        assert!(std::ptr::eq(rest.as_slice(), &seed.as_slice()[2..]));
+        assert_eq!(rest.take(4).unwrap_err().supplied, 3);
+    }
+
     #[test]
     fn split_seed_works() {
         let seed = mk_seed_from_bytes(vec![10, 11, 12, 13]);
         let (left, right) = split_seed(2, seed).expect("split");
-        assert_eq!(left.to_vec(), vec![10, 11]);
-        assert_eq!(right.to_vec(), vec![12, 13]);
+        assert_eq!(left.as_slice(), [10, 11]);
+        assert_eq!(right.as_slice(), [12, 13]);
     }
 
     #[test]
@@ -319,12 +477,85 @@ mod tests {
         assert_ne!(a.to_vec(), b.to_vec());
     }
 
+    fn expand_hex<H: HashAlgorithm>() -> (String, String) {
+        let seed = mk_seed_from_bytes((0u8..32).collect::<Vec<_>>());
+        let (a, b) = expand_seed_with::<H>(&seed);
+        (hex::encode(a.to_vec()), hex::encode(b.to_vec()))
+    }
+
+    // Expected values are `H(0x01 || seed)` and `H(0x02 || seed)` for the seed
+    // `00..1f`, computed independently with Python's `hashlib`.
+    #[test]
+    fn expand_seed_with_pinned_vectors() {
+        use crate::hash::{Blake2b256, Blake2b512, Sha512};
+
+        assert_eq!(
+            expand_hex::<Blake2b256>(),
+            (
+                "c3e8f071cd73953c3ec0ef9cf9f963edf735449f0b4fe799769a4b9e794e5664".to_owned(),
+                "302abf71c5b4ab901c81429865398872d618d47e6e5b5d76194fd5f7fce7d22b".to_owned(),
+            )
+        );
+        assert_eq!(
+            expand_hex::<Blake2b512>(),
+            (
+                "0226e7285d84426283e847274a1a9ccc583cff934e0da011445c135dfd6169ff\
+                 98e852fb944a3d73e75d4e670c967cecd9725b611766c8fb95e786f846aae2ca"
+                    .to_owned(),
+                "7ef1638a35abc40b78b990690a6db3bfdef2407915a4682e205102f60a77f9cf\
+                 2d97d6128a1dff2710f29713d3ef6d41d9fede85c3ac301dfb78144ae85511f3"
+                    .to_owned(),
+            )
+        );
+        assert_eq!(
+            expand_hex::<Sha512>(),
+            (
+                "7230b01149137ead3147c881a8c314e509da1a2d9c66251d53926ce47cee2dcd\
+                 71c844e932b99499dc70c48ee4e7d140cdda0867174d0509747da2ec1bd3d857"
+                    .to_owned(),
+                "5d38cb14cc9d829f901e6841f35ecf834aef7a86d26b20d3ec3056a03b318d1d\
+                 c2da2de5b685e8c0c5a7e8cef0ba6ea40a60f78d897e17fa34d38617cca34d32"
+                    .to_owned(),
+            )
+        );
+    }
+
+    #[test]
+    fn expand_seed_with_matches_digest_expansion() {
+        let seed = mk_seed_from_bytes((0u8..32).collect::<Vec<_>>());
+        let (a, b) = expand_seed::<Sha256>(&seed);
+        let (c, d) = expand_seed_with::<crate::hash::Sha256>(&seed);
+        assert_eq!(a, c);
+        assert_eq!(b, d);
+    }
+
+    // RFC 5869 test case 3: HKDF-SHA256 with no salt and empty info.
+    #[test]
+    fn expand_seed_labelled_matches_rfc5869() {
+        let seed = mk_seed_from_bytes(vec![0x0b; 22]);
+        let okm = expand_seed_labelled(&seed, b"", 42);
+        assert_eq!(
+            hex::encode(okm.as_slice()),
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d\
+             9d201395faa4b61a96c8"
+        );
+    }
+
+    #[test]
+    fn expand_seed_labelled_separates_labels() {
+        let seed = mk_seed_from_bytes(vec![7u8; 32]);
+        let a = expand_seed_labelled(&seed, b"a", 32);
+        assert_eq!(a, expand_seed_labelled(&seed, b"a", 32));
+        assert_ne!(a, expand_seed_labelled(&seed, b"b", 32));
+        assert_ne!(a.as_slice(), seed.as_slice());
+    }
+
     #[test]
     fn seed_rng_yields_bytes() {
         let seed = mk_seed_from_bytes((0u8..=9).collect::<Vec<_>>());
         let mut rng = SeedRng::new(seed);
         let bytes = rng.random_bytes(4).unwrap();
-        assert_eq!(bytes, vec![0, 1, 2, 3]);
+        assert_eq!(*bytes, vec![0, 1, 2, 3]);
         let mut rest = [0u8; 3];
         rng.fill_bytes_checked(&mut rest).unwrap();
         assert_eq!(&rest, &[4, 5, 6]);
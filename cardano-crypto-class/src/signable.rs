@@ -0,0 +1,160 @@
+//! Canonical signable representations of the messages a node signs.
+//!
+//! Haskell never signs loose bytes: `signDSIGN`, `signKES` and `evalVRF` take
+//! a typed message and sign its `SignableRepresentation`, and
+//! `cardano-crypto-tests` pins those representations. The types here give
+//! the messages a Cardano node actually signs the same byte layouts, so two
+//! call sites cannot disagree on, say, whether a header body is signed raw or
+//! wrapped in CBOR-in-CBOR.
+//!
+//! | Type | Haskell | Bytes signed |
+//! |------|---------|--------------|
+//! | [`HeaderBodyBytes`] | `BHBody` / `HeaderBody` | the header body's CBOR, unwrapped |
+//! | [`OcertSignable`] | `OCertSignable` | `rawSerialiseVerKeyKES vk ‖ counter ‖ period` (big-endian `Word64`s) |
+//! | [`NonceProofMessage`] | Praos `mkInputVRF` | `Blake2b-256(slot ‖ epoch nonce)` |
+//!
+//! Signing a `&[u8]` directly still works, since byte slices implement
+//! [`SignableRepresentation`] as themselves, but new code should build one
+//! of these types and sign it with [`signed_dsign`](crate::signed_dsign),
+//! [`signed_kes`](crate::kes::signed_kes) or
+//! [`eval_certified`](crate::vrf::eval_certified).
+//!
+//! ```rust
+//! use cardano_crypto_class::SignableRepresentation;
+//! use cardano_crypto_class::signable::HeaderBodyBytes;
+//!
+//! // A header body received inside its CBOR-in-CBOR wrapper.
+//! let body = HeaderBodyBytes::from_cbor_in_cbor(&[0xd8, 0x18, 0x43, 0x82, 0x01, 0x02]).unwrap();
+//! assert_eq!(body.signable_representation().as_ref(), [0x82, 0x01, 0x02]);
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+
+use cardano_binary::{BinaryError, decode_nested_cbor_bytes};
+
+use crate::hash::{Blake2b256, HashAlgorithm};
+use crate::kes::{KesAlgorithm, Period};
+use crate::util::{SignableRepresentation, write_binary_word64};
+
+/// The CBOR encoding of a block header body, as signed by the KES hot key.
+///
+/// Haskell signs `serialize' bh` of the header body itself; the tag 24
+/// CBOR-in-CBOR wrapping used when the body is embedded in a header is not
+/// part of the signed bytes. Use [`HeaderBodyBytes::from_cbor_in_cbor`] for a
+/// body that still carries it.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct HeaderBodyBytes(Vec<u8>);
+
+impl HeaderBodyBytes {
+    /// Wrap the header body's CBOR encoding, which is signed verbatim.
+    #[must_use]
+    pub fn new(cbor: Vec<u8>) -> Self {
+        HeaderBodyBytes(cbor)
+    }
+
+    /// Strip the tag 24 wrapping from a header body embedded as
+    /// CBOR-in-CBOR.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `wrapped` is not a tag 24 byte string.
+    pub fn from_cbor_in_cbor(wrapped: &[u8]) -> Result<Self, BinaryError> {
+        decode_nested_cbor_bytes(wrapped).map(HeaderBodyBytes)
+    }
+
+    /// The header body's CBOR encoding.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for HeaderBodyBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HeaderBodyBytes({})", hex::encode(&self.0))
+    }
+}
+
+impl SignableRepresentation for HeaderBodyBytes {
+    fn signable_representation(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.0)
+    }
+}
+
+/// The part of an operational certificate signed by the cold key.
+///
+/// Matches Haskell's `OCertSignable`: the raw KES verification key followed
+/// by the certificate counter and its start KES period, each as a big-endian
+/// `Word64`.
+pub struct OcertSignable<K: KesAlgorithm> {
+    /// Hot KES verification key the certificate delegates to.
+    pub kes_vk: K::VerificationKey,
+    /// Issue counter of the certificate.
+    pub counter: u64,
+    /// KES period the certificate starts in.
+    pub period: Period,
+}
+
+impl<K: KesAlgorithm> OcertSignable<K> {
+    #[must_use]
+    pub fn new(kes_vk: K::VerificationKey, counter: u64, period: Period) -> Self {
+        OcertSignable {
+            kes_vk,
+            counter,
+            period,
+        }
+    }
+}
+
+impl<K: KesAlgorithm> fmt::Debug for OcertSignable<K>
+where
+    K::VerificationKey: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OcertSignable")
+            .field("kes_vk", &self.kes_vk)
+            .field("counter", &self.counter)
+            .field("period", &self.period)
+            .finish()
+    }
+}
+
+impl<K: KesAlgorithm> SignableRepresentation for OcertSignable<K> {
+    fn signable_representation(&self) -> Cow<'_, [u8]> {
+        let mut bytes = K::raw_serialize_verification_key_kes(&self.kes_vk);
+        bytes.extend_from_slice(&write_binary_word64(self.counter));
+        bytes.extend_from_slice(&write_binary_word64(self.period));
+        Cow::Owned(bytes)
+    }
+}
+
+/// The VRF input proving leadership and contributing to the epoch nonce.
+///
+/// Matches Praos' `mkInputVRF`: the Blake2b-256 hash of the slot number as a
+/// big-endian `Word64` followed by the epoch nonce, or the slot alone for
+/// the neutral nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NonceProofMessage {
+    /// Slot the block is forged in.
+    pub slot: u64,
+    /// Epoch nonce, `None` for the neutral nonce.
+    pub epoch_nonce: Option<[u8; 32]>,
+}
+
+impl NonceProofMessage {
+    #[must_use]
+    pub fn new(slot: u64, epoch_nonce: Option<[u8; 32]>) -> Self {
+        NonceProofMessage { slot, epoch_nonce }
+    }
+}
+
+impl SignableRepresentation for NonceProofMessage {
+    fn signable_representation(&self) -> Cow<'_, [u8]> {
+        let mut input = write_binary_word64(self.slot);
+        if let Some(nonce) = &self.epoch_nonce {
+            input.extend_from_slice(nonce);
+        }
+        Cow::Owned(Blake2b256::hash(&input))
+    }
+}
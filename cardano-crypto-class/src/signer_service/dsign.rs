@@ -0,0 +1,149 @@
+//! DSIGN signing on a thread that owns an mlocked signing key.
+
+use crate::dsign::DsignMAlgorithm;
+
+use super::{Reply, ReplySender, Service, SignerConfig, SignerError, Worker};
+
+struct DsignRequest<D: DsignMAlgorithm> {
+    message: Vec<u8>,
+    reply: ReplySender<D::Signature>,
+}
+
+struct DsignWorker<D: DsignMAlgorithm> {
+    context: D::Context,
+    signing_key: Option<D::MLockedSigningKey>,
+}
+
+impl<D> Worker for DsignWorker<D>
+where
+    D: DsignMAlgorithm + 'static,
+    D::MLockedSigningKey: Send,
+    D::Signature: Send,
+    D::Context: Send,
+{
+    type Request = DsignRequest<D>;
+
+    fn handle(&mut self, DsignRequest { message, reply }: DsignRequest<D>) {
+        // The key is only taken when the thread exits.
+        if let Some(key) = &self.signing_key {
+            reply.send(D::sign_bytes_m(&self.context, &message, key).map_err(Into::into));
+        }
+    }
+
+    fn forget(mut self) {
+        if let Some(key) = self.signing_key.take() {
+            D::forget_signing_key_m(key);
+        }
+    }
+}
+
+/// A handle to a thread owning an mlocked DSIGN signing key, such as an
+/// operator's Ed25519 cold key.
+///
+/// Clones share the thread; see [`SigningService`](super::SigningService)
+/// for the queueing and shutdown behaviour.
+pub struct DsignSigningService<D: DsignMAlgorithm> {
+    service: Service<DsignRequest<D>>,
+}
+
+impl<D: DsignMAlgorithm> Clone for DsignSigningService<D> {
+    fn clone(&self) -> Self {
+        DsignSigningService {
+            service: self.service.clone(),
+        }
+    }
+}
+
+impl<D> DsignSigningService<D>
+where
+    D: DsignMAlgorithm + 'static,
+    D::MLockedSigningKey: Send,
+    D::Signature: Send,
+    D::Context: Send,
+{
+    /// Move `signing_key` onto a new signing thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignerError::Spawn`] if the thread cannot be started; the
+    /// key is dropped.
+    pub fn spawn(
+        config: &SignerConfig,
+        context: D::Context,
+        signing_key: D::MLockedSigningKey,
+    ) -> Result<Self, SignerError> {
+        let worker = DsignWorker::<D> {
+            context,
+            signing_key: Some(signing_key),
+        };
+        Ok(DsignSigningService {
+            service: Service::spawn(config, worker)?,
+        })
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Reply<D::Signature> {
+        let message = message.to_vec();
+        self.service.submit(|reply| DsignRequest { message, reply })
+    }
+
+    /// Answer the requests already queued, forget the key on the signing
+    /// thread and wait for the thread to exit.
+    pub fn shutdown(&self) {
+        self.service.shutdown();
+    }
+}
+
+#[cfg(all(test, feature = "secure-memory"))]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::dsign::DsignAlgorithm;
+    use crate::dsign::ed25519::Ed25519;
+    use crate::mlocked_seed::MLockedSeed;
+
+    fn key() -> <Ed25519 as DsignMAlgorithm>::MLockedSigningKey {
+        let mut seed = MLockedSeed::<32>::new_zeroed().unwrap();
+        seed.as_mut_bytes().copy_from_slice(&[3u8; 32]);
+        Ed25519::gen_key_m(&seed).unwrap()
+    }
+
+    #[test]
+    fn signatures_match_the_direct_path() {
+        let service =
+            DsignSigningService::<Ed25519>::spawn(&SignerConfig::default(), (), key()).unwrap();
+        let direct = key();
+        let vk = Ed25519::derive_verification_key_m(&direct).unwrap();
+
+        let workers: Vec<_> = (0u8..4)
+            .map(|worker| {
+                let service = service.clone();
+                thread::spawn(move || {
+                    (0u8..8)
+                        .map(|i| {
+                            let message = vec![worker, i];
+                            let signature = service.sign(&message).wait().unwrap();
+                            (message, signature)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        for worker in workers {
+            for (message, signature) in worker.join().unwrap() {
+                assert_eq!(
+                    signature,
+                    Ed25519::sign_bytes_m(&(), &message, &direct).unwrap()
+                );
+                Ed25519::verify_bytes(&(), &vk, &message, &signature).unwrap();
+            }
+        }
+        Ed25519::forget_signing_key_m(direct);
+
+        service.shutdown();
+        assert!(matches!(
+            service.sign(b"late").wait(),
+            Err(SignerError::Closed)
+        ));
+    }
+}
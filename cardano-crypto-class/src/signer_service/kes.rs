@@ -0,0 +1,343 @@
+//! KES signing on a thread that owns the hot key.
+
+use crate::kes::evolution::evolve_key_to;
+use crate::kes::{KesAlgorithm, KesError, KesMError, Period};
+
+use super::{Reply, ReplySender, Service, SignerConfig, SignerError, Worker};
+
+enum KesRequest<K: KesAlgorithm> {
+    Sign {
+        period: Period,
+        message: Vec<u8>,
+        reply: ReplySender<K::Signature>,
+    },
+    Evolve {
+        target: Period,
+        reply: ReplySender<()>,
+    },
+}
+
+struct KesWorker<K: KesAlgorithm> {
+    context: K::Context,
+    signing_key: Option<K::SigningKey>,
+    period: Period,
+}
+
+impl<K: KesAlgorithm> KesWorker<K> {
+    fn sign(&self, period: Period, message: &[u8]) -> Result<K::Signature, SignerError> {
+        let key = self
+            .signing_key
+            .as_ref()
+            .ok_or(KesMError::Kes(KesError::KeyExpired))?;
+        let current = self.period;
+        if period < current {
+            return Err(SignerError::StalePeriod {
+                requested: period,
+                current,
+            });
+        }
+        if period > current {
+            return Err(SignerError::FuturePeriod {
+                requested: period,
+                current,
+            });
+        }
+        Ok(K::sign_kes(&self.context, period, message, key)?)
+    }
+
+    fn evolve_to(&mut self, target: Period) -> Result<(), SignerError> {
+        Ok(evolve_key_to::<K>(
+            &self.context,
+            &mut self.signing_key,
+            &mut self.period,
+            target,
+        )?)
+    }
+}
+
+impl<K> Worker for KesWorker<K>
+where
+    K: KesAlgorithm + 'static,
+    K::SigningKey: Send,
+    K::Signature: Send,
+    K::Context: Send,
+{
+    type Request = KesRequest<K>;
+
+    fn handle(&mut self, request: KesRequest<K>) {
+        match request {
+            KesRequest::Sign {
+                period,
+                message,
+                reply,
+            } => reply.send(self.sign(period, &message)),
+            KesRequest::Evolve { target, reply } => reply.send(self.evolve_to(target)),
+        }
+    }
+
+    fn forget(mut self) {
+        if let Some(key) = self.signing_key.take() {
+            K::forget_signing_key_kes(key);
+        }
+    }
+}
+
+/// A handle to a thread owning a KES signing key.
+///
+/// Clones share the thread. The thread tracks the key's period, and `sign`
+/// only signs at that period; move the key to a new period with
+/// [`evolve_to`](Self::evolve_to) first.
+pub struct SigningService<K: KesAlgorithm> {
+    service: Service<KesRequest<K>>,
+}
+
+impl<K: KesAlgorithm> Clone for SigningService<K> {
+    fn clone(&self) -> Self {
+        SigningService {
+            service: self.service.clone(),
+        }
+    }
+}
+
+impl<K> SigningService<K>
+where
+    K: KesAlgorithm + 'static,
+    K::SigningKey: Send,
+    K::Signature: Send,
+    K::Context: Send,
+{
+    /// Move `signing_key`, currently at `period`, onto a new signing
+    /// thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignerError::Spawn`] if the thread cannot be started; the
+    /// key is dropped.
+    pub fn spawn(
+        config: &SignerConfig,
+        context: K::Context,
+        signing_key: K::SigningKey,
+        period: Period,
+    ) -> Result<Self, SignerError> {
+        let worker = KesWorker::<K> {
+            context,
+            signing_key: Some(signing_key),
+            period,
+        };
+        Ok(SigningService {
+            service: Service::spawn(config, worker)?,
+        })
+    }
+
+    /// Sign `message` at `period` with the key as it is when the request
+    /// reaches the signing thread.
+    ///
+    /// The reply is [`SignerError::StalePeriod`] or
+    /// [`SignerError::FuturePeriod`] if the key is not at `period` by then,
+    /// and `KeyExpired` once the key has expired.
+    pub fn sign(&self, period: Period, message: &[u8]) -> Reply<K::Signature> {
+        let message = message.to_vec();
+        self.service.submit(|reply| KesRequest::Sign {
+            period,
+            message,
+            reply,
+        })
+    }
+
+    /// Evolve the key forward to `target`, one period at a time.
+    ///
+    /// The reply is [`SignerError::StalePeriod`] if the key is already past
+    /// `target`, and `KeyExpired` if it expires on the way, after which
+    /// every signature fails the same way.
+    pub fn evolve_to(&self, target: Period) -> Reply<()> {
+        self.service
+            .submit(|reply| KesRequest::Evolve { target, reply })
+    }
+
+    /// Answer the requests already queued, forget the key on the signing
+    /// thread and wait for the thread to exit.
+    pub fn shutdown(&self) {
+        self.service.shutdown();
+    }
+
+    #[cfg(test)]
+    pub(super) fn stall(&self) -> std::sync::mpsc::Sender<()> {
+        self.service.stall()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread;
+
+    use super::*;
+    use crate::kes::Sum2Kes;
+    use crate::role::gen_kes_key;
+
+    fn key() -> <Sum2Kes as KesAlgorithm>::SigningKey {
+        gen_kes_key::<Sum2Kes>(&[9u8; 32]).unwrap().into_inner()
+    }
+
+    fn service(queue_depth: usize) -> SigningService<Sum2Kes> {
+        let config = SignerConfig {
+            queue_depth,
+            ..SignerConfig::default()
+        };
+        SigningService::spawn(&config, (), key(), 0).unwrap()
+    }
+
+    struct Unpark(thread::Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn signatures_match_the_direct_path() {
+        let service = service(8);
+        for period in 0..3 {
+            if period > 0 {
+                block_on(service.evolve_to(period)).unwrap();
+            }
+            let served = block_on(service.sign(period, b"header")).unwrap();
+            let expected = Sum2Kes::sign_kes(&(), period, b"header", &direct_at(period)).unwrap();
+            assert_eq!(
+                Sum2Kes::raw_serialize_signature_kes(&served),
+                Sum2Kes::raw_serialize_signature_kes(&expected)
+            );
+        }
+    }
+
+    fn direct_at(period: Period) -> <Sum2Kes as KesAlgorithm>::SigningKey {
+        let mut key = key();
+        for from in 0..period {
+            key = Sum2Kes::update_kes(&(), key, from).unwrap().unwrap();
+        }
+        key
+    }
+
+    #[test]
+    fn concurrent_requests_all_verify() {
+        let service = service(64);
+        let vk = Sum2Kes::derive_verification_key(&key()).unwrap();
+        let workers: Vec<_> = (0u8..4)
+            .map(|worker| {
+                let service = service.clone();
+                thread::spawn(move || {
+                    (0u8..8)
+                        .map(|i| {
+                            let message = vec![worker, i];
+                            let signature = service.sign(0, &message).wait().unwrap();
+                            (message, signature)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        for worker in workers {
+            for (message, signature) in worker.join().unwrap() {
+                Sum2Kes::verify_kes(&(), &vk, 0, &message, &signature).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn full_queue_is_refused_with_backpressure() {
+        let service = service(2);
+        let release = service.stall();
+        let queued = [service.sign(0, b"a"), service.sign(0, b"b")];
+        let refused = service.sign(0, b"c");
+        assert!(refused.is_ready());
+        let err = refused.wait().unwrap_err();
+        assert!(matches!(err, SignerError::Backpressure { capacity: 2 }));
+        assert!(err.is_transient());
+
+        release.send(()).unwrap();
+        for reply in queued {
+            reply.wait().unwrap();
+        }
+        service.sign(0, b"c").wait().unwrap();
+    }
+
+    #[test]
+    fn shutdown_answers_queued_requests_then_closes() {
+        let service = service(4);
+        let handle = service.clone();
+        let release = service.stall();
+        let queued = service.sign(0, b"queued");
+        drop(release);
+        service.shutdown();
+
+        queued.wait().unwrap();
+        assert!(matches!(
+            handle.sign(0, b"late").wait(),
+            Err(SignerError::Closed)
+        ));
+        assert!(matches!(
+            block_on(handle.evolve_to(1)),
+            Err(SignerError::Closed)
+        ));
+        // Shutting down again is harmless.
+        handle.shutdown();
+    }
+
+    #[test]
+    fn refuses_to_sign_at_another_period() {
+        let service = service(4);
+        service.evolve_to(1).wait().unwrap();
+        assert!(matches!(
+            service.sign(0, b"header").wait(),
+            Err(SignerError::StalePeriod {
+                requested: 0,
+                current: 1
+            })
+        ));
+        let err = service.sign(2, b"header").wait().unwrap_err();
+        assert!(matches!(
+            err,
+            SignerError::FuturePeriod {
+                requested: 2,
+                current: 1
+            }
+        ));
+        assert_eq!(err.code(), "signer.future_period");
+        service.sign(1, b"header").wait().unwrap();
+    }
+
+    #[test]
+    fn refuses_stale_periods_and_expires() {
+        let service = service(4);
+        service.evolve_to(2).wait().unwrap();
+        assert!(matches!(
+            service.evolve_to(1).wait(),
+            Err(SignerError::StalePeriod {
+                requested: 1,
+                current: 2
+            })
+        ));
+        let err = service.evolve_to(4).wait().unwrap_err();
+        assert_eq!(err.code(), "kes.key_expired");
+        assert_eq!(
+            service.sign(3, b"header").wait().unwrap_err().code(),
+            "kes.key_expired"
+        );
+    }
+}
@@ -0,0 +1,305 @@
+//! Signing on a dedicated thread that owns the key (feature
+//! `signer-service`).
+//!
+//! Signing from inside async consensus tasks spreads the key across every
+//! thread the executor migrates the task to. A signing service instead
+//! moves the key onto one OS thread when it is spawned and keeps it there:
+//! callers hold a cloneable handle and submit requests over a bounded
+//! queue, receiving a [`Reply`] they can await or block on.
+//!
+//! | Service | Key | Requests |
+//! |---------|-----|----------|
+//! | [`SigningService`] | KES signing key | [`sign`](SigningService::sign), [`evolve_to`](SigningService::evolve_to) |
+//! | [`DsignSigningService`] | mlocked DSIGN signing key | [`sign`](DsignSigningService::sign) |
+//!
+//! A request arriving while [`SignerConfig::queue_depth`] requests are
+//! pending is refused at once with [`SignerError::Backpressure`] instead of
+//! queueing without bound. [`shutdown`](SigningService::shutdown) answers
+//! the requests already queued, forgets the key on the owning thread and
+//! joins it; requests after it fail with [`SignerError::Closed`]. Dropping
+//! the last handle has the same effect without waiting for the thread.
+//!
+//! The KES service owns the key's evolution: a `sign` for any period other
+//! than the one the key is at is refused with [`SignerError::StalePeriod`]
+//! or [`SignerError::FuturePeriod`] rather than producing a signature that
+//! would not verify.
+//!
+//! KES evolutions and signatures happen on the signing thread, so the
+//! [`kes::observer`](crate::kes::observer) events are emitted from it.
+//!
+//! The replies need no async runtime. With `signer-service-tokio`,
+//! [`Reply::timeout`] bounds the wait with a tokio timer.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::dsign::DsignError;
+use crate::kes::evolution::EvolveError;
+use crate::kes::{KesMError, Period};
+
+mod dsign;
+mod kes;
+mod reply;
+#[cfg(feature = "signer-service-tokio")]
+mod tokio_adapter;
+
+pub use dsign::DsignSigningService;
+pub use kes::SigningService;
+pub use reply::Reply;
+use reply::{ReplySender, reply_channel};
+
+/// Error answered by a signing service.
+#[derive(Debug, Error)]
+pub enum SignerError {
+    /// The queue already holds `capacity` requests; retry once the signing
+    /// thread has caught up.
+    #[error("signing queue is full ({capacity} requests pending)")]
+    Backpressure { capacity: usize },
+    /// The service has shut down, or its thread has exited.
+    #[error("signing service has shut down")]
+    Closed,
+    /// The key has already evolved past the requested period.
+    #[error("KES period {requested} is before the key's current period {current}")]
+    StalePeriod { requested: Period, current: Period },
+    /// A signature was requested for a period the key has not reached yet;
+    /// [`evolve_to`](SigningService::evolve_to) it first.
+    #[error("KES period {requested} is after the key's current period {current}")]
+    FuturePeriod { requested: Period, current: Period },
+    #[error(transparent)]
+    Kes(#[from] KesMError),
+    #[error(transparent)]
+    Dsign(#[from] DsignError),
+    /// The operating system refused to start the signing thread.
+    #[error("could not spawn the signing thread: {message}")]
+    Spawn { message: String },
+    /// `Reply::timeout` elapsed before the signing thread answered. Only
+    /// returned with `signer-service-tokio`, but present in every build.
+    #[error("signing service did not answer within {timeout:?}")]
+    TimedOut { timeout: Duration },
+}
+
+impl From<EvolveError> for SignerError {
+    fn from(err: EvolveError) -> Self {
+        match err {
+            EvolveError::Stale { requested, current } => {
+                SignerError::StalePeriod { requested, current }
+            },
+            EvolveError::Kes(err) => SignerError::Kes(err),
+        }
+    }
+}
+
+impl SignerError {
+    /// Stable identifier of this variant (see the crate-level *Error codes*
+    /// section). Wrapped errors report the code of the error they wrap.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            SignerError::Backpressure { .. } => "signer.backpressure",
+            SignerError::Closed => "signer.closed",
+            SignerError::StalePeriod { .. } => "signer.stale_period",
+            SignerError::FuturePeriod { .. } => "signer.future_period",
+            SignerError::Kes(err) => err.code(),
+            SignerError::Dsign(err) => err.code(),
+            SignerError::Spawn { .. } => "signer.spawn_failed",
+            SignerError::TimedOut { .. } => "signer.timed_out",
+        }
+    }
+
+    /// Whether retrying could succeed: a full queue, a slow signing thread,
+    /// a thread the OS could not start yet, and resource failures of a
+    /// wrapped error.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        match self {
+            SignerError::Backpressure { .. }
+            | SignerError::Spawn { .. }
+            | SignerError::TimedOut { .. } => true,
+            SignerError::Kes(err) => err.is_transient(),
+            SignerError::Dsign(err) => err.is_transient(),
+            SignerError::Closed
+            | SignerError::StalePeriod { .. }
+            | SignerError::FuturePeriod { .. } => false,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SignerError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        match self {
+            SignerError::Kes(err) => return err.serialize(serializer),
+            SignerError::Dsign(err) => return err.serialize(serializer),
+            _ => {},
+        }
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        match self {
+            SignerError::Backpressure { capacity } => {
+                map.serialize_entry("capacity", capacity)?;
+            },
+            SignerError::StalePeriod { requested, current }
+            | SignerError::FuturePeriod { requested, current } => {
+                map.serialize_entry("requested", requested)?;
+                map.serialize_entry("current", current)?;
+            },
+            SignerError::TimedOut { timeout } => {
+                map.serialize_entry("timeout_ms", &timeout.as_millis())?;
+            },
+            _ => {},
+        }
+        map.serialize_entry("message", &self.to_string())?;
+        map.end()
+    }
+}
+
+/// How a signing service runs its thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignerConfig {
+    /// Requests that may wait for the signing thread before further ones
+    /// are refused with [`SignerError::Backpressure`]; at least 1.
+    pub queue_depth: usize,
+    /// Name of the signing thread, as shown by debuggers and `top -H`.
+    pub thread_name: String,
+}
+
+impl Default for SignerConfig {
+    fn default() -> Self {
+        SignerConfig {
+            queue_depth: 32,
+            thread_name: "cardano-signer".to_owned(),
+        }
+    }
+}
+
+/// The key-owning state of a signing thread.
+trait Worker: Send + 'static {
+    type Request: Send + 'static;
+
+    fn handle(&mut self, request: Self::Request);
+
+    /// Forget the key; called on the signing thread as it exits.
+    fn forget(self);
+}
+
+enum Command<R> {
+    Request(R),
+    Shutdown,
+    /// Report on `stalled`, then block the signing thread until `release`
+    /// is used or dropped.
+    #[cfg(test)]
+    Stall {
+        stalled: mpsc::Sender<()>,
+        release: Receiver<()>,
+    },
+}
+
+/// The handle side shared by both services.
+struct Service<R> {
+    sender: SyncSender<Command<R>>,
+    capacity: usize,
+    closed: Arc<AtomicBool>,
+    thread: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl<R> Clone for Service<R> {
+    fn clone(&self) -> Self {
+        Service {
+            sender: self.sender.clone(),
+            capacity: self.capacity,
+            closed: Arc::clone(&self.closed),
+            thread: Arc::clone(&self.thread),
+        }
+    }
+}
+
+impl<R: Send + 'static> Service<R> {
+    fn spawn<W: Worker<Request = R>>(
+        config: &SignerConfig,
+        worker: W,
+    ) -> Result<Self, SignerError> {
+        let capacity = config.queue_depth.max(1);
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        let thread = thread::Builder::new()
+            .name(config.thread_name.clone())
+            .spawn(move || run(worker, &receiver))
+            .map_err(|err| SignerError::Spawn {
+                message: err.to_string(),
+            })?;
+        Ok(Service {
+            sender,
+            capacity,
+            closed: Arc::new(AtomicBool::new(false)),
+            thread: Arc::new(Mutex::new(Some(thread))),
+        })
+    }
+
+    /// Queue the request built around a fresh reply slot, without waiting
+    /// for room.
+    fn submit<T>(&self, request: impl FnOnce(ReplySender<T>) -> R) -> Reply<T> {
+        if self.closed.load(Ordering::Acquire) {
+            return Reply::failed(SignerError::Closed);
+        }
+        let (sender, reply) = reply_channel();
+        match self.sender.try_send(Command::Request(request(sender))) {
+            Ok(()) => reply,
+            Err(TrySendError::Full(_)) => Reply::failed(SignerError::Backpressure {
+                capacity: self.capacity,
+            }),
+            Err(TrySendError::Disconnected(_)) => Reply::failed(SignerError::Closed),
+        }
+    }
+
+    fn shutdown(&self) {
+        if !self.closed.swap(true, Ordering::AcqRel) {
+            // Waits for room behind the queued requests; a disconnected
+            // queue means the thread is already gone.
+            let _ = self.sender.send(Command::Shutdown);
+        }
+        let thread = self
+            .thread
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(thread) = thread {
+            // A panic on the signing thread has already dropped the key.
+            let _ = thread.join();
+        }
+    }
+
+    /// Block the signing thread, with its queue empty, until the returned
+    /// sender is used or dropped.
+    #[cfg(test)]
+    fn stall(&self) -> mpsc::Sender<()> {
+        let (stalled, is_stalled) = mpsc::channel();
+        let (releaser, release) = mpsc::channel();
+        self.sender
+            .send(Command::Stall { stalled, release })
+            .expect("signing thread is running");
+        is_stalled.recv().expect("signing thread stalls");
+        releaser
+    }
+}
+
+fn run<W: Worker>(mut worker: W, receiver: &Receiver<Command<W::Request>>) {
+    // Ends on shutdown, or once every handle has been dropped.
+    for command in receiver {
+        match command {
+            Command::Request(request) => worker.handle(request),
+            Command::Shutdown => break,
+            #[cfg(test)]
+            Command::Stall { stalled, release } => {
+                let _ = stalled.send(());
+                let _ = release.recv();
+            },
+        }
+    }
+    worker.forget();
+}
@@ -0,0 +1,151 @@
+//! The one-shot slot through which the signing thread answers a request.
+//!
+//! A [`Reply`] is both a [`Future`] for async callers and a blocking
+//! [`wait`](Reply::wait) for threads; it needs no runtime because the
+//! signing thread wakes whichever kind of waiter is registered.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+use super::SignerError;
+
+struct State<T> {
+    outcome: Option<Result<T, SignerError>>,
+    waker: Option<Waker>,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    filled: Condvar,
+}
+
+impl<T> Shared<T> {
+    fn fill(&self, outcome: Result<T, SignerError>) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.outcome = Some(outcome);
+        let waker = state.waker.take();
+        drop(state);
+        self.filled.notify_all();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// An empty reply slot and the sender that fills it.
+pub(crate) fn reply_channel<T>() -> (ReplySender<T>, Reply<T>) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            outcome: None,
+            waker: None,
+        }),
+        filled: Condvar::new(),
+    });
+    (ReplySender(Some(Arc::clone(&shared))), Reply { shared })
+}
+
+/// The signing thread's end of a [`Reply`]. Dropping it unsent, because
+/// the service shut down with the request still queued, answers
+/// [`SignerError::Closed`].
+pub(crate) struct ReplySender<T>(Option<Arc<Shared<T>>>);
+
+impl<T> ReplySender<T> {
+    pub(crate) fn send(mut self, outcome: Result<T, SignerError>) {
+        if let Some(shared) = self.0.take() {
+            shared.fill(outcome);
+        }
+    }
+}
+
+impl<T> Drop for ReplySender<T> {
+    fn drop(&mut self) {
+        if let Some(shared) = self.0.take() {
+            shared.fill(Err(SignerError::Closed));
+        }
+    }
+}
+
+/// The answer to one request to a signing service.
+///
+/// Await it, or block on it with [`wait`](Self::wait). Requests the
+/// service refused up front, with [`SignerError::Backpressure`] or
+/// [`SignerError::Closed`], are already resolved.
+#[must_use = "the request is answered through the reply"]
+pub struct Reply<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Reply<T> {
+    pub(crate) fn failed(err: SignerError) -> Self {
+        let (sender, reply) = reply_channel();
+        sender.send(Err(err));
+        reply
+    }
+
+    /// Block the calling thread until the signing thread answers.
+    ///
+    /// # Errors
+    ///
+    /// Returns the request's error.
+    pub fn wait(self) -> Result<T, SignerError> {
+        let mut state = self
+            .shared
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if let Some(outcome) = state.outcome.take() {
+                return outcome;
+            }
+            state = self
+                .shared
+                .filled
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Whether the answer has arrived, so that [`wait`](Self::wait) would
+    /// not block.
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        self.shared
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .outcome
+            .is_some()
+    }
+}
+
+impl<T> Future for Reply<T> {
+    type Output = Result<T, SignerError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self
+            .shared
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match state.outcome.take() {
+            Some(outcome) => Poll::Ready(outcome),
+            None => {
+                match &mut state.waker {
+                    Some(waker) => waker.clone_from(cx.waker()),
+                    None => state.waker = Some(cx.waker().clone()),
+                }
+                Poll::Pending
+            },
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for Reply<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reply")
+            .field("ready", &self.is_ready())
+            .finish()
+    }
+}
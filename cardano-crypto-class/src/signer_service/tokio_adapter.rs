@@ -0,0 +1,54 @@
+//! Bounding the wait for a reply with a tokio timer (feature
+//! `signer-service-tokio`).
+
+use std::time::Duration;
+
+use super::{Reply, SignerError};
+
+impl<T> Reply<T> {
+    /// Await the reply for at most `timeout`.
+    ///
+    /// Must be awaited inside a tokio runtime with the time driver enabled.
+    /// A request that times out is still carried out by the signing thread;
+    /// only its answer is discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignerError::TimedOut`] if the signing thread has not
+    /// answered in time, and the request's own error otherwise.
+    pub async fn timeout(self, timeout: Duration) -> Result<T, SignerError> {
+        tokio::time::timeout(timeout, self)
+            .await
+            .unwrap_or(Err(SignerError::TimedOut { timeout }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kes::Sum2Kes;
+    use crate::role::gen_kes_key;
+    use crate::signer_service::{SignerConfig, SigningService};
+
+    #[tokio::test]
+    async fn stalled_services_time_out() {
+        let key = gen_kes_key::<Sum2Kes>(&[9u8; 32]).unwrap().into_inner();
+        let service =
+            SigningService::<Sum2Kes>::spawn(&SignerConfig::default(), (), key, 0).unwrap();
+        service
+            .sign(0, b"header")
+            .timeout(Duration::from_secs(30))
+            .await
+            .unwrap();
+
+        let release = service.stall();
+        let err = service
+            .sign(0, b"header")
+            .timeout(Duration::from_millis(20))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SignerError::TimedOut { .. }));
+        assert!(err.is_transient());
+        drop(release);
+    }
+}
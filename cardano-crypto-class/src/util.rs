@@ -11,6 +11,11 @@ pub trait Empty {}
 impl<T> Empty for T {}
 
 /// Types that can be converted into a byte representation for signing.
+///
+/// Byte slices and vectors represent themselves, which is what raw-byte
+/// signing relies on. For the messages a node signs, prefer the typed
+/// representations in [`signable`](crate::signable), whose layouts are pinned
+/// to Haskell's; signing hand-assembled bytes is kept for tests and tooling.
 pub trait SignableRepresentation {
     fn signable_representation(&self) -> Cow<'_, [u8]>;
 }
@@ -45,6 +50,37 @@ impl<const N: usize> SignableRepresentation for [u8; N] {
     }
 }
 
+/// Messages whose signable representation can be emitted in chunks.
+///
+/// Implement this directly for payloads too large to hold in memory, such as
+/// a ledger snapshot read from disk, and sign them with
+/// [`signed_dsign_streaming`](crate::dsign::signed_dsign_streaming) using an
+/// algorithm that consumes the stream incrementally, such as
+/// [`HashedDsign`](crate::dsign::hashed::HashedDsign). Every
+/// [`SignableRepresentation`] is a single-chunk stream.
+pub trait SignableStream {
+    /// Pass the signable representation to `out`, in order, in one or more
+    /// chunks.
+    fn write_signable(&self, out: &mut dyn FnMut(&[u8]));
+
+    /// Concatenate the whole stream into one buffer.
+    ///
+    /// This holds the entire message in memory. It is the fallback for
+    /// algorithms that cannot sign a stream, such as plain Ed25519 and KES,
+    /// and should not be used for multi-gigabyte payloads.
+    fn collect_signable(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_signable(&mut |chunk| bytes.extend_from_slice(chunk));
+        bytes
+    }
+}
+
+impl<T: SignableRepresentation + ?Sized> SignableStream for T {
+    fn write_signable(&self, out: &mut dyn FnMut(&[u8])) {
+        out(self.signable_representation().as_ref());
+    }
+}
+
 /// Draw a random `u64` from the provided RNG.
 pub fn get_random_word64<R: RngCore + ?Sized>(rng: &mut R) -> u64 {
     rng.next_u64()
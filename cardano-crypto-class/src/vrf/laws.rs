@@ -0,0 +1,204 @@
+//! Generic law checks for [`VRFAlgorithm`] implementations.
+//!
+//! These helpers mirror the `prop_vrf_*` properties from the Haskell
+//! `cardano-crypto-tests` suite and are available to downstream crates through
+//! the `test-util` feature so custom VRF instances can be validated against the
+//! same contract as the built-in algorithms.
+//!
+//! | Law | [`vrf_key_laws`] | [`vrf_eval_laws`] |
+//! |-----|:----------------:|:-----------------:|
+//! | `gen_key` consumes exactly `SEED_SIZE` bytes and is deterministic | ✓ | |
+//! | `gen_keypair` agrees with `derive_verification_key` | ✓ | |
+//! | key serialisations have the advertised sizes and round trip | ✓ | |
+//! | outputs are `OUTPUT_SIZE` bytes and proofs are `PROOF_SIZE` bytes | | ✓ |
+//! | `verify` accepts honest proofs and returns the evaluated output | | ✓ |
+//! | proofs round trip through raw serialisation | | ✓ |
+//! | `output_from_proof`, when supported, agrees with `verify` | | ✓ |
+//!
+//! [`NeverVRF`](super::never::NeverVRF) only satisfies the key laws: its
+//! evaluation and verification intentionally panic, matching Haskell's
+//! `NeverUsedVRF`.
+
+#![allow(clippy::panic)]
+
+use super::VRFAlgorithm;
+use crate::seed::Seed;
+
+const LAW_MESSAGES: [&[u8]; 3] = [b"", b"vrf-law", &[0xA5; 200]];
+
+/// Deterministic seed material strictly longer than `A::SEED_SIZE`.
+fn seed_material<A: VRFAlgorithm>() -> Vec<u8> {
+    (0..A::SEED_SIZE + 32)
+        .map(|i| (i as u8).wrapping_mul(37).wrapping_add(11))
+        .collect()
+}
+
+/// Check every law that does not require evaluating the VRF.
+///
+/// # Panics
+///
+/// Panics with a descriptive message when `A` violates one of the laws.
+pub fn vrf_key_laws<A: VRFAlgorithm>() {
+    let name = A::ALGORITHM_NAME;
+    let material = seed_material::<A>();
+
+    let sk = A::gen_key(&Seed::from_bytes(material.clone()));
+    let sk_bytes = A::raw_serialize_signing_key(&sk);
+    assert_eq!(
+        sk_bytes,
+        A::raw_serialize_signing_key(&A::gen_key_from_seed_bytes(&material[..A::SEED_SIZE])),
+        "{name}: gen_key must consume exactly SEED_SIZE bytes"
+    );
+    assert_eq!(
+        sk_bytes,
+        A::raw_serialize_signing_key(&A::gen_key(&Seed::from_bytes(material.clone()))),
+        "{name}: gen_key must be deterministic"
+    );
+    assert_eq!(
+        sk_bytes.len(),
+        A::SIGNING_KEY_SIZE,
+        "{name}: signing key size"
+    );
+
+    let (pair_sk, pair_vk) = A::gen_keypair(&Seed::from_bytes(material));
+    let vk_bytes = A::raw_serialize_verification_key(&A::derive_verification_key(&sk));
+    assert_eq!(
+        A::raw_serialize_signing_key(&pair_sk),
+        sk_bytes,
+        "{name}: gen_keypair signing key must match gen_key"
+    );
+    assert_eq!(
+        A::raw_serialize_verification_key(&pair_vk),
+        vk_bytes,
+        "{name}: gen_keypair must agree with derive_verification_key"
+    );
+    assert_eq!(
+        vk_bytes.len(),
+        A::VERIFICATION_KEY_SIZE,
+        "{name}: verification key size"
+    );
+
+    let sk_back = A::raw_deserialize_signing_key(&sk_bytes)
+        .unwrap_or_else(|| panic!("{name}: signing key must deserialise"));
+    assert_eq!(
+        A::raw_serialize_signing_key(&sk_back),
+        sk_bytes,
+        "{name}: signing key round trip"
+    );
+    let vk_back = A::raw_deserialize_verification_key(&vk_bytes)
+        .unwrap_or_else(|| panic!("{name}: verification key must deserialise"));
+    assert_eq!(
+        A::raw_serialize_verification_key(&vk_back),
+        vk_bytes,
+        "{name}: verification key round trip"
+    );
+}
+
+/// Check the evaluation, verification, and proof serialisation laws.
+///
+/// # Panics
+///
+/// Panics with a descriptive message when `A` violates one of the laws.
+pub fn vrf_eval_laws<A>()
+where
+    A: VRFAlgorithm,
+    A::Context: Default,
+{
+    let name = A::ALGORITHM_NAME;
+    let context = A::Context::default();
+    let (sk, vk) = A::gen_keypair(&Seed::from_bytes(seed_material::<A>()));
+
+    for message in LAW_MESSAGES {
+        let (output, proof) = A::evaluate_bytes(&context, message, &sk);
+        assert_eq!(
+            output.as_bytes().len(),
+            A::OUTPUT_SIZE,
+            "{name}: output size"
+        );
+
+        let proof_bytes = A::raw_serialize_proof(&proof);
+        assert_eq!(proof_bytes.len(), A::PROOF_SIZE, "{name}: proof size");
+
+        let verified = A::verify_bytes(&context, &vk, message, &proof)
+            .unwrap_or_else(|| panic!("{name}: honest proof must verify"));
+        assert_eq!(verified, output, "{name}: verify must return eval output");
+        if let Some(unverified) = A::output_from_proof(&proof) {
+            assert_eq!(
+                unverified, verified,
+                "{name}: output_from_proof must agree with verify"
+            );
+        }
+
+        let proof_back = A::raw_deserialize_proof(&proof_bytes)
+            .unwrap_or_else(|| panic!("{name}: proof must deserialise"));
+        assert_eq!(
+            A::raw_serialize_proof(&proof_back),
+            proof_bytes,
+            "{name}: proof round trip"
+        );
+        assert_eq!(
+            A::verify_bytes(&context, &vk, message, &proof_back).as_ref(),
+            Some(&output),
+            "{name}: deserialised proof must verify"
+        );
+
+        let mut other = message.to_vec();
+        other.push(0x01);
+        assert_ne!(
+            A::verify_bytes(&context, &vk, &other, &proof).as_ref(),
+            Some(&output),
+            "{name}: proof must not certify the same output for another message"
+        );
+    }
+}
+
+/// Run every law applicable to a fully functional VRF.
+///
+/// # Panics
+///
+/// Panics with a descriptive message when `A` violates one of the laws.
+pub fn vrf_algorithm_laws<A>()
+where
+    A: VRFAlgorithm,
+    A::Context: Default,
+{
+    vrf_key_laws::<A>();
+    vrf_eval_laws::<A>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vrf::{MockVRF, NeverVRF, PraosBatchCompatVRF, PraosVRF, SimpleVRF};
+
+    #[test]
+    fn mock_vrf_laws() {
+        vrf_algorithm_laws::<MockVRF>();
+    }
+
+    #[test]
+    fn never_vrf_key_laws() {
+        vrf_key_laws::<NeverVRF>();
+    }
+
+    #[test]
+    #[should_panic(expected = "VRF unavailable")]
+    fn never_vrf_violates_eval_laws() {
+        vrf_eval_laws::<NeverVRF>();
+    }
+
+    #[test]
+    fn simple_vrf_laws() {
+        vrf_algorithm_laws::<SimpleVRF>();
+    }
+
+    #[test]
+    fn praos_vrf_laws() {
+        vrf_algorithm_laws::<PraosVRF>();
+    }
+
+    #[test]
+    fn praos_batch_compat_vrf_laws() {
+        vrf_algorithm_laws::<PraosBatchCompatVRF>();
+    }
+}
@@ -31,7 +31,7 @@ impl fmt::Debug for MockSigningKey {
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct MockCertificate(u64);
 
-// CBOR Serialization for MockCertificate
+// Serde for MockCertificate: a CBOR byte string, hex text in JSON
 #[cfg(feature = "serde")]
 impl serde::Serialize for MockCertificate {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -39,7 +39,7 @@ impl serde::Serialize for MockCertificate {
         S: serde::Serializer,
     {
         let bytes = MockVRF::raw_serialize_proof(self);
-        serializer.serialize_bytes(&bytes)
+        cardano_binary::bytes_or_hex::serialize(&bytes, serializer)
     }
 }
 
@@ -74,7 +74,7 @@ impl<'de> serde::Deserialize<'de> for MockCertificate {
             }
         }
 
-        deserializer.deserialize_bytes(BytesVisitor)
+        cardano_binary::bytes_or_hex::deserialize_with(deserializer, BytesVisitor)
     }
 }
 
@@ -85,7 +85,7 @@ impl MockVerificationKey {
     }
 }
 
-// CBOR Serialization for MockVerificationKey
+// Serde for MockVerificationKey: a CBOR byte string, hex text in JSON
 #[cfg(feature = "serde")]
 impl serde::Serialize for MockVerificationKey {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -93,7 +93,7 @@ impl serde::Serialize for MockVerificationKey {
         S: serde::Serializer,
     {
         let bytes = MockVRF::raw_serialize_verification_key(self);
-        serializer.serialize_bytes(&bytes)
+        cardano_binary::bytes_or_hex::serialize(&bytes, serializer)
     }
 }
 
@@ -128,7 +128,7 @@ impl<'de> serde::Deserialize<'de> for MockVerificationKey {
             }
         }
 
-        deserializer.deserialize_bytes(BytesVisitor)
+        cardano_binary::bytes_or_hex::deserialize_with(deserializer, BytesVisitor)
     }
 }
 
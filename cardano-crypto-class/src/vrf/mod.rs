@@ -2,6 +2,8 @@ use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 
+#[cfg(any(test, feature = "test-util"))]
+pub mod laws;
 pub mod mock;
 pub mod never;
 pub mod praos;
@@ -9,34 +11,49 @@ pub mod praos_batch;
 pub mod simple;
 
 pub use praos::{
-    PraosConstructionError, PraosProof, PraosSeed, PraosSigningKey, PraosVRF, PraosVerificationKey,
-    gen_seed as praos_gen_seed, keypair_from_seed as praos_keypair_from_seed,
+    PraosConstructionError, PraosProof, PraosProvingContext, PraosSeed, PraosSigningKey, PraosVRF,
+    PraosVerificationKey, PraosVerificationKeyPrepared,
+    certified_to_batch_compat as praos_certified_to_batch_compat, gen_seed as praos_gen_seed,
+    gen_seed_with as praos_gen_seed_with, keypair_from_seed as praos_keypair_from_seed,
     keypair_from_seed_bytes as praos_keypair_from_seed_bytes,
+    output_from_batch_compat as praos_output_from_batch_compat,
     output_from_proof as praos_output_from_proof,
     output_to_batch_compat as praos_output_to_batch_compat,
     proof_from_bytes as praos_proof_from_bytes, proof_to_bytes as praos_proof_to_bytes,
-    seed_from_bytes as praos_seed_from_bytes, seed_to_bytes as praos_seed_to_bytes,
+    seed_from_bytes as praos_seed_from_bytes,
     signing_key_from_bytes as praos_signing_key_from_bytes,
-    signing_key_to_bytes as praos_signing_key_to_bytes,
     sk_to_batch_compat as praos_sk_to_batch_compat,
     verification_key_from_bytes as praos_verification_key_from_bytes,
-    verification_key_to_bytes as praos_verification_key_to_bytes,
-    vk_to_batch_compat as praos_vk_to_batch_compat,
+    verification_key_to_bytes as praos_verification_key_to_bytes, verify_praos_output_either,
+    vk_to_batch_compat as praos_vk_to_batch_compat, with_raw_seed as praos_with_raw_seed,
 };
 
+#[cfg(feature = "tooling")]
+pub use praos::{
+    seed_to_bytes as praos_seed_to_bytes, signing_key_to_bytes as praos_signing_key_to_bytes,
+};
+
+#[allow(deprecated)]
 pub use praos_batch::{
-    PraosBatchCompatProof, PraosBatchCompatSeed, PraosBatchCompatSigningKey, PraosBatchCompatVRF,
-    PraosBatchCompatVerificationKey, PraosBatchConstructionError, gen_seed as praos_batch_gen_seed,
-    keypair_from_seed as praos_batch_keypair_from_seed,
+    PraosBatchCompatProof, PraosBatchCompatProvingContext, PraosBatchCompatSeed,
+    PraosBatchCompatSigningKey, PraosBatchCompatVRF, PraosBatchCompatVerificationKey,
+    PraosBatchConstructionError, gen_seed as praos_batch_gen_seed,
+    gen_seed_with as praos_batch_gen_seed_with, keypair_from_seed as praos_batch_keypair_from_seed,
     keypair_from_seed_bytes as praos_batch_keypair_from_seed_bytes,
     output_from_proof as praos_batch_output_from_proof,
     proof_from_bytes as praos_batch_proof_from_bytes, proof_to_bytes as praos_batch_proof_to_bytes,
-    seed_from_bytes as praos_batch_seed_from_bytes, seed_to_bytes as praos_batch_seed_to_bytes,
+    seed_from_bytes as praos_batch_seed_from_bytes,
     signing_key_from_bytes as praos_batch_signing_key_from_bytes,
-    signing_key_to_bytes as praos_batch_signing_key_to_bytes,
     r#unsafe_raw_seed as praos_batch_unsafe_raw_seed,
     verification_key_from_bytes as praos_batch_verification_key_from_bytes,
     verification_key_to_bytes as praos_batch_verification_key_to_bytes,
+    with_raw_seed as praos_batch_with_raw_seed,
+};
+
+#[cfg(feature = "tooling")]
+pub use praos_batch::{
+    seed_to_bytes as praos_batch_seed_to_bytes,
+    signing_key_to_bytes as praos_batch_signing_key_to_bytes,
 };
 
 pub use mock::{
@@ -57,7 +74,8 @@ pub use simple::{
 use num_bigint::BigUint;
 use thiserror::Error;
 
-use crate::seed::{Seed, get_bytes_from_seed_t};
+use crate::hash::HashAlgorithm;
+use crate::seed::{KEY_DERIVATION_LABEL_PREFIX, Seed, expand_seed_labelled};
 use crate::util::{SignableRepresentation, bytes_to_natural, natural_to_bytes};
 
 /// Errors that can occur when working with VRF helpers.
@@ -71,6 +89,8 @@ pub enum VRFError {
     },
     #[error("value exceeds {expected} bytes")]
     ValueTooLarge { expected: usize },
+    #[error("VRF proof verification failed")]
+    VerificationFailed,
 }
 
 impl VRFError {
@@ -87,15 +107,67 @@ impl VRFError {
     pub fn value_too_large(expected: usize) -> Self {
         VRFError::ValueTooLarge { expected }
     }
+
+    /// Stable identifier of this variant (see the crate-level *Error codes*
+    /// section).
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            VRFError::WrongLength { .. } => "vrf.wrong_length",
+            VRFError::ValueTooLarge { .. } => "vrf.value_too_large",
+            VRFError::VerificationFailed => "vrf.verification_failed",
+        }
+    }
+
+    /// Always `false`: every variant rejects malformed or forged input.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for VRFError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        match self {
+            VRFError::WrongLength {
+                context,
+                expected,
+                actual,
+            } => {
+                map.serialize_entry("context", context)?;
+                map.serialize_entry("expected", expected)?;
+                map.serialize_entry("actual", actual)?;
+            },
+            VRFError::ValueTooLarge { expected } => map.serialize_entry("expected", expected)?,
+            VRFError::VerificationFailed => {},
+        }
+        map.serialize_entry("message", &self.to_string())?;
+        map.end()
+    }
 }
 
 /// Output bytes produced by a VRF evaluation.
-#[derive(Clone)]
 pub struct OutputVRF<A: VRFAlgorithm> {
     bytes: Vec<u8>,
     _marker: PhantomData<A>,
 }
 
+// Manual impls: deriving would require the algorithm marker type itself to be
+// `Clone`/`PartialEq`/`Debug`.
+impl<A: VRFAlgorithm> Clone for OutputVRF<A> {
+    fn clone(&self) -> Self {
+        Self {
+            bytes: self.bytes.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
 impl<A: VRFAlgorithm> PartialEq for OutputVRF<A> {
     fn eq(&self, other: &Self) -> bool {
         self.bytes == other.bytes
@@ -158,6 +230,49 @@ impl<A: VRFAlgorithm> OutputVRF<A> {
         self.bytes
     }
 
+    /// The output bytes as a fixed-size array.
+    ///
+    /// `N` must equal `A::OUTPUT_SIZE`: the [`OutputSize<N>`] bound rejects
+    /// any other size at compile time, so `output.as_array::<64>()` needs no
+    /// fallible conversion.
+    ///
+    /// ```rust
+    /// use cardano_crypto_class::vrf::{OutputVRF, PraosVRF};
+    ///
+    /// let output = OutputVRF::<PraosVRF>::from_bytes(vec![7; 64]).unwrap();
+    /// let bytes: &[u8; 64] = output.as_array();
+    /// assert_eq!(bytes, &[7; 64]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Never: the length is checked against `A::OUTPUT_SIZE` on construction.
+    #[must_use]
+    pub fn as_array<const N: usize>(&self) -> &[u8; N]
+    where
+        A: OutputSize<N>,
+    {
+        const {
+            assert!(
+                N == A::OUTPUT_SIZE,
+                "OutputSize<N> implemented with N != OUTPUT_SIZE"
+            );
+        }
+        self.bytes
+            .as_slice()
+            .try_into()
+            .expect("OutputVRF length is checked on construction")
+    }
+
+    /// Hash the output followed by `extra` with `H`, i.e. `H(output || extra)`.
+    ///
+    /// This is the mixing step the protocol applies to VRF outputs, for
+    /// example when folding a leader VRF output into the evolving nonce.
+    #[must_use]
+    pub fn concat_and_hash<H: HashAlgorithm>(&self, extra: &[u8]) -> Vec<u8> {
+        H::hash_concat(&self.bytes, extra)
+    }
+
     /// Interpret the output bytes as a natural number.
     #[must_use]
     pub fn to_natural(&self) -> BigUint {
@@ -184,17 +299,160 @@ impl<A: VRFAlgorithm> OutputVRF<A> {
     }
 }
 
+/// VRF algorithms whose outputs are exactly `N` bytes.
+///
+/// Bounds [`OutputVRF::as_array`]. Implement it with `N ==
+/// VRFAlgorithm::OUTPUT_SIZE` only; a mismatched impl fails to build as soon
+/// as `as_array` is instantiated with it.
+pub trait OutputSize<const N: usize>: VRFAlgorithm {}
+
+impl OutputSize<64> for PraosVRF {}
+impl OutputSize<64> for PraosBatchCompatVRF {}
+impl OutputSize<8> for SimpleVRF {}
+impl OutputSize<8> for MockVRF {}
+impl OutputSize<0> for NeverVRF {}
+
+// CBOR serialization for OutputVRF, matching Haskell's `ToCBOR (OutputVRF v)`:
+// a single byte string of exactly `OUTPUT_SIZE` bytes.
+#[cfg(feature = "serde")]
+impl<A: VRFAlgorithm> serde::Serialize for OutputVRF<A> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(&self.bytes)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, A: VRFAlgorithm> serde::Deserialize<'de> for OutputVRF<A> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct BytesVisitor<A>(PhantomData<A>);
+
+        impl<'de, A: VRFAlgorithm> serde::de::Visitor<'de> for BytesVisitor<A> {
+            type Value = OutputVRF<A>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "{} VRF output bytes", A::ALGORITHM_NAME)
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                OutputVRF::copy_from_slice(v)
+                    .map_err(|e| E::custom(format!("invalid VRF output: {}", e)))
+            }
+
+            fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                OutputVRF::from_bytes(v)
+                    .map_err(|e| E::custom(format!("invalid VRF output: {}", e)))
+            }
+        }
+
+        deserializer.deserialize_bytes(BytesVisitor(PhantomData))
+    }
+}
+
 /// Certified output pairing the VRF output with its proof.
-#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CertifiedVRF<A: VRFAlgorithm> {
     pub output: OutputVRF<A>,
     pub proof: A::Proof,
 }
 
+// Bounded on the proof rather than on `A`, which is an uninhabited marker.
+impl<A: VRFAlgorithm> Clone for CertifiedVRF<A>
+where
+    A::Proof: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            output: self.output.clone(),
+            proof: self.proof.clone(),
+        }
+    }
+}
+
+impl<A: VRFAlgorithm> fmt::Debug for CertifiedVRF<A>
+where
+    A::Proof: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CertifiedVRF")
+            .field("output", &self.output)
+            .field("proof", &self.proof)
+            .finish()
+    }
+}
+
+impl<A: VRFAlgorithm> PartialEq for CertifiedVRF<A>
+where
+    A::Proof: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.output == other.output && self.proof == other.proof
+    }
+}
+
+impl<A: VRFAlgorithm> Eq for CertifiedVRF<A> where A::Proof: Eq {}
+
 impl<A: VRFAlgorithm> CertifiedVRF<A> {
     pub fn new(output: OutputVRF<A>, proof: A::Proof) -> Self {
         Self { output, proof }
     }
+
+    /// Pair `proof` with the output recomputed by
+    /// [`VRFAlgorithm::output_from_proof`], or `None` if the scheme cannot
+    /// derive the output from the proof alone.
+    ///
+    /// The result is **not authenticated**; check it with
+    /// [`verify_certified`] before relying on the output.
+    pub fn from_proof(proof: A::Proof) -> Option<Self> {
+        let output = A::output_from_proof(&proof)?;
+        Some(Self { output, proof })
+    }
+}
+
+// CBOR serialization for CertifiedVRF, matching Haskell's
+// `ToCBOR (CertifiedVRF v a)`: a two-element list of the output and the proof.
+#[cfg(feature = "serde")]
+impl<A> serde::Serialize for CertifiedVRF<A>
+where
+    A: VRFAlgorithm,
+    A::Proof: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeTuple;
+
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.output)?;
+        tuple.serialize_element(&self.proof)?;
+        tuple.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, A> serde::Deserialize<'de> for CertifiedVRF<A>
+where
+    A: VRFAlgorithm,
+    A::Proof: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let (output, proof) = <(OutputVRF<A>, A::Proof)>::deserialize(deserializer)?;
+        Ok(Self { output, proof })
+    }
 }
 
 /// Trait capturing the common interface exposed by VRF algorithms.
@@ -280,14 +538,35 @@ pub trait VRFAlgorithm {
     where
         Self: Sized;
 
+    /// Recompute the output carried by `proof` without verifying it.
+    ///
+    /// Schemes whose proofs determine the output on their own (`PraosVRF`,
+    /// `PraosBatchCompatVRF`) return it here. The default returns `None`, as
+    /// do schemes whose output also depends on the message or key
+    /// (`MockVRF`, `SimpleVRF`) and `NeverVRF`.
+    ///
+    /// The returned output is **not authenticated**: anyone can craft a proof
+    /// that hashes to an output of their choosing. Only treat it as the VRF
+    /// output of a key and message once [`VRFAlgorithm::verify`] succeeds;
+    /// for valid proofs both return the same output.
+    fn output_from_proof(_proof: &Self::Proof) -> Option<OutputVRF<Self>>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
     /// Deterministically derive a signing key from the supplied seed.
     #[must_use]
     fn gen_key(seed: &Seed) -> Self::SigningKey
     where
         Self: Sized,
     {
-        let (material, _) = get_bytes_from_seed_t(Self::SEED_SIZE, seed.clone());
-        Self::gen_key_from_seed_bytes(&material)
+        let material = seed
+            .as_slice()
+            .get(..Self::SEED_SIZE)
+            .expect("seed bytes exhausted");
+        Self::gen_key_from_seed_bytes(material)
     }
 
     /// Deterministically derive a signing key from seed bytes.
@@ -337,6 +616,22 @@ pub trait VRFAlgorithm {
         Self: Sized;
 }
 
+/// Generate a VRF signing key for `A` from a seed that may also seed other
+/// algorithms.
+///
+/// The VRF counterpart of
+/// [`gen_key_domain_separated`](crate::dsign::gen_key_domain_separated): the
+/// seed is expanded under the label
+/// `cardano-crypto-class/keygen/v1/vrf/<ALGORITHM_NAME>` before
+/// [`VRFAlgorithm::gen_key_from_seed_bytes`], so a seed shared with a cold
+/// key gives a VRF key unrelated to it.
+#[must_use]
+pub fn gen_key_vrf_domain_separated<A: VRFAlgorithm>(seed: &Seed) -> A::SigningKey {
+    let label = format!("{KEY_DERIVATION_LABEL_PREFIX}vrf/{}", A::ALGORITHM_NAME);
+    let material = expand_seed_labelled(seed, label.as_bytes(), A::SEED_SIZE);
+    A::gen_key_from_seed_bytes(material.as_slice())
+}
+
 /// Convenience helper mirroring `evalCertified` from the Haskell implementation.
 pub fn eval_certified<A, M>(
     context: &A::Context,
@@ -352,6 +647,10 @@ where
 }
 
 /// Verify a certified VRF proof, returning whether it matches the derived output.
+///
+/// When the scheme supports [`VRFAlgorithm::output_from_proof`], a certified
+/// output that disagrees with its proof is rejected before the (more
+/// expensive) proof verification runs.
 pub fn verify_certified<A, M>(
     context: &A::Context,
     verification_key: &A::VerificationKey,
@@ -362,6 +661,9 @@ where
     A: VRFAlgorithm,
     M: SignableRepresentation + ?Sized,
 {
+    if A::output_from_proof(&certified.proof).is_some_and(|output| output != certified.output) {
+        return false;
+    }
     match A::verify(context, verification_key, message, &certified.proof) {
         Some(output) => output == certified.output,
         None => false,
@@ -1,16 +1,22 @@
 use std::fmt;
 
-use cardano_vrf_pure::{VrfDraft03, VrfError as VrfPureError, common};
+use cardano_vrf_pure::{
+    PreparedPublicKey, VrfDraft03, VrfDraft13, VrfError as VrfPureError, common,
+};
+use rand_core::{CryptoRng, RngCore};
 use thiserror::Error;
 
-use crate::direct_serialise::{DirectDeserialise, DirectResult, DirectSerialise, SizeCheckError};
-use crate::mlocked_bytes::{MLockedBytes, MLockedError};
+use crate::direct_serialise::{
+    DirectDeserialise, DirectResult, DirectSerialise, DirectSerialiseError,
+};
+use crate::ffi::{SizedPtr, copy_sized};
+use crate::mlocked_bytes::{MLockedBytes, MLockedError, MLockedSizedBytes};
 use crate::seed::Seed;
 
 use super::praos_batch::{
     PraosBatchCompatSigningKey, PraosBatchCompatVRF, PraosBatchCompatVerificationKey,
 };
-use super::{OutputVRF, VRFAlgorithm, VRFError};
+use super::{CertifiedVRF, OutputVRF, VRFAlgorithm, VRFError};
 
 const fn seed_size() -> usize {
     32
@@ -42,6 +48,48 @@ pub enum PraosConstructionError {
     WrongLength { expected: usize, actual: usize },
 }
 
+impl PraosConstructionError {
+    /// Stable identifier of this error. Memory failures report the code of
+    /// the wrapped [`MLockedError`].
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            PraosConstructionError::Memory(err) => err.code(),
+            PraosConstructionError::Vrf(_) => "praos.vrf",
+            PraosConstructionError::WrongLength { .. } => "praos.wrong_length",
+        }
+    }
+
+    /// Whether retrying could succeed; only mlocked memory failures can be
+    /// transient.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        match self {
+            PraosConstructionError::Memory(err) => err.is_transient(),
+            PraosConstructionError::Vrf(_) | PraosConstructionError::WrongLength { .. } => false,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PraosConstructionError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        if let PraosConstructionError::Memory(err) = self {
+            return err.serialize(serializer);
+        }
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        if let PraosConstructionError::WrongLength { expected, actual } = self {
+            map.serialize_entry("expected", expected)?;
+            map.serialize_entry("actual", actual)?;
+        }
+        map.serialize_entry("message", &self.to_string())?;
+        map.end()
+    }
+}
+
 pub struct PraosSeed {
     bytes: MLockedBytes,
 }
@@ -53,18 +101,28 @@ impl fmt::Debug for PraosSeed {
 }
 
 impl PraosSeed {
-    /// Generates a new random Praos seed.
+    /// Generates a new random Praos seed from the thread-local CSPRNG.
+    ///
+    /// This is the path node code should use. Tests that need reproducible
+    /// keys should call [`PraosSeed::generate_with`] with a seeded RNG instead.
     ///
     /// # Errors
     ///
     /// Returns an error if memory-locked allocation fails.
     pub fn generate() -> Result<Self, PraosConstructionError> {
+        Self::generate_with(&mut rand::rng())
+    }
+
+    /// Generates a Praos seed by drawing 32 bytes from `rng`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if memory-locked allocation fails.
+    pub fn generate_with<R: RngCore + CryptoRng + ?Sized>(
+        rng: &mut R,
+    ) -> Result<Self, PraosConstructionError> {
         let mut bytes = MLockedBytes::new_zeroed(seed_size())?;
-        let slice = bytes.as_mut_slice();
-        // Use Rust's rand crate instead of FFI
-        use rand_core::RngCore;
-        let mut rng = rand::rng();
-        rng.fill_bytes(slice);
+        rng.fill_bytes(bytes.as_mut_slice());
         Ok(Self { bytes })
     }
 
@@ -92,6 +150,10 @@ impl PraosSeed {
         self.bytes.as_slice()
     }
 
+    /// Copies the seed onto the regular heap. Only with the `tooling`
+    /// feature; node code borrows the mlocked bytes with
+    /// [`as_bytes`](Self::as_bytes).
+    #[cfg(feature = "tooling")]
     #[must_use]
     pub fn to_vec(&self) -> Vec<u8> {
         self.bytes.as_slice().to_vec()
@@ -114,6 +176,21 @@ pub fn gen_seed() -> Result<PraosSeed, PraosConstructionError> {
     PraosSeed::generate()
 }
 
+/// Generates a Praos seed from a caller-provided RNG.
+///
+/// Intended for tests and tooling that need reproducible keys; production
+/// code should prefer [`gen_seed`], which uses the OS-seeded thread-local
+/// CSPRNG.
+///
+/// # Errors
+///
+/// Returns an error if memory-locked allocation fails.
+pub fn gen_seed_with<R: RngCore + CryptoRng + ?Sized>(
+    rng: &mut R,
+) -> Result<PraosSeed, PraosConstructionError> {
+    PraosSeed::generate_with(rng)
+}
+
 pub struct PraosSigningKey {
     secret: MLockedBytes,
 }
@@ -149,11 +226,24 @@ impl PraosSigningKey {
         self.secret.as_slice()
     }
 
+    /// Copies the secret key onto the regular heap. Only with the `tooling`
+    /// feature; node code borrows the mlocked bytes with
+    /// [`as_bytes`](Self::as_bytes).
+    #[cfg(feature = "tooling")]
     #[must_use]
     pub fn to_vec(&self) -> Vec<u8> {
         self.secret.as_slice().to_vec()
     }
 
+    fn secret_array(&self) -> Result<&[u8; 64], PraosConstructionError> {
+        self.as_bytes()
+            .try_into()
+            .map_err(|_| PraosConstructionError::WrongLength {
+                expected: signing_key_size(),
+                actual: self.as_bytes().len(),
+            })
+    }
+
     /// Derives the verification key from this signing key.
     ///
     /// # Errors
@@ -161,10 +251,13 @@ impl PraosSigningKey {
     /// This function should not fail under normal circumstances as it performs
     /// deterministic cryptographic key derivation.
     pub fn derive_verification_key(&self) -> Result<PraosVerificationKey, PraosConstructionError> {
-        let mut seed = [0u8; 32];
-        seed.copy_from_slice(&self.as_bytes()[0..32]);
-        // Use common::seed_to_public_key to derive public key
-        let pk = common::seed_to_public_key(&seed);
+        let seed = self.secret_array()?.first_chunk::<32>().ok_or(
+            PraosConstructionError::WrongLength {
+                expected: signing_key_size(),
+                actual: self.as_bytes().len(),
+            },
+        )?;
+        let pk = common::seed_to_public_key(seed);
         Ok(PraosVerificationKey { bytes: pk.to_vec() })
     }
 
@@ -186,9 +279,7 @@ impl PraosSigningKey {
     ///
     /// Returns an error if the VRF proof generation fails.
     pub fn prove(&self, message: &[u8]) -> Result<PraosProof, PraosConstructionError> {
-        let mut sk = [0u8; 64];
-        sk.copy_from_slice(self.as_bytes());
-        let proof = VrfDraft03::prove(&sk, message)?;
+        let proof = VrfDraft03::prove(self.secret_array()?, message)?;
         Ok(PraosProof {
             bytes: proof.to_vec(),
         })
@@ -208,6 +299,85 @@ impl Drop for PraosSigningKey {
     }
 }
 
+/// A [`PraosSigningKey`] with its secret expanded once, for producing many
+/// proofs with the same key.
+///
+/// [`PraosSigningKey::prove`] hashes and clamps the seed on every call; the
+/// context does that once in [`PraosProvingContext::new`] and keeps the
+/// expanded scalar and nonce prefix in mlocked memory, which is zeroed when the
+/// context is dropped. Proofs are byte-identical to [`PraosSigningKey::prove`].
+pub struct PraosProvingContext {
+    expanded: MLockedBytes,
+    public_key: [u8; 32],
+}
+
+impl fmt::Debug for PraosProvingContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PraosProvingContext(<mlocked>)")
+    }
+}
+
+impl PraosProvingContext {
+    /// Expand `signing_key` for repeated proving.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if memory-locked allocation fails.
+    pub fn new(signing_key: &PraosSigningKey) -> Result<Self, PraosConstructionError> {
+        let secret: &[u8; 64] =
+            signing_key
+                .as_bytes()
+                .try_into()
+                .map_err(|_| PraosConstructionError::WrongLength {
+                    expected: signing_key_size(),
+                    actual: signing_key.as_bytes().len(),
+                })?;
+        let mut expanded = MLockedBytes::new_zeroed(64)?;
+        expanded
+            .as_mut_slice()
+            .copy_from_slice(VrfDraft03::expand_secret_key(secret).as_slice());
+        let mut public_key = [0u8; 32];
+        public_key.copy_from_slice(&secret[32..64]);
+        Ok(Self {
+            expanded,
+            public_key,
+        })
+    }
+
+    /// Generates a VRF proof for `message`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VRF proof generation fails.
+    pub fn prove_with_ctx(&self, message: &[u8]) -> Result<PraosProof, PraosConstructionError> {
+        let expanded: &[u8; 64] = self.expanded.as_slice().try_into().map_err(|_| {
+            PraosConstructionError::WrongLength {
+                expected: 64,
+                actual: self.expanded.len(),
+            }
+        })?;
+        let proof = VrfDraft03::prove_expanded(expanded, &self.public_key, message)?;
+        Ok(PraosProof {
+            bytes: proof.to_vec(),
+        })
+    }
+
+    /// Generates one proof per message, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from [`PraosProvingContext::prove_with_ctx`].
+    pub fn prove_many<'a, I>(&self, messages: I) -> Result<Vec<PraosProof>, PraosConstructionError>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        messages
+            .into_iter()
+            .map(|message| self.prove_with_ctx(message))
+            .collect()
+    }
+}
+
 pub struct PraosVerificationKey {
     bytes: Vec<u8>,
 }
@@ -262,6 +432,82 @@ impl PraosVerificationKey {
             Err(_) => Ok(None),
         }
     }
+
+    /// Decompresses this key once for repeated verification.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PraosConstructionError::Vrf`] if the bytes are not a curve
+    /// point. [`PraosVerificationKey::verify`] rejects every proof for such a
+    /// key, so nothing that fails here would have verified.
+    pub fn prepare(&self) -> Result<PraosVerificationKeyPrepared, PraosConstructionError> {
+        let pk: &[u8; 32] =
+            self.bytes
+                .as_slice()
+                .try_into()
+                .map_err(|_| PraosConstructionError::WrongLength {
+                    expected: verification_key_size(),
+                    actual: self.bytes.len(),
+                })?;
+        Ok(PraosVerificationKeyPrepared {
+            key: VrfDraft03::prepare_public_key(pk)?,
+        })
+    }
+}
+
+/// A [`PraosVerificationKey`] with its curve point decompressed, for
+/// verifying many proofs from the same key.
+///
+/// [`PraosVerificationKey::verify`] decompresses the key on every call; this
+/// does it once in [`PraosVerificationKey::prepare`]. Results are identical to
+/// the unprepared key. It is a runtime cache and has no serde or CBOR form;
+/// keep the [`PraosVerificationKey`] for that.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PraosVerificationKeyPrepared {
+    key: PreparedPublicKey,
+}
+
+impl PraosVerificationKeyPrepared {
+    /// The verification key this was prepared from.
+    #[must_use]
+    pub fn verification_key(&self) -> PraosVerificationKey {
+        PraosVerificationKey {
+            bytes: self.key.as_bytes().to_vec(),
+        }
+    }
+
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.key.as_bytes()
+    }
+
+    /// Verifies a VRF proof, as [`PraosVerificationKey::verify`] does.
+    ///
+    /// # Errors
+    ///
+    /// Never fails for well-formed proofs. Returns `Ok(None)` if the proof is
+    /// invalid, or `Ok(Some(output))` if the proof is valid.
+    pub fn verify(
+        &self,
+        message: &[u8],
+        proof: &PraosProof,
+    ) -> Result<Option<Vec<u8>>, PraosConstructionError> {
+        let mut proof_bytes = [0u8; 80];
+        proof_bytes.copy_from_slice(proof.bytes.as_slice());
+
+        match VrfDraft03::verify_prepared(&self.key, &proof_bytes, message) {
+            Ok(output) => Ok(Some(output.to_vec())),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+impl fmt::Debug for PraosVerificationKeyPrepared {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PraosVerificationKeyPrepared")
+            .field(&hex::encode(self.key.as_bytes()))
+            .finish()
+    }
 }
 
 impl Clone for PraosVerificationKey {
@@ -288,14 +534,14 @@ impl PartialEq for PraosVerificationKey {
 
 impl Eq for PraosVerificationKey {}
 
-// CBOR Serialization for PraosVerificationKey
+// Serde for PraosVerificationKey: a CBOR byte string, hex text in JSON
 #[cfg(feature = "serde")]
 impl serde::Serialize for PraosVerificationKey {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_bytes(&self.bytes)
+        cardano_binary::bytes_or_hex::serialize(&self.bytes, serializer)
     }
 }
 
@@ -330,7 +576,7 @@ impl<'de> serde::Deserialize<'de> for PraosVerificationKey {
             }
         }
 
-        deserializer.deserialize_bytes(BytesVisitor)
+        cardano_binary::bytes_or_hex::deserialize_with(deserializer, BytesVisitor)
     }
 }
 
@@ -350,9 +596,8 @@ impl DirectDeserialise for PraosVerificationKey {
     ) -> DirectResult<Self> {
         let mut bytes = vec![0u8; verification_key_size()];
         pull(&mut bytes)?;
-        Self::from_bytes(&bytes).map_err(|_| SizeCheckError {
-            expected_size: verification_key_size(),
-            actual_size: bytes.len(),
+        Self::from_bytes(&bytes).map_err(|_| DirectSerialiseError::InvalidValue {
+            what: "Praos verification key",
         })
     }
 }
@@ -414,14 +659,14 @@ impl PraosProof {
     }
 }
 
-// CBOR Serialization for PraosProof
+// Serde for PraosProof: a CBOR byte string, hex text in JSON
 #[cfg(feature = "serde")]
 impl serde::Serialize for PraosProof {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_bytes(&self.bytes)
+        cardano_binary::bytes_or_hex::serialize(&self.bytes, serializer)
     }
 }
 
@@ -456,7 +701,7 @@ impl<'de> serde::Deserialize<'de> for PraosProof {
             }
         }
 
-        deserializer.deserialize_bytes(BytesVisitor)
+        cardano_binary::bytes_or_hex::deserialize_with(deserializer, BytesVisitor)
     }
 }
 
@@ -476,9 +721,8 @@ impl DirectDeserialise for PraosProof {
     ) -> DirectResult<Self> {
         let mut bytes = vec![0u8; proof_size()];
         pull(&mut bytes)?;
-        Self::from_bytes(&bytes).map_err(|_| SizeCheckError {
-            expected_size: proof_size(),
-            actual_size: bytes.len(),
+        Self::from_bytes(&bytes).map_err(|_| DirectSerialiseError::InvalidValue {
+            what: "Praos proof",
         })
     }
 }
@@ -491,13 +735,20 @@ impl DirectDeserialise for PraosProof {
 pub fn keypair_from_seed(
     seed: &PraosSeed,
 ) -> Result<(PraosVerificationKey, PraosSigningKey), PraosConstructionError> {
-    let mut seed_bytes = [0u8; 32];
-    seed_bytes.copy_from_slice(seed.as_bytes());
-    let (sk_array, pk_array) = VrfDraft03::keypair_from_seed(&seed_bytes);
+    let seed_bytes: &[u8; 32] =
+        seed.as_bytes()
+            .try_into()
+            .map_err(|_| PraosConstructionError::WrongLength {
+                expected: seed_size(),
+                actual: seed.as_bytes().len(),
+            })?;
+    let (sk_array, pk_array) = VrfDraft03::keypair_from_seed(seed_bytes);
 
     // Store in mlocked memory
-    let mut sk = MLockedBytes::new_zeroed(signing_key_size())?;
-    sk.as_mut_slice().copy_from_slice(&sk_array);
+    let sk: MLockedBytes = MLockedSizedBytes::<{ signing_key_size() }>::create_sized(|dst| {
+        copy_sized(dst, SizedPtr::from_array(&sk_array));
+    })?
+    .into();
 
     Ok((
         PraosVerificationKey {
@@ -546,6 +797,9 @@ pub fn signing_key_from_bytes(bytes: &[u8]) -> Result<PraosSigningKey, PraosCons
     PraosSigningKey::from_bytes(bytes)
 }
 
+/// Copies a signing key onto the regular heap. Only with the `tooling`
+/// feature; see [`with_raw_seed`] for scoped access to the seed.
+#[cfg(feature = "tooling")]
 #[must_use]
 pub fn signing_key_to_bytes(signing_key: &PraosSigningKey) -> Vec<u8> {
     signing_key.to_vec()
@@ -592,11 +846,21 @@ pub fn seed_from_bytes(bytes: &[u8]) -> Result<PraosSeed, PraosConstructionError
     PraosSeed::from_bytes(bytes)
 }
 
+/// Copies a seed onto the regular heap. Only with the `tooling` feature.
+#[cfg(feature = "tooling")]
 #[must_use]
 pub fn seed_to_bytes(seed: &PraosSeed) -> Vec<u8> {
     seed.to_vec()
 }
 
+/// Runs `f` on the 32-byte seed of `signing_key` and returns its result.
+///
+/// The slice borrows the key's mlocked allocation, so no copy of the seed is
+/// made; `f` should not copy it to the heap either.
+pub fn with_raw_seed<R>(signing_key: &PraosSigningKey, f: impl FnOnce(&[u8]) -> R) -> R {
+    f(&signing_key.as_bytes()[..seed_size()])
+}
+
 /// Extracts VRF output from a proof.
 ///
 /// # Errors
@@ -659,17 +923,91 @@ pub fn sk_to_batch_compat(
     })
 }
 
+// Draft-03 and draft-13 both hash to 64-byte outputs, so converting between
+// them is a relabelling of the same bytes.
+const _: () = assert!(PraosVRF::OUTPUT_SIZE == PraosBatchCompatVRF::OUTPUT_SIZE);
+
 /// Converts a Praos VRF output to batch-compatible format.
 ///
+/// The conversion is lossless: the bytes are copied unchanged and
+/// [`output_from_batch_compat`] recovers the original output.
+///
 /// # Errors
 ///
-/// Returns an error if the output length is invalid.
+/// Returns [`VRFError::WrongLength`] if the output is not 64 bytes.
 pub fn output_to_batch_compat(
     output: &OutputVRF<PraosVRF>,
 ) -> Result<OutputVRF<PraosBatchCompatVRF>, VRFError> {
     OutputVRF::copy_from_slice(output.as_bytes())
 }
 
+/// Converts a batch-compatible VRF output back to the draft-03 output type.
+///
+/// Inverse of [`output_to_batch_compat`]; the bytes are copied unchanged.
+///
+/// # Errors
+///
+/// Returns [`VRFError::WrongLength`] if the output is not 64 bytes.
+pub fn output_from_batch_compat(
+    output: &OutputVRF<PraosBatchCompatVRF>,
+) -> Result<OutputVRF<PraosVRF>, VRFError> {
+    OutputVRF::copy_from_slice(output.as_bytes())
+}
+
+/// Always returns `None`: certified outputs cannot cross the draft-03 /
+/// draft-13 boundary.
+///
+/// A draft-03 proof is 80 bytes and a draft-13 proof is 128 bytes with a
+/// different challenge construction, so there is no way to turn one into the
+/// other without the signing key. Compare outputs with
+/// [`output_to_batch_compat`] instead, or re-evaluate with a converted
+/// signing key ([`sk_to_batch_compat`]).
+#[must_use]
+#[allow(clippy::needless_pass_by_value)]
+pub fn certified_to_batch_compat(
+    _certified: CertifiedVRF<PraosVRF>,
+) -> Option<CertifiedVRF<PraosBatchCompatVRF>> {
+    None
+}
+
+/// Verify a Praos proof from either side of the draft-03 / draft-13 switch.
+///
+/// The algorithm is chosen by proof length: 80 bytes verifies as
+/// [`PraosVRF`] (draft-03) and 128 bytes as [`PraosBatchCompatVRF`]
+/// (draft-13). Both use the same 32-byte verification key encoding. Returns
+/// the 64-byte output on success.
+///
+/// # Errors
+///
+/// Returns [`VRFError::WrongLength`] if the verification key is not 32 bytes
+/// or the proof is neither 80 nor 128 bytes, and
+/// [`VRFError::VerificationFailed`] if the proof does not verify.
+pub fn verify_praos_output_either(
+    verification_key: &[u8],
+    message: &[u8],
+    proof: &[u8],
+) -> Result<[u8; 64], VRFError> {
+    let pk: &[u8; 32] = verification_key.try_into().map_err(|_| {
+        VRFError::wrong_length(
+            "praos verification key",
+            verification_key_size(),
+            verification_key.len(),
+        )
+    })?;
+    let output = if let Ok(proof) = <&[u8; 80]>::try_from(proof) {
+        VrfDraft03::verify(pk, proof, message)
+    } else if let Ok(proof) = <&[u8; 128]>::try_from(proof) {
+        VrfDraft13::verify(pk, proof, message)
+    } else {
+        return Err(VRFError::wrong_length(
+            "praos proof (draft-03, or 128 bytes for draft-13)",
+            proof_size(),
+            proof.len(),
+        ));
+    };
+    output.map_err(|_| VRFError::VerificationFailed)
+}
+
 pub struct PraosVRF;
 
 impl VRFAlgorithm for PraosVRF {
@@ -718,6 +1056,10 @@ impl VRFAlgorithm for PraosVRF {
         }
     }
 
+    fn output_from_proof(proof: &Self::Proof) -> Option<OutputVRF<Self>> {
+        output_from_proof(proof).ok().flatten()
+    }
+
     fn gen_key_from_seed_bytes(seed: &[u8]) -> Self::SigningKey {
         let (_, sk) = keypair_from_seed_bytes(seed).expect("invalid praos seed bytes");
         sk
@@ -732,7 +1074,7 @@ impl VRFAlgorithm for PraosVRF {
     }
 
     fn raw_serialize_signing_key(key: &Self::SigningKey) -> Vec<u8> {
-        key.to_vec()
+        key.as_bytes().to_vec()
     }
 
     fn raw_deserialize_signing_key(bytes: &[u8]) -> Option<Self::SigningKey> {
@@ -1,38 +1,40 @@
 use std::fmt;
 
 use cardano_vrf_pure::{VrfDraft13, VrfError as VrfPureError, common};
+use rand_core::{CryptoRng, RngCore};
 use thiserror::Error;
 
-use crate::mlocked_bytes::{MLockedBytes, MLockedError};
+use crate::ffi::{SizedPtr, copy_sized};
+use crate::mlocked_bytes::{MLockedBytes, MLockedError, MLockedSizedBytes};
 use crate::seed::Seed;
 
 use super::{OutputVRF, VRFAlgorithm};
 
-fn seed_size() -> usize {
+const fn seed_size() -> usize {
     32
 }
 
-fn verification_key_size() -> usize {
+const fn verification_key_size() -> usize {
     32
 }
 
-fn signing_key_size() -> usize {
+const fn signing_key_size() -> usize {
     64
 }
 
-fn proof_size() -> usize {
+const fn proof_size() -> usize {
     128 // draft-13 batch-compatible uses 128-byte proofs
 }
 
-fn output_size() -> usize {
+const fn output_size() -> usize {
     64
 }
 
-fn io_verification_key_size() -> usize {
+const fn io_verification_key_size() -> usize {
     32
 }
 
-fn io_signing_key_size() -> usize {
+const fn io_signing_key_size() -> usize {
     64
 }
 
@@ -63,16 +65,28 @@ impl fmt::Debug for PraosBatchCompatSeed {
 }
 
 impl PraosBatchCompatSeed {
-    /// Generates a new random Praos batch-compatible seed.
+    /// Generates a new random Praos batch-compatible seed from the
+    /// thread-local CSPRNG.
+    ///
+    /// This is the path node code should use. Tests that need reproducible
+    /// keys should call [`PraosBatchCompatSeed::generate_with`] instead.
     ///
     /// # Errors
     ///
     /// Returns an error if memory-locked allocation fails.
     pub fn generate() -> Result<Self, PraosBatchConstructionError> {
+        Self::generate_with(&mut rand::rng())
+    }
+
+    /// Generates a Praos batch-compatible seed by drawing 32 bytes from `rng`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if memory-locked allocation fails.
+    pub fn generate_with<R: RngCore + CryptoRng + ?Sized>(
+        rng: &mut R,
+    ) -> Result<Self, PraosBatchConstructionError> {
         let mut bytes = MLockedBytes::new_zeroed(seed_size())?;
-        // Use Rust's rand crate
-        use rand_core::RngCore;
-        let mut rng = rand::rng();
         rng.fill_bytes(bytes.as_mut_slice());
         Ok(Self { bytes })
     }
@@ -101,6 +115,10 @@ impl PraosBatchCompatSeed {
         self.bytes.as_slice()
     }
 
+    /// Copies the seed onto the regular heap. Only with the `tooling`
+    /// feature; node code borrows the mlocked bytes with
+    /// [`as_bytes`](Self::as_bytes).
+    #[cfg(feature = "tooling")]
     #[must_use]
     pub fn to_vec(&self) -> Vec<u8> {
         self.bytes.as_slice().to_vec()
@@ -127,6 +145,21 @@ pub fn gen_seed() -> Result<PraosBatchCompatSeed, PraosBatchConstructionError> {
     PraosBatchCompatSeed::generate()
 }
 
+/// Generates a Praos batch-compatible seed from a caller-provided RNG.
+///
+/// Intended for tests and tooling that need reproducible keys; production
+/// code should prefer [`gen_seed`], which uses the OS-seeded thread-local
+/// CSPRNG.
+///
+/// # Errors
+///
+/// Returns an error if memory-locked allocation fails.
+pub fn gen_seed_with<R: RngCore + CryptoRng + ?Sized>(
+    rng: &mut R,
+) -> Result<PraosBatchCompatSeed, PraosBatchConstructionError> {
+    PraosBatchCompatSeed::generate_with(rng)
+}
+
 pub struct PraosBatchCompatSigningKey {
     secret: MLockedBytes,
 }
@@ -170,11 +203,24 @@ impl PraosBatchCompatSigningKey {
         self.secret.as_slice()
     }
 
+    /// Copies the secret key onto the regular heap. Only with the `tooling`
+    /// feature; node code borrows the mlocked bytes with
+    /// [`as_bytes`](Self::as_bytes).
+    #[cfg(feature = "tooling")]
     #[must_use]
     pub fn to_vec(&self) -> Vec<u8> {
         self.secret.as_slice().to_vec()
     }
 
+    fn secret_array(&self) -> Result<&[u8; 64], PraosBatchConstructionError> {
+        self.as_bytes()
+            .try_into()
+            .map_err(|_| PraosBatchConstructionError::WrongLength {
+                expected: signing_key_size(),
+                actual: self.as_bytes().len(),
+            })
+    }
+
     /// Derives the verification key from this signing key.
     ///
     /// # Errors
@@ -184,9 +230,13 @@ impl PraosBatchCompatSigningKey {
     pub fn derive_verification_key(
         &self,
     ) -> Result<PraosBatchCompatVerificationKey, PraosBatchConstructionError> {
-        let mut seed = [0u8; 32];
-        seed.copy_from_slice(&self.as_bytes()[0..32]);
-        let pk = common::seed_to_public_key(&seed);
+        let seed = self.secret_array()?.first_chunk::<32>().ok_or(
+            PraosBatchConstructionError::WrongLength {
+                expected: signing_key_size(),
+                actual: self.as_bytes().len(),
+            },
+        )?;
+        let pk = common::seed_to_public_key(seed);
         Ok(PraosBatchCompatVerificationKey { bytes: pk.to_vec() })
     }
 
@@ -211,9 +261,7 @@ impl PraosBatchCompatSigningKey {
         &self,
         message: &[u8],
     ) -> Result<PraosBatchCompatProof, PraosBatchConstructionError> {
-        let mut sk = [0u8; 64];
-        sk.copy_from_slice(self.as_bytes());
-        let proof = VrfDraft13::prove(&sk, message)?;
+        let proof = VrfDraft13::prove(self.secret_array()?, message)?;
         Ok(PraosBatchCompatProof {
             bytes: proof.to_vec(),
         })
@@ -228,6 +276,91 @@ impl Clone for PraosBatchCompatSigningKey {
     }
 }
 
+/// A [`PraosBatchCompatSigningKey`] with its secret expanded once, for producing many
+/// proofs with the same key.
+///
+/// [`PraosBatchCompatSigningKey::prove`] hashes and clamps the seed on every call; the
+/// context does that once in [`PraosBatchCompatProvingContext::new`] and keeps the
+/// expanded scalar and nonce prefix in mlocked memory, which is zeroed when the
+/// context is dropped. Proofs are byte-identical to [`PraosBatchCompatSigningKey::prove`].
+pub struct PraosBatchCompatProvingContext {
+    expanded: MLockedBytes,
+    public_key: [u8; 32],
+}
+
+impl fmt::Debug for PraosBatchCompatProvingContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PraosBatchCompatProvingContext(<mlocked>)")
+    }
+}
+
+impl PraosBatchCompatProvingContext {
+    /// Expand `signing_key` for repeated proving.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if memory-locked allocation fails.
+    pub fn new(
+        signing_key: &PraosBatchCompatSigningKey,
+    ) -> Result<Self, PraosBatchConstructionError> {
+        let secret: &[u8; 64] = signing_key.as_bytes().try_into().map_err(|_| {
+            PraosBatchConstructionError::WrongLength {
+                expected: signing_key_size(),
+                actual: signing_key.as_bytes().len(),
+            }
+        })?;
+        let mut expanded = MLockedBytes::new_zeroed(64)?;
+        expanded
+            .as_mut_slice()
+            .copy_from_slice(VrfDraft13::expand_secret_key(secret).as_slice());
+        let mut public_key = [0u8; 32];
+        public_key.copy_from_slice(&secret[32..64]);
+        Ok(Self {
+            expanded,
+            public_key,
+        })
+    }
+
+    /// Generates a VRF proof for `message`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VRF proof generation fails.
+    pub fn prove_with_ctx(
+        &self,
+        message: &[u8],
+    ) -> Result<PraosBatchCompatProof, PraosBatchConstructionError> {
+        let expanded: &[u8; 64] = self.expanded.as_slice().try_into().map_err(|_| {
+            PraosBatchConstructionError::WrongLength {
+                expected: 64,
+                actual: self.expanded.len(),
+            }
+        })?;
+        let proof = VrfDraft13::prove_expanded(expanded, &self.public_key, message)?;
+        Ok(PraosBatchCompatProof {
+            bytes: proof.to_vec(),
+        })
+    }
+
+    /// Generates one proof per message, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from [`PraosBatchCompatProvingContext::prove_with_ctx`].
+    pub fn prove_many<'a, I>(
+        &self,
+        messages: I,
+    ) -> Result<Vec<PraosBatchCompatProof>, PraosBatchConstructionError>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        messages
+            .into_iter()
+            .map(|message| self.prove_with_ctx(message))
+            .collect()
+    }
+}
+
 pub struct PraosBatchCompatVerificationKey {
     bytes: Vec<u8>,
 }
@@ -371,6 +504,52 @@ impl PraosBatchCompatProof {
     }
 }
 
+// Serde for PraosBatchCompatProof: a CBOR byte string, hex text in JSON
+#[cfg(feature = "serde")]
+impl serde::Serialize for PraosBatchCompatProof {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        cardano_binary::bytes_or_hex::serialize(&self.bytes, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PraosBatchCompatProof {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct BytesVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+            type Value = PraosBatchCompatProof;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "Praos batch-compatible VRF proof bytes")
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                PraosBatchCompatProof::from_bytes(v)
+                    .map_err(|e| E::custom(format!("invalid Praos batch proof: {}", e)))
+            }
+
+            fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                self.visit_bytes(&v)
+            }
+        }
+
+        cardano_binary::bytes_or_hex::deserialize_with(deserializer, BytesVisitor)
+    }
+}
+
 /// Generates a Praos batch-compatible keypair from a seed.
 ///
 /// # Errors
@@ -382,13 +561,20 @@ pub fn keypair_from_seed(
     (PraosBatchCompatVerificationKey, PraosBatchCompatSigningKey),
     PraosBatchConstructionError,
 > {
-    let mut seed_bytes = [0u8; 32];
-    seed_bytes.copy_from_slice(seed.as_bytes());
-    let (sk_array, pk_array) = VrfDraft13::keypair_from_seed(&seed_bytes);
+    let seed_bytes: &[u8; 32] =
+        seed.as_bytes()
+            .try_into()
+            .map_err(|_| PraosBatchConstructionError::WrongLength {
+                expected: seed_size(),
+                actual: seed.as_bytes().len(),
+            })?;
+    let (sk_array, pk_array) = VrfDraft13::keypair_from_seed(seed_bytes);
 
     // Store in mlocked memory
-    let mut sk = MLockedBytes::new_zeroed(signing_key_size())?;
-    sk.as_mut_slice().copy_from_slice(&sk_array);
+    let sk: MLockedBytes = MLockedSizedBytes::<{ signing_key_size() }>::create_sized(|dst| {
+        copy_sized(dst, SizedPtr::from_array(&sk_array));
+    })?
+    .into();
 
     Ok((
         PraosBatchCompatVerificationKey {
@@ -442,6 +628,9 @@ pub fn signing_key_from_bytes(
     PraosBatchCompatSigningKey::from_bytes(bytes)
 }
 
+/// Copies a signing key onto the regular heap. Only with the `tooling`
+/// feature; see [`with_raw_seed`] for scoped access to the seed.
+#[cfg(feature = "tooling")]
 #[must_use]
 pub fn signing_key_to_bytes(signing_key: &PraosBatchCompatSigningKey) -> Vec<u8> {
     signing_key.to_vec()
@@ -490,14 +679,33 @@ pub fn seed_from_bytes(bytes: &[u8]) -> Result<PraosBatchCompatSeed, PraosBatchC
     PraosBatchCompatSeed::from_bytes(bytes)
 }
 
+/// Copies a seed onto the regular heap. Only with the `tooling` feature.
+#[cfg(feature = "tooling")]
 #[must_use]
 pub fn seed_to_bytes(seed: &PraosBatchCompatSeed) -> Vec<u8> {
     seed.to_vec()
 }
 
+/// Copies a seed onto the regular heap.
+///
+/// Deprecated: use [`with_raw_seed`] on the signing key, or
+/// [`PraosBatchCompatSeed::as_bytes`], both of which borrow the mlocked
+/// bytes. This function will be removed in 0.2.0.
+#[deprecated(
+    since = "0.1.1",
+    note = "use with_raw_seed() or PraosBatchCompatSeed::as_bytes(); removal in 0.2.0"
+)]
 #[must_use]
 pub fn unsafe_raw_seed(seed: &PraosBatchCompatSeed) -> Vec<u8> {
-    seed.to_vec()
+    seed.as_bytes().to_vec()
+}
+
+/// Runs `f` on the 32-byte seed of `signing_key` and returns its result.
+///
+/// The slice borrows the key's mlocked allocation, so no copy of the seed is
+/// made; `f` should not copy it to the heap either.
+pub fn with_raw_seed<R>(signing_key: &PraosBatchCompatSigningKey, f: impl FnOnce(&[u8]) -> R) -> R {
+    f(&signing_key.as_bytes()[..seed_size()])
 }
 
 /// Extracts a batch-compatible VRF output from a proof.
@@ -580,6 +788,10 @@ impl VRFAlgorithm for PraosBatchCompatVRF {
         }
     }
 
+    fn output_from_proof(proof: &Self::Proof) -> Option<OutputVRF<Self>> {
+        output_from_proof(proof).ok().flatten()
+    }
+
     fn gen_key_from_seed_bytes(seed: &[u8]) -> Self::SigningKey {
         let (_, sk) = keypair_from_seed_bytes(seed).expect("invalid praos batch seed bytes");
         sk
@@ -594,7 +806,7 @@ impl VRFAlgorithm for PraosBatchCompatVRF {
     }
 
     fn raw_serialize_signing_key(key: &Self::SigningKey) -> Vec<u8> {
-        key.to_vec()
+        key.as_bytes().to_vec()
     }
 
     fn raw_deserialize_signing_key(bytes: &[u8]) -> Option<Self::SigningKey> {
@@ -1,3 +1,25 @@
+//! The `SimpleVRF` test algorithm: a Chaum–Pedersen style VRF over the
+//! binary elliptic curve SEC `sect113r1`, as in Haskell's
+//! `Cardano.Crypto.VRF.Simple`.
+//!
+//! The group is the prime-order subgroup generated by
+//! ([`GENERATOR_X`], [`GENERATOR_Y`]) on `y² + xy = x³ + ax² + b` over
+//! GF(2^113) with reduction polynomial [`FIELD_POLYNOMIAL`]. The curve is
+//! not a prime-field curve, so there is no modulus `p`; the field is fixed by
+//! the polynomial. The group order is [`GROUP_ORDER`] and the full curve has
+//! [`COFACTOR`] times as many points. The 113-bit curve offers no real
+//! security and exists to exercise VRF plumbing in tests.
+//!
+//! Key generation maps the first [`SEED_SIZE`](VRFAlgorithm::SEED_SIZE)
+//! (16) seed bytes, read big-endian, into `[1, GROUP_ORDER - 1]` by a single
+//! reduction, so every seed of the right length yields a key. Haskell instead
+//! draws the scalar with cryptonite's rejection sampler from a 1600-byte seed
+//! (up to 100 draws), so keys derived from the same seed differ between the
+//! two implementations. The raw encodings follow Haskell's layout, but proofs
+//! and outputs have not yet been checked against a Haskell capture; the
+//! `haskell_vectors` check in `tests/vrf_simple.rs` is ignored until one is
+//! recorded.
+
 #![allow(clippy::unreadable_literal)]
 
 use std::convert::TryFrom;
@@ -5,28 +27,38 @@ use std::fmt;
 
 use blake2::Blake2bVar;
 use blake2::digest::{Update, VariableOutput};
-use cardano_binary::serialize;
-use ciborium::value::Value;
+use cardano_binary::{Value, encode_value, serialize};
 use num_bigint::BigUint;
 
-use crate::seed::{Seed, SeedRng};
+use crate::seed::Seed;
 use crate::util::{splits_at, write_binary_natural};
 
-use super::{OutputVRF, VRFAlgorithm};
+use super::{OutputVRF, VRFAlgorithm, VRFError};
+
+/// Degree `m` of the binary field GF(2^m) underlying `sect113r1`.
+pub const FIELD_DEGREE: u32 = 113;
+/// Reduction polynomial `x^113 + x^9 + 1`, one bit per coefficient.
+pub const FIELD_POLYNOMIAL: u128 = (1u128 << FIELD_DEGREE) | (1 << 9) | 1;
+/// Curve coefficient `a` of `sect113r1`.
+pub const CURVE_A: u128 = 0x0000_3088_250C_A6E7_C7FE_649C_E858_20F7;
+/// Curve coefficient `b` of `sect113r1`.
+pub const CURVE_B: u128 = 0x0000_E8BE_E4D3_E226_0744_188B_E0E9_C723;
+/// x-coordinate of the group generator `G`.
+pub const GENERATOR_X: u128 = 0x0000_9D73_616F_35F4_AB14_07D7_3562_C10F;
+/// y-coordinate of the group generator `G`.
+pub const GENERATOR_Y: u128 = 0x0000_A528_3027_7958_EE84_D131_5ED3_1886;
+/// Prime order `n` of `G`; signing keys are scalars in `[1, n - 1]`.
+pub const GROUP_ORDER: u128 = 0x0001_0000_0000_0000_00D9_CCEC_8A39_E56F;
+/// Ratio of the number of curve points to [`GROUP_ORDER`].
+pub const COFACTOR: u128 = 2;
 
-const FIELD_DEGREE: u32 = 113;
 const FIELD_MASK: u128 = (1u128 << FIELD_DEGREE) - 1;
-const REDUCTION_POLY: u128 = (1u128 << FIELD_DEGREE) | (1 << 9) | 1;
-
-const CURVE_A: u128 = 0x0000_3088_250C_A6E7_C7FE_649C_E858_20F7;
-const CURVE_B: u128 = 0x0000_E8BE_E4D3_E226_0744_188B_E0E9_C723;
-const BASE_X: u128 = 0x0000_9D73_616F_35F4_AB14_07D7_3562_C10F;
-const BASE_Y: u128 = 0x0000_A528_3027_7958_EE84_D131_5ED3_1886;
-const CURVE_ORDER: u128 = 0x0001_0000_0000_0000_00D9_CCEC_8A39_E56F;
 
 const SIMPLE_OUTPUT_SIZE: usize = 8;
-const SIMPLE_SEED_ATTEMPTS: usize = 100;
-const SIMPLE_SEED_SIZE: usize = 16 * SIMPLE_SEED_ATTEMPTS;
+/// Seed bytes consumed by key generation: one 128-bit scalar candidate.
+const SIMPLE_SEED_SIZE: usize = 16;
+/// Bytes of one big-endian coordinate or scalar in the raw encodings.
+const SIMPLE_ELEMENT_SIZE: usize = 16;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 struct FieldElement(u128);
@@ -107,8 +139,9 @@ impl SimplePoint {
             (SimplePoint::Infinity, p) | (p, SimplePoint::Infinity) => p,
             (SimplePoint::Affine { x: x1, y: y1 }, SimplePoint::Affine { x: x2, y: y2 }) => {
                 if x1 == x2 {
-                    let y_sum = y1.add(y2);
-                    if y_sum.is_zero() {
+                    // -(x, y) = (x, x + y), so the points cancel exactly when
+                    // y1 + y2 = x1; otherwise they are equal.
+                    if y1.add(y2) == x1 {
                         SimplePoint::Infinity
                     } else {
                         SimplePoint::Affine { x: x1, y: y1 }.double()
@@ -192,11 +225,14 @@ impl SimplePoint {
 }
 
 fn base_point() -> SimplePoint {
-    SimplePoint::affine(FieldElement::new(BASE_X), FieldElement::new(BASE_Y))
+    SimplePoint::affine(
+        FieldElement::new(GENERATOR_X),
+        FieldElement::new(GENERATOR_Y),
+    )
 }
 
 fn curve_order_big() -> BigUint {
-    BigUint::from(CURVE_ORDER)
+    BigUint::from(GROUP_ORDER)
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -219,7 +255,7 @@ impl SimpleVerificationKey {
     }
 }
 
-// CBOR Serialization for SimpleVerificationKey
+// Serde for SimpleVerificationKey: a CBOR byte string, hex text in JSON
 #[cfg(feature = "serde")]
 impl serde::Serialize for SimpleVerificationKey {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -227,7 +263,7 @@ impl serde::Serialize for SimpleVerificationKey {
         S: serde::Serializer,
     {
         let bytes = SimpleVRF::raw_serialize_verification_key(self);
-        serializer.serialize_bytes(&bytes)
+        cardano_binary::bytes_or_hex::serialize(&bytes, serializer)
     }
 }
 
@@ -262,7 +298,7 @@ impl<'de> serde::Deserialize<'de> for SimpleVerificationKey {
             }
         }
 
-        deserializer.deserialize_bytes(BytesVisitor)
+        cardano_binary::bytes_or_hex::deserialize_with(deserializer, BytesVisitor)
     }
 }
 
@@ -313,7 +349,7 @@ impl SimpleCertificate {
     }
 }
 
-// CBOR Serialization for SimpleCertificate (VRF Proof)
+// Serde for SimpleCertificate (VRF Proof): a CBOR byte string, hex text in JSON
 #[cfg(feature = "serde")]
 impl serde::Serialize for SimpleCertificate {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -321,7 +357,7 @@ impl serde::Serialize for SimpleCertificate {
         S: serde::Serializer,
     {
         let bytes = SimpleVRF::raw_serialize_proof(self);
-        serializer.serialize_bytes(&bytes)
+        cardano_binary::bytes_or_hex::serialize(&bytes, serializer)
     }
 }
 
@@ -356,7 +392,7 @@ impl<'de> serde::Deserialize<'de> for SimpleCertificate {
             }
         }
 
-        deserializer.deserialize_bytes(BytesVisitor)
+        cardano_binary::bytes_or_hex::deserialize_with(deserializer, BytesVisitor)
     }
 }
 
@@ -369,7 +405,7 @@ fn gf_mul(mut a: u128, mut b: u128) -> u128 {
         b >>= 1;
         a <<= 1;
         if a & (1 << FIELD_DEGREE) != 0 {
-            a ^= REDUCTION_POLY;
+            a ^= FIELD_POLYNOMIAL;
         }
     }
     result & FIELD_MASK
@@ -405,10 +441,8 @@ fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
 }
 
 fn cbor_unsigned(value: u128) -> Value {
-    use ciborium::value::Integer;
-
     if value <= u64::MAX as u128 {
-        Value::Integer(Integer::from(value as u64))
+        Value::Int(value as i128)
     } else {
         let bytes = value
             .to_be_bytes()
@@ -424,7 +458,7 @@ fn encode_point(point: &SimplePoint) -> Vec<u8> {
         None => Value::Null,
         Some((x, y)) => Value::Array(vec![cbor_unsigned(x), cbor_unsigned(y)]),
     };
-    serialize(&encoded).expect("CBOR serialisation failed")
+    encode_value(&encoded).expect("CBOR serialisation failed")
 }
 
 fn bytes_to_u128(bytes: &[u8]) -> u128 {
@@ -448,11 +482,11 @@ fn hash_point(encoding: &[u8], scalar: u128) -> SimplePoint {
 }
 
 fn pow_base(scalar: u128) -> SimplePoint {
-    base_point().scalar_mul(scalar % CURVE_ORDER)
+    base_point().scalar_mul(scalar % GROUP_ORDER)
 }
 
 fn pow_point(point: SimplePoint, scalar: u128) -> SimplePoint {
-    point.scalar_mul(scalar % CURVE_ORDER)
+    point.scalar_mul(scalar % GROUP_ORDER)
 }
 
 fn combine_bytes(chunks: &[Vec<u8>]) -> Vec<u8> {
@@ -472,9 +506,9 @@ impl VRFAlgorithm for SimpleVRF {
 
     const ALGORITHM_NAME: &'static str = "simple";
     const SEED_SIZE: usize = SIMPLE_SEED_SIZE;
-    const VERIFICATION_KEY_SIZE: usize = 32;
-    const SIGNING_KEY_SIZE: usize = 16;
-    const PROOF_SIZE: usize = 64;
+    const VERIFICATION_KEY_SIZE: usize = 2 * SIMPLE_ELEMENT_SIZE;
+    const SIGNING_KEY_SIZE: usize = SIMPLE_ELEMENT_SIZE;
+    const PROOF_SIZE: usize = 4 * SIMPLE_ELEMENT_SIZE;
     const OUTPUT_SIZE: usize = SIMPLE_OUTPUT_SIZE;
 
     fn derive_verification_key(signing_key: &Self::SigningKey) -> Self::VerificationKey {
@@ -497,7 +531,7 @@ impl VRFAlgorithm for SimpleVRF {
         let output = OutputVRF::<Self>::from_bytes(y_bytes.to_vec())
             .expect("short hash length matches OUTPUT_SIZE");
 
-        let r = bytes_to_u128(&y_bytes) % CURVE_ORDER;
+        let r = bytes_to_u128(&y_bytes) % GROUP_ORDER;
 
         let vk_point = Self::derive_verification_key(signing_key).point();
         let pow_r = pow_base(r);
@@ -552,8 +586,8 @@ impl VRFAlgorithm for SimpleVRF {
         }
 
         let c_value = proof.challenge();
-        let c_mod = c_value % CURVE_ORDER;
-        let s_value = proof.response() % CURVE_ORDER;
+        let c_mod = c_value % GROUP_ORDER;
+        let s_value = proof.response() % GROUP_ORDER;
 
         let lhs = pow_base(s_value).add(pow_point(vk_point, c_mod).negate());
         let rhs_point =
@@ -574,14 +608,7 @@ impl VRFAlgorithm for SimpleVRF {
     }
 
     fn gen_key_from_seed_bytes(seed: &[u8]) -> Self::SigningKey {
-        assert_eq!(seed.len(), Self::SEED_SIZE, "unexpected seed length");
-        let mut rng = SeedRng::new(Seed::from_bytes(seed.to_vec()));
-        let mut buf = [0u8; 16];
-        rng.fill_bytes_checked(&mut buf)
-            .expect("insufficient seed material for SimpleVRF");
-        // Map into the range [1, CURVE_ORDER - 1] to avoid the point at infinity.
-        let candidate = bytes_to_u128(&buf) % (CURVE_ORDER - 1) + 1;
-        SimpleSigningKey(candidate)
+        SimpleVRF::signing_key_from_seed_bytes(seed).expect("unexpected seed length")
     }
 
     fn raw_serialize_verification_key(key: &Self::VerificationKey) -> Vec<u8> {
@@ -591,8 +618,11 @@ impl VRFAlgorithm for SimpleVRF {
             "cannot serialise point at infinity"
         );
         if let SimplePoint::Affine { x, y } = point {
-            let mut bytes = write_binary_natural(16, &BigUint::from(x.value()));
-            bytes.extend(write_binary_natural(16, &BigUint::from(y.value())));
+            let mut bytes = write_binary_natural(SIMPLE_ELEMENT_SIZE, &BigUint::from(x.value()));
+            bytes.extend(write_binary_natural(
+                SIMPLE_ELEMENT_SIZE,
+                &BigUint::from(y.value()),
+            ));
             bytes
         } else {
             Vec::new()
@@ -600,7 +630,7 @@ impl VRFAlgorithm for SimpleVRF {
     }
 
     fn raw_deserialize_verification_key(bytes: &[u8]) -> Option<Self::VerificationKey> {
-        let parts = splits_at(&[16, 16], bytes);
+        let parts = splits_at(&[SIMPLE_ELEMENT_SIZE; 2], bytes);
         if parts.len() != 2 {
             return None;
         }
@@ -611,12 +641,15 @@ impl VRFAlgorithm for SimpleVRF {
     }
 
     fn raw_serialize_signing_key(key: &Self::SigningKey) -> Vec<u8> {
-        write_binary_natural(16, &BigUint::from(key.value()))
+        write_binary_natural(SIMPLE_ELEMENT_SIZE, &BigUint::from(key.value()))
     }
 
     fn raw_deserialize_signing_key(bytes: &[u8]) -> Option<Self::SigningKey> {
+        if bytes.len() != SIMPLE_ELEMENT_SIZE {
+            return None;
+        }
         let value = bytes_to_u128(bytes);
-        if value == 0 || value >= CURVE_ORDER {
+        if value == 0 || value >= GROUP_ORDER {
             None
         } else {
             Some(SimpleSigningKey(value))
@@ -630,10 +663,19 @@ impl VRFAlgorithm for SimpleVRF {
             "cannot serialise point at infinity"
         );
         if let SimplePoint::Affine { x, y } = point {
-            let mut bytes = write_binary_natural(16, &BigUint::from(x.value()));
-            bytes.extend(write_binary_natural(16, &BigUint::from(y.value())));
-            bytes.extend(write_binary_natural(16, &BigUint::from(proof.challenge())));
-            bytes.extend(write_binary_natural(16, &BigUint::from(proof.response())));
+            let mut bytes = write_binary_natural(SIMPLE_ELEMENT_SIZE, &BigUint::from(x.value()));
+            bytes.extend(write_binary_natural(
+                SIMPLE_ELEMENT_SIZE,
+                &BigUint::from(y.value()),
+            ));
+            bytes.extend(write_binary_natural(
+                SIMPLE_ELEMENT_SIZE,
+                &BigUint::from(proof.challenge()),
+            ));
+            bytes.extend(write_binary_natural(
+                SIMPLE_ELEMENT_SIZE,
+                &BigUint::from(proof.response()),
+            ));
             bytes
         } else {
             Vec::new()
@@ -641,7 +683,7 @@ impl VRFAlgorithm for SimpleVRF {
     }
 
     fn raw_deserialize_proof(bytes: &[u8]) -> Option<Self::Proof> {
-        let parts = splits_at(&[16, 16, 16, 16], bytes);
+        let parts = splits_at(&[SIMPLE_ELEMENT_SIZE; 4], bytes);
         if parts.len() != 4 {
             return None;
         }
@@ -660,6 +702,31 @@ impl VRFAlgorithm for SimpleVRF {
     }
 }
 
+impl SimpleVRF {
+    /// Derive the signing key for `seed`, which must be exactly
+    /// [`SEED_SIZE`](VRFAlgorithm::SEED_SIZE) bytes.
+    ///
+    /// The seed is reduced into `[1, GROUP_ORDER - 1]` in one step, so every
+    /// seed of the right length succeeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VRFError::WrongLength`] if `seed` has any other length.
+    pub fn signing_key_from_seed_bytes(seed: &[u8]) -> Result<SimpleSigningKey, VRFError> {
+        if seed.len() != SIMPLE_SEED_SIZE {
+            return Err(VRFError::wrong_length(
+                "simple VRF seed",
+                SIMPLE_SEED_SIZE,
+                seed.len(),
+            ));
+        }
+        // Skip zero, whose verification key is the point at infinity.
+        Ok(SimpleSigningKey(
+            bytes_to_u128(seed) % (GROUP_ORDER - 1) + 1,
+        ))
+    }
+}
+
 impl From<SimpleSigningKey> for SimpleVerificationKey {
     fn from(value: SimpleSigningKey) -> Self {
         SimpleVRF::derive_verification_key(&value)
@@ -700,4 +767,20 @@ mod tests {
             .expect("verification should succeed");
         assert_eq!(output.as_bytes(), verified.as_bytes());
     }
+
+    #[test]
+    fn generator_has_the_published_order() {
+        let generator = base_point();
+        assert!(generator.is_on_curve());
+        assert!(!generator.scalar_mul(GROUP_ORDER - 1).is_infinity());
+        assert!(generator.scalar_mul(GROUP_ORDER).is_infinity());
+        assert_eq!(generator.scalar_mul(GROUP_ORDER - 1), generator.negate());
+    }
+
+    #[test]
+    fn field_polynomial_reduces_the_top_bit() {
+        // x^112 * x = x^113 = x^9 + 1
+        assert_eq!(gf_mul(1 << 112, 2), (1 << 9) | 1);
+        assert_eq!(FIELD_POLYNOMIAL & FIELD_MASK, (1 << 9) | 1);
+    }
 }
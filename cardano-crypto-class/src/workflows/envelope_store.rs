@@ -0,0 +1,335 @@
+//! Key files in a directory, in the `cardano-cli` text envelope format.
+//!
+//! [`EnvelopeStore::save`] writes `{name}.skey` or `{name}.vkey` with the
+//! envelope type of the key's role, and [`EnvelopeStore::load`] refuses a
+//! file whose type belongs to another role, so a payment key cannot be
+//! loaded where a cold key is expected. Files are written to a fresh
+//! temporary file and renamed into place, so a crash never leaves a
+//! truncated key; on Unix signing key files are created with mode `0600`.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use zeroize::Zeroizing;
+
+use crate::role::{EnvelopeRole, KeyKind, RoleTagged, TextEnvelope, TextEnvelopeKey};
+
+use super::{PersistedKesSession, WorkflowError};
+
+/// A directory of key files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvelopeStore {
+    dir: PathBuf,
+}
+
+impl EnvelopeStore {
+    /// A store over `dir`, which must already exist.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        EnvelopeStore { dir: dir.into() }
+    }
+
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the key file for `name`: `{name}.skey` for signing keys and
+    /// `{name}.vkey` for verification keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkflowError::InvalidFile`] unless `name` is a single,
+    /// non-empty file name component.
+    pub fn key_path(&self, name: &str, kind: KeyKind) -> Result<PathBuf, WorkflowError> {
+        let extension = match kind {
+            KeyKind::Signing => "skey",
+            KeyKind::Verification => "vkey",
+        };
+        self.file_path(&format!("{name}.{extension}"), name)
+    }
+
+    fn file_path(&self, file_name: &str, name: &str) -> Result<PathBuf, WorkflowError> {
+        let path = self.dir.join(file_name);
+        if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+            return Err(WorkflowError::InvalidFile {
+                path,
+                message: "key names must be a single file name component".to_owned(),
+            });
+        }
+        Ok(path)
+    }
+
+    /// Write `key` to its key file, replacing any existing one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkflowError::InvalidFile`] for an unusable `name` and
+    /// [`WorkflowError::Io`] if the file cannot be written.
+    pub fn save<R: EnvelopeRole, K: TextEnvelopeKey>(
+        &self,
+        name: &str,
+        key: &RoleTagged<R, K>,
+    ) -> Result<PathBuf, WorkflowError> {
+        let path = self.key_path(name, K::KIND)?;
+        write_json(&path, &key.to_text_envelope(), K::KIND == KeyKind::Signing)?;
+        Ok(path)
+    }
+
+    /// Read the key file for `name` as a key with role `R`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkflowError::Io`] if the file cannot be read,
+    /// [`WorkflowError::InvalidFile`] if it is not a text envelope, and
+    /// [`WorkflowError::Envelope`] if the envelope is for another role or
+    /// does not hold a valid key.
+    pub fn load<R: EnvelopeRole, K: TextEnvelopeKey>(
+        &self,
+        name: &str,
+    ) -> Result<RoleTagged<R, K>, WorkflowError> {
+        let envelope = self.load_envelope(&self.key_path(name, K::KIND)?)?;
+        Ok(RoleTagged::from_text_envelope(&envelope)?)
+    }
+
+    /// Read a text envelope from any path, without checking its type.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkflowError::Io`] if the file cannot be read and
+    /// [`WorkflowError::InvalidFile`] if it is not a text envelope.
+    pub fn load_envelope(&self, path: &Path) -> Result<TextEnvelope, WorkflowError> {
+        read_json(path)
+    }
+
+    /// Write a KES session to `{name}.kes.json`, readable only by the owner
+    /// on Unix.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkflowError::InvalidFile`] for an unusable `name` and
+    /// [`WorkflowError::Io`] if the file cannot be written.
+    pub fn save_kes_session(
+        &self,
+        name: &str,
+        session: &PersistedKesSession,
+    ) -> Result<PathBuf, WorkflowError> {
+        let path = self.file_path(&format!("{name}.kes.json"), name)?;
+        write_json(&path, session, true)?;
+        Ok(path)
+    }
+
+    /// Read a KES session written by [`save_kes_session`](Self::save_kes_session).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkflowError::Io`] if the file cannot be read and
+    /// [`WorkflowError::InvalidFile`] if it does not hold a session.
+    pub fn load_kes_session(&self, name: &str) -> Result<PersistedKesSession, WorkflowError> {
+        read_json(&self.file_path(&format!("{name}.kes.json"), name)?)
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, WorkflowError> {
+    let bytes =
+        Zeroizing::new(fs::read(path).map_err(|err| WorkflowError::io(path.to_owned(), &err))?);
+    serde_json::from_slice(&bytes).map_err(|err| WorkflowError::InvalidFile {
+        path: path.to_owned(),
+        message: err.to_string(),
+    })
+}
+
+/// Pretty-print `value` with the four-space indent `cardano-cli` uses and
+/// move it into place at `path`.
+fn write_json<T: Serialize>(path: &Path, value: &T, secret: bool) -> Result<(), WorkflowError> {
+    let mut json = Zeroizing::new(Vec::new());
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut *json, formatter);
+    value
+        .serialize(&mut serializer)
+        .map_err(|err| WorkflowError::InvalidFile {
+            path: path.to_owned(),
+            message: err.to_string(),
+        })?;
+    json.push(b'\n');
+
+    write_new(path, &json, secret)
+        .and_then(|tmp| {
+            fs::rename(&tmp, path).inspect_err(|_| {
+                let _ = fs::remove_file(&tmp);
+            })
+        })
+        .map_err(|err| WorkflowError::io(path.to_owned(), &err))
+}
+
+/// Attempts at a free temporary name before giving up.
+const TEMP_ATTEMPTS: usize = 16;
+
+/// Write `contents` to a new file next to `path` and return its name.
+///
+/// The name has a random suffix and the file is opened with `create_new`,
+/// so an existing file or symlink there, stale or planted, is never written
+/// through, and on Unix a secret file has mode `0600` from the start. The
+/// file is removed again if writing fails.
+fn write_new(path: &Path, contents: &[u8], secret: bool) -> io::Result<PathBuf> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if secret {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = secret;
+
+    let mut attempt = 0;
+    let (tmp, mut file) = loop {
+        let mut tmp_name = path.as_os_str().to_owned();
+        tmp_name.push(format!(".{:016x}.tmp", rand::random::<u64>()));
+        let tmp = PathBuf::from(tmp_name);
+        match options.open(&tmp) {
+            Ok(file) => break (tmp, file),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists && attempt < TEMP_ATTEMPTS => {
+                attempt += 1;
+            },
+            Err(err) => return Err(err),
+        }
+    };
+    match file.write_all(contents).and_then(|()| file.sync_all()) {
+        Ok(()) => Ok(tmp),
+        Err(err) => {
+            drop(file);
+            let _ = fs::remove_file(&tmp);
+            Err(err)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsign::ed25519::Ed25519;
+    use crate::dsign::ed25519::{Ed25519SigningKey, Ed25519VerificationKey};
+    use crate::role::{ColdRole, PaymentRole, derive_dsign_verification_key, gen_dsign_key};
+
+    fn store() -> (tempfile::TempDir, EnvelopeStore) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = EnvelopeStore::new(dir.path());
+        (dir, store)
+    }
+
+    #[test]
+    fn keys_round_trip_in_cardano_cli_format() {
+        let (_dir, store) = store();
+        let signing_key = gen_dsign_key::<PaymentRole, Ed25519>(&[1u8; 32]).unwrap();
+        let verification_key = derive_dsign_verification_key::<PaymentRole, Ed25519>(&signing_key);
+
+        let skey = store.save("payment", &signing_key).unwrap();
+        let vkey = store.save("payment", &verification_key).unwrap();
+        assert_eq!(skey, store.dir().join("payment.skey"));
+        assert_eq!(vkey, store.dir().join("payment.vkey"));
+
+        let text = fs::read_to_string(&vkey).unwrap();
+        assert!(text.starts_with("{\n    \"type\": \"PaymentVerificationKeyShelley_ed25519\",\n"));
+
+        let loaded: RoleTagged<PaymentRole, Ed25519SigningKey> = store.load("payment").unwrap();
+        assert_eq!(loaded, signing_key);
+        let loaded: RoleTagged<PaymentRole, Ed25519VerificationKey> =
+            store.load("payment").unwrap();
+        assert_eq!(loaded, verification_key);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signing_keys_are_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (_dir, store) = store();
+        let signing_key = gen_dsign_key::<ColdRole, Ed25519>(&[2u8; 32]).unwrap();
+        let path = store.save("cold", &signing_key).unwrap();
+        let mode = fs::metadata(path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o077, 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn saving_never_writes_through_a_planted_temporary_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (dir, store) = store();
+        // The temporary name the store used to write through.
+        let target = dir.path().join("elsewhere");
+        std::os::unix::fs::symlink(&target, dir.path().join("cold.skey.tmp")).unwrap();
+        let signing_key = gen_dsign_key::<ColdRole, Ed25519>(&[2u8; 32]).unwrap();
+        let path = store.save("cold", &signing_key).unwrap();
+
+        assert!(!target.exists());
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o077, 0);
+        let mut names: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["cold.skey", "cold.skey.tmp"]);
+    }
+
+    #[test]
+    fn load_refuses_other_roles_and_bad_files() {
+        let (_dir, store) = store();
+        let signing_key = gen_dsign_key::<PaymentRole, Ed25519>(&[3u8; 32]).unwrap();
+        store.save("key", &signing_key).unwrap();
+
+        let wrong_role = store.load::<ColdRole, Ed25519SigningKey>("key");
+        assert_eq!(
+            wrong_role.err().map(|err| err.code()),
+            Some("workflows.invalid_envelope")
+        );
+
+        let missing = store.load::<PaymentRole, Ed25519SigningKey>("missing");
+        assert!(matches!(
+            missing,
+            Err(WorkflowError::Io {
+                kind: std::io::ErrorKind::NotFound,
+                ..
+            })
+        ));
+
+        fs::write(store.dir().join("garbage.skey"), "not json").unwrap();
+        let garbage = store.load::<PaymentRole, Ed25519SigningKey>("garbage");
+        assert_eq!(
+            garbage.err().map(|err| err.code()),
+            Some("workflows.invalid_file")
+        );
+
+        for name in ["", "..", "a/b"] {
+            assert!(matches!(
+                store.save(name, &signing_key),
+                Err(WorkflowError::InvalidFile { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn kes_sessions_round_trip() {
+        use crate::kes::Sum2Kes;
+        use crate::role::gen_kes_key;
+        use crate::workflows::{KesSessionConfig, KesSessionManager};
+
+        let config = KesSessionConfig {
+            slots_per_kes_period: 10,
+            ocert_start_period: 0,
+            max_kes_evolutions: 4,
+        };
+        let (_dir, store) = store();
+        let mut session =
+            KesSessionManager::<Sum2Kes>::new(config, gen_kes_key::<Sum2Kes>(&[4u8; 32]).unwrap());
+        session.evolve_to(&(), 1).unwrap();
+        let persisted = session.persist().unwrap();
+
+        store.save_kes_session("pool", &persisted).unwrap();
+        assert_eq!(store.load_kes_session("pool").unwrap(), persisted);
+    }
+}
@@ -0,0 +1,352 @@
+//! Signing block headers with a KES hot key over the life of its
+//! operational certificate.
+//!
+//! A [`KesSessionManager`] owns the signing key and the evolution it is at.
+//! [`sign`](KesSessionManager::sign) maps the slot to the evolution the
+//! certificate requires, evolves the key forward to it and signs; asking for
+//! an evolution the key has already left is refused with
+//! [`WorkflowError::StalePeriod`] rather than silently signing with the
+//! wrong period. Once the scheme's last period is passed the key is
+//! forgotten and every further request fails with
+//! [`KesError::KeyExpired`].
+
+use zeroize::Zeroizing;
+
+use crate::direct_serialise::{
+    DirectDeserialise, DirectSerialise, direct_deserialise_buf_checked,
+    direct_serialise_buf_checked,
+};
+use crate::kes::evolution::evolve_key_to;
+use crate::kes::{KesAlgorithm, KesError, KesMError, Period, validate_ocert_period};
+use crate::role::{KesRole, KeyKind, RoleTagged, TextEnvelope, kes_envelope_type};
+
+use super::WorkflowError;
+
+/// The operational certificate and protocol parameters a session signs
+/// under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KesSessionConfig {
+    /// Slots in one KES period (129 600 on mainnet).
+    pub slots_per_kes_period: u64,
+    /// KES period the operational certificate starts at.
+    pub ocert_start_period: Period,
+    /// The protocol's `maxKESEvolutions` (62 on mainnet).
+    pub max_kes_evolutions: u64,
+}
+
+/// A KES signing key together with the evolution it has reached.
+///
+/// The key is forgotten when the manager is dropped.
+pub struct KesSessionManager<K: KesAlgorithm> {
+    config: KesSessionConfig,
+    signing_key: Option<K::SigningKey>,
+    evolution: Period,
+}
+
+impl<K: KesAlgorithm> KesSessionManager<K> {
+    /// Start a session with a freshly generated key, at evolution 0.
+    #[must_use]
+    pub fn new(config: KesSessionConfig, signing_key: RoleTagged<KesRole, K::SigningKey>) -> Self {
+        Self::at_evolution(config, signing_key.into_inner(), 0)
+    }
+
+    fn at_evolution(
+        config: KesSessionConfig,
+        signing_key: K::SigningKey,
+        evolution: Period,
+    ) -> Self {
+        KesSessionManager {
+            config,
+            signing_key: Some(signing_key),
+            evolution,
+        }
+    }
+
+    #[must_use]
+    pub fn config(&self) -> &KesSessionConfig {
+        &self.config
+    }
+
+    /// Evolution the key is currently at, relative to the certificate's
+    /// start period.
+    #[must_use]
+    pub fn evolution(&self) -> Period {
+        self.evolution
+    }
+
+    /// Whether the key has evolved past the scheme's last period and been
+    /// forgotten.
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.signing_key.is_none()
+    }
+
+    /// Derive the verification key of the session's key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KesError::KeyExpired`] once the key has been forgotten, or
+    /// the scheme's error if derivation fails.
+    pub fn verification_key(&self) -> Result<K::VerificationKey, WorkflowError> {
+        Ok(K::derive_verification_key(self.key()?)?)
+    }
+
+    fn key(&self) -> Result<&K::SigningKey, WorkflowError> {
+        self.signing_key
+            .as_ref()
+            .ok_or(WorkflowError::Kes(KesMError::Kes(KesError::KeyExpired)))
+    }
+
+    /// Evolve the key forward to `target`, one period at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkflowError::StalePeriod`] if `target` is before the
+    /// current evolution, and [`KesError::KeyExpired`] if the key expires on
+    /// the way, after which the session is expired. A failure inside
+    /// `update_kes` also leaves the session expired, since the scheme
+    /// consumes the key it evolves.
+    pub fn evolve_to(&mut self, context: &K::Context, target: Period) -> Result<(), WorkflowError> {
+        Ok(evolve_key_to::<K>(
+            context,
+            &mut self.signing_key,
+            &mut self.evolution,
+            target,
+        )?)
+    }
+
+    /// Sign `message` for a block in `slot`, evolving the key first if the
+    /// slot is in a later KES period.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkflowError::OcertPeriod`] if the certificate is not
+    /// valid at `slot`, and the errors of [`evolve_to`](Self::evolve_to)
+    /// and `sign_kes`.
+    pub fn sign(
+        &mut self,
+        context: &K::Context,
+        slot: u64,
+        message: &[u8],
+    ) -> Result<K::Signature, WorkflowError> {
+        let info = validate_ocert_period(
+            slot,
+            self.config.slots_per_kes_period,
+            self.config.ocert_start_period,
+            self.config.max_kes_evolutions,
+        )?;
+        self.evolve_to(context, info.evolution)?;
+        Ok(K::sign_kes(context, info.evolution, message, self.key()?)?)
+    }
+}
+
+impl<K> KesSessionManager<K>
+where
+    K: KesAlgorithm,
+    K::SigningKey: DirectSerialise + DirectDeserialise,
+{
+    /// Capture the key and its evolution for [`restore`](Self::restore).
+    ///
+    /// The key is written in the raw `SIGNING_KEY_SIZE` layout of the
+    /// scheme, wrapped in a `cardano-cli` KES signing key envelope whose
+    /// type names the scheme's depth (see [`kes_envelope_type`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KesError::KeyExpired`] once the key has been forgotten, or
+    /// a [`DirectSerialiseError`](crate::DirectSerialiseError) if the key
+    /// does not fill the raw layout.
+    pub fn persist(&self) -> Result<PersistedKesSession, WorkflowError> {
+        let mut raw = Zeroizing::new(vec![0u8; K::SIGNING_KEY_SIZE]);
+        let written = direct_serialise_buf_checked(&mut raw, self.key()?);
+        let key = written.map(|()| {
+            TextEnvelope::from_raw_key::<KesRole>(
+                &kes_envelope_type::<K>(KeyKind::Signing),
+                KeyKind::Signing,
+                &raw,
+            )
+        });
+        Ok(PersistedKesSession {
+            key: key?,
+            evolution: self.evolution,
+        })
+    }
+
+    /// Resume a session captured by [`persist`](Self::persist).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkflowError::Envelope`] if the envelope is not a signing
+    /// key of this scheme's depth, a
+    /// [`DirectSerialiseError`](crate::DirectSerialiseError) if its payload
+    /// does not decode as a key of this scheme, and
+    /// [`KesError::PeriodOutOfRange`] if the recorded evolution is past the
+    /// scheme's last period.
+    pub fn restore(
+        config: KesSessionConfig,
+        persisted: &PersistedKesSession,
+    ) -> Result<Self, WorkflowError> {
+        if persisted.evolution >= K::total_periods() {
+            return Err(KesMError::Kes(KesError::PeriodOutOfRange {
+                period: persisted.evolution,
+                max_period: K::total_periods(),
+            })
+            .into());
+        }
+        let raw = persisted
+            .key
+            .raw_key(&kes_envelope_type::<K>(KeyKind::Signing))?;
+        let key = direct_deserialise_buf_checked::<K::SigningKey>(&raw)?;
+        Ok(Self::at_evolution(config, key, persisted.evolution))
+    }
+}
+
+impl<K: KesAlgorithm> Drop for KesSessionManager<K> {
+    fn drop(&mut self) {
+        if let Some(key) = self.signing_key.take() {
+            K::forget_signing_key_kes(key);
+        }
+    }
+}
+
+/// A KES session saved between runs: the key at its current evolution and
+/// the evolution itself, which the raw key layout does not record.
+///
+/// The key envelope wipes its payload on drop and leaves it out of `Debug`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PersistedKesSession {
+    pub key: TextEnvelope,
+    #[serde(with = "crate::kes::serde_period")]
+    pub evolution: Period,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kes::{CompactSum2Kes, Sum2Kes};
+    use crate::role::gen_kes_key;
+
+    const CONFIG: KesSessionConfig = KesSessionConfig {
+        slots_per_kes_period: 10,
+        ocert_start_period: 3,
+        max_kes_evolutions: 4,
+    };
+
+    fn session<K: KesAlgorithm>() -> KesSessionManager<K> {
+        KesSessionManager::new(CONFIG, gen_kes_key::<K>(&[5u8; 32]).unwrap())
+    }
+
+    #[test]
+    fn signs_at_the_evolution_of_the_slot() {
+        let mut session = session::<Sum2Kes>();
+        let vk = session.verification_key().unwrap();
+        for (slot, evolution) in [(30, 0), (39, 0), (41, 1), (65, 3)] {
+            let signature = session.sign(&(), slot, b"header").unwrap();
+            assert_eq!(session.evolution(), evolution);
+            Sum2Kes::verify_kes(&(), &vk, evolution, b"header", &signature).unwrap();
+        }
+    }
+
+    #[test]
+    fn refuses_earlier_evolutions() {
+        let mut session = session::<Sum2Kes>();
+        session.sign(&(), 52, b"header").unwrap();
+        assert!(matches!(
+            session.sign(&(), 45, b"header"),
+            Err(WorkflowError::StalePeriod {
+                requested: 1,
+                current: 2
+            })
+        ));
+        // The refusal leaves the key usable.
+        session.sign(&(), 55, b"header").unwrap();
+    }
+
+    #[test]
+    fn refuses_slots_outside_the_certificate() {
+        let mut session = session::<Sum2Kes>();
+        for slot in [29, 70] {
+            assert!(matches!(
+                session.sign(&(), slot, b"header"),
+                Err(WorkflowError::OcertPeriod(_))
+            ));
+        }
+        assert_eq!(session.evolution(), 0);
+    }
+
+    #[test]
+    fn expires_after_the_last_period() {
+        let mut session = session::<Sum2Kes>();
+        session.evolve_to(&(), 3).unwrap();
+        let err = session.evolve_to(&(), 4).unwrap_err();
+        assert_eq!(err.code(), "kes.key_expired");
+        assert!(session.is_expired());
+        assert_eq!(
+            session.verification_key().unwrap_err().code(),
+            "kes.key_expired"
+        );
+    }
+
+    fn round_trip<K>()
+    where
+        K: KesAlgorithm<Context = ()>,
+        K::SigningKey: DirectSerialise + DirectDeserialise,
+    {
+        let mut original = session::<K>();
+        original.sign(&(), 52, b"header").unwrap();
+        let persisted = original.persist().unwrap();
+        assert_eq!(persisted.evolution, 2);
+        assert_eq!(persisted.key.type_, "KesSigningKey_ed25519_kes_2^2");
+
+        let json = serde_json::to_string(&persisted).unwrap();
+        let decoded: PersistedKesSession = serde_json::from_str(&json).unwrap();
+        let mut restored = KesSessionManager::<K>::restore(CONFIG, &decoded).unwrap();
+        assert_eq!(restored.evolution(), 2);
+        let restored = restored.sign(&(), 61, b"next").unwrap();
+        let original = original.sign(&(), 61, b"next").unwrap();
+        assert_eq!(
+            K::raw_serialize_signature_kes(&restored),
+            K::raw_serialize_signature_kes(&original)
+        );
+    }
+
+    #[test]
+    fn persisted_sessions_sign_like_the_original() {
+        round_trip::<Sum2Kes>();
+        round_trip::<CompactSum2Kes>();
+    }
+
+    #[test]
+    fn debug_output_leaves_out_the_key() {
+        let persisted = session::<Sum2Kes>().persist().unwrap();
+        let debug = format!("{persisted:?}");
+        assert!(!debug.contains(&persisted.key.cbor_hex[..]));
+        assert!(debug.contains("<redacted>"));
+        assert!(debug.contains("evolution: 0"));
+    }
+
+    #[test]
+    fn restore_rejects_foreign_envelopes_and_evolutions() {
+        let persisted = session::<Sum2Kes>().persist().unwrap();
+
+        let mut wrong_type = persisted.clone();
+        wrong_type.key.type_ = "VrfSigningKey_PraosVRF".to_owned();
+        assert_eq!(
+            KesSessionManager::<Sum2Kes>::restore(CONFIG, &wrong_type)
+                .err()
+                .map(|err| err.code()),
+            Some("workflows.invalid_envelope")
+        );
+
+        let mut past_end = persisted.clone();
+        past_end.evolution = 4;
+        assert_eq!(
+            KesSessionManager::<Sum2Kes>::restore(CONFIG, &past_end)
+                .err()
+                .map(|err| err.code()),
+            Some("kes.period_out_of_range")
+        );
+
+        assert!(KesSessionManager::<crate::kes::Sum3Kes>::restore(CONFIG, &persisted).is_err());
+    }
+}
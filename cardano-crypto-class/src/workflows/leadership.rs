@@ -0,0 +1,300 @@
+//! Finding the slots a pool leads in, and checking another pool's claim.
+//!
+//! Praos elects a pool in a slot when the leader value of its VRF output on
+//! [`NonceProofMessage`] is below a threshold derived from its relative
+//! stake. [`LeadershipSchedule::compute`] evaluates the VRF for every slot of
+//! a range with one [`PraosProvingContext`] and keeps the winning slots with
+//! their proofs; [`LeaderSlot::verify`] repeats the check against the
+//! pool's verification key.
+
+use std::ops::Range;
+
+use num_bigint::BigUint;
+
+use crate::hash::{Blake2b256, HashAlgorithm};
+use crate::role::{RoleTagged, VrfRole};
+use crate::signable::NonceProofMessage;
+use crate::util::SignableRepresentation;
+use crate::vrf::praos::{
+    PraosProof, PraosProvingContext, PraosSigningKey, PraosVerificationKeyPrepared,
+};
+
+use super::WorkflowError;
+
+/// Leader values are 256-bit naturals, so the threshold is a fraction of
+/// `2^256`.
+const LEADER_VALUE_BITS: u64 = 256;
+
+/// Praos' leader value of a VRF output: `Blake2b-256("L" ‖ output)`.
+#[must_use]
+pub fn leader_value(output: &[u8]) -> [u8; 32] {
+    let mut value = [0u8; 32];
+    value.copy_from_slice(&Blake2b256::hash_concat(b"L", output));
+    value
+}
+
+/// Leader values below this natural win the slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderThreshold(BigUint);
+
+impl LeaderThreshold {
+    /// A threshold computed elsewhere, e.g. by the ledger's exact
+    /// `checkLeaderNatValue` arithmetic.
+    #[must_use]
+    pub fn from_natural(threshold: BigUint) -> Self {
+        LeaderThreshold(threshold)
+    }
+
+    /// A threshold given as big-endian bytes.
+    #[must_use]
+    pub fn from_be_bytes(bytes: &[u8]) -> Self {
+        LeaderThreshold(BigUint::from_bytes_be(bytes))
+    }
+
+    /// `2^256 · (1 − (1 − f)^σ)` for active slot coefficient `f` and
+    /// relative stake `sigma`, or `None` unless `0 < f ≤ 1` and
+    /// `0 ≤ sigma ≤ 1`.
+    ///
+    /// The probability is computed in `f64` and keeps 53 significant bits,
+    /// so slots whose leader value is within about `2^-53` of the boundary
+    /// may be classified differently from the ledger. Use it for planning;
+    /// block validation must use the exact threshold via
+    /// [`from_natural`](Self::from_natural).
+    #[must_use]
+    pub fn from_stake(f: f64, sigma: f64) -> Option<Self> {
+        if !(f > 0.0 && f <= 1.0 && (0.0..=1.0).contains(&sigma)) {
+            return None;
+        }
+        // 1 − (1 − f)^σ = −expm1(σ · ln(1 − f)), without cancellation for
+        // small stakes.
+        let probability = -(sigma * (-f).ln_1p()).exp_m1();
+        let mantissa = (probability.clamp(0.0, 1.0) * (1u64 << 53) as f64) as u64;
+        Some(LeaderThreshold(
+            BigUint::from(mantissa) << (LEADER_VALUE_BITS - 53),
+        ))
+    }
+
+    #[must_use]
+    pub fn as_natural(&self) -> &BigUint {
+        &self.0
+    }
+
+    /// Whether `leader_value` wins against this threshold.
+    #[must_use]
+    pub fn is_leader(&self, leader_value: &[u8; 32]) -> bool {
+        BigUint::from_bytes_be(leader_value) < self.0
+    }
+}
+
+/// The epoch-wide inputs of the leader check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochLeadershipParams {
+    /// Epoch nonce, `None` for the neutral nonce.
+    pub epoch_nonce: Option<[u8; 32]>,
+    /// The pool's threshold for this epoch.
+    pub threshold: LeaderThreshold,
+}
+
+/// A slot the pool leads, with the proof a block header carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderSlot {
+    pub slot: u64,
+    pub proof: PraosProof,
+    pub leader_value: [u8; 32],
+}
+
+impl LeaderSlot {
+    /// Check that `proof` is a VRF proof for this slot under
+    /// `verification_key` and that its leader value wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkflowError::InvalidLeaderProof`] if the proof does not
+    /// verify, its leader value differs from the recorded one, or it is not
+    /// below the threshold.
+    pub fn verify(
+        &self,
+        verification_key: &PraosVerificationKeyPrepared,
+        params: &EpochLeadershipParams,
+    ) -> Result<(), WorkflowError> {
+        let message = NonceProofMessage::new(self.slot, params.epoch_nonce);
+        let output = verification_key
+            .verify(&message.signable_representation(), &self.proof)?
+            .ok_or(WorkflowError::InvalidLeaderProof { slot: self.slot })?;
+        let value = leader_value(&output);
+        if value != self.leader_value || !params.threshold.is_leader(&value) {
+            return Err(WorkflowError::InvalidLeaderProof { slot: self.slot });
+        }
+        Ok(())
+    }
+}
+
+/// The slots of a range a pool leads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeadershipSchedule {
+    slots: Range<u64>,
+    leader_slots: Vec<LeaderSlot>,
+}
+
+impl LeadershipSchedule {
+    /// Evaluate the leader check for every slot in `slots`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkflowError::Vrf`] if the signing key is malformed or
+    /// proving fails.
+    pub fn compute(
+        signing_key: &RoleTagged<VrfRole, PraosSigningKey>,
+        params: &EpochLeadershipParams,
+        slots: Range<u64>,
+    ) -> Result<Self, WorkflowError> {
+        let context = PraosProvingContext::new(signing_key.inner())?;
+        let mut leader_slots = Vec::new();
+        for slot in slots.clone() {
+            let message = NonceProofMessage::new(slot, params.epoch_nonce);
+            let proof = context.prove_with_ctx(&message.signable_representation())?;
+            let output = proof
+                .to_output_bytes()?
+                .ok_or(WorkflowError::InvalidLeaderProof { slot })?;
+            let value = leader_value(&output);
+            if params.threshold.is_leader(&value) {
+                leader_slots.push(LeaderSlot {
+                    slot,
+                    proof,
+                    leader_value: value,
+                });
+            }
+        }
+        Ok(LeadershipSchedule {
+            slots,
+            leader_slots,
+        })
+    }
+
+    /// Verify every leader slot with [`LeaderSlot::verify`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first slot's error.
+    pub fn verify(
+        &self,
+        verification_key: &PraosVerificationKeyPrepared,
+        params: &EpochLeadershipParams,
+    ) -> Result<(), WorkflowError> {
+        self.leader_slots
+            .iter()
+            .try_for_each(|slot| slot.verify(verification_key, params))
+    }
+
+    /// The range of slots that was checked.
+    #[must_use]
+    pub fn range(&self) -> Range<u64> {
+        self.slots.clone()
+    }
+
+    /// The slots the pool leads, in ascending order.
+    #[must_use]
+    pub fn leader_slots(&self) -> &[LeaderSlot] {
+        &self.leader_slots
+    }
+
+    #[must_use]
+    pub fn into_leader_slots(self) -> Vec<LeaderSlot> {
+        self.leader_slots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::role::{derive_vrf_verification_key, gen_vrf_key};
+    use crate::vrf::praos::PraosVRF;
+
+    fn key() -> RoleTagged<VrfRole, PraosSigningKey> {
+        gen_vrf_key::<PraosVRF>(&[7u8; 32]).unwrap()
+    }
+
+    fn params(f: f64, sigma: f64) -> EpochLeadershipParams {
+        EpochLeadershipParams {
+            epoch_nonce: Some([0x11; 32]),
+            threshold: LeaderThreshold::from_stake(f, sigma).unwrap(),
+        }
+    }
+
+    #[test]
+    fn thresholds_from_stake() {
+        let all = LeaderThreshold::from_stake(1.0, 1.0).unwrap();
+        assert_eq!(all.as_natural(), &(BigUint::from(1u8) << 256));
+        assert!(all.is_leader(&[0xff; 32]));
+
+        let none = LeaderThreshold::from_stake(0.05, 0.0).unwrap();
+        assert!(!none.is_leader(&[0; 32]));
+
+        let half = LeaderThreshold::from_stake(0.5, 1.0).unwrap();
+        assert_eq!(half.as_natural(), &(BigUint::from(1u8) << 255));
+
+        for (f, sigma) in [(0.0, 0.5), (1.5, 0.5), (0.05, -0.1), (0.05, f64::NAN)] {
+            assert_eq!(LeaderThreshold::from_stake(f, sigma), None);
+        }
+    }
+
+    #[test]
+    fn schedule_is_deterministic_and_verifies() {
+        let params = params(0.5, 0.5);
+        let schedule = LeadershipSchedule::compute(&key(), &params, 0..64).unwrap();
+        assert_eq!(
+            schedule,
+            LeadershipSchedule::compute(&key(), &params, 0..64).unwrap()
+        );
+        // About 29% of slots; far from both 0 and 64.
+        assert!((5..40).contains(&schedule.leader_slots().len()));
+
+        let vk = derive_vrf_verification_key::<PraosVRF>(&key());
+        let prepared = vk.inner().prepare().unwrap();
+        schedule.verify(&prepared, &params).unwrap();
+    }
+
+    #[test]
+    fn full_stake_leads_every_slot() {
+        let schedule = LeadershipSchedule::compute(&key(), &params(1.0, 1.0), 10..20).unwrap();
+        let slots: Vec<u64> = schedule.leader_slots().iter().map(|s| s.slot).collect();
+        assert_eq!(slots, (10..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn verify_rejects_other_slots_nonces_and_keys() {
+        let params = params(1.0, 1.0);
+        let schedule = LeadershipSchedule::compute(&key(), &params, 0..2).unwrap();
+        let prepared = derive_vrf_verification_key::<PraosVRF>(&key())
+            .inner()
+            .prepare()
+            .unwrap();
+
+        let mut moved = schedule.leader_slots()[0].clone();
+        moved.slot = 1;
+        assert!(matches!(
+            moved.verify(&prepared, &params),
+            Err(WorkflowError::InvalidLeaderProof { slot: 1 })
+        ));
+
+        let other_nonce = EpochLeadershipParams {
+            epoch_nonce: None,
+            ..params.clone()
+        };
+        assert!(schedule.verify(&prepared, &other_nonce).is_err());
+
+        let other_key = gen_vrf_key::<PraosVRF>(&[8u8; 32]).unwrap();
+        let other = derive_vrf_verification_key::<PraosVRF>(&other_key)
+            .inner()
+            .prepare()
+            .unwrap();
+        assert!(schedule.verify(&other, &params).is_err());
+
+        // A real proof is still refused against a stricter threshold.
+        let strict = EpochLeadershipParams {
+            threshold: LeaderThreshold::from_natural(BigUint::from(0u8)),
+            ..params
+        };
+        assert!(schedule.verify(&prepared, &strict).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+//! High-level entry points for the multi-step key workflows of a block
+//! producer (feature `workflows`).
+//!
+//! Each of these strings together calls that are easy to get subtly wrong
+//! in the wrong order:
+//!
+//! | Entry point | Replaces |
+//! |-------------|----------|
+//! | [`KesSessionManager`] | Evolving a KES key to the period of each slot, refusing stale periods, forgetting the key once it expires, and persisting it between runs |
+//! | [`LeadershipSchedule`] | Evaluating the Praos VRF for every slot of a range and keeping the slots whose leader value is below the stake threshold |
+//! | [`EnvelopeStore`] | Reading and writing `cardano-cli` text envelope key files, checking the role in the type string |
+//!
+//! Runnable programs for each live in the crate's `examples/` directory
+//! (`cargo run --example kes_session --features workflows`).
+
+use std::io;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+use crate::direct_serialise::DirectSerialiseError;
+use crate::kes::evolution::EvolveError;
+use crate::kes::{KesMError, OcertPeriodError, Period};
+use crate::role::TextEnvelopeError;
+use crate::vrf::PraosConstructionError;
+
+pub mod envelope_store;
+pub mod kes_session;
+pub mod leadership;
+
+pub use envelope_store::EnvelopeStore;
+pub use kes_session::{KesSessionConfig, KesSessionManager, PersistedKesSession};
+pub use leadership::{
+    EpochLeadershipParams, LeaderSlot, LeaderThreshold, LeadershipSchedule, leader_value,
+};
+
+/// Error raised by the [`workflows`](self) entry points.
+#[derive(Debug, Error)]
+pub enum WorkflowError {
+    #[error(transparent)]
+    Kes(#[from] KesMError),
+    #[error(transparent)]
+    OcertPeriod(#[from] OcertPeriodError),
+    /// The slot falls in an evolution the key has already moved past; the
+    /// secrets for it are gone.
+    #[error("KES evolution {requested} is before the key's current evolution {current}")]
+    StalePeriod { requested: Period, current: Period },
+    #[error(transparent)]
+    Vrf(#[from] PraosConstructionError),
+    /// A leadership proof does not verify, or its leader value is not below
+    /// the threshold.
+    #[error("slot {slot} is not proven to be a leader slot")]
+    InvalidLeaderProof { slot: u64 },
+    #[error(transparent)]
+    Envelope(#[from] TextEnvelopeError),
+    #[error(transparent)]
+    DirectSerialise(#[from] DirectSerialiseError),
+    #[error("{}: {message}", path.display())]
+    Io {
+        path: PathBuf,
+        kind: io::ErrorKind,
+        message: String,
+    },
+    #[error("{}: not a valid key file: {message}", path.display())]
+    InvalidFile { path: PathBuf, message: String },
+}
+
+impl From<EvolveError> for WorkflowError {
+    fn from(err: EvolveError) -> Self {
+        match err {
+            EvolveError::Stale { requested, current } => {
+                WorkflowError::StalePeriod { requested, current }
+            },
+            EvolveError::Kes(err) => WorkflowError::Kes(err),
+        }
+    }
+}
+
+impl WorkflowError {
+    pub(crate) fn io(path: PathBuf, err: &io::Error) -> Self {
+        WorkflowError::Io {
+            path,
+            kind: err.kind(),
+            message: err.to_string(),
+        }
+    }
+
+    /// Stable identifier of this variant (see the crate-level *Error codes*
+    /// section). Wrapped errors report the code of the error they wrap.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            WorkflowError::Kes(err) => err.code(),
+            WorkflowError::OcertPeriod(err) => err.code(),
+            WorkflowError::StalePeriod { .. } => "workflows.stale_period",
+            WorkflowError::Vrf(err) => err.code(),
+            WorkflowError::InvalidLeaderProof { .. } => "workflows.invalid_leader_proof",
+            WorkflowError::Envelope(_) => "workflows.invalid_envelope",
+            WorkflowError::DirectSerialise(err) => err.code(),
+            WorkflowError::Io { .. } => "workflows.io",
+            WorkflowError::InvalidFile { .. } => "workflows.invalid_file",
+        }
+    }
+
+    /// Whether retrying could succeed: resource failures of a wrapped error,
+    /// and I/O that was interrupted or timed out.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        match self {
+            WorkflowError::Kes(err) => err.is_transient(),
+            WorkflowError::Vrf(err) => err.is_transient(),
+            WorkflowError::DirectSerialise(err) => err.is_transient(),
+            WorkflowError::Io { kind, .. } => matches!(
+                kind,
+                io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            ),
+            WorkflowError::OcertPeriod(_)
+            | WorkflowError::StalePeriod { .. }
+            | WorkflowError::InvalidLeaderProof { .. }
+            | WorkflowError::Envelope(_)
+            | WorkflowError::InvalidFile { .. } => false,
+        }
+    }
+}
+
+impl serde::Serialize for WorkflowError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        match self {
+            WorkflowError::Kes(err) => return err.serialize(serializer),
+            WorkflowError::OcertPeriod(err) => return err.serialize(serializer),
+            WorkflowError::Vrf(err) => return err.serialize(serializer),
+            WorkflowError::DirectSerialise(err) => return err.serialize(serializer),
+            _ => {},
+        }
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        match self {
+            WorkflowError::StalePeriod { requested, current } => {
+                map.serialize_entry("requested", requested)?;
+                map.serialize_entry("current", current)?;
+            },
+            WorkflowError::InvalidLeaderProof { slot } => {
+                map.serialize_entry("slot", slot)?;
+            },
+            WorkflowError::Io { path, .. } | WorkflowError::InvalidFile { path, .. } => {
+                map.serialize_entry("path", &path.display().to_string())?;
+            },
+            _ => {},
+        }
+        map.serialize_entry("message", &self.to_string())?;
+        map.end()
+    }
+}
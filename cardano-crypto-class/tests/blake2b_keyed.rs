@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Keyed and personalized Blake2b.
+//!
+//! The 64-byte keyed vectors are entries of the reference `blake2b-kat.txt`
+//! (key `00..3f`); the remaining digests were produced with Python's
+//! `hashlib.blake2b(data, key=..., person=..., digest_size=...)`.
+
+use cardano_crypto_class::hash::{
+    Blake2b224, Blake2b256, Blake2b512, HashAlgorithm, HashKeyTooLong, KeyedHashAlgorithm,
+};
+
+const DATA: &[u8] = b"cardano";
+const PERSONAL: &[u8; 16] = b"Cardano-KES-Node";
+
+fn key(len: u8) -> Vec<u8> {
+    (0..len).collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    hex::encode(bytes)
+}
+
+#[test]
+fn unkeyed_outputs_are_unchanged() {
+    assert_eq!(
+        hex(&Blake2b224::hash(DATA)),
+        "dc48bf6844bb9458793babb6f78abc483c4876c641d972928c5851b8"
+    );
+    assert_eq!(
+        hex(&Blake2b256::hash(DATA)),
+        "27456857d960d4862e6b449534cdca82c19a3bebc4bf7c29a13d773388593c84"
+    );
+    assert_eq!(
+        hex(&Blake2b512::hash(DATA)),
+        "78c7c44f089e7e41e244a60f9768219255e85481c28212b3911589df58b5ccd2\
+         04dc3c782f11202b6fca46230dce6b1663df764008addbf0a54cf18f26ff6d4e"
+    );
+}
+
+#[test]
+fn empty_key_is_the_unkeyed_hash() {
+    assert_eq!(
+        Blake2b224::hash_keyed(&[], DATA).expect("key"),
+        Blake2b224::hash(DATA)
+    );
+    assert_eq!(
+        Blake2b256::hash_keyed(&[], DATA).expect("key"),
+        Blake2b256::hash(DATA)
+    );
+    assert_eq!(
+        Blake2b512::hash_keyed(&[], DATA).expect("key"),
+        Blake2b512::hash(DATA)
+    );
+    assert_eq!(
+        Blake2b256::hash_personalized(&[0; 16], DATA),
+        Blake2b256::hash(DATA)
+    );
+}
+
+#[test]
+fn blake2b512_keyed_reference_kat() {
+    let key = key(64);
+    assert_eq!(
+        hex(&Blake2b512::hash_keyed(&key, &[]).expect("key")),
+        "10ebb67700b1868efb4417987acf4690ae9d972fb7a590c2f02871799aaa4786\
+         b5e996e8f0f4eb981fc214b005f42d2ff4233499391653df7aefcbc13fc51568"
+    );
+    assert_eq!(
+        hex(&Blake2b512::hash_keyed(&key, &[0]).expect("key")),
+        "961f6dd1e4dd30f63901690c512e78e4b45e4742ed197c3c5e45c549fd25f2e4\
+         187b0bc9fe30492b16b0d0bc4ef9b0f34c7003fac09a5ef1532e69430234cebd"
+    );
+    // 255 bytes: one full block of data after the key block, plus a partial one.
+    let data: Vec<u8> = (0..=254).collect();
+    assert_eq!(
+        hex(&Blake2b512::hash_keyed(&key, &data).expect("key")),
+        "142709d62e28fcccd0af97fad0f8465b971e82201dc51070faa0372aa43e9248\
+         4be1c1e73ba10906d5d1853db6a4106e0a7bf9800d373d6dee2d46d62ef2a461"
+    );
+}
+
+#[test]
+fn keyed_vectors_for_every_variant() {
+    let short = key(32);
+    let long = key(64);
+    assert_eq!(
+        hex(&Blake2b224::hash_keyed(&short, DATA).expect("key")),
+        "110c32b47f1dd8d551a34da15caf42ae36b43e35bff4c6df02923a16"
+    );
+    assert_eq!(
+        hex(&Blake2b224::hash_keyed(&long, &[]).expect("key")),
+        "a43d14369294a04b9cd6c6d358c8e663654c4b246c47cfe6373f7788"
+    );
+    assert_eq!(
+        hex(&Blake2b256::hash_keyed(&short, DATA).expect("key")),
+        "03b28e0c36d45c0b4f19aaa41f1bd708e232ad59b804541f60d5da151599a797"
+    );
+    assert_eq!(
+        hex(&Blake2b256::hash_keyed(&long, &[]).expect("key")),
+        "2fa9fbd9be36437de204e139e97d402bce68c828f43391608c891b5faed8a98a"
+    );
+    assert_eq!(
+        hex(&Blake2b512::hash_keyed(&short, DATA).expect("key")),
+        "ca632cc3e7c0bc730223830787ca064245c7b8301f6637d687bfe434eb636070\
+         2fdb51378477545a1c822767003b608dc5251b929710dbe5e709aa3e22404e7f"
+    );
+}
+
+#[test]
+fn personalized_vectors_for_every_variant() {
+    assert_eq!(
+        hex(&Blake2b224::hash_personalized(PERSONAL, DATA)),
+        "46cb3c4fcbf74cfecb834a330748d0c59b02f36a5d9e6f62c0cf8651"
+    );
+    assert_eq!(
+        hex(&Blake2b256::hash_personalized(PERSONAL, DATA)),
+        "6f41347de46258a34cbc5baedbf83f934665ddfc5b345baf31c78ce119acb303"
+    );
+    assert_eq!(
+        hex(&Blake2b512::hash_personalized(PERSONAL, DATA)),
+        "099e0892bee84ff1ef925d5c7629656e7a6051d0d18e8a919b76d7f2f3128264\
+         03b92cc209ac59fdffb745abb4ec6f33df82bdf89a87ff1c86c5207938485f5a"
+    );
+}
+
+#[test]
+fn oversized_key_is_rejected() {
+    let err = Blake2b256::hash_keyed(&key(65), DATA).expect_err("65-byte key");
+    assert_eq!(
+        err,
+        HashKeyTooLong {
+            max: 64,
+            actual: 65
+        }
+    );
+    assert_eq!(err.code(), "hash.key_too_long");
+}
+
+fn domain_separated<H: KeyedHashAlgorithm<Personal = [u8; 16]>>(data: &[u8]) -> Vec<u8> {
+    H::hash_personalized(PERSONAL, data)
+}
+
+#[test]
+fn generic_code_can_require_keyed_hashing() {
+    let digest = domain_separated::<Blake2b256>(DATA);
+    assert_eq!(digest.len(), Blake2b256::OUTPUT_SIZE);
+    assert_ne!(digest, Blake2b256::hash(DATA));
+}
@@ -0,0 +1,203 @@
+//! Executes the embedded BLS12-381 vectors from Haskell's
+//! `bls12-381-test-vectors` against [`cardano_crypto_class::bls12_381`].
+#![cfg(feature = "bls12-381")]
+
+use cardano_crypto_class::bls12_381::{
+    BlsError, PairingTarget, Point1, Point2, Scalar, final_verify, miller_loop,
+};
+use cardano_test_vectors::bls12_381;
+use num_bigint::BigInt;
+
+fn bytes(field: &str) -> Vec<u8> {
+    hex::decode(field).expect("valid hex line")
+}
+
+fn g1(field: &str) -> Point1 {
+    Point1::uncompress(&bytes(field)).expect("G1 point")
+}
+
+fn g2(field: &str) -> Point2 {
+    Point2::uncompress(&bytes(field)).expect("G2 point")
+}
+
+fn scalar() -> Scalar {
+    Scalar::from_be_bytes(&bytes(bls12_381::EC_OPERATIONS_SCALAR)).expect("canonical scalar")
+}
+
+#[test]
+fn ec_operations_match() {
+    let ops = bls12_381::ec_operations().expect("ec operation vectors parse");
+    let s = scalar();
+
+    let (p, q) = (g1(&ops.g1_p), g1(&ops.g1_q));
+    assert_eq!(p.add(&q), g1(&ops.g1_add));
+    assert_eq!(p.add(&q.neg()), g1(&ops.g1_sub));
+    assert_eq!(q.mult(&s), g1(&ops.g1_mul));
+    assert_eq!(p.neg(), g1(&ops.g1_neg));
+    assert_eq!(p.add(&q).compress().to_vec(), bytes(&ops.g1_add));
+
+    let (p, q) = (g2(&ops.g2_p), g2(&ops.g2_q));
+    assert_eq!(p.add(&q), g2(&ops.g2_add));
+    assert_eq!(p.add(&q.neg()), g2(&ops.g2_sub));
+    assert_eq!(q.mult(&s), g2(&ops.g2_mul));
+    assert_eq!(p.neg(), g2(&ops.g2_neg));
+    assert_eq!(p.add(&q).compress().to_vec(), bytes(&ops.g2_add));
+}
+
+#[test]
+fn pairing_is_bilinear() {
+    let v = bls12_381::pairing().expect("pairing vectors parse");
+    let (p, a_p, b_p, apb_p, axb_p) =
+        (g1(&v.p), g1(&v.a_p), g1(&v.b_p), g1(&v.apb_p), g1(&v.axb_p));
+    let (q, a_q, b_q, apb_q, axb_q) =
+        (g2(&v.q), g2(&v.a_q), g2(&v.b_q), g2(&v.apb_q), g2(&v.axb_q));
+
+    // e([a]P, Q) = e(P, [a]Q)
+    assert!(final_verify(&miller_loop(&a_p, &q), &miller_loop(&p, &a_q)));
+    // e([a]P, [b]Q) = e([b]P, [a]Q)
+    assert!(final_verify(
+        &miller_loop(&a_p, &b_q),
+        &miller_loop(&b_p, &a_q)
+    ));
+    // e([a]P, [b]Q) = e([a*b]P, Q) = e(P, [a*b]Q)
+    assert!(final_verify(
+        &miller_loop(&a_p, &b_q),
+        &miller_loop(&axb_p, &q)
+    ));
+    assert!(final_verify(
+        &miller_loop(&a_p, &b_q),
+        &miller_loop(&p, &axb_q)
+    ));
+    // e([a]P, Q) * e([b]P, Q) = e([a+b]P, Q)
+    assert!(final_verify(
+        &miller_loop(&a_p, &q).mult(&miller_loop(&b_p, &q)),
+        &miller_loop(&apb_p, &q)
+    ));
+    // e(P, [a]Q) * e(P, [b]Q) = e(P, [a+b]Q)
+    assert!(final_verify(
+        &miller_loop(&p, &a_q).mult(&miller_loop(&p, &b_q)),
+        &miller_loop(&p, &apb_q)
+    ));
+    // ... and the pairing tells the points apart.
+    assert!(!final_verify(
+        &miller_loop(&a_p, &q),
+        &miller_loop(&b_p, &q)
+    ));
+    assert!(final_verify(
+        &miller_loop(&Point1::zero(), &q),
+        &PairingTarget::one()
+    ));
+}
+
+#[test]
+fn serde_vectors_are_rejected_as_annotated() {
+    let v = bls12_381::serde().expect("serde vectors parse");
+
+    assert_eq!(
+        Point1::deserialize(&bytes(&v.g1_uncompressed_not_on_curve)),
+        Err(BlsError::PointNotOnCurve)
+    );
+    assert_eq!(
+        Point1::uncompress(&bytes(&v.g1_compressed_not_on_curve)),
+        Err(BlsError::PointNotOnCurve)
+    );
+    assert_eq!(
+        Point1::uncompress(&bytes(&v.g1_compressed_not_in_group)),
+        Err(BlsError::PointNotInGroup)
+    );
+    assert_eq!(
+        Point1::deserialize(&bytes(&v.g1_uncompressed_not_in_group)),
+        Err(BlsError::PointNotInGroup)
+    );
+    assert_eq!(
+        Point2::deserialize(&bytes(&v.g2_uncompressed_not_on_curve)),
+        Err(BlsError::PointNotOnCurve)
+    );
+    assert_eq!(
+        Point2::uncompress(&bytes(&v.g2_compressed_not_on_curve)),
+        Err(BlsError::PointNotOnCurve)
+    );
+    assert_eq!(
+        Point2::uncompress(&bytes(&v.g2_compressed_not_in_group)),
+        Err(BlsError::PointNotInGroup)
+    );
+    assert_eq!(
+        Point2::deserialize(&bytes(&v.g2_uncompressed_not_in_group)),
+        Err(BlsError::PointNotInGroup)
+    );
+}
+
+#[test]
+fn hash_to_g1_reduces_large_dsts() {
+    let v = bls12_381::h2c_large_dst().expect("h2c vectors parse");
+    let hashed = Point1::hash(&bytes(&v.msg), &bytes(&v.dst), &[]);
+    assert_eq!(hashed.compress().to_vec(), bytes(&v.expected));
+}
+
+#[test]
+fn aug_signature_verifies() {
+    let v = bls12_381::sig_aug().expect("sig aug vectors parse");
+    let (sig, pk) = (g1(&v.sig), g2(&v.pk));
+    let hashed = Point1::hash(
+        bls12_381::SIG_AUG_MESSAGE,
+        bls12_381::SIG_AUG_DST,
+        bls12_381::SIG_AUG_AUG,
+    );
+    let lhs = miller_loop(&sig, &Point2::generator());
+    assert!(final_verify(&lhs, &miller_loop(&hashed, &pk)));
+
+    let other = Point1::hash(
+        b"another message",
+        bls12_381::SIG_AUG_DST,
+        bls12_381::SIG_AUG_AUG,
+    );
+    assert!(!final_verify(&lhs, &miller_loop(&other, &pk)));
+}
+
+#[test]
+fn encodings_round_trip() {
+    let ops = bls12_381::ec_operations().expect("ec operation vectors parse");
+    for point in [g1(&ops.g1_p), Point1::generator(), Point1::zero()] {
+        assert_eq!(Point1::uncompress(&point.compress()), Ok(point));
+        assert_eq!(Point1::deserialize(&point.serialize()), Ok(point));
+    }
+    for point in [g2(&ops.g2_p), Point2::generator(), Point2::zero()] {
+        assert_eq!(Point2::uncompress(&point.compress()), Ok(point));
+        assert_eq!(Point2::deserialize(&point.serialize()), Ok(point));
+    }
+    assert!(matches!(
+        Point1::uncompress(&[0u8; 47]),
+        Err(BlsError::WrongLength {
+            expected: 48,
+            actual: 47,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn scalars_follow_integer_arithmetic() {
+    let s = scalar();
+    let as_integer =
+        BigInt::parse_bytes(bls12_381::EC_OPERATIONS_SCALAR.as_bytes(), 16).expect("hex integer");
+    assert_eq!(Scalar::from_integer(&as_integer), s);
+    assert_eq!(&s.to_natural(), as_integer.magnitude());
+    assert_eq!(Scalar::from_integer(&-as_integer), -s);
+    assert_eq!(
+        Scalar::from_be_bytes(&s.to_be_bytes()),
+        Ok(s),
+        "canonical encoding round-trips"
+    );
+    assert_eq!(s * s.inverse().expect("non-zero"), Scalar::one());
+    assert_eq!(s - s, Scalar::zero());
+    assert_eq!(Scalar::zero().inverse(), None);
+    assert_eq!(Scalar::from_be_bytes(&[0xff; 32]), Err(BlsError::BadScalar));
+
+    // [a]P + [b]P = [a+b]P, with blsMult's Integer reduction modulo r.
+    let (a, b) = (Scalar::from_u64(7), s);
+    let g = Point1::generator();
+    assert_eq!(g.mult(&a).add(&g.mult(&b)), g.mult(&(a + b)));
+    assert_eq!(Point1::msm(&[(a, g), (b, g)]), g.mult(&(a + b)));
+    assert!(g.mult(&Scalar::zero()).is_inf());
+    assert!(g.in_group());
+}
@@ -0,0 +1,242 @@
+//! Borrowed verification keys must verify exactly like the owned keys they
+//! view, and their `Deserialize` impls must borrow from the input buffer.
+
+use cardano_crypto_class::dsign::DsignAlgorithm;
+use cardano_crypto_class::dsign::ed25519::{
+    Ed25519, Ed25519Signature, Ed25519SigningKey, Ed25519VerificationKeyRef,
+};
+use cardano_crypto_class::kes::{
+    CompactSum6Kes, KesAlgorithm, KesVerificationKeyRef, Period, SingleKes, Sum6Kes,
+};
+use cardano_crypto_class::seed::mk_seed_from_bytes;
+
+fn ed25519_key(fill: u8) -> Ed25519SigningKey {
+    Ed25519::gen_key(&mk_seed_from_bytes(vec![fill; 32]))
+}
+
+#[test]
+fn ed25519_ref_verifies_like_owned_key() {
+    let sk = ed25519_key(1);
+    let vk = Ed25519::derive_verification_key(&sk);
+    let other_vk = Ed25519::derive_verification_key(&ed25519_key(2));
+    let signature = Ed25519::sign_bytes(&(), b"header", &sk);
+    let mut tampered = Ed25519::raw_serialize_signature(&signature);
+    tampered[0] ^= 1;
+    let tampered = Ed25519::raw_deserialize_signature(&tampered);
+
+    let vk_bytes = Ed25519::raw_serialize_verification_key(&vk);
+    let vk_ref = Ed25519VerificationKeyRef::from_bytes(&vk_bytes).expect("valid key");
+    assert_eq!(vk_ref, Ed25519VerificationKeyRef::from(&vk));
+    assert_eq!(vk_ref.to_verification_key(), vk);
+
+    let other_bytes = Ed25519::raw_serialize_verification_key(&other_vk);
+    let other_ref = Ed25519VerificationKeyRef::from_bytes(&other_bytes).expect("valid key");
+
+    let cases: Vec<(&[u8], &Ed25519Signature)> = vec![
+        (b"header", &signature),
+        (b"other header", &signature),
+        (b"", &signature),
+    ];
+    for (message, sig) in cases {
+        assert_eq!(
+            Ed25519::verify_bytes_ref(&(), vk_ref, message, sig),
+            Ed25519::verify_bytes(&(), &vk, message, sig)
+        );
+        assert_eq!(
+            Ed25519::verify_bytes_ref(&(), other_ref, message, sig),
+            Ed25519::verify_bytes(&(), &other_vk, message, sig)
+        );
+    }
+    assert!(Ed25519::verify_bytes_ref(&(), vk_ref, b"header", &signature).is_ok());
+    if let Some(tampered) = tampered {
+        assert_eq!(
+            Ed25519::verify_bytes_ref(&(), vk_ref, b"header", &tampered),
+            Ed25519::verify_bytes(&(), &vk, b"header", &tampered)
+        );
+    }
+}
+
+#[test]
+fn ed25519_ref_accepts_exactly_what_owned_decoding_accepts() {
+    for fill in 0..=u8::MAX {
+        let mut bytes = [fill; 32];
+        bytes[31] &= 0x7f;
+        for candidate in [&bytes[..], &[fill; 32][..], &bytes[..31]] {
+            assert_eq!(
+                Ed25519VerificationKeyRef::from_bytes(candidate).is_some(),
+                Ed25519::raw_deserialize_verification_key(candidate).is_some(),
+                "fill {fill:#04x}, length {}",
+                candidate.len()
+            );
+        }
+    }
+}
+
+/// Compare `verify_kes_ref` with `verify_kes` at `periods`, for the right
+/// message, a wrong message, a wrong period and a wrong key.
+fn check_kes_ref<K>(periods: &[Period])
+where
+    K: KesAlgorithm<Context = ()>,
+{
+    let mut sk = K::gen_key_kes_from_seed_bytes(&[3u8; 32]).expect("keygen");
+    let vk = K::derive_verification_key(&sk).expect("vk");
+    let vk_bytes = K::raw_serialize_verification_key_kes(&vk);
+    let vk_ref = KesVerificationKeyRef::<K>::from_bytes(&vk_bytes).expect("key size");
+
+    let other_sk = K::gen_key_kes_from_seed_bytes(&[4u8; 32]).expect("keygen");
+    let other_vk = K::derive_verification_key(&other_sk).expect("vk");
+    let other_bytes = K::raw_serialize_verification_key_kes(&other_vk);
+    let other_ref = KesVerificationKeyRef::<K>::from_bytes(&other_bytes).expect("key size");
+    K::forget_signing_key_kes(other_sk);
+
+    let mut period = 0;
+    for &target in periods {
+        while period < target {
+            sk = K::update_kes(&(), sk, period)
+                .expect("update")
+                .expect("not expired");
+            period += 1;
+        }
+        let sig = K::sign_kes(&(), period, b"header body", &sk).expect("sign");
+        let wrong_period = (period + 1) % K::total_periods();
+        let checks: [(&[u8], Period); 3] = [
+            (b"header body", period),
+            (b"another body", period),
+            (b"header body", wrong_period),
+        ];
+        for (message, at) in checks {
+            assert_eq!(
+                K::verify_kes_ref(&(), vk_ref, at, message, &sig),
+                K::verify_kes(&(), &vk, at, message, &sig),
+                "period {period}, checked at {at}"
+            );
+            assert_eq!(
+                K::verify_kes_ref(&(), other_ref, at, message, &sig),
+                K::verify_kes(&(), &other_vk, at, message, &sig)
+            );
+        }
+        assert!(K::verify_kes_ref(&(), vk_ref, period, b"header body", &sig).is_ok());
+    }
+    K::forget_signing_key_kes(sk);
+}
+
+#[test]
+fn sum_kes_ref_verifies_like_owned_key() {
+    check_kes_ref::<Sum6Kes>(&[0, 1, 31, 32, 63]);
+}
+
+#[test]
+fn compact_sum_kes_ref_verifies_like_owned_key() {
+    check_kes_ref::<CompactSum6Kes>(&[0, 1, 31, 32, 63]);
+}
+
+#[test]
+fn single_kes_ref_uses_default_decoding() {
+    check_kes_ref::<SingleKes<Ed25519>>(&[0]);
+}
+
+#[test]
+fn kes_ref_rejects_wrong_length() {
+    assert!(KesVerificationKeyRef::<Sum6Kes>::from_bytes(&[0u8; 31]).is_none());
+    assert!(KesVerificationKeyRef::<Sum6Kes>::from_bytes(&[0u8; 33]).is_none());
+    assert!(KesVerificationKeyRef::<Sum6Kes>::from_bytes(&[0u8; 32]).is_some());
+}
+
+#[cfg(feature = "serde")]
+mod serde_borrowing {
+    use super::*;
+    use cardano_binary::{decode_full, decode_full_borrowed, serialize};
+    use cardano_crypto_class::dsign::ed25519::Ed25519VerificationKey;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    struct OwnedHeader {
+        issuer_vk: Ed25519VerificationKey,
+        #[serde(with = "serde_bytes")]
+        kes_vk: Vec<u8>,
+        period: Period,
+    }
+
+    #[derive(Deserialize)]
+    struct BorrowedHeader<'a> {
+        #[serde(borrow)]
+        issuer_vk: Ed25519VerificationKeyRef<'a>,
+        #[serde(borrow)]
+        kes_vk: KesVerificationKeyRef<'a, Sum6Kes>,
+        period: Period,
+    }
+
+    fn encoded_header() -> (Vec<u8>, OwnedHeader) {
+        let issuer_vk = Ed25519::derive_verification_key(&ed25519_key(5));
+        let kes_sk = Sum6Kes::gen_key_kes_from_seed_bytes(&[6u8; 32]).expect("keygen");
+        let kes_vk = Sum6Kes::derive_verification_key(&kes_sk).expect("vk");
+        Sum6Kes::forget_signing_key_kes(kes_sk);
+        let header = OwnedHeader {
+            issuer_vk,
+            kes_vk,
+            period: 17,
+        };
+        (serialize(&header).expect("encode"), header)
+    }
+
+    fn within(buffer: &[u8], slice: &[u8]) -> bool {
+        buffer.as_ptr_range().contains(&slice.as_ptr())
+    }
+
+    #[test]
+    fn keys_borrow_from_the_header_buffer() {
+        let (bytes, owned) = encoded_header();
+        let header: BorrowedHeader<'_> = decode_full_borrowed(&bytes).expect("decode");
+
+        assert!(within(&bytes, header.issuer_vk.as_bytes()));
+        assert!(within(&bytes, header.kes_vk.as_bytes()));
+        assert_eq!(header.issuer_vk.to_verification_key(), owned.issuer_vk);
+        assert_eq!(header.kes_vk.as_bytes(), owned.kes_vk.as_slice());
+        assert_eq!(header.period, 17);
+
+        // The owned form still decodes from the same bytes with either decoder.
+        let via_ciborium: OwnedHeader = decode_full(&bytes).expect("decode");
+        let via_borrowed: OwnedHeader = decode_full_borrowed(&bytes).expect("decode");
+        assert_eq!(via_ciborium.kes_vk, via_borrowed.kes_vk);
+        assert_eq!(via_ciborium.issuer_vk, via_borrowed.issuer_vk);
+
+        // Re-encoding the borrowed keys reproduces the owned encoding.
+        assert_eq!(
+            serialize(&header.issuer_vk).expect("encode"),
+            serialize(&owned.issuer_vk).expect("encode")
+        );
+        assert_eq!(
+            serialize(&header.kes_vk).expect("encode"),
+            serialize(&serde_bytes::Bytes::new(&owned.kes_vk)).expect("encode")
+        );
+    }
+
+    #[test]
+    fn invalid_borrowed_keys_are_rejected() {
+        let (_, owned) = encoded_header();
+        #[derive(Serialize)]
+        struct Raw<'a> {
+            #[serde(with = "serde_bytes")]
+            issuer_vk: &'a [u8],
+            #[serde(with = "serde_bytes")]
+            kes_vk: &'a [u8],
+            period: Period,
+        }
+        let issuer = Ed25519::raw_serialize_verification_key(&owned.issuer_vk);
+        let short_kes = serialize(&Raw {
+            issuer_vk: &issuer,
+            kes_vk: &owned.kes_vk[..31],
+            period: 0,
+        })
+        .expect("encode");
+        assert!(decode_full_borrowed::<BorrowedHeader<'_>>(&short_kes).is_err());
+
+        let short_issuer = serialize(&Raw {
+            issuer_vk: &issuer[..31],
+            kes_vk: &owned.kes_vk,
+            period: 0,
+        })
+        .expect("encode");
+        assert!(decode_full_borrowed::<BorrowedHeader<'_>>(&short_issuer).is_err());
+    }
+}
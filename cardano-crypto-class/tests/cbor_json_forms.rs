@@ -0,0 +1,84 @@
+//! Key, signature and proof types encode as a raw byte string in CBOR and as
+//! hex text in JSON, and each form decodes back to the same value.
+#![cfg(feature = "serde")]
+
+use std::fmt::Debug;
+
+use cardano_binary::{FromCbor, ToCbor, decode_full, serialize};
+use cardano_crypto_class::Ed25519;
+use cardano_crypto_class::dsign::DsignAlgorithm;
+use cardano_crypto_class::seed::mk_seed_from_bytes;
+use cardano_crypto_class::vrf::{MockVRF, PraosVRF, SimpleVRF, VRFAlgorithm};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// CBOR header of a definite byte string of `len` bytes (`len < 65536`).
+fn bytes_header(len: usize) -> Vec<u8> {
+    match len {
+        0..=23 => vec![0x40 | len as u8],
+        24..=255 => vec![0x58, len as u8],
+        _ => vec![0x59, (len >> 8) as u8, len as u8],
+    }
+}
+
+fn check_forms<T>(value: &T, raw: &[u8])
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let mut cbor = bytes_header(raw.len());
+    cbor.extend_from_slice(raw);
+    assert_eq!(serialize(value).expect("serialize"), cbor);
+    assert_eq!(value.to_cbor().expect("to_cbor"), cbor);
+    assert_eq!(&decode_full::<T>(&cbor).expect("decode CBOR"), value);
+    assert_eq!(&T::decode_cbor(&cbor).expect("decode_cbor"), value);
+
+    let json = serde_json::to_string(value).expect("JSON");
+    assert_eq!(json, format!("\"{}\"", hex::encode(raw)));
+    assert_eq!(
+        &serde_json::from_str::<T>(&json).expect("decode JSON"),
+        value
+    );
+}
+
+#[test]
+fn ed25519_keys_and_signatures() {
+    let sk = Ed25519::gen_key(&mk_seed_from_bytes(vec![5u8; 32]));
+    let vk = Ed25519::derive_verification_key(&sk);
+    let sig = Ed25519::sign_bytes(&(), b"forms", &sk);
+    check_forms(&vk, &Ed25519::raw_serialize_verification_key(&vk));
+    check_forms(&sig, &Ed25519::raw_serialize_signature(&sig));
+}
+
+#[test]
+fn praos_verification_key_and_proof() {
+    let (sk, vk) = PraosVRF::gen_keypair(&mk_seed_from_bytes(vec![6u8; PraosVRF::SEED_SIZE]));
+    let (_, proof) = PraosVRF::evaluate_bytes(&(), b"forms", &sk);
+    check_forms(&vk, &PraosVRF::raw_serialize_verification_key(&vk));
+    check_forms(&proof, &PraosVRF::raw_serialize_proof(&proof));
+}
+
+#[test]
+fn test_vrf_keys_and_proofs() {
+    let (sk, vk) = SimpleVRF::gen_keypair(&mk_seed_from_bytes(vec![7u8; SimpleVRF::SEED_SIZE]));
+    let (_, proof) = SimpleVRF::evaluate_bytes(&(), b"forms", &sk);
+    check_forms(&vk, &SimpleVRF::raw_serialize_verification_key(&vk));
+    check_forms(&proof, &SimpleVRF::raw_serialize_proof(&proof));
+
+    let (sk, vk) = MockVRF::gen_keypair(&mk_seed_from_bytes(vec![8u8; MockVRF::SEED_SIZE]));
+    let (_, proof) = MockVRF::evaluate_bytes(&(), b"forms", &sk);
+    check_forms(&vk, &MockVRF::raw_serialize_verification_key(&vk));
+    check_forms(&proof, &MockVRF::raw_serialize_proof(&proof));
+}
+
+#[test]
+fn json_hex_is_validated_like_cbor_bytes() {
+    type PraosVk = <PraosVRF as VRFAlgorithm>::VerificationKey;
+
+    let short = format!("\"{}\"", "00".repeat(31));
+    assert!(serde_json::from_str::<PraosVk>(&short).is_err());
+    assert!(serde_json::from_str::<PraosVk>("\"not hex\"").is_err());
+
+    // CBOR input must still be a byte string, not hex text.
+    let text = serialize(&"00".repeat(32)).expect("serialize text");
+    assert!(decode_full::<PraosVk>(&text).is_err());
+}
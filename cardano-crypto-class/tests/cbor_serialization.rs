@@ -1,5 +1,7 @@
 #[cfg(feature = "serde")]
 mod cbor_tests {
+    use cardano_binary::golden::assert_golden_cbor;
+    use cardano_binary::{decode_full, serialize};
     use cardano_crypto_class::Ed25519;
     use cardano_crypto_class::dsign::DsignAlgorithm;
     use cardano_crypto_class::seed::mk_seed_from_bytes;
@@ -13,14 +15,12 @@ mod cbor_tests {
         let verification_key = <Ed25519 as DsignAlgorithm>::derive_verification_key(&signing_key);
 
         // Serialize to CBOR
-        let mut cbor_bytes = Vec::new();
-        ciborium::into_writer(&verification_key, &mut cbor_bytes)
-            .expect("Failed to serialize verification key");
+        let cbor_bytes =
+            serialize(&verification_key).expect("Failed to serialize verification key");
 
         // Deserialize from CBOR
         let deserialized: <Ed25519 as DsignAlgorithm>::VerificationKey =
-            ciborium::from_reader(cbor_bytes.as_slice())
-                .expect("Failed to deserialize verification key");
+            decode_full(&cbor_bytes).expect("Failed to deserialize verification key");
 
         // Verify they're equal
         assert_eq!(verification_key, deserialized);
@@ -34,12 +34,11 @@ mod cbor_tests {
         let signature = <Ed25519 as DsignAlgorithm>::sign_bytes(&(), message, &signing_key);
 
         // Serialize to CBOR
-        let mut cbor_bytes = Vec::new();
-        ciborium::into_writer(&signature, &mut cbor_bytes).expect("Failed to serialize signature");
+        let cbor_bytes = serialize(&signature).expect("Failed to serialize signature");
 
         // Deserialize from CBOR
         let deserialized: <Ed25519 as DsignAlgorithm>::Signature =
-            ciborium::from_reader(cbor_bytes.as_slice()).expect("Failed to deserialize signature");
+            decode_full(&cbor_bytes).expect("Failed to deserialize signature");
 
         // Verify they're equal
         assert_eq!(signature, deserialized);
@@ -56,20 +55,16 @@ mod cbor_tests {
         let signature = <Ed25519 as DsignAlgorithm>::sign_bytes(&(), message, &signing_key);
 
         // Serialize both VK and signature to CBOR
-        let mut vk_cbor = Vec::new();
-        ciborium::into_writer(&verification_key, &mut vk_cbor)
+        let vk_cbor = serialize(&verification_key)
             .expect("Failed to serialize Ed25519 verification key to CBOR");
-        let mut sig_cbor = Vec::new();
-        ciborium::into_writer(&signature, &mut sig_cbor)
-            .expect("Failed to serialize Ed25519 signature to CBOR");
+        let sig_cbor =
+            serialize(&signature).expect("Failed to serialize Ed25519 signature to CBOR");
 
         // Deserialize
-        let vk_decoded: <Ed25519 as DsignAlgorithm>::VerificationKey =
-            ciborium::from_reader(vk_cbor.as_slice())
-                .expect("Failed to deserialize Ed25519 verification key from CBOR");
+        let vk_decoded: <Ed25519 as DsignAlgorithm>::VerificationKey = decode_full(&vk_cbor)
+            .expect("Failed to deserialize Ed25519 verification key from CBOR");
         let sig_decoded: <Ed25519 as DsignAlgorithm>::Signature =
-            ciborium::from_reader(sig_cbor.as_slice())
-                .expect("Failed to deserialize Ed25519 signature from CBOR");
+            decode_full(&sig_cbor).expect("Failed to deserialize Ed25519 signature from CBOR");
 
         // Verify the deserialized signature with deserialized VK
         let result =
@@ -78,25 +73,26 @@ mod cbor_tests {
     }
 
     #[test]
-    fn test_ed25519_cbor_deterministic() {
+    fn test_ed25519_cbor_golden() {
         // Same key should always produce same CBOR encoding
         let seed = mk_seed_from_bytes(vec![99u8; SEED_BYTES]);
         let signing_key = <Ed25519 as DsignAlgorithm>::gen_key(&seed);
         let verification_key = <Ed25519 as DsignAlgorithm>::derive_verification_key(&signing_key);
+        let signature =
+            <Ed25519 as DsignAlgorithm>::sign_bytes(&(), b"golden message", &signing_key);
 
-        let mut cbor1 = Vec::new();
-        ciborium::into_writer(&verification_key, &mut cbor1)
-            .expect("Failed to serialize Ed25519 verification key to CBOR");
-        let mut cbor2 = Vec::new();
-        ciborium::into_writer(&verification_key, &mut cbor2)
-            .expect("Failed to serialize Ed25519 verification key to CBOR");
-
-        assert_eq!(cbor1, cbor2, "CBOR encoding should be deterministic");
+        assert_golden_cbor(
+            &verification_key,
+            "tests/golden/ed25519_verification_key.cbor",
+        );
+        assert_golden_cbor(&signature, "tests/golden/ed25519_signature.cbor");
     }
 }
 
 #[cfg(feature = "serde")]
 mod vrf_cbor_tests {
+    use cardano_binary::golden::assert_golden_cbor;
+    use cardano_binary::{decode_full, serialize};
     use cardano_crypto_class::vrf::praos::{gen_seed, keypair_from_seed};
 
     #[test]
@@ -106,13 +102,12 @@ mod vrf_cbor_tests {
             keypair_from_seed(&seed).expect("Failed to generate keypair");
 
         // Serialize to CBOR
-        let mut cbor_bytes = Vec::new();
-        ciborium::into_writer(&verification_key, &mut cbor_bytes)
-            .expect("Failed to serialize Praos verification key");
+        let cbor_bytes =
+            serialize(&verification_key).expect("Failed to serialize Praos verification key");
 
         // Deserialize from CBOR
-        let deserialized = ciborium::from_reader(cbor_bytes.as_slice())
-            .expect("Failed to deserialize Praos verification key");
+        let deserialized =
+            decode_full(&cbor_bytes).expect("Failed to deserialize Praos verification key");
 
         // Verify they're equal
         assert_eq!(verification_key, deserialized);
@@ -128,12 +123,10 @@ mod vrf_cbor_tests {
         let proof = signing_key.prove(message).expect("Failed to create proof");
 
         // Serialize to CBOR
-        let mut cbor_bytes = Vec::new();
-        ciborium::into_writer(&proof, &mut cbor_bytes).expect("Failed to serialize Praos proof");
+        let cbor_bytes = serialize(&proof).expect("Failed to serialize Praos proof");
 
         // Deserialize from CBOR
-        let deserialized = ciborium::from_reader(cbor_bytes.as_slice())
-            .expect("Failed to deserialize Praos proof");
+        let deserialized = decode_full(&cbor_bytes).expect("Failed to deserialize Praos proof");
 
         // Verify they're equal
         assert_eq!(proof, deserialized);
@@ -151,18 +144,15 @@ mod vrf_cbor_tests {
         let proof = signing_key.prove(message).expect("Failed to create proof");
 
         // Serialize both VK and proof to CBOR
-        let mut vk_cbor = Vec::new();
-        ciborium::into_writer(&verification_key, &mut vk_cbor)
+        let vk_cbor = serialize(&verification_key)
             .expect("Failed to serialize Praos verification key to CBOR");
-        let mut proof_cbor = Vec::new();
-        ciborium::into_writer(&proof, &mut proof_cbor)
-            .expect("Failed to serialize Praos proof to CBOR");
+        let proof_cbor = serialize(&proof).expect("Failed to serialize Praos proof to CBOR");
 
         // Deserialize
-        let vk_decoded: PraosVerificationKey = ciborium::from_reader(vk_cbor.as_slice())
-            .expect("Failed to deserialize Praos verification key from CBOR");
-        let proof_decoded: PraosProof = ciborium::from_reader(proof_cbor.as_slice())
-            .expect("Failed to deserialize Praos proof from CBOR");
+        let vk_decoded: PraosVerificationKey =
+            decode_full(&vk_cbor).expect("Failed to deserialize Praos verification key from CBOR");
+        let proof_decoded: PraosProof =
+            decode_full(&proof_cbor).expect("Failed to deserialize Praos proof from CBOR");
 
         // Verify the deserialized proof with deserialized VK
         let result = vk_decoded.verify(message, &proof_decoded);
@@ -176,22 +166,20 @@ mod vrf_cbor_tests {
     }
 
     #[test]
-    fn test_praos_cbor_deterministic() {
-        let seed = gen_seed().expect("Failed to generate seed");
-        let (verification_key, _signing_key) =
-            keypair_from_seed(&seed).expect("Failed to generate keypair");
+    fn test_praos_cbor_golden() {
+        use cardano_crypto_class::vrf::praos::keypair_from_seed_bytes;
 
-        let mut cbor1 = Vec::new();
-        ciborium::into_writer(&verification_key, &mut cbor1)
-            .expect("Failed to serialize Praos verification key to CBOR");
-        let mut cbor2 = Vec::new();
-        ciborium::into_writer(&verification_key, &mut cbor2)
-            .expect("Failed to serialize Praos verification key to CBOR");
-
-        assert_eq!(
-            cbor1, cbor2,
-            "Praos VRF CBOR encoding should be deterministic"
+        let (verification_key, signing_key) =
+            keypair_from_seed_bytes(&[99u8; 32]).expect("Failed to generate keypair");
+        let proof = signing_key
+            .prove(b"golden message")
+            .expect("Failed to create proof");
+
+        assert_golden_cbor(
+            &verification_key,
+            "tests/golden/praos_verification_key.cbor",
         );
+        assert_golden_cbor(&proof, "tests/golden/praos_proof.cbor");
     }
 
     #[test]
@@ -205,13 +193,12 @@ mod vrf_cbor_tests {
         let (_, verification_key) = gen_keypair(&seed);
 
         // Serialize to CBOR
-        let mut cbor_bytes = Vec::new();
-        ciborium::into_writer(&verification_key, &mut cbor_bytes)
-            .expect("Failed to serialize Simple VRF verification key");
+        let cbor_bytes =
+            serialize(&verification_key).expect("Failed to serialize Simple VRF verification key");
 
         // Deserialize from CBOR
-        let deserialized = ciborium::from_reader(cbor_bytes.as_slice())
-            .expect("Failed to deserialize Simple VRF verification key");
+        let deserialized =
+            decode_full(&cbor_bytes).expect("Failed to deserialize Simple VRF verification key");
 
         // Verify they're equal
         assert_eq!(verification_key, deserialized);
@@ -231,13 +218,11 @@ mod vrf_cbor_tests {
         let (_, proof) = SimpleVRF::evaluate_bytes(&(), message, &signing_key);
 
         // Serialize to CBOR
-        let mut cbor_bytes = Vec::new();
-        ciborium::into_writer(&proof, &mut cbor_bytes)
-            .expect("Failed to serialize Simple VRF proof");
+        let cbor_bytes = serialize(&proof).expect("Failed to serialize Simple VRF proof");
 
         // Deserialize from CBOR
-        let deserialized = ciborium::from_reader(cbor_bytes.as_slice())
-            .expect("Failed to deserialize Simple VRF proof");
+        let deserialized =
+            decode_full(&cbor_bytes).expect("Failed to deserialize Simple VRF proof");
 
         // Verify they're equal
         assert_eq!(proof, deserialized);
@@ -254,13 +239,12 @@ mod vrf_cbor_tests {
         let (_, verification_key) = gen_keypair(&seed);
 
         // Serialize to CBOR
-        let mut cbor_bytes = Vec::new();
-        ciborium::into_writer(&verification_key, &mut cbor_bytes)
-            .expect("Failed to serialize Mock VRF verification key");
+        let cbor_bytes =
+            serialize(&verification_key).expect("Failed to serialize Mock VRF verification key");
 
         // Deserialize from CBOR
-        let deserialized = ciborium::from_reader(cbor_bytes.as_slice())
-            .expect("Failed to deserialize Mock VRF verification key");
+        let deserialized =
+            decode_full(&cbor_bytes).expect("Failed to deserialize Mock VRF verification key");
 
         // Verify they're equal
         assert_eq!(verification_key, deserialized);
@@ -280,12 +264,10 @@ mod vrf_cbor_tests {
         let (_, proof) = MockVRF::evaluate_bytes(&(), message, &signing_key);
 
         // Serialize to CBOR
-        let mut cbor_bytes = Vec::new();
-        ciborium::into_writer(&proof, &mut cbor_bytes).expect("Failed to serialize Mock VRF proof");
+        let cbor_bytes = serialize(&proof).expect("Failed to serialize Mock VRF proof");
 
         // Deserialize from CBOR
-        let deserialized = ciborium::from_reader(cbor_bytes.as_slice())
-            .expect("Failed to deserialize Mock VRF proof");
+        let deserialized = decode_full(&cbor_bytes).expect("Failed to deserialize Mock VRF proof");
 
         // Verify they're equal
         assert_eq!(proof, deserialized);
@@ -294,6 +276,7 @@ mod vrf_cbor_tests {
 
 #[cfg(feature = "serde")]
 mod kes_cbor_tests {
+    use cardano_binary::{decode_full, serialize};
     use cardano_crypto_class::dsign::DsignMAlgorithm;
     use cardano_crypto_class::dsign::ed25519::Ed25519;
     use cardano_crypto_class::kes::{KesAlgorithm, SingleKes};
@@ -316,13 +299,12 @@ mod kes_cbor_tests {
             .expect("Failed to derive verification key");
 
         // Serialize to CBOR
-        let mut cbor_bytes = Vec::new();
-        ciborium::into_writer(&verification_key, &mut cbor_bytes)
-            .expect("Failed to serialize SingleKes verification key");
+        let cbor_bytes =
+            serialize(&verification_key).expect("Failed to serialize SingleKes verification key");
 
         // Deserialize from CBOR
-        let deserialized = ciborium::from_reader(cbor_bytes.as_slice())
-            .expect("Failed to deserialize SingleKes verification key");
+        let deserialized =
+            decode_full(&cbor_bytes).expect("Failed to deserialize SingleKes verification key");
 
         // Verify they're equal
         assert_eq!(verification_key, deserialized);
@@ -346,13 +328,11 @@ mod kes_cbor_tests {
             .expect("Failed to sign message");
 
         // Serialize to CBOR
-        let mut cbor_bytes = Vec::new();
-        ciborium::into_writer(&signature, &mut cbor_bytes)
-            .expect("Failed to serialize SingleKes signature");
+        let cbor_bytes = serialize(&signature).expect("Failed to serialize SingleKes signature");
 
         // Deserialize from CBOR
-        let deserialized = ciborium::from_reader(cbor_bytes.as_slice())
-            .expect("Failed to deserialize SingleKes signature");
+        let deserialized =
+            decode_full(&cbor_bytes).expect("Failed to deserialize SingleKes signature");
 
         // Verify they're equal
         assert_eq!(signature, deserialized);
@@ -378,18 +358,16 @@ mod kes_cbor_tests {
             .expect("Failed to sign message");
 
         // Serialize both VK and signature to CBOR
-        let mut vk_cbor = Vec::new();
-        ciborium::into_writer(&verification_key, &mut vk_cbor)
+        let vk_cbor = serialize(&verification_key)
             .expect("Failed to serialize SingleKes verification key to CBOR");
-        let mut sig_cbor = Vec::new();
-        ciborium::into_writer(&signature, &mut sig_cbor)
-            .expect("Failed to serialize SingleKes signature to CBOR");
+        let sig_cbor =
+            serialize(&signature).expect("Failed to serialize SingleKes signature to CBOR");
 
         // Deserialize
-        let vk_decoded = ciborium::from_reader(vk_cbor.as_slice())
+        let vk_decoded = decode_full(&vk_cbor)
             .expect("Failed to deserialize SingleKes verification key from CBOR");
-        let sig_decoded = ciborium::from_reader(sig_cbor.as_slice())
-            .expect("Failed to deserialize SingleKes signature from CBOR");
+        let sig_decoded =
+            decode_full(&sig_cbor).expect("Failed to deserialize SingleKes signature from CBOR");
 
         // Verify the deserialized signature with deserialized VK
         let result = SingleKesEd25519::verify_kes(&(), &vk_decoded, 0, message, &sig_decoded);
@@ -429,12 +407,11 @@ mod kes_cbor_tests {
             .expect("Failed to derive verification key");
 
         // Serialize to CBOR
-        let mut cbor_bytes = Vec::new();
-        ciborium::into_writer(&verification_key, &mut cbor_bytes)
+        let cbor_bytes = serialize(&verification_key)
             .expect("Failed to serialize CompactSingleKes verification key");
 
         // Deserialize from CBOR
-        let deserialized = ciborium::from_reader(cbor_bytes.as_slice())
+        let deserialized = decode_full(&cbor_bytes)
             .expect("Failed to deserialize CompactSingleKes verification key");
 
         // Verify they're equal
@@ -466,13 +443,12 @@ mod kes_cbor_tests {
             .expect("Failed to sign message");
 
         // Serialize to CBOR
-        let mut cbor_bytes = Vec::new();
-        ciborium::into_writer(&signature, &mut cbor_bytes)
-            .expect("Failed to serialize CompactSingleKes signature");
+        let cbor_bytes =
+            serialize(&signature).expect("Failed to serialize CompactSingleKes signature");
 
         // Deserialize from CBOR
-        let deserialized = ciborium::from_reader(cbor_bytes.as_slice())
-            .expect("Failed to deserialize CompactSingleKes signature");
+        let deserialized =
+            decode_full(&cbor_bytes).expect("Failed to deserialize CompactSingleKes signature");
 
         // Verify they're equal
         assert_eq!(signature, deserialized);
@@ -505,17 +481,15 @@ mod kes_cbor_tests {
             .expect("Failed to sign message");
 
         // Serialize both VK and signature to CBOR
-        let mut vk_cbor = Vec::new();
-        ciborium::into_writer(&verification_key, &mut vk_cbor)
+        let vk_cbor = serialize(&verification_key)
             .expect("Failed to serialize CompactSingleKes verification key to CBOR");
-        let mut sig_cbor = Vec::new();
-        ciborium::into_writer(&signature, &mut sig_cbor)
-            .expect("Failed to serialize CompactSingleKes signature to CBOR");
+        let sig_cbor =
+            serialize(&signature).expect("Failed to serialize CompactSingleKes signature to CBOR");
 
         // Deserialize
-        let vk_decoded = ciborium::from_reader(vk_cbor.as_slice())
+        let vk_decoded = decode_full(&vk_cbor)
             .expect("Failed to deserialize CompactSingleKes verification key from CBOR");
-        let sig_decoded = ciborium::from_reader(sig_cbor.as_slice())
+        let sig_decoded = decode_full(&sig_cbor)
             .expect("Failed to deserialize CompactSingleKes signature from CBOR");
 
         // Verify the deserialized signature with deserialized VK
@@ -0,0 +1,87 @@
+//! Rebuilding the root verification key from a CompactSum signature gives the
+//! signer's key for every valid signature, and a different key as soon as
+//! any embedded verification key byte changes.
+
+use cardano_crypto_class::kes::{
+    CompactKesComponents, CompactSum3Kes, CompactSum7Kes, KesAlgorithm, KesError, Period,
+};
+
+/// Bytes of the leaf Ed25519 signature at the front of every compact
+/// signature; everything after it is an embedded verification key.
+const LEAF_SIGNATURE_SIZE: usize = 64;
+
+fn check_every_period<K>()
+where
+    K: KesAlgorithm<Context = (), VerificationKey = Vec<u8>> + CompactKesComponents,
+{
+    let mut signing_key = K::gen_key_kes_from_seed_bytes(&[42u8; 32]).expect("signing key");
+    let verification_key = K::derive_verification_key(&signing_key).expect("verification key");
+
+    for period in K::periods() {
+        let signature = K::sign_kes(&(), period, b"light client", &signing_key).expect("sign");
+        assert_eq!(
+            K::reconstruct_verification_key(&signature, period),
+            Ok(verification_key.clone()),
+            "{} at period {period}",
+            K::ALGORITHM_NAME
+        );
+
+        let bytes = K::raw_serialize_signature_kes(&signature);
+        assert_eq!(bytes.len(), K::SIGNATURE_SIZE);
+        for index in LEAF_SIGNATURE_SIZE..bytes.len() {
+            let mut flipped = bytes.clone();
+            flipped[index] ^= 0x01;
+            // A flipped leaf key may not even decode; either way it must not
+            // rebuild the signer's key.
+            if let Some(flipped) = K::raw_deserialize_signature_kes(&flipped) {
+                assert_ne!(
+                    K::reconstruct_verification_key(&flipped, period),
+                    Ok(verification_key.clone()),
+                    "byte {index} flipped at period {period}"
+                );
+            }
+        }
+
+        // A valid signature rebuilds a different key when it claims the
+        // wrong period, unless the two periods share every embedded key.
+        if let Some(other) = period.checked_sub(1) {
+            assert_ne!(
+                K::reconstruct_verification_key(&signature, other),
+                Ok(verification_key.clone()),
+                "period {period} reconstructed at {other}"
+            );
+        }
+
+        if let Some(next) = K::update_kes(&(), signing_key, period).expect("update") {
+            signing_key = next;
+        } else {
+            return;
+        }
+    }
+    unreachable!("key did not expire after its last period");
+}
+
+#[test]
+fn compact_sum3_reconstructs_the_derived_key() {
+    check_every_period::<CompactSum3Kes>();
+}
+
+#[test]
+fn compact_sum7_reconstructs_the_derived_key() {
+    check_every_period::<CompactSum7Kes>();
+}
+
+#[test]
+fn periods_past_the_end_are_rejected() {
+    let signing_key = CompactSum3Kes::gen_key_kes_from_seed_bytes(&[1u8; 32]).expect("signing key");
+    let signature = CompactSum3Kes::sign_kes(&(), 0, b"m", &signing_key).expect("sign");
+    for period in [8, 9, Period::MAX] {
+        assert_eq!(
+            CompactSum3Kes::reconstruct_verification_key(&signature, period),
+            Err(KesError::PeriodOutOfRange {
+                period,
+                max_period: 8,
+            })
+        );
+    }
+}
@@ -54,12 +54,13 @@ fn test_algorithm_signature_uniqueness() {
 
     // Sign with each algorithm
     let ed25519_ctx = ();
-    let ecdsa_ctx = ecdsa_secp256k1::Context;
-    let schnorr_ctx = schnorr_secp256k1::Context;
+    let ecdsa_ctx = ecdsa_secp256k1::EcdsaContext::default();
+    let schnorr_ctx = schnorr_secp256k1::SchnorrContext::default();
 
     let ed25519_sig = Ed25519::sign_bytes(&ed25519_ctx, message, &ed25519_sk);
+    let ecdsa_hash = ecdsa_secp256k1::hash_and_pack::<Sha256>(message);
     let ecdsa_sig =
-        ecdsa_secp256k1::EcdsaSecp256k1DSIGN::sign_bytes(&ecdsa_ctx, message, &ecdsa_sk);
+        ecdsa_secp256k1::EcdsaSecp256k1DSIGN::sign_message_hash(&ecdsa_ctx, &ecdsa_hash, &ecdsa_sk);
     let schnorr_sig =
         schnorr_secp256k1::SchnorrSecp256k1DSIGN::sign_bytes(&schnorr_ctx, message, &schnorr_sk);
 
@@ -136,19 +137,29 @@ fn test_cross_key_verification_fails() {
         &ecdsa_secp256k1::EcdsaSecp256k1DSIGN::gen_key(&seed2),
     );
 
-    let ecdsa_ctx = ecdsa_secp256k1::Context;
-    let ecdsa_sig =
-        ecdsa_secp256k1::EcdsaSecp256k1DSIGN::sign_bytes(&ecdsa_ctx, message, &ecdsa_sk1);
+    let ecdsa_ctx = ecdsa_secp256k1::EcdsaContext::default();
+    let ecdsa_hash = ecdsa_secp256k1::hash_and_pack::<Sha256>(message);
+    let ecdsa_sig = ecdsa_secp256k1::EcdsaSecp256k1DSIGN::sign_message_hash(
+        &ecdsa_ctx,
+        &ecdsa_hash,
+        &ecdsa_sk1,
+    );
 
     assert!(
-        ecdsa_secp256k1::EcdsaSecp256k1DSIGN::verify_bytes(
-            &ecdsa_ctx, &ecdsa_vk1, message, &ecdsa_sig
+        ecdsa_secp256k1::EcdsaSecp256k1DSIGN::verify_message_hash(
+            &ecdsa_ctx,
+            &ecdsa_vk1,
+            &ecdsa_hash,
+            &ecdsa_sig
         )
         .is_ok()
     );
     assert!(
-        ecdsa_secp256k1::EcdsaSecp256k1DSIGN::verify_bytes(
-            &ecdsa_ctx, &ecdsa_vk2, message, &ecdsa_sig
+        ecdsa_secp256k1::EcdsaSecp256k1DSIGN::verify_message_hash(
+            &ecdsa_ctx,
+            &ecdsa_vk2,
+            &ecdsa_hash,
+            &ecdsa_sig
         )
         .is_err()
     );
@@ -304,16 +315,29 @@ fn test_message_tampering_detection() {
     {
         let sk = ecdsa_secp256k1::EcdsaSecp256k1DSIGN::gen_key(&seed);
         let vk = ecdsa_secp256k1::EcdsaSecp256k1DSIGN::derive_verification_key(&sk);
-        let ctx = ecdsa_secp256k1::Context;
+        let ctx = ecdsa_secp256k1::EcdsaContext::default();
 
-        let sig = ecdsa_secp256k1::EcdsaSecp256k1DSIGN::sign_bytes(&ctx, original_message, &sk);
+        let original_hash = ecdsa_secp256k1::hash_and_pack::<Sha256>(original_message);
+        let tampered_hash = ecdsa_secp256k1::hash_and_pack::<Sha256>(tampered_message);
+        let sig =
+            ecdsa_secp256k1::EcdsaSecp256k1DSIGN::sign_message_hash(&ctx, &original_hash, &sk);
         assert!(
-            ecdsa_secp256k1::EcdsaSecp256k1DSIGN::verify_bytes(&ctx, &vk, original_message, &sig)
-                .is_ok()
+            ecdsa_secp256k1::EcdsaSecp256k1DSIGN::verify_message_hash(
+                &ctx,
+                &vk,
+                &original_hash,
+                &sig
+            )
+            .is_ok()
         );
         assert!(
-            ecdsa_secp256k1::EcdsaSecp256k1DSIGN::verify_bytes(&ctx, &vk, tampered_message, &sig)
-                .is_err()
+            ecdsa_secp256k1::EcdsaSecp256k1DSIGN::verify_message_hash(
+                &ctx,
+                &vk,
+                &tampered_hash,
+                &sig
+            )
+            .is_err()
         );
     }
 
@@ -321,7 +345,7 @@ fn test_message_tampering_detection() {
     {
         let sk = schnorr_secp256k1::SchnorrSecp256k1DSIGN::gen_key(&seed);
         let vk = schnorr_secp256k1::SchnorrSecp256k1DSIGN::derive_verification_key(&sk);
-        let ctx = schnorr_secp256k1::Context;
+        let ctx = schnorr_secp256k1::SchnorrContext::default();
 
         let sig = schnorr_secp256k1::SchnorrSecp256k1DSIGN::sign_bytes(&ctx, original_message, &sk);
         assert!(
@@ -361,7 +385,7 @@ fn test_bitcoin_workflow_integration() {
     let tx_hash = sha256d(tx_data);
 
     // Sign the transaction hash
-    let ctx = ecdsa_secp256k1::Context;
+    let ctx = ecdsa_secp256k1::EcdsaContext::default();
     let signature = ecdsa_secp256k1::EcdsaSecp256k1DSIGN::sign_bytes(&ctx, &tx_hash, &sk);
 
     // Verify signature
@@ -391,7 +415,7 @@ fn test_ethereum_workflow_integration() {
     let tx_hash = keccak256(tx_data);
 
     // Sign the transaction hash
-    let ctx = ecdsa_secp256k1::Context;
+    let ctx = ecdsa_secp256k1::EcdsaContext::default();
     let signature = ecdsa_secp256k1::EcdsaSecp256k1DSIGN::sign_bytes(&ctx, &tx_hash, &sk);
 
     // Verify signature
@@ -421,7 +445,7 @@ fn test_bitcoin_taproot_workflow_integration() {
     let tx_hash = sha256d(tx_data);
 
     // Sign with Schnorr
-    let ctx = schnorr_secp256k1::Context;
+    let ctx = schnorr_secp256k1::SchnorrContext::default();
     let signature = schnorr_secp256k1::SchnorrSecp256k1DSIGN::sign_bytes(&ctx, &tx_hash, &sk);
 
     // Verify signature
@@ -6,6 +6,7 @@
 
 #[cfg(feature = "serde")]
 mod cross_compat {
+    use cardano_binary::serialize_into_writer;
     use cardano_crypto_class::Ed25519;
     use cardano_crypto_class::dsign::DsignAlgorithm;
     use cardano_crypto_class::seed::mk_seed_from_bytes;
@@ -56,7 +57,7 @@ mod cross_compat {
 
     fn encode_cbor_into<T: Serialize>(value: &T, buffer: &mut Vec<u8>) {
         buffer.clear();
-        ciborium::into_writer(value, buffer).expect("CBOR serialization must succeed");
+        serialize_into_writer(value, buffer).expect("CBOR serialization must succeed");
     }
 
     // =============================================================================
@@ -359,7 +360,7 @@ mod cross_compat {
 
         println!("\n=== Simple VRF Test Vector Generation ===\n");
         println!(
-            "Note: SimpleVRF requires {} byte seeds (16 * 100 attempts)\n",
+            "Note: SimpleVRF requires {} byte seeds (one 128-bit scalar)\n",
             SimpleVRF::SEED_SIZE
         );
 
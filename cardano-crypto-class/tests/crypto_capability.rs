@@ -0,0 +1,91 @@
+//! The startup throughput probe reports every workload, scales with its
+//! budget, and returns close to the budget it is given.
+//!
+//! Budget scaling and deadline handling are checked deterministically by the
+//! unit tests in `capability.rs`. The wall-clock tests here depend on machine
+//! load and are ignored by default; run them on an idle machine with
+//! `cargo test --test crypto_capability -- --ignored`.
+
+use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
+
+use cardano_crypto_class::capability::{
+    CryptoThroughputReport, ProbeConfig, Throughput, measure_crypto_throughput,
+};
+
+fn workloads(report: &CryptoThroughputReport) -> [(&'static str, Throughput); 4] {
+    [
+        ("ed25519", report.ed25519_verify),
+        ("praos", report.praos_vrf_verify),
+        ("sum6", report.sum6_kes_verify),
+        ("blake2b256", report.blake2b256_1kib),
+    ]
+}
+
+fn probe(budget_ms: u64) -> CryptoThroughputReport {
+    measure_crypto_throughput(ProbeConfig {
+        budget: Duration::from_millis(budget_ms),
+        ..ProbeConfig::default()
+    })
+}
+
+#[test]
+fn report_is_populated() {
+    let report = probe(80);
+    assert_eq!(report.threads.get(), 1);
+    for (name, throughput) in workloads(&report) {
+        assert!(throughput.operations > 0, "{name} ran no operations");
+        assert!(throughput.elapsed >= Duration::from_millis(20), "{name}");
+        assert!(throughput.ops_per_sec() > 0.0, "{name}");
+    }
+    let line = report.to_string();
+    assert!(line.contains("Sum6 KES verify"), "{line}");
+}
+
+#[test]
+#[ignore = "wall-clock timing; flaky on loaded machines"]
+fn larger_budget_completes_more_operations() {
+    let small = probe(40);
+    let large = probe(400);
+    for ((name, small), (_, large)) in workloads(&small).into_iter().zip(workloads(&large)) {
+        assert!(
+            large.operations > small.operations,
+            "{name}: {} ops in 400 ms, {} in 40 ms",
+            large.operations,
+            small.operations
+        );
+        assert!(large.elapsed > small.elapsed, "{name}");
+    }
+}
+
+#[test]
+#[ignore = "wall-clock timing; flaky on loaded machines"]
+fn respects_the_time_budget() {
+    let budget = Duration::from_millis(200);
+    let start = Instant::now();
+    let report = measure_crypto_throughput(ProbeConfig::default());
+    let wall = start.elapsed();
+
+    assert_eq!(ProbeConfig::default().budget, budget);
+    assert!(wall >= budget, "returned after {wall:?}");
+    // One operation past each deadline plus setup; generous for debug builds
+    // on loaded CI machines.
+    assert!(wall < budget + Duration::from_millis(300), "took {wall:?}");
+    assert!(report.elapsed <= wall);
+}
+
+#[test]
+fn runs_on_several_threads() {
+    let threads = NonZeroUsize::new(2).expect("non-zero");
+    let report = measure_crypto_throughput(ProbeConfig {
+        budget: Duration::from_millis(80),
+        threads,
+    });
+    assert_eq!(report.threads, threads);
+    for (name, throughput) in workloads(&report) {
+        assert!(
+            throughput.operations >= 2,
+            "{name} did not run on both threads"
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! Direct serialisation errors convert into the crate's error enums (and
+//! `BinaryError`) with `?`, keeping the size information reachable.
+
+use std::error::Error as _;
+
+use cardano_binary::BinaryError;
+use cardano_crypto_class::direct_serialise::{
+    DirectDeserialise, DirectSerialise, DirectSerialiseError, SizeCheckError,
+    direct_deserialise_buf_checked, direct_serialise_buf_checked,
+};
+use cardano_crypto_class::dsign::ed25519::{Ed25519, Ed25519VerificationKey};
+use cardano_crypto_class::dsign::{DsignAlgorithm, DsignMError};
+use cardano_crypto_class::kes::{KesAlgorithm, KesMError, Sum2Kes};
+
+const OVERFLOW: SizeCheckError = SizeCheckError {
+    expected_size: 22,
+    actual_size: 32,
+};
+
+const UNDERFLOW: SizeCheckError = SizeCheckError {
+    expected_size: 160,
+    actual_size: 128,
+};
+
+#[test]
+fn sizes_pick_overflow_or_underflow() {
+    assert!(matches!(
+        DirectSerialiseError::from(OVERFLOW),
+        DirectSerialiseError::Overflow(OVERFLOW)
+    ));
+    assert!(matches!(
+        DirectSerialiseError::from(UNDERFLOW),
+        DirectSerialiseError::Underflow(UNDERFLOW)
+    ));
+    assert_eq!(OVERFLOW.code(), "direct.overflow");
+    assert_eq!(UNDERFLOW.code(), "direct.underflow");
+}
+
+#[test]
+fn kes_and_dsign_errors_keep_the_sizes() {
+    let kes = KesMError::from(OVERFLOW);
+    assert_eq!(kes.code(), "direct.overflow");
+    assert!(!kes.is_transient());
+    match &kes {
+        KesMError::DirectSerialise(err) => assert_eq!(err.size_check(), Some(&OVERFLOW)),
+        other => unreachable!("unexpected error: {other:?}"),
+    }
+
+    let dsign = DsignMError::from(UNDERFLOW);
+    assert_eq!(dsign.code(), "direct.underflow");
+    match &dsign {
+        DsignMError::DirectSerialise(err) => assert_eq!(err.size_check(), Some(&UNDERFLOW)),
+        other => unreachable!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn binary_error_carries_the_direct_error() {
+    let BinaryError::Io(io) = BinaryError::from(OVERFLOW) else {
+        unreachable!("direct serialisation errors travel as I/O errors");
+    };
+    assert_eq!(io.kind(), std::io::ErrorKind::UnexpectedEof);
+    let inner = io
+        .get_ref()
+        .and_then(|err| err.downcast_ref::<DirectSerialiseError>())
+        .expect("wrapped DirectSerialiseError");
+    assert_eq!(inner.size_check(), Some(&OVERFLOW));
+
+    let BinaryError::Io(io) = BinaryError::from(UNDERFLOW) else {
+        unreachable!("direct serialisation errors travel as I/O errors");
+    };
+    assert_eq!(io.kind(), std::io::ErrorKind::InvalidData);
+}
+
+fn persist<K>(signing_key: &K::SigningKey, buffer: &mut [u8]) -> Result<(), KesMError>
+where
+    K: KesAlgorithm,
+    K::SigningKey: DirectSerialise,
+{
+    direct_serialise_buf_checked(buffer, signing_key)?;
+    Ok(())
+}
+
+fn restore<K>(buffer: &[u8]) -> Result<K::SigningKey, KesMError>
+where
+    K: KesAlgorithm,
+    K::SigningKey: DirectDeserialise,
+{
+    Ok(direct_deserialise_buf_checked(buffer)?)
+}
+
+#[test]
+fn truncated_kes_restore_chains_to_the_size_check() {
+    let sk = Sum2Kes::gen_key_kes_from_seed_bytes(&[4; 32]).expect("signing key");
+    let mut buffer = vec![0u8; Sum2Kes::SIGNING_KEY_SIZE];
+    persist::<Sum2Kes>(&sk, &mut buffer).expect("persist");
+    let restored = restore::<Sum2Kes>(&buffer).expect("restore");
+    assert_eq!(
+        Sum2Kes::derive_verification_key(&restored).expect("vk"),
+        Sum2Kes::derive_verification_key(&sk).expect("vk")
+    );
+    Sum2Kes::forget_signing_key_kes(restored);
+
+    // The last chunk is a 32-byte verification key with 22 bytes left.
+    let err = restore::<Sum2Kes>(&buffer[..buffer.len() - 10])
+        .err()
+        .expect("a truncated buffer must not restore");
+    assert_eq!(err.code(), "direct.overflow");
+    assert_eq!(err.to_string(), "direct serialisation overran the buffer");
+    let sizes = err
+        .source()
+        .and_then(|source| source.downcast_ref::<SizeCheckError>())
+        .expect("the size check is the error source");
+    assert_eq!(*sizes, OVERFLOW);
+
+    // Persisting into a larger buffer leaves it partly unfilled.
+    let mut oversized = vec![0u8; Sum2Kes::SIGNING_KEY_SIZE + 32];
+    let err = persist::<Sum2Kes>(&sk, &mut oversized).expect_err("buffer too large");
+    match err {
+        KesMError::DirectSerialise(DirectSerialiseError::Underflow(sizes)) => {
+            assert_eq!(sizes.expected_size, Sum2Kes::SIGNING_KEY_SIZE + 32);
+            assert_eq!(sizes.actual_size, Sum2Kes::SIGNING_KEY_SIZE);
+        },
+        other => unreachable!("unexpected error: {other:?}"),
+    }
+    Sum2Kes::forget_signing_key_kes(sk);
+}
+
+fn decode_verification_key(bytes: &[u8]) -> Result<Ed25519VerificationKey, DsignMError> {
+    Ok(direct_deserialise_buf_checked(bytes)?)
+}
+
+#[test]
+fn invalid_keys_are_not_size_errors() {
+    let invalid = (0..=u8::MAX)
+        .map(|low| {
+            let mut bytes = [0u8; 32];
+            bytes[0] = low;
+            bytes
+        })
+        .find(|bytes| Ed25519::raw_deserialize_verification_key(bytes).is_none())
+        .expect("some small y is off the curve");
+
+    let err = decode_verification_key(&invalid).expect_err("not a point");
+    assert_eq!(err.code(), "direct.invalid_value");
+    match err {
+        DsignMError::DirectSerialise(DirectSerialiseError::InvalidValue { what }) => {
+            assert_eq!(what, "Ed25519 verification key");
+        },
+        other => unreachable!("unexpected error: {other:?}"),
+    }
+}
@@ -0,0 +1,117 @@
+//! Domain-separated key generation must be deterministic, differ from the
+//! direct Haskell-compatible path, and give unrelated keys per algorithm.
+
+use cardano_crypto_class::dsign::ed25519::Ed25519;
+use cardano_crypto_class::dsign::schnorr_secp256k1::SchnorrSecp256k1DSIGN;
+use cardano_crypto_class::dsign::{DsignAlgorithm, gen_key_domain_separated};
+use cardano_crypto_class::kes::{
+    CompactSum6Kes, KesAlgorithm, Sum6Kes, Sum7Kes, gen_key_kes_domain_separated,
+};
+use cardano_crypto_class::seed::{Seed, expand_seed_labelled, mk_seed_from_bytes};
+use cardano_crypto_class::vrf::{PraosVRF, VRFAlgorithm, gen_key_vrf_domain_separated};
+
+fn master_seed() -> Seed {
+    mk_seed_from_bytes((0u8..32).collect::<Vec<_>>())
+}
+
+fn dsign_sk<A: DsignAlgorithm>(key: &A::SigningKey) -> Vec<u8> {
+    A::raw_serialize_signing_key(key)
+}
+
+fn kes_vk<A: KesAlgorithm>(key: A::SigningKey) -> Vec<u8> {
+    let vk = A::derive_verification_key(&key).expect("vk");
+    A::forget_signing_key_kes(key);
+    A::raw_serialize_verification_key_kes(&vk)
+}
+
+#[test]
+fn dsign_separated_path_is_deterministic_and_differs_from_direct() {
+    let seed = master_seed();
+    let separated = gen_key_domain_separated::<Ed25519>(&seed);
+    assert_eq!(
+        dsign_sk::<Ed25519>(&separated),
+        dsign_sk::<Ed25519>(&gen_key_domain_separated::<Ed25519>(&seed))
+    );
+    assert_ne!(
+        dsign_sk::<Ed25519>(&separated),
+        dsign_sk::<Ed25519>(&Ed25519::gen_key(&seed))
+    );
+}
+
+#[test]
+fn dsign_separated_path_uses_the_documented_label() {
+    let seed = master_seed();
+    let material = expand_seed_labelled(&seed, b"cardano-crypto-class/keygen/v1/dsign/ed25519", 32);
+    assert_eq!(
+        dsign_sk::<Ed25519>(&gen_key_domain_separated::<Ed25519>(&seed)),
+        dsign_sk::<Ed25519>(&Ed25519::gen_key_from_seed_bytes(material.as_slice()))
+    );
+}
+
+#[test]
+fn ed25519_and_schnorr_get_unrelated_seed_material() {
+    // Both algorithms consume a 32-byte seed, so with the direct path the
+    // Ed25519 seed and the Schnorr secret key are the same bytes.
+    let seed = master_seed();
+    let direct_schnorr = dsign_sk::<SchnorrSecp256k1DSIGN>(&SchnorrSecp256k1DSIGN::gen_key(&seed));
+    assert_eq!(direct_schnorr, seed.as_slice());
+
+    let ed25519 = dsign_sk::<Ed25519>(&gen_key_domain_separated::<Ed25519>(&seed));
+    let schnorr = dsign_sk::<SchnorrSecp256k1DSIGN>(&gen_key_domain_separated::<
+        SchnorrSecp256k1DSIGN,
+    >(&seed));
+    assert_ne!(schnorr, direct_schnorr);
+    assert_ne!(schnorr[..32], ed25519[..32]);
+    assert_ne!(schnorr, seed.as_slice());
+    assert_eq!(
+        schnorr,
+        dsign_sk::<SchnorrSecp256k1DSIGN>(&gen_key_domain_separated::<SchnorrSecp256k1DSIGN>(
+            &seed
+        ))
+    );
+}
+
+#[test]
+fn kes_separated_path_is_deterministic_and_differs_from_direct() {
+    let seed = master_seed();
+    let separated = kes_vk::<Sum6Kes>(gen_key_kes_domain_separated::<Sum6Kes>(&seed).expect("sk"));
+    assert_eq!(
+        separated,
+        kes_vk::<Sum6Kes>(gen_key_kes_domain_separated::<Sum6Kes>(&seed).expect("sk"))
+    );
+    assert_ne!(
+        separated,
+        kes_vk::<Sum6Kes>(Sum6Kes::gen_key_kes(&seed).expect("sk"))
+    );
+    // Sum6 and Sum7 both report the Ed25519 name; the period count keeps
+    // them apart.
+    assert_ne!(
+        separated,
+        kes_vk::<Sum7Kes>(gen_key_kes_domain_separated::<Sum7Kes>(&seed).expect("sk"))
+    );
+    // The compact encoding of the same tree keeps the same key.
+    assert_eq!(
+        separated,
+        kes_vk::<CompactSum6Kes>(
+            gen_key_kes_domain_separated::<CompactSum6Kes>(&seed).expect("sk")
+        )
+    );
+}
+
+#[test]
+fn vrf_separated_path_is_deterministic_and_differs_from_direct_and_dsign() {
+    let seed = master_seed();
+    let separated = gen_key_vrf_domain_separated::<PraosVRF>(&seed);
+    let raw = PraosVRF::raw_serialize_signing_key(&separated);
+    assert_eq!(
+        raw,
+        PraosVRF::raw_serialize_signing_key(&gen_key_vrf_domain_separated::<PraosVRF>(&seed))
+    );
+    assert_ne!(
+        raw,
+        PraosVRF::raw_serialize_signing_key(&PraosVRF::gen_key(&seed))
+    );
+    // A Praos signing key starts with its Ed25519-style seed.
+    let cold = dsign_sk::<Ed25519>(&gen_key_domain_separated::<Ed25519>(&seed));
+    assert_ne!(raw[..32], cold[..32]);
+}
@@ -449,3 +449,49 @@ fn test_ed25519_large_message() {
     );
     println!("✓ Large message (10 KB) signing/verification works");
 }
+
+/// Sign every embedded vector and compare byte for byte with its expected
+/// signature; deterministic RFC 8032 signing has exactly one right answer.
+fn assert_signatures_match_vectors() {
+    let vectors = parse_ed25519_vectors();
+    for vector in expect_array(&vectors, "vectors") {
+        let test_name = expect_str(vector, "test_name");
+        let signing_key = Ed25519::gen_key_from_seed_bytes(&decode_hex(expect_str(vector, "seed")));
+        let verification_key = Ed25519::derive_verification_key(&signing_key);
+        let signature = Ed25519::sign_bytes(
+            &(),
+            &decode_hex(expect_str(vector, "message")),
+            &signing_key,
+        );
+
+        assert_eq!(
+            hex::encode(Ed25519::raw_serialize_verification_key(&verification_key)),
+            expect_str(vector, "expected_public_key"),
+            "public key mismatch for {test_name}"
+        );
+        assert_eq!(
+            hex::encode(Ed25519::raw_serialize_signature(&signature)),
+            expect_str(vector, "expected_signature"),
+            "signature mismatch for {test_name}"
+        );
+    }
+}
+
+#[test]
+fn test_ed25519_signatures_are_byte_exact() {
+    assert_signatures_match_vectors();
+}
+
+#[test]
+fn test_ed25519_signatures_are_byte_exact_across_threads() {
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(assert_signatures_match_vectors);
+        }
+    });
+}
+
+#[test]
+fn test_ed25519_signing_canary_passes() {
+    assert!(cardano_crypto_class::dsign::ed25519::check_deterministic_signing().is_ok());
+}
@@ -3,8 +3,8 @@
 //! This module loads test vectors from the cardano-test-vectors crate
 //! and validates the Schnorr Secp256k1 (BIP340) implementation against them.
 
-use cardano_crypto_class::dsign::DsignAlgorithm;
-use cardano_crypto_class::dsign::schnorr_secp256k1::SchnorrSecp256k1DSIGN;
+use cardano_crypto_class::dsign::schnorr_secp256k1::{SchnorrContext, SchnorrSecp256k1DSIGN};
+use cardano_crypto_class::dsign::{DsignAlgorithm, signed_dsign, verify_signed_dsign};
 use cardano_crypto_class::seed::mk_seed_from_bytes;
 use cardano_test_vectors::dsign;
 use serde_json::Value;
@@ -88,6 +88,56 @@ fn test_schnorr_key_generation_from_seed() {
     }
 }
 
+#[test]
+fn test_schnorr_key_derivation_vectors() {
+    let vectors = parse_schnorr_vectors();
+    let derivations = vectors["key_derivation_vectors"]
+        .as_array()
+        .expect("key_derivation_vectors field must be an array");
+    assert!(!derivations.is_empty());
+
+    for vector in derivations {
+        let test_name = vector["test_name"]
+            .as_str()
+            .expect("test_name must be a string");
+        let seed = mk_seed_from_bytes(decode_hex(
+            vector["seed"].as_str().expect("seed must be a hex string"),
+        ));
+        let signing_key = SchnorrSecp256k1DSIGN::gen_key(&seed);
+        let verification_key = SchnorrSecp256k1DSIGN::derive_verification_key(&signing_key);
+        assert_eq!(
+            hex::encode(SchnorrSecp256k1DSIGN::raw_serialize_verification_key(
+                &verification_key
+            )),
+            vector["verification_key"]
+                .as_str()
+                .expect("verification_key must be a hex string"),
+            "{test_name}: verification key derived from seed"
+        );
+    }
+}
+
+#[test]
+fn test_schnorr_invalid_seeds_are_rejected() {
+    let vectors = parse_schnorr_vectors();
+    let invalid = vectors["invalid_seed_vectors"]
+        .as_array()
+        .expect("invalid_seed_vectors field must be an array");
+    assert!(!invalid.is_empty());
+
+    for vector in invalid {
+        let test_name = vector["test_name"]
+            .as_str()
+            .expect("test_name must be a string");
+        let seed = decode_hex(vector["seed"].as_str().expect("seed must be a hex string"));
+        // An out-of-range scalar is an error rather than being reduced or
+        // retried with fresh bytes.
+        let result =
+            std::panic::catch_unwind(|| SchnorrSecp256k1DSIGN::gen_key_from_seed_bytes(&seed));
+        assert!(result.is_err(), "{test_name}: seed should be rejected");
+    }
+}
+
 #[test]
 fn test_schnorr_sign_and_verify() {
     let vectors = parse_schnorr_vectors();
@@ -420,3 +470,101 @@ fn test_schnorr_large_message() {
     );
     println!("✓ Large message signing/verification successful");
 }
+
+/// BIP340 `test-vectors.csv` signing cases 0-3 as
+/// `(secret_key, public_key, aux_rand, message, signature)`.
+const BIP340_SIGNING_VECTORS: [(&str, &str, &str, &str, &str); 4] = [
+    (
+        "0000000000000000000000000000000000000000000000000000000000000003",
+        "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA8215\
+         25F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+    ),
+    (
+        "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+        "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE3341\
+         8906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+    ),
+    (
+        "C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9",
+        "DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+        "C87AA53824B4D7AE2EB035A2B5BBBCCC080E76CDC6D1692C4B0B62D798E6D906",
+        "7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C",
+        "5831AAEED7B44BB74E5EAB94BA9D4294C49BCF2A60728D8B4C200F50DD313C1B\
+         AB745879A5AD954A72C45A91C3A51D3C7ADEA98D82F8481E0E1E03674A6F3FB7",
+    ),
+    (
+        "0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710",
+        "25D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517",
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+        "7EB0509757E246F19449885651611CB965ECC1A187DD51B64FDA1EDC9637D5EC\
+         97582B9CB13DB3933705B32BA982AF5AF25FD78881EBB32771FC5922EFC66EA3",
+    ),
+];
+
+#[test]
+fn test_schnorr_bip340_vectors_with_aux_rand() {
+    for (sk_hex, pk_hex, aux_hex, message_hex, signature_hex) in BIP340_SIGNING_VECTORS {
+        let signing_key = SchnorrSecp256k1DSIGN::gen_key(&mk_seed_from_bytes(decode_hex(sk_hex)));
+        let verification_key = SchnorrSecp256k1DSIGN::derive_verification_key(&signing_key);
+        assert_eq!(
+            SchnorrSecp256k1DSIGN::raw_serialize_verification_key(&verification_key),
+            decode_hex(pk_hex)
+        );
+
+        let aux_rand: [u8; 32] = decode_hex(aux_hex)
+            .try_into()
+            .expect("aux_rand is 32 bytes");
+        let context = SchnorrContext::with_aux_rand(aux_rand);
+        let message = decode_hex(message_hex);
+        let signature = SchnorrSecp256k1DSIGN::sign_bytes(&context, &message, &signing_key);
+        assert_eq!(
+            SchnorrSecp256k1DSIGN::raw_serialize_signature(&signature),
+            decode_hex(signature_hex),
+            "BIP340 signature for secret key {sk_hex}"
+        );
+        SchnorrSecp256k1DSIGN::verify_bytes(&context, &verification_key, &message, &signature)
+            .expect("BIP340 signature verifies");
+    }
+}
+
+#[test]
+fn test_schnorr_default_context_uses_fresh_aux_rand() {
+    let signing_key = SchnorrSecp256k1DSIGN::gen_key_from_seed_bytes(&[5u8; 32]);
+    let verification_key = SchnorrSecp256k1DSIGN::derive_verification_key(&signing_key);
+    let context = SchnorrContext::default();
+    assert_eq!(context.aux_rand, None);
+
+    let message = [0x11u8; 32];
+    let first = SchnorrSecp256k1DSIGN::sign_bytes(&context, &message, &signing_key);
+    let second = SchnorrSecp256k1DSIGN::sign_bytes(&context, &message, &signing_key);
+    assert_ne!(first, second, "default context randomises the nonce");
+    for signature in [&first, &second] {
+        SchnorrSecp256k1DSIGN::verify_bytes(&context, &verification_key, &message, signature)
+            .expect("default-context signature verifies");
+    }
+}
+
+#[test]
+fn test_schnorr_generic_signed_dsign_with_default_context() {
+    let signing_key = SchnorrSecp256k1DSIGN::gen_key_from_seed_bytes(&[6u8; 32]);
+    let verification_key = SchnorrSecp256k1DSIGN::derive_verification_key(&signing_key);
+    let message = b"generic path".to_vec();
+
+    let signed =
+        signed_dsign::<SchnorrSecp256k1DSIGN, _>(&Default::default(), &message, &signing_key)
+            .expect("generic signed_dsign signs");
+    verify_signed_dsign::<SchnorrSecp256k1DSIGN, _>(
+        &Default::default(),
+        &verification_key,
+        &message,
+        &signed,
+    )
+    .expect("generic signed_dsign verifies");
+}
@@ -0,0 +1,162 @@
+//! Signing and verifying a message given as several slices must behave
+//! exactly as signing and verifying their concatenation, wherever the
+//! message is split.
+
+use cardano_crypto_class::dsign::ed25519::Ed25519;
+use cardano_crypto_class::dsign::{DsignAlgorithm, DsignError, DsignVectoredVerify};
+use cardano_test_vectors::dsign;
+use proptest::prelude::*;
+use serde_json::Value;
+
+fn decode_hex(s: &str) -> Vec<u8> {
+    hex::decode(s).expect("valid hex string")
+}
+
+fn rfc8032_vectors() -> Vec<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+    let json =
+        dsign::get("ed25519_test_vectors.json").expect("Ed25519 test vectors should be available");
+    let vectors: Value = serde_json::from_str(json).expect("Ed25519 test vectors JSON");
+    vectors["vectors"]
+        .as_array()
+        .expect("vectors array")
+        .iter()
+        .filter(|vector| {
+            vector["test_name"]
+                .as_str()
+                .is_some_and(|name| name.starts_with("RFC_8032"))
+        })
+        .map(|vector| {
+            let field = |key: &str| decode_hex(vector[key].as_str().expect("string field"));
+            (field("seed"), field("message"), field("expected_signature"))
+        })
+        .collect()
+}
+
+/// Check `parts` against the concatenated message under the same key.
+fn assert_matches_concatenation(seed: &[u8], parts: &[&[u8]]) {
+    let message = parts.concat();
+    let signing_key = Ed25519::gen_key_from_seed_bytes(seed);
+    let verification_key = Ed25519::derive_verification_key(&signing_key);
+
+    let signature = Ed25519::sign_bytes(&(), &message, &signing_key);
+    let vectored = Ed25519::sign_bytes_vectored(&(), parts, &signing_key);
+    assert_eq!(vectored, signature, "{} parts", parts.len());
+    Ed25519::verify_bytes_vectored(&(), &verification_key, parts, &signature)
+        .expect("vectored verify of a valid signature");
+
+    let mut extended = parts.to_vec();
+    extended.push(&[0]);
+    assert_eq!(
+        Ed25519::verify_bytes_vectored(&(), &verification_key, &extended, &signature),
+        Err(DsignError::VerificationFailed)
+    );
+}
+
+#[test]
+fn rfc8032_vectors_at_every_split_point() {
+    let vectors = rfc8032_vectors();
+    assert!(!vectors.is_empty(), "no RFC 8032 vectors found");
+    for (seed, message, expected_signature) in vectors {
+        let signing_key = Ed25519::gen_key_from_seed_bytes(&seed);
+        let verification_key = Ed25519::derive_verification_key(&signing_key);
+        for split in 0..=message.len() {
+            let (head, tail) = message.split_at(split);
+            let signature = Ed25519::sign_bytes_vectored(&(), &[head, tail], &signing_key);
+            assert_eq!(
+                Ed25519::raw_serialize_signature(&signature),
+                expected_signature,
+                "split at {split}"
+            );
+            Ed25519::verify_bytes_vectored(&(), &verification_key, &[head, tail], &signature)
+                .expect("vectored verify at split point");
+        }
+    }
+}
+
+#[test]
+fn no_parts_is_the_empty_message() {
+    let seed = [9u8; 32];
+    assert_matches_concatenation(&seed, &[]);
+    assert_matches_concatenation(&seed, &[b"", b""]);
+}
+
+#[test]
+fn rejects_like_the_concatenation() {
+    let signing_key = Ed25519::gen_key_from_seed_bytes(&[3u8; 32]);
+    let verification_key = Ed25519::derive_verification_key(&signing_key);
+    let other_key = Ed25519::derive_verification_key(&Ed25519::gen_key_from_seed_bytes(&[4u8; 32]));
+    let signature = Ed25519::sign_bytes(&(), b"header-prefix|body-hash|trailer", &signing_key);
+
+    for (key, parts) in [
+        (
+            &verification_key,
+            [&b"header-prefix|"[..], b"body-hash|", b"trailer!"],
+        ),
+        (
+            &verification_key,
+            [&b"header-prefix|"[..], b"trailer", b"body-hash|"],
+        ),
+        (
+            &other_key,
+            [&b"header-prefix|"[..], b"body-hash|", b"trailer"],
+        ),
+    ] {
+        assert_eq!(
+            Ed25519::verify_bytes_vectored(&(), key, &parts, &signature),
+            Ed25519::verify_bytes(&(), key, &parts.concat(), &signature),
+        );
+        assert_eq!(
+            Ed25519::verify_bytes_vectored(&(), key, &parts, &signature),
+            Err(DsignError::VerificationFailed)
+        );
+    }
+}
+
+/// Split `message` at `cuts`, reduced modulo its length and sorted; repeated
+/// cuts give empty parts.
+fn split_at_cuts(message: &[u8], mut cuts: Vec<usize>) -> Vec<&[u8]> {
+    cuts.iter_mut().for_each(|cut| *cut %= message.len() + 1);
+    cuts.sort_unstable();
+    let mut parts = Vec::with_capacity(cuts.len() + 1);
+    let mut start = 0;
+    for cut in cuts {
+        parts.push(&message[start..cut]);
+        start = cut;
+    }
+    parts.push(&message[start..]);
+    parts
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(128))]
+
+    #[test]
+    fn proptest_random_splits_match_concatenation(
+        seed in any::<[u8; 32]>(),
+        message in proptest::collection::vec(any::<u8>(), 0..512),
+        cuts in proptest::collection::vec(any::<usize>(), 0..8),
+        flip in any::<usize>(),
+    ) {
+        let parts = split_at_cuts(&message, cuts);
+        let signing_key = Ed25519::gen_key_from_seed_bytes(&seed);
+        let verification_key = Ed25519::derive_verification_key(&signing_key);
+        let signature = Ed25519::sign_bytes(&(), &message, &signing_key);
+
+        prop_assert_eq!(Ed25519::sign_bytes_vectored(&(), &parts, &signing_key), signature.clone());
+        prop_assert_eq!(
+            Ed25519::verify_bytes_vectored(&(), &verification_key, &parts, &signature),
+            Ok(())
+        );
+
+        // A single flipped bit fails both ways alike.
+        if !message.is_empty() {
+            let mut corrupted = message.clone();
+            corrupted[flip % message.len()] ^= 1;
+            let parts = split_at_cuts(&corrupted, vec![flip]);
+            prop_assert_eq!(
+                Ed25519::verify_bytes_vectored(&(), &verification_key, &parts, &signature),
+                Ed25519::verify_bytes(&(), &verification_key, &corrupted, &signature)
+            );
+        }
+    }
+}
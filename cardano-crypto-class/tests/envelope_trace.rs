@@ -0,0 +1,57 @@
+//! Signing keys written to or read from text envelopes must never reach a
+//! `cardano-binary` trace sink, which copies payloads in the clear.
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use cardano_binary::{TraceEvent, clear_trace_sink, serialize, set_trace_sink};
+use cardano_crypto_class::kes::Sum2Kes;
+use cardano_crypto_class::role::{PaymentRole, RoleTagged, gen_dsign_key, gen_kes_key};
+use cardano_crypto_class::workflows::{KesSessionConfig, KesSessionManager};
+use cardano_crypto_class::{Ed25519, Ed25519SigningKey};
+
+/// The sink is process-wide, so tests that install one must not overlap.
+static SINK_LOCK: Mutex<()> = Mutex::new(());
+
+fn collect_events(run: impl FnOnce()) -> Vec<TraceEvent> {
+    let _guard = SINK_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink_events = Arc::clone(&events);
+    set_trace_sink(Box::new(move |event| {
+        sink_events.lock().expect("event lock").push(event);
+    }));
+    run();
+    clear_trace_sink();
+    events.lock().expect("event lock").clone()
+}
+
+#[test]
+fn dsign_signing_key_envelopes_are_not_traced() {
+    let events = collect_events(|| {
+        let key = gen_dsign_key::<PaymentRole, Ed25519>(&[0x42; 32]).expect("payment key");
+        let envelope = key.to_text_envelope();
+        let back = RoleTagged::<PaymentRole, Ed25519SigningKey>::from_text_envelope(&envelope)
+            .expect("import");
+        assert_eq!(back.to_text_envelope(), envelope);
+        // The sink is live: other encodes on this thread are still reported.
+        serialize(&7u8).expect("serialize");
+    });
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].payload.as_deref(), Some(&[0x07][..]));
+}
+
+#[test]
+fn persisted_kes_sessions_are_not_traced() {
+    let config = KesSessionConfig {
+        slots_per_kes_period: 10,
+        ocert_start_period: 0,
+        max_kes_evolutions: 4,
+    };
+    let events = collect_events(|| {
+        let key = gen_kes_key::<Sum2Kes>(&[0x24; 32]).expect("KES key");
+        let persisted = KesSessionManager::<Sum2Kes>::new(config, key)
+            .persist()
+            .expect("persist");
+        KesSessionManager::<Sum2Kes>::restore(config, &persisted).expect("restore");
+    });
+    assert!(events.is_empty(), "traced {events:?}");
+}
@@ -0,0 +1,564 @@
+//! Stable error codes and their structured (serde) rendering.
+
+use std::collections::HashSet;
+
+use cardano_crypto_class::SeedBytesExhausted;
+#[cfg(feature = "bls12-381")]
+use cardano_crypto_class::bls12_381::BlsError;
+use cardano_crypto_class::direct_serialise::{DirectSerialiseError, SizeCheckError};
+use cardano_crypto_class::dsign::{DsignError, DsignMError};
+use cardano_crypto_class::hash::HashKeyTooLong;
+use cardano_crypto_class::kes::{
+    KesError, KesMError, KesVerifyFailure, KesVerifyStage, OcertPeriodError,
+};
+use cardano_crypto_class::keys::TaggedKeyError;
+use cardano_crypto_class::mlocked_bytes::MLockedError;
+#[cfg(feature = "signer-service")]
+use cardano_crypto_class::signer_service::SignerError;
+use cardano_crypto_class::vrf::{PraosConstructionError, VRFError};
+#[cfg(feature = "workflows")]
+use cardano_crypto_class::workflows::WorkflowError;
+use cardano_vrf_pure::VrfError as VrfPureError;
+
+fn kes_errors() -> Vec<KesError> {
+    vec![
+        KesError::VerificationFailed,
+        KesError::wrong_length("vk", 32, 31),
+        KesError::Message("boom".to_owned()),
+        KesError::KeyExpired,
+        KesError::PeriodOutOfRange {
+            period: 64,
+            max_period: 64,
+        },
+    ]
+}
+
+fn dsign_errors() -> Vec<DsignError> {
+    vec![
+        DsignError::VerificationFailed,
+        DsignError::wrong_length("sig", 64, 63),
+        DsignError::Message("boom".to_owned()),
+        DsignError::NonDeterministicSigning {
+            algorithm: "ed25519",
+        },
+        DsignError::UnsupportedStreaming {
+            algorithm: "ed25519",
+        },
+    ]
+}
+
+fn vrf_errors() -> Vec<VRFError> {
+    vec![
+        VRFError::wrong_length("proof", 80, 79),
+        VRFError::value_too_large(64),
+        VRFError::VerificationFailed,
+    ]
+}
+
+// `RandomFailed` wraps an `OsError`, which cannot be constructed outside
+// `rand_core`; its code is covered by the match in `MLockedError::code`.
+fn mlocked_errors() -> Vec<MLockedError> {
+    vec![
+        MLockedError::AllocationFailed,
+        MLockedError::LockFailed { code: 12 },
+        MLockedError::LockLimitExceeded {
+            requested: 4096,
+            soft_limit: 65536,
+            hard_limit: u64::MAX,
+        },
+        MLockedError::InvalidAlignment,
+        MLockedError::AllocationTooLarge,
+        MLockedError::Unsupported,
+        MLockedError::Memfd {
+            source: std::io::Error::from(std::io::ErrorKind::PermissionDenied),
+        },
+        MLockedError::MemfdLength {
+            expected: 32,
+            actual: 16,
+        },
+        MLockedError::MemfdNotSealed,
+    ]
+}
+
+fn direct_serialise_errors() -> Vec<DirectSerialiseError> {
+    vec![
+        SizeCheckError {
+            expected_size: 0,
+            actual_size: 4,
+        }
+        .into(),
+        SizeCheckError {
+            expected_size: 8,
+            actual_size: 4,
+        }
+        .into(),
+        DirectSerialiseError::InvalidValue {
+            what: "Ed25519 verification key",
+        },
+    ]
+}
+
+fn tagged_key_errors() -> Vec<TaggedKeyError> {
+    vec![
+        TaggedKeyError::TruncatedHeader,
+        TaggedKeyError::UnknownAlgorithm("rsa".to_owned()),
+        TaggedKeyError::WrongAlgorithm {
+            expected: "ed25519",
+            found: "ed25519_kes_2^6".to_owned(),
+        },
+        TaggedKeyError::LengthMismatch {
+            algorithm: "ed25519",
+            expected: 32,
+            actual: 31,
+        },
+        TaggedKeyError::TrailingBytes {
+            algorithm: "ed25519",
+            extra: 1,
+        },
+        TaggedKeyError::InvalidKey {
+            algorithm: "EcdsaSecp256k1DSIGN",
+        },
+    ]
+}
+
+fn kes_verify_failures() -> Vec<KesVerifyFailure> {
+    [
+        KesVerifyStage::LeafSignature,
+        KesVerifyStage::RootHashMismatch {
+            expected_hex: "00".to_owned(),
+            actual_hex: "01".to_owned(),
+        },
+        KesVerifyStage::EmbeddedKeyMismatch,
+        KesVerifyStage::PeriodRouting {
+            signed_period: Some(3),
+        },
+    ]
+    .into_iter()
+    .map(|stage| KesVerifyFailure::at_root(stage, 5))
+    .collect()
+}
+
+fn ocert_period_errors() -> Vec<OcertPeriodError> {
+    vec![
+        OcertPeriodError::NotYetValid {
+            current_period: 9,
+            start_period: 10,
+        },
+        OcertPeriodError::Expired {
+            expired_at_slot: 1_500,
+            current_period: 15,
+        },
+        OcertPeriodError::InvalidParameters {
+            reason: "total periods must be positive",
+        },
+    ]
+}
+
+/// The variants with codes of their own; the wrapping ones report the inner
+/// code.
+#[cfg(feature = "workflows")]
+fn workflow_errors() -> Vec<WorkflowError> {
+    vec![
+        WorkflowError::StalePeriod {
+            requested: 1,
+            current: 2,
+        },
+        WorkflowError::InvalidLeaderProof { slot: 7 },
+        WorkflowError::Envelope(cardano_crypto_class::role::TextEnvelopeError::InvalidHex),
+        WorkflowError::Io {
+            path: "pool.skey".into(),
+            kind: std::io::ErrorKind::NotFound,
+            message: "not found".to_owned(),
+        },
+        WorkflowError::InvalidFile {
+            path: "pool.skey".into(),
+            message: "expected value".to_owned(),
+        },
+    ]
+}
+
+/// The variants with codes of their own.
+#[cfg(feature = "signer-service")]
+fn signer_errors() -> Vec<SignerError> {
+    vec![
+        SignerError::Backpressure { capacity: 32 },
+        SignerError::Closed,
+        SignerError::StalePeriod {
+            requested: 1,
+            current: 2,
+        },
+        SignerError::FuturePeriod {
+            requested: 3,
+            current: 2,
+        },
+        SignerError::Spawn {
+            message: "Resource temporarily unavailable".to_owned(),
+        },
+        SignerError::TimedOut {
+            timeout: std::time::Duration::from_millis(20),
+        },
+    ]
+}
+
+#[cfg(feature = "bls12-381")]
+fn bls_errors() -> Vec<BlsError> {
+    vec![
+        BlsError::WrongLength {
+            context: "G1 compressed point",
+            expected: 48,
+            actual: 47,
+        },
+        BlsError::BadEncoding,
+        BlsError::PointNotOnCurve,
+        BlsError::PointNotInGroup,
+        BlsError::BadScalar,
+    ]
+}
+
+#[test]
+fn codes_are_unique_across_error_types() {
+    let mut codes: Vec<&'static str> = Vec::new();
+    codes.extend(kes_errors().iter().map(KesError::code));
+    codes.extend(dsign_errors().iter().map(DsignError::code));
+    codes.extend(vrf_errors().iter().map(VRFError::code));
+    codes.extend(mlocked_errors().iter().map(MLockedError::code));
+    codes.extend(
+        direct_serialise_errors()
+            .iter()
+            .map(DirectSerialiseError::code),
+    );
+    codes.extend(tagged_key_errors().iter().map(TaggedKeyError::code));
+    codes.extend(kes_verify_failures().iter().map(KesVerifyFailure::code));
+    codes.extend(ocert_period_errors().iter().map(OcertPeriodError::code));
+    #[cfg(feature = "workflows")]
+    codes.extend(workflow_errors().iter().map(WorkflowError::code));
+    #[cfg(feature = "signer-service")]
+    codes.extend(signer_errors().iter().map(SignerError::code));
+    #[cfg(feature = "bls12-381")]
+    codes.extend(bls_errors().iter().map(BlsError::code));
+    codes.push(KesMError::Dsign("boom".to_owned()).code());
+    codes.push(PraosConstructionError::Vrf(VrfPureError::InvalidProof).code());
+    codes.push(
+        PraosConstructionError::WrongLength {
+            expected: 32,
+            actual: 0,
+        }
+        .code(),
+    );
+    codes.push(
+        SeedBytesExhausted {
+            supplied: 0,
+            demanded: 32,
+        }
+        .code(),
+    );
+    codes.push(
+        HashKeyTooLong {
+            max: 64,
+            actual: 65,
+        }
+        .code(),
+    );
+
+    let mut seen = HashSet::new();
+    for code in &codes {
+        assert!(seen.insert(*code), "duplicate error code {code}");
+    }
+}
+
+#[test]
+fn wrapped_errors_report_inner_codes() {
+    let kes = KesMError::from(KesError::KeyExpired);
+    assert_eq!(kes.code(), "kes.key_expired");
+    let mlocked = KesMError::from(MLockedError::LockFailed { code: 12 });
+    assert_eq!(mlocked.code(), "mlocked.lock_failed");
+    assert!(mlocked.is_transient());
+
+    let dsign = DsignMError::from(DsignError::VerificationFailed);
+    assert_eq!(dsign.code(), "dsign.verification_failed");
+    assert!(!dsign.is_transient());
+
+    let direct = DsignMError::from(DirectSerialiseError::from(MLockedError::AllocationFailed));
+    assert_eq!(direct.code(), "mlocked.allocation_failed");
+    assert!(direct.is_transient());
+
+    let praos = PraosConstructionError::from(MLockedError::AllocationFailed);
+    assert_eq!(praos.code(), "mlocked.allocation_failed");
+    assert!(praos.is_transient());
+}
+
+#[test]
+fn only_resource_failures_are_transient() {
+    assert!(kes_errors().iter().all(|err| !err.is_transient()));
+    assert!(dsign_errors().iter().all(|err| !err.is_transient()));
+    assert!(vrf_errors().iter().all(|err| !err.is_transient()));
+    assert!(tagged_key_errors().iter().all(|err| !err.is_transient()));
+    assert!(kes_verify_failures().iter().all(|err| !err.is_transient()));
+    assert!(ocert_period_errors().iter().all(|err| !err.is_transient()));
+    assert!(
+        direct_serialise_errors()
+            .iter()
+            .all(|err| !err.is_transient())
+    );
+    assert!(MLockedError::AllocationFailed.is_transient());
+    assert!(
+        MLockedError::LockLimitExceeded {
+            requested: 1,
+            soft_limit: 0,
+            hard_limit: 0
+        }
+        .is_transient()
+    );
+    assert!(!MLockedError::AllocationTooLarge.is_transient());
+    assert!(!MLockedError::InvalidAlignment.is_transient());
+    #[cfg(feature = "workflows")]
+    {
+        assert!(workflow_errors().iter().all(|err| !err.is_transient()));
+        let interrupted = WorkflowError::Io {
+            path: "pool.skey".into(),
+            kind: std::io::ErrorKind::Interrupted,
+            message: "interrupted".to_owned(),
+        };
+        assert!(interrupted.is_transient());
+        assert!(
+            WorkflowError::from(KesMError::from(MLockedError::AllocationFailed)).is_transient()
+        );
+    }
+    #[cfg(feature = "signer-service")]
+    {
+        let transient: Vec<bool> = signer_errors()
+            .iter()
+            .map(SignerError::is_transient)
+            .collect();
+        assert_eq!(&transient[..5], [true, false, false, false, true]);
+        assert!(!SignerError::from(KesMError::Kes(KesError::KeyExpired)).is_transient());
+    }
+    #[cfg(feature = "bls12-381")]
+    assert!(bls_errors().iter().all(|err| !err.is_transient()));
+}
+
+#[cfg(feature = "serde")]
+mod json {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn kes_error_shape() {
+        assert_eq!(
+            serde_json::to_value(KesError::PeriodOutOfRange {
+                period: 64,
+                max_period: 64
+            })
+            .expect("serialise"),
+            json!({
+                "code": "kes.period_out_of_range",
+                "period": 64,
+                "max_period": 64,
+                "message": "period 64 out of range [0, 64)",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(KesError::wrong_length("vk", 32, 31)).expect("serialise"),
+            json!({
+                "code": "kes.wrong_length",
+                "context": "vk",
+                "expected": 32,
+                "actual": 31,
+                "message": "vk: wrong length, expected 32 bytes but got 31",
+            })
+        );
+    }
+
+    #[test]
+    fn kes_m_error_flattens_wrapped_errors() {
+        assert_eq!(
+            serde_json::to_value(KesMError::from(MLockedError::LockFailed { code: 12 }))
+                .expect("serialise"),
+            json!({
+                "code": "mlocked.lock_failed",
+                "errno": 12,
+                "message": "mlock failed: 12",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(KesMError::from(MLockedError::LockLimitExceeded {
+                requested: 4096,
+                soft_limit: 65536,
+                hard_limit: u64::MAX,
+            }))
+            .expect("serialise"),
+            json!({
+                "code": "mlocked.lock_limit_exceeded",
+                "requested": 4096,
+                "soft_limit": 65536,
+                "hard_limit": u64::MAX,
+                "message": "cannot lock 4096 bytes: RLIMIT_MEMLOCK is 65536 bytes (hard limit \
+                            unlimited); raise it with `ulimit -l` or `LimitMEMLOCK=` in the \
+                            service unit",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(KesMError::Dsign("bad seed".to_owned())).expect("serialise"),
+            json!({
+                "code": "kes.dsign",
+                "message": "DSIGN error: bad seed",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(KesMError::from(KesError::KeyExpired)).expect("serialise"),
+            json!({
+                "code": "kes.key_expired",
+                "message": "KES key evolved beyond max period",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(KesMError::from(SizeCheckError {
+                expected_size: 22,
+                actual_size: 32,
+            }))
+            .expect("serialise"),
+            json!({
+                "code": "direct.overflow",
+                "expected_size": 22,
+                "actual_size": 32,
+                "message": "direct serialisation overran the buffer",
+            })
+        );
+    }
+
+    #[test]
+    fn dsign_and_vrf_error_shapes() {
+        assert_eq!(
+            serde_json::to_value(DsignMError::from(DsignError::VerificationFailed))
+                .expect("serialise"),
+            json!({
+                "code": "dsign.verification_failed",
+                "message": "signature verification failed",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(VRFError::value_too_large(64)).expect("serialise"),
+            json!({
+                "code": "vrf.value_too_large",
+                "expected": 64,
+                "message": "value exceeds 64 bytes",
+            })
+        );
+    }
+
+    #[test]
+    fn praos_and_seed_error_shapes() {
+        assert_eq!(
+            serde_json::to_value(PraosConstructionError::WrongLength {
+                expected: 32,
+                actual: 0
+            })
+            .expect("serialise"),
+            json!({
+                "code": "praos.wrong_length",
+                "expected": 32,
+                "actual": 0,
+                "message": "invalid length: expected 32, got 0",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(SeedBytesExhausted {
+                supplied: 16,
+                demanded: 32
+            })
+            .expect("serialise"),
+            json!({
+                "code": "seed.bytes_exhausted",
+                "supplied": 16,
+                "demanded": 32,
+                "message": "seed bytes exhausted: supplied 16, demanded 32",
+            })
+        );
+    }
+
+    #[test]
+    fn tagged_key_error_shape() {
+        assert_eq!(
+            serde_json::to_value(TaggedKeyError::LengthMismatch {
+                algorithm: "ed25519",
+                expected: 32,
+                actual: 31
+            })
+            .expect("serialise"),
+            json!({
+                "code": "tagged_key.length_mismatch",
+                "algorithm": "ed25519",
+                "expected": 32,
+                "actual": 31,
+                "message": "ed25519 key: wrong length, expected 32 bytes but got 31",
+            })
+        );
+    }
+
+    #[cfg(feature = "bls12-381")]
+    #[test]
+    fn bls_error_shape() {
+        assert_eq!(
+            serde_json::to_value(BlsError::WrongLength {
+                context: "G2 compressed point",
+                expected: 96,
+                actual: 48,
+            })
+            .expect("serialise"),
+            json!({
+                "code": "bls.wrong_length",
+                "context": "G2 compressed point",
+                "expected": 96,
+                "actual": 48,
+                "message": "G2 compressed point: wrong length, expected 96 bytes but got 48",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(BlsError::PointNotInGroup).expect("serialise"),
+            json!({
+                "code": "bls.point_not_in_group",
+                "message": "point is not in the prime-order subgroup",
+            })
+        );
+    }
+
+    #[test]
+    fn kes_verify_failure_shape() {
+        assert_eq!(
+            serde_json::to_value(KesVerifyFailure::at_root(
+                KesVerifyStage::RootHashMismatch {
+                    expected_hex: "00".to_owned(),
+                    actual_hex: "01".to_owned(),
+                },
+                5,
+            ))
+            .expect("serialise"),
+            json!({
+                "code": "kes.verify.root_hash_mismatch",
+                "period": 5,
+                "depth": 0,
+                "expected_hex": "00",
+                "actual_hex": "01",
+                "message": "KES verification failed for period 5 at depth 0: verification key hash mismatch, expected 00 but computed 01",
+            })
+        );
+    }
+
+    #[test]
+    fn ocert_period_error_shape() {
+        assert_eq!(
+            serde_json::to_value(OcertPeriodError::Expired {
+                expired_at_slot: 1_500,
+                current_period: 15,
+            })
+            .expect("serialise"),
+            json!({
+                "code": "kes.ocert_expired",
+                "expired_at_slot": 1_500,
+                "current_period": 15,
+                "message": "operational certificate expired at slot 1500 (KES period 15)",
+            })
+        );
+    }
+}
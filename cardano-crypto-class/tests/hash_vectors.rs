@@ -67,7 +67,6 @@ fn hash_vectors_match() {
     let parsed: HashVectorsFile = serde_json::from_slice(&data).expect("valid hash vectors json");
 
     use cardano_crypto_class::hash::*;
-    use cardano_crypto_class::kes::hash::KesHashAlgorithm;
 
     for v in parsed.vectors.iter() {
         let input = hex_to_bytes(&v.input_hex);
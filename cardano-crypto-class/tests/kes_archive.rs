@@ -0,0 +1,167 @@
+//! Historical KES verification across three operational certificates over
+//! Sum6 (64 periods each):
+//!
+//! | cert | start | covers    | notes                          |
+//! |------|-------|-----------|--------------------------------|
+//! | A    | 0     | 0..64     | superseded by B from period 50 |
+//! | B    | 50    | 50..114   |                                |
+//! | C    | 120   | 120..184  | periods 114..120 are a gap     |
+
+use cardano_crypto_class::kes::archive::{HistoricalVerifier, HistoricalVerifyError};
+use cardano_crypto_class::kes::{KesAlgorithm, KesError, Period, Sum6Kes};
+
+const SLOTS_PER_KES_PERIOD: u64 = 129_600;
+const MESSAGE: &[u8] = b"historical header";
+
+struct Cert {
+    start: Period,
+    vk: <Sum6Kes as KesAlgorithm>::VerificationKey,
+    /// Signatures over `MESSAGE` indexed by relative period.
+    signatures: Vec<(Period, <Sum6Kes as KesAlgorithm>::Signature)>,
+}
+
+fn cert(seed: u8, start: Period, relative_periods: &[Period]) -> Cert {
+    let mut sk = Sum6Kes::gen_key_kes_from_seed_bytes(&[seed; 32]).expect("key generation");
+    let vk = Sum6Kes::derive_verification_key(&sk).expect("verification key");
+    let mut signatures = Vec::new();
+    let mut current = 0;
+    for &period in relative_periods {
+        while current < period {
+            sk = Sum6Kes::update_kes(&(), sk, current)
+                .expect("update")
+                .expect("key not expired");
+            current += 1;
+        }
+        let sig = Sum6Kes::sign_kes(&(), period, MESSAGE, &sk).expect("sign");
+        signatures.push((period, sig));
+    }
+    Sum6Kes::forget_signing_key_kes(sk);
+    Cert {
+        start,
+        vk,
+        signatures,
+    }
+}
+
+fn signature(cert: &Cert, relative: Period) -> &<Sum6Kes as KesAlgorithm>::Signature {
+    &cert
+        .signatures
+        .iter()
+        .find(|(period, _)| *period == relative)
+        .expect("signature prepared")
+        .1
+}
+
+fn slot_in(kes_period: Period) -> u64 {
+    kes_period * SLOTS_PER_KES_PERIOD + SLOTS_PER_KES_PERIOD / 2
+}
+
+fn setup() -> (Cert, Cert, Cert, HistoricalVerifier<Sum6Kes>) {
+    let a = cert(1, 0, &[0, 49, 55]);
+    let b = cert(2, 50, &[0, 5, 63]);
+    let c = cert(3, 120, &[0, 63]);
+    // Supplied out of order: the verifier sorts by start period.
+    let verifier = HistoricalVerifier::new([
+        (c.start, c.vk.clone()),
+        (a.start, a.vk.clone()),
+        (b.start, b.vk.clone()),
+    ]);
+    (a, b, c, verifier)
+}
+
+#[test]
+fn verifies_at_range_boundaries() {
+    let (a, b, c, verifier) = setup();
+    assert_eq!(verifier.len(), 3);
+
+    let cases = [(&a, 0), (&a, 49), (&b, 0), (&b, 63), (&c, 0), (&c, 63)];
+    for (cert, relative) in cases {
+        let kes_period = cert.start + relative;
+        for slot in [
+            kes_period * SLOTS_PER_KES_PERIOD,
+            (kes_period + 1) * SLOTS_PER_KES_PERIOD - 1,
+        ] {
+            verifier
+                .verify_at_slot(
+                    slot,
+                    SLOTS_PER_KES_PERIOD,
+                    MESSAGE,
+                    signature(cert, relative),
+                )
+                .expect("signature verifies at boundary slot");
+        }
+    }
+}
+
+#[test]
+fn rejects_slots_in_gaps_and_after_expiry() {
+    let (_, b, c, verifier) = setup();
+    for kes_period in [114, 119, 184] {
+        assert_eq!(
+            verifier.verify_at_slot(
+                slot_in(kes_period),
+                SLOTS_PER_KES_PERIOD,
+                MESSAGE,
+                signature(&b, 63)
+            ),
+            Err(HistoricalVerifyError::NoKeyForPeriod {
+                slot: slot_in(kes_period),
+                kes_period,
+            })
+        );
+    }
+    assert!(verifier.key_for_period(113).is_some());
+    assert_eq!(
+        verifier.key_for_period(120).map(|(start, _)| start),
+        Some(c.start)
+    );
+}
+
+#[test]
+fn later_certificate_takes_precedence_on_overlap() {
+    let (a, b, _, verifier) = setup();
+    // Period 55 is covered by A (relative 55) and B (relative 5); B wins.
+    assert_eq!(
+        verifier.key_for_period(55).map(|(start, _)| start),
+        Some(50)
+    );
+    verifier
+        .verify_at_slot(slot_in(55), SLOTS_PER_KES_PERIOD, MESSAGE, signature(&b, 5))
+        .expect("B's signature verifies in the overlap");
+
+    let err = verifier
+        .verify_at_slot(
+            slot_in(55),
+            SLOTS_PER_KES_PERIOD,
+            MESSAGE,
+            signature(&a, 55),
+        )
+        .expect_err("A is superseded in the overlap");
+    assert_eq!(
+        err,
+        HistoricalVerifyError::Verification {
+            ocert_start_period: 50,
+            kes_period: 55,
+            source: KesError::VerificationFailed,
+        }
+    );
+
+    // Just before the overlap A is still authoritative.
+    verifier
+        .verify_at_slot(
+            slot_in(49),
+            SLOTS_PER_KES_PERIOD,
+            MESSAGE,
+            signature(&a, 49),
+        )
+        .expect("A verifies before B starts");
+}
+
+#[test]
+fn zero_slots_per_period_is_rejected() {
+    let (a, _, _, verifier) = setup();
+    assert_eq!(
+        verifier.verify_at_slot(0, 0, MESSAGE, signature(&a, 0)),
+        Err(HistoricalVerifyError::ZeroSlotsPerKesPeriod)
+    );
+}
@@ -1,3 +1,4 @@
+use cardano_crypto_class::dsign::DsignAlgorithm;
 use cardano_crypto_class::dsign::ed25519::Ed25519;
 use cardano_crypto_class::kes::compact_single::OptimizedKesSignature;
 use cardano_crypto_class::kes::{
@@ -151,3 +152,41 @@ fn compact_single_kes_embedded_vk_matches_derived() {
 
     CompactSingleKes::<Ed25519>::forget_signing_key_kes(signing_key);
 }
+
+#[test]
+fn compact_sum_signature_rejects_invalid_embedded_vks() {
+    let seed = vec![8u8; CompactSum2Kes::SEED_SIZE];
+    let signing_key =
+        CompactSum2Kes::gen_key_kes_from_seed_bytes(&seed).expect("compact sum signing key");
+    let signature =
+        CompactSum2Kes::sign_kes(&(), 0, b"compact-sum", &signing_key).expect("signing");
+    let raw = CompactSum2Kes::raw_serialize_signature_kes(&signature);
+    CompactSum2Kes::forget_signing_key_kes(signing_key);
+
+    // DSIGN signature || leaf vk || level-1 off-path vk || level-2 off-path hash
+    let sig_len = <Ed25519 as DsignAlgorithm>::SIGNATURE_SIZE;
+    let vk_len = <Ed25519 as DsignAlgorithm>::VERIFICATION_KEY_SIZE;
+    let invalid_vk = (0..=u8::MAX)
+        .map(|low| {
+            let mut bytes = [0u8; 32];
+            bytes[0] = low;
+            bytes
+        })
+        .find(|bytes| Ed25519::raw_deserialize_verification_key(bytes).is_none())
+        .expect("some small y is off the curve");
+
+    for offset in [sig_len, sig_len + vk_len] {
+        let mut corrupted = raw.clone();
+        corrupted[offset..offset + vk_len].copy_from_slice(&invalid_vk);
+        assert!(
+            CompactSum2Kes::raw_deserialize_signature_kes(&corrupted).is_none(),
+            "off-curve vk at byte {offset} must be rejected"
+        );
+    }
+
+    // The top-level off-path key is a hash, so any bytes decode.
+    let mut rehashed = raw.clone();
+    let hash_start = raw.len() - 32;
+    rehashed[hash_start..].copy_from_slice(&invalid_vk);
+    assert!(CompactSum2Kes::raw_deserialize_signature_kes(&rehashed).is_some());
+}
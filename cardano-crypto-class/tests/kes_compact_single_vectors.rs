@@ -95,6 +95,18 @@ fn compact_single_kes_vectors_match_generated_data() {
         let deserialised =
             CompactSingleKes::<Ed25519>::raw_deserialize_signature_kes(&raw_signature)
                 .expect("compact single signature deserialise");
+        assert_eq!(
+            Ed25519::raw_serialize_signature(deserialised.dsign_signature()),
+            dsign_sig,
+            "dsign_signature mismatch for {}",
+            vector.test_name
+        );
+        assert_eq!(
+            Ed25519::raw_serialize_verification_key(deserialised.embedded_verification_key()),
+            embedded_vk,
+            "embedded_verification_key mismatch for {}",
+            vector.test_name
+        );
         CompactSingleKes::<Ed25519>::verify_kes(
             &(),
             &verification_key,
@@ -121,6 +133,43 @@ fn compact_single_kes_vectors_match_generated_data() {
     }
 }
 
+#[test]
+fn compact_single_signature_rejects_invalid_embedded_vk() {
+    let fixture = kes::get("compact_single_kes_test_vectors.json")
+        .expect("embedded compact single KES vectors");
+    let parsed: CompactSingleKesVectors =
+        serde_json::from_str(fixture).expect("valid compact single KES JSON");
+    let signature_len = <Ed25519 as DsignAlgorithm>::SIGNATURE_SIZE;
+    let invalid_vk = invalid_ed25519_point();
+
+    for vector in parsed.vectors {
+        let mut raw_signature = decode_hex(&vector.expected.raw_signature);
+        assert!(
+            CompactSingleKes::<Ed25519>::raw_deserialize_signature_kes(&raw_signature).is_some(),
+            "{} must decode",
+            vector.test_name
+        );
+        raw_signature[signature_len..].copy_from_slice(&invalid_vk);
+        assert!(
+            CompactSingleKes::<Ed25519>::raw_deserialize_signature_kes(&raw_signature).is_none(),
+            "{} with an off-curve embedded vk must be rejected",
+            vector.test_name
+        );
+    }
+}
+
+/// The first 32-byte encoding, by its low byte, that is not an Ed25519 point.
+fn invalid_ed25519_point() -> [u8; 32] {
+    (0..=u8::MAX)
+        .map(|low| {
+            let mut bytes = [0u8; 32];
+            bytes[0] = low;
+            bytes
+        })
+        .find(|bytes| Ed25519::raw_deserialize_verification_key(bytes).is_none())
+        .expect("some small y is off the curve")
+}
+
 fn decode_seed(hex_seed: &str, expected_len: usize) -> Vec<u8> {
     let bytes = decode_hex(hex_seed);
     assert_eq!(bytes.len(), expected_len, "seed must fit expected length");
@@ -3,10 +3,16 @@
 //! This test verifies that KES signing keys can be serialized/deserialized using
 //! DirectSerialise. For SingleKES and CompactSingleKES, the SigningKey type is
 //! D::MLockedSigningKey which already implements DirectSerialise (e.g., Ed25519MLockedSigningKey).
+//! SumKES and CompactSumKES keys are checked at several evolution states.
 
-use cardano_crypto_class::direct_serialise::{DirectDeserialise, DirectSerialise};
+use cardano_crypto_class::direct_serialise::{
+    DirectDeserialise, DirectSerialise, direct_deserialise_from_checked,
+    direct_serialise_to_checked,
+};
 use cardano_crypto_class::dsign::ed25519::Ed25519;
-use cardano_crypto_class::kes::{CompactSingleKes, KesAlgorithm, SingleKes};
+use cardano_crypto_class::kes::{
+    CompactSingleKes, CompactSum6Kes, KesAlgorithm, SingleKes, Sum6Kes,
+};
 
 type SingleKesEd25519 = SingleKes<Ed25519>;
 type CompactSingleKesEd25519 = CompactSingleKes<Ed25519>;
@@ -181,3 +187,121 @@ fn test_multiple_keys_independent() {
     SingleKesEd25519::forget_signing_key_kes(sk1);
     SingleKesEd25519::forget_signing_key_kes(sk2);
 }
+
+/// Periods covering the first key, both sides of the top-level split, and the
+/// last period.
+const SNAPSHOT_PERIODS: [u64; 6] = [0, 1, 31, 32, 33, 63];
+
+fn snapshot<K>(sk: &K::SigningKey) -> Vec<u8>
+where
+    K: KesAlgorithm,
+    K::SigningKey: DirectSerialise,
+{
+    let mut bytes = vec![0u8; K::SIGNING_KEY_SIZE];
+    direct_serialise_to_checked(
+        |offset, chunk| {
+            bytes[offset..offset + chunk.len()].copy_from_slice(chunk);
+            Ok(())
+        },
+        K::SIGNING_KEY_SIZE,
+        sk,
+    )
+    .expect("direct format is exactly SIGNING_KEY_SIZE bytes");
+    bytes
+}
+
+fn restore<K>(bytes: &[u8]) -> K::SigningKey
+where
+    K: KesAlgorithm,
+    K::SigningKey: DirectDeserialise,
+{
+    direct_deserialise_from_checked(
+        |offset, dst| {
+            dst.copy_from_slice(&bytes[offset..offset + dst.len()]);
+            Ok(())
+        },
+        K::SIGNING_KEY_SIZE,
+    )
+    .expect("restore snapshot")
+}
+
+fn sign<K: KesAlgorithm<Context = ()>>(period: u64, sk: &K::SigningKey) -> Vec<u8> {
+    let signature = K::sign_kes(&(), period, b"snapshot", sk).expect("sign");
+    K::raw_serialize_signature_kes(&signature)
+}
+
+/// Snapshot the key at each period in [`SNAPSHOT_PERIODS`], restore it, and
+/// check that the original and restored keys sign identically both now and
+/// after evolving once more.
+fn check_snapshots<K>(seed: u8)
+where
+    K: KesAlgorithm<Context = ()>,
+    K::SigningKey: DirectSerialise + DirectDeserialise,
+{
+    let mut sk = K::gen_key_kes_from_seed_bytes(&vec![seed; K::SEED_SIZE]).expect("keygen");
+    let mut period = 0;
+    for target in SNAPSHOT_PERIODS {
+        while period < target {
+            sk = K::update_kes(&(), sk, period)
+                .expect("update")
+                .expect("not expired");
+            period += 1;
+        }
+
+        let bytes = snapshot::<K>(&sk);
+        assert_eq!(bytes.len(), K::SIGNING_KEY_SIZE);
+        let restored = restore::<K>(&bytes);
+        assert_eq!(snapshot::<K>(&restored), bytes, "period {period}");
+
+        let original_sig = sign::<K>(period, &sk);
+        let restored_sig = sign::<K>(period, &restored);
+        assert_eq!(original_sig, restored_sig, "period {period}");
+
+        match K::update_kes(&(), restored, period).expect("update restored") {
+            Some(next) => {
+                let next_sig = sign::<K>(period + 1, &next);
+                let expected = {
+                    let copy = restore::<K>(&bytes);
+                    let evolved = K::update_kes(&(), copy, period)
+                        .expect("update")
+                        .expect("not expired");
+                    let sig = sign::<K>(period + 1, &evolved);
+                    K::forget_signing_key_kes(evolved);
+                    sig
+                };
+                assert_eq!(next_sig, expected, "period {}", period + 1);
+                K::forget_signing_key_kes(next);
+            },
+            None => assert_eq!(period + 1, K::total_periods()),
+        }
+    }
+    K::forget_signing_key_kes(sk);
+}
+
+#[test]
+fn sum6_snapshots_round_trip_at_several_periods() {
+    check_snapshots::<Sum6Kes>(6);
+}
+
+#[test]
+fn compact_sum6_snapshots_round_trip_at_several_periods() {
+    check_snapshots::<CompactSum6Kes>(66);
+}
+
+#[cfg(feature = "tooling")]
+#[test]
+fn direct_format_matches_raw_signing_key_layout() {
+    use cardano_crypto_class::kes::UnsoundKesAlgorithm;
+
+    let mut sk = Sum6Kes::gen_key_kes_from_seed_bytes(&[9u8; 32]).expect("keygen");
+    for period in 0..40 {
+        if SNAPSHOT_PERIODS.contains(&period) {
+            let raw = Sum6Kes::raw_serialize_signing_key_kes(&sk).expect("raw");
+            assert_eq!(snapshot::<Sum6Kes>(&sk), *raw, "period {period}");
+        }
+        sk = Sum6Kes::update_kes(&(), sk, period)
+            .expect("update")
+            .expect("not expired");
+    }
+    Sum6Kes::forget_signing_key_kes(sk);
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "tooling")]
+
+use cardano_crypto_class::kes::dry_run::{can_sign_at_period, clone_for_dry_run, dry_run_sign};
+use cardano_crypto_class::kes::{CompactSum3Kes, KesAlgorithm, Sum6Kes, UnsoundKesAlgorithm};
+
+const MESSAGE: &[u8] = b"dry-run probe";
+
+fn sum6_key_at_period(current: u64) -> <Sum6Kes as KesAlgorithm>::SigningKey {
+    let seed = vec![0x42; Sum6Kes::SEED_SIZE];
+    let mut sk = Sum6Kes::gen_key_kes_from_seed_bytes(&seed).expect("signing key");
+    for period in 0..current {
+        sk = Sum6Kes::update_kes(&(), sk, period)
+            .expect("update succeeds")
+            .expect("key not expired");
+    }
+    sk
+}
+
+#[test]
+fn sum6_dry_run_leaves_original_key_untouched() {
+    let current = 10;
+    let sk = sum6_key_at_period(current);
+    let vk = Sum6Kes::derive_verification_key(&sk).expect("verification key");
+    let before = Sum6Kes::sign_kes(&(), current, MESSAGE, &sk).expect("sign before dry run");
+
+    for (target, expected) in [(10, true), (37, true), (63, true), (64, false)] {
+        let copy = clone_for_dry_run::<Sum6Kes>(&sk).expect("clone");
+        assert_eq!(
+            can_sign_at_period::<Sum6Kes>(&(), copy, current, target).expect("dry run"),
+            expected,
+            "target period {target}",
+        );
+    }
+
+    let probe = dry_run_sign::<Sum6Kes>(&(), &sk, current, 37, MESSAGE)
+        .expect("dry-run signing")
+        .expect("period 37 reachable");
+    Sum6Kes::verify_kes(&(), &vk, 37, MESSAGE, &probe).expect("dry-run signature verifies");
+    assert!(
+        dry_run_sign::<Sum6Kes>(&(), &sk, current, 64, MESSAGE)
+            .expect("dry-run signing")
+            .is_none()
+    );
+
+    let after = Sum6Kes::sign_kes(&(), current, MESSAGE, &sk).expect("sign after dry run");
+    assert_eq!(
+        Sum6Kes::raw_serialize_signature_kes(&after),
+        Sum6Kes::raw_serialize_signature_kes(&before),
+    );
+    Sum6Kes::verify_kes(&(), &vk, current, MESSAGE, &after).expect("original still verifies");
+}
+
+#[test]
+fn dry_run_rejects_past_periods() {
+    let sk = sum6_key_at_period(10);
+    let copy = clone_for_dry_run::<Sum6Kes>(&sk).expect("clone");
+    assert!(!can_sign_at_period::<Sum6Kes>(&(), copy, 10, 9).expect("dry run"));
+}
+
+#[test]
+fn raw_signing_key_round_trips_across_the_subtree_boundary() {
+    let seed = vec![0x17; CompactSum3Kes::SEED_SIZE];
+    let mut sk = CompactSum3Kes::gen_key_kes_from_seed_bytes(&seed).expect("signing key");
+    for period in 0..CompactSum3Kes::total_periods() {
+        let raw = CompactSum3Kes::raw_serialize_signing_key_kes(&sk).expect("serialise");
+        assert_eq!(raw.len(), CompactSum3Kes::SIGNING_KEY_SIZE);
+        // Built in place: a reallocation would have left a copy behind.
+        assert_eq!(raw.capacity(), raw.len());
+        let copy = CompactSum3Kes::raw_deserialize_signing_key_kes(&raw).expect("deserialise");
+        assert_eq!(
+            CompactSum3Kes::raw_serialize_signing_key_kes(&copy).expect("serialise copy"),
+            raw,
+            "period {period}",
+        );
+        let original = CompactSum3Kes::sign_kes(&(), period, MESSAGE, &sk).expect("sign");
+        let cloned = CompactSum3Kes::sign_kes(&(), period, MESSAGE, &copy).expect("sign copy");
+        assert_eq!(
+            CompactSum3Kes::raw_serialize_signature_kes(&cloned),
+            CompactSum3Kes::raw_serialize_signature_kes(&original),
+        );
+        CompactSum3Kes::forget_signing_key_kes(copy);
+
+        match CompactSum3Kes::update_kes(&(), sk, period).expect("update") {
+            Some(next) => sk = next,
+            None => break,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 // Test that KES types are properly exported at the top level
 
 #[test]
+#[allow(deprecated)]
 fn test_kes_exports() {
     use cardano_crypto_class::{
         // Hash algorithms
@@ -0,0 +1,180 @@
+//! `HashedVerKeyKes` must hash its key exactly once, agree with
+//! `hash_verification_key_kes`, and stay correct under concurrent first
+//! access.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use cardano_crypto_class::hash::HashAlgorithm;
+use cardano_crypto_class::kes::{
+    Blake2b224, Blake2b256, CompactSum1Kes, CompactSum2Kes, CompactSum3Kes, CompactSum4Kes,
+    CompactSum5Kes, CompactSum6Kes, CompactSum7Kes, HashedVerKeyKes, KesAlgorithm, Sum1Kes,
+    Sum2Kes, Sum3Kes, Sum4Kes, Sum5Kes, Sum6Kes, Sum7Kes,
+};
+use cardano_test_vectors::kes;
+
+static CALLS: [AtomicUsize; 3] = [const { AtomicUsize::new(0) }; 3];
+
+/// Blake2b-224 that counts how often a digest is computed. Each test uses its
+/// own `SLOT` so parallel tests do not share a counter.
+#[derive(Clone)]
+struct CountingBlake2b224<const SLOT: usize>;
+
+impl<const SLOT: usize> HashAlgorithm for CountingBlake2b224<SLOT> {
+    const OUTPUT_SIZE: usize = Blake2b224::OUTPUT_SIZE;
+    const ALGORITHM_NAME: &'static str = "counting_blake2b_224";
+
+    fn hash(data: &[u8]) -> Vec<u8> {
+        CALLS[SLOT].fetch_add(1, Ordering::SeqCst);
+        Blake2b224::hash(data)
+    }
+}
+
+fn sum6_vk(seed: u8) -> Vec<u8> {
+    let sk = Sum6Kes::gen_key_kes_from_seed_bytes(&[seed; 32]).expect("signing key");
+    let vk = Sum6Kes::derive_verification_key(&sk).expect("verification key");
+    Sum6Kes::forget_signing_key_kes(sk);
+    vk
+}
+
+#[test]
+fn repeated_calls_hash_once() {
+    let vk = sum6_vk(1);
+    let hashed = HashedVerKeyKes::<CountingBlake2b224<0>, Sum6Kes>::new(vk.clone());
+    assert_eq!(
+        CALLS[0].load(Ordering::SeqCst),
+        0,
+        "construction must not hash"
+    );
+
+    let first = hashed.hash().to_vec();
+    for _ in 0..1_000 {
+        assert!(hashed.hash_matches(&first));
+    }
+    assert_eq!(CALLS[0].load(Ordering::SeqCst), 1);
+    assert_eq!(first, Sum6Kes::hash_verification_key_kes::<Blake2b224>(&vk));
+
+    // A clone carries the cached digest along.
+    let clone = hashed.clone();
+    assert_eq!(clone.hash(), first.as_slice());
+    assert_eq!(CALLS[0].load(Ordering::SeqCst), 1);
+    assert_eq!(clone, hashed);
+    assert_eq!(clone.into_verification_key(), vk);
+}
+
+#[test]
+fn concurrent_first_access_hashes_once() {
+    const THREADS: usize = 8;
+
+    let hashed = Arc::new(HashedVerKeyKes::<CountingBlake2b224<1>, Sum6Kes>::new(
+        sum6_vk(2),
+    ));
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let digests: Vec<Vec<u8>> = (0..THREADS)
+        .map(|_| {
+            let hashed = Arc::clone(&hashed);
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                hashed.hash().to_vec()
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|handle| handle.join().expect("thread"))
+        .collect();
+
+    assert_eq!(CALLS[1].load(Ordering::SeqCst), 1);
+    let expected = Sum6Kes::hash_verification_key_kes::<Blake2b224>(hashed.verification_key());
+    assert!(digests.iter().all(|digest| *digest == expected));
+}
+
+#[test]
+fn unused_keys_are_never_hashed() {
+    let hashed = HashedVerKeyKes::<CountingBlake2b224<2>, Sum6Kes>::new(sum6_vk(3));
+    assert_eq!(
+        hashed.verification_key().len(),
+        Sum6Kes::VERIFICATION_KEY_SIZE
+    );
+    drop(hashed);
+    assert_eq!(CALLS[2].load(Ordering::SeqCst), 0);
+}
+
+fn assert_cached_hash_matches<K>(vk_hex: &str)
+where
+    K: KesAlgorithm,
+    K::VerificationKey: Clone,
+{
+    let bytes = hex::decode(vk_hex).expect("verification key hex");
+    let vk = K::raw_deserialize_verification_key_kes(&bytes).expect("verification key");
+
+    let hashed224 = HashedVerKeyKes::<Blake2b224, K>::new(vk.clone());
+    let hashed256 = HashedVerKeyKes::<Blake2b256, K>::new(vk.clone());
+    assert_eq!(
+        hashed224.hash(),
+        K::hash_verification_key_kes::<Blake2b224>(&vk).as_slice()
+    );
+    assert_eq!(
+        hashed256.hash(),
+        K::hash_verification_key_kes::<Blake2b256>(&vk).as_slice()
+    );
+}
+
+fn level_vks(file: &str) -> Vec<(u64, String)> {
+    let fixture = kes::get(file).expect("embedded KES vectors");
+    let parsed: serde_json::Value = serde_json::from_str(fixture).expect("valid KES JSON");
+    let levels = parsed["levels"].as_array().expect("levels");
+    levels
+        .iter()
+        .flat_map(|level| {
+            let number = level["level"].as_u64().expect("level number");
+            level["vectors"]
+                .as_array()
+                .expect("vectors")
+                .iter()
+                .map(move |vector| {
+                    let vk = vector["verification_key"]
+                        .as_str()
+                        .expect("verification_key");
+                    (number, vk.to_owned())
+                })
+        })
+        .collect()
+}
+
+#[test]
+fn cached_hash_matches_embedded_sum_vectors() {
+    let vks = level_vks("sum_kes_test_vectors.json");
+    assert!(!vks.is_empty());
+    for (level, vk) in vks {
+        match level {
+            1 => assert_cached_hash_matches::<Sum1Kes>(&vk),
+            2 => assert_cached_hash_matches::<Sum2Kes>(&vk),
+            3 => assert_cached_hash_matches::<Sum3Kes>(&vk),
+            4 => assert_cached_hash_matches::<Sum4Kes>(&vk),
+            5 => assert_cached_hash_matches::<Sum5Kes>(&vk),
+            6 => assert_cached_hash_matches::<Sum6Kes>(&vk),
+            7 => assert_cached_hash_matches::<Sum7Kes>(&vk),
+            other => unreachable!("unexpected sum level {other}"),
+        }
+    }
+}
+
+#[test]
+fn cached_hash_matches_embedded_compact_sum_vectors() {
+    let vks = level_vks("compact_sum_kes_test_vectors.json");
+    assert!(!vks.is_empty());
+    for (level, vk) in vks {
+        match level {
+            1 => assert_cached_hash_matches::<CompactSum1Kes>(&vk),
+            2 => assert_cached_hash_matches::<CompactSum2Kes>(&vk),
+            3 => assert_cached_hash_matches::<CompactSum3Kes>(&vk),
+            4 => assert_cached_hash_matches::<CompactSum4Kes>(&vk),
+            5 => assert_cached_hash_matches::<CompactSum5Kes>(&vk),
+            6 => assert_cached_hash_matches::<CompactSum6Kes>(&vk),
+            7 => assert_cached_hash_matches::<CompactSum7Kes>(&vk),
+            other => unreachable!("unexpected compact sum level {other}"),
+        }
+    }
+}
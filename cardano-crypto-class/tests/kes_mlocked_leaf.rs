@@ -0,0 +1,72 @@
+//! The Ed25519 leaf of every KES construction keeps its seed in mlocked
+//! memory. These checks pin the leaf signing-key type so a refactor cannot
+//! silently swap in the heap-backed `Ed25519SigningKey`.
+
+use cardano_crypto_class::Ed25519MLockedSigningKey;
+use cardano_crypto_class::dsign::ed25519::Ed25519;
+use cardano_crypto_class::kes::{
+    CompactSingleKes, CompactSum0Kes, CompactSum1Kes, KesAlgorithm, SingleKes, Sum0Kes, Sum1Kes,
+};
+
+fn assert_mlocked_leaf<K>()
+where
+    K: KesAlgorithm<SigningKey = Ed25519MLockedSigningKey>,
+{
+}
+
+#[test]
+fn leaf_signing_keys_are_mlocked() {
+    assert_mlocked_leaf::<SingleKes<Ed25519>>();
+    assert_mlocked_leaf::<CompactSingleKes<Ed25519>>();
+    assert_mlocked_leaf::<Sum0Kes>();
+    assert_mlocked_leaf::<CompactSum0Kes>();
+}
+
+#[test]
+fn mlocked_leaf_signs_like_heap_ed25519() {
+    use cardano_crypto_class::dsign::DsignAlgorithm;
+
+    let seed = [7u8; 32];
+    let message = b"mlocked leaf";
+
+    let heap_key = Ed25519::gen_key_from_seed_bytes(&seed);
+    let heap_sig = Ed25519::sign_bytes(&(), message, &heap_key);
+
+    let leaf_key = SingleKes::<Ed25519>::gen_key_kes_from_seed_bytes(&seed).expect("leaf key");
+    let leaf_sig = SingleKes::<Ed25519>::sign_kes(&(), 0, message, &leaf_key).expect("leaf sign");
+    SingleKes::<Ed25519>::forget_signing_key_kes(leaf_key);
+
+    assert_eq!(
+        Ed25519::raw_serialize_signature(&heap_sig),
+        SingleKes::<Ed25519>::raw_serialize_signature_kes(&leaf_sig)
+    );
+}
+
+#[test]
+fn sum_levels_build_on_mlocked_leaves() {
+    // Sum1 keys carry a level-0 signing key directly; evolving past it must
+    // still produce verifiable signatures from the mlocked right-hand leaf.
+    let seed = [9u8; 32];
+    let key = Sum1Kes::gen_key_kes_from_seed_bytes(&seed).expect("sum1 key");
+    let vk = Sum1Kes::derive_verification_key(&key).expect("sum1 vk");
+    let key = Sum1Kes::update_kes(&(), key, 0)
+        .expect("update")
+        .expect("period 1 available");
+    let sig = Sum1Kes::sign_kes(&(), 1, b"right", &key).expect("sign");
+    Sum1Kes::verify_kes(&(), &vk, 1, b"right", &sig).expect("verify");
+    Sum1Kes::forget_signing_key_kes(key);
+
+    let key = CompactSum1Kes::gen_key_kes_from_seed_bytes(&seed).expect("compact key");
+    CompactSum1Kes::forget_signing_key_kes(key);
+}
+
+#[cfg(feature = "mlocked-metrics")]
+#[test]
+fn forgetting_a_leaf_key_zeroizes_mlocked_memory() {
+    use cardano_crypto_class::mlocked_metrics;
+
+    let key = SingleKes::<Ed25519>::gen_key_kes_from_seed_bytes(&[3u8; 32]).expect("leaf key");
+    let before = mlocked_metrics::snapshot().zeroizations;
+    SingleKes::<Ed25519>::forget_signing_key_kes(key);
+    assert!(mlocked_metrics::snapshot().zeroizations > before);
+}
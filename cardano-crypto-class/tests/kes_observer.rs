@@ -0,0 +1,199 @@
+//! The installed `KesEventObserver` sees exactly one event per top-level
+//! evolution (and, with `kes-sign-events`, per signature), ending with the
+//! expiry event, and nothing once it is cleared.
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use cardano_crypto_class::dsign::ed25519::Ed25519;
+use cardano_crypto_class::kes::{
+    CompactSum3Kes, KesAlgorithm, KesEventObserver, Period, SingleKes, Sum3Kes, clear_kes_observer,
+    set_kes_observer,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Event {
+    Update {
+        algorithm: &'static str,
+        from: Period,
+        to: Period,
+    },
+    Expiry {
+        algorithm: &'static str,
+        final_period: Period,
+    },
+    #[cfg(feature = "kes-sign-events")]
+    Sign {
+        algorithm: &'static str,
+        period: Period,
+    },
+}
+
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<Event>>,
+    durations: Mutex<Vec<Duration>>,
+}
+
+impl Recorder {
+    fn push(&self, event: Event) {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(event);
+    }
+
+    fn take(&self) -> Vec<Event> {
+        std::mem::take(&mut *self.events.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl KesEventObserver for Recorder {
+    fn on_update(&self, algorithm: &'static str, from: Period, to: Period, duration: Duration) {
+        self.durations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(duration);
+        self.push(Event::Update {
+            algorithm,
+            from,
+            to,
+        });
+    }
+
+    fn on_expiry(&self, algorithm: &'static str, final_period: Period) {
+        self.push(Event::Expiry {
+            algorithm,
+            final_period,
+        });
+    }
+
+    #[cfg(feature = "kes-sign-events")]
+    fn on_sign(&self, algorithm: &'static str, period: Period) {
+        self.push(Event::Sign { algorithm, period });
+    }
+}
+
+/// The observer is process-wide, so tests installing one run one at a time.
+fn exclusive() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn install() -> Arc<Recorder> {
+    let recorder = Arc::new(Recorder::default());
+    set_kes_observer(recorder.clone());
+    recorder
+}
+
+/// Sign at every period and evolve after each one, until the key expires.
+fn run_to_expiry<K: KesAlgorithm<Context = ()>>() {
+    let seed = vec![0x11; K::SEED_SIZE];
+    let mut sk = Some(K::gen_key_kes_from_seed_bytes(&seed).expect("keygen"));
+    for period in 0..K::total_periods() {
+        let current = sk.take().expect("key expired early");
+        K::sign_kes(&(), period, b"header", &current).expect("sign");
+        sk = K::update_kes(&(), current, period).expect("update");
+    }
+    assert!(sk.is_none(), "key did not expire after its last period");
+}
+
+fn expected_events<K: KesAlgorithm>() -> Vec<Event> {
+    let algorithm = K::ALGORITHM_NAME;
+    let last = K::total_periods() - 1;
+    let mut events = Vec::new();
+    for period in 0..=last {
+        #[cfg(feature = "kes-sign-events")]
+        events.push(Event::Sign { algorithm, period });
+        events.push(if period == last {
+            Event::Expiry {
+                algorithm,
+                final_period: last,
+            }
+        } else {
+            Event::Update {
+                algorithm,
+                from: period,
+                to: period + 1,
+            }
+        });
+    }
+    events
+}
+
+#[test]
+fn sum3_reports_every_evolution_and_the_expiry() {
+    let _guard = exclusive();
+    let recorder = install();
+    run_to_expiry::<Sum3Kes>();
+    clear_kes_observer();
+
+    let events = recorder.take();
+    assert_eq!(events, expected_events::<Sum3Kes>());
+    assert_eq!(
+        events.last(),
+        Some(&Event::Expiry {
+            algorithm: "ed25519",
+            final_period: 7,
+        })
+    );
+    assert_eq!(
+        recorder
+            .durations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len(),
+        7
+    );
+}
+
+#[test]
+fn compact_sum_and_single_report_like_sum() {
+    let _guard = exclusive();
+    let recorder = install();
+    run_to_expiry::<CompactSum3Kes>();
+    assert_eq!(recorder.take(), expected_events::<CompactSum3Kes>());
+
+    run_to_expiry::<SingleKes<Ed25519>>();
+    clear_kes_observer();
+    assert_eq!(recorder.take(), expected_events::<SingleKes<Ed25519>>());
+}
+
+#[test]
+fn nothing_is_reported_after_clearing() {
+    let _guard = exclusive();
+    let recorder = install();
+    clear_kes_observer();
+    run_to_expiry::<Sum3Kes>();
+    assert!(recorder.take().is_empty());
+}
+
+#[test]
+fn failed_operations_are_not_reported() {
+    let _guard = exclusive();
+    let seed = vec![0x22; Sum3Kes::SEED_SIZE];
+    let sk = Sum3Kes::gen_key_kes_from_seed_bytes(&seed).expect("keygen");
+
+    let recorder = install();
+    assert!(Sum3Kes::sign_kes(&(), 8, b"header", &sk).is_err());
+    clear_kes_observer();
+    Sum3Kes::forget_signing_key_kes(sk);
+    assert!(recorder.take().is_empty());
+}
+
+#[cfg(feature = "tooling")]
+#[test]
+fn dry_runs_are_not_reported() {
+    use cardano_crypto_class::kes::dry_run::{can_sign_at_period, clone_for_dry_run};
+
+    let _guard = exclusive();
+    let seed = vec![0x33; Sum3Kes::SEED_SIZE];
+    let sk = Sum3Kes::gen_key_kes_from_seed_bytes(&seed).expect("keygen");
+
+    let recorder = install();
+    let copy = clone_for_dry_run::<Sum3Kes>(&sk).expect("copy");
+    assert!(can_sign_at_period::<Sum3Kes>(&(), copy, 0, 7).expect("dry run"));
+    clear_kes_observer();
+    Sum3Kes::forget_signing_key_kes(sk);
+    assert!(recorder.take().is_empty());
+}
@@ -0,0 +1,132 @@
+//! Periods at or beyond `total_periods()`, up to `u64::MAX`, must be rejected
+//! by sign, verify and update with `PeriodOutOfRange` naming the root's
+//! bounds: never a panic, and never a wrap into a valid subtree period.
+
+use cardano_crypto_class::dsign::ed25519::Ed25519;
+use cardano_crypto_class::kes::{
+    CompactSingleKes, CompactSum1Kes, CompactSum3Kes, CompactSum7Kes, KesAlgorithm, KesError,
+    KesMError, Period, SingleKes, Sum1Kes, Sum3Kes, Sum7Kes,
+};
+use proptest::prelude::*;
+
+fn fresh_key<K: KesAlgorithm<Context = ()>>() -> K::SigningKey {
+    K::gen_key_kes_from_seed_bytes(&vec![7u8; K::SEED_SIZE]).expect("signing key")
+}
+
+fn assert_out_of_range<K: KesAlgorithm>(result: Result<(), KesError>, period: Period) {
+    assert_eq!(
+        result,
+        Err(KesError::PeriodOutOfRange {
+            period,
+            max_period: K::total_periods(),
+        }),
+        "{} at period {period}",
+        K::ALGORITHM_NAME
+    );
+}
+
+fn assert_out_of_range_m<K: KesAlgorithm, T>(result: Result<T, KesMError>, period: Period) {
+    match result {
+        Err(KesMError::Kes(err)) => assert_out_of_range::<K>(Err(err), period),
+        Err(err) => unreachable!("period {period}: unexpected error {err:?}"),
+        Ok(_) => unreachable!("period {period} was accepted"),
+    }
+}
+
+/// Sign, verify and update at `period` with a signature that is valid for
+/// period 0, requiring all three to reject it.
+fn check_rejected<K: KesAlgorithm<Context = ()>>(period: Period) {
+    let signing_key = fresh_key::<K>();
+    let verification_key = K::derive_verification_key(&signing_key).expect("verification key");
+    let signature = K::sign_kes(&(), 0, b"period-range", &signing_key).expect("sign at 0");
+
+    assert_out_of_range_m::<K, _>(
+        K::sign_kes(&(), period, b"period-range", &signing_key),
+        period,
+    );
+    assert_out_of_range::<K>(
+        K::verify_kes(&(), &verification_key, period, b"period-range", &signature),
+        period,
+    );
+    assert_out_of_range_m::<K, _>(K::update_kes(&(), signing_key, period), period);
+}
+
+/// The last period still works; the three past it do not.
+fn check_boundaries<K: KesAlgorithm<Context = ()>>() {
+    let total = K::total_periods();
+    let mut signing_key = fresh_key::<K>();
+    let verification_key = K::derive_verification_key(&signing_key).expect("verification key");
+    for period in K::periods().take_while(|&period| period + 1 < total) {
+        signing_key = K::update_kes(&(), signing_key, period)
+            .expect("update")
+            .expect("not expired before the last period");
+    }
+
+    let last = total - 1;
+    let signature = K::sign_kes(&(), last, b"last", &signing_key).expect("sign at last period");
+    K::verify_kes(&(), &verification_key, last, b"last", &signature)
+        .expect("verify at last period");
+    assert!(
+        K::update_kes(&(), signing_key, last)
+            .expect("update at last period")
+            .is_none(),
+        "key must expire after its last period"
+    );
+
+    for period in [total, total + 1, Period::MAX] {
+        check_rejected::<K>(period);
+    }
+}
+
+#[test]
+fn period_range_covers_every_period() {
+    assert_eq!(SingleKes::<Ed25519>::period_range(), 0..1);
+    assert_eq!(CompactSingleKes::<Ed25519>::period_range(), 0..1);
+    assert_eq!(Sum3Kes::period_range(), 0..8);
+    assert_eq!(CompactSum7Kes::period_range(), 0..128);
+    assert_eq!(Sum7Kes::periods().count(), 128);
+    assert_eq!(Sum7Kes::periods().next_back(), Some(127));
+}
+
+#[test]
+fn single_rejects_periods_past_zero() {
+    check_boundaries::<SingleKes<Ed25519>>();
+    check_boundaries::<CompactSingleKes<Ed25519>>();
+}
+
+#[test]
+fn sum_levels_reject_periods_past_the_end() {
+    check_boundaries::<Sum1Kes>();
+    check_boundaries::<Sum3Kes>();
+    check_boundaries::<Sum7Kes>();
+}
+
+#[test]
+fn compact_sum_levels_reject_periods_past_the_end() {
+    check_boundaries::<CompactSum1Kes>();
+    check_boundaries::<CompactSum3Kes>();
+    check_boundaries::<CompactSum7Kes>();
+}
+
+#[test]
+fn periods_are_checked_against_the_root_bounds() {
+    // Without the root check these reach a child with the root's half
+    // subtracted, where they are rejected against the child's bounds, or
+    // overflow `period + 1` in `update_kes`.
+    for period in [Period::MAX - 63, 1 << 63, (1 << 63) + 1, 130, 256] {
+        check_rejected::<Sum7Kes>(period);
+        check_rejected::<CompactSum7Kes>(period);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn proptest_out_of_range_periods_are_rejected(
+        period in Sum3Kes::total_periods()..=Period::MAX
+    ) {
+        check_rejected::<Sum3Kes>(period);
+        check_rejected::<CompactSum3Kes>(period);
+    }
+}
@@ -0,0 +1,79 @@
+use cardano_crypto_class::kes::{
+    CompactSum1Kes, CompactSum3Kes, ConversionError, KesAlgorithm, Sum1Kes, Sum3Kes,
+    compact_to_sum_signature, sum_to_compact_signature,
+};
+
+#[test]
+fn compact_sum3_signatures_round_trip_at_every_period() {
+    let seed = vec![0x5a; CompactSum3Kes::SEED_SIZE];
+    let mut compact_key =
+        Some(CompactSum3Kes::gen_key_kes_from_seed_bytes(&seed).expect("compact signing key"));
+    let mut sum_key = Some(Sum3Kes::gen_key_kes_from_seed_bytes(&seed).expect("sum signing key"));
+    let vk = CompactSum3Kes::derive_verification_key(compact_key.as_ref().expect("compact key"))
+        .expect("compact verification key");
+
+    for period in 0..CompactSum3Kes::total_periods() {
+        let compact_active = compact_key.take().expect("compact key for period");
+        let sum_active = sum_key.take().expect("sum key for period");
+        let message = format!("conversion-period-{period}").into_bytes();
+
+        let compact_sig = CompactSum3Kes::sign_kes(&(), period, &message, &compact_active)
+            .expect("compact signing succeeds");
+        let native_sum_sig =
+            Sum3Kes::sign_kes(&(), period, &message, &sum_active).expect("sum signing succeeds");
+
+        let sum_sig = compact_to_sum_signature::<CompactSum3Kes>(&compact_sig, &vk, period)
+            .expect("compact signature converts");
+        Sum3Kes::verify_kes(&(), &vk, period, &message, &sum_sig)
+            .expect("converted signature verifies as Sum");
+        assert_eq!(
+            Sum3Kes::raw_serialize_signature_kes(&sum_sig),
+            Sum3Kes::raw_serialize_signature_kes(&native_sum_sig),
+            "period {period}: conversion must match the natively produced Sum signature",
+        );
+
+        let compact_back = sum_to_compact_signature::<CompactSum3Kes>(&sum_sig, period);
+        assert_eq!(
+            CompactSum3Kes::raw_serialize_signature_kes(&compact_back),
+            CompactSum3Kes::raw_serialize_signature_kes(&compact_sig),
+            "period {period}: compact -> sum -> compact must be the identity",
+        );
+
+        let compact_from_native =
+            sum_to_compact_signature::<CompactSum3Kes>(&native_sum_sig, period);
+        CompactSum3Kes::verify_kes(&(), &vk, period, &message, &compact_from_native)
+            .expect("converted signature verifies as CompactSum");
+
+        compact_key = CompactSum3Kes::update_kes(&(), compact_active, period)
+            .expect("compact update succeeds");
+        sum_key = Sum3Kes::update_kes(&(), sum_active, period).expect("sum update succeeds");
+    }
+}
+
+#[test]
+fn compact_to_sum_rejects_wrong_key_and_period() {
+    let seed = vec![0x11; CompactSum1Kes::SEED_SIZE];
+    let sk = CompactSum1Kes::gen_key_kes_from_seed_bytes(&seed).expect("signing key");
+    let vk = CompactSum1Kes::derive_verification_key(&sk).expect("verification key");
+    let signature = CompactSum1Kes::sign_kes(&(), 0, b"msg", &sk).expect("signing succeeds");
+
+    let other_seed = vec![0x22; CompactSum1Kes::SEED_SIZE];
+    let other_sk = CompactSum1Kes::gen_key_kes_from_seed_bytes(&other_seed).expect("signing key");
+    let other_vk = CompactSum1Kes::derive_verification_key(&other_sk).expect("verification key");
+
+    assert_eq!(
+        compact_to_sum_signature::<CompactSum1Kes>(&signature, &other_vk, 0).err(),
+        Some(ConversionError::VerificationKeyMismatch)
+    );
+    assert_eq!(
+        compact_to_sum_signature::<CompactSum1Kes>(&signature, &vk, 2).err(),
+        Some(ConversionError::PeriodOutOfRange {
+            period: 2,
+            max_period: 2
+        })
+    );
+
+    let sum = compact_to_sum_signature::<CompactSum1Kes>(&signature, &vk, 0)
+        .expect("matching key converts");
+    Sum1Kes::verify_kes(&(), &vk, 0, b"msg", &sum).expect("converted signature verifies");
+}
@@ -0,0 +1,222 @@
+//! `verify_kes_explain` must agree with `verify_kes` and name the step that
+//! rejected a tampered signature, for every KES family.
+
+use cardano_crypto_class::dsign::ed25519::Ed25519;
+use cardano_crypto_class::kes::{
+    CompactSingleKes, CompactSum2Kes, CompactSum6Kes, KesAlgorithm, KesError, KesVerifyFailure,
+    KesVerifyStage, Period, SingleKes, Sum2Kes, Sum6Kes,
+};
+
+const MESSAGE: &[u8] = b"block header body";
+
+/// Verification key and signature of `MESSAGE` at `period`.
+fn signed_at<A: KesAlgorithm<Context = ()>>(
+    seed: u8,
+    period: Period,
+) -> (A::VerificationKey, Vec<u8>) {
+    let mut sk = A::gen_key_kes_from_seed_bytes(&vec![seed; A::SEED_SIZE]).expect("signing key");
+    let vk = A::derive_verification_key(&sk).expect("verification key");
+    for current in 0..period {
+        sk = A::update_kes(&(), sk, current)
+            .expect("update")
+            .expect("key not expired");
+    }
+    let sig = A::sign_kes(&(), period, MESSAGE, &sk).expect("sign");
+    A::forget_signing_key_kes(sk);
+    (vk, A::raw_serialize_signature_kes(&sig))
+}
+
+fn explain<A: KesAlgorithm<Context = ()>>(
+    vk: &A::VerificationKey,
+    period: Period,
+    sig: &[u8],
+) -> Result<(), KesVerifyFailure> {
+    let sig = A::raw_deserialize_signature_kes(sig).expect("signature decodes");
+    let explained = A::verify_kes_explain(&(), vk, period, MESSAGE, &sig);
+    assert_eq!(
+        explained.is_ok(),
+        A::verify_kes(&(), vk, period, MESSAGE, &sig).is_ok(),
+        "explain and verify disagree"
+    );
+    explained
+}
+
+fn with_byte_flipped(sig: &[u8], index: usize) -> Vec<u8> {
+    let mut sig = sig.to_vec();
+    sig[index] ^= 0x01;
+    sig
+}
+
+fn with_range_replaced(sig: &[u8], start: usize, replacement: &[u8]) -> Vec<u8> {
+    let mut sig = sig.to_vec();
+    sig[start..start + replacement.len()].copy_from_slice(replacement);
+    sig
+}
+
+#[test]
+fn valid_signatures_explain_ok_in_every_period() {
+    for period in 0..Sum2Kes::total_periods() {
+        let (vk, sig) = signed_at::<Sum2Kes>(1, period);
+        explain::<Sum2Kes>(&vk, period, &sig).expect("sum verifies");
+        let (vk, sig) = signed_at::<CompactSum2Kes>(1, period);
+        explain::<CompactSum2Kes>(&vk, period, &sig).expect("compact sum verifies");
+    }
+}
+
+#[test]
+fn single_leaf_signature_and_period() {
+    let (vk, sig) = signed_at::<SingleKes<Ed25519>>(2, 0);
+    let failure =
+        explain::<SingleKes<Ed25519>>(&vk, 0, &with_byte_flipped(&sig, 0)).expect_err("tampered");
+    assert_eq!(failure.stage, KesVerifyStage::LeafSignature);
+    assert_eq!((failure.period, failure.depth), (0, 0));
+
+    let failure = explain::<SingleKes<Ed25519>>(&vk, 1, &sig).expect_err("wrong period");
+    assert_eq!(
+        failure.stage,
+        KesVerifyStage::PeriodRouting {
+            signed_period: Some(0)
+        }
+    );
+}
+
+#[test]
+fn compact_single_distinguishes_embedded_key() {
+    let (vk, sig) = signed_at::<CompactSingleKes<Ed25519>>(3, 0);
+    let (other_vk, _) = signed_at::<CompactSingleKes<Ed25519>>(4, 0);
+    let other_vk = CompactSingleKes::<Ed25519>::raw_serialize_verification_key_kes(&other_vk);
+
+    let failure = explain::<CompactSingleKes<Ed25519>>(&vk, 0, &with_byte_flipped(&sig, 0))
+        .expect_err("tampered signature");
+    assert_eq!(failure.stage, KesVerifyStage::LeafSignature);
+
+    let failure =
+        explain::<CompactSingleKes<Ed25519>>(&vk, 0, &with_range_replaced(&sig, 64, &other_vk))
+            .expect_err("foreign embedded key");
+    assert_eq!(failure.stage, KesVerifyStage::EmbeddedKeyMismatch);
+}
+
+#[test]
+fn sum_tampered_leaf_signature_is_reported_at_the_leaf() {
+    let (vk, sig) = signed_at::<Sum6Kes>(5, 0);
+    let failure = explain::<Sum6Kes>(&vk, 0, &with_byte_flipped(&sig, 0)).expect_err("tampered");
+    assert_eq!(failure.stage, KesVerifyStage::LeafSignature);
+    assert_eq!((failure.period, failure.depth), (0, 6));
+    assert_eq!(failure.code(), "kes.verify.leaf_signature");
+}
+
+#[test]
+fn sum_root_hash_mismatch_carries_both_hashes() {
+    let (vk, sig) = signed_at::<Sum6Kes>(6, 0);
+    let mut wrong_vk = vk.clone();
+    wrong_vk[0] ^= 0x01;
+    let failure = explain::<Sum6Kes>(&wrong_vk, 0, &sig).expect_err("wrong key");
+    assert_eq!(
+        failure.stage,
+        KesVerifyStage::RootHashMismatch {
+            expected_hex: hex::encode(&wrong_vk),
+            actual_hex: hex::encode(&vk),
+        }
+    );
+    assert_eq!(failure.depth, 0);
+}
+
+#[test]
+fn sum_tampered_inner_key_is_reported_below_the_root() {
+    // Sum6: the depth-1 subtree keys sit at 320..384 and the root pair at
+    // 384..448. Period 0 routes left, so the expected depth-1 key is vk0.
+    let (vk, sig) = signed_at::<Sum6Kes>(7, 0);
+    let tampered = with_byte_flipped(&sig, 320);
+    let failure = explain::<Sum6Kes>(&vk, 0, &tampered).expect_err("tampered subtree key");
+    let KesVerifyStage::RootHashMismatch { expected_hex, .. } = &failure.stage else {
+        unreachable!("unexpected stage {:?}", failure.stage);
+    };
+    assert_eq!(expected_hex, &hex::encode(&sig[384..416]));
+    assert_eq!((failure.period, failure.depth), (0, 1));
+}
+
+#[test]
+fn sum_wrong_period_reports_routing() {
+    let (vk, sig) = signed_at::<Sum2Kes>(8, 3);
+    for period in [0, 1, 2] {
+        let failure = explain::<Sum2Kes>(&vk, period, &sig).expect_err("wrong period");
+        assert_eq!(
+            failure.stage,
+            KesVerifyStage::PeriodRouting {
+                signed_period: Some(3)
+            }
+        );
+        assert_eq!((failure.period, failure.depth), (period, 0));
+    }
+
+    let failure = explain::<Sum2Kes>(&vk, 4, &with_byte_flipped(&sig, 0)).expect_err("range");
+    assert_eq!(
+        failure.stage,
+        KesVerifyStage::PeriodRouting {
+            signed_period: None
+        }
+    );
+}
+
+#[test]
+fn compact_sum_tampered_leaf_signature() {
+    let (vk, sig) = signed_at::<CompactSum6Kes>(9, 0);
+    let failure =
+        explain::<CompactSum6Kes>(&vk, 0, &with_byte_flipped(&sig, 0)).expect_err("tampered");
+    assert_eq!(failure.stage, KesVerifyStage::LeafSignature);
+    assert_eq!(failure.depth, 6);
+}
+
+#[test]
+fn compact_sum_tampered_off_path_key_is_a_root_mismatch() {
+    // CompactSum6: leaf signature (64) and embedded leaf key (32), then one
+    // off-path key per level; the root's is last.
+    let (vk, sig) = signed_at::<CompactSum6Kes>(10, 0);
+    let last = sig.len() - 1;
+    let failure =
+        explain::<CompactSum6Kes>(&vk, 0, &with_byte_flipped(&sig, last)).expect_err("tampered");
+    let KesVerifyStage::RootHashMismatch {
+        expected_hex,
+        actual_hex,
+    } = &failure.stage
+    else {
+        unreachable!("unexpected stage {:?}", failure.stage);
+    };
+    assert_eq!(expected_hex, &hex::encode(&vk));
+    assert_ne!(actual_hex, expected_hex);
+    assert_eq!(failure.depth, 0);
+}
+
+#[test]
+fn compact_sum_corrupt_embedded_leaf_key() {
+    let (vk, sig) = signed_at::<CompactSum6Kes>(11, 0);
+    let (foreign, _) = signed_at::<CompactSingleKes<Ed25519>>(12, 0);
+    let foreign = CompactSingleKes::<Ed25519>::raw_serialize_verification_key_kes(&foreign);
+    let failure = explain::<CompactSum6Kes>(&vk, 0, &with_range_replaced(&sig, 64, &foreign))
+        .expect_err("foreign leaf key");
+    assert_eq!(failure.stage, KesVerifyStage::EmbeddedKeyMismatch);
+    assert_eq!(failure.depth, 0);
+}
+
+#[test]
+fn compact_sum_wrong_period_reports_routing() {
+    let (vk, sig) = signed_at::<CompactSum2Kes>(13, 2);
+    let failure = explain::<CompactSum2Kes>(&vk, 1, &sig).expect_err("wrong period");
+    assert_eq!(
+        failure.stage,
+        KesVerifyStage::PeriodRouting {
+            signed_period: Some(2)
+        }
+    );
+}
+
+#[test]
+fn failures_convert_to_the_opaque_error() {
+    let (vk, sig) = signed_at::<Sum2Kes>(14, 0);
+    let failure = explain::<Sum2Kes>(&vk, 1, &sig).expect_err("wrong period");
+    assert_eq!(
+        failure.to_string(),
+        "KES verification failed for period 1 at depth 0: signature is for period 0"
+    );
+    assert_eq!(KesError::from(failure), KesError::VerificationFailed);
+}
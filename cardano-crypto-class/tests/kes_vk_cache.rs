@@ -0,0 +1,64 @@
+//! Sum and CompactSum signing keys cache their root verification key. The
+//! cached value must match the root recomputed from signatures at every
+//! period and survive evolution and (unsound) serialisation unchanged.
+
+use cardano_crypto_class::kes::{CompactSum3Kes, KesAlgorithm, Sum3Kes};
+
+fn assert_cache_stable_across_periods<K>()
+where
+    K: KesAlgorithm<Context = ()>,
+    K::VerificationKey: PartialEq + std::fmt::Debug,
+{
+    let mut key = K::gen_key_kes_from_seed_bytes(&vec![0x5a; K::SEED_SIZE]).expect("signing key");
+    let root = K::derive_verification_key(&key).expect("verification key");
+
+    for period in 0..K::total_periods() {
+        assert_eq!(
+            K::derive_verification_key(&key).expect("verification key"),
+            root,
+            "cached root changed at period {period}"
+        );
+        // Verification rebuilds the root from the signature's authentication
+        // path, so this checks the cache against a recomputed value.
+        let signature = K::sign_kes(&(), period, b"cache", &key).expect("sign");
+        K::verify_kes(&(), &root, period, b"cache", &signature).expect("verify");
+
+        match K::update_kes(&(), key, period).expect("update") {
+            Some(next) => key = next,
+            None => {
+                assert_eq!(period + 1, K::total_periods());
+                return;
+            },
+        }
+    }
+    K::forget_signing_key_kes(key);
+}
+
+#[test]
+fn sum3_cached_root_matches_every_period() {
+    assert_cache_stable_across_periods::<Sum3Kes>();
+}
+
+#[test]
+fn compact_sum3_cached_root_matches_every_period() {
+    assert_cache_stable_across_periods::<CompactSum3Kes>();
+}
+
+#[cfg(feature = "tooling")]
+#[test]
+fn unsound_round_trip_recomputes_cached_root() {
+    use cardano_crypto_class::kes::UnsoundKesAlgorithm;
+
+    let key = Sum3Kes::gen_key_kes_from_seed_bytes(&[7u8; 32]).expect("signing key");
+    let key = Sum3Kes::update_kes(&(), key, 0)
+        .expect("update")
+        .expect("period 1");
+    let raw = Sum3Kes::raw_serialize_signing_key_kes(&key).expect("serialise");
+    let restored = Sum3Kes::raw_deserialize_signing_key_kes(&raw).expect("deserialise");
+    assert_eq!(
+        Sum3Kes::derive_verification_key(&restored).expect("restored vk"),
+        Sum3Kes::derive_verification_key(&key).expect("vk")
+    );
+    Sum3Kes::forget_signing_key_kes(key);
+    Sum3Kes::forget_signing_key_kes(restored);
+}
@@ -0,0 +1,221 @@
+#![cfg(all(target_os = "linux", feature = "memfd-handoff"))]
+
+use std::io;
+use std::mem;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::net::UnixStream;
+use std::thread;
+
+use cardano_crypto_class::Ed25519MLockedSigningKey;
+use cardano_crypto_class::dsign::ed25519::Ed25519;
+use cardano_crypto_class::dsign::{DsignAlgorithm, DsignMAlgorithm};
+use cardano_crypto_class::kes::handoff::{
+    seal_signing_key_to_memfd, signing_key_from_sealed_memfd,
+};
+use cardano_crypto_class::kes::{CompactSum3Kes, KesAlgorithm, KesMError, Sum6Kes};
+use cardano_crypto_class::mlocked_bytes::{MLockedBytes, MLockedError};
+use cardano_crypto_class::mlocked_seed::MLockedSeed;
+
+const MESSAGE: &[u8] = b"memfd handoff";
+
+fn cmsg_space() -> usize {
+    // SAFETY: CMSG_SPACE only computes a size.
+    unsafe { libc::CMSG_SPACE(mem::size_of::<libc::c_int>() as u32) as usize }
+}
+
+/// Send `fd` over `stream` as `SCM_RIGHTS` ancillary data.
+fn send_fd(stream: &UnixStream, fd: &OwnedFd) -> io::Result<()> {
+    let mut payload = [0u8; 1];
+    let mut iov = libc::iovec {
+        iov_base: payload.as_mut_ptr().cast(),
+        iov_len: payload.len(),
+    };
+    let mut control = vec![0u8; cmsg_space()];
+    // SAFETY: msghdr is plain data; every pointer set below outlives sendmsg.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = control.len();
+    // SAFETY: the control buffer is sized with CMSG_SPACE for one descriptor.
+    let rc = unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(mem::size_of::<libc::c_int>() as u32) as usize;
+        libc::CMSG_DATA(cmsg)
+            .cast::<libc::c_int>()
+            .write_unaligned(fd.as_raw_fd());
+        libc::sendmsg(stream.as_raw_fd(), &msg, 0)
+    };
+    if rc < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Receive a descriptor sent with [`send_fd`].
+fn recv_fd(stream: &UnixStream) -> io::Result<OwnedFd> {
+    let mut payload = [0u8; 1];
+    let mut iov = libc::iovec {
+        iov_base: payload.as_mut_ptr().cast(),
+        iov_len: payload.len(),
+    };
+    let mut control = vec![0u8; cmsg_space()];
+    // SAFETY: msghdr is plain data; every pointer set below outlives recvmsg.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = control.len();
+    // SAFETY: recvmsg fills at most msg_controllen bytes of the control buffer.
+    let rc = unsafe { libc::recvmsg(stream.as_raw_fd(), &mut msg, libc::MSG_CMSG_CLOEXEC) };
+    if rc < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: the header, if present, lies within the control buffer.
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        if cmsg.is_null()
+            || (*cmsg).cmsg_level != libc::SOL_SOCKET
+            || (*cmsg).cmsg_type != libc::SCM_RIGHTS
+        {
+            return Err(io::Error::other("no SCM_RIGHTS descriptor received"));
+        }
+        let raw = libc::CMSG_DATA(cmsg).cast::<libc::c_int>().read_unaligned();
+        Ok(OwnedFd::from_raw_fd(raw))
+    }
+}
+
+/// Ship `fd` to a receiving thread over a socketpair and hand the received
+/// descriptor to `restore` there, as a separate process would.
+fn hand_off<T: Send + 'static>(
+    fd: OwnedFd,
+    restore: impl FnOnce(OwnedFd) -> T + Send + 'static,
+) -> T {
+    let (sender, receiver) = UnixStream::pair().expect("socketpair");
+    let handle = thread::spawn(move || restore(recv_fd(&receiver).expect("receive descriptor")));
+    send_fd(&sender, &fd).expect("send descriptor");
+    drop(fd);
+    handle.join().expect("receiver thread")
+}
+
+fn ed25519_key() -> Ed25519MLockedSigningKey {
+    let mut seed = MLockedSeed::<32>::new_zeroed().expect("seed allocation");
+    seed.as_mut_bytes().copy_from_slice(&[0x5a; 32]);
+    Ed25519::gen_key_m(&seed).expect("Ed25519 key generation")
+}
+
+#[test]
+fn ed25519_key_survives_handoff_and_signs_identically() {
+    let sk = ed25519_key();
+    let expected = Ed25519::sign_bytes_m(&(), MESSAGE, &sk).expect("sign before handoff");
+
+    let fd = sk.seal_to_memfd().expect("seal key");
+    // Mlocked keys are not `Send`, so the receiving side signs and returns
+    // only the signature bytes.
+    let signature = hand_off(fd, |fd| {
+        let restored = Ed25519MLockedSigningKey::from_sealed_memfd(fd).expect("restore key");
+        let signature = Ed25519::sign_bytes_m(&(), MESSAGE, &restored).expect("sign after handoff");
+        Ed25519::raw_serialize_signature(&signature)
+    });
+
+    assert_eq!(signature, Ed25519::raw_serialize_signature(&expected));
+}
+
+#[test]
+fn sum6_key_survives_handoff_at_later_period() {
+    let seed = vec![0x42; Sum6Kes::SEED_SIZE];
+    let mut sk = Sum6Kes::gen_key_kes_from_seed_bytes(&seed).expect("signing key");
+    for period in 0..5 {
+        sk = Sum6Kes::update_kes(&(), sk, period)
+            .expect("update succeeds")
+            .expect("key not expired");
+    }
+    let vk = Sum6Kes::derive_verification_key(&sk).expect("verification key");
+    let expected = Sum6Kes::sign_kes(&(), 5, MESSAGE, &sk).expect("sign before handoff");
+
+    let fd = seal_signing_key_to_memfd::<Sum6Kes>(&sk).expect("seal key");
+    Sum6Kes::forget_signing_key_kes(sk);
+    let signature = hand_off(fd, |fd| {
+        let restored = signing_key_from_sealed_memfd::<Sum6Kes>(fd).expect("restore key");
+        let signature = Sum6Kes::sign_kes(&(), 5, MESSAGE, &restored).expect("sign after handoff");
+        Sum6Kes::forget_signing_key_kes(restored);
+        Sum6Kes::raw_serialize_signature_kes(&signature)
+    });
+
+    assert_eq!(signature, Sum6Kes::raw_serialize_signature_kes(&expected));
+    let signature = Sum6Kes::raw_deserialize_signature_kes(&signature).expect("signature decodes");
+    Sum6Kes::verify_kes(&(), &vk, 5, MESSAGE, &signature).expect("signature verifies");
+}
+
+#[test]
+fn compact_sum3_key_survives_handoff() {
+    let seed = vec![0x17; CompactSum3Kes::SEED_SIZE];
+    let sk = CompactSum3Kes::gen_key_kes_from_seed_bytes(&seed).expect("signing key");
+    let expected = CompactSum3Kes::sign_kes(&(), 0, MESSAGE, &sk).expect("sign before handoff");
+
+    let fd = seal_signing_key_to_memfd::<CompactSum3Kes>(&sk).expect("seal key");
+    let signature = hand_off(fd, |fd| {
+        let restored = signing_key_from_sealed_memfd::<CompactSum3Kes>(fd).expect("restore key");
+        let signature =
+            CompactSum3Kes::sign_kes(&(), 0, MESSAGE, &restored).expect("sign after handoff");
+        CompactSum3Kes::forget_signing_key_kes(restored);
+        CompactSum3Kes::raw_serialize_signature_kes(&signature)
+    });
+
+    assert_eq!(
+        signature,
+        CompactSum3Kes::raw_serialize_signature_kes(&expected)
+    );
+    CompactSum3Kes::forget_signing_key_kes(sk);
+}
+
+#[test]
+fn sealed_memfd_rejects_wrong_length() {
+    let mut bytes = MLockedBytes::new_zeroed(16).expect("allocation");
+    bytes.as_mut_slice().fill(7);
+    let fd = bytes.seal_to_memfd().expect("seal bytes");
+
+    match MLockedBytes::from_sealed_memfd(fd, 32) {
+        Err(MLockedError::MemfdLength {
+            expected: 32,
+            actual: 16,
+        }) => {},
+        Err(other) => unreachable!("unexpected error: {other:?}"),
+        Ok(_) => unreachable!("a 16-byte memfd must not restore 32 bytes"),
+    }
+
+    let fd = bytes.seal_to_memfd().expect("seal bytes");
+    match signing_key_from_sealed_memfd::<Sum6Kes>(fd) {
+        Err(KesMError::Mlocked(MLockedError::MemfdLength { actual: 16, .. })) => {},
+        Err(other) => unreachable!("unexpected error: {other:?}"),
+        Ok(_) => unreachable!("a 16-byte memfd must not restore a Sum6 key"),
+    }
+}
+
+#[test]
+fn unsealed_memfd_is_rejected() {
+    // SAFETY: the name is a NUL-terminated C string literal.
+    let raw = unsafe { libc::memfd_create(c"unsealed".as_ptr(), libc::MFD_CLOEXEC) };
+    assert!(raw >= 0, "memfd_create failed");
+    // SAFETY: memfd_create returned a fresh descriptor.
+    let fd = unsafe { OwnedFd::from_raw_fd(raw) };
+
+    match MLockedBytes::from_sealed_memfd(fd, 0) {
+        Err(MLockedError::MemfdNotSealed) => {},
+        Err(other) => unreachable!("unexpected error: {other:?}"),
+        Ok(_) => unreachable!("an unsealed memfd must be rejected"),
+    }
+}
+
+#[test]
+fn sealed_memfd_cannot_be_modified() {
+    let bytes = MLockedBytes::new_zeroed(8).expect("allocation");
+    let fd = bytes.seal_to_memfd().expect("seal bytes");
+    let mut file = std::fs::File::from(fd);
+
+    assert!(std::io::Write::write_all(&mut file, b"x").is_err());
+    assert!(file.set_len(16).is_err());
+    assert!(file.set_len(0).is_err());
+}
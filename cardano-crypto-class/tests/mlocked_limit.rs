@@ -0,0 +1,93 @@
+//! `preflight_check` and the `RLIMIT_MEMLOCK` diagnosis.
+//!
+//! The limit test re-runs this binary with a lowered `RLIMIT_MEMLOCK`, set
+//! between `fork` and `exec` so the parent test process keeps its own limit.
+
+use cardano_crypto_class::mlocked_bytes::{MLockedError, current_memlock_limit, preflight_check};
+
+/// Set in the child process, whose limit is this many bytes.
+const CHILD_ENV: &str = "CARDANO_MLOCK_LIMIT_CHILD";
+const CHILD_LIMIT: u64 = 64 * 1024;
+const CHILD_REQUEST: usize = 1024 * 1024;
+/// `CAP_IPC_LOCK` from `<linux/capability.h>`, which `libc` does not export.
+#[cfg(target_os = "linux")]
+const CAP_IPC_LOCK: libc::c_ulong = 14;
+
+#[test]
+fn preflight_succeeds_for_small_sizes() {
+    if std::env::var_os(CHILD_ENV).is_some() {
+        return;
+    }
+    preflight_check(0).expect("empty preflight");
+    preflight_check(4096).expect("one page");
+}
+
+#[cfg(unix)]
+#[test]
+fn limit_is_readable() {
+    let (soft, hard) = current_memlock_limit().expect("getrlimit");
+    assert!(soft <= hard);
+}
+
+/// Runs only in the child: the lowered limit must produce the structured
+/// error unless the process may lock memory regardless of its limit.
+#[test]
+fn child_reports_lock_limit() {
+    if std::env::var_os(CHILD_ENV).is_none() {
+        return;
+    }
+    assert_eq!(current_memlock_limit(), Some((CHILD_LIMIT, CHILD_LIMIT)));
+    match preflight_check(CHILD_REQUEST) {
+        Err(MLockedError::LockLimitExceeded {
+            requested,
+            soft_limit,
+            hard_limit,
+        }) => {
+            assert_eq!(requested, CHILD_REQUEST);
+            assert_eq!((soft_limit, hard_limit), (CHILD_LIMIT, CHILD_LIMIT));
+        },
+        Ok(()) => println!("child may exceed RLIMIT_MEMLOCK (CAP_IPC_LOCK), skipping"),
+        Err(other) => unreachable!("expected LockLimitExceeded, got {other:?}"),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn lowered_limit_yields_structured_error() {
+    use std::os::unix::process::CommandExt;
+    use std::process::Command;
+
+    if std::env::var_os(CHILD_ENV).is_some() {
+        return;
+    }
+    let exe = std::env::current_exe().expect("test binary path");
+    let mut command = Command::new(exe);
+    command
+        .args(["--exact", "child_reports_lock_limit", "--nocapture"])
+        .env(CHILD_ENV, "1");
+    // SAFETY: the closure only makes async-signal-safe system calls.
+    unsafe {
+        command.pre_exec(|| {
+            let limit = libc::rlimit {
+                rlim_cur: CHILD_LIMIT as libc::rlim_t,
+                rlim_max: CHILD_LIMIT as libc::rlim_t,
+            };
+            if libc::setrlimit(libc::RLIMIT_MEMLOCK, &limit) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            // Root keeps CAP_IPC_LOCK across exec unless it leaves the
+            // bounding set; ignore failure, the child then skips.
+            #[cfg(target_os = "linux")]
+            libc::prctl(libc::PR_CAPBSET_DROP, CAP_IPC_LOCK, 0, 0, 0);
+            Ok(())
+        });
+    }
+    let output = command.output().expect("run child test");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "child failed:\n{stdout}\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("1 passed"), "child did not run:\n{stdout}");
+}
@@ -0,0 +1,56 @@
+//! `MLockedSeed` comparison and scoped `Seed` views.
+
+use cardano_crypto_class::{DsignAlgorithm, DsignMAlgorithm, Ed25519, MLockedSeed};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+fn seed_from(bytes: [u8; 32]) -> MLockedSeed<32> {
+    let mut seed = MLockedSeed::<32>::new_zeroed().expect("mlocked seed");
+    seed.as_mut_bytes().copy_from_slice(&bytes);
+    seed
+}
+
+#[test]
+fn ct_eq_compares_contents() {
+    let seed = MLockedSeed::<32>::new_random_with(&mut StdRng::seed_from_u64(7)).expect("seed");
+    let copy = seed.try_clone().expect("clone");
+    assert!(seed.ct_eq(&copy));
+    assert!(seed.ct_eq(&seed));
+
+    for index in [0, 15, 31] {
+        let mut bytes = *seed.as_bytes();
+        bytes[index] ^= 0x01;
+        assert!(!seed.ct_eq(&seed_from(bytes)), "byte {index} flipped");
+    }
+}
+
+#[test]
+fn length_is_a_constant() {
+    assert_eq!(MLockedSeed::<32>::LEN, 32);
+    let seed = MLockedSeed::<48>::new_zeroed().expect("seed");
+    assert_eq!(seed.len(), 48);
+    assert!(!seed.is_empty());
+}
+
+#[test]
+fn scoped_seed_generates_the_mlocked_key() {
+    let seed = seed_from([0x42; 32]);
+
+    let direct = Ed25519::gen_key_m(&seed).expect("mlocked key");
+    let direct_vk = Ed25519::derive_verification_key_m(&direct).expect("vk");
+    Ed25519::forget_signing_key_m(direct);
+
+    let scoped_vk = seed
+        .use_as_seed(|view| {
+            assert_eq!(view.as_slice(), seed.as_bytes());
+            Ed25519::derive_verification_key(&Ed25519::gen_key(view))
+        })
+        .expect("scoped seed");
+
+    assert_eq!(scoped_vk, direct_vk);
+}
+
+#[test]
+fn partial_eq_is_not_implemented() {
+    trybuild::TestCases::new().compile_fail("tests/ui/mlocked_seed_eq.rs");
+}
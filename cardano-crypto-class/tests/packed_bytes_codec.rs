@@ -0,0 +1,96 @@
+//! `PackedBytes` in configs and ledger types: a CBOR byte string identical to
+//! a `Vec<u8>`'s, hex text through `Display` / `FromStr`, and map lookups by
+//! byte slice.
+
+use std::collections::BTreeMap;
+
+use cardano_crypto_class::{PackedBytes, PackedBytes28};
+
+fn pattern<const N: usize>() -> PackedBytes<N> {
+    let mut data = [0u8; N];
+    for (i, byte) in data.iter_mut().enumerate() {
+        *byte = (i as u8).wrapping_mul(37).wrapping_add(0xa5);
+    }
+    PackedBytes::new(data)
+}
+
+fn hex_roundtrip<const N: usize>() {
+    let packed = pattern::<N>();
+    let text = packed.to_string();
+    assert_eq!(text, hex::encode(packed.as_slice()));
+    assert_eq!(text.parse::<PackedBytes<N>>(), Ok(packed));
+    assert_eq!(format!("0x{text}").parse::<PackedBytes<N>>(), Ok(packed));
+    assert_eq!(packed.len(), N);
+}
+
+#[test]
+fn hex_roundtrips_for_common_lengths() {
+    hex_roundtrip::<0>();
+    hex_roundtrip::<28>();
+    hex_roundtrip::<32>();
+    hex_roundtrip::<57>();
+}
+
+#[test]
+fn btree_map_lookup_by_slice() {
+    let mut map = BTreeMap::new();
+    map.insert(pattern::<28>(), "pool");
+    map.insert(PackedBytes28::new([0; 28]), "zero");
+
+    let key = pattern::<28>().to_vec();
+    assert_eq!(map.get(key.as_slice()), Some(&"pool"));
+    assert_eq!(map.get(&[0u8; 28][..]), Some(&"zero"));
+    assert_eq!(map.get(&[1u8; 28][..]), None);
+}
+
+#[cfg(feature = "serde")]
+mod serde_forms {
+    use cardano_binary::{decode_full, serialize};
+    use cardano_crypto_class::PackedBytes32;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Plain<'a>(#[serde(with = "serde_bytes")] &'a [u8]);
+
+    fn cbor_matches_vec<const N: usize>() {
+        let packed = pattern::<N>();
+        let cbor = serialize(&packed).expect("encode packed");
+        let plain = serialize(&Plain(packed.as_slice())).expect("encode vec");
+        assert_eq!(cbor, plain, "PackedBytes<{N}> differs from a byte string");
+        let back: PackedBytes<N> = decode_full(&cbor).expect("decode");
+        assert_eq!(back, packed);
+    }
+
+    #[test]
+    fn cbor_is_a_plain_byte_string() {
+        cbor_matches_vec::<0>();
+        cbor_matches_vec::<28>();
+        cbor_matches_vec::<32>();
+        cbor_matches_vec::<57>();
+    }
+
+    #[test]
+    fn cbor_length_is_checked() {
+        let cbor = serialize(&Plain(&[7; 28])).expect("encode");
+        assert!(decode_full::<PackedBytes32>(&cbor).is_err());
+        assert!(decode_full::<PackedBytes28>(&cbor).is_ok());
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct PoolConfig {
+        pool_id: PackedBytes28,
+    }
+
+    #[test]
+    fn json_uses_hex_text() {
+        let config = PoolConfig {
+            pool_id: pattern::<28>(),
+        };
+        let json = serde_json::to_string(&config).expect("json");
+        assert_eq!(json, format!("{{\"pool_id\":\"{}\"}}", config.pool_id));
+        let back: PoolConfig = serde_json::from_str(&json).expect("parse");
+        assert_eq!(back, config);
+    }
+}
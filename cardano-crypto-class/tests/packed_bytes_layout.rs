@@ -0,0 +1,89 @@
+//! `PackedBytes` stays inline: no heap allocation per value, and ordering and
+//! hashing agree with a heap-allocated copy of the same bytes.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use cardano_crypto_class::{PackedBytes, PackedBytes32, xor_packed_bytes};
+use proptest::prelude::*;
+
+/// Counts allocations made by the current thread, so the test harness's own
+/// threads cannot disturb the measurement.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        // SAFETY: forwarded unchanged to the system allocator.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: `ptr` was allocated by `System` with `layout`.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn creating_packed_bytes_never_allocates() {
+    let before = allocations();
+    let mut acc = PackedBytes32::new([0; 32]);
+    for i in 1..=10_000u32 {
+        let mut data = [0u8; 32];
+        data[..4].copy_from_slice(&i.to_le_bytes());
+        let packed = PackedBytes32::from(data);
+        let copy = packed;
+        acc = xor_packed_bytes(&acc, &copy);
+    }
+    let after = allocations();
+    assert_eq!(after - before, 0, "PackedBytes32 allocated on the heap");
+    // 1 ^ 2 ^ ... ^ n == n when n is a multiple of four.
+    assert_eq!(acc.as_slice()[..4], 10_000u32.to_le_bytes());
+}
+
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn consistent_with_boxed<const N: usize>(a: [u8; N], b: [u8; N]) -> Result<(), TestCaseError> {
+    let (packed_a, packed_b) = (PackedBytes::new(a), PackedBytes::new(b));
+    let (boxed_a, boxed_b): (Box<[u8]>, Box<[u8]>) = (Box::new(a), Box::new(b));
+    prop_assert_eq!(packed_a.cmp(&packed_b), boxed_a.cmp(&boxed_b));
+    prop_assert_eq!(packed_a == packed_b, boxed_a == boxed_b);
+    prop_assert_eq!(hash_of(&packed_a), hash_of(&*boxed_a));
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn ord_and_hash_match_boxed_at_31(a in any::<[u8; 31]>(), b in any::<[u8; 31]>()) {
+        consistent_with_boxed(a, b)?;
+    }
+
+    #[test]
+    fn ord_and_hash_match_boxed_at_32(a in any::<[u8; 32]>(), b in any::<[u8; 32]>()) {
+        consistent_with_boxed(a, b)?;
+        // Equal content must compare and hash equal too, not just random pairs.
+        consistent_with_boxed(a, a)?;
+    }
+
+    #[test]
+    fn ord_and_hash_match_boxed_at_33(a in any::<[u8; 33]>(), b in any::<[u8; 33]>()) {
+        consistent_with_boxed(a, b)?;
+    }
+}
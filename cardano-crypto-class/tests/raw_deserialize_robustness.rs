@@ -0,0 +1,154 @@
+//! Arbitrary-input robustness of the `raw_deserialize_*` functions.
+//!
+//! For every DSIGN and KES algorithm and both Praos VRF variants, decoding
+//! arbitrary bytes must never panic, and whenever it succeeds, serialising
+//! the result must reproduce the input exactly. Inputs are drawn both from
+//! arbitrary lengths and from the exact encoded size, so the success path is
+//! reached regularly. The `fuzz/` targets check the same properties without
+//! a size bound.
+
+use cardano_crypto_class::dsign::DsignAlgorithm;
+use cardano_crypto_class::dsign::ecdsa_secp256k1::EcdsaSecp256k1DSIGN;
+use cardano_crypto_class::dsign::ed25519::Ed25519;
+use cardano_crypto_class::dsign::schnorr_secp256k1::SchnorrSecp256k1DSIGN;
+use cardano_crypto_class::kes::{
+    CompactSum0Kes, CompactSum1Kes, CompactSum2Kes, CompactSum3Kes, CompactSum4Kes, CompactSum5Kes,
+    CompactSum6Kes, CompactSum7Kes, KesAlgorithm, Sum0Kes, Sum1Kes, Sum2Kes, Sum3Kes, Sum4Kes,
+    Sum5Kes, Sum6Kes, Sum7Kes,
+};
+use cardano_crypto_class::vrf::{PraosBatchCompatVRF, PraosVRF, VRFAlgorithm};
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+/// Arbitrary bytes up to a little past `size`, or exactly `size` bytes.
+fn input(size: usize) -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![vec(any::<u8>(), 0..=size + 8), vec(any::<u8>(), size)]
+}
+
+fn round_trips<T>(
+    bytes: &[u8],
+    decode: impl Fn(&[u8]) -> Option<T>,
+    encode: impl Fn(&T) -> Vec<u8>,
+) -> Result<(), TestCaseError> {
+    if let Some(value) = decode(bytes) {
+        prop_assert_eq!(encode(&value), bytes);
+    }
+    Ok(())
+}
+
+macro_rules! dsign_robustness {
+    ($name:ident, $alg:ty) => {
+        mod $name {
+            use super::*;
+
+            proptest! {
+                #[test]
+                fn verification_key(bytes in input(<$alg>::VERIFICATION_KEY_SIZE)) {
+                    round_trips(
+                        &bytes,
+                        <$alg>::raw_deserialize_verification_key,
+                        <$alg>::raw_serialize_verification_key,
+                    )?;
+                }
+
+                #[test]
+                fn signing_key(bytes in input(<$alg>::SIGNING_KEY_SIZE)) {
+                    round_trips(
+                        &bytes,
+                        <$alg>::raw_deserialize_signing_key,
+                        <$alg>::raw_serialize_signing_key,
+                    )?;
+                }
+
+                #[test]
+                fn signature(bytes in input(<$alg>::SIGNATURE_SIZE)) {
+                    round_trips(
+                        &bytes,
+                        <$alg>::raw_deserialize_signature,
+                        <$alg>::raw_serialize_signature,
+                    )?;
+                }
+            }
+        }
+    };
+}
+
+dsign_robustness!(ed25519, Ed25519);
+dsign_robustness!(ecdsa_secp256k1, EcdsaSecp256k1DSIGN);
+dsign_robustness!(schnorr_secp256k1, SchnorrSecp256k1DSIGN);
+
+macro_rules! kes_robustness {
+    ($name:ident, $alg:ty) => {
+        mod $name {
+            use super::*;
+
+            proptest! {
+                #[test]
+                fn verification_key(bytes in input(<$alg>::VERIFICATION_KEY_SIZE)) {
+                    round_trips(
+                        &bytes,
+                        <$alg>::raw_deserialize_verification_key_kes,
+                        <$alg>::raw_serialize_verification_key_kes,
+                    )?;
+                }
+
+                #[test]
+                fn signature(bytes in input(<$alg>::SIGNATURE_SIZE)) {
+                    round_trips(
+                        &bytes,
+                        <$alg>::raw_deserialize_signature_kes,
+                        <$alg>::raw_serialize_signature_kes,
+                    )?;
+                }
+            }
+        }
+    };
+}
+
+kes_robustness!(sum0, Sum0Kes);
+kes_robustness!(sum1, Sum1Kes);
+kes_robustness!(sum2, Sum2Kes);
+kes_robustness!(sum3, Sum3Kes);
+kes_robustness!(sum4, Sum4Kes);
+kes_robustness!(sum5, Sum5Kes);
+kes_robustness!(sum6, Sum6Kes);
+kes_robustness!(sum7, Sum7Kes);
+kes_robustness!(compact_sum0, CompactSum0Kes);
+kes_robustness!(compact_sum1, CompactSum1Kes);
+kes_robustness!(compact_sum2, CompactSum2Kes);
+kes_robustness!(compact_sum3, CompactSum3Kes);
+kes_robustness!(compact_sum4, CompactSum4Kes);
+kes_robustness!(compact_sum5, CompactSum5Kes);
+kes_robustness!(compact_sum6, CompactSum6Kes);
+kes_robustness!(compact_sum7, CompactSum7Kes);
+
+macro_rules! vrf_robustness {
+    ($name:ident, $alg:ty) => {
+        mod $name {
+            use super::*;
+
+            proptest! {
+                #[test]
+                fn verification_key(bytes in input(<$alg>::VERIFICATION_KEY_SIZE)) {
+                    round_trips(
+                        &bytes,
+                        <$alg>::raw_deserialize_verification_key,
+                        <$alg>::raw_serialize_verification_key,
+                    )?;
+                }
+
+                #[test]
+                fn proof(bytes in input(<$alg>::PROOF_SIZE)) {
+                    round_trips(
+                        &bytes,
+                        <$alg>::raw_deserialize_proof,
+                        <$alg>::raw_serialize_proof,
+                    )?;
+                }
+            }
+        }
+    };
+}
+
+vrf_robustness!(praos, PraosVRF);
+vrf_robustness!(praos_batch_compat, PraosBatchCompatVRF);
@@ -0,0 +1,175 @@
+use cardano_crypto_class::kes::{CompactSum6Kes, KesAlgorithm, Sum0Kes, Sum2Kes, Sum6Kes};
+use cardano_crypto_class::role::{
+    AssumeRole, ColdRole, KesRole, KeyKind, PaymentRole, RoleTagged, StakeRole, TextEnvelopeError,
+    VrfRole, derive_dsign_verification_key, derive_kes_verification_key,
+    derive_vrf_verification_key, eval_vrf, gen_dsign_key, gen_kes_key, gen_vrf_key,
+    kes_envelope_type, sign_dsign, sign_kes, verify_dsign, verify_kes, verify_vrf,
+};
+use cardano_crypto_class::vrf::PraosVRF;
+use cardano_crypto_class::{DsignAlgorithm, Ed25519, Ed25519SigningKey, Ed25519VerificationKey};
+
+#[test]
+fn dsign_envelopes_round_trip_per_role() {
+    let payment = gen_dsign_key::<PaymentRole, Ed25519>(&[1; 32]).expect("payment key");
+    let stake = gen_dsign_key::<StakeRole, Ed25519>(&[2; 32]).expect("stake key");
+    let cold = gen_dsign_key::<ColdRole, Ed25519>(&[3; 32]).expect("cold key");
+
+    let payment_sk_env = payment.to_text_envelope();
+    let payment_vk_env =
+        derive_dsign_verification_key::<PaymentRole, Ed25519>(&payment).to_text_envelope();
+    let stake_vk_env =
+        derive_dsign_verification_key::<StakeRole, Ed25519>(&stake).to_text_envelope();
+    let cold_vk_env = derive_dsign_verification_key::<ColdRole, Ed25519>(&cold).to_text_envelope();
+
+    assert_eq!(payment_sk_env.type_, "PaymentSigningKeyShelley_ed25519");
+    assert_eq!(
+        payment_vk_env.type_,
+        "PaymentVerificationKeyShelley_ed25519"
+    );
+    assert_eq!(stake_vk_env.type_, "StakeVerificationKeyShelley_ed25519");
+    assert_eq!(cold_vk_env.type_, "StakePoolVerificationKey_ed25519");
+    assert_eq!(payment_sk_env.description, "Payment Signing Key");
+    // CBOR byte string header (0x5820) followed by the 32-byte seed.
+    assert_eq!(*payment_sk_env.cbor_hex, format!("5820{}", "01".repeat(32)));
+    assert!(!format!("{payment_sk_env:?}").contains(&payment_sk_env.cbor_hex[..]));
+    assert!(format!("{payment_vk_env:?}").contains(&payment_vk_env.cbor_hex[..]));
+
+    let payment_back =
+        RoleTagged::<PaymentRole, _>::from_text_envelope(&payment_sk_env).expect("import");
+    assert_eq!(payment_back.to_text_envelope(), payment_sk_env);
+    let stake_back =
+        RoleTagged::<StakeRole, Ed25519VerificationKey>::from_text_envelope(&stake_vk_env)
+            .expect("import");
+    assert_eq!(stake_back.to_text_envelope(), stake_vk_env);
+
+    let signature = sign_dsign::<PaymentRole, Ed25519>(&(), b"tx", &payment_back)
+        .expect("sign with payment key");
+    let payment_vk = RoleTagged::<PaymentRole, _>::from_text_envelope(&payment_vk_env)
+        .expect("import verification key");
+    verify_dsign::<PaymentRole, Ed25519>(&(), &payment_vk, b"tx", &signature)
+        .expect("signature verifies");
+}
+
+#[test]
+fn envelope_import_rejects_other_roles() {
+    let stake = gen_dsign_key::<StakeRole, Ed25519>(&[2; 32]).expect("stake key");
+    let stake_vk_env =
+        derive_dsign_verification_key::<StakeRole, Ed25519>(&stake).to_text_envelope();
+
+    let err = RoleTagged::<PaymentRole, Ed25519VerificationKey>::from_text_envelope(&stake_vk_env)
+        .expect_err("stake envelope must not import as payment");
+    assert_eq!(
+        err,
+        TextEnvelopeError::WrongType {
+            expected: "PaymentVerificationKeyShelley_ed25519".to_owned(),
+            found: "StakeVerificationKeyShelley_ed25519".to_owned(),
+        }
+    );
+
+    let err = RoleTagged::<StakeRole, Ed25519SigningKey>::from_text_envelope(&stake_vk_env)
+        .expect_err("verification envelope must not import as signing key");
+    assert!(matches!(err, TextEnvelopeError::WrongType { .. }));
+
+    let mut corrupted = stake_vk_env;
+    corrupted.cbor_hex = "5801ff".to_owned().into();
+    assert_eq!(
+        RoleTagged::<StakeRole, Ed25519VerificationKey>::from_text_envelope(&corrupted),
+        Err(TextEnvelopeError::InvalidKey)
+    );
+}
+
+#[test]
+fn kes_and_vrf_helpers_use_their_roles() {
+    let seed = vec![9; Sum2Kes::SEED_SIZE];
+    let kes_sk = gen_kes_key::<Sum2Kes>(&seed).expect("kes key");
+    let kes_vk = derive_kes_verification_key::<Sum2Kes>(&kes_sk).expect("kes vk");
+    let signature = sign_kes::<Sum2Kes>(&(), 0, b"header", &kes_sk).expect("kes signature");
+    verify_kes::<Sum2Kes>(&(), &kes_vk, 0, b"header", &signature).expect("kes verifies");
+
+    let kes_env = kes_vk.to_kes_text_envelope::<Sum2Kes>();
+    assert_eq!(kes_env.type_, "KesVerificationKey_ed25519_kes_2^2");
+    assert_eq!(kes_env.description, "KES Verification Key");
+    let kes_back =
+        RoleTagged::<KesRole, _>::from_kes_text_envelope::<Sum2Kes>(&kes_env).expect("import");
+    assert_eq!(kes_back, kes_vk);
+    assert_eq!(
+        RoleTagged::<KesRole, _>::from_kes_text_envelope::<Sum6Kes>(&kes_env),
+        Err(TextEnvelopeError::WrongType {
+            expected: "KesVerificationKey_ed25519_kes_2^6".to_owned(),
+            found: "KesVerificationKey_ed25519_kes_2^2".to_owned(),
+        })
+    );
+
+    let vrf_sk = gen_vrf_key::<PraosVRF>(&[4; 32]).expect("vrf key");
+    let vrf_vk = derive_vrf_verification_key::<PraosVRF>(&vrf_sk);
+    let (output, proof) = eval_vrf::<PraosVRF>(&(), b"nonce", &vrf_sk);
+    assert_eq!(
+        verify_vrf::<PraosVRF>(&(), &vrf_vk, b"nonce", &proof),
+        Some(output)
+    );
+
+    for envelope in [vrf_sk.to_text_envelope(), vrf_vk.to_text_envelope()] {
+        assert!(envelope.type_.starts_with("Vrf"));
+    }
+    let vrf_vk_back =
+        RoleTagged::<VrfRole, _>::from_text_envelope(&vrf_vk.to_text_envelope()).expect("import");
+    assert_eq!(vrf_vk_back, vrf_vk);
+}
+
+#[test]
+fn kes_envelope_types_name_the_depth() {
+    assert_eq!(
+        kes_envelope_type::<Sum0Kes>(KeyKind::Verification),
+        "KesVerificationKey_ed25519_kes"
+    );
+    assert_eq!(
+        kes_envelope_type::<Sum6Kes>(KeyKind::Signing),
+        "KesSigningKey_ed25519_kes_2^6"
+    );
+    assert_eq!(
+        kes_envelope_type::<Sum6Kes>(KeyKind::Verification),
+        "KesVerificationKey_ed25519_kes_2^6"
+    );
+    // Compact sums share the verification key, and so the name, of the plain
+    // sum of the same depth.
+    assert_eq!(
+        kes_envelope_type::<CompactSum6Kes>(KeyKind::Verification),
+        kes_envelope_type::<Sum6Kes>(KeyKind::Verification)
+    );
+}
+
+#[test]
+fn assume_role_tags_existing_keys() {
+    let raw = Ed25519::gen_key_from_seed_bytes(&[5; 32]);
+    let tagged = raw.assume_role::<StakeRole>();
+    assert_eq!(
+        tagged.to_text_envelope().type_,
+        "StakeSigningKeyShelley_ed25519"
+    );
+}
+
+#[test]
+fn mismatched_roles_do_not_compile() {
+    trybuild::TestCases::new().compile_fail("tests/ui/role_mismatch.rs");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_is_transparent_and_envelopes_use_cli_field_names() {
+    let payment = gen_dsign_key::<PaymentRole, Ed25519>(&[1; 32]).expect("payment key");
+    let vk = derive_dsign_verification_key::<PaymentRole, Ed25519>(&payment);
+
+    let tagged_cbor = cardano_binary::serialize(&vk).expect("serialise tagged");
+    let bare_cbor = cardano_binary::serialize(vk.inner()).expect("serialise bare");
+    assert_eq!(tagged_cbor, bare_cbor);
+    let back: RoleTagged<PaymentRole, Ed25519VerificationKey> =
+        cardano_binary::decode_full(&tagged_cbor).expect("deserialise tagged");
+    assert_eq!(back, vk);
+
+    let envelope_json = serde_json::to_value(vk.to_text_envelope()).expect("serialise envelope");
+    assert_eq!(
+        envelope_json["type"],
+        "PaymentVerificationKeyShelley_ed25519"
+    );
+    assert!(envelope_json["cborHex"].is_string());
+}
@@ -0,0 +1,103 @@
+//! Periods and counters routed through `kes::serde_period` encode as
+//! Haskell's `Word64` does, byte for byte at every width boundary, and
+//! nothing but an unsigned integer decodes back.
+#![cfg(feature = "serde")]
+
+use cardano_binary::{decode_full, decode_word64_canonical, encode_word64_canonical, serialize};
+use cardano_crypto_class::kes::{Period, serde_period};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+struct KesPeriod(#[serde(with = "serde_period")] Period);
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Ocert {
+    #[serde(with = "serde_period")]
+    counter: u64,
+    #[serde(with = "serde_period")]
+    kes_period: Period,
+}
+
+/// Width boundaries and their `encodeWord64` encodings.
+const GOLDEN: [(Period, &str); 9] = [
+    (23, "17"),
+    (24, "1818"),
+    (255, "18ff"),
+    (256, "190100"),
+    (65_535, "19ffff"),
+    (65_536, "1a00010000"),
+    ((1 << 32) - 1, "1affffffff"),
+    (1 << 32, "1b0000000100000000"),
+    (Period::MAX, "1bffffffffffffffff"),
+];
+
+#[test]
+fn boundary_values_match_word64_encoding() {
+    for (period, encoding) in GOLDEN {
+        let expected = hex::decode(encoding).expect("golden hex");
+        let bytes = serialize(&KesPeriod(period)).expect("encode");
+        assert_eq!(bytes, expected, "{period}");
+        assert_eq!(bytes, encode_word64_canonical(period), "{period}");
+        assert_eq!(decode_word64_canonical(&bytes).expect("canonical"), period);
+        assert_eq!(
+            decode_full::<KesPeriod>(&bytes).expect("decode"),
+            KesPeriod(period)
+        );
+
+        let json = serde_json::to_string(&KesPeriod(period)).expect("json");
+        assert_eq!(json, period.to_string());
+        assert_eq!(
+            serde_json::from_str::<KesPeriod>(&json).expect("json decode"),
+            KesPeriod(period)
+        );
+    }
+}
+
+#[test]
+fn struct_fields_use_the_integer_form() {
+    let ocert = Ocert {
+        counter: 24,
+        kes_period: 1 << 32,
+    };
+    let bytes = serialize(&ocert).expect("encode");
+    let mut expected = vec![0xa2, 0x67];
+    expected.extend_from_slice(b"counter");
+    expected.extend_from_slice(&encode_word64_canonical(24));
+    expected.push(0x6a);
+    expected.extend_from_slice(b"kes_period");
+    expected.extend_from_slice(&encode_word64_canonical(1 << 32));
+    assert_eq!(bytes, expected);
+    assert_eq!(decode_full::<Ocert>(&bytes).expect("decode"), ocert);
+}
+
+#[test]
+fn only_unsigned_integers_decode() {
+    for encoding in [
+        // Period 1 as an 8-byte big-endian byte string
+        "480000000000000001",
+        // -1
+        "20",
+        // 1.0
+        "f93c00",
+        "6131",
+    ] {
+        let bytes = hex::decode(encoding).expect("fixture hex");
+        assert!(
+            decode_full::<KesPeriod>(&bytes).is_err(),
+            "{encoding} decoded as a period"
+        );
+    }
+    // ciborium passes a tag 2 bignum that fits in 64 bits to serde as a
+    // plain integer; only the cardano-binary decoder tells them apart.
+    let bignum = hex::decode("c24105").expect("fixture hex");
+    assert_eq!(
+        decode_full::<KesPeriod>(&bignum).expect("bignum"),
+        KesPeriod(5)
+    );
+    assert!(decode_word64_canonical(&bignum).is_err());
+
+    for json in ["-1", "1.5", "\"1\"", "18446744073709551616"] {
+        assert!(serde_json::from_str::<KesPeriod>(json).is_err(), "{json}");
+    }
+}
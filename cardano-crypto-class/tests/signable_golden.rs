@@ -0,0 +1,122 @@
+//! Golden signable representations for the typed messages in
+//! `cardano_crypto_class::signable`, pinned against the Haskell definitions:
+//! `serialize'` of the header body, `OCertSignable`, and Praos `mkInputVRF`.
+
+use cardano_crypto_class::dsign::ed25519::Ed25519;
+use cardano_crypto_class::dsign::{DsignAlgorithm, signed_dsign, verify_signed_dsign};
+use cardano_crypto_class::kes::{KesAlgorithm, SingleKes, Sum6Kes, signed_kes, verify_signed_kes};
+use cardano_crypto_class::signable::{HeaderBodyBytes, NonceProofMessage, OcertSignable};
+use cardano_crypto_class::{SignableRepresentation, write_binary_word64};
+
+/// Ed25519 verification key of the all-zero seed (RFC 8032 test 1 style).
+const ZERO_SEED_VK: &str = "3b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da29";
+
+fn hex_bytes(hex: &str) -> Vec<u8> {
+    hex::decode(hex).expect("valid hex")
+}
+
+fn representation<M: SignableRepresentation + ?Sized>(message: &M) -> String {
+    hex::encode(message.signable_representation())
+}
+
+#[test]
+fn header_body_is_signed_unwrapped() {
+    let body = HeaderBodyBytes::new(vec![0x82, 0x01, 0x02]);
+    assert_eq!(representation(&body), "820102");
+
+    let unwrapped =
+        HeaderBodyBytes::from_cbor_in_cbor(&hex_bytes("d81843820102")).expect("tag 24 body");
+    assert_eq!(unwrapped, body);
+    assert_eq!(representation(&unwrapped), "820102");
+}
+
+#[test]
+fn header_body_rejects_bytes_without_the_tag_24_wrapper() {
+    assert!(HeaderBodyBytes::from_cbor_in_cbor(&[0x82, 0x01, 0x02]).is_err());
+    assert!(HeaderBodyBytes::from_cbor_in_cbor(&hex_bytes("d81982")).is_err());
+}
+
+#[test]
+fn header_body_kes_signature_covers_the_unwrapped_body() {
+    let signing_key = Sum6Kes::gen_key_kes_from_seed_bytes(&[7u8; 32]).expect("keygen");
+    let verification_key = Sum6Kes::derive_verification_key(&signing_key).expect("derive vk");
+    let body = HeaderBodyBytes::from_cbor_in_cbor(&hex_bytes("d81843820102")).expect("tag 24 body");
+
+    let signed = signed_kes::<Sum6Kes, _>(&(), 0, &body, &signing_key).expect("sign");
+    assert!(verify_signed_kes::<Sum6Kes, _>(&(), &verification_key, &body, &signed).is_ok());
+    assert!(
+        Sum6Kes::verify_kes(
+            &(),
+            &verification_key,
+            0,
+            &[0x82, 0x01, 0x02],
+            signed.signature()
+        )
+        .is_ok()
+    );
+    Sum6Kes::forget_signing_key_kes(signing_key);
+}
+
+#[test]
+fn ocert_signable_layout() {
+    let signing_key =
+        SingleKes::<Ed25519>::gen_key_kes_from_seed_bytes(&[0u8; 32]).expect("keygen");
+    let kes_vk = SingleKes::<Ed25519>::derive_verification_key(&signing_key).expect("derive vk");
+    SingleKes::<Ed25519>::forget_signing_key_kes(signing_key);
+
+    let signable = OcertSignable::<SingleKes<Ed25519>>::new(kes_vk, 3, 400);
+    assert_eq!(
+        representation(&signable),
+        format!("{ZERO_SEED_VK}00000000000000030000000000000190")
+    );
+}
+
+#[test]
+fn ocert_signable_uses_the_raw_sum_kes_key() {
+    let signing_key = Sum6Kes::gen_key_kes_from_seed_bytes(&[1u8; 32]).expect("keygen");
+    let kes_vk = Sum6Kes::derive_verification_key(&signing_key).expect("derive vk");
+    Sum6Kes::forget_signing_key_kes(signing_key);
+
+    let mut expected = Sum6Kes::raw_serialize_verification_key_kes(&kes_vk);
+    expected.extend_from_slice(&write_binary_word64(u64::MAX));
+    expected.extend_from_slice(&write_binary_word64(0));
+
+    let signable = OcertSignable::<Sum6Kes>::new(kes_vk, u64::MAX, 0);
+    let bytes = signable.signable_representation();
+    assert_eq!(bytes.len(), Sum6Kes::VERIFICATION_KEY_SIZE + 16);
+    assert_eq!(bytes.as_ref(), expected.as_slice());
+}
+
+#[test]
+fn ocert_signable_is_signed_by_the_cold_key() {
+    let cold_key = Ed25519::gen_key_from_seed_bytes(&[9u8; 32]);
+    let cold_vk = Ed25519::derive_verification_key(&cold_key);
+    let hot_key = Sum6Kes::gen_key_kes_from_seed_bytes(&[2u8; 32]).expect("keygen");
+    let kes_vk = Sum6Kes::derive_verification_key(&hot_key).expect("derive vk");
+    Sum6Kes::forget_signing_key_kes(hot_key);
+
+    let signable = OcertSignable::<Sum6Kes>::new(kes_vk, 0, 390);
+    let signed = signed_dsign::<Ed25519, _>(&(), &signable, &cold_key).expect("sign ocert body");
+    assert!(verify_signed_dsign::<Ed25519, _>(&(), &cold_vk, &signable, &signed).is_ok());
+
+    let other_period = OcertSignable::<Sum6Kes>::new(signable.kes_vk.clone(), 0, 391);
+    assert!(verify_signed_dsign::<Ed25519, _>(&(), &cold_vk, &other_period, &signed).is_err());
+}
+
+#[test]
+fn nonce_proof_message_golden() {
+    assert_eq!(
+        representation(&NonceProofMessage::new(0, None)),
+        "81e47a19e6b29b0a65b9591762ce5143ed30d0261e5d24a3201752506b20f15c"
+    );
+
+    let nonce: [u8; 32] = core::array::from_fn(|i| i as u8);
+    assert_eq!(
+        representation(&NonceProofMessage::new(1_234_567, Some(nonce))),
+        "b27b629603c6e86a37d3105ed91908328e1f6d5555409b4586647da5e0572c7f"
+    );
+    assert_eq!(
+        representation(&NonceProofMessage::new(u64::MAX, Some([0xff; 32]))),
+        "6002773786391ba081a87d2bf0139514627f79b1b8a4696ad7bee135e1646680"
+    );
+}
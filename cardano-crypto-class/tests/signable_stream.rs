@@ -0,0 +1,151 @@
+//! Streaming signable representations: the hash-then-sign wrapper consumes a
+//! chunked message without buffering it and agrees with the buffered path,
+//! and algorithms without streaming support reject the stream.
+
+use cardano_crypto_class::dsign::ecdsa_secp256k1::{EcdsaSecp256k1DSIGN, hash_and_pack};
+use cardano_crypto_class::dsign::hashed::HashedDsign;
+use cardano_crypto_class::dsign::{
+    DsignAlgorithm, DsignError, signed_dsign, signed_dsign_streaming, verify_signed_dsign,
+    verify_signed_dsign_streaming,
+};
+use cardano_crypto_class::hash::{Blake2b256, HashAlgorithm, Sha256};
+use cardano_crypto_class::{Ed25519, SignableStream};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+const STREAM_SIZE: usize = 100 * 1024 * 1024;
+
+type HashedEd25519 = HashedDsign<Ed25519, Blake2b256>;
+
+/// A deterministic pseudo-random payload produced in fixed-size chunks from
+/// one reused buffer.
+struct SyntheticStream {
+    len: usize,
+}
+
+impl SyntheticStream {
+    fn fill(offset: usize, chunk: &mut [u8]) {
+        for (i, byte) in chunk.iter_mut().enumerate() {
+            let position = (offset + i) as u64;
+            *byte = (position.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 56) as u8;
+        }
+    }
+}
+
+impl SignableStream for SyntheticStream {
+    fn write_signable(&self, out: &mut dyn FnMut(&[u8])) {
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+        let mut offset = 0;
+        while offset < self.len {
+            let chunk = &mut buffer[..CHUNK_SIZE.min(self.len - offset)];
+            Self::fill(offset, chunk);
+            out(chunk);
+            offset += chunk.len();
+        }
+    }
+}
+
+#[test]
+fn hundred_megabyte_stream_matches_the_buffered_signature() {
+    let signing_key = HashedEd25519::gen_key_from_seed_bytes(&[5u8; 32]);
+    let verification_key = HashedEd25519::derive_verification_key(&signing_key);
+    let stream = SyntheticStream { len: STREAM_SIZE };
+
+    let streamed =
+        signed_dsign_streaming::<HashedEd25519, _>(&(), &stream, &signing_key).expect("streams");
+    assert!(
+        verify_signed_dsign_streaming::<HashedEd25519, _>(
+            &(),
+            &verification_key,
+            &stream,
+            &streamed
+        )
+        .is_ok()
+    );
+
+    let buffered = stream.collect_signable();
+    assert_eq!(buffered.len(), STREAM_SIZE);
+    assert_eq!(
+        streamed.signature(),
+        &HashedEd25519::sign_bytes(&(), &buffered, &signing_key)
+    );
+    assert!(
+        Ed25519::verify_bytes(
+            &(),
+            &verification_key,
+            &Blake2b256::hash(&buffered),
+            streamed.signature()
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn byte_messages_stream_as_a_single_chunk() {
+    let signing_key = HashedEd25519::gen_key_from_seed_bytes(&[6u8; 32]);
+    let verification_key = HashedEd25519::derive_verification_key(&signing_key);
+    let message: &[u8] = b"epoch boundary";
+
+    let streamed =
+        signed_dsign_streaming::<HashedEd25519, _>(&(), message, &signing_key).expect("streams");
+    let buffered =
+        signed_dsign::<HashedEd25519, _>(&(), message, &signing_key).expect("sign buffered");
+    assert_eq!(streamed, buffered);
+    assert!(
+        verify_signed_dsign::<HashedEd25519, _>(&(), &verification_key, message, &streamed).is_ok()
+    );
+    assert!(
+        verify_signed_dsign_streaming::<HashedEd25519, _>(
+            &(),
+            &verification_key,
+            b"epoch boundarY".as_slice(),
+            &streamed
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn hashed_ecdsa_matches_hash_and_pack() {
+    type HashedEcdsa = HashedDsign<EcdsaSecp256k1DSIGN, Sha256>;
+    let signing_key = HashedEcdsa::gen_key_from_seed_bytes(&[7u8; 32]);
+    let context = Default::default();
+    let stream = SyntheticStream {
+        len: 3 * CHUNK_SIZE + 17,
+    };
+
+    let streamed =
+        signed_dsign_streaming::<HashedEcdsa, _>(&context, &stream, &signing_key).expect("streams");
+    let hash = hash_and_pack::<Sha256>(&stream.collect_signable());
+    assert_eq!(
+        streamed.signature(),
+        &EcdsaSecp256k1DSIGN::sign_message_hash(&context, &hash, &signing_key)
+    );
+}
+
+#[test]
+fn ed25519_rejects_streams_and_falls_back_to_buffering() {
+    const { assert!(!Ed25519::STREAMING) };
+    let signing_key = Ed25519::gen_key_from_seed_bytes(&[8u8; 32]);
+    let verification_key = Ed25519::derive_verification_key(&signing_key);
+    let stream = SyntheticStream {
+        len: CHUNK_SIZE + 1,
+    };
+
+    let err = signed_dsign_streaming::<Ed25519, _>(&(), &stream, &signing_key)
+        .expect_err("Ed25519 needs the whole message");
+    assert_eq!(
+        err,
+        DsignError::UnsupportedStreaming {
+            algorithm: Ed25519::ALGORITHM_NAME
+        }
+    );
+    assert_eq!(err.code(), "dsign.unsupported_streaming");
+
+    let buffered = stream.collect_signable();
+    let signed =
+        signed_dsign::<Ed25519, _>(&(), buffered.as_slice(), &signing_key).expect("sign buffered");
+    assert!(
+        verify_signed_dsign::<Ed25519, _>(&(), &verification_key, buffered.as_slice(), &signed)
+            .is_ok()
+    );
+}
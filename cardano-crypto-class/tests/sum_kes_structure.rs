@@ -1,11 +1,12 @@
 #![allow(dead_code)]
 
+use cardano_crypto_class::KesAlgorithm;
+use cardano_crypto_class::hash::HashAlgorithm;
 use cardano_crypto_class::kes::hash::Blake2b256;
 use cardano_crypto_class::kes::{
     CompactSum0Kes, CompactSum1Kes, CompactSum2Kes, CompactSum3Kes, Sum0Kes, Sum1Kes, Sum2Kes,
     Sum3Kes, Sum4Kes, Sum5Kes, Sum6Kes, Sum7Kes,
 };
-use cardano_crypto_class::{KesAlgorithm, KesHashAlgorithm};
 
 #[derive(Debug)]
 pub struct ExpectedCompactNode {
@@ -0,0 +1,213 @@
+//! Tagged ("raw with header") verification keys: every registered algorithm
+//! round-trips, a mixed store decodes back to the right kinds, and malformed
+//! input is rejected with a precise error.
+
+use std::collections::BTreeSet;
+use std::fmt::Debug;
+
+use cardano_crypto_class::dsign::DsignAlgorithm;
+use cardano_crypto_class::dsign::ecdsa_secp256k1::EcdsaSecp256k1DSIGN;
+use cardano_crypto_class::dsign::ed25519::Ed25519;
+use cardano_crypto_class::dsign::schnorr_secp256k1::SchnorrSecp256k1DSIGN;
+use cardano_crypto_class::kes::{
+    CompactSum0Kes, CompactSum1Kes, CompactSum2Kes, CompactSum3Kes, CompactSum4Kes, CompactSum5Kes,
+    CompactSum6Kes, CompactSum7Kes, KesAlgorithm, Sum0Kes, Sum1Kes, Sum2Kes, Sum3Kes, Sum4Kes,
+    Sum5Kes, Sum6Kes, Sum7Kes,
+};
+use cardano_crypto_class::keys::tagged::{
+    TaggedKey, TaggedKeyAlgorithm, TaggedKeyError, deserialize_tagged, deserialize_tagged_any,
+    known_tags, serialize_tagged,
+};
+use cardano_crypto_class::seed::{Seed, mk_seed_from_bytes};
+use cardano_crypto_class::vrf::{
+    MockVRF, NeverVRF, PraosBatchCompatVRF, PraosVRF, SimpleVRF, VRFAlgorithm,
+};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+fn seed(byte: u8) -> Seed {
+    mk_seed_from_bytes(vec![byte; 64])
+}
+
+fn dsign_vk<A: DsignAlgorithm>(byte: u8) -> A::VerificationKey {
+    A::derive_verification_key(&A::gen_key(&seed(byte)))
+}
+
+fn vrf_vk<A: VRFAlgorithm>(byte: u8) -> A::VerificationKey {
+    A::derive_verification_key(&A::gen_key(&seed(byte)))
+}
+
+fn kes_vk<A: KesAlgorithm>(byte: u8) -> A::VerificationKey {
+    let sk = A::gen_key_kes(&seed(byte)).expect("signing key");
+    let vk = A::derive_verification_key(&sk).expect("verification key");
+    A::forget_signing_key_kes(sk);
+    vk
+}
+
+fn roundtrip<A: TaggedKeyAlgorithm>(key: A::VerificationKey, tags: &mut BTreeSet<&'static str>)
+where
+    A::VerificationKey: Clone + PartialEq + Debug,
+{
+    let bytes = serialize_tagged::<A>(&key);
+    assert_eq!(usize::from(bytes[0]), A::TAG.len());
+    assert_eq!(&bytes[1..=A::TAG.len()], A::TAG.as_bytes());
+    assert_eq!(bytes.len(), 1 + A::TAG.len() + A::VERIFICATION_KEY_SIZE);
+
+    assert_eq!(deserialize_tagged::<A>(&bytes), Ok(key.clone()));
+    let any = deserialize_tagged_any(&bytes).expect("registered algorithm");
+    assert_eq!(any.tag(), A::TAG);
+    assert_eq!(any.to_bytes(), bytes);
+    assert_eq!(any.clone(), TaggedKey::new::<A>(key.clone()));
+    assert_eq!(any.expected::<A>(), Ok(key));
+    tags.insert(A::TAG);
+}
+
+#[test]
+fn every_registered_algorithm_roundtrips() {
+    let mut tags = BTreeSet::new();
+    roundtrip::<Ed25519>(dsign_vk::<Ed25519>(1), &mut tags);
+    roundtrip::<EcdsaSecp256k1DSIGN>(dsign_vk::<EcdsaSecp256k1DSIGN>(2), &mut tags);
+    roundtrip::<SchnorrSecp256k1DSIGN>(dsign_vk::<SchnorrSecp256k1DSIGN>(3), &mut tags);
+
+    roundtrip::<Sum0Kes>(kes_vk::<Sum0Kes>(4), &mut tags);
+    roundtrip::<Sum1Kes>(kes_vk::<Sum1Kes>(4), &mut tags);
+    roundtrip::<Sum2Kes>(kes_vk::<Sum2Kes>(4), &mut tags);
+    roundtrip::<Sum3Kes>(kes_vk::<Sum3Kes>(4), &mut tags);
+    roundtrip::<Sum4Kes>(kes_vk::<Sum4Kes>(4), &mut tags);
+    roundtrip::<Sum5Kes>(kes_vk::<Sum5Kes>(4), &mut tags);
+    roundtrip::<Sum6Kes>(kes_vk::<Sum6Kes>(4), &mut tags);
+    roundtrip::<Sum7Kes>(kes_vk::<Sum7Kes>(4), &mut tags);
+    roundtrip::<CompactSum0Kes>(kes_vk::<CompactSum0Kes>(5), &mut tags);
+    roundtrip::<CompactSum1Kes>(kes_vk::<CompactSum1Kes>(5), &mut tags);
+    roundtrip::<CompactSum2Kes>(kes_vk::<CompactSum2Kes>(5), &mut tags);
+    roundtrip::<CompactSum3Kes>(kes_vk::<CompactSum3Kes>(5), &mut tags);
+    roundtrip::<CompactSum4Kes>(kes_vk::<CompactSum4Kes>(5), &mut tags);
+    roundtrip::<CompactSum5Kes>(kes_vk::<CompactSum5Kes>(5), &mut tags);
+    roundtrip::<CompactSum6Kes>(kes_vk::<CompactSum6Kes>(5), &mut tags);
+    roundtrip::<CompactSum7Kes>(kes_vk::<CompactSum7Kes>(5), &mut tags);
+
+    roundtrip::<PraosVRF>(vrf_vk::<PraosVRF>(6), &mut tags);
+    roundtrip::<PraosBatchCompatVRF>(vrf_vk::<PraosBatchCompatVRF>(7), &mut tags);
+    roundtrip::<SimpleVRF>(vrf_vk::<SimpleVRF>(8), &mut tags);
+    roundtrip::<MockVRF>(vrf_vk::<MockVRF>(9), &mut tags);
+    roundtrip::<NeverVRF>(vrf_vk::<NeverVRF>(10), &mut tags);
+
+    assert_eq!(tags, known_tags().collect::<BTreeSet<_>>());
+}
+
+#[test]
+fn compact_and_plain_sum_keys_share_a_tag() {
+    let plain = serialize_tagged::<Sum6Kes>(&kes_vk::<Sum6Kes>(11));
+    let compact = serialize_tagged::<CompactSum6Kes>(&kes_vk::<CompactSum6Kes>(11));
+    assert_eq!(plain, compact);
+    assert_eq!(
+        deserialize_tagged::<CompactSum6Kes>(&plain),
+        Ok(kes_vk::<Sum6Kes>(11))
+    );
+}
+
+#[test]
+fn mixed_store_decodes_shuffled_keys() {
+    // Ed25519, Schnorr, Sum KES and Praos batch-compatible keys are all 32
+    // raw bytes; only the header tells them apart.
+    let mut store = vec![
+        TaggedKey::new::<Ed25519>(dsign_vk::<Ed25519>(20)),
+        TaggedKey::new::<Ed25519>(dsign_vk::<Ed25519>(21)),
+        TaggedKey::new::<EcdsaSecp256k1DSIGN>(dsign_vk::<EcdsaSecp256k1DSIGN>(22)),
+        TaggedKey::new::<SchnorrSecp256k1DSIGN>(dsign_vk::<SchnorrSecp256k1DSIGN>(23)),
+        TaggedKey::new::<Sum0Kes>(kes_vk::<Sum0Kes>(24)),
+        TaggedKey::new::<Sum6Kes>(kes_vk::<Sum6Kes>(25)),
+        TaggedKey::new::<CompactSum6Kes>(kes_vk::<CompactSum6Kes>(26)),
+        TaggedKey::new::<Sum7Kes>(kes_vk::<Sum7Kes>(27)),
+        TaggedKey::new::<PraosVRF>(vrf_vk::<PraosVRF>(28)),
+        TaggedKey::new::<PraosBatchCompatVRF>(vrf_vk::<PraosBatchCompatVRF>(29)),
+        TaggedKey::new::<SimpleVRF>(vrf_vk::<SimpleVRF>(30)),
+        TaggedKey::new::<MockVRF>(vrf_vk::<MockVRF>(31)),
+    ];
+    store.shuffle(&mut StdRng::seed_from_u64(0x7a66));
+
+    let encoded: Vec<Vec<u8>> = store.iter().map(TaggedKey::to_bytes).collect();
+    let decoded: Vec<TaggedKey> = encoded
+        .iter()
+        .map(|bytes| deserialize_tagged_any(bytes).expect("decodes"))
+        .collect();
+    assert_eq!(decoded, store);
+
+    let ed25519: Vec<_> = decoded
+        .into_iter()
+        .filter_map(|key| key.expected::<Ed25519>().ok())
+        .collect();
+    assert_eq!(ed25519.len(), 2);
+    assert!(ed25519.contains(&dsign_vk::<Ed25519>(20)));
+    assert!(ed25519.contains(&dsign_vk::<Ed25519>(21)));
+}
+
+#[test]
+fn rejects_unknown_and_mismatched_algorithms() {
+    let mut unknown = vec![3];
+    unknown.extend_from_slice(b"rsa");
+    unknown.extend_from_slice(&[0; 32]);
+    assert_eq!(
+        deserialize_tagged_any(&unknown),
+        Err(TaggedKeyError::UnknownAlgorithm("rsa".to_owned()))
+    );
+
+    let kes = serialize_tagged::<Sum6Kes>(&kes_vk::<Sum6Kes>(40));
+    assert_eq!(
+        deserialize_tagged::<Ed25519>(&kes),
+        Err(TaggedKeyError::WrongAlgorithm {
+            expected: "ed25519",
+            found: "ed25519_kes_2^6".to_owned(),
+        })
+    );
+    assert_eq!(
+        deserialize_tagged_any(&kes)
+            .expect("decodes")
+            .expected::<Sum7Kes>(),
+        Err(TaggedKeyError::WrongAlgorithm {
+            expected: "ed25519_kes_2^7",
+            found: "ed25519_kes_2^6".to_owned(),
+        })
+    );
+}
+
+#[test]
+fn rejects_wrong_lengths_and_invalid_keys() {
+    let bytes = serialize_tagged::<Ed25519>(&dsign_vk::<Ed25519>(50));
+
+    let short = &bytes[..bytes.len() - 1];
+    let expected = Some(TaggedKeyError::LengthMismatch {
+        algorithm: "ed25519",
+        expected: 32,
+        actual: 31,
+    });
+    assert_eq!(deserialize_tagged_any(short).err(), expected);
+    assert_eq!(deserialize_tagged::<Ed25519>(short).err(), expected);
+
+    let mut long = bytes.clone();
+    long.extend_from_slice(&[0, 0]);
+    let expected = Some(TaggedKeyError::TrailingBytes {
+        algorithm: "ed25519",
+        extra: 2,
+    });
+    assert_eq!(deserialize_tagged_any(&long).err(), expected);
+    assert_eq!(deserialize_tagged::<Ed25519>(&long).err(), expected);
+
+    assert_eq!(
+        deserialize_tagged_any(&bytes[..4]),
+        Err(TaggedKeyError::TruncatedHeader)
+    );
+
+    let mut not_a_point = vec![19];
+    not_a_point.extend_from_slice(b"EcdsaSecp256k1DSIGN");
+    not_a_point.extend_from_slice(&[0; 33]);
+    let expected = Some(TaggedKeyError::InvalidKey {
+        algorithm: "EcdsaSecp256k1DSIGN",
+    });
+    assert_eq!(deserialize_tagged_any(&not_a_point).err(), expected);
+    assert_eq!(
+        deserialize_tagged::<EcdsaSecp256k1DSIGN>(&not_a_point).err(),
+        expected
+    );
+}
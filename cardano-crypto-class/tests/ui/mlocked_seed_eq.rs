@@ -0,0 +1,7 @@
+use cardano_crypto_class::MLockedSeed;
+
+fn same(a: &MLockedSeed<32>, b: &MLockedSeed<32>) -> bool {
+    a == b
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use cardano_crypto_class::vrf::{OutputVRF, PraosVRF};
+
+fn main() {
+    let output = OutputVRF::<PraosVRF>::from_bytes(vec![0; 64]).expect("64 bytes");
+    let _ = output.as_array::<32>();
+}
@@ -0,0 +1,10 @@
+use cardano_crypto_class::Ed25519VerificationKey;
+use cardano_crypto_class::role::{PaymentRole, RoleTagged, StakeRole};
+
+fn pay_to(_key: &RoleTagged<PaymentRole, Ed25519VerificationKey>) {}
+
+fn route(stake_key: &RoleTagged<StakeRole, Ed25519VerificationKey>) {
+    pay_to(stake_key);
+}
+
+fn main() {}
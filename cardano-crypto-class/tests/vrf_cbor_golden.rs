@@ -0,0 +1,181 @@
+//! Golden CBOR encodings for Praos VRF outputs, proofs, and certified
+//! outputs, pinned against the Haskell `ToCBOR` instances.
+//!
+//! `OutputVRF` and `CertVRF` encode as a single byte string, so each value is
+//! the two-byte `0x58 <len>` header followed by the raw bytes;
+//! `CertifiedVRF` is a two-element list of the output and the proof.
+#![cfg(feature = "serde")]
+
+use cardano_binary::{BinaryError, decode_full, serialize};
+use cardano_crypto_class::vrf::{
+    CertifiedVRF, OutputVRF, PraosBatchCompatProof, PraosBatchCompatVRF, PraosProof, PraosVRF,
+    VRFAlgorithm,
+};
+use cardano_test_vectors::vrf;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+const PRAOS_PROOF: &str = "b6b4699f87d56126c9117a7da55bd0085246f4c56dbc95d20172612e9d38e8d7ca65e573a126ed88d4e30a46f80a666854d675cf3ba81de0de043c3774f061560f55edc256a787afe701677c0f602900";
+const PRAOS_OUTPUT: &str = "5b49b554d05c0cd5a5325376b3387de59d924fd1e13ded44648ab33c21349a603f25b84ec5ed887995b33da5e3bfcb87cd2f64521c4c62cf825cffabbe5d31cc";
+const PRAOS_BATCH_PROOF: &str = "7d9c633ffeee27349264cf5c667579fc583b4bda63ab71d001f89c10003ab46f762f5c178b68f0cddcc1157918edf45ec334ac8e8286601a3256c3bbf858edd94652eba1c4612e6fce762977a59420b451e12964adbe4fbecd58a7aeff5860afcafa73589b023d14311c331a9ad15ff2fb37831e00f0acaa6d73bc9997b06501";
+const PRAOS_BATCH_OUTPUT: &str = "9d574bf9b8302ec0fc1e21c3ec5368269527b87b462ce36dab2d14ccf80c53cccf6758f058c5b1c856b116388152bbe509ee3b9ecfe63d93c3b4346c1fbc6c54";
+
+fn to_cbor<T: Serialize>(value: &T) -> Vec<u8> {
+    serialize(value).expect("CBOR encoding succeeds")
+}
+
+fn from_cbor<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, BinaryError> {
+    decode_full(bytes)
+}
+
+fn vector_field(name: &str, key: &str) -> Vec<u8> {
+    let contents = vrf::get(name).expect("VRF vector is embedded");
+    let value = contents
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+        .expect("VRF vector defines the field")
+        .trim();
+    hex::decode(value).expect("VRF vector field is hex")
+}
+
+#[test]
+fn praos_output_and_proof_match_golden_cbor() {
+    let proof = PraosProof::from_bytes(&vector_field("vrf_ver03_standard_10", "pi"))
+        .expect("vector proof is well formed");
+    let output = OutputVRF::<PraosVRF>::from_bytes(
+        proof
+            .to_output_bytes()
+            .expect("output extraction succeeds")
+            .expect("vector proof yields an output"),
+    )
+    .expect("output has OUTPUT_SIZE bytes");
+    assert_eq!(
+        output.as_bytes(),
+        vector_field("vrf_ver03_standard_10", "beta")
+    );
+
+    assert_eq!(hex::encode(to_cbor(&output)), format!("5840{PRAOS_OUTPUT}"));
+    assert_eq!(hex::encode(to_cbor(&proof)), format!("5850{PRAOS_PROOF}"));
+    assert_eq!(
+        hex::encode(to_cbor(&CertifiedVRF::<PraosVRF>::new(
+            OutputVRF::copy_from_slice(output.as_bytes()).expect("output copy"),
+            proof.clone()
+        ))),
+        format!("825840{PRAOS_OUTPUT}5850{PRAOS_PROOF}")
+    );
+
+    let decoded: CertifiedVRF<PraosVRF> =
+        from_cbor(&hex::decode(format!("825840{PRAOS_OUTPUT}5850{PRAOS_PROOF}")).expect("hex"))
+            .expect("golden CertifiedVRF decodes");
+    assert_eq!(decoded.output, output);
+    assert_eq!(decoded.proof, proof);
+}
+
+#[test]
+fn praos_batch_output_and_proof_match_golden_cbor() {
+    let proof = PraosBatchCompatProof::from_bytes(&vector_field("vrf_ver13_standard_10", "pi"))
+        .expect("vector proof is well formed");
+    let output = OutputVRF::<PraosBatchCompatVRF>::from_bytes(
+        proof
+            .to_output_bytes()
+            .expect("output extraction succeeds")
+            .expect("vector proof yields an output"),
+    )
+    .expect("output has OUTPUT_SIZE bytes");
+    assert_eq!(
+        output.as_bytes(),
+        vector_field("vrf_ver13_standard_10", "beta")
+    );
+
+    assert_eq!(
+        hex::encode(to_cbor(&output)),
+        format!("5840{PRAOS_BATCH_OUTPUT}")
+    );
+    assert_eq!(
+        hex::encode(to_cbor(&proof)),
+        format!("5880{PRAOS_BATCH_PROOF}")
+    );
+    assert_eq!(
+        hex::encode(to_cbor(&CertifiedVRF::<PraosBatchCompatVRF>::new(
+            OutputVRF::copy_from_slice(output.as_bytes()).expect("output copy"),
+            proof.clone()
+        ))),
+        format!("825840{PRAOS_BATCH_OUTPUT}5880{PRAOS_BATCH_PROOF}")
+    );
+
+    let decoded: PraosBatchCompatProof =
+        from_cbor(&to_cbor(&proof)).expect("batch proof round trips");
+    assert_eq!(decoded, proof);
+}
+
+fn assert_vectors_encode_as_byte_strings<A>(prefix: &str)
+where
+    A: VRFAlgorithm,
+    A::Proof: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+{
+    let names: Vec<_> = vrf::ALL
+        .iter()
+        .filter(|vector| vector.name.starts_with(prefix))
+        .map(|vector| vector.name)
+        .collect();
+    assert!(!names.is_empty(), "no {prefix} vectors found");
+
+    for name in names {
+        let proof_bytes = vector_field(name, "pi");
+        let output_bytes = vector_field(name, "beta");
+        let proof = A::raw_deserialize_proof(&proof_bytes).expect("vector proof is well formed");
+        let output = OutputVRF::<A>::copy_from_slice(&output_bytes).expect("vector output size");
+
+        let mut expected_proof = vec![0x58, u8::try_from(A::PROOF_SIZE).expect("short proof")];
+        expected_proof.extend_from_slice(&proof_bytes);
+        let mut expected_output = vec![0x58, u8::try_from(A::OUTPUT_SIZE).expect("short output")];
+        expected_output.extend_from_slice(&output_bytes);
+
+        assert_eq!(to_cbor(&proof), expected_proof, "{name}: proof encoding");
+        assert_eq!(to_cbor(&output), expected_output, "{name}: output encoding");
+
+        let certified = CertifiedVRF::<A>::new(output, proof);
+        let encoded = to_cbor(&certified);
+        assert_eq!(encoded[0], 0x82, "{name}: CertifiedVRF is a 2-element list");
+        let decoded: CertifiedVRF<A> = from_cbor(&encoded).expect("CertifiedVRF round trips");
+        assert_eq!(decoded.output, certified.output);
+        assert_eq!(decoded.proof, certified.proof);
+    }
+}
+
+#[test]
+fn all_praos_vectors_encode_as_byte_strings() {
+    assert_vectors_encode_as_byte_strings::<PraosVRF>("vrf_ver03");
+    assert_vectors_encode_as_byte_strings::<PraosBatchCompatVRF>("vrf_ver13");
+}
+
+#[test]
+fn output_vrf_rejects_wrong_size_and_non_byte_string_encodings() {
+    // 63-byte byte string
+    let mut short = vec![0x58, 63];
+    short.extend(std::iter::repeat_n(0u8, 63));
+    assert!(from_cbor::<OutputVRF<PraosVRF>>(&short).is_err());
+
+    // 65-byte byte string
+    let mut long = vec![0x58, 65];
+    long.extend(std::iter::repeat_n(0u8, 65));
+    assert!(from_cbor::<OutputVRF<PraosVRF>>(&long).is_err());
+
+    // An array of 64 integers, as a derived `Vec<u8>` encoding would produce
+    let output_bytes = hex::decode(PRAOS_OUTPUT).expect("hex");
+    let array = to_cbor(
+        &output_bytes
+            .iter()
+            .map(|b| u16::from(*b))
+            .collect::<Vec<_>>(),
+    );
+    assert!(from_cbor::<OutputVRF<PraosVRF>>(&array).is_err());
+
+    // A byte-string proof of the wrong size is rejected too
+    let mut proof = vec![0x58, 79];
+    proof.extend(std::iter::repeat_n(0u8, 79));
+    assert!(from_cbor::<PraosProof>(&proof).is_err());
+    let mut batch_proof = vec![0x58, 127];
+    batch_proof.extend(std::iter::repeat_n(0u8, 127));
+    assert!(from_cbor::<PraosBatchCompatProof>(&batch_proof).is_err());
+}
@@ -0,0 +1,87 @@
+//! Fixed-size access to VRF outputs and the output-mixing helper.
+
+use cardano_crypto_class::hash::{Blake2b256, HashAlgorithm, Sha256};
+use cardano_crypto_class::seed::mk_seed_from_bytes;
+use cardano_crypto_class::vrf::{
+    MockVRF, NeverVRF, OutputSize, OutputVRF, PraosBatchCompatVRF, PraosVRF, SimpleVRF,
+    VRFAlgorithm,
+};
+use num_bigint::BigUint;
+
+fn praos_output() -> OutputVRF<PraosVRF> {
+    let sk = PraosVRF::gen_key(&mk_seed_from_bytes(vec![9; 32]));
+    PraosVRF::evaluate_bytes(&(), b"slot 42", &sk).0
+}
+
+#[test]
+fn as_array_views_the_output_bytes() {
+    let output = praos_output();
+    let array: &[u8; 64] = output.as_array();
+    assert_eq!(array.as_slice(), output.as_bytes());
+
+    let batch =
+        OutputVRF::<PraosBatchCompatVRF>::copy_from_slice(output.as_bytes()).expect("same size");
+    assert_eq!(batch.as_array::<64>(), array);
+
+    let mock = OutputVRF::<MockVRF>::from_bytes(vec![1; MockVRF::OUTPUT_SIZE]).expect("size");
+    assert_eq!(
+        mock.as_array::<{ MockVRF::OUTPUT_SIZE }>(),
+        &[1; MockVRF::OUTPUT_SIZE]
+    );
+}
+
+#[test]
+fn output_size_impls_match_output_size() {
+    fn check<A: OutputSize<N>, const N: usize>() {
+        assert_eq!(N, A::OUTPUT_SIZE, "{}", A::ALGORITHM_NAME);
+    }
+    check::<PraosVRF, 64>();
+    check::<PraosBatchCompatVRF, 64>();
+    check::<SimpleVRF, 8>();
+    check::<MockVRF, 8>();
+    check::<NeverVRF, 0>();
+}
+
+#[test]
+fn as_array_rejects_the_wrong_size_at_compile_time() {
+    trybuild::TestCases::new().compile_fail("tests/ui/output_vrf_wrong_size.rs");
+}
+
+#[test]
+fn concat_and_hash_matches_manual_hashing() {
+    let output = praos_output();
+    for extra in [&b""[..], b"\x00", &[0xab; 32]] {
+        let mut manual = output.as_bytes().to_vec();
+        manual.extend_from_slice(extra);
+        assert_eq!(
+            output.concat_and_hash::<Blake2b256>(extra),
+            Blake2b256::hash(&manual)
+        );
+        assert_eq!(
+            output.concat_and_hash::<Sha256>(extra),
+            Sha256::hash(&manual)
+        );
+    }
+}
+
+#[test]
+fn byte_and_natural_conversions_are_unchanged() {
+    let output = praos_output();
+    let natural = output.to_natural();
+    assert_eq!(natural, BigUint::from_bytes_be(output.as_bytes()));
+    assert_eq!(
+        OutputVRF::<PraosVRF>::from_natural(&natural).expect("fits"),
+        output
+    );
+
+    let small = OutputVRF::<PraosVRF>::from_natural(&BigUint::from(0x0102u32)).expect("fits");
+    let mut expected = [0u8; 64];
+    expected[62..].copy_from_slice(&[1, 2]);
+    assert_eq!(small.as_array(), &expected);
+
+    assert!(OutputVRF::<PraosVRF>::from_bytes(vec![0; 63]).is_err());
+    assert_eq!(
+        OutputVRF::<PraosVRF>::from_bytes(output.as_bytes().to_vec()).expect("size"),
+        output
+    );
+}
@@ -0,0 +1,56 @@
+use cardano_crypto_class::seed::mk_seed_from_bytes;
+use cardano_crypto_class::vrf::{
+    CertifiedVRF, MockVRF, NeverCertificate, NeverVRF, OutputVRF, PraosBatchCompatVRF, PraosVRF,
+    SimpleVRF, VRFAlgorithm, eval_certified, verify_certified,
+};
+
+const MESSAGE: &[u8] = b"output_from_proof";
+
+#[test]
+fn schemes_without_proof_derived_output_return_none() {
+    assert!(NeverVRF::output_from_proof(&NeverCertificate).is_none());
+
+    let (mock_sk, _) = MockVRF::gen_keypair(&mk_seed_from_bytes(vec![7u8; MockVRF::SEED_SIZE]));
+    let (_, mock_proof) = MockVRF::evaluate_bytes(&(), MESSAGE, &mock_sk);
+    assert!(MockVRF::output_from_proof(&mock_proof).is_none());
+
+    let (simple_sk, _) =
+        SimpleVRF::gen_keypair(&mk_seed_from_bytes(vec![7u8; SimpleVRF::SEED_SIZE]));
+    let (_, simple_proof) = SimpleVRF::evaluate_bytes(&(), MESSAGE, &simple_sk);
+    assert!(SimpleVRF::output_from_proof(&simple_proof).is_none());
+    assert!(CertifiedVRF::<SimpleVRF>::from_proof(simple_proof).is_none());
+}
+
+fn check_certified_round_trip<A>()
+where
+    A: VRFAlgorithm<Context = ()>,
+{
+    let (sk, vk) = A::gen_keypair(&mk_seed_from_bytes(vec![11u8; A::SEED_SIZE]));
+    let certified = eval_certified::<A, [u8]>(&(), MESSAGE, &sk);
+    assert!(verify_certified::<A, [u8]>(&(), &vk, MESSAGE, &certified));
+
+    let proof_bytes = A::raw_serialize_proof(&certified.proof);
+    let proof = A::raw_deserialize_proof(&proof_bytes).expect("proof round-trips");
+    let rebuilt = CertifiedVRF::<A>::from_proof(proof).expect("output derivable from proof");
+    assert_eq!(rebuilt.output, certified.output);
+    assert!(verify_certified::<A, [u8]>(&(), &vk, MESSAGE, &rebuilt));
+
+    let mut tampered_bytes = certified.output.as_bytes().to_vec();
+    tampered_bytes[0] ^= 0x01;
+    let proof = A::raw_deserialize_proof(&proof_bytes).expect("proof round-trips");
+    let tampered = CertifiedVRF::<A>::new(
+        OutputVRF::from_bytes(tampered_bytes).expect("output size"),
+        proof,
+    );
+    assert!(!verify_certified::<A, [u8]>(&(), &vk, MESSAGE, &tampered));
+}
+
+#[test]
+fn praos_certified_vrf_can_be_rebuilt_from_proof() {
+    check_certified_round_trip::<PraosVRF>();
+}
+
+#[test]
+fn praos_batch_certified_vrf_can_be_rebuilt_from_proof() {
+    check_certified_round_trip::<PraosBatchCompatVRF>();
+}
@@ -1,7 +1,10 @@
 use cardano_crypto_class::VRFAlgorithm;
 use cardano_crypto_class::vrf::{
-    PraosBatchCompatProof, PraosBatchCompatSigningKey, PraosBatchCompatVRF,
-    PraosBatchCompatVerificationKey, PraosProof, PraosSigningKey, PraosVRF, PraosVerificationKey,
+    CertifiedVRF, OutputVRF, PraosBatchCompatProof, PraosBatchCompatProvingContext,
+    PraosBatchCompatSigningKey, PraosBatchCompatVRF, PraosBatchCompatVerificationKey, PraosProof,
+    PraosProvingContext, PraosSigningKey, PraosVRF, PraosVerificationKey,
+    PraosVerificationKeyPrepared, VRFError, praos_certified_to_batch_compat,
+    praos_output_from_batch_compat, praos_output_to_batch_compat, verify_praos_output_either,
 };
 use cardano_test_vectors::vrf::{self, TestVector as RawTestVector};
 use std::collections::BTreeMap;
@@ -135,6 +138,175 @@ fn praos_batch_vectors_match_reference() {
     }
 }
 
+#[test]
+fn proving_contexts_match_prove_for_many_messages() {
+    let messages: Vec<Vec<u8>> = (0u32..64).map(|slot| slot.to_be_bytes().to_vec()).collect();
+
+    let praos_sk = PraosVRF::gen_key_from_seed_bytes(&[7u8; 32]);
+    let context = PraosProvingContext::new(&praos_sk).expect("proving context");
+    let proofs = context
+        .prove_many(messages.iter().map(Vec::as_slice))
+        .expect("prove_many");
+    assert_eq!(proofs.len(), messages.len());
+    for (message, proof) in messages.iter().zip(&proofs) {
+        assert_eq!(proof, &praos_sk.prove(message).expect("prove"));
+    }
+
+    let batch_sk = PraosBatchCompatVRF::gen_key_from_seed_bytes(&[7u8; 32]);
+    let context = PraosBatchCompatProvingContext::new(&batch_sk).expect("proving context");
+    let proofs = context
+        .prove_many(messages.iter().map(Vec::as_slice))
+        .expect("prove_many");
+    assert_eq!(proofs.len(), messages.len());
+    for (message, proof) in messages.iter().zip(&proofs) {
+        assert_eq!(
+            proof.as_bytes(),
+            batch_sk.prove(message).expect("prove").as_bytes()
+        );
+    }
+}
+
+#[test]
+fn prepared_keys_verify_every_vector_like_unprepared_keys() {
+    let vectors = load_vectors("vrf_ver03");
+    assert!(!vectors.is_empty(), "no ietfdraft03 test vectors found");
+
+    for vector in vectors {
+        let verifying_key =
+            PraosVerificationKey::from_bytes(&vector.verifying_key).expect("verifying key");
+        let prepared: PraosVerificationKeyPrepared = verifying_key
+            .prepare()
+            .expect("vector keys are curve points");
+        assert_eq!(prepared.verification_key(), verifying_key);
+        assert_eq!(prepared.as_bytes(), vector.verifying_key.as_slice());
+
+        let proof = PraosProof::from_bytes(&vector.proof).expect("proof");
+        let output = prepared.verify(&vector.message, &proof).expect("verify");
+        assert_eq!(
+            output.as_deref(),
+            Some(vector.output.as_slice()),
+            "{}: prepared output mismatch",
+            vector.name
+        );
+        assert_eq!(
+            output,
+            verifying_key
+                .verify(&vector.message, &proof)
+                .expect("verify"),
+            "{}",
+            vector.name
+        );
+
+        let mut tampered = vector.message.clone();
+        tampered.push(0);
+        assert_eq!(prepared.verify(&tampered, &proof).expect("verify"), None);
+        assert_eq!(
+            verifying_key.verify(&tampered, &proof).expect("verify"),
+            None
+        );
+    }
+}
+
+#[test]
+fn invalid_keys_fail_at_prepare() {
+    // y = 2 is not the y-coordinate of any curve point.
+    let mut bytes = [0u8; 32];
+    bytes[0] = 2;
+    let verifying_key = PraosVerificationKey::from_bytes(&bytes).expect("right length");
+    let err = verifying_key.prepare().expect_err("not a curve point");
+    assert_eq!(err.code(), "praos.vrf");
+
+    // The unprepared key only finds out when verifying, and rejects the proof.
+    let vector = load_vectors("vrf_ver03_standard_10")
+        .pop()
+        .expect("embedded vector should exist");
+    let proof = PraosProof::from_bytes(&vector.proof).expect("proof");
+    assert_eq!(
+        verifying_key
+            .verify(&vector.message, &proof)
+            .expect("verify"),
+        None
+    );
+}
+
+#[cfg(feature = "mlocked-metrics")]
+#[test]
+fn proving_contexts_zeroize_on_drop() {
+    use cardano_crypto_class::mlocked_metrics;
+
+    let praos_sk = PraosVRF::gen_key_from_seed_bytes(&[8u8; 32]);
+    let context = PraosProvingContext::new(&praos_sk).expect("proving context");
+    let before = mlocked_metrics::snapshot().zeroizations;
+    drop(context);
+    assert!(mlocked_metrics::snapshot().zeroizations > before);
+
+    let batch_sk = PraosBatchCompatVRF::gen_key_from_seed_bytes(&[8u8; 32]);
+    let context = PraosBatchCompatProvingContext::new(&batch_sk).expect("proving context");
+    let before = mlocked_metrics::snapshot().zeroizations;
+    drop(context);
+    assert!(mlocked_metrics::snapshot().zeroizations > before);
+}
+
+#[test]
+fn either_verifier_dispatches_on_proof_length() {
+    for prefix in ["vrf_ver03_standard_10", "vrf_ver13_standard_10"] {
+        let vector = load_vectors(prefix)
+            .pop()
+            .expect("embedded vector should exist");
+        let output =
+            verify_praos_output_either(&vector.verifying_key, &vector.message, &vector.proof)
+                .map_err(|err| format!("{}: {err}", vector.name))
+                .expect("either-verifier should accept the vector");
+        assert_eq!(
+            output.as_slice(),
+            vector.output.as_slice(),
+            "{}",
+            vector.name
+        );
+
+        let mut tampered = vector.message.clone();
+        tampered.push(0);
+        assert_eq!(
+            verify_praos_output_either(&vector.verifying_key, &tampered, &vector.proof),
+            Err(VRFError::VerificationFailed),
+            "{}: tampered message",
+            vector.name
+        );
+    }
+
+    let vector = load_vectors("vrf_ver03_standard_10")
+        .pop()
+        .expect("embedded vector should exist");
+    assert!(matches!(
+        verify_praos_output_either(&vector.verifying_key, &vector.message, &vector.proof[..79]),
+        Err(VRFError::WrongLength { actual: 79, .. })
+    ));
+    assert!(matches!(
+        verify_praos_output_either(&vector.verifying_key[..31], &vector.message, &vector.proof),
+        Err(VRFError::WrongLength { actual: 31, .. })
+    ));
+}
+
+#[test]
+fn output_conversions_preserve_bytes() {
+    let bytes: Vec<u8> = (0..64).collect();
+    let output = OutputVRF::<PraosVRF>::from_bytes(bytes.clone()).expect("64-byte output");
+    let batch = praos_output_to_batch_compat(&output).expect("to batch-compat");
+    assert_eq!(batch.as_bytes(), bytes.as_slice());
+    let back = praos_output_from_batch_compat(&batch).expect("from batch-compat");
+    assert_eq!(back, output);
+}
+
+#[test]
+fn certified_outputs_do_not_convert() {
+    let vector = load_vectors("vrf_ver03_standard_10")
+        .pop()
+        .expect("embedded vector should exist");
+    let proof = PraosProof::from_bytes(&vector.proof).expect("proof");
+    let certified = CertifiedVRF::<PraosVRF>::from_proof(proof).expect("output from proof");
+    assert!(praos_certified_to_batch_compat(certified).is_none());
+}
+
 fn run_praos_vector(vector: &TestVector) {
     let signing_key_bytes = extend_praos_signing_key(&vector.signing_key, &vector.verifying_key);
     let signing_key = PraosSigningKey::from_bytes(&signing_key_bytes)
@@ -163,6 +335,17 @@ fn run_praos_vector(vector: &TestVector) {
         vector.name
     );
 
+    let context = PraosProvingContext::new(&signing_key).expect("proving context");
+    let context_proof = context
+        .prove_with_ctx(&vector.message)
+        .expect("context proof generation should succeed");
+    assert_eq!(
+        context_proof.as_bytes(),
+        vector.proof.as_slice(),
+        "{}: prove_with_ctx mismatch",
+        vector.name
+    );
+
     let proof_from_bytes = PraosProof::from_bytes(&vector.proof)
         .map_err(|err| format!("{}: proof_from_bytes failed: {err}", vector.name))
         .expect("Praos proof decoding should succeed");
@@ -217,6 +400,14 @@ fn run_praos_vector(vector: &TestVector) {
         "{}: VRF verification output",
         vector.name
     );
+
+    let unverified = PraosVRF::output_from_proof(&proof_from_bytes)
+        .expect("Praos output_from_proof should return Some");
+    assert_eq!(
+        unverified, verified,
+        "{}: output_from_proof must match verify output",
+        vector.name
+    );
 }
 
 fn run_praos_batch_vector(vector: &TestVector) {
@@ -252,6 +443,17 @@ fn run_praos_batch_vector(vector: &TestVector) {
         vector.name
     );
 
+    let context = PraosBatchCompatProvingContext::new(&signing_key).expect("proving context");
+    let context_proof = context
+        .prove_with_ctx(&vector.message)
+        .expect("context proof generation should succeed");
+    assert_eq!(
+        context_proof.as_bytes(),
+        vector.proof.as_slice(),
+        "{}: prove_with_ctx mismatch",
+        vector.name
+    );
+
     let proof_from_bytes = PraosBatchCompatProof::from_bytes(&vector.proof)
         .map_err(|err| format!("{}: proof_from_bytes failed: {err}", vector.name))
         .expect("Praos batch proof decoding should succeed");
@@ -307,6 +509,14 @@ fn run_praos_batch_vector(vector: &TestVector) {
         "{}: VRF verification output",
         vector.name
     );
+
+    let unverified = PraosBatchCompatVRF::output_from_proof(&proof_from_bytes)
+        .expect("Praos batch output_from_proof should return Some");
+    assert_eq!(
+        unverified, verified,
+        "{}: output_from_proof must match verify output",
+        vector.name
+    );
 }
 
 fn extend_praos_signing_key(signing_key: &[u8], verifying_key: &[u8]) -> Vec<u8> {
@@ -0,0 +1,54 @@
+//! Scoped access to Praos VRF seeds: `with_raw_seed` borrows the seed from
+//! the signing key's mlocked memory and matches what the deprecated
+//! `praos_batch_unsafe_raw_seed` copied out.
+
+use cardano_crypto_class::vrf::{
+    praos_batch_keypair_from_seed_bytes, praos_batch_with_raw_seed, praos_keypair_from_seed_bytes,
+    praos_with_raw_seed,
+};
+
+const SEED: [u8; 32] = [
+    0x9d, 0x61, 0xb1, 0x9d, 0xef, 0xfd, 0x5a, 0x60, 0xba, 0x84, 0x4a, 0xf4, 0x92, 0xec, 0x2c, 0xc4,
+    0x44, 0x49, 0xc5, 0x69, 0x7b, 0x32, 0x69, 0x19, 0x70, 0x3b, 0xac, 0x03, 0x1c, 0xae, 0x7f, 0x60,
+];
+
+#[test]
+#[allow(deprecated)]
+fn batch_scoped_seed_matches_unsafe_raw_seed() {
+    use cardano_crypto_class::vrf::praos_batch_unsafe_raw_seed;
+
+    let (_, sk) = praos_batch_keypair_from_seed_bytes(&SEED).expect("keypair");
+    let old = praos_batch_unsafe_raw_seed(&sk.to_seed().expect("seed"));
+    let scoped = praos_batch_with_raw_seed(&sk, <[u8]>::to_vec);
+    assert_eq!(scoped, old);
+    assert_eq!(scoped, SEED);
+}
+
+#[test]
+fn scoped_seed_is_the_key_generation_seed() {
+    let (_, sk) = praos_keypair_from_seed_bytes(&SEED).expect("keypair");
+    assert!(praos_with_raw_seed(&sk, |seed| seed == SEED));
+    assert_eq!(praos_with_raw_seed(&sk, <[u8]>::len), 32);
+
+    let seed = sk.to_seed().expect("seed");
+    assert!(praos_with_raw_seed(&sk, |raw| raw == seed.as_bytes()));
+}
+
+#[test]
+fn scoped_seed_rederives_the_same_key() {
+    let (vk, sk) = praos_batch_keypair_from_seed_bytes(&SEED).expect("keypair");
+    let (again, _) =
+        praos_batch_with_raw_seed(&sk, praos_batch_keypair_from_seed_bytes).expect("keypair");
+    assert_eq!(again.as_bytes(), vk.as_bytes());
+}
+
+#[cfg(feature = "tooling")]
+#[test]
+fn tooling_copies_match_borrowed_bytes() {
+    use cardano_crypto_class::vrf::{praos_batch_seed_to_bytes, praos_batch_signing_key_to_bytes};
+
+    let (_, sk) = praos_batch_keypair_from_seed_bytes(&SEED).expect("keypair");
+    assert_eq!(praos_batch_signing_key_to_bytes(&sk), sk.as_bytes());
+    let seed = sk.to_seed().expect("seed");
+    assert_eq!(praos_batch_seed_to_bytes(&seed), seed.as_bytes());
+}
@@ -0,0 +1,61 @@
+use cardano_crypto_class::vrf::{
+    PraosBatchCompatSeed, PraosSeed, praos_batch_gen_seed_with, praos_batch_keypair_from_seed,
+    praos_batch_proof_to_bytes, praos_batch_verification_key_to_bytes, praos_gen_seed_with,
+    praos_keypair_from_seed, praos_proof_to_bytes, praos_verification_key_to_bytes,
+};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+#[test]
+fn praos_seed_and_keys_are_reproducible_with_seeded_rng() {
+    let first = praos_gen_seed_with(&mut StdRng::seed_from_u64(42)).expect("seed");
+    let second = PraosSeed::generate_with(&mut StdRng::seed_from_u64(42)).expect("seed");
+    assert_eq!(first.as_bytes(), second.as_bytes());
+
+    let other = praos_gen_seed_with(&mut StdRng::seed_from_u64(43)).expect("seed");
+    assert_ne!(first.as_bytes(), other.as_bytes());
+
+    let (vk_a, sk_a) = praos_keypair_from_seed(&first).expect("keypair");
+    let (vk_b, sk_b) = praos_keypair_from_seed(&second).expect("keypair");
+    assert_eq!(
+        praos_verification_key_to_bytes(&vk_a),
+        praos_verification_key_to_bytes(&vk_b)
+    );
+    assert_eq!(sk_a.as_bytes(), sk_b.as_bytes());
+
+    let proof_a = sk_a.prove(b"seeded").expect("proof");
+    let proof_b = sk_b.prove(b"seeded").expect("proof");
+    assert_eq!(
+        praos_proof_to_bytes(&proof_a),
+        praos_proof_to_bytes(&proof_b)
+    );
+}
+
+#[test]
+fn praos_batch_seed_and_keys_are_reproducible_with_seeded_rng() {
+    let first = praos_batch_gen_seed_with(&mut StdRng::seed_from_u64(42)).expect("seed");
+    let second = PraosBatchCompatSeed::generate_with(&mut StdRng::seed_from_u64(42)).expect("seed");
+    assert_eq!(first.as_bytes(), second.as_bytes());
+
+    let (vk_a, sk_a) = praos_batch_keypair_from_seed(&first).expect("keypair");
+    let (vk_b, sk_b) = praos_batch_keypair_from_seed(&second).expect("keypair");
+    assert_eq!(
+        praos_batch_verification_key_to_bytes(&vk_a),
+        praos_batch_verification_key_to_bytes(&vk_b)
+    );
+    assert_eq!(sk_a.as_bytes(), sk_b.as_bytes());
+
+    let proof_a = sk_a.prove(b"seeded").expect("proof");
+    let proof_b = sk_b.prove(b"seeded").expect("proof");
+    assert_eq!(
+        praos_batch_proof_to_bytes(&proof_a),
+        praos_batch_proof_to_bytes(&proof_b)
+    );
+}
+
+#[test]
+fn praos_and_praos_batch_draw_the_same_seed_bytes() {
+    let praos = praos_gen_seed_with(&mut StdRng::seed_from_u64(42)).expect("seed");
+    let batch = praos_batch_gen_seed_with(&mut StdRng::seed_from_u64(42)).expect("seed");
+    assert_eq!(praos.as_bytes(), batch.as_bytes());
+}
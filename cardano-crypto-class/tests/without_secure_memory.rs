@@ -0,0 +1,136 @@
+//! Builds without the `secure-memory` feature, such as wasm32 ones, must keep
+//! verification, raw deserialisation and CBOR working, while anything that
+//! needs mlocked memory fails with `MLockedError::Unsupported`.
+//!
+//! Run with `cargo test -p cardano-crypto-class --no-default-features
+//! --features serde --test without_secure_memory`.
+
+#![cfg(not(feature = "secure-memory"))]
+
+use cardano_crypto_class::dsign::DsignAlgorithm;
+use cardano_crypto_class::dsign::ed25519::Ed25519;
+use cardano_crypto_class::kes::{KesAlgorithm, KesMError, Sum2Kes};
+use cardano_crypto_class::mlocked_bytes::{MLockedBytes, MLockedError, preflight_check};
+use cardano_crypto_class::mlocked_seed::MLockedSeed;
+use cardano_crypto_class::seed::{mk_seed_from_bytes, read_seed_from_system_entropy};
+use cardano_crypto_class::vrf::{
+    PraosProof, PraosSigningKey, PraosVRF, PraosVerificationKey, VRFAlgorithm,
+};
+use cardano_test_vectors::{kes, vrf};
+
+fn assert_unsupported(err: &MLockedError) {
+    assert!(matches!(err, MLockedError::Unsupported), "{err:?}");
+    assert_eq!(err.code(), "mlocked.unsupported");
+    assert!(!err.is_transient());
+}
+
+#[test]
+fn mlocked_allocations_are_unsupported() {
+    assert_unsupported(&MLockedBytes::new(32).err().expect("no allocator"));
+    assert_unsupported(&MLockedBytes::new_zeroed(0).err().expect("no allocator"));
+    assert_unsupported(&MLockedSeed::<32>::new_zeroed().err().expect("no allocator"));
+    assert_unsupported(&MLockedSeed::<32>::new_random().err().expect("no allocator"));
+    assert_unsupported(&preflight_check(4096).expect_err("no allocator"));
+}
+
+#[test]
+fn system_entropy_still_produces_seeds() {
+    let seed = read_seed_from_system_entropy(32);
+    assert_eq!(seed.len(), 32);
+}
+
+#[test]
+fn ed25519_heap_keys_sign_and_verify() {
+    let signing_key = Ed25519::gen_key(&mk_seed_from_bytes(vec![7; 32]));
+    let verification_key = Ed25519::derive_verification_key(&signing_key);
+    let signature = Ed25519::sign_bytes(&(), b"browser", &signing_key);
+    Ed25519::verify_bytes(&(), &verification_key, b"browser", &signature).expect("valid");
+    assert!(Ed25519::verify_bytes(&(), &verification_key, b"other", &signature).is_err());
+
+    let raw = Ed25519::raw_serialize_verification_key(&verification_key);
+    assert_eq!(
+        Ed25519::raw_deserialize_verification_key(&raw),
+        Some(verification_key)
+    );
+}
+
+#[test]
+fn sum_kes_signatures_verify_from_vectors() {
+    let fixture = kes::get("sum_kes_test_vectors.json").expect("embedded sum KES vectors");
+    let parsed: serde_json::Value = serde_json::from_str(fixture).expect("valid sum KES JSON");
+    let level = parsed["levels"]
+        .as_array()
+        .expect("levels")
+        .iter()
+        .find(|level| level["level"].as_u64() == Some(2))
+        .expect("level 2 vectors");
+
+    let mut verified = 0;
+    for vector in level["vectors"].as_array().expect("vectors") {
+        let vk_bytes = hex::decode(vector["verification_key"].as_str().expect("vk")).expect("hex");
+        let vk = Sum2Kes::raw_deserialize_verification_key_kes(&vk_bytes).expect("vk");
+        for tracked in vector["tracked_periods"].as_array().expect("periods") {
+            let period = tracked["period"].as_u64().expect("period");
+            let message = hex::decode(tracked["message"].as_str().expect("message")).expect("hex");
+            let signature_bytes =
+                hex::decode(tracked["raw_signature"].as_str().expect("signature")).expect("hex");
+            let signature =
+                Sum2Kes::raw_deserialize_signature_kes(&signature_bytes).expect("signature");
+
+            Sum2Kes::verify_kes(&(), &vk, period, &message, &signature).expect("valid");
+            assert!(Sum2Kes::verify_kes(&(), &vk, period, b"tampered", &signature).is_err());
+            verified += 1;
+        }
+    }
+    assert!(verified > 0);
+
+    // The Ed25519 leaf reports its mlocked failure through the DSIGN variant.
+    match Sum2Kes::gen_key_kes_from_seed_bytes(&[1; 32]).err() {
+        Some(KesMError::Mlocked(err)) => assert_unsupported(&err),
+        Some(KesMError::Dsign(message)) => assert!(message.contains("Unsupported"), "{message}"),
+        other => unreachable!("KES signing keys need secure memory: {other:?}"),
+    }
+}
+
+#[test]
+fn praos_proofs_verify_from_vectors() {
+    let vector = vrf::ALL
+        .iter()
+        .find(|vector| vector.name == "vrf_ver03_standard_10")
+        .expect("embedded Praos vector")
+        .parse()
+        .expect("parsable Praos vector");
+    let verification_key =
+        PraosVerificationKey::from_bytes(&vector.public_key).expect("verification key");
+    let proof = PraosProof::from_bytes(&vector.proof).expect("proof");
+
+    let output = verification_key
+        .verify(&vector.alpha, &proof)
+        .expect("verification")
+        .expect("valid proof");
+    assert_eq!(output, vector.output);
+    assert_eq!(
+        PraosVRF::output_from_proof(&proof).map(|output| output.as_bytes().to_vec()),
+        Some(output)
+    );
+
+    let err = PraosSigningKey::from_bytes(&[0; 64]).expect_err("needs mlocked memory");
+    assert_eq!(err.code(), "mlocked.unsupported");
+    assert!(!err.is_transient());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn verification_keys_round_trip_through_cbor() {
+    let signing_key = Ed25519::gen_key(&mk_seed_from_bytes(vec![9; 32]));
+    let verification_key = Ed25519::derive_verification_key(&signing_key);
+    let signature = Ed25519::sign_bytes(&(), b"cbor", &signing_key);
+
+    let vk_cbor = cardano_binary::serialize(&verification_key).expect("encode");
+    let sig_cbor = cardano_binary::serialize(&signature).expect("encode");
+    let decoded_vk: <Ed25519 as DsignAlgorithm>::VerificationKey =
+        cardano_binary::decode_full(&vk_cbor).expect("decode");
+    let decoded_sig: <Ed25519 as DsignAlgorithm>::Signature =
+        cardano_binary::decode_full(&sig_cbor).expect("decode");
+    Ed25519::verify_bytes(&(), &decoded_vk, b"cbor", &decoded_sig).expect("valid");
+}
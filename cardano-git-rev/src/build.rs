@@ -0,0 +1,262 @@
+//! Build-script support for embedding the *consumer's* git revision.
+//!
+//! [`git_rev`](crate::git_rev) reports the revision embedded when this
+//! library was compiled, which for a downstream binary is the revision of
+//! whichever checkout of `cardano-git-rev` cargo built, not the binary's own.
+//! A crate that wants its own revision adds `cardano-git-rev` as a
+//! build-dependency, calls [`emit_git_rev_env`] from its `build.rs`, and reads
+//! the result with [`git_rev_this_crate!`](crate::git_rev_this_crate):
+//!
+//! ```no_run
+//! // In the `main` function of build.rs:
+//! cardano_git_rev::build::emit_git_rev_env();
+//! ```
+//!
+//! The revision is resolved in this order:
+//!
+//! 1. `CARDANO_GIT_REV` from the environment, if it is a real revision.
+//! 2. `.cargo_vcs_info.json` in the package root. Cargo writes this file when
+//!    packaging, so it is present for crates built from a registry (e.g. via
+//!    `cargo install`), where any repository found by `git` would belong to
+//!    something else.
+//! 3. `git rev-parse --verify HEAD` run in the package root. Linked worktrees
+//!    and shallow clones resolve like any other checkout.
+//! 4. The all-zero placeholder, with a `cargo:warning` explaining why.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{GitRevError, ZERO_REV, is_real_git_rev, sanitize_git_rev};
+
+/// Name of the environment variable that carries the revision, both as a
+/// build-time override and as the `rustc-env` emitted for the crate.
+pub const GIT_REV_ENV: &str = "CARDANO_GIT_REV";
+
+/// Where a [`ResolvedGitRev`] came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GitRevSource {
+    /// The `CARDANO_GIT_REV` environment variable.
+    Environment,
+    /// The `.cargo_vcs_info.json` file of a packaged crate.
+    VcsInfo,
+    /// `git rev-parse` in the package root.
+    Git,
+    /// No revision was found; the all-zero placeholder is used.
+    Placeholder,
+}
+
+/// The outcome of resolving a package's revision in a build script.
+#[derive(Debug)]
+pub struct ResolvedGitRev {
+    /// The sanitised 40-character revision, or the all-zero placeholder.
+    pub rev: String,
+    /// Which step of the lookup produced `rev`.
+    pub source: GitRevSource,
+    /// Files whose modification should re-run the build script.
+    pub watch: Vec<PathBuf>,
+    /// Why the git lookup failed, when `source` is
+    /// [`GitRevSource::Placeholder`].
+    pub error: Option<GitRevError>,
+}
+
+impl ResolvedGitRev {
+    /// The `cargo:` directives a build script should print for this result.
+    #[must_use]
+    pub fn cargo_directives(&self) -> Vec<String> {
+        let mut lines = vec![format!("cargo:rerun-if-env-changed={GIT_REV_ENV}")];
+        lines.extend(
+            self.watch
+                .iter()
+                .map(|path| format!("cargo:rerun-if-changed={}", path.display())),
+        );
+        if let Some(error) = &self.error {
+            let message = error.to_string().replace('\n', " ");
+            lines.push(format!("cargo:warning=cardano-git-rev: {}", message.trim()));
+        }
+        lines.push(format!("cargo:rustc-env={GIT_REV_ENV}={}", self.rev));
+        lines
+    }
+}
+
+/// Resolve the revision for the package rooted at `manifest_dir`.
+///
+/// `env_override` is the value of `CARDANO_GIT_REV`, if set; it is ignored
+/// unless it sanitises to a real revision.
+#[must_use]
+pub fn resolve_git_rev(manifest_dir: &Path, env_override: Option<&str>) -> ResolvedGitRev {
+    let resolved = |rev, source, watch| ResolvedGitRev {
+        rev,
+        source,
+        watch,
+        error: None,
+    };
+
+    if let Some(rev) = env_override
+        .map(sanitize_git_rev)
+        .filter(|rev| is_real_git_rev(rev))
+    {
+        return resolved(rev, GitRevSource::Environment, Vec::new());
+    }
+
+    let vcs_info = manifest_dir.join(".cargo_vcs_info.json");
+    if let Ok(contents) = fs::read_to_string(&vcs_info) {
+        if let Some(rev) = vcs_info_sha1(&contents) {
+            return resolved(rev, GitRevSource::VcsInfo, vec![vcs_info]);
+        }
+    }
+
+    match git_head(manifest_dir) {
+        Ok(rev) => resolved(rev, GitRevSource::Git, git_watch_paths(manifest_dir)),
+        Err(error) => ResolvedGitRev {
+            rev: ZERO_REV.to_string(),
+            source: GitRevSource::Placeholder,
+            watch: Vec::new(),
+            error: Some(error),
+        },
+    }
+}
+
+/// Resolve the calling package's revision and print the `cargo:` directives
+/// that embed it as `CARDANO_GIT_REV`.
+///
+/// Call this from `build.rs`; the package root is taken from
+/// `CARGO_MANIFEST_DIR`. Failures never abort the build: they produce a
+/// `cargo:warning` and embed the all-zero placeholder.
+pub fn emit_git_rev_env() {
+    let manifest_dir =
+        env::var_os("CARGO_MANIFEST_DIR").map_or_else(|| PathBuf::from("."), PathBuf::from);
+    let env_override = env::var(GIT_REV_ENV).ok();
+    for line in resolve_git_rev(&manifest_dir, env_override.as_deref()).cargo_directives() {
+        println!("{line}");
+    }
+}
+
+/// Extract `git.sha1` from the contents of a `.cargo_vcs_info.json` file.
+///
+/// Returns `None` unless the value is a real 40-character revision.
+#[must_use]
+pub fn vcs_info_sha1(contents: &str) -> Option<String> {
+    let after_key = &contents[contents.find("\"sha1\"")? + "\"sha1\"".len()..];
+    let value = after_key.trim_start().strip_prefix(':')?.trim_start();
+    let value = value.strip_prefix('"')?;
+    let rev = sanitize_git_rev(&value[..value.find('"')?]);
+    is_real_git_rev(&rev).then_some(rev)
+}
+
+fn git_in(dir: &Path, args: &[&str]) -> Result<String, GitRevError> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(GitRevError::Spawn)?;
+    if output.status.success() {
+        String::from_utf8(output.stdout).map_err(GitRevError::Utf8)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(GitRevError::Command(stderr.trim().to_string()))
+    }
+}
+
+fn git_head(dir: &Path) -> Result<String, GitRevError> {
+    let raw = git_in(dir, &["rev-parse", "--verify", "HEAD"])?;
+    let rev = sanitize_git_rev(&raw);
+    if is_real_git_rev(&rev) {
+        Ok(rev)
+    } else {
+        Err(GitRevError::Invalid(raw.trim().to_string()))
+    }
+}
+
+/// `HEAD`, the branch it points at, and `packed-refs`, so a new commit or a
+/// checkout re-runs the build script. In a linked worktree `HEAD` lives in
+/// the worktree's git dir while refs live in the common dir. Only existing
+/// files are returned, since cargo re-runs unconditionally for missing ones.
+fn git_watch_paths(dir: &Path) -> Vec<PathBuf> {
+    let path_of = |args: &[&str]| git_in(dir, args).ok().map(|out| dir.join(out.trim()));
+    let Some(git_dir) = path_of(&["rev-parse", "--git-dir"]) else {
+        return Vec::new();
+    };
+    let common_dir = path_of(&["rev-parse", "--git-common-dir"]).unwrap_or_else(|| git_dir.clone());
+
+    let mut paths = vec![git_dir.join("HEAD"), common_dir.join("packed-refs")];
+    if let Ok(head_ref) = git_in(dir, &["symbolic-ref", "-q", "HEAD"]) {
+        paths.push(common_dir.join(head_ref.trim()));
+    }
+    paths.retain(|path| path.is_file());
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REV: &str = "0123456789abcdef0123456789abcdef01234567";
+
+    #[test]
+    fn parses_vcs_info() {
+        let contents = format!(
+            "{{\n  \"git\": {{\n    \"sha1\": \"{REV}\"\n  }},\n  \"path_in_vcs\": \"cardano-git-rev\"\n}}"
+        );
+        assert_eq!(vcs_info_sha1(&contents).as_deref(), Some(REV));
+        assert_eq!(
+            vcs_info_sha1(&format!(
+                "{{\"git\":{{\"sha1\":\"{REV}\",\"dirty\":true}}}}"
+            ))
+            .as_deref(),
+            Some(REV)
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_vcs_info() {
+        assert_eq!(vcs_info_sha1("{}"), None);
+        assert_eq!(vcs_info_sha1("{\"git\":{\"sha1\":\"abc\"}}"), None);
+        assert_eq!(vcs_info_sha1(&format!("{{\"sha1\":\"{ZERO_REV}\"}}")), None);
+        assert_eq!(vcs_info_sha1("{\"sha1\": 12}"), None);
+        assert_eq!(vcs_info_sha1("{\"sha1\": \"unterminated"), None);
+    }
+
+    #[test]
+    fn environment_override_wins_when_real() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let resolved = resolve_git_rev(dir.path(), Some(&format!("  {REV}\n")));
+        assert_eq!(resolved.rev, REV);
+        assert_eq!(resolved.source, GitRevSource::Environment);
+    }
+
+    #[test]
+    fn invalid_environment_override_is_ignored() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            dir.path().join(".cargo_vcs_info.json"),
+            format!("{{\"git\":{{\"sha1\":\"{REV}\"}}}}"),
+        )
+        .expect("write vcs info");
+        let resolved = resolve_git_rev(dir.path(), Some("not-a-rev"));
+        assert_eq!(resolved.rev, REV);
+        assert_eq!(resolved.source, GitRevSource::VcsInfo);
+    }
+
+    #[test]
+    fn directives_end_with_rustc_env() {
+        let resolved = ResolvedGitRev {
+            rev: ZERO_REV.to_string(),
+            source: GitRevSource::Placeholder,
+            watch: vec![PathBuf::from("/repo/.git/HEAD")],
+            error: Some(GitRevError::Command("fatal: not a git repository\n".into())),
+        };
+        assert_eq!(
+            resolved.cargo_directives(),
+            vec![
+                "cargo:rerun-if-env-changed=CARDANO_GIT_REV".to_string(),
+                "cargo:rerun-if-changed=/repo/.git/HEAD".to_string(),
+                "cargo:warning=cardano-git-rev: git rev-parse failed: fatal: not a git repository"
+                    .to_string(),
+                format!("cargo:rustc-env=CARDANO_GIT_REV={ZERO_REV}"),
+            ]
+        );
+    }
+}
@@ -4,6 +4,8 @@ use std::{
     sync::{Mutex, OnceLock},
 };
 
+pub mod build;
+
 const ZERO_REV: &str = "0000000000000000000000000000000000000000";
 
 /// Expose the git revision associated with this build.
@@ -26,6 +28,42 @@ pub fn git_rev() -> Cow<'static, str> {
     }
 }
 
+/// The git revision embedded in the calling crate.
+///
+/// Expands to a call to [`git_rev_with_embedded`] with the calling crate's
+/// compile-time `CARDANO_GIT_REV`, as set by a build script that runs
+/// [`build::emit_git_rev_env`]. Unlike [`git_rev`], which reports the
+/// revision this library was built from, the result describes the crate the
+/// macro is written in.
+#[macro_export]
+macro_rules! git_rev_this_crate {
+    () => {
+        $crate::git_rev_with_embedded(::core::option_env!("CARDANO_GIT_REV"))
+    };
+}
+
+/// Resolve a revision from an embedded value with the same fallbacks as
+/// [`git_rev`]: a real `embedded` revision is returned as is, otherwise `git`
+/// is queried at runtime, and failures warn once and yield the all-zero
+/// revision.
+#[must_use]
+pub fn git_rev_with_embedded(embedded: Option<&str>) -> Cow<'static, str> {
+    if let Some(rev) = embedded
+        .map(sanitize_git_rev)
+        .filter(|rev| is_real_git_rev(rev))
+    {
+        return Cow::Owned(rev);
+    }
+
+    match git_rev_runtime() {
+        Ok(rev) => Cow::Owned(rev),
+        Err(err) => {
+            emit_warning_once(&err);
+            Cow::Borrowed(ZERO_REV)
+        },
+    }
+}
+
 fn git_rev_embedded() -> Option<String> {
     let stored = {
         let lock = embedded_revision_store()
@@ -34,7 +72,7 @@ fn git_rev_embedded() -> Option<String> {
         lock.clone()
     };
 
-    if is_real_rev(&stored) {
+    if is_real_git_rev(&stored) {
         return Some(stored);
     }
 
@@ -43,7 +81,7 @@ fn git_rev_embedded() -> Option<String> {
         emit_warning_once(&GitRevError::Invalid(stored));
     }
 
-    if is_real_rev(fallback) {
+    if is_real_git_rev(fallback) {
         Some(fallback.to_string())
     } else {
         None
@@ -55,8 +93,8 @@ fn git_rev_runtime() -> Result<String, GitRevError> {
 
     if output.status.success() {
         let raw = String::from_utf8(output.stdout).map_err(GitRevError::Utf8)?;
-        let sanitized = sanitize(&raw);
-        if is_real_rev(&sanitized) {
+        let sanitized = sanitize_git_rev(&raw);
+        if is_real_git_rev(&sanitized) {
             Ok(sanitized)
         } else {
             Err(GitRevError::Invalid(raw.trim().to_string()))
@@ -67,13 +105,19 @@ fn git_rev_runtime() -> Result<String, GitRevError> {
     }
 }
 
-fn is_real_rev(input: &str) -> bool {
+/// Whether `input` is a real revision: 40 hexadecimal characters and not the
+/// all-zero placeholder.
+#[must_use]
+pub fn is_real_git_rev(input: &str) -> bool {
     input != ZERO_REV && input.len() == 40 && input.chars().all(|c| c.is_ascii_hexdigit())
 }
 
-fn sanitize(input: &str) -> String {
+/// Trim surrounding whitespace from `input`, replacing anything that is not
+/// a real revision with the all-zero placeholder.
+#[must_use]
+pub fn sanitize_git_rev(input: &str) -> String {
     let trimmed = input.trim();
-    if is_real_rev(trimmed) {
+    if is_real_git_rev(trimmed) {
         trimmed.to_string()
     } else {
         ZERO_REV.to_string()
@@ -203,15 +247,31 @@ mod tests {
 
     #[test]
     fn detects_real_rev() {
-        assert!(is_real_rev("0123456789abcdef0123456789abcdef01234567"));
-        assert!(!is_real_rev(ZERO_REV));
-        assert!(!is_real_rev("foo"));
+        assert!(is_real_git_rev("0123456789abcdef0123456789abcdef01234567"));
+        assert!(!is_real_git_rev(ZERO_REV));
+        assert!(!is_real_git_rev("foo"));
     }
 
     #[test]
     fn sanitize_invalid_input() {
-        assert_eq!(sanitize("not-a-sha"), ZERO_REV);
-        assert_eq!(sanitize(&format!("{}\n", ZERO_REV)), ZERO_REV);
+        assert_eq!(sanitize_git_rev("not-a-sha"), ZERO_REV);
+        assert_eq!(sanitize_git_rev(&format!("{}\n", ZERO_REV)), ZERO_REV);
+    }
+
+    #[test]
+    fn sanitize_trims_real_revisions() {
+        let rev = "0123456789ABCDEF0123456789abcdef01234567";
+        assert_eq!(sanitize_git_rev(&format!(" {rev}\r\n")), rev);
+        assert_eq!(sanitize_git_rev(""), ZERO_REV);
+        assert_eq!(sanitize_git_rev(&format!("{rev}0")), ZERO_REV);
+        assert_eq!(sanitize_git_rev(&rev.replace('0', "g")), ZERO_REV);
+    }
+
+    #[test]
+    fn embedded_value_is_preferred_over_runtime_git() {
+        let rev = "89abcdef0123456789abcdef0123456701234567";
+        assert_eq!(git_rev_with_embedded(Some(rev)), rev);
+        assert_eq!(git_rev_with_embedded(Some(&format!("{rev}\n"))), rev);
     }
 
     #[test]
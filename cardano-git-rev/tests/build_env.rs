@@ -0,0 +1,213 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use cardano_git_rev::build::{GitRevSource, resolve_git_rev};
+
+const ZERO_REV: &str = "0000000000000000000000000000000000000000";
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args([
+            "-c",
+            "user.name=fixture",
+            "-c",
+            "user.email=fixture@example.com",
+        ])
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .expect("spawn git");
+    assert!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout)
+        .expect("utf8")
+        .trim()
+        .to_string()
+}
+
+/// A fresh repository with one commit, returning its HEAD.
+fn init_repo(dir: &Path) -> String {
+    git(dir, &["init", "-q"]);
+    git(dir, &["commit", "-q", "--allow-empty", "-m", "fixture"]);
+    git(dir, &["rev-parse", "HEAD"])
+}
+
+#[test]
+fn resolves_repository_head() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let head = init_repo(dir.path());
+
+    let resolved = resolve_git_rev(dir.path(), None);
+    assert_eq!(resolved.rev, head);
+    assert_eq!(resolved.source, GitRevSource::Git);
+    assert!(resolved.error.is_none());
+    assert!(
+        resolved
+            .watch
+            .iter()
+            .any(|path| path.ends_with(".git/HEAD"))
+    );
+}
+
+#[test]
+fn resolves_linked_worktree() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let main = dir.path().join("main");
+    fs::create_dir(&main).expect("mkdir");
+    init_repo(&main);
+    let worktree = dir.path().join("linked");
+    git(
+        &main,
+        &[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            "side",
+            worktree.to_str().expect("utf8"),
+        ],
+    );
+    git(&worktree, &["commit", "-q", "--allow-empty", "-m", "side"]);
+    let head = git(&worktree, &["rev-parse", "HEAD"]);
+
+    let resolved = resolve_git_rev(&worktree, None);
+    assert_eq!(resolved.rev, head);
+    assert_eq!(resolved.source, GitRevSource::Git);
+    // HEAD lives in the worktree's own git dir, the branch in the common dir.
+    assert!(
+        resolved
+            .watch
+            .iter()
+            .any(|path| path.ends_with("worktrees/linked/HEAD"))
+    );
+    assert!(
+        resolved
+            .watch
+            .iter()
+            .any(|path| path.ends_with("refs/heads/side"))
+    );
+}
+
+#[test]
+fn resolves_shallow_clone() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let origin = dir.path().join("origin");
+    fs::create_dir(&origin).expect("mkdir");
+    init_repo(&origin);
+    git(&origin, &["commit", "-q", "--allow-empty", "-m", "second"]);
+    let head = git(&origin, &["rev-parse", "HEAD"]);
+
+    let url = format!("file://{}", origin.display());
+    git(
+        dir.path(),
+        &["clone", "-q", "--depth", "1", &url, "shallow"],
+    );
+
+    let resolved = resolve_git_rev(&dir.path().join("shallow"), None);
+    assert_eq!(resolved.rev, head);
+    assert_eq!(resolved.source, GitRevSource::Git);
+}
+
+#[test]
+fn outside_a_repository_yields_placeholder() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    if Command::new("git")
+        .arg("-C")
+        .arg(dir.path())
+        .args(["rev-parse", "--git-dir"])
+        .output()
+        .is_ok_and(|output| output.status.success())
+    {
+        // The temp dir sits inside some repository; nothing to test here.
+        return;
+    }
+
+    let resolved = resolve_git_rev(dir.path(), None);
+    assert_eq!(resolved.rev, ZERO_REV);
+    assert_eq!(resolved.source, GitRevSource::Placeholder);
+    assert!(resolved.error.is_some());
+    let directives = resolved.cargo_directives();
+    assert!(
+        directives
+            .iter()
+            .any(|line| line.starts_with("cargo:warning="))
+    );
+    assert_eq!(
+        directives.last().map(String::as_str),
+        Some(format!("cargo:rustc-env=CARDANO_GIT_REV={ZERO_REV}").as_str())
+    );
+}
+
+const FIXTURE_BUILD_RS: &str = "fn main() {\n    cardano_git_rev::build::emit_git_rev_env();\n}\n";
+const FIXTURE_MAIN_RS: &str =
+    "fn main() {\n    println!(\"{}\", cardano_git_rev::git_rev_this_crate!());\n}\n";
+
+fn fixture_manifest() -> String {
+    let lib = env!("CARGO_MANIFEST_DIR").replace('\\', "/");
+    format!(
+        "[package]\nname = \"git-rev-fixture\"\nversion = \"0.0.0\"\nedition = \"2024\"\n\n\
+         [workspace]\n\n\
+         [dependencies]\ncardano-git-rev = {{ path = \"{lib}\" }}\n\n\
+         [build-dependencies]\ncardano-git-rev = {{ path = \"{lib}\" }}\n"
+    )
+}
+
+fn run_fixture(crate_dir: &Path, env_rev: Option<&str>) -> String {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("git-rev-fixture");
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut command = Command::new(cargo);
+    command
+        .args(["run", "--quiet", "--offline", "--target-dir"])
+        .arg(&target_dir)
+        .current_dir(crate_dir)
+        .env_remove("CARDANO_GIT_REV");
+    if let Some(rev) = env_rev {
+        command.env("CARDANO_GIT_REV", rev);
+    }
+    let output = command.output().expect("spawn cargo");
+    assert!(
+        output.status.success(),
+        "fixture build failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout)
+        .expect("utf8")
+        .trim()
+        .to_string()
+}
+
+/// A tiny downstream crate whose build script calls `emit_git_rev_env` sees
+/// its own repository's HEAD, not the revision of this library's checkout.
+#[test]
+fn fixture_crate_embeds_its_own_revision() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let crate_dir = dir.path();
+    fs::create_dir(crate_dir.join("src")).expect("mkdir");
+    fs::write(crate_dir.join("Cargo.toml"), fixture_manifest()).expect("manifest");
+    fs::write(crate_dir.join("build.rs"), FIXTURE_BUILD_RS).expect("build.rs");
+    fs::write(crate_dir.join("src/main.rs"), FIXTURE_MAIN_RS).expect("main.rs");
+    fs::write(crate_dir.join(".gitignore"), "/Cargo.lock\n").expect("gitignore");
+    git(crate_dir, &["init", "-q"]);
+    git(crate_dir, &["add", "-A"]);
+    git(crate_dir, &["commit", "-q", "-m", "fixture"]);
+    let head = git(crate_dir, &["rev-parse", "HEAD"]);
+
+    assert_eq!(run_fixture(crate_dir, None), head);
+
+    // A new commit re-runs the build script.
+    git(
+        crate_dir,
+        &["commit", "-q", "--allow-empty", "-m", "second"],
+    );
+    let second = git(crate_dir, &["rev-parse", "HEAD"]);
+    assert_eq!(run_fixture(crate_dir, None), second);
+
+    // The environment override still takes precedence.
+    let forced = "0123456789abcdef0123456789abcdef01234567";
+    assert_eq!(run_fixture(crate_dir, Some(forced)), forced);
+}
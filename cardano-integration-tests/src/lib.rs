@@ -0,0 +1,332 @@
+//! Cross-crate integration fixtures.
+//!
+//! Every other workspace member is tested in isolation. This crate composes
+//! them the way a consensus consumer does: a [`Header`] carries slotting
+//! types, a Praos VRF certificate, an operational certificate, and a
+//! [`Sum6Kes`] signature over the CBOR encoding of its body. It is serialised
+//! with `cardano-binary`, checked with `nothunks`, and sized with `heapwords`,
+//! so feature flags that do not compose, serde mismatches, and missing trait
+//! impls fail here instead of downstream.
+//!
+//! The layout follows the Shelley `HeaderBody` in spirit but is **not** the
+//! ledger wire format: serde encodes each struct as a CBOR map keyed by field
+//! name. The crate is not published.
+
+use cardano_binary::{BinaryError, serialize};
+use cardano_crypto_class::hash::{Blake2b256, HashAlgorithm};
+use cardano_crypto_class::kes::{KesAlgorithm, KesError, KesMError, Period, Sum6Kes, serde_period};
+use cardano_crypto_class::vrf::{
+    CertifiedVRF, PraosVRF, PraosVerificationKey, VRFAlgorithm, eval_certified, verify_certified,
+};
+use cardano_crypto_class::{
+    DsignAlgorithm, DsignError, DsignVectoredVerify, Ed25519, Ed25519Signature,
+    Ed25519VerificationKey,
+};
+use cardano_slotting::{BlockNo, SlotNo};
+use heapwords::{ByteString, HeapWords, heap_words2, heap_words4, heap_words9};
+use nothunks::{NoThunks, NoThunksResult};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// KES scheme used by the header: 2^6 = 64 periods, as on mainnet.
+pub type HeaderKes = Sum6Kes;
+
+/// Slots per KES period on mainnet.
+pub const SLOTS_PER_KES_PERIOD: u64 = 129_600;
+
+/// Errors raised while forging or validating a [`Header`].
+#[derive(Debug, Error)]
+pub enum HeaderError {
+    #[error("header body encoding failed: {0}")]
+    Binary(#[from] BinaryError),
+
+    #[error("KES key operation failed: {0}")]
+    KesKey(#[from] KesMError),
+
+    #[error("KES signature rejected: {0}")]
+    KesSignature(KesError),
+
+    #[error("slot {slot} is before the operational certificate's KES period {ocert_period}")]
+    BeforeOCert { slot: u64, ocert_period: Period },
+
+    #[error("operational certificate signature rejected: {0}")]
+    OCertSignature(DsignError),
+
+    #[error("VRF certificate rejected")]
+    Vrf,
+}
+
+/// Delegation from the pool's cold key to a hot KES key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OCert {
+    #[serde(with = "serde_bytes")]
+    pub hot_vk: Vec<u8>,
+    #[serde(with = "serde_period")]
+    pub counter: u64,
+    #[serde(with = "serde_period")]
+    pub kes_period: Period,
+    pub sigma: Ed25519Signature,
+}
+
+impl OCert {
+    /// Bytes signed by the cold key: the hot key followed by the big-endian
+    /// counter and KES period. [`Header::validate`] checks the signature over
+    /// the three fields in place rather than building this buffer.
+    #[must_use]
+    pub fn signable(&self) -> Vec<u8> {
+        let mut bytes = self.hot_vk.clone();
+        bytes.extend_from_slice(&self.counter.to_be_bytes());
+        bytes.extend_from_slice(&self.kes_period.to_be_bytes());
+        bytes
+    }
+}
+
+/// The part of a header covered by the KES signature.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeaderBody {
+    pub block_no: BlockNo,
+    pub slot: SlotNo,
+    #[serde(with = "serde_bytes")]
+    pub prev_hash: Option<Vec<u8>>,
+    pub issuer_vk: Ed25519VerificationKey,
+    pub vrf_vk: PraosVerificationKey,
+    pub vrf_result: CertifiedVRF<PraosVRF>,
+    pub body_size: u32,
+    #[serde(with = "serde_bytes")]
+    pub body_hash: Vec<u8>,
+    pub ocert: OCert,
+}
+
+/// A header body and the KES signature over its CBOR encoding.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Header {
+    pub body: HeaderBody,
+    pub signature: <HeaderKes as KesAlgorithm>::Signature,
+}
+
+/// Seeds for the three keys a block issuer holds.
+#[derive(Clone, Copy, Debug)]
+pub struct IssuerSeeds {
+    pub cold: [u8; 32],
+    pub vrf: [u8; 32],
+    pub kes: [u8; 32],
+}
+
+/// What the issuer is forging on top of.
+#[derive(Clone, Debug)]
+pub struct ForgeParams<'a> {
+    pub block_no: BlockNo,
+    pub slot: SlotNo,
+    pub prev_hash: Option<Vec<u8>>,
+    pub epoch_nonce: &'a [u8],
+    pub block_body: &'a [u8],
+    /// KES period the operational certificate starts at.
+    pub ocert_kes_period: Period,
+    pub ocert_counter: u64,
+}
+
+/// VRF input for `slot`: the Blake2b-256 hash of the big-endian slot and the
+/// epoch nonce.
+#[must_use]
+pub fn vrf_input(slot: SlotNo, epoch_nonce: &[u8]) -> Vec<u8> {
+    let mut input = slot.0.to_be_bytes().to_vec();
+    input.extend_from_slice(epoch_nonce);
+    Blake2b256::hash(&input)
+}
+
+/// KES period of `slot` relative to the operational certificate.
+fn relative_kes_period(slot: SlotNo, ocert_kes_period: Period) -> Result<Period, HeaderError> {
+    let absolute = slot.0 / SLOTS_PER_KES_PERIOD;
+    absolute
+        .checked_sub(ocert_kes_period)
+        .ok_or(HeaderError::BeforeOCert {
+            slot: slot.0,
+            ocert_period: ocert_kes_period,
+        })
+}
+
+impl Header {
+    /// Deterministically forge a header from `seeds`, evolving the KES key to
+    /// the slot's period before signing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slot precedes the certificate's KES period, the
+    /// KES key cannot reach the required period, or the body cannot be
+    /// encoded.
+    pub fn forge(seeds: &IssuerSeeds, params: &ForgeParams<'_>) -> Result<Self, HeaderError> {
+        let period = relative_kes_period(params.slot, params.ocert_kes_period)?;
+
+        let cold_sk = Ed25519::gen_key_from_seed_bytes(&seeds.cold);
+        let vrf_sk = PraosVRF::gen_key_from_seed_bytes(&seeds.vrf);
+        let mut kes_sk = HeaderKes::gen_key_kes_from_seed_bytes(&seeds.kes)?;
+        let hot_vk = HeaderKes::derive_verification_key(&kes_sk)?;
+
+        let mut ocert = OCert {
+            hot_vk,
+            counter: params.ocert_counter,
+            kes_period: params.ocert_kes_period,
+            sigma: Ed25519::sign_bytes(&(), &[], &cold_sk),
+        };
+        ocert.sigma = Ed25519::sign_bytes(&(), &ocert.signable(), &cold_sk);
+
+        let body = HeaderBody {
+            block_no: params.block_no,
+            slot: params.slot,
+            prev_hash: params.prev_hash.clone(),
+            issuer_vk: Ed25519::derive_verification_key(&cold_sk),
+            vrf_vk: PraosVRF::derive_verification_key(&vrf_sk),
+            vrf_result: eval_certified(
+                &(),
+                vrf_input(params.slot, params.epoch_nonce).as_slice(),
+                &vrf_sk,
+            ),
+            body_size: u32::try_from(params.block_body.len()).unwrap_or(u32::MAX),
+            body_hash: Blake2b256::hash(params.block_body),
+            ocert,
+        };
+
+        for current in 0..period {
+            kes_sk = match HeaderKes::update_kes(&(), kes_sk, current)? {
+                Some(next) => next,
+                None => return Err(KesMError::from(KesError::KeyExpired).into()),
+            };
+        }
+        let signature = HeaderKes::sign_kes(&(), period, &serialize(&body)?, &kes_sk);
+        HeaderKes::forget_signing_key_kes(kes_sk);
+
+        Ok(Self {
+            body,
+            signature: signature?,
+        })
+    }
+
+    /// Check the operational certificate, the VRF certificate, and the KES
+    /// signature.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first check that fails.
+    pub fn validate(&self, epoch_nonce: &[u8]) -> Result<(), HeaderError> {
+        let body = &self.body;
+        let ocert = &body.ocert;
+        Ed25519::verify_bytes_vectored(
+            &(),
+            &body.issuer_vk,
+            &[
+                &ocert.hot_vk,
+                &ocert.counter.to_be_bytes(),
+                &ocert.kes_period.to_be_bytes(),
+            ],
+            &ocert.sigma,
+        )
+        .map_err(HeaderError::OCertSignature)?;
+
+        if !verify_certified(
+            &(),
+            &body.vrf_vk,
+            vrf_input(body.slot, epoch_nonce).as_slice(),
+            &body.vrf_result,
+        ) {
+            return Err(HeaderError::Vrf);
+        }
+
+        let period = relative_kes_period(body.slot, body.ocert.kes_period)?;
+        HeaderKes::verify_kes(
+            &(),
+            &body.ocert.hot_vk,
+            period,
+            &serialize(body)?,
+            &self.signature,
+        )
+        .map_err(HeaderError::KesSignature)
+    }
+}
+
+fn field_context<'a>(context: &[&'a str], ty: &'a str, field: &'a str) -> Vec<&'a str> {
+    let mut path = context.to_vec();
+    path.extend([ty, field]);
+    path
+}
+
+// Keys, certificates, and signatures are fully evaluated byte buffers, so
+// only the remaining fields are traversed.
+impl NoThunks for OCert {
+    fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
+        self.hot_vk
+            .no_thunks(&field_context(context, "OCert", "hot_vk"))?;
+        self.counter
+            .no_thunks(&field_context(context, "OCert", "counter"))?;
+        self.kes_period
+            .no_thunks(&field_context(context, "OCert", "kes_period"))
+    }
+}
+
+impl NoThunks for HeaderBody {
+    fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
+        self.block_no
+            .no_thunks(&field_context(context, "HeaderBody", "block_no"))?;
+        self.slot
+            .no_thunks(&field_context(context, "HeaderBody", "slot"))?;
+        self.prev_hash
+            .no_thunks(&field_context(context, "HeaderBody", "prev_hash"))?;
+        self.body_size
+            .no_thunks(&field_context(context, "HeaderBody", "body_size"))?;
+        self.body_hash
+            .no_thunks(&field_context(context, "HeaderBody", "body_hash"))?;
+        self.ocert
+            .no_thunks(&field_context(context, "HeaderBody", "ocert"))
+    }
+}
+
+impl NoThunks for Header {
+    fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
+        self.body
+            .no_thunks(&field_context(context, "Header", "body"))
+    }
+}
+
+/// Byte buffers are costed as strict `ByteString`s, matching the Haskell
+/// representation of hashes, keys, and signatures.
+fn bytes(raw: &[u8]) -> ByteString {
+    ByteString(raw.to_vec())
+}
+
+impl HeapWords for OCert {
+    fn heap_words(&self) -> usize {
+        heap_words4(
+            &bytes(&self.hot_vk),
+            &self.counter,
+            &self.kes_period,
+            &bytes(&Ed25519::raw_serialize_signature(&self.sigma)),
+        )
+    }
+}
+
+impl HeapWords for HeaderBody {
+    fn heap_words(&self) -> usize {
+        heap_words9(
+            &self.block_no,
+            &self.slot,
+            &self.prev_hash.as_deref().map(bytes),
+            &bytes(&Ed25519::raw_serialize_verification_key(&self.issuer_vk)),
+            &bytes(&PraosVRF::raw_serialize_verification_key(&self.vrf_vk)),
+            &(
+                bytes(self.vrf_result.output.as_bytes()),
+                bytes(&PraosVRF::raw_serialize_proof(&self.vrf_result.proof)),
+            ),
+            &self.body_size,
+            &bytes(&self.body_hash),
+            &self.ocert,
+        )
+    }
+}
+
+impl HeapWords for Header {
+    fn heap_words(&self) -> usize {
+        heap_words2(
+            &self.body,
+            &bytes(&HeaderKes::raw_serialize_signature_kes(&self.signature)),
+        )
+    }
+}
@@ -0,0 +1,114 @@
+//! End-to-end flow over a mini block header: forge from deterministic seeds,
+//! CBOR round trip, validate, and inspect with `nothunks` and `heapwords`.
+
+use cardano_binary::{decode_full, serialize};
+use cardano_integration_tests::{
+    ForgeParams, Header, HeaderError, IssuerSeeds, SLOTS_PER_KES_PERIOD,
+};
+use cardano_slotting::{BlockNo, SlotNo};
+use heapwords::HeapWords;
+use nothunks::unsafe_no_thunks;
+
+const SEEDS: IssuerSeeds = IssuerSeeds {
+    cold: [0x01; 32],
+    vrf: [0x02; 32],
+    kes: [0x03; 32],
+};
+
+const EPOCH_NONCE: [u8; 32] = [0x6e; 32];
+
+const BLOCK_BODY: &[u8] = b"transactions go here";
+
+/// CBOR size of the forged header. A change here means the encoding of one of
+/// the component types changed. Most of it is the Sum6 signature, whose
+/// intermediate verification keys (`Vec<u8>`) serde encodes as arrays of
+/// integers rather than byte strings.
+const EXPECTED_HEADER_CBOR_LEN: usize = 1299;
+
+/// `HeapWords` estimate for the forged header.
+const EXPECTED_HEADER_HEAP_WORDS: usize = 180;
+
+fn params() -> ForgeParams<'static> {
+    ForgeParams {
+        block_no: BlockNo(42),
+        // Third KES period after the certificate's start period.
+        slot: SlotNo(4 * SLOTS_PER_KES_PERIOD + 17),
+        prev_hash: Some(vec![0xab; 32]),
+        epoch_nonce: &EPOCH_NONCE,
+        block_body: BLOCK_BODY,
+        ocert_kes_period: 1,
+        ocert_counter: 5,
+    }
+}
+
+fn forge() -> Header {
+    Header::forge(&SEEDS, &params()).expect("forge header")
+}
+
+#[test]
+fn forging_is_deterministic() {
+    assert_eq!(
+        serialize(&forge()).expect("encode"),
+        serialize(&forge()).expect("encode")
+    );
+}
+
+#[test]
+fn cbor_round_trip_is_byte_exact() {
+    let header = forge();
+    let bytes = serialize(&header).expect("encode");
+    let decoded: Header = decode_full(&bytes).expect("decode");
+    assert_eq!(decoded, header);
+    assert_eq!(serialize(&decoded).expect("re-encode"), bytes);
+    assert_eq!(bytes.len(), EXPECTED_HEADER_CBOR_LEN);
+}
+
+#[test]
+fn signatures_verify_after_round_trip() {
+    let bytes = serialize(&forge()).expect("encode");
+    let decoded: Header = decode_full(&bytes).expect("decode");
+    decoded.validate(&EPOCH_NONCE).expect("valid header");
+}
+
+#[test]
+fn tampering_is_detected() {
+    let header = forge();
+
+    let mut moved = header.clone();
+    moved.body.block_no = BlockNo(43);
+    assert!(matches!(
+        moved.validate(&EPOCH_NONCE),
+        Err(HeaderError::KesSignature(_))
+    ));
+
+    assert!(matches!(header.validate(&[0u8; 32]), Err(HeaderError::Vrf)));
+
+    let mut recounted = header;
+    recounted.body.ocert.counter += 1;
+    assert!(matches!(
+        recounted.validate(&EPOCH_NONCE),
+        Err(HeaderError::OCertSignature(_))
+    ));
+}
+
+#[test]
+fn header_has_no_thunks_and_a_stable_heap_estimate() {
+    let header = forge();
+    assert_eq!(unsafe_no_thunks(&header), None);
+    assert_eq!(header.heap_words(), EXPECTED_HEADER_HEAP_WORDS);
+}
+
+#[test]
+fn slots_before_the_certificate_are_rejected() {
+    let params = ForgeParams {
+        slot: SlotNo(17),
+        ..params()
+    };
+    assert!(matches!(
+        Header::forge(&SEEDS, &params),
+        Err(HeaderError::BeforeOCert {
+            slot: 17,
+            ocert_period: 1
+        })
+    ));
+}
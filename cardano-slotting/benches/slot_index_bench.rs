@@ -0,0 +1,135 @@
+//! One million slot lookups, slot by slot through `EpochInfo` (`naive`) and
+//! through a `SlotIndex` built once for the whole range (`slot_index`).
+//!
+//! Each lookup computes a slot's epoch, relative time and nonce-window
+//! phase, folded into a checksum so the benchmark measures the queries
+//! rather than writing the results out. The slots are in pseudo-random
+//! order.
+//!
+//! `fixed` is a single era of 432 000 one-second slots per epoch, which
+//! `fixed_epoch_info` already answers with a division per query. `mainnet`
+//! is the schedule an explorer holds: 208 Byron epochs of 21 600 twenty-second
+//! slots, then Shelley epochs, behind `generalize_epoch_info` and
+//! `unsafe_linear_extend_epoch_info` as a node's forecast would be.
+
+use cardano_slotting::{
+    EpochInfo, EpochInfoSource, EpochNo, EpochSize, RelativeTime, SlotIndex, SlotNo, SlotPhase,
+    fixed_epoch_info, generalize_epoch_info, mult_slot_length, slot_length_from_sec, slot_phase,
+    unsafe_linear_extend_epoch_info,
+};
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use std::convert::Infallible;
+
+const LOOKUPS: usize = 1_000_000;
+const LAST_SLOT: u64 = 150_000_000;
+const RANDOMNESS_WINDOW: u64 = 302_400;
+
+const BYRON_EPOCHS: u64 = 208;
+const BYRON_EPOCH_SIZE: u64 = 21_600;
+const SHELLEY_EPOCH_SIZE: u64 = 432_000;
+
+#[derive(Debug)]
+struct Unknown;
+
+fn mainnet() -> EpochInfo<Unknown> {
+    let boundary = BYRON_EPOCHS * BYRON_EPOCH_SIZE;
+    let byron = slot_length_from_sec(20);
+    let shelley = slot_length_from_sec(1);
+    let boundary_time = mult_slot_length(byron, boundary);
+    let hfc = EpochInfo::from_pure(
+        |epoch: EpochNo| {
+            EpochSize(if epoch.0 < BYRON_EPOCHS {
+                BYRON_EPOCH_SIZE
+            } else {
+                SHELLEY_EPOCH_SIZE
+            })
+        },
+        move |epoch: EpochNo| {
+            SlotNo(if epoch.0 < BYRON_EPOCHS {
+                epoch.0 * BYRON_EPOCH_SIZE
+            } else {
+                boundary + (epoch.0 - BYRON_EPOCHS) * SHELLEY_EPOCH_SIZE
+            })
+        },
+        move |slot: SlotNo| {
+            EpochNo(if slot.0 < boundary {
+                slot.0 / BYRON_EPOCH_SIZE
+            } else {
+                BYRON_EPOCHS + (slot.0 - boundary) / SHELLEY_EPOCH_SIZE
+            })
+        },
+        move |slot: SlotNo| {
+            RelativeTime::new(if slot.0 < boundary {
+                mult_slot_length(byron, slot.0)
+            } else {
+                boundary_time + mult_slot_length(shelley, slot.0 - boundary)
+            })
+        },
+        move |slot: SlotNo| if slot.0 < boundary { byron } else { shelley },
+    );
+    unsafe_linear_extend_epoch_info(SlotNo(LAST_SLOT), generalize_epoch_info(hfc))
+}
+
+fn slots() -> Vec<SlotNo> {
+    let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+    (0..LOOKUPS)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            SlotNo((state >> 11) % (LAST_SLOT + 1))
+        })
+        .collect()
+}
+
+fn checksum(epoch: EpochNo, time: RelativeTime, phase: SlotPhase) -> u64 {
+    let phase = match phase {
+        SlotPhase::ContributesToNonce { slots_into_epoch } => slots_into_epoch,
+        SlotPhase::AfterNonceWindow { slots_remaining } => slots_remaining,
+    };
+    epoch.0 ^ time.duration().whole_nanoseconds() as u64 ^ phase
+}
+
+fn naive<E: std::fmt::Debug>(info: &EpochInfo<E>, slots: &[SlotNo]) -> u64 {
+    slots.iter().fold(0, |sum, &slot| {
+        let epoch = info.epoch_of_slot(slot).expect("slot in schedule");
+        let time = info.slot_to_relative_time(slot).expect("slot in schedule");
+        let phase = slot_phase(info, slot, RANDOMNESS_WINDOW).expect("slot in schedule");
+        sum.wrapping_add(checksum(epoch, time, phase))
+    })
+}
+
+fn indexed(index: &SlotIndex, slots: &[SlotNo]) -> u64 {
+    slots.iter().fold(0, |sum, &slot| {
+        let epoch = index.epoch_of(slot).expect("slot in range");
+        let time = index.relative_time_of(slot).expect("slot in range");
+        let phase = index
+            .slot_phase_of(slot, RANDOMNESS_WINDOW)
+            .expect("slot in range");
+        sum.wrapping_add(checksum(epoch, time, phase))
+    })
+}
+
+fn compare<E: std::fmt::Debug>(c: &mut Criterion, name: &str, info: &EpochInfo<E>) {
+    let slots = slots();
+    let index = SlotIndex::build(info, SlotNo(0), SlotNo(LAST_SLOT), 1).expect("valid range");
+    assert_eq!(indexed(&index, &slots), naive(info, &slots));
+
+    let mut group = c.benchmark_group(name);
+    group.sample_size(20);
+    group.bench_function("naive", |b| b.iter(|| naive(info, black_box(&slots))));
+    group.bench_function("slot_index", |b| {
+        b.iter(|| indexed(&index, black_box(&slots)));
+    });
+    group.finish();
+}
+
+fn lookups(c: &mut Criterion) {
+    let fixed: EpochInfo<Infallible> =
+        fixed_epoch_info(EpochSize(SHELLEY_EPOCH_SIZE), slot_length_from_sec(1));
+    compare(c, "fixed", &fixed);
+    compare(c, "mainnet", &mainnet());
+}
+
+criterion_group!(benches, lookups);
+criterion_main!(benches);
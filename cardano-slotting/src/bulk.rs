@@ -0,0 +1,680 @@
+//! Precomputed slot tables for bulk queries.
+//!
+//! Explorers and analytics jobs map millions of historical slots to epochs,
+//! relative times and nonce windows. Going through [`EpochInfo`] costs a few
+//! dynamic calls per query, more when decorators such as
+//! [`LinearExtension`](crate::epoch_info::extend::LinearExtension) are
+//! stacked. [`SlotIndex::build`] asks the [`EpochInfo`] once per era for a
+//! slot range and answers later queries from the table by binary search over
+//! its eras.
+
+use thiserror::Error;
+use time::Duration;
+
+use crate::epoch_info::api::{EpochInfo, EpochInfoSource};
+use crate::epoch_info::phase::SlotPhase;
+use crate::slot::{EpochNo, EpochSize, SlotNo};
+use crate::time::{RelativeTime, SlotLength, add_relative_time, mult_slot_length};
+
+/// Error building a [`SlotIndex`].
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum SlotIndexBuildError<E> {
+    /// The underlying [`EpochInfo`] failed to answer a query.
+    #[error("epoch info query failed: {0}")]
+    Source(#[from] E),
+    /// A stride of zero epochs cannot advance.
+    #[error("stride must be at least one epoch")]
+    ZeroStride,
+    /// `from_slot` is after `to_slot`.
+    #[error("empty slot range {from:?}..={to:?}")]
+    EmptyRange { from: SlotNo, to: SlotNo },
+    /// The [`EpochInfo`] reported an epoch without slots.
+    #[error("epoch {epoch:?} has no slots")]
+    ZeroEpochSize { epoch: EpochNo },
+    /// Relative times inside an era are not `anchor + n * slot_length` for
+    /// any anchor the index can represent.
+    #[error("relative time of {slot:?} is not linear in its era")]
+    NonLinearTime { slot: SlotNo },
+}
+
+/// Error answering a query from a [`SlotIndex`].
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum SlotIndexError {
+    /// The slot lies outside the indexed range.
+    #[error("slot {slot:?} is outside the indexed range {first:?}..={last:?}")]
+    SlotOutOfRange {
+        slot: SlotNo,
+        first: SlotNo,
+        last: SlotNo,
+    },
+    /// The epoch does not overlap the indexed range.
+    #[error("epoch {epoch:?} is outside the indexed range {first:?}..={last:?}")]
+    EpochOutOfRange {
+        epoch: EpochNo,
+        first: EpochNo,
+        last: EpochNo,
+    },
+    /// KES periods need a non-zero period length.
+    #[cfg(feature = "kes")]
+    #[error("slots per KES period must be non-zero")]
+    ZeroSlotsPerKesPeriod,
+}
+
+/// A run of consecutive epochs sharing an epoch size and slot length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Era {
+    first_epoch: EpochNo,
+    /// First slot of `first_epoch`, which may precede the indexed range.
+    first_slot: SlotNo,
+    epoch_size: u64,
+    slot_length: SlotLength,
+    /// Slot whose relative time the era's slot times are counted from.
+    anchor_slot: SlotNo,
+    anchor_time: RelativeTime,
+    /// The slot length in nanoseconds, when it is a whole number of them;
+    /// saves [`mult_slot_length`]'s 128-bit division.
+    nanos_per_slot: Option<i64>,
+}
+
+impl Era {
+    fn epoch_of(&self, slot: SlotNo) -> EpochNo {
+        EpochNo(self.first_epoch.0 + (slot.0 - self.first_slot.0) / self.epoch_size)
+    }
+
+    fn first_slot_of(&self, epoch: EpochNo) -> SlotNo {
+        SlotNo(self.first_slot.0 + (epoch.0 - self.first_epoch.0) * self.epoch_size)
+    }
+
+    fn slots_into_epoch(&self, slot: SlotNo) -> u64 {
+        (slot.0 - self.first_slot.0) % self.epoch_size
+    }
+
+    fn relative_time_of(&self, slot: SlotNo) -> RelativeTime {
+        let slots = slot.0 - self.anchor_slot.0;
+        let elapsed = self
+            .nanos_per_slot
+            .and_then(|nanos| i64::try_from(slots).ok()?.checked_mul(nanos))
+            .map_or_else(
+                || mult_slot_length(self.slot_length, slots),
+                Duration::nanoseconds,
+            );
+        add_relative_time(elapsed, self.anchor_time)
+    }
+
+    /// Whether `epoch`, with the given shape, belongs to this era: same
+    /// epoch size and slot length, and the first slot the era predicts.
+    /// `epoch` may precede the era's first epoch.
+    fn continues(&self, epoch: EpochNo, shape: &EpochShape) -> bool {
+        let predicted = if epoch >= self.first_epoch {
+            (epoch.0 - self.first_epoch.0)
+                .checked_mul(self.epoch_size)
+                .and_then(|slots| self.first_slot.0.checked_add(slots))
+        } else {
+            (self.first_epoch.0 - epoch.0)
+                .checked_mul(self.epoch_size)
+                .and_then(|slots| self.first_slot.0.checked_sub(slots))
+        };
+        shape.size == self.epoch_size
+            && shape.slot_length == self.slot_length
+            && predicted == Some(shape.first_slot.0)
+    }
+
+    /// Move the era's start back to `epoch`, whose first slot is `first_slot`.
+    fn extend_back(&mut self, epoch: EpochNo, first_slot: SlotNo) {
+        self.first_epoch = epoch;
+        self.first_slot = first_slot;
+        self.anchor_slot = first_slot;
+    }
+}
+
+/// What [`SlotIndex::build`] learns about one epoch.
+struct EpochShape {
+    first_slot: SlotNo,
+    size: u64,
+    slot_length: SlotLength,
+}
+
+fn epoch_shape<E>(
+    info: &EpochInfo<E>,
+    epoch: EpochNo,
+) -> Result<EpochShape, SlotIndexBuildError<E>> {
+    let first_slot = info.first_slot_of_epoch(epoch)?;
+    let EpochSize(size) = info.epoch_size(epoch)?;
+    if size == 0 {
+        return Err(SlotIndexBuildError::ZeroEpochSize { epoch });
+    }
+    let slot_length = info.slot_length_at_slot(first_slot)?;
+    Ok(EpochShape {
+        first_slot,
+        size,
+        slot_length,
+    })
+}
+
+fn whole_nanos(slot_length: SlotLength) -> Option<i64> {
+    let nanos = u128::from(slot_length.numerator()) * 1_000_000_000;
+    let denominator = u128::from(slot_length.denominator());
+    if nanos % denominator == 0 {
+        i64::try_from(nanos / denominator).ok()
+    } else {
+        None
+    }
+}
+
+fn era_at<E>(info: &EpochInfo<E>, epoch: EpochNo) -> Result<Era, SlotIndexBuildError<E>> {
+    let shape = epoch_shape(info, epoch)?;
+    Ok(Era {
+        first_epoch: epoch,
+        first_slot: shape.first_slot,
+        epoch_size: shape.size,
+        slot_length: shape.slot_length,
+        anchor_slot: shape.first_slot,
+        anchor_time: RelativeTime::default(),
+        nanos_per_slot: whole_nanos(shape.slot_length),
+    })
+}
+
+/// Epoch, slot-time and nonce-window answers for a fixed slot range,
+/// precomputed from an [`EpochInfo`].
+///
+/// The table holds one entry per era overlapping the range, so it stays a
+/// few dozen bytes for the whole of mainnet. Every query checks the range
+/// and runs in O(log #eras); answers agree exactly with the [`EpochInfo`]
+/// the index was built from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlotIndex {
+    first_slot: SlotNo,
+    last_slot: SlotNo,
+    first_epoch: EpochNo,
+    last_epoch: EpochNo,
+    eras: Vec<Era>,
+}
+
+impl SlotIndex {
+    /// Index the slots `from_slot..=to_slot` of `info`.
+    ///
+    /// Era boundaries are found by probing every `stride`-th epoch and
+    /// bisecting between two probes that disagree on the epoch size, slot
+    /// length or first slot, so building costs O(#epochs / stride + #eras
+    /// · log stride) queries. The era containing `from_slot` is followed
+    /// back to its first epoch the same way. An era shorter than `stride`
+    /// epochs between two identical eras goes unnoticed; use a stride of 1
+    /// when the schedule is not known to have long eras.
+    ///
+    /// Slot times within an era are taken to be linear from the era's first
+    /// slot, as in the hard fork combinator and
+    /// [`fixed_epoch_info`](crate::fixed_epoch_info). Each era is checked at
+    /// its first and last slot in range and at the slot in between.
+    ///
+    /// # Errors
+    ///
+    /// [`SlotIndexBuildError::ZeroStride`] and
+    /// [`SlotIndexBuildError::EmptyRange`] for unusable arguments,
+    /// [`SlotIndexBuildError::Source`] if `info` fails, and
+    /// [`SlotIndexBuildError::ZeroEpochSize`] or
+    /// [`SlotIndexBuildError::NonLinearTime`] for schedules the table cannot
+    /// represent.
+    pub fn build<E>(
+        info: &EpochInfo<E>,
+        from_slot: SlotNo,
+        to_slot: SlotNo,
+        stride: u64,
+    ) -> Result<Self, SlotIndexBuildError<E>> {
+        if stride == 0 {
+            return Err(SlotIndexBuildError::ZeroStride);
+        }
+        if from_slot > to_slot {
+            return Err(SlotIndexBuildError::EmptyRange {
+                from: from_slot,
+                to: to_slot,
+            });
+        }
+        let first_epoch = info.epoch_of_slot(from_slot)?;
+        let last_epoch = info.epoch_of_slot(to_slot)?;
+
+        // The first era may have started before the range; walk back to its
+        // start so that slot times are counted from the right anchor.
+        let mut current = era_at(info, first_epoch)?;
+        while current.first_epoch.0 > 0 {
+            let probe = EpochNo(current.first_epoch.0.saturating_sub(stride));
+            let shape = epoch_shape(info, probe)?;
+            if current.continues(probe, &shape) {
+                current.extend_back(probe, shape.first_slot);
+                continue;
+            }
+            let (mut outside, mut inside) = (probe, current.first_epoch);
+            while inside.0 - outside.0 > 1 {
+                let mid = EpochNo(outside.0 + (inside.0 - outside.0) / 2);
+                let shape = epoch_shape(info, mid)?;
+                if current.continues(mid, &shape) {
+                    inside = mid;
+                    current.extend_back(mid, shape.first_slot);
+                } else {
+                    outside = mid;
+                }
+            }
+            break;
+        }
+
+        let mut eras = Vec::new();
+        let mut known = first_epoch;
+        while known < last_epoch {
+            let probe = EpochNo(known.0.saturating_add(stride).min(last_epoch.0));
+            if current.continues(probe, &epoch_shape(info, probe)?) {
+                known = probe;
+                continue;
+            }
+            // `known` is in the current era and `probe` is not: bisect for
+            // the first epoch of the next era.
+            let (mut inside, mut outside) = (known, probe);
+            while outside.0 - inside.0 > 1 {
+                let mid = EpochNo(inside.0 + (outside.0 - inside.0) / 2);
+                if current.continues(mid, &epoch_shape(info, mid)?) {
+                    inside = mid;
+                } else {
+                    outside = mid;
+                }
+            }
+            eras.push(current);
+            current = era_at(info, outside)?;
+            known = outside;
+        }
+        eras.push(current);
+
+        let mut index = Self {
+            first_slot: from_slot,
+            last_slot: to_slot,
+            first_epoch,
+            last_epoch,
+            eras,
+        };
+        index.anchor_times(info)?;
+        Ok(index)
+    }
+
+    /// Anchor each era's slot times at its first slot, then check the
+    /// anchor against `info` at the era's first, middle and last slot in
+    /// range.
+    fn anchor_times<E>(&mut self, info: &EpochInfo<E>) -> Result<(), SlotIndexBuildError<E>> {
+        for position in 0..self.eras.len() {
+            let start = self.eras[position].first_slot.max(self.first_slot);
+            let end = self
+                .eras
+                .get(position + 1)
+                .map_or(self.last_slot, |next| SlotNo(next.first_slot.0 - 1));
+            let era = &mut self.eras[position];
+            era.anchor_time = info.slot_to_relative_time(era.anchor_slot)?;
+            for slot in [start, SlotNo(start.0 + (end.0 - start.0) / 2), end] {
+                if era.relative_time_of(slot) != info.slot_to_relative_time(slot)? {
+                    return Err(SlotIndexBuildError::NonLinearTime { slot });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// First indexed slot.
+    #[must_use]
+    pub const fn first_slot(&self) -> SlotNo {
+        self.first_slot
+    }
+
+    /// Last indexed slot (inclusive).
+    #[must_use]
+    pub const fn last_slot(&self) -> SlotNo {
+        self.last_slot
+    }
+
+    /// Number of eras in the table.
+    #[must_use]
+    pub fn era_count(&self) -> usize {
+        self.eras.len()
+    }
+
+    fn check_slot(&self, slot: SlotNo) -> Result<(), SlotIndexError> {
+        if slot < self.first_slot || slot > self.last_slot {
+            return Err(SlotIndexError::SlotOutOfRange {
+                slot,
+                first: self.first_slot,
+                last: self.last_slot,
+            });
+        }
+        Ok(())
+    }
+
+    fn era_of_slot(&self, slot: SlotNo) -> Result<&Era, SlotIndexError> {
+        self.check_slot(slot)?;
+        let position = self.eras.partition_point(|era| era.first_slot <= slot);
+        Ok(&self.eras[position - 1])
+    }
+
+    fn era_of_epoch(&self, epoch: EpochNo) -> Result<&Era, SlotIndexError> {
+        if epoch < self.first_epoch || epoch > self.last_epoch {
+            return Err(SlotIndexError::EpochOutOfRange {
+                epoch,
+                first: self.first_epoch,
+                last: self.last_epoch,
+            });
+        }
+        let position = self.eras.partition_point(|era| era.first_epoch <= epoch);
+        Ok(&self.eras[position - 1])
+    }
+
+    /// Epoch containing `slot`.
+    pub fn epoch_of(&self, slot: SlotNo) -> Result<EpochNo, SlotIndexError> {
+        self.era_of_slot(slot).map(|era| era.epoch_of(slot))
+    }
+
+    /// Time of the start of `slot`, relative to system start.
+    pub fn relative_time_of(&self, slot: SlotNo) -> Result<RelativeTime, SlotIndexError> {
+        self.era_of_slot(slot).map(|era| era.relative_time_of(slot))
+    }
+
+    /// Slot length in force at `slot`.
+    pub fn slot_length_of(&self, slot: SlotNo) -> Result<SlotLength, SlotIndexError> {
+        self.era_of_slot(slot).map(|era| era.slot_length)
+    }
+
+    /// Number of slots in `epoch`, which must overlap the indexed range.
+    ///
+    /// This is the size of the whole epoch, even when the range starts or
+    /// ends inside it.
+    pub fn slots_in_epoch(&self, epoch: EpochNo) -> Result<EpochSize, SlotIndexError> {
+        self.era_of_epoch(epoch)
+            .map(|era| EpochSize(era.epoch_size))
+    }
+
+    /// First slot of `epoch`, which must overlap the indexed range.
+    pub fn first_slot_of(&self, epoch: EpochNo) -> Result<SlotNo, SlotIndexError> {
+        self.era_of_epoch(epoch).map(|era| era.first_slot_of(epoch))
+    }
+
+    /// Classify `slot` against a randomness window of `randomness_window`
+    /// slots, exactly as [`slot_phase`](crate::slot_phase) does.
+    pub fn slot_phase_of(
+        &self,
+        slot: SlotNo,
+        randomness_window: u64,
+    ) -> Result<SlotPhase, SlotIndexError> {
+        let era = self.era_of_slot(slot)?;
+        let offset = era.slots_into_epoch(slot);
+        Ok(if offset < randomness_window {
+            SlotPhase::ContributesToNonce {
+                slots_into_epoch: offset,
+            }
+        } else {
+            SlotPhase::AfterNonceWindow {
+                slots_remaining: era.epoch_size.saturating_sub(offset + 1),
+            }
+        })
+    }
+
+    /// KES period containing `slot`, checking that it is indexed.
+    #[cfg(feature = "kes")]
+    pub fn kes_period_of(
+        &self,
+        slot: SlotNo,
+        slots_per_kes_period: u64,
+    ) -> Result<u64, SlotIndexError> {
+        self.check_slot(slot)?;
+        crate::kes::kes_period_of_slot(slot, slots_per_kes_period)
+            .ok_or(SlotIndexError::ZeroSlotsPerKesPeriod)
+    }
+
+    /// Epochs of `slots`, in the order given.
+    ///
+    /// Sorted input, such as a chain walk, is swept with a cursor that only
+    /// moves forward through the eras; other input is looked up slot by
+    /// slot, which is cheaper than sorting it first.
+    pub fn epochs_of(&self, slots: &[SlotNo]) -> Result<Vec<EpochNo>, SlotIndexError> {
+        self.sweep(slots, Era::epoch_of)
+    }
+
+    /// Relative times of `slots`, in the order given. See
+    /// [`epochs_of`](Self::epochs_of).
+    pub fn relative_times_of(&self, slots: &[SlotNo]) -> Result<Vec<RelativeTime>, SlotIndexError> {
+        self.sweep(slots, Era::relative_time_of)
+    }
+
+    /// KES periods of `slots`, in the order given.
+    #[cfg(feature = "kes")]
+    pub fn kes_periods_of(
+        &self,
+        slots: &[SlotNo],
+        slots_per_kes_period: u64,
+    ) -> Result<Vec<u64>, SlotIndexError> {
+        if slots_per_kes_period == 0 {
+            return Err(SlotIndexError::ZeroSlotsPerKesPeriod);
+        }
+        slots
+            .iter()
+            .map(|&slot| {
+                self.check_slot(slot)
+                    .map(|()| slot.0 / slots_per_kes_period)
+            })
+            .collect()
+    }
+
+    fn sweep<T>(
+        &self,
+        slots: &[SlotNo],
+        answer: impl Fn(&Era, SlotNo) -> T,
+    ) -> Result<Vec<T>, SlotIndexError> {
+        if !slots.is_sorted() {
+            return slots
+                .iter()
+                .map(|&slot| self.era_of_slot(slot).map(|era| answer(era, slot)))
+                .collect();
+        }
+        if let (Some(&lowest), Some(&highest)) = (slots.first(), slots.last()) {
+            self.check_slot(lowest)?;
+            self.check_slot(highest)?;
+        }
+        let mut era = 0;
+        Ok(slots
+            .iter()
+            .map(|&slot| {
+                while self
+                    .eras
+                    .get(era + 1)
+                    .is_some_and(|next| next.first_slot <= slot)
+                {
+                    era += 1;
+                }
+                answer(&self.eras[era], slot)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::epoch_info::phase::slot_phase;
+    use crate::fixed_epoch_info;
+    use crate::time::{slot_length_from_fraction, slot_length_from_sec};
+
+    /// A two-era schedule in the style of the hard fork combinator: `byron`
+    /// epochs of 10 slots of 20s, then epochs of 50 slots of `length`,
+    /// with times counted from the first slot of the second era.
+    fn two_eras(byron: u64, length: SlotLength) -> EpochInfo<Infallible> {
+        let boundary = byron * 10;
+        let boundary_time = mult_slot_length(slot_length_from_sec(20), boundary);
+        let size = move |epoch: EpochNo| EpochSize(if epoch.0 < byron { 10 } else { 50 });
+        let first = move |epoch: EpochNo| {
+            SlotNo(if epoch.0 < byron {
+                epoch.0 * 10
+            } else {
+                boundary + (epoch.0 - byron) * 50
+            })
+        };
+        let epoch = move |slot: SlotNo| {
+            EpochNo(if slot.0 < boundary {
+                slot.0 / 10
+            } else {
+                byron + (slot.0 - boundary) / 50
+            })
+        };
+        let relative = move |slot: SlotNo| {
+            RelativeTime::new(if slot.0 < boundary {
+                mult_slot_length(slot_length_from_sec(20), slot.0)
+            } else {
+                boundary_time + mult_slot_length(length, slot.0 - boundary)
+            })
+        };
+        let slot_length = move |slot: SlotNo| {
+            if slot.0 < boundary {
+                slot_length_from_sec(20)
+            } else {
+                length
+            }
+        };
+        EpochInfo::from_pure(size, first, epoch, relative, slot_length)
+            .with_slot_length_at_slot(move |slot| Ok(slot_length(slot)))
+    }
+
+    fn assert_agrees(info: &EpochInfo<Infallible>, index: &SlotIndex, slot: SlotNo) {
+        let epoch = info.epoch_of_slot(slot).unwrap();
+        assert_eq!(index.epoch_of(slot), Ok(epoch), "{slot:?}");
+        assert_eq!(
+            index.relative_time_of(slot),
+            Ok(info.slot_to_relative_time(slot).unwrap()),
+            "{slot:?}"
+        );
+        assert_eq!(
+            index.slots_in_epoch(epoch),
+            Ok(info.epoch_size(epoch).unwrap())
+        );
+        assert_eq!(
+            index.first_slot_of(epoch),
+            Ok(info.first_slot_of_epoch(epoch).unwrap())
+        );
+        for window in [0, 7, 40] {
+            assert_eq!(
+                index.slot_phase_of(slot, window),
+                Ok(slot_phase(info, slot, window).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn finds_era_boundaries_with_any_stride() {
+        let info = two_eras(7, slot_length_from_sec(1));
+        for stride in [1, 2, 3, 100] {
+            let index = SlotIndex::build(&info, SlotNo(15), SlotNo(500), stride).unwrap();
+            assert_eq!(index.era_count(), 2, "stride {stride}");
+            for slot in 15..=500 {
+                assert_agrees(&info, &index, SlotNo(slot));
+            }
+        }
+    }
+
+    #[test]
+    fn fractional_slot_lengths_keep_their_anchor() {
+        // Times counted from slot zero and from the era start round 1/3s
+        // slots differently; the index must reproduce whichever it is given.
+        let third = slot_length_from_fraction(1, 3);
+        let fixed = fixed_epoch_info(EpochSize(50), third);
+        let index = SlotIndex::build(&fixed, SlotNo(101), SlotNo(400), 4).unwrap();
+        let hfc = two_eras(3, third);
+        let hfc_index = SlotIndex::build(&hfc, SlotNo(1), SlotNo(400), 4).unwrap();
+        for slot in 101..=400 {
+            assert_agrees(&fixed, &index, SlotNo(slot));
+            assert_agrees(&hfc, &hfc_index, SlotNo(slot));
+        }
+    }
+
+    #[test]
+    fn out_of_range_queries_are_typed_errors() {
+        let info = fixed_epoch_info(EpochSize(10), slot_length_from_sec(1));
+        let index = SlotIndex::build(&info, SlotNo(25), SlotNo(74), 1).unwrap();
+        let out_of_range = |slot| SlotIndexError::SlotOutOfRange {
+            slot,
+            first: SlotNo(25),
+            last: SlotNo(74),
+        };
+        assert_eq!(index.epoch_of(SlotNo(24)), Err(out_of_range(SlotNo(24))));
+        assert_eq!(
+            index.relative_time_of(SlotNo(75)),
+            Err(out_of_range(SlotNo(75)))
+        );
+        assert_eq!(
+            index.epochs_of(&[SlotNo(30), SlotNo(80), SlotNo(40)]),
+            Err(out_of_range(SlotNo(80)))
+        );
+        // Epochs 2 to 7 overlap the range, including the partial ones.
+        assert_eq!(index.slots_in_epoch(EpochNo(2)), Ok(EpochSize(10)));
+        assert_eq!(index.first_slot_of(EpochNo(7)), Ok(SlotNo(70)));
+        assert_eq!(
+            index.slots_in_epoch(EpochNo(8)),
+            Err(SlotIndexError::EpochOutOfRange {
+                epoch: EpochNo(8),
+                first: EpochNo(2),
+                last: EpochNo(7),
+            })
+        );
+
+        assert_eq!(
+            SlotIndex::build(&info, SlotNo(0), SlotNo(9), 0),
+            Err(SlotIndexBuildError::ZeroStride)
+        );
+        assert_eq!(
+            SlotIndex::build(&info, SlotNo(9), SlotNo(0), 1),
+            Err(SlotIndexBuildError::EmptyRange {
+                from: SlotNo(9),
+                to: SlotNo(0),
+            })
+        );
+    }
+
+    #[cfg(feature = "kes")]
+    #[test]
+    fn kes_periods_follow_the_slot() {
+        let info = fixed_epoch_info(EpochSize(432_000), slot_length_from_sec(1));
+        let index = SlotIndex::build(&info, SlotNo(0), SlotNo(1_000_000), 1).unwrap();
+        assert_eq!(index.kes_period_of(SlotNo(129_600), 129_600), Ok(1));
+        assert_eq!(
+            index.kes_periods_of(&[SlotNo(259_200), SlotNo(5)], 129_600),
+            Ok(vec![2, 0])
+        );
+        assert_eq!(
+            index.kes_period_of(SlotNo(5), 0),
+            Err(SlotIndexError::ZeroSlotsPerKesPeriod)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_matches_epoch_info(
+            byron in 0u64..20,
+            from in 0u64..2_000,
+            span in 0u64..3_000,
+            stride in 1u64..8,
+            numerator in 1u64..4,
+            slots in proptest::collection::vec(0u64..5_000, 0..64),
+        ) {
+            let info = two_eras(byron, slot_length_from_fraction(numerator, 3));
+            let to = from + span;
+            let index = SlotIndex::build(&info, SlotNo(from), SlotNo(to), stride).unwrap();
+            let slots: Vec<SlotNo> = slots
+                .into_iter()
+                .map(|slot| SlotNo(from + slot % (span + 1)))
+                .collect();
+            for &slot in &slots {
+                assert_agrees(&info, &index, slot);
+            }
+            let epochs: Vec<EpochNo> =
+                slots.iter().map(|&slot| info.epoch_of_slot(slot).unwrap()).collect();
+            prop_assert_eq!(index.epochs_of(&slots), Ok(epochs));
+            let times: Vec<RelativeTime> = slots
+                .iter()
+                .map(|&slot| info.slot_to_relative_time(slot).unwrap())
+                .collect();
+            prop_assert_eq!(index.relative_times_of(&slots), Ok(times));
+        }
+    }
+}
@@ -2,28 +2,87 @@ use std::convert::Infallible;
 use std::fmt;
 use std::sync::Arc;
 
+use crate::epoch_info::fixed::fixed_epoch_info;
 use crate::slot::{EpochNo, EpochSize, SlotNo};
-use crate::time::{RelativeTime, SlotLength, SystemStart, from_relative_time};
+use crate::time::{RelativeTime, SlotLength, SystemStart, from_relative_time, mult_slot_length};
 use time::OffsetDateTime;
 
 pub type EpochResult<T, E> = Result<T, E>;
 
+type EpochQuery<T, E> = Box<dyn Fn(EpochNo) -> EpochResult<T, E> + Send + Sync>;
+type SlotQuery<T, E> = Box<dyn Fn(SlotNo) -> EpochResult<T, E> + Send + Sync>;
+
+/// The primitive queries behind an [`EpochInfo`].
+///
+/// Implement this for a schedule type and wrap it with
+/// [`EpochInfo::from_source`]. Decorators such as
+/// [`LinearExtension`](crate::epoch_info::extend::LinearExtension) are sources
+/// wrapping another source; [`EpochInfo`] itself implements the trait so it
+/// can be wrapped in turn.
+pub trait EpochInfoSource: Send + Sync {
+    /// Error returned by queries the source cannot answer, e.g. slots past a
+    /// forecast horizon. Use [`Infallible`] for total schedules.
+    type Error;
+
+    /// Number of slots in `epoch`.
+    fn epoch_size(&self, epoch: EpochNo) -> EpochResult<EpochSize, Self::Error>;
+
+    /// First slot of `epoch`.
+    fn first_slot_of_epoch(&self, epoch: EpochNo) -> EpochResult<SlotNo, Self::Error>;
+
+    /// Epoch containing `slot`.
+    fn epoch_of_slot(&self, slot: SlotNo) -> EpochResult<EpochNo, Self::Error>;
+
+    /// Time of the start of `slot`, relative to system start.
+    fn slot_to_relative_time(&self, slot: SlotNo) -> EpochResult<RelativeTime, Self::Error>;
+
+    /// Slot length reported for `slot`.
+    fn slot_length(&self, slot: SlotNo) -> EpochResult<SlotLength, Self::Error>;
+
+    /// Slot length applicable to `slot`.
+    ///
+    /// The default returns the slot length of the first slot of the epoch
+    /// containing `slot`, which assumes the per-epoch queries describe a
+    /// single era. Sources that know where era boundaries fall should
+    /// override it.
+    fn slot_length_at_slot(&self, slot: SlotNo) -> EpochResult<SlotLength, Self::Error> {
+        let epoch = self.epoch_of_slot(slot)?;
+        let first = self.first_slot_of_epoch(epoch)?;
+        self.slot_length(first)
+    }
+
+    /// Wall-clock time elapsed between system start and the beginning of
+    /// `slot`.
+    ///
+    /// The default multiplies `slot` by [`slot_length_at_slot`], which is only
+    /// correct within a single era starting at slot zero; across a Byron
+    /// (20s) to Shelley (1s) boundary the elapsed time must be integrated
+    /// piecewise by an overriding source.
+    ///
+    /// [`slot_length_at_slot`]: EpochInfoSource::slot_length_at_slot
+    fn elapsed_time_to_slot(&self, slot: SlotNo) -> EpochResult<RelativeTime, Self::Error> {
+        let length = self.slot_length_at_slot(slot)?;
+        Ok(RelativeTime::new(mult_slot_length(length, slot.0)))
+    }
+}
+
+/// Epoch and slot-time queries for a chain's schedule.
+///
+/// A shared handle to an [`EpochInfoSource`]: cloning copies one `Arc`, and
+/// the handle is `Send + Sync`, so it can live in long-lived state shared
+/// across threads.
 pub struct EpochInfo<E> {
-    pub(crate) size: Arc<dyn Fn(EpochNo) -> EpochResult<EpochSize, E> + Send + Sync>,
-    pub(crate) first: Arc<dyn Fn(EpochNo) -> EpochResult<SlotNo, E> + Send + Sync>,
-    pub(crate) epoch: Arc<dyn Fn(SlotNo) -> EpochResult<EpochNo, E> + Send + Sync>,
-    pub(crate) slot_to_relative: Arc<dyn Fn(SlotNo) -> EpochResult<RelativeTime, E> + Send + Sync>,
-    pub(crate) slot_length: Arc<dyn Fn(SlotNo) -> EpochResult<SlotLength, E> + Send + Sync>,
+    source: Arc<dyn EpochInfoSource<Error = E>>,
+    /// Whether [`EpochInfo::with_elapsed_time_to_slot`] installed the
+    /// elapsed-time query, which a later slot length override must keep.
+    elapsed_overridden: bool,
 }
 
 impl<E> Clone for EpochInfo<E> {
     fn clone(&self) -> Self {
         Self {
-            size: Arc::clone(&self.size),
-            first: Arc::clone(&self.first),
-            epoch: Arc::clone(&self.epoch),
-            slot_to_relative: Arc::clone(&self.slot_to_relative),
-            slot_length: Arc::clone(&self.slot_length),
+            source: Arc::clone(&self.source),
+            elapsed_overridden: self.elapsed_overridden,
         }
     }
 }
@@ -34,6 +93,51 @@ impl<E> fmt::Debug for EpochInfo<E> {
     }
 }
 
+impl<E> EpochInfoSource for EpochInfo<E> {
+    type Error = E;
+
+    fn epoch_size(&self, epoch: EpochNo) -> EpochResult<EpochSize, E> {
+        self.source.epoch_size(epoch)
+    }
+
+    fn first_slot_of_epoch(&self, epoch: EpochNo) -> EpochResult<SlotNo, E> {
+        self.source.first_slot_of_epoch(epoch)
+    }
+
+    fn epoch_of_slot(&self, slot: SlotNo) -> EpochResult<EpochNo, E> {
+        self.source.epoch_of_slot(slot)
+    }
+
+    fn slot_to_relative_time(&self, slot: SlotNo) -> EpochResult<RelativeTime, E> {
+        self.source.slot_to_relative_time(slot)
+    }
+
+    fn slot_length(&self, slot: SlotNo) -> EpochResult<SlotLength, E> {
+        self.source.slot_length(slot)
+    }
+
+    fn slot_length_at_slot(&self, slot: SlotNo) -> EpochResult<SlotLength, E> {
+        self.source.slot_length_at_slot(slot)
+    }
+
+    fn elapsed_time_to_slot(&self, slot: SlotNo) -> EpochResult<RelativeTime, E> {
+        self.source.elapsed_time_to_slot(slot)
+    }
+}
+
+impl<E> EpochInfo<E> {
+    /// Share `source` behind a new handle.
+    pub fn from_source<S>(source: S) -> Self
+    where
+        S: EpochInfoSource<Error = E> + 'static,
+    {
+        Self {
+            source: Arc::new(source),
+            elapsed_overridden: false,
+        }
+    }
+}
+
 impl<E> EpochInfo<E>
 where
     E: Send + Sync + 'static,
@@ -45,13 +149,62 @@ where
         epoch: impl Fn(SlotNo) -> EpochResult<EpochNo, E> + Send + Sync + 'static,
         slot_to_relative: impl Fn(SlotNo) -> EpochResult<RelativeTime, E> + Send + Sync + 'static,
         slot_length: impl Fn(SlotNo) -> EpochResult<SlotLength, E> + Send + Sync + 'static,
+    ) -> Self {
+        Self::from_source(FnSource {
+            size: Box::new(size),
+            first: Box::new(first),
+            epoch: Box::new(epoch),
+            slot_to_relative: Box::new(slot_to_relative),
+            slot_length: Box::new(slot_length),
+        })
+    }
+
+    /// Override the per-slot slot length query.
+    ///
+    /// Without an override, [`epoch_info_slot_length_at_slot`] uses the slot
+    /// length of the first slot of the enclosing epoch. Implementations that
+    /// know where era boundaries fall should install an exact query here.
+    ///
+    /// Unless a query is installed with
+    /// [`with_elapsed_time_to_slot`](Self::with_elapsed_time_to_slot), before
+    /// or after this one, [`epoch_info_elapsed_time_to_slot`] becomes the
+    /// slot number times this slot length, replacing the source's own
+    /// elapsed time.
+    #[must_use]
+    pub fn with_slot_length_at_slot(
+        self,
+        slot_length_at_slot: impl Fn(SlotNo) -> EpochResult<SlotLength, E> + Send + Sync + 'static,
+    ) -> Self {
+        let elapsed_overridden = self.elapsed_overridden;
+        Self {
+            elapsed_overridden,
+            ..Self::from_source(Overrides {
+                inner: self,
+                slot_length_at_slot: Some(Box::new(slot_length_at_slot)),
+                elapsed_time_to_slot: None,
+            })
+        }
+    }
+
+    /// Override the elapsed-time query.
+    ///
+    /// Without an override, [`epoch_info_elapsed_time_to_slot`] multiplies the
+    /// slot number by the slot length at that slot, which is only correct when
+    /// every slot since system start shares one slot length. Implementations
+    /// spanning several eras should install a query that integrates the slot
+    /// length piecewise.
+    #[must_use]
+    pub fn with_elapsed_time_to_slot(
+        self,
+        elapsed_time_to_slot: impl Fn(SlotNo) -> EpochResult<RelativeTime, E> + Send + Sync + 'static,
     ) -> Self {
         Self {
-            size: Arc::new(size),
-            first: Arc::new(first),
-            epoch: Arc::new(epoch),
-            slot_to_relative: Arc::new(slot_to_relative),
-            slot_length: Arc::new(slot_length),
+            elapsed_overridden: true,
+            ..Self::from_source(Overrides {
+                inner: self,
+                slot_length_at_slot: None,
+                elapsed_time_to_slot: Some(Box::new(elapsed_time_to_slot)),
+            })
         }
     }
 
@@ -59,40 +212,13 @@ where
     where
         F: Send + Sync + 'static,
     {
-        let EpochInfo {
-            size,
-            first,
-            epoch,
-            slot_to_relative,
-            slot_length,
-        } = self;
-        let f = Arc::new(f);
+        let elapsed_overridden = self.elapsed_overridden;
         EpochInfo {
-            size: Arc::new({
-                let f = Arc::clone(&f);
-                let size = Arc::clone(&size);
-                move |epoch_no| (*size)(epoch_no).map_err(|err| f(err))
-            }),
-            first: Arc::new({
-                let f = Arc::clone(&f);
-                let first = Arc::clone(&first);
-                move |epoch_no| (*first)(epoch_no).map_err(|err| f(err))
-            }),
-            epoch: Arc::new({
-                let f = Arc::clone(&f);
-                let epoch = Arc::clone(&epoch);
-                move |slot| (*epoch)(slot).map_err(|err| f(err))
-            }),
-            slot_to_relative: Arc::new({
-                let f = Arc::clone(&f);
-                let slot_to_relative = Arc::clone(&slot_to_relative);
-                move |slot| (*slot_to_relative)(slot).map_err(|err| f(err))
-            }),
-            slot_length: Arc::new({
-                let f = Arc::clone(&f);
-                let slot_length = Arc::clone(&slot_length);
-                move |slot| (*slot_length)(slot).map_err(|err| f(err))
-            }),
+            elapsed_overridden,
+            ..EpochInfo::from_source(MapError {
+                inner: self,
+                f: Box::new(f),
+            })
         }
     }
 }
@@ -106,16 +232,145 @@ impl EpochInfo<Infallible> {
         slot_to_relative: impl Fn(SlotNo) -> RelativeTime + Send + Sync + 'static,
         slot_length: impl Fn(SlotNo) -> SlotLength + Send + Sync + 'static,
     ) -> Self {
-        Self {
-            size: Arc::new(move |epoch| Ok(size(epoch))),
-            first: Arc::new(move |epoch| Ok(first(epoch))),
-            epoch: Arc::new(move |slot| Ok(epoch(slot))),
-            slot_to_relative: Arc::new(move |slot| Ok(slot_to_relative(slot))),
-            slot_length: Arc::new(move |slot| Ok(slot_length(slot))),
+        Self::new(
+            move |epoch| Ok(size(epoch)),
+            move |epoch| Ok(first(epoch)),
+            move |slot| Ok(epoch(slot)),
+            move |slot| Ok(slot_to_relative(slot)),
+            move |slot| Ok(slot_length(slot)),
+        )
+    }
+
+    /// Every epoch has `epoch_size` slots of `slot_length`, starting at slot
+    /// zero. Same as [`fixed_epoch_info`].
+    #[must_use]
+    pub fn fixed(epoch_size: EpochSize, slot_length: SlotLength) -> Self {
+        fixed_epoch_info(epoch_size, slot_length)
+    }
+}
+
+/// Source built from one closure per query, as passed to [`EpochInfo::new`].
+struct FnSource<E> {
+    size: EpochQuery<EpochSize, E>,
+    first: EpochQuery<SlotNo, E>,
+    epoch: SlotQuery<EpochNo, E>,
+    slot_to_relative: SlotQuery<RelativeTime, E>,
+    slot_length: SlotQuery<SlotLength, E>,
+}
+
+impl<E> EpochInfoSource for FnSource<E> {
+    type Error = E;
+
+    fn epoch_size(&self, epoch: EpochNo) -> EpochResult<EpochSize, E> {
+        (self.size)(epoch)
+    }
+
+    fn first_slot_of_epoch(&self, epoch: EpochNo) -> EpochResult<SlotNo, E> {
+        (self.first)(epoch)
+    }
+
+    fn epoch_of_slot(&self, slot: SlotNo) -> EpochResult<EpochNo, E> {
+        (self.epoch)(slot)
+    }
+
+    fn slot_to_relative_time(&self, slot: SlotNo) -> EpochResult<RelativeTime, E> {
+        (self.slot_to_relative)(slot)
+    }
+
+    fn slot_length(&self, slot: SlotNo) -> EpochResult<SlotLength, E> {
+        (self.slot_length)(slot)
+    }
+}
+
+/// Replaces the per-slot queries of `inner` where an override is installed.
+struct Overrides<E> {
+    inner: EpochInfo<E>,
+    slot_length_at_slot: Option<SlotQuery<SlotLength, E>>,
+    elapsed_time_to_slot: Option<SlotQuery<RelativeTime, E>>,
+}
+
+impl<E> EpochInfoSource for Overrides<E> {
+    type Error = E;
+
+    fn epoch_size(&self, epoch: EpochNo) -> EpochResult<EpochSize, E> {
+        self.inner.epoch_size(epoch)
+    }
+
+    fn first_slot_of_epoch(&self, epoch: EpochNo) -> EpochResult<SlotNo, E> {
+        self.inner.first_slot_of_epoch(epoch)
+    }
+
+    fn epoch_of_slot(&self, slot: SlotNo) -> EpochResult<EpochNo, E> {
+        self.inner.epoch_of_slot(slot)
+    }
+
+    fn slot_to_relative_time(&self, slot: SlotNo) -> EpochResult<RelativeTime, E> {
+        self.inner.slot_to_relative_time(slot)
+    }
+
+    fn slot_length(&self, slot: SlotNo) -> EpochResult<SlotLength, E> {
+        self.inner.slot_length(slot)
+    }
+
+    fn slot_length_at_slot(&self, slot: SlotNo) -> EpochResult<SlotLength, E> {
+        match &self.slot_length_at_slot {
+            Some(query) => query(slot),
+            None => self.inner.slot_length_at_slot(slot),
+        }
+    }
+
+    fn elapsed_time_to_slot(&self, slot: SlotNo) -> EpochResult<RelativeTime, E> {
+        match (&self.elapsed_time_to_slot, &self.slot_length_at_slot) {
+            (Some(query), _) => query(slot),
+            // A slot length override drives the default elapsed time, unless
+            // an elapsed-time query was installed underneath it.
+            (None, Some(query)) if !self.inner.elapsed_overridden => {
+                let length = query(slot)?;
+                Ok(RelativeTime::new(mult_slot_length(length, slot.0)))
+            },
+            (None, _) => self.inner.elapsed_time_to_slot(slot),
         }
     }
 }
 
+/// Source behind [`EpochInfo::map_error`].
+struct MapError<E, F> {
+    inner: EpochInfo<E>,
+    f: Box<dyn Fn(E) -> F + Send + Sync>,
+}
+
+impl<E, F> EpochInfoSource for MapError<E, F> {
+    type Error = F;
+
+    fn epoch_size(&self, epoch: EpochNo) -> EpochResult<EpochSize, F> {
+        self.inner.epoch_size(epoch).map_err(&self.f)
+    }
+
+    fn first_slot_of_epoch(&self, epoch: EpochNo) -> EpochResult<SlotNo, F> {
+        self.inner.first_slot_of_epoch(epoch).map_err(&self.f)
+    }
+
+    fn epoch_of_slot(&self, slot: SlotNo) -> EpochResult<EpochNo, F> {
+        self.inner.epoch_of_slot(slot).map_err(&self.f)
+    }
+
+    fn slot_to_relative_time(&self, slot: SlotNo) -> EpochResult<RelativeTime, F> {
+        self.inner.slot_to_relative_time(slot).map_err(&self.f)
+    }
+
+    fn slot_length(&self, slot: SlotNo) -> EpochResult<SlotLength, F> {
+        self.inner.slot_length(slot).map_err(&self.f)
+    }
+
+    fn slot_length_at_slot(&self, slot: SlotNo) -> EpochResult<SlotLength, F> {
+        self.inner.slot_length_at_slot(slot).map_err(&self.f)
+    }
+
+    fn elapsed_time_to_slot(&self, slot: SlotNo) -> EpochResult<RelativeTime, F> {
+        self.inner.elapsed_time_to_slot(slot).map_err(&self.f)
+    }
+}
+
 pub fn hoist_epoch_info<E, F>(
     info: EpochInfo<E>,
     f: impl Fn(E) -> F + Send + Sync + 'static,
@@ -136,22 +391,22 @@ where
 }
 
 pub fn epoch_info_size<E>(info: &EpochInfo<E>, epoch: EpochNo) -> EpochResult<EpochSize, E> {
-    (info.size)(epoch)
+    info.epoch_size(epoch)
 }
 
 pub fn epoch_info_first<E>(info: &EpochInfo<E>, epoch: EpochNo) -> EpochResult<SlotNo, E> {
-    (info.first)(epoch)
+    info.first_slot_of_epoch(epoch)
 }
 
 pub fn epoch_info_epoch<E>(info: &EpochInfo<E>, slot: SlotNo) -> EpochResult<EpochNo, E> {
-    (info.epoch)(slot)
+    info.epoch_of_slot(slot)
 }
 
 pub fn epoch_info_slot_to_relative_time<E>(
     info: &EpochInfo<E>,
     slot: SlotNo,
 ) -> EpochResult<RelativeTime, E> {
-    (info.slot_to_relative)(slot)
+    info.slot_to_relative_time(slot)
 }
 
 pub fn epoch_info_slot_to_utc_time<E>(
@@ -164,7 +419,35 @@ pub fn epoch_info_slot_to_utc_time<E>(
 }
 
 pub fn epoch_info_slot_length<E>(info: &EpochInfo<E>, slot: SlotNo) -> EpochResult<SlotLength, E> {
-    (info.slot_length)(slot)
+    info.slot_length(slot)
+}
+
+/// Slot length applicable to `slot`.
+///
+/// Uses the query installed with [`EpochInfo::with_slot_length_at_slot`] or
+/// the source's [`EpochInfoSource::slot_length_at_slot`]. By default the slot
+/// length of the first slot of the epoch containing `slot` is returned, which
+/// assumes the underlying per-epoch accessors describe a single era.
+pub fn epoch_info_slot_length_at_slot<E>(
+    info: &EpochInfo<E>,
+    slot: SlotNo,
+) -> EpochResult<SlotLength, E> {
+    info.slot_length_at_slot(slot)
+}
+
+/// Wall-clock time elapsed between system start and the beginning of `slot`.
+///
+/// Uses the query installed with [`EpochInfo::with_elapsed_time_to_slot`] or
+/// the source's [`EpochInfoSource::elapsed_time_to_slot`]. By default `slot`
+/// is multiplied by [`epoch_info_slot_length_at_slot`], which is only correct
+/// within a single era starting at slot zero; across a Byron (20s) to Shelley
+/// (1s) boundary the elapsed time must be integrated piecewise by an
+/// overriding implementation.
+pub fn epoch_info_elapsed_time_to_slot<E>(
+    info: &EpochInfo<E>,
+    slot: SlotNo,
+) -> EpochResult<RelativeTime, E> {
+    info.elapsed_time_to_slot(slot)
 }
 
 pub fn epoch_info_range<E>(
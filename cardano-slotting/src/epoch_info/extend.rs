@@ -1,9 +1,6 @@
-use crate::epoch_info::api::{
-    EpochInfo, epoch_info_epoch, epoch_info_first, epoch_info_size, epoch_info_slot_length,
-    epoch_info_slot_to_relative_time,
-};
+use crate::epoch_info::api::{EpochInfo, EpochInfoSource, EpochResult};
 use crate::slot::{EpochNo, EpochSize, SlotNo};
-use crate::time::{add_relative_time, get_slot_length, mult_nominal_diff_time};
+use crate::time::{RelativeTime, SlotLength, add_relative_time, mult_slot_length};
 
 #[must_use]
 pub fn unsafe_linear_extend_epoch_info<E>(
@@ -13,71 +10,105 @@ pub fn unsafe_linear_extend_epoch_info<E>(
 where
     E: Send + Sync + 'static,
 {
-    let size_info = underlying.clone();
-    let first_info = underlying.clone();
-    let epoch_info_closure = underlying.clone();
-    let time_info = underlying.clone();
-    let length_info = underlying.clone();
-
-    EpochInfo::new(
-        move |epoch| {
-            let last_known = epoch_info_epoch(&size_info, basis_slot)?;
-            if epoch <= last_known {
-                epoch_info_size(&size_info, epoch)
-            } else {
-                epoch_info_size(&size_info, last_known)
-            }
-        },
-        move |epoch| {
-            let last_known = epoch_info_epoch(&first_info, basis_slot)?;
-            if epoch <= last_known {
-                epoch_info_first(&first_info, epoch)
-            } else {
-                let lke_start = epoch_info_first(&first_info, last_known)?;
-                let EpochSize(size) = epoch_info_size(&first_info, epoch)?;
-                let diff_epochs = epoch.0.saturating_sub(last_known.0);
-                let offset = diff_epochs.saturating_mul(size);
-                Ok(SlotNo(lke_start.0.saturating_add(offset)))
-            }
-        },
-        move |slot| {
-            if slot <= basis_slot {
-                epoch_info_epoch(&epoch_info_closure, slot)
-            } else {
-                let last_known = epoch_info_epoch(&epoch_info_closure, basis_slot)?;
-                let last_start = epoch_info_first(&epoch_info_closure, last_known)?;
-                let EpochSize(size) = epoch_info_size(&epoch_info_closure, last_known)?;
-                let slots_forward = slot.0.saturating_sub(last_start.0);
-                Ok(EpochNo(last_known.0 + slots_forward / size))
-            }
-        },
-        move |slot| {
-            if slot <= basis_slot {
-                epoch_info_slot_to_relative_time(&time_info, slot)
-            } else {
-                let slot_diff = slot.0.saturating_sub(basis_slot.0);
-                let anchor = epoch_info_slot_to_relative_time(&time_info, basis_slot)?;
-                let length = epoch_info_slot_length(&time_info, basis_slot)?;
-                let delta = mult_nominal_diff_time(get_slot_length(length), slot_diff);
-                Ok(add_relative_time(delta, anchor))
-            }
-        },
-        move |slot| {
-            if slot <= basis_slot {
-                epoch_info_slot_length(&length_info, slot)
-            } else {
-                epoch_info_slot_length(&length_info, basis_slot)
-            }
-        },
-    )
+    EpochInfo::from_source(LinearExtension::new(basis_slot, underlying))
+}
+
+/// Source that answers from `underlying` up to `basis_slot` and projects the
+/// epoch size and slot length in force at `basis_slot` linearly beyond it.
+///
+/// Unsafe in the same sense as [`unsafe_linear_extend_epoch_info`]: past the
+/// basis slot the answers are a guess that ignores any later era change.
+#[derive(Clone, Debug)]
+pub struct LinearExtension<S> {
+    basis_slot: SlotNo,
+    underlying: S,
+}
+
+impl<S> LinearExtension<S> {
+    /// Extend `underlying` past `basis_slot`.
+    pub const fn new(basis_slot: SlotNo, underlying: S) -> Self {
+        Self {
+            basis_slot,
+            underlying,
+        }
+    }
+}
+
+impl<S: EpochInfoSource> EpochInfoSource for LinearExtension<S> {
+    type Error = S::Error;
+
+    fn epoch_size(&self, epoch: EpochNo) -> EpochResult<EpochSize, S::Error> {
+        let last_known = self.underlying.epoch_of_slot(self.basis_slot)?;
+        self.underlying.epoch_size(epoch.min(last_known))
+    }
+
+    fn first_slot_of_epoch(&self, epoch: EpochNo) -> EpochResult<SlotNo, S::Error> {
+        let last_known = self.underlying.epoch_of_slot(self.basis_slot)?;
+        if epoch <= last_known {
+            self.underlying.first_slot_of_epoch(epoch)
+        } else {
+            let lke_start = self.underlying.first_slot_of_epoch(last_known)?;
+            let EpochSize(size) = self.underlying.epoch_size(epoch)?;
+            let diff_epochs = epoch.0.saturating_sub(last_known.0);
+            let offset = diff_epochs.saturating_mul(size);
+            Ok(SlotNo(lke_start.0.saturating_add(offset)))
+        }
+    }
+
+    fn epoch_of_slot(&self, slot: SlotNo) -> EpochResult<EpochNo, S::Error> {
+        if slot <= self.basis_slot {
+            self.underlying.epoch_of_slot(slot)
+        } else {
+            let last_known = self.underlying.epoch_of_slot(self.basis_slot)?;
+            let last_start = self.underlying.first_slot_of_epoch(last_known)?;
+            let EpochSize(size) = self.underlying.epoch_size(last_known)?;
+            let slots_forward = slot.0.saturating_sub(last_start.0);
+            Ok(EpochNo(last_known.0 + slots_forward / size))
+        }
+    }
+
+    fn slot_to_relative_time(&self, slot: SlotNo) -> EpochResult<RelativeTime, S::Error> {
+        if slot <= self.basis_slot {
+            self.underlying.slot_to_relative_time(slot)
+        } else {
+            let slot_diff = slot.0.saturating_sub(self.basis_slot.0);
+            let anchor = self.underlying.slot_to_relative_time(self.basis_slot)?;
+            let length = self.underlying.slot_length(self.basis_slot)?;
+            let delta = mult_slot_length(length, slot_diff);
+            Ok(add_relative_time(delta, anchor))
+        }
+    }
+
+    fn slot_length(&self, slot: SlotNo) -> EpochResult<SlotLength, S::Error> {
+        self.underlying.slot_length(slot.min(self.basis_slot))
+    }
+
+    fn slot_length_at_slot(&self, slot: SlotNo) -> EpochResult<SlotLength, S::Error> {
+        self.underlying
+            .slot_length_at_slot(slot.min(self.basis_slot))
+    }
+
+    fn elapsed_time_to_slot(&self, slot: SlotNo) -> EpochResult<RelativeTime, S::Error> {
+        if slot <= self.basis_slot {
+            self.underlying.elapsed_time_to_slot(slot)
+        } else {
+            let slot_diff = slot.0.saturating_sub(self.basis_slot.0);
+            let anchor = self.underlying.elapsed_time_to_slot(self.basis_slot)?;
+            let length = self.underlying.slot_length_at_slot(self.basis_slot)?;
+            let delta = mult_slot_length(length, slot_diff);
+            Ok(add_relative_time(delta, anchor))
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::epoch_info::api::{epoch_info_slot_length, epoch_info_slot_to_relative_time};
+    use crate::epoch_info::api::{
+        epoch_info_elapsed_time_to_slot, epoch_info_epoch, epoch_info_slot_length,
+        epoch_info_slot_length_at_slot, epoch_info_slot_to_relative_time,
+    };
     use crate::epoch_info::fixed::fixed_epoch_info;
-    use crate::slot::{EpochNo, EpochSize, SlotNo};
     use crate::time::slot_length_from_sec;
     use time::Duration;
 
@@ -101,4 +132,72 @@ mod tests {
         let relative = epoch_info_slot_to_relative_time(&extended, far_slot).unwrap();
         assert_eq!(relative.duration(), Duration::seconds(45));
     }
+
+    #[test]
+    fn extension_integrates_elapsed_time_across_eras() {
+        // Two Byron epochs of 10 slots at 20s, then Shelley epochs of 50 slots at 1s.
+        let byron_slots = 20;
+        let byron_time = Duration::seconds(20 * 20);
+        let mixed = EpochInfo::from_pure(
+            move |epoch| {
+                if epoch.0 < 2 {
+                    EpochSize(10)
+                } else {
+                    EpochSize(50)
+                }
+            },
+            move |epoch| {
+                if epoch.0 < 2 {
+                    SlotNo(epoch.0 * 10)
+                } else {
+                    SlotNo(byron_slots + (epoch.0 - 2) * 50)
+                }
+            },
+            move |slot| {
+                if slot.0 < byron_slots {
+                    EpochNo(slot.0 / 10)
+                } else {
+                    EpochNo(2 + (slot.0 - byron_slots) / 50)
+                }
+            },
+            move |slot| {
+                if slot.0 < byron_slots {
+                    RelativeTime::new(Duration::seconds(20 * i64::try_from(slot.0).unwrap()))
+                } else {
+                    let shelley = i64::try_from(slot.0 - byron_slots).unwrap();
+                    RelativeTime::new(byron_time + Duration::seconds(shelley))
+                }
+            },
+            move |slot| {
+                if slot.0 < byron_slots {
+                    slot_length_from_sec(20)
+                } else {
+                    slot_length_from_sec(1)
+                }
+            },
+        );
+        let relative = mixed.clone();
+        let mixed = mixed.with_elapsed_time_to_slot(move |slot| {
+            epoch_info_slot_to_relative_time(&relative, slot)
+        });
+
+        let basis_slot = SlotNo(40);
+        let extended = unsafe_linear_extend_epoch_info(basis_slot, mixed);
+
+        assert_eq!(
+            epoch_info_slot_length_at_slot(&extended, SlotNo(5)).unwrap(),
+            slot_length_from_sec(20)
+        );
+        assert_eq!(
+            epoch_info_slot_length_at_slot(&extended, SlotNo(500)).unwrap(),
+            slot_length_from_sec(1)
+        );
+
+        let elapsed = epoch_info_elapsed_time_to_slot(&extended, SlotNo(500)).unwrap();
+        assert_eq!(elapsed.duration(), byron_time + Duration::seconds(480));
+        assert_eq!(
+            elapsed,
+            epoch_info_slot_to_relative_time(&extended, SlotNo(500)).unwrap()
+        );
+    }
 }
@@ -1,22 +1,48 @@
 use std::convert::Infallible;
 
-use crate::epoch_info::api::EpochInfo;
+use crate::epoch_info::api::{EpochInfo, EpochInfoSource, EpochResult};
 use crate::slot::{EpochNo, EpochSize, SlotNo};
-use crate::time::{RelativeTime, SlotLength, get_slot_length, mult_nominal_diff_time};
+use crate::time::{RelativeTime, SlotLength, mult_slot_length};
 
 #[must_use]
 pub fn fixed_epoch_info(epoch_size: EpochSize, slot_length: SlotLength) -> EpochInfo<Infallible> {
-    EpochInfo::from_pure(
-        move |_| epoch_size,
-        move |epoch| fixed_epoch_info_first(epoch_size, epoch),
-        move |slot| fixed_epoch_info_epoch(epoch_size, slot),
-        move |slot| {
-            let slot_index = slot.0;
-            let len = get_slot_length(slot_length);
-            RelativeTime::new(mult_nominal_diff_time(len, slot_index))
-        },
-        move |_| slot_length,
-    )
+    EpochInfo::from_source(FixedEpochInfo {
+        epoch_size,
+        slot_length,
+    })
+}
+
+/// Single-era schedule behind [`fixed_epoch_info`].
+struct FixedEpochInfo {
+    epoch_size: EpochSize,
+    slot_length: SlotLength,
+}
+
+impl EpochInfoSource for FixedEpochInfo {
+    type Error = Infallible;
+
+    fn epoch_size(&self, _epoch: EpochNo) -> EpochResult<EpochSize, Infallible> {
+        Ok(self.epoch_size)
+    }
+
+    fn first_slot_of_epoch(&self, epoch: EpochNo) -> EpochResult<SlotNo, Infallible> {
+        Ok(fixed_epoch_info_first(self.epoch_size, epoch))
+    }
+
+    fn epoch_of_slot(&self, slot: SlotNo) -> EpochResult<EpochNo, Infallible> {
+        Ok(fixed_epoch_info_epoch(self.epoch_size, slot))
+    }
+
+    fn slot_to_relative_time(&self, slot: SlotNo) -> EpochResult<RelativeTime, Infallible> {
+        Ok(RelativeTime::new(mult_slot_length(
+            self.slot_length,
+            slot.0,
+        )))
+    }
+
+    fn slot_length(&self, _slot: SlotNo) -> EpochResult<SlotLength, Infallible> {
+        Ok(self.slot_length)
+    }
 }
 
 #[must_use]
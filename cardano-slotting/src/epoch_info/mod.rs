@@ -1,12 +1,15 @@
 pub mod api;
 pub mod extend;
 pub mod fixed;
+pub mod phase;
 
-pub use api::EpochInfo;
 pub use api::generalize_epoch_info;
 pub use api::hoist_epoch_info;
+pub use api::{EpochInfo, EpochInfoSource, EpochResult};
 pub use api::{
-    epoch_info_epoch, epoch_info_first, epoch_info_range, epoch_info_size, epoch_info_slot_length,
+    epoch_info_elapsed_time_to_slot, epoch_info_epoch, epoch_info_first, epoch_info_range,
+    epoch_info_size, epoch_info_slot_length, epoch_info_slot_length_at_slot,
     epoch_info_slot_to_relative_time, epoch_info_slot_to_utc_time,
 };
-pub use extend::unsafe_linear_extend_epoch_info;
+pub use extend::{LinearExtension, unsafe_linear_extend_epoch_info};
+pub use phase::{SlotPhase, slot_phase, slots_into_epoch};
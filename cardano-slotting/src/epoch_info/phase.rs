@@ -0,0 +1,146 @@
+//! Where a slot sits relative to its epoch's randomness window.
+//!
+//! Block VRF outputs only feed the next epoch's nonce while the candidate
+//! nonce is still being evolved, i.e. during the first part of each epoch.
+//! [`slot_phase`] is the single place that boundary is decided.
+
+use crate::epoch_info::api::{
+    EpochInfo, EpochResult, epoch_info_epoch, epoch_info_first, epoch_info_size,
+};
+use crate::slot::SlotNo;
+
+/// Whether a slot's block contributes to the epoch nonce.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SlotPhase {
+    /// The slot is inside the randomness window; `slots_into_epoch` is its
+    /// offset from the first slot of the epoch.
+    ContributesToNonce { slots_into_epoch: u64 },
+    /// The candidate nonce is frozen; `slots_remaining` more slots follow
+    /// this one before the next epoch starts (0 for the epoch's last slot).
+    AfterNonceWindow { slots_remaining: u64 },
+}
+
+impl SlotPhase {
+    /// Whether this is [`SlotPhase::ContributesToNonce`].
+    #[must_use]
+    pub const fn contributes_to_nonce(self) -> bool {
+        matches!(self, SlotPhase::ContributesToNonce { .. })
+    }
+}
+
+/// Offset of `slot` from the first slot of its epoch.
+pub fn slots_into_epoch<E>(info: &EpochInfo<E>, slot: SlotNo) -> EpochResult<u64, E> {
+    let epoch = epoch_info_epoch(info, slot)?;
+    let first = epoch_info_first(info, epoch)?;
+    Ok(slot.0 - first.0)
+}
+
+/// Classify `slot` against a randomness window of `randomness_window` slots
+/// at the start of each epoch.
+///
+/// The slot contributes exactly when `slot < first_slot + randomness_window`,
+/// with a strict inequality as in the Shelley `UPDN` rule: the slot at offset
+/// `randomness_window` is the first one after the window. A window at least
+/// as long as the epoch makes every slot contribute.
+///
+/// The spec phrases the rule from the end of the epoch: the candidate nonce
+/// freezes `w` slots before the next epoch's first slot, where `w` is the
+/// [`stability_window`](crate::stability_window) (`3k/f`) before Conway and
+/// the [`randomness_stabilization_window`](crate::randomness_stabilization_window)
+/// (`4k/f`) from Conway on. The matching `randomness_window` is
+/// `epoch_size - w`.
+pub fn slot_phase<E>(
+    info: &EpochInfo<E>,
+    slot: SlotNo,
+    randomness_window: u64,
+) -> EpochResult<SlotPhase, E> {
+    let epoch = epoch_info_epoch(info, slot)?;
+    let first = epoch_info_first(info, epoch)?;
+    let size = epoch_info_size(info, epoch)?;
+    let offset = slot.0 - first.0;
+    Ok(if offset < randomness_window {
+        SlotPhase::ContributesToNonce {
+            slots_into_epoch: offset,
+        }
+    } else {
+        SlotPhase::AfterNonceWindow {
+            slots_remaining: size.0.saturating_sub(offset + 1),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::epoch_info::fixed::fixed_epoch_info;
+    use crate::slot::EpochSize;
+    use crate::time::slot_length_from_sec;
+
+    /// (epoch size, randomness window) pairs, including a window covering the
+    /// whole epoch and an empty one.
+    const SCHEDULES: [(u64, u64); 4] = [(10, 4), (100, 60), (7, 7), (5, 0)];
+
+    fn check_schedule(epoch_size: u64, window: u64) {
+        let info = fixed_epoch_info(EpochSize(epoch_size), slot_length_from_sec(1));
+        for epoch in [0, 1, 9] {
+            let first = epoch * epoch_size;
+            let phase = |offset: u64| slot_phase(&info, SlotNo(first + offset), window).unwrap();
+
+            if window > 0 {
+                assert_eq!(
+                    phase(window - 1),
+                    SlotPhase::ContributesToNonce {
+                        slots_into_epoch: window - 1
+                    },
+                    "size {epoch_size}, window {window}: last slot inside"
+                );
+            }
+            if window < epoch_size {
+                assert_eq!(
+                    phase(window),
+                    SlotPhase::AfterNonceWindow {
+                        slots_remaining: epoch_size - window - 1
+                    },
+                    "size {epoch_size}, window {window}: first slot after"
+                );
+            }
+            if window + 1 < epoch_size {
+                assert!(!phase(window + 1).contributes_to_nonce());
+            }
+
+            // Epoch boundaries: the first slot contributes unless the window
+            // is empty, and the last slot has nothing remaining.
+            assert_eq!(phase(0).contributes_to_nonce(), window > 0);
+            let last = phase(epoch_size - 1);
+            if window >= epoch_size {
+                assert!(last.contributes_to_nonce());
+            } else {
+                assert_eq!(last, SlotPhase::AfterNonceWindow { slots_remaining: 0 });
+            }
+            assert_eq!(slots_into_epoch(&info, SlotNo(first)).unwrap(), 0);
+            assert_eq!(
+                slots_into_epoch(&info, SlotNo(first + epoch_size - 1)).unwrap(),
+                epoch_size - 1
+            );
+        }
+    }
+
+    #[test]
+    fn window_boundaries_on_fixed_schedules() {
+        for (epoch_size, window) in SCHEDULES {
+            check_schedule(epoch_size, window);
+        }
+    }
+
+    #[test]
+    fn window_longer_than_epoch_covers_every_slot() {
+        let info = fixed_epoch_info(EpochSize(10), slot_length_from_sec(1));
+        for slot in 0..30 {
+            assert!(
+                slot_phase(&info, SlotNo(slot), 1_000)
+                    .unwrap()
+                    .contributes_to_nonce()
+            );
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! `NoThunks` and `HeapWords` impls for the slotting newtypes, mirroring the
+//! derived instances in the Haskell `cardano-slotting` package.
+//!
+//! The newtypes are erased in GHC, so their heap estimate is that of the
+//! wrapped `Word64`.
+
+use crate::block::BlockNo;
+use crate::slot::{EpochNo, EpochSize, SlotNo, WithOrigin};
+
+#[cfg(feature = "nothunks")]
+mod no_thunks {
+    use nothunks::{NoThunks, NoThunksResult};
+
+    use super::{BlockNo, EpochNo, EpochSize, SlotNo, WithOrigin};
+
+    macro_rules! impl_no_thunks_for_newtype {
+        ($($ty:ty),+ $(,)?) => {
+            $(
+                impl NoThunks for $ty {
+                    fn no_thunks(&self, _context: &[&str]) -> NoThunksResult {
+                        Ok(())
+                    }
+                }
+            )+
+        };
+    }
+
+    impl_no_thunks_for_newtype!(SlotNo, EpochNo, EpochSize, BlockNo);
+
+    impl<T: NoThunks> NoThunks for WithOrigin<T> {
+        fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
+            match self {
+                WithOrigin::Origin => Ok(()),
+                WithOrigin::At(value) => value.no_thunks(context),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "heapwords")]
+mod heap_words {
+    use heapwords::{HeapWords, heap_words1};
+
+    use super::{BlockNo, EpochNo, EpochSize, SlotNo, WithOrigin};
+
+    macro_rules! impl_heap_words_for_newtype {
+        ($($ty:ty),+ $(,)?) => {
+            $(
+                impl HeapWords for $ty {
+                    fn heap_words(&self) -> usize {
+                        self.0.heap_words()
+                    }
+                }
+            )+
+        };
+    }
+
+    impl_heap_words_for_newtype!(SlotNo, EpochNo, EpochSize, BlockNo);
+
+    impl<T: HeapWords> HeapWords for WithOrigin<T> {
+        fn heap_words(&self) -> usize {
+            match self {
+                // Nullary constructors are shared closures.
+                WithOrigin::Origin => 0,
+                WithOrigin::At(value) => heap_words1(value),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "nothunks")]
+    #[test]
+    fn slotting_types_have_no_thunks() {
+        use nothunks::unsafe_no_thunks;
+
+        assert!(unsafe_no_thunks(&SlotNo(1)).is_none());
+        assert!(unsafe_no_thunks(&WithOrigin::At(BlockNo(2))).is_none());
+        assert!(unsafe_no_thunks(&WithOrigin::<EpochNo>::Origin).is_none());
+        assert!(unsafe_no_thunks(&EpochSize(3)).is_none());
+    }
+
+    #[cfg(feature = "heapwords")]
+    #[test]
+    fn newtypes_cost_the_same_as_their_word() {
+        use heapwords::HeapWords;
+
+        assert_eq!(SlotNo(7).heap_words(), 7u64.heap_words());
+        assert_eq!(EpochNo(7).heap_words(), 2);
+        assert_eq!(WithOrigin::<SlotNo>::Origin.heap_words(), 0);
+        assert_eq!(WithOrigin::At(SlotNo(7)).heap_words(), 4);
+    }
+}
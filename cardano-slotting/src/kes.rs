@@ -0,0 +1,73 @@
+//! Operational certificate KES period checks on [`SlotNo`].
+//!
+//! Thin wrappers over `cardano_crypto_class::kes::evolution`, which works on
+//! plain `u64` slots so that the crypto crate does not depend on this one.
+
+use cardano_crypto_class::kes::evolution;
+pub use cardano_crypto_class::kes::evolution::{KesPeriodInfo, OcertPeriodError};
+
+use crate::slot::SlotNo;
+
+/// The absolute KES period containing `slot`, or `None` if
+/// `slots_per_kes_period` is zero.
+#[must_use]
+pub const fn kes_period_of_slot(slot: SlotNo, slots_per_kes_period: u64) -> Option<u64> {
+    evolution::kes_period_of_slot(slot.get(), slots_per_kes_period)
+}
+
+/// Check that an operational certificate starting at KES period
+/// `ocert_start_period` and valid for `total_periods` periods
+/// (`maxKESEvolutions`) can sign at `current_slot`.
+///
+/// See [`evolution::validate_ocert_period`] for the errors.
+pub fn validate_ocert_period(
+    current_slot: SlotNo,
+    slots_per_kes_period: u64,
+    ocert_start_period: u64,
+    total_periods: u64,
+) -> Result<KesPeriodInfo, OcertPeriodError> {
+    evolution::validate_ocert_period(
+        current_slot.get(),
+        slots_per_kes_period,
+        ocert_start_period,
+        total_periods,
+    )
+}
+
+/// First slot of KES period `period`, or `None` on overflow.
+#[must_use]
+pub const fn first_slot_of_kes_period(period: u64, slots_per_kes_period: u64) -> Option<SlotNo> {
+    match period.checked_mul(slots_per_kes_period) {
+        Some(slot) => Some(SlotNo(slot)),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adapter_matches_plain_slots() {
+        let start = first_slot_of_kes_period(400, 129_600).unwrap();
+        let info = validate_ocert_period(start, 129_600, 400, 62).unwrap();
+        assert_eq!((info.current_period, info.evolution), (400, 0));
+        assert_eq!(
+            SlotNo(info.expires_at_slot),
+            first_slot_of_kes_period(462, 129_600).unwrap()
+        );
+        assert_eq!(kes_period_of_slot(start - 1, 129_600), Some(399));
+        assert!(matches!(
+            validate_ocert_period(start - 1, 129_600, 400, 62),
+            Err(OcertPeriodError::NotYetValid { .. })
+        ));
+        assert!(matches!(
+            validate_ocert_period(SlotNo(info.expires_at_slot), 129_600, 400, 62),
+            Err(OcertPeriodError::Expired {
+                expired_at_slot: 59_875_200,
+                ..
+            })
+        ));
+        assert_eq!(first_slot_of_kes_period(u64::MAX, 2), None);
+    }
+}
@@ -6,27 +6,44 @@
 //! extend epoch information.
 
 #![cfg_attr(test, allow(clippy::unwrap_used))]
+#![cfg_attr(test, allow(clippy::panic))]
 #![allow(clippy::missing_panics_doc)]
 #![allow(clippy::missing_errors_doc)]
 
 pub mod block;
+pub mod bulk;
 pub mod epoch_info;
+#[cfg(any(feature = "nothunks", feature = "heapwords"))]
+mod instances;
+#[cfg(feature = "kes")]
+pub mod kes;
+pub mod security_param;
 pub mod slot;
+pub mod storage_key;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 pub mod time;
 
 pub use block::BlockNo;
+pub use bulk::{SlotIndex, SlotIndexBuildError, SlotIndexError};
 pub use epoch_info::{
-    EpochInfo, fixed::fixed_epoch_info, fixed::fixed_epoch_info_epoch,
-    fixed::fixed_epoch_info_first, generalize_epoch_info, hoist_epoch_info,
-    unsafe_linear_extend_epoch_info,
+    EpochInfo, EpochInfoSource, fixed::fixed_epoch_info, fixed::fixed_epoch_info_epoch,
+    fixed::fixed_epoch_info_first, generalize_epoch_info, hoist_epoch_info, phase::SlotPhase,
+    phase::slot_phase, phase::slots_into_epoch, unsafe_linear_extend_epoch_info,
+};
+pub use security_param::{
+    SecurityParam, is_within_stability_window, max_rollbacks, randomness_stabilization_window,
+    stability_window,
 };
 pub use slot::{
     EpochInterval, EpochNo, EpochSize, SlotNo, WithOrigin, add_epoch_interval, at, bin_op_epoch_no,
     from_with_origin, origin, with_origin, with_origin_from_maybe, with_origin_to_maybe,
 };
+pub use storage_key::{CompositeKey, FixedKey, StorageKeyError, decode_key, encode_key};
 pub use time::{
-    RelativeTime, SlotLength, SystemStart, TimeOrderingError, add_relative_time,
-    diff_relative_time, from_relative_time, get_slot_length, mk_slot_length,
-    mult_nominal_diff_time, mult_relative_time, slot_length_from_millisec, slot_length_from_sec,
-    slot_length_to_millisec, slot_length_to_sec, to_relative_time,
+    RelativeTime, RelativeTimeDelta, SlotLength, SlotLengthError, SystemStart, TimeConversionError,
+    TimeOrderingError, add_relative_time, diff_relative_time, from_relative_time, get_slot_length,
+    mk_slot_length, mult_nominal_diff_time, mult_relative_time, mult_slot_length,
+    slot_for_wallclock, slot_length_from_fraction, slot_length_from_millisec, slot_length_from_sec,
+    slot_length_to_millisec, slot_length_to_sec, to_relative_time, wallclock_for_slot,
 };
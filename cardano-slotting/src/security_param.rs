@@ -0,0 +1,159 @@
+//! Windows derived from the Ouroboros security parameter `k`.
+//!
+//! Consensus and the ledger measure several horizons in terms of `k` (a
+//! number of blocks) and the active slot coefficient `f` (the fraction of
+//! slots expected to carry a block). Converting between blocks and slots is
+//! where unit mistakes creep in, so every helper here states which unit it
+//! returns. All arithmetic is exact: `f` is a [`Ratio<u64>`] and the slot
+//! counts are rounded up once at the end, matching the Haskell
+//! `ceiling (fromIntegral k / f)` style formulas.
+
+use core::fmt;
+
+use num_rational::Ratio;
+use serde::{Deserialize, Serialize};
+
+use crate::slot::SlotNo;
+
+/// The security parameter `k`: the maximum number of blocks the node may
+/// roll back.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct SecurityParam(pub u64);
+
+impl SecurityParam {
+    #[must_use]
+    pub const fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    #[must_use]
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Debug for SecurityParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecurityParam({})", self.0)
+    }
+}
+
+impl fmt::Display for SecurityParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u64> for SecurityParam {
+    fn from(value: u64) -> Self {
+        SecurityParam(value)
+    }
+}
+
+impl From<SecurityParam> for u64 {
+    fn from(value: SecurityParam) -> Self {
+        value.0
+    }
+}
+
+/// Compute `ceil(multiplier * k / f)` in slots without floating point.
+///
+/// # Panics
+///
+/// Panics if `f` is zero or the result does not fit in a `u64`.
+fn slots_for_blocks(multiplier: u64, k: SecurityParam, f: Ratio<u64>) -> u64 {
+    assert!(*f.numer() != 0, "active slot coefficient must be positive");
+    let numerator = u128::from(multiplier) * u128::from(k.0) * u128::from(*f.denom());
+    let denominator = u128::from(*f.numer());
+    u64::try_from(numerator.div_ceil(denominator)).expect("slot window exceeds u64::MAX")
+}
+
+/// Number of slots after which a block is considered stable:
+/// `ceil(3k / f)` slots.
+///
+/// Within this many slots the chain grows by at least `k` blocks with
+/// overwhelming probability, so anything older than the window can no longer
+/// be rolled back. Applies from Shelley onwards; Byron, where every slot may
+/// hold a block, uses `2k` slots instead.
+///
+/// # Panics
+///
+/// Panics if `active_slot_coeff` is zero or the window does not fit in a
+/// `u64`.
+#[must_use]
+pub fn stability_window(k: SecurityParam, active_slot_coeff: Ratio<u64>) -> u64 {
+    slots_for_blocks(3, k, active_slot_coeff)
+}
+
+/// Number of slots before the end of an epoch after which the candidate
+/// nonce is frozen: `ceil(4k / f)` slots.
+///
+/// Conway widened the randomness stabilisation window from the `3k/f`
+/// stability window to `4k/f`; earlier Shelley-based eras use
+/// [`stability_window`] for this purpose.
+///
+/// # Panics
+///
+/// Panics if `active_slot_coeff` is zero or the window does not fit in a
+/// `u64`.
+#[must_use]
+pub fn randomness_stabilization_window(k: SecurityParam, active_slot_coeff: Ratio<u64>) -> u64 {
+    slots_for_blocks(4, k, active_slot_coeff)
+}
+
+/// Maximum rollback depth, in blocks: `k`.
+///
+/// Unlike the windows above this counts blocks, not slots, and holds in
+/// every era.
+#[must_use]
+pub const fn max_rollbacks(k: SecurityParam) -> u64 {
+    k.0
+}
+
+/// Whether `candidate_slot` is still inside the stability window of
+/// `tip_slot`, i.e. `tip_slot - candidate_slot < window`.
+///
+/// A block in such a slot may still be rolled back. Candidates at or after
+/// the tip are always within the window. `window` is a slot count such as
+/// the result of [`stability_window`].
+#[must_use]
+pub const fn is_within_stability_window(
+    tip_slot: SlotNo,
+    candidate_slot: SlotNo,
+    window: u64,
+) -> bool {
+    tip_slot.0.saturating_sub(candidate_slot.0) < window
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_round_up() {
+        // 3 * 1 / (2/3) = 4.5 slots, rounded up.
+        assert_eq!(stability_window(SecurityParam(1), Ratio::new(2, 3)), 5);
+        // 4 * 1 / (3/5) = 6.67 slots, rounded up.
+        assert_eq!(
+            randomness_stabilization_window(SecurityParam(1), Ratio::new(3, 5)),
+            7
+        );
+        assert_eq!(stability_window(SecurityParam(10), Ratio::new(1, 1)), 30);
+    }
+
+    #[test]
+    fn stability_window_boundary_is_exclusive() {
+        let tip = SlotNo(1_000);
+        assert!(is_within_stability_window(tip, SlotNo(901), 100));
+        assert!(!is_within_stability_window(tip, SlotNo(900), 100));
+        assert!(is_within_stability_window(tip, SlotNo(1_001), 100));
+        assert!(!is_within_stability_window(tip, tip, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "active slot coefficient must be positive")]
+    fn zero_coefficient_panics() {
+        let _ = stability_window(SecurityParam(1), Ratio::new_raw(0, 1));
+    }
+}
@@ -356,6 +356,45 @@ impl From<EpochNo> for u64 {
     }
 }
 
+impl EpochNo {
+    /// `self + interval`, or `None` past `u64::MAX`.
+    #[must_use]
+    pub const fn checked_add_interval(self, interval: EpochInterval) -> Option<EpochNo> {
+        match self.0.checked_add(interval.0 as u64) {
+            Some(epoch) => Some(EpochNo(epoch)),
+            None => None,
+        }
+    }
+
+    /// `self + interval`, saturating at `u64::MAX`.
+    #[must_use]
+    pub const fn saturating_add_interval(self, interval: EpochInterval) -> EpochNo {
+        EpochNo(self.0.saturating_add(interval.0 as u64))
+    }
+
+    /// Epochs from `earlier` to `self`, or `None` if `earlier` is later than
+    /// `self` or the distance does not fit an [`EpochInterval`].
+    #[must_use]
+    pub fn elapsed_since(self, earlier: EpochNo) -> Option<EpochInterval> {
+        let elapsed = self.0.checked_sub(earlier.0)?;
+        u32::try_from(elapsed).ok().map(EpochInterval)
+    }
+
+    /// Whether `self` lies in the window of `interval` epochs after `start`,
+    /// both ends inclusive: `start <= self <= start + interval`.
+    ///
+    /// This is the Conway rule for governance action lifetimes and DRep
+    /// activity. An action proposed in epoch `e` gets
+    /// `gasExpiresAfter = e + govActionLifetime` and is only treated as
+    /// expired once `gasExpiresAfter < currentEpoch`, so it can still be
+    /// ratified in epoch `e + govActionLifetime` itself. The end saturates
+    /// at `u64::MAX`.
+    #[must_use]
+    pub const fn is_within(self, start: EpochNo, interval: EpochInterval) -> bool {
+        start.0 <= self.0 && self.0 <= start.saturating_add_interval(interval).0
+    }
+}
+
 /// Epoch size, expressed in number of slots.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default)]
 #[serde(transparent)]
@@ -402,6 +441,39 @@ impl fmt::Display for EpochInterval {
     }
 }
 
+impl EpochInterval {
+    /// The empty interval.
+    pub const ZERO: EpochInterval = EpochInterval(0);
+
+    /// The longest interval, `u32::MAX` epochs.
+    pub const MAX: EpochInterval = EpochInterval(u32::MAX);
+
+    /// Sum of two intervals, or `None` past `u32::MAX` epochs.
+    #[must_use]
+    pub const fn checked_add(self, rhs: EpochInterval) -> Option<EpochInterval> {
+        match self.0.checked_add(rhs.0) {
+            Some(sum) => Some(EpochInterval(sum)),
+            None => None,
+        }
+    }
+
+    /// Sum of two intervals, saturating at [`EpochInterval::MAX`].
+    #[must_use]
+    pub const fn saturating_add(self, rhs: EpochInterval) -> EpochInterval {
+        EpochInterval(self.0.saturating_add(rhs.0))
+    }
+
+    /// The interval repeated `factor` times, or `None` past `u32::MAX`
+    /// epochs.
+    #[must_use]
+    pub const fn checked_mul(self, factor: u32) -> Option<EpochInterval> {
+        match self.0.checked_mul(factor) {
+            Some(product) => Some(EpochInterval(product)),
+            None => None,
+        }
+    }
+}
+
 impl From<u32> for EpochInterval {
     fn from(value: u32) -> Self {
         EpochInterval(value)
@@ -467,6 +539,28 @@ mod tests {
         assert_eq!(from_option, at(5));
     }
 
+    #[test]
+    fn epoch_interval_bounds() {
+        assert_eq!(
+            EpochInterval::ZERO.checked_add(EpochInterval(2)),
+            Some(EpochInterval(2))
+        );
+        assert_eq!(EpochInterval::MAX.checked_add(EpochInterval(1)), None);
+        assert_eq!(
+            EpochInterval::MAX.saturating_add(EpochInterval(1)),
+            EpochInterval::MAX
+        );
+        assert_eq!(EpochInterval(3).checked_mul(4), Some(EpochInterval(12)));
+        assert_eq!(EpochInterval(u32::MAX / 2 + 1).checked_mul(2), None);
+        assert_eq!(EpochNo(5).elapsed_since(EpochNo(6)), None);
+        assert_eq!(EpochNo(u64::MAX).elapsed_since(EpochNo(0)), None);
+        assert_eq!(
+            EpochNo(u64::MAX - 1).saturating_add_interval(EpochInterval(5)),
+            EpochNo(u64::MAX)
+        );
+        assert!(EpochNo(u64::MAX).is_within(EpochNo(u64::MAX - 1), EpochInterval(5)));
+    }
+
     #[test]
     fn add_epoch_interval_adds() {
         let epoch = EpochNo(10);
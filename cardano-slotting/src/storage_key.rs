@@ -0,0 +1,318 @@
+//! Fixed-width, order-preserving byte keys for key-value stores.
+//!
+//! RocksDB, LMDB and sled iterate keys in byte-wise order, so slot, epoch
+//! and block numbers must be stored big-endian: a little-endian key puts
+//! slot 256 (`00 01 ..`) before slot 1 (`01 00 ..`). Every encoding here
+//! has a fixed width and compares byte-wise exactly as the source values
+//! compare with [`Ord`]:
+//!
+//! | Type | Width | Encoding |
+//! |------|-------|----------|
+//! | [`SlotNo`], [`EpochNo`], [`BlockNo`] | 8 | big-endian `u64` |
+//! | [`WithOrigin<T>`] | `1 + T` | `00` and zeroes for `Origin`, `01` then `T` for `At` |
+//! | `(A, B)`, `(A, B, C)` | sum | components concatenated in order |
+//!
+//! [`encode_key`] checks at compile time that the output array has the
+//! key's width, and [`CompositeKey`] builds a multi-component key one
+//! component at a time:
+//!
+//! ```rust
+//! use cardano_slotting::storage_key::{CompositeKey, decode_key, encode_key};
+//! use cardano_slotting::{EpochNo, SlotNo};
+//!
+//! let key: [u8; 16] = CompositeKey::new()
+//!     .push(EpochNo(208))
+//!     .push(SlotNo(4_492_800))
+//!     .encode();
+//! assert_eq!(key, encode_key::<_, 16>(&(EpochNo(208), SlotNo(4_492_800))));
+//! assert_eq!(decode_key::<(EpochNo, SlotNo)>(&key), Ok((EpochNo(208), SlotNo(4_492_800))));
+//! ```
+
+use thiserror::Error;
+
+use crate::block::BlockNo;
+use crate::slot::{EpochNo, SlotNo, WithOrigin};
+
+/// Error decoding a storage key.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum StorageKeyError {
+    /// The input is not as wide as the key type.
+    #[error("storage key must be {expected} bytes, got {actual}")]
+    WrongLength { expected: usize, actual: usize },
+    /// A [`WithOrigin`] discriminator other than `00` or `01`.
+    #[error("invalid WithOrigin discriminator {tag:#04x}")]
+    InvalidOriginTag { tag: u8 },
+    /// An `Origin` key with non-zero padding; every value has exactly one
+    /// encoding.
+    #[error("Origin key has non-zero padding")]
+    NonZeroOriginPadding,
+}
+
+/// Values with a fixed-width, order-preserving byte encoding.
+pub trait FixedKey: Sized {
+    /// Width of the encoding in bytes.
+    const SIZE: usize;
+
+    /// Write the key into `out`, which is exactly [`SIZE`](Self::SIZE) bytes.
+    fn write_key(&self, out: &mut [u8]);
+
+    /// Read a key from exactly [`SIZE`](Self::SIZE) bytes.
+    fn read_key(bytes: &[u8]) -> Result<Self, StorageKeyError>;
+}
+
+/// Encode `key` into an array of its width.
+///
+/// A mismatched `N` is a compile-time error.
+#[must_use]
+pub fn encode_key<K: FixedKey, const N: usize>(key: &K) -> [u8; N] {
+    const {
+        assert!(
+            K::SIZE == N,
+            "output array width differs from the key's width"
+        )
+    };
+    let mut out = [0u8; N];
+    key.write_key(&mut out);
+    out
+}
+
+/// Decode a key, checking that `bytes` has the key's width.
+///
+/// # Errors
+///
+/// Returns [`StorageKeyError::WrongLength`] for input of any other width,
+/// and the [`WithOrigin`] errors for a malformed discriminator or padding.
+pub fn decode_key<K: FixedKey>(bytes: &[u8]) -> Result<K, StorageKeyError> {
+    if bytes.len() != K::SIZE {
+        return Err(StorageKeyError::WrongLength {
+            expected: K::SIZE,
+            actual: bytes.len(),
+        });
+    }
+    K::read_key(bytes)
+}
+
+macro_rules! u64_key {
+    ($($ty:ident),*) => {$(
+        impl $ty {
+            /// The 8-byte big-endian storage key.
+            #[must_use]
+            pub const fn to_be_key(&self) -> [u8; 8] {
+                self.0.to_be_bytes()
+            }
+
+            #[must_use]
+            pub const fn from_be_key(key: &[u8; 8]) -> Self {
+                $ty(u64::from_be_bytes(*key))
+            }
+        }
+
+        impl FixedKey for $ty {
+            const SIZE: usize = 8;
+
+            fn write_key(&self, out: &mut [u8]) {
+                out.copy_from_slice(&self.to_be_key());
+            }
+
+            fn read_key(bytes: &[u8]) -> Result<Self, StorageKeyError> {
+                let key = bytes.try_into().map_err(|_| StorageKeyError::WrongLength {
+                    expected: Self::SIZE,
+                    actual: bytes.len(),
+                })?;
+                Ok($ty::from_be_key(key))
+            }
+        }
+    )*};
+}
+
+u64_key!(SlotNo, EpochNo, BlockNo);
+
+const ORIGIN_TAG: u8 = 0x00;
+const AT_TAG: u8 = 0x01;
+
+impl<T: FixedKey> FixedKey for WithOrigin<T> {
+    const SIZE: usize = 1 + T::SIZE;
+
+    fn write_key(&self, out: &mut [u8]) {
+        match self {
+            WithOrigin::Origin => out.fill(0),
+            WithOrigin::At(value) => {
+                out[0] = AT_TAG;
+                value.write_key(&mut out[1..]);
+            },
+        }
+    }
+
+    fn read_key(bytes: &[u8]) -> Result<Self, StorageKeyError> {
+        match bytes.split_first() {
+            Some((&ORIGIN_TAG, rest)) if rest.iter().all(|&b| b == 0) => Ok(WithOrigin::Origin),
+            Some((&ORIGIN_TAG, _)) => Err(StorageKeyError::NonZeroOriginPadding),
+            Some((&AT_TAG, rest)) => T::read_key(rest).map(WithOrigin::At),
+            Some((&tag, _)) => Err(StorageKeyError::InvalidOriginTag { tag }),
+            None => Err(StorageKeyError::WrongLength {
+                expected: Self::SIZE,
+                actual: 0,
+            }),
+        }
+    }
+}
+
+/// The empty key, where a [`CompositeKey`] starts.
+impl FixedKey for () {
+    const SIZE: usize = 0;
+
+    fn write_key(&self, _out: &mut [u8]) {}
+
+    fn read_key(_bytes: &[u8]) -> Result<Self, StorageKeyError> {
+        Ok(())
+    }
+}
+
+impl<A: FixedKey, B: FixedKey> FixedKey for (A, B) {
+    const SIZE: usize = A::SIZE + B::SIZE;
+
+    fn write_key(&self, out: &mut [u8]) {
+        let (a, b) = out.split_at_mut(A::SIZE);
+        self.0.write_key(a);
+        self.1.write_key(b);
+    }
+
+    fn read_key(bytes: &[u8]) -> Result<Self, StorageKeyError> {
+        let (a, b) = bytes.split_at(A::SIZE);
+        Ok((A::read_key(a)?, B::read_key(b)?))
+    }
+}
+
+impl<A: FixedKey, B: FixedKey, C: FixedKey> FixedKey for (A, B, C) {
+    const SIZE: usize = A::SIZE + B::SIZE + C::SIZE;
+
+    fn write_key(&self, out: &mut [u8]) {
+        let (a, rest) = out.split_at_mut(A::SIZE);
+        let (b, c) = rest.split_at_mut(B::SIZE);
+        self.0.write_key(a);
+        self.1.write_key(b);
+        self.2.write_key(c);
+    }
+
+    fn read_key(bytes: &[u8]) -> Result<Self, StorageKeyError> {
+        let (a, rest) = bytes.split_at(A::SIZE);
+        let (b, c) = rest.split_at(B::SIZE);
+        Ok((A::read_key(a)?, B::read_key(b)?, C::read_key(c)?))
+    }
+}
+
+/// A key assembled from components in order; its width is tracked in the
+/// type, so [`encode`](Self::encode) into an array of the wrong width does
+/// not compile.
+///
+/// The encoding is the components' encodings concatenated, the same bytes
+/// as the flat tuple of the components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct CompositeKey<T = ()>(T);
+
+impl CompositeKey {
+    #[must_use]
+    pub const fn new() -> Self {
+        CompositeKey(())
+    }
+}
+
+impl<T: FixedKey> CompositeKey<T> {
+    /// Width of the key built so far.
+    pub const SIZE: usize = T::SIZE;
+
+    /// Append a component.
+    #[must_use]
+    pub fn push<K: FixedKey>(self, component: K) -> CompositeKey<(T, K)> {
+        CompositeKey((self.0, component))
+    }
+
+    #[must_use]
+    pub fn encode<const N: usize>(&self) -> [u8; N] {
+        encode_key(&self.0)
+    }
+
+    /// The components, nested as `(((), first), second)`.
+    #[must_use]
+    pub fn into_parts(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn u64_keys_are_big_endian() {
+        assert_eq!(SlotNo(0x0102).to_be_key(), [0, 0, 0, 0, 0, 0, 1, 2]);
+        assert_eq!(
+            EpochNo::from_be_key(&[0, 0, 0, 0, 0, 0, 0, 208]),
+            EpochNo(208)
+        );
+        assert_eq!(BlockNo(u64::MAX).to_be_key(), [0xff; 8]);
+    }
+
+    #[test]
+    fn with_origin_keys() {
+        let origin: [u8; 9] = encode_key(&WithOrigin::<SlotNo>::Origin);
+        assert_eq!(origin, [0; 9]);
+        let at: [u8; 9] = encode_key(&WithOrigin::At(SlotNo(0)));
+        assert_eq!(at, [1, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(origin < at);
+
+        assert_eq!(
+            decode_key::<WithOrigin<SlotNo>>(&origin),
+            Ok(WithOrigin::Origin)
+        );
+        assert_eq!(
+            decode_key::<WithOrigin<SlotNo>>(&[2, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Err(StorageKeyError::InvalidOriginTag { tag: 2 })
+        );
+        assert_eq!(
+            decode_key::<WithOrigin<SlotNo>>(&[0, 0, 0, 0, 0, 0, 0, 0, 1]),
+            Err(StorageKeyError::NonZeroOriginPadding)
+        );
+    }
+
+    #[test]
+    fn builder_matches_flat_tuples() {
+        let key = CompositeKey::new()
+            .push(EpochNo(3))
+            .push(WithOrigin::At(SlotNo(7)))
+            .push(BlockNo(9));
+        assert_eq!(
+            CompositeKey::<((((), EpochNo), WithOrigin<SlotNo>), BlockNo)>::SIZE,
+            25
+        );
+        let bytes: [u8; 25] = key.encode();
+        assert_eq!(
+            bytes,
+            encode_key::<_, 25>(&(EpochNo(3), WithOrigin::At(SlotNo(7)), BlockNo(9)))
+        );
+        assert_eq!(
+            key.into_parts(),
+            ((((), EpochNo(3)), WithOrigin::At(SlotNo(7))), BlockNo(9))
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_lengths() {
+        for len in [0, 7, 9, 16] {
+            assert_eq!(
+                decode_key::<SlotNo>(&vec![0; len]),
+                Err(StorageKeyError::WrongLength {
+                    expected: 8,
+                    actual: len
+                })
+            );
+        }
+        assert_eq!(
+            decode_key::<(EpochNo, WithOrigin<SlotNo>)>(&[0; 16]),
+            Err(StorageKeyError::WrongLength {
+                expected: 17,
+                actual: 16
+            })
+        );
+    }
+}
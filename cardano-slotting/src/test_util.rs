@@ -0,0 +1,197 @@
+//! Property-test generators and fixtures for slotting types (`test-util`
+//! feature).
+//!
+//! The strategies keep every generated value in a range where slot and time
+//! arithmetic cannot overflow, so a failing property points at the code under
+//! test rather than at a `u64::MAX` slot. All of them shrink toward the
+//! smallest value: slot 0, epoch 0, block 0, [`WithOrigin::Origin`], 100 ms
+//! slots and one-slot epochs.
+//!
+//! ```rust
+//! use cardano_slotting::epoch_info::{epoch_info_epoch, epoch_info_first};
+//! use cardano_slotting::test_util::{arb_fixed_epoch_info, arb_slot_no};
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(info in arb_fixed_epoch_info(), slot in arb_slot_no(1_000_000))| {
+//!     let epoch = epoch_info_epoch(&info, slot).unwrap();
+//!     prop_assert!(epoch_info_first(&info, epoch).unwrap() <= slot);
+//! });
+//! ```
+
+use std::convert::Infallible;
+use std::fmt;
+
+use proptest::prelude::*;
+
+use crate::block::BlockNo;
+use crate::epoch_info::EpochInfo;
+use crate::epoch_info::fixed::fixed_epoch_info;
+use crate::slot::{EpochNo, EpochSize, SlotNo, WithOrigin, with_origin_from_maybe};
+use crate::time::{SlotLength, slot_length_from_millisec, slot_length_from_sec};
+
+/// Shortest slot length produced by [`arb_slot_length`], in milliseconds.
+pub const MIN_SLOT_LENGTH_MILLIS: u64 = 100;
+
+/// Longest slot length produced by [`arb_slot_length`], in milliseconds.
+pub const MAX_SLOT_LENGTH_MILLIS: u64 = 60_000;
+
+/// Largest epoch size produced by [`arb_fixed_epoch_info`]: the Shelley
+/// mainnet epoch of 432 000 slots.
+pub const MAX_EPOCH_SIZE: u64 = 432_000;
+
+/// Slots in `0..=max`.
+pub fn arb_slot_no(max: u64) -> impl Strategy<Value = SlotNo> {
+    (0..=max).prop_map(SlotNo)
+}
+
+/// Epochs in `0..=max`.
+pub fn arb_epoch_no(max: u64) -> impl Strategy<Value = EpochNo> {
+    (0..=max).prop_map(EpochNo)
+}
+
+/// Block numbers in `0..=max`.
+pub fn arb_block_no(max: u64) -> impl Strategy<Value = BlockNo> {
+    (0..=max).prop_map(BlockNo)
+}
+
+/// `Origin` or a value drawn from `inner`, with equal probability. Shrinks
+/// to `Origin` first.
+pub fn arb_with_origin<S>(inner: S) -> impl Strategy<Value = WithOrigin<S::Value>>
+where
+    S: Strategy,
+    S::Value: fmt::Debug,
+{
+    proptest::option::of(inner).prop_map(with_origin_from_maybe)
+}
+
+/// Whole-millisecond slot lengths between 100 ms and 60 s.
+pub fn arb_slot_length() -> impl Strategy<Value = SlotLength> {
+    (MIN_SLOT_LENGTH_MILLIS..=MAX_SLOT_LENGTH_MILLIS)
+        .prop_map(|millis| slot_length_from_millisec(i128::from(millis)))
+}
+
+/// Fixed epoch schedules with `1..=MAX_EPOCH_SIZE` slots per epoch and a
+/// slot length from [`arb_slot_length`].
+pub fn arb_fixed_epoch_info() -> impl Strategy<Value = EpochInfo<Infallible>> {
+    (1..=MAX_EPOCH_SIZE, arb_slot_length())
+        .prop_map(|(epoch_size, slot_length)| fixed_epoch_info(EpochSize(epoch_size), slot_length))
+}
+
+/// Builder for [`EpochInfo`] fixtures.
+///
+/// ```rust
+/// use cardano_slotting::EpochSize;
+/// use cardano_slotting::epoch_info::epoch_info_size;
+/// use cardano_slotting::test_util::EpochInfoBuilder;
+/// use cardano_slotting::time::slot_length_from_millisec;
+///
+/// let info = EpochInfoBuilder::fixed()
+///     .epoch_size(EpochSize(100))
+///     .slot_length(slot_length_from_millisec(200))
+///     .build();
+/// assert_eq!(epoch_info_size(&info, 7.into()).unwrap(), EpochSize(100));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EpochInfoBuilder {
+    epoch_size: EpochSize,
+    slot_length: SlotLength,
+}
+
+impl EpochInfoBuilder {
+    /// Fixed schedule starting from the Shelley mainnet parameters: epochs
+    /// of 432 000 one-second slots.
+    #[must_use]
+    pub fn fixed() -> Self {
+        Self {
+            epoch_size: EpochSize(MAX_EPOCH_SIZE),
+            slot_length: slot_length_from_sec(1),
+        }
+    }
+
+    /// Number of slots per epoch.
+    #[must_use]
+    pub fn epoch_size(mut self, epoch_size: EpochSize) -> Self {
+        self.epoch_size = epoch_size;
+        self
+    }
+
+    /// Length of every slot.
+    #[must_use]
+    pub fn slot_length(mut self, slot_length: SlotLength) -> Self {
+        self.slot_length = slot_length;
+        self
+    }
+
+    /// Build the schedule.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the epoch size is zero.
+    #[must_use]
+    pub fn build(self) -> EpochInfo<Infallible> {
+        assert!(self.epoch_size.0 != 0, "epoch size must be non-zero");
+        fixed_epoch_info(self.epoch_size, self.slot_length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::{TestCaseError, TestError, TestRunner};
+
+    use super::*;
+    use crate::epoch_info::{epoch_info_size, epoch_info_slot_length};
+    use crate::time::slot_length_to_millisec;
+
+    /// Minimal value reported when every generated case fails.
+    fn minimal<S: Strategy>(strategy: &S) -> S::Value {
+        let mut runner = TestRunner::deterministic();
+        match runner.run(strategy, |_| Err(TestCaseError::fail("always fails"))) {
+            Err(TestError::Fail(_, value)) => value,
+            other => panic!("expected a failing case, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn strategies_shrink_toward_zero_and_origin() {
+        assert_eq!(minimal(&arb_slot_no(1_000_000)), SlotNo(0));
+        assert_eq!(minimal(&arb_epoch_no(500)), EpochNo(0));
+        assert_eq!(minimal(&arb_block_no(500)), BlockNo(0));
+        assert_eq!(
+            minimal(&arb_with_origin(arb_slot_no(100))),
+            WithOrigin::Origin
+        );
+        assert_eq!(
+            minimal(&arb_slot_length()),
+            slot_length_from_millisec(i128::from(MIN_SLOT_LENGTH_MILLIS))
+        );
+    }
+
+    #[test]
+    fn slot_lengths_stay_in_range() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let length = arb_slot_length().new_tree(&mut runner).unwrap().current();
+            assert!((100..=60_000).contains(&slot_length_to_millisec(length)));
+        }
+    }
+
+    #[test]
+    fn builder_defaults_to_shelley_mainnet() {
+        let info = EpochInfoBuilder::fixed().build();
+        assert_eq!(
+            epoch_info_size(&info, EpochNo(0)).unwrap(),
+            EpochSize(432_000)
+        );
+        assert_eq!(
+            epoch_info_slot_length(&info, SlotNo(0)).unwrap(),
+            slot_length_from_sec(1)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "epoch size must be non-zero")]
+    fn builder_rejects_empty_epochs() {
+        let _ = EpochInfoBuilder::fixed().epoch_size(EpochSize(0)).build();
+    }
+}
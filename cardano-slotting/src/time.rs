@@ -1,12 +1,17 @@
 use core::convert::TryFrom;
 use core::fmt;
+use core::ops::{Add, Neg, Sub};
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use time::{Duration, OffsetDateTime};
 
+use crate::slot::SlotNo;
+
 /// System start timestamp (slots are counted from this instant).
-#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Ordered by instant, regardless of the UTC offset it was written with.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct SystemStart(pub OffsetDateTime);
 
@@ -16,8 +21,60 @@ impl fmt::Debug for SystemStart {
     }
 }
 
+impl SystemStart {
+    #[must_use]
+    pub fn from_offset_date_time(instant: OffsetDateTime) -> Self {
+        SystemStart(instant)
+    }
+
+    #[must_use]
+    pub fn to_offset_date_time(self) -> OffsetDateTime {
+        self.0
+    }
+
+    /// System start `millis` milliseconds after the Unix epoch, in UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TimeConversionError::OutOfRange`] if the instant is outside
+    /// the years `time` supports.
+    pub fn from_unix_millis(millis: i64) -> Result<Self, TimeConversionError> {
+        OffsetDateTime::from_unix_timestamp_nanos(i128::from(millis) * 1_000_000)
+            .map(SystemStart)
+            .map_err(|_| TimeConversionError::OutOfRange)
+    }
+
+    /// Milliseconds since the Unix epoch, rounded down.
+    #[must_use]
+    pub fn to_unix_millis(self) -> i64 {
+        let millis = self.0.unix_timestamp_nanos().div_euclid(1_000_000);
+        i64::try_from(millis).expect("OffsetDateTime milliseconds fit in i64")
+    }
+}
+
+/// Error converting between wall-clock time and slots.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum TimeConversionError {
+    /// The wall-clock time is earlier than the system start.
+    #[error("time {provided} is earlier than system start {system_start}")]
+    BeforeSystemStart {
+        provided: OffsetDateTime,
+        system_start: OffsetDateTime,
+    },
+    /// A zero slot length cannot locate a slot.
+    #[error("slot length is zero")]
+    ZeroSlotLength,
+    /// The result does not fit the representable range.
+    #[error("time conversion out of range")]
+    OutOfRange,
+}
+
 /// Time relative to the system start.
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
+///
+/// A relative time is never meant to be negative: instants before the system
+/// start are rejected by [`to_relative_time`] with a [`TimeOrderingError`],
+/// and [`RelativeTime::checked_add_signed`] refuses to step before it.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default)]
 #[serde(transparent)]
 pub struct RelativeTime(Duration);
 
@@ -31,6 +88,14 @@ impl RelativeTime {
     pub fn duration(self) -> Duration {
         self.0
     }
+
+    /// Move by a signed `delta`, or `None` if the result would be before the
+    /// system start or overflow.
+    #[must_use]
+    pub fn checked_add_signed(self, delta: RelativeTimeDelta) -> Option<RelativeTime> {
+        let moved = self.0.checked_add(delta.0)?;
+        (!moved.is_negative()).then_some(RelativeTime(moved))
+    }
 }
 
 impl fmt::Debug for RelativeTime {
@@ -39,31 +104,269 @@ impl fmt::Debug for RelativeTime {
     }
 }
 
-/// Slot length represented as a nominal difference in time.
-#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+/// Signed span between two [`RelativeTime`]s, as returned by
+/// [`diff_relative_time`].
+///
+/// Negative when the first time is the earlier one, e.g. the time "until" a
+/// slot that has already passed. Arithmetic panics on overflow, like
+/// [`Duration`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default)]
 #[serde(transparent)]
-pub struct SlotLength(Duration);
+pub struct RelativeTimeDelta(Duration);
+
+impl RelativeTimeDelta {
+    /// The empty span.
+    pub const ZERO: RelativeTimeDelta = RelativeTimeDelta(Duration::ZERO);
+
+    #[must_use]
+    pub fn new(duration: Duration) -> Self {
+        Self(duration)
+    }
+
+    #[must_use]
+    pub fn duration(self) -> Duration {
+        self.0
+    }
+
+    /// Whether the span runs backwards in time.
+    #[must_use]
+    pub fn is_negative(self) -> bool {
+        self.0.is_negative()
+    }
+
+    /// Length of the span, dropping its sign.
+    #[must_use]
+    pub fn unsigned_abs(self) -> core::time::Duration {
+        self.0.unsigned_abs()
+    }
+}
+
+impl fmt::Debug for RelativeTimeDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RelativeTimeDelta({:?})", self.0)
+    }
+}
+
+impl Add for RelativeTimeDelta {
+    type Output = RelativeTimeDelta;
+
+    fn add(self, rhs: RelativeTimeDelta) -> RelativeTimeDelta {
+        RelativeTimeDelta(self.0 + rhs.0)
+    }
+}
+
+impl Sub for RelativeTimeDelta {
+    type Output = RelativeTimeDelta;
+
+    fn sub(self, rhs: RelativeTimeDelta) -> RelativeTimeDelta {
+        RelativeTimeDelta(self.0 - rhs.0)
+    }
+}
+
+impl Neg for RelativeTimeDelta {
+    type Output = RelativeTimeDelta;
+
+    fn neg(self) -> RelativeTimeDelta {
+        RelativeTimeDelta(-self.0)
+    }
+}
+
+/// A forward span; fails only if it exceeds `i64::MAX` seconds.
+impl TryFrom<core::time::Duration> for RelativeTimeDelta {
+    type Error = time::error::ConversionRange;
+
+    fn try_from(duration: core::time::Duration) -> Result<Self, Self::Error> {
+        Duration::try_from(duration).map(RelativeTimeDelta)
+    }
+}
+
+/// The span as a [`core::time::Duration`]; fails if it is negative. Use
+/// [`RelativeTimeDelta::unsigned_abs`] to keep only the length.
+impl TryFrom<RelativeTimeDelta> for core::time::Duration {
+    type Error = time::error::ConversionRange;
+
+    fn try_from(delta: RelativeTimeDelta) -> Result<Self, Self::Error> {
+        core::time::Duration::try_from(delta.0)
+    }
+}
+
+/// Slot length as an exact rational number of seconds.
+///
+/// Haskell stores slot lengths as a picosecond `NominalDiffTime`; storing the
+/// reduced fraction instead keeps lengths such as 1/3 s exact, so slot to time
+/// conversions through [`mult_slot_length`] do not accumulate rounding error.
+/// Lengths are non-negative and at most `i64::MAX` nanoseconds (about 292
+/// years), so [`SlotLength::duration`] never overflows.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SlotLength {
+    numerator: u64,
+    denominator: u64,
+}
 
 impl fmt::Debug for SlotLength {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "SlotLength({:?})", self.0)
+        match self.exact_nanoseconds() {
+            Some(_) => write!(f, "SlotLength({:?})", self.duration()),
+            None => write!(f, "SlotLength({}/{}s)", self.numerator, self.denominator),
+        }
+    }
+}
+
+impl Default for SlotLength {
+    fn default() -> Self {
+        SlotLength {
+            numerator: 0,
+            denominator: 1,
+        }
     }
 }
 
 impl SlotLength {
+    /// Slot length of exactly `duration`.
+    ///
+    /// Panics if `duration` is negative.
     #[must_use]
     pub fn new(duration: Duration) -> Self {
-        SlotLength(duration)
+        let nanos =
+            u64::try_from(duration.whole_nanoseconds()).expect("slot length must be non-negative");
+        Self::from_fraction(nanos, NANOS_PER_SECOND)
     }
 
+    /// Slot length of exactly `numerator / denominator` seconds.
+    ///
+    /// Panics if `denominator` is zero or the length exceeds `i64::MAX`
+    /// nanoseconds; [`SlotLength::try_from_fraction`] reports these instead.
+    #[must_use]
+    pub fn from_fraction(numerator: u64, denominator: u64) -> Self {
+        assert!(denominator != 0, "slot length denominator must be non-zero");
+        Self::try_from_fraction(numerator, denominator)
+            .expect("slot length nanoseconds exceed i64 range")
+    }
+
+    /// Slot length of exactly `numerator / denominator` seconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SlotLengthError::ZeroDenominator`] if `denominator` is zero
+    /// and [`SlotLengthError::OutOfRange`] if the length exceeds `i64::MAX`
+    /// nanoseconds.
+    pub fn try_from_fraction(numerator: u64, denominator: u64) -> Result<Self, SlotLengthError> {
+        if denominator == 0 {
+            return Err(SlotLengthError::ZeroDenominator);
+        }
+        if u128::from(numerator) * u128::from(NANOS_PER_SECOND) / u128::from(denominator)
+            > i64::MAX as u128
+        {
+            return Err(SlotLengthError::OutOfRange);
+        }
+        let divisor = gcd(numerator, denominator).max(1);
+        Ok(SlotLength {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        })
+    }
+
+    /// Numerator of the reduced fraction of seconds.
+    #[must_use]
+    pub fn numerator(self) -> u64 {
+        self.numerator
+    }
+
+    /// Denominator of the reduced fraction of seconds.
+    #[must_use]
+    pub fn denominator(self) -> u64 {
+        self.denominator
+    }
+
+    /// The slot length, rounded down to whole nanoseconds.
     #[must_use]
     pub fn duration(self) -> Duration {
-        self.0
+        mult_slot_length(self, 1)
+    }
+
+    /// The length in nanoseconds, if it is a whole number of them.
+    fn exact_nanoseconds(self) -> Option<u64> {
+        let nanos = u128::from(self.numerator) * u128::from(NANOS_PER_SECOND);
+        let denominator = u128::from(self.denominator);
+        (nanos % denominator == 0).then(|| (nanos / denominator) as u64)
+    }
+}
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Error constructing a [`SlotLength`] from a fraction of seconds.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum SlotLengthError {
+    /// The denominator of the fraction is zero.
+    #[error("slot length denominator must be non-zero")]
+    ZeroDenominator,
+    /// The length exceeds `i64::MAX` nanoseconds.
+    #[error("slot length nanoseconds exceed i64 range")]
+    OutOfRange,
+}
+
+/// On-the-wire form of [`SlotLength`].
+///
+/// Lengths that are a whole number of nanoseconds keep the `Duration`
+/// encoding used before slot lengths became exact fractions; only lengths
+/// such as 1/3 s fall back to an explicit fraction.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum SlotLengthRepr {
+    Duration(Duration),
+    Fraction { numerator: u64, denominator: u64 },
+}
+
+impl Serialize for SlotLength {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let repr = match self.exact_nanoseconds() {
+            Some(_) => SlotLengthRepr::Duration(self.duration()),
+            None => SlotLengthRepr::Fraction {
+                numerator: self.numerator,
+                denominator: self.denominator,
+            },
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SlotLength {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error as _;
+
+        let (numerator, denominator) = match SlotLengthRepr::deserialize(deserializer)? {
+            SlotLengthRepr::Duration(duration) => {
+                let nanos = u64::try_from(duration.whole_nanoseconds())
+                    .map_err(|_| D::Error::custom("slot length must be non-negative"))?;
+                (nanos, NANOS_PER_SECOND)
+            },
+            SlotLengthRepr::Fraction {
+                numerator,
+                denominator,
+            } => (numerator, denominator),
+        };
+        SlotLength::try_from_fraction(numerator, denominator).map_err(D::Error::custom)
     }
 }
 
 /// Error returned when attempting to evaluate `to_relative_time` with an input
 /// earlier than the system start.
+///
+/// This is the only way a negative [`RelativeTime`] is refused; differences
+/// between relative times are signed [`RelativeTimeDelta`]s and never fail.
 #[derive(Debug, Error)]
 #[error("time {provided} is earlier than system start {system_start}")]
 pub struct TimeOrderingError {
@@ -76,9 +379,12 @@ pub fn add_relative_time(delta: Duration, relative: RelativeTime) -> RelativeTim
     RelativeTime(relative.0 + delta)
 }
 
+/// Signed time from `rhs` to `lhs`, negative if `lhs` is earlier.
+///
+/// `rhs.checked_add_signed(diff_relative_time(lhs, rhs))` is `Some(lhs)`.
 #[must_use]
-pub fn diff_relative_time(lhs: RelativeTime, rhs: RelativeTime) -> Duration {
-    lhs.0 - rhs.0
+pub fn diff_relative_time(lhs: RelativeTime, rhs: RelativeTime) -> RelativeTimeDelta {
+    RelativeTimeDelta(lhs.0 - rhs.0)
 }
 
 #[must_use]
@@ -111,6 +417,64 @@ pub fn from_relative_time(system_start: SystemStart, relative: RelativeTime) ->
     system_start.0 + relative.0
 }
 
+/// Wall-clock start of `slot` for a chain with a single `slot_length`.
+///
+/// Exact for slot lengths that are a whole number of nanoseconds, which
+/// includes every millisecond length; otherwise the slot's start is rounded
+/// down to the nanosecond, as in [`mult_slot_length`]. Panics if the result
+/// is out of range.
+#[must_use]
+pub fn wallclock_for_slot(
+    slot: SlotNo,
+    slot_length: SlotLength,
+    system_start: SystemStart,
+) -> OffsetDateTime {
+    system_start.0 + mult_slot_length(slot_length, slot.0)
+}
+
+/// The slot in progress at `wallclock`: the last slot whose
+/// [`wallclock_for_slot`] start is at or before it.
+///
+/// Inverts [`wallclock_for_slot`] exactly, including its rounding, so
+/// `slot_for_wallclock(wallclock_for_slot(s, ..), ..)` is `Ok(s)`.
+///
+/// # Errors
+///
+/// Returns [`TimeConversionError::BeforeSystemStart`] for a time before the
+/// system start, [`TimeConversionError::ZeroSlotLength`] for a zero slot
+/// length, and [`TimeConversionError::OutOfRange`] if the slot does not fit
+/// a `u64`.
+pub fn slot_for_wallclock(
+    wallclock: OffsetDateTime,
+    slot_length: SlotLength,
+    system_start: SystemStart,
+) -> Result<SlotNo, TimeConversionError> {
+    if wallclock < system_start.0 {
+        return Err(TimeConversionError::BeforeSystemStart {
+            provided: wallclock,
+            system_start: system_start.0,
+        });
+    }
+    if slot_length.numerator == 0 {
+        return Err(TimeConversionError::ZeroSlotLength);
+    }
+    // Slot `s` starts `floor(s * n * 1e9 / d)` ns in, which is at or before
+    // `t` exactly when `s * n * 1e9 < (t + 1) * d`.
+    let elapsed = u128::try_from((wallclock - system_start.0).whole_nanoseconds())
+        .map_err(|_| TimeConversionError::OutOfRange)?;
+    let scaled = (elapsed + 1)
+        .checked_mul(u128::from(slot_length.denominator))
+        .ok_or(TimeConversionError::OutOfRange)?;
+    let per_slot = u128::from(slot_length.numerator) * u128::from(NANOS_PER_SECOND);
+    u64::try_from((scaled - 1) / per_slot)
+        .map(SlotNo)
+        .map_err(|_| TimeConversionError::OutOfRange)
+}
+
+/// Multiply a duration by a slot count.
+///
+/// Exact, since [`Duration`] has nanosecond resolution. Panics if the result
+/// exceeds `i64::MAX` nanoseconds.
 #[must_use]
 pub fn mult_nominal_diff_time(duration: Duration, factor: u64) -> Duration {
     let nanos = duration
@@ -121,9 +485,28 @@ pub fn mult_nominal_diff_time(duration: Duration, factor: u64) -> Duration {
     Duration::nanoseconds(nanos)
 }
 
+/// Time taken by `factor` slots of `slot_length`.
+///
+/// The product is computed exactly and rounded down to whole nanoseconds
+/// once, so there is no drift: for a 1/3 s slot length, slot `3k` starts
+/// exactly `k` seconds in, and consecutive slots differ by the length rounded
+/// to a neighbouring nanosecond. Panics if the result exceeds `i64::MAX`
+/// nanoseconds.
+#[must_use]
+pub fn mult_slot_length(slot_length: SlotLength, factor: u64) -> Duration {
+    let nanos = u128::from(slot_length.numerator)
+        .checked_mul(u128::from(NANOS_PER_SECOND))
+        .and_then(|nanos| nanos.checked_mul(u128::from(factor)))
+        .expect("duration multiplication overflow")
+        / u128::from(slot_length.denominator);
+    let nanos = i64::try_from(nanos).expect("duration nanoseconds exceed i64 range");
+    Duration::nanoseconds(nanos)
+}
+
+/// The slot length, rounded down to whole nanoseconds.
 #[must_use]
 pub fn get_slot_length(slot_length: SlotLength) -> Duration {
-    slot_length.0
+    slot_length.duration()
 }
 
 #[must_use]
@@ -131,13 +514,26 @@ pub fn mk_slot_length(duration: Duration) -> SlotLength {
     SlotLength::new(duration)
 }
 
+/// Slot length of exactly `numerator / denominator` seconds.
+///
+/// Panics if `denominator` is zero or the length exceeds `i64::MAX`
+/// nanoseconds.
+#[must_use]
+pub fn slot_length_from_fraction(numerator: u64, denominator: u64) -> SlotLength {
+    SlotLength::from_fraction(numerator, denominator)
+}
+
+/// Slot length of exactly `milliseconds` milliseconds.
+///
+/// Panics if `milliseconds` is negative or the length exceeds `i64::MAX`
+/// nanoseconds.
 #[must_use]
 pub fn slot_length_from_millisec(milliseconds: i128) -> SlotLength {
     let nanos = milliseconds
         .checked_mul(1_000_000)
         .expect("slot length millisecond conversion overflow");
     let nanos = i64::try_from(nanos).expect("slot length nanoseconds exceed i64 range");
-    SlotLength(Duration::nanoseconds(nanos))
+    SlotLength::new(Duration::nanoseconds(nanos))
 }
 
 #[must_use]
@@ -145,11 +541,13 @@ pub fn slot_length_from_sec(seconds: i128) -> SlotLength {
     slot_length_from_millisec(seconds * 1_000)
 }
 
+/// Whole milliseconds in `slot_length`, rounded down.
 #[must_use]
 pub fn slot_length_to_millisec(slot_length: SlotLength) -> i128 {
-    slot_length.0.whole_nanoseconds() / 1_000_000
+    i128::from(slot_length.numerator) * 1_000 / i128::from(slot_length.denominator)
 }
 
+/// Whole seconds in `slot_length`, rounded down.
 #[must_use]
 pub fn slot_length_to_sec(slot_length: SlotLength) -> i128 {
     slot_length_to_millisec(slot_length) / 1_000
@@ -176,6 +574,58 @@ mod tests {
         assert_eq!(back, later);
     }
 
+    #[test]
+    fn unix_millis_round_trip() {
+        let start = SystemStart::from_unix_millis(1_506_203_091_000).unwrap();
+        assert_eq!(
+            start.to_offset_date_time(),
+            datetime!(2017-09-23 21:44:51 UTC)
+        );
+        assert_eq!(start.to_unix_millis(), 1_506_203_091_000);
+
+        let before_epoch = SystemStart::from_unix_millis(-1).unwrap();
+        assert_eq!(before_epoch.to_unix_millis(), -1);
+        let sub_milli = SystemStart::from_offset_date_time(datetime!(1969-12-31 23:59:59.9999 UTC));
+        assert_eq!(sub_milli.to_unix_millis(), -1);
+
+        assert_eq!(
+            SystemStart::from_unix_millis(i64::MAX),
+            Err(TimeConversionError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn slot_for_wallclock_floors_and_rejects_early_times() {
+        let start = SystemStart(datetime!(2020-01-01 00:00:00 UTC));
+        let third = slot_length_from_fraction(1, 3);
+
+        assert_eq!(
+            wallclock_for_slot(SlotNo(1), third, start),
+            datetime!(2020-01-01 00:00:00.333333333 UTC)
+        );
+        assert_eq!(
+            slot_for_wallclock(datetime!(2020-01-01 00:00:00.333333332 UTC), third, start),
+            Ok(SlotNo(0))
+        );
+        assert_eq!(
+            slot_for_wallclock(datetime!(2020-01-01 00:00:00.333333333 UTC), third, start),
+            Ok(SlotNo(1))
+        );
+        assert_eq!(
+            slot_for_wallclock(datetime!(2020-01-01 00:00:01 UTC), third, start),
+            Ok(SlotNo(3))
+        );
+
+        assert!(matches!(
+            slot_for_wallclock(datetime!(2019-12-31 23:59:59 UTC), third, start),
+            Err(TimeConversionError::BeforeSystemStart { .. })
+        ));
+        assert_eq!(
+            slot_for_wallclock(start.0, SlotLength::default(), start),
+            Err(TimeConversionError::ZeroSlotLength)
+        );
+    }
+
     #[test]
     fn time_ordering_error() {
         let start = SystemStart(datetime!(2020-01-01 00:00:00 UTC));
@@ -184,6 +634,15 @@ mod tests {
         assert_eq!(err.system_start, start.0);
     }
 
+    #[test]
+    fn slot_length_fraction_is_reduced() {
+        let sl = slot_length_from_fraction(4, 12);
+        assert_eq!((sl.numerator(), sl.denominator()), (1, 3));
+        assert_eq!(sl.duration(), Duration::nanoseconds(333_333_333));
+        assert_eq!(mult_slot_length(sl, 3), Duration::seconds(1));
+        assert_eq!(slot_length_from_fraction(0, 7), SlotLength::default());
+    }
+
     #[test]
     fn multiply_duration() {
         let d = Duration::seconds(2);
@@ -196,6 +655,51 @@ mod tests {
         assert_eq!(mult_relative_time(rel, 4).duration().whole_seconds(), 8);
     }
 
+    #[test]
+    fn diff_relative_is_signed() {
+        let slot = RelativeTime(Duration::seconds(20));
+        let now = RelativeTime(Duration::seconds(25));
+
+        let until = diff_relative_time(slot, now);
+        assert!(until.is_negative());
+        assert_eq!(until, RelativeTimeDelta(Duration::seconds(-5)));
+        assert_eq!(-until, diff_relative_time(now, slot));
+        assert!(until < RelativeTimeDelta::ZERO);
+        assert_eq!(diff_relative_time(now, now), RelativeTimeDelta::ZERO);
+
+        assert_eq!(now.checked_add_signed(until), Some(slot));
+        assert_eq!(slot.checked_add_signed(-until), Some(now));
+        assert_eq!(RelativeTime::default().checked_add_signed(until), None);
+    }
+
+    #[test]
+    fn delta_arithmetic() {
+        let a = RelativeTimeDelta(Duration::seconds(3));
+        let b = RelativeTimeDelta(Duration::seconds(5));
+        assert_eq!(a - b, RelativeTimeDelta(Duration::seconds(-2)));
+        assert_eq!(a - b + b, a);
+        assert_eq!(-(a - b), b - a);
+    }
+
+    #[test]
+    fn delta_std_conversions_keep_the_sign() {
+        let forward =
+            RelativeTimeDelta::try_from(core::time::Duration::from_millis(1_500)).unwrap();
+        assert_eq!(forward, RelativeTimeDelta(Duration::milliseconds(1_500)));
+        assert_eq!(
+            core::time::Duration::try_from(forward).unwrap(),
+            core::time::Duration::from_millis(1_500)
+        );
+
+        let backward = -forward;
+        assert!(core::time::Duration::try_from(backward).is_err());
+        assert_eq!(
+            backward.unsigned_abs(),
+            core::time::Duration::from_millis(1_500)
+        );
+        assert!(RelativeTimeDelta::try_from(core::time::Duration::MAX).is_err());
+    }
+
     #[test]
     fn add_relative() {
         let rel = RelativeTime(Duration::seconds(5));
@@ -1,15 +1,40 @@
+use cardano_slotting::BlockNo;
 use cardano_slotting::epoch_info::{
-    epoch_info_first, epoch_info_range, epoch_info_size, epoch_info_slot_to_relative_time,
+    EpochInfo, EpochInfoSource, LinearExtension, SlotPhase, fixed::fixed_epoch_info,
+    generalize_epoch_info, slot_phase, unsafe_linear_extend_epoch_info,
+};
+use cardano_slotting::epoch_info::{
+    epoch_info_elapsed_time_to_slot, epoch_info_epoch, epoch_info_first, epoch_info_range,
+    epoch_info_size, epoch_info_slot_length, epoch_info_slot_length_at_slot,
+    epoch_info_slot_to_relative_time,
+};
+use cardano_slotting::security_param::{
+    SecurityParam, is_within_stability_window, max_rollbacks, randomness_stabilization_window,
+    stability_window,
 };
-use cardano_slotting::epoch_info::{fixed::fixed_epoch_info, unsafe_linear_extend_epoch_info};
 use cardano_slotting::slot::{
     EpochInterval, EpochNo, EpochSize, SlotNo, WithOrigin, add_epoch_interval, at, bin_op_epoch_no,
     origin,
 };
+use cardano_slotting::storage_key::{
+    CompositeKey, FixedKey, StorageKeyError, decode_key, encode_key,
+};
+use cardano_slotting::test_util::{
+    EpochInfoBuilder, arb_block_no, arb_epoch_no, arb_fixed_epoch_info, arb_slot_length,
+    arb_slot_no, arb_with_origin,
+};
 use cardano_slotting::time::{
-    diff_relative_time, slot_length_from_millisec, slot_length_to_millisec,
+    RelativeTime, RelativeTimeDelta, SlotLength, SlotLengthError, SystemStart, diff_relative_time,
+    slot_for_wallclock, slot_length_from_fraction, slot_length_from_millisec, slot_length_from_sec,
+    slot_length_to_millisec, slot_length_to_sec, wallclock_for_slot,
 };
+use num_rational::Ratio;
+use proptest::prelude::*;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::thread;
 use time::Duration;
+use time::macros::datetime;
 
 #[test]
 fn with_origin_conversions() {
@@ -36,6 +61,31 @@ fn epoch_interval_adds() {
     assert_eq!(add_epoch_interval(epoch, interval), EpochNo(12));
 }
 
+#[test]
+fn governance_action_lifetime_window() {
+    // Conway: an action proposed in epoch 500 with govActionLifetime = 6 has
+    // gasExpiresAfter = 506 and expires once 506 < currentEpoch.
+    let proposed = EpochNo(500);
+    let lifetime = EpochInterval(6);
+    assert_eq!(proposed.checked_add_interval(lifetime), Some(EpochNo(506)));
+    assert!(!EpochNo(499).is_within(proposed, lifetime));
+    assert!(EpochNo(500).is_within(proposed, lifetime));
+    assert!(EpochNo(506).is_within(proposed, lifetime));
+    assert!(!EpochNo(507).is_within(proposed, lifetime));
+    assert_eq!(EpochNo(506).elapsed_since(proposed), Some(lifetime));
+}
+
+#[test]
+fn epoch_interval_serializes_as_bare_integer() {
+    assert_eq!(serde_json::to_string(&EpochInterval(6)).expect("json"), "6");
+    assert_eq!(
+        serde_json::from_str::<EpochInterval>("4294967295").expect("parse"),
+        EpochInterval::MAX
+    );
+    assert!(serde_json::from_str::<EpochInterval>("4294967296").is_err());
+    assert_eq!(EpochInterval::ZERO.to_string(), "0");
+}
+
 #[test]
 fn fixed_epoch_info_behaves_like_constant_schedule() {
     let epoch_size = EpochSize(20);
@@ -67,7 +117,419 @@ fn linear_extension_matches_underlying_schedule() {
         rel,
         epoch_info_slot_to_relative_time(&base_info, SlotNo(30)).unwrap(),
     );
-    assert_eq!(diff, Duration::ZERO);
+    assert_eq!(diff, RelativeTimeDelta::ZERO);
+}
+
+// `EpochInfo` must stay storable in state shared across threads.
+const _: fn() = || {
+    fn assert_shareable<T: Send + Sync + Clone + 'static>() {}
+    assert_shareable::<EpochInfo<Infallible>>();
+    assert_shareable::<EpochInfo<String>>();
+    assert_shareable::<LinearExtension<EpochInfo<Infallible>>>();
+};
+
+/// Long-lived state holding an `EpochInfo`, as a consensus node would.
+struct NodeState {
+    epoch_info: EpochInfo<Infallible>,
+    system_start_slot: SlotNo,
+}
+
+#[test]
+fn epoch_info_is_shared_across_threads() {
+    let state = Arc::new(NodeState {
+        epoch_info: EpochInfo::fixed(EpochSize(432_000), slot_length_from_sec(1)),
+        system_start_slot: SlotNo(4_492_800),
+    });
+
+    thread::scope(|scope| {
+        for worker in 0..4u64 {
+            let state = Arc::clone(&state);
+            scope.spawn(move || {
+                for step in 0..1_000 {
+                    let slot = SlotNo(state.system_start_slot.0 + worker * 1_000_000 + step);
+                    let info = &state.epoch_info;
+                    let epoch = epoch_info_epoch(info, slot).expect("within horizon");
+                    assert_eq!(epoch, EpochNo(slot.0 / 432_000));
+                    assert!(epoch_info_first(info, epoch).expect("within horizon") <= slot);
+                    assert_eq!(
+                        epoch_info_slot_to_relative_time(info, slot)
+                            .expect("within horizon")
+                            .duration(),
+                        Duration::seconds(i64::try_from(slot.0).expect("slot fits in i64"))
+                    );
+                }
+            });
+        }
+    });
+
+    // Clones share the source.
+    let cloned = state.epoch_info.clone();
+    assert_eq!(
+        epoch_info_size(&cloned, EpochNo(0)).expect("within horizon"),
+        EpochSize(432_000)
+    );
+}
+
+/// Byron-then-Shelley schedule: epochs 0 and 1 have ten 20 s slots, later
+/// epochs fifty 1 s slots. Queries past `horizon` fail.
+struct TwoEras {
+    horizon: SlotNo,
+}
+
+const BYRON_SLOTS: u64 = 20;
+
+impl TwoEras {
+    fn check(&self, slot: SlotNo) -> Result<(), String> {
+        if slot > self.horizon {
+            Err(format!("slot {} past horizon", slot.0))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl EpochInfoSource for TwoEras {
+    type Error = String;
+
+    fn epoch_size(&self, epoch: EpochNo) -> Result<EpochSize, String> {
+        Ok(EpochSize(if epoch.0 < 2 { 10 } else { 50 }))
+    }
+
+    fn first_slot_of_epoch(&self, epoch: EpochNo) -> Result<SlotNo, String> {
+        Ok(SlotNo(if epoch.0 < 2 {
+            epoch.0 * 10
+        } else {
+            BYRON_SLOTS + (epoch.0 - 2) * 50
+        }))
+    }
+
+    fn epoch_of_slot(&self, slot: SlotNo) -> Result<EpochNo, String> {
+        self.check(slot)?;
+        Ok(EpochNo(if slot.0 < BYRON_SLOTS {
+            slot.0 / 10
+        } else {
+            2 + (slot.0 - BYRON_SLOTS) / 50
+        }))
+    }
+
+    fn slot_to_relative_time(&self, slot: SlotNo) -> Result<RelativeTime, String> {
+        self.elapsed_time_to_slot(slot)
+    }
+
+    fn slot_length(&self, slot: SlotNo) -> Result<SlotLength, String> {
+        self.check(slot)?;
+        Ok(slot_length_from_sec(if slot.0 < BYRON_SLOTS {
+            20
+        } else {
+            1
+        }))
+    }
+
+    fn elapsed_time_to_slot(&self, slot: SlotNo) -> Result<RelativeTime, String> {
+        self.check(slot)?;
+        let byron = slot.0.min(BYRON_SLOTS);
+        let shelley = slot.0.saturating_sub(BYRON_SLOTS);
+        let seconds = i64::try_from(20 * byron + shelley).expect("seconds fit in i64");
+        Ok(RelativeTime::new(Duration::seconds(seconds)))
+    }
+}
+
+#[test]
+fn custom_source_drives_free_functions_and_decorators() {
+    let info = EpochInfo::from_source(TwoEras {
+        horizon: SlotNo(100),
+    });
+
+    assert_eq!(
+        epoch_info_epoch(&info, SlotNo(25)).expect("within horizon"),
+        EpochNo(2)
+    );
+    assert_eq!(
+        epoch_info_slot_length_at_slot(&info, SlotNo(5)).expect("within horizon"),
+        slot_length_from_sec(20)
+    );
+    assert_eq!(
+        epoch_info_elapsed_time_to_slot(&info, SlotNo(30))
+            .expect("within horizon")
+            .duration(),
+        Duration::seconds(20 * 20 + 10)
+    );
+    assert_eq!(
+        epoch_info_epoch(&info, SlotNo(101)),
+        Err("slot 101 past horizon".to_string())
+    );
+
+    // Errors pass through `map_error`, overrides are kept.
+    let mapped = info.clone().map_error(|err| err.len());
+    assert_eq!(epoch_info_epoch(&mapped, SlotNo(101)), Err(21));
+    assert_eq!(
+        epoch_info_elapsed_time_to_slot(&mapped, SlotNo(30)).expect("within horizon"),
+        epoch_info_elapsed_time_to_slot(&info, SlotNo(30)).expect("within horizon")
+    );
+
+    // The linear extension answers past the horizon from the basis slot.
+    let extended = unsafe_linear_extend_epoch_info(SlotNo(100), info);
+    assert_eq!(
+        epoch_info_epoch(&extended, SlotNo(170)).expect("within horizon"),
+        EpochNo(5)
+    );
+    assert_eq!(
+        epoch_info_elapsed_time_to_slot(&extended, SlotNo(170))
+            .expect("within horizon")
+            .duration(),
+        Duration::seconds(20 * 20 + 150)
+    );
+}
+
+#[test]
+fn slot_length_override_drives_elapsed_time() {
+    let fixed = EpochInfo::fixed(EpochSize(10), slot_length_from_sec(1));
+    let overridden = fixed
+        .clone()
+        .with_slot_length_at_slot(|_| Ok(slot_length_from_sec(20)));
+    assert_eq!(
+        epoch_info_elapsed_time_to_slot(&overridden, SlotNo(5))
+            .expect("infallible")
+            .duration(),
+        Duration::seconds(100)
+    );
+    // The source's own queries are untouched.
+    assert_eq!(
+        epoch_info_slot_to_relative_time(&overridden, SlotNo(5))
+            .expect("infallible")
+            .duration(),
+        Duration::seconds(5)
+    );
+
+    // An installed elapsed-time query wins, whichever override comes first.
+    let elapsed = |slot: SlotNo| {
+        let seconds = i64::try_from(slot.0).expect("small slot");
+        Ok(RelativeTime::new(Duration::seconds(7 * seconds)))
+    };
+    let first = fixed
+        .clone()
+        .with_elapsed_time_to_slot(elapsed)
+        .with_slot_length_at_slot(|_| Ok(slot_length_from_sec(20)));
+    let last = fixed
+        .with_slot_length_at_slot(|_| Ok(slot_length_from_sec(20)))
+        .with_elapsed_time_to_slot(elapsed);
+    // `map_error` keeps the overrides as they are.
+    for info in [first, last].map(generalize_epoch_info::<String>) {
+        assert_eq!(
+            epoch_info_elapsed_time_to_slot(&info, SlotNo(5))
+                .expect("infallible")
+                .duration(),
+            Duration::seconds(35)
+        );
+        assert_eq!(
+            epoch_info_slot_length_at_slot(&info, SlotNo(5)),
+            Ok(slot_length_from_sec(20))
+        );
+    }
+}
+
+#[test]
+fn fixed_constructor_matches_fixed_epoch_info() {
+    let epoch_size = EpochSize(21_600);
+    let slot_length = slot_length_from_sec(20);
+    let a = EpochInfo::fixed(epoch_size, slot_length);
+    let b = fixed_epoch_info(epoch_size, slot_length);
+    for slot in [0, 1, 21_599, 21_600, 1_000_000].map(SlotNo) {
+        assert_eq!(epoch_info_epoch(&a, slot), epoch_info_epoch(&b, slot));
+        assert_eq!(
+            epoch_info_elapsed_time_to_slot(&a, slot),
+            epoch_info_elapsed_time_to_slot(&b, slot)
+        );
+        assert_eq!(a.slot_length_at_slot(slot), Ok(slot_length));
+    }
+}
+
+proptest! {
+    #[test]
+    fn fixed_epoch_info_per_slot_queries_agree_with_per_epoch(
+        info in arb_fixed_epoch_info(),
+        slot in arb_slot_no(10_000_000),
+    ) {
+        let epoch = epoch_info_epoch(&info, slot).unwrap();
+        let first = epoch_info_first(&info, epoch).unwrap();
+        prop_assert!(first <= slot);
+        prop_assert!(slot.0 - first.0 < epoch_info_size(&info, epoch).unwrap().0);
+        prop_assert_eq!(
+            epoch_info_slot_length_at_slot(&info, slot).unwrap(),
+            epoch_info_slot_length(&info, first).unwrap()
+        );
+        prop_assert_eq!(
+            epoch_info_elapsed_time_to_slot(&info, slot).unwrap(),
+            epoch_info_slot_to_relative_time(&info, slot).unwrap()
+        );
+    }
+
+    #[test]
+    fn wallclock_for_slot_is_strictly_increasing(
+        slot_length in arb_slot_length(),
+        denominator in 1..=7u64,
+        slot in arb_slot_no(100_000_000),
+    ) {
+        // Also cover lengths that are not a whole number of nanoseconds.
+        let slot_length =
+            slot_length_from_fraction(slot_length.numerator(), slot_length.denominator() * denominator);
+        let start = SystemStart(datetime!(2017-09-23 21:44:51 UTC));
+        let this = wallclock_for_slot(slot, slot_length, start);
+        let next = wallclock_for_slot(SlotNo(slot.0 + 1), slot_length, start);
+        prop_assert!(this < next);
+        prop_assert_eq!(slot_for_wallclock(this, slot_length, start), Ok(slot));
+        prop_assert_eq!(
+            slot_for_wallclock(next - Duration::nanoseconds(1), slot_length, start),
+            Ok(slot)
+        );
+    }
+
+    #[test]
+    fn relative_time_difference_round_trips(
+        a in 0..i64::MAX / 2,
+        b in 0..i64::MAX / 2,
+    ) {
+        let (a, b) = (
+            RelativeTime::new(Duration::nanoseconds(a)),
+            RelativeTime::new(Duration::nanoseconds(b)),
+        );
+        let delta = diff_relative_time(a, b);
+        prop_assert_eq!(delta.is_negative(), a < b);
+        prop_assert_eq!(b.checked_add_signed(delta), Some(a));
+    }
+
+    #[test]
+    fn elapsed_since_inverts_interval_addition(epoch in 0..u64::MAX / 2, interval in any::<u32>()) {
+        let (epoch, interval) = (EpochNo(epoch), EpochInterval(interval));
+        let later = epoch.checked_add_interval(interval).expect("no overflow");
+        prop_assert_eq!(later.elapsed_since(epoch), Some(interval));
+        prop_assert!(later.is_within(epoch, interval));
+        prop_assert!(!EpochNo(later.0 + 1).is_within(epoch, interval));
+    }
+
+    #[test]
+    fn interval_addition_saturates_at_u32(a in any::<u32>(), b in any::<u32>()) {
+        let (a, b) = (EpochInterval(a), EpochInterval(b));
+        match a.checked_add(b) {
+            Some(sum) => prop_assert_eq!(a.saturating_add(b), sum),
+            None => prop_assert_eq!(a.saturating_add(b), EpochInterval::MAX),
+        }
+    }
+
+    #[test]
+    fn epoch_addition_saturates_at_u64(offset in 0..=u64::from(u32::MAX), interval in any::<u32>()) {
+        let epoch = EpochNo(u64::MAX - offset);
+        let interval = EpochInterval(interval);
+        let saturated = epoch.saturating_add_interval(interval);
+        match epoch.checked_add_interval(interval) {
+            Some(sum) => prop_assert_eq!(saturated, sum),
+            None => prop_assert_eq!(saturated, EpochNo(u64::MAX)),
+        }
+        prop_assert!(saturated.is_within(epoch, interval));
+    }
+
+    #[test]
+    fn with_origin_round_trips_through_option(value in arb_with_origin(arb_slot_no(u64::MAX))) {
+        prop_assert_eq!(WithOrigin::from(value.into_option()), value);
+    }
+
+    #[test]
+    fn u64_keys_order_like_their_values(
+        slots in (arb_slot_no(u64::MAX), arb_slot_no(u64::MAX)),
+        epochs in (arb_epoch_no(u64::MAX), arb_epoch_no(u64::MAX)),
+        blocks in (arb_block_no(u64::MAX), arb_block_no(u64::MAX)),
+    ) {
+        assert_key_order::<_, 8>(&slots.0, &slots.1)?;
+        assert_key_order::<_, 8>(&epochs.0, &epochs.1)?;
+        assert_key_order::<_, 8>(&blocks.0, &blocks.1)?;
+        prop_assert_eq!(SlotNo::from_be_key(&slots.0.to_be_key()), slots.0);
+        prop_assert_eq!(EpochNo::from_be_key(&epochs.0.to_be_key()), epochs.0);
+        prop_assert_eq!(BlockNo::from_be_key(&blocks.0.to_be_key()), blocks.0);
+    }
+
+    #[test]
+    fn with_origin_keys_order_origin_first(
+        a in arb_with_origin(arb_slot_no(u64::MAX)),
+        b in arb_with_origin(arb_slot_no(u64::MAX)),
+    ) {
+        assert_key_order::<_, 9>(&a, &b)?;
+    }
+
+    #[test]
+    fn composite_keys_order_lexicographically(
+        a in (arb_epoch_no(u64::MAX), arb_with_origin(arb_slot_no(u64::MAX)), arb_block_no(u64::MAX)),
+        b in (arb_epoch_no(u64::MAX), arb_with_origin(arb_slot_no(u64::MAX)), arb_block_no(u64::MAX)),
+        shared_epoch in any::<bool>(),
+    ) {
+        // Equal leading components are rare at random, so force them half
+        // of the time to exercise the later ones.
+        let b = if shared_epoch { (a.0, b.1, b.2) } else { b };
+        assert_key_order::<_, 17>(&(a.0, a.1), &(b.0, b.1))?;
+        assert_key_order::<_, 25>(&a, &b)?;
+
+        let built: [u8; 25] = CompositeKey::new().push(a.0).push(a.1).push(a.2).encode();
+        prop_assert_eq!(built, encode_key::<_, 25>(&a));
+    }
+}
+
+/// Byte-wise order of the encodings matches `Ord`, and both decode back.
+fn assert_key_order<K, const N: usize>(a: &K, b: &K) -> Result<(), TestCaseError>
+where
+    K: FixedKey + Ord + std::fmt::Debug,
+{
+    let (key_a, key_b) = (encode_key::<K, N>(a), encode_key::<K, N>(b));
+    prop_assert_eq!(key_a.cmp(&key_b), a.cmp(b), "{:?} vs {:?}", a, b);
+    prop_assert_eq!(&decode_key::<K>(&key_a).expect("own encoding decodes"), a);
+    prop_assert_eq!(&decode_key::<K>(&key_b).expect("own encoding decodes"), b);
+    Ok(())
+}
+
+#[test]
+fn storage_keys_reject_wrong_lengths() {
+    let key = encode_key::<_, 17>(&(EpochNo(208), WithOrigin::At(SlotNo(4_492_800))));
+    for len in [0, 8, 16, 18] {
+        let mut bytes = key.to_vec();
+        bytes.resize(len, 0);
+        assert_eq!(
+            decode_key::<(EpochNo, WithOrigin<SlotNo>)>(&bytes),
+            Err(StorageKeyError::WrongLength {
+                expected: 17,
+                actual: len
+            })
+        );
+    }
+}
+
+#[test]
+fn epoch_info_builder_matches_fixed_epoch_info() {
+    let built = EpochInfoBuilder::fixed()
+        .epoch_size(EpochSize(20))
+        .slot_length(slot_length_from_millisec(1000))
+        .build();
+    let direct = fixed_epoch_info(EpochSize(20), slot_length_from_millisec(1000));
+    for slot in [0, 19, 20, 1_234].map(SlotNo) {
+        assert_eq!(
+            epoch_info_epoch(&built, slot).unwrap(),
+            epoch_info_epoch(&direct, slot).unwrap()
+        );
+        assert_eq!(
+            epoch_info_slot_to_relative_time(&built, slot).unwrap(),
+            epoch_info_slot_to_relative_time(&direct, slot).unwrap()
+        );
+    }
+}
+
+#[test]
+fn linear_extension_elapsed_time_past_basis() {
+    let base_info = fixed_epoch_info(EpochSize(10), slot_length_from_millisec(2000));
+    let extended = unsafe_linear_extend_epoch_info(SlotNo(25), base_info);
+
+    assert_eq!(
+        epoch_info_slot_length_at_slot(&extended, SlotNo(1000)).unwrap(),
+        slot_length_from_millisec(2000)
+    );
+    let elapsed = epoch_info_elapsed_time_to_slot(&extended, SlotNo(1000)).unwrap();
+    assert_eq!(elapsed.duration(), Duration::seconds(2000));
 }
 
 #[test]
@@ -75,3 +537,190 @@ fn slot_length_roundtrips() {
     let length = slot_length_from_millisec(1500);
     assert_eq!(slot_length_to_millisec(length), 1500);
 }
+
+#[test]
+fn millisecond_constructors_round_trip_exactly() {
+    for millis in (0..=5_000).chain([20_000, 86_400_000, 31_536_000_000]) {
+        let length = slot_length_from_millisec(millis);
+        assert_eq!(slot_length_to_millisec(length), millis);
+        assert_eq!(
+            length.duration(),
+            Duration::milliseconds(i64::try_from(millis).expect("millis fit in i64"))
+        );
+        assert_eq!(SlotLength::new(length.duration()), length);
+    }
+    for seconds in [0, 1, 20, 3_600] {
+        assert_eq!(slot_length_to_sec(slot_length_from_sec(seconds)), seconds);
+    }
+    assert_eq!(
+        slot_length_from_millisec(100),
+        slot_length_from_fraction(1, 10)
+    );
+}
+
+#[test]
+fn third_of_a_second_slots_do_not_drift() {
+    let length = slot_length_from_fraction(1, 3);
+    let info = fixed_epoch_info(EpochSize(432_000), length);
+    let time_at = |n: u64| {
+        epoch_info_slot_to_relative_time(&info, SlotNo(n))
+            .unwrap()
+            .duration()
+    };
+
+    // Sample the first 10^8 slots: every 9_973rd slot plus both ends.
+    let samples = (1..=1_000)
+        .chain((1..=100_000_000).step_by(9_973))
+        .chain(99_999_000..=100_000_000);
+    for n in samples {
+        let exact_nanos = i128::from(n) * 1_000_000_000 / 3;
+        let current = time_at(n);
+        assert_eq!(current.whole_nanoseconds(), exact_nanos, "slot {n}");
+
+        // Each slot lasts 1/3 s rounded to a neighbouring nanosecond.
+        let step = (current - time_at(n - 1)).whole_nanoseconds();
+        assert!(
+            step == 333_333_333 || step == 333_333_334,
+            "slot {n} lasted {step}ns"
+        );
+        // Every third slot lands exactly on a whole second.
+        if n % 3 == 0 {
+            assert_eq!(
+                current,
+                Duration::seconds(i64::try_from(n / 3).expect("seconds fit in i64"))
+            );
+        }
+    }
+}
+
+#[test]
+fn slot_length_serialization_is_backwards_compatible() {
+    let length = slot_length_from_millisec(1500);
+    assert_eq!(
+        serde_json::to_string(&length).expect("serialize"),
+        "[1,500000000]"
+    );
+    assert_eq!(
+        serde_json::from_str::<SlotLength>("[1,500000000]").expect("deserialize"),
+        length
+    );
+    assert_eq!(
+        format!("{length:?}"),
+        format!("SlotLength({:?})", Duration::milliseconds(1500))
+    );
+
+    let third = slot_length_from_fraction(2, 6);
+    let json = serde_json::to_string(&third).expect("serialize");
+    assert_eq!(json, r#"{"numerator":1,"denominator":3}"#);
+    assert_eq!(
+        serde_json::from_str::<SlotLength>(&json).expect("deserialize"),
+        third
+    );
+    assert_eq!(format!("{third:?}"), "SlotLength(1/3s)");
+
+    assert!(serde_json::from_str::<SlotLength>(r#"{"numerator":1,"denominator":0}"#).is_err());
+    assert!(serde_json::from_str::<SlotLength>("[-1,0]").is_err());
+}
+
+#[test]
+fn slot_length_rejects_out_of_range_lengths() {
+    // 10^10 s is 10^19 ns: it fits a u64 but not the i64 range of `duration`.
+    assert!(serde_json::from_str::<SlotLength>("[10000000000,0]").is_err());
+    assert!(
+        serde_json::from_str::<SlotLength>(r#"{"numerator":10000000000,"denominator":1}"#).is_err()
+    );
+    assert_eq!(
+        SlotLength::try_from_fraction(10_000_000_000, 1),
+        Err(SlotLengthError::OutOfRange)
+    );
+    assert_eq!(
+        SlotLength::try_from_fraction(1, 0),
+        Err(SlotLengthError::ZeroDenominator)
+    );
+    assert_eq!(
+        SlotLength::try_from_fraction(2, 6),
+        Ok(slot_length_from_fraction(1, 3))
+    );
+}
+
+#[test]
+fn mainnet_security_windows() {
+    let k = SecurityParam(2160);
+    let f = Ratio::new(1, 20);
+
+    let stability = stability_window(k, f);
+    assert_eq!(stability, 129_600);
+    assert_eq!(randomness_stabilization_window(k, f), 172_800);
+    assert_eq!(max_rollbacks(k), 2160);
+
+    let tip = SlotNo(200_000);
+    assert!(is_within_stability_window(
+        tip,
+        SlotNo(200_000 - 129_599),
+        stability
+    ));
+    assert!(!is_within_stability_window(
+        tip,
+        SlotNo(200_000 - 129_600),
+        stability
+    ));
+}
+
+#[test]
+fn mainnet_randomness_window_regression() {
+    // Conway mainnet: 432 000 one-second slots per epoch, and the candidate
+    // nonce freezes 4k/f = 172 800 slots before the epoch ends.
+    let info = fixed_epoch_info(EpochSize(432_000), slot_length_from_sec(1));
+    let frozen = randomness_stabilization_window(SecurityParam(2160), Ratio::new(1, 20));
+    let window = 432_000 - frozen;
+    assert_eq!(window, 259_200);
+
+    let first = 500 * 432_000;
+    assert_eq!(
+        slot_phase(&info, SlotNo(first + 259_199), window).unwrap(),
+        SlotPhase::ContributesToNonce {
+            slots_into_epoch: 259_199
+        }
+    );
+    assert_eq!(
+        slot_phase(&info, SlotNo(first + 259_200), window).unwrap(),
+        SlotPhase::AfterNonceWindow {
+            slots_remaining: 172_799
+        }
+    );
+    assert_eq!(
+        slot_phase(&info, SlotNo(first + 432_000), window).unwrap(),
+        SlotPhase::ContributesToNonce {
+            slots_into_epoch: 0
+        }
+    );
+}
+
+#[test]
+fn mainnet_system_start_maps_slots_to_wallclock() {
+    let start = SystemStart::from_unix_millis(1_506_203_091_000).expect("in range");
+    assert_eq!(
+        start,
+        SystemStart::from_offset_date_time(datetime!(2017-09-23 21:44:51 UTC))
+    );
+    assert_eq!(start.to_unix_millis(), 1_506_203_091_000);
+
+    // Byron: 20 s slots.
+    let byron = slot_length_from_sec(20);
+    assert_eq!(
+        wallclock_for_slot(SlotNo(0), byron, start),
+        start.to_offset_date_time()
+    );
+    assert_eq!(
+        slot_for_wallclock(start.to_offset_date_time(), byron, start),
+        Ok(SlotNo(0))
+    );
+    assert_eq!(
+        wallclock_for_slot(SlotNo(4_320), byron, start),
+        datetime!(2017-09-24 21:44:51 UTC)
+    );
+    assert_eq!(
+        slot_for_wallclock(datetime!(2017-09-24 21:45:10.999 UTC), byron, start),
+        Ok(SlotNo(4_320))
+    );
+}
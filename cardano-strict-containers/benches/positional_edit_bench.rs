@@ -0,0 +1,83 @@
+//! Positional edits on a 100k-element `StrictSeq`.
+//!
+//! `split_rebuild` is the workaround ported ledger rules used before
+//! `insert_at` existed: split the sequence, push the new element and
+//! concatenate. `insert_at` does the same in one pass over the spine.
+//! `nearby_edits` applies 64 neighbouring replacements, either through
+//! repeated `update` calls or through one `Cursor`.
+//!
+//! Elements own a heap-allocated key hash, as ledger certificates do, so an
+//! edit that copied elements would pay an allocation for each.
+
+use cardano_strict_containers::StrictSeq;
+use criterion::{BatchSize, Criterion, black_box, criterion_group, criterion_main};
+
+const SIZE: u64 = 100_000;
+const INDEX: usize = 61_803;
+
+#[derive(Clone)]
+struct Cert {
+    key_hash: Vec<u8>,
+}
+
+fn cert(n: u64) -> Cert {
+    let mut key_hash = vec![0; 28];
+    key_hash[..8].copy_from_slice(&n.to_be_bytes());
+    Cert { key_hash }
+}
+
+fn insert_at(c: &mut Criterion) {
+    let seq = StrictSeq::from_list((0..SIZE).map(cert));
+
+    let inserted = seq.insert_at(INDEX, cert(SIZE)).expect("index in bounds");
+    assert_eq!(
+        inserted.get(INDEX).map(|c| &c.key_hash),
+        Some(&cert(SIZE).key_hash)
+    );
+
+    let mut group = c.benchmark_group("insert_at_100k");
+    group.bench_function("split_rebuild", |b| {
+        b.iter(|| {
+            let (left, right) = seq.split_at(black_box(INDEX));
+            black_box(left.snoc(cert(SIZE)).concat(right))
+        });
+    });
+    group.bench_function("insert_at", |b| {
+        b.iter(|| black_box(seq.insert_at(black_box(INDEX), cert(SIZE))));
+    });
+    group.finish();
+}
+
+fn nearby_edits(c: &mut Criterion) {
+    let seq = StrictSeq::from_list((0..SIZE).map(cert));
+
+    let mut group = c.benchmark_group("nearby_edits_100k");
+    group.bench_function("update", |b| {
+        b.iter(|| {
+            let mut edited = seq.clone();
+            for offset in 0..64 {
+                edited = edited
+                    .update(INDEX + offset, cert(SIZE))
+                    .expect("index in bounds");
+            }
+            black_box(edited)
+        });
+    });
+    group.bench_function("cursor", |b| {
+        b.iter_batched(
+            || seq.cursor(INDEX).expect("index in bounds"),
+            |mut cursor| {
+                for _ in 0..64 {
+                    cursor.replace(cert(SIZE));
+                    cursor.move_next();
+                }
+                black_box(cursor.into_seq())
+            },
+            BatchSize::SmallInput,
+        );
+    });
+    group.finish();
+}
+
+criterion_group!(benches, insert_at, nearby_edits);
+criterion_main!(benches);
@@ -1,7 +1,8 @@
+use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::fmt;
 use std::iter::FromIterator;
-use std::marker::PhantomData;
+use std::sync::Arc;
 
 /// Minimal semigroup abstraction mirroring the Haskell API.
 pub trait Semigroup: Sized {
@@ -94,11 +95,677 @@ where
     }
 }
 
-/// Strict finger tree implemented on top of `VecDeque`.
-#[derive(Clone, Default, PartialEq, Eq)]
+/// An element stored at some depth of the tree: a user value at the top level
+/// or a 2-3 node of deeper elements further down the spine.
+enum Item<V, A> {
+    Leaf(A),
+    Node(Arc<Node<V, A>>),
+}
+
+/// Internal 2-3 node caching its measure and element count.
+struct Node<V, A> {
+    measure: V,
+    size: usize,
+    items: Vec<Item<V, A>>,
+}
+
+/// Shared spine node of a deep tree; digits hold one to four items.
+struct Deep<V, A> {
+    measure: V,
+    size: usize,
+    prefix: Vec<Item<V, A>>,
+    middle: Tree<V, A>,
+    suffix: Vec<Item<V, A>>,
+}
+
+/// A tree split around a focus item: `(before, focus, after)`.
+type Split<V, A> = (Tree<V, A>, Item<V, A>, Tree<V, A>);
+
+/// A digit split around a focus item: `(before, focus, after)`.
+type DigitSplit<V, A> = (Vec<Item<V, A>>, Item<V, A>, Vec<Item<V, A>>);
+
+enum Tree<V, A> {
+    Empty,
+    Single(Item<V, A>),
+    Deep(Arc<Deep<V, A>>),
+}
+
+impl<V, A: Clone> Clone for Item<V, A> {
+    fn clone(&self) -> Self {
+        match self {
+            Item::Leaf(value) => Item::Leaf(value.clone()),
+            Item::Node(node) => Item::Node(Arc::clone(node)),
+        }
+    }
+}
+
+impl<V: Clone, A: Clone> Clone for Node<V, A> {
+    fn clone(&self) -> Self {
+        Node {
+            measure: self.measure.clone(),
+            size: self.size,
+            items: self.items.clone(),
+        }
+    }
+}
+
+impl<V: Clone, A: Clone> Clone for Deep<V, A> {
+    fn clone(&self) -> Self {
+        Deep {
+            measure: self.measure.clone(),
+            size: self.size,
+            prefix: self.prefix.clone(),
+            middle: self.middle.clone(),
+            suffix: self.suffix.clone(),
+        }
+    }
+}
+
+impl<V, A: Clone> Clone for Tree<V, A> {
+    fn clone(&self) -> Self {
+        match self {
+            Tree::Empty => Tree::Empty,
+            Tree::Single(item) => Tree::Single(item.clone()),
+            Tree::Deep(deep) => Tree::Deep(Arc::clone(deep)),
+        }
+    }
+}
+
+/// Combine the measures of a non-empty run of items from left to right.
+fn items_measure<V, A>(items: &[Item<V, A>]) -> V
+where
+    V: Monoid + Clone,
+    A: Measured<V>,
+{
+    match items.split_first() {
+        None => V::empty(),
+        Some((first, rest)) => rest
+            .iter()
+            .fold(first.measure(), |acc, item| acc.combine(&item.measure())),
+    }
+}
+
+fn items_size<V, A>(items: &[Item<V, A>]) -> usize {
+    items.iter().map(Item::size).sum()
+}
+
+/// The item of a digit covering element `index`, with the offset of `index`
+/// inside it.
+fn digit_lookup<V, A>(items: &[Item<V, A>], mut index: usize) -> (&Item<V, A>, usize) {
+    for item in items {
+        if index < item.size() {
+            return (item, index);
+        }
+        index -= item.size();
+    }
+    unreachable!("the index lies within the digit")
+}
+
+/// Mutable counterpart of [`digit_lookup`].
+fn digit_lookup_mut<V, A>(items: &mut [Item<V, A>], mut index: usize) -> (&mut Item<V, A>, usize) {
+    for item in items {
+        if index < item.size() {
+            return (item, index);
+        }
+        index -= item.size();
+    }
+    unreachable!("the index lies within the digit")
+}
+
+/// Split a digit around the item covering element `index`, returning the
+/// offset of `index` inside the focus item.
+fn split_digit_index<V, A: Clone>(
+    items: &[Item<V, A>],
+    mut index: usize,
+) -> (DigitSplit<V, A>, usize) {
+    for (idx, item) in items.iter().enumerate() {
+        if index < item.size() {
+            return (
+                (
+                    items[..idx].to_vec(),
+                    item.clone(),
+                    items[idx + 1..].to_vec(),
+                ),
+                index,
+            );
+        }
+        index -= item.size();
+    }
+    unreachable!("the index lies within the digit")
+}
+
+impl<V, A> Item<V, A> {
+    fn size(&self) -> usize {
+        match self {
+            Item::Leaf(_) => 1,
+            Item::Node(node) => node.size,
+        }
+    }
+
+    /// The leaf at position `index` below this item; `index` must be below
+    /// `self.size()`.
+    fn leaf(&self, index: usize) -> &A {
+        match self {
+            Item::Leaf(value) => value,
+            Item::Node(node) => {
+                let (item, offset) = digit_lookup(&node.items, index);
+                item.leaf(offset)
+            },
+        }
+    }
+
+    /// Unwrap a top-level item. Only leaves are stored at depth zero.
+    fn into_leaf(self) -> A {
+        match self {
+            Item::Leaf(value) => value,
+            Item::Node(_) => unreachable!("top-level finger tree items are leaves"),
+        }
+    }
+}
+
+impl<V, A> Item<V, A>
+where
+    V: Monoid + Clone,
+    A: Measured<V>,
+{
+    fn measure(&self) -> V {
+        match self {
+            Item::Leaf(value) => value.measure(),
+            Item::Node(node) => node.measure.clone(),
+        }
+    }
+
+    fn node(items: Vec<Item<V, A>>) -> Self {
+        Item::Node(Arc::new(Node {
+            measure: items_measure(&items),
+            size: items_size(&items),
+            items,
+        }))
+    }
+
+    /// Mutable counterpart of [`Item::leaf`]. Nodes on the way down that are
+    /// shared with another tree are copied first, so only this tree changes.
+    fn leaf_mut(&mut self, index: usize) -> &mut A {
+        match self {
+            Item::Leaf(value) => value,
+            Item::Node(node) => {
+                let (item, offset) = digit_lookup_mut(&mut Arc::make_mut(node).items, index);
+                item.leaf_mut(offset)
+            },
+        }
+    }
+
+    /// Push mutable references to every leaf below this item onto `out`, in
+    /// order, copying shared nodes as [`Item::leaf_mut`] does.
+    fn leaves_mut<'a>(&'a mut self, out: &mut Vec<&'a mut A>) {
+        match self {
+            Item::Leaf(value) => out.push(value),
+            Item::Node(node) => {
+                for item in &mut Arc::make_mut(node).items {
+                    item.leaves_mut(out);
+                }
+            },
+        }
+    }
+
+    /// The children of a node-level item, used when a digit is refilled from
+    /// the middle tree.
+    fn into_digit(self) -> Vec<Item<V, A>> {
+        match self {
+            Item::Node(node) => node.items.clone(),
+            Item::Leaf(_) => unreachable!("middle finger tree items are nodes"),
+        }
+    }
+}
+
+/// Pack a run of two or more items into 2-3 nodes.
+fn nodes<V, A>(items: Vec<Item<V, A>>) -> Vec<Item<V, A>>
+where
+    V: Monoid + Clone,
+    A: Measured<V>,
+{
+    let mut remaining = items.len();
+    let mut packed = Vec::with_capacity(remaining / 2 + 1);
+    let mut rest = items.into_iter();
+    while remaining > 0 {
+        let take = match remaining {
+            2 | 4 => 2,
+            _ => 3,
+        };
+        packed.push(Item::node(rest.by_ref().take(take).collect()));
+        remaining -= take;
+    }
+    packed
+}
+
+impl<V, A> Tree<V, A>
+where
+    V: Monoid + Clone,
+    A: Measured<V>,
+{
+    fn measure(&self) -> V {
+        match self {
+            Tree::Empty => V::empty(),
+            Tree::Single(item) => item.measure(),
+            Tree::Deep(deep) => deep.measure.clone(),
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Tree::Empty => 0,
+            Tree::Single(item) => item.size(),
+            Tree::Deep(deep) => deep.size,
+        }
+    }
+
+    fn deep(prefix: Vec<Item<V, A>>, middle: Tree<V, A>, suffix: Vec<Item<V, A>>) -> Self {
+        let measure = items_measure(&prefix)
+            .combine(&middle.measure())
+            .combine(&items_measure(&suffix));
+        let size = items_size(&prefix) + middle.size() + items_size(&suffix);
+        Tree::Deep(Arc::new(Deep {
+            measure,
+            size,
+            prefix,
+            middle,
+            suffix,
+        }))
+    }
+
+    fn from_digit(mut items: Vec<Item<V, A>>) -> Self {
+        match items.len() {
+            0 => Tree::Empty,
+            1 => Tree::Single(items.remove(0)),
+            len => {
+                let suffix = items.split_off(len / 2);
+                Tree::deep(items, Tree::Empty, suffix)
+            },
+        }
+    }
+
+    fn push_front(&self, item: Item<V, A>) -> Self {
+        match self {
+            Tree::Empty => Tree::Single(item),
+            Tree::Single(other) => Tree::deep(vec![item], Tree::Empty, vec![other.clone()]),
+            Tree::Deep(deep) if deep.prefix.len() == 4 => {
+                let spill = Item::node(deep.prefix[1..].to_vec());
+                Tree::deep(
+                    vec![item, deep.prefix[0].clone()],
+                    deep.middle.push_front(spill),
+                    deep.suffix.clone(),
+                )
+            },
+            Tree::Deep(deep) => {
+                let mut prefix = Vec::with_capacity(deep.prefix.len() + 1);
+                prefix.push(item);
+                prefix.extend(deep.prefix.iter().cloned());
+                Tree::deep(prefix, deep.middle.clone(), deep.suffix.clone())
+            },
+        }
+    }
+
+    fn push_back(&self, item: Item<V, A>) -> Self {
+        match self {
+            Tree::Empty => Tree::Single(item),
+            Tree::Single(other) => Tree::deep(vec![other.clone()], Tree::Empty, vec![item]),
+            Tree::Deep(deep) if deep.suffix.len() == 4 => {
+                let spill = Item::node(deep.suffix[..3].to_vec());
+                Tree::deep(
+                    deep.prefix.clone(),
+                    deep.middle.push_back(spill),
+                    vec![deep.suffix[3].clone(), item],
+                )
+            },
+            Tree::Deep(deep) => {
+                let mut suffix = deep.suffix.clone();
+                suffix.push(item);
+                Tree::deep(deep.prefix.clone(), deep.middle.clone(), suffix)
+            },
+        }
+    }
+
+    fn view_front(&self) -> Option<(Item<V, A>, Tree<V, A>)> {
+        match self {
+            Tree::Empty => None,
+            Tree::Single(item) => Some((item.clone(), Tree::Empty)),
+            Tree::Deep(deep) => Some((
+                deep.prefix[0].clone(),
+                Tree::deep_front(deep.prefix[1..].to_vec(), &deep.middle, deep.suffix.clone()),
+            )),
+        }
+    }
+
+    fn view_back(&self) -> Option<(Tree<V, A>, Item<V, A>)> {
+        match self {
+            Tree::Empty => None,
+            Tree::Single(item) => Some((Tree::Empty, item.clone())),
+            Tree::Deep(deep) => {
+                let last = deep.suffix.len() - 1;
+                Some((
+                    Tree::deep_back(
+                        deep.prefix.clone(),
+                        &deep.middle,
+                        deep.suffix[..last].to_vec(),
+                    ),
+                    deep.suffix[last].clone(),
+                ))
+            },
+        }
+    }
+
+    /// Rebuild a deep tree whose prefix may have become empty.
+    fn deep_front(prefix: Vec<Item<V, A>>, middle: &Tree<V, A>, suffix: Vec<Item<V, A>>) -> Self {
+        if !prefix.is_empty() {
+            return Tree::deep(prefix, middle.clone(), suffix);
+        }
+        match middle.view_front() {
+            None => Tree::from_digit(suffix),
+            Some((node, rest)) => Tree::deep(node.into_digit(), rest, suffix),
+        }
+    }
+
+    /// Rebuild a deep tree whose suffix may have become empty.
+    fn deep_back(prefix: Vec<Item<V, A>>, middle: &Tree<V, A>, suffix: Vec<Item<V, A>>) -> Self {
+        if !suffix.is_empty() {
+            return Tree::deep(prefix, middle.clone(), suffix);
+        }
+        match middle.view_back() {
+            None => Tree::from_digit(prefix),
+            Some((rest, node)) => Tree::deep(prefix, rest, node.into_digit()),
+        }
+    }
+
+    fn append3(left: &Tree<V, A>, mut between: Vec<Item<V, A>>, right: &Tree<V, A>) -> Self {
+        match (left, right) {
+            (Tree::Empty, _) => between
+                .into_iter()
+                .rev()
+                .fold(right.clone(), |tree, item| tree.push_front(item)),
+            (_, Tree::Empty) => between
+                .into_iter()
+                .fold(left.clone(), |tree, item| tree.push_back(item)),
+            (Tree::Single(item), _) => {
+                Tree::append3(&Tree::Empty, between, right).push_front(item.clone())
+            },
+            (_, Tree::Single(item)) => {
+                Tree::append3(left, between, &Tree::Empty).push_back(item.clone())
+            },
+            (Tree::Deep(l), Tree::Deep(r)) => {
+                let mut joined = l.suffix.clone();
+                joined.append(&mut between);
+                joined.extend(r.prefix.iter().cloned());
+                Tree::deep(
+                    l.prefix.clone(),
+                    Tree::append3(&l.middle, nodes(joined), &r.middle),
+                    r.suffix.clone(),
+                )
+            },
+        }
+    }
+
+    /// Locate the item at which `predicate(left, right)` flips to `true`,
+    /// where `left` includes the item and `right` covers everything after it.
+    ///
+    /// `left_acc`/`right_acc` are the measures of the context surrounding this
+    /// (non-empty) subtree. Only the spine along the split path is visited, so
+    /// the cost is logarithmic in the size of the tree.
+    fn search_split<P>(&self, predicate: &mut P, left_acc: &V, right_acc: &V) -> Split<V, A>
+    where
+        P: FnMut(&V, &V) -> bool,
+    {
+        match self {
+            Tree::Empty => unreachable!("search_split requires a non-empty tree"),
+            Tree::Single(item) => (Tree::Empty, item.clone(), Tree::Empty),
+            Tree::Deep(deep) => {
+                let prefix_measure = items_measure(&deep.prefix);
+                let middle_measure = deep.middle.measure();
+                let suffix_right = items_measure(&deep.suffix).combine(right_acc);
+                let left_prefix = left_acc.combine(&prefix_measure);
+                let left_prefix_middle = left_prefix.combine(&middle_measure);
+
+                if predicate(&left_prefix, &middle_measure.combine(&suffix_right)) {
+                    let (before, item, after) = search_digit(
+                        &deep.prefix,
+                        predicate,
+                        left_acc,
+                        &middle_measure.combine(&suffix_right),
+                    );
+                    (
+                        Tree::from_digit(before),
+                        item,
+                        Tree::deep_front(after, &deep.middle, deep.suffix.clone()),
+                    )
+                } else if predicate(&left_prefix_middle, &suffix_right) {
+                    let (middle_left, node, middle_right) =
+                        deep.middle
+                            .search_split(predicate, &left_prefix, &suffix_right);
+                    let (before, item, after) = search_digit(
+                        &node.into_digit(),
+                        predicate,
+                        &left_prefix.combine(&middle_left.measure()),
+                        &middle_right.measure().combine(&suffix_right),
+                    );
+                    (
+                        Tree::deep_back(deep.prefix.clone(), &middle_left, before),
+                        item,
+                        Tree::deep_front(after, &middle_right, deep.suffix.clone()),
+                    )
+                } else {
+                    let (before, item, after) =
+                        search_digit(&deep.suffix, predicate, &left_prefix_middle, right_acc);
+                    (
+                        Tree::deep_back(deep.prefix.clone(), &deep.middle, before),
+                        item,
+                        Tree::from_digit(after),
+                    )
+                }
+            },
+        }
+    }
+    /// Positional counterpart of [`Tree::search_split`]: split around the
+    /// item covering element `index`, steering by the cached element counts
+    /// instead of the measure. Also returns the offset of `index` inside the
+    /// focus item, which is zero at the top level.
+    ///
+    /// `index` must be below `self.size()`.
+    fn split_index(&self, index: usize) -> (Split<V, A>, usize) {
+        match self {
+            Tree::Empty => unreachable!("split_index requires a non-empty tree"),
+            Tree::Single(item) => ((Tree::Empty, item.clone(), Tree::Empty), index),
+            Tree::Deep(deep) => {
+                let prefix_size = items_size(&deep.prefix);
+                let middle_size = deep.middle.size();
+                if index < prefix_size {
+                    let ((before, item, after), offset) = split_digit_index(&deep.prefix, index);
+                    (
+                        (
+                            Tree::from_digit(before),
+                            item,
+                            Tree::deep_front(after, &deep.middle, deep.suffix.clone()),
+                        ),
+                        offset,
+                    )
+                } else if index < prefix_size + middle_size {
+                    let ((middle_left, node, middle_right), offset) =
+                        deep.middle.split_index(index - prefix_size);
+                    let ((before, item, after), offset) =
+                        split_digit_index(&node.into_digit(), offset);
+                    (
+                        (
+                            Tree::deep_back(deep.prefix.clone(), &middle_left, before),
+                            item,
+                            Tree::deep_front(after, &middle_right, deep.suffix.clone()),
+                        ),
+                        offset,
+                    )
+                } else {
+                    let ((before, item, after), offset) =
+                        split_digit_index(&deep.suffix, index - prefix_size - middle_size);
+                    (
+                        (
+                            Tree::deep_back(deep.prefix.clone(), &deep.middle, before),
+                            item,
+                            Tree::from_digit(after),
+                        ),
+                        offset,
+                    )
+                }
+            },
+        }
+    }
+
+    /// The item covering element `index` and the offset of `index` inside
+    /// it, without rebuilding anything. `index` must be below `self.size()`.
+    fn item_at(&self, index: usize) -> (&Item<V, A>, usize) {
+        match self {
+            Tree::Empty => unreachable!("item_at requires a non-empty tree"),
+            Tree::Single(item) => (item, index),
+            Tree::Deep(deep) => {
+                let prefix_size = items_size(&deep.prefix);
+                let middle_size = deep.middle.size();
+                if index < prefix_size {
+                    digit_lookup(&deep.prefix, index)
+                } else if index < prefix_size + middle_size {
+                    deep.middle.item_at(index - prefix_size)
+                } else {
+                    digit_lookup(&deep.suffix, index - prefix_size - middle_size)
+                }
+            },
+        }
+    }
+
+    /// The element at `index`; `index` must be below `self.size()`.
+    fn leaf(&self, index: usize) -> &A {
+        let (item, offset) = self.item_at(index);
+        item.leaf(offset)
+    }
+
+    /// Mutable counterpart of [`Tree::leaf`], copying the shared spine and
+    /// nodes along the path; `index` must be below `self.size()`.
+    fn leaf_mut(&mut self, index: usize) -> &mut A {
+        match self {
+            Tree::Empty => unreachable!("leaf_mut requires a non-empty tree"),
+            Tree::Single(item) => item.leaf_mut(index),
+            Tree::Deep(deep) => {
+                let Deep {
+                    prefix,
+                    middle,
+                    suffix,
+                    ..
+                } = Arc::make_mut(deep);
+                let prefix_size = items_size(prefix);
+                let middle_size = middle.size();
+                let (item, offset) = if index < prefix_size {
+                    digit_lookup_mut(prefix, index)
+                } else if index < prefix_size + middle_size {
+                    return middle.leaf_mut(index - prefix_size);
+                } else {
+                    digit_lookup_mut(suffix, index - prefix_size - middle_size)
+                };
+                item.leaf_mut(offset)
+            },
+        }
+    }
+
+    /// Push mutable references to every leaf onto `out`, in order.
+    fn leaves_mut<'a>(&'a mut self, out: &mut Vec<&'a mut A>) {
+        match self {
+            Tree::Empty => {},
+            Tree::Single(item) => item.leaves_mut(out),
+            Tree::Deep(deep) => {
+                let Deep {
+                    prefix,
+                    middle,
+                    suffix,
+                    ..
+                } = Arc::make_mut(deep);
+                for item in prefix {
+                    item.leaves_mut(out);
+                }
+                middle.leaves_mut(out);
+                for item in suffix {
+                    item.leaves_mut(out);
+                }
+            },
+        }
+    }
+}
+
+/// Digit-level counterpart of [`Tree::search_split`]. The last item is chosen
+/// when the predicate never flips inside the digit.
+fn search_digit<V, A, P>(
+    items: &[Item<V, A>],
+    predicate: &mut P,
+    left_acc: &V,
+    right_acc: &V,
+) -> DigitSplit<V, A>
+where
+    V: Monoid + Clone,
+    A: Measured<V>,
+    P: FnMut(&V, &V) -> bool,
+{
+    let mut left = left_acc.clone();
+    let last = items.len() - 1;
+    for (idx, item) in items.iter().enumerate() {
+        if idx == last {
+            break;
+        }
+        left = left.combine(&item.measure());
+        let right = items_measure(&items[idx + 1..]).combine(right_acc);
+        if predicate(&left, &right) {
+            return (
+                items[..idx].to_vec(),
+                item.clone(),
+                items[idx + 1..].to_vec(),
+            );
+        }
+    }
+    (items[..last].to_vec(), items[last].clone(), Vec::new())
+}
+
+/// Strict finger tree annotated with a monoidal measure.
+///
+/// This is a persistent 2-3 finger tree in the style of Hinze and Paterson,
+/// mirroring `Data.FingerTree.Strict`. Every internal node caches its measure
+/// and element count, so `measure` and `len` are O(1), access to either end is
+/// amortised O(1), and measure-guided operations (`search`, `split`,
+/// `split_off_prefix`, `drop_prefix_by_measure`) are O(log n). Subtrees are
+/// shared between versions, so cloning a tree or deriving a new one from it
+/// never copies the elements that are left untouched.
 pub struct StrictFingerTree<V, A> {
-    data: VecDeque<A>,
-    _marker: PhantomData<V>,
+    tree: Tree<V, A>,
+}
+
+impl<V, A: Clone> Clone for StrictFingerTree<V, A> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree.clone(),
+        }
+    }
+}
+
+impl<V, A> Default for StrictFingerTree<V, A> {
+    fn default() -> Self {
+        Self { tree: Tree::Empty }
+    }
+}
+
+impl<V, A> PartialEq for StrictFingerTree<V, A>
+where
+    V: Monoid + Clone,
+    A: Measured<V> + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<V, A> Eq for StrictFingerTree<V, A>
+where
+    V: Monoid + Clone,
+    A: Measured<V> + Eq,
+{
 }
 
 /// Convenience helper mirroring the original `addMeasure` utility.
@@ -125,236 +792,333 @@ where
     V: Monoid + Clone,
     A: Measured<V>,
 {
+    fn from_tree(tree: Tree<V, A>) -> Self {
+        Self { tree }
+    }
+
     #[must_use]
     pub fn empty() -> Self {
-        Self {
-            data: VecDeque::new(),
-            _marker: PhantomData,
-        }
+        Self::from_tree(Tree::Empty)
     }
 
     pub fn singleton(item: A) -> Self {
-        let mut data = VecDeque::new();
-        data.push_back(item);
-        Self {
-            data,
-            _marker: PhantomData,
-        }
+        Self::from_tree(Tree::Single(Item::Leaf(item)))
     }
 
     pub fn from_list<I: IntoIterator<Item = A>>(iter: I) -> Self {
-        Self {
-            data: iter.into_iter().collect(),
-            _marker: PhantomData,
-        }
+        let tree = iter
+            .into_iter()
+            .fold(Tree::Empty, |tree, item| tree.push_back(Item::Leaf(item)));
+        Self::from_tree(tree)
     }
 
     #[must_use]
     pub fn from_strict(tree: VecDeque<A>) -> Self {
-        Self {
-            data: tree,
-            _marker: PhantomData,
-        }
+        Self::from_list(tree)
     }
 
     #[must_use]
     pub fn force_to_strict(tree: VecDeque<A>) -> Self {
-        Self {
-            data: tree,
-            _marker: PhantomData,
-        }
+        Self::from_list(tree)
     }
 
     #[must_use]
     pub fn into_inner(self) -> VecDeque<A> {
-        self.data
+        self.iter().cloned().collect()
     }
 
     #[must_use]
     pub fn len(&self) -> usize {
-        self.data.len()
+        self.tree.size()
     }
 
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.data.is_empty()
+        matches!(self.tree, Tree::Empty)
     }
 
     #[must_use]
     pub fn null(&self) -> bool {
-        self.data.is_empty()
+        self.is_empty()
     }
 
     #[must_use]
     pub fn viewl(&self) -> ViewL<V, A> {
-        match self.data.front() {
+        match self.tree.view_front() {
             None => ViewL::EmptyL,
-            Some(_) => {
-                let mut rest = self.data.clone();
-                let first = rest.pop_front().expect("front element exists");
-                ViewL::Cons(
-                    first,
-                    Self {
-                        data: rest,
-                        _marker: PhantomData,
-                    },
-                )
-            },
+            Some((item, rest)) => ViewL::Cons(item.into_leaf(), Self::from_tree(rest)),
         }
     }
 
     #[must_use]
     pub fn viewr(&self) -> ViewR<V, A> {
-        match self.data.back() {
+        match self.tree.view_back() {
             None => ViewR::EmptyR,
-            Some(_) => {
-                let mut rest = self.data.clone();
-                let last = rest.pop_back().expect("back element exists");
-                ViewR::Cons(
-                    Self {
-                        data: rest,
-                        _marker: PhantomData,
-                    },
-                    last,
-                )
-            },
+            Some((rest, item)) => ViewR::Cons(Self::from_tree(rest), item.into_leaf()),
         }
     }
 
-    pub fn prepend(mut self, item: A) -> Self {
-        self.data.push_front(item);
-        self
+    pub fn prepend(self, item: A) -> Self {
+        Self::from_tree(self.tree.push_front(Item::Leaf(item)))
     }
 
-    pub fn append(mut self, item: A) -> Self {
-        self.data.push_back(item);
-        self
+    pub fn append(self, item: A) -> Self {
+        Self::from_tree(self.tree.push_back(Item::Leaf(item)))
     }
 
     #[must_use]
-    pub fn concat(mut self, mut other: Self) -> Self {
-        self.data.append(&mut other.data);
-        self
+    pub fn concat(self, other: Self) -> Self {
+        Self::from_tree(Tree::append3(&self.tree, Vec::new(), &other.tree))
     }
 
     #[must_use]
     pub fn reverse(&self) -> Self {
-        Self {
-            data: self.data.iter().cloned().rev().collect(),
-            _marker: PhantomData,
-        }
+        let mut items: Vec<A> = self.iter().cloned().collect();
+        items.reverse();
+        Self::from_list(items)
     }
 
+    /// The combined measure of all elements, cached at the root.
     #[must_use]
     pub fn measure(&self) -> V {
-        self.data
-            .iter()
-            .fold(V::empty(), |acc, item| acc.combine(&item.measure()))
+        self.tree.measure()
     }
 
+    /// Locate the element at which `predicate(left, right)` flips from
+    /// `false` to `true`, following `Data.FingerTree.search`: `left` is the
+    /// measure of the elements up to and including the candidate and `right`
+    /// that of the elements after it.
+    ///
+    /// With `predicate` checked at the two ends of the tree,
+    /// `predicate(empty, measure)` and `predicate(measure, empty)`, the
+    /// result is
+    ///
+    /// - [`SearchResult::Position`] if it is `false` then `true`, with the
+    ///   elements before and after the flip element;
+    /// - [`SearchResult::OnLeft`] if it is `true` at both ends;
+    /// - [`SearchResult::OnRight`] if it is `false` at both ends;
+    /// - [`SearchResult::Nowhere`] if it is `true` then `false`, which a
+    ///   monotone predicate never gives.
+    ///
+    /// An empty tree is `OnLeft` or `OnRight`. The two trees of a `Position`
+    /// share structure with `self` and are built in O(log n); nothing else
+    /// is copied.
     pub fn search<P>(&self, mut predicate: P) -> SearchResult<V, A>
     where
         P: FnMut(&V, &V) -> bool,
     {
         let total = self.measure();
         let zero = V::empty();
-        if predicate(&zero, &total) {
-            return SearchResult::OnLeft;
-        }
-        if !predicate(&total, &zero) {
-            return SearchResult::OnRight;
-        }
-
-        let mut suffix_measures = Vec::with_capacity(self.data.len() + 1);
-        let mut acc_suffix = V::empty();
-        suffix_measures.push(acc_suffix.clone());
-        for item in self.data.iter().rev() {
-            acc_suffix = item.measure().combine(&acc_suffix);
-            suffix_measures.push(acc_suffix.clone());
-        }
-        suffix_measures.reverse();
-
-        let mut prefix_measure = V::empty();
-        let mut prefix_items: VecDeque<A> = VecDeque::new();
-
-        for (idx, item) in self.data.iter().cloned().enumerate() {
-            let item_measure = item.measure();
-            let new_prefix = prefix_measure.combine(&item_measure);
-            let suffix_measure = suffix_measures[idx + 1].clone();
-            if predicate(&new_prefix, &suffix_measure) {
-                let suffix_items: VecDeque<A> = self.data.iter().skip(idx + 1).cloned().collect();
-                return SearchResult::Position(
-                    Self {
-                        data: prefix_items.clone(),
-                        _marker: PhantomData,
-                    },
-                    item,
-                    Self {
-                        data: suffix_items,
-                        _marker: PhantomData,
-                    },
-                );
-            }
-            prefix_items.push_back(item);
-            prefix_measure = new_prefix;
+        match (predicate(&zero, &total), predicate(&total, &zero)) {
+            (true, true) => return SearchResult::OnLeft,
+            (false, false) => return SearchResult::OnRight,
+            (true, false) => return SearchResult::Nowhere,
+            (false, true) => {},
         }
 
-        SearchResult::Nowhere
+        let (left, item, right) = self.tree.search_split(&mut predicate, &zero, &zero);
+        SearchResult::Position(
+            Self::from_tree(left),
+            item.into_leaf(),
+            Self::from_tree(right),
+        )
     }
 
-    pub fn split<P>(&self, mut predicate: P) -> (Self, Self)
+    /// Split at the first element `x` for which `predicate` holds on the
+    /// measure of the elements before `x` combined with `x` itself.
+    ///
+    /// Returns `None` when the predicate never holds (including for an empty
+    /// tree).
+    fn split_point<P>(&self, mut predicate: P) -> Option<Split<V, A>>
     where
         P: FnMut(&V) -> bool,
-        A: Clone,
     {
-        let mut acc = V::empty();
-        let mut left: VecDeque<A> = VecDeque::new();
-
-        for (idx, item) in self.data.iter().cloned().enumerate() {
-            let new_acc = acc.combine(&item.measure());
-            left.push_back(item);
-            if predicate(&new_acc) {
-                let right: VecDeque<A> = self.data.iter().skip(idx + 1).cloned().collect();
-                return (
-                    Self {
-                        data: left,
-                        _marker: PhantomData,
-                    },
-                    Self {
-                        data: right,
-                        _marker: PhantomData,
-                    },
-                );
-            }
-            acc = new_acc;
+        if self.is_empty() || !predicate(&self.measure()) {
+            return None;
         }
-
-        (
-            Self {
-                data: left,
-                _marker: PhantomData,
-            },
-            Self {
-                data: VecDeque::new(),
-                _marker: PhantomData,
-            },
+        let zero = V::empty();
+        Some(
+            self.tree
+                .search_split(&mut |left: &V, _: &V| predicate(left), &zero, &zero),
         )
     }
 
+    /// `Data.FingerTree.split`: the longest prefix whose accumulated measure
+    /// does not satisfy `predicate`, and the rest, which starts with the
+    /// element at which it flips to `true`. The predicate is expected to be
+    /// monotone.
+    ///
+    /// An empty tree splits into two empty trees, and a predicate that
+    /// never holds gives `(self, empty)`. Same as
+    /// [`StrictFingerTree::split_off_prefix`]: both halves are built in
+    /// O(log n), share structure with `self` and cache their own measures.
+    pub fn split<P>(&self, predicate: P) -> (Self, Self)
+    where
+        P: FnMut(&V) -> bool,
+    {
+        self.split_off_prefix(predicate)
+    }
+
+    /// Split off the prefix of elements whose accumulated measure does not yet
+    /// satisfy `predicate`, following `Data.FingerTree.split`.
+    ///
+    /// Returns `(prefix, rest)` where `rest` starts with the first element `x`
+    /// such that `predicate(measure(prefix) <> measure(x))` holds. When the
+    /// predicate never holds the whole tree is returned as the prefix. The
+    /// predicate is expected to be monotone.
+    ///
+    /// The tree is left untouched; both halves share structure with it and
+    /// are produced in O(log n).
+    pub fn split_off_prefix<P>(&self, predicate: P) -> (Self, Self)
+    where
+        P: FnMut(&V) -> bool,
+    {
+        match self.split_point(predicate) {
+            None => (self.clone(), Self::empty()),
+            Some((left, item, right)) => (
+                Self::from_tree(left),
+                Self::from_tree(right.push_front(item)),
+            ),
+        }
+    }
+
+    /// Drop the prefix of elements whose accumulated measure does not yet
+    /// satisfy `predicate`, keeping everything from the element at which it
+    /// first holds.
+    ///
+    /// This is the second half of [`StrictFingerTree::split_off_prefix`]: the
+    /// tree is left untouched and the result is built in O(log n) without
+    /// visiting the dropped elements. When the predicate never holds the
+    /// result is empty.
+    #[must_use]
+    pub fn drop_prefix_by_measure<P>(&self, predicate: P) -> Self
+    where
+        P: FnMut(&V) -> bool,
+    {
+        match self.split_point(predicate) {
+            None => Self::empty(),
+            Some((_, item, right)) => Self::from_tree(right.push_front(item)),
+        }
+    }
+
+    /// `Data.FingerTree.takeUntil`, the first half of
+    /// [`StrictFingerTree::split`].
+    #[must_use]
     pub fn take_until<P>(&self, predicate: P) -> Self
     where
         P: FnMut(&V) -> bool,
-        A: Clone,
     {
         self.split(predicate).0
     }
 
+    /// `Data.FingerTree.dropUntil`, the second half of
+    /// [`StrictFingerTree::split`]; the dropped prefix is never visited.
+    #[must_use]
     pub fn drop_until<P>(&self, predicate: P) -> Self
     where
         P: FnMut(&V) -> bool,
-        A: Clone,
     {
-        self.split(predicate).1
+        self.drop_prefix_by_measure(predicate)
+    }
+
+    /// The element at position `index`, located in O(log n) from the
+    /// element counts cached in every node, like `Data.Sequence.lookup`.
+    #[must_use]
+    pub fn lookup(&self, index: usize) -> Option<&A> {
+        (index < self.len()).then(|| self.tree.leaf(index))
+    }
+
+    /// Split before position `index`, like `Data.Sequence.splitAt`. The first
+    /// half holds the first `index` elements (all of them when `index` is past
+    /// the end).
+    ///
+    /// Positions come from the cached element counts, so this works for any
+    /// measure and takes O(log n).
+    #[must_use]
+    pub fn split_at(&self, index: usize) -> (Self, Self) {
+        if index >= self.len() {
+            return (self.clone(), Self::empty());
+        }
+        let ((left, item, right), _) = self.tree.split_index(index);
+        (
+            Self::from_tree(left),
+            Self::from_tree(right.push_front(item)),
+        )
+    }
+
+    /// Insert `value` before position `index`; `index == len()` appends.
+    ///
+    /// Returns `None` when `index` is past the end. The tree is left
+    /// untouched and the result is built in O(log n), sharing everything
+    /// away from `index`.
+    #[must_use]
+    pub fn insert_at(&self, index: usize, value: A) -> Option<Self> {
+        match index.cmp(&self.len()) {
+            Ordering::Greater => None,
+            Ordering::Equal => Some(Self::from_tree(self.tree.push_back(Item::Leaf(value)))),
+            Ordering::Less => {
+                let ((left, item, right), _) = self.tree.split_index(index);
+                Some(Self::from_tree(Tree::append3(
+                    &left,
+                    vec![Item::Leaf(value), item],
+                    &right,
+                )))
+            },
+        }
+    }
+
+    /// Remove the element at `index`, returning it with the remaining tree,
+    /// or `None` when `index` is out of bounds. O(log n); the tree is left
+    /// untouched.
+    #[must_use]
+    pub fn remove_at(&self, index: usize) -> Option<(A, Self)> {
+        if index >= self.len() {
+            return None;
+        }
+        let ((left, item, right), _) = self.tree.split_index(index);
+        Some((
+            item.into_leaf(),
+            Self::from_tree(Tree::append3(&left, Vec::new(), &right)),
+        ))
+    }
+
+    /// Replace the element at `index` with `value`, like
+    /// `Data.Sequence.update`, or return `None` when `index` is out of bounds.
+    /// O(log n); the tree is left untouched.
+    #[must_use]
+    pub fn update_at(&self, index: usize, value: A) -> Option<Self> {
+        if index >= self.len() {
+            return None;
+        }
+        let ((left, _, right), _) = self.tree.split_index(index);
+        Some(Self::from_tree(Tree::append3(
+            &left,
+            vec![Item::Leaf(value)],
+            &right,
+        )))
+    }
+
+    /// Exchange the elements at `i` and `j`, or return `None` when either is
+    /// out of bounds. O(log n); the tree is left untouched.
+    #[must_use]
+    pub fn swap(&self, i: usize, j: usize) -> Option<Self> {
+        let first = self.lookup(i)?.clone();
+        let second = self.lookup(j)?.clone();
+        if i == j {
+            return Some(self.clone());
+        }
+        self.update_at(i, second)?.update_at(j, first)
+    }
+
+    /// A [`Cursor`] positioned before element `index`, or `None` when `index`
+    /// is past the end. `index == len()` places it after the last element.
+    #[must_use]
+    pub fn cursor(&self, index: usize) -> Option<Cursor<V, A>> {
+        let mut cursor = Cursor {
+            before: Tree::Empty,
+            after: self.tree.clone(),
+        };
+        cursor.seek(index).then_some(cursor)
     }
 
     pub fn fmap<B, V2, F>(&self, f: F) -> StrictFingerTree<V2, B>
@@ -363,27 +1127,134 @@ where
         B: Measured<V2>,
         F: FnMut(&A) -> B,
     {
-        StrictFingerTree {
-            data: self.data.iter().map(f).collect(),
-            _marker: PhantomData,
-        }
+        StrictFingerTree::from_list(self.iter().map(f))
     }
 
     pub fn unsafe_fmap<B, V2, F>(&self, f: F) -> StrictFingerTree<V2, B>
     where
         V2: Monoid + Clone,
-        B: Clone,
+        B: Measured<V2>,
         F: FnMut(&A) -> B,
     {
-        StrictFingerTree {
-            data: self.data.iter().map(f).collect(),
-            _marker: PhantomData,
-        }
+        StrictFingerTree::from_list(self.iter().map(f))
     }
 
     #[must_use]
-    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, A> {
-        self.data.iter()
+    pub fn iter(&self) -> Iter<'_, V, A> {
+        Iter {
+            front: vec![Frame::Tree(&self.tree)],
+            back: vec![Frame::Tree(&self.tree)],
+            remaining: self.len(),
+        }
+    }
+}
+
+/// Mutable access for trees without a measure. With a real measure, changing
+/// an element in place would leave the measures cached above it stale.
+impl<A: Measured<()>> StrictFingerTree<(), A> {
+    /// The element at position `index`, or `None` when out of bounds. Nodes
+    /// on the path that are shared with another tree are copied first:
+    /// O(log n).
+    pub(crate) fn lookup_mut(&mut self, index: usize) -> Option<&mut A> {
+        (index < self.len()).then(|| self.tree.leaf_mut(index))
+    }
+
+    /// Every element in order, after copying whatever nodes are shared with
+    /// another tree.
+    pub(crate) fn leaves_mut(&mut self) -> Vec<&mut A> {
+        let mut leaves = Vec::with_capacity(self.len());
+        self.tree.leaves_mut(&mut leaves);
+        leaves
+    }
+}
+
+enum Frame<'a, V, A> {
+    Tree(&'a Tree<V, A>),
+    Item(&'a Item<V, A>),
+}
+
+/// Borrowing in-order iterator over a [`StrictFingerTree`].
+///
+/// Each end walks the tree with its own stack; the shared count of remaining
+/// elements stops them once they meet.
+pub struct Iter<'a, V, A> {
+    front: Vec<Frame<'a, V, A>>,
+    back: Vec<Frame<'a, V, A>>,
+    remaining: usize,
+}
+
+impl<'a, V, A> Iterator for Iter<'a, V, A> {
+    type Item = &'a A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        while let Some(frame) = self.front.pop() {
+            match frame {
+                Frame::Tree(Tree::Empty) => {},
+                Frame::Tree(Tree::Single(item)) => self.front.push(Frame::Item(item)),
+                Frame::Tree(Tree::Deep(deep)) => {
+                    self.front.extend(deep.suffix.iter().rev().map(Frame::Item));
+                    self.front.push(Frame::Tree(&deep.middle));
+                    self.front.extend(deep.prefix.iter().rev().map(Frame::Item));
+                },
+                Frame::Item(Item::Node(node)) => {
+                    self.front.extend(node.items.iter().rev().map(Frame::Item));
+                },
+                Frame::Item(Item::Leaf(value)) => {
+                    self.remaining -= 1;
+                    return Some(value);
+                },
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<V, A> DoubleEndedIterator for Iter<'_, V, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        while let Some(frame) = self.back.pop() {
+            match frame {
+                Frame::Tree(Tree::Empty) => {},
+                Frame::Tree(Tree::Single(item)) => self.back.push(Frame::Item(item)),
+                Frame::Tree(Tree::Deep(deep)) => {
+                    self.back.extend(deep.prefix.iter().map(Frame::Item));
+                    self.back.push(Frame::Tree(&deep.middle));
+                    self.back.extend(deep.suffix.iter().map(Frame::Item));
+                },
+                Frame::Item(Item::Node(node)) => {
+                    self.back.extend(node.items.iter().map(Frame::Item));
+                },
+                Frame::Item(Item::Leaf(value)) => {
+                    self.remaining -= 1;
+                    return Some(value);
+                },
+            }
+        }
+        None
+    }
+}
+
+impl<V, A> ExactSizeIterator for Iter<'_, V, A> {}
+
+impl<'a, V, A> IntoIterator for &'a StrictFingerTree<V, A>
+where
+    V: Monoid + Clone,
+    A: Measured<V>,
+{
+    type Item = &'a A;
+    type IntoIter = Iter<'a, V, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
@@ -406,7 +1277,129 @@ where
     type IntoIter = std::collections::vec_deque::IntoIter<A>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.data.into_iter()
+        self.into_inner().into_iter()
+    }
+}
+
+/// A position between two elements of a [`StrictFingerTree`], for runs of
+/// edits close to one another.
+///
+/// The cursor holds the elements before and after its position as two
+/// separate trees. Stepping with [`move_next`](Self::move_next) and
+/// [`move_prev`](Self::move_prev) and editing at the cursor only touch the
+/// facing ends of those trees, which is amortised O(1); [`seek`](Self::seek)
+/// and [`into_tree`](Self::into_tree) rejoin the halves in O(log n).
+pub struct Cursor<V, A> {
+    before: Tree<V, A>,
+    after: Tree<V, A>,
+}
+
+impl<V, A: Clone> Clone for Cursor<V, A> {
+    fn clone(&self) -> Self {
+        Self {
+            before: self.before.clone(),
+            after: self.after.clone(),
+        }
+    }
+}
+
+impl<V, A> Cursor<V, A>
+where
+    V: Monoid + Clone,
+    A: Measured<V>,
+{
+    /// Number of elements before the cursor.
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.before.size()
+    }
+
+    /// Number of elements on both sides of the cursor.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.before.size() + self.after.size()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Move the cursor before element `index`. Returns `false`, leaving the
+    /// cursor where it was, when `index` is past the end.
+    pub fn seek(&mut self, index: usize) -> bool {
+        if index > self.len() {
+            return false;
+        }
+        if index != self.position() {
+            let joined = Tree::append3(&self.before, Vec::new(), &self.after);
+            if index == joined.size() {
+                self.before = joined;
+                self.after = Tree::Empty;
+            } else {
+                let ((left, item, right), _) = joined.split_index(index);
+                self.before = left;
+                self.after = right.push_front(item);
+            }
+        }
+        true
+    }
+
+    /// The element right after the cursor, if any.
+    #[must_use]
+    pub fn current(&self) -> Option<&A> {
+        (self.after.size() > 0).then(|| self.after.leaf(0))
+    }
+
+    /// Step over the current element. Returns `false` at the end.
+    pub fn move_next(&mut self) -> bool {
+        match self.after.view_front() {
+            None => false,
+            Some((item, rest)) => {
+                self.before = self.before.push_back(item);
+                self.after = rest;
+                true
+            },
+        }
+    }
+
+    /// Step back over the element before the cursor. Returns `false` at the
+    /// start.
+    pub fn move_prev(&mut self) -> bool {
+        match self.before.view_back() {
+            None => false,
+            Some((rest, item)) => {
+                self.after = self.after.push_front(item);
+                self.before = rest;
+                true
+            },
+        }
+    }
+
+    /// Replace the current element with `value`, returning the old one, or
+    /// `None` (dropping `value`) at the end.
+    pub fn replace(&mut self, value: A) -> Option<A> {
+        let (item, rest) = self.after.view_front()?;
+        self.after = rest.push_front(Item::Leaf(value));
+        Some(item.into_leaf())
+    }
+
+    /// Insert `value` at the cursor; it becomes the current element.
+    pub fn insert(&mut self, value: A) {
+        self.after = self.after.push_front(Item::Leaf(value));
+    }
+
+    /// Remove and return the current element, or `None` at the end.
+    pub fn remove(&mut self) -> Option<A> {
+        let (item, rest) = self.after.view_front()?;
+        self.after = rest;
+        Some(item.into_leaf())
+    }
+
+    /// Join both sides back into a single tree.
+    #[must_use]
+    pub fn into_tree(self) -> StrictFingerTree<V, A> {
+        StrictFingerTree::from_tree(Tree::append3(&self.before, Vec::new(), &self.after))
     }
 }
 
@@ -416,13 +1409,15 @@ where
     A: Measured<V> + fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_list().entries(&self.data).finish()
+        f.debug_list().entries(self.iter()).finish()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+    use std::cell::Cell;
 
     #[derive(Clone, Debug, PartialEq, Eq)]
     struct Counted(u64);
@@ -475,11 +1470,11 @@ mod tests {
         let tree = StrictFingerTree::<u64, _>::from_list([Counted(1), Counted(2), Counted(3)]);
 
         let (left, right) = tree.split(|m| *m >= 3);
+        assert_eq!(left.into_iter().collect::<Vec<_>>(), vec![Counted(1)]);
         assert_eq!(
-            left.into_iter().collect::<Vec<_>>(),
-            vec![Counted(1), Counted(2)]
+            right.into_iter().collect::<Vec<_>>(),
+            vec![Counted(2), Counted(3)]
         );
-        assert_eq!(right.into_iter().collect::<Vec<_>>(), vec![Counted(3)]);
 
         let search = tree.search(|l, r| *l >= 3 || *r <= 3);
         match search {
@@ -503,18 +1498,105 @@ mod tests {
         assert!(matches!(on_right, SearchResult::OnRight));
     }
 
+    #[test]
+    fn search_finds_where_the_running_total_exceeds_a_threshold() {
+        let values: Vec<u64> = (0..1_000).map(|i| i % 7 + 1).collect();
+        let tree = counted(&values);
+        let total = tree.measure();
+
+        for threshold in [0, 1, 2, 500, 2_000, total - 1] {
+            let SearchResult::Position(before, focus, after) =
+                tree.search(|left, _| *left > threshold)
+            else {
+                panic!("threshold {threshold} is below the total");
+            };
+            let index = reference_cut(&values, threshold + 1);
+            assert_eq!(before.len(), index);
+            assert_eq!(focus, Counted(values[index]));
+            assert_eq!(after.len(), values.len() - index - 1);
+            assert!(before.measure() <= threshold);
+            assert!(before.measure() + focus.0 > threshold);
+            assert_eq!(before.measure() + focus.0 + after.measure(), total);
+        }
+
+        // Steering by the right-hand measure finds the same element.
+        let by_right = tree.search(|_, right| *right < total - 500);
+        let by_left = tree.search(|left, _| *left > 500);
+        assert_eq!(by_right, by_left);
+
+        assert!(matches!(
+            tree.search(|left, _| *left > total),
+            SearchResult::OnRight
+        ));
+    }
+
+    #[test]
+    fn search_is_nowhere_when_the_predicate_flips_backwards() {
+        let tree = counted(&[1, 2, 3]);
+        // True at the left end and false at the right: not monotone.
+        let result = tree.search(|left, _| *left < 3);
+        assert!(matches!(result, SearchResult::Nowhere));
+        assert!(matches!(
+            tree.search(|_, right| *right > 0),
+            SearchResult::Nowhere
+        ));
+
+        // An empty tree only sees `empty` on both sides.
+        let empty = counted(&[]);
+        assert!(matches!(empty.search(|_, _| true), SearchResult::OnLeft));
+        assert!(matches!(
+            empty.search(|left, _| *left > 0),
+            SearchResult::OnRight
+        ));
+    }
+
     #[test]
     fn take_drop_until() {
         let tree = StrictFingerTree::<u64, _>::from_list([Counted(1), Counted(2), Counted(3)]);
 
         let taken = tree.take_until(|m| *m >= 3);
+        assert_eq!(taken.into_iter().collect::<Vec<_>>(), vec![Counted(1)]);
+
+        let dropped = tree.drop_until(|m| *m >= 3);
         assert_eq!(
-            taken.into_iter().collect::<Vec<_>>(),
-            vec![Counted(1), Counted(2)]
+            dropped.into_iter().collect::<Vec<_>>(),
+            vec![Counted(2), Counted(3)]
         );
+    }
 
-        let dropped = tree.drop_until(|m| *m >= 3);
-        assert_eq!(dropped.into_iter().collect::<Vec<_>>(), vec![Counted(3)]);
+    #[test]
+    fn split_degenerate_cases() {
+        let empty = counted(&[]);
+        let (left, right) = empty.split(|_| true);
+        assert!(left.is_empty() && right.is_empty());
+
+        let tree = counted(&[4, 5, 6]);
+        let (all, none) = tree.split(|m| *m > 100);
+        assert_eq!(all, tree);
+        assert!(none.is_empty());
+        assert!(tree.drop_until(|m| *m > 100).is_empty());
+
+        // Holding already for the empty prefix puts everything on the right.
+        let (none, all) = tree.split(|_| true);
+        assert!(none.is_empty());
+        assert_eq!(all, tree);
+    }
+
+    #[test]
+    fn split_fills_a_byte_budget() {
+        // Transaction sizes, split at a 1 000-byte block budget as the
+        // mempool does.
+        let sizes = [300, 250, 400, 100, 50];
+        let tree = counted(&sizes);
+        let (fits, rest) = tree.split(|bytes| *bytes > 1_000);
+        assert_eq!(
+            fits.iter().map(|tx| tx.0).collect::<Vec<_>>(),
+            [300, 250, 400]
+        );
+        assert_eq!(fits.measure(), 950);
+        assert_eq!(rest.iter().map(|tx| tx.0).collect::<Vec<_>>(), [100, 50]);
+        assert_eq!(tree.take_until(|bytes| *bytes > 1_000), fits);
+        assert_eq!(tree.drop_until(|bytes| *bytes > 1_000), rest);
     }
 
     #[test]
@@ -534,4 +1616,370 @@ mod tests {
         let combined = bin_measure::<u64, _, _>(&left, &right);
         assert_eq!(combined, 5);
     }
+
+    #[test]
+    fn split_off_prefix_keeps_flip_element_on_the_right() {
+        let tree = StrictFingerTree::<u64, _>::from_list([Counted(1), Counted(2), Counted(3)]);
+
+        let (prefix, rest) = tree.split_off_prefix(|m| *m >= 3);
+        assert_eq!(prefix.into_iter().collect::<Vec<_>>(), vec![Counted(1)]);
+        assert_eq!(
+            rest.into_iter().collect::<Vec<_>>(),
+            vec![Counted(2), Counted(3)]
+        );
+
+        let (all, none) = tree.split_off_prefix(|m| *m > 100);
+        assert_eq!(all, tree);
+        assert!(none.is_empty());
+
+        assert_eq!(tree.drop_prefix_by_measure(|m| *m >= 3).len(), 2);
+        assert!(tree.drop_prefix_by_measure(|m| *m > 100).is_empty());
+        assert_eq!(tree.len(), 3, "original tree is left untouched");
+    }
+
+    thread_local! {
+        static COMBINES: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Additive measure that counts how often it is combined.
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Tally(u64);
+
+    impl Semigroup for Tally {
+        fn combine(&self, other: &Self) -> Self {
+            COMBINES.with(|count| count.set(count.get() + 1));
+            Tally(self.0 + other.0)
+        }
+    }
+
+    impl Monoid for Tally {
+        fn empty() -> Self {
+            Tally(0)
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Slot(u64);
+
+    impl Measured<Tally> for Slot {
+        fn measure(&self) -> Tally {
+            Tally(1)
+        }
+    }
+
+    #[test]
+    fn drop_prefix_by_measure_touches_only_the_spine() {
+        const SIZE: u64 = 100_000;
+        let tree = StrictFingerTree::<Tally, _>::from_list((0..SIZE).map(Slot));
+        let log_n = (SIZE as f64).log2().ceil() as usize;
+
+        for cut in [1, 17, SIZE / 3, SIZE / 2, SIZE - 5] {
+            COMBINES.with(|count| count.set(0));
+            let rest = tree.drop_prefix_by_measure(|m| m.0 > cut);
+            let combines = COMBINES.with(Cell::get);
+
+            assert_eq!(rest.len() as u64, SIZE - cut);
+            assert_eq!(rest.iter().next(), Some(&Slot(cut)));
+            assert!(
+                combines <= 20 * log_n,
+                "dropping {cut} elements took {combines} measure combinations"
+            );
+        }
+    }
+
+    #[test]
+    fn positional_edits_touch_only_the_spine() {
+        const SIZE: u64 = 100_000;
+        let tree = StrictFingerTree::<Tally, _>::from_list((0..SIZE).map(Slot));
+        let log_n = (SIZE as f64).log2().ceil() as usize;
+
+        for index in [0, 1, 17, SIZE / 3, SIZE / 2, SIZE - 1] {
+            let index = index as usize;
+            COMBINES.with(|count| count.set(0));
+            let inserted = tree.insert_at(index, Slot(SIZE)).expect("index in bounds");
+            let (removed, rest) = inserted.remove_at(index).expect("index in bounds");
+            let updated = rest
+                .update_at(index, Slot(SIZE + 1))
+                .expect("index in bounds");
+            let combines = COMBINES.with(Cell::get);
+
+            assert_eq!(removed, Slot(SIZE));
+            assert_eq!(rest.measure(), tree.measure());
+            assert_eq!(updated.lookup(index), Some(&Slot(SIZE + 1)));
+            assert_eq!(updated.len() as u64, SIZE);
+            assert!(
+                combines <= 60 * log_n,
+                "editing at {index} took {combines} measure combinations"
+            );
+        }
+    }
+
+    #[test]
+    fn cursor_walks_and_edits_in_place() {
+        let tree = counted(&[1, 2, 3, 4]);
+        assert!(tree.cursor(5).is_none());
+
+        let mut cursor = tree.cursor(1).expect("position in bounds");
+        assert_eq!(cursor.current(), Some(&Counted(2)));
+        assert_eq!(cursor.replace(Counted(20)), Some(Counted(2)));
+        assert!(cursor.move_next());
+        cursor.insert(Counted(30));
+        assert_eq!(cursor.position(), 2);
+        assert!(cursor.move_prev() && cursor.move_prev());
+        assert!(!cursor.move_prev());
+        assert_eq!(cursor.remove(), Some(Counted(1)));
+
+        assert!(cursor.seek(cursor.len()));
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.replace(Counted(0)), None);
+        assert!(!cursor.move_next());
+        assert!(!cursor.seek(cursor.len() + 1));
+
+        let edited = cursor.into_tree();
+        assert_eq!(edited, counted(&[20, 30, 3, 4]));
+        assert_eq!(edited.measure(), 57);
+        assert_eq!(tree, counted(&[1, 2, 3, 4]));
+    }
+
+    /// A positional edit for the reference-model property tests. Indices are
+    /// taken modulo the current length (plus one where appending is allowed)
+    /// so most edits land in bounds; `Lookup` probes out of bounds too.
+    #[derive(Clone, Debug)]
+    enum Edit {
+        Insert(usize, u64),
+        Remove(usize),
+        Update(usize, u64),
+        Swap(usize, usize),
+        Lookup(usize),
+    }
+
+    fn edit() -> impl Strategy<Value = Edit> {
+        prop_oneof![
+            (0usize..512, 0u64..20).prop_map(|(i, v)| Edit::Insert(i, v)),
+            (0usize..512).prop_map(Edit::Remove),
+            (0usize..512, 0u64..20).prop_map(|(i, v)| Edit::Update(i, v)),
+            (0usize..512, 0usize..512).prop_map(|(i, j)| Edit::Swap(i, j)),
+            (0usize..600).prop_map(Edit::Lookup),
+        ]
+    }
+
+    /// A cursor step or edit for the cursor property test.
+    #[derive(Clone, Debug)]
+    enum Step {
+        Seek(usize),
+        Next,
+        Prev,
+        Replace(u64),
+        Insert(u64),
+        Remove,
+    }
+
+    fn step() -> impl Strategy<Value = Step> {
+        prop_oneof![
+            (0usize..300).prop_map(Step::Seek),
+            Just(Step::Next),
+            Just(Step::Prev),
+            (0u64..20).prop_map(Step::Replace),
+            (0u64..20).prop_map(Step::Insert),
+            Just(Step::Remove),
+        ]
+    }
+
+    fn counted(values: &[u64]) -> StrictFingerTree<u64, Counted> {
+        StrictFingerTree::from_list(values.iter().copied().map(Counted))
+    }
+
+    /// Number of leading elements whose running sum stays below `limit`.
+    fn reference_cut(values: &[u64], limit: u64) -> usize {
+        let mut acc = 0;
+        values
+            .iter()
+            .take_while(|value| {
+                acc += **value;
+                acc < limit
+            })
+            .count()
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_iter_from_both_ends_matches_vec(values in proptest::collection::vec(0u64..20, 0..200), ends in proptest::collection::vec(any::<bool>(), 0..220)) {
+            let tree = counted(&values);
+            let mut iter = tree.iter();
+            let mut model = values.iter();
+            for from_back in ends {
+                let (got, expected) = if from_back {
+                    (iter.next_back(), model.next_back())
+                } else {
+                    (iter.next(), model.next())
+                };
+                prop_assert_eq!(got.map(|c| c.0), expected.copied());
+                prop_assert_eq!(iter.len(), model.len());
+            }
+        }
+
+        #[test]
+        fn proptest_split_preserves_measures(values in proptest::collection::vec(0u64..20, 1_000..4_000), limit in 0u64..50_000) {
+            let tree = counted(&values);
+            let cut = reference_cut(&values, limit);
+            let (left, right) = tree.split(|m| *m >= limit);
+
+            prop_assert_eq!(left.measure().combine(&right.measure()), tree.measure());
+            prop_assert_eq!(left.measure(), values[..cut].iter().sum::<u64>());
+            prop_assert_eq!((left.len(), right.len()), (cut, values.len() - cut));
+            prop_assert_eq!(left.clone().concat(right.clone()), tree.clone());
+            prop_assert_eq!(tree.take_until(|m| *m >= limit), left);
+            prop_assert_eq!(tree.drop_until(|m| *m >= limit), right);
+        }
+
+        #[test]
+        fn proptest_split_off_prefix_matches_vec(values in proptest::collection::vec(0u64..20, 0..200), limit in 0u64..2_000) {
+            let tree = counted(&values);
+            let cut = reference_cut(&values, limit);
+            let (prefix, rest) = tree.split_off_prefix(|m| *m >= limit);
+
+            let prefix: Vec<u64> = prefix.iter().map(|c| c.0).collect();
+            let rest: Vec<u64> = rest.iter().map(|c| c.0).collect();
+            prop_assert_eq!(prefix, values[..cut].to_vec());
+            prop_assert_eq!(rest, values[cut..].to_vec());
+        }
+
+        #[test]
+        fn proptest_drop_prefix_by_measure_matches_vec(values in proptest::collection::vec(0u64..20, 0..200), limit in 0u64..2_000) {
+            let tree = counted(&values);
+            let cut = reference_cut(&values, limit);
+            let rest = tree.drop_prefix_by_measure(|m| *m >= limit);
+
+            prop_assert_eq!(rest.measure(), values[cut..].iter().sum::<u64>());
+            prop_assert_eq!(rest.iter().map(|c| c.0).collect::<Vec<_>>(), values[cut..].to_vec());
+            prop_assert_eq!(tree.iter().map(|c| c.0).collect::<Vec<_>>(), values);
+        }
+
+        #[test]
+        fn proptest_positional_edits_match_vec(values in proptest::collection::vec(0u64..20, 0..200), edits in proptest::collection::vec(edit(), 0..64)) {
+            let mut tree = counted(&values);
+            let mut model = values;
+            for edit in edits {
+                let before = tree.clone();
+                match edit {
+                    Edit::Insert(i, v) => {
+                        prop_assert!(tree.insert_at(model.len() + 1, Counted(v)).is_none());
+                        let i = i % (model.len() + 1);
+                        tree = tree.insert_at(i, Counted(v)).expect("index in bounds");
+                        model.insert(i, v);
+                    },
+                    Edit::Remove(i) => {
+                        prop_assert!(tree.remove_at(model.len()).is_none());
+                        if !model.is_empty() {
+                            let i = i % model.len();
+                            let (removed, rest) = tree.remove_at(i).expect("index in bounds");
+                            prop_assert_eq!(removed.0, model.remove(i));
+                            tree = rest;
+                        }
+                    },
+                    Edit::Update(i, v) => {
+                        prop_assert!(tree.update_at(model.len(), Counted(v)).is_none());
+                        if !model.is_empty() {
+                            let i = i % model.len();
+                            tree = tree.update_at(i, Counted(v)).expect("index in bounds");
+                            model[i] = v;
+                        }
+                    },
+                    Edit::Swap(i, j) => {
+                        prop_assert!(tree.swap(i, model.len()).is_none());
+                        if !model.is_empty() {
+                            let (i, j) = (i % model.len(), j % model.len());
+                            tree = tree.swap(i, j).expect("indices in bounds");
+                            model.swap(i, j);
+                        }
+                    },
+                    Edit::Lookup(i) => {
+                        prop_assert_eq!(tree.lookup(i).map(|c| c.0), model.get(i).copied());
+                        let (left, right) = tree.split_at(i);
+                        let cut = i.min(model.len());
+                        prop_assert_eq!(left.iter().map(|c| c.0).collect::<Vec<_>>(), model[..cut].to_vec());
+                        prop_assert_eq!(right.iter().map(|c| c.0).collect::<Vec<_>>(), model[cut..].to_vec());
+                    },
+                }
+                // Persistent edits never disturb the version they started from.
+                prop_assert_eq!(before.len(), before.iter().count());
+                prop_assert_eq!(tree.len(), model.len());
+                prop_assert_eq!(tree.measure(), model.iter().sum::<u64>());
+                prop_assert_eq!(tree.iter().map(|c| c.0).collect::<Vec<_>>(), model.clone());
+            }
+        }
+
+        #[test]
+        fn proptest_cursor_matches_vec(values in proptest::collection::vec(0u64..20, 0..200), start in 0usize..256, steps in proptest::collection::vec(step(), 0..64)) {
+            let tree = counted(&values);
+            let mut model = values.clone();
+            let mut position = start.min(model.len());
+            let mut cursor = tree.cursor(position).expect("position in bounds");
+            for step in steps {
+                match step {
+                    Step::Seek(i) => {
+                        let moved = cursor.seek(i);
+                        prop_assert_eq!(moved, i <= model.len());
+                        if moved {
+                            position = i;
+                        }
+                    },
+                    Step::Next => {
+                        prop_assert_eq!(cursor.move_next(), position < model.len());
+                        position = (position + 1).min(model.len());
+                    },
+                    Step::Prev => {
+                        prop_assert_eq!(cursor.move_prev(), position > 0);
+                        position = position.saturating_sub(1);
+                    },
+                    Step::Replace(v) => {
+                        let old = cursor.replace(Counted(v)).map(|c| c.0);
+                        let expected = model.get_mut(position).map(|slot| std::mem::replace(slot, v));
+                        prop_assert_eq!(old, expected);
+                    },
+                    Step::Insert(v) => {
+                        cursor.insert(Counted(v));
+                        model.insert(position, v);
+                    },
+                    Step::Remove => {
+                        let removed = cursor.remove().map(|c| c.0);
+                        let expected = (position < model.len()).then(|| model.remove(position));
+                        prop_assert_eq!(removed, expected);
+                    },
+                }
+                prop_assert_eq!(cursor.position(), position);
+                prop_assert_eq!(cursor.len(), model.len());
+                prop_assert_eq!(cursor.current().map(|c| c.0), model.get(position).copied());
+            }
+            let edited = cursor.into_tree();
+            prop_assert_eq!(edited.measure(), model.iter().sum::<u64>());
+            prop_assert_eq!(edited.iter().map(|c| c.0).collect::<Vec<_>>(), model);
+            prop_assert_eq!(tree.iter().map(|c| c.0).collect::<Vec<_>>(), values);
+        }
+
+        #[test]
+        fn proptest_structure_matches_vec(front in proptest::collection::vec(0u64..20, 0..100), back in proptest::collection::vec(0u64..20, 0..100)) {
+            let mut tree = counted(&back);
+            for value in front.iter().rev() {
+                tree = tree.prepend(Counted(*value));
+            }
+            let tree = tree.concat(counted(&back));
+            let expected: Vec<u64> = front.iter().chain(&back).chain(&back).copied().collect();
+
+            prop_assert_eq!(tree.len(), expected.len());
+            prop_assert_eq!(tree.measure(), expected.iter().sum::<u64>());
+            prop_assert_eq!(tree.iter().map(|c| c.0).collect::<Vec<_>>(), expected.clone());
+            let reversed: Vec<u64> = tree.reverse().iter().map(|c| c.0).collect();
+            prop_assert_eq!(reversed, expected.iter().rev().copied().collect::<Vec<_>>());
+
+            let mut drained = Vec::new();
+            let mut cursor = tree;
+            while let ViewR::Cons(rest, last) = cursor.viewr() {
+                drained.push(last.0);
+                cursor = rest;
+            }
+            drained.reverse();
+            prop_assert_eq!(drained, expected);
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use crate::strict_finger_tree::{Monoid as TreeMonoid, Semigroup as TreeSemigroup};
 use core::fmt;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Strict analogue of `Option` where the inner value is eagerly evaluated.
@@ -161,6 +162,7 @@ impl<T: fmt::Debug> fmt::Debug for StrictMaybe<T> {
     }
 }
 
+#[cfg(feature = "serde")]
 impl<T: Serialize> Serialize for StrictMaybe<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -173,6 +175,7 @@ impl<T: Serialize> Serialize for StrictMaybe<T> {
     }
 }
 
+#[cfg(feature = "serde")]
 impl<'de, T: Deserialize<'de>> Deserialize<'de> for StrictMaybe<T> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -182,6 +185,48 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for StrictMaybe<T> {
     }
 }
 
+/// Checks the `SJust` payload with the caller's context, exactly as for
+/// `Option`.
+#[cfg(feature = "nothunks")]
+impl<T: nothunks::NoThunks> nothunks::NoThunks for StrictMaybe<T> {
+    fn no_thunks(&self, context: &[&str]) -> nothunks::NoThunksResult {
+        Option::<&T>::from(self.as_ref()).no_thunks(context)
+    }
+}
+
+#[cfg(feature = "deepseq")]
+impl<T: deepseq::NFData> deepseq::NFData for StrictMaybe<T> {
+    fn rnf(&self) {
+        if let StrictMaybe::SJust(value) = self {
+            value.rnf();
+        }
+    }
+}
+
+#[cfg(feature = "deepseq")]
+impl<T> deepseq::NFData1<T> for StrictMaybe<T> {
+    fn lift_rnf<F>(&self, f: &mut F)
+    where
+        F: FnMut(&T),
+    {
+        if let StrictMaybe::SJust(value) = self {
+            f(value);
+        }
+    }
+}
+
+/// `heapWords SNothing = 0` and `heapWords (SJust a) = heapWords1 a`, as for
+/// `Maybe`.
+#[cfg(feature = "heapwords")]
+impl<T: heapwords::HeapWords> heapwords::HeapWords for StrictMaybe<T> {
+    fn heap_words(&self) -> usize {
+        match self {
+            StrictMaybe::SNothing => heapwords::heap_words0(),
+            StrictMaybe::SJust(value) => heapwords::heap_words1(value),
+        }
+    }
+}
+
 impl<T> TreeSemigroup for StrictMaybe<T>
 where
     T: TreeSemigroup + Clone,
@@ -274,4 +319,57 @@ mod tests {
         let right_identity = TreeSemigroup::combine(&left, &TreeMonoid::empty());
         assert_eq!(right_identity, left);
     }
+
+    #[cfg(feature = "nothunks")]
+    #[test]
+    fn no_thunks_matches_option() {
+        use nothunks::{NoThunks, NoThunksResult, ThunkInfo, unsafe_no_thunks};
+
+        struct Lazy;
+
+        impl NoThunks for Lazy {
+            fn no_thunks(&self, _context: &[&str]) -> NoThunksResult {
+                Err(ThunkInfo {
+                    path: vec!["Lazy".to_owned()],
+                    message: None,
+                })
+            }
+        }
+
+        assert!(unsafe_no_thunks(&StrictMaybe::SJust(vec![1u8])).is_none());
+        assert!(unsafe_no_thunks(&StrictMaybe::<Lazy>::SNothing).is_none());
+
+        let context = ["Header", "body"];
+        let err = StrictMaybe::SJust(Lazy)
+            .no_thunks(&context)
+            .expect_err("thunk");
+        assert_eq!(err, Some(Lazy).no_thunks(&context).expect_err("thunk"));
+        assert_eq!(err.path, ["Header", "body", "Lazy"]);
+    }
+
+    #[cfg(feature = "deepseq")]
+    #[test]
+    fn nfdata_forces_the_payload() {
+        use deepseq::{NFData1, force};
+
+        assert_eq!(
+            force(StrictMaybe::SJust(vec![1u8])),
+            StrictMaybe::SJust(vec![1u8])
+        );
+        let mut seen = Vec::new();
+        StrictMaybe::SJust(3u8).lift_rnf(&mut |value| seen.push(*value));
+        StrictMaybe::<u8>::SNothing.lift_rnf(&mut |value| seen.push(*value));
+        assert_eq!(seen, [3]);
+    }
+
+    #[cfg(feature = "heapwords")]
+    #[test]
+    fn heap_words_match_option() {
+        use heapwords::HeapWords;
+
+        let just = StrictMaybe::SJust(vec![1u64, 2]);
+        assert_eq!(just.heap_words(), Some(vec![1u64, 2]).heap_words());
+        assert_eq!(StrictMaybe::<Vec<u64>>::SNothing.heap_words(), 0);
+        assert!(StrictMaybe::<u64>::SNothing.heap_words() < StrictMaybe::SJust(0u64).heap_words());
+    }
 }
@@ -1,33 +1,128 @@
+use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
+use std::mem;
 use std::ops::{Index, IndexMut};
+use std::sync::{Arc, OnceLock};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::strict_finger_tree::{self, Measured, StrictFingerTree, ViewL, ViewR};
+
+/// Strict counterpart of `Data.Sequence.Seq`, backed by a
+/// [`StrictFingerTree`] as `Seq` is backed by a finger tree.
+///
+/// Versions of a sequence share structure: cloning is O(1); positional
+/// edits (`insert_at`, `remove_at`, `update`, `swap`), `split_at` and
+/// `concat` are O(log n); and access to either end is amortised O(1). Each
+/// element sits behind an `Arc`, so none of these copy an element. They
+/// still require `T: Clone`, as they did when the sequence was a `VecDeque`:
+/// the first of them to share an element records `T::clone`, and an element
+/// is cloned only when it is taken out of, or mutated in, a version that
+/// still shares it.
+///
+/// Methods that consume the sequence or hand out owned or mutable elements
+/// (`into_iter`, `pop_front`, `get_mut`, `iter_mut`, `into_split_at`, ...)
+/// need no `T: Clone`, so a sequence of non-`Clone` elements keeps its move
+/// semantics.
+///
+/// With the `serde` feature it serialises as a plain sequence, exactly like
+/// `Vec<T>`: a definite-length CBOR list through `cardano-binary`, matching
+/// Haskell's `ToCBOR (StrictSeq a)`, and a JSON array.
+pub struct StrictSeq<T> {
+    tree: StrictFingerTree<(), Elem<T>>,
+    /// `T::clone`, set by the first operation that shares elements with
+    /// another version. Unset, no element is shared.
+    clone_elem: OnceLock<CloneElem<T>>,
+}
 
-use serde::{Deserialize, Serialize};
+/// Copies an element still shared with another version of a sequence.
+type CloneElem<T> = fn(&T) -> T;
 
-/// Strict counterpart of `Data.Sequence.Seq` backed by a `VecDeque`.
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
-pub struct StrictSeq<T> {
-    data: VecDeque<T>,
+/// The recorded `T::clone`, which exists whenever an element is shared.
+fn shared_clone<T>(clone_elem: Option<CloneElem<T>>) -> CloneElem<T> {
+    clone_elem.expect("StrictSeq elements are only shared once T::clone is recorded")
+}
+
+/// An element of a [`StrictSeq`], shared by every version that contains it.
+struct Elem<T>(Arc<T>);
+
+impl<T> Elem<T> {
+    fn new(value: T) -> Self {
+        Elem(Arc::new(value))
+    }
+
+    /// The element itself, cloned only if another version still shares it.
+    fn into_value(self, clone_elem: Option<CloneElem<T>>) -> T {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| shared_clone(clone_elem)(&shared))
+    }
+
+    /// Mutable access, cloning the element first if it is shared.
+    fn make_mut(&mut self, clone_elem: Option<CloneElem<T>>) -> &mut T {
+        if Arc::get_mut(&mut self.0).is_none() {
+            self.0 = Arc::new(shared_clone(clone_elem)(&self.0));
+        }
+        Arc::get_mut(&mut self.0).expect("a freshly allocated element is unique")
+    }
+}
+
+impl<T> Clone for Elem<T> {
+    fn clone(&self) -> Self {
+        Elem(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Measured<()> for Elem<T> {
+    fn measure(&self) {}
 }
 
 impl<T> StrictSeq<T> {
-    #[must_use]
-    pub fn empty() -> Self {
+    fn from_tree(tree: StrictFingerTree<(), Elem<T>>) -> Self {
+        Self::with_clone_elem(tree, None)
+    }
+
+    fn with_clone_elem(
+        tree: StrictFingerTree<(), Elem<T>>,
+        clone_elem: Option<CloneElem<T>>,
+    ) -> Self {
         Self {
-            data: VecDeque::new(),
+            tree,
+            clone_elem: clone_elem.map_or_else(OnceLock::new, OnceLock::from),
         }
     }
 
+    /// Record `T::clone` before `self` shares its elements with another
+    /// version, and return it for that version.
+    fn share(&self) -> CloneElem<T>
+    where
+        T: Clone,
+    {
+        *self.clone_elem.get_or_init(|| T::clone)
+    }
+
+    /// A version built from `tree`, which shares elements with `self`.
+    fn derive(&self, tree: StrictFingerTree<(), Elem<T>>) -> Self
+    where
+        T: Clone,
+    {
+        Self::with_clone_elem(tree, Some(self.share()))
+    }
+
+    #[must_use]
+    pub fn empty() -> Self {
+        Self::from_tree(StrictFingerTree::empty())
+    }
+
     pub fn singleton(item: T) -> Self {
-        let mut data = VecDeque::new();
-        data.push_back(item);
-        Self { data }
+        Self::from_tree(StrictFingerTree::singleton(Elem::new(item)))
     }
 
     #[must_use]
     pub fn len(&self) -> usize {
-        self.data.len()
+        self.tree.len()
     }
 
     #[must_use]
@@ -37,7 +132,7 @@ impl<T> StrictSeq<T> {
 
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.data.is_empty()
+        self.tree.is_empty()
     }
 
     #[must_use]
@@ -46,11 +141,11 @@ impl<T> StrictSeq<T> {
     }
 
     pub fn push_front(&mut self, item: T) {
-        self.data.push_front(item);
+        self.tree = mem::take(&mut self.tree).prepend(Elem::new(item));
     }
 
     pub fn push_back(&mut self, item: T) {
-        self.data.push_back(item);
+        self.tree = mem::take(&mut self.tree).append(Elem::new(item));
     }
 
     pub fn cons(mut self, item: T) -> Self {
@@ -63,38 +158,35 @@ impl<T> StrictSeq<T> {
         self
     }
 
+    /// Haskell's `><`; O(log n).
     #[must_use]
-    pub fn concat(mut self, mut other: Self) -> Self {
-        self.data.append(&mut other.data);
-        self
+    pub fn concat(self, other: Self) -> Self {
+        let clone_elem = self.clone_elem.get().or(other.clone_elem.get()).copied();
+        Self::with_clone_elem(self.tree.concat(other.tree), clone_elem)
     }
 
     pub fn from_list<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        Self {
-            data: iter.into_iter().collect(),
-        }
+        Self::from_tree(iter.into_iter().map(Elem::new).collect())
     }
 
     #[must_use]
     pub fn from_vec(vec: Vec<T>) -> Self {
-        Self {
-            data: VecDeque::from(vec),
-        }
+        Self::from_list(vec)
     }
 
     #[must_use]
     pub fn from_vec_deque(deque: VecDeque<T>) -> Self {
-        Self { data: deque }
+        Self::from_list(deque)
     }
 
     #[must_use]
     pub fn into_vec_deque(self) -> VecDeque<T> {
-        self.data
+        self.into_iter().collect()
     }
 
     #[must_use]
     pub fn into_vec(self) -> Vec<T> {
-        self.data.into_iter().collect()
+        self.into_iter().collect()
     }
 
     #[must_use]
@@ -102,25 +194,37 @@ impl<T> StrictSeq<T> {
     where
         T: Clone,
     {
-        self.data.iter().cloned().collect()
+        self.iter().cloned().collect()
     }
 
     #[must_use]
     pub fn front(&self) -> Option<&T> {
-        self.data.front()
+        self.lookup(0)
     }
 
     #[must_use]
     pub fn back(&self) -> Option<&T> {
-        self.data.back()
+        self.len().checked_sub(1).and_then(|last| self.lookup(last))
     }
 
     pub fn pop_front(&mut self) -> Option<T> {
-        self.data.pop_front()
+        match self.tree.viewl() {
+            ViewL::EmptyL => None,
+            ViewL::Cons(first, rest) => {
+                self.tree = rest;
+                Some(first.into_value(self.clone_elem.get().copied()))
+            },
+        }
     }
 
     pub fn pop_back(&mut self) -> Option<T> {
-        self.data.pop_back()
+        match self.tree.viewr() {
+            ViewR::EmptyR => None,
+            ViewR::Cons(rest, last) => {
+                self.tree = rest;
+                Some(last.into_value(self.clone_elem.get().copied()))
+            },
+        }
     }
 
     #[must_use]
@@ -128,7 +232,7 @@ impl<T> StrictSeq<T> {
     where
         T: Clone,
     {
-        Self::from_list(self.data.iter().take(n).cloned())
+        self.split_at(n).0
     }
 
     #[must_use]
@@ -136,7 +240,7 @@ impl<T> StrictSeq<T> {
     where
         T: Clone,
     {
-        Self::from_list(self.data.iter().skip(n).cloned())
+        self.split_at(n).1
     }
 
     #[must_use]
@@ -163,12 +267,16 @@ impl<T> StrictSeq<T> {
         self.take(len - n)
     }
 
+    /// Haskell's `splitAt`: the first `index` elements and the rest, with
+    /// `index` past the end giving `(self, empty)`. O(log n); both halves
+    /// share structure with `self`.
     #[must_use]
     pub fn split_at(&self, index: usize) -> (Self, Self)
     where
         T: Clone,
     {
-        (self.take(index), self.drop(index))
+        let (left, right) = self.tree.split_at(index);
+        (self.derive(left), self.derive(right))
     }
 
     #[must_use]
@@ -184,19 +292,35 @@ impl<T> StrictSeq<T> {
         }
     }
 
+    /// [`StrictSeq::split_at`], consuming the sequence: the first `index`
+    /// elements and the rest, with `index` past the end giving
+    /// `(self, empty)`.
+    ///
+    /// Elements are moved rather than cloned, so `T` need not be `Clone`;
+    /// `.0` and `.1` are the owning `take` and `drop`.
+    #[must_use]
+    pub fn into_split_at(self, index: usize) -> (Self, Self) {
+        let clone_elem = self.clone_elem.get().copied();
+        let (left, right) = self.tree.split_at(index);
+        (
+            Self::with_clone_elem(left, clone_elem),
+            Self::with_clone_elem(right, clone_elem),
+        )
+    }
+
     pub fn scanl<B, F>(&self, init: B, mut f: F) -> StrictSeq<B>
     where
         B: Clone,
         F: FnMut(B, &T) -> B,
     {
         let mut acc = init.clone();
-        let mut out = VecDeque::with_capacity(self.len() + 1);
-        out.push_back(init);
-        for item in &self.data {
+        let mut out = Vec::with_capacity(self.len() + 1);
+        out.push(init);
+        for item in self {
             acc = f(acc.clone(), item);
-            out.push_back(acc.clone());
+            out.push(acc.clone());
         }
-        StrictSeq { data: out }
+        StrictSeq::from_vec(out)
     }
 
     pub fn take_while_l<F>(&self, mut predicate: F) -> Self
@@ -204,15 +328,7 @@ impl<T> StrictSeq<T> {
         T: Clone,
         F: FnMut(&T) -> bool,
     {
-        let mut collected = VecDeque::new();
-        for item in &self.data {
-            if predicate(item) {
-                collected.push_back(item.clone());
-            } else {
-                break;
-            }
-        }
-        StrictSeq { data: collected }
+        self.take(self.iter().take_while(|item| predicate(item)).count())
     }
 
     pub fn take_while_r<F>(&self, mut predicate: F) -> Self
@@ -220,15 +336,7 @@ impl<T> StrictSeq<T> {
         T: Clone,
         F: FnMut(&T) -> bool,
     {
-        let mut collected = VecDeque::new();
-        for item in self.data.iter().rev() {
-            if predicate(item) {
-                collected.push_front(item.clone());
-            } else {
-                break;
-            }
-        }
-        StrictSeq { data: collected }
+        self.take_last(self.iter().rev().take_while(|item| predicate(item)).count())
     }
 
     pub fn drop_while_l<F>(&self, mut predicate: F) -> Self
@@ -236,15 +344,7 @@ impl<T> StrictSeq<T> {
         T: Clone,
         F: FnMut(&T) -> bool,
     {
-        let mut iter = self.data.iter();
-        while let Some(item) = iter.next() {
-            if !predicate(item) {
-                let rest: VecDeque<T> =
-                    std::iter::once(item.clone()).chain(iter.cloned()).collect();
-                return StrictSeq { data: rest };
-            }
-        }
-        StrictSeq::empty()
+        self.drop(self.iter().take_while(|item| predicate(item)).count())
     }
 
     pub fn drop_while_r<F>(&self, mut predicate: F) -> Self
@@ -252,15 +352,7 @@ impl<T> StrictSeq<T> {
         T: Clone,
         F: FnMut(&T) -> bool,
     {
-        let mut items: Vec<T> = self.data.iter().cloned().collect();
-        while let Some(last) = items.last() {
-            if predicate(last) {
-                items.pop();
-            } else {
-                break;
-            }
-        }
-        StrictSeq::from_list(items)
+        self.drop_last(self.iter().rev().take_while(|item| predicate(item)).count())
     }
 
     pub fn spanl<F>(&self, mut predicate: F) -> (Self, Self)
@@ -268,15 +360,7 @@ impl<T> StrictSeq<T> {
         T: Clone,
         F: FnMut(&T) -> bool,
     {
-        let mut count = 0;
-        for item in &self.data {
-            if predicate(item) {
-                count += 1;
-            } else {
-                break;
-            }
-        }
-        (self.take(count), self.drop(count))
+        self.split_at(self.iter().take_while(|item| predicate(item)).count())
     }
 
     pub fn spanr<F>(&self, mut predicate: F) -> (Self, Self)
@@ -284,39 +368,51 @@ impl<T> StrictSeq<T> {
         T: Clone,
         F: FnMut(&T) -> bool,
     {
-        let mut count = 0;
-        for item in self.data.iter().rev() {
-            if predicate(item) {
-                count += 1;
-            } else {
-                break;
-            }
-        }
-        let suffix = self.take_last(count);
-        let prefix = self.drop_last(count);
+        let count = self.iter().rev().take_while(|item| predicate(item)).count();
+        let (prefix, suffix) = self.split_at(self.len() - count);
         (suffix, prefix)
     }
 
+    /// Haskell's `breakl`: [`StrictSeq::spanl`] with the predicate negated,
+    /// splitting before the first element that satisfies `predicate`.
+    pub fn breakl<F>(&self, mut predicate: F) -> (Self, Self)
+    where
+        T: Clone,
+        F: FnMut(&T) -> bool,
+    {
+        self.spanl(|item| !predicate(item))
+    }
+
+    /// Haskell's `breakr`: [`StrictSeq::spanr`] with the predicate negated,
+    /// returning `(suffix, prefix)` split after the last element that
+    /// satisfies `predicate`.
+    pub fn breakr<F>(&self, mut predicate: F) -> (Self, Self)
+    where
+        T: Clone,
+        F: FnMut(&T) -> bool,
+    {
+        self.spanr(|item| !predicate(item))
+    }
+
     pub fn find_index_l<F>(&self, predicate: F) -> Option<usize>
     where
         F: FnMut(&T) -> bool,
     {
-        self.data.iter().position(predicate)
+        self.iter().position(predicate)
     }
 
     pub fn find_index_r<F>(&self, predicate: F) -> Option<usize>
     where
         F: FnMut(&T) -> bool,
     {
-        self.data.iter().rposition(predicate)
+        self.iter().rposition(predicate)
     }
 
     pub fn find_indices_l<F>(&self, mut predicate: F) -> Vec<usize>
     where
         F: FnMut(&T) -> bool,
     {
-        self.data
-            .iter()
+        self.iter()
             .enumerate()
             .filter_map(|(idx, item)| predicate(item).then_some(idx))
             .collect()
@@ -326,8 +422,7 @@ impl<T> StrictSeq<T> {
     where
         F: FnMut(&T) -> bool,
     {
-        self.data
-            .iter()
+        self.iter()
             .enumerate()
             .rev()
             .filter_map(|(idx, item)| predicate(item).then_some(idx))
@@ -336,14 +431,117 @@ impl<T> StrictSeq<T> {
 
     #[must_use]
     pub fn lookup(&self, index: usize) -> Option<&T> {
-        self.data.get(index)
+        self.tree.lookup(index).map(|elem| &*elem.0)
     }
 
+    /// The element at `index`, Haskell's `!?`; O(log n).
     #[must_use]
     pub fn get(&self, index: usize) -> Option<&T> {
         self.lookup(index)
     }
 
+    /// Mutable access to the element at `index`, or `None` when out of
+    /// bounds; O(log n). Whatever the element and the path to it share with
+    /// another version is copied first, so only `self` changes.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let clone_elem = self.clone_elem.get().copied();
+        self.tree
+            .lookup_mut(index)
+            .map(|elem| elem.make_mut(clone_elem))
+    }
+
+    /// Replace the element at `index`, returning whether `index` was in
+    /// bounds; O(log n). Out of bounds the sequence is unchanged.
+    pub fn set(&mut self, index: usize, value: T) -> bool {
+        match self.tree.lookup_mut(index) {
+            Some(elem) => {
+                *elem = Elem::new(value);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Haskell's `update`: the sequence with the element at `index` replaced
+    /// by `value`, or `None` when `index` is out of bounds. O(log n); `self`
+    /// is left untouched.
+    #[must_use]
+    pub fn update(&self, index: usize, value: T) -> Option<Self>
+    where
+        T: Clone,
+    {
+        self.tree
+            .update_at(index, Elem::new(value))
+            .map(|tree| self.derive(tree))
+    }
+
+    /// Haskell's `adjust`: the sequence with the element at `index` replaced
+    /// by `f` of it, or `None` when `index` is out of bounds, in which case
+    /// `f` is not called. O(log n), like [`StrictSeq::update`].
+    #[must_use]
+    pub fn adjust<F>(&self, index: usize, f: F) -> Option<Self>
+    where
+        T: Clone,
+        F: FnOnce(&T) -> T,
+    {
+        let value = f(self.lookup(index)?);
+        self.update(index, value)
+    }
+
+    /// Insert `value` before position `index`, returning the new sequence, or
+    /// `None` when `index` is past the end; `index == len()` appends.
+    ///
+    /// O(log n): only the spine around `index` is rebuilt, and `self` is left
+    /// untouched. For a run of edits close to one another, a [`Cursor`] is
+    /// cheaper still.
+    #[must_use]
+    pub fn insert_at(&self, index: usize, value: T) -> Option<Self>
+    where
+        T: Clone,
+    {
+        self.tree
+            .insert_at(index, Elem::new(value))
+            .map(|tree| self.derive(tree))
+    }
+
+    /// Remove the element at `index`, returning it with the remaining
+    /// sequence, or `None` when `index` is out of bounds.
+    ///
+    /// O(log n). `self` still holds the removed element, so the returned one
+    /// is a clone.
+    #[must_use]
+    pub fn remove_at(&self, index: usize) -> Option<(T, Self)>
+    where
+        T: Clone,
+    {
+        let (removed, rest) = self.tree.remove_at(index)?;
+        Some((removed.into_value(Some(self.share())), self.derive(rest)))
+    }
+
+    /// Exchange the elements at `i` and `j`, returning the new sequence, or
+    /// `None` when either is out of bounds. O(log n).
+    #[must_use]
+    pub fn swap(&self, i: usize, j: usize) -> Option<Self>
+    where
+        T: Clone,
+    {
+        self.tree.swap(i, j).map(|tree| self.derive(tree))
+    }
+
+    /// A [`Cursor`] positioned before element `index`, or `None` when `index`
+    /// is past the end. `index == len()` places it after the last element.
+    #[must_use]
+    pub fn cursor(&self, index: usize) -> Option<Cursor<T>>
+    where
+        T: Clone,
+    {
+        let inner = self.tree.cursor(index)?;
+        Some(Cursor {
+            inner,
+            clone_elem: self.share(),
+        })
+    }
+
     #[must_use]
     pub fn zip<U>(self, other: StrictSeq<U>) -> StrictSeq<(T, U)> {
         self.zip_with(other, |a, b| (a, b))
@@ -353,16 +551,7 @@ impl<T> StrictSeq<T> {
     where
         F: FnMut(T, U) -> V,
     {
-        let len = self.len().min(other.len());
-        let mut data = VecDeque::with_capacity(len);
-        let mut left = self.data.into_iter();
-        let mut right = other.data.into_iter();
-        for _ in 0..len {
-            if let (Some(a), Some(b)) = (left.next(), right.next()) {
-                data.push_back(f(a, b));
-            }
-        }
-        StrictSeq { data }
+        StrictSeq::from_list(self.into_iter().zip(other).map(|(a, b)| f(a, b)))
     }
 
     #[must_use]
@@ -377,14 +566,14 @@ impl<T> StrictSeq<T> {
     where
         F: FnMut(T) -> (A, B),
     {
-        let mut left = VecDeque::with_capacity(self.len());
-        let mut right = VecDeque::with_capacity(self.len());
-        for item in self.data {
+        let mut left = Vec::with_capacity(self.len());
+        let mut right = Vec::with_capacity(self.len());
+        for item in self {
             let (a, b) = f(item);
-            left.push_back(a);
-            right.push_back(b);
+            left.push(a);
+            right.push(b);
         }
-        (StrictSeq { data: left }, StrictSeq { data: right })
+        (StrictSeq::from_vec(left), StrictSeq::from_vec(right))
     }
 
     pub fn filter<F>(&self, mut predicate: F) -> Self
@@ -392,39 +581,353 @@ impl<T> StrictSeq<T> {
         T: Clone,
         F: FnMut(&T) -> bool,
     {
-        StrictSeq::from_list(self.data.iter().filter(|item| predicate(item)).cloned())
+        self.derive(
+            self.tree
+                .iter()
+                .filter(|elem| predicate(&elem.0))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Keep only the elements satisfying `predicate`, preserving their order.
+    ///
+    /// Consumes the sequence; the kept elements move into the result without
+    /// being cloned.
+    #[must_use]
+    pub fn retain<F>(self, mut predicate: F) -> Self
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let clone_elem = self.clone_elem.get().copied();
+        let kept = self
+            .tree
+            .into_iter()
+            .filter(|elem| predicate(&elem.0))
+            .collect();
+        Self::with_clone_elem(kept, clone_elem)
+    }
+
+    /// Split the elements into those satisfying `predicate` and the rest,
+    /// preserving the relative order within each half.
+    ///
+    /// Consumes the sequence; elements are moved rather than cloned.
+    pub fn partition<F>(self, mut predicate: F) -> (Self, Self)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let clone_elem = self.clone_elem.get().copied();
+        let (matching, rest): (Vec<_>, Vec<_>) =
+            self.tree.into_iter().partition(|elem| predicate(&elem.0));
+        (
+            Self::with_clone_elem(matching.into_iter().collect(), clone_elem),
+            Self::with_clone_elem(rest.into_iter().collect(), clone_elem),
+        )
+    }
+
+    /// Map every element through `f`, keeping the `Some` results in order.
+    ///
+    /// Consumes the sequence; each result is evaluated before the new
+    /// sequence is returned.
+    pub fn filter_map<U, F>(self, f: F) -> StrictSeq<U>
+    where
+        F: FnMut(T) -> Option<U>,
+    {
+        StrictSeq::from_list(self.into_iter().filter_map(f))
+    }
+
+    /// Remove the longest prefix whose elements satisfy `predicate`.
+    ///
+    /// Consumes the sequence. Only the dropped prefix is traversed, and the
+    /// rest is split off in O(log n).
+    #[must_use]
+    pub fn drop_while_front<F>(self, predicate: F) -> Self
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.take_while_front(predicate).1
+    }
+
+    /// Split off the longest prefix whose elements satisfy `predicate`.
+    ///
+    /// Consumes the sequence and returns `(prefix, rest)`; elements are moved
+    /// rather than cloned, and only the prefix is traversed.
+    pub fn take_while_front<F>(self, mut predicate: F) -> (Self, Self)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let count = self.iter().take_while(|item| predicate(item)).count();
+        self.into_split_at(count)
     }
 
     #[must_use]
-    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, T> {
-        self.data.iter()
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.tree.iter(),
+        }
     }
 
-    pub fn iter_mut(&mut self) -> std::collections::vec_deque::IterMut<'_, T> {
-        self.data.iter_mut()
+    /// Mutable iteration in order. Elements and nodes shared with another
+    /// version are copied first, so only `self` changes.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            clone_elem: self.clone_elem.get().copied(),
+            inner: self.tree.leaves_mut().into_iter(),
+        }
     }
 }
 
-impl<T> FromIterator<T> for StrictSeq<T> {
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+impl<T: Clone> Clone for StrictSeq<T> {
+    fn clone(&self) -> Self {
+        self.derive(self.tree.clone())
+    }
+}
+
+impl<T> Default for StrictSeq<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T: PartialEq> PartialEq for StrictSeq<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for StrictSeq<T> {}
+
+impl<T: PartialOrd> PartialOrd for StrictSeq<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for StrictSeq<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for StrictSeq<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for item in self {
+            item.hash(state);
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for StrictSeq<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for StrictSeq<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::from_vec)
+    }
+}
+
+/// Borrowing in-order iterator over a [`StrictSeq`].
+pub struct Iter<'a, T> {
+    inner: strict_finger_tree::Iter<'a, (), Elem<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|elem| &*elem.0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|elem| &*elem.0)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// Mutable in-order iterator over a [`StrictSeq`], from
+/// [`StrictSeq::iter_mut`].
+pub struct IterMut<'a, T> {
+    inner: std::vec::IntoIter<&'a mut Elem<T>>,
+    clone_elem: Option<CloneElem<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let clone_elem = self.clone_elem;
+        self.inner.next().map(|elem| elem.make_mut(clone_elem))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let clone_elem = self.clone_elem;
+        self.inner.next_back().map(|elem| elem.make_mut(clone_elem))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+/// Owning iterator over a [`StrictSeq`]. Elements are moved out, or cloned
+/// if another version still shares them.
+pub struct IntoIter<T> {
+    inner: std::collections::vec_deque::IntoIter<Elem<T>>,
+    clone_elem: Option<CloneElem<T>>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let clone_elem = self.clone_elem;
+        self.inner.next().map(|elem| elem.into_value(clone_elem))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let clone_elem = self.clone_elem;
+        self.inner
+            .next_back()
+            .map(|elem| elem.into_value(clone_elem))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+/// A position between two elements of a [`StrictSeq`], for runs of edits
+/// close to one another; see [`StrictSeq::cursor`].
+///
+/// Like [`strict_finger_tree::Cursor`], which it wraps, stepping and editing
+/// at the cursor are amortised O(1), while [`seek`](Self::seek) and
+/// [`into_seq`](Self::into_seq) are O(log n).
+pub struct Cursor<T> {
+    inner: strict_finger_tree::Cursor<(), Elem<T>>,
+    clone_elem: CloneElem<T>,
+}
+
+impl<T> Clone for Cursor<T> {
+    fn clone(&self) -> Self {
         Self {
-            data: VecDeque::from_iter(iter),
+            inner: self.inner.clone(),
+            clone_elem: self.clone_elem,
         }
     }
 }
 
+impl<T> Cursor<T> {
+    /// Number of elements before the cursor.
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.inner.position()
+    }
+
+    /// Number of elements on both sides of the cursor.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Move the cursor before element `index`. Returns `false`, leaving the
+    /// cursor where it was, when `index` is past the end.
+    pub fn seek(&mut self, index: usize) -> bool {
+        self.inner.seek(index)
+    }
+
+    /// The element right after the cursor, if any.
+    #[must_use]
+    pub fn current(&self) -> Option<&T> {
+        self.inner.current().map(|elem| &*elem.0)
+    }
+
+    /// Step over the current element. Returns `false` at the end.
+    pub fn move_next(&mut self) -> bool {
+        self.inner.move_next()
+    }
+
+    /// Step back over the element before the cursor. Returns `false` at the
+    /// start.
+    pub fn move_prev(&mut self) -> bool {
+        self.inner.move_prev()
+    }
+
+    /// Replace the current element with `value`, returning the old one, or
+    /// `None` (dropping `value`) at the end.
+    pub fn replace(&mut self, value: T) -> Option<T> {
+        self.inner
+            .replace(Elem::new(value))
+            .map(|elem| elem.into_value(Some(self.clone_elem)))
+    }
+
+    /// Insert `value` at the cursor; it becomes the current element.
+    pub fn insert(&mut self, value: T) {
+        self.inner.insert(Elem::new(value));
+    }
+
+    /// Remove and return the current element, or `None` at the end.
+    pub fn remove(&mut self) -> Option<T> {
+        self.inner
+            .remove()
+            .map(|elem| elem.into_value(Some(self.clone_elem)))
+    }
+
+    /// Join both sides back into a single sequence.
+    #[must_use]
+    pub fn into_seq(self) -> StrictSeq<T> {
+        StrictSeq::with_clone_elem(self.inner.into_tree(), Some(self.clone_elem))
+    }
+}
+
+impl<T> FromIterator<T> for StrictSeq<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_list(iter)
+    }
+}
+
 impl<T> IntoIterator for StrictSeq<T> {
     type Item = T;
-    type IntoIter = std::collections::vec_deque::IntoIter<T>;
+    type IntoIter = IntoIter<T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.data.into_iter()
+        IntoIter {
+            clone_elem: self.clone_elem.get().copied(),
+            inner: self.tree.into_inner().into_iter(),
+        }
     }
 }
 
 impl<T> Extend<T> for StrictSeq<T> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        self.data.extend(iter);
+        for item in iter {
+            self.push_back(item);
+        }
     }
 }
 
@@ -433,7 +936,7 @@ where
     T: Clone,
 {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
-        self.data.extend(iter.into_iter().cloned());
+        self.extend(iter.into_iter().cloned());
     }
 }
 
@@ -441,34 +944,37 @@ impl<T> Index<usize> for StrictSeq<T> {
     type Output = T;
 
     fn index(&self, index: usize) -> &Self::Output {
-        &self.data[index]
+        self.lookup(index).expect("StrictSeq index out of bounds")
     }
 }
 
 impl<T> IndexMut<usize> for StrictSeq<T> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.data[index]
+        self.get_mut(index).expect("StrictSeq index out of bounds")
     }
 }
 
 impl<'a, T> IntoIterator for &'a StrictSeq<T> {
     type Item = &'a T;
-    type IntoIter = std::collections::vec_deque::Iter<'a, T>;
+    type IntoIter = Iter<'a, T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.data.iter()
+        self.iter()
     }
 }
 
 impl<T: fmt::Debug> fmt::Debug for StrictSeq<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_list().entries(&self.data).finish()
+        f.debug_list().entries(self).finish()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "serde")]
+    use cardano_binary::{decode_full, serialize};
+    use proptest::prelude::*;
 
     #[test]
     fn construction_and_basic_ops() {
@@ -548,6 +1054,58 @@ mod tests {
         let (suffix_r, prefix_r) = seq.spanr(|&x| x < 3);
         assert_eq!(suffix_r, StrictSeq::from_list([2, 1]));
         assert_eq!(prefix_r, StrictSeq::from_list([1, 2, 3]));
+
+        assert_eq!(seq.breakl(|&x| x >= 3), (prefix, suffix));
+        assert_eq!(seq.breakr(|&x| x >= 3), (suffix_r, prefix_r));
+        assert_eq!(seq.breakl(|&x| x > 9), (seq.clone(), StrictSeq::empty()));
+    }
+
+    #[test]
+    fn into_split_at_moves_elements() {
+        // Not `Clone`: splitting shares the elements instead of copying them.
+        #[derive(Debug, PartialEq, Eq)]
+        struct Unique(u32);
+
+        let seq: StrictSeq<Unique> = (0..5).map(Unique).collect();
+        let (left, right) = seq.into_split_at(2);
+        assert!(left.iter().eq(&[Unique(0), Unique(1)]));
+        assert!(right.iter().eq(&[Unique(2), Unique(3), Unique(4)]));
+
+        let seq: StrictSeq<Unique> = (0..3).map(Unique).collect();
+        let (left, right) = seq.into_split_at(7);
+        assert_eq!(left.len(), 3);
+        assert!(right.is_empty());
+    }
+
+    #[test]
+    fn non_clone_elements_move_in_and_out() {
+        /// Owns its secret, like an mlocked key, so it cannot be `Clone`.
+        #[derive(Debug, PartialEq, Eq)]
+        struct Key(Box<u32>);
+
+        let key = |n| Key(Box::new(n));
+        let mut seq: StrictSeq<Key> = (0..6).map(key).collect();
+        seq.push_front(key(9));
+        assert_eq!(seq.pop_front(), Some(key(9)));
+        assert_eq!(seq.pop_back(), Some(key(5)));
+        *seq.get_mut(0).expect("in bounds").0 += 10;
+        *seq[1].0 += 20;
+        for k in seq.iter_mut() {
+            *k.0 += 1;
+        }
+        assert!(seq.set(4, key(40)));
+
+        let (big, small) = seq.partition(|k| *k.0 > 10);
+        let seq = small.concat(big).retain(|k| *k.0 != 4);
+        let (front, rest) = seq.take_while_front(|k| *k.0 < 20);
+        assert_eq!(front.into_vec(), vec![key(3), key(11)]);
+        let (left, right) = rest.into_split_at(1);
+        assert_eq!(left.into_vec_deque(), VecDeque::from([key(22)]));
+
+        let pairs = right.zip(StrictSeq::from_list([key(7)]));
+        let (keys, sevens): (StrictSeq<Key>, StrictSeq<Key>) = pairs.unzip();
+        assert_eq!(keys.into_iter().collect::<Vec<_>>(), vec![key(40)]);
+        assert_eq!(sevens.filter_map(|k| Some(*k.0)).into_vec(), vec![7]);
     }
 
     #[test]
@@ -572,4 +1130,347 @@ mod tests {
         assert_eq!(seq.find_indices_l(|&x| x == 2), vec![1, 3]);
         assert_eq!(seq.find_indices_r(|&x| x == 2), vec![3, 1]);
     }
+
+    #[test]
+    fn retain_partition_and_filter_map() {
+        let seq = StrictSeq::from_list([1, 2, 3, 4, 5]);
+        assert_eq!(
+            seq.clone().retain(|&x| x % 2 == 1),
+            StrictSeq::from_list([1, 3, 5])
+        );
+        assert_eq!(
+            seq.clone().partition(|&x| x > 3),
+            (
+                StrictSeq::from_list([4, 5]),
+                StrictSeq::from_list([1, 2, 3])
+            )
+        );
+        assert_eq!(
+            seq.filter_map(|x| (x % 2 == 0).then(|| x.to_string())),
+            StrictSeq::from_list(["2".to_string(), "4".to_string()])
+        );
+    }
+
+    #[test]
+    fn front_while_variants() {
+        let seq = StrictSeq::from_list([1, 2, 3, 1]);
+        assert_eq!(
+            seq.clone().drop_while_front(|&x| x < 3),
+            StrictSeq::from_list([3, 1])
+        );
+        assert_eq!(
+            seq.take_while_front(|&x| x < 3),
+            (StrictSeq::from_list([1, 2]), StrictSeq::from_list([3, 1]))
+        );
+    }
+
+    #[test]
+    fn positional_edits_leave_the_original() {
+        let seq = StrictSeq::from_list([1, 2, 3]);
+        assert_eq!(
+            seq.insert_at(1, 9),
+            Some(StrictSeq::from_list([1, 9, 2, 3]))
+        );
+        assert_eq!(
+            seq.insert_at(3, 9),
+            Some(StrictSeq::from_list([1, 2, 3, 9]))
+        );
+        assert_eq!(seq.insert_at(4, 9), None);
+        assert_eq!(seq.remove_at(0), Some((1, StrictSeq::from_list([2, 3]))));
+        assert_eq!(seq.remove_at(3), None);
+        assert_eq!(seq.swap(0, 2), Some(StrictSeq::from_list([3, 2, 1])));
+        assert_eq!(seq.swap(0, 3), None);
+        assert_eq!(seq, StrictSeq::from_list([1, 2, 3]));
+    }
+
+    #[test]
+    fn edits_share_elements_instead_of_cloning() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug, PartialEq, Eq)]
+        struct Cert(u32);
+
+        impl Clone for Cert {
+            fn clone(&self) -> Self {
+                CLONES.fetch_add(1, Ordering::SeqCst);
+                Cert(self.0)
+            }
+        }
+
+        let seq: StrictSeq<Cert> = (0..1_000).map(Cert).collect();
+        let edited = seq
+            .insert_at(500, Cert(9_999))
+            .and_then(|s| s.update(10, Cert(10_000)))
+            .and_then(|s| s.swap(0, 999))
+            .expect("indices in bounds");
+        let (left, right) = edited.split_at(300);
+        let joined = left.concat(right).clone();
+        assert_eq!(CLONES.load(Ordering::SeqCst), 0);
+        assert_eq!(joined.len(), 1_001);
+        assert_eq!(joined[500], Cert(9_999));
+
+        // Mutating a shared element copies it, leaving the other version be.
+        let mut copy = seq.clone();
+        copy[1].0 = 7;
+        assert_eq!(CLONES.load(Ordering::SeqCst), 1);
+        assert_eq!((seq[1].0, copy[1].0), (1, 7));
+        for cert in copy.iter_mut().take(3) {
+            cert.0 += 1;
+        }
+        assert_eq!((seq[0].0, copy[0].0, copy[1].0), (0, 1, 8));
+        // An unshared element moves out without a clone.
+        let before = CLONES.load(Ordering::SeqCst);
+        assert_eq!(copy.pop_front(), Some(Cert(1)));
+        assert_eq!(CLONES.load(Ordering::SeqCst), before);
+    }
+
+    #[test]
+    fn cursor_edits_leave_the_original() {
+        let seq = StrictSeq::from_list([1, 2, 3, 4]);
+        assert!(seq.cursor(5).is_none());
+
+        let mut cursor = seq.cursor(1).expect("position in bounds");
+        assert_eq!(cursor.current(), Some(&2));
+        assert_eq!(cursor.replace(20), Some(2));
+        assert!(cursor.move_next());
+        cursor.insert(30);
+        assert_eq!(cursor.position(), 2);
+        assert!(cursor.move_prev() && cursor.move_prev());
+        assert!(!cursor.move_prev());
+        assert_eq!(cursor.remove(), Some(1));
+        assert!(cursor.seek(cursor.len()));
+        assert_eq!(cursor.current(), None);
+
+        assert_eq!(cursor.into_seq(), StrictSeq::from_list([20, 30, 3, 4]));
+        assert_eq!(seq, StrictSeq::from_list([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn indexed_access_and_updates() {
+        let collected: StrictSeq<u32> = (1..=4).collect();
+        let mut pushed = StrictSeq::empty();
+        for value in 1..=4 {
+            pushed.push_back(value);
+        }
+        // Built from both ends.
+        let mut wrapped = StrictSeq::from_list([0, 0, 3, 4]);
+        wrapped.pop_front();
+        wrapped.pop_front();
+        wrapped.push_front(2);
+        wrapped.push_front(1);
+
+        for mut seq in [collected, pushed, wrapped] {
+            assert_eq!(seq.get(2), Some(&3));
+            assert_eq!(seq.get(4), None);
+            assert_eq!(seq.update(1, 20), Some(StrictSeq::from_list([1, 20, 3, 4])));
+            assert_eq!(seq.update(4, 20), None);
+            assert_eq!(
+                seq.adjust(3, |x| x * 10),
+                Some(StrictSeq::from_list([1, 2, 3, 40]))
+            );
+            assert_eq!(seq.adjust(9, |_| unreachable!("out of bounds")), None);
+            assert_eq!(seq, StrictSeq::from_list([1, 2, 3, 4]));
+
+            *seq.get_mut(0).expect("in bounds") += 10;
+            assert!(seq.set(3, 0));
+            assert!(!seq.set(4, 0));
+            assert!(seq.get_mut(4).is_none());
+            assert_eq!(seq, StrictSeq::from_list([11, 2, 3, 0]));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_positional_edits_match_vec(values in proptest::collection::vec(0u32..100, 0..64), edits in proptest::collection::vec((0u8..3, 0usize..80, 0usize..80, 0u32..100), 0..32)) {
+            let mut seq = StrictSeq::from_vec(values.clone());
+            let mut model = values;
+            for (kind, i, j, value) in edits {
+                match kind {
+                    0 => {
+                        let expected = (i <= model.len()).then(|| {
+                            model.insert(i, value);
+                            model.clone()
+                        });
+                        let edited = seq.insert_at(i, value);
+                        prop_assert_eq!(edited.clone().map(StrictSeq::into_vec), expected);
+                        seq = edited.unwrap_or(seq);
+                    },
+                    1 => {
+                        let expected = (i < model.len()).then(|| model.remove(i));
+                        let edited = seq.remove_at(i);
+                        prop_assert_eq!(edited.as_ref().map(|(removed, _)| *removed), expected);
+                        seq = edited.map_or(seq, |(_, rest)| rest);
+                    },
+                    _ => {
+                        let in_bounds = i < model.len() && j < model.len();
+                        if in_bounds {
+                            model.swap(i, j);
+                        }
+                        let edited = seq.swap(i, j);
+                        prop_assert_eq!(edited.is_some(), in_bounds);
+                        seq = edited.unwrap_or(seq);
+                    },
+                }
+                prop_assert_eq!(seq.to_vec(), model.clone());
+            }
+        }
+
+        #[test]
+        fn proptest_update_then_get(values in proptest::collection::vec(0u32..100, 0..64), index in 0usize..80, value in 100u32..200) {
+            let seq = StrictSeq::from_vec(values.clone());
+            match seq.update(index, value) {
+                Some(updated) => {
+                    prop_assert!(index < values.len());
+                    prop_assert_eq!(updated.len(), values.len());
+                    prop_assert_eq!(updated.get(index), Some(&value));
+                    let mut expected = values.clone();
+                    expected[index] = value;
+                    prop_assert_eq!(updated.into_vec(), expected);
+                },
+                None => prop_assert!(index >= values.len()),
+            }
+            prop_assert_eq!(seq.adjust(index, |_| value), seq.update(index, value));
+
+            let mut in_place = seq;
+            prop_assert_eq!(in_place.set(index, value), index < values.len());
+            prop_assert_eq!(in_place.len(), values.len());
+        }
+
+        #[test]
+        fn proptest_slicing_matches_vec(values in proptest::collection::vec(0u32..100, 0..64), other in proptest::collection::vec(0u32..100, 0..64), n in 0usize..80, limit in 0u32..100) {
+            let seq = StrictSeq::from_vec(values.clone());
+            let at = n.min(values.len());
+
+            prop_assert_eq!(seq.take(n).into_vec(), values[..at].to_vec());
+            prop_assert_eq!(seq.drop(n).into_vec(), values[at..].to_vec());
+            let (left, right) = seq.split_at(n);
+            prop_assert_eq!((left.into_vec(), right.into_vec()), (values[..at].to_vec(), values[at..].to_vec()));
+            let (left, right) = seq.clone().into_split_at(n);
+            prop_assert_eq!((left.into_vec(), right.into_vec()), (values[..at].to_vec(), values[at..].to_vec()));
+
+            let span = values.iter().take_while(|&&x| x < limit).count();
+            let (prefix, rest) = seq.spanl(|&x| x < limit);
+            prop_assert_eq!((prefix.into_vec(), rest.into_vec()), (values[..span].to_vec(), values[span..].to_vec()));
+            let brk = values.iter().position(|&x| x >= limit).unwrap_or(values.len());
+            let (prefix, rest) = seq.breakl(|&x| x >= limit);
+            prop_assert_eq!((prefix.into_vec(), rest.into_vec()), (values[..brk].to_vec(), values[brk..].to_vec()));
+            let brk = values.iter().rposition(|&x| x >= limit).map_or(0, |i| i + 1);
+            let (suffix, prefix) = seq.breakr(|&x| x >= limit);
+            prop_assert_eq!((suffix.into_vec(), prefix.into_vec()), (values[brk..].to_vec(), values[..brk].to_vec()));
+
+            let zipped: Vec<(u32, u32)> = values.iter().copied().zip(other.iter().copied()).collect();
+            prop_assert_eq!(seq.zip(StrictSeq::from_vec(other)).into_vec(), zipped);
+        }
+
+        #[test]
+        fn proptest_retain_matches_vec(values in proptest::collection::vec(0u32..100, 0..64), modulus in 1u32..8) {
+            let mut expected = values.clone();
+            expected.retain(|x| x % modulus == 0);
+            let actual = StrictSeq::from_vec(values).retain(|x| x % modulus == 0);
+            prop_assert_eq!(actual.into_vec(), expected);
+        }
+
+        #[test]
+        fn proptest_partition_matches_vec(values in proptest::collection::vec(0u32..100, 0..64), pivot in 0u32..100) {
+            let (expected_lo, expected_hi): (Vec<_>, Vec<_>) =
+                values.iter().copied().partition(|&x| x < pivot);
+            let (lo, hi) = StrictSeq::from_vec(values).partition(|&x| x < pivot);
+            prop_assert_eq!(lo.into_vec(), expected_lo);
+            prop_assert_eq!(hi.into_vec(), expected_hi);
+        }
+
+        #[test]
+        fn proptest_filter_map_matches_vec(values in proptest::collection::vec(0u32..100, 0..64)) {
+            let f = |x: u32| (x % 3 != 0).then_some(u64::from(x) * 7);
+            let expected: Vec<u64> = values.iter().copied().filter_map(f).collect();
+            prop_assert_eq!(StrictSeq::from_vec(values).filter_map(f).into_vec(), expected);
+        }
+
+        #[test]
+        fn proptest_front_while_matches_vec(values in proptest::collection::vec(0u32..100, 0..64), limit in 0u32..100) {
+            let taken: Vec<u32> = values.iter().copied().take_while(|&x| x < limit).collect();
+            let dropped: Vec<u32> = values.iter().copied().skip_while(|&x| x < limit).collect();
+            let seq = StrictSeq::from_vec(values);
+
+            prop_assert_eq!(seq.clone().drop_while_front(|&x| x < limit).into_vec(), dropped.clone());
+            let (prefix, rest) = seq.take_while_front(|&x| x < limit);
+            prop_assert_eq!(prefix.into_vec(), taken);
+            prop_assert_eq!(rest.into_vec(), dropped);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn cbor_matches_vec_encoding() {
+        let empty = StrictSeq::<u64>::empty();
+        assert_eq!(serialize(&empty).expect("encodes"), [0x80]);
+        assert_eq!(
+            decode_full::<StrictSeq<u64>>(&[0x80]).expect("decodes"),
+            empty
+        );
+
+        let nested: Vec<Vec<u64>> = vec![vec![], vec![1, 2], vec![u64::MAX]];
+        let seq: StrictSeq<StrictSeq<u64>> = nested
+            .iter()
+            .map(|inner| StrictSeq::from_vec(inner.clone()))
+            .collect();
+        let bytes = serialize(&seq).expect("encodes");
+        assert_eq!(bytes, serialize(&nested).expect("encodes"));
+        assert_eq!(bytes[0], 0x83, "definite-length list header");
+        assert_eq!(
+            decode_full::<StrictSeq<StrictSeq<u64>>>(&bytes).expect("decodes"),
+            seq
+        );
+
+        assert_eq!(
+            serde_json::to_value(&seq).expect("encodes"),
+            serde_json::json!([[], [1, 2], [u64::MAX]])
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn failed_decode_drops_decoded_elements() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+        /// Decodes from a `u64` and counts its live instances.
+        struct Counted;
+
+        impl<'de> Deserialize<'de> for Counted {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                u64::deserialize(deserializer)?;
+                LIVE.fetch_add(1, Ordering::SeqCst);
+                Ok(Counted)
+            }
+        }
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                LIVE.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        // [1, 2, "a"]: the third element is not an integer.
+        let bytes = [0x83, 0x01, 0x02, 0x61, b'a'];
+        assert!(decode_full::<StrictSeq<Counted>>(&bytes).is_err());
+        assert_eq!(LIVE.load(Ordering::SeqCst), 0);
+        // A list cut short after two elements.
+        assert!(decode_full::<StrictSeq<Counted>>(&bytes[..3]).is_err());
+        assert_eq!(LIVE.load(Ordering::SeqCst), 0);
+    }
+
+    #[cfg(feature = "serde")]
+    proptest! {
+        #[test]
+        fn cbor_round_trips_like_vec(items in proptest::collection::vec(any::<u64>(), 0..64)) {
+            let seq = StrictSeq::from_vec(items.clone());
+            let bytes = serialize(&seq).expect("encodes");
+            prop_assert_eq!(&bytes, &serialize(&items).expect("encodes"));
+            prop_assert_eq!(decode_full::<StrictSeq<u64>>(&bytes).expect("decodes"), seq);
+        }
+    }
 }
@@ -91,10 +91,10 @@ for detailed logging.\n"
         let vk_hex = encode_upper(&vk_bytes);
         let sig_hex = encode_upper(&sig_bytes);
 
-        debug::log(|| format!("  Seed:             {}", vector.seed));
-        debug::log(|| format!("  Message:          {}", vector.message));
-        debug::log(|| format!("  Verification Key: {}", vk_hex));
-        debug::log(|| format!("  Signature:        {}", sig_hex));
+        debug::record("ed25519", "seed", &seed_bytes);
+        debug::record("ed25519", "message", &message_bytes);
+        debug::record("ed25519", "verification_key", &vk_bytes);
+        debug::record("ed25519", "signature", &sig_bytes);
 
         if let Some(expected) = vector.expected_public_key.as_deref() {
             let expected_upper = expected.to_ascii_uppercase();
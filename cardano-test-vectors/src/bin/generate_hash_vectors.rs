@@ -14,11 +14,11 @@
 use std::fs;
 use std::path::PathBuf;
 
+use cardano_crypto_class::hash::HashAlgorithm;
 use cardano_crypto_class::hash::{
     Blake2b256, Blake2b512, blake2b224, hash160, keccak256, ripemd160, sha3_256, sha3_512, sha256,
     sha256d, sha512,
 };
-use cardano_crypto_class::kes::hash::KesHashAlgorithm;
 use serde::Serialize;
 
 #[derive(Debug)]
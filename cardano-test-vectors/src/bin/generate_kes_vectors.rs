@@ -8,9 +8,11 @@ use cardano_crypto_class::dsign::DsignAlgorithm;
 use cardano_crypto_class::dsign::ed25519::Ed25519;
 use cardano_crypto_class::kes::{
     CompactSingleKes, CompactSum1Kes, CompactSum2Kes, CompactSum3Kes, CompactSum4Kes,
-    CompactSum5Kes, CompactSum6Kes, CompactSum7Kes, KesAlgorithm, SingleKes, Sum1Kes, Sum2Kes,
-    Sum3Kes, Sum4Kes, Sum5Kes, Sum6Kes, Sum7Kes,
+    CompactSum5Kes, CompactSum6Kes, CompactSum7Kes, KesAlgorithm, Sum1Kes, Sum2Kes, Sum3Kes,
+    Sum4Kes, Sum5Kes, Sum6Kes, Sum7Kes, signed_kes, verify_signed_kes,
 };
+use cardano_crypto_class::signable::HeaderBodyBytes;
+use cardano_test_vectors::{debug, generate};
 use hex::encode_upper;
 use serde::Serialize;
 
@@ -313,14 +315,10 @@ fn build_single_kes_vectors(
         let seed_bytes = decode_seed(def.seed_hex.as_ref())?;
         let message_bytes = decode_hex(def.message_hex.as_ref())?;
 
-        let signing_key = SingleKes::<Ed25519>::gen_key_kes_from_seed_bytes(&seed_bytes)?;
-        let verification_key = SingleKes::<Ed25519>::derive_verification_key(&signing_key)?;
-        let signature = SingleKes::<Ed25519>::sign_kes(&(), 0, &message_bytes, &signing_key)?;
-
-        let vk_bytes = SingleKes::<Ed25519>::raw_serialize_verification_key_kes(&verification_key);
-        let signature_bytes = SingleKes::<Ed25519>::raw_serialize_signature_kes(&signature);
-
-        SingleKes::<Ed25519>::forget_signing_key_kes(signing_key);
+        let generate::SingleKesOutput {
+            verification_key: vk_bytes,
+            signature: signature_bytes,
+        } = generate::single_kes(&seed_bytes, &message_bytes)?;
 
         vectors.push(SingleKesVectorEntry {
             test_name: def.test_name.to_string(),
@@ -353,6 +351,8 @@ fn build_compact_single_kes_vectors(
     for def in definitions {
         let seed_bytes = decode_seed(def.seed_hex.as_ref())?;
         let message_bytes = decode_hex(def.message_hex.as_ref())?;
+        debug::record("compact_single_kes", "seed", &seed_bytes);
+        debug::record("compact_single_kes", "message", &message_bytes);
 
         let signing_key = CompactSingleKes::<Ed25519>::gen_key_kes_from_seed_bytes(&seed_bytes)?;
         let verification_key = CompactSingleKes::<Ed25519>::derive_verification_key(&signing_key)?;
@@ -364,6 +364,8 @@ fn build_compact_single_kes_vectors(
         let raw_signature = CompactSingleKes::<Ed25519>::raw_serialize_signature_kes(&signature);
 
         let (dsign_signature, embedded_vk) = raw_signature.split_at(signature_len);
+        debug::record("compact_single_kes", "verification_key", &vk_bytes);
+        debug::record("compact_single_kes", "signature", &raw_signature);
 
         CompactSingleKes::<Ed25519>::forget_signing_key_kes(signing_key);
 
@@ -563,29 +565,30 @@ where
     let verification_key = K::derive_verification_key(&signing_key)?;
     let vk_bytes = K::raw_serialize_verification_key_kes(&verification_key);
     let total_periods = K::total_periods();
+    debug::record("hierarchical_kes", "seed", &seed_bytes);
+    debug::record("hierarchical_kes", "verification_key", &vk_bytes);
 
     let mut periods = Vec::with_capacity(total_periods as usize);
 
     for period in 0..total_periods {
-        let message_bytes = message_for_period(&base_message, period);
-        let signature = K::sign_kes(&(), period, &message_bytes, &signing_key)?;
-        let raw_signature = K::raw_serialize_signature_kes(&signature);
+        // Each period signs a header body, which is signed as its bytes.
+        let header_body = HeaderBodyBytes::new(message_for_period(&base_message, period));
+        let message_bytes = header_body.as_bytes();
+        let signed = signed_kes::<K, _>(&(), period, &header_body, &signing_key)?;
+        let raw_signature = K::raw_serialize_signature_kes(signed.signature());
+        debug::record("hierarchical_kes", "period", &period.to_be_bytes());
+        debug::record("hierarchical_kes", "message", message_bytes);
+        debug::record("hierarchical_kes", "signature", &raw_signature);
 
-        K::verify_kes(&(), &verification_key, period, &message_bytes, &signature)?;
+        verify_signed_kes(&(), &verification_key, &header_body, &signed)?;
 
         let deserialised =
             K::raw_deserialize_signature_kes(&raw_signature).expect("signature decode");
-        K::verify_kes(
-            &(),
-            &verification_key,
-            period,
-            &message_bytes,
-            &deserialised,
-        )?;
+        K::verify_kes(&(), &verification_key, period, message_bytes, &deserialised)?;
 
         periods.push(PeriodVectorEntry {
             period,
-            message: encode_upper(&message_bytes),
+            message: encode_upper(message_bytes),
             signature: encode_upper(&raw_signature),
             raw_signature: encode_upper(&raw_signature),
         });
@@ -0,0 +1,839 @@
+//! Run every embedded vector through the implementation it pins.
+//!
+//! First reports any [`validate_all`] schema issues, then parses each file
+//! with its [`schema`](cardano_test_vectors::schema) type and checks it
+//! against `cardano-crypto-class` (DSIGN, KES, BLS12-381) or
+//! `cardano-vrf-pure` (VRF), printing one pass/fail line per file. Exits
+//! non-zero if any file has an issue or a failing vector.
+//!
+//! ```text
+//! cargo run -p cardano-test-vectors --bin verify_all
+//! ```
+
+use std::panic;
+use std::process::ExitCode;
+
+use cardano_crypto_class::bls12_381::{
+    BlsError, Point1, Point2, Scalar, final_verify, miller_loop,
+};
+use cardano_crypto_class::dsign::DsignAlgorithm;
+use cardano_crypto_class::dsign::ecdsa_secp256k1::{
+    EcdsaContext, EcdsaSecp256k1DSIGN, MessageHash, hash_and_pack,
+};
+use cardano_crypto_class::dsign::ed25519::Ed25519;
+use cardano_crypto_class::dsign::schnorr_secp256k1::SchnorrSecp256k1DSIGN;
+use cardano_crypto_class::hash::{Blake2b256, Sha3_256, Sha256};
+use cardano_crypto_class::kes::compact_single::OptimizedKesSignature;
+use cardano_crypto_class::kes::{
+    CompactSingleKes, CompactSum1Kes, CompactSum2Kes, CompactSum3Kes, CompactSum4Kes,
+    CompactSum5Kes, CompactSum6Kes, CompactSum7Kes, KesAlgorithm, SingleKes, Sum1Kes, Sum2Kes,
+    Sum3Kes, Sum4Kes, Sum5Kes, Sum6Kes, Sum7Kes,
+};
+use cardano_crypto_class::seed::mk_seed_from_bytes;
+use cardano_test_vectors::schema::{
+    BlsEcOperationsFile, BlsH2cLargeDstFile, BlsPairingFile, BlsSerdeFile, BlsSigAugFile,
+    CompactSingleKesFile, EcdsaFile, Ed25519File, KeyDerivationVector, SchnorrFile, SingleKesFile,
+    SumKesEvolutionFile, SumKesFile, SumKesPeriod, VectorFile, VrfFile,
+};
+use cardano_test_vectors::{bls12_381, dsign, kes, validate_all, vrf};
+use cardano_vrf_pure::{VrfDraft03, VrfDraft13, VrfRfc9381};
+
+const ECDSA_CONTEXT: EcdsaContext = EcdsaContext {
+    enforce_low_s: true,
+};
+
+/// Outcome of checking one file.
+#[derive(Default)]
+struct Checker {
+    vectors: usize,
+    failures: Vec<String>,
+}
+
+impl Checker {
+    fn check(&mut self, ok: bool, at: &str, what: &str) {
+        if !ok {
+            self.failures.push(format!("{at}: {what}"));
+        }
+    }
+}
+
+/// Decode a hex field; undecodable fields are already reported by
+/// [`validate_all`], so they decode as empty here and fail their check.
+fn bytes(value: &str) -> Vec<u8> {
+    hex::decode(value).unwrap_or_default()
+}
+
+fn array<const N: usize>(value: &[u8]) -> [u8; N] {
+    let mut out = [0u8; N];
+    if value.len() == N {
+        out.copy_from_slice(value);
+    }
+    out
+}
+
+/// Whether `f` panics, without printing the panic message.
+fn panics<R>(f: impl FnOnce() -> R + panic::UnwindSafe) -> bool {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(f);
+    panic::set_hook(hook);
+    result.is_err()
+}
+
+fn check_file<S: VectorFile>(contents: &str, run: impl FnOnce(&S, &mut Checker)) -> Checker {
+    let mut checker = Checker::default();
+    match S::parse(contents) {
+        Ok(file) => run(&file, &mut checker),
+        Err(message) => checker.failures.push(message),
+    }
+    checker
+}
+
+fn verify_vrf(file: &VrfFile, checker: &mut Checker) {
+    let seed = array::<32>(&bytes(&file.sk));
+    let alpha = if file.alpha.eq_ignore_ascii_case("empty") {
+        Vec::new()
+    } else {
+        bytes(&file.alpha)
+    };
+    let (public_key, proof, output) = match file.ver.as_str() {
+        "ietfdraft03" => {
+            let (sk, pk) = VrfDraft03::keypair_from_seed(&seed);
+            let proof = VrfDraft03::prove(&sk, &alpha).ok();
+            let output = proof.and_then(|proof| VrfDraft03::verify(&pk, &proof, &alpha).ok());
+            (pk, proof.map(Vec::from), output)
+        },
+        "ietfdraft13" => {
+            let (sk, pk) = VrfDraft13::keypair_from_seed(&seed);
+            let proof = VrfDraft13::prove(&sk, &alpha).ok();
+            let output = proof.and_then(|proof| VrfDraft13::verify(&pk, &proof, &alpha).ok());
+            (pk, proof.map(Vec::from), output)
+        },
+        "rfc9381" => {
+            let (sk, pk) = VrfRfc9381::keypair_from_seed(&seed);
+            let proof = VrfRfc9381::prove(&sk, &alpha).ok();
+            let output = proof.and_then(|proof| VrfRfc9381::verify(&pk, &proof, &alpha).ok());
+            (pk, proof.map(Vec::from), output)
+        },
+        other => {
+            checker.check(false, "", &format!("unknown VRF version {other}"));
+            return;
+        },
+    };
+    checker.vectors = 1;
+    checker.check(public_key[..] == bytes(&file.pk), "", "public key");
+    checker.check(proof == Some(bytes(&file.pi)), "", "proof");
+    checker.check(
+        output.map(Vec::from) == Some(bytes(&file.beta)),
+        "",
+        "output",
+    );
+}
+
+fn verify_ed25519(file: &Ed25519File, checker: &mut Checker) {
+    for vector in &file.vectors {
+        let at = vector.test_name.as_str();
+        let message = bytes(&vector.message);
+        let signing_key = Ed25519::gen_key_from_seed_bytes(&array::<32>(&bytes(&vector.seed)));
+        let verification_key = Ed25519::derive_verification_key(&signing_key);
+        let signature = Ed25519::sign_bytes(&(), &message, &signing_key);
+        checker.check(
+            Ed25519::raw_serialize_verification_key(&verification_key)
+                == bytes(&vector.expected_public_key),
+            at,
+            "public key",
+        );
+        checker.check(
+            Ed25519::raw_serialize_signature(&signature) == bytes(&vector.expected_signature),
+            at,
+            "signature",
+        );
+        checker.check(
+            Ed25519::verify_bytes(&(), &verification_key, &message, &signature).is_ok(),
+            at,
+            "verification",
+        );
+        checker.vectors += 1;
+    }
+}
+
+fn verify_key_derivations<A: DsignAlgorithm>(
+    derivations: &[KeyDerivationVector],
+    checker: &mut Checker,
+) {
+    for vector in derivations {
+        let signing_key = A::gen_key(&mk_seed_from_bytes(bytes(&vector.seed)));
+        checker.check(
+            A::raw_serialize_verification_key(&A::derive_verification_key(&signing_key))
+                == bytes(&vector.verification_key),
+            &vector.test_name,
+            "derived verification key",
+        );
+        checker.vectors += 1;
+    }
+}
+
+fn verify_ecdsa(file: &EcdsaFile, checker: &mut Checker) {
+    type Ecdsa = EcdsaSecp256k1DSIGN;
+
+    for vector in &file.sign_and_verify_vectors {
+        let at = vector.test_name.as_str();
+        let hash = hash_and_pack::<Sha3_256>(vector.message.as_bytes());
+        checker.check(
+            hash.as_bytes()[..] == bytes(&vector.message_hash),
+            at,
+            "message hash",
+        );
+        let signing_key = Ecdsa::gen_key(&mk_seed_from_bytes(bytes(&vector.secret_key)));
+        let verification_key = Ecdsa::derive_verification_key(&signing_key);
+        let signature = Ecdsa::sign_message_hash(&ECDSA_CONTEXT, &hash, &signing_key);
+        checker.check(
+            Ecdsa::raw_serialize_signature(&signature) == bytes(&vector.signature),
+            at,
+            "signature",
+        );
+        checker.check(
+            Ecdsa::verify_message_hash(&ECDSA_CONTEXT, &verification_key, &hash, &signature)
+                .is_ok(),
+            at,
+            "verification",
+        );
+        checker.vectors += 1;
+    }
+
+    for vector in &file.verify_only_vectors {
+        let at = vector.test_name.as_str();
+        let verified = match (
+            Ecdsa::raw_deserialize_verification_key(&bytes(&vector.verification_key)),
+            MessageHash::from_bytes(&bytes(&vector.message_hash)),
+            Ecdsa::raw_deserialize_signature(&bytes(&vector.signature)),
+        ) {
+            (Some(key), Ok(hash), Some(signature)) => {
+                Ecdsa::verify_message_hash(&ECDSA_CONTEXT, &key, &hash, &signature).is_ok()
+            },
+            _ => false,
+        };
+        checker.check(verified == vector.should_verify, at, "verification result");
+        checker.vectors += 1;
+    }
+
+    for vector in &file.error_vectors {
+        let at = vector.test_name.as_str();
+        let should_parse = vector.should_parse.unwrap_or(true);
+        if let Some(raw) = &vector.verification_key_raw {
+            let parsed = Ecdsa::raw_deserialize_verification_key(&bytes(raw)).is_some();
+            checker.check(parsed == should_parse, at, "verification key parsing");
+        }
+        if let Some(raw) = &vector.signature_raw {
+            let parsed = Ecdsa::raw_deserialize_signature(&bytes(raw)).is_some();
+            checker.check(parsed == should_parse, at, "signature parsing");
+        }
+        if let Some(raw) = &vector.message_raw {
+            checker.check(
+                MessageHash::from_bytes(&bytes(raw)).is_err(),
+                at,
+                "unhashed message rejected",
+            );
+        }
+        if let (Some(key), Some(hash), Some(signature), Some(should_verify)) = (
+            &vector.verification_key,
+            &vector.message_hash,
+            &vector.signature,
+            vector.should_verify,
+        ) {
+            let verified = match (
+                Ecdsa::raw_deserialize_verification_key(&bytes(key)),
+                MessageHash::from_bytes(&bytes(hash)),
+                Ecdsa::raw_deserialize_signature(&bytes(signature)),
+            ) {
+                (Some(key), Ok(hash), Some(signature)) => {
+                    Ecdsa::verify_message_hash(&ECDSA_CONTEXT, &key, &hash, &signature).is_ok()
+                },
+                _ => false,
+            };
+            checker.check(verified == should_verify, at, "verification result");
+        }
+        checker.vectors += 1;
+    }
+
+    for vector in &file.message_hash_vectors {
+        let at = vector.test_name.as_str();
+        let preimage = bytes(&vector.preimage);
+        let hash = match vector.hash_algorithm.as_str() {
+            "sha256" => hash_and_pack::<Sha256>(&preimage),
+            "sha3_256" => hash_and_pack::<Sha3_256>(&preimage),
+            "blake2b_256" => hash_and_pack::<Blake2b256>(&preimage),
+            other => {
+                checker.check(false, at, &format!("unknown hash algorithm {other}"));
+                continue;
+            },
+        };
+        checker.check(
+            hash.as_bytes()[..] == bytes(&vector.message_hash),
+            at,
+            "message hash",
+        );
+        let signing_key = Ecdsa::gen_key(&mk_seed_from_bytes(bytes(&vector.secret_key)));
+        checker.check(
+            Ecdsa::raw_serialize_verification_key(&Ecdsa::derive_verification_key(&signing_key))
+                == bytes(&vector.verification_key),
+            at,
+            "verification key",
+        );
+        let signature = Ecdsa::sign_message_hash(&ECDSA_CONTEXT, &hash, &signing_key);
+        checker.check(
+            Ecdsa::raw_serialize_signature(&signature) == bytes(&vector.signature),
+            at,
+            "signature",
+        );
+        checker.vectors += 1;
+    }
+
+    verify_key_derivations::<Ecdsa>(&file.key_derivation_vectors, checker);
+    for vector in &file.invalid_seed_vectors {
+        let seed = bytes(&vector.seed);
+        checker.check(
+            panics(|| Ecdsa::gen_key_from_seed_bytes(&seed)),
+            &vector.test_name,
+            "invalid seed rejected",
+        );
+        checker.vectors += 1;
+    }
+}
+
+fn verify_schnorr(file: &SchnorrFile, checker: &mut Checker) {
+    type Schnorr = SchnorrSecp256k1DSIGN;
+    let context = Default::default();
+
+    for vector in &file.sign_and_verify_vectors {
+        let message = bytes(&vector.message);
+        let signing_key = Schnorr::gen_key(&mk_seed_from_bytes(bytes(&vector.secret_key)));
+        let verification_key = Schnorr::derive_verification_key(&signing_key);
+        let signature = Schnorr::sign_bytes(&context, &message, &signing_key);
+        checker.check(
+            Schnorr::verify_bytes(&context, &verification_key, &message, &signature).is_ok(),
+            &vector.test_name,
+            "sign and verify round trip",
+        );
+        checker.vectors += 1;
+    }
+
+    for vector in &file.verify_only_vectors {
+        let verified = match (
+            Schnorr::raw_deserialize_verification_key(&bytes(&vector.verification_key)),
+            Schnorr::raw_deserialize_signature(&bytes(&vector.signature)),
+        ) {
+            (Some(key), Some(signature)) => {
+                Schnorr::verify_bytes(&context, &key, &bytes(&vector.message), &signature).is_ok()
+            },
+            _ => false,
+        };
+        checker.check(
+            verified == vector.should_verify,
+            &vector.test_name,
+            "verification result",
+        );
+        checker.vectors += 1;
+    }
+
+    for vector in &file.error_vectors {
+        if let Some(raw) = &vector.signature_raw {
+            let parsed = Schnorr::raw_deserialize_signature(&bytes(raw)).is_some();
+            checker.check(
+                parsed == vector.should_parse.unwrap_or(true),
+                &vector.test_name,
+                "signature parsing",
+            );
+        }
+        checker.vectors += 1;
+    }
+
+    verify_key_derivations::<Schnorr>(&file.key_derivation_vectors, checker);
+    for vector in &file.invalid_seed_vectors {
+        let seed = bytes(&vector.seed);
+        checker.check(
+            panics(|| Schnorr::gen_key_from_seed_bytes(&seed)),
+            &vector.test_name,
+            "invalid seed rejected",
+        );
+        checker.vectors += 1;
+    }
+}
+
+/// Derive the verification key of the key generated from `seed`.
+fn kes_verification_key<K: KesAlgorithm>(seed: &str) -> Option<K::VerificationKey> {
+    let signing_key = K::gen_key_kes_from_seed_bytes(&bytes(seed)).ok()?;
+    let verification_key = K::derive_verification_key(&signing_key).ok();
+    K::forget_signing_key_kes(signing_key);
+    verification_key
+}
+
+fn verify_kes_signature<K: KesAlgorithm<Context = ()>>(
+    verification_key: &K::VerificationKey,
+    period: u64,
+    message: &str,
+    raw_signature: &str,
+) -> bool {
+    K::raw_deserialize_signature_kes(&bytes(raw_signature)).is_some_and(|signature| {
+        K::verify_kes(&(), verification_key, period, &bytes(message), &signature).is_ok()
+    })
+}
+
+fn verify_single_kes(file: &SingleKesFile, checker: &mut Checker) {
+    type Single = SingleKes<Ed25519>;
+    for vector in &file.vectors {
+        let at = vector.test_name.as_str();
+        let Some(verification_key) = kes_verification_key::<Single>(&vector.seed) else {
+            checker.check(false, at, "key generation");
+            continue;
+        };
+        checker.check(
+            Single::raw_serialize_verification_key_kes(&verification_key)
+                == bytes(&vector.expected.verification_key),
+            at,
+            "verification key",
+        );
+        checker.check(
+            verify_kes_signature::<Single>(
+                &verification_key,
+                vector.period,
+                &vector.message,
+                &vector.expected.raw_signature,
+            ),
+            at,
+            "signature verification",
+        );
+        checker.vectors += 1;
+    }
+}
+
+fn verify_compact_single_kes(file: &CompactSingleKesFile, checker: &mut Checker) {
+    type Compact = CompactSingleKes<Ed25519>;
+    for vector in &file.vectors {
+        let at = vector.test_name.as_str();
+        let expected = &vector.expected;
+        let Some(verification_key) = kes_verification_key::<Compact>(&vector.seed) else {
+            checker.check(false, at, "key generation");
+            continue;
+        };
+        checker.check(
+            Compact::raw_serialize_verification_key_kes(&verification_key)
+                == bytes(&expected.derived_verification_key),
+            at,
+            "derived verification key",
+        );
+        let embedded = Compact::raw_deserialize_signature_kes(&bytes(&expected.raw_signature)).map(
+            |signature| {
+                Compact::raw_serialize_verification_key_kes(signature.extract_verification_key())
+            },
+        );
+        checker.check(
+            embedded == Some(bytes(&expected.embedded_verification_key)),
+            at,
+            "embedded verification key",
+        );
+        checker.check(
+            verify_kes_signature::<Compact>(
+                &verification_key,
+                vector.period,
+                &vector.message,
+                &expected.raw_signature,
+            ),
+            at,
+            "signature verification",
+        );
+        checker.vectors += 1;
+    }
+}
+
+/// A `SumKES` key of either file layout with its recorded signatures.
+struct SumKesKey<'a> {
+    at: &'a str,
+    seed: &'a str,
+    verification_key: &'a str,
+    periods: &'a [SumKesPeriod],
+}
+
+/// The keys of one `SumKES` depth.
+struct SumKesLevelKeys<'a> {
+    level: u8,
+    total_periods: u64,
+    keys: Vec<SumKesKey<'a>>,
+}
+
+fn verify_sum_level<K: KesAlgorithm<Context = ()>>(
+    total_periods: u64,
+    keys: &[SumKesKey<'_>],
+    checker: &mut Checker,
+) {
+    for &SumKesKey {
+        at,
+        seed,
+        verification_key: expected_key,
+        periods,
+    } in keys
+    {
+        checker.check(total_periods == K::total_periods(), at, "total periods");
+        let Some(verification_key) = kes_verification_key::<K>(seed) else {
+            checker.check(false, at, "key generation");
+            continue;
+        };
+        checker.check(
+            K::raw_serialize_verification_key_kes(&verification_key) == bytes(expected_key),
+            at,
+            "verification key",
+        );
+        for entry in periods {
+            checker.check(
+                entry.signature == entry.raw_signature,
+                at,
+                &format!("period {} signature and raw signature differ", entry.period),
+            );
+            checker.check(
+                verify_kes_signature::<K>(
+                    &verification_key,
+                    entry.period,
+                    &entry.message,
+                    &entry.raw_signature,
+                ),
+                at,
+                &format!("period {} signature verification", entry.period),
+            );
+        }
+        checker.vectors += 1;
+    }
+}
+
+fn verify_sum_levels(compact: bool, levels: Vec<SumKesLevelKeys<'_>>, checker: &mut Checker) {
+    for SumKesLevelKeys {
+        level,
+        total_periods,
+        keys,
+    } in levels
+    {
+        match (compact, level) {
+            (false, 1) => verify_sum_level::<Sum1Kes>(total_periods, &keys, checker),
+            (false, 2) => verify_sum_level::<Sum2Kes>(total_periods, &keys, checker),
+            (false, 3) => verify_sum_level::<Sum3Kes>(total_periods, &keys, checker),
+            (false, 4) => verify_sum_level::<Sum4Kes>(total_periods, &keys, checker),
+            (false, 5) => verify_sum_level::<Sum5Kes>(total_periods, &keys, checker),
+            (false, 6) => verify_sum_level::<Sum6Kes>(total_periods, &keys, checker),
+            (false, 7) => verify_sum_level::<Sum7Kes>(total_periods, &keys, checker),
+            (true, 1) => verify_sum_level::<CompactSum1Kes>(total_periods, &keys, checker),
+            (true, 2) => verify_sum_level::<CompactSum2Kes>(total_periods, &keys, checker),
+            (true, 3) => verify_sum_level::<CompactSum3Kes>(total_periods, &keys, checker),
+            (true, 4) => verify_sum_level::<CompactSum4Kes>(total_periods, &keys, checker),
+            (true, 5) => verify_sum_level::<CompactSum5Kes>(total_periods, &keys, checker),
+            (true, 6) => verify_sum_level::<CompactSum6Kes>(total_periods, &keys, checker),
+            (true, 7) => verify_sum_level::<CompactSum7Kes>(total_periods, &keys, checker),
+            _ => checker.check(false, "", &format!("unsupported level {level}")),
+        }
+    }
+}
+
+fn verify_kes(name: &str, contents: &str) -> Checker {
+    let compact = name.starts_with("compact_");
+    match name {
+        "single_kes_test_vectors.json" => check_file(contents, verify_single_kes),
+        "compact_single_kes_test_vectors.json" => check_file(contents, verify_compact_single_kes),
+        "sum_kes_test_vectors.json" | "compact_sum_kes_test_vectors.json" => {
+            check_file(contents, |file: &SumKesFile, checker| {
+                let levels = file
+                    .levels
+                    .iter()
+                    .map(|level| {
+                        let keys = level
+                            .vectors
+                            .iter()
+                            .map(|vector| SumKesKey {
+                                at: &vector.test_name,
+                                seed: &vector.seed,
+                                verification_key: &vector.verification_key,
+                                periods: &vector.tracked_periods,
+                            })
+                            .collect();
+                        SumKesLevelKeys {
+                            level: level.level,
+                            total_periods: level.total_periods,
+                            keys,
+                        }
+                    })
+                    .collect();
+                verify_sum_levels(compact, levels, checker);
+            })
+        },
+        "sum_kes_period_evolution_vectors.json"
+        | "compact_sum_kes_period_evolution_vectors.json" => {
+            check_file(contents, |file: &SumKesEvolutionFile, checker| {
+                let levels = file
+                    .levels
+                    .iter()
+                    .map(|level| {
+                        let keys = level
+                            .vectors
+                            .iter()
+                            .map(|vector| SumKesKey {
+                                at: &vector.test_name,
+                                seed: &vector.seed,
+                                verification_key: &vector.verification_key,
+                                periods: &vector.periods,
+                            })
+                            .collect();
+                        SumKesLevelKeys {
+                            level: level.level,
+                            total_periods: level.total_periods,
+                            keys,
+                        }
+                    })
+                    .collect();
+                verify_sum_levels(compact, levels, checker);
+            })
+        },
+        _ => {
+            let mut checker = Checker::default();
+            checker.check(false, "", "no verifier for this file");
+            checker
+        },
+    }
+}
+
+fn verify_dsign(name: &str, contents: &str) -> Checker {
+    match name {
+        "ed25519_test_vectors.json" => check_file(contents, verify_ed25519),
+        "ecdsa_secp256k1_test_vectors.json" => check_file(contents, verify_ecdsa),
+        "schnorr_secp256k1_test_vectors.json" => check_file(contents, verify_schnorr),
+        _ => {
+            let mut checker = Checker::default();
+            checker.check(false, "", "no verifier for this file");
+            checker
+        },
+    }
+}
+
+fn g1(value: &str) -> Result<Point1, BlsError> {
+    Point1::uncompress(&bytes(value))
+}
+
+fn g2(value: &str) -> Result<Point2, BlsError> {
+    Point2::uncompress(&bytes(value))
+}
+
+fn verify_bls_sig_aug(file: &BlsSigAugFile, checker: &mut Checker) {
+    checker.vectors = 1;
+    let verified = g1(&file.sig).and_then(|sig| {
+        let pk = g2(&file.pk)?;
+        let hashed = Point1::hash(
+            bls12_381::SIG_AUG_MESSAGE,
+            bls12_381::SIG_AUG_DST,
+            bls12_381::SIG_AUG_AUG,
+        );
+        Ok(final_verify(
+            &miller_loop(&sig, &Point2::generator()),
+            &miller_loop(&hashed, &pk),
+        ))
+    });
+    checker.check(verified == Ok(true), "", "signature");
+}
+
+fn verify_bls_ec_operations(file: &BlsEcOperationsFile, checker: &mut Checker) {
+    checker.vectors = 1;
+    let Ok(scalar) = Scalar::from_be_bytes(&bytes(bls12_381::EC_OPERATIONS_SCALAR)) else {
+        checker.check(false, "", "scalar");
+        return;
+    };
+    let g1_ops = g1(&file.g1_p).and_then(|p| {
+        let q = g1(&file.g1_q)?;
+        Ok([p.add(&q), p.add(&q.neg()), q.mult(&scalar), p.neg()])
+    });
+    let g1_expected = [&file.g1_add, &file.g1_sub, &file.g1_mul, &file.g1_neg];
+    let g2_ops = g2(&file.g2_p).and_then(|p| {
+        let q = g2(&file.g2_q)?;
+        Ok([p.add(&q), p.add(&q.neg()), q.mult(&scalar), p.neg()])
+    });
+    let g2_expected = [&file.g2_add, &file.g2_sub, &file.g2_mul, &file.g2_neg];
+    for (i, what) in ["add", "sub", "mul", "neg"].into_iter().enumerate() {
+        checker.check(
+            g1_ops
+                .as_ref()
+                .is_ok_and(|ops| ops[i].compress()[..] == bytes(g1_expected[i])),
+            "",
+            &format!("g1_{what}"),
+        );
+        checker.check(
+            g2_ops
+                .as_ref()
+                .is_ok_and(|ops| ops[i].compress()[..] == bytes(g2_expected[i])),
+            "",
+            &format!("g2_{what}"),
+        );
+    }
+}
+
+fn verify_bls_h2c_large_dst(file: &BlsH2cLargeDstFile, checker: &mut Checker) {
+    checker.vectors = 1;
+    let hashed = Point1::hash(&bytes(&file.msg), &bytes(&file.dst), &[]);
+    checker.check(
+        hashed.compress()[..] == bytes(&file.expected),
+        "",
+        "expected",
+    );
+}
+
+fn verify_bls_pairing(file: &BlsPairingFile, checker: &mut Checker) {
+    checker.vectors = 1;
+    let points = || -> Result<_, BlsError> {
+        Ok((
+            [
+                g1(&file.p)?,
+                g1(&file.a_p)?,
+                g1(&file.b_p)?,
+                g1(&file.apb_p)?,
+                g1(&file.axb_p)?,
+            ],
+            [
+                g2(&file.q)?,
+                g2(&file.a_q)?,
+                g2(&file.b_q)?,
+                g2(&file.apb_q)?,
+                g2(&file.axb_q)?,
+            ],
+        ))
+    };
+    let Ok(([p, a_p, b_p, apb_p, axb_p], [q, a_q, b_q, apb_q, axb_q])) = points() else {
+        checker.check(false, "", "points decode");
+        return;
+    };
+    let e = miller_loop;
+    let equalities = [
+        ("e([a]P, Q) = e(P, [a]Q)", e(&a_p, &q), e(&p, &a_q)),
+        (
+            "e([a]P, [b]Q) = e([b]P, [a]Q)",
+            e(&a_p, &b_q),
+            e(&b_p, &a_q),
+        ),
+        ("e([a]P, [b]Q) = e([a*b]P, Q)", e(&a_p, &b_q), e(&axb_p, &q)),
+        ("e([a]P, [b]Q) = e(P, [a*b]Q)", e(&a_p, &b_q), e(&p, &axb_q)),
+        (
+            "e([a]P, Q) * e([b]P, Q) = e([a+b]P, Q)",
+            e(&a_p, &q).mult(&e(&b_p, &q)),
+            e(&apb_p, &q),
+        ),
+        (
+            "e(P, [a]Q) * e(P, [b]Q) = e(P, [a+b]Q)",
+            e(&p, &a_q).mult(&e(&p, &b_q)),
+            e(&p, &apb_q),
+        ),
+    ];
+    for (what, lhs, rhs) in equalities {
+        checker.check(final_verify(&lhs, &rhs), "", what);
+    }
+}
+
+fn verify_bls_serde(file: &BlsSerdeFile, checker: &mut Checker) {
+    checker.vectors = 1;
+    let rejections = [
+        (
+            "g1_uncompressed_not_on_curve",
+            Point1::deserialize(&bytes(&file.g1_uncompressed_not_on_curve)).err(),
+            BlsError::PointNotOnCurve,
+        ),
+        (
+            "g1_compressed_not_on_curve",
+            g1(&file.g1_compressed_not_on_curve).err(),
+            BlsError::PointNotOnCurve,
+        ),
+        (
+            "g1_compressed_not_in_group",
+            g1(&file.g1_compressed_not_in_group).err(),
+            BlsError::PointNotInGroup,
+        ),
+        (
+            "g1_uncompressed_not_in_group",
+            Point1::deserialize(&bytes(&file.g1_uncompressed_not_in_group)).err(),
+            BlsError::PointNotInGroup,
+        ),
+        (
+            "g2_uncompressed_not_on_curve",
+            Point2::deserialize(&bytes(&file.g2_uncompressed_not_on_curve)).err(),
+            BlsError::PointNotOnCurve,
+        ),
+        (
+            "g2_compressed_not_on_curve",
+            g2(&file.g2_compressed_not_on_curve).err(),
+            BlsError::PointNotOnCurve,
+        ),
+        (
+            "g2_compressed_not_in_group",
+            g2(&file.g2_compressed_not_in_group).err(),
+            BlsError::PointNotInGroup,
+        ),
+        (
+            "g2_uncompressed_not_in_group",
+            Point2::deserialize(&bytes(&file.g2_uncompressed_not_in_group)).err(),
+            BlsError::PointNotInGroup,
+        ),
+    ];
+    for (what, error, expected) in rejections {
+        checker.check(error == Some(expected), "", what);
+    }
+}
+
+fn verify_bls12_381(name: &str, contents: &str) -> Checker {
+    match name {
+        "bls_sig_aug_test_vectors" => check_file(contents, verify_bls_sig_aug),
+        "ec_operations_test_vectors" => check_file(contents, verify_bls_ec_operations),
+        "h2c_large_dst" => check_file(contents, verify_bls_h2c_large_dst),
+        "pairing_test_vectors" => check_file(contents, verify_bls_pairing),
+        "serde_test_vectors" => check_file(contents, verify_bls_serde),
+        _ => {
+            let mut checker = Checker::default();
+            checker.check(false, "", "no verifier for this file");
+            checker
+        },
+    }
+}
+
+fn report(name: &str, checker: &Checker) -> bool {
+    if checker.failures.is_empty() {
+        println!("PASS {name} ({} vectors)", checker.vectors);
+        return true;
+    }
+    println!(
+        "FAIL {name} ({} vectors, {} failures)",
+        checker.vectors,
+        checker.failures.len()
+    );
+    for failure in &checker.failures {
+        println!("    {failure}");
+    }
+    false
+}
+
+fn main() -> ExitCode {
+    let issues = validate_all();
+    for issue in &issues {
+        println!("ISSUE {issue}");
+    }
+    let mut ok = issues.is_empty();
+
+    for vector in vrf::ALL {
+        ok &= report(vector.name, &check_file(vector.contents, verify_vrf));
+    }
+    for vector in dsign::ALL {
+        ok &= report(vector.name, &verify_dsign(vector.name, vector.contents));
+    }
+    for vector in kes::ALL {
+        ok &= report(vector.name, &verify_kes(vector.name, vector.contents));
+    }
+    for vector in bls12_381::ALL {
+        ok &= report(vector.name, &verify_bls12_381(vector.name, vector.contents));
+    }
+
+    if ok {
+        println!("all embedded vectors verified");
+        ExitCode::SUCCESS
+    } else {
+        println!("vector verification failed");
+        ExitCode::FAILURE
+    }
+}
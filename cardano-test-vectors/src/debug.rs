@@ -1,9 +1,41 @@
-//! Internal debugging utilities for DSIGN and generator workflows.
+//! Structured tracing for DSIGN, KES and VRF vector generation.
 //!
-//! Logging is disabled by default so binaries remain quiet during normal
-//! execution. Enable the `ed25519-debug` feature (and optionally set the
-//! `CARDANO_ED25519_DEBUG` environment variable) to print diagnostic output.
+//! Generators report intermediate values (seeds, derived verification keys,
+//! signatures, per-period messages, …) through [`record`]. Each record carries
+//! a `step` naming the generation stage (for example `"single_kes"`), a `name`
+//! for the value within that step, and the raw bytes.
+//!
+//! Records go to the [`DebugSink`] registered on the current thread with
+//! [`register_sink`]. Tests typically register a [`MemorySink`] and inspect
+//! the captured sequence afterwards:
+//!
+//! ```rust
+//! use std::rc::Rc;
+//!
+//! use cardano_test_vectors::debug::{self, MemorySink};
+//!
+//! let sink = Rc::new(MemorySink::new());
+//! {
+//!     let _guard = debug::register_sink(sink.clone());
+//!     debug::record("example", "seed", &[0u8; 32]);
+//! }
+//! debug::record("example", "ignored", &[1]);
+//!
+//! let records = sink.records();
+//! assert_eq!(records.len(), 1);
+//! assert_eq!(records[0].name, "seed");
+//! assert_eq!(records[0].bytes.len(), 32);
+//! ```
+//!
+//! When no sink is registered, records fall back to a stderr hex dump. The
+//! dump is disabled by default so binaries remain quiet during normal
+//! execution: enable the `ed25519-debug` feature and set the
+//! `CARDANO_ED25519_DEBUG` environment variable to print it, together with
+//! the free-form messages passed to [`log`]. This mirrors the strategy used by
+//! `cardano-vrf-pure`.
 
+use std::cell::RefCell;
+use std::rc::Rc;
 #[cfg(feature = "ed25519-debug")]
 use std::sync::OnceLock;
 
@@ -19,6 +51,111 @@ fn is_enabled() -> bool {
     false
 }
 
+/// Destination for structured trace records.
+pub trait DebugSink {
+    /// Receive the value `name` produced during generation step `step`.
+    fn record(&self, step: &'static str, name: &'static str, bytes: &[u8]);
+}
+
+/// A single captured trace record.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DebugRecord {
+    /// Generation step that produced the value.
+    pub step: &'static str,
+    /// Name of the value within the step.
+    pub name: &'static str,
+    /// Raw bytes of the value.
+    pub bytes: Vec<u8>,
+}
+
+/// Sink collecting records in memory, intended for tests.
+#[derive(Debug, Default)]
+pub struct MemorySink {
+    records: RefCell<Vec<DebugRecord>>,
+}
+
+impl MemorySink {
+    /// Create an empty sink.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot of the records captured so far, in emission order.
+    #[must_use]
+    pub fn records(&self) -> Vec<DebugRecord> {
+        self.records.borrow().clone()
+    }
+
+    /// Remove and return the records captured so far.
+    #[must_use]
+    pub fn take(&self) -> Vec<DebugRecord> {
+        self.records.take()
+    }
+}
+
+impl DebugSink for MemorySink {
+    fn record(&self, step: &'static str, name: &'static str, bytes: &[u8]) {
+        self.records.borrow_mut().push(DebugRecord {
+            step,
+            name,
+            bytes: bytes.to_vec(),
+        });
+    }
+}
+
+/// Sink printing each record to stderr as upper-case hex.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StderrSink;
+
+impl DebugSink for StderrSink {
+    fn record(&self, step: &'static str, name: &'static str, bytes: &[u8]) {
+        eprintln!(
+            "  [{step}] {name} ({} bytes): {}",
+            bytes.len(),
+            hex::encode_upper(bytes)
+        );
+    }
+}
+
+thread_local! {
+    static SINK: RefCell<Option<Rc<dyn DebugSink>>> = const { RefCell::new(None) };
+}
+
+/// Restores the previously registered sink when dropped.
+#[must_use = "the sink is unregistered as soon as the guard is dropped"]
+pub struct SinkGuard {
+    previous: Option<Rc<dyn DebugSink>>,
+}
+
+impl Drop for SinkGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        SINK.with(|slot| *slot.borrow_mut() = previous);
+    }
+}
+
+/// Route records emitted on the current thread to `sink` until the returned
+/// guard is dropped. Registrations nest; dropping a guard reinstates the sink
+/// that was active before it.
+pub fn register_sink(sink: Rc<dyn DebugSink>) -> SinkGuard {
+    let previous = SINK.with(|slot| slot.borrow_mut().replace(sink));
+    SinkGuard { previous }
+}
+
+/// Emit a structured trace record.
+///
+/// The record goes to the sink registered on this thread, or to the stderr
+/// hex dump when none is registered and debugging is enabled.
+pub fn record(step: &'static str, name: &'static str, bytes: &[u8]) {
+    let sink = SINK.with(|slot| slot.borrow().clone());
+    match sink {
+        Some(sink) => sink.record(step, name, bytes),
+        None if is_enabled() => StderrSink.record(step, name, bytes),
+        None => {},
+    }
+}
+
 /// Emit a lazily constructed debug message when DSIGN debugging is enabled.
 #[inline(always)]
 pub fn log<F>(message: F)
@@ -0,0 +1,54 @@
+//! Deterministic generation routines shared by the vector generator binaries
+//! and the regression tests.
+//!
+//! Every routine reports its intermediate values through [`crate::debug`], so
+//! a registered [`MemorySink`](crate::debug::MemorySink) captures exactly what
+//! ends up in the generated fixture.
+
+use cardano_crypto_class::dsign::ed25519::Ed25519;
+use cardano_crypto_class::kes::{KesAlgorithm, KesMError, SingleKes};
+
+use crate::debug;
+
+/// Trace step used by [`single_kes`].
+pub const SINGLE_KES_STEP: &str = "single_kes";
+
+/// Raw outputs of one SingleKES (Ed25519) vector.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SingleKesOutput {
+    /// Raw verification key bytes.
+    pub verification_key: Vec<u8>,
+    /// Raw signature bytes for period 0.
+    pub signature: Vec<u8>,
+}
+
+/// Generate a SingleKES (Ed25519) key from `seed` and sign `message` at
+/// period 0.
+///
+/// Records `seed`, `message`, `verification_key` and `signature` under
+/// [`SINGLE_KES_STEP`], in that order.
+///
+/// # Errors
+///
+/// Returns an error if key generation, derivation or signing fails.
+pub fn single_kes(seed: &[u8; 32], message: &[u8]) -> Result<SingleKesOutput, KesMError> {
+    debug::record(SINGLE_KES_STEP, "seed", seed);
+    debug::record(SINGLE_KES_STEP, "message", message);
+
+    let signing_key = SingleKes::<Ed25519>::gen_key_kes_from_seed_bytes(seed)?;
+    let verification_key = SingleKes::<Ed25519>::derive_verification_key(&signing_key)?;
+    let signature = SingleKes::<Ed25519>::sign_kes(&(), 0, message, &signing_key);
+    SingleKes::<Ed25519>::forget_signing_key_kes(signing_key);
+    let signature = signature?;
+
+    let verification_key =
+        SingleKes::<Ed25519>::raw_serialize_verification_key_kes(&verification_key);
+    let signature = SingleKes::<Ed25519>::raw_serialize_signature_kes(&signature);
+    debug::record(SINGLE_KES_STEP, "verification_key", &verification_key);
+    debug::record(SINGLE_KES_STEP, "signature", &signature);
+
+    Ok(SingleKesOutput {
+        verification_key,
+        signature,
+    })
+}
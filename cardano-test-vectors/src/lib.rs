@@ -5,11 +5,22 @@
 //! compile time so consumers can rely on the fixtures without performing any
 //! I/O at runtime or maintaining their own copies.
 
-/// Internal debugging helpers mirroring the strategy used by `cardano-vrf-pure`.
-/// Enable the `ed25519-debug` feature (and optionally set the
-/// `CARDANO_ED25519_DEBUG` environment variable) to surface detailed logs.
+/// Structured tracing of generator intermediates. Register a
+/// [`debug::MemorySink`] to capture records in tests, or enable the
+/// `ed25519-debug` feature and set `CARDANO_ED25519_DEBUG` for a stderr dump.
 pub mod debug;
 
+/// Deterministic generation routines shared by the generator binaries.
+pub mod generate;
+
+/// Strict serde schemas for each vector file family.
+pub mod schema;
+
+/// Schema validation of every embedded vector file.
+pub mod validate;
+
+pub use validate::{VectorIssue, VectorIssueKind, validate_all};
+
 /// VRF (Verifiable Random Function) fixtures originating from the Haskell
 /// `cardano-base` repository.
 pub mod vrf {
@@ -80,6 +91,18 @@ pub mod vrf {
             name: "vrf_ver13_standard_12",
             contents: include_str!("../test_vectors/vrf_ver13_standard_12"),
         },
+        TestVector {
+            name: "vrf_rfc9381_ell2_16",
+            contents: include_str!("../test_vectors/vrf_rfc9381_ell2_16"),
+        },
+        TestVector {
+            name: "vrf_rfc9381_ell2_17",
+            contents: include_str!("../test_vectors/vrf_rfc9381_ell2_17"),
+        },
+        TestVector {
+            name: "vrf_rfc9381_ell2_18",
+            contents: include_str!("../test_vectors/vrf_rfc9381_ell2_18"),
+        },
     ];
 
     /// Look up a VRF test vector by its file name.
@@ -95,6 +118,139 @@ pub mod vrf {
     pub fn names() -> impl Iterator<Item = &'static str> {
         ALL.iter().map(|vector| vector.name)
     }
+
+    /// File name prefix of the RFC 9381 ECVRF-EDWARDS25519-SHA512-ELL2
+    /// vectors (RFC 9381 Appendix B.3, examples 16 to 18).
+    pub const RFC9381_PREFIX: &str = "vrf_rfc9381_ell2_";
+
+    /// A VRF test vector with its hex fields decoded.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct VrfVector {
+        /// File name of the vector.
+        pub name: &'static str,
+        /// Algorithm the vector was produced for (`vrf` field).
+        pub algorithm: String,
+        /// Specification version (`ver` field).
+        pub version: String,
+        /// Secret key seed (`sk` field).
+        pub secret_key: Vec<u8>,
+        /// Public key (`pk` field).
+        pub public_key: Vec<u8>,
+        /// Message (`alpha` field; `empty` decodes to no bytes).
+        pub alpha: Vec<u8>,
+        /// Proof (`pi` field).
+        pub proof: Vec<u8>,
+        /// Output (`beta` field).
+        pub output: Vec<u8>,
+    }
+
+    /// Error raised when an embedded VRF vector does not parse.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum VectorParseError {
+        /// A non-empty line has no `key: value` separator.
+        MalformedLine {
+            /// Vector file name.
+            vector: &'static str,
+            /// The offending line.
+            line: String,
+        },
+        /// A required field is absent.
+        MissingField {
+            /// Vector file name.
+            vector: &'static str,
+            /// Field name.
+            field: &'static str,
+        },
+        /// A field is neither hex nor `empty`.
+        InvalidHex {
+            /// Vector file name.
+            vector: &'static str,
+            /// Field name.
+            field: &'static str,
+        },
+    }
+
+    impl std::fmt::Display for VectorParseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::MalformedLine { vector, line } => {
+                    write!(f, "{vector}: line {line:?} has no ':' separator")
+                },
+                Self::MissingField { vector, field } => write!(f, "{vector}: missing {field}"),
+                Self::InvalidHex { vector, field } => write!(f, "{vector}: {field} is not hex"),
+            }
+        }
+    }
+
+    impl std::error::Error for VectorParseError {}
+
+    impl TestVector {
+        /// Parse the `key: value` lines of the vector and decode its fields.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`VectorParseError`] if a line is malformed, a field is
+        /// missing, or a byte field is not hex.
+        pub fn parse(&self) -> Result<VrfVector, VectorParseError> {
+            let vector = self.name;
+            let mut fields = std::collections::BTreeMap::new();
+            for line in self.contents.lines().map(str::trim) {
+                if line.is_empty() {
+                    continue;
+                }
+                let (key, value) =
+                    line.split_once(':')
+                        .ok_or_else(|| VectorParseError::MalformedLine {
+                            vector,
+                            line: line.to_owned(),
+                        })?;
+                fields.insert(key.trim(), value.trim());
+            }
+
+            let text = |field: &'static str| {
+                fields
+                    .get(field)
+                    .map(|value| (*value).to_owned())
+                    .ok_or(VectorParseError::MissingField { vector, field })
+            };
+            let bytes = |field: &'static str| {
+                let value = fields
+                    .get(field)
+                    .ok_or(VectorParseError::MissingField { vector, field })?;
+                if value.eq_ignore_ascii_case("empty") {
+                    return Ok(Vec::new());
+                }
+                hex::decode(value).map_err(|_| VectorParseError::InvalidHex { vector, field })
+            };
+
+            Ok(VrfVector {
+                name: vector,
+                algorithm: text("vrf")?,
+                version: text("ver")?,
+                secret_key: bytes("sk")?,
+                public_key: bytes("pk")?,
+                alpha: bytes("alpha")?,
+                proof: bytes("pi")?,
+                output: bytes("beta")?,
+            })
+        }
+    }
+
+    /// Parse every embedded vector whose name starts with `prefix`, in name
+    /// order.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`VectorParseError`] encountered.
+    pub fn parse_with_prefix(prefix: &str) -> Result<Vec<VrfVector>, VectorParseError> {
+        let mut vectors = ALL
+            .iter()
+            .filter(|vector| vector.name.starts_with(prefix))
+            .map(TestVector::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        vectors.sort_by_key(|vector| vector.name);
+        Ok(vectors)
+    }
 }
 
 /// DSIGN (Digital Signature) fixtures extracted from the Haskell
@@ -196,7 +352,28 @@ pub mod kes {
 
 /// BLS12-381 (pairings and signature operations) fixtures from the Haskell
 /// `cardano-crypto-tests` repository.
+///
+/// The files hold only points and byte strings; the parameters the upstream
+/// generator used for them are the constants below.
 pub mod bls12_381 {
+    use crate::schema::{
+        BlsEcOperationsFile, BlsH2cLargeDstFile, BlsPairingFile, BlsSerdeFile, BlsSigAugFile,
+        VectorFile,
+    };
+
+    /// Scalar, big-endian hex, by which `ec_operations_test_vectors`
+    /// multiplies `g1_q` and `g2_q`.
+    pub const EC_OPERATIONS_SCALAR: &str =
+        "40df499974f62e2f268cd5096b0d952073900054122ffce0a27c9d96932891a5";
+    /// Domain separation tag the `bls_sig_aug_test_vectors` message is
+    /// hashed to G1 under. It names G2, as in blst's `min_pk` example,
+    /// although the signature is in G1.
+    pub const SIG_AUG_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+    /// Message signed in `bls_sig_aug_test_vectors`.
+    pub const SIG_AUG_MESSAGE: &[u8] = b"blst is such a blast";
+    /// Augmentation prefixed to [`SIG_AUG_MESSAGE`] when hashing it.
+    pub const SIG_AUG_AUG: &[u8] = b"Random value for test aug. ";
+
     /// Metadata describing an embedded BLS12-381 test vector file.
     #[derive(Clone, Copy, Debug)]
     pub struct TestVector {
@@ -243,4 +420,54 @@ pub mod bls12_381 {
     pub fn names() -> impl Iterator<Item = &'static str> {
         ALL.iter().map(|vector| vector.name)
     }
+
+    fn parse<S: VectorFile>(name: &str) -> Result<S, String> {
+        let contents = get(name).ok_or_else(|| format!("{name} is not embedded"))?;
+        S::parse(contents).map_err(|message| format!("{name}: {message}"))
+    }
+
+    /// `bls_sig_aug_test_vectors`, parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the schema error if the embedded file does not parse.
+    pub fn sig_aug() -> Result<BlsSigAugFile, String> {
+        parse("bls_sig_aug_test_vectors")
+    }
+
+    /// `ec_operations_test_vectors`, parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the schema error if the embedded file does not parse.
+    pub fn ec_operations() -> Result<BlsEcOperationsFile, String> {
+        parse("ec_operations_test_vectors")
+    }
+
+    /// `h2c_large_dst`, parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the schema error if the embedded file does not parse.
+    pub fn h2c_large_dst() -> Result<BlsH2cLargeDstFile, String> {
+        parse("h2c_large_dst")
+    }
+
+    /// `pairing_test_vectors`, parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the schema error if the embedded file does not parse.
+    pub fn pairing() -> Result<BlsPairingFile, String> {
+        parse("pairing_test_vectors")
+    }
+
+    /// `serde_test_vectors`, parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the schema error if the embedded file does not parse.
+    pub fn serde() -> Result<BlsSerdeFile, String> {
+        parse("serde_test_vectors")
+    }
 }
@@ -0,0 +1,827 @@
+//! Strict schemas for every embedded vector file family.
+//!
+//! Each file format has a serde type with `deny_unknown_fields`, so a field
+//! added by a generator but not read by any consumer, or a renamed field
+//! that a consumer silently stops seeing, fails to parse instead of being
+//! ignored. The line-oriented VRF and BLS12-381 formats are parsed through
+//! the same serde types: VRF `key: value` lines as a map, BLS12-381 files as
+//! a sequence of hex lines in their upstream order.
+//!
+//! [`VectorFile::visit_hex`] walks every hex-encoded field so
+//! [`validate_all`](crate::validate::validate_all) can check the encodings
+//! without each consumer repeating the decoding.
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+
+/// Whether a hex field is an input to an operation or a value the
+/// implementation must reproduce.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldRole {
+    /// Key material, messages and other inputs. May be empty.
+    Input,
+    /// An output pinned by the vector. Must not be empty.
+    Expected,
+}
+
+/// A hex-encoded field of a parsed vector file.
+#[derive(Clone, Copy, Debug)]
+pub struct HexField<'a> {
+    /// Test name (or level and test name) of the vector holding the field,
+    /// empty for file-level fields.
+    pub location: &'a str,
+    /// KES period of the signature holding the field, for `SumKES` files.
+    pub period: Option<u64>,
+    /// Field name as it appears in the file.
+    pub field: &'static str,
+    /// The encoded value.
+    pub value: &'a str,
+    /// Input or expected output.
+    pub role: FieldRole,
+}
+
+/// A vector file format with a strict schema.
+pub trait VectorFile: DeserializeOwned {
+    /// Parse `contents` against the schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns the serde message for an unknown field, a missing field, or a
+    /// value of the wrong type.
+    fn parse(contents: &str) -> Result<Self, String> {
+        serde_json::from_str(contents).map_err(|err| err.to_string())
+    }
+
+    /// Call `visit` for every hex-encoded field, in file order.
+    fn visit_hex<'a>(&'a self, visit: &mut dyn FnMut(HexField<'a>));
+}
+
+fn input<'a>(location: &'a str, field: &'static str, value: &'a str) -> HexField<'a> {
+    HexField {
+        location,
+        period: None,
+        field,
+        value,
+        role: FieldRole::Input,
+    }
+}
+
+fn expected<'a>(location: &'a str, field: &'static str, value: &'a str) -> HexField<'a> {
+    HexField {
+        location,
+        period: None,
+        field,
+        value,
+        role: FieldRole::Expected,
+    }
+}
+
+fn parse_lines<T: DeserializeOwned>(contents: &str) -> Result<T, String> {
+    let lines = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    T::deserialize(SeqDeserializer::<_, serde::de::value::Error>::new(lines))
+        .map_err(|err| err.to_string())
+}
+
+/// A VRF vector: `key: value` lines as in the Haskell `cardano-crypto-praos`
+/// test vectors.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VrfFile {
+    /// Algorithm name (`PraosVRF`, `PraosBatchCompatVRF`, `VrfRfc9381`).
+    pub vrf: String,
+    /// Specification version (`ietfdraft03`, `ietfdraft13`, `rfc9381`).
+    pub ver: String,
+    /// Cipher suite name.
+    pub ciphersuite: String,
+    /// Secret key seed.
+    pub sk: String,
+    /// Public key.
+    pub pk: String,
+    /// Message, or `empty`.
+    pub alpha: String,
+    /// Proof.
+    pub pi: String,
+    /// Output.
+    pub beta: String,
+}
+
+impl VectorFile for VrfFile {
+    fn parse(contents: &str) -> Result<Self, String> {
+        let mut fields = Vec::new();
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("line {line:?} has no ':' separator"))?;
+            fields.push((key.trim(), value.trim()));
+        }
+        Self::deserialize(MapDeserializer::<_, serde::de::value::Error>::new(
+            fields.into_iter(),
+        ))
+        .map_err(|err| err.to_string())
+    }
+
+    fn visit_hex<'a>(&'a self, visit: &mut dyn FnMut(HexField<'a>)) {
+        visit(input("", "sk", &self.sk));
+        visit(input("", "pk", &self.pk));
+        if !self.alpha.eq_ignore_ascii_case("empty") {
+            visit(input("", "alpha", &self.alpha));
+        }
+        visit(expected("", "pi", &self.pi));
+        visit(expected("", "beta", &self.beta));
+    }
+}
+
+/// `ed25519_test_vectors.json`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Ed25519File {
+    pub description: String,
+    pub algorithm: String,
+    pub source: String,
+    pub vectors: Vec<Ed25519Vector>,
+}
+
+/// One Ed25519 signing vector.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Ed25519Vector {
+    pub test_name: String,
+    pub seed: String,
+    pub message: String,
+    pub expected_public_key: String,
+    pub expected_signature: String,
+    pub description: String,
+}
+
+impl VectorFile for Ed25519File {
+    fn visit_hex<'a>(&'a self, visit: &mut dyn FnMut(HexField<'a>)) {
+        for vector in &self.vectors {
+            let at = vector.test_name.as_str();
+            visit(input(at, "seed", &vector.seed));
+            visit(input(at, "message", &vector.message));
+            visit(expected(
+                at,
+                "expected_public_key",
+                &vector.expected_public_key,
+            ));
+            visit(expected(
+                at,
+                "expected_signature",
+                &vector.expected_signature,
+            ));
+        }
+    }
+}
+
+/// `ecdsa_secp256k1_test_vectors.json`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EcdsaFile {
+    pub description: String,
+    pub algorithm: String,
+    pub source: String,
+    /// Hash applied to each `message` text to obtain its `message_hash`.
+    pub message_hash_algorithm: String,
+    pub message_hash_note: String,
+    pub sign_and_verify_vectors: Vec<EcdsaSignVector>,
+    pub verify_only_vectors: Vec<EcdsaVerifyVector>,
+    pub error_vectors: Vec<EcdsaErrorVector>,
+    pub message_hash_vectors: Vec<EcdsaMessageHashVector>,
+    pub key_derivation_note: String,
+    pub key_derivation_vectors: Vec<KeyDerivationVector>,
+    pub invalid_seed_vectors: Vec<InvalidSeedVector>,
+}
+
+/// An ECDSA vector signing the hash of `message` with `secret_key`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EcdsaSignVector {
+    pub test_name: String,
+    pub secret_key: String,
+    /// Message text (not hex); `message_hash` is its hash.
+    pub message: String,
+    pub message_hash: String,
+    pub signature: String,
+    pub description: String,
+}
+
+/// An ECDSA signature checked against a known verification key.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EcdsaVerifyVector {
+    pub test_name: String,
+    pub verification_key: String,
+    /// Message text (not hex); `message_hash` is its hash.
+    pub message: String,
+    pub message_hash: String,
+    pub signature: String,
+    pub should_verify: bool,
+    pub description: String,
+}
+
+/// An ECDSA input that must be rejected. Which fields are present selects
+/// the check: a raw key or signature that must not parse, a message that is
+/// not a 32-byte hash, or a signature that must not verify.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EcdsaErrorVector {
+    pub test_name: String,
+    pub description: String,
+    pub verification_key: Option<String>,
+    pub verification_key_raw: Option<String>,
+    pub signature_raw: Option<String>,
+    pub should_parse: Option<bool>,
+    /// Message text (not hex).
+    pub message: Option<String>,
+    pub message_hash: Option<String>,
+    pub message_raw: Option<String>,
+    pub signature: Option<String>,
+    pub should_verify: Option<bool>,
+}
+
+/// An ECDSA `hashAndPack` vector.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EcdsaMessageHashVector {
+    pub test_name: String,
+    /// `sha256`, `sha3_256` or `blake2b_256`.
+    pub hash_algorithm: String,
+    pub secret_key: String,
+    pub verification_key: String,
+    pub preimage: String,
+    pub message_hash: String,
+    pub signature: String,
+    pub description: String,
+}
+
+/// A verification key derived from a seed.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeyDerivationVector {
+    pub test_name: String,
+    pub seed: String,
+    pub verification_key: String,
+    pub description: String,
+}
+
+/// A seed key generation must reject.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InvalidSeedVector {
+    pub test_name: String,
+    pub seed: String,
+    pub description: String,
+}
+
+fn visit_key_vectors<'a>(
+    derivations: &'a [KeyDerivationVector],
+    invalid_seeds: &'a [InvalidSeedVector],
+    visit: &mut dyn FnMut(HexField<'a>),
+) {
+    for vector in derivations {
+        let at = vector.test_name.as_str();
+        visit(input(at, "seed", &vector.seed));
+        visit(expected(at, "verification_key", &vector.verification_key));
+    }
+    for vector in invalid_seeds {
+        visit(input(&vector.test_name, "seed", &vector.seed));
+    }
+}
+
+impl VectorFile for EcdsaFile {
+    fn visit_hex<'a>(&'a self, visit: &mut dyn FnMut(HexField<'a>)) {
+        for vector in &self.sign_and_verify_vectors {
+            let at = vector.test_name.as_str();
+            visit(input(at, "secret_key", &vector.secret_key));
+            visit(expected(at, "message_hash", &vector.message_hash));
+            visit(expected(at, "signature", &vector.signature));
+        }
+        for vector in &self.verify_only_vectors {
+            let at = vector.test_name.as_str();
+            visit(input(at, "verification_key", &vector.verification_key));
+            visit(expected(at, "message_hash", &vector.message_hash));
+            visit(input(at, "signature", &vector.signature));
+        }
+        for vector in &self.error_vectors {
+            let at = vector.test_name.as_str();
+            let optional = [
+                ("verification_key", &vector.verification_key),
+                ("verification_key_raw", &vector.verification_key_raw),
+                ("signature_raw", &vector.signature_raw),
+                ("message_hash", &vector.message_hash),
+                ("message_raw", &vector.message_raw),
+                ("signature", &vector.signature),
+            ];
+            for (field, value) in optional {
+                if let Some(value) = value {
+                    visit(input(at, field, value));
+                }
+            }
+        }
+        for vector in &self.message_hash_vectors {
+            let at = vector.test_name.as_str();
+            visit(input(at, "secret_key", &vector.secret_key));
+            visit(input(at, "preimage", &vector.preimage));
+            visit(expected(at, "verification_key", &vector.verification_key));
+            visit(expected(at, "message_hash", &vector.message_hash));
+            visit(expected(at, "signature", &vector.signature));
+        }
+        visit_key_vectors(
+            &self.key_derivation_vectors,
+            &self.invalid_seed_vectors,
+            visit,
+        );
+    }
+}
+
+/// `schnorr_secp256k1_test_vectors.json`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SchnorrFile {
+    pub description: String,
+    pub algorithm: String,
+    pub source: String,
+    pub sign_and_verify_vectors: Vec<SchnorrSignVector>,
+    pub verify_only_vectors: Vec<SchnorrVerifyVector>,
+    pub error_vectors: Vec<SchnorrErrorVector>,
+    pub key_derivation_note: String,
+    pub key_derivation_vectors: Vec<KeyDerivationVector>,
+    pub invalid_seed_vectors: Vec<InvalidSeedVector>,
+}
+
+/// A Schnorr sign-then-verify round trip.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SchnorrSignVector {
+    pub test_name: String,
+    pub secret_key: String,
+    pub message: String,
+    pub description: String,
+}
+
+/// A Schnorr signature checked against a known verification key.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SchnorrVerifyVector {
+    pub test_name: String,
+    pub verification_key: String,
+    pub message: String,
+    pub signature: String,
+    pub should_verify: bool,
+    pub description: String,
+}
+
+/// A Schnorr input that must be rejected.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SchnorrErrorVector {
+    pub test_name: String,
+    pub description: String,
+    pub verification_key: Option<String>,
+    pub signature_raw: Option<String>,
+    pub should_parse: Option<bool>,
+}
+
+impl VectorFile for SchnorrFile {
+    fn visit_hex<'a>(&'a self, visit: &mut dyn FnMut(HexField<'a>)) {
+        for vector in &self.sign_and_verify_vectors {
+            let at = vector.test_name.as_str();
+            visit(input(at, "secret_key", &vector.secret_key));
+            visit(input(at, "message", &vector.message));
+        }
+        for vector in &self.verify_only_vectors {
+            let at = vector.test_name.as_str();
+            visit(input(at, "verification_key", &vector.verification_key));
+            visit(input(at, "message", &vector.message));
+            visit(input(at, "signature", &vector.signature));
+        }
+        for vector in &self.error_vectors {
+            let at = vector.test_name.as_str();
+            if let Some(value) = &vector.verification_key {
+                visit(input(at, "verification_key", value));
+            }
+            if let Some(value) = &vector.signature_raw {
+                visit(input(at, "signature_raw", value));
+            }
+        }
+        visit_key_vectors(
+            &self.key_derivation_vectors,
+            &self.invalid_seed_vectors,
+            visit,
+        );
+    }
+}
+
+/// `single_kes_test_vectors.json`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SingleKesFile {
+    pub description: String,
+    pub algorithm: String,
+    pub source: String,
+    pub vectors: Vec<SingleKesVector>,
+}
+
+/// One `SingleKES` vector.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SingleKesVector {
+    pub test_name: String,
+    pub seed: String,
+    pub message: String,
+    pub period: u64,
+    pub description: String,
+    pub expected: SingleKesExpected,
+}
+
+/// Expected `SingleKES` outputs.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SingleKesExpected {
+    pub verification_key: String,
+    pub signature: String,
+    pub raw_signature: String,
+}
+
+impl VectorFile for SingleKesFile {
+    fn visit_hex<'a>(&'a self, visit: &mut dyn FnMut(HexField<'a>)) {
+        for vector in &self.vectors {
+            let at = vector.test_name.as_str();
+            visit(input(at, "seed", &vector.seed));
+            visit(input(at, "message", &vector.message));
+            visit(expected(
+                at,
+                "verification_key",
+                &vector.expected.verification_key,
+            ));
+            visit(expected(at, "signature", &vector.expected.signature));
+            visit(expected(
+                at,
+                "raw_signature",
+                &vector.expected.raw_signature,
+            ));
+        }
+    }
+}
+
+/// `compact_single_kes_test_vectors.json`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompactSingleKesFile {
+    pub description: String,
+    pub algorithm: String,
+    pub source: String,
+    pub vectors: Vec<CompactSingleKesVector>,
+}
+
+/// One `CompactSingleKES` vector.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompactSingleKesVector {
+    pub test_name: String,
+    pub seed: String,
+    pub message: String,
+    pub period: u64,
+    pub description: String,
+    pub expected: CompactSingleKesExpected,
+}
+
+/// Expected `CompactSingleKES` outputs.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompactSingleKesExpected {
+    pub derived_verification_key: String,
+    pub embedded_verification_key: String,
+    pub signature: String,
+    pub raw_signature: String,
+}
+
+impl VectorFile for CompactSingleKesFile {
+    fn visit_hex<'a>(&'a self, visit: &mut dyn FnMut(HexField<'a>)) {
+        for vector in &self.vectors {
+            let at = vector.test_name.as_str();
+            let outputs = &vector.expected;
+            visit(input(at, "seed", &vector.seed));
+            visit(input(at, "message", &vector.message));
+            visit(expected(
+                at,
+                "derived_verification_key",
+                &outputs.derived_verification_key,
+            ));
+            visit(expected(
+                at,
+                "embedded_verification_key",
+                &outputs.embedded_verification_key,
+            ));
+            visit(expected(at, "signature", &outputs.signature));
+            visit(expected(at, "raw_signature", &outputs.raw_signature));
+        }
+    }
+}
+
+/// `sum_kes_test_vectors.json` and `compact_sum_kes_test_vectors.json`:
+/// signatures at selected periods of each `SumKES` depth.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SumKesFile {
+    pub description: String,
+    pub algorithm: String,
+    pub source: String,
+    pub levels: Vec<SumKesLevel<SumKesTrackedVector>>,
+}
+
+/// `sum_kes_period_evolution_vectors.json` and
+/// `compact_sum_kes_period_evolution_vectors.json`: signatures at every
+/// period of each `SumKES` depth.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SumKesEvolutionFile {
+    pub description: String,
+    pub algorithm: String,
+    pub source: String,
+    pub levels: Vec<SumKesLevel<SumKesEvolutionVector>>,
+}
+
+/// The vectors of one `SumKES` depth.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SumKesLevel<V> {
+    /// Depth of the sum composition (`Sum<level>KES`).
+    pub level: u8,
+    pub total_periods: u64,
+    pub vectors: Vec<V>,
+}
+
+/// A `SumKES` key with signatures at selected periods.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SumKesTrackedVector {
+    pub test_name: String,
+    pub seed: String,
+    pub description: String,
+    pub verification_key: String,
+    pub tracked_periods: Vec<SumKesPeriod>,
+}
+
+/// A `SumKES` key with a signature at every period.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SumKesEvolutionVector {
+    pub test_name: String,
+    pub seed: String,
+    pub description: String,
+    pub verification_key: String,
+    pub periods: Vec<SumKesPeriod>,
+}
+
+/// A `SumKES` signature at one period.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SumKesPeriod {
+    pub period: u64,
+    pub message: String,
+    pub signature: String,
+    pub raw_signature: String,
+}
+
+fn visit_sum_kes<'a>(
+    at: &'a str,
+    seed: &'a str,
+    verification_key: &'a str,
+    periods: &'a [SumKesPeriod],
+    visit: &mut dyn FnMut(HexField<'a>),
+) {
+    visit(input(at, "seed", seed));
+    visit(expected(at, "verification_key", verification_key));
+    for entry in periods {
+        let period = Some(entry.period);
+        visit(HexField {
+            period,
+            ..input(at, "message", &entry.message)
+        });
+        visit(HexField {
+            period,
+            ..expected(at, "signature", &entry.signature)
+        });
+        visit(HexField {
+            period,
+            ..expected(at, "raw_signature", &entry.raw_signature)
+        });
+    }
+}
+
+impl VectorFile for SumKesFile {
+    fn visit_hex<'a>(&'a self, visit: &mut dyn FnMut(HexField<'a>)) {
+        for vector in self.levels.iter().flat_map(|level| &level.vectors) {
+            visit_sum_kes(
+                &vector.test_name,
+                &vector.seed,
+                &vector.verification_key,
+                &vector.tracked_periods,
+                visit,
+            );
+        }
+    }
+}
+
+impl VectorFile for SumKesEvolutionFile {
+    fn visit_hex<'a>(&'a self, visit: &mut dyn FnMut(HexField<'a>)) {
+        for vector in self.levels.iter().flat_map(|level| &level.vectors) {
+            visit_sum_kes(
+                &vector.test_name,
+                &vector.seed,
+                &vector.verification_key,
+                &vector.periods,
+                visit,
+            );
+        }
+    }
+}
+
+/// `bls_sig_aug_test_vectors`: a BLS signature in G1 over
+/// [`SIG_AUG_MESSAGE`](crate::bls12_381::SIG_AUG_MESSAGE), hashed with
+/// [`SIG_AUG_DST`](crate::bls12_381::SIG_AUG_DST) and
+/// [`SIG_AUG_AUG`](crate::bls12_381::SIG_AUG_AUG).
+#[derive(Clone, Debug, Deserialize)]
+pub struct BlsSigAugFile {
+    /// Signature, compressed G1.
+    pub sig: String,
+    /// Public key, compressed G2.
+    pub pk: String,
+}
+
+/// `ec_operations_test_vectors`: G1 and G2 arithmetic on points `p`, `q`,
+/// all compressed: `p + q`, `p - q`, `[s] q` for
+/// [`EC_OPERATIONS_SCALAR`](crate::bls12_381::EC_OPERATIONS_SCALAR) and
+/// `-p`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct BlsEcOperationsFile {
+    pub g1_p: String,
+    pub g1_q: String,
+    pub g1_add: String,
+    pub g1_sub: String,
+    pub g1_mul: String,
+    pub g1_neg: String,
+    pub g2_p: String,
+    pub g2_q: String,
+    pub g2_add: String,
+    pub g2_sub: String,
+    pub g2_mul: String,
+    pub g2_neg: String,
+}
+
+/// `h2c_large_dst`: hash-to-G1 with a domain separation tag over 255 bytes.
+#[derive(Clone, Debug, Deserialize)]
+pub struct BlsH2cLargeDstFile {
+    pub msg: String,
+    pub dst: String,
+    /// Expected point, compressed G1.
+    pub expected: String,
+}
+
+/// `pairing_test_vectors`: bilinearity checks over unrecorded scalars `a`
+/// and `b`; `p` and its multiples are compressed G1 points, `q` and its
+/// multiples compressed G2 points.
+#[derive(Clone, Debug, Deserialize)]
+pub struct BlsPairingFile {
+    pub p: String,
+    pub a_p: String,
+    pub b_p: String,
+    pub apb_p: String,
+    pub axb_p: String,
+    pub q: String,
+    pub a_q: String,
+    pub b_q: String,
+    pub apb_q: String,
+    pub axb_q: String,
+}
+
+/// `serde_test_vectors`: encodings that must be rejected, for the reason
+/// each field names.
+#[derive(Clone, Debug, Deserialize)]
+pub struct BlsSerdeFile {
+    pub g1_uncompressed_not_on_curve: String,
+    pub g1_compressed_not_on_curve: String,
+    pub g1_compressed_not_in_group: String,
+    pub g1_uncompressed_not_in_group: String,
+    pub g2_uncompressed_not_on_curve: String,
+    pub g2_compressed_not_on_curve: String,
+    pub g2_compressed_not_in_group: String,
+    pub g2_uncompressed_not_in_group: String,
+}
+
+impl VectorFile for BlsSigAugFile {
+    fn parse(contents: &str) -> Result<Self, String> {
+        parse_lines(contents)
+    }
+
+    fn visit_hex<'a>(&'a self, visit: &mut dyn FnMut(HexField<'a>)) {
+        visit(expected("", "sig", &self.sig));
+        visit(input("", "pk", &self.pk));
+    }
+}
+
+impl VectorFile for BlsEcOperationsFile {
+    fn parse(contents: &str) -> Result<Self, String> {
+        parse_lines(contents)
+    }
+
+    fn visit_hex<'a>(&'a self, visit: &mut dyn FnMut(HexField<'a>)) {
+        visit(input("", "g1_p", &self.g1_p));
+        visit(input("", "g1_q", &self.g1_q));
+        visit(expected("", "g1_add", &self.g1_add));
+        visit(expected("", "g1_sub", &self.g1_sub));
+        visit(expected("", "g1_mul", &self.g1_mul));
+        visit(expected("", "g1_neg", &self.g1_neg));
+        visit(input("", "g2_p", &self.g2_p));
+        visit(input("", "g2_q", &self.g2_q));
+        visit(expected("", "g2_add", &self.g2_add));
+        visit(expected("", "g2_sub", &self.g2_sub));
+        visit(expected("", "g2_mul", &self.g2_mul));
+        visit(expected("", "g2_neg", &self.g2_neg));
+    }
+}
+
+impl VectorFile for BlsH2cLargeDstFile {
+    fn parse(contents: &str) -> Result<Self, String> {
+        parse_lines(contents)
+    }
+
+    fn visit_hex<'a>(&'a self, visit: &mut dyn FnMut(HexField<'a>)) {
+        visit(input("", "msg", &self.msg));
+        visit(input("", "dst", &self.dst));
+        visit(expected("", "expected", &self.expected));
+    }
+}
+
+impl VectorFile for BlsPairingFile {
+    fn parse(contents: &str) -> Result<Self, String> {
+        parse_lines(contents)
+    }
+
+    fn visit_hex<'a>(&'a self, visit: &mut dyn FnMut(HexField<'a>)) {
+        visit(input("", "p", &self.p));
+        visit(expected("", "a_p", &self.a_p));
+        visit(expected("", "b_p", &self.b_p));
+        visit(expected("", "apb_p", &self.apb_p));
+        visit(expected("", "axb_p", &self.axb_p));
+        visit(input("", "q", &self.q));
+        visit(expected("", "a_q", &self.a_q));
+        visit(expected("", "b_q", &self.b_q));
+        visit(expected("", "apb_q", &self.apb_q));
+        visit(expected("", "axb_q", &self.axb_q));
+    }
+}
+
+impl VectorFile for BlsSerdeFile {
+    fn parse(contents: &str) -> Result<Self, String> {
+        parse_lines(contents)
+    }
+
+    fn visit_hex<'a>(&'a self, visit: &mut dyn FnMut(HexField<'a>)) {
+        let fields = [
+            (
+                "g1_uncompressed_not_on_curve",
+                &self.g1_uncompressed_not_on_curve,
+            ),
+            (
+                "g1_compressed_not_on_curve",
+                &self.g1_compressed_not_on_curve,
+            ),
+            (
+                "g1_compressed_not_in_group",
+                &self.g1_compressed_not_in_group,
+            ),
+            (
+                "g1_uncompressed_not_in_group",
+                &self.g1_uncompressed_not_in_group,
+            ),
+            (
+                "g2_uncompressed_not_on_curve",
+                &self.g2_uncompressed_not_on_curve,
+            ),
+            (
+                "g2_compressed_not_on_curve",
+                &self.g2_compressed_not_on_curve,
+            ),
+            (
+                "g2_compressed_not_in_group",
+                &self.g2_compressed_not_in_group,
+            ),
+            (
+                "g2_uncompressed_not_in_group",
+                &self.g2_uncompressed_not_in_group,
+            ),
+        ];
+        for (field, value) in fields {
+            visit(input("", field, value));
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Schema validation of the embedded vector files.
+//!
+//! [`validate_all`] parses every file in [`vrf::ALL`], [`dsign::ALL`],
+//! [`kes::ALL`] and [`bls12_381::ALL`] against its
+//! [`schema`](crate::schema) type and decodes every hex field. A workspace
+//! test asserts it reports nothing, so a malformed or drifted vector fails
+//! CI as soon as it is added rather than when a consumer happens to skip it.
+
+use std::fmt;
+
+use crate::schema::{
+    BlsEcOperationsFile, BlsH2cLargeDstFile, BlsPairingFile, BlsSerdeFile, BlsSigAugFile,
+    CompactSingleKesFile, EcdsaFile, Ed25519File, FieldRole, SchnorrFile, SingleKesFile,
+    SumKesEvolutionFile, SumKesFile, VectorFile, VrfFile,
+};
+use crate::{bls12_381, dsign, kes, vrf};
+
+/// What is wrong with a vector file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VectorIssueKind {
+    /// The file has a field its schema does not declare.
+    UnknownField {
+        /// Field name.
+        field: String,
+    },
+    /// The file lacks a field its schema requires.
+    MissingField {
+        /// Field name.
+        field: String,
+    },
+    /// An expected output is present but empty.
+    MissingExpected {
+        /// Field name.
+        field: &'static str,
+    },
+    /// A byte field is not an even-length hex string.
+    InvalidHex {
+        /// Field name.
+        field: &'static str,
+    },
+    /// The file does not parse for another reason, or has no schema.
+    Malformed {
+        /// Parser message.
+        message: String,
+    },
+}
+
+/// A problem found in one embedded vector file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VectorIssue {
+    /// File name of the vector.
+    pub file: &'static str,
+    /// Test name and KES period of the offending vector, empty for
+    /// file-level problems.
+    pub location: String,
+    /// What is wrong.
+    pub kind: VectorIssueKind,
+}
+
+impl fmt::Display for VectorIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file)?;
+        if !self.location.is_empty() {
+            write!(f, " ({})", self.location)?;
+        }
+        match &self.kind {
+            VectorIssueKind::UnknownField { field } => write!(f, ": unknown field {field}"),
+            VectorIssueKind::MissingField { field } => write!(f, ": missing field {field}"),
+            VectorIssueKind::MissingExpected { field } => {
+                write!(f, ": expected value {field} is empty")
+            },
+            VectorIssueKind::InvalidHex { field } => write!(f, ": {field} is not hex"),
+            VectorIssueKind::Malformed { message } => write!(f, ": {message}"),
+        }
+    }
+}
+
+/// Classify a serde error message, which names the field between backticks
+/// for unknown and missing fields.
+fn parse_error_kind(message: String) -> VectorIssueKind {
+    let field = |prefix: &str| {
+        message
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.split_once('`'))
+            .map(|(field, _)| field.to_owned())
+    };
+    if let Some(field) = field("unknown field `") {
+        VectorIssueKind::UnknownField { field }
+    } else if let Some(field) = field("missing field `") {
+        VectorIssueKind::MissingField { field }
+    } else {
+        VectorIssueKind::Malformed { message }
+    }
+}
+
+/// Validate `contents` as a file of format `S`.
+///
+/// Returns the schema error if the file does not parse, and otherwise every
+/// empty expected value and undecodable hex field.
+#[must_use]
+pub fn validate<S: VectorFile>(file: &'static str, contents: &str) -> Vec<VectorIssue> {
+    let parsed = match S::parse(contents) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            return vec![VectorIssue {
+                file,
+                location: String::new(),
+                kind: parse_error_kind(message),
+            }];
+        },
+    };
+
+    let mut issues = Vec::new();
+    parsed.visit_hex(&mut |hex| {
+        let kind = if hex.role == FieldRole::Expected && hex.value.is_empty() {
+            VectorIssueKind::MissingExpected { field: hex.field }
+        } else if hex::decode(hex.value).is_err() {
+            VectorIssueKind::InvalidHex { field: hex.field }
+        } else {
+            return;
+        };
+        let location = match hex.period {
+            Some(period) => format!("{} period {period}", hex.location),
+            None => hex.location.to_owned(),
+        };
+        issues.push(VectorIssue {
+            file,
+            location,
+            kind,
+        });
+    });
+    issues
+}
+
+fn no_schema(file: &'static str) -> Vec<VectorIssue> {
+    vec![VectorIssue {
+        file,
+        location: String::new(),
+        kind: VectorIssueKind::Malformed {
+            message: "no schema registered for this file".to_owned(),
+        },
+    }]
+}
+
+/// Validate one embedded DSIGN file against its schema.
+#[must_use]
+pub fn validate_dsign(vector: &dsign::TestVector) -> Vec<VectorIssue> {
+    let (file, contents) = (vector.name, vector.contents);
+    match file {
+        "ed25519_test_vectors.json" => validate::<Ed25519File>(file, contents),
+        "ecdsa_secp256k1_test_vectors.json" => validate::<EcdsaFile>(file, contents),
+        "schnorr_secp256k1_test_vectors.json" => validate::<SchnorrFile>(file, contents),
+        _ => no_schema(file),
+    }
+}
+
+/// Validate one embedded KES file against its schema.
+#[must_use]
+pub fn validate_kes(vector: &kes::TestVector) -> Vec<VectorIssue> {
+    let (file, contents) = (vector.name, vector.contents);
+    match file {
+        "single_kes_test_vectors.json" => validate::<SingleKesFile>(file, contents),
+        "compact_single_kes_test_vectors.json" => validate::<CompactSingleKesFile>(file, contents),
+        "sum_kes_test_vectors.json" | "compact_sum_kes_test_vectors.json" => {
+            validate::<SumKesFile>(file, contents)
+        },
+        "sum_kes_period_evolution_vectors.json"
+        | "compact_sum_kes_period_evolution_vectors.json" => {
+            validate::<SumKesEvolutionFile>(file, contents)
+        },
+        _ => no_schema(file),
+    }
+}
+
+/// Validate one embedded BLS12-381 file against its schema.
+#[must_use]
+pub fn validate_bls12_381(vector: &bls12_381::TestVector) -> Vec<VectorIssue> {
+    let (file, contents) = (vector.name, vector.contents);
+    match file {
+        "bls_sig_aug_test_vectors" => validate::<BlsSigAugFile>(file, contents),
+        "ec_operations_test_vectors" => validate::<BlsEcOperationsFile>(file, contents),
+        "h2c_large_dst" => validate::<BlsH2cLargeDstFile>(file, contents),
+        "pairing_test_vectors" => validate::<BlsPairingFile>(file, contents),
+        "serde_test_vectors" => validate::<BlsSerdeFile>(file, contents),
+        _ => no_schema(file),
+    }
+}
+
+/// Validate every embedded vector file against its [`schema`](crate::schema)
+/// type.
+///
+/// Reports unknown and missing fields, empty expected values and hex fields
+/// that do not decode. An empty result means every fixture is well formed;
+/// it says nothing about whether the implementations reproduce the values,
+/// which the `verify_all` binary checks.
+#[must_use]
+pub fn validate_all() -> Vec<VectorIssue> {
+    let mut issues = Vec::new();
+    for vector in vrf::ALL {
+        issues.extend(validate::<VrfFile>(vector.name, vector.contents));
+    }
+    for vector in dsign::ALL {
+        issues.extend(validate_dsign(vector));
+    }
+    for vector in kes::ALL {
+        issues.extend(validate_kes(vector));
+    }
+    for vector in bls12_381::ALL {
+        issues.extend(validate_bls12_381(vector));
+    }
+    issues
+}
@@ -0,0 +1,103 @@
+//! Structured trace capture through `cardano_test_vectors::debug`.
+
+use std::rc::Rc;
+
+use cardano_test_vectors::debug::{self, DebugRecord, MemorySink};
+use cardano_test_vectors::generate::{self, SINGLE_KES_STEP};
+use cardano_test_vectors::kes;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct SingleKesFile {
+    vectors: Vec<SingleKesVector>,
+}
+
+#[derive(Deserialize)]
+struct SingleKesVector {
+    seed: String,
+    message: String,
+    expected: SingleKesExpected,
+}
+
+#[derive(Deserialize)]
+struct SingleKesExpected {
+    verification_key: String,
+    signature: String,
+}
+
+fn sample_single_kes_vector() -> SingleKesVector {
+    let json = kes::get("single_kes_test_vectors.json").expect("vector file is embedded");
+    let file: SingleKesFile = serde_json::from_str(json).expect("valid JSON structure");
+    file.vectors
+        .into_iter()
+        .nth(2)
+        .expect("at least three SingleKES vectors present")
+}
+
+#[test]
+fn single_kes_generation_records_intermediate_values() {
+    let vector = sample_single_kes_vector();
+    let seed: [u8; 32] = hex::decode(&vector.seed)
+        .expect("seed hex")
+        .try_into()
+        .expect("32-byte seed");
+    let message = hex::decode(&vector.message).expect("message hex");
+
+    let sink = Rc::new(MemorySink::new());
+    let output = {
+        let _guard = debug::register_sink(sink.clone());
+        generate::single_kes(&seed, &message).expect("SingleKES generation")
+    };
+
+    let records = sink.take();
+    let steps: Vec<_> = records
+        .iter()
+        .map(|record| (record.step, record.name, record.bytes.len()))
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            (SINGLE_KES_STEP, "seed", 32),
+            (SINGLE_KES_STEP, "message", message.len()),
+            (SINGLE_KES_STEP, "verification_key", 32),
+            (SINGLE_KES_STEP, "signature", 64),
+        ]
+    );
+
+    let value = |name: &str| -> &DebugRecord {
+        records
+            .iter()
+            .find(|record| record.name == name)
+            .expect("record present")
+    };
+    assert_eq!(value("seed").bytes, seed);
+    assert_eq!(value("verification_key").bytes, output.verification_key);
+    assert_eq!(value("signature").bytes, output.signature);
+    assert_eq!(
+        hex::encode_upper(&output.verification_key),
+        vector.expected.verification_key
+    );
+    assert_eq!(
+        hex::encode_upper(&output.signature),
+        vector.expected.signature
+    );
+}
+
+#[test]
+fn sink_registration_is_scoped_and_nested() {
+    let outer = Rc::new(MemorySink::new());
+    let inner = Rc::new(MemorySink::new());
+
+    let _outer_guard = debug::register_sink(outer.clone());
+    debug::record("scope", "outer_before", &[1]);
+    {
+        let _inner_guard = debug::register_sink(inner.clone());
+        debug::record("scope", "inner", &[2, 3]);
+    }
+    debug::record("scope", "outer_after", &[4]);
+
+    let names =
+        |sink: &MemorySink| -> Vec<_> { sink.records().iter().map(|record| record.name).collect() };
+    assert_eq!(names(&outer), ["outer_before", "outer_after"]);
+    assert_eq!(names(&inner), ["inner"]);
+}
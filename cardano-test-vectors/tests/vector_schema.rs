@@ -0,0 +1,197 @@
+//! Every embedded fixture matches its strict schema, and the validator
+//! reports each class of defect it is meant to catch.
+
+use cardano_test_vectors::schema::{BlsSigAugFile, Ed25519File, VectorFile, VrfFile};
+use cardano_test_vectors::validate::validate;
+use cardano_test_vectors::{VectorIssue, VectorIssueKind, bls12_381, dsign, validate_all, vrf};
+
+const ED25519: &str = "ed25519_test_vectors.json";
+
+fn ed25519_with(vector: &str) -> String {
+    format!(
+        r#"{{"description": "d", "algorithm": "Ed25519", "source": "s", "vectors": [{vector}]}}"#
+    )
+}
+
+fn kinds(issues: &[VectorIssue]) -> Vec<&VectorIssueKind> {
+    issues.iter().map(|issue| &issue.kind).collect()
+}
+
+#[test]
+fn embedded_vectors_have_no_issues() {
+    let issues = validate_all();
+    let report: Vec<String> = issues.iter().map(ToString::to_string).collect();
+    assert!(issues.is_empty(), "vector issues:\n{}", report.join("\n"));
+}
+
+#[test]
+fn embedded_vectors_parse_into_their_schemas() {
+    let ed25519 =
+        Ed25519File::parse(dsign::get(ED25519).expect("embedded")).expect("Ed25519 vectors parse");
+    assert_eq!(ed25519.vectors.len(), 7);
+
+    let draft03 = VrfFile::parse(vrf::get("vrf_ver03_standard_10").expect("embedded"))
+        .expect("VRF vector parses");
+    assert_eq!(draft03.ver, "ietfdraft03");
+    assert_eq!(draft03.alpha, "empty");
+}
+
+fn widths<'a>(fields: impl IntoIterator<Item = &'a String>) -> Vec<usize> {
+    fields
+        .into_iter()
+        .map(|field| hex::decode(field).expect("hex line").len())
+        .collect()
+}
+
+#[test]
+fn bls12_381_vectors_parse_into_their_schemas() {
+    let sig_aug = bls12_381::sig_aug().expect("sig aug vectors parse");
+    assert_eq!(widths([&sig_aug.sig, &sig_aug.pk]), [48, 96]);
+
+    let ops = bls12_381::ec_operations().expect("ec operation vectors parse");
+    assert_eq!(
+        widths([
+            &ops.g1_p,
+            &ops.g1_q,
+            &ops.g1_add,
+            &ops.g1_sub,
+            &ops.g1_mul,
+            &ops.g1_neg,
+        ]),
+        [48; 6]
+    );
+    assert_eq!(
+        widths([
+            &ops.g2_p,
+            &ops.g2_q,
+            &ops.g2_add,
+            &ops.g2_sub,
+            &ops.g2_mul,
+            &ops.g2_neg,
+        ]),
+        [96; 6]
+    );
+    assert_eq!(widths([&bls12_381::EC_OPERATIONS_SCALAR.to_owned()]), [32]);
+
+    let h2c = bls12_381::h2c_large_dst().expect("h2c vectors parse");
+    let dst_len = widths([&h2c.dst])[0];
+    assert!(dst_len > 255, "DST of {dst_len} bytes");
+    assert_eq!(widths([&h2c.expected]), [48]);
+
+    let pairing = bls12_381::pairing().expect("pairing vectors parse");
+    assert_eq!(
+        widths([
+            &pairing.p,
+            &pairing.a_p,
+            &pairing.b_p,
+            &pairing.apb_p,
+            &pairing.axb_p,
+        ]),
+        [48; 5]
+    );
+    assert_eq!(
+        widths([
+            &pairing.q,
+            &pairing.a_q,
+            &pairing.b_q,
+            &pairing.apb_q,
+            &pairing.axb_q,
+        ]),
+        [96; 5]
+    );
+
+    let serde = bls12_381::serde().expect("serde vectors parse");
+    assert_eq!(
+        widths([
+            &serde.g1_uncompressed_not_on_curve,
+            &serde.g1_compressed_not_on_curve,
+            &serde.g1_compressed_not_in_group,
+            &serde.g1_uncompressed_not_in_group,
+            &serde.g2_uncompressed_not_on_curve,
+            &serde.g2_compressed_not_on_curve,
+            &serde.g2_compressed_not_in_group,
+            &serde.g2_uncompressed_not_in_group,
+        ]),
+        [96, 48, 48, 96, 192, 96, 96, 192]
+    );
+}
+
+#[test]
+fn unknown_json_fields_are_reported() {
+    let contents = ed25519_with(
+        r#"{"test_name": "t", "seed": "00", "message": "", "expected_public_key": "01",
+            "expected_signature": "02", "description": "d", "expected_vk_cbor": "03"}"#,
+    );
+    let issues = validate::<Ed25519File>(ED25519, &contents);
+    assert_eq!(
+        kinds(&issues),
+        [&VectorIssueKind::UnknownField {
+            field: "expected_vk_cbor".to_owned()
+        }]
+    );
+}
+
+#[test]
+fn missing_and_empty_expected_values_are_reported() {
+    let missing = ed25519_with(
+        r#"{"test_name": "t", "seed": "00", "message": "", "expected_public_key": "01",
+            "description": "d"}"#,
+    );
+    assert_eq!(
+        kinds(&validate::<Ed25519File>(ED25519, &missing)),
+        [&VectorIssueKind::MissingField {
+            field: "expected_signature".to_owned()
+        }]
+    );
+
+    let empty = ed25519_with(
+        r#"{"test_name": "t", "seed": "00", "message": "", "expected_public_key": "01",
+            "expected_signature": "", "description": "d"}"#,
+    );
+    let issues = validate::<Ed25519File>(ED25519, &empty);
+    assert_eq!(
+        kinds(&issues),
+        [&VectorIssueKind::MissingExpected {
+            field: "expected_signature"
+        }]
+    );
+    assert_eq!(issues[0].location, "t");
+}
+
+#[test]
+fn invalid_hex_is_reported_per_field() {
+    let contents = ed25519_with(
+        r#"{"test_name": "t", "seed": "0g", "message": "abc", "expected_public_key": "01",
+            "expected_signature": "02", "description": "d"}"#,
+    );
+    assert_eq!(
+        kinds(&validate::<Ed25519File>(ED25519, &contents)),
+        [
+            &VectorIssueKind::InvalidHex { field: "seed" },
+            &VectorIssueKind::InvalidHex { field: "message" },
+        ]
+    );
+}
+
+#[test]
+fn text_formats_are_strict() {
+    let vrf = "vrf: PraosVRF\nver: ietfdraft03\nciphersuite: c\nsk: 00\npk: 00\nalpha: empty\n\
+               pi: 00\nbeta: 00\nextra: 00\n";
+    assert_eq!(
+        kinds(&validate::<VrfFile>("vrf", vrf)),
+        [&VectorIssueKind::UnknownField {
+            field: "extra".to_owned()
+        }]
+    );
+    assert!(validate::<VrfFile>("vrf", &vrf.replace("extra: 00\n", "")).is_empty());
+
+    assert!(validate::<BlsSigAugFile>("bls", "00\n01\n").is_empty());
+    assert!(matches!(
+        kinds(&validate::<BlsSigAugFile>("bls", "00\n01\n02\n"))[..],
+        [VectorIssueKind::Malformed { .. }]
+    ));
+    assert_eq!(
+        kinds(&validate::<BlsSigAugFile>("bls", "zz\n01\n")),
+        [&VectorIssueKind::InvalidHex { field: "sig" }]
+    );
+}
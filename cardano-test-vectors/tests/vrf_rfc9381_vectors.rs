@@ -0,0 +1,96 @@
+//! RFC 9381 ECVRF-EDWARDS25519-SHA512-ELL2 against the RFC's Appendix B.3
+//! examples, and the legacy draft suites against every embedded vector so
+//! the new suite cannot disturb them.
+
+use cardano_test_vectors::vrf::{self, VrfVector};
+use cardano_vrf_pure::{VrfDraft03, VrfDraft13, VrfRfc9381, draft03, draft13, rfc9381};
+
+fn array<const N: usize>(bytes: &[u8], vector: &VrfVector) -> [u8; N] {
+    assert_eq!(bytes.len(), N, "{}", vector.name);
+    let mut out = [0u8; N];
+    out.copy_from_slice(bytes);
+    out
+}
+
+fn vectors(prefix: &str) -> Vec<VrfVector> {
+    let vectors = vrf::parse_with_prefix(prefix).expect("embedded vectors parse");
+    assert!(!vectors.is_empty(), "no vectors with prefix {prefix}");
+    vectors
+}
+
+#[test]
+fn rfc9381_appendix_vectors() {
+    let vectors = vectors(vrf::RFC9381_PREFIX);
+    assert_eq!(vectors.len(), 3);
+    for vector in &vectors {
+        assert_eq!(vector.version, "rfc9381");
+        let seed = array::<{ rfc9381::SEED_SIZE }>(&vector.secret_key, vector);
+        let (sk, pk) = VrfRfc9381::keypair_from_seed(&seed);
+        assert_eq!(pk.as_slice(), vector.public_key, "{}", vector.name);
+
+        let proof = VrfRfc9381::prove(&sk, &vector.alpha).expect("prove");
+        assert_eq!(proof.as_slice(), vector.proof, "{}", vector.name);
+        let output = VrfRfc9381::verify(&pk, &proof, &vector.alpha).expect("verify");
+        assert_eq!(output.as_slice(), vector.output, "{}", vector.name);
+        assert_eq!(VrfRfc9381::proof_to_hash(&proof).expect("hash"), output);
+
+        let mut tampered = proof;
+        tampered[32] ^= 1;
+        assert!(VrfRfc9381::verify(&pk, &tampered, &vector.alpha).is_err());
+    }
+}
+
+#[test]
+fn rfc9381_matches_draft13_gamma_s_and_output() {
+    // RFC 9381 examples 16-18 use the keys and messages of the draft-13
+    // standard vectors 10-12; only the proof encoding differs.
+    let rfc = vectors(vrf::RFC9381_PREFIX);
+    let draft = vectors("vrf_ver13_standard_");
+    assert_eq!(rfc.len(), draft.len());
+    for (rfc, draft) in rfc.iter().zip(&draft) {
+        assert_eq!(rfc.secret_key, draft.secret_key);
+        assert_eq!(rfc.alpha, draft.alpha);
+        assert_eq!(rfc.proof[..32], draft.proof[..32], "{}: Gamma", rfc.name);
+        assert_eq!(rfc.proof[48..], draft.proof[96..], "{}: s", rfc.name);
+        assert_eq!(rfc.output, draft.output);
+    }
+}
+
+#[test]
+fn draft13_vectors_unchanged() {
+    for vector in vectors("vrf_ver13_") {
+        let pk = array::<{ draft13::PUBLIC_KEY_SIZE }>(&vector.public_key, &vector);
+        let mut sk = [0u8; draft13::SECRET_KEY_SIZE];
+        sk[..32].copy_from_slice(&vector.secret_key);
+        sk[32..].copy_from_slice(&pk);
+
+        let proof = VrfDraft13::prove(&sk, &vector.alpha).expect("prove");
+        assert_eq!(proof.as_slice(), vector.proof, "{}", vector.name);
+        let output = VrfDraft13::verify(&pk, &proof, &vector.alpha).expect("verify");
+        assert_eq!(output.as_slice(), vector.output, "{}", vector.name);
+    }
+}
+
+#[test]
+fn draft03_vectors_unchanged() {
+    for vector in vectors("vrf_ver03_") {
+        let pk = array::<{ draft03::PUBLIC_KEY_SIZE }>(&vector.public_key, &vector);
+        let mut sk = [0u8; draft03::SECRET_KEY_SIZE];
+        sk[..32].copy_from_slice(&vector.secret_key);
+        sk[32..].copy_from_slice(&pk);
+
+        let proof = VrfDraft03::prove(&sk, &vector.alpha).expect("prove");
+        assert_eq!(proof.as_slice(), vector.proof, "{}", vector.name);
+        let output = VrfDraft03::verify(&pk, &proof, &vector.alpha).expect("verify");
+        assert_eq!(output.as_slice(), vector.output, "{}", vector.name);
+    }
+}
+
+#[test]
+fn every_embedded_vector_parses() {
+    for vector in vrf::ALL {
+        let parsed = vector.parse().expect("vector parses");
+        assert_eq!(parsed.name, vector.name);
+        assert_eq!(parsed.output.len(), 64, "{}", vector.name);
+    }
+}
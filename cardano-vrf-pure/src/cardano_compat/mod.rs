@@ -26,8 +26,8 @@ pub mod prove;
 pub mod verify;
 
 // Re-export main API
-pub use prove::cardano_vrf_prove;
-pub use verify::cardano_vrf_verify;
+pub use prove::{cardano_vrf_expand_secret, cardano_vrf_prove, cardano_vrf_prove_expanded};
+pub use verify::{PreparedPublicKey, cardano_vrf_verify, cardano_vrf_verify_prepared};
 
 #[cfg(test)]
 mod tests;
@@ -47,25 +47,54 @@ use crate::{
 ///
 /// Returns error if hash-to-curve fails or key is invalid
 pub fn cardano_vrf_prove(secret_key: &[u8; 64], message: &[u8]) -> VrfResult<[u8; 80]> {
-    // Step 1: Expand secret key
+    let az = cardano_vrf_expand_secret(secret_key);
+    let pk: &[u8; 32] = secret_key[32..64]
+        .try_into()
+        .expect("public key slice must be 32 bytes");
+    cardano_vrf_prove_expanded(&az, pk, message)
+}
+
+/// Steps 1 and 2 of [`cardano_vrf_prove`]: the clamped SHA-512 expansion of
+/// the seed half of `secret_key`.
+///
+/// The first 32 bytes are the secret scalar, the last 32 the nonce prefix.
+/// Callers producing many proofs with one key can expand once and use
+/// [`cardano_vrf_prove_expanded`].
+#[must_use]
+pub fn cardano_vrf_expand_secret(secret_key: &[u8; 64]) -> Zeroizing<[u8; 64]> {
     let mut az = Zeroizing::new([0u8; 64]);
     let mut hasher = Sha512::new();
     hasher.update(&secret_key[0..32]);
     let hash = hasher.finalize();
     az.copy_from_slice(&hash);
 
-    // Step 2: Clamp scalar (same as Ed25519)
+    // Clamp scalar (same as Ed25519)
     az[0] &= 248;
     az[31] &= 127;
     az[31] |= 64;
+    az
+}
 
+/// [`cardano_vrf_prove`] from an already expanded secret (steps 3 to 9).
+///
+/// `expanded` must come from [`cardano_vrf_expand_secret`] and `public_key`
+/// is the public half of the same secret key; the proof is then
+/// byte-identical to [`cardano_vrf_prove`].
+///
+/// # Errors
+///
+/// Returns error if hash-to-curve fails
+pub fn cardano_vrf_prove_expanded(
+    expanded: &[u8; 64],
+    public_key: &[u8; 32],
+    message: &[u8],
+) -> VrfResult<[u8; 80]> {
+    let az = expanded;
     let secret_scalar_bytes: [u8; 32] = az[0..32]
         .try_into()
         .expect("secret key slice must be 32 bytes");
     let x = Scalar::from_bytes_mod_order(secret_scalar_bytes);
-
-    // Extract public key
-    let pk = &secret_key[32..64];
+    let pk = public_key;
 
     // Step 3: Compute H = hash_to_curve(suite || 0x01 || pk || message)
     let mut h_hasher = Sha512::new();
@@ -3,8 +3,12 @@
 //! This module implements VRF proof verification matching Cardano's libsodium
 //! implementation byte-for-byte.
 
+use std::fmt;
+
 use curve25519_dalek::{
-    constants::ED25519_BASEPOINT_TABLE, edwards::CompressedEdwardsY, scalar::Scalar,
+    constants::ED25519_BASEPOINT_TABLE,
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar,
 };
 use sha2::{Digest, Sha512};
 
@@ -16,6 +20,50 @@ use crate::{
 
 // Suite / marker constants imported from common.rs to avoid duplication
 
+/// A VRF public key decompressed once for repeated verification.
+///
+/// [`cardano_vrf_verify`] decompresses the 32-byte key on every call;
+/// [`cardano_vrf_verify_prepared`] takes the point from here instead and
+/// returns exactly what [`cardano_vrf_verify`] returns for the same bytes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct PreparedPublicKey {
+    bytes: [u8; 32],
+    point: EdwardsPoint,
+}
+
+impl PreparedPublicKey {
+    /// Decompress `public_key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VrfError::InvalidPublicKey`] if the bytes do not encode a
+    /// curve point, which is the error [`cardano_vrf_verify`] reports for
+    /// such a key.
+    pub fn new(public_key: &[u8; 32]) -> VrfResult<Self> {
+        let point = CompressedEdwardsY(*public_key)
+            .decompress()
+            .ok_or(VrfError::InvalidPublicKey)?;
+        Ok(Self {
+            bytes: *public_key,
+            point,
+        })
+    }
+
+    /// The 32-byte encoding the key was prepared from.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.bytes
+    }
+}
+
+impl fmt::Debug for PreparedPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PreparedPublicKey")
+            .field(&self.bytes)
+            .finish()
+    }
+}
+
 /// Verify VRF proof using Cardano-compatible method
 ///
 /// Verifies a VRF proof and returns the VRF output if valid.
@@ -47,6 +95,22 @@ pub fn cardano_vrf_verify(
     public_key: &[u8; 32],
     proof: &[u8; 80],
     message: &[u8],
+) -> VrfResult<[u8; 64]> {
+    cardano_vrf_verify_prepared(&PreparedPublicKey::new(public_key)?, proof, message)
+}
+
+/// Verify VRF proof against a key decompressed by [`PreparedPublicKey::new`]
+///
+/// Skips the public key decompression of [`cardano_vrf_verify`]; the result is
+/// otherwise identical.
+///
+/// # Errors
+///
+/// Returns error if proof is invalid, point decompression fails, or hash-to-curve fails
+pub fn cardano_vrf_verify_prepared(
+    public_key: &PreparedPublicKey,
+    proof: &[u8; 80],
+    message: &[u8],
 ) -> VrfResult<[u8; 64]> {
     // Step 1: Parse proof components
     let gamma_bytes: [u8; 32] = proof[0..32]
@@ -59,10 +123,8 @@ pub fn cardano_vrf_verify(
         .try_into()
         .expect("VRF proof scalar segment must be 32 bytes");
 
-    // Parse public key
-    let y_point = CompressedEdwardsY(*public_key)
-        .decompress()
-        .ok_or(VrfError::InvalidPublicKey)?;
+    // Public key, decompressed when it was prepared
+    let y_point = public_key.point;
 
     // Parse Gamma
     let gamma = CompressedEdwardsY(gamma_bytes)
@@ -82,7 +144,7 @@ pub fn cardano_vrf_verify(
     let mut h_hasher = Sha512::new();
     h_hasher.update(&[SUITE_DRAFT03]);
     h_hasher.update(&[ONE]);
-    h_hasher.update(public_key);
+    h_hasher.update(public_key.as_bytes());
     h_hasher.update(message);
     let r_string = h_hasher.finalize();
 
@@ -151,6 +213,38 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_prepared_key_matches_unprepared() {
+        let (sk, pk) = crate::VrfDraft03::keypair_from_seed(&[5u8; 32]);
+        let prepared = PreparedPublicKey::new(&pk).unwrap();
+        assert_eq!(prepared.as_bytes(), &pk);
+
+        let proof = super::super::cardano_vrf_prove(&sk, b"slot").unwrap();
+        let output = cardano_vrf_verify(&pk, &proof, b"slot").unwrap();
+        assert_eq!(
+            cardano_vrf_verify_prepared(&prepared, &proof, b"slot"),
+            Ok(output)
+        );
+        for (proof, message) in [(proof, &b"other"[..]), ([0u8; 80], &b"slot"[..])] {
+            assert_eq!(
+                cardano_vrf_verify_prepared(&prepared, &proof, message),
+                cardano_vrf_verify(&pk, &proof, message)
+            );
+        }
+    }
+
+    #[test]
+    fn test_prepare_rejects_non_points() {
+        // y = 2 has no matching x on the curve.
+        let mut pk = [0u8; 32];
+        pk[0] = 2;
+        assert_eq!(PreparedPublicKey::new(&pk), Err(VrfError::InvalidPublicKey));
+        assert_eq!(
+            cardano_vrf_verify(&pk, &[0u8; 80], b"test"),
+            Err(VrfError::InvalidPublicKey)
+        );
+    }
+
     #[test]
     fn test_challenge_reconstruction() {
         // Verify challenge bytes are correctly padded
@@ -17,6 +17,9 @@ pub const SUITE_DRAFT03: u8 = 0x04;
 /// Note: Cardano uses the same suite ID (0x04) for both draft-03 and draft-13
 pub const SUITE_DRAFT13: u8 = 0x04;
 
+/// Suite identifier for RFC 9381 ECVRF-EDWARDS25519-SHA512-ELL2
+pub const SUITE_RFC9381: u8 = 0x04;
+
 /// Constant byte value 0x01 used in hash computations
 pub const ONE: u8 = 0x01;
 /// Constant byte value 0x02 used in hash computations
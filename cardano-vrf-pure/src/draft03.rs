@@ -6,11 +6,17 @@
 #![allow(clippy::unwrap_used)]
 
 use crate::VrfResult;
-use crate::cardano_compat::{cardano_vrf_prove, cardano_vrf_verify, point::cardano_clear_cofactor};
+use crate::cardano_compat::{
+    cardano_vrf_expand_secret, cardano_vrf_prove, cardano_vrf_prove_expanded, cardano_vrf_verify,
+    cardano_vrf_verify_prepared, point::cardano_clear_cofactor,
+};
 use crate::common::{
     SUITE_DRAFT03, THREE, bytes_to_point, point_to_bytes, secret_key_to_public, seed_to_secret_key,
 };
 use sha2::{Digest, Sha512};
+use zeroize::Zeroizing;
+
+pub use crate::cardano_compat::PreparedPublicKey;
 
 /// VRF proof size for draft-03 (80 bytes)
 pub const PROOF_SIZE: usize = 80;
@@ -55,6 +61,30 @@ impl VrfDraft03 {
         cardano_vrf_prove(secret_key, message)
     }
 
+    /// Expand the seed half of `secret_key` into the clamped secret scalar
+    /// and nonce prefix used by [`VrfDraft03::prove_expanded`].
+    #[must_use]
+    pub fn expand_secret_key(secret_key: &[u8; SECRET_KEY_SIZE]) -> Zeroizing<[u8; 64]> {
+        cardano_vrf_expand_secret(secret_key)
+    }
+
+    /// Generate a VRF proof from a secret expanded once with
+    /// [`VrfDraft03::expand_secret_key`].
+    ///
+    /// The proof is byte-identical to [`VrfDraft03::prove`] with the secret key
+    /// whose seed was expanded and whose public half is `public_key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VrfError` if the proof generation fails.
+    pub fn prove_expanded(
+        expanded: &[u8; 64],
+        public_key: &[u8; PUBLIC_KEY_SIZE],
+        message: &[u8],
+    ) -> VrfResult<[u8; PROOF_SIZE]> {
+        cardano_vrf_prove_expanded(expanded, public_key, message)
+    }
+
     /// Verify a VRF proof and return the output
     ///
     /// # Arguments
@@ -72,6 +102,32 @@ impl VrfDraft03 {
         cardano_vrf_verify(public_key, proof, message)
     }
 
+    /// Decompress `public_key` once for use with
+    /// [`VrfDraft03::verify_prepared`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `VrfError::InvalidPublicKey` if the bytes are not a curve point.
+    pub fn prepare_public_key(public_key: &[u8; PUBLIC_KEY_SIZE]) -> VrfResult<PreparedPublicKey> {
+        PreparedPublicKey::new(public_key)
+    }
+
+    /// Verify a VRF proof against a prepared public key and return the output
+    ///
+    /// Gives the same result as [`VrfDraft03::verify`] with the bytes the key
+    /// was prepared from, without decompressing the key again.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VrfError` if the proof is invalid.
+    pub fn verify_prepared(
+        public_key: &PreparedPublicKey,
+        proof: &[u8; PROOF_SIZE],
+        message: &[u8],
+    ) -> VrfResult<[u8; OUTPUT_SIZE]> {
+        cardano_vrf_verify_prepared(public_key, proof, message)
+    }
+
     /// Convert a proof to VRF output hash
     ///
     /// # Arguments
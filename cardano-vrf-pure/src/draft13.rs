@@ -44,7 +44,15 @@ impl VrfDraft13 {
         secret_key: &[u8; SECRET_KEY_SIZE],
         message: &[u8],
     ) -> VrfResult<[u8; PROOF_SIZE]> {
-        // Expand the secret key
+        let az = Self::expand_secret_key(secret_key);
+        let pk: &[u8; PUBLIC_KEY_SIZE] = secret_key[32..64].try_into().unwrap();
+        Self::prove_expanded(&az, pk, message)
+    }
+
+    /// Expand the seed half of `secret_key` into the clamped secret scalar
+    /// and nonce prefix used by [`VrfDraft13::prove_expanded`].
+    #[must_use]
+    pub fn expand_secret_key(secret_key: &[u8; SECRET_KEY_SIZE]) -> Zeroizing<[u8; 64]> {
         let mut az = Zeroizing::new([0u8; 64]);
         let mut hasher = Sha512::new();
         hasher.update(&secret_key[0..32]); // Hash the seed part
@@ -55,11 +63,26 @@ impl VrfDraft13 {
         az[0] &= 248;
         az[31] &= 127;
         az[31] |= 64;
+        az
+    }
 
+    /// Generate a VRF proof from a secret expanded once with
+    /// [`VrfDraft13::expand_secret_key`].
+    ///
+    /// The proof is byte-identical to [`VrfDraft13::prove`] with the secret key
+    /// whose seed was expanded and whose public half is `public_key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VrfError` if hashing to the curve fails.
+    pub fn prove_expanded(
+        expanded: &[u8; 64],
+        public_key: &[u8; PUBLIC_KEY_SIZE],
+        message: &[u8],
+    ) -> VrfResult<[u8; PROOF_SIZE]> {
+        let az = expanded;
         let x = Scalar::from_bytes_mod_order(az[0..32].try_into().unwrap());
-
-        // Extract public key
-        let pk = &secret_key[32..64];
+        let pk = public_key;
 
         let (h_point, h_string) = cardano_hash_to_curve_draft13(pk, message)?;
 
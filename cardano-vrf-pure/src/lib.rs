@@ -4,6 +4,7 @@
 //! following the IETF specifications:
 //! - ECVRF-ED25519-SHA512-Elligator2 (draft-03)
 //! - ECVRF-ED25519-SHA512-TAI (draft-13 batch-compatible)
+//! - ECVRF-EDWARDS25519-SHA512-ELL2 (RFC 9381, the final specification)
 //!
 //! All implementations are memory-safe and use constant-time operations where appropriate
 //! to prevent timing attacks.
@@ -28,9 +29,11 @@ pub mod cardano_compat;
 pub mod common;
 pub mod draft03;
 pub mod draft13;
+pub mod rfc9381;
 
-pub use draft03::VrfDraft03;
+pub use draft03::{PreparedPublicKey, VrfDraft03};
 pub use draft13::VrfDraft13;
+pub use rfc9381::VrfRfc9381;
 
 /// Error types for VRF operations
 #[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
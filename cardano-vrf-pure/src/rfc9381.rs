@@ -0,0 +1,382 @@
+//! VRF implementation following RFC 9381, the final IETF VRF specification
+//!
+//! This implements the ECVRF-EDWARDS25519-SHA512-ELL2 cipher suite (suite
+//! string `0x04`) with the standard 80-byte proof `Gamma || c || s`.
+//!
+//! Compared with the legacy drafts Cardano uses:
+//! - draft-03 hashes `suite || 0x01 || pk || alpha` once and maps it with
+//!   Elligator2, leaves the public key out of the challenge and omits the
+//!   trailing `0x00` in the challenge and output hashes;
+//! - draft-13 (as shipped by Cardano) already uses RFC 9381's
+//!   `encode_to_curve` (hash-to-curve with DST
+//!   `ECVRF_edwards25519_XMD:SHA-512_ELL2_NU_\x04` and the public key as
+//!   salt), nonce and challenge, but encodes proofs in the 128-byte
+//!   batch-compatible form `Gamma || U || V || s`.
+//!
+//! An RFC 9381 proof therefore shares `Gamma`, `s` and the output with the
+//! draft-13 proof for the same key and message; only the encoding differs.
+//! [`VrfDraft03`](crate::VrfDraft03) and [`VrfDraft13`](crate::VrfDraft13)
+//! are unaffected by this module.
+
+use curve25519_dalek::{edwards::EdwardsPoint, scalar::Scalar, traits::VartimeMultiscalarMul};
+use sha2::{Digest, Sha512};
+use zeroize::Zeroizing;
+
+use crate::cardano_compat::point::{cardano_clear_cofactor, cardano_hash_to_curve_draft13};
+use crate::common::{
+    SUITE_RFC9381, THREE, TWO, bytes_to_point, has_small_order, point_to_bytes, seed_to_secret_key,
+    verify_16,
+};
+use crate::{VrfError, VrfResult};
+
+/// VRF proof size for RFC 9381 (80 bytes)
+pub const PROOF_SIZE: usize = 80;
+
+/// Public key size (32 bytes)
+pub const PUBLIC_KEY_SIZE: usize = 32;
+
+/// Secret key size (64 bytes: 32-byte seed + 32-byte public key)
+pub const SECRET_KEY_SIZE: usize = 64;
+
+/// Seed size (32 bytes)
+pub const SEED_SIZE: usize = 32;
+
+/// Output size (64 bytes)
+pub const OUTPUT_SIZE: usize = 64;
+
+/// Challenge size (16 bytes, `cLen` in the RFC)
+const CHALLENGE_SIZE: usize = 16;
+
+/// VRF RFC 9381 implementation
+#[derive(Clone)]
+pub struct VrfRfc9381;
+
+/// A proof split into its three components (RFC 9381 `decode_proof`).
+struct DecodedProof {
+    gamma: EdwardsPoint,
+    gamma_bytes: [u8; 32],
+    c: [u8; CHALLENGE_SIZE],
+    s: Scalar,
+}
+
+impl VrfRfc9381 {
+    /// Generate a VRF proof
+    ///
+    /// # Arguments
+    /// * `secret_key` - 64-byte secret key (32-byte seed + 32-byte public key)
+    /// * `message` - Message to prove
+    ///
+    /// # Returns
+    /// 80-byte proof
+    ///
+    /// # Errors
+    ///
+    /// Returns `VrfError` if hashing to the curve fails.
+    pub fn prove(
+        secret_key: &[u8; SECRET_KEY_SIZE],
+        message: &[u8],
+    ) -> VrfResult<[u8; PROOF_SIZE]> {
+        let az = Self::expand_secret_key(secret_key);
+        let mut pk = [0u8; PUBLIC_KEY_SIZE];
+        pk.copy_from_slice(&secret_key[32..64]);
+        Self::prove_expanded(&az, &pk, message)
+    }
+
+    /// Expand the seed half of `secret_key` into the clamped secret scalar
+    /// and nonce prefix used by [`VrfRfc9381::prove_expanded`].
+    #[must_use]
+    pub fn expand_secret_key(secret_key: &[u8; SECRET_KEY_SIZE]) -> Zeroizing<[u8; 64]> {
+        let mut az = Zeroizing::new([0u8; 64]);
+        az.copy_from_slice(&Sha512::digest(&secret_key[0..32]));
+        az[0] &= 248;
+        az[31] &= 127;
+        az[31] |= 64;
+        az
+    }
+
+    /// Generate a VRF proof from a secret expanded once with
+    /// [`VrfRfc9381::expand_secret_key`].
+    ///
+    /// The proof is byte-identical to [`VrfRfc9381::prove`] with the secret
+    /// key whose seed was expanded and whose public half is `public_key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VrfError` if hashing to the curve fails.
+    pub fn prove_expanded(
+        expanded: &[u8; 64],
+        public_key: &[u8; PUBLIC_KEY_SIZE],
+        message: &[u8],
+    ) -> VrfResult<[u8; PROOF_SIZE]> {
+        let mut x_bytes = Zeroizing::new([0u8; 32]);
+        x_bytes.copy_from_slice(&expanded[0..32]);
+        let x = Scalar::from_bytes_mod_order(*x_bytes);
+
+        // H = encode_to_curve(salt = PK, alpha)
+        let (h_point, h_string) = cardano_hash_to_curve_draft13(public_key, message)?;
+        let gamma = h_point * x;
+
+        // Nonce (RFC 9381 section 5.4.2.2): k = SHA-512(az[32..64] || H) mod q
+        let mut nonce_hasher = Sha512::new();
+        nonce_hasher.update(&expanded[32..64]);
+        nonce_hasher.update(h_string);
+        let mut nonce_hash = Zeroizing::new([0u8; 64]);
+        nonce_hash.copy_from_slice(&nonce_hasher.finalize());
+        let k = Scalar::from_bytes_mod_order_wide(&nonce_hash);
+
+        let gamma_bytes = point_to_bytes(&gamma);
+        let c = challenge(
+            public_key,
+            &h_string,
+            &gamma_bytes,
+            &point_to_bytes(&EdwardsPoint::mul_base(&k)),
+            &point_to_bytes(&(h_point * k)),
+        );
+        let s = k + challenge_scalar(&c) * x;
+
+        let mut proof = [0u8; PROOF_SIZE];
+        proof[0..32].copy_from_slice(&gamma_bytes);
+        proof[32..48].copy_from_slice(&c);
+        proof[48..80].copy_from_slice(s.as_bytes());
+        Ok(proof)
+    }
+
+    /// Verify a VRF proof and return the output
+    ///
+    /// # Arguments
+    /// * `public_key` - 32-byte public key
+    /// * `proof` - 80-byte proof
+    /// * `message` - Message that was proven
+    ///
+    /// # Returns
+    /// 64-byte VRF output on success
+    ///
+    /// # Errors
+    ///
+    /// Returns `VrfError::InvalidPublicKey` for small-order or undecodable
+    /// keys, `VrfError::InvalidPoint` or `VrfError::InvalidScalar` for a
+    /// malformed proof, and `VrfError::VerificationFailed` if the challenge
+    /// does not match.
+    pub fn verify(
+        public_key: &[u8; PUBLIC_KEY_SIZE],
+        proof: &[u8; PROOF_SIZE],
+        message: &[u8],
+    ) -> VrfResult<[u8; OUTPUT_SIZE]> {
+        // validate_key: reject undecodable and small-order keys
+        let y_point = bytes_to_point(public_key).map_err(|_| VrfError::InvalidPublicKey)?;
+        if has_small_order(&y_point) {
+            return Err(VrfError::InvalidPublicKey);
+        }
+
+        let decoded = decode_proof(proof)?;
+        let (h_point, h_string) = cardano_hash_to_curve_draft13(public_key, message)?;
+
+        // U = s*B - c*Y, V = s*H - c*Gamma
+        let neg_c = -challenge_scalar(&decoded.c);
+        let u = EdwardsPoint::vartime_multiscalar_mul(
+            [decoded.s, neg_c],
+            [EdwardsPoint::mul_base(&Scalar::ONE), y_point],
+        );
+        let v = EdwardsPoint::vartime_multiscalar_mul([decoded.s, neg_c], [h_point, decoded.gamma]);
+
+        let expected = challenge(
+            public_key,
+            &h_string,
+            &decoded.gamma_bytes,
+            &point_to_bytes(&u),
+            &point_to_bytes(&v),
+        );
+        if !verify_16(&expected, &decoded.c) {
+            return Err(VrfError::VerificationFailed);
+        }
+
+        Ok(gamma_to_hash(&decoded.gamma))
+    }
+
+    /// Convert a proof to VRF output hash
+    ///
+    /// # Arguments
+    /// * `proof` - 80-byte proof
+    ///
+    /// # Returns
+    /// 64-byte VRF output
+    ///
+    /// # Errors
+    ///
+    /// Returns `VrfError::InvalidPoint` or `VrfError::InvalidScalar` if the
+    /// proof does not decode. The proof itself is not verified.
+    pub fn proof_to_hash(proof: &[u8; PROOF_SIZE]) -> VrfResult<[u8; OUTPUT_SIZE]> {
+        Ok(gamma_to_hash(&decode_proof(proof)?.gamma))
+    }
+
+    /// Generate keypair from seed
+    #[must_use]
+    pub fn keypair_from_seed(
+        seed: &[u8; SEED_SIZE],
+    ) -> ([u8; SECRET_KEY_SIZE], [u8; PUBLIC_KEY_SIZE]) {
+        let sk = seed_to_secret_key(seed);
+        let mut pk = [0u8; PUBLIC_KEY_SIZE];
+        pk.copy_from_slice(&sk[32..64]);
+        (sk, pk)
+    }
+}
+
+/// RFC 9381 `decode_proof`, rejecting a non-canonical `s`.
+fn decode_proof(proof: &[u8; PROOF_SIZE]) -> VrfResult<DecodedProof> {
+    let mut gamma_bytes = [0u8; 32];
+    gamma_bytes.copy_from_slice(&proof[0..32]);
+    let mut c = [0u8; CHALLENGE_SIZE];
+    c.copy_from_slice(&proof[32..48]);
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&proof[48..80]);
+
+    let gamma = bytes_to_point(&gamma_bytes)?;
+    let s = Option::<Scalar>::from(Scalar::from_canonical_bytes(s_bytes))
+        .ok_or(VrfError::InvalidScalar)?;
+    Ok(DecodedProof {
+        gamma,
+        gamma_bytes,
+        c,
+        s,
+    })
+}
+
+/// RFC 9381 `challenge_generation` over `Y, H, Gamma, U, V`, truncated to
+/// `cLen` bytes.
+fn challenge(
+    public_key: &[u8; 32],
+    h_string: &[u8; 32],
+    gamma: &[u8; 32],
+    u: &[u8; 32],
+    v: &[u8; 32],
+) -> [u8; CHALLENGE_SIZE] {
+    let mut hasher = Sha512::new();
+    hasher.update([SUITE_RFC9381, TWO]);
+    hasher.update(public_key);
+    hasher.update(h_string);
+    hasher.update(gamma);
+    hasher.update(u);
+    hasher.update(v);
+    hasher.update([0u8]);
+    let mut c = [0u8; CHALLENGE_SIZE];
+    c.copy_from_slice(&hasher.finalize()[..CHALLENGE_SIZE]);
+    c
+}
+
+fn challenge_scalar(c: &[u8; CHALLENGE_SIZE]) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[..CHALLENGE_SIZE].copy_from_slice(c);
+    Scalar::from_bytes_mod_order(bytes)
+}
+
+/// `beta = SHA-512(suite || 0x03 || cofactor * Gamma || 0x00)`
+fn gamma_to_hash(gamma: &EdwardsPoint) -> [u8; OUTPUT_SIZE] {
+    let mut hasher = Sha512::new();
+    hasher.update([SUITE_RFC9381, THREE]);
+    hasher.update(point_to_bytes(&cardano_clear_cofactor(gamma)));
+    hasher.update([0u8]);
+    let mut output = [0u8; OUTPUT_SIZE];
+    output.copy_from_slice(&hasher.finalize());
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VrfDraft13;
+    use hex::FromHex;
+
+    #[test]
+    fn test_prove_verify_roundtrip() {
+        let (sk, pk) = VrfRfc9381::keypair_from_seed(&[42u8; SEED_SIZE]);
+        let message = b"test message";
+
+        let proof = VrfRfc9381::prove(&sk, message).expect("prove failed");
+        let output = VrfRfc9381::verify(&pk, &proof, message).expect("verify failed");
+        assert_eq!(output, VrfRfc9381::proof_to_hash(&proof).unwrap());
+
+        assert_eq!(
+            VrfRfc9381::verify(&pk, &proof, b"other message"),
+            Err(VrfError::VerificationFailed)
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_proofs() {
+        let (sk, pk) = VrfRfc9381::keypair_from_seed(&[7u8; SEED_SIZE]);
+        let proof = VrfRfc9381::prove(&sk, b"msg").unwrap();
+
+        let mut bad_c = proof;
+        bad_c[40] ^= 1;
+        assert_eq!(
+            VrfRfc9381::verify(&pk, &bad_c, b"msg"),
+            Err(VrfError::VerificationFailed)
+        );
+
+        let mut big_s = proof;
+        big_s[79] = 0xff;
+        assert_eq!(
+            VrfRfc9381::verify(&pk, &big_s, b"msg"),
+            Err(VrfError::InvalidScalar)
+        );
+        assert_eq!(
+            VrfRfc9381::proof_to_hash(&big_s),
+            Err(VrfError::InvalidScalar)
+        );
+
+        let identity = {
+            let mut bytes = [0u8; 32];
+            bytes[0] = 1;
+            bytes
+        };
+        assert_eq!(
+            VrfRfc9381::verify(&identity, &proof, b"msg"),
+            Err(VrfError::InvalidPublicKey)
+        );
+    }
+
+    #[test]
+    fn test_shares_gamma_s_and_output_with_draft13() {
+        let (sk, pk) = VrfRfc9381::keypair_from_seed(&[3u8; SEED_SIZE]);
+        let proof = VrfRfc9381::prove(&sk, b"alpha").unwrap();
+        let batch = VrfDraft13::prove(&sk, b"alpha").unwrap();
+
+        assert_eq!(proof[0..32], batch[0..32]);
+        assert_eq!(proof[48..80], batch[96..128]);
+        assert_eq!(
+            VrfRfc9381::verify(&pk, &proof, b"alpha").unwrap(),
+            VrfDraft13::verify(&pk, &batch, b"alpha").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rfc9381_example_16() {
+        // RFC 9381 Appendix B.3, Example 16 (empty alpha)
+        let seed = <[u8; SEED_SIZE]>::from_hex(
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        )
+        .unwrap();
+        let (sk, pk) = VrfRfc9381::keypair_from_seed(&seed);
+        assert_eq!(
+            pk,
+            <[u8; PUBLIC_KEY_SIZE]>::from_hex(
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+            )
+            .unwrap()
+        );
+
+        let expected_proof = <[u8; PROOF_SIZE]>::from_hex(
+            "7d9c633ffeee27349264cf5c667579fc583b4bda63ab71d001f89c10003ab46f14adf9a3cd8b8412d9038531e865c341cafa73589b023d14311c331a9ad15ff2fb37831e00f0acaa6d73bc9997b06501",
+        )
+        .unwrap();
+        let expected_beta = <[u8; OUTPUT_SIZE]>::from_hex(
+            "9d574bf9b8302ec0fc1e21c3ec5368269527b87b462ce36dab2d14ccf80c53cccf6758f058c5b1c856b116388152bbe509ee3b9ecfe63d93c3b4346c1fbc6c54",
+        )
+        .unwrap();
+
+        let proof = VrfRfc9381::prove(&sk, b"").unwrap();
+        assert_eq!(proof, expected_proof);
+        assert_eq!(VrfRfc9381::verify(&pk, &proof, b"").unwrap(), expected_beta);
+        assert_eq!(VrfRfc9381::proof_to_hash(&proof).unwrap(), expected_beta);
+    }
+}
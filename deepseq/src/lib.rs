@@ -464,6 +464,38 @@ impl_nfdata_for_tuple!(A, B, C, D, E, F, G, H, I, J);
 impl_nfdata_for_tuple!(A, B, C, D, E, F, G, H, I, J, K);
 impl_nfdata_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
 
+#[cfg(feature = "time")]
+impl_nfdata_for_copy!(time::OffsetDateTime, time::Date, time::Duration);
+
+#[cfg(feature = "num-bigint")]
+impl NFData for num_bigint::BigInt {
+    fn rnf(&self) {}
+}
+
+#[cfg(feature = "num-bigint")]
+impl NFData for num_bigint::BigUint {
+    fn rnf(&self) {}
+}
+
+#[cfg(feature = "num-rational")]
+impl<T: NFData> NFData for num_rational::Ratio<T> {
+    fn rnf(&self) {
+        self.numer().rnf();
+        self.denom().rnf();
+    }
+}
+
+#[cfg(feature = "num-rational")]
+impl<T> NFData1<T> for num_rational::Ratio<T> {
+    fn lift_rnf<F>(&self, f: &mut F)
+    where
+        F: FnMut(&T),
+    {
+        f(self.numer());
+        f(self.denom());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -538,4 +570,29 @@ mod tests {
         // This should not panic and should traverse all fields without issue.
         example.rnf();
     }
+
+    #[cfg(all(feature = "time", feature = "num-bigint", feature = "num-rational"))]
+    #[test]
+    fn foreign_types_force_to_normal_form() {
+        use num_bigint::{BigInt, BigUint};
+        use num_rational::Ratio;
+        use time::{Date, Duration, OffsetDateTime};
+
+        let params = (
+            OffsetDateTime::UNIX_EPOCH,
+            Date::MIN,
+            Duration::seconds(20),
+            BigInt::from(-45_000_000_000_000_000_i64),
+            BigUint::from(13_888_022_852_926_644_u64),
+            Ratio::new(1_u64, 5),
+            Ratio::new(BigInt::from(3), BigInt::from(1_000)),
+        );
+        params.rnf();
+        let forced = force(params.clone());
+        assert_eq!(forced, params);
+
+        let mut seen = Vec::new();
+        Ratio::new(2_u64, 6).lift_rnf(&mut |part| seen.push(*part));
+        assert_eq!(seen, vec![1, 3]);
+    }
 }
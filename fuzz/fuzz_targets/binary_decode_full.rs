@@ -0,0 +1,30 @@
+#![no_main]
+
+use cardano_binary::{decode_full, serialize};
+use libfuzzer_sys::fuzz_target;
+use serde::{Deserialize, Serialize};
+
+/// Shaped like a ledger sum type: unit, newtype, tuple, and struct variants
+/// with nested containers.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Message {
+    Ping,
+    Slot(u64),
+    Range(u64, u64),
+    Block {
+        header: Vec<u8>,
+        txs: Vec<(u32, String)>,
+        parent: Option<Box<Message>>,
+    },
+}
+
+// CBOR permits non-minimal encodings that `serialize` never emits, so the
+// input bytes are not compared; the decoded value and its re-encoding must
+// be stable instead.
+fuzz_target!(|bytes: &[u8]| {
+    if let Ok(value) = decode_full::<Message>(bytes) {
+        let encoded = serialize(&value).expect("decoded value must re-encode");
+        let decoded: Message = decode_full(&encoded).expect("re-encoding must decode");
+        assert_eq!(decoded, value);
+    }
+});
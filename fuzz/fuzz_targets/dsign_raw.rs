@@ -0,0 +1,35 @@
+#![no_main]
+
+use cardano_base_fuzz::assert_round_trip;
+use cardano_crypto_class::dsign::DsignAlgorithm;
+use cardano_crypto_class::dsign::ecdsa_secp256k1::EcdsaSecp256k1DSIGN;
+use cardano_crypto_class::dsign::ed25519::Ed25519;
+use cardano_crypto_class::dsign::schnorr_secp256k1::SchnorrSecp256k1DSIGN;
+use libfuzzer_sys::fuzz_target;
+
+fn check<A: DsignAlgorithm>(name: &str, bytes: &[u8]) {
+    assert_round_trip(
+        &format!("{name} verification key"),
+        bytes,
+        A::raw_deserialize_verification_key,
+        A::raw_serialize_verification_key,
+    );
+    assert_round_trip(
+        &format!("{name} signing key"),
+        bytes,
+        A::raw_deserialize_signing_key,
+        A::raw_serialize_signing_key,
+    );
+    assert_round_trip(
+        &format!("{name} signature"),
+        bytes,
+        A::raw_deserialize_signature,
+        A::raw_serialize_signature,
+    );
+}
+
+fuzz_target!(|bytes: &[u8]| {
+    check::<Ed25519>("Ed25519", bytes);
+    check::<EcdsaSecp256k1DSIGN>("EcdsaSecp256k1", bytes);
+    check::<SchnorrSecp256k1DSIGN>("SchnorrSecp256k1", bytes);
+});
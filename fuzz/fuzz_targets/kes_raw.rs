@@ -0,0 +1,43 @@
+#![no_main]
+
+use cardano_base_fuzz::assert_round_trip;
+use cardano_crypto_class::kes::{
+    CompactSum0Kes, CompactSum1Kes, CompactSum2Kes, CompactSum3Kes, CompactSum4Kes, CompactSum5Kes,
+    CompactSum6Kes, CompactSum7Kes, KesAlgorithm, Sum0Kes, Sum1Kes, Sum2Kes, Sum3Kes, Sum4Kes,
+    Sum5Kes, Sum6Kes, Sum7Kes,
+};
+use libfuzzer_sys::fuzz_target;
+
+fn check<K: KesAlgorithm>(name: &str, bytes: &[u8]) {
+    assert_round_trip(
+        &format!("{name} verification key"),
+        bytes,
+        K::raw_deserialize_verification_key_kes,
+        K::raw_serialize_verification_key_kes,
+    );
+    assert_round_trip(
+        &format!("{name} signature"),
+        bytes,
+        K::raw_deserialize_signature_kes,
+        K::raw_serialize_signature_kes,
+    );
+}
+
+fuzz_target!(|bytes: &[u8]| {
+    check::<Sum0Kes>("Sum0Kes", bytes);
+    check::<Sum1Kes>("Sum1Kes", bytes);
+    check::<Sum2Kes>("Sum2Kes", bytes);
+    check::<Sum3Kes>("Sum3Kes", bytes);
+    check::<Sum4Kes>("Sum4Kes", bytes);
+    check::<Sum5Kes>("Sum5Kes", bytes);
+    check::<Sum6Kes>("Sum6Kes", bytes);
+    check::<Sum7Kes>("Sum7Kes", bytes);
+    check::<CompactSum0Kes>("CompactSum0Kes", bytes);
+    check::<CompactSum1Kes>("CompactSum1Kes", bytes);
+    check::<CompactSum2Kes>("CompactSum2Kes", bytes);
+    check::<CompactSum3Kes>("CompactSum3Kes", bytes);
+    check::<CompactSum4Kes>("CompactSum4Kes", bytes);
+    check::<CompactSum5Kes>("CompactSum5Kes", bytes);
+    check::<CompactSum6Kes>("CompactSum6Kes", bytes);
+    check::<CompactSum7Kes>("CompactSum7Kes", bytes);
+});
@@ -0,0 +1,25 @@
+#![no_main]
+
+use cardano_base_fuzz::assert_round_trip;
+use cardano_crypto_class::vrf::{PraosBatchCompatVRF, PraosVRF, VRFAlgorithm};
+use libfuzzer_sys::fuzz_target;
+
+fn check<V: VRFAlgorithm>(name: &str, bytes: &[u8]) {
+    assert_round_trip(
+        &format!("{name} verification key"),
+        bytes,
+        V::raw_deserialize_verification_key,
+        V::raw_serialize_verification_key,
+    );
+    assert_round_trip(
+        &format!("{name} proof"),
+        bytes,
+        V::raw_deserialize_proof,
+        V::raw_serialize_proof,
+    );
+}
+
+fuzz_target!(|bytes: &[u8]| {
+    check::<PraosVRF>("PraosVRF", bytes);
+    check::<PraosBatchCompatVRF>("PraosBatchCompatVRF", bytes);
+});
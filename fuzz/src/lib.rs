@@ -0,0 +1,17 @@
+//! Shared checks for the fuzz targets.
+
+/// Decode `bytes`; if that succeeds, re-encoding must reproduce them.
+///
+/// # Panics
+///
+/// Panics (reported by libFuzzer as a crash) when the round trip fails.
+pub fn assert_round_trip<T>(
+    what: &str,
+    bytes: &[u8],
+    decode: impl Fn(&[u8]) -> Option<T>,
+    encode: impl Fn(&T) -> Vec<u8>,
+) {
+    if let Some(value) = decode(bytes) {
+        assert_eq!(encode(&value), bytes, "{what} does not round-trip");
+    }
+}
@@ -1,25 +1,34 @@
 //! Estimating heap usage for Cardano data structures.
 //!
 //! This crate mirrors the behaviour of the original Haskell
-//! `Cardano.HeapWords` helpers. The heuristics assume a 64-bit runtime and
-//! report sizes in machine *words* (8 bytes on the supported target).
+//! `Cardano.HeapWords` helpers and reports sizes in machine *words*.
+//!
+//! With the default `haskell-compat` feature a word is always 8 bytes, as on
+//! the 64-bit Haskell node, so estimates from any target can be compared with
+//! the node's. Without it the word size follows the target: 8 bytes on 64-bit
+//! targets and 4 bytes on 32-bit ones such as `wasm32`.
 
-#![cfg_attr(not(target_pointer_width = "64"), allow(dead_code))]
 #![allow(clippy::too_many_arguments)]
 
-#[cfg(not(target_pointer_width = "64"))]
-compile_error!("heapwords assumes a 64-bit target platform");
-
 use num_bigint::{BigInt, BigUint, Sign};
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 use std::rc::Rc;
 use std::sync::Arc;
-use time::{Date, OffsetDateTime};
+use time::{Date, Duration, OffsetDateTime};
 
-/// Size of a machine word in bytes (assuming a 64-bit architecture).
+/// Size of a machine word in bytes.
+///
+/// 8 with the `haskell-compat` feature or on 64-bit targets, 4 otherwise.
+#[cfg(any(feature = "haskell-compat", target_pointer_width = "64"))]
 pub const WORD_SIZE: usize = 8;
 
+/// Size of a machine word in bytes.
+///
+/// 8 with the `haskell-compat` feature or on 64-bit targets, 4 otherwise.
+#[cfg(not(any(feature = "haskell-compat", target_pointer_width = "64")))]
+pub const WORD_SIZE: usize = 4;
+
 /// Convert a number of heap words into megabytes.
 #[must_use]
 pub fn heap_size_mb(words: usize) -> usize {
@@ -372,27 +381,114 @@ where
     value.heap_words().saturating_sub(2)
 }
 
+/// Implement [`HeapWords`] for a struct by listing its fields.
+///
+/// The generated estimate is `1 + N + sum(field.heap_words())` for `N` fields,
+/// i.e. the same constructor header and per-field pointer that the
+/// `heap_wordsN` helpers charge. The arithmetic is generated directly, so
+/// there is no upper bound on the number of fields. Fields marked
+/// `#[heapwords(unpacked)]` contribute [`heap_words_unpacked`] instead of their
+/// full size. Generic parameters are bounded by `HeapWords`.
+///
+/// Named fields are listed in braces and tuple fields by index in parentheses:
+///
+/// ```rust
+/// use heapwords::{HeapWords, heap_words2, heap_words_unpacked, impl_heap_words_for_struct};
+///
+/// struct Entry<K> {
+///     key: K,
+///     slot: u64,
+/// }
+/// impl_heap_words_for_struct!(Entry<K> { key, #[heapwords(unpacked)] slot });
+///
+/// struct Pair(Vec<u8>, u32);
+/// impl_heap_words_for_struct!(Pair(0, 1));
+///
+/// let entry = Entry { key: vec![1u8, 2], slot: 7 };
+/// assert_eq!(
+///     entry.heap_words(),
+///     3 + entry.key.heap_words() + heap_words_unpacked(&entry.slot)
+/// );
+///
+/// let pair = Pair(vec![1, 2, 3], 4);
+/// assert_eq!(pair.heap_words(), heap_words2(&pair.0, &pair.1));
+/// ```
+#[macro_export]
+macro_rules! impl_heap_words_for_struct {
+    (@field [] $value:expr) => {
+        $crate::HeapWords::heap_words($value)
+    };
+    (@field [unpacked] $value:expr) => {
+        $crate::heap_words_unpacked($value)
+    };
+    (@impl $name:ident [$($generic:ident),*] { $( [$($kind:ident)?] $field:tt ),* $(,)? }) => {
+        impl<$($generic: $crate::HeapWords),*> $crate::HeapWords for $name<$($generic),*> {
+            fn heap_words(&self) -> usize {
+                1 $( + 1 + $crate::impl_heap_words_for_struct!(@field [$($kind)?] &self.$field) )*
+            }
+        }
+    };
+    (
+        $name:ident $(< $($generic:ident),+ $(,)? >)?
+        { $( $(#[heapwords($kind:ident)])? $field:ident ),* $(,)? }
+    ) => {
+        $crate::impl_heap_words_for_struct!(
+            @impl $name [$($($generic),+)?] { $( [$($kind)?] $field ),* }
+        );
+    };
+    // Tuple indices must stay `tt`s so `self.$field` parses, so their
+    // attributes are peeled off one field at a time.
+    (@tuple $name:ident $generics:tt [$($acc:tt)*]) => {
+        $crate::impl_heap_words_for_struct!(@impl $name $generics { $($acc)* });
+    };
+    (@tuple $name:ident $generics:tt [$($acc:tt)*]
+        #[heapwords($kind:ident)] $field:tt $(, $($rest:tt)*)?) => {
+        $crate::impl_heap_words_for_struct!(
+            @tuple $name $generics [$($acc)* [$kind] $field,] $($($rest)*)?
+        );
+    };
+    (@tuple $name:ident $generics:tt [$($acc:tt)*] $field:tt $(, $($rest:tt)*)?) => {
+        $crate::impl_heap_words_for_struct!(
+            @tuple $name $generics [$($acc)* [] $field,] $($($rest)*)?
+        );
+    };
+    ($name:ident $(< $($generic:ident),+ $(,)? >)? ( $($fields:tt)* )) => {
+        $crate::impl_heap_words_for_struct!(@tuple $name [$($($generic),+)?] [] $($fields)*);
+    };
+}
+
 /// Estimate the heap words of an array of primitive values.
 #[inline]
 #[must_use]
 pub fn heap_words_uarray(element_size_bytes: usize, len: usize) -> usize {
-    13 + element_size_bytes.saturating_mul(len) / WORD_SIZE
+    uarray_words(element_size_bytes, len, WORD_SIZE)
+}
+
+#[inline]
+const fn uarray_words(element_size_bytes: usize, len: usize, word_size: usize) -> usize {
+    13 + element_size_bytes.saturating_mul(len) / word_size
 }
 
 /// Estimate the heap words of an unboxed vector.
 #[inline]
 #[must_use]
 pub fn heap_words_uvector(element_size_bytes: usize, len: usize) -> usize {
-    5 + element_size_bytes.saturating_mul(len) / WORD_SIZE
+    uvector_words(element_size_bytes, len, WORD_SIZE)
+}
+
+#[inline]
+const fn uvector_words(element_size_bytes: usize, len: usize, word_size: usize) -> usize {
+    5 + element_size_bytes.saturating_mul(len) / word_size
 }
 
 #[inline]
 fn ceil_words(bytes: usize) -> usize {
-    if bytes == 0 {
-        0
-    } else {
-        1 + (bytes - 1) / WORD_SIZE
-    }
+    ceil_words_of(bytes, WORD_SIZE)
+}
+
+#[inline]
+const fn ceil_words_of(bytes: usize, word_size: usize) -> usize {
+    bytes.div_ceil(word_size)
 }
 
 /// Strict ByteString compatibility wrapper.
@@ -461,14 +557,16 @@ impl From<&str> for Text {
 
 impl HeapWords for Text {
     fn heap_words(&self) -> usize {
-        let code_units = self.0.chars().count();
-        let units_per_word = WORD_SIZE / 2;
-        let words = code_units / units_per_word;
-        let remainder = code_units % units_per_word;
-        5 + words + usize::from(remainder > 0)
+        text_words(self.0.chars().count(), WORD_SIZE)
     }
 }
 
+/// Words of a `Data.Text` holding `code_units` 16-bit units.
+#[inline]
+const fn text_words(code_units: usize, word_size: usize) -> usize {
+    5 + code_units.div_ceil(word_size / 2)
+}
+
 impl HeapWords for String {
     fn heap_words(&self) -> usize {
         Text(self.clone()).heap_words()
@@ -491,6 +589,13 @@ impl<V> From<BTreeMap<i64, V>> for IntMap<V> {
     }
 }
 
+/// Haskell's `HeapWords (IntMap a)` instance: 8 words per entry plus the
+/// value, `sum [8 + heapWords v | v <- IntMap.elems m]`.
+///
+/// The constant already covers the Patricia trie. An `n`-entry map is `n`
+/// `Tip` nodes (header, unpacked key and value pointer: 3 words) joined by
+/// `n - 1` `Bin` nodes (header, unpacked prefix and mask, two children: 5
+/// words), `8n - 5` words in all, which Haskell rounds up to `8n`.
 impl<V> HeapWords for IntMap<V>
 where
     V: HeapWords,
@@ -510,6 +615,12 @@ impl From<BTreeSet<i64>> for IntSet {
     }
 }
 
+/// Haskell's `HeapWords IntSet` instance, marked as an estimate there: 4 words
+/// per member, `4 * IntSet.size s`.
+///
+/// `IntSet` packs up to 64 neighbouring members into one 3-word `Tip` bitmap,
+/// so the real cost depends on how the members are spread; the flat rate is
+/// kept so that estimates agree with the node's.
 impl HeapWords for IntSet {
     fn heap_words(&self) -> usize {
         4 * self.0.len()
@@ -526,6 +637,11 @@ impl<T> From<VecDeque<T>> for Seq<T> {
     }
 }
 
+/// Haskell's `HeapWords (Seq a)` instance, marked as an estimate there: 5
+/// words per element plus the element, `sum [5 + heapWords a | a <- toList s]`.
+///
+/// This amortises the finger tree's 2-3 nodes and digits over its elements
+/// rather than counting them for each size.
 impl<T> HeapWords for Seq<T>
 where
     T: HeapWords,
@@ -591,6 +707,30 @@ impl HeapWords for BigInt {
     }
 }
 
+/// `DiffTime` is a newtype over `Pico`, i.e. an `Integer` count of
+/// picoseconds: two words while it fits in a machine word, a big integer
+/// otherwise.
+impl HeapWords for Duration {
+    fn heap_words(&self) -> usize {
+        integer_words(self.whole_nanoseconds().saturating_mul(1_000), WORD_SIZE)
+    }
+}
+
+/// Words of a Haskell `Integer` with value `value`: a small integer while it
+/// fits in a signed machine word, a big integer otherwise.
+#[inline]
+fn integer_words(value: i128, word_size: usize) -> usize {
+    let word_bits = word_size * 8;
+    let fits_in_word = (value >> (word_bits - 1)) == 0 || (value >> (word_bits - 1)) == -1;
+    if fits_in_word {
+        2
+    } else {
+        let magnitude = value.unsigned_abs();
+        let bytes = (u128::BITS - magnitude.leading_zeros()).div_ceil(8) as usize;
+        4 + ceil_words_of(bytes, word_size)
+    }
+}
+
 impl HeapWords for BigUint {
     fn heap_words(&self) -> usize {
         if self.bits() == 0 {
@@ -602,6 +742,14 @@ impl HeapWords for BigUint {
     }
 }
 
+/// `Ratio` is a single constructor (`:%`) with two strict fields.
+#[cfg(feature = "num-rational")]
+impl<T: HeapWords> HeapWords for num_rational::Ratio<T> {
+    fn heap_words(&self) -> usize {
+        3 + self.numer().heap_words() + self.denom().heap_words()
+    }
+}
+
 impl<T> HeapWords for Vec<T>
 where
     T: HeapWords,
@@ -842,6 +990,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compat_word_size_matches_the_haskell_node() {
+        if cfg!(any(feature = "haskell-compat", target_pointer_width = "64")) {
+            assert_eq!(WORD_SIZE, 8);
+        } else {
+            assert_eq!(WORD_SIZE, 4);
+        }
+    }
+
+    #[test]
+    fn word_dependent_sizes_on_32_bit_words() {
+        assert_eq!(ceil_words_of(17, 8), 3);
+        assert_eq!(ceil_words_of(17, 4), 5);
+        assert_eq!(ceil_words_of(0, 4), 0);
+        // ByteString and ShortByteString payloads.
+        assert_eq!(5 + ceil_words_of(32, 4), 13);
+        assert_eq!(4 + ceil_words_of(28, 4), 11);
+        // Two UTF-16 units per 4-byte word.
+        assert_eq!(text_words(4, 8), 6);
+        assert_eq!(text_words(4, 4), 7);
+        assert_eq!(text_words(5, 4), 8);
+        assert_eq!(uarray_words(8, 3, 4), 19);
+        assert_eq!(uvector_words(2, 10, 4), 10);
+    }
+
+    #[test]
+    fn integer_spills_to_a_big_integer_past_the_word() {
+        assert_eq!(integer_words(i128::from(i32::MAX), 4), 2);
+        assert_eq!(integer_words(i128::from(i32::MIN), 4), 2);
+        assert_eq!(integer_words(i128::from(i32::MAX) + 1, 4), 5);
+        assert_eq!(integer_words(i128::from(i32::MAX) + 1, 8), 2);
+        assert_eq!(integer_words(i128::from(i64::MIN) - 1, 8), 5);
+        // 17_280_000 s of picoseconds needs 8 bytes: two 4-byte limbs.
+        assert_eq!(integer_words(17_280_000 * 1_000_000_000_000, 4), 6);
+    }
+
+    #[cfg(all(not(feature = "haskell-compat"), target_pointer_width = "32"))]
+    #[test]
+    fn native_32_bit_accounting() {
+        assert_eq!(ByteString(vec![0u8; 17]).heap_words(), 10);
+        assert_eq!(Text::from("abcd").heap_words(), 7);
+        assert_eq!(heap_words_uvector(8, 4), 13);
+        assert_eq!(Duration::seconds(3).heap_words(), 6);
+    }
+
     #[test]
     fn text_counts_code_units() {
         let text = Text::from("abcd");
@@ -858,6 +1051,30 @@ mod tests {
         assert_eq!(expected, map.heap_words());
     }
 
+    /// Container sizes and the words Haskell's `heapWords` reports for them
+    /// with `Word64` values (2 words each): `IntMap`, `IntSet` and `Seq`.
+    const CONTAINER_WORDS: [(usize, usize, usize, usize); 6] = [
+        (0, 0, 0, 0),
+        (1, 10, 4, 7),
+        (2, 20, 8, 14),
+        (10, 100, 40, 70),
+        (1_000, 10_000, 4_000, 7_000),
+        (100_000, 1_000_000, 400_000, 700_000),
+    ];
+
+    #[test]
+    fn int_containers_match_haskell_estimates() {
+        for (size, int_map, int_set, seq) in CONTAINER_WORDS {
+            let keys = (0_i64..).take(size);
+            let map = IntMap(keys.clone().map(|key| (key, key.unsigned_abs())).collect());
+            let set = IntSet(keys.clone().collect());
+            let sequence = Seq(keys.map(i64::unsigned_abs).collect());
+            assert_eq!(map.heap_words(), int_map, "IntMap of {size}");
+            assert_eq!(set.heap_words(), int_set, "IntSet of {size}");
+            assert_eq!(sequence.heap_words(), seq, "Seq of {size}");
+        }
+    }
+
     #[test]
     fn seq_estimate_matches_sum() {
         let mut deque = VecDeque::new();
@@ -867,4 +1084,235 @@ mod tests {
         let expected = (5 + 1_u8.heap_words()) * 2;
         assert_eq!(expected, seq.heap_words());
     }
+
+    struct Two {
+        a: u64,
+        b: Vec<u8>,
+    }
+    impl_heap_words_for_struct!(Two { a, b });
+
+    struct Thirteen {
+        f0: u8,
+        f1: u16,
+        f2: u32,
+        f3: u64,
+        f4: Vec<u8>,
+        f5: String,
+        f6: Option<u64>,
+        f7: ByteString,
+        f8: bool,
+        f9: i64,
+        f10: Vec<u64>,
+        f11: Text,
+        f12: (u8, u8),
+    }
+    impl_heap_words_for_struct!(Thirteen {
+        f0,
+        f1,
+        f2,
+        f3,
+        f4,
+        f5,
+        f6,
+        f7,
+        f8,
+        f9,
+        f10,
+        f11,
+        f12,
+    });
+
+    struct Seventeen(
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        Vec<u8>,
+        Vec<u8>,
+        Vec<u8>,
+        #[allow(dead_code)] u64,
+    );
+    impl_heap_words_for_struct!(Seventeen(
+        0,
+        1,
+        2,
+        3,
+        4,
+        5,
+        6,
+        7,
+        8,
+        9,
+        10,
+        11,
+        12,
+        13,
+        14,
+        15,
+        #[heapwords(unpacked)]
+        16
+    ));
+
+    struct Tagged<T> {
+        tag: u32,
+        #[allow(dead_code)]
+        hint: u64,
+        payload: T,
+    }
+    impl_heap_words_for_struct!(Tagged<T> { tag, #[heapwords(unpacked)] hint, payload });
+
+    #[test]
+    fn struct_macro_matches_heap_words2() {
+        let value = Two {
+            a: 9,
+            b: vec![1, 2, 3],
+        };
+        assert_eq!(heap_words2(&value.a, &value.b), value.heap_words());
+    }
+
+    #[test]
+    fn struct_macro_matches_heap_words13() {
+        let value = Thirteen {
+            f0: 0,
+            f1: 1,
+            f2: 2,
+            f3: 3,
+            f4: vec![4; 9],
+            f5: "five".to_owned(),
+            f6: Some(6),
+            f7: ByteString(vec![7; 20]),
+            f8: true,
+            f9: -9,
+            f10: vec![10, 10],
+            f11: Text::from("eleven"),
+            f12: (1, 2),
+        };
+        let expected = heap_words13(
+            &value.f0, &value.f1, &value.f2, &value.f3, &value.f4, &value.f5, &value.f6, &value.f7,
+            &value.f8, &value.f9, &value.f10, &value.f11, &value.f12,
+        );
+        assert_eq!(expected, value.heap_words());
+    }
+
+    #[test]
+    fn struct_macro_supports_seventeen_fields() {
+        let value = Seventeen(
+            0,
+            1,
+            2,
+            3,
+            4,
+            5,
+            6,
+            7,
+            8,
+            9,
+            10,
+            11,
+            12,
+            vec![1],
+            vec![1, 2],
+            vec![1, 2, 3],
+            16,
+        );
+        let small = 13 * 0_u8.heap_words();
+        let vectors = value.13.heap_words() + value.14.heap_words() + value.15.heap_words();
+        let expected = 1 + 17 + small + vectors + heap_words_unpacked(&value.16);
+        assert_eq!(expected, value.heap_words());
+    }
+
+    #[test]
+    fn struct_macro_bounds_generic_parameters() {
+        let with_int = Tagged {
+            tag: 1,
+            hint: 2,
+            payload: 3_u64,
+        };
+        let with_vec = Tagged {
+            tag: 1,
+            hint: 2,
+            payload: vec![1_u8, 2, 3, 4],
+        };
+
+        let expected_int = heap_words3(&with_int.tag, &with_int.hint, &with_int.payload)
+            - with_int.hint.heap_words()
+            + heap_words_unpacked(&with_int.hint);
+        assert_eq!(expected_int, with_int.heap_words());
+        assert_eq!(
+            4 + with_vec.tag.heap_words()
+                + heap_words_unpacked(&2_u64)
+                + with_vec.payload.heap_words(),
+            with_vec.heap_words()
+        );
+    }
+
+    #[test]
+    fn duration_counts_picosecond_integer() {
+        assert_eq!(2, Duration::seconds(1).heap_words());
+        // 200 days of picoseconds no longer fit in a machine word.
+        assert_eq!(5, Duration::days(200).heap_words());
+    }
+
+    #[cfg(feature = "num-rational")]
+    #[test]
+    fn ratio_adds_constructor_to_components() {
+        use num_rational::Ratio;
+
+        let rho = Ratio::new(BigInt::from(3), BigInt::from(1_000));
+        assert_eq!(
+            3 + BigInt::from(3).heap_words() + BigInt::from(1_000).heap_words(),
+            rho.heap_words()
+        );
+        assert_eq!(3 + 2 + 2, Ratio::new(1_u64, 5).heap_words());
+
+        struct ProtocolParams {
+            system_start: OffsetDateTime,
+            epoch_start: Date,
+            slot_length: Duration,
+            treasury: BigInt,
+            reserves: BigUint,
+            decentralisation: Ratio<u64>,
+            monetary_expansion: Ratio<BigInt>,
+        }
+        crate::impl_heap_words_for_struct!(ProtocolParams {
+            system_start,
+            epoch_start,
+            slot_length,
+            treasury,
+            reserves,
+            decentralisation,
+            monetary_expansion
+        });
+
+        let params = ProtocolParams {
+            system_start: OffsetDateTime::UNIX_EPOCH,
+            epoch_start: Date::MIN,
+            slot_length: Duration::seconds(20),
+            treasury: BigInt::from(-45_000_000_000_000_000_i64),
+            reserves: BigUint::from(13_888_022_852_926_644_u64),
+            decentralisation: Ratio::new(1_u64, 5),
+            monetary_expansion: rho,
+        };
+        assert_eq!(
+            params.heap_words(),
+            heap_words7(
+                &params.system_start,
+                &params.epoch_start,
+                &params.slot_length,
+                &params.treasury,
+                &params.reserves,
+                &params.decentralisation,
+                &params.monetary_expansion,
+            )
+        );
+    }
 }
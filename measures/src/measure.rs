@@ -3,6 +3,11 @@ use std::cmp::Ordering;
 use thiserror::Error;
 
 /// Error raised when a measurement addition would overflow the underlying type.
+///
+/// Only addition can overflow: [`Measure::try_plus`] reports it with this
+/// error, [`Measure::saturating_plus`] (and therefore [`Measure::plus`])
+/// clamps at the maximum instead, and the iterator helpers propagate it.
+/// `zero`, `min_measure` and `max_measure` never overflow.
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 #[error("measure addition overflowed")]
 pub struct MeasureOverflowError;
@@ -12,12 +17,41 @@ pub struct MeasureOverflowError;
 /// Implementations must satisfy the same algebraic laws as the original
 /// Haskell version: `(zero, plus)` forms a commutative monoid, `min_measure`
 /// and `max_measure` define a lattice, and `plus` distributes over `min_measure`.
+/// The laws hold for every sum that does not overflow.
+///
+/// # Overflow
+///
+/// Haskell measures over `Natural` cannot overflow, while the fixed-width
+/// Rust types can. Implementations provide both a checked
+/// ([`try_plus`](Self::try_plus)) and a saturating
+/// ([`saturating_plus`](Self::saturating_plus)) addition; `plus` is the
+/// saturating one, so it neither panics nor wraps. Callers accounting for
+/// untrusted sizes (mempool bytes, block budgets) should use `try_plus`, since
+/// a saturated total is indistinguishable from a genuine maximum.
 pub trait Measure: PartialEq + Clone {
     /// Zero measurement representing the absence of usage.
     fn zero() -> Self;
 
-    /// Combine two measurements component-wise.
-    fn plus(&self, other: &Self) -> Self;
+    /// Combine two measurements component-wise, saturating each component at
+    /// its maximum.
+    ///
+    /// Defaults to [`saturating_plus`](Self::saturating_plus).
+    fn plus(&self, other: &Self) -> Self {
+        self.saturating_plus(other)
+    }
+
+    /// Combine two measurements component-wise, failing if any component
+    /// overflows.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeasureOverflowError`] if a component sum is not
+    /// representable.
+    fn try_plus(&self, other: &Self) -> Result<Self, MeasureOverflowError>;
+
+    /// Combine two measurements component-wise, clamping each component that
+    /// overflows to its maximum.
+    fn saturating_plus(&self, other: &Self) -> Self;
 
     /// Component-wise minimum.
     fn min_measure(&self, other: &Self) -> Self;
@@ -57,9 +91,12 @@ macro_rules! impl_numeric_measure {
             impl Measure for $ty {
                 fn zero() -> Self { 0 }
 
-                fn plus(&self, other: &Self) -> Self {
-                    self.checked_add(*other)
-                        .unwrap_or_else(|| panic!("{}", MeasureOverflowError))
+                fn try_plus(&self, other: &Self) -> Result<Self, MeasureOverflowError> {
+                    self.checked_add(*other).ok_or(MeasureOverflowError)
+                }
+
+                fn saturating_plus(&self, other: &Self) -> Self {
+                    (*self).saturating_add(*other)
                 }
 
                 fn min_measure(&self, other: &Self) -> Self {
@@ -94,8 +131,12 @@ macro_rules! tuple_measure_impl {
                     ( $( $name::zero(), )+ )
                 }
 
-                fn plus(&self, other: &Self) -> Self {
-                    ( $( Measure::plus(&self.$index, &other.$index), )+ )
+                fn try_plus(&self, other: &Self) -> Result<Self, MeasureOverflowError> {
+                    Ok(( $( Measure::try_plus(&self.$index, &other.$index)?, )+ ))
+                }
+
+                fn saturating_plus(&self, other: &Self) -> Self {
+                    ( $( Measure::saturating_plus(&self.$index, &other.$index), )+ )
                 }
 
                 fn min_measure(&self, other: &Self) -> Self {
@@ -130,7 +171,16 @@ tuple_measure_impl! {
 }
 
 /// Split an iterator of items once the accumulated measurement would exceed the limit.
-pub fn measure_split_at<T, M, F, I>(measure: F, limit: M, iter: I) -> (Vec<T>, Vec<T>)
+///
+/// # Errors
+///
+/// Returns [`MeasureOverflowError`] if accumulating the measurements
+/// overflows before the limit is reached.
+pub fn measure_split_at<T, M, F, I>(
+    measure: F,
+    limit: M,
+    iter: I,
+) -> Result<(Vec<T>, Vec<T>), MeasureOverflowError>
 where
     M: Measure,
     F: Fn(&T) -> M,
@@ -142,38 +192,56 @@ where
     let mut iter = iter.into_iter();
 
     while let Some(item) = iter.next() {
-        let candidate_total = total.plus(&measure(&item));
+        let candidate_total = total.try_plus(&measure(&item))?;
         if candidate_total.less_equal(&limit) {
             prefix.push(item);
             total = candidate_total;
         } else {
             remainder.push(item);
             remainder.extend(iter);
-            return (prefix, remainder);
+            return Ok((prefix, remainder));
         }
     }
 
-    (prefix, remainder)
+    Ok((prefix, remainder))
 }
 
 /// Return the longest prefix whose accumulated measurement does not exceed the limit.
-pub fn measure_take<T, M, F, I>(measure: F, limit: M, iter: I) -> Vec<T>
+///
+/// # Errors
+///
+/// Returns [`MeasureOverflowError`] under the same conditions as
+/// [`measure_split_at`].
+pub fn measure_take<T, M, F, I>(
+    measure: F,
+    limit: M,
+    iter: I,
+) -> Result<Vec<T>, MeasureOverflowError>
 where
     M: Measure,
     F: Fn(&T) -> M,
     I: IntoIterator<Item = T>,
 {
-    measure_split_at(measure, limit, iter).0
+    measure_split_at(measure, limit, iter).map(|(prefix, _)| prefix)
 }
 
 /// Drop the longest prefix whose accumulated measurement stays under the limit.
-pub fn measure_drop<T, M, F, I>(measure: F, limit: M, iter: I) -> Vec<T>
+///
+/// # Errors
+///
+/// Returns [`MeasureOverflowError`] under the same conditions as
+/// [`measure_split_at`].
+pub fn measure_drop<T, M, F, I>(
+    measure: F,
+    limit: M,
+    iter: I,
+) -> Result<Vec<T>, MeasureOverflowError>
 where
     M: Measure,
     F: Fn(&T) -> M,
     I: IntoIterator<Item = T>,
 {
-    measure_split_at(measure, limit, iter).1
+    measure_split_at(measure, limit, iter).map(|(_, rest)| rest)
 }
 
 #[cfg(test)]
@@ -194,20 +262,57 @@ mod tests {
         let a = (1u16, 2u16);
         let b = (3u16, 4u16);
         assert_eq!(a.plus(&b), (4, 6));
+        assert_eq!(a.try_plus(&b), Ok((4, 6)));
     }
 
+    // Regression: `plus` used to panic with "measure addition overflowed",
+    // and so did the iterator helpers when the running total overflowed.
     #[test]
-    #[should_panic(expected = "measure addition overflowed")]
-    fn plus_panics_on_overflow() {
+    fn plus_saturates_instead_of_panicking() {
         let a: u8 = 250;
         let b: u8 = 10;
-        let _ = a.plus(&b);
+        assert_eq!(a.plus(&b), u8::MAX);
+        assert_eq!(a.try_plus(&b), Err(MeasureOverflowError));
+    }
+
+    #[test]
+    fn split_at_reports_overflow_instead_of_panicking() {
+        let items = vec![u64::MAX - 1, 1, 1];
+        assert_eq!(
+            measure_split_at(|x| *x, u64::MAX, items.clone()),
+            Err(MeasureOverflowError)
+        );
+        assert_eq!(
+            measure_take(|x| *x, u64::MAX, items.clone()),
+            Err(MeasureOverflowError)
+        );
+        assert_eq!(
+            measure_drop(|x| *x, u64::MAX, items),
+            Err(MeasureOverflowError)
+        );
+    }
+
+    #[test]
+    fn tuple_overflow_in_any_component() {
+        let a = (1u64, u64::MAX);
+        let b = (1u64, 1u64);
+        assert_eq!(a.try_plus(&b), Err(MeasureOverflowError));
+        assert_eq!(a.saturating_plus(&b), (2, u64::MAX));
+        assert_eq!(a.plus(&b), (2, u64::MAX));
+    }
+
+    #[test]
+    fn natural_measure_checks_overflow() {
+        let big: Natural = Natural::MAX;
+        assert_eq!(big.try_plus(&1), Err(MeasureOverflowError));
+        assert_eq!(big.saturating_plus(&1), Natural::MAX);
+        assert_eq!(Natural::zero().try_plus(&7), Ok(7));
     }
 
     #[test]
     fn split_at_respects_limit() {
         let items = vec![1u32, 2, 3, 4];
-        let (prefix, rest) = measure_split_at(|x| *x, 3u32, items.clone());
+        let (prefix, rest) = measure_split_at(|x| *x, 3u32, items.clone()).expect("no overflow");
         assert_eq!(prefix, vec![1, 2]);
         assert_eq!(rest, vec![3, 4]);
     }
@@ -215,24 +320,24 @@ mod tests {
     #[test]
     fn take_stops_before_exceeding_limit() {
         let items = vec![3u16, 1, 1];
-        let taken = measure_take(|x| *x, 2u16, items.clone());
+        let taken = measure_take(|x| *x, 2u16, items.clone()).expect("no overflow");
         assert!(taken.is_empty());
 
-        let taken2 = measure_take(|x| *x, 4u16, items);
+        let taken2 = measure_take(|x| *x, 4u16, items).expect("no overflow");
         assert_eq!(taken2, vec![3, 1]);
     }
 
     #[test]
     fn drop_returns_remaining_suffix() {
         let items = vec![1u8, 1, 2, 1];
-        let dropped = measure_drop(|x| *x, 2u8, items);
+        let dropped = measure_drop(|x| *x, 2u8, items).expect("no overflow");
         assert_eq!(dropped, vec![2, 1]);
     }
 
     proptest! {
         #[test]
         fn proptest_split_at_roundtrip(limit in 0u32..10_000, values in proptest::collection::vec(0u32..1_000, 0..16)) {
-            let (prefix, rest) = measure_split_at(|x| *x, limit, values.clone());
+            let (prefix, rest) = measure_split_at(|x| *x, limit, values.clone()).expect("no overflow");
             let mut recombined = prefix;
             recombined.extend(rest);
             prop_assert_eq!(recombined, values);
@@ -243,7 +348,51 @@ mod tests {
             let taken = measure_take(|x| *x, limit, values.clone());
             let dropped = measure_drop(|x| *x, limit, values.clone());
             let split = measure_split_at(|x| *x, limit, values);
-            prop_assert_eq!((taken, dropped), split);
+            prop_assert_eq!(taken.and_then(|t| dropped.map(|d| (t, d))), split);
+        }
+
+        #[test]
+        fn proptest_checked_and_saturating_near_u64_max(a in (u64::MAX - 1_000)..=u64::MAX, b in 0u64..=2_000) {
+            match a.checked_add(b) {
+                Some(sum) => {
+                    prop_assert_eq!(a.try_plus(&b), Ok(sum));
+                    prop_assert_eq!(a.saturating_plus(&b), sum);
+                }
+                None => {
+                    prop_assert_eq!(a.try_plus(&b), Err(MeasureOverflowError));
+                    prop_assert_eq!(a.saturating_plus(&b), u64::MAX);
+                }
+            }
+            prop_assert_eq!(a.plus(&b), a.saturating_plus(&b));
+            prop_assert_eq!(a.try_plus(&b), b.try_plus(&a));
+        }
+
+        #[test]
+        fn proptest_tuple_checked_matches_components(
+            a in (u64::MAX - 100)..=u64::MAX,
+            b in 0u64..=200,
+            c in 0u32..=u32::MAX,
+            d in 0u32..=u32::MAX,
+        ) {
+            let expected = a.try_plus(&b).and_then(|x| c.try_plus(&d).map(|y| (x, y)));
+            prop_assert_eq!((a, c).try_plus(&(b, d)), expected);
+            prop_assert_eq!(
+                (a, c).saturating_plus(&(b, d)),
+                (a.saturating_plus(&b), c.saturating_plus(&d))
+            );
+        }
+
+        #[test]
+        fn proptest_split_at_overflow_near_u64_max(start in (u64::MAX - 10)..=u64::MAX, values in proptest::collection::vec(0u64..=20, 0..8)) {
+            let mut items = vec![start];
+            items.extend(values.iter().copied());
+            let overflows = items.iter().try_fold(0u64, |acc, x| acc.checked_add(*x)).is_none();
+            let result = measure_split_at(|x| *x, u64::MAX, items.clone());
+            if overflows {
+                prop_assert_eq!(result, Err(MeasureOverflowError));
+            } else {
+                prop_assert_eq!(result, Ok((items, Vec::new())));
+            }
         }
     }
 }
@@ -0,0 +1,268 @@
+//! Wrappers mirroring the combinators of the Haskell `nothunks` package.
+//!
+//! Haskell annotates types with `deriving NoThunks via AllowThunksIn '["f"] T`
+//! or `InspectHeap T`; the types here (and the aliases carrying the Haskell
+//! names) let such annotations be ported one for one.
+
+use std::cell::Cell;
+
+use base_deriving_via::InstantiatedAt;
+
+use crate::{NoThunks, NoThunksResult, OnlyCheckWhnf, apply_context};
+
+thread_local! {
+    /// Levels the current traversal may still descend, if a [`CheckedDepth`]
+    /// is active on this thread.
+    static REMAINING_DEPTH: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Restores the depth budget that was active before a nested step.
+struct DepthGuard(Option<usize>);
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        REMAINING_DEPTH.with(|depth| depth.set(self.0));
+    }
+}
+
+/// Run `check` one level deeper, or report `Ok` if the active
+/// [`CheckedDepth`] budget is exhausted.
+pub(crate) fn descend(check: impl FnOnce() -> NoThunksResult) -> NoThunksResult {
+    let previous = REMAINING_DEPTH.with(Cell::get);
+    match previous {
+        None => check(),
+        Some(0) => Ok(()),
+        Some(remaining) => {
+            let _guard = DepthGuard(previous);
+            REMAINING_DEPTH.with(|depth| depth.set(Some(remaining - 1)));
+            check()
+        },
+    }
+}
+
+/// The depth budget active on this thread, for handing to worker threads.
+#[cfg(feature = "rayon")]
+pub(crate) fn remaining_depth() -> Option<usize> {
+    REMAINING_DEPTH.with(Cell::get)
+}
+
+/// Run `check` with the depth budget `depth`, which a parallel check
+/// captured on the thread that started it.
+#[cfg(feature = "rayon")]
+pub(crate) fn with_remaining_depth(
+    depth: Option<usize>,
+    check: impl FnOnce() -> NoThunksResult,
+) -> NoThunksResult {
+    let _guard = DepthGuard(REMAINING_DEPTH.with(Cell::get));
+    REMAINING_DEPTH.with(|remaining| remaining.set(depth));
+    check()
+}
+
+pub(crate) fn with_depth_limit(
+    limit: usize,
+    check: impl FnOnce() -> NoThunksResult,
+) -> NoThunksResult {
+    let previous = REMAINING_DEPTH.with(Cell::get);
+    let _guard = DepthGuard(previous);
+    let limit = previous.map_or(limit, |remaining| remaining.min(limit));
+    REMAINING_DEPTH.with(|depth| depth.set(Some(limit)));
+    check()
+}
+
+/// Check a single struct field, reporting thunks under
+/// `context ++ [type_name, field]`.
+///
+/// This is the building block of [`impl_no_thunks_for_struct!`](crate::impl_no_thunks_for_struct)
+/// and keeps hand-written impls consistent with the generated ones.
+pub fn no_thunks_in_field<T: NoThunks + ?Sized>(
+    value: &T,
+    context: &[&str],
+    type_name: &str,
+    field: &str,
+) -> NoThunksResult {
+    let mut path = context.to_vec();
+    path.extend([type_name, field]);
+    apply_context(descend(|| value.no_thunks(&[])), &path)
+}
+
+/// Field-wise thunk checks with a hook for skipping named fields.
+///
+/// Implemented by [`impl_no_thunks_for_struct!`](crate::impl_no_thunks_for_struct).
+pub trait NoThunksFields {
+    /// Check every field whose name is not in `skip`.
+    fn no_thunks_skipping(&self, context: &[&str], skip: &[&str]) -> NoThunksResult;
+}
+
+/// Field names whose thunks are tolerated when checked through
+/// [`AllowThunksIn`], the counterpart of the type-level list in Haskell's
+/// `AllowThunksIn '["field"]`.
+pub trait AllowedThunks {
+    /// Names of the fields to skip.
+    const FIELDS: &'static [&'static str];
+}
+
+/// Check every field of `T` except those listed in [`AllowedThunks::FIELDS`].
+///
+/// ```rust
+/// use nothunks::{AllowThunksIn, AllowedThunks, NoThunks, impl_no_thunks_for_struct};
+///
+/// struct Cache {
+///     tip: u64,
+///     memo: Vec<u8>,
+/// }
+///
+/// impl_no_thunks_for_struct!(struct Cache { tip: u64, memo: Vec<u8> });
+///
+/// impl AllowedThunks for Cache {
+///     const FIELDS: &'static [&'static str] = &["memo"];
+/// }
+///
+/// let cache = AllowThunksIn(Cache { tip: 1, memo: vec![] });
+/// assert!(cache.no_thunks(&[]).is_ok());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AllowThunksIn<T>(pub T);
+
+impl<T> AllowThunksIn<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: NoThunksFields + AllowedThunks> NoThunks for AllowThunksIn<T> {
+    fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
+        self.0.no_thunks_skipping(context, T::FIELDS)
+    }
+}
+
+/// Check `T` but stop descending after `MAX` levels.
+///
+/// A level is one step that adds a segment to a reported path: a struct field
+/// checked through [`no_thunks_in_field`], a collection element, a map key or
+/// value. Anything below the limit is not visited and counts as free of
+/// thunks, so `CheckedDepth<T, 0>` only runs `T`'s own check. `Box`, `Option`
+/// and other transparent wrappers do not count as a level. When wrappers nest,
+/// the tighter remaining budget wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CheckedDepth<T, const MAX: usize>(pub T);
+
+impl<T, const MAX: usize> CheckedDepth<T, MAX> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: NoThunks, const MAX: usize> NoThunks for CheckedDepth<T, MAX> {
+    fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
+        with_depth_limit(MAX, || self.0.no_thunks(context))
+    }
+}
+
+/// Haskell's `InspectHeap`: check everything reachable through the generic
+/// representation of `T`.
+pub type InspectHeap<T> = InstantiatedAt<T>;
+
+/// Haskell's `AllowThunk`: accept the value without inspecting it.
+pub type AllowThunk<T> = OnlyCheckWhnf<T>;
+
+/// Alternative name for [`AllowThunksIn`] used by some Haskell code bases.
+pub type NoThunksSkippingFields<T> = AllowThunksIn<T>;
+
+/// Implement [`NoThunks`] and [`NoThunksFields`] for a record-style struct.
+///
+/// Each field is checked with [`no_thunks_in_field`], so a thunk is reported
+/// as `context ++ [StructName, field, ..]`. A leading
+/// `#[allow_thunks_in("field", ..)]` skips the listed fields in the generated
+/// [`NoThunks`] impl and also implements [`AllowedThunks`], mirroring
+/// `deriving NoThunks via AllowThunksIn '["field"] StructName`.
+///
+/// ```rust
+/// use nothunks::{NoThunks, impl_no_thunks_for_struct};
+///
+/// struct Ledger<T> {
+///     tip: u64,
+///     pending: Vec<T>,
+/// }
+///
+/// impl_no_thunks_for_struct!(
+///     #[allow_thunks_in("pending")]
+///     struct Ledger<T> {
+///         tip: u64,
+///         pending: Vec<T>,
+///     }
+/// );
+///
+/// let ledger = Ledger { tip: 7, pending: vec!["tx".to_owned()] };
+/// assert!(ledger.no_thunks(&["ledger"]).is_ok());
+/// ```
+#[macro_export]
+macro_rules! impl_no_thunks_for_struct {
+    (
+        #[allow_thunks_in($($allowed:literal),* $(,)?)]
+        struct $name:ident $(<$($generics:tt),*>)? {
+            $($field:ident : $ty:ty),* $(,)?
+        }
+    ) => {
+        $crate::impl_no_thunks_for_struct!(@impl [$($allowed),*] struct $name $(<$($generics),*>)? {
+            $($field: $ty),*
+        });
+
+        impl $(<$($generics),*>)? $crate::AllowedThunks for $name $(<$($generics),*>)? {
+            const FIELDS: &'static [&'static str] = &[$($allowed),*];
+        }
+    };
+    (
+        struct $name:ident $(<$($generics:tt),*>)? {
+            $($field:ident : $ty:ty),* $(,)?
+        }
+    ) => {
+        $crate::impl_no_thunks_for_struct!(@impl [] struct $name $(<$($generics),*>)? {
+            $($field: $ty),*
+        });
+    };
+    (
+        @impl [$($allowed:literal),*] struct $name:ident $(<$($generics:tt),*>)? {
+            $($field:ident : $ty:ty),*
+        }
+    ) => {
+        impl $(<$($generics),*>)? $crate::NoThunksFields for $name $(<$($generics),*>)?
+        where
+            $($ty: $crate::NoThunks,)*
+        {
+            fn no_thunks_skipping(
+                &self,
+                context: &[&str],
+                skip: &[&str],
+            ) -> $crate::NoThunksResult {
+                $(
+                    if !skip.contains(&stringify!($field)) {
+                        $crate::no_thunks_in_field(
+                            &self.$field,
+                            context,
+                            stringify!($name),
+                            stringify!($field),
+                        )?;
+                    }
+                )*
+                Ok(())
+            }
+        }
+
+        impl $(<$($generics),*>)? $crate::NoThunks for $name $(<$($generics),*>)?
+        where
+            $($ty: $crate::NoThunks,)*
+        {
+            fn no_thunks(&self, context: &[&str]) -> $crate::NoThunksResult {
+                $crate::NoThunksFields::no_thunks_skipping(self, context, &[$($allowed),*])
+            }
+        }
+    };
+}
@@ -8,6 +8,20 @@
 
 #![allow(clippy::missing_errors_doc)]
 
+mod combinators;
+#[cfg(feature = "rayon")]
+mod par;
+
+pub use combinators::{
+    AllowThunk, AllowThunksIn, AllowedThunks, CheckedDepth, InspectHeap, NoThunksFields,
+    NoThunksSkippingFields, no_thunks_in_field,
+};
+
+#[cfg(feature = "rayon")]
+pub use par::{NoThunksPar, no_thunks_par, unsafe_no_thunks_par};
+
+use combinators::descend;
+
 use base_deriving_via::{Generic, InstantiatedAt};
 
 use std::borrow::{Cow, ToOwned};
@@ -259,7 +273,7 @@ impl<T: NoThunks + ?Sized> NoThunks for Arc<T> {
 impl<T: NoThunks> NoThunks for Vec<T> {
     fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
         for (idx, item) in self.iter().enumerate() {
-            if let Err(info) = apply_context(item.no_thunks(context), context) {
+            if let Err(info) = apply_context(descend(|| item.no_thunks(context)), context) {
                 return Err(info.prepend(idx.to_string()));
             }
         }
@@ -270,7 +284,7 @@ impl<T: NoThunks> NoThunks for Vec<T> {
 impl<T: NoThunks> NoThunks for VecDeque<T> {
     fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
         for (idx, item) in self.iter().enumerate() {
-            if let Err(info) = apply_context(item.no_thunks(context), context) {
+            if let Err(info) = apply_context(descend(|| item.no_thunks(context)), context) {
                 return Err(info.prepend(idx.to_string()));
             }
         }
@@ -281,7 +295,7 @@ impl<T: NoThunks> NoThunks for VecDeque<T> {
 impl<T: NoThunks> NoThunks for [T] {
     fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
         for (idx, item) in self.iter().enumerate() {
-            if let Err(info) = apply_context(item.no_thunks(context), context) {
+            if let Err(info) = apply_context(descend(|| item.no_thunks(context)), context) {
                 return Err(info.prepend(idx.to_string()));
             }
         }
@@ -317,7 +331,7 @@ impl<T: NoThunks, E: NoThunks> NoThunks for Result<T, E> {
 impl<T: NoThunks> NoThunks for BTreeSet<T> {
     fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
         for (idx, item) in self.iter().enumerate() {
-            if let Err(info) = apply_context(item.no_thunks(context), context) {
+            if let Err(info) = apply_context(descend(|| item.no_thunks(context)), context) {
                 return Err(info.prepend(idx.to_string()));
             }
         }
@@ -331,7 +345,7 @@ where
 {
     fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
         for (idx, item) in self.iter().enumerate() {
-            if let Err(info) = apply_context(item.no_thunks(context), context) {
+            if let Err(info) = apply_context(descend(|| item.no_thunks(context)), context) {
                 return Err(info.prepend(idx.to_string()));
             }
         }
@@ -342,10 +356,10 @@ where
 impl<K: NoThunks + Ord, V: NoThunks> NoThunks for BTreeMap<K, V> {
     fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
         for (key, value) in self.iter() {
-            if let Err(info) = apply_context(key.no_thunks(context), context) {
+            if let Err(info) = apply_context(descend(|| key.no_thunks(context)), context) {
                 return Err(info.prepend("key"));
             }
-            if let Err(info) = apply_context(value.no_thunks(context), context) {
+            if let Err(info) = apply_context(descend(|| value.no_thunks(context)), context) {
                 return Err(info.prepend("value"));
             }
         }
@@ -356,10 +370,10 @@ impl<K: NoThunks + Ord, V: NoThunks> NoThunks for BTreeMap<K, V> {
 impl<K: NoThunks + Eq + Hash, V: NoThunks> NoThunks for HashMap<K, V> {
     fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
         for (key, value) in self.iter() {
-            if let Err(info) = apply_context(key.no_thunks(context), context) {
+            if let Err(info) = apply_context(descend(|| key.no_thunks(context)), context) {
                 return Err(info.prepend("key"));
             }
-            if let Err(info) = apply_context(value.no_thunks(context), context) {
+            if let Err(info) = apply_context(descend(|| value.no_thunks(context)), context) {
                 return Err(info.prepend("value"));
             }
         }
@@ -407,6 +421,36 @@ where
     }
 }
 
+#[cfg(feature = "time")]
+impl_nothunks_for_copy!(time::OffsetDateTime, time::Date, time::Duration);
+
+#[cfg(feature = "num-bigint")]
+impl NoThunks for num_bigint::BigInt {
+    fn no_thunks(&self, _context: &[&str]) -> NoThunksResult {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl NoThunks for num_bigint::BigUint {
+    fn no_thunks(&self, _context: &[&str]) -> NoThunksResult {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "num-rational")]
+impl<T: NoThunks> NoThunks for num_rational::Ratio<T> {
+    fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
+        if let Err(info) = apply_context(descend(|| self.numer().no_thunks(context)), context) {
+            return Err(info.prepend("numer"));
+        }
+        if let Err(info) = apply_context(descend(|| self.denom().no_thunks(context)), context) {
+            return Err(info.prepend("denom"));
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -480,4 +524,61 @@ mod tests {
         let wrapped = OnlyCheckWhnf(AlwaysThunk);
         assert!(wrapped.no_thunks(&[]).is_ok());
     }
+
+    #[cfg(all(feature = "time", feature = "num-bigint", feature = "num-rational"))]
+    #[test]
+    fn foreign_types_have_no_thunks() {
+        use num_bigint::{BigInt, BigUint};
+        use num_rational::Ratio;
+        use time::{Date, Duration, OffsetDateTime};
+
+        let params = (
+            OffsetDateTime::UNIX_EPOCH,
+            Date::MIN,
+            Duration::seconds(20),
+            BigInt::from(-45_000_000_000_000_000_i64),
+            BigUint::from(13_888_022_852_926_644_u64),
+            Ratio::new(1_u64, 5),
+            Ratio::new(BigInt::from(3), BigInt::from(1_000)),
+        );
+        assert!(no_thunks(&["ProtocolParams"], &params).is_ok());
+
+        let wrapped = Ratio::new_raw(OnlyCheckWhnf(AlwaysThunk), OnlyCheckWhnf(AlwaysThunk));
+        assert!(wrapped.no_thunks(&[]).is_ok());
+    }
+
+    #[cfg(feature = "num-rational")]
+    #[test]
+    fn ratio_reports_component_path() {
+        #[derive(Debug, Clone)]
+        struct Fine;
+
+        impl NoThunks for Fine {
+            fn no_thunks(&self, _context: &[&str]) -> NoThunksResult {
+                Ok(())
+            }
+        }
+
+        #[derive(Debug, Clone)]
+        enum Part {
+            Fine(Fine),
+            Thunk(AlwaysThunk),
+        }
+
+        impl NoThunks for Part {
+            fn no_thunks(&self, context: &[&str]) -> NoThunksResult {
+                match self {
+                    Part::Fine(value) => value.no_thunks(context),
+                    Part::Thunk(value) => value.no_thunks(context),
+                }
+            }
+        }
+
+        let ratio = num_rational::Ratio::new_raw(Part::Fine(Fine), Part::Thunk(AlwaysThunk));
+        let info = ratio.no_thunks(&[]).expect_err("denominator holds a thunk");
+        assert_eq!(
+            info.path,
+            vec!["denom".to_string(), "AlwaysThunk".to_string()]
+        );
+    }
 }
@@ -0,0 +1,253 @@
+//! Parallel traversal for very large values, behind the `rayon` feature.
+//!
+//! [`NoThunksPar`] checks the elements of vectors, slices, sets and maps on
+//! the rayon thread pool. The result is the one the sequential
+//! [`NoThunks::no_thunks`] returns: the first failure in iteration order, with
+//! the same path, even when a later element fails on a faster thread. A
+//! [`CheckedDepth`] budget in force when a parallel check starts applies on
+//! every worker as it would on a single thread.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::sync::Arc;
+
+use rayon::prelude::*;
+
+use crate::combinators::{descend, remaining_depth, with_depth_limit, with_remaining_depth};
+use crate::{
+    AllowThunksIn, AllowedThunks, CheckedDepth, NoThunks, NoThunksFields, NoThunksResult,
+    OnlyCheckWhnf, OnlyCheckWhnfNamed, ThunkInfo, apply_context,
+};
+
+/// [`NoThunks`] with a parallel check for large collections.
+///
+/// The default falls back to the sequential check, so leaf types and structs
+/// opt in with an empty impl. Collections of `NoThunksPar` elements check the
+/// elements in parallel and call `no_thunks_par` on each, so nested
+/// collections are split as well.
+pub trait NoThunksPar: NoThunks + Sync {
+    /// Check for thunks like [`NoThunks::no_thunks`], using the rayon pool
+    /// for the elements of collections.
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        self.no_thunks(context)
+    }
+
+    /// Parallel counterpart of [`NoThunks::unsafe_no_thunks`].
+    fn unsafe_no_thunks_par(&self) -> Option<ThunkInfo> {
+        self.no_thunks_par(&[]).err()
+    }
+}
+
+/// Check a value for thunks in parallel while providing an explicit context.
+pub fn no_thunks_par<T: NoThunksPar>(context: &[&str], value: &T) -> NoThunksResult {
+    value.no_thunks_par(context)
+}
+
+/// Check a value in parallel without a context, returning information about
+/// the first thunk in iteration order.
+pub fn unsafe_no_thunks_par<T: NoThunksPar>(value: &T) -> Option<ThunkInfo> {
+    value.unsafe_no_thunks_par()
+}
+
+/// Run `check` on every item and return the failure of the lowest index.
+///
+/// Each task runs under the depth budget of the calling thread, so
+/// `descend` cuts off at the same level on every worker.
+fn first_failure<I, F>(items: I, check: F) -> NoThunksResult
+where
+    I: IndexedParallelIterator,
+    F: Fn(I::Item) -> NoThunksResult + Sync + Send,
+{
+    let depth = remaining_depth();
+    match items
+        .map(|item| with_remaining_depth(depth, || check(item)))
+        .find_map_first(Result::err)
+    {
+        Some(info) => Err(info),
+        None => Ok(()),
+    }
+}
+
+fn elements_par<'a, T: NoThunksPar + 'a>(
+    items: impl IndexedParallelIterator<Item = &'a T>,
+    context: &[&str],
+) -> NoThunksResult {
+    first_failure(items.enumerate(), |(idx, item)| {
+        apply_context(descend(|| item.no_thunks_par(context)), context)
+            .map_err(|info| info.prepend(idx.to_string()))
+    })
+}
+
+fn entries_par<K: NoThunksPar, V: NoThunksPar>(
+    entries: Vec<(&K, &V)>,
+    context: &[&str],
+) -> NoThunksResult {
+    first_failure(entries.into_par_iter(), |(key, value)| {
+        apply_context(descend(|| key.no_thunks_par(context)), context)
+            .map_err(|info| info.prepend("key"))?;
+        apply_context(descend(|| value.no_thunks_par(context)), context)
+            .map_err(|info| info.prepend("value"))
+    })
+}
+
+macro_rules! impl_nothunks_par_sequential {
+    ($($ty:ty),+ $(,)?) => {
+        $( impl NoThunksPar for $ty {} )+
+    };
+}
+
+impl_nothunks_par_sequential!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    String,
+    str,
+);
+
+impl<T: Sync> NoThunksPar for OnlyCheckWhnf<T> {}
+
+impl<T: Sync> NoThunksPar for OnlyCheckWhnfNamed<T> {}
+
+impl<T: NoThunksFields + AllowedThunks + Sync> NoThunksPar for AllowThunksIn<T> {}
+
+impl<T: NoThunksPar, const MAX: usize> NoThunksPar for CheckedDepth<T, MAX> {
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        with_depth_limit(MAX, || self.0.no_thunks_par(context))
+    }
+}
+
+impl<T: NoThunksPar + ?Sized> NoThunksPar for &T {
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        T::no_thunks_par(self, context)
+    }
+}
+
+impl<T: NoThunksPar + ?Sized> NoThunksPar for Box<T> {
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        apply_context(self.as_ref().no_thunks_par(context), context)
+    }
+}
+
+impl<T: NoThunksPar + Send + ?Sized> NoThunksPar for Arc<T> {
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        apply_context(self.as_ref().no_thunks_par(context), context)
+    }
+}
+
+impl<T: NoThunksPar> NoThunksPar for Option<T> {
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        if let Some(value) = self.as_ref() {
+            apply_context(value.no_thunks_par(context), context)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T: NoThunksPar, E: NoThunksPar> NoThunksPar for Result<T, E> {
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        match self {
+            Ok(value) => apply_context(value.no_thunks_par(context), context),
+            Err(err) => apply_context(err.no_thunks_par(context), context),
+        }
+    }
+}
+
+impl<T: NoThunksPar> NoThunksPar for Vec<T> {
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        elements_par(self.par_iter(), context)
+    }
+}
+
+impl<T: NoThunksPar> NoThunksPar for VecDeque<T> {
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        elements_par(self.par_iter(), context)
+    }
+}
+
+impl<T: NoThunksPar> NoThunksPar for [T] {
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        elements_par(self.par_iter(), context)
+    }
+}
+
+impl<T: NoThunksPar, const N: usize> NoThunksPar for [T; N] {
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        self.as_slice().no_thunks_par(context)
+    }
+}
+
+// Sets and maps are collected in their own iteration order first, which is
+// the order the sequential check reports in.
+
+impl<T: NoThunksPar> NoThunksPar for BTreeSet<T> {
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        let items: Vec<&T> = self.iter().collect();
+        elements_par(items.into_par_iter(), context)
+    }
+}
+
+impl<T: NoThunksPar + Eq + Hash> NoThunksPar for HashSet<T> {
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        let items: Vec<&T> = self.iter().collect();
+        elements_par(items.into_par_iter(), context)
+    }
+}
+
+impl<K: NoThunksPar + Ord, V: NoThunksPar> NoThunksPar for BTreeMap<K, V> {
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        entries_par(self.iter().collect(), context)
+    }
+}
+
+impl<K: NoThunksPar + Eq + Hash, V: NoThunksPar> NoThunksPar for HashMap<K, V> {
+    fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+        entries_par(self.iter().collect(), context)
+    }
+}
+
+macro_rules! impl_nothunks_par_for_tuple {
+    ($($name:ident),+ $(,)?) => {
+        impl<$($name: NoThunksPar),+> NoThunksPar for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn no_thunks_par(&self, context: &[&str]) -> NoThunksResult {
+                let ($($name,)+) = self;
+                $( apply_context($name.no_thunks_par(context), context)?; )+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_nothunks_par_for_tuple!(A);
+impl_nothunks_par_for_tuple!(A, B);
+impl_nothunks_par_for_tuple!(A, B, C);
+impl_nothunks_par_for_tuple!(A, B, C, D);
+impl_nothunks_par_for_tuple!(A, B, C, D, E);
+impl_nothunks_par_for_tuple!(A, B, C, D, E, F);
+impl_nothunks_par_for_tuple!(A, B, C, D, E, F, G);
+impl_nothunks_par_for_tuple!(A, B, C, D, E, F, G, H);
+impl_nothunks_par_for_tuple!(A, B, C, D, E, F, G, H, I);
+impl_nothunks_par_for_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_nothunks_par_for_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_nothunks_par_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+#[cfg(feature = "time")]
+impl_nothunks_par_sequential!(time::OffsetDateTime, time::Date, time::Duration);
+
+#[cfg(feature = "num-bigint")]
+impl_nothunks_par_sequential!(num_bigint::BigInt, num_bigint::BigUint);
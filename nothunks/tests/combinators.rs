@@ -0,0 +1,252 @@
+//! `AllowThunksIn`, `CheckedDepth`, and the struct macro must report the same
+//! paths a hand-written impl would, and skip exactly what they are told to.
+
+use base_deriving_via::impl_generic_for_struct;
+use nothunks::{
+    AllowThunk, AllowThunksIn, AllowedThunks, CheckedDepth, InspectHeap, NoThunks, NoThunksFields,
+    NoThunksResult, NoThunksSkippingFields, ThunkInfo, impl_no_thunks_for_struct,
+    no_thunks_in_field,
+};
+
+#[derive(Debug, Clone)]
+struct AlwaysThunk;
+
+impl NoThunks for AlwaysThunk {
+    fn no_thunks(&self, _context: &[&str]) -> NoThunksResult {
+        Err(ThunkInfo {
+            path: vec!["AlwaysThunk".to_string()],
+            message: Some("simulated thunk".to_string()),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Strict {
+    tip: u64,
+    cache: AlwaysThunk,
+}
+
+impl_no_thunks_for_struct!(
+    struct Strict {
+        tip: u64,
+        cache: AlwaysThunk,
+    }
+);
+
+#[derive(Debug, Clone)]
+struct Lenient {
+    tip: u64,
+    cache: AlwaysThunk,
+}
+
+impl_no_thunks_for_struct!(
+    #[allow_thunks_in("cache")]
+    struct Lenient {
+        tip: u64,
+        cache: AlwaysThunk,
+    }
+);
+
+#[derive(Debug, Clone)]
+struct WrongList {
+    tip: u64,
+    cache: AlwaysThunk,
+}
+
+impl_no_thunks_for_struct!(
+    #[allow_thunks_in("tip")]
+    struct WrongList {
+        tip: u64,
+        cache: AlwaysThunk,
+    }
+);
+
+fn path(segments: &[&str]) -> Vec<String> {
+    segments.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn thunky_field_fails_when_not_allowed() {
+    let value = Strict {
+        tip: 1,
+        cache: AlwaysThunk,
+    };
+    let info = value.no_thunks(&["ledger"]).expect_err("cache is thunky");
+    assert_eq!(
+        info.path,
+        path(&["ledger", "Strict", "cache", "AlwaysThunk"])
+    );
+    assert_eq!(info.message.as_deref(), Some("simulated thunk"));
+}
+
+#[test]
+fn thunky_field_passes_when_listed() {
+    let value = Lenient {
+        tip: 1,
+        cache: AlwaysThunk,
+    };
+    assert!(value.no_thunks(&["ledger"]).is_ok());
+    assert_eq!(<Lenient as AllowedThunks>::FIELDS, &["cache"]);
+    assert!(AllowThunksIn(value).no_thunks(&[]).is_ok());
+}
+
+#[test]
+fn listing_another_field_still_reports_the_thunk() {
+    let value = WrongList {
+        tip: 1,
+        cache: AlwaysThunk,
+    };
+    let info = value.no_thunks(&[]).expect_err("only tip is allowed");
+    assert_eq!(info.path, path(&["WrongList", "cache", "AlwaysThunk"]));
+    let info = AllowThunksIn(value)
+        .no_thunks(&["outer"])
+        .expect_err("only tip is allowed");
+    assert_eq!(
+        info.path,
+        path(&["outer", "WrongList", "cache", "AlwaysThunk"])
+    );
+}
+
+#[test]
+fn wrapper_applies_a_list_declared_separately() {
+    #[derive(Debug, Clone)]
+    struct Annotated {
+        cache: AlwaysThunk,
+    }
+
+    impl_no_thunks_for_struct!(
+        struct Annotated {
+            cache: AlwaysThunk,
+        }
+    );
+
+    impl AllowedThunks for Annotated {
+        const FIELDS: &'static [&'static str] = &["cache"];
+    }
+
+    let value = Annotated { cache: AlwaysThunk };
+    let info = value.no_thunks(&[]).expect_err("plain impl checks cache");
+    assert_eq!(info.path, path(&["Annotated", "cache", "AlwaysThunk"]));
+    assert!(
+        NoThunksSkippingFields::new(value.clone())
+            .no_thunks(&[])
+            .is_ok()
+    );
+    assert!(value.no_thunks_skipping(&[], &["cache"]).is_ok());
+}
+
+#[test]
+fn nested_structs_and_collections_extend_the_path() {
+    #[derive(Debug, Clone)]
+    struct Outer {
+        inner: Vec<Strict>,
+    }
+
+    impl_no_thunks_for_struct!(
+        struct Outer {
+            inner: Vec<Strict>,
+        }
+    );
+
+    let value = Outer {
+        inner: vec![Strict {
+            tip: 0,
+            cache: AlwaysThunk,
+        }],
+    };
+    let info = value.no_thunks(&[]).expect_err("nested thunk");
+    assert_eq!(
+        info.path,
+        path(&["Outer", "inner", "0", "Strict", "cache", "AlwaysThunk"])
+    );
+}
+
+#[test]
+fn checked_depth_truncates_below_the_limit() {
+    // Outer -> inner (1) -> element 0 (2) -> Strict.cache (3)
+    #[derive(Debug, Clone)]
+    struct Outer {
+        inner: Vec<Strict>,
+    }
+
+    impl_no_thunks_for_struct!(
+        struct Outer {
+            inner: Vec<Strict>,
+        }
+    );
+
+    let value = Outer {
+        inner: vec![Strict {
+            tip: 0,
+            cache: AlwaysThunk,
+        }],
+    };
+    for limit_ok in [
+        CheckedDepth::<_, 0>(value.clone()).no_thunks(&[]),
+        CheckedDepth::<_, 1>(value.clone()).no_thunks(&[]),
+        CheckedDepth::<_, 2>(value.clone()).no_thunks(&[]),
+    ] {
+        assert!(limit_ok.is_ok());
+    }
+    let info = CheckedDepth::<_, 3>(value.clone())
+        .no_thunks(&[])
+        .expect_err("cache is within three levels");
+    assert_eq!(
+        info.path,
+        path(&["Outer", "inner", "0", "Strict", "cache", "AlwaysThunk"])
+    );
+
+    // The inner, tighter budget wins and the budget is restored afterwards.
+    let nested = CheckedDepth::<_, 10>(vec![CheckedDepth::<_, 1>(value.clone())]);
+    assert!(nested.no_thunks(&[]).is_ok());
+    assert!(value.no_thunks(&[]).is_err());
+}
+
+#[test]
+fn checked_depth_zero_still_runs_the_top_level_check() {
+    assert!(CheckedDepth::<_, 0>(AlwaysThunk).no_thunks(&[]).is_err());
+    assert!(
+        CheckedDepth::<_, 0>(vec![AlwaysThunk])
+            .no_thunks(&[])
+            .is_ok()
+    );
+}
+
+#[test]
+fn haskell_aliases_behave_like_their_targets() {
+    assert!(AllowThunk::new(AlwaysThunk).no_thunks(&[]).is_ok());
+
+    #[derive(Debug, Clone)]
+    struct Pair {
+        left: u64,
+        right: Vec<AlwaysThunk>,
+    }
+
+    impl_generic_for_struct!(
+        struct Pair {
+            left: u64,
+            right: Vec<AlwaysThunk>,
+        }
+    );
+
+    let clean: InspectHeap<Pair> = InspectHeap::new(Pair {
+        left: 1,
+        right: vec![],
+    });
+    assert!(clean.no_thunks(&[]).is_ok());
+    let thunky: InspectHeap<Pair> = InspectHeap::new(Pair {
+        left: 1,
+        right: vec![AlwaysThunk],
+    });
+    assert_eq!(
+        thunky.no_thunks(&[]).expect_err("reachable thunk").path,
+        path(&["0", "AlwaysThunk"])
+    );
+}
+
+#[test]
+fn field_helper_matches_generated_paths() {
+    let info =
+        no_thunks_in_field(&AlwaysThunk, &["ctx"], "Header", "body").expect_err("thunky field");
+    assert_eq!(info.path, path(&["ctx", "Header", "body", "AlwaysThunk"]));
+}
@@ -0,0 +1,209 @@
+//! `NoThunksPar` must report exactly what the sequential check reports: the
+//! first failure in iteration order with the same path, however the work is
+//! split across threads.
+
+#![cfg(feature = "rayon")]
+
+use std::cell::Cell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+
+use nothunks::{
+    CheckedDepth, NoThunks, NoThunksPar, NoThunksResult, ThunkInfo, no_thunks_par,
+    unsafe_no_thunks_par,
+};
+
+/// A value that is a thunk when its number is in the seeded failure set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Probe {
+    id: u64,
+    thunk: bool,
+}
+
+impl NoThunks for Probe {
+    fn no_thunks(&self, _context: &[&str]) -> NoThunksResult {
+        if self.thunk {
+            Err(ThunkInfo {
+                path: vec![format!("Probe{}", self.id)],
+                message: Some("seeded thunk".to_string()),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl NoThunksPar for Probe {}
+
+fn probes(len: u64, thunks: &[u64]) -> Vec<Probe> {
+    (0..len)
+        .map(|id| Probe {
+            id,
+            thunk: thunks.contains(&id),
+        })
+        .collect()
+}
+
+fn pool(threads: usize) -> rayon::ThreadPool {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .expect("rayon pool")
+}
+
+/// Run both checks on a four-thread pool and require identical results.
+fn assert_same<T: NoThunksPar>(value: &T, context: &[&str]) -> NoThunksResult {
+    let sequential = value.no_thunks(context);
+    let parallel = pool(4).install(|| value.no_thunks_par(context));
+    assert_eq!(parallel, sequential);
+    parallel
+}
+
+#[test]
+fn vectors_report_the_first_seeded_failure() {
+    let seeds: [&[u64]; 4] = [&[], &[0], &[9_999, 17, 4_000], &[5_000, 5_001, 9_998]];
+    for thunks in seeds {
+        let values = probes(10_000, thunks);
+        let result = assert_same(&values, &["ledger"]);
+        assert_eq!(
+            assert_same(&VecDeque::from(values.clone()), &["ledger"]),
+            result
+        );
+        assert_eq!(assert_same(&values.as_slice(), &["ledger"]), result);
+        assert_eq!(unsafe_no_thunks_par(&values), values.unsafe_no_thunks());
+        match thunks.iter().min() {
+            Some(first) => {
+                let info = result.expect_err("seeded thunk");
+                assert_eq!(
+                    info.path,
+                    vec![first.to_string(), "ledger".into(), format!("Probe{first}")]
+                );
+            },
+            None => assert_eq!(result, Ok(())),
+        }
+    }
+}
+
+#[test]
+fn nested_collections_keep_their_paths() {
+    let nested: Vec<Vec<Probe>> = (0..64)
+        .map(|row| probes(500, if row % 7 == 3 { &[499, 250] } else { &[] }))
+        .collect();
+    let info = assert_same(&nested, &["rows"]).expect_err("seeded thunk");
+    assert_eq!(info.path[0], "3");
+    assert_eq!(info.path.last().map(String::as_str), Some("Probe250"));
+
+    let boxed: Option<Box<(u64, Vec<Probe>)>> = Some(Box::new((1, probes(100, &[60, 30]))));
+    assert_same(&boxed, &["tip"]).expect_err("seeded thunk");
+    let array = [probes(10, &[]), probes(10, &[9]), probes(10, &[1])];
+    assert_same(&array, &[]).expect_err("seeded thunk");
+}
+
+#[test]
+fn maps_and_sets_follow_their_iteration_order() {
+    let failing = |id| Probe { id, thunk: true };
+    let fine = |id| Probe { id, thunk: false };
+
+    let btree: BTreeMap<u64, Probe> = (0..5_000)
+        .map(|id| {
+            (
+                id,
+                if id % 997 == 13 {
+                    failing(id)
+                } else {
+                    fine(id)
+                },
+            )
+        })
+        .collect();
+    let info = assert_same(&btree, &["utxo"]).expect_err("seeded thunk");
+    assert_eq!(info.path, vec!["value", "utxo", "Probe13"]);
+
+    let keyed: BTreeMap<Probe, u64> = (0..5_000)
+        .map(|id| (if id == 4_321 { failing(id) } else { fine(id) }, id))
+        .collect();
+    assert_eq!(
+        assert_same(&keyed, &[]).expect_err("seeded thunk").path[0],
+        "key"
+    );
+
+    let hash: HashMap<u64, Vec<Probe>> = (0..2_000)
+        .map(|id| (id, probes(3, if id % 301 == 0 { &[2, 1] } else { &[] })))
+        .collect();
+    assert_same(&hash, &["pools"]).expect_err("seeded thunk");
+
+    let set: BTreeSet<Probe> = (0..5_000)
+        .map(|id| {
+            if id % 1_001 == 500 {
+                failing(id)
+            } else {
+                fine(id)
+            }
+        })
+        .collect();
+    assert_same(&set, &[]).expect_err("seeded thunk");
+    let hash_set: HashSet<Probe> = set.iter().copied().collect();
+    assert_same(&hash_set, &["set"]).expect_err("seeded thunk");
+}
+
+#[test]
+fn depth_limits_apply_on_every_worker() {
+    let nested: Vec<Vec<Probe>> = (0..200).map(|row| probes(50, &[row % 50])).collect();
+    assert_eq!(
+        assert_same(&CheckedDepth::<_, 1>(nested.clone()), &[]),
+        Ok(())
+    );
+    let info = assert_same(&CheckedDepth::<_, 2>(nested.clone()), &[]).expect_err("in budget");
+    assert_eq!(info.path, vec!["0", "0", "Probe0"]);
+
+    // A budget set outside the parallel check still holds inside it.
+    let outer = CheckedDepth::<_, 2>(vec![CheckedDepth::<_, 5>(nested)]);
+    assert_eq!(assert_same(&outer, &[]), Ok(()));
+}
+
+thread_local! {
+    static RECORDED: Cell<bool> = const { Cell::new(false) };
+}
+
+static WORKERS: Mutex<BTreeSet<usize>> = Mutex::new(BTreeSet::new());
+
+/// An element that records which rayon worker checked it.
+#[derive(Debug, Clone, Copy)]
+struct Observed(u32);
+
+impl NoThunks for Observed {
+    fn no_thunks(&self, _context: &[&str]) -> NoThunksResult {
+        if let Some(worker) = rayon::current_thread_index() {
+            if !RECORDED.with(|recorded| recorded.replace(true)) {
+                WORKERS.lock().expect("workers").insert(worker);
+            }
+        }
+        if self.0 % 3_000_017 == 3_000_016 {
+            return Err(ThunkInfo {
+                path: vec!["Observed".into()],
+                message: None,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl NoThunksPar for Observed {}
+
+#[test]
+fn ten_million_elements_are_split_across_threads() {
+    let values: Vec<Observed> = (0..10_000_000).map(Observed).collect();
+    let sequential = values.no_thunks(&["state"]);
+    assert_eq!(WORKERS.lock().expect("workers").len(), 0);
+
+    let parallel = pool(4).install(|| no_thunks_par(&["state"], &values));
+    assert_eq!(parallel, sequential);
+    assert_eq!(
+        parallel.expect_err("seeded thunk").path,
+        vec!["3000016", "state", "Observed"]
+    );
+    assert!(
+        WORKERS.lock().expect("workers").len() > 1,
+        "the check ran on a single worker"
+    );
+}